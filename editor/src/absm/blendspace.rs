@@ -6,6 +6,7 @@ use crate::{
         },
         selection::{AbsmSelection, SelectedEntity},
     },
+    localization::window_title,
     menu::create_menu_item,
     message::MessageSender,
     send_sync_message,
@@ -33,7 +34,7 @@ use fyrox::{
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
         widget::{Widget, WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, Control, HorizontalAlignment, RcUiNodeHandle, Thickness, UiNode,
         UserInterface, VerticalAlignment, BRUSH_DARK, BRUSH_LIGHT, BRUSH_LIGHTEST,
     },
@@ -767,7 +768,7 @@ impl BlendSpaceEditor {
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(400.0))
             .open(false)
             .with_content(content)
-            .with_title(WindowTitle::text("Blend Space Editor"))
+            .with_title(window_title("Blend Space Editor", ctx))
             .build(ctx);
 
         Self {
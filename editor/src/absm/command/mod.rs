@@ -882,3 +882,32 @@ impl GameSceneCommandTrait for SetLayerMaskCommand {
         self.swap(context)
     }
 }
+
+#[derive(Debug)]
+pub struct SetLayerBlendModeCommand {
+    pub absm_node_handle: Handle<Node>,
+    pub layer_index: usize,
+    pub blend_mode: LayerBlendMode,
+}
+
+impl SetLayerBlendModeCommand {
+    fn swap(&mut self, context: &mut GameSceneContext) {
+        let layer =
+            &mut fetch_machine(context, self.absm_node_handle).layers_mut()[self.layer_index];
+        self.blend_mode = layer.set_blend_mode(self.blend_mode);
+    }
+}
+
+impl GameSceneCommandTrait for SetLayerBlendModeCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Set Layer Blend Mode".to_string()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(context)
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
         state_viewer::StateViewer,
         toolbar::{Toolbar, ToolbarAction},
     },
+    localization::window_title,
     message::MessageSender,
     scene::{GameScene, Selection},
     Message,
@@ -24,7 +25,7 @@ use fyrox::{
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, UiNode, UserInterface,
     },
     scene::{
@@ -35,15 +36,15 @@ use fyrox::{
 };
 
 mod blendspace;
-mod canvas;
+pub(crate) mod canvas;
 pub mod command;
-mod connection;
-mod node;
+pub(crate) mod connection;
+pub(crate) mod node;
 mod parameter;
 mod segment;
 mod selectable;
 pub mod selection;
-mod socket;
+pub(crate) mod socket;
 mod state_graph;
 mod state_viewer;
 mod toolbar;
@@ -155,7 +156,7 @@ impl AbsmEditor {
         )
         .open(false)
         .with_content(content)
-        .with_title(WindowTitle::text("ABSM Editor"))
+        .with_title(window_title("ABSM Editor", ctx))
         .build(ctx);
 
         Self {
@@ -1,7 +1,8 @@
 use crate::message::MessageSender;
 use crate::{
     absm::command::parameter::make_set_parameters_property_command,
-    inspector::editors::make_property_editors_container, Message, MessageDirection, MSG_SYNC_FLAG,
+    inspector::editors::make_property_editors_container, localization::window_title, Message,
+    MessageDirection, MSG_SYNC_FLAG,
 };
 use fyrox::graph::SceneGraph;
 use fyrox::{
@@ -19,7 +20,7 @@ use fyrox::{
         message::UiMessage,
         scroll_viewer::ScrollViewerBuilder,
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowTitle},
+        window::WindowBuilder,
         BuildContext, UiNode, UserInterface,
     },
     scene::{animation::absm::prelude::*, node::Node},
@@ -42,7 +43,7 @@ impl ParameterPanel {
 
         let inspector;
         let window = WindowBuilder::new(WidgetBuilder::new())
-            .with_title(WindowTitle::text("Parameters"))
+            .with_title(window_title("Parameters", ctx))
             .with_content(
                 ScrollViewerBuilder::new(WidgetBuilder::new())
                     .with_content({
@@ -1,7 +1,12 @@
 use fyrox::{
     core::{
-        algebra::Vector2, color::Color, pool::Handle, reflect::prelude::*, type_traits::prelude::*,
-        uuid_provider, visitor::prelude::*,
+        algebra::Vector2,
+        color::Color,
+        pool::{ErasedHandle, Handle},
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid_provider,
+        visitor::prelude::*,
     },
     gui::{
         brush::Brush,
@@ -14,7 +19,6 @@ use fyrox::{
         widget::{Widget, WidgetBuilder, WidgetMessage},
         BuildContext, Control, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
-    scene::animation::absm::prelude::*,
 };
 use std::ops::{Deref, DerefMut};
 
@@ -42,9 +46,8 @@ pub enum SocketDirection {
 pub struct Socket {
     widget: Widget,
     click_position: Option<Vector2<f32>>,
-    pub parent_node: Handle<PoseNode>,
+    pub parent_node: ErasedHandle,
     pub direction: SocketDirection,
-    #[allow(dead_code)] // TODO
     editor: Handle<UiNode>,
     pin: Handle<UiNode>,
     pub index: usize,
@@ -114,7 +117,7 @@ impl Control for Socket {
 
 pub struct SocketBuilder {
     widget_builder: WidgetBuilder,
-    parent_node: Handle<PoseNode>,
+    parent_node: ErasedHandle,
     direction: SocketDirection,
     editor: Handle<UiNode>,
     index: usize,
@@ -133,7 +136,7 @@ impl SocketBuilder {
         }
     }
 
-    pub fn with_parent_node(mut self, parent_node: Handle<PoseNode>) -> Self {
+    pub fn with_parent_node(mut self, parent_node: ErasedHandle) -> Self {
         self.parent_node = parent_node;
         self
     }
@@ -143,7 +146,6 @@ impl SocketBuilder {
         self
     }
 
-    #[allow(dead_code)] // TODO
     pub fn with_editor(mut self, editor: Handle<UiNode>) -> Self {
         self.editor = editor;
         self
@@ -10,6 +10,7 @@ use crate::{
         transition::{TransitionBuilder, TransitionMessage, TransitionView},
         NORMAL_BACKGROUND, NORMAL_ROOT_COLOR, SELECTED_BACKGROUND, SELECTED_ROOT_COLOR,
     },
+    localization::window_title,
     scene::{
         commands::{ChangeSelectionCommand, CommandGroup, GameSceneCommand},
         Selection,
@@ -23,7 +24,7 @@ use fyrox::{
         border::BorderBuilder,
         message::{MessageDirection, UiMessage},
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowTitle},
+        window::WindowBuilder,
         BuildContext, Thickness, UiNode, UserInterface,
     },
     scene::{animation::absm::prelude::*, node::Node},
@@ -60,7 +61,7 @@ impl StateGraphViewer {
         .build(ctx);
 
         let window = WindowBuilder::new(WidgetBuilder::new())
-            .with_title(WindowTitle::text("State Graph"))
+            .with_title(window_title("State Graph", ctx))
             .can_close(false)
             .can_minimize(false)
             .with_content(
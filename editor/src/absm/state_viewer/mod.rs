@@ -17,6 +17,7 @@ use crate::{
         state_viewer::context::{CanvasContextMenu, ConnectionContextMenu, NodeContextMenu},
         NORMAL_BACKGROUND, NORMAL_ROOT_COLOR, SELECTED_BACKGROUND, SELECTED_ROOT_COLOR,
     },
+    localization::{tr, window_title},
     scene::{
         commands::{ChangeSelectionCommand, CommandGroup, GameSceneCommand},
         Selection,
@@ -62,7 +63,7 @@ fn create_socket(
 ) -> Handle<UiNode> {
     SocketBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
         .with_direction(direction)
-        .with_parent_node(parent_node)
+        .with_parent_node(parent_node.into())
         .with_index(index)
         .with_show_index(show_index)
         .build(&mut ui.build_ctx())
@@ -94,6 +95,7 @@ fn fetch_socket_pose_node_model_handle(
         .query_component::<Socket>()
         .unwrap()
         .parent_node
+        .into()
 }
 
 fn make_play_animation_name(
@@ -146,7 +148,7 @@ impl StateViewer {
         let window = WindowBuilder::new(WidgetBuilder::new())
             .can_close(false)
             .can_minimize(false)
-            .with_title(WindowTitle::text("State Viewer"))
+            .with_title(window_title("State Viewer", ctx))
             .with_content(
                 BorderBuilder::new(
                     WidgetBuilder::new()
@@ -197,12 +199,12 @@ impl StateViewer {
                     true,
                 )
             })
-            .unwrap_or_else(|| (String::from("<No State>"), false));
+            .unwrap_or_else(|| (format!("<{}>", tr("No State")), false));
 
         ui.send_message(WindowMessage::title(
             self.window,
             MessageDirection::ToWidget,
-            WindowTitle::text(format!("State Viewer - {}", state_name)),
+            WindowTitle::text(format!("{} - {}", tr("State Viewer"), state_name)),
         ));
 
         ui.send_message(WidgetMessage::enabled(
@@ -222,7 +224,7 @@ impl StateViewer {
         ui.send_message(WindowMessage::title(
             self.window,
             MessageDirection::ToWidget,
-            WindowTitle::text("State Viewer - No State"),
+            WindowTitle::text(format!("{} - {}", tr("State Viewer"), tr("No State"))),
         ));
 
         ui.send_message(WidgetMessage::enabled(
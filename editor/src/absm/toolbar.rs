@@ -1,11 +1,15 @@
 use crate::{
     absm::{
-        command::{AddLayerCommand, RemoveLayerCommand, SetLayerMaskCommand, SetLayerNameCommand},
+        command::{
+            AddLayerCommand, RemoveLayerCommand, SetLayerBlendModeCommand, SetLayerMaskCommand,
+            SetLayerNameCommand,
+        },
         fetch_selection,
         selection::AbsmSelection,
     },
     gui::make_dropdown_list_option,
     load_image,
+    localization::tr,
     message::MessageSender,
     scene::{
         commands::{ChangeSelectionCommand, CommandGroup, GameSceneCommand},
@@ -39,6 +43,8 @@ use fyrox::{
         node::Node,
     },
 };
+use std::str::FromStr;
+use strum::VariantNames;
 
 pub struct Toolbar {
     pub panel: Handle<UiNode>,
@@ -48,6 +54,7 @@ pub struct Toolbar {
     pub add_layer: Handle<UiNode>,
     pub remove_layer: Handle<UiNode>,
     pub edit_mask: Handle<UiNode>,
+    pub blend_mode: Handle<UiNode>,
     pub node_selector: Handle<UiNode>,
 }
 
@@ -65,6 +72,7 @@ impl Toolbar {
         let add_layer;
         let remove_layer;
         let edit_mask;
+        let blend_mode;
         let panel = StackPanelBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -145,6 +153,25 @@ impl Toolbar {
                     )
                     .build(ctx);
                     edit_mask
+                })
+                .with_child({
+                    blend_mode = DropdownListBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_width(100.0)
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Defines how the layer is combined with the previous layers.",
+                            )),
+                    )
+                    .with_items(
+                        LayerBlendMode::VARIANTS
+                            .iter()
+                            .map(|name| make_dropdown_list_option(ctx, name))
+                            .collect(),
+                    )
+                    .build(ctx);
+                    blend_mode
                 }),
         )
         .with_orientation(Orientation::Horizontal)
@@ -158,6 +185,7 @@ impl Toolbar {
             add_layer,
             remove_layer,
             edit_mask,
+            blend_mode,
             node_selector: Handle::NONE,
         }
     }
@@ -194,6 +222,21 @@ impl Toolbar {
                     Selection::Absm(new_selection),
                     editor_selection.clone(),
                 ));
+            } else if message.destination() == self.blend_mode
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let (Some(layer_index), Some(blend_mode)) = (
+                    selection.layer,
+                    LayerBlendMode::VARIANTS
+                        .get(*index)
+                        .and_then(|name| LayerBlendMode::from_str(name).ok()),
+                ) {
+                    sender.do_scene_command(SetLayerBlendModeCommand {
+                        absm_node_handle: selection.absm_node_handle,
+                        layer_index,
+                        blend_mode,
+                    });
+                }
             }
         } else if let Some(TextMessage::Text(text)) = message.data() {
             if message.destination() == self.layer_name
@@ -269,7 +312,9 @@ impl Toolbar {
                             WidgetBuilder::new().with_width(300.0).with_height(400.0),
                         )
                         .open(false)
-                        .with_title(WindowTitle::text("Select nodes that will NOT be animated")),
+                        .with_title(WindowTitle::text(tr(
+                            "Select nodes that will NOT be animated",
+                        ))),
                     )
                     .with_hierarchy(root)
                     .build(&mut ui.build_ctx());
@@ -394,6 +439,17 @@ impl Toolbar {
                         layer.name().to_string(),
                     ),
                 );
+
+                send_sync_message(
+                    ui,
+                    DropdownListMessage::selection(
+                        self.blend_mode,
+                        MessageDirection::ToWidget,
+                        LayerBlendMode::VARIANTS
+                            .iter()
+                            .position(|name| *name == layer.blend_mode().as_ref()),
+                    ),
+                );
             }
         }
     }
@@ -0,0 +1,652 @@
+//! Central registry of editor actions. Every action could be executed from the command palette and
+//! built-in actions could also be executed via hot keys.
+
+use crate::settings::keys::{ActionBinding, HotKeyContext, KeyBindings};
+use fyrox::{
+    core::pool::Handle,
+    graph::SceneGraph,
+    gui::{
+        key::HotKey,
+        menu::{MenuItem, MenuItemMessage},
+        message::{KeyCode, KeyboardModifiers, MessageDirection},
+        text::Text,
+        UiNode, UserInterface,
+    },
+};
+use std::borrow::Cow;
+
+/// Actions, that are built into the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinAction {
+    Undo,
+    Redo,
+    EnableSelectMode,
+    EnableMoveMode,
+    EnableRotateMode,
+    EnableScaleMode,
+    EnableNavmeshMode,
+    EnableTerrainMode,
+    EnableJointMode,
+    NewScene,
+    LoadScene,
+    SaveScene,
+    CloseScene,
+    CopySelection,
+    Paste,
+    RemoveSelection,
+    FocusSelection,
+    Play,
+    Stop,
+    OpenSettings,
+    OpenKeyBindings,
+    OpenCommandPalette,
+    FrameSelection,
+    IsolateSelection,
+    GroupSelection,
+    UngroupSelection,
+    SaveCameraBookmark(usize),
+    RestoreCameraBookmark(usize),
+}
+
+/// Amount of numbered camera bookmarks, every bookmark has its own save and restore actions.
+pub const CAMERA_BOOKMARK_COUNT: usize = 9;
+
+const BOOKMARK_KEYS: [KeyCode; CAMERA_BOOKMARK_COUNT] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+impl BuiltinAction {
+    /// Every action without parameters.
+    pub const ALL: [BuiltinAction; 26] = [
+        BuiltinAction::Undo,
+        BuiltinAction::Redo,
+        BuiltinAction::EnableSelectMode,
+        BuiltinAction::EnableMoveMode,
+        BuiltinAction::EnableRotateMode,
+        BuiltinAction::EnableScaleMode,
+        BuiltinAction::EnableNavmeshMode,
+        BuiltinAction::EnableTerrainMode,
+        BuiltinAction::EnableJointMode,
+        BuiltinAction::NewScene,
+        BuiltinAction::LoadScene,
+        BuiltinAction::SaveScene,
+        BuiltinAction::CloseScene,
+        BuiltinAction::CopySelection,
+        BuiltinAction::Paste,
+        BuiltinAction::RemoveSelection,
+        BuiltinAction::FocusSelection,
+        BuiltinAction::Play,
+        BuiltinAction::Stop,
+        BuiltinAction::OpenSettings,
+        BuiltinAction::OpenKeyBindings,
+        BuiltinAction::OpenCommandPalette,
+        BuiltinAction::FrameSelection,
+        BuiltinAction::IsolateSelection,
+        BuiltinAction::GroupSelection,
+        BuiltinAction::UngroupSelection,
+    ];
+
+    /// Iterates over every built-in action, including the actions with parameters.
+    pub fn all() -> impl Iterator<Item = BuiltinAction> {
+        Self::ALL
+            .into_iter()
+            .chain((1..=CAMERA_BOOKMARK_COUNT).map(BuiltinAction::SaveCameraBookmark))
+            .chain((1..=CAMERA_BOOKMARK_COUNT).map(BuiltinAction::RestoreCameraBookmark))
+    }
+
+    pub fn id(self) -> Cow<'static, str> {
+        match self {
+            BuiltinAction::Undo => "undo".into(),
+            BuiltinAction::Redo => "redo".into(),
+            BuiltinAction::EnableSelectMode => "enable_select_mode".into(),
+            BuiltinAction::EnableMoveMode => "enable_move_mode".into(),
+            BuiltinAction::EnableRotateMode => "enable_rotate_mode".into(),
+            BuiltinAction::EnableScaleMode => "enable_scale_mode".into(),
+            BuiltinAction::EnableNavmeshMode => "enable_navmesh_mode".into(),
+            BuiltinAction::EnableTerrainMode => "enable_terrain_mode".into(),
+            BuiltinAction::EnableJointMode => "enable_joint_mode".into(),
+            BuiltinAction::NewScene => "new_scene".into(),
+            BuiltinAction::LoadScene => "load_scene".into(),
+            BuiltinAction::SaveScene => "save_scene".into(),
+            BuiltinAction::CloseScene => "close_scene".into(),
+            BuiltinAction::CopySelection => "copy_selection".into(),
+            BuiltinAction::Paste => "paste".into(),
+            BuiltinAction::RemoveSelection => "remove_selection".into(),
+            BuiltinAction::FocusSelection => "focus".into(),
+            BuiltinAction::Play => "play".into(),
+            BuiltinAction::Stop => "stop".into(),
+            BuiltinAction::OpenSettings => "open_settings".into(),
+            BuiltinAction::OpenKeyBindings => "open_key_bindings".into(),
+            BuiltinAction::OpenCommandPalette => "command_palette".into(),
+            BuiltinAction::FrameSelection => "frame_selection".into(),
+            BuiltinAction::IsolateSelection => "isolate_selection".into(),
+            BuiltinAction::GroupSelection => "group_selection".into(),
+            BuiltinAction::UngroupSelection => "ungroup_selection".into(),
+            BuiltinAction::SaveCameraBookmark(index) => {
+                format!("save_camera_bookmark_{index}").into()
+            }
+            BuiltinAction::RestoreCameraBookmark(index) => {
+                format!("restore_camera_bookmark_{index}").into()
+            }
+        }
+    }
+
+    pub fn name(self) -> Cow<'static, str> {
+        match self {
+            BuiltinAction::Undo => "Undo".into(),
+            BuiltinAction::Redo => "Redo".into(),
+            BuiltinAction::EnableSelectMode => "Select Mode".into(),
+            BuiltinAction::EnableMoveMode => "Move Mode".into(),
+            BuiltinAction::EnableRotateMode => "Rotate Mode".into(),
+            BuiltinAction::EnableScaleMode => "Scale Mode".into(),
+            BuiltinAction::EnableNavmeshMode => "Navmesh Mode".into(),
+            BuiltinAction::EnableTerrainMode => "Terrain Mode".into(),
+            BuiltinAction::EnableJointMode => "Joint Mode".into(),
+            BuiltinAction::NewScene => "New Scene".into(),
+            BuiltinAction::LoadScene => "Load Scene".into(),
+            BuiltinAction::SaveScene => "Save Scene".into(),
+            BuiltinAction::CloseScene => "Close Scene".into(),
+            BuiltinAction::CopySelection => "Copy Selection".into(),
+            BuiltinAction::Paste => "Paste".into(),
+            BuiltinAction::RemoveSelection => "Remove Selection".into(),
+            BuiltinAction::FocusSelection => "Focus Selection".into(),
+            BuiltinAction::Play => "Play".into(),
+            BuiltinAction::Stop => "Stop".into(),
+            BuiltinAction::OpenSettings => "Open Settings".into(),
+            BuiltinAction::OpenKeyBindings => "Key Bindings".into(),
+            BuiltinAction::OpenCommandPalette => "Command Palette".into(),
+            BuiltinAction::FrameSelection => "Frame Selection".into(),
+            BuiltinAction::IsolateSelection => "Isolate Selection".into(),
+            BuiltinAction::GroupSelection => "Group Selection".into(),
+            BuiltinAction::UngroupSelection => "Ungroup Selection".into(),
+            BuiltinAction::SaveCameraBookmark(index) => {
+                format!("Save Camera Bookmark {index}").into()
+            }
+            BuiltinAction::RestoreCameraBookmark(index) => {
+                format!("Restore Camera Bookmark {index}").into()
+            }
+        }
+    }
+
+    /// Returns a hot key of the action from the given key bindings.
+    pub fn hot_key(self, key_bindings: &KeyBindings) -> HotKey {
+        if let Some(hot_key) = self.hot_key_field(key_bindings) {
+            hot_key.clone()
+        } else {
+            key_bindings
+                .action_binding(&self.id())
+                .and_then(|b| b.hot_key.clone())
+                .unwrap_or_else(|| self.default_hot_key())
+        }
+    }
+
+    /// Returns a hot key of the action, that has no dedicated field in the key bindings and was not
+    /// rebound yet.
+    fn default_hot_key(self) -> HotKey {
+        match self {
+            // Ctrl+Shift+N saves a bookmark and Ctrl+N restores it.
+            BuiltinAction::SaveCameraBookmark(index) => BOOKMARK_KEYS
+                .get(index.wrapping_sub(1))
+                .map_or(HotKey::NotSet, |code| HotKey::Some {
+                    code: *code,
+                    modifiers: KeyboardModifiers {
+                        control: true,
+                        shift: true,
+                        ..Default::default()
+                    },
+                }),
+            BuiltinAction::RestoreCameraBookmark(index) => BOOKMARK_KEYS
+                .get(index.wrapping_sub(1))
+                .map_or(HotKey::NotSet, |code| HotKey::ctrl_key(*code)),
+            BuiltinAction::GroupSelection => HotKey::ctrl_key(KeyCode::KeyG),
+            BuiltinAction::UngroupSelection => HotKey::Some {
+                code: KeyCode::KeyG,
+                modifiers: KeyboardModifiers {
+                    control: true,
+                    shift: true,
+                    ..Default::default()
+                },
+            },
+            _ => HotKey::NotSet,
+        }
+    }
+
+    /// Returns a dedicated field of the key bindings, that stores the hot key of the action.
+    fn hot_key_field(self, key_bindings: &KeyBindings) -> Option<&HotKey> {
+        Some(match self {
+            BuiltinAction::Undo => &key_bindings.undo,
+            BuiltinAction::Redo => &key_bindings.redo,
+            BuiltinAction::EnableSelectMode => &key_bindings.enable_select_mode,
+            BuiltinAction::EnableMoveMode => &key_bindings.enable_move_mode,
+            BuiltinAction::EnableRotateMode => &key_bindings.enable_rotate_mode,
+            BuiltinAction::EnableScaleMode => &key_bindings.enable_scale_mode,
+            BuiltinAction::EnableNavmeshMode => &key_bindings.enable_navmesh_mode,
+            BuiltinAction::EnableTerrainMode => &key_bindings.enable_terrain_mode,
+            BuiltinAction::EnableJointMode => &key_bindings.enable_joint_mode,
+            BuiltinAction::NewScene => &key_bindings.new_scene,
+            BuiltinAction::LoadScene => &key_bindings.load_scene,
+            BuiltinAction::SaveScene => &key_bindings.save_scene,
+            BuiltinAction::CloseScene => &key_bindings.close_scene,
+            BuiltinAction::CopySelection => &key_bindings.copy_selection,
+            BuiltinAction::Paste => &key_bindings.paste,
+            BuiltinAction::RemoveSelection => &key_bindings.remove_selection,
+            BuiltinAction::FocusSelection => &key_bindings.focus,
+            BuiltinAction::OpenCommandPalette => &key_bindings.command_palette,
+            BuiltinAction::Play
+            | BuiltinAction::Stop
+            | BuiltinAction::OpenSettings
+            | BuiltinAction::OpenKeyBindings
+            | BuiltinAction::FrameSelection
+            | BuiltinAction::IsolateSelection
+            | BuiltinAction::GroupSelection
+            | BuiltinAction::UngroupSelection
+            | BuiltinAction::SaveCameraBookmark(_)
+            | BuiltinAction::RestoreCameraBookmark(_) => return None,
+        })
+    }
+
+    fn hot_key_field_mut(self, key_bindings: &mut KeyBindings) -> Option<&mut HotKey> {
+        Some(match self {
+            BuiltinAction::Undo => &mut key_bindings.undo,
+            BuiltinAction::Redo => &mut key_bindings.redo,
+            BuiltinAction::EnableSelectMode => &mut key_bindings.enable_select_mode,
+            BuiltinAction::EnableMoveMode => &mut key_bindings.enable_move_mode,
+            BuiltinAction::EnableRotateMode => &mut key_bindings.enable_rotate_mode,
+            BuiltinAction::EnableScaleMode => &mut key_bindings.enable_scale_mode,
+            BuiltinAction::EnableNavmeshMode => &mut key_bindings.enable_navmesh_mode,
+            BuiltinAction::EnableTerrainMode => &mut key_bindings.enable_terrain_mode,
+            BuiltinAction::EnableJointMode => &mut key_bindings.enable_joint_mode,
+            BuiltinAction::NewScene => &mut key_bindings.new_scene,
+            BuiltinAction::LoadScene => &mut key_bindings.load_scene,
+            BuiltinAction::SaveScene => &mut key_bindings.save_scene,
+            BuiltinAction::CloseScene => &mut key_bindings.close_scene,
+            BuiltinAction::CopySelection => &mut key_bindings.copy_selection,
+            BuiltinAction::Paste => &mut key_bindings.paste,
+            BuiltinAction::RemoveSelection => &mut key_bindings.remove_selection,
+            BuiltinAction::FocusSelection => &mut key_bindings.focus,
+            BuiltinAction::OpenCommandPalette => &mut key_bindings.command_palette,
+            BuiltinAction::Play
+            | BuiltinAction::Stop
+            | BuiltinAction::OpenSettings
+            | BuiltinAction::OpenKeyBindings
+            | BuiltinAction::FrameSelection
+            | BuiltinAction::IsolateSelection
+            | BuiltinAction::GroupSelection
+            | BuiltinAction::UngroupSelection
+            | BuiltinAction::SaveCameraBookmark(_)
+            | BuiltinAction::RestoreCameraBookmark(_) => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionKind {
+    /// An action built into the editor.
+    Builtin(BuiltinAction),
+    /// An action, that clicks the menu item.
+    MenuItem(Handle<UiNode>),
+    /// An action of an editor plugin. Plugins should react to [`crate::Message::ExecuteAction`] in
+    /// [`crate::plugin::EditorPlugin::on_message`].
+    Plugin,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Action {
+    /// Unique id of the action.
+    pub id: String,
+    /// Human-readable name of the action.
+    pub name: String,
+    pub hot_key: HotKey,
+    pub context: HotKeyContext,
+    pub kind: ActionKind,
+}
+
+impl Action {
+    /// Performs the action, if it could be done without the editor. Returns `true` if the action was
+    /// done.
+    pub fn execute_in_ui(&self, ui: &UserInterface) -> bool {
+        if let ActionKind::MenuItem(item) = self.kind {
+            ui.send_message(MenuItemMessage::click(item, MessageDirection::FromWidget));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ActionRegistry {
+    plugin_actions: Vec<Action>,
+}
+
+impl ActionRegistry {
+    /// Registers an action of an editor plugin, existing action with the same id is replaced.
+    pub fn register_plugin_action(&mut self, id: &str, name: &str, hot_key: HotKey) {
+        let action = Action {
+            id: id.to_string(),
+            name: name.to_string(),
+            hot_key,
+            context: HotKeyContext::Global,
+            kind: ActionKind::Plugin,
+        };
+        if let Some(existing) = self.plugin_actions.iter_mut().find(|a| a.id == id) {
+            *existing = action;
+        } else {
+            self.plugin_actions.push(action);
+        }
+    }
+
+    pub fn plugin_actions(&self) -> &[Action] {
+        &self.plugin_actions
+    }
+
+    /// Collects every available action: built-in actions, enabled menu items of the given menu and
+    /// actions of editor plugins. Hot keys and contexts of the actions are taken from the key
+    /// bindings.
+    pub fn collect(
+        &self,
+        menu: Handle<UiNode>,
+        ui: &UserInterface,
+        key_bindings: &KeyBindings,
+    ) -> Vec<Action> {
+        let mut actions = BuiltinAction::all()
+            .map(|action| Action {
+                id: action.id().to_string(),
+                name: action.name().to_string(),
+                hot_key: action.hot_key(key_bindings),
+                context: HotKeyContext::Global,
+                kind: ActionKind::Builtin(action),
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(menu) = ui.try_get(menu) {
+            for &item in menu.children() {
+                collect_menu_items(item, "", ui, &mut actions);
+            }
+        }
+
+        actions.extend(self.plugin_actions.iter().cloned());
+
+        for action in actions.iter_mut() {
+            if let Some(binding) = key_bindings.action_binding(&action.id) {
+                if let Some(hot_key) = binding.hot_key.as_ref() {
+                    action.hot_key = hot_key.clone();
+                }
+                action.context = binding.context;
+            }
+        }
+
+        actions
+    }
+}
+
+/// Changes the hot key and the context of the action. The change is stored in the key bindings.
+pub fn rebind(
+    action: &Action,
+    hot_key: HotKey,
+    context: HotKeyContext,
+    key_bindings: &mut KeyBindings,
+) {
+    let hot_key = match action.kind {
+        ActionKind::Builtin(builtin) => match builtin.hot_key_field_mut(key_bindings) {
+            Some(field) => {
+                *field = hot_key;
+                None
+            }
+            None => Some(hot_key),
+        },
+        _ => Some(hot_key),
+    };
+
+    key_bindings.set_action_binding(ActionBinding {
+        action: action.id.clone(),
+        hot_key,
+        context,
+    });
+}
+
+/// Searches for an action with the given hot key. `contexts` is a list of currently active contexts
+/// sorted by their priority.
+pub fn find_action_by_hot_key<'a>(
+    actions: &'a [Action],
+    hot_key: &HotKey,
+    contexts: &[HotKeyContext],
+) -> Option<&'a Action> {
+    if *hot_key == HotKey::NotSet {
+        return None;
+    }
+
+    contexts.iter().find_map(|context| {
+        actions
+            .iter()
+            .find(|action| action.context == *context && action.hot_key == *hot_key)
+    })
+}
+
+/// Returns pairs of indices of the actions, that have the same hot key in the same context.
+pub fn find_conflicts(actions: &[Action]) -> Vec<(usize, usize)> {
+    let mut conflicts = Vec::new();
+    for (i, a) in actions.iter().enumerate() {
+        if a.hot_key == HotKey::NotSet {
+            continue;
+        }
+        for (j, b) in actions.iter().enumerate().skip(i + 1) {
+            if a.hot_key == b.hot_key && a.context == b.context {
+                conflicts.push((i, j));
+            }
+        }
+    }
+    conflicts
+}
+
+fn menu_item_text(item: Handle<UiNode>, ui: &UserInterface) -> Option<String> {
+    let text = ui.find_handle(item, &mut |n| n.cast::<Text>().is_some());
+    ui.try_get(text)
+        .and_then(|n| n.cast::<Text>())
+        .map(|t| t.text())
+}
+
+fn collect_menu_items(
+    item: Handle<UiNode>,
+    prefix: &str,
+    ui: &UserInterface,
+    actions: &mut Vec<Action>,
+) {
+    let Some(node) = ui.try_get(item) else {
+        return;
+    };
+    let Some(menu_item) = node.cast::<MenuItem>() else {
+        return;
+    };
+    if !node.visibility() || !node.enabled() {
+        return;
+    }
+    let Some(text) = menu_item_text(item, ui) else {
+        return;
+    };
+
+    let name = if prefix.is_empty() {
+        text
+    } else {
+        format!("{prefix} > {text}")
+    };
+
+    if menu_item.items.is_empty() {
+        actions.push(Action {
+            id: format!("menu:{name}"),
+            name,
+            hot_key: HotKey::NotSet,
+            context: HotKeyContext::Global,
+            kind: ActionKind::MenuItem(item),
+        });
+    } else {
+        for &sub_item in menu_item.items.iter() {
+            collect_menu_items(sub_item, &name, ui, actions);
+        }
+    }
+}
+
+/// Performs fuzzy matching of the pattern against the text. Every character of the pattern must be
+/// found in the text in the same order (case-insensitive). Returns `None` if there is no match,
+/// otherwise returns a score of the match (the higher the better). Consecutive characters and
+/// characters at the beginning of words give higher score.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut pattern_chars = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    let mut previous_matched = false;
+    let mut previous_char = None;
+
+    for (position, c) in text.chars().enumerate() {
+        let Some(&expected) = pattern_chars.peek() else {
+            break;
+        };
+
+        if c.to_lowercase().eq(std::iter::once(expected)) {
+            pattern_chars.next();
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            if previous_char.map_or(true, |p: char| !p.is_alphanumeric()) {
+                score += 10;
+            }
+            if position == 0 {
+                score += 5;
+            }
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+        }
+
+        previous_char = Some(c);
+    }
+
+    if pattern_chars.peek().is_some() {
+        None
+    } else {
+        // Prefer shorter texts when the scores are equal.
+        Some(score * 100 - text.chars().count() as i32)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        find_action_by_hot_key, find_conflicts, fuzzy_match, Action, ActionKind, BuiltinAction,
+    };
+    use crate::settings::keys::HotKeyContext;
+    use fyrox::{
+        fxhash::FxHashSet,
+        gui::{key::HotKey, message::KeyCode},
+    };
+
+    fn action(id: &str, hot_key: HotKey, context: HotKeyContext) -> Action {
+        Action {
+            id: id.to_string(),
+            name: id.to_string(),
+            hot_key,
+            context,
+            kind: ActionKind::Plugin,
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("", "Save Scene").is_some());
+        assert!(fuzzy_match("svsc", "Save Scene").is_some());
+        assert!(fuzzy_match("SAVE", "Save Scene").is_some());
+        assert!(fuzzy_match("scs", "Save").is_none());
+        assert!(fuzzy_match("cs", "Scene").is_none());
+
+        // Word starts are better than random characters.
+        assert!(fuzzy_match("ss", "Save Scene") > fuzzy_match("ss", "Lossless"));
+        // Shorter texts are better when the rest is equal.
+        assert!(fuzzy_match("undo", "Undo") > fuzzy_match("undo", "Undo All"));
+    }
+
+    #[test]
+    fn test_builtin_action_ids_are_unique() {
+        let ids = BuiltinAction::all()
+            .map(|action| action.id())
+            .collect::<FxHashSet<_>>();
+        assert_eq!(ids.len(), BuiltinAction::all().count());
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let actions = [
+            action("a", HotKey::ctrl_key(KeyCode::KeyS), HotKeyContext::Global),
+            action(
+                "b",
+                HotKey::ctrl_key(KeyCode::KeyS),
+                HotKeyContext::Viewport,
+            ),
+            action("c", HotKey::ctrl_key(KeyCode::KeyS), HotKeyContext::Global),
+            action("d", HotKey::NotSet, HotKeyContext::Global),
+            action("e", HotKey::NotSet, HotKeyContext::Global),
+        ];
+
+        assert_eq!(find_conflicts(&actions), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_find_action_by_hot_key() {
+        let actions = [
+            action(
+                "global",
+                HotKey::ctrl_key(KeyCode::KeyA),
+                HotKeyContext::Global,
+            ),
+            action(
+                "viewport",
+                HotKey::ctrl_key(KeyCode::KeyA),
+                HotKeyContext::Viewport,
+            ),
+            action(
+                "text",
+                HotKey::ctrl_key(KeyCode::KeyZ),
+                HotKeyContext::TextEditing,
+            ),
+        ];
+
+        let find = |hot_key: HotKey, contexts: &[HotKeyContext]| {
+            find_action_by_hot_key(&actions, &hot_key, contexts).map(|a| a.id.as_str())
+        };
+
+        assert_eq!(
+            find(
+                HotKey::ctrl_key(KeyCode::KeyA),
+                &[HotKeyContext::Viewport, HotKeyContext::Global]
+            ),
+            Some("viewport")
+        );
+        assert_eq!(
+            find(HotKey::ctrl_key(KeyCode::KeyA), &[HotKeyContext::Global]),
+            Some("global")
+        );
+        assert_eq!(
+            find(HotKey::ctrl_key(KeyCode::KeyZ), &[HotKeyContext::Global]),
+            None
+        );
+        assert_eq!(
+            find(
+                HotKey::ctrl_key(KeyCode::KeyZ),
+                &[HotKeyContext::TextEditing, HotKeyContext::Global]
+            ),
+            Some("text")
+        );
+        assert_eq!(find(HotKey::NotSet, &[HotKeyContext::Global]), None);
+    }
+}
@@ -11,6 +11,7 @@ use crate::{
         toolbar::{Toolbar, ToolbarAction},
         track::TrackList,
     },
+    localization::window_title,
     scene::{commands::ChangeSelectionCommand, GameScene, Selection},
     send_sync_message, Message,
 };
@@ -26,7 +27,7 @@ use fyrox::{
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, UiNode, UserInterface, BRUSH_DARK, BRUSH_PRIMARY,
     },
     scene::{animation::prelude::*, node::Node, Scene},
@@ -155,7 +156,7 @@ impl AnimationEditor {
         )
         .with_content(content)
         .open(false)
-        .with_title(WindowTitle::text("Animation Editor"))
+        .with_title(window_title("Animation Editor", ctx))
         .build(ctx);
 
         Self {
@@ -217,7 +218,7 @@ impl AnimationEditor {
                 {
                     let ui = &engine.user_interface;
                     match msg {
-                        CurveEditorMessage::Sync(curve) => {
+                        CurveEditorMessage::Changed(curve) => {
                             sender.do_scene_command(ReplaceTrackCurveCommand {
                                 animation_player: selection.animation_player,
                                 animation: selection.animation,
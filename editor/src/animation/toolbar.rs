@@ -10,6 +10,7 @@ use crate::{
     },
     gui::make_dropdown_list_option_universal,
     load_image,
+    localization::{tr, window_title},
     message::MessageSender,
     scene::{
         commands::{ChangeSelectionCommand, CommandGroup, GameSceneCommand},
@@ -91,6 +92,7 @@ struct RootMotionDropdownArea {
     ignore_y: Handle<UiNode>,
     ignore_z: Handle<UiNode>,
     ignore_rotation: Handle<UiNode>,
+    keep_in_pose: Handle<UiNode>,
     node_selector: Handle<UiNode>,
 }
 
@@ -127,10 +129,11 @@ impl RootMotionDropdownArea {
         let ignore_y = check_box(3, ctx);
         let ignore_z = check_box(4, ctx);
         let ignore_rotation = check_box(5, ctx);
+        let keep_in_pose = check_box(6, ctx);
         let popup = PopupBuilder::new(
             WidgetBuilder::new()
                 .with_width(220.0)
-                .with_height(135.0)
+                .with_height(157.0)
                 .with_visibility(false),
         )
         .stays_open(false)
@@ -159,7 +162,9 @@ impl RootMotionDropdownArea {
                     .with_child(text("Ignore Z", 4, ctx))
                     .with_child(ignore_z)
                     .with_child(text("Ignore Rotation", 5, ctx))
-                    .with_child(ignore_rotation),
+                    .with_child(ignore_rotation)
+                    .with_child(text("Keep In Pose", 6, ctx))
+                    .with_child(keep_in_pose),
             )
             .add_column(Column::strict(90.0))
             .add_column(Column::stretch())
@@ -169,6 +174,7 @@ impl RootMotionDropdownArea {
             .add_row(Row::strict(22.0))
             .add_row(Row::strict(22.0))
             .add_row(Row::strict(22.0))
+            .add_row(Row::strict(22.0))
             .add_row(Row::stretch())
             .build(ctx),
         )
@@ -182,6 +188,7 @@ impl RootMotionDropdownArea {
             ignore_y,
             ignore_z,
             ignore_rotation,
+            keep_in_pose,
             node_selector: Default::default(),
         }
     }
@@ -239,6 +246,13 @@ impl RootMotionDropdownArea {
                                 ..*settings
                             }));
                         }
+                    } else if message.destination() == self.keep_in_pose {
+                        if let Some(settings) = animation.root_motion_settings_ref() {
+                            send_command(Some(RootMotionSettings {
+                                keep_in_pose: *value,
+                                ..*settings
+                            }));
+                        }
                     }
                 }
             } else if let Some(ButtonMessage::Click) = message.data() {
@@ -248,7 +262,7 @@ impl RootMotionDropdownArea {
                             WindowBuilder::new(
                                 WidgetBuilder::new().with_width(300.0).with_height(400.0),
                             )
-                            .with_title(WindowTitle::text("Select a Root Node"))
+                            .with_title(WindowTitle::text(tr("Select a Root Node")))
                             .open(false),
                         )
                         .build(&mut ui.build_ctx());
@@ -336,6 +350,7 @@ impl RootMotionDropdownArea {
                 self.ignore_y,
                 self.ignore_z,
                 self.ignore_rotation,
+                self.keep_in_pose,
             ] {
                 send_sync_message(
                     ui,
@@ -364,6 +379,7 @@ impl RootMotionDropdownArea {
                 sync_checked(ui, self.ignore_y, settings.ignore_y_movement);
                 sync_checked(ui, self.ignore_z, settings.ignore_z_movement);
                 sync_checked(ui, self.ignore_rotation, settings.ignore_rotations);
+                sync_checked(ui, self.keep_in_pose, settings.keep_in_pose);
             }
         }
     }
@@ -787,7 +803,7 @@ impl Toolbar {
 
         let node_selector = NodeSelectorWindowBuilder::new(
             WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
-                .with_title(WindowTitle::text("Select a Target Node"))
+                .with_title(window_title("Select a Target Node", ctx))
                 .open(false),
         )
         .build(ctx);
@@ -795,15 +811,15 @@ impl Toolbar {
         let file_selector = FileSelectorBuilder::new(
             WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
                 .open(false)
-                .with_title(WindowTitle::text("Select Animation To Import")),
+                .with_title(window_title("Select Animation To Import", ctx)),
         )
         .with_filter(Filter::new(|p: &Path| {
             p.is_dir()
                 || p.extension().map_or(false, |ext|
-                // TODO: Here we allow importing only FBX files, but they can contain
+                // TODO: Here we allow importing only FBX and glTF files, but they can contain
                 // multiple animations and it might be good to also add animation selector
                 // that will be used to select a particular animation to import.
-                ext.to_string_lossy().as_ref() == "fbx")
+                matches!(ext.to_string_lossy().to_lowercase().as_str(), "fbx" | "gltf" | "glb"))
         }))
         .build(ctx);
 
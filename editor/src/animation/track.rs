@@ -10,6 +10,7 @@ use crate::{
     },
     gui::make_image_button_with_tooltip,
     load_image,
+    localization::tr,
     menu::create_menu_item,
     message::MessageSender,
     scene::{
@@ -730,7 +731,7 @@ impl TrackList {
             {
                 self.node_selector = NodeSelectorWindowBuilder::new(
                     WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
-                        .with_title(WindowTitle::text("Select a Node To Animate")),
+                        .with_title(WindowTitle::text(tr("Select a Node To Animate"))),
                 )
                 .with_hierarchy(HierarchyNode::from_scene_node(
                     game_scene.scene_content_root,
@@ -999,7 +1000,7 @@ impl TrackList {
             } else if message.destination() == self.context_menu.set_target {
                 self.context_menu.target_node_selector = NodeSelectorWindowBuilder::new(
                     WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
-                        .with_title(WindowTitle::text("Select a New Target Node")),
+                        .with_title(WindowTitle::text(tr("Select a New Target Node"))),
                 )
                 .with_hierarchy(HierarchyNode::from_scene_node(
                     game_scene.scene_content_root,
@@ -1105,7 +1106,9 @@ impl TrackList {
 
         let property_selector = PropertySelectorWindowBuilder::new(
             WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
-                .with_title(WindowTitle::text("Select a Numeric Property To Animate"))
+                .with_title(WindowTitle::text(tr(
+                    "Select a Numeric Property To Animate",
+                )))
                 .open(false),
         )
         .with_allowed_types(Some(FxHashSet::from_iter(define_allowed_types! {
@@ -1,3 +1,4 @@
+use crate::localization::window_title;
 use fyrox::asset::state::ResourceState;
 use fyrox::{
     asset::{
@@ -15,7 +16,7 @@ use fyrox::{
         text::TextBuilder,
         tree::{TreeBuilder, TreeRootBuilder, TreeRootMessage},
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
         VerticalAlignment,
     },
@@ -63,7 +64,7 @@ impl DependencyViewer {
         let close;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
             .open(false)
-            .with_title(WindowTitle::text("Dependency Viewer"))
+            .with_title(window_title("Dependency Viewer", ctx))
             .with_content(
                 GridBuilder::new(
                     WidgetBuilder::new()
@@ -11,7 +11,7 @@ use fyrox::{
         draw::{CommandTexture, Draw, DrawingContext},
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
-        image::ImageBuilder,
+        image::{ImageBuilder, ImageMessage},
         message::{MessageDirection, UiMessage},
         text::TextBuilder,
         widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -102,6 +102,16 @@ impl Control for AssetItem {
                     },
                 ));
             }
+        } else if let Some(AssetItemMessage::Icon(icon)) = message.data::<AssetItemMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                ui.send_message(ImageMessage::texture(
+                    self.preview,
+                    MessageDirection::ToWidget,
+                    icon.clone(),
+                ));
+            }
         }
     }
 }
@@ -1,9 +1,10 @@
 use crate::{
     asset::{
         dependency::DependencyViewer, inspector::AssetInspector, item::AssetItemBuilder,
-        preview::AssetPreviewGeneratorsCollection,
+        preview::AssetPreviewGeneratorsCollection, thumbnail::ThumbnailCache,
     },
     gui::{make_dropdown_list_option, AssetItemMessage},
+    localization::window_title,
     message::MessageSender,
     preview::PreviewPanel,
     utils::window_content,
@@ -39,13 +40,15 @@ use fyrox::{
         text_box::TextBoxBuilder,
         utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         wrap_panel::WrapPanelBuilder,
         BuildContext, HorizontalAlignment, Orientation, RcUiNodeHandle, Thickness, UiNode,
         UserInterface, VerticalAlignment, BRUSH_DARK,
     },
     material::Material,
-    resource::{model::Model, texture::Texture},
+    resource::{
+        audio_event::AudioEventBank, model::Model, texture::Texture, visual_script::VisualScript,
+    },
     scene::sound::SoundBuffer,
     walkdir,
 };
@@ -60,6 +63,7 @@ mod dependency;
 mod inspector;
 pub mod item;
 pub mod preview;
+pub mod thumbnail;
 
 struct ContextMenu {
     menu: RcUiNodeHandle,
@@ -203,6 +207,30 @@ impl ContextMenu {
                                 sender.send(Message::OpenMaterialEditor(material));
                             }
                         }
+                    } else if item
+                        .path
+                        .extension()
+                        .map_or(false, |ext| ext == "audio_bank")
+                    {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(bank) =
+                                block_on(engine.resource_manager.request::<AudioEventBank>(path))
+                            {
+                                sender.send(Message::OpenAudioEventBankEditor(bank));
+                            }
+                        }
+                    } else if item
+                        .path
+                        .extension()
+                        .map_or(false, |ext| ext == "visual_script")
+                    {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(script) =
+                                block_on(engine.resource_manager.request::<VisualScript>(path))
+                            {
+                                sender.send(Message::OpenVisualScriptEditor(script));
+                            }
+                        }
                     } else {
                         open_in_explorer(&item.path)
                     }
@@ -254,7 +282,7 @@ impl ResourceCreator {
         let cancel;
         let resource_constructors_list;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
-            .with_title(WindowTitle::text("Resource Creator"))
+            .with_title(window_title("Resource Creator", ctx))
             .open(false)
             .with_content(
                 GridBuilder::new(
@@ -447,6 +475,7 @@ pub struct AssetBrowser {
     dependency_viewer: DependencyViewer,
     resource_creator: Option<ResourceCreator>,
     pub preview_generators: AssetPreviewGeneratorsCollection,
+    thumbnail_cache: ThumbnailCache,
 }
 
 fn is_supported_resource(ext: &OsStr, resource_manager: &ResourceManager) -> bool {
@@ -460,6 +489,7 @@ fn is_supported_resource(ext: &OsStr, resource_manager: &ResourceManager) -> boo
 impl AssetBrowser {
     pub fn new(engine: &mut Engine) -> Self {
         let preview = PreviewPanel::new(engine, 250, 250);
+        let thumbnail_cache = ThumbnailCache::new(engine);
         let ctx = &mut engine.user_interface.build_ctx();
 
         let inspector = AssetInspector::new(ctx, 1, 0);
@@ -471,7 +501,7 @@ impl AssetBrowser {
         let add_resource;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("AssetBrowser"))
             .can_minimize(false)
-            .with_title(WindowTitle::text("Asset Browser"))
+            .with_title(window_title("Asset Browser", ctx))
             .with_content(
                 GridBuilder::new(
                     WidgetBuilder::new()
@@ -604,6 +634,7 @@ impl AssetBrowser {
             add_resource,
             resource_creator: None,
             preview_generators: AssetPreviewGeneratorsCollection::new(),
+            thumbnail_cache,
         }
     }
 
@@ -622,11 +653,14 @@ impl AssetBrowser {
     }
 
     fn find_icon_for_asset(
-        &self,
+        &mut self,
         resource_manager: &ResourceManager,
         resource_path: &Path,
     ) -> Option<UntypedResource> {
         if let Ok(resource) = block_on(resource_manager.request_untyped(resource_path)) {
+            if let Some(thumbnail) = self.thumbnail_cache.request(&resource) {
+                return Some(thumbnail);
+            }
             return self
                 .preview_generators
                 .map
@@ -919,8 +953,32 @@ impl AssetBrowser {
         self.item_to_select = Some(path);
     }
 
-    pub fn update(&mut self, engine: &mut Engine) {
-        self.preview.update(engine)
+    /// Returns `true` if the asset browser has some unfinished background work (thumbnail
+    /// generation, for example) and needs to be updated in the next frame.
+    pub fn update(&mut self, engine: &mut Engine) -> bool {
+        self.preview.update(engine);
+
+        let ready = self
+            .thumbnail_cache
+            .update(engine, &mut self.preview_generators);
+        for (path, thumbnail) in ready {
+            for &item in self.items.iter() {
+                if engine
+                    .user_interface
+                    .try_get(item)
+                    .and_then(|n| n.cast::<AssetItem>())
+                    .is_some_and(|item| item.path == path)
+                {
+                    engine.user_interface.send_message(AssetItemMessage::icon(
+                        item,
+                        MessageDirection::ToWidget,
+                        Some(thumbnail.clone()),
+                    ));
+                }
+            }
+        }
+
+        self.thumbnail_cache.is_busy()
     }
 
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
@@ -944,7 +1002,7 @@ impl AssetBrowser {
                 return false;
             };
 
-            // The engine cannot write FBX resources, so we must filter out these and warn the user
+            // The engine cannot write FBX or glTF resources, so we must filter out these and warn the user
             // that resource references cannot be automatically fixed.
             if let Some(model) = res.try_cast::<Model>() {
                 let kind = model.kind();
@@ -952,11 +1010,12 @@ impl AssetBrowser {
                     path.extension()
                         .map(|ext| ext.to_string_lossy().to_lowercase())
                 }) {
-                    if ext == "fbx" {
+                    if matches!(ext.as_str(), "fbx" | "gltf" | "glb") {
                         Log::warn(format!(
                             "Resource {} cannot be scanned for \
-                        references, because FBX cannot be exported.",
-                            kind
+                        references, because {} cannot be exported.",
+                            kind,
+                            ext.to_uppercase()
                         ));
                         return false;
                     }
@@ -0,0 +1,530 @@
+//! Thumbnail cache for the asset browser. Thumbnails are generated in the background and stored
+//! on disk, each thumbnail file is named after the hash of the content of its source file. This
+//! way a thumbnail is automatically invalidated when its source file changes.
+//!
+//! Images are decoded and scaled down directly in a worker thread. Models and materials, however,
+//! must be rendered first and this can only be done on the main thread, because it requires the
+//! graphics context. Such assets are rendered one at a time in an off-screen scene, the rendered
+//! frame is then read back from GPU memory and sent to the worker thread to be saved on disk.
+
+use crate::asset::preview::AssetPreviewGeneratorsCollection;
+use fyrox::{
+    asset::{event::ResourceEvent, untyped::UntypedResource},
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        color::Color,
+        log::Log,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        TypeUuidProvider,
+    },
+    engine::{Engine, GraphicsContext},
+    fxhash::{FxHashMap, FxHashSet},
+    graph::SceneGraph,
+    material::Material,
+    resource::{
+        model::Model,
+        texture::{
+            CompressionOptions, Texture, TextureImportOptions, TextureMinificationFilter,
+            TextureResource, TextureResourceExtension,
+        },
+    },
+    scene::{
+        base::BaseBuilder,
+        camera::{CameraBuilder, Projection},
+        light::{directional::DirectionalLightBuilder, BaseLightBuilder},
+        mesh::Mesh,
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+        Scene, SceneRenderingOptions,
+    },
+};
+use std::{
+    collections::VecDeque,
+    hash::Hasher,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+};
+
+/// Size (in pixels) of each side of a thumbnail.
+pub const THUMBNAIL_SIZE: u32 = 128;
+
+/// Maximum amount of frames to wait for the resources of a rendered asset to load.
+const MAX_RENDER_WAIT_FRAMES: usize = 120;
+
+enum ThumbnailSource {
+    Image,
+    Render,
+}
+
+enum WorkerRequest {
+    Prepare {
+        path: PathBuf,
+        source: ThumbnailSource,
+    },
+    Store {
+        path: PathBuf,
+        cache_path: PathBuf,
+        pixels: Vec<u8>,
+    },
+}
+
+enum WorkerResponse {
+    Ready { path: PathBuf, png: Vec<u8> },
+    NeedsRender { path: PathBuf, cache_path: PathBuf },
+    Failed { path: PathBuf },
+}
+
+fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = fyrox::fxhash::FxHasher64::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+fn encode_png(image: &image::RgbaImage) -> Result<Vec<u8>, image::ImageError> {
+    let mut png = Vec::new();
+    image.write_to(
+        &mut std::io::Cursor::new(&mut png),
+        image::ImageOutputFormat::Png,
+    )?;
+    Ok(png)
+}
+
+fn save_thumbnail(cache_path: &Path, png: &[u8]) {
+    if let Some(dir) = cache_path.parent() {
+        Log::verify(std::fs::create_dir_all(dir));
+    }
+    if let Err(err) = std::fs::write(cache_path, png) {
+        Log::err(format!(
+            "Unable to save thumbnail {}. Reason: {:?}",
+            cache_path.display(),
+            err
+        ));
+    }
+}
+
+fn prepare(cache_dir: &Path, path: PathBuf, source: ThumbnailSource) -> WorkerResponse {
+    let Ok(bytes) = std::fs::read(&path) else {
+        return WorkerResponse::Failed { path };
+    };
+
+    let cache_path = cache_dir.join(format!("{:016x}.png", content_hash(&bytes)));
+    if let Ok(png) = std::fs::read(&cache_path) {
+        return WorkerResponse::Ready { path, png };
+    }
+
+    match source {
+        ThumbnailSource::Image => {
+            let Ok(image) = image::load_from_memory(&bytes) else {
+                return WorkerResponse::Failed { path };
+            };
+            match encode_png(&image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgba8()) {
+                Ok(png) => {
+                    save_thumbnail(&cache_path, &png);
+                    WorkerResponse::Ready { path, png }
+                }
+                Err(_) => WorkerResponse::Failed { path },
+            }
+        }
+        ThumbnailSource::Render => WorkerResponse::NeedsRender { path, cache_path },
+    }
+}
+
+fn store(path: PathBuf, cache_path: PathBuf, pixels: Vec<u8>) -> WorkerResponse {
+    let Some(mut image) = image::RgbaImage::from_raw(THUMBNAIL_SIZE, THUMBNAIL_SIZE, pixels) else {
+        return WorkerResponse::Failed { path };
+    };
+    // Rendered frames are stored upside down.
+    image::imageops::flip_vertical_in_place(&mut image);
+    match encode_png(&image) {
+        Ok(png) => {
+            save_thumbnail(&cache_path, &png);
+            WorkerResponse::Ready { path, png }
+        }
+        Err(_) => WorkerResponse::Failed { path },
+    }
+}
+
+struct RenderTask {
+    path: PathBuf,
+    cache_path: PathBuf,
+}
+
+struct ActiveRender {
+    task: RenderTask,
+    model: Handle<Node>,
+    frames: usize,
+}
+
+/// Off-screen scene that is used to render thumbnails of models and materials.
+struct ThumbnailRenderer {
+    scene: Handle<Scene>,
+    camera_pivot: Handle<Node>,
+    camera: Handle<Node>,
+}
+
+impl ThumbnailRenderer {
+    fn new(engine: &mut Engine) -> Self {
+        let mut scene = Scene::new();
+
+        let camera;
+        let camera_pivot = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_rotation(UnitQuaternion::from_axis_angle(
+                            &Vector3::y_axis(),
+                            45.0f32.to_radians(),
+                        ))
+                        .build(),
+                )
+                .with_children(&[PivotBuilder::new(
+                    BaseBuilder::new()
+                        .with_local_transform(
+                            TransformBuilder::new()
+                                .with_local_rotation(UnitQuaternion::from_axis_angle(
+                                    &Vector3::x_axis(),
+                                    -30.0f32.to_radians(),
+                                ))
+                                .build(),
+                        )
+                        .with_children(&[{
+                            camera = CameraBuilder::new(
+                                BaseBuilder::new().with_local_transform(
+                                    TransformBuilder::new()
+                                        .with_local_rotation(UnitQuaternion::from_axis_angle(
+                                            &Vector3::y_axis(),
+                                            180.0f32.to_radians(),
+                                        ))
+                                        .build(),
+                                ),
+                            )
+                            .build(&mut scene.graph);
+                            camera
+                        }]),
+                )
+                .build(&mut scene.graph)]),
+        )
+        .build(&mut scene.graph);
+
+        DirectionalLightBuilder::new(
+            BaseLightBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_rotation(UnitQuaternion::from_axis_angle(
+                            &Vector3::y_axis(),
+                            45.0f32.to_radians(),
+                        ))
+                        .build(),
+                ),
+            )
+            .cast_shadows(false),
+        )
+        .build(&mut scene.graph);
+
+        scene.rendering_options = SceneRenderingOptions {
+            render_target: Some(TextureResource::new_render_target(
+                THUMBNAIL_SIZE,
+                THUMBNAIL_SIZE,
+            )),
+            clear_color: Some(Color::opaque(50, 50, 50)),
+            ambient_lighting_color: Color::opaque(80, 80, 80),
+            ..Default::default()
+        }
+        .into();
+
+        // The scene is enabled only when there's something to render.
+        scene.enabled.set_value_and_mark_modified(false);
+
+        Self {
+            scene: engine.scenes.add(scene),
+            camera_pivot,
+            camera,
+        }
+    }
+
+    fn begin(
+        &mut self,
+        resource: &UntypedResource,
+        engine: &mut Engine,
+        generators: &mut AssetPreviewGeneratorsCollection,
+    ) -> Handle<Node> {
+        let scene = &mut engine.scenes[self.scene];
+
+        let Some(generator) = generators.map.get_mut(&resource.type_uuid()) else {
+            return Handle::NONE;
+        };
+        let model = generator.generate(resource, &engine.resource_manager, scene);
+        if model.is_none() {
+            return Handle::NONE;
+        }
+
+        // Models could have their own cameras, they must not interfere with the thumbnail camera.
+        let cameras = scene
+            .graph
+            .traverse_handle_iter(model)
+            .filter(|handle| scene.graph[*handle].is_camera())
+            .collect::<Vec<_>>();
+        for camera in cameras {
+            scene.graph[camera].as_camera_mut().set_enabled(false);
+        }
+
+        scene.enabled.set_value_and_mark_modified(true);
+
+        model
+    }
+
+    fn fit(&mut self, engine: &mut Engine) {
+        let scene = &mut engine.scenes[self.scene];
+
+        scene.graph.update_hierarchical_data();
+
+        let mut bounding_box = AxisAlignedBoundingBox::default();
+        for node in scene.graph.linear_iter() {
+            if let Some(mesh) = node.cast::<Mesh>() {
+                bounding_box.add_box(mesh.accurate_world_bounding_box(&scene.graph))
+            }
+        }
+
+        if let Projection::Perspective(proj) = scene.graph[self.camera].as_camera().projection() {
+            let radius = (bounding_box.max - bounding_box.min).norm() * 0.5;
+            let distance = radius / (proj.fov * 0.5).sin();
+            scene.graph[self.camera_pivot]
+                .local_transform_mut()
+                .set_position(bounding_box.center());
+            scene.graph[self.camera]
+                .local_transform_mut()
+                .set_position(Vector3::new(0.0, 0.0, distance));
+        }
+    }
+
+    fn end(&mut self, model: Handle<Node>, engine: &mut Engine) {
+        let scene = &mut engine.scenes[self.scene];
+        if scene.graph.is_valid_handle(model) {
+            scene.graph.remove_node(model);
+        }
+        scene.enabled.set_value_and_mark_modified(false);
+    }
+}
+
+/// Generates, caches and keeps track of thumbnails of assets. See module docs for more info.
+pub struct ThumbnailCache {
+    request_sender: Sender<WorkerRequest>,
+    response_receiver: Receiver<WorkerResponse>,
+    resource_events: Receiver<ResourceEvent>,
+    thumbnails: FxHashMap<PathBuf, UntypedResource>,
+    pending: FxHashSet<PathBuf>,
+    render_queue: VecDeque<RenderTask>,
+    active_render: Option<ActiveRender>,
+    renderer: ThumbnailRenderer,
+}
+
+impl ThumbnailCache {
+    /// Returns a path to the directory where the thumbnails are stored. Thumbnails are named
+    /// after the content of their source files, so the directory can be shared across projects.
+    pub fn directory() -> PathBuf {
+        std::env::temp_dir().join("fyrox_thumbnails")
+    }
+
+    pub fn new(engine: &mut Engine) -> Self {
+        let (request_sender, request_receiver) = mpsc::channel::<WorkerRequest>();
+        let (response_sender, response_receiver) = mpsc::channel();
+
+        let cache_dir = Self::directory();
+        std::thread::Builder::new()
+            .name("ThumbnailWorker".to_string())
+            .spawn(move || {
+                while let Ok(request) = request_receiver.recv() {
+                    let response = match request {
+                        WorkerRequest::Prepare { path, source } => {
+                            prepare(&cache_dir, path, source)
+                        }
+                        WorkerRequest::Store {
+                            path,
+                            cache_path,
+                            pixels,
+                        } => store(path, cache_path, pixels),
+                    };
+                    if response_sender.send(response).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("Unable to spawn thumbnail worker thread!");
+
+        let (event_sender, resource_events) = mpsc::channel();
+        engine
+            .resource_manager
+            .state()
+            .event_broadcaster
+            .add(event_sender);
+
+        Self {
+            request_sender,
+            response_receiver,
+            resource_events,
+            thumbnails: Default::default(),
+            pending: Default::default(),
+            render_queue: Default::default(),
+            active_render: None,
+            renderer: ThumbnailRenderer::new(engine),
+        }
+    }
+
+    /// Returns a thumbnail of the given resource if it is ready. Otherwise schedules thumbnail
+    /// generation (if the resource supports thumbnails) and returns `None`.
+    pub fn request(&mut self, resource: &UntypedResource) -> Option<UntypedResource> {
+        let path = resource.kind().path_owned()?;
+
+        if let Some(thumbnail) = self.thumbnails.get(&path) {
+            return Some(thumbnail.clone());
+        }
+
+        let type_uuid = resource.type_uuid();
+        let source = if type_uuid == Texture::type_uuid() {
+            ThumbnailSource::Image
+        } else if type_uuid == Model::type_uuid() || type_uuid == Material::type_uuid() {
+            ThumbnailSource::Render
+        } else {
+            return None;
+        };
+
+        if self.pending.insert(path.clone()) {
+            Log::verify(
+                self.request_sender
+                    .send(WorkerRequest::Prepare { path, source }),
+            );
+        }
+
+        None
+    }
+
+    /// Returns `true` if there is any unfinished work.
+    pub fn is_busy(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Processes finished thumbnails and renders the next queued asset (if any). Returns a list
+    /// of pairs `(asset path, thumbnail)` of the thumbnails that became ready in this frame.
+    pub fn update(
+        &mut self,
+        engine: &mut Engine,
+        generators: &mut AssetPreviewGeneratorsCollection,
+    ) -> Vec<(PathBuf, UntypedResource)> {
+        let mut changed = Vec::new();
+        while let Ok(event) = self.resource_events.try_recv() {
+            if let ResourceEvent::Reloaded(resource) = event {
+                if let Some(path) = resource.kind().path_owned() {
+                    if self.thumbnails.remove(&path).is_some() {
+                        changed.push(resource);
+                    }
+                }
+            }
+        }
+        // Regenerate thumbnails for the changed files, the content hash will be different now.
+        for resource in changed {
+            self.request(&resource);
+        }
+
+        let mut ready = Vec::new();
+        while let Ok(response) = self.response_receiver.try_recv() {
+            match response {
+                WorkerResponse::Ready { path, png } => {
+                    self.pending.remove(&path);
+                    match TextureResource::load_from_memory(
+                        Default::default(),
+                        &png,
+                        TextureImportOptions::default()
+                            .with_compression(CompressionOptions::NoCompression)
+                            .with_minification_filter(TextureMinificationFilter::Linear),
+                    ) {
+                        Ok(texture) => {
+                            let thumbnail: UntypedResource = texture.into();
+                            self.thumbnails.insert(path.clone(), thumbnail.clone());
+                            ready.push((path, thumbnail));
+                        }
+                        Err(err) => Log::err(format!(
+                            "Unable to load thumbnail of {}. Reason: {:?}",
+                            path.display(),
+                            err
+                        )),
+                    }
+                }
+                WorkerResponse::NeedsRender { path, cache_path } => {
+                    self.render_queue.push_back(RenderTask { path, cache_path });
+                }
+                WorkerResponse::Failed { path } => {
+                    self.pending.remove(&path);
+                }
+            }
+        }
+
+        self.update_render(engine, generators);
+
+        ready
+    }
+
+    fn update_render(
+        &mut self,
+        engine: &mut Engine,
+        generators: &mut AssetPreviewGeneratorsCollection,
+    ) {
+        let GraphicsContext::Initialized(_) = engine.graphics_context else {
+            return;
+        };
+
+        if let Some(active_render) = self.active_render.as_mut() {
+            // The asset was rendered at the end of the previous frame, but it could still have
+            // some dependencies (textures, for example) being loaded, wait for them first.
+            active_render.frames += 1;
+            if engine.resource_manager.state().count_pending_resources() > 0
+                && active_render.frames < MAX_RENDER_WAIT_FRAMES
+            {
+                self.renderer.fit(engine);
+                return;
+            }
+
+            let active_render = self.active_render.take().unwrap();
+            let pixels = engine
+                .graphics_context
+                .as_initialized_ref()
+                .renderer
+                .read_scene_frame_pixels(self.renderer.scene);
+            self.renderer.end(active_render.model, engine);
+
+            let task = active_render.task;
+            match pixels {
+                Some(pixels) => Log::verify(self.request_sender.send(WorkerRequest::Store {
+                    path: task.path,
+                    cache_path: task.cache_path,
+                    pixels,
+                })),
+                None => {
+                    self.pending.remove(&task.path);
+                }
+            }
+        }
+
+        while let Some(task) = self.render_queue.pop_front() {
+            let model = match fyrox::core::futures::executor::block_on(
+                engine.resource_manager.request_untyped(&task.path),
+            ) {
+                Ok(resource) => self.renderer.begin(&resource, engine, generators),
+                Err(_) => Handle::NONE,
+            };
+
+            if model.is_some() {
+                self.renderer.fit(engine);
+                self.active_render = Some(ActiveRender {
+                    task,
+                    model,
+                    frames: 0,
+                });
+                break;
+            } else {
+                self.pending.remove(&task.path);
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    inspector::editors::make_property_editors_container, localization::window_title,
+    message::MessageSender, Engine, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    asset::ResourceData,
+    core::{log::Log, pool::Handle},
+    graph::SceneGraph,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface,
+    },
+    resource::audio_event::AudioEventBankResource,
+};
+use std::sync::Arc;
+
+pub struct AudioEventBankEditor {
+    pub window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    save: Handle<UiNode>,
+    bank: Option<AudioEventBankResource>,
+    sender: MessageSender,
+}
+
+impl AudioEventBankEditor {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let inspector;
+        let save;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("AudioEventBankEditor")
+                .with_width(350.0)
+                .with_height(450.0),
+        )
+        .with_title(window_title("Audio Event Bank Editor", ctx))
+        .open(false)
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        ScrollViewerBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(0)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_content({
+                            inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+                            inspector
+                        })
+                        .build(ctx),
+                    )
+                    .with_child({
+                        save = ButtonBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_width(100.0)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_horizontal_alignment(HorizontalAlignment::Right),
+                        )
+                        .with_text("Save")
+                        .build(ctx);
+                        save
+                    }),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(25.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            save,
+            bank: None,
+            sender,
+        }
+    }
+
+    pub fn open(&mut self, bank: AudioEventBankResource, ui: &mut UserInterface) {
+        if let Some(data) = bank.state().data() {
+            let context = InspectorContext::from_object(
+                &*data,
+                &mut ui.build_ctx(),
+                Arc::new(make_property_editors_container(self.sender.clone())),
+                None,
+                MSG_SYNC_FLAG,
+                0,
+                true,
+                Default::default(),
+            );
+            ui.send_message(InspectorMessage::context(
+                self.inspector,
+                MessageDirection::ToWidget,
+                context,
+            ));
+        }
+
+        self.bank = Some(bank);
+
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn sync_to_model(&self, ui: &mut UserInterface) {
+        if let Some(bank) = self.bank.as_ref() {
+            if let Some(data) = bank.state().data() {
+                let context = ui
+                    .node(self.inspector)
+                    .cast::<Inspector>()
+                    .expect("Must be inspector")
+                    .context()
+                    .clone();
+                if let Err(errors) = context.sync(&*data, ui, 0, true, Default::default()) {
+                    Log::err(format!("Failed to sync audio event bank: {errors:?}"));
+                }
+            }
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        let Some(bank) = self.bank.clone() else {
+            return;
+        };
+
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(data) = bank.state().data() {
+                    PropertyAction::from_field_kind(&args.value).apply(
+                        &args.path(),
+                        data,
+                        &mut |result| {
+                            Log::verify(result);
+                        },
+                    );
+                }
+                // Collections could change their size, so the inspector must be synced.
+                self.sync_to_model(&mut engine.user_interface);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.save {
+                if let Some(path) = bank.kind().into_path() {
+                    if let Some(data) = bank.state().data() {
+                        match data.save(&path) {
+                            Ok(_) => Log::info(format!("Audio event bank {path:?} was saved.")),
+                            Err(e) => Log::err(format!(
+                                "Unable to save audio event bank {path:?}. Reason: {e:?}"
+                            )),
+                        }
+                    }
+                }
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.bank = None;
+            }
+        }
+    }
+}
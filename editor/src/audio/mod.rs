@@ -3,10 +3,13 @@ use crate::{
     audio::bus::{AudioBusView, AudioBusViewBuilder, AudioBusViewMessage},
     gui::make_dropdown_list_option,
     inspector::editors::resource::{ResourceFieldBuilder, ResourceFieldMessage},
+    localization::window_title,
     message::MessageSender,
     scene::commands::{
-        effect::{AddAudioBusCommand, LinkAudioBuses, RemoveAudioBusCommand},
-        sound_context::{SetDistanceModelCommand, SetRendererCommand},
+        effect::{
+            AddAudioBusCommand, LinkAudioBuses, RemoveAudioBusCommand, SetAudioBusGraphCommand,
+        },
+        sound_context::{SetDistanceModelCommand, SetDopplerFactorCommand, SetRendererCommand},
         CommandGroup,
     },
     send_sync_message,
@@ -18,29 +21,31 @@ use fyrox::asset::manager::ResourceManager;
 use fyrox::core::parking_lot::Mutex;
 use fyrox::graph::SceneGraph;
 use fyrox::{
-    core::{futures::executor::block_on, pool::Handle},
+    core::{futures::executor::block_on, log::Log, pool::Handle},
     engine::Engine,
     gui::{
         button::{ButtonBuilder, ButtonMessage},
         dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
         grid::{Column, Row},
         list_view::{ListView, ListViewBuilder, ListViewMessage},
         message::UiMessage,
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
         utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowTitle},
-        Orientation, Thickness, UiNode, VerticalAlignment,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, VerticalAlignment,
     },
     scene::sound::{AudioBus, AudioBusGraph, DistanceModel, HrirSphereResourceData, Renderer},
 };
-use std::cmp::Ordering;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use strum::VariantNames;
 
 mod bus;
+pub mod event_bank;
 pub mod preview;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -62,8 +67,13 @@ pub struct AudioPanel {
     pub window: Handle<UiNode>,
     add_bus: Handle<UiNode>,
     remove_bus: Handle<UiNode>,
+    save_preset: Handle<UiNode>,
+    load_preset: Handle<UiNode>,
+    save_preset_dialog: Handle<UiNode>,
+    load_preset_dialog: Handle<UiNode>,
     audio_buses: Handle<UiNode>,
     distance_model: Handle<UiNode>,
+    doppler_factor: Handle<UiNode>,
     renderer: Handle<UiNode>,
     hrir_resource: Handle<UiNode>,
 }
@@ -88,6 +98,27 @@ fn fetch_possible_parent_buses(
     result
 }
 
+fn make_preset_file_selector(ctx: &mut BuildContext, mode: FileBrowserMode) -> Handle<UiNode> {
+    let title = match mode {
+        FileBrowserMode::Open => "Load Mixer Preset",
+        FileBrowserMode::Save { .. } => "Save Mixer Preset As",
+    };
+
+    FileSelectorBuilder::new(
+        WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+            .with_title(WindowTitle::text(title))
+            .open(false),
+    )
+    .with_mode(mode)
+    .with_path("./")
+    .with_filter(Filter::new(|p: &Path| {
+        p.is_dir()
+            || p.extension()
+                .map_or(false, |ext| ext.to_string_lossy().as_ref() == "mixer")
+    }))
+    .build(ctx)
+}
+
 fn audio_bus_effect_names(audio_bus: &AudioBus) -> Vec<String> {
     audio_bus
         .effects()
@@ -101,8 +132,11 @@ impl AudioPanel {
 
         let add_bus;
         let remove_bus;
+        let save_preset;
+        let load_preset;
         let buses;
         let distance_model;
+        let doppler_factor;
         let renderer;
         let hrir_resource;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("AudioPanel"))
@@ -143,6 +177,32 @@ impl AudioPanel {
                                         .build(ctx);
                                         distance_model
                                     })
+                                    .with_child(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text("Doppler")
+                                        .build(ctx),
+                                    )
+                                    .with_child({
+                                        doppler_factor = NumericUpDownBuilder::<f32>::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_width(60.0)
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Doppler Factor. Exaggerates (values greater \
+                                                    than 1) or weakens (values less than 1) the \
+                                                    doppler effect. Zero disables the effect.",
+                                                )),
+                                        )
+                                        .with_min_value(0.0)
+                                        .with_value(1.0)
+                                        .build(ctx);
+                                        doppler_factor
+                                    })
                                     .with_child(
                                         TextBuilder::new(
                                             WidgetBuilder::new()
@@ -223,6 +283,36 @@ impl AudioPanel {
                                         .with_text("Remove Bus")
                                         .build(ctx);
                                         remove_bus
+                                    })
+                                    .with_child({
+                                        save_preset = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Saves the audio bus graph to a file, that \
+                                                    could be loaded in other scenes.",
+                                                )),
+                                        )
+                                        .with_text("Save Preset...")
+                                        .build(ctx);
+                                        save_preset
+                                    })
+                                    .with_child({
+                                        load_preset = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Replaces the audio bus graph with the one \
+                                                    loaded from a file.",
+                                                )),
+                                        )
+                                        .with_text("Load Preset...")
+                                        .build(ctx);
+                                        load_preset
                                     }),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -235,15 +325,28 @@ impl AudioPanel {
                 .add_row(Row::strict(25.0))
                 .build(ctx),
             )
-            .with_title(WindowTitle::text("Audio Context"))
+            .with_title(window_title("Audio Mixer", ctx))
             .build(ctx);
 
+        let save_preset_dialog = make_preset_file_selector(
+            ctx,
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("unnamed.mixer"),
+            },
+        );
+        let load_preset_dialog = make_preset_file_selector(ctx, FileBrowserMode::Open);
+
         Self {
             window,
             audio_buses: buses,
             distance_model,
+            doppler_factor,
             add_bus,
             remove_bus,
+            save_preset,
+            load_preset,
+            save_preset_dialog,
+            load_preset_dialog,
             renderer,
             hrir_resource,
         }
@@ -254,6 +357,7 @@ impl AudioPanel {
         message: &UiMessage,
         editor_selection: &Selection,
         sender: &MessageSender,
+        game_scene: &GameScene,
         engine: &Engine,
     ) {
         if let Some(ButtonMessage::Click) = message.data() {
@@ -274,6 +378,62 @@ impl AudioPanel {
 
                     sender.do_scene_command(CommandGroup::from(commands));
                 }
+            } else if message.destination() == self.save_preset
+                || message.destination() == self.load_preset
+            {
+                let dialog = if message.destination() == self.save_preset {
+                    self.save_preset_dialog
+                } else {
+                    self.load_preset_dialog
+                };
+
+                engine
+                    .user_interface
+                    .send_message(WindowMessage::open_modal(
+                        dialog,
+                        MessageDirection::ToWidget,
+                        true,
+                    ));
+                engine
+                    .user_interface
+                    .send_message(FileSelectorMessage::root(
+                        dialog,
+                        MessageDirection::ToWidget,
+                        Some(std::env::current_dir().unwrap()),
+                    ));
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.save_preset_dialog {
+                let mut graph = engine.scenes[game_scene.scene]
+                    .graph
+                    .sound_context
+                    .state()
+                    .bus_graph_ref()
+                    .clone();
+                if let Err(err) = graph.save(path) {
+                    Log::err(format!(
+                        "Unable to save mixer preset to {}. Reason: {:?}",
+                        path.display(),
+                        err
+                    ));
+                }
+            } else if message.destination() == self.load_preset_dialog {
+                match block_on(AudioBusGraph::load(path)) {
+                    Ok(graph) => {
+                        sender.do_scene_command(CommandGroup::from(vec![
+                            GameSceneCommand::new(ChangeSelectionCommand::new(
+                                Selection::None,
+                                editor_selection.clone(),
+                            )),
+                            GameSceneCommand::new(SetAudioBusGraphCommand { graph }),
+                        ]));
+                    }
+                    Err(err) => Log::err(format!(
+                        "Unable to load mixer preset from {}. Reason: {:?}",
+                        path.display(),
+                        err
+                    )),
+                }
             }
         } else if let Some(ListViewMessage::SelectionChanged(Some(effect_index))) = message.data() {
             if message.destination() == self.audio_buses
@@ -333,6 +493,12 @@ impl AudioPanel {
                     sender.do_scene_command(SetDistanceModelCommand::new(distance_model));
                 }
             }
+        } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
+            if message.destination() == self.doppler_factor
+                && message.direction() == MessageDirection::FromWidget
+            {
+                sender.do_scene_command(SetDopplerFactorCommand::new(*value));
+            }
         } else if let Some(ResourceFieldMessage::Value(resource)) =
             message.data::<ResourceFieldMessage<HrirSphereResourceData>>()
         {
@@ -360,57 +526,48 @@ impl AudioPanel {
             .items()
             .to_vec();
 
-        match (context_state.bus_graph_ref().len()).cmp(&items.len()) {
-            Ordering::Less => {
-                for &item in &items {
-                    let bus_handle = item_bus(item, ui);
-                    if context_state
-                        .bus_graph_ref()
-                        .buses_pair_iter()
-                        .all(|(other_bus_handle, _)| other_bus_handle != bus_handle)
-                    {
-                        send_sync_message(
-                            ui,
-                            ListViewMessage::remove_item(
-                                self.audio_buses,
-                                MessageDirection::ToWidget,
-                                item,
-                            ),
-                        );
-                    }
-                }
+        // Remove views of the buses that no longer exist (the graph could be replaced entirely
+        // when loading a preset, so the amount of buses is not enough to detect changes).
+        for &item in &items {
+            let bus_handle = item_bus(item, ui);
+            if context_state
+                .bus_graph_ref()
+                .try_get_bus_ref(bus_handle)
+                .is_none()
+            {
+                send_sync_message(
+                    ui,
+                    ListViewMessage::remove_item(
+                        self.audio_buses,
+                        MessageDirection::ToWidget,
+                        item,
+                    ),
+                );
             }
-            Ordering::Greater => {
-                for (audio_bus_handle, audio_bus) in context_state.bus_graph_ref().buses_pair_iter()
-                {
-                    if items.iter().all(|i| item_bus(*i, ui) != audio_bus_handle) {
-                        let item = AudioBusViewBuilder::new(
-                            WidgetBuilder::new()
-                                .with_width(100.0)
-                                .with_margin(Thickness::uniform(1.0)),
-                        )
-                        .with_name(audio_bus.name())
-                        .with_effect_names(audio_bus_effect_names(audio_bus))
-                        .with_parent_bus(audio_bus.parent())
-                        .with_possible_parent_buses(fetch_possible_parent_buses(
-                            audio_bus_handle,
-                            context_state.bus_graph_ref(),
-                        ))
-                        .with_audio_bus(audio_bus_handle)
-                        .build(&mut ui.build_ctx());
-
-                        send_sync_message(
-                            ui,
-                            ListViewMessage::add_item(
-                                self.audio_buses,
-                                MessageDirection::ToWidget,
-                                item,
-                            ),
-                        );
-                    }
-                }
+        }
+
+        for (audio_bus_handle, audio_bus) in context_state.bus_graph_ref().buses_pair_iter() {
+            if items.iter().all(|i| item_bus(*i, ui) != audio_bus_handle) {
+                let item = AudioBusViewBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(100.0)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_name(audio_bus.name())
+                .with_effect_names(audio_bus_effect_names(audio_bus))
+                .with_parent_bus(audio_bus.parent())
+                .with_possible_parent_buses(fetch_possible_parent_buses(
+                    audio_bus_handle,
+                    context_state.bus_graph_ref(),
+                ))
+                .with_audio_bus(audio_bus_handle)
+                .build(&mut ui.build_ctx());
+
+                send_sync_message(
+                    ui,
+                    ListViewMessage::add_item(self.audio_buses, MessageDirection::ToWidget, item),
+                );
             }
-            _ => (),
         }
 
         let mut selection_index = None;
@@ -460,6 +617,13 @@ impl AudioPanel {
                 .node(*audio_bus_view)
                 .query_component::<AudioBusView>()
                 .unwrap();
+            // The view could be scheduled for removal already.
+            let Some(audio_bus_ref) = context_state
+                .bus_graph_ref()
+                .try_get_bus_ref(audio_bus_view_ref.bus)
+            else {
+                continue;
+            };
             send_sync_message(
                 ui,
                 AudioBusViewMessage::possible_parent_buses(
@@ -471,10 +635,6 @@ impl AudioPanel {
                     ),
                 ),
             );
-            let audio_bus_ref = context_state
-                .bus_graph_ref()
-                .try_get_bus_ref(audio_bus_view_ref.bus)
-                .unwrap();
             send_sync_message(
                 ui,
                 AudioBusViewMessage::effect_names(
@@ -502,6 +662,15 @@ impl AudioPanel {
             ),
         );
 
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(
+                self.doppler_factor,
+                MessageDirection::ToWidget,
+                context_state.doppler_factor(),
+            ),
+        );
+
         send_sync_message(
             ui,
             DropdownListMessage::selection(
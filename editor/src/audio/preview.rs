@@ -1,4 +1,5 @@
 use crate::{
+    localization::window_title,
     scene::{GameScene, Selection},
     send_sync_message, Message,
 };
@@ -14,7 +15,7 @@ use fyrox::{
         scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
         text::TextBuilder,
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, Thickness, UiNode, VerticalAlignment,
     },
     scene::{
@@ -49,7 +50,7 @@ impl AudioPreviewPanel {
                 .with_width(300.0)
                 .with_height(70.0),
         )
-        .with_title(WindowTitle::text("Audio Preview Panel"))
+        .with_title(window_title("Audio Preview Panel", ctx))
         .open(false)
         .with_content(
             GridBuilder::new(
@@ -1,4 +1,4 @@
-use crate::{message::MessageSender, Message};
+use crate::{localization::window_title, message::MessageSender, Message};
 use fyrox::{
     core::{parking_lot::Mutex, pool::Handle},
     gui::{
@@ -10,7 +10,7 @@ use fyrox::{
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
         BRUSH_DARKEST,
     },
@@ -100,7 +100,7 @@ impl BuildWindow {
                 .add_column(Column::stretch())
                 .build(ctx),
             )
-            .with_title(WindowTitle::text("Building the Game..."))
+            .with_title(window_title("Building the Game...", ctx))
             .build(ctx);
 
         Self {
@@ -1,7 +1,7 @@
 use crate::settings::{
     camera::CameraSettings,
     keys::KeyBindings,
-    scene::{SceneCameraSettings, SceneSettings},
+    scene::{CameraBookmark, SceneCameraSettings, SceneSettings},
     Settings,
 };
 use fyrox::graph::SceneGraph;
@@ -33,7 +33,7 @@ use fyrox::{
 };
 use std::path::Path;
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
 };
 
@@ -70,6 +70,9 @@ pub struct CameraController {
     editor_context: PickContext,
     scene_context: PickContext,
     prev_interaction_state: bool,
+    bookmarks: HashMap<usize, CameraBookmark>,
+    /// A flag, that tells that the camera settings of the scene must be saved on next update.
+    settings_changed: bool,
 }
 
 #[derive(Clone)]
@@ -165,6 +168,8 @@ impl CameraController {
             editor_context: Default::default(),
             scene_context: Default::default(),
             prev_interaction_state: false,
+            bookmarks: settings.bookmarks,
+            settings_changed: false,
         }
     }
 
@@ -180,19 +185,14 @@ impl CameraController {
     }
 
     pub fn fit_object(&mut self, scene: &mut Scene, handle: Handle<Node>) {
-        // Combine AABBs from the descendants.
-        let mut aabb = AxisAlignedBoundingBox::default();
-        for descendant in scene.graph.traverse_iter(handle) {
-            let descendant_aabb = descendant.local_bounding_box();
-            if !descendant_aabb.is_invalid_or_degenerate() {
-                aabb.add_box(descendant_aabb.transform(&descendant.global_transform()))
-            }
-        }
+        self.fit_objects(scene, &[handle])
+    }
 
-        if aabb.is_invalid_or_degenerate() {
-            // To prevent the camera from flying away into abyss.
-            aabb = AxisAlignedBoundingBox::from_point(scene.graph[handle].global_position());
-        }
+    /// Moves the camera so the combined bounding box of the given objects fits in the view.
+    pub fn fit_objects(&mut self, scene: &mut Scene, handles: &[Handle<Node>]) {
+        let Some(aabb) = objects_bounding_box(&scene.graph, handles) else {
+            return;
+        };
 
         let fit_parameters = scene.graph[self.camera].as_camera().fit(
             &aabb,
@@ -229,6 +229,51 @@ impl CameraController {
         }
     }
 
+    /// Moves the center of rotation of the camera to the given point, keeping the camera at its
+    /// current position. The camera will look at the point.
+    pub fn orbit_around(&mut self, center: Vector3<f32>, graph: &mut Graph) {
+        let camera_position = graph[self.camera].global_position();
+        let Some(look) = (center - camera_position).try_normalize(f32::EPSILON) else {
+            return;
+        };
+
+        self.yaw = look.x.atan2(look.z);
+        self.pitch = (-look.y).clamp(-1.0, 1.0).asin();
+        self.z_offset = -center.metric_distance(&camera_position);
+        graph[self.pivot].local_transform_mut().set_position(center);
+    }
+
+    /// Saves current pose of the camera in a bookmark with the given index.
+    pub fn save_bookmark(&mut self, index: usize, graph: &Graph) {
+        self.bookmarks.insert(
+            index,
+            CameraBookmark {
+                position: self.position(graph),
+                yaw: self.yaw,
+                pitch: self.pitch,
+                z_offset: self.z_offset,
+            },
+        );
+        self.settings_changed = true;
+    }
+
+    /// Restores the camera pose from a bookmark with the given index. Returns `false` if there is
+    /// no such bookmark.
+    pub fn restore_bookmark(&mut self, index: usize, graph: &mut Graph) -> bool {
+        let Some(bookmark) = self.bookmarks.get(&index) else {
+            return false;
+        };
+
+        self.yaw = bookmark.yaw;
+        self.pitch = bookmark.pitch;
+        self.z_offset = bookmark.z_offset;
+        graph[self.pivot]
+            .local_transform_mut()
+            .set_position(bookmark.position);
+        self.settings_changed = true;
+        true
+    }
+
     pub fn set_projection(&self, graph: &mut Graph, projection: Projection) {
         graph[self.camera]
             .as_camera_mut()
@@ -283,6 +328,7 @@ impl CameraController {
                 position: self.position(graph),
                 yaw: self.yaw,
                 pitch: self.pitch,
+                bookmarks: self.bookmarks.clone(),
             };
 
             if let Some(scene_settings) = settings.scene_settings.get(path) {
@@ -536,9 +582,10 @@ impl CameraController {
         self.drag_side = 0.0;
         self.drag_up = 0.0;
 
-        if !self.is_interacting() && self.prev_interaction_state {
+        if !self.is_interacting() && (self.prev_interaction_state || self.settings_changed) {
             self.on_interaction_ended(settings, scene_path, graph);
             self.prev_interaction_state = false;
+            self.settings_changed = false;
         } else {
             self.prev_interaction_state = true;
         }
@@ -704,6 +751,67 @@ fn read_triangle(
     Some([a, b, c])
 }
 
+/// Returns world-space position of the vertex of the given mesh node, that is closest to the given
+/// world-space point.
+pub fn closest_mesh_vertex(node: &Node, point: Vector3<f32>) -> Option<Vector3<f32>> {
+    let mesh = node.query_component_ref::<Mesh>()?;
+    let transform = mesh.global_transform();
+
+    let mut closest = None;
+    let mut closest_distance = f32::MAX;
+    for surface in mesh.surfaces() {
+        let data = surface.data();
+        let data = data.lock();
+        for vertex in data.vertex_buffer.iter() {
+            let Ok(position) = vertex.read_3_f32(VertexAttributeUsage::Position) else {
+                continue;
+            };
+            let position = transform_vertex(position, &transform);
+            let distance = position.metric_distance(&point);
+            if distance < closest_distance {
+                closest_distance = distance;
+                closest = Some(position);
+            }
+        }
+    }
+    closest
+}
+
+/// Calculates world-space bounding box of the given objects and their descendants. If the objects
+/// have no bounds, the box encloses their positions.
+pub fn objects_bounding_box(
+    graph: &Graph,
+    handles: &[Handle<Node>],
+) -> Option<AxisAlignedBoundingBox> {
+    let mut aabb = AxisAlignedBoundingBox::default();
+    let mut positions = AxisAlignedBoundingBox::default();
+    let mut found = false;
+    for &handle in handles {
+        let Some(node) = graph.try_get(handle) else {
+            continue;
+        };
+        found = true;
+        positions.add_point(node.global_position());
+
+        // Combine AABBs from the descendants.
+        for descendant in graph.traverse_iter(handle) {
+            let descendant_aabb = descendant.local_bounding_box();
+            if !descendant_aabb.is_invalid_or_degenerate() {
+                aabb.add_box(descendant_aabb.transform(&descendant.global_transform()))
+            }
+        }
+    }
+
+    if !found {
+        None
+    } else if aabb.is_invalid_or_degenerate() {
+        // To prevent the camera from flying away into abyss.
+        Some(positions)
+    } else {
+        Some(aabb)
+    }
+}
+
 fn has_hull(node: &Node) -> bool {
     node.query_component_ref::<Mesh>().is_some()
 }
@@ -1,4 +1,5 @@
 use crate::{
+    localization::window_title,
     scene::{GameScene, Selection},
     send_sync_message, Message,
 };
@@ -12,7 +13,7 @@ use fyrox::{
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, Orientation, UiNode, VerticalAlignment,
     },
     scene::{camera::Camera, node::Node},
@@ -29,7 +30,7 @@ impl CameraPreviewControlPanel {
     pub fn new(scene_viewer_frame: Handle<UiNode>, ctx: &mut BuildContext) -> Self {
         let preview;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("CameraPanel"))
-            .with_title(WindowTitle::text("Camera Preview"))
+            .with_title(window_title("Camera Preview", ctx))
             .with_content(
                 StackPanelBuilder::new(
                     WidgetBuilder::new()
@@ -15,6 +15,12 @@ macro_rules! define_universal_commands {
                 fyrox::gui::inspector::PropertyAction::RemoveItem { index } => Some(<$command_wrapper>::new(
                     RemoveCollectionItemCommand::new($handle_ident, property_changed.path(), index, $($field_name),*)
                 )),
+                fyrox::gui::inspector::PropertyAction::InsertItem { index, value } => Some(<$command_wrapper>::new(
+                    InsertCollectionItemCommand::new($handle_ident, property_changed.path(), index, value, $($field_name),*)
+                )),
+                fyrox::gui::inspector::PropertyAction::MoveItem { old_index, new_index } => Some(<$command_wrapper>::new(
+                    MoveCollectionItemCommand::new($handle_ident, property_changed.path(), old_index, new_index, $($field_name),*)
+                )),
                 // Must be handled outside, there is not enough context and it near to impossible to create universal reversion
                 // for InheritableVariable<T>.
                 fyrox::gui::inspector::PropertyAction::Revert => None
@@ -244,5 +250,120 @@ macro_rules! define_universal_commands {
                 })
             }
         }
+
+        #[derive(Debug)]
+        pub struct InsertCollectionItemCommand {
+            #[allow(dead_code)]
+            $handle_ident: $handle,
+            path: String,
+            index: usize,
+            item: Option<Box<dyn fyrox::core::reflect::Reflect>>,
+            $($field_name: $field_type),*
+        }
+
+        impl InsertCollectionItemCommand {
+            pub fn new($handle_ident: $handle, path: String, index: usize, item: Box<dyn fyrox::core::reflect::Reflect>, $($field_name: $field_type),*) -> Self {
+                Self {
+                    $handle_ident,
+                    path,
+                    index,
+                    item: Some(item),
+                    $($field_name),*
+                }
+            }
+        }
+
+        impl $command for InsertCollectionItemCommand {
+            fn name(&mut $self, _: &$ctx) -> String {
+                format!("Insert item to {} collection at {}", $self.path, $self.index)
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            if let Err(item) = list.reflect_insert($self.index, $self.item.take().unwrap()) {
+                                fyrox::core::log::Log::err(format!(
+                                    "Failed to insert item to {} collection. Type mismatch {} and {}!",
+                                    $self.path, item.type_name(), list.type_name()
+                                ));
+                                $self.item = Some(item);
+                            }
+                        } else {
+                            fyrox::core::log::Log::err(format!("Property {} is not a collection!", $self.path))
+                        }
+                    });
+                })
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            if let Some(item) = list.reflect_remove($self.index) {
+                                $self.item = Some(item);
+                            } else {
+                                fyrox::core::log::Log::err(format!("Failed to remove item from {} collection!", $self.path))
+                            }
+                        } else {
+                            fyrox::core::log::Log::err(format!("Property {} is not a collection!", $self.path))
+                        }
+                    });
+                })
+            }
+        }
+
+        #[derive(Debug)]
+        pub struct MoveCollectionItemCommand {
+            #[allow(dead_code)]
+            $handle_ident: $handle,
+            path: String,
+            old_index: usize,
+            new_index: usize,
+            $($field_name: $field_type),*
+        }
+
+        impl MoveCollectionItemCommand {
+            pub fn new($handle_ident: $handle, path: String, old_index: usize, new_index: usize, $($field_name: $field_type),*) -> Self {
+                Self {
+                    $handle_ident,
+                    path,
+                    old_index,
+                    new_index,
+                    $($field_name),*
+                }
+            }
+
+            fn move_item(&mut $self, $ctx_ident: &mut $ctx, from: usize, to: usize) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            if !fyrox::gui::inspector::move_list_item(list, from, to) {
+                                fyrox::core::log::Log::err(format!(
+                                    "Failed to move item {} of {} collection to {}!",
+                                    from, $self.path, to
+                                ))
+                            }
+                        } else {
+                            fyrox::core::log::Log::err(format!("Property {} is not a collection!", $self.path))
+                        }
+                    });
+                })
+            }
+        }
+
+        impl $command for MoveCollectionItemCommand {
+            fn name(&mut $self, _: &$ctx) -> String {
+                format!("Move collection {} item {} to {}", $self.path, $self.old_index, $self.new_index)
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                $self.move_item($ctx_ident, $self.old_index, $self.new_index);
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                $self.move_item($ctx_ident, $self.new_index, $self.old_index);
+            }
+        }
     };
 }
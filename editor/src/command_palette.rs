@@ -0,0 +1,276 @@
+//! Command palette allows to find any editor action by its name (using fuzzy matching) and execute it
+//! using keyboard only.
+
+use crate::{
+    action::{fuzzy_match, Action, ActionKind, BuiltinAction},
+    localization::window_title,
+    message::MessageSender,
+    Message,
+};
+use fyrox::graph::SceneGraph;
+use fyrox::{
+    core::pool::Handle,
+    gui::{
+        border::BorderBuilder,
+        decorator::DecoratorBuilder,
+        grid::{Column, GridBuilder, Row},
+        key::HotKey,
+        list_view::{ListView, ListViewBuilder, ListViewMessage},
+        message::{KeyCode, MessageDirection, MouseButton, UiMessage},
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBoxBuilder, TextCommitMode},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+        BRUSH_LIGHTER,
+    },
+};
+
+pub struct CommandPalette {
+    pub window: Handle<UiNode>,
+    search_bar: Handle<UiNode>,
+    list: Handle<UiNode>,
+    actions: Vec<Action>,
+    /// Indices of the actions, that match the current search pattern, sorted by the match score.
+    filtered: Vec<usize>,
+    selected: Option<usize>,
+}
+
+fn make_item(action: &Action, ctx: &mut BuildContext) -> Handle<UiNode> {
+    let shortcut = if let HotKey::NotSet = action.hot_key {
+        String::new()
+    } else {
+        action.hot_key.to_string()
+    };
+
+    DecoratorBuilder::new(BorderBuilder::new(
+        WidgetBuilder::new().with_height(22.0).with_child(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::left(2.0))
+                                .on_column(0),
+                        )
+                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                        .with_text(&action.name)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::right(2.0))
+                                .with_foreground(BRUSH_LIGHTER)
+                                .on_column(1),
+                        )
+                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                        .with_horizontal_text_alignment(HorizontalAlignment::Right)
+                        .with_text(shortcut)
+                        .build(ctx),
+                    ),
+            )
+            .add_column(Column::stretch())
+            .add_column(Column::auto())
+            .add_row(Row::stretch())
+            .build(ctx),
+        ),
+    ))
+    .build(ctx)
+}
+
+impl CommandPalette {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let search_bar;
+        let list;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(300.0))
+            .open(false)
+            .with_title(window_title("Command Palette", ctx))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            search_bar = TextBoxBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .on_row(0),
+                            )
+                            .with_text_commit_mode(TextCommitMode::Immediate)
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx);
+                            search_bar
+                        })
+                        .with_child({
+                            list = ListViewBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .on_row(1),
+                            )
+                            .build(ctx);
+                            list
+                        }),
+                )
+                .add_row(Row::strict(24.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            search_bar,
+            list,
+            actions: Default::default(),
+            filtered: Default::default(),
+            selected: None,
+        }
+    }
+
+    /// Opens the palette with the given set of actions.
+    pub fn open(&mut self, actions: Vec<Action>, ui: &mut UserInterface) {
+        self.actions = actions
+            .into_iter()
+            .filter(|action| action.kind != ActionKind::Builtin(BuiltinAction::OpenCommandPalette))
+            .collect();
+
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        ui.send_message(TextMessage::text(
+            self.search_bar,
+            MessageDirection::ToWidget,
+            Default::default(),
+        ));
+        ui.send_message(WidgetMessage::focus(
+            self.search_bar,
+            MessageDirection::ToWidget,
+        ));
+
+        self.filter("", ui);
+    }
+
+    fn close(&mut self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+        self.actions.clear();
+        self.filtered.clear();
+        self.selected = None;
+    }
+
+    fn filter(&mut self, pattern: &str, ui: &mut UserInterface) {
+        let mut matches = self
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, action)| fuzzy_match(pattern, &action.name).map(|score| (i, score)))
+            .collect::<Vec<_>>();
+        // Stable sort keeps the order of the actions with the same score.
+        matches.sort_by(|(_, a), (_, b)| b.cmp(a));
+        self.filtered = matches.into_iter().map(|(i, _)| i).collect();
+
+        let ctx = &mut ui.build_ctx();
+        let items = self
+            .filtered
+            .iter()
+            .map(|i| make_item(&self.actions[*i], ctx))
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+
+        // The new items are not in the list yet, so there is nothing to bring into view.
+        self.selected = if self.filtered.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        ui.send_message(ListViewMessage::selection(
+            self.list,
+            MessageDirection::ToWidget,
+            self.selected,
+        ));
+    }
+
+    fn select(&mut self, index: Option<usize>, ui: &UserInterface) {
+        self.selected = index;
+        ui.send_message(ListViewMessage::selection(
+            self.list,
+            MessageDirection::ToWidget,
+            index,
+        ));
+        if let Some(index) = index {
+            ui.send_message(ListViewMessage::bring_item_into_view(
+                self.list,
+                MessageDirection::ToWidget,
+                ui.node(self.list)
+                    .cast::<ListView>()
+                    .and_then(|l| l.items().get(index).cloned())
+                    .unwrap_or_default(),
+            ));
+        }
+    }
+
+    fn execute_selected(&mut self, ui: &UserInterface, sender: &MessageSender) {
+        if let Some(action) = self
+            .selected
+            .and_then(|i| self.filtered.get(i))
+            .and_then(|i| self.actions.get(*i))
+        {
+            sender.send(Message::ExecuteAction(action.clone()));
+        }
+        self.close(ui);
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+    ) {
+        if let Some(TextMessage::Text(pattern)) = message.data() {
+            if message.destination() == self.search_bar
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let pattern = pattern.clone();
+                self.filter(&pattern, ui);
+            }
+        } else if let Some(WidgetMessage::KeyDown(key)) = message.data() {
+            if message.destination() == self.search_bar {
+                let count = self.filtered.len();
+                match *key {
+                    KeyCode::ArrowDown if count > 0 => {
+                        let index = self.selected.map_or(0, |i| (i + 1) % count);
+                        self.select(Some(index), ui);
+                    }
+                    KeyCode::ArrowUp if count > 0 => {
+                        let index = self.selected.map_or(0, |i| (i + count - 1) % count);
+                        self.select(Some(index), ui);
+                    }
+                    KeyCode::Enter | KeyCode::NumpadEnter => self.execute_selected(ui, sender),
+                    KeyCode::Escape => self.close(ui),
+                    _ => (),
+                }
+            }
+        } else if let Some(WidgetMessage::DoubleClick {
+            button: MouseButton::Left,
+        }) = message.data()
+        {
+            if ui.is_node_child_of(message.destination(), self.list) {
+                self.execute_selected(ui, sender);
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(selection)) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.selected = *selection;
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
-    define_command_stack, send_sync_message, utils::create_file_selector, MessageBoxButtons,
-    MessageBoxMessage, MSG_SYNC_FLAG,
+    define_command_stack,
+    localization::{tr, window_title},
+    send_sync_message,
+    utils::create_file_selector,
+    MessageBoxButtons, MessageBoxMessage, MSG_SYNC_FLAG,
 };
 use fyrox::asset::untyped::ResourceKind;
 use fyrox::{
@@ -107,7 +110,7 @@ impl CurveEditorWindow {
         let save_changes_message_box = MessageBoxBuilder::new(
             WindowBuilder::new(WidgetBuilder::new())
                 .open(false)
-                .with_title(WindowTitle::text("Unsaved Changes")),
+                .with_title(window_title("Unsaved Changes", ctx)),
         )
         .with_text(
             "You have unsaved changes, do you want to save it before closing the curve editor?",
@@ -118,7 +121,7 @@ impl CurveEditorWindow {
         let cancel_message_box = MessageBoxBuilder::new(
             WindowBuilder::new(WidgetBuilder::new())
                 .open(false)
-                .with_title(WindowTitle::text("Unsaved Changes")),
+                .with_title(window_title("Unsaved Changes", ctx)),
         )
         .with_text("You have unsaved changes, do you want to quit the curve editor without saving?")
         .with_buttons(MessageBoxButtons::YesNo)
@@ -256,7 +259,7 @@ impl CurveEditorWindow {
                 .add_column(Column::stretch())
                 .build(ctx),
             )
-            .with_title(WindowTitle::text("Curve Editor"))
+            .with_title(window_title("Curve Editor", ctx))
             .build(ctx);
 
         Self {
@@ -343,13 +346,15 @@ impl CurveEditorWindow {
             let kind = curve_resource.header().kind.clone();
 
             match kind {
-                ResourceKind::Embedded => "Curve Editor - Unnamed Curve".to_string(),
+                ResourceKind::Embedded => {
+                    format!("{} - {}", tr("Curve Editor"), tr("Unnamed Curve"))
+                }
                 ResourceKind::External(path) => {
-                    format!("Curve Editor - {}", path.display())
+                    format!("{} - {}", tr("Curve Editor"), path.display())
                 }
             }
         } else {
-            "Curve Editor".to_string()
+            tr("Curve Editor")
         };
 
         ui.send_message(WindowMessage::title(
@@ -432,7 +437,7 @@ impl CurveEditorWindow {
                     self.close(ui);
                 }
             }
-        } else if let Some(CurveEditorMessage::Sync(curve)) = message.data() {
+        } else if let Some(CurveEditorMessage::Changed(curve)) = message.data() {
             if message.destination() == self.curve_editor
                 && message.direction() == MessageDirection::FromWidget
                 && message.flags != MSG_SYNC_FLAG
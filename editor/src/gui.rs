@@ -21,6 +21,7 @@ use std::sync::Arc;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AssetItemMessage {
     Select(bool),
+    Icon(Option<UntypedResource>),
 }
 
 pub fn make_dropdown_list_option_universal<T: Send + 'static>(
@@ -76,6 +77,7 @@ pub fn make_dropdown_list_option_with_height(
 
 impl AssetItemMessage {
     define_constructor!(AssetItemMessage:Select => fn select(bool), layout: false);
+    define_constructor!(AssetItemMessage:Icon => fn icon(Option<UntypedResource>), layout: false);
 }
 
 pub fn make_image_button_with_tooltip(
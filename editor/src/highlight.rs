@@ -302,8 +302,12 @@ impl SceneRenderPass for HighlightRenderPass {
                                 matrix_storage: ctx.matrix_storage,
                                 persistent_identifier: instance.persistent_identifier,
                                 light_data: None,
+                                light_clusters: None,
+                                motion: None,
                                 ambient_light: Default::default(),
                                 scene_depth: Some(&ctx.depth_texture),
+                                instance_matrices: &[],
+                                instance_color: instance.color,
                             });
                         },
                     )?;
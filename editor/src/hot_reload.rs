@@ -0,0 +1,111 @@
+//! Rebuilds the game library every time the source code of the game changes, so the running game
+//! (started with `dylib` feature of the executor) could reload it.
+
+use fyrox::core::{log::Log, watcher::FileSystemWatcher};
+use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Stdio},
+    time::Duration,
+};
+
+pub struct GameLibraryBuilder {
+    watcher: FileSystemWatcher,
+    process: Option<Child>,
+    need_rebuild: bool,
+    release: bool,
+}
+
+impl GameLibraryBuilder {
+    pub const SOURCE_DIRECTORY: &'static str = "game/src";
+    pub const PACKAGE: &'static str = "game_dylib";
+
+    pub fn new(release: bool) -> Option<Self> {
+        match FileSystemWatcher::new(Self::SOURCE_DIRECTORY, Duration::from_secs(1)) {
+            Ok(watcher) => Some(Self {
+                watcher,
+                process: None,
+                need_rebuild: false,
+                release,
+            }),
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to watch {} for changes, hot reloading is disabled. Reason: {:?}",
+                    Self::SOURCE_DIRECTORY,
+                    e
+                ));
+                None
+            }
+        }
+    }
+
+    pub fn update(&mut self) {
+        while let Some(event) = self.watcher.try_get_event() {
+            if event.kind.is_modify() || event.kind.is_create() || event.kind.is_remove() {
+                self.need_rebuild = true;
+            }
+        }
+
+        if let Some(process) = self.process.as_mut() {
+            match process.try_wait() {
+                Ok(Some(status)) => {
+                    if status.success() {
+                        Log::info("Game library was rebuilt successfully, reloading...");
+                    } else {
+                        Log::err("Failed to rebuild the game library!");
+                    }
+                    self.process = None;
+                }
+                Ok(None) => {
+                    // Still building.
+                    return;
+                }
+                Err(err) => {
+                    Log::err(format!("Failed to wait for build process: {:?}", err));
+                    self.process = None;
+                }
+            }
+        }
+
+        if self.need_rebuild {
+            self.need_rebuild = false;
+            self.rebuild();
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let mut process = std::process::Command::new("cargo");
+        process
+            .stderr(Stdio::piped())
+            .arg("build")
+            .arg("--package")
+            .arg(Self::PACKAGE);
+
+        if self.release {
+            process.arg("--release");
+        }
+
+        match process.spawn() {
+            Ok(mut process) => {
+                Log::info("Source code of the game has changed, rebuilding the game library...");
+
+                let stderr = process.stderr.take().unwrap();
+                std::thread::spawn(move || {
+                    for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                        Log::info(line);
+                    }
+                });
+
+                self.process = Some(process);
+            }
+            Err(e) => Log::err(format!("Failed to rebuild the game library: {:?}", e)),
+        }
+    }
+}
+
+impl Drop for GameLibraryBuilder {
+    fn drop(&mut self) {
+        if let Some(mut process) = self.process.take() {
+            Log::verify(process.kill());
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Collision layer selector for `InteractionGroups` fields.
+
+use crate::{gui::make_dropdown_list_option, inspector::EditorEnvironment};
+use fyrox::{
+    gui::{
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        inspector::{
+            editors::{
+                PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+                PropertyEditorMessageContext, PropertyEditorTranslationContext,
+            },
+            FieldKind, InspectorEnvironment, InspectorError, PropertyChanged,
+        },
+        message::{MessageDirection, UiMessage},
+        widget::WidgetBuilder,
+    },
+    scene::collider::{CollisionLayers, InteractionGroups},
+};
+use std::{any::TypeId, sync::Arc};
+
+fn collision_layers(environment: &Option<Arc<dyn InspectorEnvironment>>) -> CollisionLayers {
+    EditorEnvironment::try_get_from(environment)
+        .map(|environment| environment.collision_layers.clone())
+        .unwrap_or_default()
+}
+
+/// Shows interaction groups as a dropdown list with named collision layers. Groups that do not
+/// match any layer are shown as "Custom".
+#[derive(Debug)]
+pub struct CollisionGroupsPropertyEditorDefinition;
+
+impl PropertyEditorDefinition for CollisionGroupsPropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<InteractionGroups>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = ctx.property_info.cast_value::<InteractionGroups>()?;
+        let layers = collision_layers(&ctx.environment);
+        let items = layers
+            .layers()
+            .iter()
+            .map(|layer| layer.name.as_str())
+            .chain(["Custom"])
+            .map(|name| make_dropdown_list_option(ctx.build_context, name))
+            .collect();
+        Ok(PropertyEditorInstance::Simple {
+            editor: DropdownListBuilder::new(WidgetBuilder::new())
+                .with_items(items)
+                .with_selected(layers.find_layer(value).unwrap_or(layers.layers().len()))
+                .build(ctx.build_context),
+        })
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = ctx.property_info.cast_value::<InteractionGroups>()?;
+        let layers = collision_layers(&ctx.environment);
+        Ok(Some(DropdownListMessage::selection(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            Some(layers.find_layer(value).unwrap_or(layers.layers().len())),
+        )))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            if let Some(DropdownListMessage::SelectionChanged(Some(index))) = ctx.message.data() {
+                // "Custom" item has no groups, so it does not change anything.
+                if let Some(groups) = collision_layers(&ctx.environment).interaction_groups(*index)
+                {
+                    return Some(PropertyChanged {
+                        name: ctx.name.to_string(),
+                        owner_type_id: ctx.owner_type_id,
+                        value: FieldKind::object(groups),
+                    });
+                }
+            }
+        }
+        None
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     load_image,
+    localization::tr,
     message::MessageSender,
     scene::selector::{HierarchyNode, NodeSelectorMessage, NodeSelectorWindowBuilder},
     world::graph::item::SceneItem,
@@ -209,7 +210,7 @@ impl Control for HandlePropertyEditor {
             } else if message.destination == self.pick {
                 let node_selector = NodeSelectorWindowBuilder::new(
                     WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
-                        .with_title(WindowTitle::text("Select a Node"))
+                        .with_title(WindowTitle::text(tr("Select a Node")))
                         .open(false),
                 )
                 .build(&mut ui.build_ctx());
@@ -4,6 +4,7 @@ use crate::{
             AnimationContainerPropertyEditorDefinition, AnimationPropertyEditorDefinition,
             MachinePropertyEditorDefinition,
         },
+        collision_groups::CollisionGroupsPropertyEditorDefinition,
         font::FontPropertyEditorDefinition,
         handle::{EntityKind, NodeHandlePropertyEditorDefinition},
         material::MaterialPropertyEditorDefinition,
@@ -13,12 +14,14 @@ use crate::{
         surface::SurfaceDataPropertyEditorDefinition,
         texture::TexturePropertyEditorDefinition,
     },
+    localization::tr,
     message::MessageSender,
 };
 use fyrox::gui::font::FontResource;
 use fyrox::{
     asset::{manager::ResourceManager, Resource},
     core::{
+        curve::Curve,
         futures::executor::block_on,
         parking_lot::Mutex,
         pool::{ErasedHandle, Handle},
@@ -36,26 +39,38 @@ use fyrox::{
     },
     renderer::framework::state::PolygonFillMode,
     resource::{
+        audio_event::{AudioEvent, AudioEventSelection},
+        convex_decomposition::ConvexDecomposition,
         curve::{CurveResource, CurveResourceState},
-        model::{MaterialSearchOptions, Model, ModelResource},
+        model::{
+            AnimationClipImportOptions, AnimationCompression, LodImportOptions,
+            MaterialSearchOptions, Model, ModelResource, ModelUpAxis,
+        },
         texture::{
             CompressionOptions, MipFilter, TextureMagnificationFilter, TextureMinificationFilter,
             TextureResource, TextureWrapMode,
         },
+        visual_script::{BinaryOperation, Value, VisualNodeKind, VisualScript},
     },
     scene::{
-        animation::{absm::prelude::*, prelude::*},
+        animation::{
+            absm::prelude::*,
+            ik::{FabrikSolver, IkSolver, LookAtSolver, TwoBoneSolver},
+            prelude::*,
+        },
         base::{Base, LevelOfDetail, LodGroup, Mobility, Property, PropertyValue},
         camera::{
             ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection, Projection,
-            SkyBox,
+            SkyBox, ToneMapping,
         },
+        cloth::ClothPin,
         collider::{
-            BallShape, BitMask, CapsuleShape, ColliderShape, ConeShape, ConvexPolyhedronShape,
-            CuboidShape, CylinderShape, GeometrySource, HeightfieldShape, InteractionGroups,
-            SegmentShape, TriangleShape, TrimeshShape,
+            BallShape, BitMask, CapsuleShape, ColliderShape, ConeShape, ConvexDecompositionShape,
+            ConvexPolyhedronShape, CuboidShape, CylinderShape, GeometrySource, HeightfieldShape,
+            InteractionGroups, SegmentShape, TriangleShape, TrimeshShape,
         },
         dim2,
+        foliage::{FoliageLayer, ScatterRules},
         graph::physics::CoefficientCombineRule,
         joint::*,
         light::{
@@ -63,36 +78,50 @@ use fyrox::{
             BaseLight,
         },
         mesh::{
+            lod::{MeshLod, MeshLodMode},
             surface::{BlendShape, Surface, SurfaceSharedData},
             RenderPath,
         },
         node::Node,
         particle_system::{
             emitter::{
-                base::BaseEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter,
-                sphere::SphereEmitter, Emitter,
+                base::BaseEmitter,
+                cuboid::CuboidEmitter,
+                cylinder::CylinderEmitter,
+                mesh::{MeshEmissionMode, MeshEmitter},
+                sphere::SphereEmitter,
+                Emitter,
             },
-            ParticleSystemRng,
+            sub_emitter::{SubEmitter, SubEmitterEvent},
+            GpuParticleSettings, ParticleCollision, ParticleSimulationMode, ParticleSystemRng,
         },
         ragdoll::Limb,
         rigidbody::RigidBodyType,
+        sky::SkyClouds,
         sound::{
             self,
+            compressor::Compressor,
+            equalizer::Equalizer,
             filter::{
                 AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect,
                 HighShelfFilterEffect, LowPassFilterEffect, LowShelfFilterEffect,
             },
             reverb::Reverb,
-            Attenuate, AudioBus, Biquad, DistanceModel, Effect, SoundBuffer, SoundBufferResource,
-            Status,
+            reverb_zone::ReverbZoneShape,
+            Attenuate, AudioBus, AudioBusSend, Biquad, DistanceModel, Effect, SoundBuffer,
+            SoundBufferResource, SoundOcclusion, Status,
         },
-        terrain::{Chunk, Layer},
+        spline::SplinePoint,
+        terrain::{Chunk, Layer, TerrainStreaming},
+        trail::{TrailAlignment, TrailTextureMode},
         transform::Transform,
     },
+    utils::navmesh::{bake::NavmeshBakeSettings, OffMeshLink},
 };
 use std::{path::Path, sync::Arc};
 
 pub mod animation;
+pub mod collision_groups;
 pub mod font;
 pub mod handle;
 pub mod material;
@@ -122,7 +151,9 @@ pub fn make_status_enum_editor_definition() -> EnumPropertyEditorDefinition<Stat
 }
 
 pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorDefinitionContainer {
-    let container = PropertyEditorDefinitionContainer::new();
+    let mut container = PropertyEditorDefinitionContainer::new();
+
+    container.property_name_translator = Some(tr);
 
     container.insert(TexturePropertyEditorDefinition { untyped: false });
     container.insert(TexturePropertyEditorDefinition { untyped: true });
@@ -153,8 +184,13 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.register_inheritable_vec_collection::<Layer>();
     container.register_inheritable_inspectable::<Layer>();
+    container.register_inheritable_inspectable::<TerrainStreaming>();
 
     container.register_inheritable_vec_collection::<Emitter>();
+    container.register_inheritable_vec_collection::<SubEmitter>();
+
+    container.register_inheritable_vec_collection::<MeshLod>();
+    container.register_inheritable_inspectable::<MeshLod>();
 
     container.register_inheritable_vec_collection::<LevelOfDetail>();
     container.register_inheritable_inspectable::<LevelOfDetail>();
@@ -168,6 +204,13 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_vec_collection::<GeometrySource>();
     container.register_inheritable_inspectable::<GeometrySource>();
 
+    container.register_inheritable_inspectable::<SoundOcclusion>();
+
+    container.register_inheritable_inspectable::<NavmeshBakeSettings>();
+
+    container.register_inheritable_vec_collection::<OffMeshLink>();
+    container.register_inheritable_inspectable::<OffMeshLink>();
+
     container.insert(make_status_enum_editor_definition());
 
     container.insert(EnumPropertyEditorDefinition::<LodGroup>::new_optional());
@@ -224,6 +267,30 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InheritablePropertyEditorDefinition::<Option<CurveResource>>::new());
     container.register_inheritable_vec_collection::<Option<CurveResource>>();
 
+    container.insert(
+        ResourceFieldPropertyEditorDefinition::<ConvexDecomposition>::new(
+            Arc::new(Mutex::new(
+                |resource_manager: &ResourceManager, path: &Path| {
+                    resource_manager
+                        .try_request::<ConvexDecomposition>(path)
+                        .map(block_on)
+                },
+            )),
+            sender.clone(),
+        ),
+    );
+
+    container.insert(ResourceFieldPropertyEditorDefinition::<VisualScript>::new(
+        Arc::new(Mutex::new(
+            |resource_manager: &ResourceManager, path: &Path| {
+                resource_manager
+                    .try_request::<VisualScript>(path)
+                    .map(block_on)
+            },
+        )),
+        sender.clone(),
+    ));
+
     container.insert(ResourceFieldPropertyEditorDefinition::<UserInterface>::new(
         Arc::new(Mutex::new(
             |resource_manager: &ResourceManager, path: &Path| {
@@ -251,7 +318,8 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_vec_collection::<Option<ShaderResource>>();
 
     container.register_inheritable_inspectable::<ColorGradingLut>();
-    container.register_inheritable_inspectable::<InteractionGroups>();
+    container.insert(CollisionGroupsPropertyEditorDefinition);
+    container.insert(InheritablePropertyEditorDefinition::<InteractionGroups>::new());
 
     container.register_inheritable_enum::<JointParams, _>();
     container.register_inheritable_enum::<dim2::joint::JointParams, _>();
@@ -262,8 +330,25 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<dim2::joint::FixedJoint>();
     container.register_inheritable_inspectable::<RevoluteJoint>();
     container.register_inheritable_inspectable::<PrismaticJoint>();
+    container.register_inheritable_inspectable::<JointMotor>();
+
+    container.register_inheritable_vec_collection::<ClothPin>();
+    container.register_inheritable_inspectable::<ClothPin>();
+    container.register_inheritable_vec_collection::<FoliageLayer>();
+    container.register_inheritable_inspectable::<FoliageLayer>();
+    container.register_inheritable_inspectable::<ScatterRules>();
+    container.register_inheritable_vec_collection::<SplinePoint>();
+    container.register_inheritable_inspectable::<SplinePoint>();
+    container.register_inheritable_inspectable::<SkyClouds>();
+    container.register_inheritable_enum::<TrailTextureMode, _>();
+    container.register_inheritable_enum::<TrailAlignment, _>();
     container.register_inheritable_inspectable::<dim2::joint::PrismaticJoint>();
 
+    container.register_inheritable_enum::<IkSolver, _>();
+    container.register_inheritable_inspectable::<TwoBoneSolver>();
+    container.register_inheritable_inspectable::<FabrikSolver>();
+    container.register_inheritable_inspectable::<LookAtSolver>();
+
     container.register_inheritable_inspectable::<Base>();
     container.register_inheritable_inspectable::<BaseLight>();
 
@@ -278,6 +363,13 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InspectablePropertyEditorDefinition::<LowShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<HighShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<Reverb>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Equalizer>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Compressor>::new());
+
+    container.insert(InspectablePropertyEditorDefinition::<AudioBusSend>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<AudioBusSend>::new());
+    container.insert(InspectablePropertyEditorDefinition::<AudioEvent>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<AudioEvent>::new());
 
     container.register_inheritable_enum::<Emitter, _>();
 
@@ -287,6 +379,13 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<SphereEmitter>();
     container.register_inheritable_inspectable::<CylinderEmitter>();
     container.register_inheritable_inspectable::<CuboidEmitter>();
+    container.register_inheritable_inspectable::<MeshEmitter>();
+    container.register_inheritable_enum::<MeshEmissionMode, _>();
+    container.register_inheritable_enum::<ParticleSimulationMode, _>();
+    container.register_inheritable_inspectable::<GpuParticleSettings>();
+    container.register_inheritable_inspectable::<ParticleCollision>();
+    container.register_inheritable_inspectable::<SubEmitter>();
+    container.register_inheritable_enum::<SubEmitterEvent, _>();
     container.register_inheritable_inspectable::<PerspectiveProjection>();
     container.register_inheritable_inspectable::<OrthographicProjection>();
     container.register_inheritable_inspectable::<Transform>();
@@ -301,6 +400,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_option::<ColorGradingLut>();
     container.register_inheritable_option::<Biquad>();
     container.register_inheritable_option::<SkyBox>();
+    container.register_inheritable_option::<Curve>();
 
     container.register_inheritable_inspectable::<SkyBox>();
 
@@ -316,11 +416,18 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_enum::<Mobility, _>();
     container.register_inheritable_enum::<RigidBodyType, _>();
     container.register_inheritable_enum::<Exposure, _>();
+    container.register_inheritable_enum::<ToneMapping, _>();
     container.register_inheritable_enum::<FrustumSplitOptions, _>();
     container.register_inheritable_enum::<MaterialSearchOptions, _>();
     container.register_inheritable_enum::<DistanceModel, _>();
     container.register_inheritable_enum::<sound::Renderer, _>();
+    container.register_inheritable_enum::<ReverbZoneShape, _>();
+    container.register_inheritable_enum::<AudioEventSelection, _>();
+    container.register_inheritable_enum::<VisualNodeKind, _>();
+    container.register_inheritable_enum::<Value, _>();
+    container.register_inheritable_enum::<BinaryOperation, _>();
     container.register_inheritable_enum::<RenderPath, _>();
+    container.register_inheritable_enum::<MeshLodMode, _>();
 
     container.insert(ScriptPropertyEditorDefinition {});
     container.insert(BitFieldPropertyEditorDefinition::<BitMask>::new());
@@ -342,6 +449,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<HeightfieldShape>();
     container.register_inheritable_inspectable::<dim2::collider::HeightfieldShape>();
     container.register_inheritable_inspectable::<ConvexPolyhedronShape>();
+    container.register_inheritable_inspectable::<ConvexDecompositionShape>();
     container.insert(SpriteSheetFramesContainerEditorDefinition);
 
     container.insert(SurfaceDataPropertyEditorDefinition);
@@ -386,6 +494,16 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(EnumPropertyEditorDefinition::<PolygonFillMode>::new());
 
     container.insert(EnumPropertyEditorDefinition::<MipFilter>::new());
+    container.insert(EnumPropertyEditorDefinition::<ModelUpAxis>::new());
+    container.insert(EnumPropertyEditorDefinition::<AnimationCompression>::new());
+    container.insert(InspectablePropertyEditorDefinition::<
+        AnimationClipImportOptions,
+    >::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<
+        AnimationClipImportOptions,
+    >::new());
+    container.insert(InspectablePropertyEditorDefinition::<LodImportOptions>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<LodImportOptions>::new());
 
     container.register_inheritable_inspectable::<Limb>();
     container.insert(VecCollectionPropertyEditorDefinition::<Limb>::new());
@@ -7,6 +7,7 @@ use fyrox::{
     asset::{manager::ResourceManager, state::LoadError, Resource, TypedResourceData},
     core::{
         color::Color,
+        log::Log,
         make_relative_path,
         parking_lot::Mutex,
         pool::Handle,
@@ -44,6 +45,27 @@ use std::{
     sync::Arc,
 };
 
+/// Checks whether an asset at the given path could be assigned to a property with the given list of
+/// allowed extensions (see `#[reflect(extensions = "..")]` attribute). Empty list allows any asset.
+pub fn is_extension_allowed(path: &Path, extensions: &[String]) -> bool {
+    let allowed = extensions.is_empty()
+        || path.extension().map_or(false, |ext| {
+            extensions
+                .iter()
+                .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+        });
+
+    if !allowed {
+        Log::warn(format!(
+            "{} cannot be assigned to the property, only {} assets are allowed.",
+            path.display(),
+            extensions.join(", ")
+        ));
+    }
+
+    allowed
+}
+
 fn resource_path<T>(resource: &Option<Resource<T>>) -> String
 where
     T: TypedResourceData,
@@ -117,6 +139,9 @@ where
     #[visit(skip)]
     #[reflect(hidden)]
     sender: MessageSender,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    extensions: Vec<String>,
 }
 
 impl<T> Debug for ResourceField<T>
@@ -141,6 +166,7 @@ where
             loader: self.loader.clone(),
             locate: self.locate,
             sender: self.sender.clone(),
+            extensions: self.extensions.clone(),
         }
     }
 }
@@ -204,7 +230,10 @@ where
                         make_relative_path(&item.path)
                     };
 
-                    if let Ok(path) = path {
+                    if let Some(path) = path
+                        .ok()
+                        .filter(|path| is_extension_allowed(path, &self.extensions))
+                    {
                         if let Some(Ok(value)) =
                             (self.loader.lock())(&self.resource_manager, path.as_path())
                         {
@@ -252,6 +281,7 @@ where
     resource: Option<Resource<T>>,
     loader: ResourceLoaderCallback<T>,
     sender: MessageSender,
+    extensions: Vec<String>,
 }
 
 impl<T> ResourceFieldBuilder<T>
@@ -268,6 +298,7 @@ where
             resource: None,
             loader,
             sender,
+            extensions: Default::default(),
         }
     }
 
@@ -276,6 +307,12 @@ where
         self
     }
 
+    /// Sets a list of extensions of assets, that could be dropped on the field. Empty list allows any asset.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
     pub fn build(
         self,
         ctx: &mut BuildContext,
@@ -339,6 +376,7 @@ where
             loader: self.loader,
             locate,
             sender: self.sender,
+            extensions: self.extensions,
         };
 
         ctx.add_node(UiNode::new(field))
@@ -392,6 +430,13 @@ where
                 self.sender.clone(),
             )
             .with_resource(value.clone())
+            .with_extensions(
+                ctx.property_info
+                    .extensions
+                    .iter()
+                    .map(|ext| ext.to_string())
+                    .collect(),
+            )
             .build(
                 ctx.build_context,
                 ctx.environment
@@ -269,7 +269,8 @@ impl PropertyEditorDefinition for ScriptPropertyEditorDefinition {
         let editor;
         let container = make_expander_container(
             ctx.layer_index,
-            ctx.property_info.display_name,
+            &ctx.definition_container
+                .property_display_name(ctx.property_info.display_name),
             ctx.property_info.description,
             variant_selector,
             {
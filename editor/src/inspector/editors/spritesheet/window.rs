@@ -1,4 +1,5 @@
 use crate::inspector::editors::spritesheet::SpriteSheetFramesPropertyEditorMessage;
+use crate::localization::window_title;
 use fyrox::graph::SceneGraph;
 use fyrox::{
     core::{
@@ -19,7 +20,7 @@ use fyrox::{
         text::TextBuilder,
         utils::make_simple_tooltip,
         widget::{Widget, WidgetBuilder, WidgetMessage},
-        window::{Window, WindowBuilder, WindowMessage, WindowTitle},
+        window::{Window, WindowBuilder, WindowMessage},
         BuildContext, Control, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
         VerticalAlignment,
     },
@@ -382,7 +383,7 @@ impl SpriteSheetFramesEditorWindow {
                 )
                 .open(false)
                 .can_minimize(false)
-                .with_title(WindowTitle::text("Sprite Sheet Frames Editor"))
+                .with_title(window_title("Sprite Sheet Frames Editor", ctx))
                 .build_window(ctx),
             animation: SpriteSheetAnimation::with_container(container),
             editor,
@@ -1,4 +1,7 @@
-use crate::{asset::item::AssetItem, inspector::EditorEnvironment};
+use crate::{
+    asset::item::AssetItem,
+    inspector::{editors::resource::is_extension_allowed, EditorEnvironment},
+};
 use fyrox::graph::SceneGraph;
 use fyrox::{
     asset::manager::ResourceManager,
@@ -37,6 +40,9 @@ pub struct TextureEditor {
     #[reflect(hidden)]
     resource_manager: ResourceManager,
     texture: Option<TextureResource>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    extensions: Vec<String>,
 }
 
 impl Debug for TextureEditor {
@@ -78,11 +84,13 @@ impl Control for TextureEditor {
             if message.destination() == self.image {
                 if let Some(item) = ui.node(*dropped).cast::<AssetItem>() {
                     if let Ok(relative_path) = make_relative_path(&item.path) {
-                        ui.send_message(TextureEditorMessage::texture(
-                            self.handle(),
-                            MessageDirection::ToWidget,
-                            Some(self.resource_manager.request::<Texture>(relative_path)),
-                        ));
+                        if is_extension_allowed(&relative_path, &self.extensions) {
+                            ui.send_message(TextureEditorMessage::texture(
+                                self.handle(),
+                                MessageDirection::ToWidget,
+                                Some(self.resource_manager.request::<Texture>(relative_path)),
+                            ));
+                        }
                     }
                 }
             }
@@ -107,6 +115,7 @@ impl Control for TextureEditor {
 pub struct TextureEditorBuilder {
     widget_builder: WidgetBuilder,
     texture: Option<TextureResource>,
+    extensions: Vec<String>,
 }
 
 impl TextureEditorBuilder {
@@ -114,6 +123,7 @@ impl TextureEditorBuilder {
         Self {
             widget_builder,
             texture: None,
+            extensions: Default::default(),
         }
     }
 
@@ -122,6 +132,13 @@ impl TextureEditorBuilder {
         self
     }
 
+    /// Sets a list of extensions of textures, that could be dropped on the editor. Empty list allows any
+    /// texture.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
     pub fn build(
         self,
         ctx: &mut BuildContext,
@@ -148,6 +165,7 @@ impl TextureEditorBuilder {
             image,
             resource_manager,
             texture: None,
+            extensions: self.extensions,
         };
 
         ctx.add_node(UiNode::new(editor))
@@ -191,6 +209,13 @@ impl PropertyEditorDefinition for TexturePropertyEditorDefinition {
                 WidgetBuilder::new().with_min_size(Vector2::new(0.0, 17.0)),
             )
             .with_texture(value.clone())
+            .with_extensions(
+                ctx.property_info
+                    .extensions
+                    .iter()
+                    .map(|ext| ext.to_string())
+                    .collect(),
+            )
             .build(
                 ctx.build_context,
                 ctx.environment
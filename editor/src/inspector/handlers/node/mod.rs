@@ -7,7 +7,7 @@ use crate::{
     GameSceneCommand,
 };
 use fyrox::{
-    core::pool::Handle,
+    core::{log::Log, pool::Handle},
     gui::inspector::{CollectionChanged, FieldKind, PropertyChanged},
     scene::{node::Node, terrain::Terrain},
 };
@@ -16,22 +16,30 @@ use std::any::TypeId;
 pub struct SceneNodePropertyChangedHandler;
 
 impl SceneNodePropertyChangedHandler {
+    /// Returns `Some` if the property change is handled in a special way. The inner option is
+    /// `None` if the change is not supported.
     fn try_get_command(
         &self,
         args: &PropertyChanged,
         handle: Handle<Node>,
         _node: &mut Node,
-    ) -> Option<GameSceneCommand> {
+    ) -> Option<Option<GameSceneCommand>> {
         // Terrain is special and have its own commands for specific properties.
         if args.path() == Terrain::LAYERS && args.owner_type_id == TypeId::of::<Terrain>() {
             match args.value {
                 FieldKind::Collection(ref collection_changed) => match **collection_changed {
-                    CollectionChanged::Add(_) => {
-                        Some(GameSceneCommand::new(AddTerrainLayerCommand::new(handle)))
-                    }
-                    CollectionChanged::Remove(index) => Some(GameSceneCommand::new(
+                    CollectionChanged::Add(_) => Some(Some(GameSceneCommand::new(
+                        AddTerrainLayerCommand::new(handle),
+                    ))),
+                    CollectionChanged::Remove(index) => Some(Some(GameSceneCommand::new(
                         DeleteTerrainLayerCommand::new(handle, index),
-                    )),
+                    ))),
+                    // Layers are bound to the masks of terrain chunks, inserting or moving them
+                    // in the middle of the list would mix up the masks.
+                    CollectionChanged::Insert { .. } | CollectionChanged::Move { .. } => {
+                        Log::warn("Inserting or reordering terrain layers is not supported!");
+                        Some(None)
+                    }
                     CollectionChanged::ItemChanged { .. } => None,
                 },
                 _ => None,
@@ -49,20 +57,22 @@ impl SceneNodePropertyChangedHandler {
         handle: Handle<Node>,
         node: &mut Node,
     ) -> Option<GameSceneCommand> {
-        self.try_get_command(args, handle, node).or_else(|| {
-            if args.is_inheritable() {
-                // Prevent reverting property value if there's no parent resource.
-                if node.resource().is_some() {
-                    Some(GameSceneCommand::new(RevertSceneNodePropertyCommand::new(
-                        args.path(),
-                        handle,
-                    )))
-                } else {
-                    None
-                }
+        if let Some(command) = self.try_get_command(args, handle, node) {
+            return command;
+        }
+
+        if args.is_inheritable() {
+            // Prevent reverting property value if there's no parent resource.
+            if node.resource().is_some() {
+                Some(GameSceneCommand::new(RevertSceneNodePropertyCommand::new(
+                    args.path(),
+                    handle,
+                )))
             } else {
-                make_set_node_property_command(handle, args)
+                None
             }
-        })
+        } else {
+            make_set_node_property_command(handle, args)
+        }
     }
 }
@@ -2,9 +2,11 @@ use crate::{
     gui::make_image_button_with_tooltip,
     inspector::editors::make_property_editors_container,
     load_image,
+    localization::window_title,
     message::MessageSender,
     scene::{controller::SceneController, GameScene, Selection},
     send_sync_message,
+    settings::Settings,
     utils::window_content,
     Brush, Engine, Message, Mode, WidgetMessage, WrapMode, MSG_SYNC_FLAG,
 };
@@ -29,11 +31,12 @@ use fyrox::{
         scroll_viewer::ScrollViewerBuilder,
         text::{TextBuilder, TextMessage},
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowTitle},
+        window::WindowBuilder,
         BuildContext, Thickness, UiNode, UserInterface,
     },
     scene::animation::prelude::*,
     scene::animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+    scene::collider::CollisionLayers,
 };
 use std::{any::Any, sync::Arc};
 
@@ -52,6 +55,9 @@ pub struct EditorEnvironment {
     /// List of animations definitions (name + handle). It is filled only if current selection
     /// is `AnimationBlendingStateMachine`. The list is filled using ABSM's animation player.
     pub available_animations: Vec<AnimationDefinition>,
+    /// Collision layers of the project, they're used to show interaction groups of colliders
+    /// as named layers.
+    pub collision_layers: CollisionLayers,
     pub sender: MessageSender,
 }
 
@@ -164,7 +170,7 @@ impl Inspector {
         let inspector;
         let docs_button;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("Inspector"))
-            .with_title(WindowTitle::text("Inspector"))
+            .with_title(window_title("Inspector", ctx))
             .with_content(
                 GridBuilder::new(
                     WidgetBuilder::new()
@@ -284,12 +290,14 @@ impl Inspector {
         resource_manager: ResourceManager,
         serialization_context: Arc<SerializationContext>,
         available_animations: &[AnimationDefinition],
+        collision_layers: &CollisionLayers,
         sender: &MessageSender,
     ) {
         let environment = Arc::new(EditorEnvironment {
             resource_manager,
             serialization_context,
             available_animations: available_animations.to_vec(),
+            collision_layers: collision_layers.clone(),
             sender: sender.clone(),
         });
 
@@ -328,6 +336,7 @@ impl Inspector {
         editor_selection: &Selection,
         controller: &dyn SceneController,
         engine: &mut Engine,
+        settings: &Settings,
         sender: &MessageSender,
     ) {
         if let Message::SelectionChanged { .. } = message {
@@ -349,6 +358,7 @@ impl Inspector {
                         engine.resource_manager.clone(),
                         engine.serialization_context.clone(),
                         &available_animations,
+                        &settings.physics.collision_layers,
                         sender,
                     )
                 });
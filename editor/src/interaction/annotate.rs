@@ -0,0 +1,106 @@
+//! Annotate mode allows to place notes in a scene by clicking on a surface. Placed notes are shown
+//! as editable labels over the scene preview frame, clearing the text of a note removes it.
+
+use crate::{
+    interaction::{make_interaction_mode_button, measure::pick_world_point, InteractionMode},
+    message::MessageSender,
+    scene::{
+        annotation::Annotation, commands::annotation::AddAnnotationCommand,
+        controller::SceneController, GameScene, Selection,
+    },
+    settings::Settings,
+};
+use fyrox::{
+    core::{
+        algebra::Vector2,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        TypeUuidProvider,
+    },
+    engine::Engine,
+    gui::{BuildContext, UiNode},
+};
+
+pub struct AnnotateInteractionMode {
+    message_sender: MessageSender,
+}
+
+impl AnnotateInteractionMode {
+    pub fn new(message_sender: MessageSender) -> Self {
+        Self { message_sender }
+    }
+}
+
+impl TypeUuidProvider for AnnotateInteractionMode {
+    fn type_uuid() -> Uuid {
+        uuid!("9b3e6a21-7c4f-4e0d-b8a5-52d7e1c0f6b4")
+    }
+}
+
+impl InteractionMode for AnnotateInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        if let Some(position) =
+            pick_world_point(game_scene, engine, mouse_pos, frame_size, settings)
+        {
+            self.message_sender
+                .do_scene_command(AddAnnotationCommand::new(Annotation {
+                    position,
+                    text: "Note".to_string(),
+                }));
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        _editor_selection: &Selection,
+        _controller: &mut dyn SceneController,
+        _engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        _mouse_position: Vector2<f32>,
+        _editor_selection: &Selection,
+        _controller: &mut dyn SceneController,
+        _engine: &mut Engine,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
+    fn deactivate(&mut self, _controller: &dyn SceneController, _engine: &mut Engine) {}
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        let annotate_mode_tooltip = "Annotate\n\nAnnotate mode allows you to place notes in \
+        the scene by clicking on a surface. Notes are stored next to the scene file and could be \
+        edited directly in the viewport, clear the text of a note to remove it.";
+
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../resources/doc.png"),
+            annotate_mode_tooltip,
+            selected,
+        )
+    }
+
+    fn uuid(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
@@ -0,0 +1,374 @@
+//! Foliage paint mode allows to paint instances of the selected foliage onto terrains and meshes
+//! of the scene.
+
+use crate::{
+    interaction::{make_interaction_mode_button, InteractionMode},
+    localization::window_title,
+    message::MessageSender,
+    scene::{
+        commands::foliage::ModifyFoliageInstancesCommand, controller::SceneController, GameScene,
+        Selection,
+    },
+    settings::Settings,
+    MSG_SYNC_FLAG,
+};
+use fyrox::graph::SceneGraph;
+use fyrox::{
+    core::{
+        algebra::{Vector2, Vector3},
+        color::Color,
+        log::{Log, MessageKind},
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        TypeUuidProvider,
+    },
+    engine::Engine,
+    gui::{
+        inspector::{
+            editors::PropertyEditorDefinitionContainer, Inspector, InspectorBuilder,
+            InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    scene::{
+        camera::Camera,
+        debug::{Line, SceneDrawingContext},
+        foliage::{self, Foliage, FoliageBrush, FoliageInstance},
+        graph::Graph,
+        node::Node,
+    },
+};
+use std::sync::Arc;
+
+/// Height above the brush center from which the surfaces are probed when painting.
+const PROBE_HEIGHT: f32 = 100.0;
+
+struct BrushPanel {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+}
+
+impl BrushPanel {
+    fn new(ctx: &mut BuildContext, brush: &FoliageBrush) -> Self {
+        let context = InspectorContext::from_object(
+            brush,
+            ctx,
+            Arc::new(PropertyEditorDefinitionContainer::new()),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+
+        let inspector;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(150.0))
+            .can_minimize(false)
+            .can_maximize(false)
+            .with_content({
+                inspector = InspectorBuilder::new(WidgetBuilder::new())
+                    .with_context(context)
+                    .build(ctx);
+                inspector
+            })
+            .open(false)
+            .with_title(window_title("Foliage Brush Options", ctx))
+            .build(ctx);
+
+        Self { window, inspector }
+    }
+
+    fn sync_to_model(&self, ui: &mut UserInterface, brush: &FoliageBrush) {
+        let ctx = ui
+            .node(self.inspector)
+            .cast::<Inspector>()
+            .expect("Must be Inspector!")
+            .context()
+            .clone();
+
+        if let Err(e) = ctx.sync(brush, ui, 0, true, Default::default()) {
+            Log::writeln(
+                MessageKind::Error,
+                format!(
+                    "Failed to sync foliage BrushPanel's inspector. Reason: {:?}",
+                    e
+                ),
+            )
+        }
+    }
+
+    fn handle_ui_message(&self, message: &UiMessage, brush: &mut FoliageBrush) {
+        if message.destination() == self.inspector
+            && message.direction() == MessageDirection::FromWidget
+        {
+            if let Some(InspectorMessage::PropertyChanged(msg)) = message.data::<InspectorMessage>()
+            {
+                PropertyAction::from_field_kind(&msg.value).apply(
+                    &msg.path(),
+                    brush,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        }
+    }
+}
+
+pub struct FoliageInteractionMode {
+    message_sender: MessageSender,
+    brush: FoliageBrush,
+    brush_panel: BrushPanel,
+    brush_center: Option<Vector3<f32>>,
+    /// Instances of every layer of the foliage at the beginning of the current stroke.
+    initial_instances: Option<Vec<Vec<FoliageInstance>>>,
+    scene_viewer_frame: Handle<UiNode>,
+}
+
+impl FoliageInteractionMode {
+    pub fn new(
+        engine: &mut Engine,
+        message_sender: MessageSender,
+        scene_viewer_frame: Handle<UiNode>,
+    ) -> Self {
+        let brush = FoliageBrush::default();
+        let brush_panel = BrushPanel::new(&mut engine.user_interface.build_ctx(), &brush);
+
+        Self {
+            message_sender,
+            brush,
+            brush_panel,
+            brush_center: None,
+            initial_instances: None,
+            scene_viewer_frame,
+        }
+    }
+}
+
+impl TypeUuidProvider for FoliageInteractionMode {
+    fn type_uuid() -> Uuid {
+        uuid!("71ac259e-8cae-4fbf-a7e4-aa90fef7baa9")
+    }
+}
+
+fn selected_foliage(editor_selection: &Selection, graph: &Graph) -> Option<Handle<Node>> {
+    if let Selection::Graph(selection) = editor_selection {
+        if selection.is_single_selection() {
+            let handle = selection.nodes()[0];
+            if graph.try_get_of_type::<Foliage>(handle).is_some() {
+                return Some(handle);
+            }
+        }
+    }
+    None
+}
+
+fn copy_instances(foliage: &Foliage) -> Vec<Vec<FoliageInstance>> {
+    foliage
+        .layers()
+        .iter()
+        .map(|layer| layer.instances().to_vec())
+        .collect()
+}
+
+fn draw_brush(ctx: &mut SceneDrawingContext, center: Vector3<f32>, radius: f32, color: Color) {
+    const SEGMENTS: usize = 32;
+    let point = |i: usize| {
+        let angle = i as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+        center + Vector3::new(angle.cos() * radius, 0.0, angle.sin() * radius)
+    };
+    for i in 0..SEGMENTS {
+        ctx.add_line(Line {
+            begin: point(i),
+            end: point(i + 1),
+            color,
+        });
+    }
+}
+
+impl InteractionMode for FoliageInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        let Some(handle) = selected_foliage(editor_selection, graph) else {
+            return;
+        };
+
+        if let Some(foliage) = graph.try_get_of_type::<Foliage>(handle) {
+            self.initial_instances = Some(copy_instances(foliage));
+        }
+
+        if let Some(center) = self.brush_center {
+            foliage::paint_foliage(graph, handle, &self.brush, center, PROBE_HEIGHT);
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let Some(old_instances) = self.initial_instances.take() else {
+            return;
+        };
+
+        let graph = &engine.scenes[game_scene.scene].graph;
+        let Some(handle) = selected_foliage(editor_selection, graph) else {
+            return;
+        };
+
+        if let Some(foliage) = graph.try_get_of_type::<Foliage>(handle) {
+            let new_instances = copy_instances(foliage);
+            if new_instances != old_instances {
+                self.message_sender
+                    .do_scene_command(ModifyFoliageInstancesCommand::new(
+                        handle,
+                        old_instances,
+                        new_instances,
+                    ));
+            }
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        let Some(handle) = selected_foliage(editor_selection, graph) else {
+            return;
+        };
+
+        let Some(ray) = graph
+            .try_get_of_type::<Camera>(game_scene.camera_controller.camera)
+            .map(|camera| camera.make_ray(mouse_position, frame_size))
+        else {
+            return;
+        };
+
+        self.brush_center = foliage::probe_graph(graph, &ray, handle).map(|p| p.position);
+
+        if let (Some(center), Some(_)) = (self.brush_center, self.initial_instances.as_ref()) {
+            foliage::paint_foliage(graph, handle, &self.brush, center, PROBE_HEIGHT);
+        }
+    }
+
+    fn update(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+        if selected_foliage(editor_selection, &scene.graph).is_none() {
+            return;
+        }
+
+        if let Some(center) = self.brush_center {
+            let color = if self.brush.erase {
+                Color::RED
+            } else {
+                Color::GREEN
+            };
+            draw_brush(&mut scene.drawing_context, center, self.brush.radius, color);
+        }
+    }
+
+    fn activate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        self.brush_panel
+            .sync_to_model(&mut engine.user_interface, &self.brush);
+
+        engine
+            .user_interface
+            .send_message(WindowMessage::open_and_align(
+                self.brush_panel.window,
+                MessageDirection::ToWidget,
+                self.scene_viewer_frame,
+                HorizontalAlignment::Right,
+                VerticalAlignment::Top,
+                Thickness::top_right(5.0),
+                false,
+            ));
+    }
+
+    fn deactivate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        self.brush_center = None;
+        self.initial_instances = None;
+
+        engine.user_interface.send_message(WindowMessage::close(
+            self.brush_panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        _editor_selection: &Selection,
+        _controller: &mut dyn SceneController,
+        _engine: &mut Engine,
+    ) {
+        self.brush_panel.handle_ui_message(message, &mut self.brush);
+    }
+
+    fn on_drop(&mut self, engine: &mut Engine) {
+        engine.user_interface.send_message(WidgetMessage::remove(
+            self.brush_panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        let foliage_mode_tooltip = "Paint Foliage\n\nFoliage paint mode allows you to paint \
+        instances of the selected foliage onto terrains and meshes. Enable \"erase\" option of \
+        the brush to remove instances.";
+
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../resources/model.png"),
+            foliage_mode_tooltip,
+            selected,
+        )
+    }
+
+    fn uuid(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
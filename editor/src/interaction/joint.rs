@@ -0,0 +1,540 @@
+//! Joint edit mode allows to modify limits of the selected joint directly in the viewport, by dragging
+//! handles at the ends of the limit arcs (or segments for linear limits).
+
+use crate::{
+    interaction::{
+        calculate_gizmo_distance_scaling, make_interaction_mode_button, InteractionMode,
+    },
+    message::MessageSender,
+    scene::{commands::SetPropertyCommand, controller::SceneController, GameScene, Selection},
+    settings::Settings,
+};
+use fyrox::{
+    core::{
+        algebra::{Vector2, Vector3},
+        color::Color,
+        math::{plane::Plane, ray::Ray},
+        pool::Handle,
+        reflect::Reflect,
+        uuid::{uuid, Uuid},
+        TypeUuidProvider,
+    },
+    engine::Engine,
+    gui::{BuildContext, UiNode},
+    scene::{
+        camera::Camera,
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        joint::{Joint, JointParams},
+        node::Node,
+    },
+};
+use std::ops::Range;
+
+/// Radius of limit arcs (in gizmo scale units).
+const ARC_RADIUS: f32 = 1.5;
+/// Radius of limit handles (in gizmo scale units).
+const HANDLE_RADIUS: f32 = 0.12;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum LimitEnd {
+    Start,
+    End,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum LimitKind {
+    /// Rotation around the given axis, angles are measured from the reference vector.
+    Angular { axis: usize, reference: usize },
+    /// Translation along the given axis.
+    Linear { axis: usize },
+}
+
+struct LimitGizmo {
+    /// Path to the limits property of the joint node.
+    path: &'static str,
+    enabled: bool,
+    range: Range<f32>,
+    kind: LimitKind,
+    color: Color,
+}
+
+fn joint_limits(params: &JointParams) -> Vec<LimitGizmo> {
+    match params {
+        JointParams::BallJoint(v) => vec![
+            LimitGizmo {
+                path: "params.BallJoint@0.x_limits_angles",
+                enabled: v.x_limits_enabled,
+                range: v.x_limits_angles.clone(),
+                kind: LimitKind::Angular {
+                    axis: 0,
+                    reference: 1,
+                },
+                color: Color::RED,
+            },
+            LimitGizmo {
+                path: "params.BallJoint@0.y_limits_angles",
+                enabled: v.y_limits_enabled,
+                range: v.y_limits_angles.clone(),
+                kind: LimitKind::Angular {
+                    axis: 1,
+                    reference: 2,
+                },
+                color: Color::GREEN,
+            },
+            LimitGizmo {
+                path: "params.BallJoint@0.z_limits_angles",
+                enabled: v.z_limits_enabled,
+                range: v.z_limits_angles.clone(),
+                kind: LimitKind::Angular {
+                    axis: 2,
+                    reference: 0,
+                },
+                color: Color::BLUE,
+            },
+        ],
+        JointParams::FixedJoint(_) => vec![],
+        JointParams::PrismaticJoint(v) => vec![LimitGizmo {
+            path: "params.PrismaticJoint@0.limits",
+            enabled: v.limits_enabled,
+            range: v.limits.clone(),
+            kind: LimitKind::Linear { axis: 0 },
+            color: Color::RED,
+        }],
+        JointParams::RevoluteJoint(v) => vec![LimitGizmo {
+            path: "params.RevoluteJoint@0.limits",
+            enabled: v.limits_enabled,
+            range: v.limits.clone(),
+            kind: LimitKind::Angular {
+                axis: 0,
+                reference: 1,
+            },
+            color: Color::RED,
+        }],
+    }
+}
+
+fn set_joint_limits(params: &mut JointParams, index: usize, range: Range<f32>) {
+    match params {
+        JointParams::BallJoint(v) => match index {
+            0 => v.x_limits_angles = range,
+            1 => v.y_limits_angles = range,
+            2 => v.z_limits_angles = range,
+            _ => (),
+        },
+        JointParams::FixedJoint(_) => (),
+        JointParams::PrismaticJoint(v) => v.limits = range,
+        JointParams::RevoluteJoint(v) => v.limits = range,
+    }
+}
+
+/// World-space frame of a joint.
+struct JointFrame {
+    origin: Vector3<f32>,
+    axes: [Vector3<f32>; 3],
+    scale: f32,
+}
+
+impl JointFrame {
+    fn new(graph: &Graph, joint: Handle<Node>, camera: Handle<Node>) -> Self {
+        let node = &graph[joint];
+        Self {
+            origin: node.global_position(),
+            axes: [
+                node.side_vector()
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::x),
+                node.up_vector()
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::y),
+                node.look_vector()
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::z),
+            ],
+            scale: calculate_gizmo_distance_scaling(graph, camera, joint).x,
+        }
+    }
+
+    fn point(&self, kind: LimitKind, value: f32) -> Vector3<f32> {
+        match kind {
+            LimitKind::Angular { axis, reference } => {
+                let a = self.axes[reference];
+                let b = self.axes[axis].cross(&a);
+                self.origin
+                    + (a.scale(value.cos()) + b.scale(value.sin())).scale(ARC_RADIUS * self.scale)
+            }
+            LimitKind::Linear { axis } => self.origin + self.axes[axis].scale(value),
+        }
+    }
+
+    /// Calculates the limit value that corresponds to the given picking ray.
+    fn value(&self, kind: LimitKind, ray: &Ray) -> Option<f32> {
+        match kind {
+            LimitKind::Angular { axis, reference } => {
+                let plane = Plane::from_normal_and_point(&self.axes[axis], &self.origin)?;
+                let point = ray.plane_intersection_point(&plane)?;
+                let a = self.axes[reference];
+                let b = self.axes[axis].cross(&a);
+                let v = point - self.origin;
+                Some(v.dot(&b).atan2(v.dot(&a)))
+            }
+            LimitKind::Linear { axis } => {
+                // Find the closest point on the axis line to the ray.
+                let d1 = self.axes[axis];
+                let d2 = ray.dir.try_normalize(f32::EPSILON)?;
+                let r = self.origin - ray.origin;
+                let b = d1.dot(&d2);
+                let denom = 1.0 - b * b;
+                if denom.abs() <= f32::EPSILON {
+                    return None;
+                }
+                Some((b * r.dot(&d2) - r.dot(&d1)) / denom)
+            }
+        }
+    }
+}
+
+fn distance_to_ray(ray: &Ray, point: Vector3<f32>) -> f32 {
+    let Some(dir) = ray.dir.try_normalize(f32::EPSILON) else {
+        return f32::MAX;
+    };
+    let v = point - ray.origin;
+    (v - dir.scale(v.dot(&dir))).norm()
+}
+
+fn draw_limit(
+    ctx: &mut SceneDrawingContext,
+    frame: &JointFrame,
+    limit: &LimitGizmo,
+    active: Option<LimitEnd>,
+) {
+    let color = if limit.enabled {
+        limit.color
+    } else {
+        Color::opaque(120, 120, 120)
+    };
+
+    match limit.kind {
+        LimitKind::Angular { .. } => {
+            let segments = 32;
+            let step = (limit.range.end - limit.range.start) / segments as f32;
+            for i in 0..segments {
+                ctx.add_line(Line {
+                    begin: frame.point(limit.kind, limit.range.start + step * i as f32),
+                    end: frame.point(limit.kind, limit.range.start + step * (i + 1) as f32),
+                    color,
+                });
+            }
+            for value in [limit.range.start, limit.range.end] {
+                ctx.add_line(Line {
+                    begin: frame.origin,
+                    end: frame.point(limit.kind, value),
+                    color,
+                });
+            }
+        }
+        LimitKind::Linear { .. } => {
+            ctx.add_line(Line {
+                begin: frame.point(limit.kind, limit.range.start),
+                end: frame.point(limit.kind, limit.range.end),
+                color,
+            });
+        }
+    }
+
+    for (end, value) in [
+        (LimitEnd::Start, limit.range.start),
+        (LimitEnd::End, limit.range.end),
+    ] {
+        ctx.draw_sphere(
+            frame.point(limit.kind, value),
+            8,
+            8,
+            HANDLE_RADIUS * frame.scale,
+            if active == Some(end) {
+                Color::opaque(255, 255, 0)
+            } else {
+                color
+            },
+        );
+    }
+}
+
+struct DragContext {
+    joint: Handle<Node>,
+    limit: usize,
+    end: LimitEnd,
+    initial_range: Range<f32>,
+    range: Range<f32>,
+}
+
+pub struct JointInteractionMode {
+    message_sender: MessageSender,
+    drag_context: Option<DragContext>,
+}
+
+impl JointInteractionMode {
+    pub fn new(message_sender: MessageSender) -> Self {
+        Self {
+            message_sender,
+            drag_context: None,
+        }
+    }
+}
+
+impl TypeUuidProvider for JointInteractionMode {
+    fn type_uuid() -> Uuid {
+        uuid!("6c0e7c37-5d9a-4b4e-a7b9-2b9e3f1bd4a2")
+    }
+}
+
+fn selected_joint(editor_selection: &Selection, graph: &Graph) -> Option<Handle<Node>> {
+    if let Selection::Graph(selection) = editor_selection {
+        if selection.is_single_selection() {
+            let handle = selection.nodes()[0];
+            if graph.try_get_of_type::<Joint>(handle).is_some() {
+                return Some(handle);
+            }
+        }
+    }
+    None
+}
+
+fn picking_ray(
+    graph: &Graph,
+    camera: Handle<Node>,
+    mouse_pos: Vector2<f32>,
+    frame_size: Vector2<f32>,
+) -> Option<Ray> {
+    graph
+        .try_get_of_type::<Camera>(camera)
+        .map(|camera| camera.make_ray(mouse_pos, frame_size))
+}
+
+impl InteractionMode for JointInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let graph = &engine.scenes[game_scene.scene].graph;
+        let camera = game_scene.camera_controller.camera;
+        let Some(joint) = selected_joint(editor_selection, graph) else {
+            return;
+        };
+        let Some(ray) = picking_ray(graph, camera, mouse_pos, frame_size) else {
+            return;
+        };
+
+        let Some(joint_ref) = graph.try_get_of_type::<Joint>(joint) else {
+            return;
+        };
+
+        let frame = JointFrame::new(graph, joint, camera);
+        let pick_radius = 1.5 * HANDLE_RADIUS * frame.scale;
+
+        let mut closest = None;
+        let mut closest_distance = f32::MAX;
+        for (index, limit) in joint_limits(joint_ref.params()).into_iter().enumerate() {
+            for (end, value) in [
+                (LimitEnd::Start, limit.range.start),
+                (LimitEnd::End, limit.range.end),
+            ] {
+                let distance = distance_to_ray(&ray, frame.point(limit.kind, value));
+                if distance < pick_radius && distance < closest_distance {
+                    closest_distance = distance;
+                    closest = Some(DragContext {
+                        joint,
+                        limit: index,
+                        end,
+                        initial_range: limit.range.clone(),
+                        range: limit.range.clone(),
+                    });
+                }
+            }
+        }
+
+        self.drag_context = closest;
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        if let Some(drag_context) = self.drag_context.take() {
+            let graph = &mut engine.scenes[game_scene.scene].graph;
+            let Some(joint) = graph.try_get_mut_of_type::<Joint>(drag_context.joint) else {
+                return;
+            };
+
+            let Some(limit) = joint_limits(joint.params())
+                .into_iter()
+                .nth(drag_context.limit)
+            else {
+                return;
+            };
+
+            // Revert preview changes and do the actual change using a command, so it could be
+            // undone.
+            set_joint_limits(
+                joint.params_mut(),
+                drag_context.limit,
+                drag_context.initial_range.clone(),
+            );
+
+            if drag_context.range != drag_context.initial_range {
+                self.message_sender
+                    .do_scene_command(SetPropertyCommand::new(
+                        drag_context.joint,
+                        limit.path.to_string(),
+                        Box::new(drag_context.range) as Box<dyn Reflect>,
+                    ));
+            }
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let Some(drag_context) = self.drag_context.as_mut() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        let camera = game_scene.camera_controller.camera;
+        let Some(ray) = picking_ray(graph, camera, mouse_position, frame_size) else {
+            return;
+        };
+        let frame = JointFrame::new(graph, drag_context.joint, camera);
+
+        let Some(joint) = graph.try_get_mut_of_type::<Joint>(drag_context.joint) else {
+            return;
+        };
+        let Some(limit) = joint_limits(joint.params())
+            .into_iter()
+            .nth(drag_context.limit)
+        else {
+            return;
+        };
+
+        if let Some(value) = frame.value(limit.kind, &ray) {
+            let range = &mut drag_context.range;
+            match drag_context.end {
+                LimitEnd::Start => range.start = value.min(range.end),
+                LimitEnd::End => range.end = value.max(range.start),
+            }
+
+            set_joint_limits(joint.params_mut(), drag_context.limit, range.clone());
+        }
+    }
+
+    fn update(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+        let Some(joint) = selected_joint(editor_selection, &scene.graph) else {
+            return;
+        };
+
+        let Some(joint_ref) = scene.graph.try_get_of_type::<Joint>(joint) else {
+            return;
+        };
+        let limits = joint_limits(joint_ref.params());
+
+        let frame = JointFrame::new(&scene.graph, joint, game_scene.camera_controller.camera);
+
+        // Draw the axes of the joint first, limits are defined relative to them.
+        for (axis, color) in frame
+            .axes
+            .iter()
+            .zip([Color::RED, Color::GREEN, Color::BLUE])
+        {
+            scene.drawing_context.add_line(Line {
+                begin: frame.origin,
+                end: frame.origin + axis.scale(0.5 * ARC_RADIUS * frame.scale),
+                color,
+            });
+        }
+
+        for (index, limit) in limits.iter().enumerate() {
+            let active = self
+                .drag_context
+                .as_ref()
+                .filter(|ctx| ctx.limit == index)
+                .map(|ctx| ctx.end);
+            draw_limit(&mut scene.drawing_context, &frame, limit, active);
+        }
+    }
+
+    fn deactivate(&mut self, controller: &dyn SceneController, engine: &mut Engine) {
+        let Some(game_scene) = controller.downcast_ref::<GameScene>() else {
+            return;
+        };
+
+        // Revert unfinished changes.
+        if let Some(drag_context) = self.drag_context.take() {
+            if let Some(joint) = engine.scenes[game_scene.scene]
+                .graph
+                .try_get_mut_of_type::<Joint>(drag_context.joint)
+            {
+                set_joint_limits(
+                    joint.params_mut(),
+                    drag_context.limit,
+                    drag_context.initial_range,
+                );
+            }
+        }
+    }
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        let joint_mode_tooltip = "Edit Joint\n\nJoint edit mode allows you to modify limits of \
+        the selected joint by dragging the handles at the ends of the limits.";
+
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../resources/joint.png"),
+            joint_mode_tooltip,
+            selected,
+        )
+    }
+
+    fn uuid(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
@@ -0,0 +1,299 @@
+//! Measure mode allows to measure distances between picked points and angles between the segments
+//! of the resulting polyline. Results are shown using labels over the scene preview frame.
+
+use crate::{
+    camera::PickingOptions,
+    interaction::{make_interaction_mode_button, InteractionMode},
+    scene::{controller::SceneController, GameScene, Selection},
+    scene_viewer::labels::{make_label, place_label},
+    settings::Settings,
+};
+use fyrox::graph::SceneGraph;
+use fyrox::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        color::Color,
+        math::plane::Plane,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        TypeUuidProvider,
+    },
+    engine::Engine,
+    gui::{
+        message::{KeyCode, MessageDirection},
+        widget::WidgetMessage,
+        BuildContext, UiNode,
+    },
+    scene::debug::Line,
+};
+
+/// Picks a world-space point under the cursor. If there's no object under the cursor, the point is
+/// picked on the horizontal plane that goes through the origin.
+pub fn pick_world_point(
+    game_scene: &mut GameScene,
+    engine: &Engine,
+    mouse_position: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    settings: &Settings,
+) -> Option<Vector3<f32>> {
+    let graph = &engine.scenes[game_scene.scene].graph;
+
+    if let Some(result) = game_scene.camera_controller.pick(PickingOptions {
+        cursor_pos: mouse_position,
+        graph,
+        editor_objects_root: game_scene.editor_objects_root,
+        scene_content_root: game_scene.scene_content_root,
+        screen_size: frame_size,
+        editor_only: false,
+        filter: |_, _| true,
+        ignore_back_faces: settings.selection.ignore_back_faces,
+        use_picking_loop: false,
+        only_meshes: false,
+    }) {
+        return Some(result.position);
+    }
+
+    game_scene.camera_controller.pick_on_plane(
+        Plane::from_normal_and_point(&Vector3::y(), &Vector3::default())?,
+        graph,
+        mouse_position,
+        frame_size,
+        Matrix4::identity(),
+    )
+}
+
+/// Returns a set of labels (position and text) for the polyline with the given points: length of
+/// every segment, angle between adjacent segments and total length of the polyline.
+fn measurement_labels(points: &[Vector3<f32>]) -> Vec<(Vector3<f32>, String)> {
+    let mut labels = Vec::new();
+
+    for segment in points.windows(2) {
+        labels.push((
+            (segment[0] + segment[1]).scale(0.5),
+            format!("{:.3} m", segment[0].metric_distance(&segment[1])),
+        ));
+    }
+
+    for corner in points.windows(3) {
+        let a = corner[0] - corner[1];
+        let b = corner[2] - corner[1];
+        if a.norm() > f32::EPSILON && b.norm() > f32::EPSILON {
+            labels.push((corner[1], format!("{:.1}°", a.angle(&b).to_degrees())));
+        }
+    }
+
+    if points.len() > 2 {
+        let total = points
+            .windows(2)
+            .map(|segment| segment[0].metric_distance(&segment[1]))
+            .sum::<f32>();
+        labels.push((points[points.len() - 1], format!("Total: {total:.3} m")));
+    }
+
+    labels
+}
+
+pub struct MeasureInteractionMode {
+    frame: Handle<UiNode>,
+    canvas: Handle<UiNode>,
+    points: Vec<Vector3<f32>>,
+    labels: Vec<(Handle<UiNode>, Vector3<f32>)>,
+    labels_dirty: bool,
+}
+
+impl MeasureInteractionMode {
+    pub fn new(frame: Handle<UiNode>, canvas: Handle<UiNode>) -> Self {
+        Self {
+            frame,
+            canvas,
+            points: Default::default(),
+            labels: Default::default(),
+            labels_dirty: false,
+        }
+    }
+
+    fn remove_labels(&mut self, engine: &Engine) {
+        for (label, _) in self.labels.drain(..) {
+            engine
+                .user_interface
+                .send_message(WidgetMessage::remove(label, MessageDirection::ToWidget));
+        }
+    }
+}
+
+impl TypeUuidProvider for MeasureInteractionMode {
+    fn type_uuid() -> Uuid {
+        uuid!("1f0a8d4e-3b6c-4d2a-9e57-c8b41f6a2d93")
+    }
+}
+
+impl InteractionMode for MeasureInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        if let Some(point) = pick_world_point(game_scene, engine, mouse_pos, frame_size, settings) {
+            self.points.push(point);
+            self.labels_dirty = true;
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        _editor_selection: &Selection,
+        _controller: &mut dyn SceneController,
+        _engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        _mouse_position: Vector2<f32>,
+        _editor_selection: &Selection,
+        _controller: &mut dyn SceneController,
+        _engine: &mut Engine,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        if self.labels_dirty {
+            self.labels_dirty = false;
+            self.remove_labels(engine);
+            for (position, text) in measurement_labels(&self.points) {
+                let ui = &mut engine.user_interface;
+                let label = make_label(&text, &mut ui.build_ctx());
+                ui.send_message(WidgetMessage::link(
+                    label,
+                    MessageDirection::ToWidget,
+                    self.canvas,
+                ));
+                self.labels.push((label, position));
+            }
+        }
+
+        let scene = &mut engine.scenes[game_scene.scene];
+        let camera_position = game_scene.camera_controller.position(&scene.graph);
+
+        for segment in self.points.windows(2) {
+            scene.drawing_context.add_line(Line {
+                begin: segment[0],
+                end: segment[1],
+                color: Color::opaque(255, 255, 0),
+            });
+        }
+        for point in self.points.iter() {
+            scene.drawing_context.draw_sphere(
+                *point,
+                8,
+                8,
+                0.01 * point.metric_distance(&camera_position),
+                Color::opaque(255, 255, 0),
+            );
+        }
+
+        let frame_size = engine.user_interface.node(self.frame).actual_local_size();
+        let camera = scene.graph[game_scene.camera_controller.camera].as_camera();
+        for (label, position) in self.labels.iter() {
+            place_label(
+                *label,
+                *position,
+                camera,
+                frame_size,
+                &engine.user_interface,
+            );
+        }
+    }
+
+    fn deactivate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        self.points.clear();
+        self.labels_dirty = false;
+        self.remove_labels(engine);
+    }
+
+    fn on_key_down(
+        &mut self,
+        key: KeyCode,
+        _editor_selection: &Selection,
+        _controller: &mut dyn SceneController,
+        _engine: &mut Engine,
+    ) -> bool {
+        match key {
+            KeyCode::Escape if !self.points.is_empty() => {
+                self.points.clear();
+                self.labels_dirty = true;
+                true
+            }
+            KeyCode::Backspace if !self.points.is_empty() => {
+                self.points.pop();
+                self.labels_dirty = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        let measure_mode_tooltip = "Measure\n\nMeasure mode allows you to measure distances \
+        between the picked points and angles between the segments. Press Backspace to remove the \
+        last point and Escape to clear the measurement.";
+
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../resources/pick.png"),
+            measure_mode_tooltip,
+            selected,
+        )
+    }
+
+    fn uuid(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::measurement_labels;
+    use fyrox::core::algebra::Vector3;
+
+    #[test]
+    fn test_measurement_labels() {
+        assert!(measurement_labels(&[Vector3::default()]).is_empty());
+
+        let labels = measurement_labels(&[
+            Vector3::new(2.0, 0.0, 0.0),
+            Vector3::default(),
+            Vector3::new(0.0, 0.0, 3.0),
+        ]);
+        let texts = labels
+            .iter()
+            .map(|(_, text)| text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["2.000 m", "3.000 m", "90.0°", "Total: 5.000 m"]);
+        assert_eq!(labels[0].0, Vector3::new(1.0, 0.0, 0.0));
+    }
+}
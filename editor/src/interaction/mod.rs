@@ -26,13 +26,18 @@ use fyrox::{
 };
 use std::any::Any;
 
+pub mod annotate;
+pub mod foliage;
 pub mod gizmo;
+pub mod joint;
+pub mod measure;
 pub mod move_mode;
 pub mod navmesh;
 pub mod plane;
 pub mod rotate_mode;
 pub mod scale_mode;
 pub mod select_mode;
+pub mod spline;
 pub mod terrain;
 
 pub trait BaseInteractionMode: 'static {
@@ -2,7 +2,7 @@ use crate::interaction::make_interaction_mode_button;
 use crate::message::MessageSender;
 use crate::scene::controller::SceneController;
 use crate::{
-    camera::{CameraController, PickingOptions},
+    camera::{closest_mesh_vertex, CameraController, PickingOptions},
     interaction::{
         calculate_gizmo_distance_scaling, gizmo::move_gizmo::MoveGizmo, plane::PlaneKind,
         InteractionMode,
@@ -50,6 +50,8 @@ struct MoveContext {
     plane_kind: PlaneKind,
     gizmo_inv_transform: Matrix4<f32>,
     gizmo_local_transform: Matrix4<f32>,
+    /// World-space position of the gizmo at the beginning of the movement.
+    initial_pivot: Vector3<f32>,
 }
 
 impl MoveContext {
@@ -99,6 +101,7 @@ impl MoveContext {
             gizmo_local_transform: gizmo_origin.local_transform().matrix(),
             gizmo_inv_transform,
             plane_kind,
+            initial_pivot: gizmo_origin.global_position(),
         }
     }
 
@@ -158,6 +161,13 @@ impl MoveContext {
         mouse_position: Vector2<f32>,
         frame_size: Vector2<f32>,
     ) {
+        if let Some(target) =
+            self.pick_snap_target(graph, game_scene, settings, mouse_position, frame_size)
+        {
+            self.move_pivot_to(target);
+            return;
+        }
+
         match self.plane_kind {
             PlaneKind::SMART => {
                 self.update_smart_move(graph, game_scene, settings, mouse_position, frame_size);
@@ -172,6 +182,64 @@ impl MoveContext {
         }
     }
 
+    fn moved_nodes(&self, graph: &Graph) -> FxHashSet<Handle<Node>> {
+        self.objects
+            .iter()
+            .map(|f| f.node)
+            .flat_map(|node| graph.traverse_handle_iter(node))
+            .collect()
+    }
+
+    /// Searches for a point under the cursor the pivot should be snapped to, according to the
+    /// vertex/surface snapping settings.
+    fn pick_snap_target(
+        &self,
+        graph: &Graph,
+        game_scene: &mut GameScene,
+        settings: &Settings,
+        mouse_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> Option<Vector3<f32>> {
+        let snap_settings = &settings.move_mode_settings;
+        if !snap_settings.vertex_snapping && !snap_settings.surface_snapping {
+            return None;
+        }
+
+        let moved_nodes = self.moved_nodes(graph);
+
+        let result = game_scene.camera_controller.pick(PickingOptions {
+            cursor_pos: mouse_position,
+            graph,
+            editor_objects_root: game_scene.editor_objects_root,
+            scene_content_root: game_scene.scene_content_root,
+            screen_size: frame_size,
+            editor_only: false,
+            filter: |handle, _| !moved_nodes.contains(&handle),
+            ignore_back_faces: settings.selection.ignore_back_faces,
+            use_picking_loop: false,
+            only_meshes: !snap_settings.surface_snapping,
+        })?;
+
+        if snap_settings.vertex_snapping {
+            if let Some(vertex) = closest_mesh_vertex(&graph[result.node], result.position) {
+                return Some(vertex);
+            }
+        }
+
+        snap_settings.surface_snapping.then_some(result.position)
+    }
+
+    /// Moves every object by the same offset, so the pivot ends up at the given world-space point.
+    fn move_pivot_to(&mut self, target: Vector3<f32>) {
+        let offset = target - self.initial_pivot;
+        for entry in self.objects.iter_mut() {
+            entry.new_local_position = entry.initial_local_position
+                + entry
+                    .initial_parent_inv_global_transform
+                    .transform_vector(&offset);
+        }
+    }
+
     fn update_smart_move(
         &mut self,
         graph: &Graph,
@@ -180,12 +248,7 @@ impl MoveContext {
         mouse_position: Vector2<f32>,
         frame_size: Vector2<f32>,
     ) {
-        let preview_nodes = self
-            .objects
-            .iter()
-            .map(|f| f.node)
-            .flat_map(|node| graph.traverse_handle_iter(node))
-            .collect::<FxHashSet<Handle<Node>>>();
+        let preview_nodes = self.moved_nodes(graph);
 
         let new_position = if let Some(result) = game_scene.camera_controller.pick(PickingOptions {
             cursor_pos: mouse_position,
@@ -391,7 +454,7 @@ impl InteractionMode for MoveInteractionMode {
                     scene_content_root: game_scene.scene_content_root,
                     screen_size: frame_size,
                     editor_only: false,
-                    filter: |_, _| true,
+                    filter: |handle, _| !game_scene.node_flags.is_locked(handle, &scene.graph),
                     ignore_back_faces: settings.selection.ignore_back_faces,
                     use_picking_loop: true,
                     only_meshes: false,
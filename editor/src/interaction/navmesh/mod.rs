@@ -8,12 +8,13 @@ use crate::{
         plane::PlaneKind,
         InteractionMode,
     },
+    localization::window_title,
     message::MessageSender,
     scene::{
         commands::{
             navmesh::{
                 AddNavmeshEdgeCommand, ConnectNavmeshEdgesCommand, DeleteNavmeshVertexCommand,
-                MoveNavmeshVertexCommand,
+                MoveNavmeshVertexCommand, SetNavmeshCommand,
             },
             ChangeSelectionCommand, CommandGroup, GameSceneCommand,
         },
@@ -28,6 +29,7 @@ use fyrox::{
     core::{
         algebra::{Vector2, Vector3},
         color::Color,
+        log::Log,
         math::{ray::CylinderKind, TriangleEdge},
         pool::Handle,
         scope_profile,
@@ -40,22 +42,36 @@ use fyrox::{
         grid::{Column, GridBuilder, Row},
         message::{KeyCode, MessageDirection, UiMessage},
         stack_panel::StackPanelBuilder,
+        utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, Orientation, Thickness, UiNode, UserInterface,
     },
     gui::{HorizontalAlignment, VerticalAlignment},
-    scene::{camera::Camera, navmesh::NavigationalMesh},
+    scene::{camera::Camera, debug::Line, navmesh::NavigationalMesh, node::Node},
+    utils::navmesh::{
+        bake::{self, NavmeshBakeGeometry},
+        Navmesh,
+    },
 };
 use std::collections::HashMap;
 
 pub mod selection;
 
+struct BakePreview {
+    navmesh_node: Handle<Node>,
+    navmesh: Navmesh,
+}
+
 pub struct NavmeshPanel {
     pub window: Handle<UiNode>,
     connect_edges: Handle<UiNode>,
+    bake: Handle<UiNode>,
+    apply_bake: Handle<UiNode>,
+    discard_bake: Handle<UiNode>,
     sender: MessageSender,
     scene_frame: Handle<UiNode>,
+    preview: Option<BakePreview>,
 }
 
 fn fetch_selection(editor_selection: &Selection) -> Option<NavmeshSelection> {
@@ -74,26 +90,74 @@ fn fetch_selection(editor_selection: &Selection) -> Option<NavmeshSelection> {
 impl NavmeshPanel {
     pub fn new(scene_frame: Handle<UiNode>, ctx: &mut BuildContext, sender: MessageSender) -> Self {
         let connect_edges;
+        let bake;
+        let apply_bake;
+        let discard_bake;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("NavmeshPanel"))
             .open(false)
-            .with_title(WindowTitle::text("Navmesh"))
+            .with_title(window_title("Navmesh", ctx))
             .with_content(
                 GridBuilder::new(
-                    WidgetBuilder::new().with_child(
-                        StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                            connect_edges = ButtonBuilder::new(
-                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(WidgetBuilder::new().with_child({
+                                connect_edges = ButtonBuilder::new(
+                                    WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_text("Connect Edges")
+                                .build(ctx);
+                                connect_edges
+                            }))
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_child({
+                                        bake = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Bakes the navmesh from static meshes and \
+                                                    terrains of the scene using the bake settings \
+                                                    of the navmesh. The result is shown as a preview.",
+                                                )),
+                                        )
+                                        .with_text("Bake")
+                                        .build(ctx);
+                                        bake
+                                    })
+                                    .with_child({
+                                        apply_bake = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_enabled(false)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Apply")
+                                        .build(ctx);
+                                        apply_bake
+                                    })
+                                    .with_child({
+                                        discard_bake = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_enabled(false)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Discard")
+                                        .build(ctx);
+                                        discard_bake
+                                    }),
                             )
-                            .with_text("Connect Edges")
-                            .build(ctx);
-                            connect_edges
-                        }))
-                        .with_orientation(Orientation::Horizontal)
-                        .build(ctx),
-                    ),
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
                 )
                 .add_column(Column::stretch())
                 .add_row(Row::strict(20.0))
+                .add_row(Row::strict(20.0))
                 .build(ctx),
             )
             .build(ctx);
@@ -102,15 +166,100 @@ impl NavmeshPanel {
             window,
             sender,
             connect_edges,
+            bake,
+            apply_bake,
+            discard_bake,
             scene_frame,
+            preview: None,
+        }
+    }
+
+    fn set_preview(&mut self, preview: Option<BakePreview>, ui: &UserInterface) {
+        for button in [self.apply_bake, self.discard_bake] {
+            ui.send_message(WidgetMessage::enabled(
+                button,
+                MessageDirection::ToWidget,
+                preview.is_some(),
+            ));
+        }
+        self.preview = preview;
+    }
+
+    fn bake_preview(
+        &mut self,
+        selection: &NavmeshSelection,
+        game_scene: &GameScene,
+        engine: &Engine,
+    ) {
+        let graph = &engine.scenes[game_scene.scene].graph;
+        let Some(navmesh) = graph.try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+        else {
+            return;
+        };
+
+        let geometry = NavmeshBakeGeometry::from_graph(graph, game_scene.scene_content_root);
+        let navmesh = bake::bake(&geometry, navmesh.bake_settings());
+        Log::info(format!(
+            "Navmesh was baked: {} triangles, {} vertices.",
+            navmesh.triangles().len(),
+            navmesh.vertices().len()
+        ));
+
+        self.set_preview(
+            Some(BakePreview {
+                navmesh_node: selection.navmesh_node(),
+                navmesh,
+            }),
+            &engine.user_interface,
+        );
+    }
+
+    /// Draws baked, but not yet applied navmesh on top of the scene.
+    pub fn update(&self, game_scene: &GameScene, engine: &mut Engine) {
+        let Some(preview) = self.preview.as_ref() else {
+            return;
+        };
+
+        // Lift the preview a bit, so it won't be hidden by the geometry it was baked from.
+        let offset = Vector3::new(0.0, 0.05, 0.0);
+        let ctx = &mut engine.scenes[game_scene.scene].drawing_context;
+        let vertices = preview.navmesh.vertices();
+        for triangle in preview.navmesh.triangles() {
+            for edge in triangle.edges() {
+                ctx.add_line(Line {
+                    begin: vertices[edge.a as usize] + offset,
+                    end: vertices[edge.b as usize] + offset,
+                    color: Color::opaque(0, 200, 255),
+                });
+            }
         }
     }
 
-    pub fn handle_message(&mut self, message: &UiMessage, editor_selection: &Selection) {
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        editor_selection: &Selection,
+        game_scene: &GameScene,
+        engine: &Engine,
+    ) {
         scope_profile!();
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
-            if message.destination() == self.connect_edges {
+            if message.destination() == self.bake {
+                if let Some(selection) = fetch_selection(editor_selection) {
+                    self.bake_preview(&selection, game_scene, engine);
+                }
+            } else if message.destination() == self.apply_bake {
+                if let Some(preview) = self.preview.take() {
+                    self.sender.do_scene_command(SetNavmeshCommand::new(
+                        preview.navmesh_node,
+                        preview.navmesh,
+                    ));
+                }
+                self.set_preview(None, &engine.user_interface);
+            } else if message.destination() == self.discard_bake {
+                self.set_preview(None, &engine.user_interface);
+            } else if message.destination() == self.connect_edges {
                 if let Some(selection) = fetch_selection(editor_selection) {
                     let vertices = selection
                         .entities()
@@ -149,6 +298,14 @@ impl NavmeshPanel {
                 .is_some();
         }
 
+        if self.preview.as_ref().map_or(false, |p| {
+            graph
+                .try_get_of_type::<NavigationalMesh>(p.navmesh_node)
+                .is_none()
+        }) {
+            self.set_preview(None, &engine.user_interface);
+        }
+
         if navmesh_selected {
             engine
                 .user_interface
@@ -146,7 +146,7 @@ impl InteractionMode for RotateInteractionMode {
                     scene_content_root: game_scene.scene_content_root,
                     screen_size: frame_size,
                     editor_only: false,
-                    filter: |_, _| true,
+                    filter: |handle, _| !game_scene.node_flags.is_locked(handle, graph),
                     ignore_back_faces: settings.selection.ignore_back_faces,
                     use_picking_loop: true,
                     only_meshes: false,
@@ -1,3 +1,4 @@
+use crate::camera::PickingOptions;
 use crate::interaction::make_interaction_mode_button;
 use crate::scene::controller::SceneController;
 use crate::{
@@ -13,13 +14,80 @@ use fyrox::core::TypeUuidProvider;
 use fyrox::graph::SceneGraph;
 use fyrox::gui::BuildContext;
 use fyrox::{
-    core::{algebra::Vector2, pool::Handle},
-    gui::{message::MessageDirection, widget::WidgetMessage, UiNode},
-    scene::node::Node,
+    core::{algebra::Vector2, math::Rect, pool::Handle},
+    gui::{
+        message::{KeyboardModifiers, MessageDirection},
+        widget::WidgetMessage,
+        UiNode,
+    },
+    scene::{camera::Camera, node::Node},
 };
 
+/// Frames smaller than this size (in pixels) are treated as a single click.
+const CLICK_THRESHOLD: f32 = 3.0;
+
+/// Defines how newly selected nodes are combined with the current selection.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum SelectionOperation {
+    /// New selection replaces the current one.
+    Replace,
+    /// New nodes are added to the current selection (Shift).
+    Add,
+    /// New nodes are removed from the current selection (Ctrl).
+    Subtract,
+}
+
+impl SelectionOperation {
+    fn from_modifiers(modifiers: KeyboardModifiers) -> Self {
+        if modifiers.shift {
+            Self::Add
+        } else if modifiers.control {
+            Self::Subtract
+        } else {
+            Self::Replace
+        }
+    }
+
+    fn apply(self, current: &GraphSelection, nodes: &[Handle<Node>]) -> GraphSelection {
+        match self {
+            Self::Replace => GraphSelection::from_list(nodes.to_vec()),
+            Self::Add => {
+                let mut selection = current.clone();
+                for &node in nodes {
+                    if !selection.contains(node) {
+                        selection.nodes.push(node);
+                    }
+                }
+                selection
+            }
+            Self::Subtract => GraphSelection::from_list(
+                current
+                    .nodes()
+                    .iter()
+                    .filter(|node| !nodes.contains(node))
+                    .cloned()
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Calculates screen-space bounds of the node. Returns `None` if the node is completely behind the
+/// camera.
+fn node_screen_bounds(node: &Node, camera: &Camera, frame_size: Vector2<f32>) -> Option<Rect<f32>> {
+    let mut bounds: Option<Rect<f32>> = None;
+    for corner in node.world_bounding_box().corners() {
+        if let Some(point) = camera.project(corner, frame_size) {
+            match bounds.as_mut() {
+                Some(bounds) => bounds.push(point),
+                None => bounds = Some(Rect::new(point.x, point.y, 0.0, 0.0)),
+            }
+        }
+    }
+    bounds
+}
+
 pub struct SelectInteractionMode {
-    preview: Handle<UiNode>,
     selection_frame: Handle<UiNode>,
     message_sender: MessageSender,
     stack: Vec<Handle<Node>>,
@@ -27,13 +95,8 @@ pub struct SelectInteractionMode {
 }
 
 impl SelectInteractionMode {
-    pub fn new(
-        preview: Handle<UiNode>,
-        selection_frame: Handle<UiNode>,
-        message_sender: MessageSender,
-    ) -> Self {
+    pub fn new(selection_frame: Handle<UiNode>, message_sender: MessageSender) -> Self {
         Self {
-            preview,
             selection_frame,
             message_sender,
             stack: Vec::new(),
@@ -87,49 +150,88 @@ impl InteractionMode for SelectInteractionMode {
         editor_selection: &Selection,
         controller: &mut dyn SceneController,
         engine: &mut Engine,
-        _mouse_pos: Vector2<f32>,
+        mouse_pos: Vector2<f32>,
         frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
             return;
         };
 
-        let scene = &engine.scenes[game_scene.scene];
-        let camera = scene.graph[game_scene.camera_controller.camera].as_camera();
-        let preview_screen_bounds = engine.user_interface.node(self.preview).screen_bounds();
-        let frame_screen_bounds = engine
+        engine
             .user_interface
-            .node(self.selection_frame)
-            .screen_bounds();
-        let relative_bounds = frame_screen_bounds.translate(-preview_screen_bounds.position);
-        self.stack.clear();
-        self.stack.push(scene.graph.get_root());
-        let mut graph_selection = GraphSelection::default();
-        while let Some(handle) = self.stack.pop() {
-            let node = &scene.graph[handle];
-            if handle == game_scene.editor_objects_root {
-                continue;
-            }
-            if handle == scene.graph.get_root() {
-                self.stack.extend_from_slice(node.children());
-                continue;
-            }
+            .send_message(WidgetMessage::visibility(
+                self.selection_frame,
+                MessageDirection::ToWidget,
+                false,
+            ));
 
-            for screen_corner in node
-                .local_bounding_box()
-                .corners()
-                .iter()
-                .filter_map(|&p| camera.project(p + node.global_position(), frame_size))
-            {
-                if relative_bounds.contains(screen_corner) {
-                    graph_selection.insert_or_exclude(handle);
-                    break;
+        let modifiers = engine.user_interface.keyboard_modifiers();
+        let operation = SelectionOperation::from_modifiers(modifiers);
+        let current_selection = if let Selection::Graph(selection) = editor_selection {
+            selection.clone()
+        } else {
+            Default::default()
+        };
+
+        let scene = &engine.scenes[game_scene.scene];
+        let frame = Rect::new(
+            self.click_pos.x.min(mouse_pos.x),
+            self.click_pos.y.min(mouse_pos.y),
+            (mouse_pos.x - self.click_pos.x).abs(),
+            (mouse_pos.y - self.click_pos.y).abs(),
+        );
+
+        let graph_selection = if frame.w() < CLICK_THRESHOLD && frame.h() < CLICK_THRESHOLD {
+            let picked = game_scene.camera_controller.pick(PickingOptions {
+                cursor_pos: mouse_pos,
+                graph: &scene.graph,
+                editor_objects_root: game_scene.editor_objects_root,
+                scene_content_root: game_scene.scene_content_root,
+                screen_size: frame_size,
+                editor_only: false,
+                filter: |handle, _| !game_scene.node_flags.is_locked(handle, &scene.graph),
+                ignore_back_faces: settings.selection.ignore_back_faces,
+                use_picking_loop: true,
+                only_meshes: false,
+            });
+            match picked {
+                // Ctrl+Click toggles selection of an object, the same as in other modes.
+                Some(result) if modifiers.control => {
+                    let mut selection = current_selection;
+                    selection.insert_or_exclude(result.node);
+                    selection
                 }
+                Some(result) => operation.apply(&current_selection, &[result.node]),
+                None => operation.apply(&current_selection, &[]),
             }
+        } else {
+            let camera = scene.graph[game_scene.camera_controller.camera].as_camera();
+            self.stack.clear();
+            self.stack.push(scene.graph.get_root());
+            let mut framed = Vec::new();
+            while let Some(handle) = self.stack.pop() {
+                let node = &scene.graph[handle];
+                if handle == game_scene.editor_objects_root {
+                    continue;
+                }
+                if handle == scene.graph.get_root() {
+                    self.stack.extend_from_slice(node.children());
+                    continue;
+                }
 
-            self.stack.extend_from_slice(node.children());
-        }
+                if node.global_visibility()
+                    && !game_scene.node_flags.is_locked(handle, &scene.graph)
+                    && node_screen_bounds(node, camera, frame_size)
+                        .map_or(false, |bounds| bounds.intersects(frame))
+                {
+                    framed.push(handle);
+                }
+
+                self.stack.extend_from_slice(node.children());
+            }
+            operation.apply(&current_selection, &framed)
+        };
 
         let new_selection = Selection::Graph(graph_selection);
 
@@ -140,13 +242,6 @@ impl InteractionMode for SelectInteractionMode {
                     editor_selection.clone(),
                 ));
         }
-        engine
-            .user_interface
-            .send_message(WidgetMessage::visibility(
-                self.selection_frame,
-                MessageDirection::ToWidget,
-                false,
-            ));
     }
 
     fn on_mouse_move(
@@ -206,7 +301,8 @@ impl InteractionMode for SelectInteractionMode {
     fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
         let select_mode_tooltip = "Select Object(s) - Shortcut: [1]\n\nSelection interaction mode \
         allows you to select an object by a single left mouse button click or multiple objects using either \
-        frame selection (click and drag) or by holding Ctrl+Click";
+        frame selection (click and drag) or by holding Ctrl+Click. Hold Shift to add framed objects \
+        to the selection or Ctrl to remove them from it.";
 
         make_interaction_mode_button(
             ctx,
@@ -220,3 +316,31 @@ impl InteractionMode for SelectInteractionMode {
         Self::type_uuid()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::SelectionOperation;
+    use crate::world::graph::selection::GraphSelection;
+    use fyrox::core::pool::Handle;
+
+    #[test]
+    fn test_selection_operations() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+        let c = Handle::new(3, 1);
+        let current = GraphSelection::from_list(vec![a, b]);
+
+        assert_eq!(
+            SelectionOperation::Replace.apply(&current, &[c]),
+            GraphSelection::from_list(vec![c])
+        );
+        assert_eq!(
+            SelectionOperation::Add.apply(&current, &[b, c]),
+            GraphSelection::from_list(vec![a, b, c])
+        );
+        assert_eq!(
+            SelectionOperation::Subtract.apply(&current, &[b, c]),
+            GraphSelection::from_list(vec![a])
+        );
+    }
+}
@@ -0,0 +1,660 @@
+//! Spline edit mode allows to edit control points and tangents of the selected spline, and to build
+//! roads along it.
+
+use crate::{
+    interaction::{make_interaction_mode_button, InteractionMode},
+    localization::window_title,
+    menu::create::placeholder_material,
+    message::MessageSender,
+    scene::{
+        commands::{
+            graph::AddNodeCommand, spline::SetSplinePointsCommand,
+            terrain::ModifyTerrainHeightCommand, CommandGroup, GameSceneCommand,
+        },
+        controller::SceneController,
+        GameScene, Selection,
+    },
+    settings::Settings,
+    MSG_SYNC_FLAG,
+};
+use fyrox::graph::SceneGraph;
+use fyrox::{
+    core::{
+        algebra::{Point3, Vector2, Vector3},
+        color::Color,
+        log::{Log, MessageKind},
+        math::plane::Plane,
+        pool::Handle,
+        uuid::{uuid, Uuid},
+        TypeUuidProvider,
+    },
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        inspector::{
+            editors::PropertyEditorDefinitionContainer, Inspector, InspectorBuilder,
+            InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{KeyCode, MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::{
+        camera::Camera,
+        debug::Line,
+        graph::Graph,
+        node::Node,
+        spline::{self, RoadSettings, Spline, SplinePoint},
+        terrain::Terrain,
+    },
+};
+use std::sync::Arc;
+
+/// Maximum distance (in pixels) between the cursor and a handle to pick it.
+const PICK_RADIUS: f32 = 10.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum SplineHandle {
+    Position(usize),
+    Tangent(usize),
+}
+
+struct DragContext {
+    handle: SplineHandle,
+    initial_points: Vec<SplinePoint>,
+    /// Dragged handle moves in a horizontal plane at this height.
+    plane_height: f32,
+}
+
+struct SplinePanel {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    auto_tangents: Handle<UiNode>,
+    build_road: Handle<UiNode>,
+}
+
+impl SplinePanel {
+    fn new(ctx: &mut BuildContext, settings: &RoadSettings) -> Self {
+        let context = InspectorContext::from_object(
+            settings,
+            ctx,
+            Arc::new(PropertyEditorDefinitionContainer::new()),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+
+        let inspector;
+        let auto_tangents;
+        let build_road;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(200.0))
+            .can_minimize(false)
+            .can_maximize(false)
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            inspector = InspectorBuilder::new(WidgetBuilder::new())
+                                .with_context(context)
+                                .build(ctx);
+                            inspector
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        auto_tangents = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Auto Tangents")
+                                        .build(ctx);
+                                        auto_tangents
+                                    })
+                                    .with_child({
+                                        build_road = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Build Road")
+                                        .build(ctx);
+                                        build_road
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .build(ctx),
+            )
+            .open(false)
+            .with_title(window_title("Spline", ctx))
+            .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            auto_tangents,
+            build_road,
+        }
+    }
+
+    fn sync_to_model(&self, ui: &mut UserInterface, settings: &RoadSettings) {
+        let ctx = ui
+            .node(self.inspector)
+            .cast::<Inspector>()
+            .expect("Must be Inspector!")
+            .context()
+            .clone();
+
+        if let Err(e) = ctx.sync(settings, ui, 0, true, Default::default()) {
+            Log::writeln(
+                MessageKind::Error,
+                format!("Failed to sync SplinePanel's inspector. Reason: {:?}", e),
+            )
+        }
+    }
+}
+
+pub struct SplineInteractionMode {
+    message_sender: MessageSender,
+    road_settings: RoadSettings,
+    panel: SplinePanel,
+    drag_context: Option<DragContext>,
+    selected_point: Option<usize>,
+    scene_viewer_frame: Handle<UiNode>,
+}
+
+impl SplineInteractionMode {
+    pub fn new(
+        engine: &mut Engine,
+        message_sender: MessageSender,
+        scene_viewer_frame: Handle<UiNode>,
+    ) -> Self {
+        let road_settings = RoadSettings::default();
+        let panel = SplinePanel::new(&mut engine.user_interface.build_ctx(), &road_settings);
+
+        Self {
+            message_sender,
+            road_settings,
+            panel,
+            drag_context: None,
+            selected_point: None,
+            scene_viewer_frame,
+        }
+    }
+
+    fn build_road(&self, spline_handle: Handle<Node>, graph: &mut Graph) {
+        let Some(spline) = graph.try_get_of_type::<Spline>(spline_handle) else {
+            return;
+        };
+        let spline = spline.clone();
+
+        let mut commands = Vec::new();
+
+        let terrains = graph
+            .pair_iter()
+            .filter(|(_, n)| n.is_globally_enabled() && n.cast::<Terrain>().is_some())
+            .map(|(h, _)| h)
+            .collect::<Vec<_>>();
+        for handle in terrains {
+            let Some(terrain) = graph.try_get_mut_of_type::<Terrain>(handle) else {
+                continue;
+            };
+
+            let old_heightmaps = terrain
+                .chunks_ref()
+                .iter()
+                .map(|c| c.heightmap_owned())
+                .collect();
+            if spline::flatten_terrain(&spline, terrain, &self.road_settings) {
+                let new_heightmaps = terrain
+                    .chunks_ref()
+                    .iter()
+                    .map(|c| c.heightmap_owned())
+                    .collect();
+                commands.push(GameSceneCommand::new(ModifyTerrainHeightCommand::new(
+                    handle,
+                    old_heightmaps,
+                    new_heightmaps,
+                )));
+            }
+        }
+
+        let road = spline::make_road_mesh(&spline, &self.road_settings, placeholder_material())
+            .build_node();
+        commands.push(GameSceneCommand::new(AddNodeCommand::new(
+            road,
+            spline_handle,
+            false,
+        )));
+
+        self.message_sender
+            .do_scene_command(CommandGroup::from(commands).with_custom_name("Build Road"));
+    }
+}
+
+impl TypeUuidProvider for SplineInteractionMode {
+    fn type_uuid() -> Uuid {
+        uuid!("4f2a9c7e-6b1d-4e3a-8f5c-2d7b9e1a6c38")
+    }
+}
+
+fn selected_spline(editor_selection: &Selection, graph: &Graph) -> Option<Handle<Node>> {
+    if let Selection::Graph(selection) = editor_selection {
+        if selection.is_single_selection() {
+            let handle = selection.nodes()[0];
+            if graph.try_get_of_type::<Spline>(handle).is_some() {
+                return Some(handle);
+            }
+        }
+    }
+    None
+}
+
+/// Returns world-space position of the given handle. Tangent handles are placed at one third of
+/// the tangent, this way they match the control points of an equivalent Bezier curve.
+fn handle_position(spline: &Spline, handle: SplineHandle) -> Option<Vector3<f32>> {
+    let transform = spline.global_transform();
+    let (index, local) = match handle {
+        SplineHandle::Position(index) => (index, Vector3::default()),
+        SplineHandle::Tangent(index) => {
+            (index, spline.points().get(index)?.tangent.scale(1.0 / 3.0))
+        }
+    };
+    let point = spline.points().get(index)?;
+    Some(
+        transform
+            .transform_point(&Point3::from(point.position + local))
+            .coords,
+    )
+}
+
+fn handles(spline: &Spline) -> impl Iterator<Item = SplineHandle> {
+    (0..spline.points().len()).flat_map(|i| [SplineHandle::Position(i), SplineHandle::Tangent(i)])
+}
+
+fn pick_handle(
+    spline: &Spline,
+    camera: &Camera,
+    mouse_position: Vector2<f32>,
+    frame_size: Vector2<f32>,
+) -> Option<SplineHandle> {
+    let mut closest = None;
+    let mut closest_distance = PICK_RADIUS;
+    for handle in handles(spline) {
+        let Some(screen_position) = handle_position(spline, handle)
+            .and_then(|position| camera.project(position, frame_size))
+        else {
+            continue;
+        };
+        let distance = screen_position.metric_distance(&mouse_position);
+        if distance < closest_distance {
+            closest_distance = distance;
+            closest = Some(handle);
+        }
+    }
+    closest
+}
+
+fn pick_on_plane(
+    camera: &Camera,
+    mouse_position: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    height: f32,
+) -> Option<Vector3<f32>> {
+    let ray = camera.make_ray(mouse_position, frame_size);
+    let plane = Plane::from_normal_and_point(&Vector3::y(), &Vector3::new(0.0, height, 0.0))?;
+    ray.plane_intersection_point(&plane)
+}
+
+impl InteractionMode for SplineInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let graph = &engine.scenes[game_scene.scene].graph;
+        let Some(handle) = selected_spline(editor_selection, graph) else {
+            return;
+        };
+        let (Some(spline), Some(camera)) = (
+            graph.try_get_of_type::<Spline>(handle),
+            graph.try_get_of_type::<Camera>(game_scene.camera_controller.camera),
+        ) else {
+            return;
+        };
+
+        if let Some(picked) = pick_handle(spline, camera, mouse_pos, frame_size) {
+            let (SplineHandle::Position(index) | SplineHandle::Tangent(index)) = picked;
+            self.selected_point = Some(index);
+            self.drag_context = Some(DragContext {
+                handle: picked,
+                initial_points: spline.points().to_vec(),
+                plane_height: handle_position(spline, picked).unwrap_or_default().y,
+            });
+        } else if engine.user_interface.keyboard_modifiers().shift {
+            // Append a new point at the height of the last point.
+            let height = spline
+                .points()
+                .len()
+                .checked_sub(1)
+                .and_then(|last| handle_position(spline, SplineHandle::Position(last)))
+                .unwrap_or_else(|| spline.global_position())
+                .y;
+            let Some(world_position) = pick_on_plane(camera, mouse_pos, frame_size, height) else {
+                return;
+            };
+            let Some(inv_transform) = spline.global_transform().try_inverse() else {
+                return;
+            };
+
+            let position = inv_transform
+                .transform_point(&Point3::from(world_position))
+                .coords;
+            let mut points = spline.points().to_vec();
+            let tangent = points
+                .last()
+                .map(|last| position - last.position)
+                .unwrap_or_default();
+            points.push(SplinePoint { position, tangent });
+            self.selected_point = Some(points.len() - 1);
+            self.message_sender
+                .do_scene_command(SetSplinePointsCommand::new(handle, points));
+        } else {
+            self.selected_point = None;
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let Some(drag_context) = self.drag_context.take() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        let Some(handle) = selected_spline(editor_selection, graph) else {
+            return;
+        };
+
+        if let Some(spline) = graph.try_get_mut_of_type::<Spline>(handle) {
+            if spline.points() != drag_context.initial_points {
+                // Revert the changes, the command will apply them back.
+                let new_points = spline.set_points(drag_context.initial_points);
+                self.message_sender
+                    .do_scene_command(SetSplinePointsCommand::new(handle, new_points));
+            }
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let Some(drag_context) = self.drag_context.as_ref() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        let Some(handle) = selected_spline(editor_selection, graph) else {
+            return;
+        };
+
+        let Some(world_position) = graph
+            .try_get_of_type::<Camera>(game_scene.camera_controller.camera)
+            .and_then(|camera| {
+                pick_on_plane(
+                    camera,
+                    mouse_position,
+                    frame_size,
+                    drag_context.plane_height,
+                )
+            })
+        else {
+            return;
+        };
+
+        let Some(spline) = graph.try_get_mut_of_type::<Spline>(handle) else {
+            return;
+        };
+        let Some(inv_transform) = spline.global_transform().try_inverse() else {
+            return;
+        };
+        let local_position = inv_transform
+            .transform_point(&Point3::from(world_position))
+            .coords;
+
+        match drag_context.handle {
+            SplineHandle::Position(index) => {
+                if let Some(point) = spline.points_mut().get_mut(index) {
+                    point.position = local_position;
+                }
+            }
+            SplineHandle::Tangent(index) => {
+                if let Some(point) = spline.points_mut().get_mut(index) {
+                    point.tangent = (local_position - point.position).scale(3.0);
+                }
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+        let Some(spline) = selected_spline(editor_selection, &scene.graph)
+            .and_then(|handle| scene.graph.try_get_of_type::<Spline>(handle))
+        else {
+            return;
+        };
+
+        spline::draw_spline(spline, &mut scene.drawing_context, Color::ORANGE);
+
+        for index in 0..spline.points().len() {
+            let (Some(position), Some(tangent)) = (
+                handle_position(spline, SplineHandle::Position(index)),
+                handle_position(spline, SplineHandle::Tangent(index)),
+            ) else {
+                continue;
+            };
+
+            let color = if self.selected_point == Some(index) {
+                Color::GREEN
+            } else {
+                Color::WHITE
+            };
+            scene.drawing_context.add_line(Line {
+                begin: position,
+                end: tangent,
+                color,
+            });
+            scene
+                .drawing_context
+                .draw_wire_sphere(position, 0.2, 8, color);
+            scene
+                .drawing_context
+                .draw_wire_sphere(tangent, 0.1, 8, color);
+        }
+    }
+
+    fn activate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        self.panel
+            .sync_to_model(&mut engine.user_interface, &self.road_settings);
+
+        engine
+            .user_interface
+            .send_message(WindowMessage::open_and_align(
+                self.panel.window,
+                MessageDirection::ToWidget,
+                self.scene_viewer_frame,
+                HorizontalAlignment::Right,
+                VerticalAlignment::Top,
+                Thickness::top_right(5.0),
+                false,
+            ));
+    }
+
+    fn deactivate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        self.drag_context = None;
+        self.selected_point = None;
+
+        engine.user_interface.send_message(WindowMessage::close(
+            self.panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn on_key_down(
+        &mut self,
+        key: KeyCode,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+    ) -> bool {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return false;
+        };
+
+        if key != KeyCode::Delete {
+            return false;
+        }
+
+        let graph = &engine.scenes[game_scene.scene].graph;
+        let (Some(handle), Some(selected_point)) = (
+            selected_spline(editor_selection, graph),
+            self.selected_point,
+        ) else {
+            return false;
+        };
+
+        let Some(spline) = graph.try_get_of_type::<Spline>(handle) else {
+            return false;
+        };
+
+        let mut points = spline.points().to_vec();
+        if selected_point >= points.len() {
+            return false;
+        }
+        points.remove(selected_point);
+        self.selected_point = None;
+        self.message_sender
+            .do_scene_command(SetSplinePointsCommand::new(handle, points));
+
+        true
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+    ) {
+        if message.destination() == self.panel.inspector
+            && message.direction() == MessageDirection::FromWidget
+        {
+            if let Some(InspectorMessage::PropertyChanged(msg)) = message.data::<InspectorMessage>()
+            {
+                PropertyAction::from_field_kind(&msg.value).apply(
+                    &msg.path(),
+                    &mut self.road_settings,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+                return;
+            };
+            let graph = &mut engine.scenes[game_scene.scene].graph;
+            let Some(handle) = selected_spline(editor_selection, graph) else {
+                return;
+            };
+
+            if message.destination() == self.panel.auto_tangents {
+                if let Some(spline) = graph.try_get_of_type::<Spline>(handle) {
+                    let mut spline = spline.clone();
+                    spline.calculate_tangents();
+                    self.message_sender
+                        .do_scene_command(SetSplinePointsCommand::new(
+                            handle,
+                            spline.points().to_vec(),
+                        ));
+                }
+            } else if message.destination() == self.panel.build_road {
+                self.build_road(handle, graph);
+            }
+        }
+    }
+
+    fn on_drop(&mut self, engine: &mut Engine) {
+        engine.user_interface.send_message(WidgetMessage::remove(
+            self.panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        let spline_mode_tooltip = "Edit Spline\n\nSpline edit mode allows you to move control \
+        points and tangents of the selected spline. Hold Shift and click to add a new point, \
+        press Delete to remove the selected point. Use \"Build Road\" button to create a road \
+        along the spline.";
+
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../resources/curve.png"),
+            spline_mode_tooltip,
+            selected,
+        )
+    }
+
+    fn uuid(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
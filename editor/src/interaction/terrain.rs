@@ -1,13 +1,20 @@
 use crate::interaction::make_interaction_mode_button;
 use crate::scene::controller::SceneController;
 use crate::{
+    gui::make_dropdown_list_option,
+    inspector::editors::material::{MaterialFieldEditorBuilder, MaterialFieldMessage},
     interaction::InteractionMode,
+    localization::window_title,
     make_color_material,
     message::MessageSender,
     scene::{
-        commands::terrain::{ModifyTerrainHeightCommand, ModifyTerrainLayerMaskCommand},
+        commands::terrain::{
+            ModifyTerrainHeightCommand, ModifyTerrainHolesCommand, ModifyTerrainLayerMaskCommand,
+            SetTerrainLayerMaterialCommand,
+        },
         GameScene, Selection,
     },
+    send_sync_message,
     settings::Settings,
     MSG_SYNC_FLAG,
 };
@@ -16,6 +23,7 @@ use fyrox::core::TypeUuidProvider;
 use fyrox::graph::SceneGraph;
 use fyrox::gui::{HorizontalAlignment, Thickness, VerticalAlignment};
 use fyrox::{
+    asset::manager::ResourceManager,
     core::{
         algebra::{Matrix4, Vector2, Vector3},
         arrayvec::ArrayVec,
@@ -26,6 +34,8 @@ use fyrox::{
     },
     engine::Engine,
     gui::{
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        grid::{Column, GridBuilder, Row},
         inspector::{
             editors::{
                 enumeration::EnumPropertyEditorDefinition, PropertyEditorDefinitionContainer,
@@ -34,10 +44,13 @@ use fyrox::{
         },
         key::HotKey,
         message::{MessageDirection, UiMessage},
+        text::TextBuilder,
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, UiNode, UserInterface,
     },
+    material::MaterialResource,
+    resource::texture::{TextureResource, TextureResourceExtension},
     scene::{
         base::BaseBuilder,
         camera::Camera,
@@ -47,14 +60,15 @@ use fyrox::{
             MeshBuilder, RenderPath,
         },
         node::Node,
-        terrain::{Brush, BrushMode, BrushShape, Terrain, TerrainRayCastResult},
+        terrain::{Brush, BrushMode, BrushShape, Layer, Terrain, TerrainRayCastResult},
     },
 };
 use std::sync::Arc;
 
 pub struct TerrainInteractionMode {
     heightmaps: Vec<Vec<f32>>,
-    masks: Vec<Vec<u8>>,
+    masks: Vec<Vec<Vec<u8>>>,
+    hole_masks: Vec<Option<TextureResource>>,
     message_sender: MessageSender,
     interacting: bool,
     brush_gizmo: BrushGizmo,
@@ -74,9 +88,15 @@ impl TerrainInteractionMode {
             center: Default::default(),
             shape: BrushShape::Circle { radius: 1.0 },
             mode: BrushMode::ModifyHeightMap { amount: 1.0 },
+            falloff: 0.5,
         };
 
-        let brush_panel = BrushPanel::new(&mut engine.user_interface.build_ctx(), &brush);
+        let brush_panel = BrushPanel::new(
+            &mut engine.user_interface.build_ctx(),
+            &brush,
+            message_sender.clone(),
+            engine.resource_manager.clone(),
+        );
 
         Self {
             brush_panel,
@@ -86,6 +106,7 @@ impl TerrainInteractionMode {
             message_sender,
             brush,
             masks: Default::default(),
+            hole_masks: Default::default(),
             scene_viewer_frame,
         }
     }
@@ -125,17 +146,26 @@ impl BrushGizmo {
     }
 }
 
-fn copy_layer_masks(terrain: &Terrain, layer: usize) -> Vec<Vec<u8>> {
-    let mut masks = Vec::new();
-
-    for chunk in terrain.chunks_ref() {
-        match chunk.layer_masks.get(layer) {
-            Some(mask) => masks.push(mask.data_ref().data().to_vec()),
-            None => Log::err("layer index out of range"),
-        }
-    }
+fn copy_layer_masks(terrain: &Terrain) -> Vec<Vec<Vec<u8>>> {
+    terrain
+        .chunks_ref()
+        .iter()
+        .map(|chunk| {
+            chunk
+                .layer_masks
+                .iter()
+                .map(|mask| mask.data_ref().data().to_vec())
+                .collect()
+        })
+        .collect()
+}
 
-    masks
+fn copy_hole_masks(terrain: &Terrain) -> Vec<Option<TextureResource>> {
+    terrain
+        .chunks_ref()
+        .iter()
+        .map(|chunk| chunk.hole_mask().map(|mask| mask.deep_clone()))
+        .collect()
 }
 
 impl TypeUuidProvider for TerrainInteractionMode {
@@ -186,8 +216,11 @@ impl InteractionMode for TerrainInteractionMode {
                                 .map(|c| c.heightmap_owned())
                                 .collect();
                         }
-                        BrushMode::DrawOnMask { layer, .. } => {
-                            self.masks = copy_layer_masks(terrain, layer);
+                        BrushMode::DrawOnMask { .. } => {
+                            self.masks = copy_layer_masks(terrain);
+                        }
+                        BrushMode::DrawHoles { .. } => {
+                            self.hole_masks = copy_hole_masks(terrain);
                         }
                     }
 
@@ -234,13 +267,21 @@ impl InteractionMode for TerrainInteractionMode {
                                     ),
                                 );
                             }
-                            BrushMode::DrawOnMask { layer, .. } => {
+                            BrushMode::DrawOnMask { .. } => {
                                 self.message_sender.do_scene_command(
                                     ModifyTerrainLayerMaskCommand::new(
                                         handle,
                                         std::mem::take(&mut self.masks),
-                                        copy_layer_masks(terrain, layer),
-                                        layer,
+                                        copy_layer_masks(terrain),
+                                    ),
+                                );
+                            }
+                            BrushMode::DrawHoles { .. } => {
+                                self.message_sender.do_scene_command(
+                                    ModifyTerrainHolesCommand::new(
+                                        handle,
+                                        std::mem::take(&mut self.hole_masks),
+                                        copy_hole_masks(terrain),
                                     ),
                                 );
                             }
@@ -299,6 +340,11 @@ impl InteractionMode for TerrainInteractionMode {
                                         *height *= -1.0;
                                     }
                                 }
+                                BrushMode::DrawHoles { erase } => {
+                                    if engine.user_interface.keyboard_modifiers().shift {
+                                        *erase = !*erase;
+                                    }
+                                }
                             }
 
                             if self.interacting {
@@ -324,6 +370,31 @@ impl InteractionMode for TerrainInteractionMode {
         }
     }
 
+    fn update(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        if let Selection::Graph(selection) = editor_selection {
+            if selection.is_single_selection() {
+                let graph = &engine.scenes[game_scene.scene].graph;
+                if let Some(terrain) = graph.try_get_of_type::<Terrain>(selection.nodes()[0]) {
+                    self.brush_panel.sync_layers(
+                        &mut engine.user_interface,
+                        terrain.layers(),
+                        &self.brush,
+                    );
+                }
+            }
+        }
+    }
+
     fn activate(&mut self, controller: &dyn SceneController, engine: &mut Engine) {
         let Some(game_scene) = controller.downcast_ref::<GameScene>() else {
             return;
@@ -367,11 +438,17 @@ impl InteractionMode for TerrainInteractionMode {
         message: &UiMessage,
         editor_selection: &Selection,
         _controller: &mut dyn SceneController,
-        _engine: &mut Engine,
+        engine: &mut Engine,
     ) {
         if let Selection::Graph(selection) = editor_selection {
             if selection.is_single_selection() {
-                self.brush_panel.handle_ui_message(message, &mut self.brush);
+                self.brush_panel.handle_ui_message(
+                    message,
+                    &mut self.brush,
+                    selection.nodes()[0],
+                    &self.message_sender,
+                    &mut engine.user_interface,
+                );
             }
         }
     }
@@ -436,6 +513,7 @@ impl InteractionMode for TerrainInteractionMode {
                     *height -= 0.01;
                 }
                 BrushMode::DrawOnMask { alpha, .. } => modify_clamp(alpha, -0.01, 0.0, 1.0),
+                BrushMode::DrawHoles { .. } => (),
             }
             processed = true;
         } else if hotkey == &key_bindings.increase_brush_opacity {
@@ -447,6 +525,7 @@ impl InteractionMode for TerrainInteractionMode {
                     *height += 0.01;
                 }
                 BrushMode::DrawOnMask { alpha, .. } => modify_clamp(alpha, 0.01, 0.0, 1.0),
+                BrushMode::DrawHoles { .. } => (),
             }
             processed = true;
         } else if hotkey == &key_bindings.prev_layer {
@@ -490,6 +569,14 @@ impl InteractionMode for TerrainInteractionMode {
 struct BrushPanel {
     window: Handle<UiNode>,
     inspector: Handle<UiNode>,
+    layers: Handle<UiNode>,
+    material: Handle<UiNode>,
+    layer_names: Vec<String>,
+    layer_material: Option<MaterialResource>,
+}
+
+fn make_layer_name(index: usize, layer: &Layer) -> String {
+    format!("Layer {} - {}", index, layer.material.kind())
 }
 
 fn make_brush_mode_enum_property_editor_definition() -> EnumPropertyEditorDefinition<BrushMode> {
@@ -501,18 +588,21 @@ fn make_brush_mode_enum_property_editor_definition() -> EnumPropertyEditorDefini
                 alpha: 1.0,
             },
             2 => BrushMode::FlattenHeightMap { height: 0.0 },
+            3 => BrushMode::DrawHoles { erase: false },
             _ => unreachable!(),
         },
         index_generator: |v| match v {
             BrushMode::ModifyHeightMap { .. } => 0,
             BrushMode::DrawOnMask { .. } => 1,
             BrushMode::FlattenHeightMap { .. } => 2,
+            BrushMode::DrawHoles { .. } => 3,
         },
         names_generator: || {
             vec![
                 "Modify Height Map".to_string(),
                 "Draw On Mask".to_string(),
                 "Flatten Height Map".to_string(),
+                "Draw Holes".to_string(),
             ]
         },
     }
@@ -537,7 +627,12 @@ fn make_brush_shape_enum_property_editor_definition() -> EnumPropertyEditorDefin
 }
 
 impl BrushPanel {
-    fn new(ctx: &mut BuildContext, brush: &Brush) -> Self {
+    fn new(
+        ctx: &mut BuildContext,
+        brush: &Brush,
+        sender: MessageSender,
+        resource_manager: ResourceManager,
+    ) -> Self {
         let property_editors = PropertyEditorDefinitionContainer::new();
         property_editors.insert(make_brush_mode_enum_property_editor_definition());
         property_editors.insert(make_brush_shape_enum_property_editor_definition());
@@ -554,20 +649,86 @@ impl BrushPanel {
         );
 
         let inspector;
-        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(150.0))
+        let layers;
+        let material;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(250.0))
             .can_minimize(false)
             .can_maximize(false)
-            .with_content({
-                inspector = InspectorBuilder::new(WidgetBuilder::new())
-                    .with_context(context)
-                    .build(ctx);
-                inspector
-            })
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            inspector = InspectorBuilder::new(WidgetBuilder::new().on_row(0))
+                                .with_context(context)
+                                .build(ctx);
+                            inspector
+                        })
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("Paint Layer")
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            layers = DropdownListBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            layers
+                        })
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("Layer Material")
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            material = MaterialFieldEditorBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .on_column(1)
+                                    .with_enabled(false)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(
+                                ctx,
+                                sender,
+                                Default::default(),
+                                resource_manager,
+                            );
+                            material
+                        }),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(22.0))
+                .add_row(Row::auto())
+                .add_column(Column::strict(100.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
             .open(false)
-            .with_title(WindowTitle::text("Brush Options"))
+            .with_title(window_title("Brush Options", ctx))
             .build(ctx);
 
-        Self { window, inspector }
+        Self {
+            window,
+            inspector,
+            layers,
+            material,
+            layer_names: Default::default(),
+            layer_material: None,
+        }
     }
 
     fn sync_to_model(&self, ui: &mut UserInterface, brush: &Brush) {
@@ -584,9 +745,73 @@ impl BrushPanel {
                 format!("Failed to sync BrushPanel's inspector. Reason: {:?}", e),
             )
         }
+
+        let selected_layer = match brush.mode {
+            BrushMode::DrawOnMask { layer, .. } => Some(layer),
+            _ => None,
+        };
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(self.layers, MessageDirection::ToWidget, selected_layer),
+        );
+    }
+
+    /// Syncs the list of layers and the material of the painted layer with the given terrain
+    /// layers. Does nothing if nothing has changed.
+    fn sync_layers(&mut self, ui: &mut UserInterface, layers: &[Layer], brush: &Brush) {
+        let layer_names = layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| make_layer_name(index, layer))
+            .collect::<Vec<_>>();
+        if layer_names != self.layer_names {
+            let items = layer_names
+                .iter()
+                .map(|name| make_dropdown_list_option(&mut ui.build_ctx(), name))
+                .collect();
+            send_sync_message(
+                ui,
+                DropdownListMessage::items(self.layers, MessageDirection::ToWidget, items),
+            );
+            self.layer_names = layer_names;
+            self.sync_to_model(ui, brush);
+        }
+
+        let layer_material = match brush.mode {
+            BrushMode::DrawOnMask { layer, .. } => layers.get(layer).map(|l| l.material.clone()),
+            _ => None,
+        };
+        if layer_material != self.layer_material {
+            if let Some(material) = layer_material.clone() {
+                send_sync_message(
+                    ui,
+                    MaterialFieldMessage::material(
+                        self.material,
+                        MessageDirection::ToWidget,
+                        material,
+                    ),
+                );
+            }
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(
+                    self.material,
+                    MessageDirection::ToWidget,
+                    layer_material.is_some(),
+                ),
+            );
+            self.layer_material = layer_material;
+        }
     }
 
-    fn handle_ui_message(&self, message: &UiMessage, brush: &mut Brush) -> Option<()> {
+    fn handle_ui_message(
+        &self,
+        message: &UiMessage,
+        brush: &mut Brush,
+        terrain: Handle<Node>,
+        sender: &MessageSender,
+        ui: &mut UserInterface,
+    ) -> Option<()> {
         if message.destination() == self.inspector
             && message.direction() == MessageDirection::FromWidget
         {
@@ -600,6 +825,38 @@ impl BrushPanel {
                     },
                 );
             }
+        } else if message.destination() == self.layers
+            && message.direction() == MessageDirection::FromWidget
+            && message.flags != MSG_SYNC_FLAG
+        {
+            if let Some(DropdownListMessage::SelectionChanged(Some(layer))) = message.data() {
+                let alpha = match brush.mode {
+                    BrushMode::DrawOnMask { alpha, .. } => alpha,
+                    _ => 1.0,
+                };
+                brush.mode = BrushMode::DrawOnMask {
+                    layer: *layer,
+                    alpha,
+                };
+                self.sync_to_model(ui, brush);
+            }
+        } else if message.destination() == self.material
+            && message.direction() == MessageDirection::FromWidget
+            && message.flags != MSG_SYNC_FLAG
+        {
+            if let (
+                Some(MaterialFieldMessage::Material(material)),
+                BrushMode::DrawOnMask { layer, .. },
+            ) = (message.data(), &brush.mode)
+            {
+                if self.layer_material.as_ref() != Some(material) {
+                    sender.do_scene_command(SetTerrainLayerMaterialCommand::new(
+                        terrain,
+                        *layer,
+                        material.clone(),
+                    ));
+                }
+            }
         }
         Some(())
     }
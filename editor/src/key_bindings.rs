@@ -0,0 +1,246 @@
+//! Key bindings window allows to rebind every editor action, to choose a context in which a hot key
+//! of an action works and shows conflicting hot keys.
+
+use crate::{
+    action::{find_conflicts, rebind, Action},
+    gui::make_dropdown_list_option,
+    localization::window_title,
+    settings::{keys::HotKeyContext, Settings},
+};
+use fyrox::{
+    core::{color::Color, log::Log, pool::Handle},
+    gui::{
+        brush::Brush,
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        grid::{Column, GridBuilder, Row},
+        key::{HotKey, HotKeyEditorBuilder, HotKeyEditorMessage},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+use std::str::FromStr;
+use strum::VariantNames;
+
+struct ActionRow {
+    hot_key: Handle<UiNode>,
+    context: Handle<UiNode>,
+    conflicts: Handle<UiNode>,
+}
+
+pub struct KeyBindingsWindow {
+    pub window: Handle<UiNode>,
+    scroll_viewer: Handle<UiNode>,
+    actions: Vec<Action>,
+    rows: Vec<ActionRow>,
+}
+
+fn make_row(action: &Action, ctx: &mut BuildContext) -> (Handle<UiNode>, ActionRow) {
+    let hot_key;
+    let context;
+    let conflicts;
+    let grid = GridBuilder::new(
+        WidgetBuilder::new()
+            .with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(1.0))
+                        .on_column(0),
+                )
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text(&action.name)
+                .build(ctx),
+            )
+            .with_child({
+                hot_key = HotKeyEditorBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(1.0))
+                        .on_column(1),
+                )
+                .with_value(action.hot_key.clone())
+                .build(ctx);
+                hot_key
+            })
+            .with_child({
+                context = DropdownListBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(1.0))
+                        .on_column(2),
+                )
+                .with_items(
+                    HotKeyContext::VARIANTS
+                        .iter()
+                        .map(|name| make_dropdown_list_option(ctx, name))
+                        .collect(),
+                )
+                .with_selected(
+                    HotKeyContext::VARIANTS
+                        .iter()
+                        .position(|name| *name == action.context.as_ref())
+                        .unwrap_or_default(),
+                )
+                .build(ctx);
+                context
+            })
+            .with_child({
+                conflicts = TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_foreground(Brush::Solid(Color::RED))
+                        .on_column(3),
+                )
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .build(ctx);
+                conflicts
+            }),
+    )
+    .add_row(Row::strict(24.0))
+    .add_column(Column::strict(200.0))
+    .add_column(Column::strict(150.0))
+    .add_column(Column::strict(100.0))
+    .add_column(Column::stretch())
+    .build(ctx);
+
+    (
+        grid,
+        ActionRow {
+            hot_key,
+            context,
+            conflicts,
+        },
+    )
+}
+
+impl KeyBindingsWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let scroll_viewer;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(700.0).with_height(500.0))
+            .open(false)
+            .with_title(window_title("Key Bindings", ctx))
+            .with_content({
+                scroll_viewer = ScrollViewerBuilder::new(
+                    WidgetBuilder::new().with_margin(Thickness::uniform(2.0)),
+                )
+                .build(ctx);
+                scroll_viewer
+            })
+            .build(ctx);
+
+        Self {
+            window,
+            scroll_viewer,
+            actions: Default::default(),
+            rows: Default::default(),
+        }
+    }
+
+    /// Opens the window with the given set of actions.
+    pub fn open(&mut self, actions: Vec<Action>, ui: &mut UserInterface) {
+        let ctx = &mut ui.build_ctx();
+        let (children, rows): (Vec<_>, Vec<_>) =
+            actions.iter().map(|action| make_row(action, ctx)).unzip();
+        let panel = StackPanelBuilder::new(WidgetBuilder::new().with_children(children)).build(ctx);
+
+        self.actions = actions;
+        self.rows = rows;
+
+        ui.send_message(ScrollViewerMessage::content(
+            self.scroll_viewer,
+            MessageDirection::ToWidget,
+            panel,
+        ));
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+
+        self.sync_conflicts(ui);
+    }
+
+    fn sync_conflicts(&self, ui: &UserInterface) {
+        let mut conflicts = vec![Vec::new(); self.actions.len()];
+        for (a, b) in find_conflicts(&self.actions) {
+            conflicts[a].push(self.actions[b].name.as_str());
+            conflicts[b].push(self.actions[a].name.as_str());
+        }
+
+        for (row, names) in self.rows.iter().zip(conflicts) {
+            let text = if names.is_empty() {
+                String::new()
+            } else {
+                format!("Conflicts with {}", names.join(", "))
+            };
+            ui.send_message(TextMessage::text(
+                row.conflicts,
+                MessageDirection::ToWidget,
+                text,
+            ));
+        }
+    }
+
+    fn apply_binding(&mut self, index: usize, settings: &mut Settings, ui: &UserInterface) {
+        let action = &self.actions[index];
+        rebind(
+            action,
+            action.hot_key.clone(),
+            action.context,
+            &mut settings.key_bindings,
+        );
+
+        if let Some(other) = self.actions.iter().enumerate().find(|(i, other)| {
+            *i != index
+                && action.hot_key == other.hot_key
+                && action.context == other.context
+                && other.hot_key != HotKey::NotSet
+        }) {
+            Log::warn(format!(
+                "Hot key {} of {} conflicts with {}.",
+                action.hot_key, action.name, other.1.name
+            ));
+        }
+
+        self.sync_conflicts(ui);
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        settings: &mut Settings,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(HotKeyEditorMessage::Value(hot_key)) = message.data() {
+            if let Some(index) = self
+                .rows
+                .iter()
+                .position(|row| row.hot_key == message.destination())
+            {
+                self.actions[index].hot_key = hot_key.clone();
+                self.apply_binding(index, settings, ui);
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(selection))) = message.data()
+        {
+            if let Some(index) = self
+                .rows
+                .iter()
+                .position(|row| row.context == message.destination())
+            {
+                if let Some(context) = HotKeyContext::VARIANTS
+                    .get(*selection)
+                    .and_then(|name| HotKeyContext::from_str(name).ok())
+                {
+                    self.actions[index].context = context;
+                    self.apply_binding(index, settings, ui);
+                }
+            }
+        }
+    }
+}
@@ -10,19 +10,24 @@
 extern crate lazy_static;
 
 pub mod absm;
+pub mod action;
 pub mod animation;
 pub mod asset;
 pub mod audio;
 pub mod build;
 pub mod camera;
 pub mod command;
+pub mod command_palette;
 pub mod configurator;
 pub mod curve_editor;
 pub mod gui;
 pub mod highlight;
+pub mod hot_reload;
 pub mod inspector;
 pub mod interaction;
+pub mod key_bindings;
 pub mod light;
+pub mod localization;
 pub mod log;
 pub mod material;
 pub mod menu;
@@ -30,28 +35,37 @@ pub mod message;
 pub mod overlay;
 pub mod particle;
 pub mod physics;
+pub mod play;
 pub mod plugin;
 pub mod preview;
 pub mod scene;
 pub mod scene_viewer;
+pub mod script_messages;
 pub mod settings;
+pub mod stats;
 pub mod ui_scene;
 pub mod utils;
+pub mod validation;
+pub mod visual_script;
 pub mod world;
 
 use crate::{
     absm::AbsmEditor,
+    action::{find_action_by_hot_key, Action, ActionKind, ActionRegistry, BuiltinAction},
     animation::AnimationEditor,
-    asset::{item::AssetItem, AssetBrowser},
-    audio::{preview::AudioPreviewPanel, AudioPanel},
+    asset::{item::AssetItem, preview::AssetPreview, AssetBrowser},
+    audio::{event_bank::AudioEventBankEditor, preview::AudioPreviewPanel, AudioPanel},
     build::BuildWindow,
     camera::panel::CameraPreviewControlPanel,
     command::{panel::CommandStackViewer, GameSceneCommandTrait},
+    command_palette::CommandPalette,
     configurator::Configurator,
     curve_editor::CurveEditorWindow,
     highlight::HighlightRenderPass,
+    hot_reload::GameLibraryBuilder,
     inspector::Inspector,
     interaction::{
+        joint::JointInteractionMode,
         move_mode::MoveInteractionMode,
         navmesh::{EditNavmeshMode, NavmeshPanel},
         rotate_mode::RotateInteractionMode,
@@ -59,6 +73,7 @@ use crate::{
         select_mode::SelectInteractionMode,
         terrain::TerrainInteractionMode,
     },
+    key_bindings::KeyBindingsWindow,
     light::LightPanel,
     log::LogPanel,
     material::MaterialEditor,
@@ -67,10 +82,13 @@ use crate::{
     overlay::OverlayRenderPass,
     particle::ParticleSystemPreviewControlPanel,
     physics::ColliderControlPanel,
+    play::PlaySession,
     plugin::EditorPlugin,
     scene::{
+        clipboard::Clipboard,
         commands::{
-            make_delete_selection_command, ChangeSelectionCommand, GameSceneCommand,
+            make_delete_selection_command, make_group_selection_command,
+            make_ungroup_selection_command, ChangeSelectionCommand, GameSceneCommand,
             GameSceneContext, PasteCommand,
         },
         container::{EditorSceneEntry, SceneContainer},
@@ -79,18 +97,28 @@ use crate::{
         GameScene, Selection,
     },
     scene_viewer::SceneViewer,
-    settings::Settings,
+    script_messages::ScriptMessagesPanel,
+    settings::{keys::HotKeyContext, Settings},
+    stats::StatisticsWindow,
     ui_scene::{
         commands::graph::PasteWidgetCommand, commands::UiSceneCommand, menu::WidgetContextMenu,
         utils::UiSceneWorldViewerDataProvider, UiScene,
     },
-    utils::{doc::DocWindow, path_fixer::PathFixer, ragdoll::RagdollWizard},
+    utils::{
+        bulk_rename::BulkRenameWindow, doc::DocWindow, find_replace::FindReplaceWindow,
+        path_fixer::PathFixer, ragdoll::RagdollWizard,
+    },
+    validation::ValidationPanel,
+    visual_script::VisualScriptEditor,
     world::{graph::menu::SceneNodeContextMenu, graph::EditorSceneWrapper, WorldViewer},
 };
 use fyrox::dpi::LogicalSize;
 use fyrox::graph::SceneGraph;
 use fyrox::{
-    asset::{io::FsResourceIo, manager::ResourceManager, untyped::UntypedResource},
+    asset::{
+        io::FsResourceIo, loader::ResourceLoader, manager::ResourceManager,
+        untyped::UntypedResource,
+    },
     core::{
         algebra::{Matrix3, Vector2},
         color::Color,
@@ -121,10 +149,12 @@ use fyrox::{
         font::Font,
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
+        inspector::editors::PropertyEditorDefinition,
         key::HotKey,
         message::{MessageDirection, UiMessage},
         messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage, MessageBoxResult},
         text::TextBuilder,
+        text_box::TextBox,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, UiNode, UserInterface, VerticalAlignment,
@@ -139,6 +169,7 @@ use fyrox::{
         TextureResource, TextureResourceExtension,
     },
     scene::{graph::GraphUpdateSwitches, mesh::Mesh, Scene, SceneLoader},
+    script::ScriptMessageTrafficEntry,
     utils::{translate_cursor_icon, translate_event},
     window::{Icon, WindowAttributes},
 };
@@ -263,6 +294,8 @@ pub enum Mode {
     Play {
         process: std::process::Child,
         active: Arc<AtomicBool>,
+        library_builder: Option<GameLibraryBuilder>,
+        session: PlaySession,
     },
 }
 
@@ -488,11 +521,19 @@ pub struct Editor {
     pub configurator: Configurator,
     pub log: LogPanel,
     pub command_stack_viewer: CommandStackViewer,
+    pub statistics_window: StatisticsWindow,
+    pub script_messages_panel: ScriptMessagesPanel,
+    pub validation_panel: ValidationPanel,
+    pub command_palette: CommandPalette,
+    pub key_bindings_window: KeyBindingsWindow,
+    pub actions: ActionRegistry,
     pub validation_message_box: Handle<UiNode>,
     pub navmesh_panel: NavmeshPanel,
     pub settings: Settings,
     pub path_fixer: PathFixer,
     pub material_editor: MaterialEditor,
+    pub audio_event_bank_editor: AudioEventBankEditor,
+    pub visual_script_editor: VisualScriptEditor,
     pub inspector: Inspector,
     pub curve_editor: CurveEditorWindow,
     pub audio_panel: AudioPanel,
@@ -500,6 +541,9 @@ pub struct Editor {
     pub mode: Mode,
     pub build_window: BuildWindow,
     pub build_profile: BuildProfile,
+    /// Whether the transform changes made by the game in play mode should be applied to the edited
+    /// scene when the play mode is stopped.
+    pub keep_play_mode_changes: bool,
     pub scene_settings: SceneSettingsWindow,
     pub animation_editor: AnimationEditor,
     pub particle_system_control_panel: ParticleSystemPreviewControlPanel,
@@ -514,12 +558,16 @@ pub struct Editor {
     pub update_loop_state: UpdateLoopState,
     pub is_suspended: bool,
     pub ragdoll_wizard: RagdollWizard,
+    pub find_replace_window: FindReplaceWindow,
+    pub bulk_rename_window: BulkRenameWindow,
     pub scene_node_context_menu: Rc<RefCell<SceneNodeContextMenu>>,
     pub widget_context_menu: Rc<RefCell<WidgetContextMenu>>,
     pub widget_constructors: Arc<WidgetConstructorContainer>,
     pub collider_control_panel: ColliderControlPanel,
     pub overlay_pass: Option<Rc<RefCell<OverlayRenderPass>>>,
     pub highlighter: Option<Rc<RefCell<HighlightRenderPass>>>,
+    /// Clipboard shared by every game scene.
+    pub clipboard: Clipboard,
 }
 
 impl Editor {
@@ -584,6 +632,11 @@ impl Editor {
                     .unwrap();
         }
 
+        // Set the style before building any widgets, so they will take their look from it right away.
+        engine
+            .user_interface
+            .set_style(settings.general.style.make_style());
+
         let configurator = Configurator::new(
             message_sender.clone(),
             &mut engine.user_interface.build_ctx(),
@@ -601,6 +654,11 @@ impl Editor {
         let widget_context_menu = Rc::new(RefCell::new(WidgetContextMenu::new(ctx)));
         let world_outliner = WorldViewer::new(ctx, message_sender.clone(), &settings);
         let command_stack_viewer = CommandStackViewer::new(ctx, message_sender.clone());
+        let statistics_window = StatisticsWindow::new(ctx);
+        let script_messages_panel = ScriptMessagesPanel::new(ctx);
+        let validation_panel = ValidationPanel::new(ctx);
+        let command_palette = CommandPalette::new(ctx);
+        let key_bindings_window = KeyBindingsWindow::new(ctx);
         let log = LogPanel::new(ctx, log_message_receiver);
         let inspector = Inspector::new(ctx, message_sender.clone());
         let animation_editor = AnimationEditor::new(ctx);
@@ -613,6 +671,8 @@ impl Editor {
         let doc_window = DocWindow::new(ctx);
         let node_removal_dialog = NodeRemovalDialog::new(ctx);
         let ragdoll_wizard = RagdollWizard::new(ctx, message_sender.clone());
+        let find_replace_window = FindReplaceWindow::new(ctx);
+        let bulk_rename_window = BulkRenameWindow::new(ctx);
 
         let docking_manager;
         let root_grid = GridBuilder::new(
@@ -742,6 +802,9 @@ impl Editor {
                             navmesh_panel.window,
                             doc_window.window,
                             light_panel.window,
+                            statistics_window.window,
+                            script_messages_panel.window,
+                            validation_panel.window,
                         ])
                         .build(ctx);
                     docking_manager
@@ -784,6 +847,20 @@ impl Editor {
 
         let material_editor = MaterialEditor::new(&mut engine, message_sender.clone());
 
+        let audio_event_bank_editor = AudioEventBankEditor::new(
+            &mut engine.user_interface.build_ctx(),
+            message_sender.clone(),
+        );
+
+        let visual_script_editor = VisualScriptEditor::new(
+            &mut engine.user_interface.build_ctx(),
+            message_sender.clone(),
+        );
+
+        // Translate every widget of the editor, widgets that are created later will use translated
+        // strings right away.
+        localization::apply_settings(&settings.general, &mut engine.user_interface);
+
         if let Some(layout) = settings.windows.layout.as_ref() {
             engine
                 .user_interface
@@ -813,15 +890,24 @@ impl Editor {
             log,
             light_panel,
             command_stack_viewer,
+            statistics_window,
+            script_messages_panel,
+            validation_panel,
+            command_palette,
+            key_bindings_window,
+            actions: Default::default(),
             validation_message_box,
             settings,
             path_fixer,
             material_editor,
+            audio_event_bank_editor,
+            visual_script_editor,
             inspector,
             curve_editor,
             audio_panel,
             save_scene_dialog,
             mode: Mode::Edit,
+            keep_play_mode_changes: false,
             game_loop_data: GameLoopData {
                 clock: Instant::now(),
                 lag: 0.0,
@@ -843,12 +929,15 @@ impl Editor {
             update_loop_state: UpdateLoopState::default(),
             is_suspended: false,
             ragdoll_wizard,
+            find_replace_window,
+            bulk_rename_window,
             scene_node_context_menu,
             widget_constructors: Arc::new(WidgetConstructorContainer::new()),
             widget_context_menu,
             collider_control_panel,
             overlay_pass: None,
             highlighter: None,
+            clipboard: Default::default(),
         };
 
         if let Some(data) = startup_data {
@@ -884,6 +973,13 @@ impl Editor {
         match Settings::load() {
             Ok(settings) => {
                 self.settings = settings;
+                self.engine
+                    .user_interface
+                    .set_style(self.settings.general.style.make_style());
+                localization::apply_settings(
+                    &self.settings.general,
+                    &mut self.engine.user_interface,
+                );
 
                 Log::info("Editor settings were reloaded successfully!");
             }
@@ -946,171 +1042,271 @@ impl Editor {
         self.on_scene_changed();
     }
 
-    pub fn handle_hotkeys(&mut self, message: &UiMessage) {
-        // A message could be handled already somewhere else (for example in a TextBox or any other
-        // widget, that handles keyboard input), we must not respond to such messages.
-        if message.handled() {
-            return;
+    /// Returns a list of hot key contexts, that are active for the given message, sorted by their
+    /// priority.
+    fn hot_key_contexts(&self, message: &UiMessage) -> Vec<HotKeyContext> {
+        let ui = &self.engine.user_interface;
+        let focus = ui.keyboard_focus_node();
+        let text_editing = ui
+            .try_get(focus)
+            .map_or(false, |n| n.has_component::<TextBox>());
+
+        if text_editing {
+            // Text editing bindings are always active in a text field, global bindings only work if
+            // the text field ignored the key.
+            if message.handled() {
+                vec![HotKeyContext::TextEditing]
+            } else {
+                vec![HotKeyContext::TextEditing, HotKeyContext::Global]
+            }
+        } else if message.handled() {
+            // A message could be handled already somewhere else (for example in any widget, that
+            // handles keyboard input), we must not respond to such messages.
+            vec![]
+        } else if focus.is_some() && ui.is_node_child_of(focus, self.scene_viewer.window()) {
+            vec![HotKeyContext::Viewport, HotKeyContext::Global]
+        } else {
+            vec![HotKeyContext::Global]
         }
+    }
 
+    pub fn handle_hotkeys(&mut self, message: &UiMessage) {
         let modifiers = self.engine.user_interface.keyboard_modifiers();
-        let sender = self.message_sender.clone();
-        let engine = &mut self.engine;
 
         if let Some(WidgetMessage::KeyDown(key)) = message.data() {
+            let contexts = self.hot_key_contexts(message);
+            if contexts.is_empty() {
+                return;
+            }
+
             let hot_key = HotKey::Some {
                 code: *key,
                 modifiers,
             };
 
             let mut processed = false;
-            if let Some(scene) = self.scenes.current_scene_entry_mut() {
+            if message.handled() {
+                // Interaction modes must not react to keys, that were handled by a text field.
+            } else if let Some(scene) = self.scenes.current_scene_entry_mut() {
                 if let Some(current_interaction_mode) = scene.current_interaction_mode {
                     processed |= scene
                         .interaction_modes
                         .map
                         .get_mut(&current_interaction_mode)
                         .unwrap()
-                        .on_hot_key(&hot_key, &mut *scene.controller, engine, &self.settings);
+                        .on_hot_key(
+                            &hot_key,
+                            &mut *scene.controller,
+                            &mut self.engine,
+                            &self.settings,
+                        );
                 }
             }
 
             if !processed {
-                let key_bindings = &self.settings.key_bindings;
-
-                if hot_key == key_bindings.redo {
-                    sender.send(Message::RedoCurrentSceneCommand);
-                } else if hot_key == key_bindings.undo {
-                    sender.send(Message::UndoCurrentSceneCommand);
-                } else if hot_key == key_bindings.enable_select_mode {
-                    sender.send(Message::SetInteractionMode(
-                        SelectInteractionMode::type_uuid(),
-                    ));
-                } else if hot_key == key_bindings.enable_move_mode {
-                    sender.send(Message::SetInteractionMode(MoveInteractionMode::type_uuid()));
-                } else if hot_key == key_bindings.enable_rotate_mode {
-                    sender.send(Message::SetInteractionMode(
-                        RotateInteractionMode::type_uuid(),
-                    ));
-                } else if hot_key == key_bindings.enable_scale_mode {
-                    sender.send(Message::SetInteractionMode(
-                        ScaleInteractionMode::type_uuid(),
-                    ));
-                } else if hot_key == key_bindings.enable_navmesh_mode {
-                    sender.send(Message::SetInteractionMode(EditNavmeshMode::type_uuid()));
-                } else if hot_key == key_bindings.enable_terrain_mode {
-                    sender.send(Message::SetInteractionMode(
-                        TerrainInteractionMode::type_uuid(),
-                    ));
-                } else if hot_key == key_bindings.load_scene {
-                    sender.send(Message::OpenLoadSceneDialog);
-                } else if hot_key == key_bindings.save_scene {
-                    if let Some(entry) = self.scenes.current_scene_entry_ref() {
-                        if let Some(path) = entry.path.as_ref() {
-                            self.message_sender.send(Message::SaveScene {
-                                id: entry.id,
-                                path: path.clone(),
-                            });
-                        } else {
-                            self.message_sender.send(Message::OpenSaveSceneDialog {
-                                default_file_name: entry.default_file_name(),
-                            });
-                        }
+                let actions = self.collect_actions();
+                if let Some(action) = find_action_by_hot_key(&actions, &hot_key, &contexts) {
+                    match action.kind {
+                        ActionKind::Plugin => self
+                            .message_sender
+                            .send(Message::ExecuteAction(action.clone())),
+                        _ => self.execute_action(action),
                     }
-                } else if hot_key == key_bindings.copy_selection {
-                    if let Some(entry) = self.scenes.current_scene_entry_mut() {
-                        if let Selection::Graph(graph_selection) = &entry.selection {
-                            if let Some(game_scene) = entry.controller.downcast_mut::<GameScene>() {
-                                game_scene.clipboard.fill_from_selection(
-                                    graph_selection,
-                                    game_scene.scene,
-                                    engine,
-                                );
-                            } else if let Some(ui_scene) =
-                                entry.controller.downcast_mut::<UiScene>()
-                            {
-                                if let Selection::Ui(ref selection) = entry.selection {
-                                    ui_scene
-                                        .clipboard
-                                        .fill_from_selection(selection, &ui_scene.ui);
-                                }
-                            }
-                        }
+                }
+            }
+        }
+    }
+
+    /// Collects every action, that could be executed from the command palette.
+    pub fn collect_actions(&self) -> Vec<Action> {
+        self.actions.collect(
+            self.menu.menu,
+            &self.engine.user_interface,
+            &self.settings.key_bindings,
+        )
+    }
+
+    /// Registers an action of an editor plugin, it will be shown in the command palette and could be
+    /// executed via the given hot key. Execution of the action sends [`Message::ExecuteAction`], that
+    /// could be caught in [`EditorPlugin::on_message`].
+    pub fn register_action(&mut self, id: &str, name: &str, hot_key: HotKey) {
+        self.actions.register_plugin_action(id, name, hot_key);
+    }
+
+    pub fn execute_action(&mut self, action: &Action) {
+        match action.kind {
+            ActionKind::Builtin(builtin) => self.execute_builtin_action(builtin),
+            ActionKind::MenuItem(_) => {
+                action.execute_in_ui(&self.engine.user_interface);
+            }
+            // Plugins handle their actions by themselves.
+            ActionKind::Plugin => (),
+        }
+    }
+
+    pub fn execute_builtin_action(&mut self, action: BuiltinAction) {
+        let sender = self.message_sender.clone();
+        let engine = &mut self.engine;
+
+        match action {
+            BuiltinAction::Redo => sender.send(Message::RedoCurrentSceneCommand),
+            BuiltinAction::Undo => sender.send(Message::UndoCurrentSceneCommand),
+            BuiltinAction::EnableSelectMode => sender.send(Message::SetInteractionMode(
+                SelectInteractionMode::type_uuid(),
+            )),
+            BuiltinAction::EnableMoveMode => {
+                sender.send(Message::SetInteractionMode(MoveInteractionMode::type_uuid()))
+            }
+            BuiltinAction::EnableRotateMode => sender.send(Message::SetInteractionMode(
+                RotateInteractionMode::type_uuid(),
+            )),
+            BuiltinAction::EnableScaleMode => sender.send(Message::SetInteractionMode(
+                ScaleInteractionMode::type_uuid(),
+            )),
+            BuiltinAction::EnableNavmeshMode => {
+                sender.send(Message::SetInteractionMode(EditNavmeshMode::type_uuid()))
+            }
+            BuiltinAction::EnableTerrainMode => sender.send(Message::SetInteractionMode(
+                TerrainInteractionMode::type_uuid(),
+            )),
+            BuiltinAction::EnableJointMode => sender.send(Message::SetInteractionMode(
+                JointInteractionMode::type_uuid(),
+            )),
+            BuiltinAction::LoadScene => sender.send(Message::OpenLoadSceneDialog),
+            BuiltinAction::SaveScene => {
+                if let Some(entry) = self.scenes.current_scene_entry_ref() {
+                    if let Some(path) = entry.path.as_ref() {
+                        sender.send(Message::SaveScene {
+                            id: entry.id,
+                            path: path.clone(),
+                        });
+                    } else {
+                        sender.send(Message::OpenSaveSceneDialog {
+                            default_file_name: entry.default_file_name(),
+                        });
                     }
-                } else if hot_key == key_bindings.paste {
-                    if let Some(controller) = self.scenes.current_scene_controller_mut() {
-                        if let Some(game_scene) = controller.downcast_mut::<GameScene>() {
-                            if !game_scene.clipboard.is_empty() {
-                                sender.do_scene_command(PasteCommand::new(
-                                    game_scene.scene_content_root,
-                                ));
-                            }
-                        } else if let Some(ui_scene) = controller.downcast_mut::<UiScene>() {
-                            if !ui_scene.clipboard.is_empty() {
-                                sender.do_ui_scene_command(PasteWidgetCommand::new(
-                                    ui_scene.ui.root(),
-                                ));
+                }
+            }
+            BuiltinAction::CopySelection => {
+                if let Some(entry) = self.scenes.current_scene_entry_mut() {
+                    if let Selection::Graph(graph_selection) = &entry.selection {
+                        if let Some(game_scene) = entry.controller.downcast_mut::<GameScene>() {
+                            game_scene.clipboard.fill_from_selection(
+                                graph_selection,
+                                game_scene.scene,
+                                engine,
+                            );
+                        } else if let Some(ui_scene) = entry.controller.downcast_mut::<UiScene>() {
+                            if let Selection::Ui(ref selection) = entry.selection {
+                                ui_scene
+                                    .clipboard
+                                    .fill_from_selection(selection, &ui_scene.ui);
                             }
                         }
                     }
-                } else if hot_key == key_bindings.new_scene {
-                    sender.send(Message::NewScene);
-                } else if hot_key == key_bindings.close_scene {
-                    if let Some(entry) = self.scenes.current_scene_entry_ref() {
-                        sender.send(Message::CloseScene(entry.id));
+                }
+            }
+            BuiltinAction::Paste => {
+                if let Some(controller) = self.scenes.current_scene_controller_mut() {
+                    if let Some(game_scene) = controller.downcast_mut::<GameScene>() {
+                        if !game_scene.clipboard.is_empty() {
+                            sender
+                                .do_scene_command(PasteCommand::new(game_scene.scene_content_root));
+                        }
+                    } else if let Some(ui_scene) = controller.downcast_mut::<UiScene>() {
+                        if !ui_scene.clipboard.is_empty() {
+                            sender.do_ui_scene_command(PasteWidgetCommand::new(ui_scene.ui.root()));
+                        }
                     }
-                } else if hot_key == key_bindings.remove_selection {
-                    if let Some(entry) = self.scenes.current_scene_entry_mut() {
-                        if !entry.selection.is_empty() {
-                            match entry.selection {
-                                Selection::Graph(_) => {
-                                    if let Some(game_scene) =
-                                        entry.controller.downcast_mut::<GameScene>()
+                }
+            }
+            BuiltinAction::NewScene => sender.send(Message::NewScene),
+            BuiltinAction::CloseScene => {
+                if let Some(entry) = self.scenes.current_scene_entry_ref() {
+                    sender.send(Message::CloseScene(entry.id));
+                }
+            }
+            BuiltinAction::RemoveSelection => {
+                if let Some(entry) = self.scenes.current_scene_entry_mut() {
+                    if !entry.selection.is_empty() {
+                        match entry.selection {
+                            Selection::Graph(_) => {
+                                if let Some(game_scene) =
+                                    entry.controller.downcast_mut::<GameScene>()
+                                {
+                                    if self.settings.general.show_node_removal_dialog
+                                        && game_scene.is_current_selection_has_external_refs(
+                                            &entry.selection,
+                                            &engine.scenes[game_scene.scene].graph,
+                                        )
                                     {
-                                        if self.settings.general.show_node_removal_dialog
-                                            && game_scene.is_current_selection_has_external_refs(
+                                        sender.send(Message::OpenNodeRemovalDialog);
+                                    } else {
+                                        sender.send(Message::DoGameSceneCommand(
+                                            make_delete_selection_command(
                                                 &entry.selection,
-                                                &engine.scenes[game_scene.scene].graph,
-                                            )
-                                        {
-                                            sender.send(Message::OpenNodeRemovalDialog);
-                                        } else {
-                                            sender.send(Message::DoGameSceneCommand(
-                                                make_delete_selection_command(
-                                                    &entry.selection,
-                                                    game_scene,
-                                                    engine,
-                                                ),
-                                            ));
-                                        }
-                                    }
-                                }
-                                Selection::Ui(ref selection) => {
-                                    if let Some(ui_scene) =
-                                        entry.controller.downcast_mut::<UiScene>()
-                                    {
-                                        sender.send(Message::DoUiSceneCommand(
-                                            selection.make_deletion_command(
-                                                &ui_scene.ui,
-                                                entry.selection.clone(),
+                                                game_scene,
+                                                engine,
                                             ),
                                         ));
                                     }
                                 }
-                                _ => {}
                             }
+                            Selection::Ui(ref selection) => {
+                                if let Some(ui_scene) = entry.controller.downcast_mut::<UiScene>() {
+                                    sender.send(Message::DoUiSceneCommand(
+                                        selection.make_deletion_command(
+                                            &ui_scene.ui,
+                                            entry.selection.clone(),
+                                        ),
+                                    ));
+                                }
+                            }
+                            _ => {}
                         }
                     }
-                } else if hot_key == key_bindings.focus {
-                    if let Some(entry) = self.scenes.current_scene_entry_mut() {
-                        if let Selection::Graph(selection) = &entry.selection {
-                            if let Some(first) = selection.nodes.first() {
-                                sender.send(Message::FocusObject(*first));
-                            }
+                }
+            }
+            BuiltinAction::FocusSelection => {
+                if let Some(entry) = self.scenes.current_scene_entry_mut() {
+                    if let Selection::Graph(selection) = &entry.selection {
+                        if let Some(first) = selection.nodes.first() {
+                            sender.send(Message::FocusObject(*first));
+                        }
+                    }
+                }
+            }
+            BuiltinAction::FrameSelection => sender.send(Message::FrameSelection),
+            BuiltinAction::IsolateSelection => sender.send(Message::ToggleIsolateSelection),
+            BuiltinAction::GroupSelection | BuiltinAction::UngroupSelection => {
+                if let Some(entry) = self.scenes.current_scene_entry_ref() {
+                    if let Some(game_scene) = entry.controller.downcast_ref::<GameScene>() {
+                        let command = if action == BuiltinAction::GroupSelection {
+                            make_group_selection_command(&entry.selection, game_scene, engine)
+                        } else {
+                            make_ungroup_selection_command(&entry.selection, game_scene, engine)
+                        };
+                        if let Some(command) = command {
+                            sender.send(Message::DoGameSceneCommand(command));
                         }
                     }
                 }
             }
+            BuiltinAction::SaveCameraBookmark(index) => {
+                sender.send(Message::SaveCameraBookmark(index))
+            }
+            BuiltinAction::RestoreCameraBookmark(index) => {
+                sender.send(Message::RestoreCameraBookmark(index))
+            }
+            BuiltinAction::Play => sender.send(Message::SwitchToBuildMode),
+            BuiltinAction::Stop => sender.send(Message::SwitchToEditMode),
+            BuiltinAction::OpenSettings => sender.send(Message::OpenSettings),
+            BuiltinAction::OpenKeyBindings => sender.send(Message::OpenKeyBindings),
+            BuiltinAction::OpenCommandPalette => {
+                let actions = self.collect_actions();
+                self.command_palette
+                    .open(actions, &mut self.engine.user_interface);
+            }
         }
     }
 
@@ -1150,9 +1346,13 @@ impl Editor {
                     curve_editor: &self.curve_editor,
                     absm_editor: &self.absm_editor,
                     command_stack_panel: self.command_stack_viewer.window,
+                    statistics_panel: self.statistics_window.window,
+                    script_messages_panel: self.script_messages_panel.window,
+                    validation_panel: self.validation_panel.window,
                     scene_settings: &self.scene_settings,
                     animation_editor: &self.animation_editor,
                     ragdoll_wizard: &self.ragdoll_wizard,
+                    find_replace: &self.find_replace_window,
                 },
                 settings: &mut self.settings,
             },
@@ -1161,6 +1361,18 @@ impl Editor {
         self.build_window
             .handle_ui_message(message, &self.message_sender, &engine.user_interface);
         self.log.handle_ui_message(message, engine);
+        self.script_messages_panel
+            .handle_ui_message(message, engine);
+        self.command_palette.handle_ui_message(
+            message,
+            &mut engine.user_interface,
+            &self.message_sender,
+        );
+        self.key_bindings_window.handle_ui_message(
+            message,
+            &engine.user_interface,
+            &mut self.settings,
+        );
         self.asset_browser
             .handle_ui_message(message, engine, self.message_sender.clone());
         self.command_stack_viewer.handle_ui_message(message);
@@ -1178,6 +1390,18 @@ impl Editor {
             &self.settings,
             &self.mode,
         );
+        self.find_replace_window.handle_ui_message(
+            message,
+            &mut self.scenes,
+            engine,
+            &self.message_sender,
+        );
+        self.bulk_rename_window.handle_ui_message(
+            message,
+            &self.scenes,
+            engine,
+            &self.message_sender,
+        );
 
         let current_scene_entry = self.scenes.current_scene_entry_mut();
 
@@ -1242,6 +1466,7 @@ impl Editor {
                     message,
                     &current_scene_entry.selection,
                     &self.message_sender,
+                    game_scene,
                     engine,
                 );
                 self.node_removal_dialog.handle_ui_message(
@@ -1253,9 +1478,19 @@ impl Editor {
                 );
                 self.scene_settings
                     .handle_ui_message(message, &self.message_sender);
+                self.validation_panel.handle_ui_message(
+                    message,
+                    game_scene,
+                    engine,
+                    &self.message_sender,
+                );
 
-                self.navmesh_panel
-                    .handle_message(message, &current_scene_entry.selection);
+                self.navmesh_panel.handle_message(
+                    message,
+                    &current_scene_entry.selection,
+                    game_scene,
+                    engine,
+                );
 
                 if let Some(current_im) = current_scene_entry.current_interaction_mode {
                     current_scene_entry
@@ -1322,6 +1557,9 @@ impl Editor {
 
             self.material_editor
                 .handle_ui_message(message, engine, &self.message_sender);
+            self.audio_event_bank_editor
+                .handle_ui_message(message, engine);
+            self.visual_script_editor.handle_ui_message(message, engine);
         }
 
         if let Some(MessageBoxMessage::Close(result)) = message.data() {
@@ -1363,13 +1601,23 @@ impl Editor {
 
     fn set_play_mode(&mut self) {
         if let Some(entry) = self.scenes.current_scene_entry_ref() {
-            if let Some(path) = entry.path.as_ref().cloned() {
-                self.save_scene(entry.id, path.clone());
+            if let Some(game_scene) = entry.controller.downcast_ref::<GameScene>() {
+                // The game runs on a snapshot of the scene, so the edited scene stays untouched.
+                let session = match PlaySession::new(entry.id, game_scene, &mut self.engine) {
+                    Ok(session) => session,
+                    Err(e) => {
+                        Log::err(format!(
+                            "Failed to save the scene snapshot for play mode. Reason: {e}"
+                        ));
+                        return;
+                    }
+                };
 
                 let mut process = std::process::Command::new("cargo");
 
                 process
                     .stdout(Stdio::piped())
+                    .stdin(Stdio::piped())
                     .arg("run")
                     .arg("--package")
                     .arg("executor");
@@ -1378,7 +1626,20 @@ impl Editor {
                     process.arg("--release");
                 };
 
-                process.arg("--").arg("--override-scene").arg(path);
+                let hot_reload = self.settings.general.hot_reload_game;
+                if hot_reload {
+                    process.arg("--features").arg("dylib");
+                }
+
+                process
+                    .arg("--")
+                    .arg("--override-scene")
+                    .arg(session.snapshot_path())
+                    .arg("--editor-controlled");
+
+                if self.script_messages_panel.is_capturing() {
+                    process.arg("--log-script-messages");
+                }
 
                 match process.spawn() {
                     Ok(mut process) => {
@@ -1387,22 +1648,43 @@ impl Editor {
                         // Capture output from child process.
                         let mut stdout = process.stdout.take().unwrap();
                         let reader_active = active.clone();
+                        let traffic_sender = self.script_messages_panel.sender();
                         std::thread::spawn(move || {
                             while reader_active.load(Ordering::SeqCst) {
                                 for line in BufReader::new(&mut stdout).lines().take(10).flatten() {
-                                    Log::info(line);
+                                    if let Some(entry) =
+                                        ScriptMessageTrafficEntry::from_log_line(&line)
+                                    {
+                                        let _ = traffic_sender.send(entry);
+                                    } else {
+                                        Log::info(line);
+                                    }
                                 }
                             }
                         });
 
-                        self.mode = Mode::Play { active, process };
+                        let library_builder = if hot_reload {
+                            GameLibraryBuilder::new(matches!(
+                                self.build_profile,
+                                BuildProfile::Release
+                            ))
+                        } else {
+                            None
+                        };
+
+                        self.mode = Mode::Play {
+                            active,
+                            process,
+                            library_builder,
+                            session,
+                        };
 
                         self.on_mode_changed();
                     }
                     Err(e) => Log::err(format!("Failed to enter play mode: {:?}", e)),
                 }
             } else {
-                Log::err("Save you scene first!");
+                Log::err("Only game scenes could be played!");
             }
         } else {
             Log::err("Cannot enter build mode when there is no scene!");
@@ -1412,7 +1694,7 @@ impl Editor {
     fn set_build_mode(&mut self) {
         if let Mode::Edit = self.mode {
             if let Some(entry) = self.scenes.current_scene_entry_ref() {
-                if entry.path.is_some() {
+                if entry.controller.downcast_ref::<GameScene>().is_some() {
                     let mut process = std::process::Command::new("cargo");
                     process
                         .stderr(Stdio::piped())
@@ -1424,6 +1706,14 @@ impl Editor {
                         process.arg("--release");
                     }
 
+                    if self.settings.general.hot_reload_game {
+                        process
+                            .arg("--package")
+                            .arg(GameLibraryBuilder::PACKAGE)
+                            .arg("--features")
+                            .arg("executor/dylib");
+                    }
+
                     match process.spawn() {
                         Ok(mut process) => {
                             self.build_window.listen(
@@ -1438,7 +1728,7 @@ impl Editor {
                         Err(e) => Log::err(format!("Failed to enter build mode: {:?}", e)),
                     }
                 } else {
-                    Log::err("Save you scene first!");
+                    Log::err("Only game scenes could be played!");
                 }
             } else {
                 Log::err("Cannot enter build mode when there is no scene!");
@@ -1449,6 +1739,23 @@ impl Editor {
     }
 
     fn set_editor_mode(&mut self) {
+        if let Mode::Play {
+            ref mut process,
+            ref mut session,
+            ..
+        } = self.mode
+        {
+            // The game will save its state and exit by itself, the changes will be applied when
+            // the game process is finished (see `update`). Stopping the game while it saves its
+            // state closes it immediately.
+            if self.keep_play_mode_changes
+                && !session.is_saving_state()
+                && session.request_state(process)
+            {
+                return;
+            }
+        }
+
         if let Mode::Play { mut process, .. } | Mode::Build { mut process } =
             std::mem::replace(&mut self.mode, Mode::Edit)
         {
@@ -1486,6 +1793,7 @@ impl Editor {
         );
 
         self.scene_viewer.sync_to_model(&self.scenes, engine);
+        self.bulk_rename_window.sync_to_model(&self.scenes, engine);
 
         if let Some(current_scene_entry) = self.scenes.current_scene_entry_mut() {
             self.command_stack_viewer.sync_to_model(
@@ -1533,6 +1841,7 @@ impl Editor {
                     &current_scene_entry.selection,
                     game_scene,
                 );
+                self.validation_panel.sync_to_model(game_scene, engine);
             } else if let Some(ui_scene) = current_scene_entry.controller.downcast_mut::<UiScene>()
             {
                 self.world_viewer.sync_to_model(
@@ -1546,10 +1855,12 @@ impl Editor {
                     &mut engine.user_interface,
                     &self.settings,
                 );
+                self.validation_panel.clear(&mut engine.user_interface);
             }
         } else {
             self.inspector.clear(&engine.user_interface);
             self.world_viewer.clear(&engine.user_interface);
+            self.validation_panel.clear(&mut engine.user_interface);
         }
     }
 
@@ -1557,7 +1868,7 @@ impl Editor {
         if let Some(entry) = self.scenes.current_scene_entry_mut() {
             if let Some(game_scene) = entry.controller.downcast_ref::<GameScene>() {
                 self.world_viewer.post_update(
-                    &EditorSceneWrapper {
+                    &mut EditorSceneWrapper {
                         selection: &entry.selection,
                         game_scene,
                         scene: &mut self.engine.scenes[game_scene.scene],
@@ -1571,7 +1882,7 @@ impl Editor {
                 );
             } else if let Some(ui_scene) = entry.controller.downcast_mut::<UiScene>() {
                 self.world_viewer.post_update(
-                    &UiSceneWorldViewerDataProvider {
+                    &mut UiSceneWorldViewerDataProvider {
                         ui: &mut ui_scene.ui,
                         path: entry.path.as_deref(),
                         selection: &entry.selection,
@@ -1783,6 +2094,7 @@ impl Editor {
                             self.message_sender.clone(),
                             &self.scene_viewer,
                             self.highlighter.clone(),
+                            self.clipboard.clone(),
                         );
                         self.add_scene(entry);
                     }
@@ -1910,6 +2222,7 @@ impl Editor {
             self.message_sender.clone(),
             &self.scene_viewer,
             self.highlighter.clone(),
+            self.clipboard.clone(),
         );
         self.add_scene(entry);
     }
@@ -2026,13 +2339,33 @@ impl Editor {
             Mode::Play {
                 ref mut process,
                 ref active,
+                ref mut library_builder,
+                ref session,
             } => {
+                if let Some(library_builder) = library_builder.as_mut() {
+                    library_builder.update();
+                }
+
                 match process.try_wait() {
                     Ok(status) => {
                         if let Some(status) = status {
                             // Stop reader thread.
                             active.store(false, Ordering::SeqCst);
 
+                            if session.is_saving_state() {
+                                if let Some(game_scene) = self
+                                    .scenes
+                                    .entry_by_scene_id(session.scene_id)
+                                    .and_then(|e| e.controller.downcast_ref::<GameScene>())
+                                {
+                                    session.apply_state(
+                                        game_scene,
+                                        &self.engine,
+                                        &self.message_sender,
+                                    );
+                                }
+                            }
+
                             self.mode = Mode::Edit;
                             self.on_mode_changed();
 
@@ -2070,12 +2403,22 @@ impl Editor {
         }
 
         self.log.update(&mut self.engine);
+        self.script_messages_panel.update(&mut self.engine);
         self.material_editor.update(&mut self.engine);
-        self.asset_browser.update(&mut self.engine);
+        if self.asset_browser.update(&mut self.engine) {
+            self.update_loop_state.request_update_in_next_frame();
+        }
+
+        // Keep the editor active while changed files are waiting to be hot-reloaded, otherwise
+        // the changes won't be visible until some other event wakes the editor up.
+        if self.engine.resource_manager.state().has_pending_reloads() {
+            self.update_loop_state.request_update_in_next_frame();
+        }
 
         if let Some(entry) = self.scenes.current_scene_entry_ref() {
             if let Some(game_scene) = entry.controller.downcast_ref::<GameScene>() {
                 self.light_panel.update(game_scene, &mut self.engine);
+                self.statistics_window.update(game_scene, &mut self.engine);
                 self.animation_editor
                     .update(&entry.selection, game_scene, &self.engine);
                 self.audio_preview_panel
@@ -2084,6 +2427,13 @@ impl Editor {
             }
         }
 
+        self.scene_viewer.update_annotation_labels(
+            self.scenes
+                .current_scene_entry_ref()
+                .and_then(|entry| entry.controller.downcast_ref::<GameScene>()),
+            &mut self.engine,
+        );
+
         if let Some(overlay_pass) = self.overlay_pass.as_ref() {
             overlay_pass.borrow_mut().pictogram_size = self.settings.debugging.pictogram_size;
         }
@@ -2113,6 +2463,7 @@ impl Editor {
                         &entry.selection,
                         &*entry.controller,
                         &mut self.engine,
+                        &self.settings,
                         &self.message_sender,
                     );
 
@@ -2211,6 +2562,7 @@ impl Editor {
                         self.configure(working_directory);
                         needs_sync = true;
                     }
+                    Message::ExecuteAction(action) => self.execute_action(&action),
                     Message::OpenSettings => {
                         self.menu.file_menu.settings.open(
                             &mut self.engine.user_interface,
@@ -2218,7 +2570,21 @@ impl Editor {
                             &self.message_sender,
                         );
                     }
+                    Message::OpenKeyBindings => {
+                        let actions = self.collect_actions();
+                        self.key_bindings_window
+                            .open(actions, &mut self.engine.user_interface);
+                    }
                     Message::OpenMaterialEditor(material) => self.open_material_editor(material),
+                    Message::OpenAudioEventBankEditor(bank) => self
+                        .audio_event_bank_editor
+                        .open(bank, &mut self.engine.user_interface),
+                    Message::OpenVisualScriptEditor(script) => self
+                        .visual_script_editor
+                        .open(script, &mut self.engine.user_interface),
+                    Message::OpenBulkRenameDialog => {
+                        self.bulk_rename_window.open(&self.scenes, &mut self.engine)
+                    }
                     Message::OpenNodeRemovalDialog => {
                         if let Some(entry) = self.scenes.current_scene_entry_ref() {
                             // TODO
@@ -2244,6 +2610,33 @@ impl Editor {
                     },
                     Message::SwitchToBuildMode => self.set_build_mode(),
                     Message::SwitchToEditMode => self.set_editor_mode(),
+                    Message::TogglePlayModePause => {
+                        if let Mode::Play {
+                            ref mut process,
+                            ref mut session,
+                            ..
+                        } = self.mode
+                        {
+                            session.toggle_pause(process);
+                            self.scene_viewer.set_play_mode_paused(
+                                &self.engine.user_interface,
+                                session.is_paused(),
+                            );
+                        }
+                    }
+                    Message::StepPlayMode => {
+                        if let Mode::Play {
+                            ref mut process,
+                            ref mut session,
+                            ..
+                        } = self.mode
+                        {
+                            session.step(process);
+                        }
+                    }
+                    Message::SetKeepPlayModeChanges(keep) => {
+                        self.keep_play_mode_changes = keep;
+                    }
                     Message::OpenLoadSceneDialog => {
                         self.menu
                             .open_load_file_selector(&mut self.engine.user_interface);
@@ -2320,6 +2713,7 @@ impl Editor {
             if let Some(game_scene) = controller.downcast_ref::<GameScene>() {
                 self.absm_editor
                     .update(&entry.selection, game_scene, &mut self.engine);
+                self.navmesh_panel.update(game_scene, &mut self.engine);
             }
 
             if let Some(mode) = entry.current_interaction_mode {
@@ -2332,6 +2726,8 @@ impl Editor {
             }
         }
 
+        for_each_plugin!(self.plugins => on_draw_overlay(self));
+
         self.settings.update();
     }
 
@@ -2372,6 +2768,58 @@ impl Editor {
         self.plugins.push(Some(Box::new(plugin)));
     }
 
+    /// Registers a window of an editor plugin. The window becomes a floating window of the docking
+    /// manager (so it could be docked and its position is stored in the layout) and a new item, that
+    /// opens or closes the window, is added to the "View" menu. The window must have a unique name.
+    pub fn add_plugin_window(&mut self, window: Handle<UiNode>, title: &str) {
+        if let Some(docking_manager) = self
+            .engine
+            .user_interface
+            .try_get(self.docking_manager)
+            .and_then(|n| n.query_component::<DockingManager>())
+        {
+            docking_manager.floating_windows.borrow_mut().push(window);
+        }
+
+        self.menu
+            .add_plugin_window(title, window, &mut self.engine.user_interface);
+    }
+
+    /// Adds a menu item of an editor plugin to the "Plugins" menu. Clicks on the item could be caught
+    /// in [`EditorPlugin::on_ui_message`].
+    pub fn add_plugin_menu_item(&mut self, item: Handle<UiNode>) {
+        self.menu.add_plugin_item(item, &self.engine.user_interface);
+    }
+
+    /// Adds a new property editor definition to the Inspector, existing definition for the same type
+    /// is replaced.
+    pub fn add_property_editor<T>(&mut self, definition: T)
+    where
+        T: PropertyEditorDefinition + 'static,
+    {
+        self.inspector.property_editors.insert(definition);
+    }
+
+    /// Adds a new asset importer (resource loader), existing loader of the same type is replaced.
+    /// The files with the extensions supported by the loader will be shown in the asset browser.
+    pub fn add_asset_importer<T>(&mut self, loader: T)
+    where
+        T: ResourceLoader,
+    {
+        self.engine.resource_manager.state().loaders.set(loader);
+    }
+
+    /// Adds a preview generator for the resources of the given type, that is used by the asset
+    /// browser.
+    pub fn add_asset_preview<T>(&mut self, resource_type_uuid: Uuid, generator: T)
+    where
+        T: AssetPreview,
+    {
+        self.asset_browser
+            .preview_generators
+            .add(resource_type_uuid, generator);
+    }
+
     pub fn is_active(&self) -> bool {
         !self.update_loop_state.is_suspended()
             && (self.focused || !self.settings.general.suspend_unfocused_editor)
@@ -2440,6 +2888,16 @@ impl Editor {
                 e
             )),
         }
+
+        graphics_context
+            .renderer
+            .set_occlusion_culling_debug(self.settings.debugging.show_occlusion_culling);
+        graphics_context
+            .renderer
+            .set_light_clusters_debug(self.settings.debugging.show_light_clusters);
+        graphics_context
+            .renderer
+            .set_cascades_debug(self.settings.debugging.show_shadow_cascades);
     }
 
     fn on_suspended(&mut self) {
@@ -1,9 +1,11 @@
 use crate::{
-    inspector::editors::make_property_editors_container, message::MessageSender, scene::GameScene,
-    Engine, MSG_SYNC_FLAG,
+    inspector::editors::make_property_editors_container, localization::window_title,
+    message::MessageSender, scene::GameScene, Engine, MSG_SYNC_FLAG,
 };
 use fyrox::{
     core::{log::Log, pool::Handle, reflect::prelude::*, scope_profile},
+    engine::GraphicsContext,
+    graph::SceneGraph,
     gui::{
         button::{ButtonBuilder, ButtonMessage},
         formatted_text::WrapMode,
@@ -18,8 +20,12 @@ use fyrox::{
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
+    renderer::lightmapper::GpuLightmapper,
+    resource::texture::TextureResource,
+    scene::{camera::Camera, reflection_probe::ReflectionProbe},
     utils::lightmap::{
-        CancellationToken, Lightmap, LightmapGenerationError, LightmapInputData, ProgressIndicator,
+        CancellationToken, Lightmap, LightmapGenerationError, LightmapInputData,
+        PreparedLightmapData, ProgressIndicator,
     },
 };
 use std::{
@@ -50,10 +56,23 @@ struct LightmapperSettings {
     )]
     spacing: f32,
     #[reflect(
-        description = "Path to the directory which will be used to save the generated light maps. Keep in mind, that \
-    the lightmapper automatically generates names for the files."
+        description = "Path to the directory which will be used to save the generated light maps and baked cube maps of \
+    reflection probes. Keep in mind, that the lightmapper automatically generates names for the files."
     )]
     path: PathBuf,
+    #[reflect(
+        description = "Defines whether the lighting should be calculated on GPU or not. GPU lightmapper is much faster \
+    than CPU lightmapper, shows intermediate results while baking, but its shadows are less precise. Secondary texture \
+    coordinates are always generated on CPU."
+    )]
+    use_gpu: bool,
+    #[reflect(
+        description = "Size of shadow maps, that are used by GPU lightmapper to calculate shadows. The more the value, the \
+    sharper the shadows will be. Default value is 2048.",
+        min_value = 64.0,
+        max_value = 8192.0
+    )]
+    shadow_map_size: u32,
 }
 
 impl Default for LightmapperSettings {
@@ -62,10 +81,19 @@ impl Default for LightmapperSettings {
             texels_per_unit: 64,
             spacing: 0.005,
             path: Default::default(),
+            use_gpu: true,
+            shadow_map_size: 2048,
         }
     }
 }
 
+enum LightmapperMessage {
+    /// Secondary texture coordinates were generated, lighting must be calculated on GPU.
+    Prepared(PreparedLightmapData),
+    /// Lightmap was fully generated on CPU.
+    Finished(Lightmap),
+}
+
 struct ProgressWindow {
     window: Handle<UiNode>,
     progress_bar: Handle<UiNode>,
@@ -86,7 +114,7 @@ impl ProgressWindow {
         let text;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(120.0))
             .open(false)
-            .with_title(WindowTitle::text("Progress"))
+            .with_title(window_title("Progress", ctx))
             .with_content(
                 GridBuilder::new(
                     WidgetBuilder::new()
@@ -187,10 +215,14 @@ pub struct LightPanel {
     pub window: Handle<UiNode>,
     inspector: Handle<UiNode>,
     generate: Handle<UiNode>,
+    bake_probes: Handle<UiNode>,
     settings: LightmapperSettings,
     progress_window: Option<ProgressWindow>,
-    sender: Sender<Result<Lightmap, LightmapGenerationError>>,
-    receiver: Receiver<Result<Lightmap, LightmapGenerationError>>,
+    sender: Sender<Result<LightmapperMessage, LightmapGenerationError>>,
+    receiver: Receiver<Result<LightmapperMessage, LightmapGenerationError>>,
+    gpu_lightmapper: Option<GpuLightmapper>,
+    // Lightmap of the scene before the preview of GPU lightmapper was applied.
+    lightmap_backup: Option<Option<Lightmap>>,
 }
 
 impl LightPanel {
@@ -199,6 +231,7 @@ impl LightPanel {
         let container = Arc::new(make_property_editors_container(sender));
 
         let generate;
+        let bake_probes;
         let inspector;
         let ctx = &mut engine.user_interface.build_ctx();
         let window = WindowBuilder::new(
@@ -247,11 +280,23 @@ impl LightPanel {
                         .with_text("Generate Lightmap")
                         .build(ctx);
                         generate
+                    })
+                    .with_child({
+                        bake_probes = ButtonBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(2)
+                                .on_column(0)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_text("Bake Reflection Probes")
+                        .build(ctx);
+                        bake_probes
                     }),
             )
             .add_column(Column::stretch())
             .add_row(Row::stretch())
             .add_row(Row::strict(25.0))
+            .add_row(Row::strict(25.0))
             .build(ctx),
         )
         .build(ctx);
@@ -262,10 +307,13 @@ impl LightPanel {
             window,
             inspector,
             generate,
+            bake_probes,
             settings,
             progress_window: None,
             sender,
             receiver,
+            gpu_lightmapper: None,
+            lightmap_backup: None,
         }
     }
 
@@ -302,31 +350,38 @@ impl LightPanel {
                     let texels_per_unit = self.settings.texels_per_unit;
                     let spacing = self.settings.spacing;
                     let path = self.settings.path.clone();
+                    let use_gpu = self.settings.use_gpu;
                     let resource_manager = engine.resource_manager.clone();
 
                     if let Err(e) = std::thread::Builder::new()
                         .name("LightmapGenerationThread".to_string())
                         .spawn(move || {
-                            match Lightmap::new(
-                                input_data,
+                            let result = input_data.prepare(
                                 texels_per_unit,
                                 spacing,
-                                cancellation_token,
-                                progress_indicator,
-                            ) {
-                                Ok(lightmap) => {
-                                    if lightmap.save_textures(path, resource_manager).is_err() {
-                                        sender
-                                            .send(Err(LightmapGenerationError::Cancelled))
-                                            .unwrap();
-                                    } else {
-                                        sender.send(Ok(lightmap)).unwrap();
-                                    }
-                                }
-                                Err(err) => {
-                                    sender.send(Err(err)).unwrap();
-                                }
-                            }
+                                cancellation_token.clone(),
+                                progress_indicator.clone(),
+                            );
+
+                            let message = if use_gpu {
+                                // Lighting will be calculated on the main thread, because it needs
+                                // the graphics context.
+                                result.map(LightmapperMessage::Prepared)
+                            } else {
+                                result
+                                    .and_then(|data| {
+                                        data.bake(cancellation_token, progress_indicator)
+                                    })
+                                    .and_then(|lightmap| {
+                                        if lightmap.save_textures(path, resource_manager).is_err() {
+                                            Err(LightmapGenerationError::Cancelled)
+                                        } else {
+                                            Ok(LightmapperMessage::Finished(lightmap))
+                                        }
+                                    })
+                            };
+
+                            sender.send(message).unwrap();
                         })
                     {
                         Log::err(format!(
@@ -337,6 +392,10 @@ impl LightPanel {
                 }
             }
 
+            if message.destination() == self.bake_probes {
+                self.bake_reflection_probes(game_scene, engine);
+            }
+
             if let Some(progress_window) = self.progress_window.as_ref() {
                 if message.destination() == progress_window.cancel {
                     progress_window.cancellation_token.cancel();
@@ -357,6 +416,95 @@ impl LightPanel {
         }
     }
 
+    fn bake_reflection_probes(&self, game_scene: &GameScene, engine: &mut Engine) {
+        let GraphicsContext::Initialized(ref mut graphics_context) = engine.graphics_context else {
+            Log::err("Unable to bake reflection probes, because there's no graphics context!");
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        // Editor objects (gizmos, grid, etc.) must not be baked into the cube maps.
+        let editor_objects_root = &mut scene.graph[game_scene.editor_objects_root];
+        let was_enabled = editor_objects_root.is_enabled();
+        editor_objects_root.set_enabled(false);
+        scene.graph.update_hierarchical_data();
+
+        let skybox = scene
+            .graph
+            .try_get(game_scene.camera_controller.camera)
+            .and_then(|node| node.cast::<Camera>())
+            .and_then(|camera| camera.skybox_ref().cloned());
+
+        let probes = scene
+            .graph
+            .pair_iter()
+            .filter_map(|(handle, node)| {
+                node.cast::<ReflectionProbe>().map(|probe| {
+                    (
+                        handle,
+                        probe.global_position(),
+                        probe.resolution(),
+                        probe.z_near(),
+                        probe.z_far(),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if !probes.is_empty() && !self.settings.path.exists() {
+            Log::verify(std::fs::create_dir_all(&self.settings.path));
+        }
+
+        for (handle, position, resolution, z_near, z_far) in probes {
+            let texture = match graphics_context.renderer.render_cube_map(
+                scene,
+                position,
+                resolution,
+                z_near,
+                z_far,
+                skybox.clone(),
+            ) {
+                Ok(texture) => TextureResource::new_ok(Default::default(), texture),
+                Err(err) => {
+                    Log::err(format!(
+                        "Failed to bake reflection probe {}. Reason: {:?}",
+                        handle, err
+                    ));
+                    continue;
+                }
+            };
+
+            // Forget the previously baked cube map, so the new one could be registered at its path.
+            let path = self
+                .settings
+                .path
+                .join(format!("reflection_probe_{}.dds", handle.index()));
+            engine.resource_manager.state().unregister(&path);
+
+            match engine.resource_manager.register(
+                texture.clone().into_untyped(),
+                &path,
+                |texture, path| texture.save(path).is_ok(),
+            ) {
+                Ok(_) => {
+                    if let Some(probe) = scene.graph[handle].cast_mut::<ReflectionProbe>() {
+                        probe.set_environment(Some(texture));
+                    }
+                }
+                Err(err) => Log::err(format!(
+                    "Failed to save baked reflection probe {} to {}. Reason: {:?}",
+                    handle,
+                    path.display(),
+                    err
+                )),
+            }
+        }
+
+        scene.graph[game_scene.editor_objects_root].set_enabled(was_enabled);
+        scene.graph.update_hierarchical_data();
+    }
+
     pub fn update(&mut self, game_scene: &GameScene, engine: &mut Engine) {
         if let Some(progress_window) = self.progress_window.as_ref() {
             progress_window.show_progress(&engine.user_interface);
@@ -365,7 +513,34 @@ impl LightPanel {
         if let Ok(result) = self.receiver.try_recv() {
             let scene = &mut engine.scenes[game_scene.scene];
             match result {
-                Ok(lightmap) => {
+                Ok(LightmapperMessage::Prepared(data)) => {
+                    if let Some(progress_window) = self.progress_window.as_ref() {
+                        if let GraphicsContext::Initialized(ref mut graphics_context) =
+                            engine.graphics_context
+                        {
+                            match GpuLightmapper::new(
+                                graphics_context.renderer.pipeline_state(),
+                                data,
+                                self.settings.shadow_map_size as usize,
+                                progress_window.cancellation_token.clone(),
+                                progress_window.progress_indicator.clone(),
+                            ) {
+                                Ok(lightmapper) => {
+                                    self.gpu_lightmapper = Some(lightmapper);
+                                    // Keep the progress window open until the lightmapper is done.
+                                    return;
+                                }
+                                Err(err) => Log::err(format!(
+                                    "Failed to create GPU lightmapper. Reason: {}",
+                                    err
+                                )),
+                            }
+                        } else {
+                            Log::err("Unable to generate a lightmap on GPU, because there's no graphics context!");
+                        }
+                    }
+                }
+                Ok(LightmapperMessage::Finished(lightmap)) => {
                     if let Err(err) = scene.graph.set_lightmap(lightmap) {
                         Log::err(format!("Failed to set generated lightmap. Reason: {}", err));
                     }
@@ -379,6 +554,74 @@ impl LightPanel {
                 progress_window.close(&engine.user_interface);
             }
         }
+
+        self.update_gpu_lightmapper(game_scene, engine);
+    }
+
+    fn update_gpu_lightmapper(&mut self, game_scene: &GameScene, engine: &mut Engine) {
+        let Some(lightmapper) = self.gpu_lightmapper.as_mut() else {
+            return;
+        };
+
+        let GraphicsContext::Initialized(ref mut graphics_context) = engine.graphics_context else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        // Bake one light per frame and show intermediate result.
+        let state = graphics_context.renderer.pipeline_state();
+        let result = lightmapper
+            .step(state)
+            .and_then(|_| lightmapper.lightmap(state));
+        let is_finished = lightmapper.is_finished();
+
+        match result {
+            Ok(lightmap) if is_finished => {
+                if let Err(err) =
+                    lightmap.save_textures(&self.settings.path, engine.resource_manager.clone())
+                {
+                    Log::err(format!(
+                        "Failed to save lightmap textures. Reason: {:?}",
+                        err
+                    ));
+                }
+                if let Err(err) = scene.graph.set_lightmap(lightmap) {
+                    Log::err(format!("Failed to set generated lightmap. Reason: {}", err));
+                }
+                self.lightmap_backup = None;
+            }
+            Ok(preview) => {
+                match scene.graph.set_lightmap(preview) {
+                    Ok(previous) => {
+                        if self.lightmap_backup.is_none() {
+                            self.lightmap_backup = Some(previous);
+                        }
+                    }
+                    Err(err) => Log::err(format!(
+                        "Failed to preview generated lightmap. Reason: {}",
+                        err
+                    )),
+                }
+                return;
+            }
+            Err(err) => {
+                // Revert the preview.
+                match self.lightmap_backup.take() {
+                    Some(Some(previous)) => Log::verify(scene.graph.set_lightmap(previous)),
+                    Some(None) => {
+                        scene.graph.clear_lightmap();
+                    }
+                    None => (),
+                }
+                Log::err(format!("Failed to generated a lightmap. Reason: {}", err));
+            }
+        }
+
+        self.gpu_lightmapper = None;
+        if let Some(progress_window) = self.progress_window.take() {
+            progress_window.close(&engine.user_interface);
+        }
     }
 
     pub fn is_in_preview_mode(&self) -> bool {
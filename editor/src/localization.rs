@@ -0,0 +1,329 @@
+//! Localization of the editor. Translations are stored in RON files, that map original (English)
+//! strings to translated ones. There are a few built-in translations, custom translations could be
+//! put in `locale` folder next to the editor executable, they override built-in ones with the same
+//! name. Language could be switched at runtime, see [`set_language`] docs for more info.
+
+use crate::settings::general::{FallbackFont, GeneralSettings};
+use fyrox::{
+    core::{log::Log, parking_lot::RwLock, pool::Handle},
+    fxhash::FxHashMap,
+    graph::SceneGraph,
+    gui::{
+        message::{MessageDirection, UiMessage},
+        text::{Text, TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::WindowTitle,
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Language of the original strings of the editor.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+const BUILT_IN_TRANSLATIONS: [(&str, &str); 2] = [
+    ("ru", include_str!("../resources/locale/ru.ron")),
+    ("zh_cn", include_str!("../resources/locale/zh_cn.ron")),
+];
+
+/// A set of translated strings of a language.
+#[derive(Deserialize, Default, Debug)]
+pub struct Translation {
+    /// Human-readable name of the language.
+    pub name: String,
+    /// Original strings mapped to the translated ones.
+    pub strings: FxHashMap<String, String>,
+}
+
+struct Localization {
+    /// Translations mapped to their language codes (names of translation files without extension).
+    translations: FxHashMap<String, Translation>,
+    language: String,
+    /// Text widgets mapped to the original strings they show.
+    texts: FxHashMap<Handle<UiNode>, String>,
+}
+
+lazy_static! {
+    static ref LOCALIZATION: RwLock<Localization> = RwLock::new(Localization::load());
+}
+
+fn parse_translation(code: &str, text: &str) -> Option<Translation> {
+    match ron::from_str(text) {
+        Ok(translation) => Some(translation),
+        Err(e) => {
+            Log::err(format!(
+                "Unable to load {code} translation of the editor. Reason: {e}"
+            ));
+            None
+        }
+    }
+}
+
+fn custom_translations_dir() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| path.parent().map(|dir| dir.join("locale")))
+}
+
+impl Localization {
+    fn load() -> Self {
+        let mut translations = FxHashMap::default();
+
+        for (code, text) in BUILT_IN_TRANSLATIONS {
+            if let Some(translation) = parse_translation(code, text) {
+                translations.insert(code.to_string(), translation);
+            }
+        }
+
+        if let Some(Ok(entries)) = custom_translations_dir().map(std::fs::read_dir) {
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.extension().map_or(true, |ext| ext != "ron") {
+                    continue;
+                }
+                let Some(code) = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                else {
+                    continue;
+                };
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => {
+                        if let Some(translation) = parse_translation(&code, &text) {
+                            translations.insert(code, translation);
+                        }
+                    }
+                    Err(e) => Log::err(format!(
+                        "Unable to read {} translation file. Reason: {e}",
+                        path.display()
+                    )),
+                }
+            }
+        }
+
+        Self {
+            translations,
+            language: DEFAULT_LANGUAGE.to_string(),
+            texts: Default::default(),
+        }
+    }
+
+    fn translate<'a>(&'a self, language: &str, text: &'a str) -> &'a str {
+        self.translations
+            .get(language)
+            .and_then(|translation| translation.strings.get(text))
+            .map_or(text, |translated| translated.as_str())
+    }
+
+    /// Creates messages, that translate the registered text widgets to the given language. Dead
+    /// widgets are unregistered.
+    fn retranslate(&mut self, language: &str, ui: &UserInterface) -> Vec<UiMessage> {
+        self.texts
+            .retain(|handle, _| ui.try_get(*handle).and_then(|n| n.cast::<Text>()).is_some());
+
+        let mut messages = Vec::new();
+        for (handle, original) in self.texts.iter() {
+            let Some(text) = ui.try_get(*handle).and_then(|n| n.cast::<Text>()) else {
+                continue;
+            };
+            // Texts that were changed by something else (for example, window titles that show
+            // the name of an edited resource) are left intact.
+            if text.text() != self.translate(&self.language, original) {
+                continue;
+            }
+            messages.push(TextMessage::text(
+                *handle,
+                MessageDirection::ToWidget,
+                self.translate(language, original).to_string(),
+            ));
+        }
+        messages
+    }
+}
+
+/// Translates the given text to the current language of the editor. The text is returned as is, if
+/// there's no translation for it.
+pub fn tr(text: &str) -> String {
+    let localization = LOCALIZATION.read();
+    localization
+        .translate(&localization.language, text)
+        .to_string()
+}
+
+/// Registers the text widget, that shows a translation of the given string. The widget will be
+/// translated to the new language when the language of the editor changes. The text of the widget
+/// is left intact if it was changed to something else than the translation of the string.
+pub fn register_text(text: Handle<UiNode>, original: &str) {
+    LOCALIZATION
+        .write()
+        .texts
+        .insert(text, original.to_string());
+}
+
+/// Registers the label of the given menu item, see [`register_text`] for more info. The menu item
+/// must have [`fyrox::gui::menu::MenuItemContent::Text`] content.
+pub fn register_menu_item(menu_item: Handle<UiNode>, original: &str, ctx: &BuildContext) {
+    let label = find_text(menu_item, ctx);
+    if label.is_some() {
+        register_text(label, original);
+    }
+}
+
+fn find_text(node: Handle<UiNode>, ctx: &BuildContext) -> Handle<UiNode> {
+    let node_ref = &ctx[node];
+    if node_ref.cast::<Text>().is_some() {
+        return node;
+    }
+    node_ref
+        .children()
+        .iter()
+        .map(|child| find_text(*child, ctx))
+        .find(|text| text.is_some())
+        .unwrap_or_default()
+}
+
+/// Creates a title for a window, that shows a translation of the given string. The title will be
+/// translated to the new language when the language of the editor changes.
+pub fn window_title(original: &str, ctx: &mut BuildContext) -> WindowTitle {
+    let text = TextBuilder::new(
+        WidgetBuilder::new()
+            .with_margin(Thickness::left(5.0))
+            .on_row(0)
+            .on_column(0),
+    )
+    .with_text(tr(original))
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .with_horizontal_text_alignment(HorizontalAlignment::Left)
+    .build(ctx);
+    register_text(text, original);
+    WindowTitle::node(text)
+}
+
+/// Sets the current language of the editor. Every registered text widget (see [`register_text`]) is
+/// translated to the new language. Widgets that are created after this call should use [`tr`] to
+/// get the translated strings.
+pub fn set_language(language: &str, ui: &mut UserInterface) {
+    let mut localization = LOCALIZATION.write();
+
+    if localization.language == language {
+        return;
+    }
+
+    if language != DEFAULT_LANGUAGE && !localization.translations.contains_key(language) {
+        Log::warn(format!(
+            "There's no {language} translation of the editor, original strings will be used."
+        ));
+    }
+
+    let messages = localization.retranslate(language, ui);
+
+    localization.language = language.to_string();
+    drop(localization);
+
+    for message in messages {
+        ui.send_message(message);
+    }
+}
+
+/// Replaces the fallback fonts of the default font of the user interface. Fallback fonts are used
+/// to render characters, that are missing in the default font (for example, CJK characters in the
+/// names of scene nodes). Missing font files are ignored.
+pub fn set_fallback_fonts(fonts: &[FallbackFont], ui: &mut UserInterface) {
+    let default_font = ui.default_font.clone();
+    let mut font_state = default_font.state();
+    let Some(font) = font_state.data() else {
+        return;
+    };
+
+    font.clear_fallbacks();
+
+    for fallback in fonts {
+        if !fallback.path.exists() {
+            continue;
+        }
+        let result = std::fs::read(&fallback.path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| {
+                font.add_fallback_from_memory(data)
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            Log::err(format!(
+                "Unable to load {} fallback font. Reason: {e}",
+                fallback.path.display()
+            ));
+        }
+    }
+
+    drop(font_state);
+
+    ui.invalidate_layout();
+}
+
+/// Applies the language and the fallback fonts from the given settings.
+pub fn apply_settings(settings: &GeneralSettings, ui: &mut UserInterface) {
+    set_fallback_fonts(&settings.fallback_fonts, ui);
+    set_language(&settings.language, ui);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_translation, Localization, BUILT_IN_TRANSLATIONS};
+    use fyrox::{
+        core::algebra::Vector2,
+        graph::SceneGraph,
+        gui::{
+            text::{TextBuilder, TextMessage},
+            widget::WidgetBuilder,
+            UserInterface,
+        },
+    };
+
+    #[test]
+    fn test_built_in_translations() {
+        for (code, text) in BUILT_IN_TRANSLATIONS {
+            let translation = parse_translation(code, text).unwrap();
+            assert!(!translation.name.is_empty());
+            assert_eq!(
+                translation.strings.get("File").map(|s| s.is_empty()),
+                Some(false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_retranslate_registered_texts_only() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let ctx = &mut ui.build_ctx();
+        let registered = TextBuilder::new(WidgetBuilder::new())
+            .with_text("File")
+            .build(ctx);
+        let changed = TextBuilder::new(WidgetBuilder::new())
+            .with_text("Camera")
+            .build(ctx);
+        let removed = TextBuilder::new(WidgetBuilder::new())
+            .with_text("Edit")
+            .build(ctx);
+        TextBuilder::new(WidgetBuilder::new())
+            .with_text("View")
+            .build(ctx);
+        ui.remove_node(removed);
+
+        let mut localization = Localization::load();
+        for (handle, original) in [(registered, "File"), (changed, "Edit"), (removed, "Edit")] {
+            localization.texts.insert(handle, original.to_string());
+        }
+
+        let messages = localization.retranslate("ru", &ui);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].destination(), registered);
+        assert_eq!(
+            messages[0].data::<TextMessage>(),
+            Some(&TextMessage::Text(
+                localization.translate("ru", "File").to_string()
+            ))
+        );
+        assert!(!localization.texts.contains_key(&removed));
+    }
+}
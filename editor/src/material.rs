@@ -1,6 +1,7 @@
 use crate::{
     asset::item::AssetItem,
     inspector::editors::resource::{ResourceFieldBuilder, ResourceFieldMessage},
+    localization::window_title,
     message::MessageSender,
     preview::PreviewPanel,
     scene::commands::material::{SetMaterialPropertyValueCommand, SetMaterialShaderCommand},
@@ -37,7 +38,7 @@ use fyrox::{
             Vec4EditorBuilder, Vec4EditorMessage,
         },
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowTitle},
+        window::WindowBuilder,
         BuildContext, RcUiNodeHandle, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
     material::{shader::Shader, MaterialResource, PropertyValue},
@@ -253,7 +254,7 @@ impl MaterialEditor {
         let shader;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(350.0))
             .open(false)
-            .with_title(WindowTitle::text("Material Editor"))
+            .with_title(window_title("Material Editor", ctx))
             .with_content(
                 GridBuilder::new(
                     WidgetBuilder::new()
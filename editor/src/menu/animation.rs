@@ -3,7 +3,7 @@ use fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
-        animation::{absm::prelude::*, prelude::*},
+        animation::{absm::prelude::*, ik::IkChainBuilder, prelude::*},
         base::BaseBuilder,
         node::Node,
     },
@@ -13,12 +13,14 @@ pub struct AnimationMenu {
     pub menu: Handle<UiNode>,
     create_animation_player: Handle<UiNode>,
     create_absm: Handle<UiNode>,
+    create_ik_chain: Handle<UiNode>,
 }
 
 impl AnimationMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_animation_player;
         let create_absm;
+        let create_ik_chain;
 
         let menu = create_menu_item(
             "Animation",
@@ -31,6 +33,10 @@ impl AnimationMenu {
                     create_absm = create_menu_item("Animation Blending State Machine", vec![], ctx);
                     create_absm
                 },
+                {
+                    create_ik_chain = create_menu_item("IK Chain", vec![], ctx);
+                    create_ik_chain
+                },
             ],
             ctx,
         );
@@ -39,6 +45,7 @@ impl AnimationMenu {
             menu,
             create_animation_player,
             create_absm,
+            create_ik_chain,
         }
     }
 
@@ -63,6 +70,10 @@ impl AnimationMenu {
                 .with_machine(machine)
                 .build_node();
                 Some(node)
+            } else if message.destination() == self.create_ik_chain {
+                let node =
+                    IkChainBuilder::new(BaseBuilder::new().with_name("IK Chain")).build_node();
+                Some(node)
             } else {
                 None
             }
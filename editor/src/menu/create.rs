@@ -26,6 +26,7 @@ use fyrox::{
         base::BaseBuilder,
         camera::CameraBuilder,
         decal::DecalBuilder,
+        foliage::{FoliageBuilder, FoliageLayer},
         light::{
             directional::DirectionalLightBuilder, point::PointLightBuilder, spot::SpotLightBuilder,
             BaseLightBuilder,
@@ -34,16 +35,23 @@ use fyrox::{
             surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
             MeshBuilder,
         },
-        navmesh::NavigationalMeshBuilder,
+        navmesh::{
+            agent::NavigationAgentBuilder, obstacle::NavmeshObstacleBuilder,
+            NavigationalMeshBuilder,
+        },
         node::Node,
         particle_system::{
             emitter::{base::BaseEmitterBuilder, sphere::SphereEmitterBuilder},
             ParticleSystemBuilder,
         },
         pivot::PivotBuilder,
-        sound::{listener::ListenerBuilder, SoundBuilder},
+        reflection_probe::ReflectionProbeBuilder,
+        sky::SkyBuilder,
+        sound::{listener::ListenerBuilder, reverb_zone::ReverbZoneBuilder, SoundBuilder},
+        spline::{SplineBuilder, SplinePoint},
         sprite::SpriteBuilder,
         terrain::{Layer, TerrainBuilder},
+        trail::TrailBuilder,
     },
     utils::navmesh::Navmesh,
 };
@@ -98,15 +106,23 @@ pub struct CreateEntityMenu {
     create_cylinder: Handle<UiNode>,
     create_quad: Handle<UiNode>,
     create_decal: Handle<UiNode>,
+    create_reflection_probe: Handle<UiNode>,
+    create_sky: Handle<UiNode>,
     create_point_light: Handle<UiNode>,
     create_spot_light: Handle<UiNode>,
     create_directional_light: Handle<UiNode>,
     create_navmesh: Handle<UiNode>,
+    create_navmesh_obstacle: Handle<UiNode>,
+    create_navigation_agent: Handle<UiNode>,
     create_terrain: Handle<UiNode>,
+    create_foliage: Handle<UiNode>,
+    create_spline: Handle<UiNode>,
     create_camera: Handle<UiNode>,
     create_sprite: Handle<UiNode>,
     create_particle_system: Handle<UiNode>,
+    create_trail: Handle<UiNode>,
     create_listener: Handle<UiNode>,
+    create_reverb_zone: Handle<UiNode>,
     create_sound_source: Handle<UiNode>,
     physics_menu: PhysicsMenu,
     physics2d_menu: Physics2dMenu,
@@ -119,7 +135,7 @@ pub struct CreateEntityMenu {
     light_menu: Handle<UiNode>,
 }
 
-fn placeholder_material() -> MaterialResource {
+pub fn placeholder_material() -> MaterialResource {
     let mut material = Material::standard();
     let _ = material.set_texture(&"diffuseTexture".into(), Some(PLACEHOLDER.clone()));
     MaterialResource::new_ok(ResourceKind::Embedded, material)
@@ -138,12 +154,20 @@ impl CreateEntityMenu {
         let create_camera;
         let create_sprite;
         let create_decal;
+        let create_reflection_probe;
+        let create_sky;
         let create_navmesh;
+        let create_navmesh_obstacle;
+        let create_navigation_agent;
         let create_particle_system;
+        let create_trail;
         let create_terrain;
+        let create_foliage;
+        let create_spline;
         let create_pivot;
         let create_sound_source;
         let create_listener;
+        let create_reverb_zone;
         let physics_menu = PhysicsMenu::new(ctx);
         let physics2d_menu = Physics2dMenu::new(ctx);
         let dim2_menu = Dim2Menu::new(ctx);
@@ -201,6 +225,10 @@ impl CreateEntityMenu {
                             create_listener = create_menu_item("Listener", vec![], ctx);
                             create_listener
                         },
+                        {
+                            create_reverb_zone = create_menu_item("Reverb Zone", vec![], ctx);
+                            create_reverb_zone
+                        },
                     ],
                     ctx,
                 );
@@ -244,18 +272,46 @@ impl CreateEntityMenu {
                 create_particle_system = create_menu_item("Particle System", vec![], ctx);
                 create_particle_system
             },
+            {
+                create_trail = create_menu_item("Trail", vec![], ctx);
+                create_trail
+            },
             {
                 create_terrain = create_menu_item("Terrain", vec![], ctx);
                 create_terrain
             },
+            {
+                create_foliage = create_menu_item("Foliage", vec![], ctx);
+                create_foliage
+            },
+            {
+                create_spline = create_menu_item("Spline", vec![], ctx);
+                create_spline
+            },
             {
                 create_decal = create_menu_item("Decal", vec![], ctx);
                 create_decal
             },
+            {
+                create_reflection_probe = create_menu_item("Reflection Probe", vec![], ctx);
+                create_reflection_probe
+            },
+            {
+                create_sky = create_menu_item("Sky", vec![], ctx);
+                create_sky
+            },
             {
                 create_navmesh = create_menu_item("Navmesh", vec![], ctx);
                 create_navmesh
             },
+            {
+                create_navmesh_obstacle = create_menu_item("Navmesh Obstacle", vec![], ctx);
+                create_navmesh_obstacle
+            },
+            {
+                create_navigation_agent = create_menu_item("Navigation Agent", vec![], ctx);
+                create_navigation_agent
+            },
         ];
 
         (
@@ -271,12 +327,20 @@ impl CreateEntityMenu {
                 create_camera,
                 create_sprite,
                 create_particle_system,
+                create_trail,
                 create_pivot,
                 create_terrain,
+                create_foliage,
+                create_spline,
                 create_sound_source,
                 create_listener,
+                create_reverb_zone,
                 create_navmesh,
+                create_navmesh_obstacle,
+                create_navigation_agent,
                 create_decal,
+                create_reflection_probe,
+                create_sky,
                 physics_menu,
                 physics2d_menu,
                 dim2_menu,
@@ -305,11 +369,18 @@ impl CreateEntityMenu {
             self.create_camera,
             self.create_sprite,
             self.create_particle_system,
+            self.create_trail,
             self.create_pivot,
             self.create_terrain,
+            self.create_foliage,
+            self.create_spline,
             self.sound_menu,
             self.create_navmesh,
+            self.create_navmesh_obstacle,
+            self.create_navigation_agent,
             self.create_decal,
+            self.create_reflection_probe,
+            self.create_sky,
             self.physics_menu.menu,
             self.physics2d_menu.menu,
             self.dim2_menu.menu,
@@ -444,6 +515,20 @@ impl CreateEntityMenu {
                                 .with_navmesh(navmesh)
                                 .build_node(),
                         )
+                    } else if message.destination() == self.create_navmesh_obstacle {
+                        Some(
+                            NavmeshObstacleBuilder::new(
+                                BaseBuilder::new().with_name("Navmesh Obstacle"),
+                            )
+                            .build_node(),
+                        )
+                    } else if message.destination() == self.create_navigation_agent {
+                        Some(
+                            NavigationAgentBuilder::new(
+                                BaseBuilder::new().with_name("Navigation Agent"),
+                            )
+                            .build_node(),
+                        )
                     } else if message.destination() == self.create_sprite {
                         Some(
                             SpriteBuilder::new(BaseBuilder::new().with_name("Sprite")).build_node(),
@@ -464,6 +549,8 @@ impl CreateEntityMenu {
                             .build()])
                             .build_node(),
                         )
+                    } else if message.destination() == self.create_trail {
+                        Some(TrailBuilder::new(BaseBuilder::new().with_name("Trail")).build_node())
                     } else if message.destination() == self.create_terrain {
                         Some(
                             TerrainBuilder::new(BaseBuilder::new().with_name("Terrain"))
@@ -473,13 +560,53 @@ impl CreateEntityMenu {
                                 }])
                                 .build_node(),
                         )
+                    } else if message.destination() == self.create_foliage {
+                        Some(
+                            FoliageBuilder::new(BaseBuilder::new().with_name("Foliage"))
+                                .with_layers(vec![FoliageLayer::new(
+                                    "Grass",
+                                    vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                                        SurfaceData::make_quad(&Matrix4::new_translation(
+                                            &Vector3::new(0.0, 0.5, 0.0),
+                                        )),
+                                    ))
+                                    .with_material(placeholder_material())
+                                    .build()],
+                                )])
+                                .build_node(),
+                        )
+                    } else if message.destination() == self.create_spline {
+                        Some(
+                            SplineBuilder::new(BaseBuilder::new().with_name("Spline"))
+                                .with_points(vec![
+                                    SplinePoint::new(Vector3::new(0.0, 0.0, 0.0)),
+                                    SplinePoint::new(Vector3::new(5.0, 0.0, 5.0)),
+                                    SplinePoint::new(Vector3::new(10.0, 0.0, 0.0)),
+                                ])
+                                .with_auto_tangents()
+                                .build_node(),
+                        )
                     } else if message.destination() == self.create_decal {
                         Some(DecalBuilder::new(BaseBuilder::new().with_name("Decal")).build_node())
+                    } else if message.destination() == self.create_reflection_probe {
+                        Some(
+                            ReflectionProbeBuilder::new(
+                                BaseBuilder::new().with_name("Reflection Probe"),
+                            )
+                            .build_node(),
+                        )
+                    } else if message.destination() == self.create_sky {
+                        Some(SkyBuilder::new(BaseBuilder::new().with_name("Sky")).build_node())
                     } else if message.destination() == self.create_listener {
                         Some(
                             ListenerBuilder::new(BaseBuilder::new().with_name("Listener"))
                                 .build_node(),
                         )
+                    } else if message.destination() == self.create_reverb_zone {
+                        Some(
+                            ReverbZoneBuilder::new(BaseBuilder::new().with_name("Reverb Zone"))
+                                .build_node(),
+                        )
                     } else {
                         None
                     }
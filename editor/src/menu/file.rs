@@ -7,9 +7,11 @@ use crate::{
     Engine, Message, Mode, Panels, SaveSceneConfirmationDialogAction,
 };
 use fyrox::{
-    core::pool::Handle,
+    asset::vfs::{PackBuilder, PACK_EXTENSION},
+    core::{log::Log, pool::Handle},
+    engine::executor::Executor,
     gui::{
-        file_browser::{FileSelectorBuilder, FileSelectorMessage},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
         menu::MenuItemMessage,
         message::{MessageDirection, UiMessage},
         messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage},
@@ -18,7 +20,7 @@ use fyrox::{
         BuildContext, UiNode, UserInterface,
     },
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub struct FileMenu {
     pub menu: Handle<UiNode>,
@@ -30,6 +32,7 @@ pub struct FileMenu {
     pub close_scene: Handle<UiNode>,
     exit: Handle<UiNode>,
     pub open_settings: Handle<UiNode>,
+    pub open_key_bindings: Handle<UiNode>,
     configure: Handle<UiNode>,
     pub save_file_selector: Handle<UiNode>,
     pub load_file_selector: Handle<UiNode>,
@@ -38,6 +41,8 @@ pub struct FileMenu {
     pub recent_files_container: Handle<UiNode>,
     pub recent_files: Vec<Handle<UiNode>>,
     pub open_scene_settings: Handle<UiNode>,
+    build_pack: Handle<UiNode>,
+    build_pack_file_selector: Handle<UiNode>,
 }
 
 fn make_recent_files_items(
@@ -51,6 +56,48 @@ fn make_recent_files_items(
         .collect::<Vec<_>>()
 }
 
+/// Returns `true` if the given path should be included in a resource pack. Source code, build
+/// artifacts and hidden files are excluded.
+fn is_packable(path: &Path) -> bool {
+    let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+        return true;
+    };
+    if name.starts_with('.') && name != "." {
+        return false;
+    }
+    if path.is_dir() {
+        !matches!(name.as_ref(), "target" | "src")
+    } else {
+        !path.extension().map_or(false, |ext| {
+            matches!(
+                ext.to_string_lossy().as_ref(),
+                "rs" | "toml" | "lock" | "log" | PACK_EXTENSION
+            )
+        })
+    }
+}
+
+/// Packs every resource in the working directory in a single pack file, that can be shipped with
+/// the game instead of loose files.
+fn build_resource_pack(path: &Path) {
+    let mut builder = PackBuilder::new();
+    match builder
+        .add_directory(".", is_packable)
+        .and_then(|builder| builder.build(path))
+    {
+        Ok(_) => Log::info(format!(
+            "Resource pack {} with {} files was successfully built.",
+            path.display(),
+            builder.len()
+        )),
+        Err(err) => Log::err(format!(
+            "Unable to build resource pack {}. Reason: {:?}",
+            path.display(),
+            err
+        )),
+    }
+}
+
 impl FileMenu {
     pub fn new(engine: &mut Engine, settings: &Settings) -> Self {
         let new_scene;
@@ -60,8 +107,10 @@ impl FileMenu {
         let close_scene;
         let load;
         let open_settings;
+        let open_key_bindings;
         let open_scene_settings;
         let configure;
+        let build_pack;
         let exit;
         let recent_files_container;
 
@@ -110,6 +159,10 @@ impl FileMenu {
                     open_settings = create_menu_item("Editor Settings...", vec![], ctx);
                     open_settings
                 },
+                {
+                    open_key_bindings = create_menu_item("Key Bindings...", vec![], ctx);
+                    open_key_bindings
+                },
                 {
                     open_scene_settings = create_menu_item("Scene Settings...", vec![], ctx);
                     open_scene_settings
@@ -118,6 +171,10 @@ impl FileMenu {
                     configure = create_menu_item("Configure...", vec![], ctx);
                     configure
                 },
+                {
+                    build_pack = create_menu_item("Build Resource Pack...", vec![], ctx);
+                    build_pack
+                },
                 {
                     recent_files_container =
                         create_menu_item("Recent Files", recent_files.clone(), ctx);
@@ -139,6 +196,21 @@ impl FileMenu {
         .with_filter(make_scene_file_filter())
         .build(ctx);
 
+        let build_pack_file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::Text("Save Resource Pack As".into())),
+        )
+        .with_mode(FileBrowserMode::Save {
+            default_file_name: PathBuf::from(Executor::DEFAULT_PACK_FILE),
+        })
+        .with_filter(Filter::new(|p: &Path| {
+            p.is_dir()
+                || p.extension()
+                    .map_or(false, |ext| ext.to_string_lossy() == PACK_EXTENSION)
+        }))
+        .build(ctx);
+
         Self {
             save_file_selector: Handle::NONE,
             load_file_selector,
@@ -151,12 +223,15 @@ impl FileMenu {
             load,
             exit,
             open_settings,
+            open_key_bindings,
             configure,
             configure_message,
             settings: SettingsWindow::new(engine),
             recent_files_container,
             recent_files,
             open_scene_settings,
+            build_pack,
+            build_pack_file_selector,
         }
     }
 
@@ -224,6 +299,8 @@ impl FileMenu {
                 self.save_file_selector = Handle::NONE;
             } else if message.destination() == self.load_file_selector {
                 sender.send(Message::LoadScene(path.to_owned()));
+            } else if message.destination() == self.build_pack_file_selector {
+                build_resource_pack(path);
             }
         } else if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.save {
@@ -287,6 +364,23 @@ impl FileMenu {
             } else if message.destination() == self.open_settings {
                 self.settings
                     .open(&mut engine.user_interface, settings, sender);
+            } else if message.destination() == self.open_key_bindings {
+                sender.send(Message::OpenKeyBindings);
+            } else if message.destination() == self.build_pack {
+                engine
+                    .user_interface
+                    .send_message(WindowMessage::open_modal(
+                        self.build_pack_file_selector,
+                        MessageDirection::ToWidget,
+                        true,
+                    ));
+                engine
+                    .user_interface
+                    .send_message(FileSelectorMessage::path(
+                        self.build_pack_file_selector,
+                        MessageDirection::ToWidget,
+                        std::env::current_dir().unwrap(),
+                    ));
             } else if message.destination() == self.open_scene_settings {
                 panels.scene_settings.open(&engine.user_interface);
             } else if let Some(recent_file) = self
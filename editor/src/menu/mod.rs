@@ -1,6 +1,7 @@
 use crate::scene::controller::SceneController;
 use crate::{
     animation::AnimationEditor,
+    localization::{register_menu_item, tr},
     menu::{
         create::CreateEntityRootMenu, edit::EditMenu, file::FileMenu, help::HelpMenu,
         utils::UtilsMenu, view::ViewMenu,
@@ -9,13 +10,13 @@ use crate::{
     scene::container::EditorSceneEntry,
     send_sync_message,
     settings::Settings,
-    utils::ragdoll::RagdollWizard,
+    utils::{find_replace::FindReplaceWindow, ragdoll::RagdollWizard},
     AbsmEditor, CurveEditorWindow, Engine, Mode, SceneSettingsWindow,
 };
 use fyrox::{
     core::{algebra::Vector2, pool::Handle, scope_profile},
     gui::{
-        menu::{MenuBuilder, MenuItemBuilder, MenuItemContent},
+        menu::{MenuBuilder, MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::{MessageDirection, UiMessage},
         widget::{WidgetBuilder, WidgetMessage},
         BuildContext, Thickness, UiNode, UserInterface,
@@ -44,6 +45,8 @@ pub struct Menu {
     message_sender: MessageSender,
     utils_menu: UtilsMenu,
     help_menu: HelpMenu,
+    /// A root menu for the items of editor plugins, it is hidden until the first item is added.
+    plugins_menu: Handle<UiNode>,
 }
 
 pub struct Panels<'b> {
@@ -52,6 +55,9 @@ pub struct Panels<'b> {
     pub navmesh_panel: Handle<UiNode>,
     pub audio_panel: Handle<UiNode>,
     pub command_stack_panel: Handle<UiNode>,
+    pub statistics_panel: Handle<UiNode>,
+    pub script_messages_panel: Handle<UiNode>,
+    pub validation_panel: Handle<UiNode>,
     pub inspector_window: Handle<UiNode>,
     pub world_outliner_window: Handle<UiNode>,
     pub asset_window: Handle<UiNode>,
@@ -62,6 +68,7 @@ pub struct Panels<'b> {
     pub scene_settings: &'b SceneSettingsWindow,
     pub animation_editor: &'b AnimationEditor,
     pub ragdoll_wizard: &'b RagdollWizard,
+    pub find_replace: &'b FindReplaceWindow,
 }
 
 pub struct MenuContext<'a, 'b> {
@@ -76,10 +83,12 @@ pub fn create_root_menu_item(
     items: Vec<Handle<UiNode>>,
     ctx: &mut BuildContext,
 ) -> Handle<UiNode> {
-    MenuItemBuilder::new(WidgetBuilder::new().with_margin(Thickness::right(10.0)))
-        .with_content(MenuItemContent::text_no_arrow(text))
+    let item = MenuItemBuilder::new(WidgetBuilder::new().with_margin(Thickness::right(10.0)))
+        .with_content(MenuItemContent::text_no_arrow(&tr(text)))
         .with_items(items)
-        .build(ctx)
+        .build(ctx);
+    register_menu_item(item, text, ctx);
+    item
 }
 
 pub fn create_menu_item(
@@ -87,10 +96,12 @@ pub fn create_menu_item(
     items: Vec<Handle<UiNode>>,
     ctx: &mut BuildContext,
 ) -> Handle<UiNode> {
-    MenuItemBuilder::new(WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)))
-        .with_content(MenuItemContent::text(text))
+    let item = MenuItemBuilder::new(WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)))
+        .with_content(MenuItemContent::text(&tr(text)))
         .with_items(items)
-        .build(ctx)
+        .build(ctx);
+    register_menu_item(item, text, ctx);
+    item
 }
 
 pub fn create_menu_item_shortcut(
@@ -99,10 +110,12 @@ pub fn create_menu_item_shortcut(
     items: Vec<Handle<UiNode>>,
     ctx: &mut BuildContext,
 ) -> Handle<UiNode> {
-    MenuItemBuilder::new(WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)))
-        .with_content(MenuItemContent::text_with_shortcut(text, shortcut))
+    let item = MenuItemBuilder::new(WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)))
+        .with_content(MenuItemContent::text_with_shortcut(&tr(text), shortcut))
         .with_items(items)
-        .build(ctx)
+        .build(ctx);
+    register_menu_item(item, text, ctx);
+    item
 }
 
 impl Menu {
@@ -114,6 +127,13 @@ impl Menu {
         let view_menu = ViewMenu::new(ctx);
         let utils_menu = UtilsMenu::new(ctx);
         let help_menu = HelpMenu::new(ctx);
+        let plugins_menu = MenuItemBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_margin(Thickness::right(10.0)),
+        )
+        .with_content(MenuItemContent::text_no_arrow("Plugins"))
+        .build(ctx);
 
         let menu = MenuBuilder::new(WidgetBuilder::new().on_row(0))
             .with_items(vec![
@@ -122,6 +142,7 @@ impl Menu {
                 create_entity_menu.menu,
                 view_menu.menu,
                 utils_menu.menu,
+                plugins_menu,
                 help_menu.menu,
             ])
             .build(ctx);
@@ -135,9 +156,34 @@ impl Menu {
             view_menu,
             utils_menu,
             help_menu,
+            plugins_menu,
         }
     }
 
+    /// Adds a menu item of an editor plugin to the "Plugins" menu.
+    pub fn add_plugin_item(&self, item: Handle<UiNode>, ui: &UserInterface) {
+        ui.send_message(MenuItemMessage::add_item(
+            self.plugins_menu,
+            MessageDirection::ToWidget,
+            item,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.plugins_menu,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    /// Adds a menu item, that opens or closes a window of an editor plugin, to the "View" menu.
+    pub fn add_plugin_window(
+        &mut self,
+        title: &str,
+        window: Handle<UiNode>,
+        ui: &mut UserInterface,
+    ) {
+        self.view_menu.add_plugin_window(title, window, ui)
+    }
+
     pub fn open_load_file_selector(&self, ui: &mut UserInterface) {
         self.file_menu.open_load_file_selector(ui)
     }
@@ -187,8 +233,12 @@ impl Menu {
             );
         }
 
-        self.utils_menu
-            .handle_ui_message(message, &ctx.panels, &ctx.engine.user_interface);
+        self.utils_menu.handle_ui_message(
+            message,
+            &self.message_sender,
+            &ctx.panels,
+            &ctx.engine.user_interface,
+        );
         self.file_menu.handle_ui_message(
             message,
             &self.message_sender,
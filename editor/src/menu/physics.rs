@@ -3,8 +3,15 @@ use fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
-        base::BaseBuilder, collider::*, joint::*, node::Node, ragdoll::RagdollBuilder,
+        base::BaseBuilder,
+        character::CharacterControllerBuilder,
+        cloth::ClothBuilder,
+        collider::*,
+        joint::*,
+        node::Node,
+        ragdoll::RagdollBuilder,
         rigidbody::RigidBodyBuilder,
+        vehicle::{VehicleBuilder, WheelBuilder},
     },
 };
 
@@ -17,6 +24,10 @@ pub struct PhysicsMenu {
     create_fixed_joint: Handle<UiNode>,
     create_collider: Handle<UiNode>,
     create_ragdoll: Handle<UiNode>,
+    create_vehicle: Handle<UiNode>,
+    create_wheel: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
+    create_cloth: Handle<UiNode>,
 }
 
 impl PhysicsMenu {
@@ -28,6 +39,10 @@ impl PhysicsMenu {
         let create_prismatic_joint;
         let create_fixed_joint;
         let create_ragdoll;
+        let create_vehicle;
+        let create_wheel;
+        let create_character_controller;
+        let create_cloth;
         let menu = create_menu_item(
             "Physics",
             vec![
@@ -59,6 +74,23 @@ impl PhysicsMenu {
                     create_ragdoll = create_menu_item("Ragdoll", vec![], ctx);
                     create_ragdoll
                 },
+                {
+                    create_vehicle = create_menu_item("Vehicle", vec![], ctx);
+                    create_vehicle
+                },
+                {
+                    create_wheel = create_menu_item("Wheel", vec![], ctx);
+                    create_wheel
+                },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
+                {
+                    create_cloth = create_menu_item("Cloth", vec![], ctx);
+                    create_cloth
+                },
             ],
             ctx,
         );
@@ -72,6 +104,10 @@ impl PhysicsMenu {
             create_fixed_joint,
             create_collider,
             create_ragdoll,
+            create_vehicle,
+            create_wheel,
+            create_character_controller,
+            create_cloth,
         }
     }
 
@@ -111,6 +147,19 @@ impl PhysicsMenu {
                 )
             } else if message.destination == self.create_ragdoll {
                 Some(RagdollBuilder::new(BaseBuilder::new().with_name("Ragdoll")).build_node())
+            } else if message.destination == self.create_vehicle {
+                Some(VehicleBuilder::new(BaseBuilder::new().with_name("Vehicle")).build_node())
+            } else if message.destination == self.create_wheel {
+                Some(WheelBuilder::new(BaseBuilder::new().with_name("Wheel")).build_node())
+            } else if message.destination == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller"),
+                    )
+                    .build_node(),
+                )
+            } else if message.destination == self.create_cloth {
+                Some(ClothBuilder::new(BaseBuilder::new().with_name("Cloth")).build_node())
             } else {
                 None
             }
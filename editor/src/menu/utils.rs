@@ -1,4 +1,9 @@
-use crate::menu::{create_menu_item, create_root_menu_item, Panels};
+use crate::{
+    menu::{create_menu_item, create_root_menu_item, Panels},
+    message::MessageSender,
+    scene::commands::graph::{BuildStaticBatchesCommand, ClearStaticBatchesCommand},
+    Message,
+};
 use fyrox::{
     asset::core::pool::Handle,
     gui::{
@@ -7,6 +12,7 @@ use fyrox::{
         window::WindowMessage,
         BuildContext, UiNode, UserInterface,
     },
+    utils::static_batching::StaticBatchingOptions,
 };
 
 pub struct UtilsMenu {
@@ -16,6 +22,10 @@ pub struct UtilsMenu {
     absm_editor: Handle<UiNode>,
     animation_editor: Handle<UiNode>,
     ragdoll_wizard: Handle<UiNode>,
+    find_replace: Handle<UiNode>,
+    bulk_rename: Handle<UiNode>,
+    build_static_batches: Handle<UiNode>,
+    clear_static_batches: Handle<UiNode>,
 }
 
 impl UtilsMenu {
@@ -25,6 +35,10 @@ impl UtilsMenu {
         let absm_editor;
         let animation_editor;
         let ragdoll_wizard;
+        let find_replace;
+        let bulk_rename;
+        let build_static_batches;
+        let clear_static_batches;
         let menu = create_root_menu_item(
             "Utils",
             vec![
@@ -48,6 +62,22 @@ impl UtilsMenu {
                     ragdoll_wizard = create_menu_item("Ragdoll Wizard", vec![], ctx);
                     ragdoll_wizard
                 },
+                {
+                    find_replace = create_menu_item("Find And Replace", vec![], ctx);
+                    find_replace
+                },
+                {
+                    bulk_rename = create_menu_item("Bulk Rename", vec![], ctx);
+                    bulk_rename
+                },
+                {
+                    build_static_batches = create_menu_item("Build Static Batches", vec![], ctx);
+                    build_static_batches
+                },
+                {
+                    clear_static_batches = create_menu_item("Clear Static Batches", vec![], ctx);
+                    clear_static_batches
+                },
             ],
             ctx,
         );
@@ -59,10 +89,20 @@ impl UtilsMenu {
             absm_editor,
             animation_editor,
             ragdoll_wizard,
+            find_replace,
+            bulk_rename,
+            build_static_batches,
+            clear_static_batches,
         }
     }
 
-    pub fn handle_ui_message(&mut self, message: &UiMessage, panels: &Panels, ui: &UserInterface) {
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        panels: &Panels,
+        ui: &UserInterface,
+    ) {
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.open_path_fixer {
                 ui.send_message(WindowMessage::open_modal(
@@ -78,6 +118,18 @@ impl UtilsMenu {
                 panels.animation_editor.open(ui);
             } else if message.destination() == self.ragdoll_wizard {
                 panels.ragdoll_wizard.open(ui);
+            } else if message.destination() == self.find_replace {
+                panels.find_replace.open(ui);
+            } else if message.destination() == self.bulk_rename {
+                sender.send(Message::OpenBulkRenameDialog);
+            } else if message.destination() == self.build_static_batches {
+                sender.do_scene_command(BuildStaticBatchesCommand::new(
+                    StaticBatchingOptions::default(),
+                ));
+            } else if message.destination() == self.clear_static_batches {
+                sender.do_scene_command(ClearStaticBatchesCommand::new(
+                    StaticBatchingOptions::default(),
+                ));
             }
         }
     }
@@ -24,8 +24,13 @@ pub struct ViewMenu {
     nav_mesh: Handle<UiNode>,
     audio: Handle<UiNode>,
     command_stack: Handle<UiNode>,
+    statistics: Handle<UiNode>,
+    script_messages: Handle<UiNode>,
+    validation: Handle<UiNode>,
     save_layout: Handle<UiNode>,
     load_layout: Handle<UiNode>,
+    /// Pairs of menu items and windows registered by editor plugins.
+    plugin_windows: Vec<(Handle<UiNode>, Handle<UiNode>)>,
 }
 
 fn switch_window_state(window: Handle<UiNode>, ui: &UserInterface, center: bool) {
@@ -47,6 +52,9 @@ impl ViewMenu {
         let nav_mesh;
         let audio;
         let command_stack;
+        let statistics;
+        let script_messages;
+        let validation;
         let save_layout;
         let load_layout;
         let menu = create_root_menu_item(
@@ -84,6 +92,18 @@ impl ViewMenu {
                     command_stack = create_menu_item("Command Stack Panel", vec![], ctx);
                     command_stack
                 },
+                {
+                    statistics = create_menu_item("Statistics Panel", vec![], ctx);
+                    statistics
+                },
+                {
+                    script_messages = create_menu_item("Script Messages Panel", vec![], ctx);
+                    script_messages
+                },
+                {
+                    validation = create_menu_item("Validation Panel", vec![], ctx);
+                    validation
+                },
                 {
                     save_layout = create_menu_item("Save Layout", vec![], ctx);
                     save_layout
@@ -106,11 +126,31 @@ impl ViewMenu {
             nav_mesh,
             audio,
             command_stack,
+            statistics,
+            script_messages,
+            validation,
             save_layout,
             load_layout,
+            plugin_windows: Default::default(),
         }
     }
 
+    /// Adds a new item, that opens or closes the given window, to the menu.
+    pub fn add_plugin_window(
+        &mut self,
+        title: &str,
+        window: Handle<UiNode>,
+        ui: &mut UserInterface,
+    ) {
+        let item = create_menu_item(title, vec![], &mut ui.build_ctx());
+        ui.send_message(MenuItemMessage::add_item(
+            self.menu,
+            MessageDirection::ToWidget,
+            item,
+        ));
+        self.plugin_windows.push((item, window));
+    }
+
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
@@ -135,10 +175,22 @@ impl ViewMenu {
                 switch_window_state(panels.audio_panel, ui, false);
             } else if message.destination() == self.command_stack {
                 switch_window_state(panels.command_stack_panel, ui, false);
+            } else if message.destination() == self.statistics {
+                switch_window_state(panels.statistics_panel, ui, false);
+            } else if message.destination() == self.script_messages {
+                switch_window_state(panels.script_messages_panel, ui, false);
+            } else if message.destination() == self.validation {
+                switch_window_state(panels.validation_panel, ui, false);
             } else if message.destination() == self.save_layout {
                 sender.send(Message::SaveLayout);
             } else if message.destination() == self.load_layout {
                 sender.send(Message::LoadLayout);
+            } else if let Some((_, window)) = self
+                .plugin_windows
+                .iter()
+                .find(|(item, _)| *item == message.destination())
+            {
+                switch_window_state(*window, ui, false);
             }
         }
     }
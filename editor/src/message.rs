@@ -1,4 +1,5 @@
 use crate::{
+    action::Action,
     command::GameSceneCommandTrait,
     scene::{commands::GameSceneCommand, Selection},
     ui_scene::commands::{UiCommand, UiSceneCommand},
@@ -12,6 +13,7 @@ use fyrox::{
     },
     gui::UiNode,
     material::MaterialResource,
+    resource::{audio_event::AudioEventBankResource, visual_script::VisualScriptResource},
     scene::{camera::Projection, node::Node},
 };
 use std::{path::PathBuf, sync::mpsc::Sender};
@@ -42,10 +44,15 @@ pub enum Message {
         force: bool,
     },
     OpenSettings,
+    OpenKeyBindings,
     OpenAnimationEditor,
     OpenAbsmEditor,
     OpenMaterialEditor(MaterialResource),
+    OpenAudioEventBankEditor(AudioEventBankResource),
+    OpenVisualScriptEditor(VisualScriptResource),
     OpenNodeRemovalDialog,
+    /// Opens the bulk rename dialog for the selected nodes.
+    OpenBulkRenameDialog,
     ShowInAssetBrowser(PathBuf),
     LocateObject {
         handle: ErasedHandle,
@@ -55,9 +62,29 @@ pub enum Message {
     },
     SetCurrentScene(Uuid),
     FocusObject(Handle<Node>),
+    /// Fits every selected object in the view of the editor camera.
+    FrameSelection,
+    SaveCameraBookmark(usize),
+    /// Hides or shows the node in the editor, it does not change visibility of the node in the game.
+    SetNodeHidden {
+        node: Handle<Node>,
+        hidden: bool,
+    },
+    /// Locks the node, locked nodes (and their descendants) cannot be selected in the viewport.
+    SetNodeLocked {
+        node: Handle<Node>,
+        locked: bool,
+    },
+    /// Hides every object except the selected ones, or shows them back if they're hidden already.
+    ToggleIsolateSelection,
+    RestoreCameraBookmark(usize),
     SetEditorCameraProjection(Projection),
     SwitchToBuildMode,
     SwitchToEditMode,
+    TogglePlayModePause,
+    StepPlayMode,
+    SetKeepPlayModeChanges(bool),
+    ExecuteAction(Action),
     SwitchMode,
     OpenLoadSceneDialog,
     OpenSaveSceneDialog {
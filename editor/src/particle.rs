@@ -1,4 +1,5 @@
 use crate::{
+    localization::window_title,
     scene::{GameScene, Selection},
     send_sync_message, Message, FIXED_TIMESTEP,
 };
@@ -14,7 +15,7 @@ use fyrox::{
         numeric::{NumericUpDownBuilder, NumericUpDownMessage},
         text::TextBuilder,
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, Thickness, UiNode, VerticalAlignment,
     },
     scene::{node::Node, particle_system::ParticleSystem},
@@ -126,7 +127,7 @@ impl ParticleSystemPreviewControlPanel {
                 .with_height(70.0),
         )
         .open(false)
-        .with_title(WindowTitle::text("Particle System"))
+        .with_title(window_title("Particle System", ctx))
         .with_content(
             GridBuilder::new(
                 WidgetBuilder::new().with_child(grid).with_child(
@@ -252,17 +253,25 @@ impl ParticleSystemPreviewControlPanel {
         let node_overrides = game_scene.graph_switches.node_overrides.as_mut().unwrap();
 
         if let Selection::Graph(ref new_graph_selection) = editor_selection {
-            // Enable particle systems from new selection.
-            for &node_handle in &new_graph_selection.nodes {
-                if scene
-                    .graph
-                    .try_get_of_type::<ParticleSystem>(node_handle)
-                    .is_some()
+            // Enable particle systems from new selection and the targets of their sub-emitters.
+            let mut stack = new_graph_selection.nodes.clone();
+            while let Some(node_handle) = stack.pop() {
+                if let Some(particle_system) =
+                    scene.graph.try_get_of_type::<ParticleSystem>(node_handle)
                 {
+                    if !node_overrides.insert(node_handle) {
+                        continue;
+                    }
+
+                    stack.extend(
+                        particle_system
+                            .sub_emitters
+                            .iter()
+                            .map(|sub_emitter| sub_emitter.particle_system),
+                    );
+
                     self.particle_systems_state
                         .push((node_handle, scene.graph[node_handle].clone_box()));
-
-                    assert!(node_overrides.insert(node_handle));
                 }
             }
         }
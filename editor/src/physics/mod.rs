@@ -1,4 +1,5 @@
 use crate::{
+    localization::window_title,
     message::MessageSender,
     scene::{
         commands::{CommandGroup, GameSceneCommand, SetPropertyCommand},
@@ -16,7 +17,7 @@ use fyrox::{
         stack_panel::StackPanelBuilder,
         utils::make_simple_tooltip,
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, HorizontalAlignment, Thickness, UiNode, VerticalAlignment,
     },
     scene::{
@@ -52,7 +53,7 @@ impl ColliderControlPanel {
 
         let fit;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(250.0).with_height(50.0))
-            .with_title(WindowTitle::text("Collider Control Panel"))
+            .with_title(window_title("Collider Control Panel", ctx))
             .with_content(
                 StackPanelBuilder::new(
                     WidgetBuilder::new()
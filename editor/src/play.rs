@@ -0,0 +1,206 @@
+//! Play mode session. The game is started on a snapshot of the edited scene, so the edited scene is
+//! never touched by the game. Optionally, transform changes made by the game could be applied back to
+//! the edited scene when the play mode is stopped.
+
+use crate::{
+    message::MessageSender,
+    scene::commands::{
+        graph::{MoveNodeCommand, RotateNodeCommand, ScaleNodeCommand},
+        CommandGroup, GameSceneCommand,
+    },
+    GameScene,
+};
+use fyrox::{
+    asset::io::FsResourceIo,
+    core::{futures::executor::block_on, log::Log, pool::Handle, uuid::Uuid, visitor::Visitor},
+    engine::{executor::PlayModeCommand, Engine},
+    fxhash::FxHashMap,
+    graph::SceneGraph,
+    scene::{node::Node, Scene, SceneLoader},
+};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::Child,
+};
+
+pub struct PlaySession {
+    /// Id of the edited scene the snapshot was made of.
+    pub scene_id: Uuid,
+    snapshot_path: PathBuf,
+    /// Maps handles of the nodes in the snapshot to the handles of the nodes in the edited scene.
+    handle_map: FxHashMap<Handle<Node>, Handle<Node>>,
+    paused: bool,
+    /// A path to the state of the game, it is set when the editor waits for the game to save its state.
+    state_path: Option<PathBuf>,
+}
+
+impl PlaySession {
+    /// Saves a snapshot of the given scene to a temporary file that should be used as a scene
+    /// override for the game.
+    pub fn new(
+        scene_id: Uuid,
+        game_scene: &GameScene,
+        engine: &mut Engine,
+    ) -> Result<Self, String> {
+        let (mut snapshot, map) = game_scene.make_purified_scene_with_map(engine);
+
+        let snapshot_path = std::env::temp_dir().join(format!("fyrox_play_{scene_id}.rgs"));
+        let mut visitor = Visitor::new();
+        snapshot
+            .save("Scene", &mut visitor)
+            .map_err(|e| e.to_string())?;
+        visitor
+            .save_binary(&snapshot_path)
+            .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            scene_id,
+            snapshot_path,
+            handle_map: map
+                .into_inner()
+                .into_iter()
+                .map(|(editor, snapshot)| (snapshot, editor))
+                .collect(),
+            paused: false,
+            state_path: None,
+        })
+    }
+
+    pub fn snapshot_path(&self) -> &Path {
+        &self.snapshot_path
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn is_saving_state(&self) -> bool {
+        self.state_path.is_some()
+    }
+
+    pub fn toggle_pause(&mut self, process: &mut Child) {
+        let command = if self.paused {
+            PlayModeCommand::Resume
+        } else {
+            PlayModeCommand::Pause
+        };
+        if send_command(process, command) {
+            self.paused = !self.paused;
+        }
+    }
+
+    pub fn step(&mut self, process: &mut Child) {
+        if self.paused {
+            send_command(process, PlayModeCommand::Step);
+        }
+    }
+
+    /// Asks the game to save its state and exit. Returns `false` if the command cannot be sent, in
+    /// this case the game should be closed by other means.
+    pub fn request_state(&mut self, process: &mut Child) -> bool {
+        let state_path = self.snapshot_path.with_extension("state.rgs");
+        if send_command(process, PlayModeCommand::SaveAndExit(state_path.clone())) {
+            self.state_path = Some(state_path);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Loads the state saved by the game (if any) and applies transform changes of the nodes, that
+    /// exist in both the snapshot and the edited scene, back to the edited scene as a single command.
+    pub fn apply_state(&self, game_scene: &GameScene, engine: &Engine, sender: &MessageSender) {
+        let Some(state_path) = self.state_path.as_ref() else {
+            return;
+        };
+
+        let state = match block_on(SceneLoader::from_file(
+            state_path,
+            &FsResourceIo,
+            engine.serialization_context.clone(),
+            engine.resource_manager.clone(),
+        )) {
+            Ok(loader) => block_on(loader.0.finish(&engine.resource_manager)),
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to keep play mode changes, the game state cannot be loaded. Reason: {e}"
+                ));
+                return;
+            }
+        };
+
+        let commands = self.collect_changes(&state, &engine.scenes[game_scene.scene]);
+        if commands.is_empty() {
+            Log::info("There are no play mode changes to keep.");
+        } else {
+            sender.do_scene_command(
+                CommandGroup::from(commands).with_custom_name("Keep Play Mode Changes"),
+            );
+        }
+    }
+
+    fn collect_changes(&self, state: &Scene, edited: &Scene) -> Vec<GameSceneCommand> {
+        let mut commands = Vec::new();
+        for (snapshot_handle, editor_handle) in self.handle_map.iter() {
+            let (Some(state_node), Some(editor_node)) = (
+                state.graph.try_get(*snapshot_handle),
+                edited.graph.try_get(*editor_handle),
+            ) else {
+                continue;
+            };
+
+            let new = state_node.local_transform();
+            let old = editor_node.local_transform();
+
+            if **new.position() != **old.position() {
+                commands.push(GameSceneCommand::new(MoveNodeCommand::new(
+                    *editor_handle,
+                    **old.position(),
+                    **new.position(),
+                )));
+            }
+            if **new.rotation() != **old.rotation() {
+                commands.push(GameSceneCommand::new(RotateNodeCommand::new(
+                    *editor_handle,
+                    **old.rotation(),
+                    **new.rotation(),
+                )));
+            }
+            if **new.scale() != **old.scale() {
+                commands.push(GameSceneCommand::new(ScaleNodeCommand::new(
+                    *editor_handle,
+                    **old.scale(),
+                    **new.scale(),
+                )));
+            }
+        }
+        commands
+    }
+}
+
+impl Drop for PlaySession {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.snapshot_path);
+        if let Some(state_path) = self.state_path.as_ref() {
+            let _ = std::fs::remove_file(state_path);
+        }
+    }
+}
+
+fn send_command(process: &mut Child, command: PlayModeCommand) -> bool {
+    let Some(stdin) = process.stdin.as_mut() else {
+        Log::err("Unable to control the game, its input is not available.");
+        return false;
+    };
+
+    match writeln!(stdin, "{}", command.to_line()).and_then(|_| stdin.flush()) {
+        Ok(_) => true,
+        Err(e) => {
+            Log::err(format!(
+                "Unable to send {command:?} to the game. Reason: {e}"
+            ));
+            false
+        }
+    }
+}
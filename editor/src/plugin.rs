@@ -20,6 +20,20 @@ use fyrox::gui::message::UiMessage;
 /// The editor usually operates on scenes (there could be multiple opened scenes, but only one active) and any modification of
 /// their content **must** be done via _commands_. [Command](https://en.wikipedia.org/wiki/Command_pattern) is a standard
 /// pattern that encapsulates an action. Command pattern is used for undo/redo functionality.
+///
+/// ## Extending the Editor
+///
+/// Plugins are registered in the editor executable of your game via [`Editor::add_editor_plugin`]. Usually, a plugin
+/// creates its widgets and registers them in [`Self::on_start`], using the following methods of the editor:
+///
+/// - [`Editor::add_plugin_window`] - adds a window, that could be docked and opened via the "View" menu.
+/// - [`Editor::add_plugin_menu_item`] - adds a menu item to the "Plugins" menu.
+/// - [`Editor::add_property_editor`] - adds a property editor for a custom type to the Inspector.
+/// - [`Editor::add_asset_importer`] - adds a resource loader, that imports custom assets.
+/// - [`Editor::add_asset_preview`] - adds a preview generator for custom assets in the asset browser.
+/// - [`Editor::register_action`] - adds an action, that could be executed from the command palette or via a hot key.
+///
+/// Viewport overlays could be drawn in [`Self::on_draw_overlay`].
 pub trait EditorPlugin {
     /// This method is called right after the editor was fully initialized. It is guaranteed to be called only once.
     fn on_start(&mut self, #[allow(unused_variables)] editor: &mut Editor) {}
@@ -77,6 +91,12 @@ pub trait EditorPlugin {
     /// perform some actions, that require all pre-defined steps to be done.
     fn on_post_update(&mut self, #[allow(unused_variables)] editor: &mut Editor) {}
 
+    /// This method is called every frame right after the editor has drawn its own auxiliary geometry (gizmos, bounding
+    /// boxes, etc.) of the current scene. It could be used to draw custom overlays in the scene viewport via the drawing
+    /// context of the current scene (`scene.drawing_context`). Anything drawn in other methods will be erased by the
+    /// editor.
+    fn on_draw_overlay(&mut self, #[allow(unused_variables)] editor: &mut Editor) {}
+
     /// This method is called when the editor receives a control message. It could be used to catch and react to specific
     /// actions in the editor (such as: scene loading, command execution, undo, redo, etc.).
     fn on_message(
@@ -0,0 +1,93 @@
+//! Annotations are notes placed in a scene, they're useful for level design collaboration. The
+//! annotations are editor-only metadata, that is stored in a separate file next to the scene, so
+//! they could be shared using version control without affecting the scene itself.
+
+use fyrox::core::{algebra::Vector3, log::Log};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Annotation {
+    pub position: Vector3<f32>,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct SceneAnnotations {
+    pub annotations: Vec<Annotation>,
+}
+
+impl SceneAnnotations {
+    /// Returns a path of the annotations file of the scene with the given path.
+    pub fn path_for(scene_path: &Path) -> PathBuf {
+        let mut path = scene_path.as_os_str().to_owned();
+        path.push(".annotations");
+        path.into()
+    }
+
+    /// Loads annotations of the scene with the given path. Returns empty set of annotations if the
+    /// scene has no annotations file.
+    pub fn load(scene_path: &Path) -> Self {
+        let path = Self::path_for(scene_path);
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Default::default();
+        };
+
+        ron::from_str(&text).unwrap_or_else(|e| {
+            Log::err(format!(
+                "Unable to load annotations from {}. Reason: {e}",
+                path.display()
+            ));
+            Default::default()
+        })
+    }
+
+    /// Saves annotations next to the scene with the given path. The annotations file is removed if
+    /// there are no annotations.
+    pub fn save(&self, scene_path: &Path) -> Result<(), String> {
+        let path = Self::path_for(scene_path);
+        if self.annotations.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        } else {
+            let text = ron::ser::to_string_pretty(self, PrettyConfig::default())
+                .map_err(|e| e.to_string())?;
+            std::fs::write(&path, text).map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Annotation, SceneAnnotations};
+    use fyrox::core::algebra::Vector3;
+    use std::path::Path;
+
+    #[test]
+    fn test_annotations_path() {
+        assert_eq!(
+            SceneAnnotations::path_for(Path::new("data/level.rgs")),
+            Path::new("data/level.rgs.annotations")
+        );
+    }
+
+    #[test]
+    fn test_annotations_round_trip() {
+        let scene_path = std::env::temp_dir().join("fyrox_annotations_test.rgs");
+        let annotations = SceneAnnotations {
+            annotations: vec![Annotation {
+                position: Vector3::new(1.0, 2.0, 3.0),
+                text: "Move this wall".to_string(),
+            }],
+        };
+
+        annotations.save(&scene_path).unwrap();
+        assert_eq!(SceneAnnotations::load(&scene_path), annotations);
+
+        SceneAnnotations::default().save(&scene_path).unwrap();
+        assert!(!SceneAnnotations::path_for(&scene_path).exists());
+    }
+}
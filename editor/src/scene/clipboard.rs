@@ -1,22 +1,25 @@
+//! Clipboard for scene nodes. Copied nodes are stored in a serialized form, so the clipboard does not
+//! depend on the scene the nodes were copied from. The same clipboard is shared across every opened
+//! game scene, which allows to copy nodes in one scene and paste them in another.
+
 use crate::{scene::GraphSelection, Engine};
 use fyrox::{
-    core::pool::Handle,
+    asset::manager::ResourceManager,
+    core::{
+        log::Log,
+        pool::Handle,
+        visitor::{Visit, VisitError, Visitor},
+    },
+    engine::SerializationContext,
     scene::{graph::Graph, node::Node, Scene},
 };
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 
+/// A handle to the clipboard, clones of the handle share the same content.
+#[derive(Clone, Default)]
 pub struct Clipboard {
-    graph: Graph,
-    empty: bool,
-}
-
-impl Default for Clipboard {
-    fn default() -> Self {
-        Self {
-            graph: Graph::new(),
-            empty: true,
-        }
-    }
+    /// Serialized graph, which root node contains copied nodes.
+    data: Rc<RefCell<Option<Vec<u8>>>>,
 }
 
 #[derive(Default, Debug)]
@@ -55,6 +58,28 @@ fn deep_clone_nodes(
     result
 }
 
+fn serialize_graph(mut graph: Graph) -> Result<Vec<u8>, VisitError> {
+    let mut visitor = Visitor::new();
+    graph.visit("Graph", &mut visitor)?;
+    visitor.save_binary_to_vec()
+}
+
+/// Restores the graph from the clipboard data. Embedded resources are restored as new instances
+/// and external resources are requested from the resource manager by their paths, so the pasted
+/// nodes never share embedded data with the scene they were copied from.
+fn deserialize_graph(
+    data: &[u8],
+    serialization_context: Arc<SerializationContext>,
+    resource_manager: ResourceManager,
+) -> Result<Graph, VisitError> {
+    let mut visitor = Visitor::load_from_memory(data)?;
+    visitor.blackboard.register(serialization_context);
+    visitor.blackboard.register(Arc::new(resource_manager));
+    let mut graph = Graph::default();
+    graph.visit("Graph", &mut visitor)?;
+    Ok(graph)
+}
+
 impl Clipboard {
     pub fn fill_from_selection(
         &mut self,
@@ -68,27 +93,42 @@ impl Clipboard {
 
         let root_nodes = selection.root_nodes(&scene.graph);
 
-        deep_clone_nodes(&root_nodes, &scene.graph, &mut self.graph);
+        let mut graph = Graph::new();
+        deep_clone_nodes(&root_nodes, &scene.graph, &mut graph);
 
-        self.empty = false;
+        match serialize_graph(graph) {
+            Ok(data) => *self.data.borrow_mut() = Some(data),
+            Err(e) => Log::err(format!(
+                "Unable to copy the selected nodes to the clipboard. Reason: {e:?}"
+            )),
+        }
     }
 
-    pub fn paste(&mut self, dest_graph: &mut Graph) -> DeepCloneResult {
-        assert!(!self.empty);
-
-        deep_clone_nodes(
-            self.graph[self.graph.get_root()].children(),
-            &self.graph,
-            dest_graph,
-        )
+    pub fn paste(
+        &mut self,
+        dest_graph: &mut Graph,
+        serialization_context: Arc<SerializationContext>,
+        resource_manager: ResourceManager,
+    ) -> DeepCloneResult {
+        let data = self.data.borrow();
+        let data = data.as_ref().expect("Clipboard must not be empty!");
+
+        match deserialize_graph(data, serialization_context, resource_manager) {
+            Ok(graph) => deep_clone_nodes(graph[graph.get_root()].children(), &graph, dest_graph),
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to paste the nodes from the clipboard. Reason: {e:?}"
+                ));
+                Default::default()
+            }
+        }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.empty
+        self.data.borrow().is_none()
     }
 
     pub fn clear(&mut self) {
-        self.empty = true;
-        self.graph = Graph::new();
+        *self.data.borrow_mut() = None;
     }
 }
@@ -0,0 +1,99 @@
+use crate::{
+    command::GameSceneCommandTrait,
+    scene::{annotation::Annotation, commands::GameSceneContext},
+};
+
+#[derive(Debug)]
+pub struct AddAnnotationCommand {
+    annotation: Option<Annotation>,
+}
+
+impl AddAnnotationCommand {
+    pub fn new(annotation: Annotation) -> Self {
+        Self {
+            annotation: Some(annotation),
+        }
+    }
+}
+
+impl GameSceneCommandTrait for AddAnnotationCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Add Annotation".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        context
+            .annotations
+            .annotations
+            .push(self.annotation.take().unwrap());
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.annotation = context.annotations.annotations.pop();
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveAnnotationCommand {
+    index: usize,
+    annotation: Option<Annotation>,
+}
+
+impl RemoveAnnotationCommand {
+    pub fn new(index: usize) -> Self {
+        Self {
+            index,
+            annotation: None,
+        }
+    }
+}
+
+impl GameSceneCommandTrait for RemoveAnnotationCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Remove Annotation".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.annotation = Some(context.annotations.annotations.remove(self.index));
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        context
+            .annotations
+            .annotations
+            .insert(self.index, self.annotation.take().unwrap());
+    }
+}
+
+#[derive(Debug)]
+pub struct SetAnnotationTextCommand {
+    index: usize,
+    text: String,
+}
+
+impl SetAnnotationTextCommand {
+    pub fn new(index: usize, text: String) -> Self {
+        Self { index, text }
+    }
+
+    fn swap(&mut self, context: &mut GameSceneContext) {
+        std::mem::swap(
+            &mut context.annotations.annotations[self.index].text,
+            &mut self.text,
+        );
+    }
+}
+
+impl GameSceneCommandTrait for SetAnnotationTextCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Set Annotation Text".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+}
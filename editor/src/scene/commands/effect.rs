@@ -7,7 +7,7 @@ use fyrox::{
         pool::{Handle, Ticket},
         reflect::{prelude::*, ResolvePath},
     },
-    scene::sound::AudioBus,
+    scene::sound::{AudioBus, AudioBusGraph},
 };
 
 define_universal_commands!(
@@ -171,3 +171,31 @@ impl GameSceneCommandTrait for LinkAudioBuses {
         self.swap(context)
     }
 }
+
+#[derive(Debug)]
+pub struct SetAudioBusGraphCommand {
+    pub graph: AudioBusGraph,
+}
+
+impl SetAudioBusGraphCommand {
+    fn swap(&mut self, context: &mut GameSceneContext) {
+        std::mem::swap(
+            context.scene.graph.sound_context.state().bus_graph_mut(),
+            &mut self.graph,
+        );
+    }
+}
+
+impl GameSceneCommandTrait for SetAudioBusGraphCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Set Audio Bus Graph".to_string()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(context)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{command::GameSceneCommandTrait, scene::commands::GameSceneContext};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    scene::{
+        foliage::{Foliage, FoliageInstance},
+        node::Node,
+    },
+};
+
+#[derive(Debug)]
+pub struct ModifyFoliageInstancesCommand {
+    foliage: Handle<Node>,
+    // TODO: This is very memory-inefficient solution, it could be done
+    //  better by storing only added and removed instances.
+    /// Instances of every layer of the foliage.
+    old_instances: Vec<Vec<FoliageInstance>>,
+    new_instances: Vec<Vec<FoliageInstance>>,
+}
+
+impl ModifyFoliageInstancesCommand {
+    pub fn new(
+        foliage: Handle<Node>,
+        old_instances: Vec<Vec<FoliageInstance>>,
+        new_instances: Vec<Vec<FoliageInstance>>,
+    ) -> Self {
+        Self {
+            foliage,
+            old_instances,
+            new_instances,
+        }
+    }
+
+    fn swap(&mut self, context: &mut GameSceneContext) {
+        let Some(foliage) = context
+            .scene
+            .graph
+            .try_get_mut_of_type::<Foliage>(self.foliage)
+        else {
+            Log::err("Invalid foliage handle.");
+            return;
+        };
+
+        for (layer, (old, new)) in foliage.layers_mut().iter_mut().zip(
+            self.old_instances
+                .iter_mut()
+                .zip(self.new_instances.iter_mut()),
+        ) {
+            layer.set_instances(new.clone());
+            std::mem::swap(old, new);
+        }
+    }
+}
+
+impl GameSceneCommandTrait for ModifyFoliageInstancesCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Modify Foliage Instances".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+}
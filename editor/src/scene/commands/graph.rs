@@ -5,14 +5,19 @@ use crate::{
 use fyrox::graph::{LinkScheme, SceneGraph};
 use fyrox::{
     core::{
-        algebra::{UnitQuaternion, Vector3},
+        algebra::{Point3, UnitQuaternion, Vector3},
+        log::Log,
+        math::Matrix4Ext,
         pool::{Handle, Ticket},
     },
     scene::{
-        base::Base,
+        base::{Base, BaseBuilder},
         graph::{Graph, SubGraph},
         node::Node,
+        pivot::PivotBuilder,
+        transform::Transform,
     },
+    utils::static_batching::{build_static_batches, clear_static_batches, StaticBatchingOptions},
 };
 
 #[derive(Debug)]
@@ -464,3 +469,451 @@ impl GameSceneCommandTrait for SetGraphRootCommand {
         self.root = std::mem::replace(context.scene_content_root, self.root);
     }
 }
+
+#[derive(Debug)]
+pub struct SetNodeNameCommand {
+    node: Handle<Node>,
+    name: String,
+}
+
+impl SetNodeNameCommand {
+    pub fn new(node: Handle<Node>, name: String) -> Self {
+        Self { node, name }
+    }
+
+    fn swap(&mut self, graph: &mut Graph) {
+        let old_name = graph[self.node].name_owned();
+        graph[self.node].set_name(std::mem::replace(&mut self.name, old_name));
+    }
+}
+
+impl GameSceneCommandTrait for SetNodeNameCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Set Node Name".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+}
+
+#[derive(Debug)]
+pub struct BuildStaticBatchesCommand {
+    options: StaticBatchingOptions,
+}
+
+impl BuildStaticBatchesCommand {
+    pub fn new(options: StaticBatchingOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl GameSceneCommandTrait for BuildStaticBatchesCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Build Static Batches".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        let report = build_static_batches(
+            &mut context.scene.graph,
+            *context.scene_content_root,
+            &self.options,
+        );
+        Log::info(format!(
+            "{} static batches were built from {} surfaces of {} meshes.",
+            report.batch_count, report.merged_surface_count, report.merged_mesh_count
+        ));
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        clear_static_batches(&mut context.scene.graph, *context.scene_content_root);
+    }
+}
+
+#[derive(Debug)]
+pub struct ClearStaticBatchesCommand {
+    options: StaticBatchingOptions,
+}
+
+impl ClearStaticBatchesCommand {
+    pub fn new(options: StaticBatchingOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl GameSceneCommandTrait for ClearStaticBatchesCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Clear Static Batches".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        clear_static_batches(&mut context.scene.graph, *context.scene_content_root);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        build_static_batches(
+            &mut context.scene.graph,
+            *context.scene_content_root,
+            &self.options,
+        );
+    }
+}
+
+/// Links the child with the parent, while keeping global position, rotation and scale of the child.
+/// Global transforms of both nodes must be up-to-date.
+fn link_nodes_keep_global_transform(graph: &mut Graph, child: Handle<Node>, parent: Handle<Node>) {
+    let parent_transform_inv = graph[parent]
+        .global_transform()
+        .try_inverse()
+        .unwrap_or_default();
+    let relative_transform = parent_transform_inv * graph[child].global_transform();
+    let basis = relative_transform.basis();
+    let scale = Vector3::new(
+        basis.column(0).norm(),
+        basis.column(1).norm(),
+        basis.column(2).norm(),
+    );
+    // Scale must be removed from the basis, otherwise the rotation will be extracted incorrectly.
+    let mut rotation = basis;
+    for mut column in rotation.column_iter_mut() {
+        if let Some(normalized) = column.try_normalize(f32::EPSILON) {
+            column.copy_from(&normalized);
+        }
+    }
+    graph[child]
+        .local_transform_mut()
+        .set_position(relative_transform.position())
+        .set_rotation(UnitQuaternion::from_matrix(&rotation))
+        .set_scale(scale);
+    graph.link_nodes(child, parent);
+}
+
+/// Creates a new group node (pivot) and moves the given nodes into it, keeping their global
+/// transforms. The group is placed at the center of the nodes and selected.
+#[derive(Debug)]
+pub struct GroupNodesCommand {
+    nodes: Vec<Handle<Node>>,
+    parent: Handle<Node>,
+    group: Handle<Node>,
+    ticket: Option<Ticket<Node>>,
+    group_node: Option<Node>,
+    /// Parents, local transforms and positions among siblings of the nodes before grouping.
+    prev_links: Vec<(Handle<Node>, Transform, usize)>,
+    prev_selection: Selection,
+}
+
+impl GroupNodesCommand {
+    pub fn new(nodes: Vec<Handle<Node>>, parent: Handle<Node>) -> Self {
+        Self::with_group_node(
+            nodes,
+            parent,
+            PivotBuilder::new(BaseBuilder::new().with_name("Group")).build_node(),
+        )
+    }
+
+    /// Creates a command, that uses the given node as a group node instead of a pivot.
+    pub fn with_group_node(nodes: Vec<Handle<Node>>, parent: Handle<Node>, group: Node) -> Self {
+        Self {
+            nodes,
+            parent,
+            group: Default::default(),
+            ticket: None,
+            group_node: Some(group),
+            prev_links: Default::default(),
+            prev_selection: Selection::None,
+        }
+    }
+}
+
+impl GameSceneCommandTrait for GroupNodesCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Group Nodes".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        let graph = &mut context.scene.graph;
+        graph.update_hierarchical_data();
+
+        let center = self
+            .nodes
+            .iter()
+            .map(|node| graph[*node].global_position())
+            .sum::<Vector3<f32>>()
+            .scale(1.0 / self.nodes.len().max(1) as f32);
+
+        let group_node = self.group_node.take().unwrap();
+        self.group = match self.ticket.take() {
+            None => graph.add_node(group_node),
+            Some(ticket) => graph.put_back(ticket, group_node),
+        };
+        graph.link_nodes(self.group, self.parent);
+        let local_center = graph[self.parent]
+            .global_transform()
+            .try_inverse()
+            .unwrap_or_default()
+            .transform_point(&Point3::from(center))
+            .coords;
+        graph[self.group]
+            .local_transform_mut()
+            .set_position(local_center);
+        graph.update_hierarchical_data_for_descendants(self.group);
+
+        self.prev_links.clear();
+        for &node in self.nodes.iter() {
+            let node_ref = &graph[node];
+            let parent = node_ref.parent();
+            let position = graph[parent]
+                .children()
+                .iter()
+                .position(|c| *c == node)
+                .unwrap_or_default();
+            self.prev_links
+                .push((parent, node_ref.local_transform().clone(), position));
+        }
+        for &node in self.nodes.iter() {
+            link_nodes_keep_global_transform(graph, node, self.group);
+        }
+
+        self.prev_selection = std::mem::replace(
+            context.selection,
+            Selection::Graph(GraphSelection::single_or_empty(self.group)),
+        );
+        context.message_sender.send(Message::SelectionChanged {
+            old_selection: self.prev_selection.clone(),
+        });
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        let graph = &mut context.scene.graph;
+        let mut links = self
+            .nodes
+            .iter()
+            .cloned()
+            .zip(self.prev_links.drain(..))
+            .collect::<Vec<_>>();
+        for (node, (parent, transform, _)) in links.iter() {
+            graph.link_nodes(*node, *parent);
+            *graph[*node].local_transform_mut() = transform.clone();
+        }
+        // Restore the order of the siblings. The nodes are moved in ascending order of their
+        // previous positions, so every node lands exactly where it was.
+        links.sort_by_key(|(_, (_, _, position))| *position);
+        for (node, (_, _, position)) in links {
+            graph.set_child_position(node, position);
+        }
+
+        let (ticket, group_node) = graph.take_reserve(self.group);
+        self.ticket = Some(ticket);
+        self.group_node = Some(group_node);
+
+        std::mem::swap(context.selection, &mut self.prev_selection);
+        context.message_sender.send(Message::SelectionChanged {
+            old_selection: self.prev_selection.clone(),
+        });
+    }
+
+    fn finalize(&mut self, context: &mut GameSceneContext) {
+        if let Some(ticket) = self.ticket.take() {
+            context
+                .scene
+                .graph
+                .forget_ticket(ticket, self.group_node.take().unwrap());
+        }
+    }
+}
+
+/// Moves children of the group node to the parent of the group, keeping their global transforms,
+/// and removes the group node. It is the inverse of [`GroupNodesCommand`].
+#[derive(Debug)]
+pub struct UngroupNodesCommand {
+    group: Handle<Node>,
+    parent: Handle<Node>,
+    ticket: Option<Ticket<Node>>,
+    group_node: Option<Node>,
+    /// Children of the group with their local transforms before ungrouping.
+    children: Vec<(Handle<Node>, Transform)>,
+}
+
+impl UngroupNodesCommand {
+    pub fn new(group: Handle<Node>) -> Self {
+        Self {
+            group,
+            parent: Default::default(),
+            ticket: None,
+            group_node: None,
+            children: Default::default(),
+        }
+    }
+}
+
+impl GameSceneCommandTrait for UngroupNodesCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Ungroup Nodes".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        let graph = &mut context.scene.graph;
+        graph.update_hierarchical_data();
+
+        self.parent = graph[self.group].parent();
+        self.children = graph[self.group]
+            .children()
+            .iter()
+            .map(|child| (*child, graph[*child].local_transform().clone()))
+            .collect();
+        for (child, _) in self.children.iter() {
+            link_nodes_keep_global_transform(graph, *child, self.parent);
+        }
+
+        let (ticket, group_node) = graph.take_reserve(self.group);
+        self.ticket = Some(ticket);
+        self.group_node = Some(group_node);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        let graph = &mut context.scene.graph;
+        let group = graph.put_back(self.ticket.take().unwrap(), self.group_node.take().unwrap());
+        assert_eq!(group, self.group);
+        graph.link_nodes(self.group, self.parent);
+
+        for (child, transform) in self.children.drain(..) {
+            graph.link_nodes(child, self.group);
+            *graph[child].local_transform_mut() = transform;
+        }
+    }
+
+    fn finalize(&mut self, context: &mut GameSceneContext) {
+        if let Some(ticket) = self.ticket.take() {
+            context
+                .scene
+                .graph
+                .forget_ticket(ticket, self.group_node.take().unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{link_nodes_keep_global_transform, GroupNodesCommand};
+    use crate::{
+        command::GameSceneCommandTrait,
+        message::MessageSender,
+        scene::{
+            annotation::SceneAnnotations, clipboard::Clipboard, commands::GameSceneContext,
+            Selection,
+        },
+    };
+    use fyrox::{
+        asset::manager::ResourceManager,
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        engine::SerializationContext,
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder, Scene,
+        },
+    };
+    use std::sync::{mpsc, Arc};
+
+    #[test]
+    fn test_link_nodes_keep_global_transform() {
+        let mut graph = Graph::new();
+        let parent = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 2.0, 3.0))
+                    .with_local_rotation(UnitQuaternion::from_axis_angle(
+                        &Vector3::y_axis(),
+                        90.0f32.to_radians(),
+                    ))
+                    .with_local_scale(Vector3::repeat(2.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(5.0, 0.0, 0.0))
+                    .with_local_scale(Vector3::new(1.0, 3.0, 1.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        graph.update_hierarchical_data();
+        let global_transform = graph[child].global_transform();
+
+        link_nodes_keep_global_transform(&mut graph, child, parent);
+        graph.update_hierarchical_data();
+
+        assert_eq!(graph[child].parent(), parent);
+        assert!(graph[child]
+            .global_transform()
+            .relative_eq(&global_transform, 0.0001, 0.0001));
+    }
+
+    #[test]
+    fn test_link_nodes_keep_global_transform_non_uniform_scale() {
+        let mut graph = Graph::new();
+        let parent = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        // Extraction of the rotation from such basis without normalization never converges.
+        let scale = Vector3::new(100000.0, 1.0, 0.001);
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 170.0f32.to_radians());
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_rotation(rotation)
+                    .with_local_scale(scale)
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        graph.update_hierarchical_data();
+
+        link_nodes_keep_global_transform(&mut graph, child, parent);
+
+        let local_transform = graph[child].local_transform();
+        assert!(local_transform.rotation().angle_to(&rotation) < 0.0001);
+        assert!(local_transform.scale().relative_eq(&scale, 0.0001, 0.0001));
+    }
+
+    #[test]
+    fn test_group_nodes_revert_restores_order() {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        let children = (0..5)
+            .map(|_| PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph))
+            .collect::<Vec<Handle<Node>>>();
+        let original_order = scene.graph[root].children().to_vec();
+
+        let mut selection = Selection::None;
+        let mut scene_content_root = root;
+        let mut clipboard = Clipboard::default();
+        let mut annotations = SceneAnnotations::default();
+        let (sender, _receiver) = mpsc::channel();
+        let mut context = GameSceneContext {
+            selection: &mut selection,
+            scene: &mut scene,
+            scene_content_root: &mut scene_content_root,
+            clipboard: &mut clipboard,
+            annotations: &mut annotations,
+            message_sender: MessageSender(sender),
+            resource_manager: ResourceManager::new(Arc::new(Default::default())),
+            serialization_context: Arc::new(SerializationContext::new()),
+        };
+
+        let mut command = GroupNodesCommand::new(vec![children[3], children[1]], root);
+        command.execute(&mut context);
+        assert!(!context.scene.graph[root].children().contains(&children[1]));
+        command.revert(&mut context);
+
+        assert_eq!(context.scene.graph[root].children(), original_order);
+        command.finalize(&mut context);
+    }
+}
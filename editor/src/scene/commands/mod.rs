@@ -3,8 +3,9 @@ use crate::{
     define_universal_commands,
     message::MessageSender,
     scene::{
+        annotation::SceneAnnotations,
         clipboard::{Clipboard, DeepCloneResult},
-        commands::graph::DeleteSubGraphCommand,
+        commands::graph::{DeleteSubGraphCommand, GroupNodesCommand, UngroupNodesCommand},
         GameScene, GraphSelection, Selection,
     },
     Engine, Message,
@@ -21,12 +22,15 @@ use std::{
     sync::Arc,
 };
 
+pub mod annotation;
 pub mod effect;
+pub mod foliage;
 pub mod graph;
 pub mod material;
 pub mod mesh;
 pub mod navmesh;
 pub mod sound_context;
+pub mod spline;
 pub mod terrain;
 
 pub struct GameSceneContext<'a> {
@@ -34,6 +38,7 @@ pub struct GameSceneContext<'a> {
     pub scene: &'a mut Scene,
     pub scene_content_root: &'a mut Handle<Node>,
     pub clipboard: &'a mut Clipboard,
+    pub annotations: &'a mut SceneAnnotations,
     pub message_sender: MessageSender,
     pub resource_manager: ResourceManager,
     pub serialization_context: Arc<SerializationContext>,
@@ -181,6 +186,71 @@ pub fn make_delete_selection_command(
     GameSceneCommand::new(command_group)
 }
 
+/// Creates a command, that moves selected nodes into a new group node. The group is attached to the
+/// parent of the first selected node. Returns `None` if there's nothing to group.
+pub fn make_group_selection_command(
+    editor_selection: &Selection,
+    game_scene: &GameScene,
+    engine: &Engine,
+) -> Option<GameSceneCommand> {
+    let graph = &engine.scenes[game_scene.scene].graph;
+    // Scene root cannot be moved into a group, the same as it cannot be deleted.
+    let nodes = selection_to_delete(editor_selection, game_scene).root_nodes(graph);
+    let parent = graph[*nodes.first()?].parent();
+    Some(GameSceneCommand::new(GroupNodesCommand::new(nodes, parent)))
+}
+
+/// Creates a command, that removes selected group nodes (pivots) and moves their children to the
+/// parents of the groups. Children of the groups become selected. Returns `None` if there's no
+/// selected groups.
+pub fn make_ungroup_selection_command(
+    editor_selection: &Selection,
+    game_scene: &GameScene,
+    engine: &Engine,
+) -> Option<GameSceneCommand> {
+    let graph = &engine.scenes[game_scene.scene].graph;
+    let groups = selection_to_delete(editor_selection, game_scene)
+        .nodes
+        .into_iter()
+        .filter(|node| {
+            let is_group = graph[*node].is_pivot();
+            if !is_group {
+                Log::warn(format!(
+                    "{} is not a group node (pivot) and cannot be ungrouped.",
+                    graph[*node].name()
+                ));
+            }
+            is_group
+        })
+        .collect::<Vec<_>>();
+
+    if groups.is_empty() {
+        return None;
+    }
+
+    let new_selection = groups
+        .iter()
+        .flat_map(|group| graph[*group].children())
+        .filter(|child| !groups.contains(*child))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut command_group =
+        CommandGroup::from(vec![GameSceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Graph(GraphSelection::from_list(new_selection)),
+            editor_selection.clone(),
+        ))]);
+    // Parent of a group is fetched when the group is ungrouped, so nested groups could be ungrouped
+    // in any order.
+    for group in groups {
+        command_group.push(GameSceneCommand::new(UngroupNodesCommand::new(group)));
+    }
+
+    Some(GameSceneCommand::new(
+        command_group.with_custom_name("Ungroup Nodes"),
+    ))
+}
+
 #[derive(Debug)]
 pub struct ChangeSelectionCommand {
     new_selection: Selection,
@@ -264,7 +334,11 @@ impl GameSceneCommandTrait for PasteCommand {
     fn execute(&mut self, context: &mut GameSceneContext) {
         match std::mem::replace(&mut self.state, PasteCommandState::Undefined) {
             PasteCommandState::NonExecuted => {
-                let paste_result = context.clipboard.paste(&mut context.scene.graph);
+                let paste_result = context.clipboard.paste(
+                    &mut context.scene.graph,
+                    context.serialization_context.clone(),
+                    context.resource_manager.clone(),
+                );
 
                 for &handle in paste_result.root_nodes.iter() {
                     context.scene.graph.link_nodes(handle, self.parent);
@@ -315,3 +315,39 @@ impl GameSceneCommandTrait for MoveNavmeshVertexCommand {
         self.set_position(fetch_navmesh(context, self.navmesh_node), position);
     }
 }
+
+#[derive(Debug)]
+pub struct SetNavmeshCommand {
+    navmesh_node: Handle<Node>,
+    navmesh: Navmesh,
+}
+
+impl SetNavmeshCommand {
+    pub fn new(navmesh_node: Handle<Node>, navmesh: Navmesh) -> Self {
+        Self {
+            navmesh_node,
+            navmesh,
+        }
+    }
+
+    fn swap(&mut self, context: &mut GameSceneContext) {
+        let navmesh = std::mem::take(&mut self.navmesh);
+        self.navmesh = context.scene.graph[self.navmesh_node]
+            .as_navigational_mesh_mut()
+            .set_navmesh(navmesh);
+    }
+}
+
+impl GameSceneCommandTrait for SetNavmeshCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Set Navmesh".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+}
@@ -41,6 +41,7 @@ macro_rules! define_sound_context_command {
 define_sound_context_command! {
     SetDistanceModelCommand("Set Distance Model", DistanceModel, distance_model, set_distance_model);
     SetRendererCommand("Set Renderer", Renderer, renderer, set_renderer);
+    SetDopplerFactorCommand("Set Doppler Factor", f32, doppler_factor, set_doppler_factor);
 }
 
 #[derive(Debug)]
@@ -0,0 +1,47 @@
+use crate::{command::GameSceneCommandTrait, scene::commands::GameSceneContext};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    scene::{
+        node::Node,
+        spline::{Spline, SplinePoint},
+    },
+};
+
+#[derive(Debug)]
+pub struct SetSplinePointsCommand {
+    spline: Handle<Node>,
+    points: Vec<SplinePoint>,
+}
+
+impl SetSplinePointsCommand {
+    pub fn new(spline: Handle<Node>, points: Vec<SplinePoint>) -> Self {
+        Self { spline, points }
+    }
+
+    fn swap(&mut self, context: &mut GameSceneContext) {
+        let Some(spline) = context
+            .scene
+            .graph
+            .try_get_mut_of_type::<Spline>(self.spline)
+        else {
+            Log::err("Invalid spline handle.");
+            return;
+        };
+
+        self.points = spline.set_points(std::mem::take(&mut self.points));
+    }
+}
+
+impl GameSceneCommandTrait for SetSplinePointsCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Set Spline Points".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+}
@@ -8,6 +8,7 @@ use fyrox::resource::texture::{
 };
 use fyrox::{
     core::pool::Handle,
+    material::MaterialResource,
     resource::texture::TextureResource,
     scene::{node::Node, terrain::Layer},
 };
@@ -166,23 +167,22 @@ pub struct ModifyTerrainLayerMaskCommand {
     // TODO: This is very memory-inefficient solution, it could be done
     //  better by either pack/unpack data on the fly, or by saving changes
     //  for sub-chunks.
-    old_masks: Vec<Vec<u8>>,
-    new_masks: Vec<Vec<u8>>,
-    layer: usize,
+    /// Masks of every layer of every chunk - `[chunk][layer]`. Painting on one layer could affect
+    /// other layers, so all of them are stored.
+    old_masks: Vec<Vec<Vec<u8>>>,
+    new_masks: Vec<Vec<Vec<u8>>>,
 }
 
 impl ModifyTerrainLayerMaskCommand {
     pub fn new(
         terrain: Handle<Node>,
-        old_masks: Vec<Vec<u8>>,
-        new_masks: Vec<Vec<u8>>,
-        layer: usize,
+        old_masks: Vec<Vec<Vec<u8>>>,
+        new_masks: Vec<Vec<Vec<u8>>>,
     ) -> Self {
         Self {
             terrain,
             old_masks,
             new_masks,
-            layer,
         }
     }
 
@@ -193,19 +193,21 @@ impl ModifyTerrainLayerMaskCommand {
             if i >= self.old_masks.len() || i >= self.new_masks.len() {
                 Log::err("Invalid mask index.")
             } else {
-                let old = &mut self.old_masks[i];
-                let new = &mut self.new_masks[i];
-                let chunk_mask = &mut chunk.layer_masks[self.layer];
+                for (chunk_mask, (old, new)) in chunk.layer_masks.iter_mut().zip(
+                    self.old_masks[i]
+                        .iter_mut()
+                        .zip(self.new_masks[i].iter_mut()),
+                ) {
+                    let mut texture_data = chunk_mask.data_ref();
 
-                let mut texture_data = chunk_mask.data_ref();
+                    for (mask_pixel, new_pixel) in
+                        texture_data.modify().data_mut().iter_mut().zip(new.iter())
+                    {
+                        *mask_pixel = *new_pixel;
+                    }
 
-                for (mask_pixel, new_pixel) in
-                    texture_data.modify().data_mut().iter_mut().zip(new.iter())
-                {
-                    *mask_pixel = *new_pixel;
+                    std::mem::swap(old, new);
                 }
-
-                std::mem::swap(old, new);
             }
         }
     }
@@ -224,3 +226,94 @@ impl GameSceneCommandTrait for ModifyTerrainLayerMaskCommand {
         self.swap(context);
     }
 }
+
+#[derive(Debug)]
+pub struct SetTerrainLayerMaterialCommand {
+    terrain: Handle<Node>,
+    layer: usize,
+    material: MaterialResource,
+}
+
+impl SetTerrainLayerMaterialCommand {
+    pub fn new(terrain: Handle<Node>, layer: usize, material: MaterialResource) -> Self {
+        Self {
+            terrain,
+            layer,
+            material,
+        }
+    }
+
+    fn swap(&mut self, context: &mut GameSceneContext) {
+        let terrain = context.scene.graph[self.terrain].as_terrain_mut();
+        if let Some(layer) = terrain.layers_mut().get_mut(self.layer) {
+            std::mem::swap(&mut layer.material, &mut self.material);
+        } else {
+            Log::err("Invalid layer index.")
+        }
+    }
+}
+
+impl GameSceneCommandTrait for SetTerrainLayerMaterialCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Set Terrain Layer Material".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+}
+
+#[derive(Debug)]
+pub struct ModifyTerrainHolesCommand {
+    terrain: Handle<Node>,
+    /// Hole masks of every chunk, `None` means that a chunk has no holes.
+    old_masks: Vec<Option<TextureResource>>,
+    new_masks: Vec<Option<TextureResource>>,
+}
+
+impl ModifyTerrainHolesCommand {
+    pub fn new(
+        terrain: Handle<Node>,
+        old_masks: Vec<Option<TextureResource>>,
+        new_masks: Vec<Option<TextureResource>>,
+    ) -> Self {
+        Self {
+            terrain,
+            old_masks,
+            new_masks,
+        }
+    }
+
+    fn swap(&mut self, context: &mut GameSceneContext) {
+        let terrain = context.scene.graph[self.terrain].as_terrain_mut();
+
+        for (chunk, (old, new)) in terrain
+            .chunks_mut()
+            .iter_mut()
+            .zip(self.old_masks.iter_mut().zip(self.new_masks.iter_mut()))
+        {
+            // Deep copy is needed, because the mask of the chunk could be modified in-place by
+            // further painting.
+            chunk.set_hole_mask(new.as_ref().map(|m| m.deep_clone()));
+            std::mem::swap(old, new);
+        }
+    }
+}
+
+impl GameSceneCommandTrait for ModifyTerrainHolesCommand {
+    fn name(&mut self, _context: &GameSceneContext) -> String {
+        "Modify Terrain Holes".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut GameSceneContext) {
+        self.swap(context);
+    }
+}
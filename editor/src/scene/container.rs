@@ -1,13 +1,15 @@
 use crate::{
     highlight::HighlightRenderPass,
     interaction::{
+        annotate::AnnotateInteractionMode, foliage::FoliageInteractionMode,
+        joint::JointInteractionMode, measure::MeasureInteractionMode,
         move_mode::MoveInteractionMode, navmesh::EditNavmeshMode,
         rotate_mode::RotateInteractionMode, scale_mode::ScaleInteractionMode,
-        select_mode::SelectInteractionMode, terrain::TerrainInteractionMode,
-        InteractionModeContainer,
+        select_mode::SelectInteractionMode, spline::SplineInteractionMode,
+        terrain::TerrainInteractionMode, InteractionModeContainer,
     },
     message::MessageSender,
-    scene::{controller::SceneController, GameScene, Selection},
+    scene::{clipboard::Clipboard, controller::SceneController, GameScene, Selection},
     scene_viewer::SceneViewer,
     settings::{keys::KeyBindings, Settings},
     ui_scene::{
@@ -49,6 +51,7 @@ impl EditorSceneEntry {
         message_sender: MessageSender,
         scene_viewer: &SceneViewer,
         highlighter: Option<Rc<RefCell<HighlightRenderPass>>>,
+        clipboard: Clipboard,
     ) -> Self {
         let game_scene = GameScene::from_native_scene(
             scene,
@@ -57,11 +60,11 @@ impl EditorSceneEntry {
             settings,
             message_sender.clone(),
             highlighter,
+            clipboard,
         );
 
         let mut interaction_modes = InteractionModeContainer::default();
         interaction_modes.add(SelectInteractionMode::new(
-            scene_viewer.frame(),
             scene_viewer.selection_frame(),
             message_sender.clone(),
         ));
@@ -91,6 +94,22 @@ impl EditorSceneEntry {
             message_sender.clone(),
             scene_viewer.frame(),
         ));
+        interaction_modes.add(FoliageInteractionMode::new(
+            engine,
+            message_sender.clone(),
+            scene_viewer.frame(),
+        ));
+        interaction_modes.add(SplineInteractionMode::new(
+            engine,
+            message_sender.clone(),
+            scene_viewer.frame(),
+        ));
+        interaction_modes.add(JointInteractionMode::new(message_sender.clone()));
+        interaction_modes.add(MeasureInteractionMode::new(
+            scene_viewer.frame(),
+            scene_viewer.canvas(),
+        ));
+        interaction_modes.add(AnnotateInteractionMode::new(message_sender.clone()));
 
         let mut entry = EditorSceneEntry {
             has_unsaved_changes: false,
@@ -332,6 +351,12 @@ impl EditorSceneEntry {
             }
         }
 
+        if button == MouseButton::Middle && settings.camera.orbit_around_selection {
+            if let Some(game_scene) = self.controller.downcast_mut::<GameScene>() {
+                game_scene.orbit_around_selection(&self.selection, engine);
+            }
+        }
+
         self.controller
             .on_mouse_down(button, pos, screen_bounds, engine, settings)
     }
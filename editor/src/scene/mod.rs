@@ -13,7 +13,7 @@ use crate::{
     },
     asset::item::AssetItem,
     audio::AudioBusSelection,
-    camera::{CameraController, PickingOptions},
+    camera::{objects_bounding_box, CameraController, PickingOptions},
     command::{GameSceneCommandStack, GameSceneCommandTrait},
     inspector::{
         editors::handle::HandlePropertyEditorMessage,
@@ -22,6 +22,7 @@ use crate::{
     interaction::navmesh::selection::NavmeshSelection,
     message::MessageSender,
     scene::{
+        annotation::SceneAnnotations,
         clipboard::Clipboard,
         commands::effect::make_set_audio_bus_property_command,
         commands::{
@@ -29,6 +30,7 @@ use crate::{
             CommandGroup, GameSceneCommand, GameSceneContext,
         },
         controller::SceneController,
+        node_flags::NodeFlags,
         selector::HierarchyNode,
     },
     settings::keys::KeyBindings,
@@ -36,10 +38,10 @@ use crate::{
     world::graph::selection::GraphSelection,
     Message, Settings,
 };
-use fyrox::graph::SceneGraph;
+use fyrox::graph::{NodeHandleMap, SceneGraph};
 use fyrox::{
     core::{
-        algebra::{Vector2, Vector3},
+        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
         color::Color,
         futures::executor::block_on,
         log::Log,
@@ -61,17 +63,23 @@ use fyrox::{
         texture::{Texture, TextureKind, TextureResource, TextureResourceExtension},
     },
     scene::{
-        animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+        animation::{
+            absm::AnimationBlendingStateMachine,
+            ik::{IkChain, IkSolver},
+            AnimationPlayer,
+        },
         base::BaseBuilder,
         camera::{Camera, Projection},
+        character::CharacterController,
         debug::{Line, SceneDrawingContext},
-        graph::{Graph, GraphUpdateSwitches},
+        graph::{physics::PhysicsDebugDrawOptions, Graph, GraphUpdateSwitches},
         light::{point::PointLight, spot::SpotLight},
         mesh::Mesh,
         navmesh::NavigationalMesh,
         node::Node,
         pivot::PivotBuilder,
         terrain::Terrain,
+        vehicle::Wheel,
         Scene, SceneContainer,
     },
 };
@@ -79,8 +87,10 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::{any::Any, fs::File, io::Write, path::Path};
 
+pub mod annotation;
 pub mod clipboard;
 pub mod dialog;
+pub mod node_flags;
 pub mod property;
 pub mod selector;
 pub mod settings;
@@ -110,6 +120,8 @@ pub struct GameScene {
     pub camera_state: Vec<(Handle<Node>, bool)>,
     pub node_property_changed_handler: SceneNodePropertyChangedHandler,
     pub highlighter: Option<Rc<RefCell<HighlightRenderPass>>>,
+    pub annotations: SceneAnnotations,
+    pub node_flags: NodeFlags,
 }
 
 impl GameScene {
@@ -120,6 +132,7 @@ impl GameScene {
         settings: &Settings,
         sender: MessageSender,
         highlighter: Option<Rc<RefCell<HighlightRenderPass>>>,
+        clipboard: Clipboard,
     ) -> Self {
         scene.rendering_options.render_target = Some(TextureResource::new_render_target(0, 0));
 
@@ -148,7 +161,7 @@ impl GameScene {
             command_stack: GameSceneCommandStack::new(false),
             preview_instance: None,
             scene: engine.scenes.add(scene),
-            clipboard: Default::default(),
+            clipboard,
             preview_camera: Default::default(),
             graph_switches: GraphUpdateSwitches {
                 physics2d: true,
@@ -159,26 +172,43 @@ impl GameScene {
                 // Update only editor's camera.
                 node_overrides: Some(Default::default()),
                 paused: false,
+                // Streamed content must not be saved with the scene.
+                streaming: false,
             },
             sender,
             camera_state: Default::default(),
             node_property_changed_handler: SceneNodePropertyChangedHandler,
             highlighter,
+            annotations: path.map(SceneAnnotations::load).unwrap_or_default(),
+            node_flags: Default::default(),
         }
     }
 
     pub fn make_purified_scene(&self, engine: &mut Engine) -> Scene {
+        self.make_purified_scene_with_map(engine).0
+    }
+
+    /// Same as [`Self::make_purified_scene`], but also returns a map of the handles of the nodes in
+    /// the edited scene to the handles of their copies in the purified scene.
+    pub fn make_purified_scene_with_map(
+        &self,
+        engine: &mut Engine,
+    ) -> (Scene, NodeHandleMap<Node>) {
         let scene = &mut engine.scenes[self.scene];
 
         let editor_root = self.editor_objects_root;
-        let (pure_scene, _) = scene.clone(
+        let (mut pure_scene, map) = scene.clone(
             self.scene_content_root,
             &mut |node, _| node != editor_root,
             &mut |_, _| {},
             &mut |_, _, _| {},
         );
 
-        pure_scene
+        // Nodes hidden in the editor must keep their visibility in the scene.
+        self.node_flags
+            .restore_visibility(&mut pure_scene.graph, &map);
+
+        (pure_scene, map)
     }
 
     #[allow(clippy::redundant_clone)] // false positive
@@ -209,6 +239,14 @@ impl GameScene {
                     }
                 }
 
+                if let Err(e) = self.annotations.save(path) {
+                    Log::err(format!(
+                        "Failed to save annotations of scene {}! Reason: {}",
+                        path.display(),
+                        e
+                    ));
+                }
+
                 Ok(format!("Scene {} was successfully saved!", path.display()))
             }
         } else {
@@ -230,6 +268,17 @@ impl GameScene {
 
         scene.drawing_context.clear_lines();
 
+        let camera_position = self.camera_controller.position(&scene.graph);
+        for annotation in self.annotations.annotations.iter() {
+            scene.drawing_context.draw_sphere(
+                annotation.position,
+                6,
+                6,
+                0.01 * annotation.position.metric_distance(&camera_position),
+                Color::opaque(255, 200, 0),
+            );
+        }
+
         if let Selection::Graph(selection) = editor_selection {
             for &node in selection.nodes() {
                 let node = &scene.graph[node];
@@ -242,8 +291,113 @@ impl GameScene {
         }
 
         if debug_settings.show_physics {
-            scene.graph.physics.draw(&mut scene.drawing_context);
-            scene.graph.physics2d.draw(&mut scene.drawing_context);
+            let options = PhysicsDebugDrawOptions {
+                contacts: debug_settings.show_physics_contacts,
+                joints: debug_settings.show_physics_joints,
+                centers_of_mass: debug_settings.show_physics_centers_of_mass,
+                ..Default::default()
+            };
+            scene
+                .graph
+                .physics
+                .draw_with_options(&mut scene.drawing_context, &options);
+            scene
+                .graph
+                .physics2d
+                .draw_with_options(&mut scene.drawing_context, &options);
+        }
+
+        fn draw_ik_chain(ik_chain: &IkChain, graph: &Graph, ctx: &mut SceneDrawingContext) {
+            let bones = ik_chain.collect_bones(graph);
+            for pair in bones.windows(2) {
+                ctx.add_line(Line {
+                    begin: graph[pair[0]].global_position(),
+                    end: graph[pair[1]].global_position(),
+                    color: Color::opaque(0, 200, 255),
+                });
+            }
+            for &bone in bones.iter() {
+                ctx.draw_wire_sphere(
+                    graph[bone].global_position(),
+                    0.025,
+                    8,
+                    Color::opaque(0, 200, 255),
+                );
+            }
+
+            if let Some(target) = graph.try_get(ik_chain.target()) {
+                let target_position = target.global_position();
+                ctx.draw_wire_sphere(target_position, 0.05, 12, Color::ORANGE);
+                if let Some(&effector) = bones.last() {
+                    ctx.add_line(Line {
+                        begin: graph[effector].global_position(),
+                        end: target_position,
+                        color: Color::ORANGE,
+                    });
+                }
+            }
+
+            if let IkSolver::TwoBone(ref solver) = ik_chain.solver() {
+                if let (Some(pole), Some(&middle)) =
+                    (graph.try_get(solver.pole_target), bones.get(1))
+                {
+                    let pole_position = pole.global_position();
+                    ctx.draw_wire_sphere(pole_position, 0.05, 12, Color::GREEN);
+                    ctx.add_line(Line {
+                        begin: graph[middle].global_position(),
+                        end: pole_position,
+                        color: Color::GREEN,
+                    });
+                }
+            }
+        }
+
+        fn draw_wheel(wheel: &Wheel, ctx: &mut SceneDrawingContext) {
+            let transform = wheel.global_transform();
+            let mount = wheel.global_position();
+            let up = wheel
+                .up_vector()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y);
+
+            // Suspension ray.
+            ctx.add_line(Line {
+                begin: mount,
+                end: mount - up * (wheel.suspension_rest_length() + wheel.radius()),
+                color: Color::GREEN,
+            });
+            ctx.draw_wire_sphere(mount, 0.03, 8, Color::GREEN);
+
+            // Wheel itself, it lies in the YZ plane of the wheel node.
+            let wheel_transform = transform
+                * Matrix4::new_translation(&Vector3::new(0.0, -wheel.suspension_length(), 0.0))
+                * UnitQuaternion::from_axis_angle(
+                    &Vector3::y_axis(),
+                    wheel.steering_angle() + std::f32::consts::FRAC_PI_2,
+                )
+                .to_homogeneous();
+            ctx.draw_circle(
+                Vector3::default(),
+                wheel.radius(),
+                24,
+                wheel_transform,
+                Color::opaque(0, 200, 255),
+            );
+        }
+
+        fn draw_character_controller(
+            controller: &CharacterController,
+            graph: &Graph,
+            ctx: &mut SceneDrawingContext,
+        ) {
+            if let Some(body) = graph.try_get(controller.body()) {
+                ctx.draw_capsule(
+                    controller.radius(),
+                    controller.height(),
+                    Matrix4::new_translation(&body.global_position()),
+                    Color::opaque(0, 200, 255),
+                );
+            }
         }
 
         fn draw_recursively(
@@ -329,6 +483,12 @@ impl GameScene {
                         }
                     }
                 }
+            } else if let Some(ik_chain) = node.query_component_ref::<IkChain>() {
+                draw_ik_chain(ik_chain, graph, ctx);
+            } else if let Some(wheel) = node.query_component_ref::<Wheel>() {
+                draw_wheel(wheel, ctx);
+            } else if let Some(controller) = node.query_component_ref::<CharacterController>() {
+                draw_character_controller(controller, graph, ctx);
             } else {
                 node.debug_draw(ctx);
             }
@@ -420,6 +580,16 @@ impl GameScene {
         }
     }
 
+    /// Moves the center of rotation of the camera to the center of the selected objects.
+    pub fn orbit_around_selection(&mut self, selection: &Selection, engine: &mut Engine) {
+        if let Selection::Graph(selection) = selection {
+            let graph = &mut engine.scenes[self.scene].graph;
+            if let Some(aabb) = objects_bounding_box(graph, selection.nodes()) {
+                self.camera_controller.orbit_around(aabb.center(), graph);
+            }
+        }
+    }
+
     pub fn do_command(
         &mut self,
         command: Box<dyn GameSceneCommandTrait>,
@@ -434,6 +604,7 @@ impl GameScene {
                 message_sender: self.sender.clone(),
                 scene_content_root: &mut self.scene_content_root,
                 clipboard: &mut self.clipboard,
+                annotations: &mut self.annotations,
                 resource_manager: engine.resource_manager.clone(),
                 serialization_context: engine.serialization_context.clone(),
             },
@@ -721,6 +892,7 @@ impl SceneController for GameScene {
             message_sender: self.sender.clone(),
             scene_content_root: &mut self.scene_content_root,
             clipboard: &mut self.clipboard,
+            annotations: &mut self.annotations,
             resource_manager: engine.resource_manager.clone(),
             serialization_context: engine.serialization_context.clone(),
         });
@@ -733,6 +905,7 @@ impl SceneController for GameScene {
             message_sender: self.sender.clone(),
             scene_content_root: &mut self.scene_content_root,
             clipboard: &mut self.clipboard,
+            annotations: &mut self.annotations,
             resource_manager: engine.resource_manager.clone(),
             serialization_context: engine.serialization_context.clone(),
         });
@@ -745,6 +918,7 @@ impl SceneController for GameScene {
             message_sender: self.sender.clone(),
             scene_content_root: &mut self.scene_content_root,
             clipboard: &mut self.clipboard,
+            annotations: &mut self.annotations,
             resource_manager: engine.resource_manager.clone(),
             serialization_context: engine.serialization_context.clone(),
         });
@@ -860,6 +1034,7 @@ impl SceneController for GameScene {
             message_sender: self.sender.clone(),
             scene_content_root: &mut self.scene_content_root,
             clipboard: &mut self.clipboard,
+            annotations: &mut self.annotations,
             resource_manager: engine.resource_manager.clone(),
             serialization_context: engine.serialization_context.clone(),
         });
@@ -893,6 +1068,55 @@ impl SceneController for GameScene {
                 self.camera_controller.fit_object(scene, *handle);
                 false
             }
+            Message::FrameSelection => {
+                if let Selection::Graph(selection) = selection {
+                    let scene = &mut engine.scenes[self.scene];
+                    self.camera_controller.fit_objects(scene, selection.nodes());
+                }
+                false
+            }
+            Message::SetNodeHidden { node, hidden } => {
+                self.node_flags
+                    .set_hidden(*node, *hidden, &mut engine.scenes[self.scene].graph);
+                true
+            }
+            Message::SetNodeLocked { node, locked } => {
+                self.node_flags.set_locked(*node, *locked);
+                true
+            }
+            Message::ToggleIsolateSelection => {
+                let graph = &mut engine.scenes[self.scene].graph;
+                if self.node_flags.is_isolation_enabled() {
+                    self.node_flags.exit_isolation(graph);
+                } else {
+                    match selection {
+                        Selection::Graph(selection) if !selection.is_empty() => {
+                            self.node_flags.isolate(
+                                selection.nodes(),
+                                self.scene_content_root,
+                                graph,
+                            );
+                        }
+                        _ => Log::warn("Select some objects to isolate them."),
+                    }
+                }
+                false
+            }
+            Message::SaveCameraBookmark(index) => {
+                self.camera_controller
+                    .save_bookmark(*index, &engine.scenes[self.scene].graph);
+                Log::info(format!("Camera bookmark {index} saved."));
+                false
+            }
+            Message::RestoreCameraBookmark(index) => {
+                if !self
+                    .camera_controller
+                    .restore_bookmark(*index, &mut engine.scenes[self.scene].graph)
+                {
+                    Log::warn(format!("There is no camera bookmark {index}."));
+                }
+                false
+            }
             Message::SyncNodeHandleName { view, handle } => {
                 let scene = &engine.scenes[self.scene];
                 engine
@@ -940,6 +1164,7 @@ impl SceneController for GameScene {
                     scene: &mut engine.scenes[self.scene],
                     scene_content_root: &mut self.scene_content_root,
                     clipboard: &mut self.clipboard,
+                    annotations: &mut self.annotations,
                     message_sender: self.sender.clone(),
                     resource_manager: engine.resource_manager.clone(),
                     serialization_context: engine.serialization_context.clone(),
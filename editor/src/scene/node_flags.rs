@@ -0,0 +1,183 @@
+//! Editor-only flags of scene nodes. Hidden nodes are not rendered in the editor, locked nodes cannot
+//! be selected in the viewport. The flags are never saved to the scene: hidden nodes are hidden by
+//! changing their visibility, and the original visibility is restored in the purified scene.
+
+use fyrox::{
+    core::pool::Handle,
+    fxhash::{FxHashMap, FxHashSet},
+    graph::{NodeHandleMap, SceneGraph},
+    scene::{graph::Graph, node::Node},
+};
+
+#[derive(Default)]
+pub struct NodeFlags {
+    hidden: FxHashSet<Handle<Node>>,
+    locked: FxHashSet<Handle<Node>>,
+    /// Nodes, that are temporarily hidden by the isolation mode.
+    isolated: FxHashSet<Handle<Node>>,
+    isolation_enabled: bool,
+    /// Visibility of the nodes before they were hidden by the editor.
+    original_visibility: FxHashMap<Handle<Node>, bool>,
+}
+
+impl NodeFlags {
+    pub fn is_hidden(&self, node: Handle<Node>) -> bool {
+        self.hidden.contains(&node)
+    }
+
+    /// Returns `true` if the node or any of its ancestors is locked.
+    pub fn is_locked(&self, node: Handle<Node>, graph: &Graph) -> bool {
+        let mut current = node;
+        while let Some(node_ref) = graph.try_get(current) {
+            if self.locked.contains(&current) {
+                return true;
+            }
+            current = node_ref.parent();
+        }
+        false
+    }
+
+    /// Returns `true` if the node itself is locked, ignoring its ancestors.
+    pub fn is_locked_directly(&self, node: Handle<Node>) -> bool {
+        self.locked.contains(&node)
+    }
+
+    pub fn is_isolation_enabled(&self) -> bool {
+        self.isolation_enabled
+    }
+
+    pub fn set_hidden(&mut self, node: Handle<Node>, hidden: bool, graph: &mut Graph) {
+        if hidden {
+            self.hidden.insert(node);
+        } else {
+            self.hidden.remove(&node);
+        }
+        self.sync_visibility(node, graph);
+    }
+
+    pub fn set_locked(&mut self, node: Handle<Node>, locked: bool) {
+        if locked {
+            self.locked.insert(node);
+        } else {
+            self.locked.remove(&node);
+        }
+    }
+
+    /// Hides every node of the graph, except the given nodes, their ancestors and descendants.
+    pub fn isolate(&mut self, nodes: &[Handle<Node>], root: Handle<Node>, graph: &mut Graph) {
+        self.exit_isolation(graph);
+
+        let mut visible = FxHashSet::default();
+        for &node in nodes {
+            let mut current = node;
+            while let Some(node_ref) = graph.try_get(current) {
+                visible.insert(current);
+                current = node_ref.parent();
+            }
+
+            let mut stack = vec![node];
+            while let Some(handle) = stack.pop() {
+                if let Some(node_ref) = graph.try_get(handle) {
+                    visible.insert(handle);
+                    stack.extend_from_slice(node_ref.children());
+                }
+            }
+        }
+
+        let mut stack = vec![root];
+        while let Some(handle) = stack.pop() {
+            let Some(node_ref) = graph.try_get(handle) else {
+                continue;
+            };
+            if visible.contains(&handle) {
+                stack.extend_from_slice(node_ref.children());
+            } else {
+                // Hidden node hides its descendants as well, so there's no need to go deeper.
+                self.isolated.insert(handle);
+            }
+        }
+
+        let isolated = self.isolated.iter().cloned().collect::<Vec<_>>();
+        for node in isolated {
+            self.sync_visibility(node, graph);
+        }
+
+        self.isolation_enabled = true;
+    }
+
+    pub fn exit_isolation(&mut self, graph: &mut Graph) {
+        for node in std::mem::take(&mut self.isolated) {
+            self.sync_visibility(node, graph);
+        }
+        self.isolation_enabled = false;
+    }
+
+    fn sync_visibility(&mut self, node: Handle<Node>, graph: &mut Graph) {
+        let Some(node_ref) = graph.try_get_mut(node) else {
+            self.original_visibility.remove(&node);
+            return;
+        };
+
+        if self.hidden.contains(&node) || self.isolated.contains(&node) {
+            self.original_visibility
+                .entry(node)
+                .or_insert_with(|| node_ref.visibility());
+            node_ref.set_visibility(false);
+        } else if let Some(visibility) = self.original_visibility.remove(&node) {
+            node_ref.set_visibility(visibility);
+        }
+    }
+
+    /// Restores original visibility of the nodes in a copy of the graph, the map should contain
+    /// pairs of the original handles and the handles of their copies.
+    pub fn restore_visibility(&self, copy: &mut Graph, map: &NodeHandleMap<Node>) {
+        for (&node, &visibility) in self.original_visibility.iter() {
+            let mut copy_handle = node;
+            if map.try_map(&mut copy_handle) {
+                if let Some(node_ref) = copy.try_get_mut(copy_handle) {
+                    node_ref.set_visibility(visibility);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NodeFlags;
+    use fyrox::graph::SceneGraph;
+    use fyrox::scene::{base::BaseBuilder, graph::Graph, pivot::PivotBuilder};
+
+    #[test]
+    fn test_hide_and_isolate() {
+        let mut graph = Graph::new();
+        let root = graph.get_root();
+        let a = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let b = PivotBuilder::new(BaseBuilder::new().with_visibility(false)).build(&mut graph);
+        let c = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        graph.link_nodes(c, a);
+
+        let mut flags = NodeFlags::default();
+
+        flags.set_hidden(a, true, &mut graph);
+        assert!(!graph[a].visibility());
+        flags.set_hidden(a, false, &mut graph);
+        assert!(graph[a].visibility());
+
+        flags.isolate(&[c], root, &mut graph);
+        assert!(graph[a].visibility());
+        assert!(graph[c].visibility());
+        assert!(!graph[b].visibility());
+
+        // Hiding an isolated node must keep its original visibility.
+        flags.set_hidden(b, true, &mut graph);
+        flags.exit_isolation(&mut graph);
+        assert!(!graph[b].visibility());
+        flags.set_hidden(b, false, &mut graph);
+        assert!(!graph[b].visibility());
+
+        flags.set_locked(a, true);
+        assert!(flags.is_locked(c, &graph));
+        assert!(!flags.is_locked(b, &graph));
+    }
+}
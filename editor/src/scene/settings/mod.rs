@@ -1,7 +1,7 @@
 use crate::{
-    inspector::editors::make_property_editors_container, message::MessageSender,
-    scene::settings::command::make_set_scene_property_command, GameScene, Message,
-    MessageDirection, MSG_SYNC_FLAG,
+    inspector::editors::make_property_editors_container, localization::window_title,
+    message::MessageSender, scene::settings::command::make_set_scene_property_command, GameScene,
+    Message, MessageDirection, MSG_SYNC_FLAG,
 };
 use fyrox::{
     core::{color::Color, pool::Handle},
@@ -16,7 +16,7 @@ use fyrox::{
         message::UiMessage,
         scroll_viewer::ScrollViewerBuilder,
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, UiNode, UserInterface,
     },
     resource::texture::TextureResource,
@@ -55,7 +55,7 @@ impl SceneSettingsWindow {
             )
             .open(false)
             .can_minimize(false)
-            .with_title(WindowTitle::text("Scene Settings"))
+            .with_title(window_title("Scene Settings", ctx))
             .build(ctx);
 
         let container = make_property_editors_container(sender);
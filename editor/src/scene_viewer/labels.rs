@@ -0,0 +1,170 @@
+//! Labels are UI widgets placed over the scene preview frame at screen-space positions of world-space
+//! points. They're used to show measurement results and scene annotations.
+
+use crate::{
+    message::MessageSender,
+    scene::{
+        commands::annotation::{RemoveAnnotationCommand, SetAnnotationTextCommand},
+        GameScene,
+    },
+};
+use fyrox::{
+    core::{
+        algebra::{Vector2, Vector3},
+        color::Color,
+        pool::Handle,
+    },
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        message::{MessageDirection, UiMessage},
+        text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    scene::camera::Camera,
+};
+
+/// Creates a read-only label, that does not intercept mouse input.
+pub fn make_label(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    BorderBuilder::new(
+        WidgetBuilder::new()
+            .with_hit_test_visibility(false)
+            .with_background(Brush::Solid(Color::from_rgba(0, 0, 0, 160)))
+            .with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_hit_test_visibility(false)
+                        .with_margin(Thickness::uniform(2.0)),
+                )
+                .with_text(text)
+                .build(ctx),
+            ),
+    )
+    .with_stroke_thickness(Thickness::uniform(0.0))
+    .build(ctx)
+}
+
+/// Moves the label to the screen-space position of the given world-space point. The label is hidden
+/// if the point is behind the camera.
+pub fn place_label(
+    label: Handle<UiNode>,
+    position: Vector3<f32>,
+    camera: &Camera,
+    frame_size: Vector2<f32>,
+    ui: &UserInterface,
+) {
+    let screen_position = camera.project(position, frame_size);
+
+    ui.send_message(WidgetMessage::visibility(
+        label,
+        MessageDirection::ToWidget,
+        screen_position.is_some(),
+    ));
+    if let Some(screen_position) = screen_position {
+        ui.send_message(WidgetMessage::desired_position(
+            label,
+            MessageDirection::ToWidget,
+            screen_position,
+        ));
+    }
+}
+
+struct AnnotationLabel {
+    text_box: Handle<UiNode>,
+    /// Last text, that was synced with the annotation.
+    text: String,
+}
+
+/// Editable labels of the annotations of the current scene.
+#[derive(Default)]
+pub struct AnnotationLabels {
+    labels: Vec<AnnotationLabel>,
+}
+
+impl AnnotationLabels {
+    pub fn clear(&mut self, ui: &UserInterface) {
+        for label in self.labels.drain(..) {
+            ui.send_message(WidgetMessage::remove(
+                label.text_box,
+                MessageDirection::ToWidget,
+            ));
+        }
+    }
+
+    pub fn sync(
+        &mut self,
+        game_scene: &GameScene,
+        camera: &Camera,
+        frame_size: Vector2<f32>,
+        canvas: Handle<UiNode>,
+        ui: &mut UserInterface,
+    ) {
+        let annotations = &game_scene.annotations.annotations;
+
+        while self.labels.len() > annotations.len() {
+            let label = self.labels.pop().unwrap();
+            ui.send_message(WidgetMessage::remove(
+                label.text_box,
+                MessageDirection::ToWidget,
+            ));
+        }
+
+        while self.labels.len() < annotations.len() {
+            let text_box = TextBoxBuilder::new(
+                WidgetBuilder::new()
+                    .with_min_size(Vector2::new(40.0, 0.0))
+                    .with_background(Brush::Solid(Color::from_rgba(60, 60, 20, 200))),
+            )
+            .build(&mut ui.build_ctx());
+            ui.send_message(WidgetMessage::link(
+                text_box,
+                MessageDirection::ToWidget,
+                canvas,
+            ));
+            self.labels.push(AnnotationLabel {
+                text_box,
+                text: Default::default(),
+            });
+        }
+
+        for (label, annotation) in self.labels.iter_mut().zip(annotations) {
+            if label.text != annotation.text {
+                label.text.clone_from(&annotation.text);
+                ui.send_message(TextMessage::text(
+                    label.text_box,
+                    MessageDirection::ToWidget,
+                    annotation.text.clone(),
+                ));
+            }
+
+            place_label(label.text_box, annotation.position, camera, frame_size, ui);
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, sender: &MessageSender) {
+        let Some(TextMessage::Text(text)) = message.data() else {
+            return;
+        };
+
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(index) = self
+            .labels
+            .iter()
+            .position(|label| label.text_box == message.destination())
+        {
+            if text.is_empty() {
+                // Labels are shifted after the removal, force the label to take the text of the next
+                // annotation.
+                self.labels[index].text.clear();
+                sender.do_scene_command(RemoveAnnotationCommand::new(index));
+            } else if *text != self.labels[index].text {
+                sender.do_scene_command(SetAnnotationTextCommand::new(index, text.clone()));
+            }
+        }
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{
     gui::{make_dropdown_list_option, make_dropdown_list_option_with_height},
     load_image,
+    localization::{tr, window_title},
     message::MessageSender,
     scene::container::EditorSceneEntry,
-    scene_viewer::gizmo::{SceneGizmo, SceneGizmoAction},
+    scene_viewer::{
+        gizmo::{SceneGizmo, SceneGizmoAction},
+        labels::AnnotationLabels,
+    },
     send_sync_message,
     utils::enable_widget,
     BuildProfile, DropdownListBuilder, GameScene, Message, Mode, SaveSceneConfirmationDialogAction,
@@ -19,6 +23,7 @@ use fyrox::{
         brush::Brush,
         button::{Button, ButtonBuilder, ButtonMessage},
         canvas::CanvasBuilder,
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
         decorator::DecoratorMessage,
         dropdown_list::DropdownListMessage,
         formatted_text::WrapMode,
@@ -43,15 +48,22 @@ use fyrox::{
 use std::cmp::Ordering;
 
 mod gizmo;
+pub mod labels;
 
 pub struct SceneViewer {
     frame: Handle<UiNode>,
     window: Handle<UiNode>,
     selection_frame: Handle<UiNode>,
+    canvas: Handle<UiNode>,
+    annotation_labels: AnnotationLabels,
     interaction_modes: FxHashMap<Uuid, Handle<UiNode>>,
     camera_projection: Handle<UiNode>,
     play: Handle<UiNode>,
+    pause: Handle<UiNode>,
+    pause_text: Handle<UiNode>,
+    step: Handle<UiNode>,
     stop: Handle<UiNode>,
+    keep_changes: Handle<UiNode>,
     build_profile: Handle<UiNode>,
     sender: MessageSender,
     interaction_mode_panel: Handle<UiNode>,
@@ -71,9 +83,14 @@ impl SceneViewer {
 
         let frame;
         let selection_frame;
+        let canvas;
         let camera_projection;
         let play;
+        let pause;
+        let pause_text;
+        let step;
         let stop;
+        let keep_changes;
         let build_profile;
 
         let interaction_mode_panel = StackPanelBuilder::new(
@@ -170,6 +187,41 @@ impl SceneViewer {
                                 .build(ctx);
                                 play
                             })
+                            .with_child({
+                                pause_text = TextBuilder::new(WidgetBuilder::new())
+                                    .with_text("||")
+                                    .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                                    .build(ctx);
+                                pause = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Pause or resume the game.",
+                                        ))
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_width(26.0),
+                                )
+                                .with_content(pause_text)
+                                .build(ctx);
+                                pause
+                            })
+                            .with_child({
+                                step = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Advance the paused game by one frame.",
+                                        ))
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_width(26.0),
+                                )
+                                .with_text(">|")
+                                .build(ctx);
+                                step
+                            })
                             .with_child({
                                 stop = ButtonBuilder::new(
                                     WidgetBuilder::new()
@@ -194,6 +246,31 @@ impl SceneViewer {
                                 )
                                 .build(ctx);
                                 stop
+                            })
+                            .with_child({
+                                keep_changes = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Keep Changes\nApply the transform changes made \
+                                            by the game to the edited scene when the play \
+                                            mode is stopped.",
+                                        ))
+                                        .with_vertical_alignment(VerticalAlignment::Center)
+                                        .with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center)
+                                            .with_margin(Thickness::left(2.0)),
+                                    )
+                                    .with_text("Keep Changes")
+                                    .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                keep_changes
                             }),
                     )
                     .with_orientation(Orientation::Horizontal)
@@ -259,24 +336,26 @@ impl SceneViewer {
                                         .build(ctx);
                                         frame
                                     })
-                                    .with_child(
-                                        CanvasBuilder::new(WidgetBuilder::new().with_child({
-                                            selection_frame = BorderBuilder::new(
-                                                WidgetBuilder::new()
-                                                    .with_visibility(false)
-                                                    .with_background(Brush::Solid(
-                                                        Color::from_rgba(255, 255, 255, 40),
-                                                    ))
-                                                    .with_foreground(Brush::Solid(Color::opaque(
-                                                        0, 255, 0,
-                                                    ))),
-                                            )
-                                            .with_stroke_thickness(Thickness::uniform(1.0))
+                                    .with_child({
+                                        canvas =
+                                            CanvasBuilder::new(WidgetBuilder::new().with_child({
+                                                selection_frame = BorderBuilder::new(
+                                                    WidgetBuilder::new()
+                                                        .with_visibility(false)
+                                                        .with_background(Brush::Solid(
+                                                            Color::from_rgba(255, 255, 255, 40),
+                                                        ))
+                                                        .with_foreground(Brush::Solid(
+                                                            Color::opaque(0, 255, 0),
+                                                        )),
+                                                )
+                                                .with_stroke_thickness(Thickness::uniform(1.0))
+                                                .build(ctx);
+                                                selection_frame
+                                            }))
                                             .build(ctx);
-                                            selection_frame
-                                        }))
-                                        .build(ctx),
-                                    ),
+                                        canvas
+                                    }),
                             )
                             .add_row(Row::stretch())
                             .add_column(Column::stretch())
@@ -289,7 +368,7 @@ impl SceneViewer {
                 .add_column(Column::stretch())
                 .build(ctx),
             )
-            .with_title(WindowTitle::text("Scene Preview"))
+            .with_title(window_title("Scene Preview", ctx))
             .build(ctx);
 
         Self {
@@ -298,8 +377,14 @@ impl SceneViewer {
             frame,
             interaction_modes: Default::default(),
             selection_frame,
+            canvas,
+            annotation_labels: Default::default(),
             camera_projection,
             play,
+            pause,
+            pause_text,
+            step,
+            keep_changes,
             interaction_mode_panel,
             contextual_actions,
             global_position_display,
@@ -326,6 +411,11 @@ impl SceneViewer {
         self.selection_frame
     }
 
+    /// Returns a canvas over the scene preview frame, that is used to place labels.
+    pub fn canvas(&self) -> Handle<UiNode> {
+        self.canvas
+    }
+
     pub fn handle_message(&mut self, message: &Message, engine: &mut Engine) {
         if let Message::SetInteractionMode(mode) = message {
             if let Some(&active_button) = self.interaction_modes.get(mode) {
@@ -384,6 +474,9 @@ impl SceneViewer {
     ) {
         let ui = &engine.user_interface;
 
+        self.annotation_labels
+            .handle_ui_message(message, &self.sender);
+
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             for (mode_id, mode_button) in self.interaction_modes.iter() {
                 if message.destination() == *mode_button {
@@ -395,6 +488,16 @@ impl SceneViewer {
                 self.sender.send(Message::SwitchToBuildMode);
             } else if message.destination() == self.stop {
                 self.sender.send(Message::SwitchToEditMode);
+            } else if message.destination() == self.pause {
+                self.sender.send(Message::TogglePlayModePause);
+            } else if message.destination() == self.step {
+                self.sender.send(Message::StepPlayMode);
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.keep_changes
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.sender.send(Message::SetKeepPlayModeChanges(*value));
             }
         } else if let Some(WidgetMessage::MouseDown { button, .. }) =
             message.data::<WidgetMessage>()
@@ -659,13 +762,12 @@ impl SceneViewer {
             self.set_title(
                 &engine.user_interface,
                 format!(
-                    "Scene Preview - {}",
+                    "{} - {}",
+                    tr("Scene Preview"),
                     entry
                         .path
                         .as_ref()
-                        .map_or("Unnamed Scene".to_string(), |p| p
-                            .to_string_lossy()
-                            .to_string())
+                        .map_or(tr("Unnamed Scene"), |p| p.to_string_lossy().to_string())
                 ),
             );
 
@@ -724,6 +826,23 @@ impl SceneViewer {
             MessageDirection::ToWidget,
             !mode.is_edit(),
         ));
+        let is_playing = matches!(mode, Mode::Play { .. });
+        for widget in [self.pause, self.step] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                is_playing,
+            ));
+        }
+        self.set_play_mode_paused(ui, false);
+    }
+
+    pub fn set_play_mode_paused(&self, ui: &UserInterface, paused: bool) {
+        ui.send_message(TextMessage::text(
+            self.pause_text,
+            MessageDirection::ToWidget,
+            if paused { ">" } else { "||" }.to_string(),
+        ));
     }
 
     pub fn set_render_target(&self, ui: &UserInterface, render_target: Option<TextureResource>) {
@@ -758,4 +877,26 @@ impl SceneViewer {
     pub fn update(&self, game_scene: &GameScene, engine: &mut Engine) {
         self.scene_gizmo.sync_rotations(game_scene, engine);
     }
+
+    pub fn update_annotation_labels(
+        &mut self,
+        game_scene: Option<&GameScene>,
+        engine: &mut Engine,
+    ) {
+        let Some(game_scene) = game_scene else {
+            self.annotation_labels.clear(&engine.user_interface);
+            return;
+        };
+
+        let frame_size = engine.user_interface.node(self.frame).actual_local_size();
+        let scene = &engine.scenes[game_scene.scene];
+        let camera = scene.graph[game_scene.camera_controller.camera].as_camera();
+        self.annotation_labels.sync(
+            game_scene,
+            camera,
+            frame_size,
+            self.canvas,
+            &mut engine.user_interface,
+        );
+    }
 }
@@ -0,0 +1,244 @@
+//! Script messages panel shows every script message dispatched by the game running in play mode. The
+//! game writes the messages to its log (see `--log-script-messages` argument of the executor) and the
+//! editor picks them from the output of the game process.
+
+use crate::{
+    gui::make_image_button_with_tooltip, load_image, localization::window_title, Brush, Color,
+    Engine,
+};
+use fyrox::{
+    core::{make_pretty_type_name, pool::Handle},
+    graph::SceneGraph,
+    gui::{
+        border::BorderBuilder,
+        button::ButtonMessage,
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListView, ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::WidgetBuilder,
+        window::WindowBuilder,
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, VerticalAlignment,
+    },
+    script::ScriptMessageTrafficEntry,
+};
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+pub struct ScriptMessagesPanel {
+    pub window: Handle<UiNode>,
+    messages: Handle<UiNode>,
+    clear: Handle<UiNode>,
+    capture: Handle<UiNode>,
+    capturing: bool,
+    sender: Sender<ScriptMessageTrafficEntry>,
+    receiver: Receiver<ScriptMessageTrafficEntry>,
+}
+
+impl ScriptMessagesPanel {
+    /// Maximum amount of entries in the list, the list is cleared when the limit is reached.
+    const MAX_ENTRIES: usize = 1000;
+
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let messages;
+        let clear;
+        let capture;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("ScriptMessagesPanel")
+                .with_width(400.0)
+                .with_height(300.0),
+        )
+        .open(false)
+        .with_title(window_title("Script Messages", ctx))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Left)
+                                .on_row(0)
+                                .on_column(0)
+                                .with_child({
+                                    clear = make_image_button_with_tooltip(
+                                        ctx,
+                                        24.0,
+                                        24.0,
+                                        load_image(include_bytes!("../resources/clear.png")),
+                                        "Clear the list.",
+                                    );
+                                    clear
+                                })
+                                .with_child({
+                                    capture = CheckBoxBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_content(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_vertical_alignment(VerticalAlignment::Center)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Capture (applied on next play)")
+                                        .build(ctx),
+                                    )
+                                    .checked(Some(false))
+                                    .build(ctx);
+                                    capture
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child({
+                        messages = ListViewBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::uniform(1.0))
+                                .on_row(1)
+                                .on_column(0),
+                        )
+                        .with_scroll_viewer(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(3.0)),
+                            )
+                            .with_horizontal_scroll_allowed(true)
+                            .with_vertical_scroll_allowed(true)
+                            .build(ctx),
+                        )
+                        .build(ctx);
+                        messages
+                    }),
+            )
+            .add_row(Row::strict(26.0))
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        let (sender, receiver) = channel();
+
+        Self {
+            window,
+            messages,
+            clear,
+            capture,
+            capturing: false,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Returns `true` if the game should be started with script messages logging.
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    /// Returns a sender that should be used to pass the entries parsed from the output of the game.
+    pub fn sender(&self) -> Sender<ScriptMessageTrafficEntry> {
+        self.sender.clone()
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.clear {
+                engine.user_interface.send_message(ListViewMessage::items(
+                    self.messages,
+                    MessageDirection::ToWidget,
+                    vec![],
+                ));
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.capture
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.capturing = *value;
+            }
+        }
+    }
+
+    pub fn update(&mut self, engine: &mut Engine) {
+        let mut count = engine
+            .user_interface
+            .node(self.messages)
+            .cast::<ListView>()
+            .map(|v| v.items().len())
+            .unwrap_or_default();
+
+        let mut item_to_bring_into_view = Handle::NONE;
+
+        while let Ok(entry) = self.receiver.try_recv() {
+            if count >= Self::MAX_ENTRIES {
+                engine.user_interface.send_message(ListViewMessage::items(
+                    self.messages,
+                    MessageDirection::ToWidget,
+                    vec![],
+                ));
+                count = 0;
+            }
+
+            let text = format!(
+                "[{:.2}s] {} from {} to {:?}\n{}\n{}",
+                entry.time,
+                make_pretty_type_name(&entry.type_name),
+                entry.sender,
+                entry.receivers,
+                entry.kind,
+                entry.payload
+            );
+
+            let ctx = &mut engine.user_interface.build_ctx();
+            let item = BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_background(Brush::Solid(if count % 2 == 0 {
+                        Color::opaque(70, 70, 70)
+                    } else {
+                        Color::opaque(40, 40, 40)
+                    }))
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_foreground(Brush::Solid(if entry.receivers.is_empty() {
+                                    Color::GOLD
+                                } else {
+                                    Color::ANTIQUE_WHITE
+                                })),
+                        )
+                        .with_text(text)
+                        .with_wrap(WrapMode::Word)
+                        .build(ctx),
+                    ),
+            )
+            .build(ctx);
+
+            engine
+                .user_interface
+                .send_message(ListViewMessage::add_item(
+                    self.messages,
+                    MessageDirection::ToWidget,
+                    item,
+                ));
+
+            item_to_bring_into_view = item;
+
+            count += 1;
+        }
+
+        if item_to_bring_into_view.is_some() {
+            engine
+                .user_interface
+                .send_message(ListViewMessage::bring_item_into_view(
+                    self.messages,
+                    MessageDirection::ToWidget,
+                    item_to_bring_into_view,
+                ));
+        }
+    }
+}
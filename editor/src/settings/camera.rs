@@ -23,6 +23,10 @@ pub struct CameraSettings {
     #[reflect(min_value = 0.0, max_value = 1000.0)]
     #[serde(default = "default_zoom_range")]
     pub zoom_range: Range<f32>,
+    /// If set, orbital rotation (middle mouse button) rotates the camera around the selected
+    /// objects, instead of the point in front of the camera.
+    #[serde(default)]
+    pub orbit_around_selection: bool,
 }
 
 impl Default for CameraSettings {
@@ -33,6 +37,7 @@ impl Default for CameraSettings {
             drag_speed: 0.01,
             zoom_speed: default_zoom_speed(),
             zoom_range: default_zoom_range(),
+            orbit_around_selection: false,
         }
     }
 }
@@ -20,6 +20,40 @@ pub struct DebuggingSettings {
     )]
     #[serde(default)]
     pub save_scene_in_text_form: bool,
+    #[reflect(
+        description = "Draws occluders in green and objects hidden by occlusion culling in red."
+    )]
+    #[serde(default)]
+    pub show_occlusion_culling: bool,
+    #[reflect(
+        description = "Draws amount of lights affecting visible surfaces in forward rendering \
+        as a heat map (blue - few lights, red - many lights)."
+    )]
+    #[serde(default)]
+    pub show_light_clusters: bool,
+    #[reflect(
+        description = "Tints surfaces lit by directional lights with a color of the shadow cascade \
+        they belong to (red - first, green - second, blue - third)."
+    )]
+    #[serde(default)]
+    pub show_shadow_cascades: bool,
+    #[reflect(
+        description = "Draws contact points and contact normals. Requires physics to be shown."
+    )]
+    #[serde(default = "default_true")]
+    pub show_physics_contacts: bool,
+    #[reflect(description = "Draws anchors of joints. Requires physics to be shown.")]
+    #[serde(default = "default_true")]
+    pub show_physics_joints: bool,
+    #[reflect(
+        description = "Draws centers of mass of dynamic rigid bodies. Requires physics to be shown."
+    )]
+    #[serde(default = "default_true")]
+    pub show_physics_centers_of_mass: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for DebuggingSettings {
@@ -33,6 +67,12 @@ impl Default for DebuggingSettings {
             show_camera_bounds: true,
             pictogram_size: 0.33,
             save_scene_in_text_form: false,
+            show_occlusion_culling: false,
+            show_light_clusters: false,
+            show_shadow_cascades: false,
+            show_physics_contacts: true,
+            show_physics_joints: true,
+            show_physics_centers_of_mass: true,
         }
     }
 }
@@ -1,5 +1,53 @@
-use fyrox::core::reflect::prelude::*;
+use crate::localization::DEFAULT_LANGUAGE;
+use fyrox::{
+    core::{reflect::prelude::*, uuid_provider},
+    gui::style::{Style, StyleResource},
+};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Visual style of the editor.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum EditorStyle {
+    #[default]
+    Dark,
+    Light,
+}
+
+uuid_provider!(EditorStyle = "0f5e2c0b-6e80-4cfa-a3f2-7dd2f2b5c8a1");
+
+impl EditorStyle {
+    pub fn make_style(self) -> StyleResource {
+        let style = match self {
+            EditorStyle::Dark => Style::dark_style(),
+            EditorStyle::Light => Style::light_style(),
+        };
+        StyleResource::new_ok(Default::default(), style)
+    }
+}
+
+/// A font, that is used to render characters that are missing in the main font of the editor.
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default, Reflect)]
+pub struct FallbackFont {
+    #[reflect(description = "Path to a font file (ttf, otf or ttc).")]
+    pub path: PathBuf,
+}
+
+uuid_provider!(FallbackFont = "5d0c3a4e-8b1f-4e27-9a6d-2f7c1b9e0d43");
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct GeneralSettings {
@@ -14,6 +62,55 @@ pub struct GeneralSettings {
     )]
     #[serde(default = "default_suspension_state")]
     pub suspend_unfocused_editor: bool,
+
+    #[reflect(
+        description = "Visual style of the editor. Some parts of the editor have fixed colors and do not depend on the style."
+    )]
+    #[serde(default)]
+    pub style: EditorStyle,
+
+    #[reflect(
+        description = "When set, the game in play mode is loaded from `game_dylib` library, which is rebuilt \
+    every time the source code of the game changes. The running game reloads the library and keeps its state. \
+    Requires `game-dylib` package and `dylib` feature of the executor, both are created by the project template."
+    )]
+    #[serde(default)]
+    pub hot_reload_game: bool,
+
+    #[reflect(
+        description = "Language of the editor. It is a name of a translation file without extension, built-in \
+    languages are: en, ru, zh_cn. Custom translations could be put in `locale` folder next to the editor executable."
+    )]
+    #[serde(default = "default_language")]
+    pub language: String,
+
+    #[reflect(
+        description = "Fonts that are used to render characters that are missing in the main font of the editor \
+    (for example, CJK characters). The fonts are checked in order, missing files are ignored."
+    )]
+    #[serde(default = "default_fallback_fonts")]
+    pub fallback_fonts: Vec<FallbackFont>,
+}
+
+fn default_language() -> String {
+    DEFAULT_LANGUAGE.to_string()
+}
+
+fn default_fallback_fonts() -> Vec<FallbackFont> {
+    // Common system fonts with CJK characters on Windows, macOS and Linux.
+    [
+        "C:/Windows/Fonts/msyh.ttc",
+        "C:/Windows/Fonts/msgothic.ttc",
+        "C:/Windows/Fonts/malgun.ttf",
+        "/System/Library/Fonts/PingFang.ttc",
+        "/System/Library/Fonts/Hiragino Sans GB.ttc",
+        "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+        "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    ]
+    .into_iter()
+    .map(|path| FallbackFont { path: path.into() })
+    .collect()
 }
 
 fn default_suspension_state() -> bool {
@@ -25,6 +122,10 @@ impl Default for GeneralSettings {
         Self {
             show_node_removal_dialog: true,
             suspend_unfocused_editor: default_suspension_state(),
+            style: Default::default(),
+            hot_reload_game: false,
+            language: default_language(),
+            fallback_fonts: default_fallback_fonts(),
         }
     }
 }
@@ -6,6 +6,45 @@ use fyrox::{
     },
 };
 use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines where a hot key of an action is active.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum HotKeyContext {
+    /// The hot key works everywhere, except focused text fields that handle the key by themselves.
+    #[default]
+    Global,
+    /// The hot key works only when the scene viewer has keyboard focus. Viewport hot keys take
+    /// priority over global ones.
+    Viewport,
+    /// The hot key works only when a text field has keyboard focus. Text editing hot keys take
+    /// priority over global ones.
+    TextEditing,
+}
+
+/// Custom binding of an editor action.
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
+pub struct ActionBinding {
+    /// Id of the action (see [`crate::action::Action::id`]).
+    pub action: String,
+    /// Hot key of the action. It is `None` for the actions, whose hot keys are stored in the fields
+    /// of [`KeyBindings`].
+    pub hot_key: Option<HotKey>,
+    pub context: HotKeyContext,
+}
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct TerrainKeyBindings {
@@ -50,12 +89,47 @@ pub struct KeyBindings {
     pub focus: HotKey,
     #[serde(default = "default_terrain_key_bindings")]
     pub terrain_key_bindings: TerrainKeyBindings,
+    #[serde(default = "default_joint_mode_hotkey")]
+    pub enable_joint_mode: HotKey,
+    #[serde(default = "default_command_palette_hotkey")]
+    pub command_palette: HotKey,
+    /// Custom bindings of the actions, they're edited in the key bindings window.
+    #[serde(default)]
+    #[reflect(hidden)]
+    pub action_bindings: Vec<ActionBinding>,
+}
+
+impl KeyBindings {
+    pub fn action_binding(&self, action: &str) -> Option<&ActionBinding> {
+        self.action_bindings.iter().find(|b| b.action == action)
+    }
+
+    /// Adds a new binding of the action or replaces existing.
+    pub fn set_action_binding(&mut self, binding: ActionBinding) {
+        if let Some(existing) = self
+            .action_bindings
+            .iter_mut()
+            .find(|b| b.action == binding.action)
+        {
+            *existing = binding;
+        } else {
+            self.action_bindings.push(binding);
+        }
+    }
 }
 
 fn default_focus_hotkey() -> HotKey {
     HotKey::from_key_code(KeyCode::KeyF)
 }
 
+fn default_joint_mode_hotkey() -> HotKey {
+    HotKey::from_key_code(KeyCode::Digit7)
+}
+
+fn default_command_palette_hotkey() -> HotKey {
+    HotKey::ctrl_key(KeyCode::KeyP)
+}
+
 fn default_terrain_key_bindings() -> TerrainKeyBindings {
     TerrainKeyBindings {
         modify_height_map_mode: HotKey::from_key_code(KeyCode::F1),
@@ -99,6 +173,9 @@ impl Default for KeyBindings {
             remove_selection: HotKey::from_key_code(KeyCode::Delete),
             focus: default_focus_hotkey(),
             terrain_key_bindings: default_terrain_key_bindings(),
+            enable_joint_mode: default_joint_mode_hotkey(),
+            command_palette: default_command_palette_hotkey(),
+            action_bindings: Default::default(),
         }
     }
 }
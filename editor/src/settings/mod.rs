@@ -1,15 +1,27 @@
 use crate::{
     inspector::editors::make_property_editors_container,
+    localization,
     message::MessageSender,
     settings::{
-        camera::CameraSettings, debugging::DebuggingSettings, general::GeneralSettings,
-        graphics::GraphicsSettings, keys::KeyBindings, keys::TerrainKeyBindings,
-        model::ModelSettings, move_mode::MoveInteractionModeSettings, navmesh::NavmeshSettings,
-        recent::RecentFiles, rotate_mode::RotateInteractionModeSettings, scene::SceneSettings,
-        selection::SelectionSettings, windows::WindowsSettings,
+        camera::CameraSettings,
+        debugging::DebuggingSettings,
+        general::{EditorStyle, FallbackFont, GeneralSettings},
+        graphics::GraphicsSettings,
+        keys::KeyBindings,
+        keys::TerrainKeyBindings,
+        model::ModelSettings,
+        move_mode::MoveInteractionModeSettings,
+        navmesh::NavmeshSettings,
+        physics::PhysicsSettings,
+        recent::RecentFiles,
+        rotate_mode::RotateInteractionModeSettings,
+        scene::SceneSettings,
+        selection::SelectionSettings,
+        windows::WindowsSettings,
     },
     Engine, MSG_SYNC_FLAG,
 };
+use fyrox::graph::SceneGraph;
 use fyrox::{
     core::{log::Log, pool::Handle, reflect::prelude::*, scope_profile},
     gui::{
@@ -17,11 +29,13 @@ use fyrox::{
         grid::{Column, GridBuilder, Row},
         inspector::{
             editors::{
+                collection::VecCollectionPropertyEditorDefinition,
                 enumeration::EnumPropertyEditorDefinition,
                 inspectable::InspectablePropertyEditorDefinition,
                 key::HotKeyPropertyEditorDefinition, PropertyEditorDefinitionContainer,
             },
-            InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction, PropertyChanged,
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+            PropertyChanged,
         },
         message::{MessageDirection, UiMessage},
         scroll_viewer::ScrollViewerBuilder,
@@ -31,6 +45,7 @@ use fyrox::{
         HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
     },
     renderer::{CsmSettings, QualitySettings, ShadowMapPrecision},
+    scene::collider::{CollisionLayer, CollisionLayers},
 };
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
@@ -51,6 +66,7 @@ pub mod keys;
 pub mod model;
 pub mod move_mode;
 pub mod navmesh;
+pub mod physics;
 pub mod recent;
 pub mod rotate_mode;
 pub mod scene;
@@ -77,6 +93,8 @@ pub struct SettingsData {
     pub camera: CameraSettings,
     pub navmesh: NavmeshSettings,
     pub key_bindings: KeyBindings,
+    #[serde(default)]
+    pub physics: PhysicsSettings,
     #[reflect(hidden)]
     pub scene_settings: HashMap<PathBuf, SceneSettings>,
     #[reflect(hidden)]
@@ -181,6 +199,9 @@ impl SettingsData {
         let container = make_property_editors_container(sender);
 
         container.insert(InspectablePropertyEditorDefinition::<GeneralSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<EditorStyle>::new());
+        container.insert(InspectablePropertyEditorDefinition::<FallbackFont>::new());
+        container.insert(VecCollectionPropertyEditorDefinition::<FallbackFont>::new());
         container.insert(InspectablePropertyEditorDefinition::<GraphicsSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<SelectionSettings>::new());
         container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
@@ -199,16 +220,27 @@ impl SettingsData {
         container.insert(InspectablePropertyEditorDefinition::<KeyBindings>::new());
         container.insert(InspectablePropertyEditorDefinition::<TerrainKeyBindings>::new());
         container.insert(HotKeyPropertyEditorDefinition);
+        container.insert(InspectablePropertyEditorDefinition::<PhysicsSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<CollisionLayers>::new());
+        container.insert(InspectablePropertyEditorDefinition::<CollisionLayer>::new());
+        container.insert(VecCollectionPropertyEditorDefinition::<CollisionLayer>::new());
 
         Arc::new(container)
     }
 
     fn handle_property_changed(&mut self, property_changed: &PropertyChanged) {
+        let prev_collision_layers = self.physics.collision_layers.clone();
+
         PropertyAction::from_field_kind(&property_changed.value).apply(
             &property_changed.path(),
             self,
             &mut Log::verify,
         );
+
+        // Keep the collision matrix symmetric.
+        self.physics
+            .collision_layers
+            .mirror_changes(&prev_collision_layers);
     }
 }
 
@@ -309,6 +341,21 @@ impl SettingsWindow {
         ));
     }
 
+    fn sync_values(&self, ui: &mut UserInterface, settings: &Settings) {
+        let ctx = ui
+            .node(self.inspector)
+            .cast::<Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+
+        if let Err(sync_errors) = ctx.sync(&**settings, ui, 0, true, Default::default()) {
+            for error in sync_errors {
+                Log::err(format!("Failed to sync property. Reason: {:?}", error))
+            }
+        }
+    }
+
     pub fn handle_message(
         &mut self,
         message: &UiMessage,
@@ -326,12 +373,47 @@ impl SettingsWindow {
                 ));
             } else if message.destination() == self.default {
                 **settings = Default::default();
+                engine
+                    .user_interface
+                    .set_style(settings.general.style.make_style());
+                localization::apply_settings(&settings.general, &mut engine.user_interface);
 
                 self.sync_to_model(&mut engine.user_interface, settings, sender);
             }
         } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
             if message.destination() == self.inspector {
+                let prev_style = settings.general.style;
+                let prev_language = settings.general.language.clone();
+                let prev_fallback_fonts = settings.general.fallback_fonts.clone();
+                let prev_collision_layers = settings.physics.collision_layers.clone();
+
                 settings.handle_property_changed(property_changed);
+
+                if settings.physics.collision_layers != prev_collision_layers {
+                    self.sync_values(&mut engine.user_interface, settings);
+                }
+
+                if settings.general.style != prev_style {
+                    engine
+                        .user_interface
+                        .set_style(settings.general.style.make_style());
+                }
+
+                if settings.general.language != prev_language {
+                    localization::set_language(
+                        &settings.general.language,
+                        &mut engine.user_interface,
+                    );
+                    // Property names are translated when the inspector is created.
+                    self.sync_to_model(&mut engine.user_interface, settings, sender);
+                }
+
+                if settings.general.fallback_fonts != prev_fallback_fonts {
+                    localization::set_fallback_fonts(
+                        &settings.general.fallback_fonts,
+                        &mut engine.user_interface,
+                    );
+                }
             }
         }
 
@@ -350,5 +432,15 @@ impl SettingsWindow {
                 Log::info("New graphics quality settings were successfully set!");
             }
         }
+
+        graphics_context
+            .renderer
+            .set_occlusion_culling_debug(settings.debugging.show_occlusion_culling);
+        graphics_context
+            .renderer
+            .set_light_clusters_debug(settings.debugging.show_light_clusters);
+        graphics_context
+            .renderer
+            .set_cascades_debug(settings.debugging.show_shadow_cascades);
     }
 }
@@ -7,6 +7,13 @@ pub struct MoveInteractionModeSettings {
     pub x_snap_step: f32,
     pub y_snap_step: f32,
     pub z_snap_step: f32,
+    /// Snaps the pivot of moved objects to the closest vertex of a mesh under the cursor.
+    #[serde(default)]
+    pub vertex_snapping: bool,
+    /// Snaps the pivot of moved objects to a surface under the cursor. Vertex snapping has higher
+    /// priority, if both are enabled.
+    #[serde(default)]
+    pub surface_snapping: bool,
 }
 
 impl Default for MoveInteractionModeSettings {
@@ -16,6 +23,8 @@ impl Default for MoveInteractionModeSettings {
             x_snap_step: 0.05,
             y_snap_step: 0.05,
             z_snap_step: 0.05,
+            vertex_snapping: false,
+            surface_snapping: false,
         }
     }
 }
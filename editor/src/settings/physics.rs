@@ -0,0 +1,11 @@
+use fyrox::{core::reflect::prelude::*, scene::collider::CollisionLayers};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Default, Debug, Reflect)]
+pub struct PhysicsSettings {
+    #[reflect(
+        description = "Named collision layers of the project. Every row defines with which layers the layer collides. \
+        Colliders could be assigned to a layer in the Inspector."
+    )]
+    pub collision_layers: CollisionLayers,
+}
@@ -3,11 +3,23 @@ use fyrox::core::pool::ErasedHandle;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Saved pose of the editor camera.
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+pub struct CameraBookmark {
+    pub position: Vector3<f32>,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub z_offset: f32,
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
 pub struct SceneCameraSettings {
     pub position: Vector3<f32>,
     pub yaw: f32,
     pub pitch: f32,
+    /// Numbered camera bookmarks of the scene.
+    #[serde(default)]
+    pub bookmarks: HashMap<usize, CameraBookmark>,
 }
 
 impl Default for SceneCameraSettings {
@@ -16,6 +28,7 @@ impl Default for SceneCameraSettings {
             position: Vector3::new(0.0, 1.0, 0.0),
             yaw: 0.0,
             pitch: 0.0,
+            bookmarks: Default::default(),
         }
     }
 }
@@ -0,0 +1,235 @@
+//! Statistics panel shows live information about the current scene and the renderer, it is
+//! useful to find out why a scene is slow without any external tools.
+
+use crate::{localization::window_title, scene::GameScene, Engine};
+use fyrox::{
+    asset::{entry::UnloadPolicy, manager::ResidencyReason},
+    core::{make_pretty_type_name, pool::Handle, reflect::Reflect},
+    engine::GraphicsContext,
+    graph::SceneGraph,
+    gui::{
+        message::MessageDirection,
+        scroll_viewer::ScrollViewerBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::WindowBuilder,
+        BuildContext, Thickness, UiNode,
+    },
+    renderer::profiler::{GpuPass, GpuPassTimings},
+    resource::texture::Texture,
+};
+use std::{collections::HashMap, fmt::Write};
+
+pub struct StatisticsWindow {
+    pub window: Handle<UiNode>,
+    text: Handle<UiNode>,
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.2} MiB", bytes as f32 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.2} KiB", bytes as f32 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+impl StatisticsWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let text;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("StatisticsPanel")
+                .with_width(300.0)
+                .with_height(400.0),
+        )
+        .open(false)
+        .with_title(window_title("Statistics", ctx))
+        .with_content(
+            ScrollViewerBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                .with_content({
+                    text =
+                        TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                            .build(ctx);
+                    text
+                })
+                .build(ctx),
+        )
+        .build(ctx);
+
+        Self { window, text }
+    }
+
+    fn collect(&self, game_scene: &GameScene, engine: &Engine) -> String {
+        let mut output = String::new();
+
+        let scene = &engine.scenes[game_scene.scene];
+
+        // Scene nodes by their types, the most common types first.
+        let mut type_counters = HashMap::<String, usize>::new();
+        for (_, node) in scene.graph.pair_iter() {
+            *type_counters
+                .entry(make_pretty_type_name(Reflect::type_name(node)).to_string())
+                .or_default() += 1;
+        }
+        let mut type_counters = type_counters.into_iter().collect::<Vec<_>>();
+        type_counters.sort_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+        });
+
+        let _ = writeln!(output, "Nodes: {}", scene.graph.node_count());
+        for (type_name, count) in type_counters {
+            let _ = writeln!(output, "\t{}: {}", type_name, count);
+        }
+
+        // Pool fragmentation shows how many free (reserved, but unused) slots the pool has.
+        let capacity = scene.graph.capacity();
+        let alive = scene.graph.node_count();
+        let _ = writeln!(
+            output,
+            "\nNode Pool: {} of {} slots used ({:.1}% fragmentation)",
+            alive,
+            capacity,
+            if capacity > 0 {
+                (capacity - alive) as f32 / capacity as f32 * 100.0
+            } else {
+                0.0
+            }
+        );
+
+        let mut texture_count = 0;
+        let mut texture_memory = 0;
+        for resource in engine.resource_manager.state().iter() {
+            if let Some(texture) = resource.try_cast::<Texture>() {
+                if texture.is_ok() {
+                    texture_count += 1;
+                    texture_memory += texture.data_ref().data().len();
+                }
+            }
+        }
+        let _ = writeln!(
+            output,
+            "\nTextures: {} ({})",
+            texture_count,
+            format_bytes(texture_memory)
+        );
+
+        self.collect_resources(engine, &mut output);
+
+        if let GraphicsContext::Initialized(ref graphics_context) = engine.graphics_context {
+            let renderer = &graphics_context.renderer;
+            let statistics = renderer.get_statistics();
+
+            let _ = writeln!(output, "\nRenderer\n{}", statistics);
+
+            if renderer.is_gpu_profiling_supported() {
+                self.collect_gpu_timings(&statistics.gpu_timings, &mut output);
+            } else {
+                let _ = writeln!(
+                    output,
+                    "GPU profiling is not supported by the graphics driver."
+                );
+            }
+        }
+
+        let _ = writeln!(output, "\nScene\n{}", scene.performance_statistics);
+
+        output
+    }
+
+    fn collect_gpu_timings(&self, timings: &GpuPassTimings, output: &mut String) {
+        // Width of the bar that corresponds to the total GPU time of the frame.
+        const BAR_WIDTH: f32 = 20.0;
+
+        let total = timings.total();
+        let _ = writeln!(output, "GPU Pass Timings ({:.3} ms)", total);
+        for (name, pass) in [
+            ("G-Buffer", GpuPass::GBuffer),
+            ("Shadows", GpuPass::Shadows),
+            ("SSAO", GpuPass::Ssao),
+            ("Lighting", GpuPass::Lighting),
+            ("Forward", GpuPass::Forward),
+            ("Post Effects", GpuPass::PostEffects),
+            ("UI", GpuPass::Ui),
+        ] {
+            let time = timings.get(pass);
+            let fraction = if total > 0.0 { time / total } else { 0.0 };
+            let _ = writeln!(
+                output,
+                "\t{}: {:.3} ms ({:.1}%) {}",
+                name,
+                time,
+                fraction * 100.0,
+                "|".repeat((fraction * BAR_WIDTH).round() as usize)
+            );
+        }
+    }
+
+    fn collect_resources(&self, engine: &Engine, output: &mut String) {
+        // Amount of the largest resources to show, the full list could be huge.
+        const MAX_LISTED_RESOURCES: usize = 32;
+
+        let state = engine.resource_manager.state();
+        let mut resident = state.resident_resources();
+        resident.sort_by_key(|r| std::cmp::Reverse(r.memory_usage));
+
+        let _ = writeln!(
+            output,
+            "\nResources: {} ({})\nUnload Policy: {:?}",
+            resident.len(),
+            format_bytes(resident.iter().map(|r| r.memory_usage).sum()),
+            state.unload_policy()
+        );
+        for info in resident.iter().take(MAX_LISTED_RESOURCES) {
+            let reason = match info.reason {
+                ResidencyReason::Pinned => "pinned".to_string(),
+                ResidencyReason::Used { users } => format!("{} user(s)", users),
+                ResidencyReason::Cached { time_to_live } => match state.unload_policy() {
+                    UnloadPolicy::Timed { .. } => {
+                        format!("cached, {:.0}s left", time_to_live.max(0.0))
+                    }
+                    _ => "cached".to_string(),
+                },
+            };
+            let _ = writeln!(
+                output,
+                "\t{} - {} ({})",
+                info.resource.kind(),
+                format_bytes(info.memory_usage),
+                reason
+            );
+        }
+        if resident.len() > MAX_LISTED_RESOURCES {
+            let _ = writeln!(
+                output,
+                "\t... and {} more",
+                resident.len() - MAX_LISTED_RESOURCES
+            );
+        }
+    }
+
+    pub fn update(&self, game_scene: &GameScene, engine: &mut Engine) {
+        let visible = engine.user_interface.node(self.window).visibility();
+
+        // GPU profiling has small overhead, so keep it enabled only while the panel is open.
+        if let GraphicsContext::Initialized(ref mut graphics_context) = engine.graphics_context {
+            let renderer = &mut graphics_context.renderer;
+            let enabled = visible && renderer.is_gpu_profiling_supported();
+            if renderer.is_gpu_profiling_enabled() != enabled {
+                renderer.set_gpu_profiling_enabled(enabled);
+            }
+        }
+
+        // Do not waste time on collecting statistics when nobody looks at them.
+        if !visible {
+            return;
+        }
+
+        engine.user_interface.send_message(TextMessage::text(
+            self.text,
+            MessageDirection::ToWidget,
+            self.collect(game_scene, engine),
+        ));
+    }
+}
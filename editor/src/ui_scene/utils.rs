@@ -8,13 +8,13 @@ use crate::{
         },
         selection::UiSelection,
     },
-    world::WorldViewerDataProvider,
+    world::{graph::item::SceneItemFlags, WorldViewerDataProvider},
 };
 use fyrox::{
     asset::{manager::ResourceManager, untyped::UntypedResource},
     core::{
-        futures::executor::block_on, make_pretty_type_name, make_relative_path, pool::ErasedHandle,
-        pool::Handle, reflect::Reflect,
+        log::Log, make_pretty_type_name, make_relative_path, pool::ErasedHandle, pool::Handle,
+        reflect::Reflect,
     },
     graph::{SceneGraph, SceneGraphNode},
     gui::{UiNode, UserInterface, UserInterfaceResourceExtension},
@@ -75,6 +75,24 @@ impl<'a> WorldViewerDataProvider for UiSceneWorldViewerDataProvider<'a> {
         })
     }
 
+    fn type_name_of(&self, node: ErasedHandle) -> Option<Cow<str>> {
+        self.ui
+            .try_get(node.into())
+            .map(|n| Cow::Borrowed(make_pretty_type_name(Reflect::type_name(n))))
+    }
+
+    fn script_name_of(&self, _node: ErasedHandle) -> Option<Cow<str>> {
+        None
+    }
+
+    fn tag_of(&self, _node: ErasedHandle) -> Option<Cow<str>> {
+        None
+    }
+
+    fn flags_of(&self, _node: ErasedHandle) -> Option<SceneItemFlags> {
+        None
+    }
+
     fn is_valid_handle(&self, node: ErasedHandle) -> bool {
         self.ui.try_get(node.into()).is_some()
     }
@@ -102,68 +120,83 @@ impl<'a> WorldViewerDataProvider for UiSceneWorldViewerDataProvider<'a> {
         }
     }
 
-    fn on_change_hierarchy_request(&self, child: ErasedHandle, parent: ErasedHandle) {
-        let child: Handle<UiNode> = child.into();
+    fn on_change_hierarchy_request(&self, children: &[ErasedHandle], parent: ErasedHandle) {
         let parent: Handle<UiNode> = parent.into();
 
-        if let Selection::Ui(ref selection) = self.selection {
-            if selection.widgets.contains(&child) {
-                let mut commands = Vec::new();
-
-                for &widget_handle in selection.widgets.iter() {
-                    // Make sure we won't create any loops - child must not have parent in its
-                    // descendants.
-                    let mut attach = true;
-                    let mut p = parent;
-                    while p.is_some() {
-                        if p == widget_handle {
-                            attach = false;
-                            break;
-                        }
-                        p = self.ui.node(p).parent();
-                    }
-
-                    if attach {
-                        commands.push(UiSceneCommand::new(LinkWidgetsCommand::new(
-                            widget_handle,
-                            parent,
-                        )));
-                    }
-                }
+        let mut commands = Vec::new();
+
+        for &widget_handle in children {
+            let widget_handle: Handle<UiNode> = widget_handle.into();
 
-                if !commands.is_empty() {
-                    self.sender
-                        .do_ui_scene_command(UiCommandGroup::from(commands));
+            // Make sure we won't create any loops - child must not have parent in its
+            // descendants.
+            let mut attach = true;
+            let mut p = parent;
+            while p.is_some() {
+                if p == widget_handle {
+                    attach = false;
+                    break;
                 }
+                p = self.ui.node(p).parent();
             }
+
+            if attach {
+                commands.push(UiSceneCommand::new(LinkWidgetsCommand::new(
+                    widget_handle,
+                    parent,
+                )));
+            }
+        }
+
+        if !commands.is_empty() {
+            self.sender
+                .do_ui_scene_command(UiCommandGroup::from(commands));
         }
     }
 
-    fn on_asset_dropped(&mut self, path: PathBuf, node: ErasedHandle) {
-        if let Ok(relative_path) = make_relative_path(path) {
-            // No model was loaded yet, do it.
-            if let Some(prefab) = self
-                .resource_manager
+    fn request_asset(&self, path: PathBuf) -> Option<UntypedResource> {
+        make_relative_path(path).ok().and_then(|relative_path| {
+            self.resource_manager
                 .try_request::<UserInterface>(relative_path)
-                .and_then(|m| block_on(m).ok())
-            {
-                let (instance, _) = prefab.instantiate(self.ui);
-
-                let sub_graph = self.ui.take_reserve_sub_graph(instance);
-
-                let group = vec![
-                    UiSceneCommand::new(AddUiPrefabCommand::new(sub_graph)),
-                    UiSceneCommand::new(LinkWidgetsCommand::new(instance, node.into())),
-                    // We also want to select newly instantiated model.
-                    UiSceneCommand::new(ChangeUiSelectionCommand::new(
-                        Selection::Ui(UiSelection::single_or_empty(instance)),
-                        self.selection.clone(),
-                    )),
-                ];
-
-                self.sender.do_ui_scene_command(UiCommandGroup::from(group));
-            }
+                .map(|prefab| prefab.into_untyped())
+        })
+    }
+
+    fn on_asset_loaded(&mut self, asset: UntypedResource, node: ErasedHandle) {
+        let Some(prefab) = asset.try_cast::<UserInterface>() else {
+            return;
+        };
+
+        if !prefab.is_ok() {
+            Log::err(format!(
+                "Unable to instantiate {:?} prefab, because it failed to load.",
+                prefab.kind()
+            ));
+            return;
         }
+
+        // The widget could be deleted while the prefab was loading.
+        let parent = if self.ui.try_get(node.into()).is_some() {
+            node.into()
+        } else {
+            self.ui.root()
+        };
+
+        let (instance, _) = prefab.instantiate(self.ui);
+
+        let sub_graph = self.ui.take_reserve_sub_graph(instance);
+
+        let group = vec![
+            UiSceneCommand::new(AddUiPrefabCommand::new(sub_graph)),
+            UiSceneCommand::new(LinkWidgetsCommand::new(instance, parent)),
+            // We also want to select newly instantiated model.
+            UiSceneCommand::new(ChangeUiSelectionCommand::new(
+                Selection::Ui(UiSelection::single_or_empty(instance)),
+                self.selection.clone(),
+            )),
+        ];
+
+        self.sender.do_ui_scene_command(UiCommandGroup::from(group));
     }
 
     fn validate(&self) -> Vec<(ErasedHandle, Result<(), String>)> {
@@ -0,0 +1,425 @@
+//! Bulk rename tool renames every selected node at once. It supports find and replace, prefixes and
+//! suffixes and naming patterns with auto-numbering (for example `Crate_{:02}`). Every rename is done
+//! as a single command group, so it could be undone in one step.
+
+use crate::{
+    localization::window_title,
+    message::MessageSender,
+    scene::{
+        commands::{graph::SetNodeNameCommand, CommandGroup, GameSceneCommand},
+        container::SceneContainer,
+        GameScene, Selection,
+    },
+};
+use fyrox::{
+    core::pool::Handle,
+    engine::Engine,
+    graph::SceneGraph,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBoxBuilder, TextCommitMode},
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::node::Node,
+};
+
+#[derive(Default, Clone, Debug)]
+struct RenameOptions {
+    find: String,
+    replace: String,
+    prefix: String,
+    suffix: String,
+    pattern: String,
+    start: usize,
+}
+
+/// Max width of a number in `{:N}` placeholders, wider placeholders are left as is.
+const MAX_PATTERN_WIDTH: usize = 16;
+
+/// Formats the naming pattern. `{}` is replaced with the number, `{:N}` and `{:0N}` are replaced
+/// with the number padded to `N` characters with spaces or zeros respectively, `{name}` is replaced
+/// with the current name of the node. Everything else (including placeholders wider than
+/// [`MAX_PATTERN_WIDTH`]) is left as is.
+fn format_pattern(pattern: &str, name: &str, number: usize) -> String {
+    let mut result = String::new();
+    let mut rest = pattern;
+    while let Some(begin) = rest.find('{') {
+        result.push_str(&rest[..begin]);
+        rest = &rest[begin..];
+
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let placeholder = &rest[1..end];
+        if placeholder == "name" {
+            result.push_str(name);
+        } else if placeholder.is_empty() {
+            result.push_str(&number.to_string());
+        } else if let Some(width) = placeholder
+            .strip_prefix(':')
+            .and_then(|format| format.parse::<usize>().ok())
+            .filter(|width| *width <= MAX_PATTERN_WIDTH)
+        {
+            if placeholder.starts_with(":0") {
+                result.push_str(&format!("{number:0width$}"));
+            } else {
+                result.push_str(&format!("{number:width$}"));
+            }
+        } else {
+            result.push_str(&rest[..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Makes a new name of a node with the given index in the selection.
+fn make_name(name: &str, index: usize, options: &RenameOptions) -> String {
+    let mut name = if options.find.is_empty() {
+        name.to_string()
+    } else {
+        name.replace(&options.find, &options.replace)
+    };
+    if !options.pattern.is_empty() {
+        name = format_pattern(&options.pattern, &name, options.start + index);
+    }
+    format!("{}{}{}", options.prefix, name, options.suffix)
+}
+
+pub struct BulkRenameWindow {
+    pub window: Handle<UiNode>,
+    find: Handle<UiNode>,
+    replace: Handle<UiNode>,
+    prefix: Handle<UiNode>,
+    suffix: Handle<UiNode>,
+    pattern: Handle<UiNode>,
+    start: Handle<UiNode>,
+    preview: Handle<UiNode>,
+    rename: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    options: RenameOptions,
+}
+
+fn make_text_box(row: usize, tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBoxBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(1)
+            .with_height(22.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text_commit_mode(TextCommitMode::Immediate)
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+fn make_label(row: usize, text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_button(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(100.0)
+            .with_height(24.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn selected_nodes(selection: &Selection) -> &[Handle<Node>] {
+    if let Selection::Graph(selection) = selection {
+        selection.nodes()
+    } else {
+        &[]
+    }
+}
+
+impl BulkRenameWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let find = make_text_box(0, "A substring to replace in the names.", ctx);
+        let replace = make_text_box(1, "A string, that will replace every found substring.", ctx);
+        let prefix = make_text_box(
+            2,
+            "A string, that will be added to the start of the names.",
+            ctx,
+        );
+        let suffix = make_text_box(
+            3,
+            "A string, that will be added to the end of the names.",
+            ctx,
+        );
+        let pattern = make_text_box(
+            4,
+            "A pattern of the new names, leave empty to keep the names. {} is replaced with \
+            the number of the node in the selection, {:03} - with the number padded with zeros \
+            and {name} - with the current name. For example: Crate_{:02}",
+            ctx,
+        );
+        let start = NumericUpDownBuilder::<usize>::new(
+            WidgetBuilder::new()
+                .on_row(5)
+                .on_column(1)
+                .with_height(22.0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_precision(0)
+        .with_value(0)
+        .build(ctx);
+        let preview = ListViewBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .build(ctx);
+        let rename = make_button("Rename", ctx);
+        let cancel = make_button("Cancel", ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(400.0)
+                .with_height(450.0)
+                .with_name("BulkRenameWindow"),
+        )
+        .open(false)
+        .with_title(window_title("Bulk Rename", ctx))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(0)
+                                .with_child(make_label(0, "Find", ctx))
+                                .with_child(find)
+                                .with_child(make_label(1, "Replace", ctx))
+                                .with_child(replace)
+                                .with_child(make_label(2, "Prefix", ctx))
+                                .with_child(prefix)
+                                .with_child(make_label(3, "Suffix", ctx))
+                                .with_child(suffix)
+                                .with_child(make_label(4, "Pattern", ctx))
+                                .with_child(pattern)
+                                .with_child(make_label(5, "Start Number", ctx))
+                                .with_child(start),
+                        )
+                        .add_column(Column::strict(90.0))
+                        .add_column(Column::stretch())
+                        .add_row(Row::auto())
+                        .add_row(Row::auto())
+                        .add_row(Row::auto())
+                        .add_row(Row::auto())
+                        .add_row(Row::auto())
+                        .add_row(Row::auto())
+                        .build(ctx),
+                    )
+                    .with_child(make_label(1, "Preview", ctx))
+                    .with_child(preview)
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(3)
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .with_child(rename)
+                                .with_child(cancel),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::auto())
+            .add_row(Row::auto())
+            .add_row(Row::stretch())
+            .add_row(Row::auto())
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            find,
+            replace,
+            prefix,
+            suffix,
+            pattern,
+            start,
+            preview,
+            rename,
+            cancel,
+            options: Default::default(),
+        }
+    }
+
+    pub fn open(&self, scenes: &SceneContainer, engine: &mut Engine) {
+        engine.user_interface.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        self.sync_preview(scenes, engine);
+    }
+
+    /// Updates the preview of the new names, does nothing if the window is closed.
+    pub fn sync_to_model(&self, scenes: &SceneContainer, engine: &mut Engine) {
+        if engine.user_interface.node(self.window).visibility() {
+            self.sync_preview(scenes, engine);
+        }
+    }
+
+    fn sync_preview(&self, scenes: &SceneContainer, engine: &mut Engine) {
+        let mut lines = Vec::new();
+        if let Some(entry) = scenes.current_scene_entry_ref() {
+            if let Some(game_scene) = entry.controller.downcast_ref::<GameScene>() {
+                let graph = &engine.scenes[game_scene.scene].graph;
+                for (index, node) in selected_nodes(&entry.selection).iter().enumerate() {
+                    if let Some(node) = graph.try_get(*node) {
+                        lines.push(format!(
+                            "{} -> {}",
+                            node.name(),
+                            make_name(node.name(), index, &self.options)
+                        ));
+                    }
+                }
+            }
+        }
+
+        let ui = &mut engine.user_interface;
+        let items = lines
+            .into_iter()
+            .map(|line| {
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                    .with_text(line)
+                    .build(&mut ui.build_ctx())
+            })
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.preview,
+            MessageDirection::ToWidget,
+            items,
+        ));
+    }
+
+    fn rename(&self, scenes: &SceneContainer, engine: &Engine, sender: &MessageSender) {
+        let Some(entry) = scenes.current_scene_entry_ref() else {
+            return;
+        };
+        let Some(game_scene) = entry.controller.downcast_ref::<GameScene>() else {
+            return;
+        };
+
+        let graph = &engine.scenes[game_scene.scene].graph;
+        let commands = selected_nodes(&entry.selection)
+            .iter()
+            .enumerate()
+            .filter_map(|(index, handle)| {
+                let name = graph.try_get(*handle)?.name();
+                let new_name = make_name(name, index, &self.options);
+                (new_name != name)
+                    .then(|| GameSceneCommand::new(SetNodeNameCommand::new(*handle, new_name)))
+            })
+            .collect::<Vec<_>>();
+
+        if !commands.is_empty() {
+            sender.do_scene_command(CommandGroup::from(commands).with_custom_name("Bulk Rename"));
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        scenes: &SceneContainer,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(TextMessage::Text(text)) = message.data() {
+            let option = if message.destination() == self.find {
+                &mut self.options.find
+            } else if message.destination() == self.replace {
+                &mut self.options.replace
+            } else if message.destination() == self.prefix {
+                &mut self.options.prefix
+            } else if message.destination() == self.suffix {
+                &mut self.options.suffix
+            } else if message.destination() == self.pattern {
+                &mut self.options.pattern
+            } else {
+                return;
+            };
+            option.clone_from(text);
+            self.sync_preview(scenes, engine);
+        } else if let Some(NumericUpDownMessage::Value(value)) = message.data() {
+            if message.destination() == self.start {
+                self.options.start = *value;
+                self.sync_preview(scenes, engine);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.rename {
+                self.rename(scenes, engine, sender);
+            } else if message.destination() == self.cancel {
+                engine.user_interface.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_pattern, make_name, RenameOptions};
+
+    #[test]
+    fn test_format_pattern() {
+        assert_eq!(format_pattern("Crate_{:02}", "Box", 3), "Crate_03");
+        assert_eq!(format_pattern("{name}_{}", "Box", 12), "Box_12");
+        assert_eq!(format_pattern("[{:3}]", "Box", 7), "[  7]");
+        assert_eq!(format_pattern("{foo} {", "Box", 1), "{foo} {");
+        assert_eq!(format_pattern("{:016}", "Box", 5), "0000000000000005");
+        assert_eq!(
+            format_pattern("Box_{:99999999999}", "Box", 5),
+            "Box_{:99999999999}"
+        );
+        assert_eq!(format_pattern("{:17}", "Box", 5), "{:17}");
+    }
+
+    #[test]
+    fn test_make_name() {
+        let options = RenameOptions {
+            find: "Box".to_string(),
+            replace: "Crate".to_string(),
+            prefix: "Big".to_string(),
+            suffix: "_LOD0".to_string(),
+            pattern: "{name}{:02}".to_string(),
+            start: 1,
+        };
+        assert_eq!(make_name("Box", 0, &options), "BigCrate01_LOD0");
+        assert_eq!(make_name("Box", 1, &options), "BigCrate02_LOD0");
+    }
+}
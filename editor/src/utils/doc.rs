@@ -1,3 +1,4 @@
+use crate::localization::window_title;
 use fyrox::{
     core::pool::Handle,
     gui::{
@@ -7,7 +8,7 @@ use fyrox::{
         text::TextMessage,
         text_box::TextBoxBuilder,
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, Thickness, UiNode, UserInterface,
     },
 };
@@ -40,7 +41,7 @@ impl DocWindow {
                 })
                 .build(ctx),
         )
-        .with_title(WindowTitle::text("Documentation"))
+        .with_title(window_title("Documentation", ctx))
         .build(ctx);
         Self { window, text }
     }
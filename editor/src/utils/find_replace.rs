@@ -0,0 +1,497 @@
+//! Find and replace tool searches scene nodes by their names or by values of their properties and
+//! replaces the found values in a batch. Every replacement is done as a single command group per
+//! scene, so it could be undone in one step.
+
+use crate::{
+    localization::window_title,
+    message::MessageSender,
+    scene::{
+        commands::{graph::SetNodeNameCommand, CommandGroup, GameSceneCommand, SetPropertyCommand},
+        container::SceneContainer,
+        GameScene,
+    },
+    Message,
+};
+use fyrox::{
+    core::{
+        log::Log,
+        pool::{ErasedHandle, Handle},
+        reflect::prelude::*,
+        uuid::Uuid,
+    },
+    engine::Engine,
+    graph::SceneGraph,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBoxBuilder, TextCommitMode},
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::node::Node,
+};
+
+#[derive(Default, Clone)]
+struct Query {
+    text: String,
+    replacement: String,
+    property: String,
+    case_sensitive: bool,
+    all_scenes: bool,
+}
+
+struct SearchMatch {
+    scene: Uuid,
+    node: Handle<Node>,
+    value: String,
+    // Only string values could be replaced.
+    replaceable: bool,
+}
+
+fn find_substring(
+    haystack: &str,
+    needle: &str,
+    from: usize,
+    case_sensitive: bool,
+) -> Option<usize> {
+    if needle.is_empty() {
+        return None;
+    }
+
+    if case_sensitive {
+        haystack[from..].find(needle).map(|i| i + from)
+    } else {
+        haystack[from..]
+            .char_indices()
+            .map(|(i, _)| i + from)
+            .find(|&i| {
+                haystack
+                    .get(i..i + needle.len())
+                    .is_some_and(|s| s.eq_ignore_ascii_case(needle))
+            })
+    }
+}
+
+fn replace_substring(
+    haystack: &str,
+    needle: &str,
+    replacement: &str,
+    case_sensitive: bool,
+) -> String {
+    let mut result = String::new();
+    let mut position = 0;
+    while let Some(found) = find_substring(haystack, needle, position, case_sensitive) {
+        result.push_str(&haystack[position..found]);
+        result.push_str(replacement);
+        position = found + needle.len();
+    }
+    result.push_str(&haystack[position..]);
+    result
+}
+
+/// Returns a string representation of the value and a flag, that tells whether the value is a
+/// string or not.
+fn value_to_string(value: &dyn Reflect) -> (String, bool) {
+    let mut string = None;
+    value.downcast_ref::<String>(&mut |s| string = s.cloned());
+    if let Some(string) = string {
+        (string, true)
+    } else {
+        let mut string = String::new();
+        value.as_reflect(&mut |inner| string = format!("{:?}", inner));
+        (string, false)
+    }
+}
+
+fn node_value(node: &Node, property: &str) -> Option<(String, bool)> {
+    if property.is_empty() {
+        Some((node.name_owned(), true))
+    } else {
+        let mut value = None;
+        node.as_reflect(&mut |reflect| {
+            reflect.resolve_path(property, &mut |result| {
+                if let Ok(field) = result {
+                    value = Some(value_to_string(field));
+                }
+            })
+        });
+        value
+    }
+}
+
+pub struct FindReplaceWindow {
+    pub window: Handle<UiNode>,
+    text: Handle<UiNode>,
+    replacement: Handle<UiNode>,
+    property: Handle<UiNode>,
+    case_sensitive: Handle<UiNode>,
+    all_scenes: Handle<UiNode>,
+    find: Handle<UiNode>,
+    replace_all: Handle<UiNode>,
+    results: Handle<UiNode>,
+    status: Handle<UiNode>,
+    query: Query,
+    matches: Vec<SearchMatch>,
+}
+
+fn make_text_box(row: usize, tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBoxBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(1)
+            .with_height(22.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text_commit_mode(TextCommitMode::Immediate)
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+fn make_label(row: usize, text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_check_box(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    CheckBoxBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+        .checked(Some(false))
+        .with_content(
+            TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text(text)
+                .build(ctx),
+        )
+        .build(ctx)
+}
+
+fn make_button(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(100.0)
+            .with_height(24.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl FindReplaceWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let text = make_text_box(0, "A substring to search for.", ctx);
+        let replacement =
+            make_text_box(1, "A string, that will replace every found substring.", ctx);
+        let property = make_text_box(
+            2,
+            "A path to a property (for example: `base.tag`), leave empty to search in node names.",
+            ctx,
+        );
+        let case_sensitive = make_check_box("Case Sensitive", ctx);
+        let all_scenes = make_check_box("All Opened Scenes", ctx);
+        let find = make_button("Find", ctx);
+        let replace_all = make_button("Replace All", ctx);
+        let results = ListViewBuilder::new(
+            WidgetBuilder::new()
+                .on_row(4)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .build(ctx);
+        let status = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(5)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .build(ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(400.0)
+                .with_height(450.0)
+                .with_name("FindReplaceWindow"),
+        )
+        .open(false)
+        .with_title(window_title("Find And Replace", ctx))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(0)
+                                .with_child(make_label(0, "Find", ctx))
+                                .with_child(text)
+                                .with_child(make_label(1, "Replace", ctx))
+                                .with_child(replacement)
+                                .with_child(make_label(2, "Property", ctx))
+                                .with_child(property),
+                        )
+                        .add_column(Column::strict(70.0))
+                        .add_column(Column::stretch())
+                        .add_row(Row::auto())
+                        .add_row(Row::auto())
+                        .add_row(Row::auto())
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_child(case_sensitive)
+                                .with_child(all_scenes),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(2)
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .with_child(find)
+                                .with_child(replace_all),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child(make_label(3, "Results", ctx))
+                    .with_child(results)
+                    .with_child(status),
+            )
+            .add_row(Row::auto())
+            .add_row(Row::auto())
+            .add_row(Row::auto())
+            .add_row(Row::auto())
+            .add_row(Row::stretch())
+            .add_row(Row::auto())
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            text,
+            replacement,
+            property,
+            case_sensitive,
+            all_scenes,
+            find,
+            replace_all,
+            results,
+            status,
+            query: Default::default(),
+            matches: Default::default(),
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn search(&mut self, scenes: &SceneContainer, engine: &Engine) {
+        self.matches.clear();
+
+        let current_scene = scenes.current_scene_entry_ref().map(|e| e.id);
+        for entry in scenes.iter() {
+            if !self.query.all_scenes && Some(entry.id) != current_scene {
+                continue;
+            }
+
+            let Some(game_scene) = entry.controller.downcast_ref::<GameScene>() else {
+                continue;
+            };
+
+            let graph = &engine.scenes[game_scene.scene].graph;
+            for handle in graph.traverse_handle_iter(game_scene.scene_content_root) {
+                if let Some((value, replaceable)) = node_value(&graph[handle], &self.query.property)
+                {
+                    if find_substring(&value, &self.query.text, 0, self.query.case_sensitive)
+                        .is_some()
+                    {
+                        self.matches.push(SearchMatch {
+                            scene: entry.id,
+                            node: handle,
+                            value,
+                            replaceable,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn sync_results(&self, scenes: &SceneContainer, engine: &mut Engine) {
+        let ui = &mut engine.user_interface;
+
+        let items = self
+            .matches
+            .iter()
+            .map(|m| {
+                let scene_name = scenes
+                    .iter()
+                    .find(|e| e.id == m.scene)
+                    .map(|e| e.name())
+                    .unwrap_or_default();
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                    .with_text(format!("{}: {} ({})", scene_name, m.value, m.node))
+                    .build(&mut ui.build_ctx())
+            })
+            .collect::<Vec<_>>();
+
+        ui.send_message(ListViewMessage::items(
+            self.results,
+            MessageDirection::ToWidget,
+            items,
+        ));
+
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            format!("{} match(es) found.", self.matches.len()),
+        ));
+    }
+
+    fn replace_all(
+        &mut self,
+        scenes: &mut SceneContainer,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        let current_scene = scenes.current_scene_entry_ref().map(|e| e.id);
+
+        let mut replaced = 0;
+        let mut skipped = 0;
+        for entry in scenes.iter_mut() {
+            let mut commands = Vec::new();
+            for m in self.matches.iter().filter(|m| m.scene == entry.id) {
+                if !m.replaceable {
+                    skipped += 1;
+                    continue;
+                }
+
+                let new_value = replace_substring(
+                    &m.value,
+                    &self.query.text,
+                    &self.query.replacement,
+                    self.query.case_sensitive,
+                );
+
+                commands.push(if self.query.property.is_empty() {
+                    GameSceneCommand::new(SetNodeNameCommand::new(m.node, new_value))
+                } else {
+                    GameSceneCommand::new(SetPropertyCommand::new(
+                        m.node,
+                        self.query.property.clone(),
+                        Box::new(new_value),
+                    ))
+                });
+            }
+
+            if commands.is_empty() {
+                continue;
+            }
+
+            replaced += commands.len();
+
+            let group = CommandGroup::from(commands).with_custom_name("Find And Replace");
+            if Some(entry.id) == current_scene {
+                sender.do_scene_command(group);
+            } else if let Some(game_scene) = entry.controller.downcast_mut::<GameScene>() {
+                game_scene.do_command(Box::new(group), &mut entry.selection, engine);
+                entry.has_unsaved_changes = true;
+            }
+        }
+
+        if skipped > 0 {
+            Log::warn(format!(
+                "{} value(s) were not replaced, because only string properties could be replaced.",
+                skipped
+            ));
+        }
+
+        engine.user_interface.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            format!("{} value(s) replaced.", replaced),
+        ));
+
+        self.matches.clear();
+        engine.user_interface.send_message(ListViewMessage::items(
+            self.results,
+            MessageDirection::ToWidget,
+            vec![],
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        scenes: &mut SceneContainer,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.text {
+                self.query.text.clone_from(text);
+            } else if message.destination() == self.replacement {
+                self.query.replacement.clone_from(text);
+            } else if message.destination() == self.property {
+                self.query.property.clone_from(text);
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.case_sensitive {
+                self.query.case_sensitive = *value;
+            } else if message.destination() == self.all_scenes {
+                self.query.all_scenes = *value;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.find {
+                self.search(scenes, engine);
+                self.sync_results(scenes, engine);
+            } else if message.destination() == self.replace_all {
+                // Search again, the scenes could be changed since the last search.
+                self.search(scenes, engine);
+                self.replace_all(scenes, engine, sender);
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.results {
+                if let Some(m) = self.matches.get(*index) {
+                    // The scene could be closed since the last search.
+                    if scenes.entry_by_scene_id(m.scene).is_none() {
+                        return;
+                    }
+
+                    if scenes.current_scene_entry_ref().map(|e| e.id) != Some(m.scene) {
+                        sender.send(Message::SetCurrentScene(m.scene));
+                    }
+                    sender.send(Message::SelectObject {
+                        handle: ErasedHandle::from(m.node),
+                    });
+                }
+            }
+        }
+    }
+}
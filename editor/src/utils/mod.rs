@@ -11,7 +11,9 @@ use fyrox::{
 };
 use std::{fs::File, io::Read, path::Path};
 
+pub mod bulk_rename;
 pub mod doc;
+pub mod find_replace;
 pub mod path_fixer;
 pub mod ragdoll;
 
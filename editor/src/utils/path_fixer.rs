@@ -1,7 +1,7 @@
 //! Special utility that allows you to fix paths to resources. It is very useful if you've
 //! moved a resource in a file system, but a scene has old path.
 
-use crate::{make_scene_file_filter, Message};
+use crate::{localization::window_title, make_scene_file_filter, Message};
 use fyrox::asset::untyped::ResourceKind;
 use fyrox::graph::SceneGraph;
 use fyrox::{
@@ -93,7 +93,7 @@ impl PathFixer {
         let fix;
         let resource_path;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(500.0))
-            .with_title(WindowTitle::text("Path Fixer"))
+            .with_title(window_title("Path Fixer", ctx))
             .open(false)
             .with_content(
                 GridBuilder::new(
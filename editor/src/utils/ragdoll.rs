@@ -1,5 +1,6 @@
 use crate::{
     inspector::editors::make_property_editors_container,
+    localization::window_title,
     message::MessageSender,
     scene::{
         commands::{
@@ -28,7 +29,7 @@ use fyrox::{
         stack_panel::StackPanelBuilder,
         utils::make_simple_tooltip,
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowMessage, WindowTitle},
+        window::{WindowBuilder, WindowMessage},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
     },
     scene::{
@@ -119,6 +120,41 @@ pub struct RagdollPreset {
     rag doll. It could be used to filter out interactions between character capsule and any part of the rag doll."
     )]
     solver_groups: InteractionGroups,
+    #[reflect(
+        description = "Scale of the size of the head collider relative to the size, calculated using average \
+    human body proportions.",
+        min_value = 0.1,
+        step = 0.05
+    )]
+    head_scale: f32,
+    #[reflect(
+        description = "Scale of the size of the colliders of the hips, spine and neck relative to the size, calculated \
+    using average human body proportions.",
+        min_value = 0.1,
+        step = 0.05
+    )]
+    torso_scale: f32,
+    #[reflect(
+        description = "Scale of the thickness of the colliders of the arms relative to the thickness, calculated using \
+    average human body proportions.",
+        min_value = 0.1,
+        step = 0.05
+    )]
+    arms_scale: f32,
+    #[reflect(
+        description = "Scale of the thickness of the colliders of the legs relative to the thickness, calculated using \
+    average human body proportions.",
+        min_value = 0.1,
+        step = 0.05
+    )]
+    legs_scale: f32,
+    #[reflect(
+        description = "Scale of the size of the colliders of the hands and feet relative to the size, calculated using \
+    average human body proportions.",
+        min_value = 0.1,
+        step = 0.05
+    )]
+    hands_and_feet_scale: f32,
 }
 
 impl Default for RagdollPreset {
@@ -150,6 +186,11 @@ impl Default for RagdollPreset {
             can_sleep: true,
             collision_groups: Default::default(),
             solver_groups: Default::default(),
+            head_scale: 1.0,
+            torso_scale: 1.0,
+            arms_scale: 1.0,
+            legs_scale: 1.0,
+            hands_and_feet_scale: 1.0,
         }
     }
 }
@@ -463,9 +504,12 @@ impl RagdollPreset {
         sender: &MessageSender,
     ) {
         let base_size = self.measure_base_size(graph);
-        let hand_radius = 0.3 * base_size;
-        let head_radius = 0.5 * base_size;
-        let foot_radius = 0.2 * base_size;
+        let hand_radius = 0.3 * base_size * self.hands_and_feet_scale;
+        let head_radius = 0.5 * base_size * self.head_scale;
+        let foot_radius = 0.2 * base_size * self.hands_and_feet_scale;
+        let leg_size = base_size * self.legs_scale;
+        let arm_size = base_size * self.arms_scale;
+        let torso_size = base_size * self.torso_scale;
 
         let head_mass = 0.0823 * self.total_mass;
         let thorax_mass = 0.1856 * self.total_mass;
@@ -487,7 +531,7 @@ impl RagdollPreset {
         let left_up_leg = self.make_oriented_capsule(
             self.left_up_leg,
             self.left_leg,
-            0.35 * base_size,
+            0.35 * leg_size,
             thigh_mass,
             "RagdollLeftUpLeg",
             ragdoll,
@@ -497,7 +541,7 @@ impl RagdollPreset {
         let left_leg = self.make_oriented_capsule(
             self.left_leg,
             self.left_foot,
-            0.3 * base_size,
+            0.3 * leg_size,
             leg_mass,
             "RagdollLeftLeg",
             ragdoll,
@@ -506,7 +550,7 @@ impl RagdollPreset {
 
         let left_foot = self.make_sphere(
             self.left_foot,
-            0.2 * base_size,
+            foot_radius,
             foot_mass,
             "RagdollLeftFoot",
             ragdoll,
@@ -517,7 +561,7 @@ impl RagdollPreset {
         let right_up_leg = self.make_oriented_capsule(
             self.right_up_leg,
             self.right_leg,
-            0.35 * base_size,
+            0.35 * leg_size,
             thigh_mass,
             "RagdollRightUpLeg",
             ragdoll,
@@ -527,7 +571,7 @@ impl RagdollPreset {
         let right_leg = self.make_oriented_capsule(
             self.right_leg,
             self.right_foot,
-            0.3 * base_size,
+            0.3 * leg_size,
             leg_mass,
             "RagdollRightLeg",
             ragdoll,
@@ -546,7 +590,7 @@ impl RagdollPreset {
 
         let hips = self.make_cuboid(
             self.hips,
-            Vector3::new(base_size * 0.5, base_size * 0.2, base_size * 0.4),
+            Vector3::new(torso_size * 0.5, torso_size * 0.2, torso_size * 0.4),
             pelvis_mass,
             "RagdollHips",
             ragdoll,
@@ -555,7 +599,7 @@ impl RagdollPreset {
 
         let spine = self.make_cuboid(
             self.spine,
-            Vector3::new(base_size * 0.45, base_size * 0.2, base_size * 0.4),
+            Vector3::new(torso_size * 0.45, torso_size * 0.2, torso_size * 0.4),
             abdomen_mass,
             "RagdollSpine",
             ragdoll,
@@ -564,7 +608,7 @@ impl RagdollPreset {
 
         let spine1 = self.make_cuboid(
             self.spine1,
-            Vector3::new(base_size * 0.45, base_size * 0.2, base_size * 0.4),
+            Vector3::new(torso_size * 0.45, torso_size * 0.2, torso_size * 0.4),
             thorax_mass / 2.0,
             "RagdollSpine1",
             ragdoll,
@@ -573,7 +617,7 @@ impl RagdollPreset {
 
         let spine2 = self.make_cuboid(
             self.spine2,
-            Vector3::new(base_size * 0.45, base_size * 0.2, base_size * 0.4),
+            Vector3::new(torso_size * 0.45, torso_size * 0.2, torso_size * 0.4),
             thorax_mass / 2.0,
             "RagdollSpine2",
             ragdoll,
@@ -584,7 +628,7 @@ impl RagdollPreset {
         let left_shoulder = self.make_oriented_capsule(
             self.left_shoulder,
             self.left_arm,
-            0.2 * base_size,
+            0.2 * arm_size,
             upper_arm_mass / 2.0,
             "RagdollLeftShoulder",
             ragdoll,
@@ -594,7 +638,7 @@ impl RagdollPreset {
         let left_arm = self.make_oriented_capsule(
             self.left_arm,
             self.left_fore_arm,
-            0.2 * base_size,
+            0.2 * arm_size,
             upper_arm_mass / 2.0,
             "RagdollLeftArm",
             ragdoll,
@@ -604,7 +648,7 @@ impl RagdollPreset {
         let left_fore_arm = self.make_oriented_capsule(
             self.left_fore_arm,
             self.left_hand,
-            0.2 * base_size,
+            0.2 * arm_size,
             fore_arm_mass,
             "RagdollLeftForeArm",
             ragdoll,
@@ -625,7 +669,7 @@ impl RagdollPreset {
         let right_shoulder = self.make_oriented_capsule(
             self.right_shoulder,
             self.right_arm,
-            0.2 * base_size,
+            0.2 * arm_size,
             upper_arm_mass / 2.0,
             "RagdollRightShoulder",
             ragdoll,
@@ -635,7 +679,7 @@ impl RagdollPreset {
         let right_arm = self.make_oriented_capsule(
             self.right_arm,
             self.right_fore_arm,
-            0.2 * base_size,
+            0.2 * arm_size,
             upper_arm_mass / 2.0,
             "RagdollRightArm",
             ragdoll,
@@ -645,7 +689,7 @@ impl RagdollPreset {
         let right_fore_arm = self.make_oriented_capsule(
             self.right_fore_arm,
             self.right_hand,
-            0.2 * base_size,
+            0.2 * arm_size,
             fore_arm_mass,
             "RagdollRightForeArm",
             ragdoll,
@@ -665,7 +709,7 @@ impl RagdollPreset {
         let neck = self.make_oriented_capsule(
             self.neck,
             self.head,
-            0.2 * base_size,
+            0.2 * torso_size,
             0.3 * head_mass,
             "RagdollNeck",
             ragdoll,
@@ -674,7 +718,7 @@ impl RagdollPreset {
 
         let head = self.make_sphere(
             self.head,
-            0.5 * base_size,
+            head_radius,
             0.7 * head_mass,
             "RadgollHead",
             ragdoll,
@@ -1013,7 +1057,7 @@ impl RagdollWizard {
                 .with_name("RagdollWizard"),
         )
         .open(false)
-        .with_title(WindowTitle::text("Ragdoll Wizard"))
+        .with_title(window_title("Ragdoll Wizard", ctx))
         .with_content(
             GridBuilder::new(
                 WidgetBuilder::new()
@@ -0,0 +1,458 @@
+//! Validation panel lists problems of the current scene: errors reported by the nodes themselves,
+//! resources that failed to load and properties that refer to deleted nodes. Clicking on a problem
+//! selects its node, some of the problems could be fixed automatically.
+
+use crate::{
+    localization::window_title,
+    message::MessageSender,
+    scene::{
+        commands::{
+            graph::{AddNodeCommand, GroupNodesCommand},
+            CommandGroup, GameSceneCommand, SetPropertyCommand,
+        },
+        GameScene,
+    },
+    Message,
+};
+use fyrox::{
+    asset::{collect_used_resources, state::ResourceState},
+    core::{
+        color::Color,
+        pool::{ErasedHandle, Handle},
+        reflect::prelude::*,
+    },
+    engine::Engine,
+    fxhash::FxHashSet,
+    graph::SceneGraph,
+    gui::{
+        brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    scene::{
+        base::BaseBuilder,
+        collider::ColliderBuilder,
+        dim2,
+        graph::Graph,
+        node::Node,
+        rigidbody::{RigidBody, RigidBodyBuilder},
+        Scene,
+    },
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// An automatic fix of a problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QuickFix {
+    /// Resets the property with the given path to [`Handle::NONE`].
+    ResetHandle(String),
+    /// Moves the collider into a new rigid body.
+    AddRigidBody,
+    /// Moves the 2D collider into a new 2D rigid body.
+    AddRigidBody2D,
+    /// Adds a new collider to the rigid body.
+    AddCollider,
+    /// Adds a new 2D collider to the 2D rigid body.
+    AddCollider2D,
+}
+
+impl QuickFix {
+    pub fn description(&self) -> &'static str {
+        match self {
+            QuickFix::ResetHandle(_) => "Reset Handle",
+            QuickFix::AddRigidBody | QuickFix::AddRigidBody2D => "Add Rigid Body",
+            QuickFix::AddCollider | QuickFix::AddCollider2D => "Add Collider",
+        }
+    }
+
+    fn make_command(&self, node: Handle<Node>, graph: &Graph) -> Option<GameSceneCommand> {
+        let node_ref = graph.try_get(node)?;
+        let command = match self {
+            QuickFix::ResetHandle(path) => GameSceneCommand::new(SetPropertyCommand::new(
+                node,
+                path.clone(),
+                Box::new(Handle::<Node>::NONE),
+            )),
+            QuickFix::AddRigidBody | QuickFix::AddRigidBody2D => {
+                let base = BaseBuilder::new().with_name(format!("{}Body", node_ref.name()));
+                let body = if *self == QuickFix::AddRigidBody {
+                    RigidBodyBuilder::new(base).build_node()
+                } else {
+                    dim2::rigidbody::RigidBodyBuilder::new(base).build_node()
+                };
+                GameSceneCommand::new(
+                    CommandGroup::from(vec![GameSceneCommand::new(
+                        GroupNodesCommand::with_group_node(vec![node], node_ref.parent(), body),
+                    )])
+                    .with_custom_name("Add Rigid Body"),
+                )
+            }
+            QuickFix::AddCollider | QuickFix::AddCollider2D => {
+                let base = BaseBuilder::new().with_name("Collider");
+                let collider = if *self == QuickFix::AddCollider {
+                    ColliderBuilder::new(base).build_node()
+                } else {
+                    dim2::collider::ColliderBuilder::new(base).build_node()
+                };
+                GameSceneCommand::new(AddNodeCommand::new(collider, node, false))
+            }
+        };
+        Some(command)
+    }
+}
+
+/// A single problem of a scene node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub node: Handle<Node>,
+    pub severity: Severity,
+    pub message: String,
+    pub fix: Option<QuickFix>,
+}
+
+fn physics_quick_fix(node: &Node, graph: &Graph) -> Option<QuickFix> {
+    let has_child_of_type = |is_type: fn(&Node) -> bool| {
+        node.children()
+            .iter()
+            .any(|child| graph.try_get(*child).map_or(false, is_type))
+    };
+
+    if node.is_collider() && graph.try_get_of_type::<RigidBody>(node.parent()).is_none() {
+        Some(QuickFix::AddRigidBody)
+    } else if node.is_collider2d()
+        && graph
+            .try_get_of_type::<dim2::rigidbody::RigidBody>(node.parent())
+            .is_none()
+    {
+        Some(QuickFix::AddRigidBody2D)
+    } else if node.is_rigid_body() && !has_child_of_type(|n| n.is_collider()) {
+        Some(QuickFix::AddCollider)
+    } else if node.is_rigid_body2d() && !has_child_of_type(|n| n.is_collider2d()) {
+        Some(QuickFix::AddCollider2D)
+    } else {
+        None
+    }
+}
+
+/// Collects problems of every node of the hierarchy starting from the given root.
+pub fn collect_diagnostics(scene: &Scene, root: Handle<Node>) -> Vec<Diagnostic> {
+    let graph = &scene.graph;
+    let mut diagnostics = Vec::new();
+
+    for handle in graph.traverse_handle_iter(root) {
+        let node = &graph[handle];
+
+        if let Err(message) = node.validate(scene) {
+            diagnostics.push(Diagnostic {
+                node: handle,
+                severity: Severity::Error,
+                message,
+                fix: physics_quick_fix(node, graph),
+            });
+        }
+
+        let mut resources = FxHashSet::default();
+        collect_used_resources(node as &dyn Reflect, &mut resources);
+        for resource in resources {
+            let header = resource.0.lock();
+            if let ResourceState::LoadError { ref error, .. } = header.state {
+                // Embedded resources cannot be missing, the error is not related to the scene.
+                if header.kind.is_external() {
+                    diagnostics.push(Diagnostic {
+                        node: handle,
+                        severity: Severity::Error,
+                        message: format!("Resource {} failed to load: {:?}", header.kind, error),
+                        fix: None,
+                    });
+                }
+            }
+        }
+
+        (node as &dyn Reflect).enumerate_fields_recursively(
+            &mut |path, field_info, value| {
+                if field_info.map_or(false, |info| info.read_only) {
+                    return;
+                }
+                value.downcast_ref::<Handle<Node>>(&mut |referenced| {
+                    if let Some(referenced) = referenced {
+                        if referenced.is_some() && !graph.is_valid_handle(*referenced) {
+                            diagnostics.push(Diagnostic {
+                                node: handle,
+                                severity: Severity::Warning,
+                                message: format!("Property {path} refers to a deleted node."),
+                                fix: Some(QuickFix::ResetHandle(path.to_string())),
+                            });
+                        }
+                    }
+                });
+            },
+            &[],
+        );
+    }
+
+    diagnostics
+}
+
+pub struct ValidationPanel {
+    pub window: Handle<UiNode>,
+    list: Handle<UiNode>,
+    status: Handle<UiNode>,
+    diagnostics: Vec<Diagnostic>,
+    /// Buttons of quick fixes and indices of the diagnostics.
+    fix_buttons: Vec<(Handle<UiNode>, usize)>,
+}
+
+impl ValidationPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let list = ListViewBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .build(ctx);
+        let status = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .build(ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("ValidationPanel")
+                .with_width(400.0)
+                .with_height(300.0),
+        )
+        .open(false)
+        .with_title(window_title("Validation", ctx))
+        .with_content(
+            GridBuilder::new(WidgetBuilder::new().with_child(list).with_child(status))
+                .add_row(Row::stretch())
+                .add_row(Row::auto())
+                .add_column(Column::stretch())
+                .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            list,
+            status,
+            diagnostics: Default::default(),
+            fix_buttons: Default::default(),
+        }
+    }
+
+    fn make_item(
+        &mut self,
+        index: usize,
+        diagnostic: &Diagnostic,
+        graph: &Graph,
+        ctx: &mut BuildContext,
+    ) -> Handle<UiNode> {
+        let (severity, color) = match diagnostic.severity {
+            Severity::Warning => ("Warning", Color::ORANGE),
+            Severity::Error => ("Error", Color::RED),
+        };
+        let name = graph
+            .try_get(diagnostic.node)
+            .map(|n| n.name_owned())
+            .unwrap_or_default();
+
+        let fix = if let Some(fix) = diagnostic.fix.as_ref() {
+            let button = ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .on_column(1)
+                    .with_vertical_alignment(VerticalAlignment::Center)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text(fix.description())
+            .build(ctx);
+            self.fix_buttons.push((button, index));
+            button
+        } else {
+            Handle::NONE
+        };
+
+        GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(
+                    TextBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(0)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_foreground(Brush::Solid(color)),
+                    )
+                    .with_text(format!(
+                        "[{}] {} ({}): {}",
+                        severity, name, diagnostic.node, diagnostic.message
+                    ))
+                    .with_wrap(WrapMode::Word)
+                    .build(ctx),
+                )
+                .with_child(fix),
+        )
+        .add_row(Row::auto())
+        .add_column(Column::stretch())
+        .add_column(Column::auto())
+        .build(ctx)
+    }
+
+    fn set_diagnostics(
+        &mut self,
+        diagnostics: Vec<Diagnostic>,
+        graph: &Graph,
+        ui: &mut UserInterface,
+    ) {
+        self.fix_buttons.clear();
+
+        let ctx = &mut ui.build_ctx();
+        let items = diagnostics
+            .iter()
+            .enumerate()
+            .map(|(index, diagnostic)| self.make_item(index, diagnostic, graph, ctx))
+            .collect::<Vec<_>>();
+        self.diagnostics = diagnostics;
+
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            if self.diagnostics.is_empty() {
+                "No problems found.".to_string()
+            } else {
+                format!("{} problem(s) found.", self.diagnostics.len())
+            },
+        ));
+    }
+
+    /// Validates the scene and updates the list, if the set of problems has changed. Does nothing if
+    /// the panel is closed, because validation of large scenes is quite slow.
+    pub fn sync_to_model(&mut self, game_scene: &GameScene, engine: &mut Engine) {
+        let ui = &mut engine.user_interface;
+        if !ui.node(self.window).visibility() {
+            return;
+        }
+
+        let scene = &engine.scenes[game_scene.scene];
+        let diagnostics = collect_diagnostics(scene, game_scene.scene_content_root);
+        if diagnostics != self.diagnostics {
+            self.set_diagnostics(diagnostics, &scene.graph, ui);
+        }
+    }
+
+    pub fn clear(&mut self, ui: &mut UserInterface) {
+        if !self.diagnostics.is_empty() {
+            self.set_diagnostics(Default::default(), &Graph::new(), ui);
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        game_scene: &GameScene,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        if let Some(WindowMessage::Open { .. }) = message.data() {
+            // Validation is skipped while the panel is closed, so refresh it.
+            if message.destination() == self.window {
+                self.sync_to_model(game_scene, engine);
+            }
+            return;
+        }
+
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list {
+                if let Some(diagnostic) = self.diagnostics.get(*index) {
+                    sender.send(Message::SelectObject {
+                        handle: ErasedHandle::from(diagnostic.node),
+                    });
+                }
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if let Some((_, index)) = self
+                .fix_buttons
+                .iter()
+                .find(|(button, _)| *button == message.destination())
+            {
+                let diagnostic = &self.diagnostics[*index];
+                if let Some(command) = diagnostic.fix.as_ref().and_then(|fix| {
+                    fix.make_command(diagnostic.node, &engine.scenes[game_scene.scene].graph)
+                }) {
+                    sender.send(Message::DoGameSceneCommand(command));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{collect_diagnostics, QuickFix, Severity};
+    use fyrox::{
+        core::pool::Handle,
+        graph::SceneGraph,
+        scene::{
+            base::BaseBuilder, collider::ColliderBuilder, joint::JointBuilder, pivot::PivotBuilder,
+            rigidbody::RigidBodyBuilder, Scene,
+        },
+    };
+
+    #[test]
+    fn test_collect_diagnostics() {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+
+        let body = RigidBodyBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let collider = ColliderBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        scene.graph.link_nodes(collider, body);
+        assert!(collect_diagnostics(&scene, root).is_empty());
+
+        let deleted = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let joint = JointBuilder::new(BaseBuilder::new())
+            .with_body1(body)
+            .with_body2(deleted)
+            .build(&mut scene.graph);
+        scene.graph.remove_node(deleted);
+        let lonely_collider = ColliderBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+
+        let diagnostics = collect_diagnostics(&scene, root);
+        let of = |node: Handle<_>| {
+            diagnostics
+                .iter()
+                .filter(|d| d.node == node)
+                .map(|d| (d.severity, d.fix.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            of(lonely_collider),
+            [(Severity::Error, Some(QuickFix::AddRigidBody))]
+        );
+        assert!(of(joint).contains(&(
+            Severity::Warning,
+            Some(QuickFix::ResetHandle("body2".to_string()))
+        )));
+        assert!(of(body).is_empty());
+    }
+}
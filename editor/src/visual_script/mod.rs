@@ -0,0 +1,643 @@
+//! Graph editor for visual scripts. It reuses the canvas, nodes, sockets and connections of the
+//! ABSM editor.
+
+use crate::{
+    absm::{
+        canvas::{AbsmCanvasBuilder, AbsmCanvasMessage},
+        connection::ConnectionBuilder,
+        node::{AbsmNode, AbsmNodeBuilder},
+        socket::{Socket, SocketBuilder, SocketDirection},
+    },
+    inspector::editors::make_property_editors_container,
+    localization::window_title,
+    menu::create_menu_item,
+    message::MessageSender,
+    send_sync_message, Engine, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    asset::ResourceData,
+    core::{color::Color, log::Log, pool::Handle},
+    fxhash::FxHashMap,
+    graph::SceneGraph,
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction},
+        menu::MenuItemMessage,
+        message::{MessageDirection, UiMessage},
+        popup::{Placement, PopupBuilder, PopupMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage},
+        BuildContext, HorizontalAlignment, RcUiNodeHandle, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    resource::visual_script::{
+        VisualConnectionKind, VisualNode, VisualNodeKind, VisualScriptResource,
+    },
+};
+use std::{str::FromStr, sync::Arc};
+use strum::VariantNames;
+
+const NORMAL_FLOW_COLOR: Color = Color::opaque(60, 60, 60);
+const SELECTED_FLOW_COLOR: Color = Color::opaque(80, 80, 80);
+const NORMAL_EVENT_COLOR: Color = Color::opaque(40, 80, 0);
+const SELECTED_EVENT_COLOR: Color = Color::opaque(60, 100, 0);
+const NORMAL_EXPRESSION_COLOR: Color = Color::opaque(30, 50, 80);
+const SELECTED_EXPRESSION_COLOR: Color = Color::opaque(50, 70, 100);
+
+/// Describes which pin of a visual script node a socket represents.
+#[derive(Copy, Clone, Debug)]
+struct SocketInfo {
+    node: Handle<VisualNode>,
+    kind: VisualConnectionKind,
+    pin: u32,
+}
+
+fn make_node_name(kind: &VisualNodeKind) -> String {
+    match kind {
+        VisualNodeKind::OnMessage { name }
+        | VisualNodeKind::SetVariable { name }
+        | VisualNodeKind::SendMessage { name }
+        | VisualNodeKind::GetVariable { name }
+        | VisualNodeKind::FindNode { name } => name.clone(),
+        VisualNodeKind::SetProperty { path } | VisualNodeKind::GetProperty { path } => path.clone(),
+        VisualNodeKind::Constant { value } => value.to_string(),
+        VisualNodeKind::Binary { operation } => operation.as_ref().to_owned(),
+        _ => String::new(),
+    }
+}
+
+pub struct VisualScriptEditor {
+    pub window: Handle<UiNode>,
+    canvas: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    save: Handle<UiNode>,
+    canvas_context_menu: RcUiNodeHandle,
+    add_node_items: Vec<(Handle<UiNode>, VisualNodeKind)>,
+    node_context_menu: RcUiNodeHandle,
+    remove_node: Handle<UiNode>,
+    connection_context_menu: RcUiNodeHandle,
+    remove_connection: Handle<UiNode>,
+    placement_target: Handle<UiNode>,
+    sockets: FxHashMap<Handle<UiNode>, SocketInfo>,
+    connections: FxHashMap<Handle<UiNode>, usize>,
+    selected: Handle<VisualNode>,
+    script: Option<VisualScriptResource>,
+    sender: MessageSender,
+}
+
+impl VisualScriptEditor {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let mut add_node_items = Vec::new();
+        let mut events = Vec::new();
+        let mut flow = Vec::new();
+        let mut expressions = Vec::new();
+        for name in VisualNodeKind::VARIANTS {
+            let kind = VisualNodeKind::from_str(name).unwrap();
+            let item = create_menu_item(name, vec![], ctx);
+            if kind.is_event() {
+                events.push(item);
+            } else if kind.has_flow_input() {
+                flow.push(item);
+            } else {
+                expressions.push(item);
+            }
+            add_node_items.push((item, kind));
+        }
+
+        let canvas_context_menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(create_menu_item("Events", events, ctx))
+                        .with_child(create_menu_item("Flow", flow, ctx))
+                        .with_child(create_menu_item("Expressions", expressions, ctx)),
+                )
+                .build(ctx),
+            )
+            .build(ctx);
+        let canvas_context_menu = RcUiNodeHandle::new(canvas_context_menu, ctx.sender());
+
+        let remove_node;
+        let node_context_menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(WidgetBuilder::new().with_child({
+                    remove_node = create_menu_item("Remove", vec![], ctx);
+                    remove_node
+                }))
+                .build(ctx),
+            )
+            .build(ctx);
+        let node_context_menu = RcUiNodeHandle::new(node_context_menu, ctx.sender());
+
+        let remove_connection;
+        let connection_context_menu =
+            PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+                .with_content(
+                    StackPanelBuilder::new(WidgetBuilder::new().with_child({
+                        remove_connection = create_menu_item("Remove Connection", vec![], ctx);
+                        remove_connection
+                    }))
+                    .build(ctx),
+                )
+                .build(ctx);
+        let connection_context_menu = RcUiNodeHandle::new(connection_context_menu, ctx.sender());
+
+        let canvas = AbsmCanvasBuilder::new(
+            WidgetBuilder::new().with_context_menu(canvas_context_menu.clone()),
+        )
+        .build(ctx);
+
+        let inspector;
+        let save;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("VisualScriptEditor")
+                .with_width(900.0)
+                .with_height(600.0),
+        )
+        .with_title(window_title("Visual Script Editor", ctx))
+        .open(false)
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        BorderBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(0)
+                                .on_column(0)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child(canvas),
+                        )
+                        .build(ctx),
+                    )
+                    .with_child(
+                        ScrollViewerBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(0)
+                                .on_column(1)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_content({
+                            inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+                            inspector
+                        })
+                        .build(ctx),
+                    )
+                    .with_child({
+                        save = ButtonBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .on_column(1)
+                                .with_width(100.0)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_horizontal_alignment(HorizontalAlignment::Right),
+                        )
+                        .with_text("Save")
+                        .build(ctx);
+                        save
+                    }),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(25.0))
+            .add_column(Column::stretch())
+            .add_column(Column::strict(300.0))
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            canvas,
+            inspector,
+            save,
+            canvas_context_menu,
+            add_node_items,
+            node_context_menu,
+            remove_node,
+            connection_context_menu,
+            remove_connection,
+            placement_target: Default::default(),
+            sockets: Default::default(),
+            connections: Default::default(),
+            selected: Default::default(),
+            script: None,
+            sender,
+        }
+    }
+
+    pub fn open(&mut self, script: VisualScriptResource, ui: &mut UserInterface) {
+        self.script = Some(script);
+        self.selected = Handle::NONE;
+        self.sync_inspector(ui);
+        self.rebuild_canvas(ui);
+
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn create_socket(
+        &mut self,
+        direction: SocketDirection,
+        info: SocketInfo,
+        label: String,
+        ui: &mut UserInterface,
+    ) -> Handle<UiNode> {
+        let ctx = &mut ui.build_ctx();
+        let label = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .with_text(label)
+            .build(ctx);
+        let socket = SocketBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+            .with_direction(direction)
+            .with_parent_node(info.node.into())
+            .with_index(info.pin as usize)
+            .with_show_index(false)
+            .with_editor(label)
+            .build(ctx);
+        self.sockets.insert(socket, info);
+        socket
+    }
+
+    fn rebuild_canvas(&mut self, ui: &mut UserInterface) {
+        for &child in ui.node(self.canvas).children() {
+            send_sync_message(ui, WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+        self.sockets.clear();
+        self.connections.clear();
+
+        let Some(resource) = self.script.clone() else {
+            return;
+        };
+        let mut state = resource.state();
+        let Some(script) = state.data() else {
+            return;
+        };
+
+        let mut views = FxHashMap::default();
+        for (handle, node) in script.nodes().pair_iter() {
+            let mut input_sockets = Vec::new();
+            if node.kind.has_flow_input() {
+                let info = SocketInfo {
+                    node: handle,
+                    kind: VisualConnectionKind::Flow,
+                    pin: 0,
+                };
+                input_sockets.push(self.create_socket(
+                    SocketDirection::Input,
+                    info,
+                    ">".to_owned(),
+                    ui,
+                ));
+            }
+            for (pin, name) in node.kind.data_inputs().iter().enumerate() {
+                let info = SocketInfo {
+                    node: handle,
+                    kind: VisualConnectionKind::Data,
+                    pin: pin as u32,
+                };
+                input_sockets.push(self.create_socket(
+                    SocketDirection::Input,
+                    info,
+                    name.to_string(),
+                    ui,
+                ));
+            }
+
+            let mut output_sockets = Vec::new();
+            for (pin, name) in node.kind.flow_outputs().iter().enumerate() {
+                let info = SocketInfo {
+                    node: handle,
+                    kind: VisualConnectionKind::Flow,
+                    pin: pin as u32,
+                };
+                output_sockets.push(self.create_socket(
+                    SocketDirection::Output,
+                    info,
+                    format!("> {name}"),
+                    ui,
+                ));
+            }
+            if node.kind.has_data_output() {
+                let info = SocketInfo {
+                    node: handle,
+                    kind: VisualConnectionKind::Data,
+                    pin: 0,
+                };
+                output_sockets.push(self.create_socket(
+                    SocketDirection::Output,
+                    info,
+                    "Value".to_owned(),
+                    ui,
+                ));
+            }
+
+            let (normal_color, selected_color) = if node.kind.is_event() {
+                (NORMAL_EVENT_COLOR, SELECTED_EVENT_COLOR)
+            } else if node.kind.has_data_output() {
+                (NORMAL_EXPRESSION_COLOR, SELECTED_EXPRESSION_COLOR)
+            } else {
+                (NORMAL_FLOW_COLOR, SELECTED_FLOW_COLOR)
+            };
+
+            let ctx = &mut ui.build_ctx();
+            let output_panel =
+                StackPanelBuilder::new(WidgetBuilder::new().with_children(output_sockets))
+                    .build(ctx);
+            let view = AbsmNodeBuilder::new(
+                WidgetBuilder::new()
+                    .with_desired_position(node.position)
+                    .with_context_menu(self.node_context_menu.clone()),
+            )
+            .with_name(make_node_name(&node.kind))
+            .with_title(node.kind.as_ref().to_owned())
+            .with_input_sockets(input_sockets)
+            .with_output_socket(output_panel)
+            .with_normal_color(normal_color)
+            .with_selected_color(selected_color)
+            .with_model_handle(handle)
+            .build(ctx);
+
+            send_sync_message(
+                ui,
+                WidgetMessage::link(view, MessageDirection::ToWidget, self.canvas),
+            );
+
+            views.insert(handle, view);
+        }
+
+        // Force update layout to be able to fetch positions of sockets for connections.
+        ui.update(ui.screen_size(), 0.0);
+
+        for (index, connection) in script.connections().iter().enumerate() {
+            let find_socket = |node: Handle<VisualNode>, pin: u32, direction: SocketDirection| {
+                self.sockets
+                    .iter()
+                    .find(|(socket, info)| {
+                        info.node == node
+                            && info.pin == pin
+                            && info.kind == connection.kind
+                            && ui
+                                .node(**socket)
+                                .query_component::<Socket>()
+                                .map_or(false, |s| s.direction == direction)
+                    })
+                    .map(|(socket, _)| *socket)
+            };
+
+            let (Some(source_socket), Some(dest_socket), Some(source_node), Some(dest_node)) = (
+                find_socket(
+                    connection.source,
+                    connection.source_pin,
+                    SocketDirection::Output,
+                ),
+                find_socket(
+                    connection.target,
+                    connection.target_pin,
+                    SocketDirection::Input,
+                ),
+                views.get(&connection.source),
+                views.get(&connection.target),
+            ) else {
+                continue;
+            };
+
+            let view = ConnectionBuilder::new(
+                WidgetBuilder::new().with_context_menu(self.connection_context_menu.clone()),
+            )
+            .with_source_socket(source_socket)
+            .with_source_node(*source_node)
+            .with_dest_socket(dest_socket)
+            .with_dest_node(*dest_node)
+            .build(self.canvas, &mut ui.build_ctx());
+
+            send_sync_message(
+                ui,
+                WidgetMessage::link(view, MessageDirection::ToWidget, self.canvas),
+            );
+            send_sync_message(
+                ui,
+                WidgetMessage::lowermost(view, MessageDirection::ToWidget),
+            );
+
+            self.connections.insert(view, index);
+        }
+
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::selection_changed(
+                self.canvas,
+                MessageDirection::ToWidget,
+                views.get(&self.selected).cloned().into_iter().collect(),
+            ),
+        );
+
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::force_sync_dependent_objects(
+                self.canvas,
+                MessageDirection::ToWidget,
+            ),
+        );
+    }
+
+    fn sync_inspector(&self, ui: &mut UserInterface) {
+        let node = self.script.as_ref().and_then(|script| {
+            script
+                .state()
+                .data()
+                .and_then(|s| s.node(self.selected))
+                .cloned()
+        });
+
+        let context = match node {
+            Some(node) => InspectorContext::from_object(
+                &node,
+                &mut ui.build_ctx(),
+                Arc::new(make_property_editors_container(self.sender.clone())),
+                None,
+                MSG_SYNC_FLAG,
+                0,
+                true,
+                Default::default(),
+            ),
+            None => Default::default(),
+        };
+
+        ui.send_message(InspectorMessage::context(
+            self.inspector,
+            MessageDirection::ToWidget,
+            context,
+        ));
+    }
+
+    fn find_node_view(&self, handle: Handle<UiNode>, ui: &UserInterface) -> Handle<UiNode> {
+        let Some(node) = ui.try_get(handle) else {
+            return Handle::NONE;
+        };
+
+        if node.has_component::<AbsmNode<VisualNode>>() {
+            handle
+        } else {
+            node.find_by_criteria_up(ui, |n| n.has_component::<AbsmNode<VisualNode>>())
+        }
+    }
+
+    fn model_handle(&self, view: Handle<UiNode>, ui: &UserInterface) -> Handle<VisualNode> {
+        ui.try_get(view)
+            .and_then(|n| n.query_component::<AbsmNode<VisualNode>>())
+            .map(|n| n.model_handle)
+            .unwrap_or_default()
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        let Some(resource) = self.script.clone() else {
+            return;
+        };
+        let ui = &mut engine.user_interface;
+
+        if let Some(msg) = message.data::<AbsmCanvasMessage>() {
+            if message.destination() != self.canvas
+                || message.direction() != MessageDirection::FromWidget
+            {
+                return;
+            }
+
+            match msg {
+                AbsmCanvasMessage::CommitDrag { entries } => {
+                    if let Some(script) = resource.state().data() {
+                        for entry in entries {
+                            let handle = self.model_handle(entry.node, ui);
+                            if let Some(node) = script.node_mut(handle) {
+                                node.position = ui.node(entry.node).actual_local_position();
+                            }
+                        }
+                    }
+                }
+                AbsmCanvasMessage::CommitConnection {
+                    source_socket,
+                    dest_socket,
+                } => {
+                    let (Some(source), Some(dest)) = (
+                        self.sockets.get(source_socket).cloned(),
+                        self.sockets.get(dest_socket).cloned(),
+                    ) else {
+                        return;
+                    };
+
+                    if source.kind == dest.kind {
+                        let connected = resource.state().data().map_or(false, |script| {
+                            script.connect(
+                                source.kind,
+                                source.node,
+                                source.pin,
+                                dest.node,
+                                dest.pin,
+                            )
+                        });
+                        if connected {
+                            self.rebuild_canvas(ui);
+                        }
+                    }
+                }
+                AbsmCanvasMessage::SelectionChanged(selection) => {
+                    let selected = selection
+                        .first()
+                        .map(|view| self.model_handle(*view, ui))
+                        .unwrap_or_default();
+                    if selected != self.selected {
+                        self.selected = selected;
+                        self.sync_inspector(ui);
+                    }
+                }
+                _ => (),
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(script) = resource.state().data() {
+                    if let Some(node) = script.node_mut(self.selected) {
+                        PropertyAction::from_field_kind(&args.value).apply(
+                            &args.path(),
+                            node,
+                            &mut |result| {
+                                Log::verify(result);
+                            },
+                        );
+                    }
+                    // Pins depend on the kind of the node.
+                    script.remove_invalid_connections();
+                }
+                // Enum variants have different fields, so the inspector must be synced.
+                self.sync_inspector(ui);
+                self.rebuild_canvas(ui);
+            }
+        } else if let Some(MenuItemMessage::Click) = message.data() {
+            if let Some((_, kind)) = self
+                .add_node_items
+                .iter()
+                .find(|(item, _)| *item == message.destination())
+            {
+                let position = ui
+                    .node(self.canvas)
+                    .screen_to_local(ui.node(self.canvas_context_menu.handle()).screen_position());
+                if let Some(script) = resource.state().data() {
+                    script.add_node(VisualNode::new(kind.clone()).with_position(position));
+                }
+                self.rebuild_canvas(ui);
+            } else if message.destination() == self.remove_node {
+                let view = self.find_node_view(self.placement_target, ui);
+                let handle = self.model_handle(view, ui);
+                if let Some(script) = resource.state().data() {
+                    script.remove_node(handle);
+                }
+                if handle == self.selected {
+                    self.selected = Handle::NONE;
+                    self.sync_inspector(ui);
+                }
+                self.rebuild_canvas(ui);
+            } else if message.destination() == self.remove_connection {
+                if let Some(index) = self.connections.get(&self.placement_target) {
+                    if let Some(script) = resource.state().data() {
+                        script.disconnect(*index);
+                    }
+                    self.rebuild_canvas(ui);
+                }
+            }
+        } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
+            if message.destination() == self.node_context_menu.handle()
+                || message.destination() == self.connection_context_menu.handle()
+            {
+                self.placement_target = *target;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.save {
+                if let Some(path) = resource.kind().into_path() {
+                    if let Some(script) = resource.state().data() {
+                        match script.save(&path) {
+                            Ok(_) => Log::info(format!("Visual script {path:?} was saved.")),
+                            Err(e) => Log::err(format!(
+                                "Unable to save visual script {path:?}. Reason: {e:?}"
+                            )),
+                        }
+                    }
+                }
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.script = None;
+                self.selected = Handle::NONE;
+                self.rebuild_canvas(ui);
+                self.sync_inspector(ui);
+            }
+        }
+    }
+}
@@ -2,15 +2,23 @@ use crate::{load_image, message::MessageSender, utils::make_node_name, Message};
 use fyrox::{
     asset::untyped::UntypedResource,
     core::{
-        algebra::Vector2, pool::ErasedHandle, pool::Handle, reflect::prelude::*,
-        type_traits::prelude::*, uuid_provider, visitor::prelude::*,
+        algebra::Vector2,
+        color::{Color, Hsv},
+        pool::ErasedHandle,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid_provider,
+        visitor::prelude::*,
     },
     gui::{
         brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
         define_constructor,
         draw::DrawingContext,
         grid::{Column, GridBuilder, Row},
-        image::ImageBuilder,
+        image::{ImageBuilder, ImageMessage},
         message::{MessageDirection, OsEvent, UiMessage},
         text::{TextBuilder, TextMessage},
         tree::{Tree, TreeBuilder},
@@ -20,7 +28,9 @@ use fyrox::{
     },
 };
 use std::{
+    collections::hash_map::DefaultHasher,
     fmt::{Debug, Formatter},
+    hash::{Hash, Hasher},
     ops::{Deref, DerefMut},
     sync::mpsc::Sender,
 };
@@ -28,12 +38,40 @@ use std::{
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SceneItemMessage {
     Name(String),
+    Tag(String),
     Validate(Result<(), String>),
+    Flags(SceneItemFlags),
 }
 
 impl SceneItemMessage {
     define_constructor!(SceneItemMessage:Name => fn name(String), layout: false);
+    define_constructor!(SceneItemMessage:Tag => fn tag(String), layout: false);
     define_constructor!(SceneItemMessage:Validate => fn validate(Result<(), String>), layout: false);
+    define_constructor!(SceneItemMessage:Flags => fn flags(SceneItemFlags), layout: false);
+}
+
+/// Editor-only flags of a scene node, they're shown as toggles next to the name of the item.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SceneItemFlags {
+    pub hidden: bool,
+    pub locked: bool,
+}
+
+fn visibility_icon(hidden: bool) -> Option<UntypedResource> {
+    if hidden {
+        load_image(include_bytes!("../../../resources/invisible.png"))
+    } else {
+        load_image(include_bytes!("../../../resources/visible.png"))
+    }
+}
+
+/// Generates a stable color for the given tag, so every item with the same tag will have the same
+/// color in the world viewer.
+pub fn tag_color(tag: &str) -> Color {
+    let mut hasher = DefaultHasher::new();
+    tag.hash(&mut hasher);
+    let hue = (hasher.finish() % 360) as f32;
+    Color::from(Hsv::new(hue, 60.0, 90.0))
 }
 
 #[derive(Visit, Reflect, ComponentProvider)]
@@ -42,10 +80,19 @@ pub struct SceneItem {
     pub tree: Tree,
     text_name: Handle<UiNode>,
     name_value: String,
+    text_tag: Handle<UiNode>,
+    tag_value: String,
     grid: Handle<UiNode>,
     pub entity_handle: ErasedHandle,
     // Can be unassigned if there's no warning.
     pub warning_icon: Handle<UiNode>,
+    // Flags and their toggles are unassigned if the entity does not support them.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    flags: Option<SceneItemFlags>,
+    hidden_toggle: Handle<UiNode>,
+    hidden_image: Handle<UiNode>,
+    locked_toggle: Handle<UiNode>,
     #[reflect(hidden)]
     #[visit(skip)]
     sender: MessageSender,
@@ -55,6 +102,14 @@ impl SceneItem {
     pub fn name(&self) -> &str {
         &self.name_value
     }
+
+    pub fn tag(&self) -> &str {
+        &self.tag_value
+    }
+
+    pub fn flags(&self) -> Option<SceneItemFlags> {
+        self.flags
+    }
 }
 
 impl Clone for SceneItem {
@@ -63,9 +118,15 @@ impl Clone for SceneItem {
             tree: self.tree.clone(),
             text_name: self.text_name,
             name_value: self.name_value.clone(),
+            text_tag: self.text_tag,
+            tag_value: self.tag_value.clone(),
             grid: self.grid,
             entity_handle: self.entity_handle,
             warning_icon: self.warning_icon,
+            flags: self.flags,
+            hidden_toggle: self.hidden_toggle,
+            hidden_image: self.hidden_image,
+            locked_toggle: self.locked_toggle,
             sender: self.sender.clone(),
         }
     }
@@ -123,6 +184,26 @@ impl Control for SceneItem {
                     self.name_value.clone(),
                 ));
             }
+        } else if let Some(SceneItemMessage::Tag(tag)) = message.data() {
+            if message.destination() == self.handle() {
+                self.tag_value = tag.clone();
+
+                ui.send_message(TextMessage::text(
+                    self.text_tag,
+                    MessageDirection::ToWidget,
+                    make_tag_text(tag),
+                ));
+                ui.send_message(WidgetMessage::foreground(
+                    self.text_tag,
+                    MessageDirection::ToWidget,
+                    Brush::Solid(tag_color(tag)),
+                ));
+                ui.send_message(WidgetMessage::visibility(
+                    self.text_tag,
+                    MessageDirection::ToWidget,
+                    !tag.is_empty(),
+                ));
+            }
         } else if let Some(SceneItemMessage::Validate(result)) = message.data() {
             if message.destination() == self.handle() {
                 match result {
@@ -141,7 +222,7 @@ impl Control for SceneItem {
                                 .with_tooltip(make_simple_tooltip(&mut ui.build_ctx(), msg))
                                 .with_margin(Thickness::uniform(1.0))
                                 .on_row(0)
-                                .on_column(2),
+                                .on_column(3),
                         )
                         .with_opt_texture(load_image(include_bytes!(
                             "../../../resources/warning.png"
@@ -156,6 +237,40 @@ impl Control for SceneItem {
                     }
                 }
             }
+        } else if let Some(SceneItemMessage::Flags(flags)) = message.data() {
+            if message.destination() == self.handle() && self.flags.is_some() {
+                self.flags = Some(*flags);
+
+                ui.send_message(ImageMessage::texture(
+                    self.hidden_image,
+                    MessageDirection::ToWidget,
+                    visibility_icon(flags.hidden),
+                ));
+                ui.send_message(CheckBoxMessage::checked(
+                    self.locked_toggle,
+                    MessageDirection::ToWidget,
+                    Some(flags.locked),
+                ));
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.hidden_toggle {
+                if let Some(flags) = self.flags {
+                    self.sender.send(Message::SetNodeHidden {
+                        node: self.entity_handle.into(),
+                        hidden: !flags.hidden,
+                    });
+                }
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(locked))) = message.data() {
+            if message.destination() == self.locked_toggle
+                && message.direction() == MessageDirection::FromWidget
+                && self.flags.map_or(false, |flags| flags.locked != *locked)
+            {
+                self.sender.send(Message::SetNodeLocked {
+                    node: self.entity_handle.into(),
+                    locked: *locked,
+                });
+            }
         } else if let Some(WidgetMessage::DoubleClick { .. }) = message.data() {
             let flag = 0b0010;
             if message.flags & flag != flag {
@@ -181,12 +296,18 @@ impl Control for SceneItem {
     }
 }
 
+fn make_tag_text(tag: &str) -> String {
+    format!("[{}]", tag)
+}
+
 pub struct SceneItemBuilder {
     tree_builder: TreeBuilder,
     entity_handle: ErasedHandle,
     name: String,
+    tag: String,
     icon: Option<UntypedResource>,
     text_brush: Option<Brush>,
+    flags: Option<SceneItemFlags>,
 }
 
 impl SceneItemBuilder {
@@ -195,8 +316,10 @@ impl SceneItemBuilder {
             tree_builder,
             entity_handle: Default::default(),
             name: Default::default(),
+            tag: Default::default(),
             icon: None,
             text_brush: None,
+            flags: None,
         }
     }
 
@@ -210,6 +333,11 @@ impl SceneItemBuilder {
         self
     }
 
+    pub fn with_tag(mut self, tag: String) -> Self {
+        self.tag = tag;
+        self
+    }
+
     pub fn with_icon(mut self, icon: Option<UntypedResource>) -> Self {
         self.icon = icon;
         self
@@ -220,8 +348,46 @@ impl SceneItemBuilder {
         self
     }
 
+    /// Sets editor-only flags of the entity, the item will have toggles for every flag.
+    pub fn with_flags(mut self, flags: Option<SceneItemFlags>) -> Self {
+        self.flags = flags;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext, sender: MessageSender) -> Handle<UiNode> {
         let text_name;
+        let text_tag;
+        let mut hidden_toggle = Handle::NONE;
+        let mut hidden_image = Handle::NONE;
+        let mut locked_toggle = Handle::NONE;
+        if let Some(flags) = self.flags {
+            hidden_image =
+                ImageBuilder::new(WidgetBuilder::new().with_width(14.0).with_height(14.0))
+                    .with_opt_texture(visibility_icon(flags.hidden))
+                    .build(ctx);
+            hidden_toggle = ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_tooltip(make_simple_tooltip(
+                        ctx,
+                        "Hide or show the object in the editor",
+                    ))
+                    .on_column(4),
+            )
+            .with_content(hidden_image)
+            .build(ctx);
+            locked_toggle = CheckBoxBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_tooltip(make_simple_tooltip(
+                        ctx,
+                        "Lock the object, locked objects cannot be selected in the viewport",
+                    ))
+                    .on_column(5),
+            )
+            .checked(Some(flags.locked))
+            .build(ctx);
+        }
         let content = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child(
@@ -255,12 +421,30 @@ impl SceneItemBuilder {
                     ))
                     .build(ctx);
                     text_name
-                }),
+                })
+                .with_child({
+                    text_tag = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_foreground(Brush::Solid(tag_color(&self.tag)))
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_visibility(!self.tag.is_empty())
+                            .on_column(2)
+                            .with_vertical_alignment(VerticalAlignment::Center),
+                    )
+                    .with_text(make_tag_text(&self.tag))
+                    .build(ctx);
+                    text_tag
+                })
+                .with_child(hidden_toggle)
+                .with_child(locked_toggle),
         )
         .add_row(Row::stretch())
         .add_column(Column::auto())
         .add_column(Column::stretch())
         .add_column(Column::auto())
+        .add_column(Column::auto())
+        .add_column(Column::auto())
+        .add_column(Column::auto())
         .build(ctx);
 
         let tree = self.tree_builder.with_content(content).build_tree(ctx);
@@ -270,8 +454,14 @@ impl SceneItemBuilder {
             entity_handle: self.entity_handle,
             name_value: self.name,
             text_name,
+            text_tag,
+            tag_value: self.tag,
             grid: content,
             warning_icon: Default::default(),
+            flags: self.flags,
+            hidden_toggle,
+            hidden_image,
+            locked_toggle,
             sender,
         };
 
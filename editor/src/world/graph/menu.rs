@@ -1,11 +1,13 @@
 use crate::{
+    localization::window_title,
     make_save_file_selector,
     menu::{create::CreateEntityMenu, create_menu_item, create_menu_item_shortcut},
     message::MessageSender,
     scene::{
         commands::{
             graph::{AddNodeCommand, ReplaceNodeCommand, SetGraphRootCommand},
-            make_delete_selection_command, CommandGroup, GameSceneCommand,
+            make_delete_selection_command, make_group_selection_command,
+            make_ungroup_selection_command, CommandGroup, GameSceneCommand,
             RevertSceneNodePropertyCommand,
         },
         controller::SceneController,
@@ -18,20 +20,39 @@ use crate::{
 };
 use fyrox::graph::SceneGraph;
 use fyrox::{
-    asset::untyped::UntypedResource,
-    core::{algebra::Vector2, pool::Handle, reflect::Reflect, scope_profile},
+    asset::{untyped::UntypedResource, ResourceData},
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+        log::Log,
+        make_relative_path,
+        pool::Handle,
+        reflect::Reflect,
+        scope_profile,
+    },
     gui::{
-        file_browser::FileSelectorMessage,
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
         menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::UiMessage,
         popup::{Placement, PopupBuilder, PopupMessage},
         stack_panel::StackPanelBuilder,
         widget::{WidgetBuilder, WidgetMessage},
-        window::WindowMessage,
+        window::{WindowBuilder, WindowMessage},
         BuildContext, RcUiNodeHandle, UiNode,
     },
+    resource::convex_decomposition::ConvexDecomposition,
+    scene::{
+        base::BaseBuilder,
+        collider::{ColliderBuilder, ColliderShape},
+        mesh::Mesh,
+        node::Node,
+        rigidbody::RigidBody,
+        transform::TransformBuilder,
+    },
+};
+use std::{
+    any::TypeId,
+    path::{Path, PathBuf},
 };
-use std::{any::TypeId, path::PathBuf};
 
 pub struct SceneNodeContextMenu {
     menu: RcUiNodeHandle,
@@ -44,8 +65,13 @@ pub struct SceneNodeContextMenu {
     save_as_prefab_dialog: Handle<UiNode>,
     paste: Handle<UiNode>,
     make_root: Handle<UiNode>,
+    group_selection: Handle<UiNode>,
+    rename_selection: Handle<UiNode>,
+    ungroup_selection: Handle<UiNode>,
     open_asset: Handle<UiNode>,
     reset_inheritable_properties: Handle<UiNode>,
+    generate_convex_decomposition: Handle<UiNode>,
+    convex_decomposition_dialog: Handle<UiNode>,
 }
 
 impl WorldViewerItemContextMenu for SceneNodeContextMenu {
@@ -70,6 +96,102 @@ fn resource_path_of_first_selected_node(
     None
 }
 
+fn first_selected_node(editor_selection: &Selection) -> Option<Handle<Node>> {
+    if let Selection::Graph(graph_selection) = editor_selection {
+        graph_selection.nodes.first().cloned()
+    } else {
+        None
+    }
+}
+
+fn make_convex_decomposition_file_selector(ctx: &mut BuildContext) -> Handle<UiNode> {
+    FileSelectorBuilder::new(
+        WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+            .with_title(window_title("Save Convex Decomposition As", ctx))
+            .open(false),
+    )
+    .with_mode(FileBrowserMode::Save {
+        default_file_name: PathBuf::from("unnamed.convex"),
+    })
+    .with_path("./")
+    .with_filter(Filter::new(|p: &Path| {
+        p.is_dir()
+            || p.extension()
+                .map_or(false, |ext| ext.to_string_lossy().as_ref() == "convex")
+    }))
+    .build(ctx)
+}
+
+/// Performs convex decomposition of the given mesh, saves the result to the given path and adds a
+/// collider that uses the decomposition. Convex decomposition is a slow process, so it must be done
+/// only once and its result is stored as an asset.
+fn generate_convex_decomposition(
+    mesh_handle: Handle<Node>,
+    path: &Path,
+    game_scene: &GameScene,
+    engine: &mut Engine,
+    sender: &MessageSender,
+) {
+    let graph = &engine.scenes[game_scene.scene].graph;
+    let Some(mesh) = graph.try_get_of_type::<Mesh>(mesh_handle) else {
+        return;
+    };
+
+    // Colliders do not support scaling, so bake it into the convex hulls.
+    let local_transform = mesh.local_transform();
+    let mut decomposition = ConvexDecomposition::from_mesh(
+        mesh,
+        &Matrix4::new_nonuniform_scaling(local_transform.scale()),
+        &Default::default(),
+    );
+
+    if let Err(err) = decomposition.save(path) {
+        Log::err(format!(
+            "Unable to save convex decomposition to {}. Reason: {:?}",
+            path.display(),
+            err
+        ));
+        return;
+    }
+
+    // Colliders must be direct children of rigid bodies, so put the collider next to the mesh if
+    // it is attached to a rigid body.
+    let (parent, position, rotation) =
+        if graph.try_get_of_type::<RigidBody>(mesh.parent()).is_some() {
+            (
+                mesh.parent(),
+                **local_transform.position(),
+                **local_transform.rotation(),
+            )
+        } else {
+            Log::warn(format!(
+                "Mesh {} is not attached to a rigid body. The collider will be added as a \
+            child of the mesh, move it to a rigid body to make it work.",
+                mesh.name()
+            ));
+            (mesh_handle, Vector3::default(), UnitQuaternion::default())
+        };
+
+    let collider = ColliderBuilder::new(
+        BaseBuilder::new()
+            .with_name(format!("{}Collider", mesh.name()))
+            .with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .with_local_rotation(rotation)
+                    .build(),
+            ),
+    )
+    .with_shape(ColliderShape::convex_decomposition(
+        engine.resource_manager.request::<ConvexDecomposition>(
+            make_relative_path(path).unwrap_or_else(|_| path.to_path_buf()),
+        ),
+    ))
+    .build_node();
+
+    sender.do_scene_command(AddNodeCommand::new(collider, parent, true));
+}
+
 impl SceneNodeContextMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let delete_selection;
@@ -77,8 +199,12 @@ impl SceneNodeContextMenu {
         let save_as_prefab;
         let paste;
         let make_root;
+        let group_selection;
+        let rename_selection;
+        let ungroup_selection;
         let open_asset;
         let reset_inheritable_properties;
+        let generate_convex_decomposition;
 
         let (create_entity_menu, create_entity_menu_root_items) = CreateEntityMenu::new(ctx);
         let (replace_with_menu, replace_with_menu_root_items) = CreateEntityMenu::new(ctx);
@@ -117,6 +243,24 @@ impl SceneNodeContextMenu {
                             make_root = create_menu_item("Make Root", vec![], ctx);
                             make_root
                         })
+                        .with_child({
+                            group_selection =
+                                create_menu_item_shortcut("Group Selection", "Ctrl+G", vec![], ctx);
+                            group_selection
+                        })
+                        .with_child({
+                            ungroup_selection = create_menu_item_shortcut(
+                                "Ungroup Selection",
+                                "Ctrl+Shift+G",
+                                vec![],
+                                ctx,
+                            );
+                            ungroup_selection
+                        })
+                        .with_child({
+                            rename_selection = create_menu_item("Rename Selection...", vec![], ctx);
+                            rename_selection
+                        })
                         .with_child(
                             MenuItemBuilder::new(
                                 WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)),
@@ -131,8 +275,13 @@ impl SceneNodeContextMenu {
                         })
                         .with_child({
                             reset_inheritable_properties =
-                                create_menu_item("Reset Inheritable Properties", vec![], ctx);
+                                create_menu_item("Revert To Prefab", vec![], ctx);
                             reset_inheritable_properties
+                        })
+                        .with_child({
+                            generate_convex_decomposition =
+                                create_menu_item("Generate Convex Decomposition...", vec![], ctx);
+                            generate_convex_decomposition
                         }),
                 )
                 .build(ctx),
@@ -142,6 +291,7 @@ impl SceneNodeContextMenu {
 
         // TODO: Not sure if this is the right place for this dialog.
         let save_as_prefab_dialog = make_save_file_selector(ctx, PathBuf::from("unnamed.rgs"));
+        let convex_decomposition_dialog = make_convex_decomposition_file_selector(ctx);
 
         Self {
             create_entity_menu,
@@ -154,8 +304,13 @@ impl SceneNodeContextMenu {
             replace_with_menu,
             paste,
             make_root,
+            group_selection,
+            rename_selection,
+            ungroup_selection,
             open_asset,
             reset_inheritable_properties,
+            generate_convex_decomposition,
+            convex_decomposition_dialog,
         }
     }
 
@@ -241,6 +396,21 @@ impl SceneNodeContextMenu {
                             MessageDirection::ToWidget,
                             Some(std::env::current_dir().unwrap()),
                         ));
+                } else if message.destination() == self.generate_convex_decomposition {
+                    engine
+                        .user_interface
+                        .send_message(WindowMessage::open_modal(
+                            self.convex_decomposition_dialog,
+                            MessageDirection::ToWidget,
+                            true,
+                        ));
+                    engine
+                        .user_interface
+                        .send_message(FileSelectorMessage::root(
+                            self.convex_decomposition_dialog,
+                            MessageDirection::ToWidget,
+                            Some(std::env::current_dir().unwrap()),
+                        ));
                 } else if message.destination() == self.make_root {
                     if let Selection::Graph(graph_selection) = editor_selection {
                         if let Some(first) = graph_selection.nodes.first() {
@@ -250,6 +420,20 @@ impl SceneNodeContextMenu {
                             });
                         }
                     }
+                } else if message.destination() == self.group_selection {
+                    if let Some(command) =
+                        make_group_selection_command(editor_selection, game_scene, engine)
+                    {
+                        sender.send(Message::DoGameSceneCommand(command));
+                    }
+                } else if message.destination() == self.rename_selection {
+                    sender.send(Message::OpenBulkRenameDialog);
+                } else if message.destination() == self.ungroup_selection {
+                    if let Some(command) =
+                        make_ungroup_selection_command(editor_selection, game_scene, engine)
+                    {
+                        sender.send(Message::DoGameSceneCommand(command));
+                    }
                 } else if message.destination() == self.open_asset {
                     if let Some(path) =
                         resource_path_of_first_selected_node(editor_selection, game_scene, engine)
@@ -267,7 +451,8 @@ impl SceneNodeContextMenu {
                                 (node as &dyn Reflect).enumerate_fields_recursively(
                                     &mut |path, _, val| {
                                         val.as_inheritable_variable(&mut |inheritable| {
-                                            if inheritable.is_some() {
+                                            // Only overridden properties must be reverted.
+                                            if inheritable.is_some_and(|v| v.is_modified()) {
                                                 commands.push(GameSceneCommand::new(
                                                     RevertSceneNodePropertyCommand::new(
                                                         path.to_string(),
@@ -281,7 +466,9 @@ impl SceneNodeContextMenu {
                                 )
                             }
                         }
-                        sender.do_scene_command(CommandGroup::from(commands));
+                        if !commands.is_empty() {
+                            sender.do_scene_command(CommandGroup::from(commands));
+                        }
                     }
                 }
             } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data()
@@ -302,10 +489,47 @@ impl SceneNodeContextMenu {
                         resource_path_of_first_selected_node(editor_selection, game_scene, engine)
                             .map_or(false, |p| utils::is_native_scene(&p)),
                     ));
+
+                    // Only prefab instances can be reverted.
+                    let is_any_instance_selected =
+                        if let Selection::Graph(graph_selection) = editor_selection {
+                            let scene = &engine.scenes[game_scene.scene];
+                            graph_selection.nodes.iter().any(|n| {
+                                scene
+                                    .graph
+                                    .try_get(*n)
+                                    .is_some_and(|n| n.resource().is_some())
+                            })
+                        } else {
+                            false
+                        };
+                    engine.user_interface.send_message(WidgetMessage::enabled(
+                        self.reset_inheritable_properties,
+                        MessageDirection::ToWidget,
+                        is_any_instance_selected,
+                    ));
+
+                    // Convex decomposition could be generated only for meshes.
+                    let is_mesh_selected =
+                        first_selected_node(editor_selection).map_or(false, |n| {
+                            engine.scenes[game_scene.scene]
+                                .graph
+                                .try_get_of_type::<Mesh>(n)
+                                .is_some()
+                        });
+                    engine.user_interface.send_message(WidgetMessage::enabled(
+                        self.generate_convex_decomposition,
+                        MessageDirection::ToWidget,
+                        is_mesh_selected,
+                    ));
                 }
             } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
                 if message.destination() == self.save_as_prefab_dialog {
                     sender.send(Message::SaveSelectionAsPrefab(path.clone()));
+                } else if message.destination() == self.convex_decomposition_dialog {
+                    if let Some(mesh) = first_selected_node(editor_selection) {
+                        generate_convex_decomposition(mesh, path, game_scene, engine, sender);
+                    }
                 }
             }
         }
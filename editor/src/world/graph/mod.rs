@@ -8,15 +8,19 @@ use crate::{
         },
         GameScene, Selection,
     },
-    world::{graph::selection::GraphSelection, WorldViewerDataProvider},
+    world::{
+        graph::{item::SceneItemFlags, selection::GraphSelection},
+        WorldViewerDataProvider,
+    },
 };
 use fyrox::{
     asset::{manager::ResourceManager, untyped::UntypedResource},
     core::{
         algebra::Vector3,
-        futures::executor::block_on,
-        make_relative_path,
+        log::Log,
+        make_pretty_type_name, make_relative_path,
         pool::{ErasedHandle, Handle},
+        reflect::Reflect,
     },
     graph::SceneGraph,
     resource::model::{Model, ModelResourceExtension},
@@ -89,6 +93,36 @@ impl<'a> WorldViewerDataProvider for EditorSceneWrapper<'a> {
             .map(|n| Cow::Borrowed(n.name()))
     }
 
+    fn type_name_of(&self, node: ErasedHandle) -> Option<Cow<str>> {
+        self.scene
+            .graph
+            .try_get(node.into())
+            .map(|n| Cow::Borrowed(make_pretty_type_name(Reflect::type_name(n))))
+    }
+
+    fn script_name_of(&self, node: ErasedHandle) -> Option<Cow<str>> {
+        self.scene
+            .graph
+            .try_get(node.into())
+            .and_then(|n| n.script())
+            .map(|s| Cow::Borrowed(make_pretty_type_name(s.type_name())))
+    }
+
+    fn tag_of(&self, node: ErasedHandle) -> Option<Cow<str>> {
+        self.scene
+            .graph
+            .try_get(node.into())
+            .map(|n| Cow::Borrowed(n.tag()))
+    }
+
+    fn flags_of(&self, node: ErasedHandle) -> Option<SceneItemFlags> {
+        let node: Handle<Node> = node.into();
+        (node != self.game_scene.scene_content_root).then(|| SceneItemFlags {
+            hidden: self.game_scene.node_flags.is_hidden(node),
+            locked: self.game_scene.node_flags.is_locked_directly(node),
+        })
+    }
+
     fn is_valid_handle(&self, node: ErasedHandle) -> bool {
         self.scene.graph.is_valid_handle(node.into())
     }
@@ -129,70 +163,86 @@ impl<'a> WorldViewerDataProvider for EditorSceneWrapper<'a> {
         }
     }
 
-    fn on_change_hierarchy_request(&self, child: ErasedHandle, parent: ErasedHandle) {
-        let child: Handle<Node> = child.into();
+    fn on_change_hierarchy_request(&self, children: &[ErasedHandle], parent: ErasedHandle) {
         let parent: Handle<Node> = parent.into();
 
-        if let Selection::Graph(ref selection) = self.selection {
-            if selection.nodes.contains(&child) {
-                let mut commands = Vec::new();
-
-                for &node_handle in selection.nodes.iter() {
-                    // Make sure we won't create any loops - child must not have parent in its
-                    // descendants.
-                    let mut attach = true;
-                    let mut p = parent;
-                    while p.is_some() {
-                        if p == node_handle {
-                            attach = false;
-                            break;
-                        }
-                        p = self.scene.graph[p].parent();
-                    }
-
-                    if attach {
-                        commands.push(GameSceneCommand::new(LinkNodesCommand::new(
-                            node_handle,
-                            parent,
-                        )));
-                    }
-                }
+        let mut commands = Vec::new();
 
-                if !commands.is_empty() {
-                    self.sender.do_scene_command(CommandGroup::from(commands));
+        for &node_handle in children {
+            let node_handle: Handle<Node> = node_handle.into();
+
+            // Make sure we won't create any loops - child must not have parent in its
+            // descendants.
+            let mut attach = true;
+            let mut p = parent;
+            while p.is_some() {
+                if p == node_handle {
+                    attach = false;
+                    break;
                 }
+                p = self.scene.graph[p].parent();
+            }
+
+            if attach {
+                commands.push(GameSceneCommand::new(LinkNodesCommand::new(
+                    node_handle,
+                    parent,
+                )));
             }
         }
+
+        if !commands.is_empty() {
+            self.sender.do_scene_command(CommandGroup::from(commands));
+        }
     }
 
-    fn on_asset_dropped(&mut self, path: PathBuf, node: ErasedHandle) {
-        if let Ok(relative_path) = make_relative_path(path) {
-            if let Some(model) = self
-                .resource_manager
+    fn request_asset(&self, path: PathBuf) -> Option<UntypedResource> {
+        make_relative_path(path).ok().and_then(|relative_path| {
+            self.resource_manager
                 .try_request::<Model>(relative_path)
-                .and_then(|m| block_on(m).ok())
-            {
-                // Instantiate the model.
-                let instance = model.instantiate(self.scene);
-
-                self.scene.graph[instance]
-                    .local_transform_mut()
-                    .set_scale(self.instantiation_scale);
-
-                let sub_graph = self.scene.graph.take_reserve_sub_graph(instance);
-
-                let group = vec![
-                    GameSceneCommand::new(AddModelCommand::new(sub_graph)),
-                    GameSceneCommand::new(LinkNodesCommand::new(instance, node.into())),
-                    GameSceneCommand::new(ChangeSelectionCommand::new(
-                        Selection::Graph(GraphSelection::single_or_empty(instance)),
-                        self.selection.clone(),
-                    )),
-                ];
-
-                self.sender.do_scene_command(CommandGroup::from(group));
-            }
+                .map(|model| model.into_untyped())
+        })
+    }
+
+    fn on_asset_loaded(&mut self, asset: UntypedResource, node: ErasedHandle) {
+        let Some(model) = asset.try_cast::<Model>() else {
+            return;
+        };
+
+        if !model.is_ok() {
+            Log::err(format!(
+                "Unable to instantiate {:?} model, because it failed to load.",
+                model.kind()
+            ));
+            return;
         }
+
+        // The node could be deleted while the model was loading.
+        let parent = if self.scene.graph.is_valid_handle(node.into()) {
+            node.into()
+        } else {
+            self.game_scene.scene_content_root
+        };
+
+        // Instantiate the model.
+        let instance = model.instantiate(self.scene);
+
+        self.scene.graph[instance]
+            .local_transform_mut()
+            .set_scale(self.instantiation_scale);
+
+        let sub_graph = self.scene.graph.take_reserve_sub_graph(instance);
+
+        let group = vec![
+            GameSceneCommand::new(AddModelCommand::new(sub_graph)),
+            GameSceneCommand::new(LinkNodesCommand::new(instance, parent)),
+            GameSceneCommand::new(ChangeSelectionCommand::new(
+                Selection::Graph(GraphSelection::single_or_empty(instance)),
+                self.selection.clone(),
+            )),
+        ];
+
+        self.sender.do_scene_command(CommandGroup::from(group));
     }
 
     fn validate(&self) -> Vec<(ErasedHandle, Result<(), String>)> {
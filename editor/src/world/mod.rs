@@ -2,10 +2,11 @@ use crate::{
     asset::item::AssetItem,
     gui::make_image_button_with_tooltip,
     load_image,
+    localization::window_title,
     message::MessageSender,
     send_sync_message,
     utils::window_content,
-    world::graph::item::{SceneItem, SceneItemBuilder, SceneItemMessage},
+    world::graph::item::{SceneItem, SceneItemBuilder, SceneItemFlags, SceneItemMessage},
     Mode, Settings,
 };
 use fyrox::{
@@ -15,6 +16,7 @@ use fyrox::{
         pool::{ErasedHandle, Handle},
         scope_profile,
     },
+    fxhash::FxHashSet,
     graph::SceneGraph,
     gui::{
         border::BorderBuilder,
@@ -27,13 +29,13 @@ use fyrox::{
         scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
         searchbar::{SearchBarBuilder, SearchBarMessage},
         stack_panel::StackPanelBuilder,
-        text::TextBuilder,
+        text::{TextBuilder, TextMessage},
         tree::{
             TreeBuilder, TreeExpansionStrategy, TreeMessage, TreeRoot, TreeRootBuilder,
             TreeRootMessage,
         },
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowTitle},
+        window::WindowBuilder,
         wrap_panel::WrapPanelBuilder,
         BuildContext, Orientation, RcUiNodeHandle, Thickness, UiNode, UserInterface,
         VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_PRIMARY,
@@ -44,7 +46,7 @@ use std::{
     borrow::Cow,
     cell::RefCell,
     cmp::Ordering,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops::Deref,
     path::{Path, PathBuf},
     rc::Rc,
@@ -67,6 +69,21 @@ pub trait WorldViewerDataProvider {
 
     fn name_of(&self, node: ErasedHandle) -> Option<Cow<str>>;
 
+    /// Returns a short (without module path) type name of the node, it is used by the search bar
+    /// to filter the tree by node types (for example `light` or `rigid_body`).
+    fn type_name_of(&self, node: ErasedHandle) -> Option<Cow<str>>;
+
+    /// Returns a short type name of a script assigned to the node (if any), it is used by the
+    /// search bar to filter the tree by script names.
+    fn script_name_of(&self, node: ErasedHandle) -> Option<Cow<str>>;
+
+    /// Returns a tag of the node (if any). Tags are shown next to the names of the items and can
+    /// be used in the search bar to filter the tree.
+    fn tag_of(&self, node: ErasedHandle) -> Option<Cow<str>>;
+
+    /// Returns editor-only flags of the node, or `None` if the node does not support them.
+    fn flags_of(&self, node: ErasedHandle) -> Option<SceneItemFlags>;
+
     fn is_valid_handle(&self, node: ErasedHandle) -> bool;
 
     fn icon_of(&self, node: ErasedHandle) -> Option<UntypedResource>;
@@ -75,9 +92,17 @@ pub trait WorldViewerDataProvider {
 
     fn selection(&self) -> Vec<ErasedHandle>;
 
-    fn on_change_hierarchy_request(&self, child: ErasedHandle, parent: ErasedHandle);
+    /// Asks the data provider to attach given nodes to the new parent. Nodes are given in the
+    /// order in which they should appear in the list of children of the new parent.
+    fn on_change_hierarchy_request(&self, children: &[ErasedHandle], parent: ErasedHandle);
+
+    /// Starts loading of an asset that was dropped on the tree. Returns `None` if the asset cannot
+    /// be instantiated in the scene. The asset is loaded in the background, when it is fully loaded
+    /// the world viewer calls [`Self::on_asset_loaded`].
+    fn request_asset(&self, path: PathBuf) -> Option<UntypedResource>;
 
-    fn on_asset_dropped(&mut self, path: PathBuf, node: ErasedHandle);
+    /// Instantiates a fully loaded asset and attaches the instance to the given node.
+    fn on_asset_loaded(&mut self, asset: UntypedResource, node: ErasedHandle);
 
     fn validate(&self) -> Vec<(ErasedHandle, Result<(), String>)>;
 
@@ -108,12 +133,27 @@ pub struct WorldViewer {
     scroll_view: Handle<UiNode>,
     pub item_context_menu: Option<Rc<RefCell<dyn WorldViewerItemContextMenu>>>,
     node_to_view_map: HashMap<ErasedHandle, Handle<UiNode>>,
+    /// Expansion state of every item of the tree, that was captured right before a non-empty
+    /// filter was applied. It is used to restore the tree to its previous state, when the filter
+    /// is cleared.
+    expansion_state_before_filter: Option<HashMap<Handle<UiNode>, bool>>,
+    /// Assets that were dropped on the tree, but still loading. Each asset will be instantiated
+    /// and attached to the paired node as soon as it is loaded.
+    pending_asset_drops: Vec<(UntypedResource, ErasedHandle)>,
+    loading_indicator: Handle<UiNode>,
+    /// Children of collapsed items are not created until they're needed (see `sync_graph`). This flag is raised when
+    /// some of such items must be created outside of regular model syncing, for example when an item was expanded.
+    needs_materialization: bool,
+    /// Objects that were requested to be located, but do not have their items created yet.
+    pending_locate: Vec<ErasedHandle>,
 }
 
 fn make_graph_node_item(
     name: Cow<str>,
+    tag: Option<Cow<str>>,
     is_instance: bool,
     icon: Option<UntypedResource>,
+    flags: Option<SceneItemFlags>,
     handle: ErasedHandle,
     ctx: &mut BuildContext,
     context_menu: RcUiNodeHandle,
@@ -139,8 +179,10 @@ fn make_graph_node_item(
         Brush::Solid(fyrox::gui::COLOR_FOREGROUND)
     })
     .with_name(name.deref().to_owned())
+    .with_tag(tag.map(|t| t.into_owned()).unwrap_or_default())
     .with_entity_handle(handle)
     .with_icon(icon)
+    .with_flags(flags)
     .build(ctx, sender)
 }
 
@@ -201,6 +243,7 @@ impl WorldViewer {
         let locate_selection;
         let scroll_view;
         let track_selection;
+        let loading_indicator;
         let search_bar = SearchBarBuilder::new(
             WidgetBuilder::new()
                 .on_row(1)
@@ -210,7 +253,7 @@ impl WorldViewer {
         let size = 15.0;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("WorldOutliner"))
             .can_minimize(false)
-            .with_title(WindowTitle::text("World Viewer"))
+            .with_title(window_title("World Viewer", ctx))
             .with_content(
                 GridBuilder::new(
                     WidgetBuilder::new()
@@ -272,6 +315,16 @@ impl WorldViewer {
                                         .checked(Some(settings.selection.track_selection))
                                         .build(ctx);
                                         track_selection
+                                    })
+                                    .with_child({
+                                        loading_indicator = TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_visibility(false)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .build(ctx);
+                                        loading_indicator
                                     }),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -325,6 +378,11 @@ impl WorldViewer {
             item_context_menu: None,
             node_to_view_map: Default::default(),
             filter: Default::default(),
+            expansion_state_before_filter: None,
+            pending_asset_drops: Default::default(),
+            loading_indicator,
+            needs_materialization: false,
+            pending_locate: Default::default(),
         }
     }
 
@@ -421,6 +479,19 @@ impl WorldViewer {
         data_provider: &dyn WorldViewerDataProvider,
         settings: &Settings,
     ) {
+        // Items of the selected (or located) nodes must always exist, so their ancestors must be expandable.
+        let mut required = HashSet::new();
+        for node in data_provider
+            .selection()
+            .into_iter()
+            .chain(self.pending_locate.iter().cloned())
+        {
+            let mut parent = data_provider.parent_of(node);
+            while parent.is_some() && required.insert(parent) {
+                parent = data_provider.parent_of(parent);
+            }
+        }
+
         // Sync tree structure with graph structure.
         self.stack.clear();
         self.stack.push((self.tree_root, data_provider.root_node()));
@@ -431,6 +502,27 @@ impl WorldViewer {
                 let child_count = data_provider.child_count_of(node_handle);
                 let items = item.tree.items.clone();
 
+                // Children of collapsed items are created only when the item is expanded (or when they're needed for
+                // filtering or selection), this way large graphs do not create thousands of hidden items.
+                let has_hidden_children = items.is_empty()
+                    && child_count > 0
+                    && !item.tree.is_expanded
+                    && self.filter.is_empty()
+                    && !required.contains(&node_handle);
+                if item.tree.always_show_expander != has_hidden_children {
+                    send_sync_message(
+                        ui,
+                        TreeMessage::set_expander_shown(
+                            tree_handle,
+                            MessageDirection::ToWidget,
+                            has_hidden_children,
+                        ),
+                    );
+                }
+                if has_hidden_children {
+                    continue;
+                }
+
                 match child_count.cmp(&items.len()) {
                     Ordering::Less => {
                         for &item in items.iter() {
@@ -479,8 +571,10 @@ impl WorldViewer {
                                 );
                                 let graph_node_item = make_graph_node_item(
                                     data_provider.name_of(child_handle).unwrap_or_default(),
+                                    data_provider.tag_of(child_handle),
                                     data_provider.is_instance(child_handle),
                                     data_provider.icon_of(child_handle),
+                                    data_provider.flags_of(child_handle),
                                     child_handle,
                                     &mut ui.build_ctx(),
                                     menu,
@@ -511,8 +605,10 @@ impl WorldViewer {
                     );
                     let new_root_item = make_graph_node_item(
                         data_provider.name_of(node_handle).unwrap_or_default(),
+                        data_provider.tag_of(node_handle),
                         data_provider.is_instance(node_handle),
                         data_provider.icon_of(node_handle),
+                        data_provider.flags_of(node_handle),
                         node_handle,
                         &mut ui.build_ctx(),
                         menu,
@@ -553,6 +649,26 @@ impl WorldViewer {
                         );
                     }
 
+                    let flags = data_provider.flags_of(item.entity_handle);
+                    if let Some(flags) = flags.filter(|flags| item.flags() != Some(*flags)) {
+                        send_sync_message(
+                            ui,
+                            SceneItemMessage::flags(handle, MessageDirection::ToWidget, flags),
+                        );
+                    }
+
+                    let tag = data_provider.tag_of(item.entity_handle).unwrap_or_default();
+                    if item.tag() != tag {
+                        send_sync_message(
+                            ui,
+                            SceneItemMessage::tag(
+                                handle,
+                                MessageDirection::ToWidget,
+                                tag.into_owned(),
+                            ),
+                        );
+                    }
+
                     stack.extend_from_slice(&item.tree.items);
                 }
             } else if let Some(root) = ui_node.cast::<TreeRoot>() {
@@ -571,32 +687,81 @@ impl WorldViewer {
         colorize(self.tree_root, ui, &mut index);
     }
 
-    fn apply_filter(&self, data_provider: &dyn WorldViewerDataProvider, ui: &UserInterface) {
-        fn apply_filter_recursive(node: Handle<UiNode>, filter: &str, ui: &UserInterface) -> bool {
+    fn apply_filter(&mut self, data_provider: &dyn WorldViewerDataProvider, ui: &UserInterface) {
+        fn apply_filter_recursive(
+            node: Handle<UiNode>,
+            filter: &str,
+            data_provider: &dyn WorldViewerDataProvider,
+            ui: &UserInterface,
+        ) -> bool {
             let node_ref = ui.node(node);
 
-            let mut is_any_match = false;
+            let mut is_any_child_match = false;
             for &child in node_ref.children() {
-                is_any_match |= apply_filter_recursive(child, filter, ui)
+                is_any_child_match |= apply_filter_recursive(child, filter, data_provider, ui)
             }
 
-            let name = node_ref.cast::<SceneItem>().map(|i| i.name());
+            if let Some(item) = node_ref.cast::<SceneItem>() {
+                let is_match =
+                    is_any_child_match || is_item_matches_filter(item, filter, data_provider);
 
-            if let Some(name) = name {
-                is_any_match |= name.to_lowercase().contains(filter)
-                    || fuzzy_compare(filter, name.to_lowercase().as_str()) >= 0.33;
+                // Expand every item that contains matching descendants, so the results will be
+                // visible immediately.
+                if !filter.is_empty() && is_any_child_match && !item.tree.is_expanded {
+                    ui.send_message(TreeMessage::expand(
+                        node,
+                        MessageDirection::ToWidget,
+                        true,
+                        TreeExpansionStrategy::Direct,
+                    ));
+                }
 
                 ui.send_message(WidgetMessage::visibility(
                     node,
                     MessageDirection::ToWidget,
-                    is_any_match,
+                    is_match,
                 ));
+
+                is_match
+            } else {
+                is_any_child_match
             }
+        }
 
-            is_any_match
+        if self.filter.is_empty() {
+            if let Some(expansion_state) = self.expansion_state_before_filter.take() {
+                for (view, is_expanded) in expansion_state {
+                    if let Some(item) = ui.try_get(view).and_then(|n| n.cast::<SceneItem>()) {
+                        if item.tree.is_expanded != is_expanded {
+                            ui.send_message(TreeMessage::expand(
+                                view,
+                                MessageDirection::ToWidget,
+                                is_expanded,
+                                TreeExpansionStrategy::Direct,
+                            ));
+                        }
+                    }
+                }
+            }
+        } else if self.expansion_state_before_filter.is_none() {
+            let mut expansion_state = HashMap::new();
+            let mut stack = vec![self.tree_root];
+            while let Some(handle) = stack.pop() {
+                let node_ref = ui.node(handle);
+                if let Some(item) = node_ref.cast::<SceneItem>() {
+                    expansion_state.insert(handle, item.tree.is_expanded);
+                }
+                stack.extend_from_slice(node_ref.children());
+            }
+            self.expansion_state_before_filter = Some(expansion_state);
         }
 
-        apply_filter_recursive(self.tree_root, &self.filter.to_lowercase(), ui);
+        apply_filter_recursive(
+            self.tree_root,
+            &self.filter.to_lowercase(),
+            data_provider,
+            ui,
+        );
 
         if self.filter.is_empty() {
             if let Some(first) = data_provider.selection().first() {
@@ -618,6 +783,10 @@ impl WorldViewer {
         ui: &UserInterface,
     ) {
         self.filter = filter;
+        if !self.filter.is_empty() {
+            // Filtering requires every item to exist, the filter will be applied again once they're created.
+            self.needs_materialization = true;
+        }
         self.apply_filter(data_provider, ui)
     }
 
@@ -685,12 +854,25 @@ impl WorldViewer {
                         .or_default()
                         .is_expanded = *expand;
                 }
+
+                if *expand
+                    && scene_view_item.tree.items.is_empty()
+                    && data_provider.child_count_of(scene_view_item.entity_handle) > 0
+                {
+                    self.needs_materialization = true;
+                }
             }
         }
     }
 
-    pub fn try_locate_object(&self, handle: ErasedHandle, ui: &UserInterface) {
-        self.locate_selection(&[handle], ui)
+    pub fn try_locate_object(&mut self, handle: ErasedHandle, ui: &UserInterface) {
+        if self.map_selection(&[handle], ui).is_empty() {
+            // The item might not exist yet, because its parent was never expanded.
+            self.pending_locate.push(handle);
+            self.needs_materialization = true;
+        } else {
+            self.locate_selection(&[handle], ui)
+        }
     }
 
     fn locate_selection(&self, selection: &[ErasedHandle], ui: &UserInterface) {
@@ -734,7 +916,7 @@ impl WorldViewer {
     /// `target` - is a node at which `dropped` was dropped.
     /// `dropped` - is a node which was dropped at `target`.
     fn handle_drop(
-        &self,
+        &mut self,
         ui: &UserInterface,
         data_provider: &mut dyn WorldViewerDataProvider,
         target: Handle<UiNode>,
@@ -742,7 +924,10 @@ impl WorldViewer {
     ) {
         if let Some(item) = ui.node(dropped).cast::<AssetItem>() {
             if let Some(parent) = ui.node(target).cast::<SceneItem>() {
-                data_provider.on_asset_dropped(item.path.clone(), parent.entity_handle);
+                if let Some(asset) = data_provider.request_asset(item.path.clone()) {
+                    self.pending_asset_drops.push((asset, parent.entity_handle));
+                    self.sync_loading_indicator(ui);
+                }
             }
         } else if ui.is_node_child_of(dropped, self.tree_root)
             && ui.is_node_child_of(target, self.tree_root)
@@ -752,7 +937,8 @@ impl WorldViewer {
                 ui.node(dropped).cast::<SceneItem>(),
                 ui.node(target).cast::<SceneItem>(),
             ) {
-                data_provider.on_change_hierarchy_request(child.entity_handle, parent.entity_handle)
+                let children = collect_dragged_nodes(child.entity_handle, data_provider);
+                data_provider.on_change_hierarchy_request(&children, parent.entity_handle)
             }
         }
     }
@@ -761,12 +947,70 @@ impl WorldViewer {
         map_selection(selection, self.tree_root, ui)
     }
 
+    fn sync_loading_indicator(&self, ui: &UserInterface) {
+        let count = self.pending_asset_drops.len();
+
+        ui.send_message(WidgetMessage::visibility(
+            self.loading_indicator,
+            MessageDirection::ToWidget,
+            count > 0,
+        ));
+
+        if count > 0 {
+            ui.send_message(TextMessage::text(
+                self.loading_indicator,
+                MessageDirection::ToWidget,
+                format!("Loading {} asset(s)...", count),
+            ));
+        }
+    }
+
+    fn update_pending_asset_drops(
+        &mut self,
+        data_provider: &mut dyn WorldViewerDataProvider,
+        ui: &UserInterface,
+    ) {
+        if self.pending_asset_drops.is_empty() {
+            return;
+        }
+
+        let mut loaded = Vec::new();
+        self.pending_asset_drops.retain(|(asset, node)| {
+            if asset.is_loading() {
+                true
+            } else {
+                loaded.push((asset.clone(), *node));
+                false
+            }
+        });
+
+        if !loaded.is_empty() {
+            self.sync_loading_indicator(ui);
+
+            for (asset, node) in loaded {
+                data_provider.on_asset_loaded(asset, node);
+            }
+        }
+    }
+
     pub fn post_update(
         &mut self,
-        data_provider: &dyn WorldViewerDataProvider,
+        data_provider: &mut dyn WorldViewerDataProvider,
         ui: &mut UserInterface,
         settings: &Settings,
     ) {
+        self.update_pending_asset_drops(data_provider, ui);
+
+        if self.needs_materialization {
+            self.needs_materialization = false;
+            self.sync_graph(ui, &*data_provider, settings);
+            if !self.filter.is_empty() {
+                self.apply_filter(&*data_provider, ui);
+            }
+            let pending_locate = std::mem::take(&mut self.pending_locate);
+            self.locate_selection(&pending_locate, ui);
+        }
+
         // Hack. See `self.sync_selection` for details.
         if self.sync_selection {
             let trees = self.map_selection(&data_provider.selection(), ui);
@@ -787,6 +1031,11 @@ impl WorldViewer {
 
     pub fn clear(&mut self, ui: &UserInterface) {
         self.node_to_view_map.clear();
+        self.expansion_state_before_filter = None;
+        self.pending_asset_drops.clear();
+        self.pending_locate.clear();
+        self.needs_materialization = false;
+        self.sync_loading_indicator(ui);
         self.clear_breadcrumbs(ui);
         ui.send_message(TreeRootMessage::items(
             self.tree_root,
@@ -829,6 +1078,70 @@ impl WorldViewer {
     }
 }
 
+/// Collects a list of nodes, that should be moved together with the dragged node. If the dragged
+/// node is selected, then every other selected node is dragged as well. Selected nodes, that have
+/// a selected ancestor, are excluded, because they will be moved with their ancestor. The result
+/// is sorted in the hierarchical (depth-first) order, so the relative order of the nodes will be
+/// preserved when they're attached to a new parent.
+fn collect_dragged_nodes(
+    dragged: ErasedHandle,
+    data_provider: &dyn WorldViewerDataProvider,
+) -> Vec<ErasedHandle> {
+    let selection = data_provider
+        .selection()
+        .into_iter()
+        .collect::<FxHashSet<_>>();
+    if !selection.contains(&dragged) {
+        return vec![dragged];
+    }
+
+    let mut nodes = Vec::new();
+    let mut stack = vec![data_provider.root_node()];
+    while let Some(node) = stack.pop() {
+        if selection.contains(&node) {
+            nodes.push(node);
+        } else {
+            stack.extend(data_provider.children_of(node).into_iter().rev());
+        }
+    }
+    nodes
+}
+
+/// Removes every separator from the given (lowercase) string, so `rigid_body`, `rigid body` and
+/// `RigidBody` could be matched with each other.
+fn normalize_type_filter(s: &str) -> String {
+    s.chars().filter(|c| *c != '_' && *c != ' ').collect()
+}
+
+/// Checks whether the item matches the given lowercase filter either by its name, its tag, its
+/// type name or by the name of a script assigned to it.
+fn is_item_matches_filter(
+    item: &SceneItem,
+    filter: &str,
+    data_provider: &dyn WorldViewerDataProvider,
+) -> bool {
+    let name = item.name().to_lowercase();
+    if name.contains(filter)
+        || fuzzy_compare(filter, name.as_str()) >= 0.33
+        || item.tag().to_lowercase().contains(filter)
+    {
+        return true;
+    }
+
+    let normalized_filter = normalize_type_filter(filter);
+    if normalized_filter.is_empty() {
+        return false;
+    }
+
+    [
+        data_provider.type_name_of(item.entity_handle),
+        data_provider.script_name_of(item.entity_handle),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|name| normalize_type_filter(&name.to_lowercase()).contains(&normalized_filter))
+}
+
 fn map_selection(
     selection: &[ErasedHandle],
     root_node: Handle<UiNode>,
@@ -850,3 +1163,128 @@ fn map_selection(
         })
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use super::{collect_dragged_nodes, SceneItemFlags, WorldViewerDataProvider};
+    use fyrox::{asset::untyped::UntypedResource, core::pool::ErasedHandle};
+    use std::{
+        borrow::Cow,
+        path::{Path, PathBuf},
+    };
+
+    struct TestDataProvider {
+        // Index of a node is its handle, the value is a list of children.
+        children: Vec<Vec<u32>>,
+        selection: Vec<u32>,
+    }
+
+    fn handle(index: u32) -> ErasedHandle {
+        ErasedHandle::new(index, 1)
+    }
+
+    impl WorldViewerDataProvider for TestDataProvider {
+        fn root_node(&self) -> ErasedHandle {
+            handle(0)
+        }
+
+        fn path(&self) -> Option<&Path> {
+            None
+        }
+
+        fn children_of(&self, node: ErasedHandle) -> Vec<ErasedHandle> {
+            self.children[node.index() as usize]
+                .iter()
+                .map(|i| handle(*i))
+                .collect()
+        }
+
+        fn child_count_of(&self, node: ErasedHandle) -> usize {
+            self.children[node.index() as usize].len()
+        }
+
+        fn is_node_has_child(&self, node: ErasedHandle, child: ErasedHandle) -> bool {
+            self.children_of(node).contains(&child)
+        }
+
+        fn parent_of(&self, node: ErasedHandle) -> ErasedHandle {
+            (0..self.children.len() as u32)
+                .map(handle)
+                .find(|parent| self.is_node_has_child(*parent, node))
+                .unwrap_or_default()
+        }
+
+        fn name_of(&self, _node: ErasedHandle) -> Option<Cow<str>> {
+            None
+        }
+
+        fn type_name_of(&self, _node: ErasedHandle) -> Option<Cow<str>> {
+            None
+        }
+
+        fn script_name_of(&self, _node: ErasedHandle) -> Option<Cow<str>> {
+            None
+        }
+
+        fn tag_of(&self, _node: ErasedHandle) -> Option<Cow<str>> {
+            None
+        }
+
+        fn flags_of(&self, _node: ErasedHandle) -> Option<SceneItemFlags> {
+            None
+        }
+
+        fn is_valid_handle(&self, node: ErasedHandle) -> bool {
+            (node.index() as usize) < self.children.len()
+        }
+
+        fn icon_of(&self, _node: ErasedHandle) -> Option<UntypedResource> {
+            None
+        }
+
+        fn is_instance(&self, _node: ErasedHandle) -> bool {
+            false
+        }
+
+        fn selection(&self) -> Vec<ErasedHandle> {
+            self.selection.iter().map(|i| handle(*i)).collect()
+        }
+
+        fn on_change_hierarchy_request(&self, _children: &[ErasedHandle], _parent: ErasedHandle) {}
+
+        fn request_asset(&self, _path: PathBuf) -> Option<UntypedResource> {
+            None
+        }
+
+        fn on_asset_loaded(&mut self, _asset: UntypedResource, _node: ErasedHandle) {}
+
+        fn validate(&self) -> Vec<(ErasedHandle, Result<(), String>)> {
+            Vec::new()
+        }
+
+        fn on_selection_changed(&self, _new_selection: &[ErasedHandle]) {}
+    }
+
+    #[test]
+    fn test_collect_dragged_nodes() {
+        // 0
+        // ├── 1
+        // │   ├── 2
+        // │   └── 3
+        // ├── 4
+        // └── 5
+        let provider = TestDataProvider {
+            children: vec![vec![1, 4, 5], vec![2, 3], vec![], vec![], vec![], vec![]],
+            selection: vec![5, 2, 1, 4],
+        };
+
+        // Descendants of selected nodes are excluded, the rest is in the hierarchical order.
+        assert_eq!(
+            collect_dragged_nodes(handle(5), &provider),
+            vec![handle(1), handle(4), handle(5)]
+        );
+
+        // Dragging of a node outside of the selection moves only that node.
+        assert_eq!(collect_dragged_nodes(handle(3), &provider), vec![handle(3)]);
+    }
+}
@@ -4,8 +4,9 @@
 #![warn(missing_docs)]
 
 use crate::{
+    container::TrackValueKind,
     core::{
-        algebra::{UnitQuaternion, Vector3},
+        algebra::{Matrix4, UnitQuaternion, Vector3},
         math::wrapf,
         pool::{Handle, Pool, Ticket},
         reflect::prelude::*,
@@ -190,6 +191,10 @@ pub struct Animation<T: EntityId> {
     #[reflect(hidden)]
     #[visit(skip)]
     events: VecDeque<AnimationEvent>,
+    // Non-serialized
+    #[reflect(hidden)]
+    #[visit(skip)]
+    last_tick_events: Vec<AnimationEvent>,
 }
 
 impl<T: EntityId> TypeUuidProvider for Animation<T> {
@@ -223,6 +228,11 @@ pub struct RootMotionSettings<T: EntityId> {
     pub ignore_z_movement: bool,
     /// Keeps rotational part of the motion.
     pub ignore_rotations: bool,
+    /// If `true`, the motion will be extracted, but it won't be removed from the pose of the root
+    /// node. It is useful when the motion should only be measured (for example, to sync movement
+    /// speed of a character controller with the animation), but not applied manually.
+    #[visit(optional)]
+    pub keep_in_pose: bool,
 }
 
 /// Motion of a root node of an hierarchy of nodes. It contains relative rotation and translation in local
@@ -245,6 +255,47 @@ impl RootMotion {
         self.delta_position = self.delta_position.lerp(&other.delta_position, weight);
         self.delta_rotation = self.delta_rotation.nlerp(&other.delta_rotation, weight);
     }
+
+    /// Transforms the translational part of the motion using the given transform (usually the
+    /// global transform of the animated model) and returns the offset in the space of the transform.
+    pub fn transformed_delta_position(&self, transform: &Matrix4<f32>) -> Vector3<f32> {
+        transform.transform_vector(&self.delta_position)
+    }
+
+    /// Calculates linear velocity of the motion in the space of the given transform (usually the
+    /// global transform of the animated model). The result could be directly applied to a rigid
+    /// body of a character controller. `dt` must be the same time step that was used to update the
+    /// animation, zero or negative time step results in zero velocity.
+    pub fn linear_velocity(&self, transform: &Matrix4<f32>, dt: f32) -> Vector3<f32> {
+        if dt > 0.0 {
+            self.transformed_delta_position(transform).scale(1.0 / dt)
+        } else {
+            Vector3::default()
+        }
+    }
+}
+
+/// Parameters of lossy compression of an animation. See [`Animation::compress`] for more info.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationCompressionSettings {
+    /// Maximum allowed error of keys of every track except rotation tracks (positions, scales and
+    /// other properties).
+    pub tolerance: f32,
+    /// Maximum allowed error (in radians) of keys of rotation tracks.
+    pub rotation_tolerance: f32,
+    /// Rotation keys are rounded to the nearest multiple of this step (in radians). Zero disables
+    /// the quantization.
+    pub rotation_quantization_step: f32,
+}
+
+impl Default for AnimationCompressionSettings {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.001,
+            rotation_tolerance: 0.002,
+            rotation_quantization_step: 0.0,
+        }
+    }
 }
 
 impl<T: EntityId> NameProvider for Animation<T> {
@@ -266,6 +317,7 @@ impl<T: EntityId> Clone for Animation<T> {
             signals: self.signals.clone(),
             root_motion_settings: self.root_motion_settings.clone(),
             events: Default::default(),
+            last_tick_events: Default::default(),
             time_slice: self.time_slice.clone(),
             root_motion: self.root_motion.clone(),
         }
@@ -376,18 +428,36 @@ impl<T: EntityId> Animation<T> {
         let current_time_position = self.time_position();
         let new_time_position = current_time_position + dt * self.speed();
 
+        self.last_tick_events.clear();
         for signal in self.signals.iter_mut().filter(|s| s.enabled) {
-            if self.speed >= 0.0
-                && (current_time_position < signal.time && new_time_position >= signal.time)
-                || self.speed < 0.0
-                    && (current_time_position > signal.time && new_time_position <= signal.time)
+            let is_crossed = |time: f32| {
+                self.speed >= 0.0 && (current_time_position < time && new_time_position >= time)
+                    || self.speed < 0.0
+                        && (current_time_position > time && new_time_position <= time)
+            };
+
+            // Looping animations could wrap the time position on this tick, in this case signals
+            // at the beginning (or at the end, if played backwards) of the next cycle must be
+            // taken into account as well.
+            let length = self.time_slice.end - self.time_slice.start;
+            let next_cycle_time = if self.speed >= 0.0 {
+                signal.time + length
+            } else {
+                signal.time - length
+            };
+
+            if is_crossed(signal.time) || self.looped && length > 0.0 && is_crossed(next_cycle_time)
             {
+                let event = AnimationEvent {
+                    signal_id: signal.id,
+                    name: signal.name.clone(),
+                };
+
+                self.last_tick_events.push(event.clone());
+
                 // TODO: Make this configurable.
                 if self.events.len() < 32 {
-                    self.events.push_back(AnimationEvent {
-                        signal_id: signal.id,
-                        name: signal.name.clone(),
-                    });
+                    self.events.push_back(event);
                 }
             }
         }
@@ -400,10 +470,14 @@ impl<T: EntityId> Animation<T> {
     }
 
     fn update_root_motion(&mut self, prev_time_position: f32) {
-        fn fetch_position_at_time<T: EntityId>(tracks: &[Track<T>], time: f32) -> Vector3<f32> {
+        fn fetch_position_at_time<T: EntityId>(
+            tracks: &[Track<T>],
+            node: T,
+            time: f32,
+        ) -> Vector3<f32> {
             tracks
                 .iter()
-                .find(|track| track.binding() == &ValueBinding::Position)
+                .find(|track| track.target() == node && track.binding() == &ValueBinding::Position)
                 .and_then(|track| track.fetch(time))
                 .and_then(|value| {
                     if let TrackValue::Vector3(position) = value.value {
@@ -417,11 +491,12 @@ impl<T: EntityId> Animation<T> {
 
         fn fetch_rotation_at_time<T: EntityId>(
             tracks: &[Track<T>],
+            node: T,
             time: f32,
         ) -> UnitQuaternion<f32> {
             tracks
                 .iter()
-                .find(|track| track.binding() == &ValueBinding::Rotation)
+                .find(|track| track.target() == node && track.binding() == &ValueBinding::Rotation)
                 .and_then(|track| track.fetch(time))
                 .and_then(|value| {
                     if let TrackValue::UnitQuaternion(rotation) = value.value {
@@ -455,18 +530,25 @@ impl<T: EntityId> Animation<T> {
                 self.time_slice.start
             };
 
+            let root_node = root_motion_settings.node;
             let mut root_motion = RootMotion::default();
-            if let Some(root_pose) = self.pose.poses_mut().get_mut(&root_motion_settings.node) {
+            if let Some(root_pose) = self.pose.poses_mut().get_mut(&root_node) {
                 for bound_value in root_pose.values.values.iter_mut() {
                     match bound_value.binding {
                         ValueBinding::Position => {
                             if let TrackValue::Vector3(pose_position) = bound_value.value {
                                 let delta = if new_loop_cycle_started {
-                                    root_motion.prev_position =
-                                        fetch_position_at_time(&self.tracks, cycle_start_time);
+                                    root_motion.prev_position = fetch_position_at_time(
+                                        &self.tracks,
+                                        root_node,
+                                        cycle_start_time,
+                                    );
 
-                                    let end_value =
-                                        fetch_position_at_time(&self.tracks, cycle_end_time);
+                                    let end_value = fetch_position_at_time(
+                                        &self.tracks,
+                                        root_node,
+                                        cycle_end_time,
+                                    );
 
                                     end_value - prev_root_motion.prev_position
                                 } else {
@@ -493,9 +575,16 @@ impl<T: EntityId> Animation<T> {
                                         delta.z
                                     };
 
+                                if root_motion_settings.keep_in_pose {
+                                    continue;
+                                }
+
                                 // Reset position so the root won't move.
-                                let start_position =
-                                    fetch_position_at_time(&self.tracks, self.time_slice.start);
+                                let start_position = fetch_position_at_time(
+                                    &self.tracks,
+                                    root_node,
+                                    self.time_slice.start,
+                                );
 
                                 bound_value.value = TrackValue::Vector3(Vector3::new(
                                     if root_motion_settings.ignore_x_movement {
@@ -520,11 +609,17 @@ impl<T: EntityId> Animation<T> {
                             if let TrackValue::UnitQuaternion(pose_rotation) = bound_value.value {
                                 if !root_motion_settings.ignore_rotations {
                                     if new_loop_cycle_started {
-                                        root_motion.prev_rotation =
-                                            fetch_rotation_at_time(&self.tracks, cycle_start_time);
-
-                                        let end_value =
-                                            fetch_rotation_at_time(&self.tracks, cycle_end_time);
+                                        root_motion.prev_rotation = fetch_rotation_at_time(
+                                            &self.tracks,
+                                            root_node,
+                                            cycle_start_time,
+                                        );
+
+                                        let end_value = fetch_rotation_at_time(
+                                            &self.tracks,
+                                            root_node,
+                                            cycle_end_time,
+                                        );
 
                                         root_motion.delta_rotation =
                                             prev_root_motion.prev_rotation.inverse() * end_value;
@@ -537,9 +632,14 @@ impl<T: EntityId> Animation<T> {
                                     }
 
                                     // Reset rotation so the root won't rotate.
-                                    bound_value.value = TrackValue::UnitQuaternion(
-                                        fetch_rotation_at_time(&self.tracks, self.time_slice.start),
-                                    );
+                                    if !root_motion_settings.keep_in_pose {
+                                        bound_value.value =
+                                            TrackValue::UnitQuaternion(fetch_rotation_at_time(
+                                                &self.tracks,
+                                                root_node,
+                                                self.time_slice.start,
+                                            ));
+                                    }
                                 }
                             }
                         }
@@ -571,6 +671,20 @@ impl<T: EntityId> Animation<T> {
         self.root_motion.as_ref()
     }
 
+    /// Returns a list of events, that were produced by the signals of the animation on the last
+    /// update tick. Unlike the events queue, this list is cleared on every tick, so it could be
+    /// used to react on the events without extracting them from the queue.
+    pub fn last_tick_events(&self) -> &[AnimationEvent] {
+        &self.last_tick_events
+    }
+
+    /// Takes the list of events produced on the last update tick, leaving the list empty. The
+    /// engine uses it to deliver the events to scripts (see `AnimationEventMessage` in the engine
+    /// docs), so the events are delivered only once even if the animation is not updated anymore.
+    pub fn take_last_tick_events(&mut self) -> Vec<AnimationEvent> {
+        std::mem::take(&mut self.last_tick_events)
+    }
+
     /// Extracts a first event from the events queue of the animation.
     pub fn pop_event(&mut self) -> Option<AnimationEvent> {
         self.events.pop_front()
@@ -749,6 +863,37 @@ impl<T: EntityId> Animation<T> {
         }
     }
 
+    /// Samples every enabled track of the animation at the given time and adds the values to the given pose. Unlike
+    /// [`Self::tick`], this method does not modify the state of the animation. It could be useful to fetch a pose at
+    /// some specific time, for example a reference pose for additive blending.
+    pub fn sample_pose(&self, time: f32, pose: &mut AnimationPose<T>) {
+        for track in self.tracks.iter() {
+            if track.is_enabled() {
+                if let Some(bound_value) = track.fetch(time) {
+                    pose.add_to_node_pose(track.target(), bound_value);
+                }
+            }
+        }
+    }
+
+    /// Performs lossy compression of the animation. At first, values of the keys of rotation tracks are quantized
+    /// (if enabled), then every key that can be restored by interpolation between its neighbours (with the error that
+    /// does not exceed the tolerance from the settings) is removed. It is very effective for baked animations, that
+    /// usually have a key on every frame for every track. The time slice of the animation is not changed.
+    pub fn compress(&mut self, settings: &AnimationCompressionSettings) {
+        for track in self.tracks.iter_mut() {
+            let is_rotation = track.data_container().value_kind() == TrackValueKind::UnitQuaternion;
+            for curve in track.data_container_mut().curves_mut() {
+                if is_rotation {
+                    curve.quantize_values(settings.rotation_quantization_step);
+                    curve.reduce_keys(settings.rotation_tolerance);
+                } else {
+                    curve.reduce_keys(settings.tolerance);
+                }
+            }
+        }
+    }
+
     /// Returns current pose of the animation (a final result that can be applied to a scene graph).
     pub fn pose(&self) -> &AnimationPose<T> {
         &self.pose
@@ -768,6 +913,7 @@ impl<T: EntityId> Default for Animation<T> {
             signals: Default::default(),
             root_motion_settings: None,
             events: Default::default(),
+            last_tick_events: Default::default(),
             time_slice: Default::default(),
             root_motion: None,
         }
@@ -960,3 +1106,150 @@ impl<T: EntityId> IndexMut<Handle<Animation<T>>> for AnimationContainer<T> {
         &mut self.pool[index]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::Vector3,
+            curve::{CurveKey, CurveKeyKind},
+            pool::ErasedHandle,
+        },
+        track::Track,
+        value::TrackValue,
+        Animation, AnimationCompressionSettings, AnimationPose, AnimationSignal,
+        RootMotionSettings,
+    };
+
+    fn make_animation(root: ErasedHandle, keep_in_pose: bool) -> Animation<ErasedHandle> {
+        let mut track = Track::new_position().with_target(root);
+        let curves = track.data_container_mut().curves_mut();
+        curves[0].add_key(CurveKey::new(0.0, 0.0, CurveKeyKind::Linear));
+        curves[0].add_key(CurveKey::new(1.0, 1.0, CurveKeyKind::Linear));
+        for curve in curves[1..].iter_mut() {
+            curve.add_key(CurveKey::new(0.0, 0.0, CurveKeyKind::Linear));
+        }
+
+        let mut animation = Animation::default();
+        animation.add_track(track);
+        animation.fit_length_to_content();
+        animation.set_loop(false);
+        animation.set_root_motion_settings(Some(RootMotionSettings {
+            node: root,
+            keep_in_pose,
+            ..Default::default()
+        }));
+        animation
+    }
+
+    fn pose_position(pose: &AnimationPose<ErasedHandle>, root: ErasedHandle) -> Vector3<f32> {
+        match pose.poses()[&root].values.values[0].value {
+            TrackValue::Vector3(position) => position,
+            _ => unreachable!(),
+        }
+    }
+
+    fn root_position(animation: &Animation<ErasedHandle>, root: ErasedHandle) -> Vector3<f32> {
+        match animation.pose().poses()[&root].values.values[0].value {
+            TrackValue::Vector3(position) => position,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_root_motion_extraction() {
+        let root = ErasedHandle::new(1, 1);
+
+        for keep_in_pose in [false, true] {
+            let mut animation = make_animation(root, keep_in_pose);
+            animation.tick(0.5);
+            animation.tick(0.5);
+
+            let root_motion = animation.root_motion().unwrap();
+            assert!((root_motion.delta_position.x - 0.5).abs() < 0.001);
+
+            let expected_x = if keep_in_pose { 0.5 } else { 0.0 };
+            assert!((root_position(&animation, root).x - expected_x).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_signals_of_looped_animation() {
+        let root = ErasedHandle::new(1, 1);
+        let mut animation = make_animation(root, false);
+        animation.set_loop(true);
+        animation.add_signal(AnimationSignal::new(Default::default(), "Footstep", 0.1));
+
+        animation.set_time_position(0.95);
+        animation.tick(0.2);
+        assert_eq!(animation.last_tick_events().len(), 1);
+        assert_eq!(animation.last_tick_events()[0].name, "Footstep");
+
+        animation.tick(0.2);
+        assert!(animation.last_tick_events().is_empty());
+        assert_eq!(animation.events_ref().len(), 1);
+    }
+
+    #[test]
+    fn test_additive_pose() {
+        let root = ErasedHandle::new(1, 1);
+
+        let mut base = Animation::default();
+        let mut track = Track::new_position().with_target(root);
+        for curve in track.data_container_mut().curves_mut() {
+            curve.add_key(CurveKey::new(0.0, 1.0, CurveKeyKind::Linear));
+        }
+        base.add_track(track);
+
+        let additive = make_animation(root, false);
+
+        let mut pose = AnimationPose::default();
+        base.sample_pose(0.5, &mut pose);
+
+        let mut additive_pose = AnimationPose::default();
+        additive.sample_pose(1.0, &mut additive_pose);
+
+        let mut reference_pose = AnimationPose::default();
+        additive.sample_pose(0.0, &mut reference_pose);
+
+        pose.add_difference(&additive_pose, &reference_pose, 0.5);
+
+        let position = pose_position(&pose, root);
+        assert!((position - Vector3::new(1.5, 1.0, 1.0)).norm() < 0.001);
+    }
+
+    #[test]
+    fn test_animation_compression() {
+        let root = ErasedHandle::new(1, 1);
+
+        let mut track = Track::new_position().with_target(root);
+        for curve in track.data_container_mut().curves_mut() {
+            for i in 0..=30 {
+                let time = i as f32 / 30.0;
+                curve.add_key(CurveKey::new(time, time * 2.0, CurveKeyKind::Linear));
+            }
+        }
+
+        let mut animation = Animation::default();
+        animation.add_track(track);
+        animation.fit_length_to_content();
+
+        let reference = animation.clone();
+
+        animation.compress(&AnimationCompressionSettings::default());
+
+        for curve in animation.tracks()[0].data_container().curves_ref() {
+            assert_eq!(curve.keys().len(), 2);
+        }
+        assert_eq!(animation.time_slice(), reference.time_slice());
+
+        for i in 0..=10 {
+            let time = i as f32 / 10.0;
+            let mut expected = AnimationPose::default();
+            reference.sample_pose(time, &mut expected);
+            let mut actual = AnimationPose::default();
+            animation.sample_pose(time, &mut actual);
+            assert!((pose_position(&expected, root) - pose_position(&actual, root)).norm() < 0.001);
+        }
+    }
+}
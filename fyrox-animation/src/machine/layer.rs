@@ -14,7 +14,36 @@ use crate::{
     },
     Animation, AnimationContainer, AnimationEvent, AnimationPose, EntityId,
 };
-use fyrox_core::{find_by_name_mut, find_by_name_ref, NameProvider};
+use fyrox_core::{find_by_name_mut, find_by_name_ref, uuid_provider, NameProvider};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines how the pose of a layer is combined with the poses of the previous layers of a state machine.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    EnumVariantNames,
+    EnumString,
+    AsRefStr,
+)]
+pub enum LayerBlendMode {
+    /// The pose of the layer is blended with the poses of the previous layers using the weight of the layer. With
+    /// the weight of 1.0 the pose of the layer completely overrides the animated properties.
+    #[default]
+    Override,
+
+    /// The difference between the pose of the layer and its reference pose is added on top of the poses of the previous
+    /// layers. The reference pose is the pose of every animation of the layer at the beginning of its time slice. This
+    /// mode is useful for aim offsets, breathing, leaning and so on, that should be applied on top of locomotion.
+    Additive,
+}
+
+uuid_provider!(LayerBlendMode = "3f8b2a31-9cd4-4a63-9d3e-54b51c5f3e1d");
 
 /// Layer is a separate state graph. Layers mainly used to animate different parts of humanoid (but not only) characters. For
 /// example there could a layer for upper body and a layer for lower body. Upper body layer could contain animations for aiming,
@@ -67,6 +96,9 @@ pub struct MachineLayer<T: EntityId> {
 
     weight: f32,
 
+    #[visit(optional)]
+    blend_mode: LayerBlendMode,
+
     mask: LayerMask<T>,
 
     #[reflect(hidden)]
@@ -91,6 +123,10 @@ pub struct MachineLayer<T: EntityId> {
     #[reflect(hidden)]
     final_pose: AnimationPose<T>,
 
+    #[visit(skip)]
+    #[reflect(hidden)]
+    reference_pose: AnimationPose<T>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     events: FixedEventQueue<T>,
@@ -154,10 +190,12 @@ impl<T: EntityId> MachineLayer<T> {
             states: Default::default(),
             transitions: Default::default(),
             final_pose: Default::default(),
+            reference_pose: Default::default(),
             active_state: Default::default(),
             entry_state: Default::default(),
             active_transition: Default::default(),
             weight: 1.0,
+            blend_mode: Default::default(),
             events: FixedEventQueue::new(2048),
             debug: false,
             mask: Default::default(),
@@ -515,6 +553,18 @@ impl<T: EntityId> MachineLayer<T> {
         self.weight
     }
 
+    /// Sets a new blend mode of the layer. See [`LayerBlendMode`] docs for more info.
+    #[inline]
+    pub fn set_blend_mode(&mut self, blend_mode: LayerBlendMode) -> LayerBlendMode {
+        std::mem::replace(&mut self.blend_mode, blend_mode)
+    }
+
+    /// Returns current blend mode of the layer.
+    #[inline]
+    pub fn blend_mode(&self) -> LayerBlendMode {
+        self.blend_mode
+    }
+
     /// Sets new layer mask. See docs of [`LayerMask`] for more info about layer masks.
     #[inline]
     pub fn set_mask(&mut self, mask: LayerMask<T>) -> LayerMask<T> {
@@ -533,6 +583,13 @@ impl<T: EntityId> MachineLayer<T> {
         &self.final_pose
     }
 
+    /// Returns the reference pose of the layer. It is calculated only for layers with [`LayerBlendMode::Additive`]
+    /// blend mode and it is empty for every other layer.
+    #[inline]
+    pub fn reference_pose(&self) -> &AnimationPose<T> {
+        &self.reference_pose
+    }
+
     /// Returns an iterator over all animations of a given state. It fetches the animations from [`PoseNode::PlayAnimation`]
     /// nodes and returns them. This method could be useful to extract all animations used by a particular state. For example,
     /// to listen for animation events and react to them.
@@ -680,6 +737,18 @@ impl<T: EntityId> MachineLayer<T> {
             .poses_mut()
             .retain(|h, _| self.mask.should_animate(*h));
 
+        self.reference_pose.reset();
+        if self.blend_mode == LayerBlendMode::Additive {
+            for node in self.nodes.iter() {
+                if let PoseNode::PlayAnimation(play_animation) = node {
+                    if let Some(animation) = animations.try_get(play_animation.animation) {
+                        animation
+                            .sample_pose(animation.time_slice().start, &mut self.reference_pose);
+                    }
+                }
+            }
+        }
+
         &self.final_pose
     }
 }
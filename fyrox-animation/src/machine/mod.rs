@@ -15,7 +15,7 @@ use crate::{
 
 pub use event::Event;
 use fyrox_core::{find_by_name_mut, find_by_name_ref};
-pub use layer::MachineLayer;
+pub use layer::{LayerBlendMode, MachineLayer};
 pub use mask::LayerMask;
 pub use node::{
     blend::{BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput},
@@ -299,10 +299,20 @@ impl<T: EntityId> Machine<T> {
         self.final_pose.reset();
 
         for layer in self.layers.iter_mut() {
-            let weight = layer.weight();
-            let pose = layer.evaluate_pose(animations, &self.parameters, dt);
+            layer.evaluate_pose(animations, &self.parameters, dt);
 
-            self.final_pose.blend_with(pose, weight);
+            match layer.blend_mode() {
+                LayerBlendMode::Override => {
+                    self.final_pose.blend_with(layer.pose(), layer.weight());
+                }
+                LayerBlendMode::Additive => {
+                    self.final_pose.add_difference(
+                        layer.pose(),
+                        layer.reference_pose(),
+                        layer.weight(),
+                    );
+                }
+            }
         }
 
         &self.final_pose
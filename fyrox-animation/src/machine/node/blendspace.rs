@@ -1,4 +1,5 @@
-#![allow(missing_docs)] // TODO
+//! Blend space is a pose node, that blends multiple poses placed on a 2D plane. See [`BlendSpace`]
+//! docs for more info.
 
 use crate::{
     core::{
@@ -23,9 +24,12 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+/// A point of a blend space. It binds a pose source to a position on the plane of the blend space.
 #[derive(Debug, Visit, Clone, Reflect, PartialEq, Default)]
 pub struct BlendSpacePoint<T: EntityId> {
+    /// Position of the point on the plane of the blend space.
     pub position: Vector2<f32>,
+    /// A handle of a pose node, which pose will be used at the point.
     pub pose_source: Handle<PoseNode<T>>,
 }
 
@@ -35,6 +39,17 @@ impl<T: EntityId> TypeUuidProvider for BlendSpacePoint<T> {
     }
 }
 
+/// Blend space is a pose node, that blends multiple poses placed on a 2D plane. A typical example
+/// is a locomotion blend space, where X axis is the speed of a character and Y axis is movement
+/// direction, and a set of walk/run animations is placed on the plane (walk forward, run forward,
+/// strafe left, etc.).
+///
+/// The points of the blend space are triangulated (using Delaunay triangulation) each time they
+/// change. At runtime, the blend space takes a sampling point from a [`Parameter::SamplingPoint`]
+/// parameter with the name defined by [`Self::set_sampling_parameter`], finds a triangle that
+/// contains the point and blends three poses of the triangle using barycentric coordinates of the
+/// sampling point as weights. If the sampling point is outside of every triangle, then the closest
+/// edge (or the closest point) is used instead.
 #[derive(Debug, Visit, Clone, Reflect, PartialEq)]
 pub struct BlendSpace<T: EntityId> {
     base: BasePoseNode<T>,
@@ -203,6 +218,8 @@ impl<T: EntityId> AnimationPoseSource<T> for BlendSpace<T> {
     }
 }
 
+/// A guard, that provides mutable access to the points of a blend space and re-triangulates the
+/// points when dropped.
 pub struct PointsMut<'a, T: EntityId> {
     blend_space: &'a mut BlendSpace<T>,
 }
@@ -228,6 +245,8 @@ impl<'a, T: EntityId> Drop for PointsMut<'a, T> {
 }
 
 impl<T: EntityId> BlendSpace<T> {
+    /// Adds a new point to the blend space. Returns `true` if the points were successfully
+    /// triangulated, `false` - otherwise (there are less than three points or they're degenerate).
     pub fn add_point(&mut self, point: BlendSpacePoint<T>) -> bool {
         self.points.push(point);
         self.triangulate()
@@ -239,77 +258,100 @@ impl<T: EntityId> BlendSpace<T> {
         self.triangulate()
     }
 
+    /// Removes every point from the blend space.
     pub fn clear_points(&mut self) {
         self.points.clear();
         self.triangles.clear();
     }
 
+    /// Returns a reference to the points of the blend space.
     pub fn points(&self) -> &[BlendSpacePoint<T>] {
         &self.points
     }
 
+    /// Returns a guard, that provides mutable access to the points of the blend space. The points
+    /// will be re-triangulated when the guard is dropped.
     pub fn points_mut(&mut self) -> PointsMut<T> {
         PointsMut { blend_space: self }
     }
 
+    /// Returns a list of triangles, that were produced by the triangulation of the points.
     pub fn triangles(&self) -> &[TriangleDefinition] {
         &self.triangles
     }
 
+    /// Returns a list of pose sources of every point of the blend space.
     pub fn children(&self) -> Vec<Handle<PoseNode<T>>> {
         self.points.iter().map(|p| p.pose_source).collect()
     }
 
+    /// Sets the lower bounds of the blend space plane. Max values will be adjusted to be always
+    /// greater or equal than the min values.
     pub fn set_min_values(&mut self, min_values: Vector2<f32>) {
         self.min_values = min_values;
         self.max_values = self.max_values.sup(&self.min_values);
     }
 
+    /// Returns the lower bounds of the blend space plane.
     pub fn min_values(&self) -> Vector2<f32> {
         self.min_values
     }
 
+    /// Sets the upper bounds of the blend space plane. Min values will be adjusted to be always
+    /// less or equal than the max values.
     pub fn set_max_values(&mut self, max_values: Vector2<f32>) {
         self.max_values = max_values;
         self.min_values = self.min_values.inf(&self.max_values);
     }
 
+    /// Returns the upper bounds of the blend space plane.
     pub fn max_values(&self) -> Vector2<f32> {
         self.max_values
     }
 
+    /// Sets snapping step, that is used by [`Self::try_snap_points`].
     pub fn set_snap_step(&mut self, step: Vector2<f32>) {
         self.snap_step = step;
     }
 
+    /// Returns current snapping step.
     pub fn snap_step(&self) -> Vector2<f32> {
         self.snap_step
     }
 
+    /// Sets a name of a [`Parameter::SamplingPoint`] parameter, that will be used to sample the
+    /// blend space.
     pub fn set_sampling_parameter(&mut self, parameter: String) {
         self.sampling_parameter = parameter;
     }
 
+    /// Returns a name of the sampling parameter.
     pub fn sampling_parameter(&self) -> &str {
         &self.sampling_parameter
     }
 
+    /// Sets a name of X axis. It is used only for display purposes.
     pub fn set_x_axis_name(&mut self, name: String) -> String {
         std::mem::replace(&mut self.x_axis_name, name)
     }
 
+    /// Returns a name of X axis.
     pub fn x_axis_name(&self) -> &str {
         &self.x_axis_name
     }
 
+    /// Sets a name of Y axis. It is used only for display purposes.
     pub fn set_y_axis_name(&mut self, name: String) -> String {
         std::mem::replace(&mut self.y_axis_name, name)
     }
 
+    /// Returns a name of Y axis.
     pub fn y_axis_name(&self) -> &str {
         &self.y_axis_name
     }
 
+    /// Snaps every point to the grid defined by the snapping step and clamps them to the bounds of
+    /// the blend space.
     pub fn try_snap_points(&mut self) {
         for point in self.points.iter_mut() {
             let x = math::round_to_step(point.position.x, self.snap_step.x)
@@ -320,6 +362,9 @@ impl<T: EntityId> BlendSpace<T> {
         }
     }
 
+    /// Calculates weights of (up to) three points for the given sampling point. Each element of
+    /// the returned array is a pair of a point index and its weight. Returns `None` only if the
+    /// blend space is empty.
     pub fn fetch_weights(&self, sampling_point: Vector2<f32>) -> Option<[(usize, f32); 3]> {
         if self.points.is_empty() {
             return None;
@@ -395,7 +440,20 @@ impl<T: EntityId> BlendSpace<T> {
             }
         }
 
-        weights
+        // The sampling point is outside of every triangle and does not project on any edge (for
+        // example, it is beyond a corner of the blend space), use the closest point then.
+        weights.or_else(|| {
+            self.points
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    a.position
+                        .metric_distance(&sampling_point)
+                        .partial_cmp(&b.position.metric_distance(&sampling_point))
+                        .unwrap_or(Ordering::Equal)
+                })
+                .map(|(index, _)| [(index, 1.0), (index, 0.0), (index, 0.0)])
+        })
     }
 
     fn triangulate(&mut self) -> bool {
@@ -520,4 +578,30 @@ mod test {
             Some([(0, 0.0), (1, 1.0), (0, 0.0)])
         );
     }
+
+    #[test]
+    fn test_blend_space_sampling_outside() {
+        let mut blend_space = BlendSpace::<ErasedHandle>::default();
+
+        blend_space.set_points(vec![
+            BlendSpacePoint {
+                position: Vector2::new(0.0, 0.0),
+                pose_source: Default::default(),
+            },
+            BlendSpacePoint {
+                position: Vector2::new(1.0, 0.0),
+                pose_source: Default::default(),
+            },
+        ]);
+
+        assert_eq!(
+            blend_space.fetch_weights(Vector2::new(2.0, 0.0)),
+            Some([(1, 1.0), (1, 0.0), (1, 0.0)])
+        );
+
+        assert_eq!(
+            blend_space.fetch_weights(Vector2::new(-1.0, -1.0)),
+            Some([(0, 1.0), (0, 0.0), (0, 0.0)])
+        );
+    }
 }
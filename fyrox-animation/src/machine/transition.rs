@@ -66,6 +66,9 @@ macro_rules! define_two_args_node {
                         max_value: None,
                         step: None,
                         precision: None,
+                        multiline: false,
+                        options: &[],
+                        extensions: &[],
                         doc: "",
                     },
                     FieldInfo {
@@ -81,7 +84,10 @@ macro_rules! define_two_args_node {
                         min_value: None,
                         max_value: None,
                         step: None,
-                        precision: None,doc: "",
+                        precision: None,
+                        multiline: false,
+                        options: &[],
+                        extensions: &[],doc: "",
                     },
                 ])
             }
@@ -207,6 +213,9 @@ impl<T: EntityId> Reflect for NotNode<T> {
             max_value: None,
             step: None,
             precision: None,
+            multiline: false,
+            options: &[],
+            extensions: &[],
             doc: "",
         }])
     }
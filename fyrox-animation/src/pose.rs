@@ -67,6 +67,28 @@ impl<T: EntityId> AnimationPose<T> {
             .blend_with(&other.root_motion.clone().unwrap_or_default(), weight);
     }
 
+    /// Adds the difference between the `additive` pose and the `reference` pose to the current pose using a weight
+    /// coefficient. Unlike [`Self::blend_with`], node poses that are missing in the current pose are not added to it,
+    /// because there is nothing to add the difference to. Root motion of the current pose is left unchanged.
+    pub fn add_difference(
+        &mut self,
+        additive: &AnimationPose<T>,
+        reference: &AnimationPose<T>,
+        weight: f32,
+    ) {
+        for (handle, additive_pose) in additive.poses.iter() {
+            if let (Some(current_pose), Some(reference_pose)) =
+                (self.poses.get_mut(handle), reference.poses.get(handle))
+            {
+                current_pose.values.add_difference(
+                    &additive_pose.values,
+                    &reference_pose.values,
+                    weight,
+                );
+            }
+        }
+    }
+
     fn add_node_pose(&mut self, local_pose: NodePose<T>) {
         self.poses.insert(local_pose.node, local_pose);
     }
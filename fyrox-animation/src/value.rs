@@ -152,6 +152,25 @@ impl TrackValue {
         }
     }
 
+    /// Adds the difference between the `value` and the `reference` value to the current value, scaled by the given
+    /// weight. It is used for additive blending, when some value (for example, an aim offset) must be applied on top
+    /// of an other value. Rotations are combined in local space. Blending is possible only if the types are the same.
+    pub fn add_difference(&mut self, value: &Self, reference: &Self, weight: f32) {
+        match (self, value, reference) {
+            (Self::Real(a), Self::Real(v), Self::Real(r)) => *a += (*v - *r) * weight,
+            (Self::Vector2(a), Self::Vector2(v), Self::Vector2(r)) => *a += (v - r).scale(weight),
+            (Self::Vector3(a), Self::Vector3(v), Self::Vector3(r)) => *a += (v - r).scale(weight),
+            (Self::Vector4(a), Self::Vector4(v), Self::Vector4(r)) => *a += (v - r).scale(weight),
+            (Self::UnitQuaternion(a), Self::UnitQuaternion(v), Self::UnitQuaternion(r)) => {
+                let difference = r.inverse() * v;
+                *a *= UnitQuaternion::identity()
+                    .try_slerp(&difference, weight, f32::EPSILON)
+                    .unwrap_or(difference);
+            }
+            _ => (),
+        }
+    }
+
     /// Tries to perform a numeric type casting of the current value to some other and returns a boxed value, that can
     /// be used to set the value using reflection.
     pub fn numeric_type_cast(&self, value_type: ValueType) -> Option<Box<dyn Reflect>> {
@@ -304,6 +323,15 @@ impl BoundValue {
         assert_eq!(self.binding, other.binding);
         self.value.blend_with(&other.value, weight);
     }
+
+    /// Adds the difference between two other values to the current value using the given weight. See
+    /// [`TrackValue::add_difference`] for more info.
+    pub fn add_difference(&mut self, value: &Self, reference: &Self, weight: f32) {
+        assert_eq!(self.binding, value.binding);
+        assert_eq!(self.binding, reference.binding);
+        self.value
+            .add_difference(&value.value, &reference.value, weight);
+    }
 }
 
 /// A collection of values that are bounds to some properties.
@@ -323,4 +351,18 @@ impl BoundValueCollection {
             }
         }
     }
+
+    /// Tries to add the difference between respective (by binding) values of two other collections to each value of
+    /// the current collection. Values, that are missing in either of the collections, are left unchanged. See
+    /// [`TrackValue::add_difference`] docs for more info.
+    pub fn add_difference(&mut self, values: &Self, reference: &Self, weight: f32) {
+        for value in self.values.iter_mut() {
+            if let (Some(other_value), Some(reference_value)) = (
+                values.values.iter().find(|v| v.binding == value.binding),
+                reference.values.iter().find(|v| v.binding == value.binding),
+            ) {
+                value.add_difference(other_value, reference_value, weight);
+            }
+        }
+    }
 }
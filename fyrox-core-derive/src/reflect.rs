@@ -107,6 +107,12 @@ fn quote_field_prop(
 
     let description = field.description.clone().unwrap_or_default();
 
+    let multiline = field.multiline;
+
+    let options = split_list(&field.options);
+
+    let extensions = split_list(&field.extensions);
+
     quote! {
         FieldInfo {
             owner_type_id: std::any::TypeId::of::<Self>(),
@@ -122,11 +128,22 @@ fn quote_field_prop(
             step: #step,
             precision: #precision,
             description: #description,
+            multiline: #multiline,
+            options: &[#(#options),*],
+            extensions: &[#(#extensions),*],
             type_name: std::any::type_name::<#ty>()
         }
     }
 }
 
+fn split_list(list: &Option<String>) -> Vec<String> {
+    list.iter()
+        .flat_map(|list| list.split(','))
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
 fn impl_reflect_struct(ty_args: &args::TypeArgs, field_args: &args::Fields) -> TokenStream2 {
     // Property keys for `Reflect::{field, field_mut, set_field}` impls:
     let props = prop::props(ty_args).collect::<Vec<_>>();
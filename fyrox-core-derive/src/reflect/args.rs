@@ -210,6 +210,26 @@ pub struct FieldArgs {
     /// Description of the property.
     #[darling(default)]
     pub description: Option<String>,
+
+    /// `#[reflect(multiline)]`
+    ///
+    /// The field should be edited in a multiline editor. Works only for string fields!
+    #[darling(default)]
+    pub multiline: bool,
+
+    /// `#[reflect(options = "Easy, Normal, Hard")]`
+    ///
+    /// Comma-separated list of named options, the index of the selected option is the value of the
+    /// field. Works only for integer fields!
+    #[darling(default)]
+    pub options: Option<String>,
+
+    /// `#[reflect(extensions = "fbx, gltf")]`
+    ///
+    /// Comma-separated list of file extensions of assets that could be assigned to the field. Works
+    /// only for resource fields!
+    #[darling(default)]
+    pub extensions: Option<String>,
 }
 
 impl FieldArgs {
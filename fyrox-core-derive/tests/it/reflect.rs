@@ -324,6 +324,9 @@ fn default_prop() -> FieldInfo<'static, 'static> {
         step: None,
         precision: None,
         description: "",
+        multiline: false,
+        options: &[],
+        extensions: &[],
         type_name: "",
         doc: "",
     }
@@ -383,6 +386,12 @@ fn inspect_attributes() {
             description = "This is a property description."
         )]
         y: f32,
+        #[reflect(multiline)]
+        text: String,
+        #[reflect(options = "Easy, Normal, Hard")]
+        difficulty: u32,
+        #[reflect(extensions = "fbx, gltf")]
+        model: String,
     }
 
     let data = Data::default();
@@ -409,12 +418,39 @@ fn inspect_attributes() {
             step: Some(0.1),
             precision: Some(3),
             description: "This is a property description.",
+            multiline: false,
+            options: &[],
+            extensions: &[],
             type_name: std::any::type_name::<f32>(),
             doc: "",
         },
+        FieldInfo {
+            owner_type_id: TypeId::of::<Data>(),
+            name: "text",
+            display_name: "Text",
+            value: &data.text,
+            multiline: true,
+            ..default_prop()
+        },
+        FieldInfo {
+            owner_type_id: TypeId::of::<Data>(),
+            name: "difficulty",
+            display_name: "Difficulty",
+            value: &data.difficulty,
+            options: &["Easy", "Normal", "Hard"],
+            ..default_prop()
+        },
+        FieldInfo {
+            owner_type_id: TypeId::of::<Data>(),
+            name: "model",
+            display_name: "Model",
+            value: &data.model,
+            extensions: &["fbx", "gltf"],
+            ..default_prop()
+        },
     ];
 
-    data.fields_info(&mut |fields_info| assert_eq!(fields_info[0..2], expected));
+    data.fields_info(&mut |fields_info| assert_eq!(fields_info[0..5], expected));
 }
 
 #[test]
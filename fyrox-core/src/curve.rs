@@ -1,6 +1,7 @@
 use crate::{
     math::{cubicf, inf_sup_cubicf, lerpf, Rect},
     reflect::prelude::*,
+    uuid_provider,
     visitor::prelude::*,
 };
 use std::cmp::Ordering;
@@ -123,6 +124,8 @@ pub struct Curve {
     keys: Vec<CurveKey>,
 }
 
+uuid_provider!(Curve = "e9b2c4d1-6a3f-4c7e-9b8d-2f5a1e7c3d90");
+
 impl Default for Curve {
     fn default() -> Self {
         Self {
@@ -238,6 +241,47 @@ impl Curve {
         }
     }
 
+    /// Removes every key that can be restored by interpolation between its neighbours with an error
+    /// that does not exceed the given tolerance. First and last keys are always kept.
+    pub fn reduce_keys(&mut self, tolerance: f32) {
+        if self.keys.len() < 3 {
+            return;
+        }
+
+        let mut kept = Vec::with_capacity(self.keys.len());
+        kept.push(self.keys[0].clone());
+        let mut anchor = 0;
+        for i in 1..self.keys.len() - 1 {
+            let left = &self.keys[anchor];
+            let right = &self.keys[i + 1];
+            let span = right.location - left.location;
+            let is_redundant = span > 0.0
+                && self.keys[anchor + 1..=i].iter().all(|key| {
+                    let t = (key.location - left.location) / span;
+                    (left.interpolate(right, t) - key.value).abs() <= tolerance
+                });
+            if !is_redundant {
+                kept.push(self.keys[i].clone());
+                anchor = i;
+            }
+        }
+        kept.push(self.keys[self.keys.len() - 1].clone());
+
+        self.keys = kept;
+    }
+
+    /// Rounds values of every key to the nearest multiple of the given step. Does nothing if the
+    /// step is not positive.
+    pub fn quantize_values(&mut self, step: f32) {
+        if step <= 0.0 {
+            return;
+        }
+
+        for key in self.keys.iter_mut() {
+            key.value = (key.value / step).round() * step;
+        }
+    }
+
     pub fn bounds(&self) -> Rect<f32> {
         let mut max_y = -f32::MAX;
         let mut min_y = f32::MAX;
@@ -486,4 +530,34 @@ mod test {
         assert_eq!(curve.name(), "");
         assert_eq!(curve.keys(), vec![key, key2, key4, key3,]);
     }
+
+    #[test]
+    fn test_curve_reduce_keys() {
+        let mut curve = Curve::default();
+        for i in 0..=10 {
+            let location = i as f32 / 10.0;
+            // A linear part followed by a constant part.
+            let value = location.min(0.5);
+            curve.add_key(CurveKey::new(location, value, CurveKeyKind::Linear));
+        }
+
+        curve.reduce_keys(0.0001);
+
+        let locations = curve.keys().iter().map(|k| k.location).collect::<Vec<_>>();
+        assert_eq!(locations, vec![0.0, 0.5, 1.0]);
+        assert!((curve.value_at(0.25) - 0.25).abs() < 0.0001);
+        assert!((curve.value_at(0.75) - 0.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_curve_quantize_values() {
+        let mut curve = Curve::default();
+        curve.add_key(CurveKey::new(0.0, 0.26, CurveKeyKind::Linear));
+        curve.add_key(CurveKey::new(1.0, -0.74, CurveKeyKind::Linear));
+
+        curve.quantize_values(0.5);
+
+        assert_eq!(curve.keys()[0].value, 0.5);
+        assert_eq!(curve.keys()[1].value, -0.5);
+    }
 }
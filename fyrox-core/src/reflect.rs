@@ -90,6 +90,17 @@ pub struct FieldInfo<'a, 'b> {
 
     /// Maximum amount of decimal places for a numeric property.
     pub precision: Option<usize>,
+
+    /// A property should be edited in a multiline editor. Works only with string properties!
+    pub multiline: bool,
+
+    /// A list of named options, the index of the selected option is the value of the property. Works
+    /// only with integer properties!
+    pub options: &'b [&'b str],
+
+    /// A list of file extensions (without dots) of assets that could be assigned to the property. Empty
+    /// list means that any asset of a suitable type could be assigned. Works only with resource properties!
+    pub extensions: &'b [&'b str],
 }
 
 impl<'a, 'b> FieldInfo<'a, 'b> {
@@ -119,6 +130,9 @@ impl<'a, 'b> fmt::Debug for FieldInfo<'a, 'b> {
             .field("step", &self.step)
             .field("precision", &self.precision)
             .field("description", &self.description)
+            .field("multiline", &self.multiline)
+            .field("options", &self.options)
+            .field("extensions", &self.extensions)
             .finish()
     }
 }
@@ -138,6 +152,9 @@ impl<'a, 'b> PartialEq<Self> for FieldInfo<'a, 'b> {
             && self.step == other.step
             && self.precision == other.precision
             && self.description == other.description
+            && self.multiline == other.multiline
+            && self.options == other.options
+            && self.extensions == other.extensions
     }
 }
 
@@ -153,6 +170,11 @@ impl<'a, 'b> PartialEq<Self> for FieldInfo<'a, 'b> {
 /// - `#[reflect(deref)]`: Delegate the field access with deref
 /// - `#[reflect(field = <method call>)]`
 /// - `#[reflect(field_mut = <method call>)]`
+/// - `#[reflect(min_value = 0.0, max_value = 1.0, step = 0.1, precision = 2)]`: Limits of numeric fields
+/// - `#[reflect(description = "..")]`: Description of the field, shown as a tooltip in the Inspector
+/// - `#[reflect(multiline)]`: Edit a string field in a multiline editor
+/// - `#[reflect(options = "Easy, Normal, Hard")]`: Edit an integer field as a list of named options
+/// - `#[reflect(extensions = "fbx, gltf")]`: Accept only assets with the given extensions
 ///
 /// # Additional Trait Bounds
 ///
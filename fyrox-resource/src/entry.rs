@@ -9,6 +9,38 @@ use std::ops::{Deref, DerefMut};
 /// Lifetime of orphaned resource in seconds (with only one strong ref which is resource manager itself)
 pub const DEFAULT_RESOURCE_LIFETIME: f32 = 60.0;
 
+/// Defines when the resource manager unloads resources, that are not used by anyone except the
+/// manager itself. Pinned resources are never unloaded, no matter what policy is used.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UnloadPolicy {
+    /// Unused resources are unloaded when they weren't used for the given amount of time (in
+    /// seconds). This is the default policy.
+    Timed {
+        /// Lifetime of an unused resource in seconds.
+        lifetime: f32,
+    },
+    /// Unused resources are unloaded on the next update of the resource manager.
+    Immediate,
+    /// Unused resources are kept in memory until the total memory usage of resident resources
+    /// exceeds the given budget (in bytes). When it happens, the least recently used resources are
+    /// unloaded first.
+    Lru {
+        /// Memory budget in bytes.
+        memory_budget: usize,
+    },
+    /// Unused resources are unloaded only on explicit request. See
+    /// [`crate::manager::ResourceManagerState::destroy_unused_resources`].
+    Manual,
+}
+
+impl Default for UnloadPolicy {
+    fn default() -> Self {
+        Self::Timed {
+            lifetime: DEFAULT_RESOURCE_LIFETIME,
+        }
+    }
+}
+
 /// Resource container with fixed TTL (time-to-live). Resource will be removed
 /// (and unloaded) if there were no other strong references to it in given time
 /// span.
@@ -17,6 +49,8 @@ pub struct TimedEntry<T> {
     pub value: T,
     /// Time to live in seconds.
     pub time_to_live: f32,
+    /// Pinned entries are never removed, even if they're not used by anyone.
+    pub pinned: bool,
 }
 
 impl<T> Deref for TimedEntry<T> {
@@ -41,6 +75,7 @@ where
         Self {
             value: Default::default(),
             time_to_live: DEFAULT_RESOURCE_LIFETIME,
+            pinned: false,
         }
     }
 }
@@ -53,6 +88,7 @@ where
         Self {
             value: self.value.clone(),
             time_to_live: self.time_to_live,
+            pinned: self.pinned,
         }
     }
 }
@@ -67,6 +103,7 @@ mod test {
 
         assert_eq!(t.value, 0);
         assert_eq!(t.time_to_live, DEFAULT_RESOURCE_LIFETIME);
+        assert!(!t.pinned);
     }
 
     #[test]
@@ -94,10 +131,12 @@ mod test {
         let t = TimedEntry {
             value: 42,
             time_to_live: 15.0,
+            pinned: true,
         };
         let t2 = t.clone();
 
         assert_eq!(t.value, t2.value);
         assert_eq!(t.time_to_live, t2.time_to_live);
+        assert_eq!(t.pinned, t2.pinned);
     }
 }
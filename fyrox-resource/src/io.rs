@@ -83,6 +83,17 @@ pub trait ResourceIo: Send + Sync + 'static {
         })
     }
 
+    /// Returns the size (in bytes) of the file at the given path, if it is known. It is used only
+    /// for progress reporting, so it is fine to return [`None`] if the size cannot be obtained cheaply.
+    ///
+    /// Default implementation always returns [`None`]
+    fn file_size<'a>(
+        &'a self,
+        #[allow(unused)] path: &'a Path,
+    ) -> ResourceIoFuture<'a, Option<u64>> {
+        Box::pin(ready(None))
+    }
+
     /// Used to check whether a path exists
     fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool>;
 
@@ -196,6 +207,11 @@ impl ResourceIo for FsResourceIo {
         })
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn file_size<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<u64>> {
+        Box::pin(async move { std::fs::metadata(path).ok().map(|metadata| metadata.len()) })
+    }
+
     fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
         Box::pin(fyrox_core::io::exists(path))
     }
@@ -41,8 +41,10 @@ pub mod io;
 pub mod loader;
 pub mod manager;
 pub mod options;
+pub mod progress;
 pub mod state;
 pub mod untyped;
+pub mod vfs;
 
 /// Type UUID of texture resource. It is defined here to load old versions of resources.
 pub const TEXTURE_RESOURCE_UUID: Uuid = uuid!("02c23a44-55fa-411a-bc39-eb7a5eadf15c");
@@ -78,6 +80,14 @@ pub trait ResourceData: 'static + Debug + Visit + Send + Reflect {
     /// resource type supports saving, for example there might be temporary resource type that is
     /// used only at runtime which does not need saving at all.
     fn can_be_saved(&self) -> bool;
+
+    /// Returns approximate amount of memory (in bytes) used by the resource data. It is used by the
+    /// resource manager to decide which resources should be unloaded when a memory budget is set.
+    /// Default implementation returns the size of the data type itself, resources that hold heap
+    /// data (pixels, samples, etc.) should override it.
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
 }
 
 /// Extension trait for a resource data of a particular type, which adds additional functionality,
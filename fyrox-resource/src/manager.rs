@@ -15,17 +15,22 @@ use crate::{
         watcher::FileSystemWatcher,
         TypeUuidProvider,
     },
-    entry::{TimedEntry, DEFAULT_RESOURCE_LIFETIME},
+    entry::{TimedEntry, UnloadPolicy, DEFAULT_RESOURCE_LIFETIME},
     event::{ResourceEvent, ResourceEventBroadcaster},
     io::{FsResourceIo, ResourceIo},
     loader::{ResourceLoader, ResourceLoadersContainer},
     options::OPTIONS_EXTENSION,
+    progress::{
+        progress_from_state, LoadingBatch, LoadingProgress, LoadingStage, ProgressResource,
+        ProgressResourceIo,
+    },
     state::{LoadError, ResourceState},
     Resource, ResourceData, TypedResourceData, UntypedResource,
 };
 use fxhash::{FxHashMap, FxHashSet};
 use rayon::prelude::*;
 use std::{
+    ffi::OsStr,
     fmt::{Debug, Display, Formatter},
     marker::PhantomData,
     path::{Path, PathBuf},
@@ -53,6 +58,43 @@ impl ResourceWaitContext {
     }
 }
 
+/// A reason why a resource is kept in memory.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ResidencyReason {
+    /// The resource is pinned and won't be unloaded until it is unpinned.
+    Pinned,
+    /// The resource is used by someone else except the resource manager.
+    Used {
+        /// Amount of users of the resource (excluding the resource manager).
+        users: usize,
+    },
+    /// The resource is not used, but it is kept in memory by the current unload policy.
+    Cached {
+        /// Remaining time (in seconds) before the resource will be unloaded. Makes sense only for
+        /// [`UnloadPolicy::Timed`] policy.
+        time_to_live: f32,
+    },
+}
+
+/// Information about a resource, that is currently resident in the resource manager.
+#[derive(Clone, Debug)]
+pub struct ResidentResourceInfo {
+    /// The resource itself.
+    pub resource: UntypedResource,
+    /// Approximate amount of memory (in bytes) used by the resource. See
+    /// [`ResourceData::memory_usage`] for more info.
+    pub memory_usage: usize,
+    /// The reason why the resource is kept in memory.
+    pub reason: ResidencyReason,
+}
+
+fn resource_memory_usage(resource: &UntypedResource) -> usize {
+    match resource.0.lock().state {
+        ResourceState::Ok(ref data) => data.memory_usage(),
+        _ => 0,
+    }
+}
+
 /// See module docs.
 pub struct ResourceManagerState {
     /// A set of resource loaders. Use this field to register your own resource loader.
@@ -69,8 +111,16 @@ pub struct ResourceManagerState {
     resources: Vec<TimedEntry<UntypedResource>>,
     task_pool: Arc<TaskPool>,
     watcher: Option<FileSystemWatcher>,
+    pending_reloads: FxHashMap<PathBuf, f32>,
+    loading: FxHashMap<PathBuf, LoadingProgress>,
+    unload_policy: UnloadPolicy,
 }
 
+/// Amount of time (in seconds) the manager waits after the last modification of a file before
+/// reloading a respective resource. Many programs write files in a few steps, so reloading a
+/// resource on the first modification event could read partially written data.
+pub const HOT_RELOAD_DELAY: f32 = 0.25;
+
 /// See module docs.
 #[derive(Clone)]
 pub struct ResourceManager {
@@ -189,6 +239,47 @@ impl ResourceManager {
         }
     }
 
+    /// The same as [`Self::request`], but also returns loading progress of the resource. The progress
+    /// reports the amount of bytes read so far, the total amount of bytes and current loading stage.
+    /// It is useful for loading screens.
+    ///
+    /// If the resource was already requested, the progress of the existing loading task is returned.
+    ///
+    /// ## Panic
+    ///
+    /// This method will panic, if type UUID of `T` does not match the actual type UUID of the resource.
+    pub fn request_with_progress<T>(&self, path: impl AsRef<Path>) -> ProgressResource<T>
+    where
+        T: TypedResourceData,
+    {
+        let (untyped, progress) = self.state().request_with_progress(path);
+        let actual_type_uuid = untyped.type_uuid();
+        assert_eq!(actual_type_uuid, <T as TypeUuidProvider>::type_uuid());
+        ProgressResource::new(
+            Resource {
+                untyped,
+                phantom: PhantomData::<T>,
+            },
+            progress,
+        )
+    }
+
+    /// Requests a set of resources at once and returns a batch, that can be used to track total
+    /// loading progress of every resource in the set. Resources in the batch could be of any type.
+    pub fn request_batch<I, P>(&self, paths: I) -> LoadingBatch
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut state = self.state();
+        let mut batch = LoadingBatch::default();
+        for path in paths {
+            let (resource, progress) = state.request_with_progress(path);
+            batch.push(resource, progress);
+        }
+        batch
+    }
+
     /// Same as [`Self::request`], but returns untyped resource.
     pub fn request_untyped<P>(&self, path: P) -> UntypedResource
     where
@@ -351,12 +442,94 @@ impl ResourceManagerState {
             event_broadcaster: Default::default(),
             constructors_container: Default::default(),
             watcher: None,
+            pending_reloads: Default::default(),
+            loading: Default::default(),
+            unload_policy: Default::default(),
             built_in_resources: Default::default(),
             // Use the file system resource io by default
             resource_io: Arc::new(FsResourceIo),
         }
     }
 
+    /// Sets new unload policy, that defines when unused resources are unloaded. See [`UnloadPolicy`]
+    /// docs for more info.
+    pub fn set_unload_policy(&mut self, policy: UnloadPolicy) {
+        self.unload_policy = policy;
+    }
+
+    /// Returns current unload policy.
+    pub fn unload_policy(&self) -> UnloadPolicy {
+        self.unload_policy
+    }
+
+    fn resource_lifetime(&self) -> f32 {
+        match self.unload_policy {
+            UnloadPolicy::Timed { lifetime } => lifetime,
+            _ => DEFAULT_RESOURCE_LIFETIME,
+        }
+    }
+
+    /// Pins the resource, so it won't be unloaded even if no one uses it. Returns `false` if the
+    /// resource is not registered in the manager.
+    pub fn pin(&mut self, resource: &UntypedResource) -> bool {
+        self.set_pinned(resource, true)
+    }
+
+    /// Unpins the resource, so it could be unloaded according to the current unload policy. Returns
+    /// `false` if the resource is not registered in the manager.
+    pub fn unpin(&mut self, resource: &UntypedResource) -> bool {
+        self.set_pinned(resource, false)
+    }
+
+    fn set_pinned(&mut self, resource: &UntypedResource, pinned: bool) -> bool {
+        if let Some(entry) = self.resources.iter_mut().find(|e| &e.value == resource) {
+            entry.pinned = pinned;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the resource is pinned.
+    pub fn is_pinned(&self, resource: &UntypedResource) -> bool {
+        self.resources
+            .iter()
+            .any(|e| e.pinned && &e.value == resource)
+    }
+
+    /// Returns approximate amount of memory (in bytes) used by every resource in the manager.
+    pub fn memory_usage(&self) -> usize {
+        self.resources
+            .iter()
+            .map(|e| resource_memory_usage(&e.value))
+            .sum()
+    }
+
+    /// Returns a list of resources in the manager together with the reason why they're still
+    /// resident. It could be used to find out what is taking memory.
+    pub fn resident_resources(&self) -> Vec<ResidentResourceInfo> {
+        self.resources
+            .iter()
+            .map(|entry| {
+                let users = entry.value.use_count().saturating_sub(1);
+                let reason = if entry.pinned {
+                    ResidencyReason::Pinned
+                } else if users > 0 {
+                    ResidencyReason::Used { users }
+                } else {
+                    ResidencyReason::Cached {
+                        time_to_live: entry.time_to_live,
+                    }
+                };
+                ResidentResourceInfo {
+                    resource: entry.value.clone(),
+                    memory_usage: resource_memory_usage(&entry.value),
+                    reason,
+                }
+            })
+            .collect()
+    }
+
     /// Set the IO source that the resource manager should use when
     /// loading assets
     pub fn set_resource_io(&mut self, resource_io: Arc<dyn ResourceIo>) {
@@ -397,22 +570,29 @@ impl ResourceManagerState {
     /// Normally, this is called from `Engine::update()`.
     /// You should only call this manually if you don't use that method.
     pub fn update(&mut self, dt: f32) {
+        // Progress of finished tasks is still available through resource state.
+        self.loading
+            .retain(|_, progress| !progress.stage().is_done());
+
+        let policy = self.unload_policy;
+        let lifetime = self.resource_lifetime();
+        let event_broadcaster = &self.event_broadcaster;
         self.resources.retain_mut(|resource| {
             // One usage means that the resource has single owner, and that owner
             // is this container. Such resources have limited life time, if the time
             // runs out before it gets shared again, the resource will be deleted.
             if resource.value.use_count() <= 1 {
                 resource.time_to_live -= dt;
-                if resource.time_to_live <= 0.0 {
-                    if let Some(path) = resource.0.lock().kind.path_owned() {
-                        Log::info(format!(
-                            "Resource {} destroyed because it is not used anymore!",
-                            path.display()
-                        ));
-
-                        self.event_broadcaster
-                            .broadcast(ResourceEvent::Removed(path));
-                    }
+
+                let expired = match policy {
+                    UnloadPolicy::Timed { .. } => resource.time_to_live <= 0.0,
+                    UnloadPolicy::Immediate => true,
+                    // Unloaded separately (if needed), or on explicit request.
+                    UnloadPolicy::Lru { .. } | UnloadPolicy::Manual => false,
+                };
+
+                if expired && !resource.pinned {
+                    Self::on_unloaded(event_broadcaster, &resource.value);
 
                     false
                 } else {
@@ -421,31 +601,64 @@ impl ResourceManagerState {
                 }
             } else {
                 // Make sure to reset timer if a resource is used by more than one owner.
-                resource.time_to_live = DEFAULT_RESOURCE_LIFETIME;
+                resource.time_to_live = lifetime;
 
                 // Keep resource alive while it has more than one owner.
                 true
             }
         });
 
+        if let UnloadPolicy::Lru { memory_budget } = policy {
+            self.unload_least_recently_used(memory_budget);
+        }
+
         if let Some(watcher) = self.watcher.as_ref() {
-            if let Some(evt) = watcher.try_get_event() {
-                if let notify::EventKind::Modify(_) = evt.kind {
+            while let Some(evt) = watcher.try_get_event() {
+                // Some programs save files by writing a temporary file first and then renaming
+                // it, so creation events must be taken into account too.
+                if let notify::EventKind::Modify(_) | notify::EventKind::Create(_) = evt.kind {
                     for path in evt.paths {
-                        if let Ok(relative_path) = make_relative_path(path) {
-                            if self.try_reload_resource_from_path(&relative_path) {
-                                Log::info(format!(
-                                        "File {} was changed, trying to reload a respective resource...",
-                                        relative_path.display()
-                                    ));
-
-                                break;
+                        if let Ok(mut relative_path) = make_relative_path(path) {
+                            // Changed import options of a resource means that the resource must
+                            // be re-imported.
+                            if relative_path.extension() == Some(OsStr::new(OPTIONS_EXTENSION)) {
+                                relative_path.set_extension("");
+                            }
+                            if self.find(&relative_path).is_some() {
+                                // Restart the timer on every modification of the same file.
+                                self.pending_reloads.insert(relative_path, HOT_RELOAD_DELAY);
                             }
                         }
                     }
                 }
             }
         }
+
+        let mut ready_to_reload = Vec::new();
+        self.pending_reloads.retain(|path, delay| {
+            *delay -= dt;
+            if *delay <= 0.0 {
+                ready_to_reload.push(path.clone());
+                false
+            } else {
+                true
+            }
+        });
+
+        for path in ready_to_reload {
+            if self.try_reload_resource_from_path(&path) {
+                Log::info(format!(
+                    "File {} was changed, trying to reload a respective resource...",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    /// Returns `true` if there are any changed files, that are waiting to be reloaded. See
+    /// [`HOT_RELOAD_DELAY`] for more info.
+    pub fn has_pending_reloads(&self) -> bool {
+        !self.pending_reloads.is_empty()
     }
 
     /// Adds a new resource in the container.
@@ -455,7 +668,8 @@ impl ResourceManagerState {
 
         self.resources.push(TimedEntry {
             value: resource,
-            time_to_live: DEFAULT_RESOURCE_LIFETIME,
+            time_to_live: self.resource_lifetime(),
+            pinned: false,
         });
     }
 
@@ -490,10 +704,68 @@ impl ResourceManagerState {
         self.resources.iter().map(|entry| &entry.value)
     }
 
-    /// Immediately destroys all resources in the manager that are not used anywhere else.
+    /// Immediately destroys all resources in the manager that are not used anywhere else. Pinned
+    /// resources are kept.
     pub fn destroy_unused_resources(&mut self) {
-        self.resources
-            .retain(|resource| resource.value.use_count() > 1);
+        let event_broadcaster = &self.event_broadcaster;
+        self.resources.retain(|resource| {
+            let keep = resource.pinned || resource.value.use_count() > 1;
+            if !keep {
+                Self::on_unloaded(event_broadcaster, &resource.value);
+            }
+            keep
+        });
+    }
+
+    fn on_unloaded(event_broadcaster: &ResourceEventBroadcaster, resource: &UntypedResource) {
+        if let Some(path) = resource.0.lock().kind.path_owned() {
+            Log::info(format!(
+                "Resource {} destroyed because it is not used anymore!",
+                path.display()
+            ));
+
+            event_broadcaster.broadcast(ResourceEvent::Removed(path));
+        }
+    }
+
+    fn unload_least_recently_used(&mut self, memory_budget: usize) {
+        let mut memory_usage = self.memory_usage();
+        if memory_usage <= memory_budget {
+            return;
+        }
+
+        // Time to live decreases while a resource is not used, so the smallest one belongs to
+        // the least recently used resource.
+        let mut candidates = self
+            .resources
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.pinned && e.value.use_count() <= 1)
+            .map(|(i, e)| (i, e.time_to_live, resource_memory_usage(&e.value)))
+            // Unloading of pending or failed resources won't free any memory.
+            .filter(|(_, _, size)| *size > 0)
+            .collect::<Vec<_>>();
+        candidates.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut to_remove = FxHashSet::default();
+        for (index, _, size) in candidates {
+            if memory_usage <= memory_budget {
+                break;
+            }
+            memory_usage = memory_usage.saturating_sub(size);
+            to_remove.insert(index);
+        }
+
+        let mut index = 0;
+        let event_broadcaster = &self.event_broadcaster;
+        self.resources.retain(|resource| {
+            let keep = !to_remove.contains(&index);
+            if !keep {
+                Self::on_unloaded(event_broadcaster, &resource.value);
+            }
+            index += 1;
+            keep
+        });
     }
 
     /// Returns total amount of resources that still loading.
@@ -540,7 +812,9 @@ impl ResourceManagerState {
 
                 if let Some(loader) = self.find_loader(path.as_ref()) {
                     let resource = UntypedResource::new_pending(kind, loader.data_type_uuid());
-                    self.spawn_loading_task(path, resource.clone(), loader, false);
+                    let progress =
+                        self.spawn_loading_task(path.clone(), resource.clone(), loader, false);
+                    self.loading.insert(path, progress);
                     self.push(resource.clone());
                     resource
                 } else {
@@ -552,6 +826,21 @@ impl ResourceManagerState {
         }
     }
 
+    /// The same as [`Self::request`], but also returns loading progress of the resource.
+    pub fn request_with_progress<P>(&mut self, path: P) -> (UntypedResource, LoadingProgress)
+    where
+        P: AsRef<Path>,
+    {
+        let resource = self.request(path.as_ref());
+        let progress = resource
+            .kind()
+            .path()
+            .and_then(|path| self.loading.get(path))
+            .cloned()
+            .unwrap_or_else(|| progress_from_state(&resource));
+        (resource, progress)
+    }
+
     fn find_loader(&self, path: &Path) -> Option<&dyn ResourceLoader> {
         path.extension().and_then(|extension| {
             self.loaders
@@ -566,9 +855,15 @@ impl ResourceManagerState {
         resource: UntypedResource,
         loader: &dyn ResourceLoader,
         reload: bool,
-    ) {
+    ) -> LoadingProgress {
         let event_broadcaster = self.event_broadcaster.clone();
-        let loader_future = loader.load(path.clone(), self.resource_io.clone());
+        let progress = LoadingProgress::new();
+        let io = Arc::new(ProgressResourceIo {
+            inner: self.resource_io.clone(),
+            progress: progress.clone(),
+        });
+        let loader_future = loader.load(path.clone(), io);
+        let task_progress = progress.clone();
         self.task_pool.spawn_task(async move {
             match loader_future.await {
                 Ok(data) => {
@@ -584,6 +879,7 @@ impl ResourceManagerState {
                         let mut mutex_guard = resource.0.lock();
                         assert_eq!(mutex_guard.type_uuid, data.type_uuid());
                         assert!(mutex_guard.kind.is_external());
+                        task_progress.set_stage(LoadingStage::Finished);
                         mutex_guard.state.commit(ResourceState::Ok(data));
                     }
 
@@ -596,10 +892,13 @@ impl ResourceManagerState {
                         error
                     ));
 
+                    task_progress.set_stage(LoadingStage::Failed);
+
                     resource.commit_error(error);
                 }
             }
         });
+        progress
     }
 
     /// Reloads a single resource.
@@ -612,7 +911,8 @@ impl ResourceManagerState {
                     header.state.switch_to_pending_state();
                     drop(header);
 
-                    self.spawn_loading_task(path, resource, loader, true);
+                    let progress = self.spawn_loading_task(path.clone(), resource, loader, true);
+                    self.loading.insert(path, progress);
                 } else {
                     let msg = format!(
                         "There's no resource loader for {} resource!",
@@ -722,6 +1022,10 @@ mod test {
         fn can_be_saved(&self) -> bool {
             false
         }
+
+        fn memory_usage(&self) -> usize {
+            100
+        }
     }
 
     impl ResourceLoader for Stub {
@@ -767,9 +1071,26 @@ mod test {
         assert!(state.built_in_resources.is_empty());
         assert!(state.constructors_container.is_empty());
         assert!(state.watcher.is_none());
+        assert!(!state.has_pending_reloads());
         assert!(state.is_empty());
     }
 
+    #[test]
+    fn resource_manager_state_pending_reloads() {
+        let mut state = new_resource_manager();
+
+        state
+            .pending_reloads
+            .insert(PathBuf::from("test.txt"), HOT_RELOAD_DELAY);
+        assert!(state.has_pending_reloads());
+
+        state.update(HOT_RELOAD_DELAY * 0.5);
+        assert!(state.has_pending_reloads());
+
+        state.update(HOT_RELOAD_DELAY);
+        assert!(!state.has_pending_reloads());
+    }
+
     #[test]
     fn resource_manager_state_set_watcher() {
         let mut state = new_resource_manager();
@@ -827,6 +1148,60 @@ mod test {
         assert_eq!(state.loading_progress(), 33);
     }
 
+    #[test]
+    fn resource_manager_state_unload_policy() {
+        let mut state = new_resource_manager();
+        assert_eq!(state.unload_policy(), UnloadPolicy::default());
+
+        state.set_unload_policy(UnloadPolicy::Immediate);
+        let pinned = UntypedResource::new_ok(Default::default(), Stub {});
+        state.push(pinned.clone());
+        state.push(UntypedResource::new_ok(Default::default(), Stub {}));
+        assert!(state.pin(&pinned));
+        assert!(state.is_pinned(&pinned));
+        drop(pinned);
+
+        state.update(0.0);
+        assert_eq!(state.len(), 1);
+        assert_eq!(
+            state.resident_resources()[0].reason,
+            ResidencyReason::Pinned
+        );
+
+        state.set_unload_policy(UnloadPolicy::Manual);
+        let pinned = state.resources()[0].clone();
+        assert!(state.unpin(&pinned));
+        drop(pinned);
+        state.update(100.0);
+        assert_eq!(state.len(), 1);
+        state.destroy_unused_resources();
+        assert!(state.is_empty());
+    }
+
+    #[test]
+    fn resource_manager_state_lru_unload_policy() {
+        let mut state = new_resource_manager();
+        state.set_unload_policy(UnloadPolicy::Lru { memory_budget: 250 });
+
+        let used = UntypedResource::new_ok(Default::default(), Stub {});
+        state.push(used.clone());
+        for _ in 0..3 {
+            state.push(UntypedResource::new_ok(Default::default(), Stub {}));
+            state.update(1.0);
+        }
+        assert_eq!(state.memory_usage(), 200);
+        assert_eq!(state.len(), 2);
+
+        // The most recently added unused resource must survive.
+        let resident = state.resident_resources();
+        assert_eq!(resident[0].reason, ResidencyReason::Used { users: 1 });
+        assert_eq!(resident[1].memory_usage, 100);
+        assert!(matches!(
+            resident[1].reason,
+            ResidencyReason::Cached { time_to_live } if time_to_live == DEFAULT_RESOURCE_LIFETIME - 1.0
+        ));
+    }
+
     #[test]
     fn resource_manager_state_find() {
         let mut state = new_resource_manager();
@@ -976,6 +1351,37 @@ mod test {
         assert_eq!(res, resource);
     }
 
+    #[test]
+    fn resource_manager_request_with_progress() {
+        let manager = ResourceManager::new(Arc::new(Default::default()));
+        let resource = UntypedResource::new_ok(Default::default(), Stub {});
+        let res = manager.register(resource.clone(), PathBuf::from("foo.txt"), |_, __| true);
+        assert!(res.is_ok());
+
+        let res = manager.request_with_progress::<Stub>(Path::new("foo.txt"));
+        assert_eq!(res.untyped, resource);
+        assert_eq!(res.progress().stage(), LoadingStage::Finished);
+        assert_eq!(res.progress().fraction(), 1.0);
+    }
+
+    #[test]
+    fn resource_manager_request_batch() {
+        let manager = ResourceManager::new(Arc::new(Default::default()));
+        manager.state().loaders.set(Stub {});
+
+        let batch = manager.request_batch(["foo.txt", "bar.txt", "foo.txt"]);
+        assert_eq!(batch.len(), 3);
+        fyrox_core::futures::executor::block_on(batch.wait());
+
+        assert!(batch.is_all_loaded());
+        assert_eq!(batch.failed_count(), 0);
+        assert_eq!(batch.fraction(), 1.0);
+
+        manager.state().update(0.0);
+        let res = manager.request_with_progress::<Stub>("bar.txt");
+        assert_eq!(res.progress().stage(), LoadingStage::Finished);
+    }
+
     #[test]
     fn display_for_resource_registration_error() {
         assert_eq!(
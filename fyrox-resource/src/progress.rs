@@ -0,0 +1,406 @@
+//! Resource loading progress reporting. It is mostly useful for loading screens, that need to
+//! display accurate progress bars. See [`crate::manager::ResourceManager::request_with_progress`]
+//! and [`crate::manager::ResourceManager::request_batch`] for more info.
+
+use crate::{
+    core::{futures::future::join_all, io::FileLoadError, parking_lot::Mutex},
+    io::{FileReader, PathIter, ResourceIo, ResourceIoFuture},
+    state::ResourceState,
+    Resource, TypedResourceData, UntypedResource,
+};
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// A stage of resource loading.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum LoadingStage {
+    /// Resource is waiting in the queue to be loaded.
+    #[default]
+    Queued,
+    /// Raw bytes of the resource are being read from the storage.
+    Io,
+    /// Raw bytes were read and a resource loader is decoding them.
+    Decode,
+    /// Resource data was decoded and is being uploaded to GPU. The resource manager knows nothing
+    /// about GPU, so this stage is set by the code that does the uploading (if any).
+    GpuUpload,
+    /// Resource was loaded successfully.
+    Finished,
+    /// Resource was failed to load.
+    Failed,
+}
+
+impl LoadingStage {
+    /// Returns `true` if the stage is either [`Self::Finished`] or [`Self::Failed`].
+    pub fn is_done(self) -> bool {
+        matches!(self, Self::Finished | Self::Failed)
+    }
+}
+
+#[derive(Default, Debug)]
+struct ProgressState {
+    stage: LoadingStage,
+    bytes_loaded: u64,
+    bytes_total: u64,
+}
+
+/// A shared progress of a single resource loading. It is cheap to clone, all the clones share the
+/// same state.
+#[derive(Clone, Default, Debug)]
+pub struct LoadingProgress {
+    state: Arc<Mutex<ProgressState>>,
+}
+
+impl LoadingProgress {
+    /// Creates a new progress in [`LoadingStage::Queued`] stage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new progress in the given stage.
+    pub fn with_stage(stage: LoadingStage) -> Self {
+        let progress = Self::default();
+        progress.set_stage(stage);
+        progress
+    }
+
+    /// Returns current loading stage.
+    pub fn stage(&self) -> LoadingStage {
+        self.state.lock().stage
+    }
+
+    /// Sets new loading stage.
+    pub fn set_stage(&self, stage: LoadingStage) {
+        self.state.lock().stage = stage;
+    }
+
+    /// Returns amount of bytes read from the storage so far.
+    pub fn bytes_loaded(&self) -> u64 {
+        self.state.lock().bytes_loaded
+    }
+
+    /// Returns total amount of bytes that is known to be read. The value could grow over time,
+    /// because some resources (for example - models) could load dependent files.
+    pub fn bytes_total(&self) -> u64 {
+        self.state.lock().bytes_total
+    }
+
+    /// Returns loading progress in `[0.0; 1.0]` range. The first half of the range is taken by the
+    /// I/O stage (proportionally to the amount of bytes read), the rest is taken by the decoding
+    /// and GPU upload stages.
+    pub fn fraction(&self) -> f32 {
+        let state = self.state.lock();
+        match state.stage {
+            LoadingStage::Queued => 0.0,
+            LoadingStage::Io => {
+                if state.bytes_total > 0 {
+                    0.5 * (state.bytes_loaded as f32 / state.bytes_total as f32).min(1.0)
+                } else {
+                    0.0
+                }
+            }
+            LoadingStage::Decode => 0.5,
+            LoadingStage::GpuUpload => 0.75,
+            LoadingStage::Finished | LoadingStage::Failed => 1.0,
+        }
+    }
+
+    fn add_total(&self, bytes: u64) {
+        self.state.lock().bytes_total += bytes;
+    }
+
+    fn add_loaded(&self, bytes: u64, expected: Option<u64>) {
+        let mut state = self.state.lock();
+        state.bytes_loaded += bytes;
+        // Size of the file could be unknown beforehand, or be different from the actual one.
+        if expected != Some(bytes) {
+            state.bytes_total = state.bytes_total - expected.unwrap_or_default() + bytes;
+        }
+    }
+}
+
+/// A resource with its loading progress. It dereferences to the resource itself.
+pub struct ProgressResource<T>
+where
+    T: TypedResourceData,
+{
+    resource: Resource<T>,
+    progress: LoadingProgress,
+}
+
+impl<T> ProgressResource<T>
+where
+    T: TypedResourceData,
+{
+    pub(crate) fn new(resource: Resource<T>, progress: LoadingProgress) -> Self {
+        Self { resource, progress }
+    }
+
+    /// Returns a reference to the resource.
+    pub fn resource(&self) -> &Resource<T> {
+        &self.resource
+    }
+
+    /// Returns a reference to the loading progress of the resource.
+    pub fn progress(&self) -> &LoadingProgress {
+        &self.progress
+    }
+
+    /// Splits the handle into the resource and its loading progress.
+    pub fn into_inner(self) -> (Resource<T>, LoadingProgress) {
+        (self.resource, self.progress)
+    }
+}
+
+impl<T> Deref for ProgressResource<T>
+where
+    T: TypedResourceData,
+{
+    type Target = Resource<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.resource
+    }
+}
+
+/// A set of resources, that are loading together. Use it to display total progress of loading
+/// of multiple resources at once.
+#[derive(Default)]
+pub struct LoadingBatch {
+    entries: Vec<(UntypedResource, LoadingProgress)>,
+}
+
+impl LoadingBatch {
+    pub(crate) fn push(&mut self, resource: UntypedResource, progress: LoadingProgress) {
+        self.entries.push((resource, progress));
+    }
+
+    /// Returns a list of resources in the batch.
+    pub fn resources(&self) -> impl Iterator<Item = &UntypedResource> {
+        self.entries.iter().map(|(resource, _)| resource)
+    }
+
+    /// Returns a list of resources in the batch together with their loading progress.
+    pub fn entries(&self) -> &[(UntypedResource, LoadingProgress)] {
+        &self.entries
+    }
+
+    /// Returns total amount of resources in the batch.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns amount of resources that were loaded (or failed to load).
+    pub fn done_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|(_, progress)| progress.stage().is_done())
+            .count()
+    }
+
+    /// Returns amount of resources that were failed to load.
+    pub fn failed_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|(_, progress)| progress.stage() == LoadingStage::Failed)
+            .count()
+    }
+
+    /// Returns `true` if every resource in the batch was loaded (or failed to load).
+    pub fn is_all_loaded(&self) -> bool {
+        self.done_count() == self.len()
+    }
+
+    /// Returns total amount of bytes read by every resource in the batch.
+    pub fn bytes_loaded(&self) -> u64 {
+        self.entries.iter().map(|(_, p)| p.bytes_loaded()).sum()
+    }
+
+    /// Returns total amount of bytes that is known to be read by every resource in the batch.
+    pub fn bytes_total(&self) -> u64 {
+        self.entries.iter().map(|(_, p)| p.bytes_total()).sum()
+    }
+
+    /// Returns total loading progress of the batch in `[0.0; 1.0]` range. Progress of each resource
+    /// is weighted by its size, so large resources affect the total progress more than small ones.
+    pub fn fraction(&self) -> f32 {
+        if self.entries.is_empty() {
+            return 1.0;
+        }
+
+        let mut weighted = 0.0;
+        let mut total_weight = 0.0;
+        for (_, progress) in self.entries.iter() {
+            let weight = progress.bytes_total().max(1) as f32;
+            weighted += progress.fraction() * weight;
+            total_weight += weight;
+        }
+        weighted / total_weight
+    }
+
+    /// Waits until every resource in the batch is loaded (or failed to load).
+    pub async fn wait(&self) {
+        join_all(self.entries.iter().map(|(resource, _)| resource.clone())).await;
+    }
+}
+
+/// Creates progress object from the current state of the resource. Used for resources that were
+/// already loaded (or are loading without progress tracking).
+pub(crate) fn progress_from_state(resource: &UntypedResource) -> LoadingProgress {
+    match resource.0.lock().state {
+        ResourceState::Pending { .. } => LoadingProgress::new(),
+        ResourceState::LoadError { .. } => LoadingProgress::with_stage(LoadingStage::Failed),
+        ResourceState::Ok(_) => LoadingProgress::with_stage(LoadingStage::Finished),
+    }
+}
+
+/// Resource I/O wrapper, that counts read bytes and reports them to the given progress.
+pub(crate) struct ProgressResourceIo {
+    pub(crate) inner: Arc<dyn ResourceIo>,
+    pub(crate) progress: LoadingProgress,
+}
+
+impl ProgressResourceIo {
+    async fn read_with_progress(&self, path: &Path) -> Result<Vec<u8>, FileLoadError> {
+        self.progress.set_stage(LoadingStage::Io);
+        let expected = self.inner.file_size(path).await;
+        if let Some(expected) = expected {
+            self.progress.add_total(expected);
+        }
+        let result = self.inner.load_file(path).await;
+        match result {
+            Ok(ref bytes) => self.progress.add_loaded(bytes.len() as u64, expected),
+            // Do not count failed reads.
+            Err(_) => self.progress.add_loaded(0, expected),
+        }
+        // Once the bytes are read, a loader starts decoding them. If it needs more files, the
+        // stage will be switched back to I/O.
+        self.progress.set_stage(LoadingStage::Decode);
+        result
+    }
+}
+
+impl ResourceIo for ProgressResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(self.read_with_progress(path))
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        self.inner.move_file(source, dest)
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        self.inner.canonicalize_path(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        self.inner.read_directory(path)
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        self.inner.walk_directory(path)
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        // Streamed reads cannot be tracked precisely, so the whole file is counted at once.
+        Box::pin(async move {
+            self.progress.set_stage(LoadingStage::Io);
+            let expected = self.inner.file_size(path).await;
+            let reader = self.inner.file_reader(path).await?;
+            if let Some(expected) = expected {
+                self.progress.add_total(expected);
+                self.progress.add_loaded(expected, Some(expected));
+            }
+            self.progress.set_stage(LoadingStage::Decode);
+            Ok(reader)
+        })
+    }
+
+    fn file_size<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<u64>> {
+        self.inner.file_size(path)
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.exists(path)
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_file(path)
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_dir(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::FsResourceIo;
+    use fyrox_core::futures::executor::block_on;
+
+    #[test]
+    fn test_progress_fraction() {
+        let progress = LoadingProgress::new();
+        assert_eq!(progress.fraction(), 0.0);
+        progress.set_stage(LoadingStage::Io);
+        // The first file is read, the second one is still reading.
+        progress.add_total(50);
+        progress.add_loaded(50, Some(50));
+        progress.add_total(150);
+        assert_eq!(progress.fraction(), 0.125);
+        progress.set_stage(LoadingStage::Decode);
+        assert_eq!(progress.fraction(), 0.5);
+        progress.set_stage(LoadingStage::Finished);
+        assert_eq!(progress.fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_progress_io_counts_bytes() {
+        let dir = std::env::temp_dir().join("fyrox_progress_io_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.bin");
+        std::fs::write(&path, vec![0u8; 1234]).unwrap();
+
+        let progress = LoadingProgress::new();
+        let io = ProgressResourceIo {
+            inner: Arc::new(FsResourceIo),
+            progress: progress.clone(),
+        };
+        let bytes = block_on(io.load_file(&path)).unwrap();
+        assert_eq!(bytes.len(), 1234);
+        assert_eq!(progress.stage(), LoadingStage::Decode);
+        assert_eq!(progress.bytes_loaded(), 1234);
+        assert_eq!(progress.bytes_total(), 1234);
+
+        assert!(block_on(io.load_file(&dir.join("missing.bin"))).is_err());
+        assert_eq!(progress.bytes_total(), 1234);
+    }
+}
@@ -0,0 +1,800 @@
+//! Virtual file system (VFS) allows to mount directories and pack files (archives) at specific
+//! virtual paths. It implements [`ResourceIo`], so the resource manager can load resources from it
+//! the same way as from the real file system. This allows to keep the same resource paths in
+//! development (when the resources are stored in a directory) and release (when the resources are
+//! stored in a single pack file).
+//!
+//! ```rust,no_run
+//! # use fyrox_resource::vfs::{PackFile, VirtualFileSystem};
+//! let mut vfs = VirtualFileSystem::new();
+//! // Resources from the pack are available at the root, so `data/model.rgs` will be loaded from
+//! // the pack file (if the pack contains it).
+//! vfs.mount_pack("", PackFile::open("data.pak").unwrap());
+//! // Loose files have priority over the files in the pack, because the directory is mounted last.
+//! vfs.mount_directory("", ".");
+//! ```
+//!
+//! Pack files could be created using [`PackBuilder`].
+
+use crate::io::{FileReader, PathIter, ResourceIo, ResourceIoFuture};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder};
+use fxhash::{FxHashMap, FxHashSet};
+use fyrox_core::{io::FileLoadError, parking_lot::Mutex};
+use std::{
+    fmt::{Debug, Formatter},
+    io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// Default extension of pack files.
+pub const PACK_EXTENSION: &str = "pak";
+
+const PACK_MAGIC: [u8; 4] = *b"FPAK";
+const PACK_VERSION: u32 = 1;
+/// Magic, version and offset of the table of contents.
+const PACK_HEADER_SIZE: u64 = 16;
+/// Path length, offset, size, uncompressed size and compression method of an entry.
+const PACK_ENTRY_MIN_SIZE: u64 = 29;
+const PACK_MAX_PATH_LEN: u32 = 4096;
+/// Upper limit of the size of a single entry, anything bigger is considered as corrupted data.
+const PACK_MAX_ENTRY_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+
+/// Converts the given path to the form that is used by the virtual file system: relative path
+/// without `.` and `..` components.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => normalized.push(name),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => (),
+        }
+    }
+    normalized
+}
+
+fn path_to_string(path: &Path) -> String {
+    // Always use forward slashes, so pack files are portable across platforms.
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn not_found(path: &Path) -> FileLoadError {
+    FileLoadError::Io(std::io::Error::new(
+        ErrorKind::NotFound,
+        format!(
+            "{} does not exist in the virtual file system",
+            path.display()
+        ),
+    ))
+}
+
+fn invalid_data(message: &str) -> FileLoadError {
+    FileLoadError::Io(std::io::Error::new(ErrorKind::InvalidData, message))
+}
+
+fn read_u8(reader: &mut dyn Read) -> Result<u8, FileLoadError> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn read_u32(reader: &mut dyn Read) -> Result<u32, FileLoadError> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut dyn Read) -> Result<u64, FileLoadError> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Compression method of a pack file entry.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum PackCompression {
+    /// The entry is stored as is. It is used for files that are already compressed (PNG, OGG,
+    /// etc.) or cannot be compressed efficiently.
+    Store = 0,
+    /// The entry is compressed using Deflate algorithm.
+    Deflate = 1,
+}
+
+impl PackCompression {
+    fn from_id(id: u8) -> Result<Self, FileLoadError> {
+        match id {
+            0 => Ok(Self::Store),
+            1 => Ok(Self::Deflate),
+            _ => Err(invalid_data("Unknown compression method of a pack entry!")),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PackEntry {
+    offset: u64,
+    size: u64,
+    uncompressed_size: u64,
+    compression: PackCompression,
+}
+
+/// Pack file is a single archive that contains multiple files. It is read lazily, only the table
+/// of contents is loaded when the pack is opened.
+///
+/// # Format
+///
+/// All numbers are little-endian.
+///
+/// - Header: `FPAK` magic, version (`u32`), offset of the table of contents (`u64`).
+/// - Data of the entries.
+/// - Table of contents: entry count (`u32`), then for each entry: path length (`u32`), UTF-8 path
+///   with forward slashes, offset (`u64`), size (`u64`), uncompressed size (`u64`), compression
+///   method (`u8`).
+pub struct PackFile {
+    entries: FxHashMap<PathBuf, PackEntry>,
+    directories: FxHashSet<PathBuf>,
+    reader: Mutex<Box<dyn FileReader>>,
+}
+
+impl Debug for PackFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PackFile with {} entries", self.entries.len())
+    }
+}
+
+impl PackFile {
+    /// Reads the table of contents of a pack file from the given reader. Offsets and sizes of the
+    /// entries are validated against the size of the pack, so a corrupted pack is rejected with
+    /// [`ErrorKind::InvalidData`] error instead of causing huge allocations on read.
+    pub fn new<R: FileReader>(mut reader: R) -> Result<Self, FileLoadError> {
+        let file_len = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != PACK_MAGIC {
+            return Err(invalid_data("Not a pack file!"));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != PACK_VERSION {
+            return Err(FileLoadError::Custom(format!(
+                "Unsupported pack file version {version}!"
+            )));
+        }
+        let toc_offset = read_u64(&mut reader)?;
+        if toc_offset < PACK_HEADER_SIZE || toc_offset > file_len {
+            return Err(invalid_data("Invalid offset of the table of contents!"));
+        }
+        reader.seek(SeekFrom::Start(toc_offset))?;
+
+        let count = read_u32(&mut reader)?;
+        if count as u64 * PACK_ENTRY_MIN_SIZE > file_len - toc_offset {
+            return Err(invalid_data("Invalid entry count of a pack file!"));
+        }
+        let mut entries = FxHashMap::default();
+        let mut directories = FxHashSet::default();
+        directories.insert(PathBuf::new());
+        for _ in 0..count {
+            let path_len = read_u32(&mut reader)?;
+            if path_len > PACK_MAX_PATH_LEN || path_len as u64 > file_len - toc_offset {
+                return Err(invalid_data("Invalid path length of a pack entry!"));
+            }
+            let mut path = vec![0; path_len as usize];
+            reader.read_exact(&mut path)?;
+            let path = PathBuf::from(
+                String::from_utf8(path).map_err(|_| invalid_data("Invalid pack entry path!"))?,
+            );
+            let entry = PackEntry {
+                offset: read_u64(&mut reader)?,
+                size: read_u64(&mut reader)?,
+                uncompressed_size: read_u64(&mut reader)?,
+                compression: PackCompression::from_id(read_u8(&mut reader)?)?,
+            };
+            let is_valid = entry.offset >= PACK_HEADER_SIZE
+                && entry
+                    .offset
+                    .checked_add(entry.size)
+                    .is_some_and(|end| end <= toc_offset)
+                && entry.uncompressed_size <= PACK_MAX_ENTRY_SIZE
+                && (entry.compression != PackCompression::Store
+                    || entry.size == entry.uncompressed_size);
+            if !is_valid {
+                return Err(invalid_data("Invalid offset or size of a pack entry!"));
+            }
+            let path = normalize_path(&path);
+            directories.extend(path.ancestors().skip(1).map(|p| p.to_path_buf()));
+            entries.insert(path, entry);
+        }
+
+        Ok(Self {
+            entries,
+            directories,
+            reader: Mutex::new(Box::new(reader)),
+        })
+    }
+
+    /// Opens a pack file at the given path.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, FileLoadError> {
+        Self::new(std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+
+    /// Reads a pack file from memory. It could be useful on platforms without file system access
+    /// or if the pack is embedded in the executable.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, FileLoadError> {
+        Self::new(Cursor::new(bytes))
+    }
+
+    /// Returns `true` if the pack contains a file at the given path.
+    pub fn is_file(&self, path: &Path) -> bool {
+        self.entries.contains_key(&normalize_path(path))
+    }
+
+    /// Returns `true` if the pack contains a file whose path starts with the given path.
+    pub fn is_dir(&self, path: &Path) -> bool {
+        self.directories.contains(&normalize_path(path))
+    }
+
+    /// Returns uncompressed size of a file at the given path.
+    pub fn file_size(&self, path: &Path) -> Option<u64> {
+        self.entries
+            .get(&normalize_path(path))
+            .map(|entry| entry.uncompressed_size)
+    }
+
+    /// Returns an iterator over the paths of every file in the pack.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(|path| path.as_path())
+    }
+
+    /// Reads and decompresses a file at the given path.
+    pub fn read(&self, path: &Path) -> Result<Vec<u8>, FileLoadError> {
+        let entry = self
+            .entries
+            .get(&normalize_path(path))
+            .ok_or_else(|| not_found(path))?;
+
+        let mut compressed = vec![0; entry.size as usize];
+        {
+            let mut reader = self.reader.lock();
+            reader.seek(SeekFrom::Start(entry.offset))?;
+            reader.read_exact(&mut compressed)?;
+        }
+
+        match entry.compression {
+            PackCompression::Store => Ok(compressed),
+            PackCompression::Deflate => {
+                let mut data = Vec::with_capacity(entry.uncompressed_size as usize);
+                // Read one extra byte to detect entries that are bigger than declared.
+                DeflateDecoder::new(compressed.as_slice())
+                    .take(entry.uncompressed_size + 1)
+                    .read_to_end(&mut data)?;
+                if data.len() as u64 != entry.uncompressed_size {
+                    return Err(invalid_data(
+                        "Size of a pack entry does not match its data!",
+                    ));
+                }
+                Ok(data)
+            }
+        }
+    }
+
+    fn children<'a>(
+        &'a self,
+        dir: &'a Path,
+        recursive: bool,
+    ) -> impl Iterator<Item = PathBuf> + 'a {
+        self.entries
+            .keys()
+            .chain(self.directories.iter())
+            .filter(move |path| {
+                if recursive {
+                    path.starts_with(dir)
+                } else {
+                    path.parent() == Some(dir)
+                }
+            })
+            .cloned()
+    }
+}
+
+/// Creates pack files. See [`PackFile`] docs for more info about the format.
+///
+/// ```rust,no_run
+/// # use fyrox_resource::vfs::PackBuilder;
+/// let mut builder = PackBuilder::new();
+/// builder.add_directory("data", |_| true).unwrap();
+/// builder.build("data.pak").unwrap();
+/// ```
+#[derive(Default, Debug)]
+pub struct PackBuilder {
+    files: Vec<(PathBuf, PathBuf)>,
+}
+
+impl PackBuilder {
+    /// Creates new empty pack builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file at the given path in the file system to the pack. The file will be available
+    /// at the given virtual path.
+    pub fn add_file<V, P>(&mut self, virtual_path: V, path: P) -> &mut Self
+    where
+        V: AsRef<Path>,
+        P: AsRef<Path>,
+    {
+        self.files.push((
+            normalize_path(virtual_path.as_ref()),
+            path.as_ref().to_path_buf(),
+        ));
+        self
+    }
+
+    /// Adds every file from the given directory (recursively) that passes the given filter. The
+    /// files will be available at the same paths in the pack as they are in the file system,
+    /// relative to the working directory (if the directory is in the working directory).
+    pub fn add_directory<P, F>(
+        &mut self,
+        directory: P,
+        filter: F,
+    ) -> Result<&mut Self, FileLoadError>
+    where
+        P: AsRef<Path>,
+        F: Fn(&Path) -> bool,
+    {
+        let directory = directory.as_ref();
+        let working_directory = std::env::current_dir()?;
+        for entry in walkdir::WalkDir::new(directory)
+            .into_iter()
+            .filter_entry(|entry| filter(entry.path()))
+        {
+            let entry = entry.map_err(|e| FileLoadError::Custom(e.to_string()))?;
+            if entry.file_type().is_file() {
+                let path = entry.path();
+                let virtual_path = match path.strip_prefix(&working_directory) {
+                    Ok(relative) => relative,
+                    Err(_) => path,
+                };
+                self.add_file(virtual_path, path);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns the number of files that will be written in the pack.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if there are no files in the pack.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Writes the pack to the given writer. Every file is compressed, unless compression does not
+    /// give any significant benefit (for example, for already compressed PNG or OGG files).
+    pub fn write<W: Write + Seek>(&self, mut writer: W) -> Result<(), FileLoadError> {
+        writer.write_all(&PACK_MAGIC)?;
+        writer.write_all(&PACK_VERSION.to_le_bytes())?;
+        // Offset of the table of contents, it will be written when the data is written.
+        writer.write_all(&0u64.to_le_bytes())?;
+
+        let mut entries = Vec::with_capacity(self.files.len());
+        for (virtual_path, path) in self.files.iter() {
+            let data = std::fs::read(path)?;
+
+            let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&data)?;
+            let compressed = encoder.finish()?;
+
+            let (compression, bytes) = if compressed.len() < data.len() * 9 / 10 {
+                (PackCompression::Deflate, compressed)
+            } else {
+                (PackCompression::Store, data.clone())
+            };
+
+            let entry = PackEntry {
+                offset: writer.stream_position()?,
+                size: bytes.len() as u64,
+                uncompressed_size: data.len() as u64,
+                compression,
+            };
+            writer.write_all(&bytes)?;
+            entries.push((virtual_path, entry));
+        }
+
+        let toc_offset = writer.stream_position()?;
+        writer.write_all(&(entries.len() as u32).to_le_bytes())?;
+        for (virtual_path, entry) in entries {
+            let path = path_to_string(virtual_path);
+            writer.write_all(&(path.len() as u32).to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&entry.offset.to_le_bytes())?;
+            writer.write_all(&entry.size.to_le_bytes())?;
+            writer.write_all(&entry.uncompressed_size.to_le_bytes())?;
+            writer.write_all(&[entry.compression as u8])?;
+        }
+
+        writer.seek(SeekFrom::Start(8))?;
+        writer.write_all(&toc_offset.to_le_bytes())?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes the pack to a file at the given path.
+    pub fn build<P: AsRef<Path>>(&self, path: P) -> Result<(), FileLoadError> {
+        self.write(std::io::BufWriter::new(std::fs::File::create(path)?))
+    }
+}
+
+enum MountSource {
+    Directory(PathBuf),
+    Pack(PackFile),
+}
+
+struct Mount {
+    point: PathBuf,
+    source: MountSource,
+}
+
+/// Virtual file system. See module docs for more info.
+#[derive(Default)]
+pub struct VirtualFileSystem {
+    mounts: Vec<Mount>,
+}
+
+impl VirtualFileSystem {
+    /// Creates new virtual file system without any mounts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts a directory of the real file system at the given virtual path (use empty path to
+    /// mount at the root). Mounts added later have priority over the mounts added earlier.
+    pub fn mount_directory<P, D>(&mut self, mount_point: P, directory: D) -> &mut Self
+    where
+        P: AsRef<Path>,
+        D: Into<PathBuf>,
+    {
+        self.mounts.push(Mount {
+            point: normalize_path(mount_point.as_ref()),
+            source: MountSource::Directory(directory.into()),
+        });
+        self
+    }
+
+    /// Mounts a pack file at the given virtual path (use empty path to mount at the root). Mounts
+    /// added later have priority over the mounts added earlier.
+    pub fn mount_pack<P>(&mut self, mount_point: P, pack: PackFile) -> &mut Self
+    where
+        P: AsRef<Path>,
+    {
+        self.mounts.push(Mount {
+            point: normalize_path(mount_point.as_ref()),
+            source: MountSource::Pack(pack),
+        });
+        self
+    }
+
+    /// Returns mounts that could contain the given path, in priority order, along with the path
+    /// relative to the mount point.
+    fn resolve(&self, path: &Path) -> Vec<(&Mount, PathBuf)> {
+        let path = normalize_path(path);
+        self.mounts
+            .iter()
+            .rev()
+            .filter_map(|mount| {
+                path.strip_prefix(&mount.point)
+                    .ok()
+                    .map(|relative| (mount, relative.to_path_buf()))
+            })
+            .collect()
+    }
+
+    fn is_file_sync(&self, path: &Path) -> bool {
+        self.resolve(path)
+            .into_iter()
+            .any(|(mount, relative)| match mount.source {
+                MountSource::Directory(ref dir) => dir.join(relative).is_file(),
+                MountSource::Pack(ref pack) => pack.is_file(&relative),
+            })
+    }
+
+    fn is_dir_sync(&self, path: &Path) -> bool {
+        self.resolve(path)
+            .into_iter()
+            .any(|(mount, relative)| match mount.source {
+                MountSource::Directory(ref dir) => dir.join(relative).is_dir(),
+                MountSource::Pack(ref pack) => pack.is_dir(&relative),
+            })
+    }
+
+    fn list(&self, path: &Path, recursive: bool) -> PathIter {
+        let mut visited = FxHashSet::default();
+        let mut paths = Vec::new();
+        for (mount, relative) in self.resolve(path) {
+            let children: Vec<PathBuf> = match mount.source {
+                MountSource::Directory(ref dir) => {
+                    let root = dir.join(&relative);
+                    let iter: Box<dyn Iterator<Item = PathBuf>> = if recursive {
+                        Box::new(
+                            walkdir::WalkDir::new(&root)
+                                .into_iter()
+                                .flatten()
+                                .map(|entry| entry.into_path()),
+                        )
+                    } else {
+                        match std::fs::read_dir(&root) {
+                            Ok(iter) => Box::new(iter.flatten().map(|entry| entry.path())),
+                            Err(_) => Box::new(std::iter::empty()),
+                        }
+                    };
+                    iter.filter_map(|p| p.strip_prefix(dir).ok().map(normalize_path))
+                        .collect()
+                }
+                MountSource::Pack(ref pack) => pack.children(&relative, recursive).collect(),
+            };
+            for child in children {
+                let virtual_path = mount.point.join(child);
+                if visited.insert(virtual_path.clone()) {
+                    paths.push(virtual_path);
+                }
+            }
+        }
+        Box::new(paths.into_iter())
+    }
+}
+
+impl ResourceIo for VirtualFileSystem {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            for (mount, relative) in self.resolve(path) {
+                match mount.source {
+                    MountSource::Directory(ref dir) => {
+                        let real_path = dir.join(relative);
+                        if real_path.is_file() {
+                            return fyrox_core::io::load_file(real_path).await;
+                        }
+                    }
+                    MountSource::Pack(ref pack) => {
+                        if pack.is_file(&relative) {
+                            return pack.read(&relative);
+                        }
+                    }
+                }
+            }
+            Err(not_found(path))
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            // Only files in mounted directories can be moved, pack files are read-only.
+            for (mount, relative) in self.resolve(source) {
+                match mount.source {
+                    MountSource::Directory(ref dir) => {
+                        let real_source = dir.join(relative);
+                        if real_source.is_file() {
+                            let real_dest = normalize_path(dest)
+                                .strip_prefix(&mount.point)
+                                .map(|relative| dir.join(relative))
+                                .map_err(|_| {
+                                    FileLoadError::Custom(format!(
+                                        "Unable to move {} to {}, because they're in different mounts.",
+                                        source.display(),
+                                        dest.display()
+                                    ))
+                                })?;
+                            std::fs::rename(real_source, real_dest)?;
+                            return Ok(());
+                        }
+                    }
+                    MountSource::Pack(ref pack) => {
+                        if pack.is_file(&relative) {
+                            return Err(FileLoadError::Custom(format!(
+                                "Unable to move {}, because pack files are read-only.",
+                                source.display()
+                            )));
+                        }
+                    }
+                }
+            }
+            Err(not_found(source))
+        })
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(async move { Ok(normalize_path(path)) })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(async move { Ok(self.list(path, false)) })
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(async move { Ok(self.list(path, true)) })
+    }
+
+    fn file_size<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<u64>> {
+        Box::pin(async move {
+            self.resolve(path)
+                .into_iter()
+                .find_map(|(mount, relative)| match mount.source {
+                    MountSource::Directory(ref dir) => std::fs::metadata(dir.join(relative))
+                        .ok()
+                        .filter(|metadata| metadata.is_file())
+                        .map(|metadata| metadata.len()),
+                    MountSource::Pack(ref pack) => pack.file_size(&relative),
+                })
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.is_file_sync(path) || self.is_dir_sync(path) })
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.is_file_sync(path) })
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.is_dir_sync(path) })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox_core::futures::executor::block_on;
+
+    fn write_test_files(root: &Path) {
+        std::fs::create_dir_all(root.join("textures")).unwrap();
+        std::fs::write(root.join("textures/a.txt"), "a".repeat(1000)).unwrap();
+        std::fs::write(root.join("b.bin"), [1, 2, 3]).unwrap();
+    }
+
+    fn build_test_pack(root: &Path) -> PackFile {
+        let mut builder = PackBuilder::new();
+        builder
+            .add_file("data/textures/a.txt", root.join("textures/a.txt"))
+            .add_file("./data/b.bin", root.join("b.bin"));
+        let mut bytes = Cursor::new(Vec::new());
+        builder.write(&mut bytes).unwrap();
+        PackFile::from_bytes(bytes.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(Path::new("./data/../data/textures/a.png")),
+            PathBuf::from("data/textures/a.png")
+        );
+        assert_eq!(normalize_path(Path::new(".")), PathBuf::new());
+    }
+
+    #[test]
+    fn test_pack_round_trip() {
+        let root = std::env::temp_dir().join("fyrox_vfs_pack_test");
+        write_test_files(&root);
+        let pack = build_test_pack(&root);
+
+        assert_eq!(
+            pack.read(Path::new("data/textures/a.txt")).unwrap(),
+            "a".repeat(1000).as_bytes()
+        );
+        assert_eq!(pack.read(Path::new("data/b.bin")).unwrap(), [1, 2, 3]);
+        assert!(pack.is_dir(Path::new("data/textures")));
+        assert!(!pack.is_file(Path::new("data/textures")));
+        assert!(pack.read(Path::new("data/c.bin")).is_err());
+
+        // Repetitive data must be compressed, tiny files must be stored as is.
+        let a = &pack.entries[Path::new("data/textures/a.txt")];
+        assert_eq!(a.compression, PackCompression::Deflate);
+        assert!(a.size < a.uncompressed_size);
+        let b = &pack.entries[Path::new("data/b.bin")];
+        assert_eq!(b.compression, PackCompression::Store);
+    }
+
+    #[test]
+    fn test_corrupted_pack() {
+        let root = std::env::temp_dir().join("fyrox_vfs_corrupted_test");
+        write_test_files(&root);
+        let mut builder = PackBuilder::new();
+        builder.add_file("b.bin", root.join("b.bin"));
+        let mut bytes = Cursor::new(Vec::new());
+        builder.write(&mut bytes).unwrap();
+        let bytes = bytes.into_inner();
+
+        let is_invalid_data = |bytes: Vec<u8>| {
+            matches!(
+                PackFile::from_bytes(bytes),
+                Err(FileLoadError::Io(err)) if err.kind() == ErrorKind::InvalidData
+            )
+        };
+
+        assert!(PackFile::from_bytes(bytes.clone()).is_ok());
+
+        // Offset of the table of contents points outside of the file.
+        let mut corrupted = bytes.clone();
+        corrupted[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(is_invalid_data(corrupted));
+
+        // Huge entry count.
+        let toc_offset = u64::from_le_bytes(bytes[8..16].try_into().unwrap()) as usize;
+        let mut corrupted = bytes.clone();
+        corrupted[toc_offset..toc_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(is_invalid_data(corrupted));
+
+        // Size of the entry exceeds the size of the file. The entry is the last one, so its size
+        // is at the end of the table right before uncompressed size and compression method.
+        let size_offset = bytes.len() - 17;
+        let mut corrupted = bytes.clone();
+        corrupted[size_offset..size_offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(is_invalid_data(corrupted));
+    }
+
+    #[test]
+    fn test_mount_priority() {
+        let root = std::env::temp_dir().join("fyrox_vfs_mount_test");
+        write_test_files(&root);
+        let pack = build_test_pack(&root);
+
+        let overrides = root.join("overrides");
+        std::fs::create_dir_all(&overrides).unwrap();
+        std::fs::write(overrides.join("b.bin"), [4, 5, 6]).unwrap();
+
+        let mut vfs = VirtualFileSystem::new();
+        vfs.mount_pack("", pack).mount_directory("data", &overrides);
+
+        block_on(async {
+            assert_eq!(
+                vfs.load_file(Path::new("data/b.bin")).await.unwrap(),
+                [4, 5, 6]
+            );
+            assert_eq!(
+                vfs.load_file(Path::new("./data/textures/a.txt"))
+                    .await
+                    .unwrap()
+                    .len(),
+                1000
+            );
+            assert!(vfs.load_file(Path::new("data/c.bin")).await.is_err());
+            assert!(vfs.is_dir(Path::new("data/textures")).await);
+            assert!(vfs.exists(Path::new("data/b.bin")).await);
+
+            let mut children = vfs
+                .read_directory(Path::new("data"))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>();
+            children.sort();
+            assert_eq!(
+                children,
+                [PathBuf::from("data/b.bin"), PathBuf::from("data/textures")]
+            );
+        });
+    }
+}
@@ -1,6 +1,6 @@
 //! Sound buffer loader.
 
-use crate::buffer::{DataSource, SoundBuffer};
+use crate::buffer::{streaming::StreamingBuffer, DataSource, SoundBuffer};
 use fyrox_core::{reflect::prelude::*, uuid::Uuid, TypeUuidProvider};
 use fyrox_resource::{
     io::ResourceIo,
@@ -14,10 +14,30 @@ use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, sync::Arc};
 
 /// Defines sound buffer resource import options.
-#[derive(Clone, Deserialize, Serialize, Default, Debug, Reflect)]
+#[derive(Clone, Deserialize, Serialize, Debug, Reflect)]
 pub struct SoundBufferImportOptions {
-    /// Whether the buffer is streaming or not.
+    /// Whether the buffer is streaming or not. Streaming buffers decode the data in small blocks
+    /// while playing, which is ideal for long sounds such as music.
     pub stream: bool,
+
+    /// Amount of samples (per channel) that will be decoded at once by a streaming buffer. Has no
+    /// effect on non-streaming buffers.
+    #[serde(default = "default_stream_block_size")]
+    #[reflect(min_value = 2.0)]
+    pub stream_block_size: usize,
+}
+
+fn default_stream_block_size() -> usize {
+    StreamingBuffer::STREAM_SAMPLE_COUNT
+}
+
+impl Default for SoundBufferImportOptions {
+    fn default() -> Self {
+        Self {
+            stream: false,
+            stream_block_size: default_stream_block_size(),
+        }
+    }
 }
 
 impl ImportOptions for SoundBufferImportOptions {}
@@ -52,7 +72,7 @@ impl ResourceLoader for SoundBufferLoader {
                 .map_err(LoadError::new)?;
 
             let result = if import_options.stream {
-                SoundBuffer::raw_streaming(source)
+                SoundBuffer::raw_streaming_with_block_size(source, import_options.stream_block_size)
             } else {
                 SoundBuffer::raw_generic(source)
             };
@@ -228,6 +228,18 @@ impl SoundBuffer {
         Ok(Self::Streaming(StreamingBuffer::new(data_source)?))
     }
 
+    /// Same as [`Self::raw_streaming`], but allows you to specify the amount of samples `per channel`
+    /// which will be decoded at once. See [`StreamingBuffer::with_block_size`] for more info.
+    pub fn raw_streaming_with_block_size(
+        data_source: DataSource,
+        block_size: usize,
+    ) -> Result<Self, DataSource> {
+        Ok(Self::Streaming(StreamingBuffer::with_block_size(
+            data_source,
+            block_size,
+        )?))
+    }
+
     /// Tries to create new generic sound buffer from a given data source. It returns raw sound
     /// buffer that has to be wrapped into Arc<Mutex<>> for use with sound sources.
     pub fn raw_generic(data_source: DataSource) -> Result<Self, DataSource> {
@@ -285,4 +297,8 @@ impl ResourceData for SoundBuffer {
     fn can_be_saved(&self) -> bool {
         false
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + std::mem::size_of_val(self.samples())
+    }
 }
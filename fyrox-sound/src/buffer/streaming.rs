@@ -50,6 +50,10 @@ pub struct StreamingBuffer {
     #[visit(skip)]
     #[reflect(hidden)]
     streaming_source: StreamingSource,
+    /// Amount of samples per channel in each block.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    block_size: usize,
 }
 
 #[derive(Debug)]
@@ -121,9 +125,9 @@ impl StreamingSource {
     }
 
     #[inline]
-    fn read_next_samples_block_into(&mut self, buffer: &mut Vec<f32>) -> usize {
+    fn read_next_samples_block_into(&mut self, buffer: &mut Vec<f32>, block_size: usize) -> usize {
         buffer.clear();
-        let count = StreamingBuffer::STREAM_SAMPLE_COUNT * self.channel_count();
+        let count = block_size * self.channel_count();
         match self {
             StreamingSource::Decoder(decoder) => {
                 for _ in 0..count {
@@ -151,23 +155,33 @@ impl StreamingSource {
 }
 
 impl StreamingBuffer {
-    /// Defines amount of samples `per channel` which each streaming buffer will use for internal buffer.
+    /// Defines default amount of samples `per channel` which each streaming buffer will use for internal
+    /// buffer.
     pub const STREAM_SAMPLE_COUNT: usize = 44100;
 
     /// Creates new streaming buffer using given data source. May fail if data source has unsupported format
-    /// or it has corrupted data. Length of internal generic buffer cannot be changed but can be fetched from
-    /// `StreamingBuffer::STREAM_SAMPLE_COUNT`
+    /// or it has corrupted data. Length of internal generic buffer is `StreamingBuffer::STREAM_SAMPLE_COUNT`,
+    /// use [`Self::with_block_size`] to specify custom length.
     ///
     /// # Notes
     ///
     /// This function will return Err if data source is `Raw`. It makes no sense to stream raw data which
     /// is already loaded into memory. Use Generic source instead!
     pub fn new(source: DataSource) -> Result<Self, DataSource> {
+        Self::with_block_size(source, Self::STREAM_SAMPLE_COUNT)
+    }
+
+    /// Creates new streaming buffer using given data source and the amount of samples `per channel`
+    /// which will be decoded at once. Smaller blocks use less memory, but require more frequent
+    /// decoding. Length of internal generic buffer cannot be changed after creation.
+    pub fn with_block_size(source: DataSource, block_size: usize) -> Result<Self, DataSource> {
+        // Resampling requires at least two samples in a block.
+        let block_size = block_size.max(2);
         let mut streaming_source = StreamingSource::new(source)?;
 
         let mut samples = Vec::new();
         let channel_count = streaming_source.channel_count();
-        streaming_source.read_next_samples_block_into(&mut samples);
+        streaming_source.read_next_samples_block_into(&mut samples, block_size);
         debug_assert_eq!(samples.len() % channel_count, 0);
 
         Ok(Self {
@@ -179,13 +193,27 @@ impl StreamingBuffer {
             },
             use_count: 0,
             streaming_source,
+            block_size,
         })
     }
 
+    /// Returns amount of samples `per channel` which are decoded at once.
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
     #[inline]
     pub(crate) fn read_next_block(&mut self) {
         self.streaming_source
-            .read_next_samples_block_into(&mut self.generic.samples);
+            .read_next_samples_block_into(&mut self.generic.samples, self.block_size);
+    }
+
+    /// Returns `true` if currently loaded block is the last one, in other words the decoder has
+    /// reached the end of the data source.
+    #[inline]
+    pub(crate) fn is_last_block(&self) -> bool {
+        self.generic.samples.len() < self.block_size * self.generic.channel_count
     }
 
     #[inline]
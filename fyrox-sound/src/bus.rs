@@ -5,9 +5,14 @@ use crate::effects::{Effect, EffectRenderTrait};
 use fyrox_core::{
     pool::{Handle, Pool, Ticket},
     reflect::prelude::*,
+    uuid_provider,
     visitor::prelude::*,
 };
-use std::fmt::{Debug, Formatter};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    path::Path,
+};
 
 #[derive(Default, Clone)]
 struct PingPongBuffer {
@@ -76,6 +81,49 @@ impl PingPongBuffer {
     }
 }
 
+fn mix(input: &[(f32, f32)], output: &mut [(f32, f32)], gain: f32) {
+    for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+        *output_left += *input_left * gain;
+        *output_right += *input_right * gain;
+    }
+}
+
+/// Audio bus send is an additional output of an audio bus. It sends the processed samples of the bus
+/// to some other bus (not necessarily the parent one) with the given gain. Sends are usually used to
+/// share a single expensive effect between multiple buses, for example a few buses could send a
+/// portion of their signal to a bus with reverberation effect.
+///
+/// Just like sound sources, sends are bound to audio buses by names. Samples are sent after the effects
+/// chain of the bus, but before its own gain is applied.
+#[derive(Debug, Clone, PartialEq, Reflect, Visit)]
+pub struct AudioBusSend {
+    /// Name of an audio bus to send the samples to.
+    pub bus: String,
+    /// Gain of the send.
+    pub gain: f32,
+}
+
+uuid_provider!(AudioBusSend = "7c6b4d0e-2f5a-4b8e-93c1-5e0a8d9f6b24");
+
+impl Default for AudioBusSend {
+    fn default() -> Self {
+        Self {
+            bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            gain: 1.0,
+        }
+    }
+}
+
+impl AudioBusSend {
+    /// Creates a new send to an audio bus with the given name.
+    pub fn new<S: AsRef<str>>(bus: S, gain: f32) -> Self {
+        Self {
+            bus: bus.as_ref().to_owned(),
+            gain,
+        }
+    }
+}
+
 /// Audio bus is a top-level audio processing unit. It takes data from multiple audio sources and passes their
 /// samples through a chain of effects. Output signal is then can be either sent to an audio playback device or
 /// to some other audio bus and be processed again, but with different sound effects (this can be done via
@@ -86,6 +134,9 @@ pub struct AudioBus {
     effects: Vec<Effect>,
     gain: f32,
 
+    #[visit(optional)]
+    sends: Vec<AudioBusSend>,
+
     #[reflect(hidden)]
     child_buses: Vec<Handle<AudioBus>>,
 
@@ -104,6 +155,7 @@ impl Default for AudioBus {
             child_buses: Default::default(),
             effects: Default::default(),
             gain: 1.0,
+            sends: Default::default(),
             ping_pong_buffer: Default::default(),
             parent_bus: Default::default(),
         }
@@ -202,6 +254,26 @@ impl AudioBus {
     pub fn effects_mut(&mut self) -> impl Iterator<Item = &mut Effect> {
         self.effects.iter_mut()
     }
+
+    /// Adds a new send to the bus. See [`AudioBusSend`] docs for more info.
+    pub fn add_send(&mut self, send: AudioBusSend) {
+        self.sends.push(send)
+    }
+
+    /// Removes a send at the given index.
+    pub fn remove_send(&mut self, index: usize) -> AudioBusSend {
+        self.sends.remove(index)
+    }
+
+    /// Returns a slice with every send of the audio bus.
+    pub fn sends(&self) -> &[AudioBusSend] {
+        &self.sends
+    }
+
+    /// Returns a mutable slice with every send of the audio bus.
+    pub fn sends_mut(&mut self) -> &mut [AudioBusSend] {
+        &mut self.sends
+    }
 }
 
 /// Audio bus graph is a complex audio data processing entity; it allows you to route samples from
@@ -283,6 +355,18 @@ impl AudioBus {
 /// ```
 ///
 /// If you delete an audio bus to which a bunch of sound sources is bound, then they will simply stop playing.
+///
+/// # Processing order
+///
+/// Every audio bus is processed only after all the buses that output their samples to it (its children
+/// and every bus that has a [`AudioBusSend`] to it). It means that effects of a parent bus are applied
+/// to the mix of its children. Sends that form a loop are not supported, samples of such sends will be
+/// lost.
+///
+/// # Presets
+///
+/// Audio bus graph could be saved to a file using [`AudioBusGraph::save`] and loaded back using
+/// [`AudioBusGraph::load`]. This way the same mixer configuration could be shared across multiple scenes.
 #[derive(Default, Debug, Clone, Visit, Reflect)]
 pub struct AudioBusGraph {
     buses: Pool<AudioBus>,
@@ -374,6 +458,13 @@ impl AudioBusGraph {
         bus
     }
 
+    /// Tries to find an audio bus with the given name.
+    pub fn find_bus_by_name(&self, name: &str) -> Option<Handle<AudioBus>> {
+        self.buses
+            .pair_iter()
+            .find_map(|(handle, bus)| (bus.name == name).then_some(handle))
+    }
+
     /// Returns a handle of the primary audio bus. Primary bus outputs its samples directly to an audio playback
     /// device.
     pub fn primary_bus_handle(&self) -> Handle<AudioBus> {
@@ -459,39 +550,106 @@ impl AudioBusGraph {
         }
     }
 
-    pub(crate) fn end_render(&mut self, output_device_buffer: &mut [(f32, f32)]) {
-        let mut leafs = Vec::new();
-        for (handle, bus) in self.buses.pair_iter_mut() {
-            bus.apply_effects();
-
-            if bus.child_buses.is_empty() {
-                leafs.push(handle);
+    /// Saves the graph to the given file. Saved graph could be used as a mixer preset, that could be
+    /// loaded using [`Self::load`].
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("AudioBusGraph", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Loads a graph from the given file, that was previously saved using [`Self::save`].
+    pub async fn load<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        let mut graph = Self::default();
+        graph.visit("AudioBusGraph", &mut visitor)?;
+        Ok(graph)
+    }
+
+    fn outputs(&self, handle: Handle<AudioBus>) -> impl Iterator<Item = Handle<AudioBus>> + '_ {
+        let bus = &self.buses[handle];
+        bus.parent_bus
+            .is_some()
+            .then_some(bus.parent_bus)
+            .into_iter()
+            .chain(
+                bus.sends
+                    .iter()
+                    .filter_map(|send| self.find_bus_by_name(&send.bus)),
+            )
+            .filter(move |output| *output != handle)
+    }
+
+    /// Sorts the buses so every bus goes after all the buses that output their samples to it.
+    fn processing_order(&self) -> Vec<Handle<AudioBus>> {
+        let mut input_count = HashMap::<Handle<AudioBus>, usize>::new();
+        for handle in self.buses.pair_iter().map(|(handle, _)| handle) {
+            input_count.entry(handle).or_default();
+            for output in self.outputs(handle) {
+                *input_count.entry(output).or_default() += 1;
             }
         }
 
-        for mut leaf in leafs {
-            while leaf.is_some() {
-                let ctx = self.buses.begin_multi_borrow();
-
-                let leaf_ref = ctx.try_get_mut(leaf).expect("Malformed bus graph!");
-
-                let input_buffer = leaf_ref.ping_pong_buffer.input_ref();
-                let leaf_gain = leaf_ref.gain;
-                let mut parent_buffer = ctx.try_get_mut(leaf_ref.parent_bus);
-                let output_buffer = parent_buffer
-                    .as_mut()
-                    .map(|parent| parent.ping_pong_buffer.input_mut())
-                    // Special case for the root bus - it writes directly to the output device buffer.
-                    .unwrap_or(&mut *output_device_buffer);
-                for ((input_left, input_right), (output_left, output_right)) in
-                    input_buffer.iter().zip(output_buffer)
-                {
-                    *output_left += *input_left * leaf_gain;
-                    *output_right += *input_right * leaf_gain;
+        let mut stack = input_count
+            .iter()
+            .filter_map(|(handle, count)| (*count == 0).then_some(*handle))
+            .collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(input_count.len());
+        while let Some(handle) = stack.pop() {
+            order.push(handle);
+            for output in self.outputs(handle) {
+                let count = input_count.get_mut(&output).expect("Malformed bus graph!");
+                *count -= 1;
+                if *count == 0 {
+                    stack.push(output);
                 }
+            }
+        }
+
+        if order.len() != input_count.len() {
+            // Sends form a loop, process the rest of the buses in arbitrary order.
+            order.extend(
+                input_count
+                    .into_iter()
+                    .filter_map(|(handle, count)| (count != 0).then_some(handle)),
+            );
+        }
 
-                leaf = leaf_ref.parent_bus;
+        order
+    }
+
+    pub(crate) fn end_render(&mut self, output_device_buffer: &mut [(f32, f32)]) {
+        for handle in self.processing_order() {
+            let sends = self.buses[handle]
+                .sends
+                .iter()
+                .filter_map(|send| {
+                    self.find_bus_by_name(&send.bus)
+                        .filter(|target| *target != handle)
+                        .map(|target| (target, send.gain))
+                })
+                .collect::<Vec<_>>();
+
+            let ctx = self.buses.begin_multi_borrow();
+
+            let mut bus = ctx.try_get_mut(handle).expect("Malformed bus graph!");
+            bus.apply_effects();
+
+            let input_buffer = bus.ping_pong_buffer.input_ref();
+
+            for (target, gain) in sends {
+                if let Ok(mut target) = ctx.try_get_mut(target) {
+                    mix(input_buffer, target.ping_pong_buffer.input_mut(), gain);
+                }
             }
+
+            let mut parent_buffer = ctx.try_get_mut(bus.parent_bus);
+            let output_buffer = parent_buffer
+                .as_mut()
+                .map(|parent| parent.ping_pong_buffer.input_mut())
+                // Special case for the root bus - it writes directly to the output device buffer.
+                .unwrap_or(&mut *output_device_buffer);
+            mix(input_buffer, output_buffer, bus.gain);
         }
     }
 }
@@ -499,7 +657,7 @@ impl AudioBusGraph {
 #[cfg(test)]
 mod test {
     use crate::{
-        bus::{AudioBus, AudioBusGraph},
+        bus::{AudioBus, AudioBusGraph, AudioBusSend},
         effects::{Attenuate, Effect},
     };
 
@@ -580,6 +738,36 @@ mod test {
 
         graph.end_render(&mut output_buffer);
 
-        assert_eq!(output_buffer[0], (0.75, 0.75));
+        // Effects of the parent bus must be applied to the mix of its children: (1.0 + 1.0 * 0.5) * 0.25
+        assert_eq!(output_buffer[0], (0.375, 0.375));
+    }
+
+    #[test]
+    fn test_bus_send() {
+        let mut output_buffer = [(0.0f32, 0.0f32)];
+
+        let mut graph = AudioBusGraph::new();
+
+        let mut reverb = AudioBus::new("Reverb".to_string());
+        reverb.add_effect(Effect::Attenuate(Attenuate::new(0.5)));
+        let reverb = graph.add_bus(reverb, graph.root);
+
+        let mut sfx = AudioBus::new("SFX".to_string());
+        sfx.add_send(AudioBusSend::new("Reverb", 0.5));
+        let sfx = graph.add_bus(sfx, graph.root);
+
+        assert_eq!(graph.find_bus_by_name("Reverb"), Some(reverb));
+
+        graph.begin_render(output_buffer.len());
+
+        for (left, right) in graph.buses[sfx].input_buffer() {
+            *left = 1.0;
+            *right = 1.0;
+        }
+
+        graph.end_render(&mut output_buffer);
+
+        // Direct signal plus the signal that went through the reverb bus: 1.0 + 1.0 * 0.5 * 0.5
+        assert_eq!(output_buffer[0], (1.25, 1.25));
     }
 }
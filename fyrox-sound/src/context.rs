@@ -120,6 +120,8 @@ pub struct State {
     bus_graph: AudioBusGraph,
     distance_model: DistanceModel,
     paused: bool,
+    doppler_factor: f32,
+    speed_of_sound: f32,
     /// A set of flags, that can be used to define what should be skipped during the
     /// serialization of a sound context.
     #[reflect(hidden)]
@@ -170,6 +172,28 @@ impl State {
         self.distance_model
     }
 
+    /// Sets new doppler factor. It is used to exaggerate (values greater than `1.0`) or to weaken
+    /// (values less than `1.0`) the doppler effect. Zero disables the effect completely.
+    pub fn set_doppler_factor(&mut self, doppler_factor: f32) {
+        self.doppler_factor = doppler_factor.max(0.0);
+    }
+
+    /// Returns current doppler factor.
+    pub fn doppler_factor(&self) -> f32 {
+        self.doppler_factor
+    }
+
+    /// Sets new speed of sound (in units per second), default value is 343.3 which is the speed
+    /// of sound in the air (in meters per second).
+    pub fn set_speed_of_sound(&mut self, speed_of_sound: f32) {
+        self.speed_of_sound = speed_of_sound.max(f32::EPSILON);
+    }
+
+    /// Returns current speed of sound.
+    pub fn speed_of_sound(&self) -> f32 {
+        self.speed_of_sound
+    }
+
     /// Normalizes given frequency using context's sampling rate. Normalized frequency then can be used
     /// to create filters.
     pub fn normalize_frequency(&self, f: f32) -> f32 {
@@ -275,6 +299,11 @@ impl State {
             {
                 if let Some(bus_input_buffer) = self.bus_graph.try_get_bus_input_buffer(&source.bus)
                 {
+                    source.doppler_pitch = source.calculate_doppler_pitch(
+                        &self.listener,
+                        self.doppler_factor,
+                        self.speed_of_sound,
+                    );
                     source.render(output_device_buffer.len());
 
                     match self.renderer {
@@ -313,6 +342,12 @@ impl SoundContext {
 
     pub(crate) const HRTF_INTERPOLATION_STEPS: usize = 4;
 
+    /// Default doppler factor of a context.
+    pub const DEFAULT_DOPPLER_FACTOR: f32 = 1.0;
+
+    /// Default speed of sound (in meters per second) of a context.
+    pub const DEFAULT_SPEED_OF_SOUND: f32 = 343.3;
+
     pub(crate) const SAMPLES_PER_CHANNEL: usize =
         Self::HRTF_BLOCK_LEN * Self::HRTF_INTERPOLATION_STEPS;
 
@@ -329,6 +364,8 @@ impl SoundContext {
                 bus_graph: AudioBusGraph::new(),
                 distance_model: DistanceModel::InverseDistance,
                 paused: false,
+                doppler_factor: Self::DEFAULT_DOPPLER_FACTOR,
+                speed_of_sound: Self::DEFAULT_SPEED_OF_SOUND,
                 serialization_options: Default::default(),
             }))),
         }
@@ -387,6 +424,21 @@ impl Visit for State {
         self.renderer.visit("Renderer", &mut region)?;
         self.paused.visit("Paused", &mut region)?;
         self.distance_model.visit("DistanceModel", &mut region)?;
+        // Backward compatibility.
+        if self
+            .doppler_factor
+            .visit("DopplerFactor", &mut region)
+            .is_err()
+        {
+            self.doppler_factor = SoundContext::DEFAULT_DOPPLER_FACTOR;
+        }
+        if self
+            .speed_of_sound
+            .visit("SpeedOfSound", &mut region)
+            .is_err()
+        {
+            self.speed_of_sound = SoundContext::DEFAULT_SPEED_OF_SOUND;
+        }
 
         Ok(())
     }
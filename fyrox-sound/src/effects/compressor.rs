@@ -0,0 +1,179 @@
+//! Dynamic range compressor effect. It reduces the volume of loud sounds, which could be used to
+//! prevent clipping of the master bus or to make sound effects more "dense".
+
+use crate::{context::SAMPLE_RATE, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+/// Dynamic range compressor with stereo-linked peak envelope detection. Any signal which level
+/// exceeds the threshold will be attenuated according to the ratio, for example ratio of `4.0` means
+/// that every 4 dB above the threshold will produce only 1 dB of output.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct Compressor {
+    #[reflect(
+        description = "Level (in decibels) above which the signal will be compressed.",
+        setter = "set_threshold_db",
+        max_value = 0.0
+    )]
+    threshold_db: f32,
+
+    #[reflect(
+        description = "Compression ratio. The higher the value the stronger the compression.",
+        setter = "set_ratio",
+        min_value = 1.0
+    )]
+    ratio: f32,
+
+    #[reflect(
+        description = "Time (in seconds) needed for the compressor to react on a signal above the threshold.",
+        setter = "set_attack_time",
+        min_value = 0.0
+    )]
+    attack_time: f32,
+
+    #[reflect(
+        description = "Time (in seconds) needed for the compressor to stop compressing after the signal has gone below the threshold.",
+        setter = "set_release_time",
+        min_value = 0.0
+    )]
+    release_time: f32,
+
+    #[reflect(
+        description = "Gain that is applied after the compression to compensate the loss of volume.",
+        setter = "set_makeup_gain",
+        min_value = 0.0
+    )]
+    makeup_gain: f32,
+
+    #[reflect(hidden)]
+    envelope: f32,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self {
+            threshold_db: -20.0,
+            ratio: 4.0,
+            attack_time: 0.01,
+            release_time: 0.1,
+            makeup_gain: 1.0,
+            envelope: 0.0,
+        }
+    }
+}
+
+fn time_coefficient(time: f32) -> f32 {
+    if time <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / (time * SAMPLE_RATE as f32)).exp()
+    }
+}
+
+impl EffectRenderTrait for Compressor {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let attack = time_coefficient(self.attack_time);
+        let release = time_coefficient(self.release_time);
+        let slope = 1.0 - 1.0 / self.ratio;
+
+        for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            let level = input_left.abs().max(input_right.abs());
+            let coefficient = if level > self.envelope {
+                attack
+            } else {
+                release
+            };
+            self.envelope = level + coefficient * (self.envelope - level);
+
+            let envelope_db = 20.0 * self.envelope.max(f32::EPSILON).log10();
+            let gain_db = if envelope_db > self.threshold_db {
+                (self.threshold_db - envelope_db) * slope
+            } else {
+                0.0
+            };
+            let gain = 10.0f32.powf(gain_db / 20.0) * self.makeup_gain;
+
+            *output_left = *input_left * gain;
+            *output_right = *input_right * gain;
+        }
+    }
+}
+
+impl Compressor {
+    /// Sets the level (in decibels) above which the signal will be compressed.
+    pub fn set_threshold_db(&mut self, threshold_db: f32) {
+        self.threshold_db = threshold_db.min(0.0);
+    }
+
+    /// Returns the level (in decibels) above which the signal will be compressed.
+    pub fn threshold_db(&self) -> f32 {
+        self.threshold_db
+    }
+
+    /// Sets the compression ratio, it must be greater or equal than `1.0`.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    /// Returns the compression ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets the time (in seconds) needed for the compressor to react on a signal above the threshold.
+    pub fn set_attack_time(&mut self, attack_time: f32) {
+        self.attack_time = attack_time.max(0.0);
+    }
+
+    /// Returns the attack time (in seconds).
+    pub fn attack_time(&self) -> f32 {
+        self.attack_time
+    }
+
+    /// Sets the time (in seconds) needed for the compressor to stop compressing after the signal has
+    /// gone below the threshold.
+    pub fn set_release_time(&mut self, release_time: f32) {
+        self.release_time = release_time.max(0.0);
+    }
+
+    /// Returns the release time (in seconds).
+    pub fn release_time(&self) -> f32 {
+        self.release_time
+    }
+
+    /// Sets the gain that is applied after the compression.
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) {
+        self.makeup_gain = makeup_gain.max(0.0);
+    }
+
+    /// Returns the gain that is applied after the compression.
+    pub fn makeup_gain(&self) -> f32 {
+        self.makeup_gain
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{compressor::Compressor, EffectRenderTrait};
+
+    #[test]
+    fn test_compressor_reduces_loud_signal() {
+        let mut compressor = Compressor::default();
+        compressor.set_attack_time(0.0);
+
+        let input = [(1.0, -1.0); 16];
+        let mut output = [(0.0, 0.0); 16];
+        compressor.render(&input, &mut output);
+
+        // 0 dB input, -20 dB threshold and 4:1 ratio gives -15 dB output.
+        let expected = 10.0f32.powf(-15.0 / 20.0);
+        for (left, right) in output {
+            assert!((left - expected).abs() < 0.001);
+            assert!((right + expected).abs() < 0.001);
+        }
+
+        let quiet_input = [(0.01, 0.01); 16];
+        let mut compressor = Compressor::default();
+        compressor.render(&quiet_input, &mut output);
+        assert!(output.iter().all(|(left, _)| (left - 0.01).abs() < 0.0001));
+    }
+}
@@ -0,0 +1,198 @@
+//! Three-band equalizer effect. It could be used to tune the tonal balance of an audio bus, for
+//! example to cut low frequencies of a music bus to make dialogs more intelligible.
+
+use crate::{
+    context::SAMPLE_RATE,
+    dsp::filters::{Biquad, BiquadKind},
+    effects::EffectRenderTrait,
+};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+#[derive(Clone, Default, Debug, PartialEq, Visit)]
+struct ChannelEqualizer {
+    low: Biquad,
+    mid: Biquad,
+    high: Biquad,
+}
+
+impl ChannelEqualizer {
+    fn feed(&mut self, sample: f32) -> f32 {
+        self.high.feed(self.mid.feed(self.low.feed(sample)))
+    }
+}
+
+/// Three-band equalizer, that consists of a low shelf filter, a peaking filter and a high shelf
+/// filter connected in series. Each band has its own frequency and gain, gain of `1.0` leaves the
+/// band untouched, values less than `1.0` cut the band and values greater than `1.0` boost it.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct Equalizer {
+    #[reflect(
+        description = "Frequency (in Hertz) below which the low band gain is applied.",
+        setter = "set_low_frequency_hz"
+    )]
+    low_frequency_hz: f32,
+
+    #[reflect(description = "Gain of the low band.", setter = "set_low_gain")]
+    low_gain: f32,
+
+    #[reflect(
+        description = "Center frequency (in Hertz) of the mid band.",
+        setter = "set_mid_frequency_hz"
+    )]
+    mid_frequency_hz: f32,
+
+    #[reflect(description = "Gain of the mid band.", setter = "set_mid_gain")]
+    mid_gain: f32,
+
+    #[reflect(
+        description = "Width of the mid band, the higher the value the narrower the band.",
+        setter = "set_mid_quality"
+    )]
+    mid_quality: f32,
+
+    #[reflect(
+        description = "Frequency (in Hertz) above which the high band gain is applied.",
+        setter = "set_high_frequency_hz"
+    )]
+    high_frequency_hz: f32,
+
+    #[reflect(description = "Gain of the high band.", setter = "set_high_gain")]
+    high_gain: f32,
+
+    #[reflect(hidden)]
+    left: ChannelEqualizer,
+    #[reflect(hidden)]
+    right: ChannelEqualizer,
+}
+
+impl Default for Equalizer {
+    fn default() -> Self {
+        let mut equalizer = Self {
+            low_frequency_hz: 250.0,
+            low_gain: 1.0,
+            mid_frequency_hz: 1000.0,
+            mid_gain: 1.0,
+            mid_quality: 0.7,
+            high_frequency_hz: 4000.0,
+            high_gain: 1.0,
+            left: Default::default(),
+            right: Default::default(),
+        };
+        equalizer.update();
+        equalizer
+    }
+}
+
+impl EffectRenderTrait for Equalizer {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            *output_left = self.left.feed(*input_left);
+            *output_right = self.right.feed(*input_right);
+        }
+    }
+}
+
+impl Equalizer {
+    /// Sets the frequency (in Hertz) below which the low band gain is applied.
+    pub fn set_low_frequency_hz(&mut self, frequency: f32) {
+        self.low_frequency_hz = frequency;
+        self.update();
+    }
+
+    /// Returns the frequency (in Hertz) below which the low band gain is applied.
+    pub fn low_frequency_hz(&self) -> f32 {
+        self.low_frequency_hz
+    }
+
+    /// Sets the gain of the low band.
+    pub fn set_low_gain(&mut self, gain: f32) {
+        self.low_gain = gain.max(0.0);
+        self.update();
+    }
+
+    /// Returns the gain of the low band.
+    pub fn low_gain(&self) -> f32 {
+        self.low_gain
+    }
+
+    /// Sets the center frequency (in Hertz) of the mid band.
+    pub fn set_mid_frequency_hz(&mut self, frequency: f32) {
+        self.mid_frequency_hz = frequency;
+        self.update();
+    }
+
+    /// Returns the center frequency (in Hertz) of the mid band.
+    pub fn mid_frequency_hz(&self) -> f32 {
+        self.mid_frequency_hz
+    }
+
+    /// Sets the gain of the mid band.
+    pub fn set_mid_gain(&mut self, gain: f32) {
+        // Peaking filter divides by the gain, so it cannot be zero.
+        self.mid_gain = gain.max(f32::EPSILON);
+        self.update();
+    }
+
+    /// Returns the gain of the mid band.
+    pub fn mid_gain(&self) -> f32 {
+        self.mid_gain
+    }
+
+    /// Sets the width of the mid band, the higher the value the narrower the band. See more info
+    /// [here](https://ccrma.stanford.edu/~jos/filters/Quality_Factor_Q.html)
+    pub fn set_mid_quality(&mut self, quality: f32) {
+        self.mid_quality = quality.max(0.01);
+        self.update();
+    }
+
+    /// Returns the width of the mid band.
+    pub fn mid_quality(&self) -> f32 {
+        self.mid_quality
+    }
+
+    /// Sets the frequency (in Hertz) above which the high band gain is applied.
+    pub fn set_high_frequency_hz(&mut self, frequency: f32) {
+        self.high_frequency_hz = frequency;
+        self.update();
+    }
+
+    /// Returns the frequency (in Hertz) above which the high band gain is applied.
+    pub fn high_frequency_hz(&self) -> f32 {
+        self.high_frequency_hz
+    }
+
+    /// Sets the gain of the high band.
+    pub fn set_high_gain(&mut self, gain: f32) {
+        self.high_gain = gain.max(0.0);
+        self.update();
+    }
+
+    /// Returns the gain of the high band.
+    pub fn high_gain(&self) -> f32 {
+        self.high_gain
+    }
+
+    fn update(&mut self) {
+        let sample_rate = SAMPLE_RATE as f32;
+        for channel in [&mut self.left, &mut self.right] {
+            channel.low.tune(
+                BiquadKind::LowShelf,
+                self.low_frequency_hz / sample_rate,
+                self.low_gain,
+                0.7,
+            );
+            channel.mid.tune(
+                BiquadKind::Peak,
+                self.mid_frequency_hz / sample_rate,
+                self.mid_gain,
+                self.mid_quality,
+            );
+            channel.high.tune(
+                BiquadKind::HighShelf,
+                self.high_frequency_hz / sample_rate,
+                self.high_gain,
+                0.7,
+            );
+        }
+    }
+}
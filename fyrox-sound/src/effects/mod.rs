@@ -1,6 +1,8 @@
 //! Contins everything related to audio effects that can be applied to an audio bus.
 
 use crate::{
+    effects::compressor::Compressor,
+    effects::equalizer::Equalizer,
     effects::filter::{
         AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect, HighShelfFilterEffect,
         LowPassFilterEffect, LowShelfFilterEffect,
@@ -10,6 +12,8 @@ use crate::{
 use fyrox_core::{reflect::prelude::*, uuid_provider, visitor::prelude::*};
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub mod compressor;
+pub mod equalizer;
 pub mod filter;
 pub mod reverb;
 
@@ -66,6 +70,10 @@ pub enum Effect {
     LowShelfFilter(LowShelfFilterEffect),
     /// See [`HighShelfFilterEffect`] docs for more info.
     HighShelfFilter(HighShelfFilterEffect),
+    /// See [`Equalizer`] docs for more info.
+    Equalizer(Equalizer),
+    /// See [`Compressor`] docs for more info.
+    Compressor(Compressor),
 }
 
 uuid_provider!(Effect = "fc52e441-d1ec-4881-937c-9e2e53a6d621");
@@ -91,6 +99,8 @@ macro_rules! static_dispatch {
             Effect::AllPassFilter(v) => v.$func($($args),*),
             Effect::LowShelfFilter(v) => v.$func($($args),*),
             Effect::HighShelfFilter(v) => v.$func($($args),*),
+            Effect::Equalizer(v) => v.$func($($args),*),
+            Effect::Compressor(v) => v.$func($($args),*),
         }
     };
 }
@@ -135,6 +135,8 @@ impl ChannelReverb {
 pub struct Reverb {
     dry: f32,
     wet: f32,
+    #[reflect(setter = "set_gain", min_value = 0.0)]
+    gain: f32,
     #[reflect(setter = "set_decay_time", min_value = 0.0)]
     decay_time: f32,
     #[reflect(setter = "set_fc", min_value = 0.0, max_value = 1.0)]
@@ -151,6 +153,10 @@ impl Visit for Reverb {
 
         self.dry.visit("Dry", &mut region)?;
         self.wet.visit("Wet", &mut region)?;
+        // Backward compatibility.
+        if self.gain.visit("Gain", &mut region).is_err() {
+            self.gain = 1.0;
+        }
         self.decay_time.visit("DecayTime", &mut region)?;
         self.fc.visit("Fc", &mut region)?;
 
@@ -180,7 +186,7 @@ impl Reverb {
     /// too much which will cause signal overflow.
     ///
     /// 2.0 here because left and right signals will be mixed together.
-    const GAIN: f32 = 1.0 / (2.0 * Self::TOTAL_FILTERS_COUNT);
+    const INPUT_GAIN: f32 = 1.0 / (2.0 * Self::TOTAL_FILTERS_COUNT);
 
     /// Creates new instance of reverb effect with cutoff frequency of ~11.2 kHz and
     /// 5 seconds decay time.
@@ -192,6 +198,7 @@ impl Reverb {
         Self {
             dry: 1.0,
             wet: 1.0,
+            gain: 1.0,
             decay_time: 2.0,
             fc,
             left: ChannelReverb::new(0, fc, Reverb::FEEDBACK, decay_time),
@@ -224,6 +231,17 @@ impl Reverb {
         self.wet
     }
 
+    /// Sets the gain of the reverberated part of the signal. It does not affect the dry part of the
+    /// signal, so it could be used to smoothly fade reverberation in and out. Default value is 1.0.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain.max(0.0);
+    }
+
+    /// Returns the gain of the reverberated part of the signal.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
     /// Sets actual sample rate of effect. It was designed to 44100 Hz sampling rate.
     /// TODO: This shouldn't be in public API.
     pub fn set_sample_rate(&mut self, sample_rate: usize) {
@@ -269,12 +287,12 @@ impl Reverb {
 
 impl EffectRenderTrait for Reverb {
     fn render(&mut self, input: &[(f32, f32)], mix_buf: &mut [(f32, f32)]) {
-        let wet = self.wet;
-        let dry = 1.0 - self.wet;
+        let wet = self.wet * self.gain;
+        let dry = (1.0 - self.wet) * self.gain;
 
         for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(input.iter()) {
             let mid = (left + right) * 0.5;
-            let input = mid * Self::GAIN;
+            let input = mid * Self::INPUT_GAIN;
 
             let processed_left = self.left.feed(input);
             let processed_right = self.right.feed(input);
@@ -17,6 +17,8 @@ use fyrox_core::{
 pub struct Listener {
     basis: Matrix3<f32>,
     position: Vector3<f32>,
+    #[visit(optional)]
+    velocity: Vector3<f32>,
 }
 
 impl Default for Listener {
@@ -30,6 +32,7 @@ impl Listener {
         Self {
             basis: Matrix3::identity(),
             position: Vector3::new(0.0, 0.0, 0.0),
+            velocity: Vector3::new(0.0, 0.0, 0.0),
         }
     }
 
@@ -86,6 +89,17 @@ impl Listener {
         self.position
     }
 
+    /// Sets current velocity (in units per second) in world space. It is used to calculate doppler
+    /// effect.
+    pub fn set_velocity(&mut self, velocity: Vector3<f32>) {
+        self.velocity = velocity;
+    }
+
+    /// Returns velocity of listener.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
+
     /// Returns up axis from basis.
     pub fn up_axis(&self) -> Vector3<f32> {
         self.basis.up()
@@ -31,7 +31,8 @@
 use crate::{
     buffer::{streaming::StreamingBuffer, SoundBuffer, SoundBufferResource},
     bus::AudioBusGraph,
-    context::DistanceModel,
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::filters::OnePole,
     error::SoundError,
     listener::Listener,
 };
@@ -121,6 +122,8 @@ pub struct SoundSource {
     #[reflect(min_value = 0.0, step = 0.05)]
     radius: f32,
     position: Vector3<f32>,
+    #[visit(optional)]
+    velocity: Vector3<f32>,
     #[reflect(min_value = 0.0, step = 0.05)]
     max_distance: f32,
     #[reflect(min_value = 0.0, step = 0.05)]
@@ -138,6 +141,21 @@ pub struct SoundSource {
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) prev_distance_gain: Option<f32>,
+    // Occlusion is driven by the owner of the source (usually the engine) every frame, so there is
+    // no need to save it.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_gain: f32,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_cutoff_frequency_hz: f32,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_filters: (OnePole, OnePole),
+    // Pitch multiplier caused by doppler effect, it is calculated by the context every frame.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) doppler_pitch: f64,
 }
 
 impl Default for SoundSource {
@@ -168,6 +186,11 @@ impl Default for SoundSource {
             prev_right_samples: Default::default(),
             prev_sampling_vector: Vector3::new(0.0, 0.0, 1.0),
             prev_distance_gain: None,
+            occlusion_gain: 1.0,
+            occlusion_cutoff_frequency_hz: f32::MAX,
+            occlusion_filters: Default::default(),
+            doppler_pitch: 1.0,
+            velocity: Vector3::new(0.0, 0.0, 0.0),
         }
     }
 }
@@ -356,6 +379,18 @@ impl SoundSource {
         self.position
     }
 
+    /// Sets velocity (in units per second) of the source in world space. It is used to calculate
+    /// doppler effect.
+    pub fn set_velocity(&mut self, velocity: Vector3<f32>) -> &mut Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Returns velocity of the source.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
+
     /// Sets radius of imaginable sphere around source in which no distance attenuation is applied.
     pub fn set_radius(&mut self, radius: f32) -> &mut Self {
         self.radius = radius;
@@ -405,6 +440,45 @@ impl SoundSource {
         &self.bus
     }
 
+    /// Sets occlusion parameters of the sound source. Gain is an additional attenuation factor
+    /// (`1.0` - no attenuation) and cutoff frequency (in Hertz) defines a low pass filter that is
+    /// applied to the source's samples to muffle it. Use `f32::MAX` cutoff frequency to disable
+    /// the filter. These parameters are usually set by the engine from the results of physics
+    /// raycasts between the listener and the source.
+    pub fn set_occlusion(&mut self, gain: f32, cutoff_frequency_hz: f32) {
+        self.occlusion_gain = gain.max(0.0);
+        self.occlusion_cutoff_frequency_hz = cutoff_frequency_hz.max(0.0);
+        let fc = self.occlusion_cutoff_frequency_hz / SAMPLE_RATE as f32;
+        self.occlusion_filters.0.set_fc(fc);
+        self.occlusion_filters.1.set_fc(fc);
+    }
+
+    /// Returns current occlusion gain of the sound source.
+    pub fn occlusion_gain(&self) -> f32 {
+        self.occlusion_gain
+    }
+
+    /// Returns current cutoff frequency (in Hertz) of the occlusion low pass filter.
+    pub fn occlusion_cutoff_frequency_hz(&self) -> f32 {
+        self.occlusion_cutoff_frequency_hz
+    }
+
+    fn apply_occlusion(&mut self) {
+        let filtered = self.occlusion_cutoff_frequency_hz < SAMPLE_RATE as f32 * 0.5;
+        if !filtered && self.occlusion_gain == 1.0 {
+            return;
+        }
+
+        for (left, right) in self.frame_samples.iter_mut() {
+            if filtered {
+                *left = self.occlusion_filters.0.feed(*left);
+                *right = self.occlusion_filters.1.feed(*right);
+            }
+            *left *= self.occlusion_gain;
+            *right *= self.occlusion_gain;
+        }
+    }
+
     // Distance models were taken from OpenAL Specification because it looks like they're
     // standard in industry and there is no need to reinvent it.
     // https://www.openal.org/documentation/openal-1.1-specification.pdf
@@ -429,6 +503,34 @@ impl SoundSource {
         }
     }
 
+    // Doppler effect formula was taken from OpenAL Specification as well.
+    pub(crate) fn calculate_doppler_pitch(
+        &self,
+        listener: &Listener,
+        doppler_factor: f32,
+        speed_of_sound: f32,
+    ) -> f64 {
+        if doppler_factor <= 0.0 || speed_of_sound <= 0.0 {
+            return 1.0;
+        }
+
+        let Some(direction) = (listener.position() - self.position).try_normalize(f32::EPSILON)
+        else {
+            return 1.0;
+        };
+
+        // Velocities cannot exceed the speed of sound, otherwise the pitch will be negative.
+        let max_speed = speed_of_sound / doppler_factor;
+        let listener_speed = listener.velocity().dot(&direction).min(max_speed);
+        let source_speed = self.velocity.dot(&direction).min(max_speed);
+
+        let pitch = (speed_of_sound - doppler_factor * listener_speed)
+            / (speed_of_sound - doppler_factor * source_speed).max(f32::EPSILON);
+
+        // Non-spatial sounds are not affected by doppler effect.
+        (1.0 + (pitch - 1.0) * self.spatial_blend) as f64
+    }
+
     pub(crate) fn calculate_panning(&self, listener: &Listener) -> f32 {
         (listener.position() - self.position)
             .try_normalize(f32::EPSILON)
@@ -468,16 +570,25 @@ impl SoundSource {
                     streaming.time_seek(time.clamp(Duration::from_secs(0), streaming.duration()));
                 }
                 // Set absolute position first.
-                self.playback_pos = (time.as_secs_f64() * buffer.sample_rate as f64)
-                    .clamp(0.0, buffer.duration().as_secs_f64());
+                self.playback_pos = (time.as_secs_f64() * buffer.sample_rate as f64).clamp(
+                    0.0,
+                    buffer.channel_duration_in_samples().saturating_sub(1) as f64,
+                );
                 // Then adjust buffer read position.
                 self.buf_read_pos = match *buffer {
                     SoundBuffer::Streaming(ref mut streaming) => {
                         // Make sure to load correct data into buffer from decoder.
                         streaming.read_next_block();
+                        if streaming.samples.is_empty() {
+                            // Seek past the end, start over.
+                            let _ = streaming.rewind();
+                            streaming.read_next_block();
+                            self.playback_pos = 0.0;
+                        }
                         // Streaming sources has different buffer read position because
-                        // buffer contains only small portion of data.
-                        self.playback_pos % (StreamingBuffer::STREAM_SAMPLE_COUNT as f64)
+                        // buffer contains only small portion of data, that starts right at
+                        // the seek position.
+                        self.playback_pos.fract()
                     }
                     SoundBuffer::Generic(_) => self.playback_pos,
                 };
@@ -506,6 +617,8 @@ impl SoundSource {
         }
         // Fill the remaining part of frame_samples.
         self.frame_samples.resize(amount, (0.0, 0.0));
+
+        self.apply_occlusion();
     }
 
     fn render_playing(&mut self, buffer: &mut SoundBuffer, amount: usize) {
@@ -520,14 +633,19 @@ impl SoundSource {
             let len = buffer.samples().len();
             let mut end_reached = true;
             if let SoundBuffer::Streaming(streaming) = buffer {
-                // Means that this is the last available block.
-                if len != channel_count * StreamingBuffer::STREAM_SAMPLE_COUNT {
+                self.prev_buffer_sample = get_last_sample(streaming);
+                end_reached = streaming.is_last_block();
+                if end_reached {
                     let _ = streaming.rewind();
-                } else {
-                    end_reached = false;
                 }
-                self.prev_buffer_sample = get_last_sample(streaming);
                 streaming.read_next_block();
+                if !end_reached && streaming.samples.is_empty() {
+                    // Length of the data source is a multiple of the block size, so the previous
+                    // block was the last one. Start over right away, so looping stays seamless.
+                    end_reached = true;
+                    let _ = streaming.rewind();
+                    streaming.read_next_block();
+                }
             }
             if end_reached {
                 self.buf_read_pos = 0.0;
@@ -545,7 +663,7 @@ impl SoundSource {
     // Renders until the end of the block or until amount samples is written and returns
     // the number of written samples.
     fn render_until_block_end(&mut self, buffer: &mut SoundBuffer, mut amount: usize) -> usize {
-        let step = self.pitch * self.resampling_multiplier;
+        let step = self.pitch * self.doppler_pitch * self.resampling_multiplier;
         if step == 1.0 {
             if self.buf_read_pos < 0.0 {
                 // This can theoretically happen if we change pitch on the fly.
@@ -862,7 +980,19 @@ impl SoundSourceBuilder {
             prev_left_samples: Default::default(),
             prev_right_samples: Default::default(),
             bus: self.bus,
-            ..Default::default()
+            buf_read_pos: 0.0,
+            playback_pos: 0.0,
+            resampling_multiplier: 1.0,
+            last_left_gain: None,
+            last_right_gain: None,
+            prev_buffer_sample: (0.0, 0.0),
+            velocity: Vector3::new(0.0, 0.0, 0.0),
+            prev_sampling_vector: Vector3::new(0.0, 0.0, 1.0),
+            prev_distance_gain: None,
+            occlusion_gain: 1.0,
+            occlusion_cutoff_frequency_hz: f32::MAX,
+            occlusion_filters: Default::default(),
+            doppler_pitch: 1.0,
         };
 
         source.set_buffer(self.buffer)?;
@@ -871,3 +1001,150 @@ impl SoundSourceBuilder {
         Ok(source)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, RawStreamingDataSource, SoundBuffer},
+        context::{SoundContext, SAMPLE_RATE},
+        error::SoundError,
+        listener::Listener,
+        source::{SoundSource, SoundSourceBuilder, Status},
+    };
+    use fyrox_core::algebra::Vector3;
+    use fyrox_resource::{untyped::ResourceKind, Resource};
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct Ramp {
+        position: usize,
+        length: usize,
+    }
+
+    impl Iterator for Ramp {
+        type Item = f32;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.position < self.length {
+                self.position += 1;
+                Some((self.position - 1) as f32)
+            } else {
+                None
+            }
+        }
+    }
+
+    impl RawStreamingDataSource for Ramp {
+        fn sample_rate(&self) -> usize {
+            SAMPLE_RATE as usize
+        }
+
+        fn channel_count(&self) -> usize {
+            1
+        }
+
+        fn rewind(&mut self) -> Result<(), SoundError> {
+            self.position = 0;
+            Ok(())
+        }
+
+        fn time_seek(&mut self, duration: Duration) {
+            self.position = (duration.as_secs_f64() * SAMPLE_RATE as f64) as usize;
+        }
+
+        fn channel_duration_in_samples(&self) -> usize {
+            self.length
+        }
+    }
+
+    #[test]
+    fn test_streaming_looping_is_seamless() {
+        // Length of the source is a multiple of the block size, which is an edge case for
+        // the end of stream detection.
+        let buffer = SoundBuffer::raw_streaming_with_block_size(
+            DataSource::RawStreaming(Box::new(Ramp {
+                position: 0,
+                length: 8,
+            })),
+            4,
+        )
+        .unwrap();
+
+        let mut source = SoundSourceBuilder::new()
+            .with_buffer(Resource::new_ok(ResourceKind::Embedded, buffer))
+            .with_looping(true)
+            .with_status(Status::Playing)
+            .build()
+            .unwrap();
+
+        source.render(20);
+
+        let expected = (0..20).map(|i| (i % 8) as f32).collect::<Vec<_>>();
+        let rendered = source
+            .frame_samples()
+            .iter()
+            .map(|(left, _)| *left)
+            .collect::<Vec<_>>();
+        assert_eq!(rendered, expected);
+        assert_eq!(source.status(), Status::Playing);
+    }
+
+    #[test]
+    fn test_occlusion() {
+        let buffer = SoundBuffer::raw_streaming_with_block_size(
+            DataSource::RawStreaming(Box::new(Ramp {
+                position: 0,
+                length: 8,
+            })),
+            4,
+        )
+        .unwrap();
+
+        let mut source = SoundSourceBuilder::new()
+            .with_buffer(Resource::new_ok(ResourceKind::Embedded, buffer))
+            .with_status(Status::Playing)
+            .build()
+            .unwrap();
+        assert_eq!(source.occlusion_gain(), 1.0);
+
+        source.set_occlusion(0.5, f32::MAX);
+        source.render(8);
+
+        let expected = (0..8).map(|i| i as f32 * 0.5).collect::<Vec<_>>();
+        let rendered = source
+            .frame_samples()
+            .iter()
+            .map(|(left, _)| *left)
+            .collect::<Vec<_>>();
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn test_doppler_pitch() {
+        let listener = Listener::new();
+        let mut source = SoundSource::default();
+        source.set_position(Vector3::new(0.0, 0.0, 10.0));
+
+        let pitch = |source: &SoundSource| {
+            source.calculate_doppler_pitch(
+                &listener,
+                SoundContext::DEFAULT_DOPPLER_FACTOR,
+                SoundContext::DEFAULT_SPEED_OF_SOUND,
+            )
+        };
+
+        assert_eq!(pitch(&source), 1.0);
+
+        // Approaching source sounds higher.
+        source.set_velocity(Vector3::new(0.0, 0.0, -30.0));
+        assert!(pitch(&source) > 1.0);
+
+        // Receding source sounds lower.
+        source.set_velocity(Vector3::new(0.0, 0.0, 30.0));
+        assert!(pitch(&source) < 1.0);
+
+        // Non-spatial sounds are not affected.
+        source.set_spatial_blend(0.0);
+        assert_eq!(pitch(&source), 1.0);
+    }
+}
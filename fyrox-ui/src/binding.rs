@@ -0,0 +1,547 @@
+//! Data binding allows you to bind widget properties (text, visibility, progress) to observable values or
+//! to reflected fields of an object (for example, a script). Bound widgets are updated automatically when
+//! their source changes, which removes the need of manual synchronization code. See [`Bindings`] docs for
+//! more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{parking_lot::Mutex, pool::Handle, reflect::prelude::*},
+    message::{MessageDirection, UiMessage},
+    progress_bar::ProgressBarMessage,
+    text::TextMessage,
+    widget::WidgetMessage,
+    UiNode, UserInterface,
+};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    fmt::{Debug, Display, Formatter},
+    sync::Arc,
+};
+
+/// A value, that could be shown by a bound property of a widget.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoundValue {
+    /// A string.
+    Text(String),
+    /// A boolean value.
+    Bool(bool),
+    /// A numeric value.
+    Number(f64),
+}
+
+impl Display for BoundValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundValue::Text(text) => write!(f, "{text}"),
+            BoundValue::Bool(value) => write!(f, "{value}"),
+            BoundValue::Number(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<String> for BoundValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for BoundValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<bool> for BoundValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for BoundValue {
+                fn from(value: $ty) -> Self {
+                    Self::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl From<f64> for BoundValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl_from_number!(f32, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+fn bound_value_from_any(any: &dyn Any) -> Option<BoundValue> {
+    macro_rules! try_downcast {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = any.downcast_ref::<$ty>() {
+                    return Some(BoundValue::from(*value));
+                }
+            )*
+        };
+    }
+
+    if let Some(text) = any.downcast_ref::<String>() {
+        return Some(BoundValue::Text(text.clone()));
+    }
+
+    try_downcast!(bool, f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+    None
+}
+
+impl BoundValue {
+    /// Tries to convert a reflected value to a bound value. Strings, booleans and numbers are supported,
+    /// [`None`] is returned for any other type.
+    pub fn from_reflect(value: &dyn Reflect) -> Option<Self> {
+        let mut result = None;
+        value.as_any(&mut |any| result = bound_value_from_any(any));
+        result
+    }
+
+    /// Converts the value to a boolean. Numbers are treated as `true` if they're non-zero, strings - if
+    /// they're non-empty.
+    pub fn as_bool(&self) -> bool {
+        match self {
+            BoundValue::Text(text) => !text.is_empty(),
+            BoundValue::Bool(value) => *value,
+            BoundValue::Number(value) => *value != 0.0,
+        }
+    }
+
+    /// Returns the numeric value, or [`None`] if the value is not a number.
+    pub fn as_number(&self) -> Option<f64> {
+        if let BoundValue::Number(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+}
+
+/// A property of a widget, that could be bound to a source.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BindingTarget {
+    /// Text of a [`crate::text::Text`] or [`crate::text_box::TextBox`] widget. Any value is converted
+    /// to a string.
+    Text,
+    /// Visibility of any widget. See [`BoundValue::as_bool`] for conversion rules.
+    Visibility,
+    /// Progress of a [`crate::progress_bar::ProgressBar`] widget. The value must be a number, it is
+    /// clamped to `[0; 1]` range.
+    Progress,
+}
+
+impl BindingTarget {
+    fn make_message(self, widget: Handle<UiNode>, value: &BoundValue) -> Option<UiMessage> {
+        match self {
+            BindingTarget::Text => Some(TextMessage::text(
+                widget,
+                MessageDirection::ToWidget,
+                value.to_string(),
+            )),
+            BindingTarget::Visibility => Some(WidgetMessage::visibility(
+                widget,
+                MessageDirection::ToWidget,
+                value.as_bool(),
+            )),
+            BindingTarget::Progress => value.as_number().map(|progress| {
+                ProgressBarMessage::progress(
+                    widget,
+                    MessageDirection::ToWidget,
+                    progress.clamp(0.0, 1.0) as f32,
+                )
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ObservableState<T> {
+    value: T,
+    version: u64,
+}
+
+/// Observable value is a shared value, that tracks its modifications. Clones of an observable value
+/// share the same value, so one clone could be stored in game code while the other one is bound to a
+/// widget (see [`Bindings::bind`]).
+pub struct Observable<T> {
+    state: Arc<Mutex<ObservableState<T>>>,
+}
+
+impl<T> Clone for Observable<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Observable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Observable({:?})", self.state.lock().value)
+    }
+}
+
+impl<T: Default> Default for Observable<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> Observable<T> {
+    /// Creates a new observable value.
+    pub fn new(value: T) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ObservableState { value, version: 0 })),
+        }
+    }
+
+    /// Sets a new value. Bound widgets are updated only if the new value differs from the current one.
+    pub fn set(&self, value: T)
+    where
+        T: PartialEq,
+    {
+        let mut state = self.state.lock();
+        if state.value != value {
+            state.value = value;
+            state.version += 1;
+        }
+    }
+
+    /// Modifies the value using the given function. The value is always treated as modified.
+    pub fn modify<F: FnOnce(&mut T)>(&self, func: F) {
+        let mut state = self.state.lock();
+        func(&mut state.value);
+        state.version += 1;
+    }
+
+    /// Returns a copy of the current value.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.state.lock().value.clone()
+    }
+
+    /// Returns the version of the value, it is increased every time when the value is modified.
+    pub fn version(&self) -> u64 {
+        self.state.lock().version
+    }
+}
+
+trait ObservableSource: Send + Sync + Debug {
+    fn version(&self) -> u64;
+
+    fn value(&self) -> BoundValue;
+}
+
+impl<T> ObservableSource for Mutex<ObservableState<T>>
+where
+    T: Clone + Into<BoundValue> + Send + Debug,
+{
+    fn version(&self) -> u64 {
+        self.lock().version
+    }
+
+    fn value(&self) -> BoundValue {
+        self.lock().value.clone().into()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum BindingSource {
+    Observable(Arc<dyn ObservableSource>),
+    Reflected(String),
+}
+
+#[derive(Clone, Debug)]
+struct Binding {
+    widget: Handle<UiNode>,
+    target: BindingTarget,
+    source: BindingSource,
+    /// Version of the observable value, that was sent to the widget last time.
+    version: Cell<Option<u64>>,
+    /// A value, that was sent to the widget last time.
+    value: RefCell<Option<BoundValue>>,
+}
+
+/// A set of bindings between widget properties and their sources. Every time when [`Bindings::update`]
+/// is called, it checks the sources and sends respective messages to the bound widgets if the sources
+/// have changed. Every user interface has its own set of bindings (see [`UserInterface::bindings_mut`]),
+/// which is updated automatically, but it supports observable values only. Bindings to reflected fields
+/// must be stored by their owner, which must update them manually.
+///
+/// ## Example
+///
+/// The following example shows how to bind a text and a progress bar to observable values. Bound widgets
+/// will be updated automatically when the values change.
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     binding::{BindingTarget, Observable},
+/// #     core::pool::Handle,
+/// #     UiNode, UserInterface,
+/// # };
+/// fn bind_hud(
+///     ui: &mut UserInterface,
+///     score_text: Handle<UiNode>,
+///     health_bar: Handle<UiNode>,
+/// ) -> (Observable<u32>, Observable<f32>) {
+///     let score = Observable::new(0u32);
+///     let health = Observable::new(1.0f32);
+///
+///     ui.bindings_mut()
+///         .bind(score_text, BindingTarget::Text, &score)
+///         .bind(health_bar, BindingTarget::Progress, &health);
+///
+///     // Game code keeps the values and modifies them, the widgets will be synchronized on the next
+///     // update of the user interface.
+///     score.set(100);
+///     health.set(0.5);
+///
+///     (score, health)
+/// }
+/// ```
+///
+/// Fields of an object (for example, a script) could be bound using their reflection paths. Such
+/// bindings must be updated manually, the object is passed to [`Bindings::update`]:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     binding::{BindingTarget, Bindings},
+/// #     core::{pool::Handle, reflect::prelude::*},
+/// #     UiNode, UserInterface,
+/// # };
+/// #[derive(Reflect, Debug, Default)]
+/// struct Player {
+///     name: String,
+///     health: f32,
+///     #[reflect(hidden)]
+///     bindings: Bindings,
+/// }
+///
+/// impl Player {
+///     fn bind(&mut self, name_text: Handle<UiNode>, health_bar: Handle<UiNode>) {
+///         self.bindings
+///             .bind_reflected(name_text, BindingTarget::Text, "name")
+///             .bind_reflected(health_bar, BindingTarget::Progress, "health");
+///     }
+///
+///     fn update(&self, ui: &UserInterface) {
+///         self.bindings.update(ui, Some(self));
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Bindings {
+    bindings: Vec<Binding>,
+}
+
+impl Bindings {
+    /// Creates a new empty set of bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&mut self, widget: Handle<UiNode>, target: BindingTarget, source: BindingSource) {
+        self.bindings.push(Binding {
+            widget,
+            target,
+            source,
+            version: Default::default(),
+            value: Default::default(),
+        });
+    }
+
+    /// Binds the property of the widget to the given observable value.
+    pub fn bind<T>(
+        &mut self,
+        widget: Handle<UiNode>,
+        target: BindingTarget,
+        observable: &Observable<T>,
+    ) -> &mut Self
+    where
+        T: Clone + Into<BoundValue> + Send + Debug + 'static,
+    {
+        self.add(
+            widget,
+            target,
+            BindingSource::Observable(observable.state.clone()),
+        );
+        self
+    }
+
+    /// Binds the property of the widget to a field of an object at the given reflection path (for
+    /// example, `stats.health`). The object is passed to [`Bindings::update`].
+    pub fn bind_reflected<P: Into<String>>(
+        &mut self,
+        widget: Handle<UiNode>,
+        target: BindingTarget,
+        path: P,
+    ) -> &mut Self {
+        self.add(widget, target, BindingSource::Reflected(path.into()));
+        self
+    }
+
+    /// Removes every binding of the given widget.
+    pub fn unbind(&mut self, widget: Handle<UiNode>) {
+        self.bindings.retain(|binding| binding.widget != widget);
+    }
+
+    /// Removes every binding.
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+    }
+
+    /// Returns `true` if there are no bindings.
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Forces every binding to send the value of its source on the next update, even if the source
+    /// hasn't changed. It could be useful if bound widgets were changed by something else.
+    pub fn invalidate(&self) {
+        for binding in self.bindings.iter() {
+            binding.version.set(None);
+            binding.value.replace(None);
+        }
+    }
+
+    /// Checks the sources of the bindings and sends messages to the widgets, which sources have changed.
+    /// The given object is used as a source for bindings to reflected fields, such bindings are skipped
+    /// if there's no object. Bindings of deleted widgets are skipped as well.
+    pub fn update(&self, ui: &UserInterface, object: Option<&dyn Reflect>) {
+        for binding in self.bindings.iter() {
+            if !ui.nodes().is_valid_handle(binding.widget) {
+                continue;
+            }
+
+            let value = match binding.source {
+                BindingSource::Observable(ref source) => {
+                    let version = source.version();
+                    if binding.version.get() == Some(version) {
+                        continue;
+                    }
+                    binding.version.set(Some(version));
+                    source.value()
+                }
+                BindingSource::Reflected(ref path) => {
+                    let Some(object) = object else {
+                        continue;
+                    };
+                    let mut value = None;
+                    object.resolve_path(path, &mut |result| {
+                        value = result.ok().and_then(BoundValue::from_reflect)
+                    });
+                    let Some(value) = value else {
+                        continue;
+                    };
+                    value
+                }
+            };
+
+            if binding.value.borrow().as_ref() == Some(&value) {
+                continue;
+            }
+
+            if let Some(message) = binding.target.make_message(binding.widget, &value) {
+                ui.send_message(message);
+            }
+
+            binding.value.replace(Some(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        binding::{BindingTarget, Bindings, Observable},
+        core::{algebra::Vector2, reflect::prelude::*},
+        message::MessageDirection,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+
+    #[derive(Reflect, Debug, Default)]
+    struct Stats {
+        name: String,
+    }
+
+    #[test]
+    fn test_bindings() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let text = TextBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+
+        let score = Observable::new(0u32);
+        let mut bindings = Bindings::new();
+        bindings.bind(text, BindingTarget::Text, &score);
+
+        // The initial value is always sent.
+        bindings.update(&ui, None);
+        assert_eq!(
+            ui.poll_message(),
+            Some(TextMessage::text(
+                text,
+                MessageDirection::ToWidget,
+                "0".to_string()
+            ))
+        );
+
+        // Nothing has changed.
+        score.set(0);
+        bindings.update(&ui, None);
+        assert_eq!(ui.poll_message(), None);
+
+        score.set(10);
+        bindings.update(&ui, None);
+        assert_eq!(
+            ui.poll_message(),
+            Some(TextMessage::text(
+                text,
+                MessageDirection::ToWidget,
+                "10".to_string()
+            ))
+        );
+
+        let mut stats = Stats {
+            name: "Player".to_string(),
+        };
+        bindings.clear();
+        bindings.bind_reflected(text, BindingTarget::Text, "name");
+        bindings.update(&ui, Some(&stats));
+        assert_eq!(
+            ui.poll_message(),
+            Some(TextMessage::text(
+                text,
+                MessageDirection::ToWidget,
+                "Player".to_string()
+            ))
+        );
+
+        bindings.update(&ui, Some(&stats));
+        assert_eq!(ui.poll_message(), None);
+
+        stats.name = "Enemy".to_string();
+        bindings.update(&ui, Some(&stats));
+        assert_eq!(
+            ui.poll_message(),
+            Some(TextMessage::text(
+                text,
+                MessageDirection::ToWidget,
+                "Enemy".to_string()
+            ))
+        );
+    }
+}
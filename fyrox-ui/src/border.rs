@@ -6,13 +6,14 @@
 use crate::{
     core::{
         algebra::Vector2, math::Rect, pool::Handle, reflect::prelude::*, scope_profile,
-        type_traits::prelude::*, visitor::prelude::*,
+        sstorage::ImmutableString, type_traits::prelude::*, visitor::prelude::*,
     },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
+    style::Style,
     widget::{Widget, WidgetBuilder},
-    BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface, BRUSH_PRIMARY,
+    BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface,
 };
 use fyrox_core::variable::InheritableVariable;
 use std::ops::{Deref, DerefMut};
@@ -211,8 +212,10 @@ impl BorderBuilder {
 
     /// Creates a [`Border`] widget, but does not add it to the user interface. Also see [`Self::build`] docs.
     pub fn build_border(mut self) -> Border {
-        if self.widget_builder.foreground.is_none() {
-            self.widget_builder.foreground = Some(BRUSH_PRIMARY);
+        if self.widget_builder.foreground.is_none()
+            && self.widget_builder.foreground_style.is_none()
+        {
+            self.widget_builder.foreground_style = Some(ImmutableString::new(Style::BRUSH_PRIMARY));
         }
         Border {
             widget: self.widget_builder.build(),
@@ -2,8 +2,8 @@
 //! more info.
 
 use crate::{
-    core::pool::Handle, font::FontResource, message::UiMessage, RestrictionEntry, UiNode,
-    UserInterface,
+    core::pool::Handle, font::FontResource, message::UiMessage, style::StyleResource,
+    RestrictionEntry, UiNode, UserInterface,
 };
 use fyrox_graph::SceneGraph;
 use std::{
@@ -95,6 +95,11 @@ impl<'a> BuildContext<'a> {
         self.ui.default_font.clone()
     }
 
+    /// Returns current style of the UI. Widget builders should take the values of their styled properties from it.
+    pub fn style(&self) -> &StyleResource {
+        &self.ui.style
+    }
+
     /// Returns current message sender of the UI, that is used for message passing mechanism. You can
     /// send messages for your widgets inside your builders, however this has limited use and should
     /// be avoided in the favor of explicit state modification to not overload message pipeline.
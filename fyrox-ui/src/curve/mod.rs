@@ -1,5 +1,6 @@
 use crate::{
     brush::Brush,
+    check_box::{CheckBoxBuilder, CheckBoxMessage},
     core::{
         algebra::{Matrix3, Point2, SimdPartialOrd, Vector2, Vector3},
         color::Color,
@@ -38,6 +39,9 @@ pub mod key;
 #[derive(Debug, Clone, PartialEq)]
 pub enum CurveEditorMessage {
     Sync(Curve),
+    /// Emitted by the curve editor (with [`MessageDirection::FromWidget`]) when the curve was
+    /// changed by the user. Contains the new curve.
+    Changed(Curve),
     ViewPosition(Vector2<f32>),
     Zoom(Vector2<f32>),
     ZoomToFit {
@@ -46,6 +50,8 @@ pub enum CurveEditorMessage {
         after_layout: bool,
     },
     HighlightZones(Vec<HighlightZone>),
+    /// Enables or disables snapping of the keys to the grid.
+    SnapToGrid(bool),
 
     // Internal messages. Use only when you know what you're doing.
     // These are internal because you must use Sync message to request changes
@@ -60,10 +66,12 @@ pub enum CurveEditorMessage {
 
 impl CurveEditorMessage {
     define_constructor!(CurveEditorMessage:Sync => fn sync(Curve), layout: false);
+    define_constructor!(CurveEditorMessage:Changed => fn changed(Curve), layout: false);
     define_constructor!(CurveEditorMessage:ViewPosition => fn view_position(Vector2<f32>), layout: false);
     define_constructor!(CurveEditorMessage:Zoom => fn zoom(Vector2<f32>), layout: false);
     define_constructor!(CurveEditorMessage:ZoomToFit => fn zoom_to_fit(after_layout: bool), layout: true);
     define_constructor!(CurveEditorMessage:HighlightZones => fn hightlight_zones(Vec<HighlightZone>), layout: false);
+    define_constructor!(CurveEditorMessage:SnapToGrid => fn snap_to_grid(bool), layout: false);
     // Internal. Use only when you know what you're doing.
     define_constructor!(CurveEditorMessage:RemoveSelection => fn remove_selection(), layout: false);
     define_constructor!(CurveEditorMessage:ChangeSelectedKeysKind => fn change_selected_keys_kind(CurveKeyKind), layout: false);
@@ -126,6 +134,8 @@ pub struct CurveEditor {
     #[visit(skip)]
     #[reflect(hidden)]
     zoom_to_fit_timer: Option<usize>,
+    #[visit(optional)]
+    snap_to_grid: bool,
 }
 
 crate::define_widget_deref!(CurveEditor);
@@ -140,6 +150,7 @@ struct ContextMenu {
     make_linear: Handle<UiNode>,
     make_cubic: Handle<UiNode>,
     zoom_to_fit: Handle<UiNode>,
+    snap_to_grid: Handle<UiNode>,
     key_properties: Handle<UiNode>,
     key_value: Handle<UiNode>,
     key_location: Handle<UiNode>,
@@ -232,8 +243,12 @@ impl Control for CurveEditor {
                                 } => {
                                     let local_delta = local_mouse_pos - initial_mouse_pos;
                                     for entry in entries {
+                                        let position = self.snap_position(
+                                            entry.initial_position + local_delta,
+                                            ui.keyboard_modifiers().shift,
+                                        );
                                         if let Some(key) = self.key_container.key_mut(entry.key) {
-                                            key.position = entry.initial_position + local_delta;
+                                            key.position = position;
                                         }
                                     }
                                     self.sort_keys();
@@ -496,7 +511,10 @@ impl Control for CurveEditor {
                             self.change_selected_keys_kind(kind.clone(), ui);
                         }
                         CurveEditorMessage::AddKey(screen_pos) => {
-                            let local_pos = self.point_to_local_space(*screen_pos);
+                            let local_pos = self.snap_position(
+                                self.point_to_local_space(*screen_pos),
+                                ui.keyboard_modifiers().shift,
+                            );
                             self.key_container.add(CurveKeyView {
                                 position: local_pos,
                                 kind: CurveKeyKind::Linear,
@@ -524,6 +542,19 @@ impl Control for CurveEditor {
                         CurveEditorMessage::HighlightZones(zones) => {
                             self.highlight_zones = zones.clone();
                         }
+                        CurveEditorMessage::SnapToGrid(snap) => {
+                            if self.snap_to_grid != *snap {
+                                self.snap_to_grid = *snap;
+                                ui.send_message(CheckBoxMessage::checked(
+                                    self.context_menu.snap_to_grid,
+                                    MessageDirection::ToWidget,
+                                    Some(*snap),
+                                ));
+                                ui.send_message(message.reverse());
+                            }
+                        }
+                        // Outgoing message.
+                        CurveEditorMessage::Changed(_) => {}
                     }
                 }
             }
@@ -572,6 +603,16 @@ impl Control for CurveEditor {
                     false,
                 ));
             }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.context_menu.snap_to_grid
+                && message.direction() == MessageDirection::FromWidget
+            {
+                ui.send_message(CurveEditorMessage::snap_to_grid(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    *value,
+                ));
+            }
         } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
             if message.direction() == MessageDirection::FromWidget && !message.handled() {
                 if message.destination() == self.context_menu.key_value {
@@ -625,6 +666,14 @@ fn round_to_step(x: f32, step: f32) -> f32 {
     x - x % step
 }
 
+fn snap_to_step(x: f32, step: f32) -> f32 {
+    if step > 0.0 {
+        (x / step).round() * step
+    } else {
+        x
+    }
+}
+
 impl CurveEditor {
     #[allow(clippy::let_and_return)] // Improves readability
     fn set_view_position(&mut self, position: Vector2<f32>) {
@@ -729,6 +778,19 @@ impl CurveEditor {
             .coords
     }
 
+    /// Snaps the given point in local space to the nearest grid node, if the snapping is enabled.
+    /// `invert` temporarily flips the snapping mode (i.e. when Shift key is held).
+    fn snap_position(&self, position: Vector2<f32>, invert: bool) -> Vector2<f32> {
+        if self.snap_to_grid != invert {
+            Vector2::new(
+                snap_to_step(position.x, self.grid_size.x / self.zoom.x),
+                snap_to_step(position.y, self.grid_size.y / self.zoom.y),
+            )
+        } else {
+            position
+        }
+    }
+
     fn sort_keys(&mut self) {
         self.key_container.sort_keys();
     }
@@ -890,7 +952,7 @@ impl CurveEditor {
     }
 
     fn send_curve(&self, ui: &UserInterface) {
-        ui.send_message(CurveEditorMessage::sync(
+        ui.send_message(CurveEditorMessage::changed(
             self.handle,
             MessageDirection::FromWidget,
             self.key_container.curve(),
@@ -1228,6 +1290,7 @@ pub struct CurveEditorBuilder {
     min_zoom: Vector2<f32>,
     max_zoom: Vector2<f32>,
     highlight_zones: Vec<HighlightZone>,
+    snap_to_grid: bool,
 }
 
 impl CurveEditorBuilder {
@@ -1244,6 +1307,7 @@ impl CurveEditorBuilder {
             min_zoom: Vector2::new(0.001, 0.001),
             max_zoom: Vector2::new(1000.0, 1000.0),
             highlight_zones: Default::default(),
+            snap_to_grid: false,
         }
     }
 
@@ -1298,6 +1362,13 @@ impl CurveEditorBuilder {
         self
     }
 
+    /// Enables or disables snapping of the keys to the grid. Snapping could be temporarily
+    /// inverted by holding Shift key while dragging the keys.
+    pub fn with_snap_to_grid(mut self, snap_to_grid: bool) -> Self {
+        self.snap_to_grid = snap_to_grid;
+        self
+    }
+
     pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let keys = KeyContainer::from(&self.curve);
 
@@ -1308,6 +1379,7 @@ impl CurveEditorBuilder {
         let make_cubic;
         let key;
         let zoom_to_fit;
+        let snap_to_grid;
         let key_properties;
         let key_value;
         let key_location;
@@ -1412,6 +1484,23 @@ impl CurveEditorBuilder {
                                 .with_content(MenuItemContent::text("Zoom To Fit"))
                                 .build(ctx);
                             zoom_to_fit
+                        })
+                        .with_child({
+                            snap_to_grid = CheckBoxBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .checked(Some(self.snap_to_grid))
+                            .with_content(
+                                TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_vertical_alignment(VerticalAlignment::Center)
+                                        .with_margin(Thickness::left(2.0)),
+                                )
+                                .with_text("Snap To Grid")
+                                .build(ctx),
+                            )
+                            .build(ctx);
+                            snap_to_grid
                         }),
                 )
                 .build(ctx),
@@ -1456,6 +1545,7 @@ impl CurveEditorBuilder {
                 make_cubic,
                 key,
                 zoom_to_fit,
+                snap_to_grid,
                 key_properties,
                 key_value,
                 key_location,
@@ -1468,6 +1558,7 @@ impl CurveEditorBuilder {
             max_zoom: self.max_zoom,
             highlight_zones: self.highlight_zones,
             zoom_to_fit_timer: None,
+            snap_to_grid: self.snap_to_grid,
         };
 
         ctx.add_node(UiNode::new(editor))
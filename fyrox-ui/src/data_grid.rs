@@ -0,0 +1,1173 @@
+//! Data grid is a table with resizable and sortable columns, that shows the data of a [`DataGridProvider`]. See
+//! [`DataGrid`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    brush::Brush,
+    button::{ButtonBuilder, ButtonMessage},
+    canvas::CanvasBuilder,
+    core::{
+        algebra::Vector2, color::Color, parking_lot::Mutex, pool::Handle, reflect::prelude::*,
+        type_traits::prelude::*, visitor::prelude::*,
+    },
+    decorator::{DecoratorBuilder, DecoratorMessage},
+    define_constructor,
+    grid::{Column, GridBuilder, Row},
+    message::{CursorIcon, MessageDirection, MouseButton, UiMessage},
+    numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+    scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
+    stack_panel::StackPanelBuilder,
+    text::{TextBuilder, TextMessage},
+    text_box::{TextBoxBuilder, TextCommitMode},
+    utils::{make_arrow, ArrowDirection},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    VerticalAlignment, BRUSH_DARKER, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_PRIMARY,
+};
+use fyrox_core::{uuid_provider, variable::InheritableVariable};
+use fyrox_graph::SceneGraph;
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display, Formatter},
+    ops::{Deref, DerefMut},
+    sync::{mpsc::Sender, Arc},
+};
+
+/// Minimal width of a column, columns cannot be resized to be less than this value.
+pub const MIN_COLUMN_WIDTH: f32 = 16.0;
+
+/// A value of a single cell of a [`DataGrid`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataGridCell {
+    /// Arbitrary text.
+    Text(String),
+    /// A number. Cells with numbers are sorted numerically, not lexicographically.
+    Number(f64),
+}
+
+impl Default for DataGridCell {
+    fn default() -> Self {
+        Self::Text(Default::default())
+    }
+}
+
+impl Display for DataGridCell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataGridCell::Text(text) => write!(f, "{}", text),
+            DataGridCell::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+impl DataGridCell {
+    /// Compares two cells. Numbers are always less than text.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (DataGridCell::Number(a), DataGridCell::Number(b)) => a.total_cmp(b),
+            (DataGridCell::Text(a), DataGridCell::Text(b)) => a.cmp(b),
+            (DataGridCell::Number(_), DataGridCell::Text(_)) => Ordering::Less,
+            (DataGridCell::Text(_), DataGridCell::Number(_)) => Ordering::Greater,
+        }
+    }
+}
+
+/// Defines which editor should be used to edit the cells of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Visit, Reflect)]
+pub enum DataGridCellEditor {
+    /// Cells cannot be edited.
+    #[default]
+    None,
+    /// Cells are edited using a text box.
+    Text,
+    /// Cells are edited using a numeric up-down.
+    Number,
+}
+
+/// Description of a single column of a [`DataGrid`].
+#[derive(Debug, Clone, PartialEq, Default, Visit, Reflect)]
+pub struct DataGridColumn {
+    /// Name of the column, it is shown in the header of the data grid.
+    pub name: String,
+    /// Width of the column in local units.
+    pub width: f32,
+    /// Whether the data grid could be sorted by the column or not.
+    pub sortable: bool,
+    /// An editor that is used to edit the cells of the column.
+    pub editor: DataGridCellEditor,
+}
+
+impl DataGridColumn {
+    /// Creates a new non-sortable and non-editable column.
+    pub fn new(name: impl Into<String>, width: f32) -> Self {
+        Self {
+            name: name.into(),
+            width: width.max(MIN_COLUMN_WIDTH),
+            sortable: false,
+            editor: DataGridCellEditor::None,
+        }
+    }
+
+    /// Allows the data grid to be sorted by the column.
+    pub fn with_sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// Sets an editor for the cells of the column.
+    pub fn with_editor(mut self, editor: DataGridCellEditor) -> Self {
+        self.editor = editor;
+        self
+    }
+}
+
+/// Data provider is a source of the data for a [`DataGrid`]. The data grid requests only the cells of visible rows, so
+/// the provider could hold any number of rows.
+pub trait DataGridProvider: Debug + Send {
+    /// Returns total amount of rows.
+    fn row_count(&self) -> usize;
+
+    /// Returns a value of a cell at the given row and column.
+    fn cell(&self, row: usize, column: usize) -> DataGridCell;
+}
+
+/// A shared reference to a [`DataGridProvider`].
+#[derive(Debug, Clone)]
+pub struct DataGridProviderRef(pub Arc<Mutex<dyn DataGridProvider>>);
+
+impl DataGridProviderRef {
+    /// Wraps the given provider in a shared reference.
+    pub fn new<P: DataGridProvider + 'static>(provider: P) -> Self {
+        Self(Arc::new(Mutex::new(provider)))
+    }
+}
+
+impl PartialEq for DataGridProviderRef {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Sorting direction of a [`DataGrid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortDirection {
+    /// From the smallest values to the largest.
+    #[default]
+    Ascending,
+    /// From the largest values to the smallest.
+    Descending,
+}
+
+/// A set of messages that can be used to modify/fetch the state of a [`DataGrid`] widget at runtime. Row indices in
+/// the messages are always the indices of the rows in the data provider, not the indices of the rows on the screen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataGridMessage {
+    /// Sets a new data provider of the data grid.
+    Provider(Option<DataGridProviderRef>),
+    /// Forces the data grid to re-fetch the data from its provider. This message must be sent every time when the
+    /// data of the provider has changed.
+    Refresh,
+    /// Sorts the rows by the values of the given column. The data grid sends this message with
+    /// [`MessageDirection::FromWidget`], when a user clicks on the header of a sortable column.
+    Sort {
+        /// Index of the column.
+        column: usize,
+        /// Sorting direction.
+        direction: SortDirection,
+    },
+    /// Changes width of a column. The data grid sends this message with [`MessageDirection::FromWidget`], when a
+    /// user has finished resizing of a column.
+    ColumnWidth {
+        /// Index of the column.
+        column: usize,
+        /// New width of the column.
+        width: f32,
+    },
+    /// A message, that is used to either fetch or modify current selection of the data grid.
+    SelectionChanged(Option<usize>),
+    /// The data grid sends this message with [`MessageDirection::FromWidget`], when a user has double-clicked on a
+    /// row.
+    RowActivated(usize),
+    /// The data grid sends this message with [`MessageDirection::FromWidget`], when a user has changed a value of a
+    /// cell using in-place editor. The data grid does not change the data by itself, it is up to the user to write
+    /// the value back to the data provider and send [`DataGridMessage::Refresh`].
+    CellEdited {
+        /// Index of the row.
+        row: usize,
+        /// Index of the column.
+        column: usize,
+        /// New value of the cell.
+        value: DataGridCell,
+    },
+}
+
+impl DataGridMessage {
+    define_constructor!(
+        /// Creates [`DataGridMessage::Provider`] message.
+        DataGridMessage:Provider => fn provider(Option<DataGridProviderRef>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::Refresh`] message.
+        DataGridMessage:Refresh => fn refresh(), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::Sort`] message.
+        DataGridMessage:Sort => fn sort(column: usize, direction: SortDirection), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::ColumnWidth`] message.
+        DataGridMessage:ColumnWidth => fn column_width(column: usize, width: f32), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::SelectionChanged`] message.
+        DataGridMessage:SelectionChanged => fn selection(Option<usize>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::RowActivated`] message.
+        DataGridMessage:RowActivated => fn row_activated(usize), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::CellEdited`] message.
+        DataGridMessage:CellEdited => fn cell_edited(row: usize, column: usize, value: DataGridCell), layout: false
+    );
+}
+
+/// A row, that is currently shown on the screen.
+#[derive(Debug, Clone, Default)]
+struct VisibleRow {
+    /// Index of the row in the data provider.
+    row: usize,
+    /// Handle of the row decorator.
+    handle: Handle<UiNode>,
+    /// Cell containers of the row, one per column.
+    cells: Vec<Handle<UiNode>>,
+}
+
+/// A cell, that is currently being edited.
+#[derive(Debug, Clone, Default)]
+struct EditedCell {
+    row: usize,
+    column: usize,
+    kind: DataGridCellEditor,
+    editor: Handle<UiNode>,
+    /// A new value of the cell, it will be committed when the editing is finished.
+    value: Option<DataGridCell>,
+}
+
+/// A column, that is currently being resized.
+#[derive(Debug, Clone, Default)]
+struct ResizedColumn {
+    column: usize,
+    initial_cursor_x: f32,
+    initial_width: f32,
+}
+
+/// Data grid is a table with resizable and sortable columns. It takes a column schema and a [`DataGridProvider`],
+/// that is used to fetch the data of the cells. The data grid materializes only visible rows, so it can show
+/// millions of rows without any performance issues.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     data_grid::{
+/// #         DataGridBuilder, DataGridCell, DataGridCellEditor, DataGridColumn, DataGridProvider,
+/// #         DataGridProviderRef,
+/// #     },
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// #[derive(Debug)]
+/// struct Items(Vec<(String, f64)>);
+///
+/// impl DataGridProvider for Items {
+///     fn row_count(&self) -> usize {
+///         self.0.len()
+///     }
+///
+///     fn cell(&self, row: usize, column: usize) -> DataGridCell {
+///         let (name, price) = &self.0[row];
+///         match column {
+///             0 => DataGridCell::Text(name.clone()),
+///             _ => DataGridCell::Number(*price),
+///         }
+///     }
+/// }
+///
+/// fn create_data_grid(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     DataGridBuilder::new(WidgetBuilder::new())
+///         .with_columns(vec![
+///             DataGridColumn::new("Name", 150.0).with_sortable(true),
+///             DataGridColumn::new("Price", 80.0)
+///                 .with_sortable(true)
+///                 .with_editor(DataGridCellEditor::Number),
+///         ])
+///         .with_provider(DataGridProviderRef::new(Items(vec![
+///             ("Sword".to_string(), 100.0),
+///             ("Shield".to_string(), 75.0),
+///         ])))
+///         .build(ctx)
+/// }
+/// ```
+///
+/// ## Selection
+///
+/// The data grid supports single selection, use [`DataGridMessage::SelectionChanged`] with
+/// [`MessageDirection::ToWidget`] to change it and listen to the same message with [`MessageDirection::FromWidget`]
+/// to catch the moment when a user has changed the selection.
+///
+/// ## Editing
+///
+/// Cells of the columns with an editor could be edited in-place by double-clicking on them. When a user has changed
+/// a value, the data grid sends [`DataGridMessage::CellEdited`] message. It is up to the user to write the new value
+/// to the data provider and send [`DataGridMessage::Refresh`] to show the changes.
+#[derive(Default, Clone, Visit, Reflect, Debug, ComponentProvider)]
+pub struct DataGrid {
+    /// Base widget of the data grid.
+    pub widget: Widget,
+    /// Columns of the data grid.
+    pub columns: InheritableVariable<Vec<DataGridColumn>>,
+    /// Height of each row (and the header) in local units.
+    pub row_height: InheritableVariable<f32>,
+    /// A panel, that holds the header cells.
+    pub header: InheritableVariable<Handle<UiNode>>,
+    /// A canvas, that holds visible rows.
+    pub body: InheritableVariable<Handle<UiNode>>,
+    /// Vertical scroll bar.
+    pub scroll_bar: InheritableVariable<Handle<UiNode>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    provider: Option<DataGridProviderRef>,
+    /// Indices of the rows in the data provider in the order in which they're shown on the screen.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    order: Vec<usize>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    sort: Option<(usize, SortDirection)>,
+    /// Current selection (index of the row in the data provider).
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub selected_row: Option<usize>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    scroll_offset: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    visible_rows: Vec<VisibleRow>,
+    /// Header cell containers and the handles of their resize grips, one pair per column.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    header_cells: Vec<(Handle<UiNode>, Handle<UiNode>, Handle<UiNode>)>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    body_size: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    last_size: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    resized_column: Option<ResizedColumn>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    edited_cell: Option<EditedCell>,
+}
+
+crate::define_widget_deref!(DataGrid);
+
+uuid_provider!(DataGrid = "3f8c9d38-2d0c-4b43-9a3d-6d2c5f1e0a77");
+
+fn make_cell(
+    ctx: &mut BuildContext,
+    column: &DataGridColumn,
+    value: &DataGridCell,
+) -> Handle<UiNode> {
+    BorderBuilder::new(
+        WidgetBuilder::new()
+            .with_width(column.width)
+            .with_foreground(BRUSH_DARKER)
+            .with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::left(2.0))
+                        .with_vertical_alignment(VerticalAlignment::Center),
+                )
+                .with_text(value.to_string())
+                .build(ctx),
+            ),
+    )
+    .with_stroke_thickness(Thickness::right(1.0))
+    .build(ctx)
+}
+
+fn make_header_cell(
+    ctx: &mut BuildContext,
+    column: &DataGridColumn,
+    sort: Option<SortDirection>,
+) -> (Handle<UiNode>, Handle<UiNode>, Handle<UiNode>) {
+    let button = ButtonBuilder::new(WidgetBuilder::new().on_column(0))
+        .with_back(
+            DecoratorBuilder::new(BorderBuilder::new(
+                WidgetBuilder::new().with_foreground(BRUSH_DARKER),
+            ))
+            .with_normal_brush(BRUSH_PRIMARY)
+            .with_hover_brush(BRUSH_LIGHTER)
+            .build(ctx),
+        )
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::left(2.0))
+                                .on_column(0),
+                        )
+                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                        .with_text(&column.name)
+                        .build(ctx),
+                    )
+                    .with_children(sort.map(|direction| {
+                        let arrow = make_arrow(
+                            ctx,
+                            match direction {
+                                SortDirection::Ascending => ArrowDirection::Top,
+                                SortDirection::Descending => ArrowDirection::Bottom,
+                            },
+                            6.0,
+                        );
+                        ctx[arrow].set_column(1);
+                        arrow
+                    })),
+            )
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .add_column(Column::strict(12.0))
+            .build(ctx),
+        )
+        .build(ctx);
+
+    let grip = BorderBuilder::new(
+        WidgetBuilder::new()
+            .on_column(1)
+            .with_width(4.0)
+            .with_cursor(Some(CursorIcon::EwResize))
+            .with_background(BRUSH_DARKER),
+    )
+    .build(ctx);
+
+    let cell = GridBuilder::new(
+        WidgetBuilder::new()
+            .with_width(column.width)
+            .with_child(button)
+            .with_child(grip),
+    )
+    .add_row(Row::stretch())
+    .add_column(Column::stretch())
+    .add_column(Column::auto())
+    .build(ctx);
+
+    (cell, button, grip)
+}
+
+impl DataGrid {
+    /// Returns current data provider.
+    pub fn provider(&self) -> Option<&DataGridProviderRef> {
+        self.provider.as_ref()
+    }
+
+    /// Returns current sorting column and direction.
+    pub fn sort(&self) -> Option<(usize, SortDirection)> {
+        self.sort
+    }
+
+    fn row_count(&self) -> usize {
+        self.provider
+            .as_ref()
+            .map_or(0, |provider| provider.0.lock().row_count())
+    }
+
+    /// Re-calculates the order of the rows according to current sorting.
+    fn sync_order(&mut self) {
+        let row_count = self.row_count();
+
+        self.order = (0..row_count).collect();
+
+        if let (Some(provider), Some((column, direction))) = (self.provider.as_ref(), self.sort) {
+            let provider = provider.0.lock();
+            let keys = (0..row_count)
+                .map(|row| provider.cell(row, column))
+                .collect::<Vec<_>>();
+            self.order.sort_by(|a, b| {
+                let ordering = keys[*a].compare(&keys[*b]);
+                match direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            });
+        }
+    }
+
+    fn rebuild_header(&mut self, ui: &mut UserInterface) {
+        for (cell, _, _) in self.header_cells.drain(..) {
+            ui.send_message(WidgetMessage::remove(cell, MessageDirection::ToWidget));
+        }
+
+        let ctx = &mut ui.build_ctx();
+        for (i, column) in self.columns.iter().enumerate() {
+            let sort = self
+                .sort
+                .and_then(|(sort_column, direction)| (sort_column == i).then_some(direction));
+            self.header_cells.push(make_header_cell(ctx, column, sort));
+        }
+
+        for (cell, _, _) in self.header_cells.iter() {
+            ui.send_message(WidgetMessage::link(
+                *cell,
+                MessageDirection::ToWidget,
+                *self.header,
+            ));
+        }
+    }
+
+    /// Removes all visible rows and creates new ones for current scroll offset.
+    fn rebuild_rows(&mut self, ui: &mut UserInterface) {
+        self.end_editing(ui);
+
+        for row in self.visible_rows.drain(..) {
+            ui.send_message(WidgetMessage::remove(
+                row.handle,
+                MessageDirection::ToWidget,
+            ));
+        }
+
+        let row_height = *self.row_height;
+        let view_height = self.body_size.y;
+        let total_height = self.order.len() as f32 * row_height;
+        let max_offset = (total_height - view_height).max(0.0);
+
+        ui.send_message(ScrollBarMessage::max_value(
+            *self.scroll_bar,
+            MessageDirection::ToWidget,
+            max_offset,
+        ));
+
+        self.scroll_offset = self.scroll_offset.clamp(0.0, max_offset);
+
+        let Some(provider) = self.provider.clone() else {
+            return;
+        };
+        let provider = provider.0.lock();
+
+        if row_height <= 0.0 {
+            return;
+        }
+
+        let first = (self.scroll_offset / row_height).floor() as usize;
+        let last = (((self.scroll_offset + view_height) / row_height).ceil() as usize + 1)
+            .min(self.order.len());
+
+        let ctx = &mut ui.build_ctx();
+        for view_index in first..last {
+            let row = self.order[view_index];
+
+            let cells = self
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(column_index, column)| {
+                    make_cell(ctx, column, &provider.cell(row, column_index))
+                })
+                .collect::<Vec<_>>();
+
+            let handle = DecoratorBuilder::new(BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_height(row_height)
+                    .with_desired_position(Vector2::new(
+                        0.0,
+                        view_index as f32 * row_height - self.scroll_offset,
+                    ))
+                    .with_child(
+                        StackPanelBuilder::new(WidgetBuilder::new().with_children(cells.clone()))
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                    ),
+            ))
+            .with_normal_brush(if view_index % 2 == 0 {
+                Brush::Solid(Color::opaque(50, 50, 50))
+            } else {
+                Brush::Solid(Color::opaque(60, 60, 60))
+            })
+            .with_hover_brush(BRUSH_LIGHT)
+            .with_selected(self.selected_row == Some(row))
+            .build(ctx);
+
+            self.visible_rows.push(VisibleRow { row, handle, cells });
+        }
+
+        for row in self.visible_rows.iter() {
+            ui.send_message(WidgetMessage::link(
+                row.handle,
+                MessageDirection::ToWidget,
+                *self.body,
+            ));
+        }
+    }
+
+    fn sync_selection(&self, ui: &UserInterface) {
+        for row in self.visible_rows.iter() {
+            ui.send_message(DecoratorMessage::select(
+                row.handle,
+                MessageDirection::ToWidget,
+                self.selected_row == Some(row.row),
+            ));
+        }
+    }
+
+    fn set_column_width(&mut self, column: usize, width: f32, ui: &UserInterface) {
+        let width = width.max(MIN_COLUMN_WIDTH);
+
+        if let Some(column_desc) = self
+            .columns
+            .get_value_mut_and_mark_modified()
+            .get_mut(column)
+        {
+            column_desc.width = width;
+        } else {
+            return;
+        }
+
+        if let Some((cell, _, _)) = self.header_cells.get(column) {
+            ui.send_message(WidgetMessage::width(
+                *cell,
+                MessageDirection::ToWidget,
+                width,
+            ));
+        }
+
+        for row in self.visible_rows.iter() {
+            if let Some(cell) = row.cells.get(column) {
+                ui.send_message(WidgetMessage::width(
+                    *cell,
+                    MessageDirection::ToWidget,
+                    width,
+                ));
+            }
+        }
+    }
+
+    /// Returns a row and a column of the cell, that contains the given widget.
+    fn find_cell(&self, widget: Handle<UiNode>, ui: &UserInterface) -> Option<(usize, usize)> {
+        self.visible_rows.iter().find_map(|row| {
+            row.cells
+                .iter()
+                .position(|cell| *cell == widget || ui.is_node_child_of(widget, *cell))
+                .map(|column| (row.row, column))
+        })
+    }
+
+    fn begin_editing(&mut self, row: usize, column: usize, ui: &mut UserInterface) {
+        self.end_editing(ui);
+
+        let Some(editor_kind) = self.columns.get(column).map(|c| c.editor) else {
+            return;
+        };
+
+        let Some(cell) = self
+            .visible_rows
+            .iter()
+            .find(|r| r.row == row)
+            .and_then(|r| r.cells.get(column).cloned())
+        else {
+            return;
+        };
+
+        let Some(value) = self
+            .provider
+            .as_ref()
+            .map(|provider| provider.0.lock().cell(row, column))
+        else {
+            return;
+        };
+
+        let ctx = &mut ui.build_ctx();
+        let editor = match editor_kind {
+            DataGridCellEditor::None => return,
+            DataGridCellEditor::Text => TextBoxBuilder::new(WidgetBuilder::new())
+                .with_text_commit_mode(TextCommitMode::LostFocusPlusEnter)
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text(value.to_string())
+                .build(ctx),
+            DataGridCellEditor::Number => NumericUpDownBuilder::<f64>::new(WidgetBuilder::new())
+                .with_value(match value {
+                    DataGridCell::Number(number) => number,
+                    DataGridCell::Text(ref text) => text.parse().unwrap_or_default(),
+                })
+                .build(ctx),
+        };
+
+        // Hide the text of the cell, the editor will be drawn instead.
+        for &child in ui.node(cell).children() {
+            ui.send_message(WidgetMessage::visibility(
+                child,
+                MessageDirection::ToWidget,
+                false,
+            ));
+        }
+
+        ui.send_message(WidgetMessage::link(
+            editor,
+            MessageDirection::ToWidget,
+            cell,
+        ));
+        ui.send_message(WidgetMessage::focus(editor, MessageDirection::ToWidget));
+
+        self.edited_cell = Some(EditedCell {
+            row,
+            column,
+            kind: editor_kind,
+            editor,
+            value: None,
+        });
+    }
+
+    /// Finishes editing of a cell and sends [`DataGridMessage::CellEdited`] message if the value was changed.
+    fn end_editing(&mut self, ui: &UserInterface) {
+        if let Some(edited_cell) = self.edited_cell.take() {
+            if let Some(value) = edited_cell.value {
+                ui.send_message(DataGridMessage::cell_edited(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    edited_cell.row,
+                    edited_cell.column,
+                    value,
+                ));
+            }
+
+            let cell = self
+                .visible_rows
+                .iter()
+                .find(|r| r.row == edited_cell.row)
+                .and_then(|r| r.cells.get(edited_cell.column).cloned());
+
+            if let Some(cell) = cell {
+                for &child in ui.node(cell).children() {
+                    ui.send_message(WidgetMessage::visibility(
+                        child,
+                        MessageDirection::ToWidget,
+                        true,
+                    ));
+                }
+            }
+
+            ui.send_message(WidgetMessage::remove(
+                edited_cell.editor,
+                MessageDirection::ToWidget,
+            ));
+        }
+    }
+
+    /// Returns a mutable reference to currently edited cell, if the message comes from its editor.
+    fn edited_cell_of(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+    ) -> Option<&mut EditedCell> {
+        self.edited_cell.as_mut().filter(|edited_cell| {
+            message.destination() == edited_cell.editor
+                || ui.is_node_child_of(message.destination(), edited_cell.editor)
+        })
+    }
+
+    fn is_editor_message(&self, message: &UiMessage, ui: &UserInterface) -> bool {
+        self.edited_cell.as_ref().is_some_and(|edited_cell| {
+            message.destination() == edited_cell.editor
+                || ui.is_node_child_of(message.destination(), edited_cell.editor)
+        })
+    }
+}
+
+impl Control for DataGrid {
+    fn update(&mut self, _dt: f32, sender: &Sender<UiMessage>, _screen_size: Vector2<f32>) {
+        // Visible rows depend on the size of the grid, so they must be re-created when the size has changed.
+        let size = self.actual_local_size();
+        if size != self.last_size {
+            self.last_size = size;
+            let _ = sender.send(DataGridMessage::refresh(
+                self.handle,
+                MessageDirection::ToWidget,
+            ));
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<DataGridMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    DataGridMessage::Provider(provider) => {
+                        self.provider = provider.clone();
+                        self.selected_row = None;
+                        self.sync_order();
+                        self.rebuild_rows(ui);
+                    }
+                    DataGridMessage::Refresh => {
+                        self.body_size = ui.node(*self.body).actual_local_size();
+                        self.sync_order();
+                        if self.selected_row.is_some_and(|row| row >= self.order.len()) {
+                            self.selected_row = None;
+                        }
+                        self.rebuild_rows(ui);
+                    }
+                    &DataGridMessage::Sort { column, direction } => {
+                        if column < self.columns.len() && self.sort != Some((column, direction)) {
+                            self.sort = Some((column, direction));
+                            self.sync_order();
+                            self.rebuild_header(ui);
+                            self.rebuild_rows(ui);
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    &DataGridMessage::ColumnWidth { column, width } => {
+                        self.set_column_width(column, width, ui);
+                    }
+                    &DataGridMessage::SelectionChanged(selection) => {
+                        if self.selected_row != selection {
+                            self.selected_row = selection;
+                            self.sync_selection(ui);
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    DataGridMessage::RowActivated(_) | DataGridMessage::CellEdited { .. } => (),
+                }
+            }
+        } else if let Some(ScrollBarMessage::Value(value)) = message.data() {
+            if message.destination() == *self.scroll_bar
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.scroll_offset = *value;
+                self.rebuild_rows(ui);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if let Some(column) = self
+                .header_cells
+                .iter()
+                .position(|(_, button, _)| *button == message.destination())
+            {
+                if self.columns[column].sortable {
+                    let direction = match self.sort {
+                        Some((sort_column, SortDirection::Ascending)) if sort_column == column => {
+                            SortDirection::Descending
+                        }
+                        _ => SortDirection::Ascending,
+                    };
+                    ui.send_message(DataGridMessage::sort(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        column,
+                        direction,
+                    ));
+                }
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(edited_cell) = self.edited_cell_of(message, ui) {
+                    // Numeric editor has its own text box inside, its messages must be ignored.
+                    if edited_cell.kind == DataGridCellEditor::Text {
+                        edited_cell.value = Some(DataGridCell::Text(text.clone()));
+                        self.end_editing(ui);
+                    }
+                }
+            }
+        } else if let Some(NumericUpDownMessage::<f64>::Value(value)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(edited_cell) = self.edited_cell_of(message, ui) {
+                    edited_cell.value = Some(DataGridCell::Number(*value));
+                }
+            }
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { pos, button } => {
+                    if let Some(column) = self
+                        .header_cells
+                        .iter()
+                        .position(|(_, _, grip)| *grip == message.destination())
+                    {
+                        self.resized_column = Some(ResizedColumn {
+                            column,
+                            initial_cursor_x: pos.x,
+                            initial_width: self.columns[column].width,
+                        });
+                        ui.capture_mouse(message.destination());
+                        message.set_handled(true);
+                    } else if *button == MouseButton::Left
+                        && !message.handled()
+                        && !self.is_editor_message(message, ui)
+                    {
+                        if let Some(row) = self.visible_rows.iter().find(|r| {
+                            r.handle == message.destination()
+                                || ui.is_node_child_of(message.destination(), r.handle)
+                        }) {
+                            ui.send_message(DataGridMessage::selection(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                Some(row.row),
+                            ));
+                        }
+                    }
+                }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    if let Some(resized_column) = self.resized_column.as_ref() {
+                        let new_width = resized_column.initial_width
+                            + (pos.x - resized_column.initial_cursor_x)
+                                / self.visual_transform().m11.max(f32::EPSILON);
+                        self.set_column_width(resized_column.column, new_width, ui);
+                    }
+                }
+                WidgetMessage::MouseUp { .. } => {
+                    if let Some(resized_column) = self.resized_column.take() {
+                        ui.release_mouse_capture();
+                        ui.send_message(DataGridMessage::column_width(
+                            self.handle,
+                            MessageDirection::FromWidget,
+                            resized_column.column,
+                            self.columns[resized_column.column].width,
+                        ));
+                        message.set_handled(true);
+                    }
+                }
+                WidgetMessage::DoubleClick { button }
+                    if *button == MouseButton::Left && !self.is_editor_message(message, ui) =>
+                {
+                    if let Some((row, column)) = self.find_cell(message.destination(), ui) {
+                        ui.send_message(DataGridMessage::row_activated(
+                            self.handle,
+                            MessageDirection::FromWidget,
+                            row,
+                        ));
+
+                        self.begin_editing(row, column, ui);
+                    }
+                }
+                WidgetMessage::MouseWheel { amount, .. }
+                    if !message.handled()
+                        && (message.destination() == *self.body
+                            || ui.is_node_child_of(message.destination(), *self.body))
+                        && !self.is_editor_message(message, ui) =>
+                {
+                    ui.send_message(ScrollBarMessage::value(
+                        *self.scroll_bar,
+                        MessageDirection::ToWidget,
+                        self.scroll_offset - amount * 3.0 * *self.row_height,
+                    ));
+                    message.set_handled(true);
+                }
+                // Text editor commits its value on its own, when it loses focus.
+                WidgetMessage::Unfocus
+                    if self.is_editor_message(message, ui)
+                        && self
+                            .edited_cell
+                            .as_ref()
+                            .is_some_and(|c| c.kind == DataGridCellEditor::Number) =>
+                {
+                    self.end_editing(ui);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Data grid builder creates [`DataGrid`] widget instances and adds them to the user interface.
+pub struct DataGridBuilder {
+    widget_builder: WidgetBuilder,
+    columns: Vec<DataGridColumn>,
+    provider: Option<DataGridProviderRef>,
+    row_height: f32,
+}
+
+impl DataGridBuilder {
+    /// Creates new data grid builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            columns: Default::default(),
+            provider: None,
+            row_height: 20.0,
+        }
+    }
+
+    /// Sets desired columns of the data grid.
+    pub fn with_columns(mut self, columns: Vec<DataGridColumn>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets desired data provider of the data grid.
+    pub fn with_provider(mut self, provider: DataGridProviderRef) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Sets desired height of rows of the data grid.
+    pub fn with_row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    /// Finishes data grid building and adds it to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let header_cells = self
+            .columns
+            .iter()
+            .map(|column| make_header_cell(ctx, column, None))
+            .collect::<Vec<_>>();
+
+        let header = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .on_column(0)
+                .with_children(header_cells.iter().map(|(cell, _, _)| *cell)),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let body = CanvasBuilder::new(WidgetBuilder::new().on_row(1).on_column(0)).build(ctx);
+
+        let scroll_bar = ScrollBarBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .on_column(1)
+                .with_width(16.0)
+                .with_horizontal_alignment(HorizontalAlignment::Right),
+        )
+        .with_orientation(Orientation::Vertical)
+        .with_min(0.0)
+        .with_max(0.0)
+        .with_step(self.row_height)
+        .build(ctx);
+
+        let grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(header)
+                .with_child(body)
+                .with_child(scroll_bar),
+        )
+        .add_row(Row::strict(self.row_height))
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .add_column(Column::auto())
+        .build(ctx);
+
+        let mut data_grid = DataGrid {
+            widget: self.widget_builder.with_child(grid).build(),
+            columns: self.columns.into(),
+            row_height: self.row_height.into(),
+            header: header.into(),
+            body: body.into(),
+            scroll_bar: scroll_bar.into(),
+            provider: self.provider,
+            order: Default::default(),
+            sort: None,
+            selected_row: None,
+            scroll_offset: 0.0,
+            visible_rows: Default::default(),
+            header_cells,
+            body_size: Default::default(),
+            last_size: Default::default(),
+            resized_column: None,
+            edited_cell: None,
+        };
+
+        data_grid.sync_order();
+
+        ctx.add_node(UiNode::new(data_grid))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        data_grid::{
+            DataGrid, DataGridBuilder, DataGridCell, DataGridColumn, DataGridMessage,
+            DataGridProvider, DataGridProviderRef, SortDirection,
+        },
+        message::MessageDirection,
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_core::algebra::Vector2;
+    use fyrox_graph::SceneGraph;
+
+    #[derive(Debug)]
+    struct Items(Vec<(&'static str, f64)>);
+
+    impl DataGridProvider for Items {
+        fn row_count(&self) -> usize {
+            self.0.len()
+        }
+
+        fn cell(&self, row: usize, column: usize) -> DataGridCell {
+            match column {
+                0 => DataGridCell::Text(self.0[row].0.to_string()),
+                _ => DataGridCell::Number(self.0[row].1),
+            }
+        }
+    }
+
+    #[test]
+    fn test_data_grid_sorting() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let data_grid = DataGridBuilder::new(WidgetBuilder::new())
+            .with_columns(vec![
+                DataGridColumn::new("Name", 50.0).with_sortable(true),
+                DataGridColumn::new("Price", 50.0).with_sortable(true),
+            ])
+            .with_provider(DataGridProviderRef::new(Items(vec![
+                ("b", 10.0),
+                ("c", 2.0),
+                ("a", 100.0),
+            ])))
+            .build(&mut ui.build_ctx());
+
+        let order =
+            |ui: &UserInterface| ui.node(data_grid).cast::<DataGrid>().unwrap().order.clone();
+
+        assert_eq!(order(&ui), vec![0, 1, 2]);
+
+        ui.send_message(DataGridMessage::sort(
+            data_grid,
+            MessageDirection::ToWidget,
+            0,
+            SortDirection::Ascending,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(order(&ui), vec![2, 0, 1]);
+
+        // Numbers must be sorted numerically.
+        ui.send_message(DataGridMessage::sort(
+            data_grid,
+            MessageDirection::ToWidget,
+            1,
+            SortDirection::Descending,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(order(&ui), vec![2, 0, 1]);
+
+        ui.send_message(DataGridMessage::sort(
+            data_grid,
+            MessageDirection::ToWidget,
+            1,
+            SortDirection::Ascending,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(order(&ui), vec![1, 0, 2]);
+    }
+}
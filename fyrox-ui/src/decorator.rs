@@ -7,15 +7,16 @@ use crate::{
     border::{Border, BorderBuilder},
     brush::Brush,
     core::{
-        algebra::Vector2, pool::Handle, reflect::prelude::*, type_traits::prelude::*,
-        visitor::prelude::*,
+        algebra::Vector2, pool::Handle, reflect::prelude::*, sstorage::ImmutableString,
+        type_traits::prelude::*, visitor::prelude::*,
     },
     define_constructor,
     draw::DrawingContext,
     message::{MessageDirection, UiMessage},
+    style::Style,
     widget::{Widget, WidgetMessage},
-    BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_DARKER, BRUSH_LIGHT,
-    BRUSH_LIGHTER, BRUSH_LIGHTEST,
+    BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_LIGHT, BRUSH_LIGHTER,
+    BRUSH_LIGHTEST,
 };
 use fyrox_core::uuid_provider;
 use fyrox_core::variable::InheritableVariable;
@@ -62,6 +63,20 @@ impl DecoratorMessage {
     );
 }
 
+/// Names of the style properties, that are used as the brushes of a [`Decorator`]. A brush without a name was set
+/// explicitly and it does not change when the style changes. See [`Style`] docs for more info.
+#[derive(Default, Clone, Visit, Reflect, Debug, PartialEq)]
+pub struct DecoratorStyle {
+    /// Name of a style property for `Normal` state.
+    pub normal: Option<ImmutableString>,
+    /// Name of a style property for `Hovered` state.
+    pub hover: Option<ImmutableString>,
+    /// Name of a style property for `Pressed` state.
+    pub pressed: Option<ImmutableString>,
+    /// Name of a style property for `Selected` state.
+    pub selected: Option<ImmutableString>,
+}
+
 /// A visual element that is used to highlight standard states of interactive widgets. It has "pressed", "hover",
 /// "selected", "normal" appearances (only one can be active at a time):
 ///
@@ -74,6 +89,9 @@ impl DecoratorMessage {
 /// to decorate buttons - it has use of three of these states. When it is clicked - the decorator will be in `Pressed`
 /// state, when hovered by a cursor - `Hovered`, otherwise it stays in `Normal` state.
 ///
+/// Brushes, that weren't set explicitly, are taken from the current [`Style`] of the user interface and they're
+/// updated when the style changes.
+///
 /// ## Example
 ///
 /// ```rust
@@ -108,6 +126,9 @@ pub struct Decorator {
     pub is_selected: InheritableVariable<bool>,
     /// Whether the decorator should react to mouse clicks and switch its state to `Pressed` or not.
     pub is_pressable: InheritableVariable<bool>,
+    /// Names of the style properties, that are used as the brushes of the decorator.
+    #[visit(optional)]
+    pub brush_styles: DecoratorStyle,
 }
 
 impl Deref for Decorator {
@@ -126,6 +147,29 @@ impl DerefMut for Decorator {
 
 uuid_provider!(Decorator = "bb4b60aa-c657-4ed6-8db6-d7f374397c73");
 
+impl Decorator {
+    fn apply_style(&mut self, style: &Style) {
+        let apply = |brush: &mut InheritableVariable<Brush>, name: &Option<ImmutableString>| {
+            if let Some(new_brush) = name.as_ref().and_then(|name| style.get_brush(name)) {
+                brush.set_value_and_mark_modified(new_brush);
+            }
+        };
+        apply(&mut self.normal_brush, &self.brush_styles.normal);
+        apply(&mut self.hover_brush, &self.brush_styles.hover);
+        apply(&mut self.pressed_brush, &self.brush_styles.pressed);
+        apply(&mut self.selected_brush, &self.brush_styles.selected);
+
+        let background = if *self.is_selected {
+            (*self.selected_brush).clone()
+        } else if self.is_mouse_directly_over {
+            (*self.hover_brush).clone()
+        } else {
+            (*self.normal_brush).clone()
+        };
+        self.set_background(background);
+    }
+}
+
 impl Control for Decorator {
     fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
         self.border.measure_override(ui, available_size)
@@ -203,7 +247,13 @@ impl Control for Decorator {
                 }
             }
         } else if let Some(msg) = message.data::<WidgetMessage>() {
-            if message.destination() == self.handle()
+            if let WidgetMessage::Style(style) = msg {
+                if message.destination() == self.handle()
+                    && message.direction() == MessageDirection::ToWidget
+                {
+                    self.apply_style(&style.data_ref());
+                }
+            } else if message.destination() == self.handle()
                 || self.has_descendant(message.destination(), ui)
             {
                 match msg {
@@ -261,10 +311,10 @@ impl Control for Decorator {
 /// Creates [`Decorator`] widget instances and adds them to the user interface.
 pub struct DecoratorBuilder {
     border_builder: BorderBuilder,
-    normal_brush: Brush,
-    hover_brush: Brush,
-    pressed_brush: Brush,
-    selected_brush: Brush,
+    normal_brush: Option<Brush>,
+    hover_brush: Option<Brush>,
+    pressed_brush: Option<Brush>,
+    selected_brush: Option<Brush>,
     pressable: bool,
     selected: bool,
 }
@@ -274,10 +324,10 @@ impl DecoratorBuilder {
     pub fn new(border_builder: BorderBuilder) -> Self {
         Self {
             border_builder,
-            normal_brush: BRUSH_LIGHT,
-            hover_brush: BRUSH_LIGHTER,
-            pressed_brush: BRUSH_LIGHTEST,
-            selected_brush: BRUSH_BRIGHT,
+            normal_brush: None,
+            hover_brush: None,
+            pressed_brush: None,
+            selected_brush: None,
             pressable: true,
             selected: false,
         }
@@ -285,25 +335,25 @@ impl DecoratorBuilder {
 
     /// Sets a desired brush for `Normal` state.
     pub fn with_normal_brush(mut self, brush: Brush) -> Self {
-        self.normal_brush = brush;
+        self.normal_brush = Some(brush);
         self
     }
 
     /// Sets a desired brush for `Hovered` state.
     pub fn with_hover_brush(mut self, brush: Brush) -> Self {
-        self.hover_brush = brush;
+        self.hover_brush = Some(brush);
         self
     }
 
     /// Sets a desired brush for `Pressed` state.
     pub fn with_pressed_brush(mut self, brush: Brush) -> Self {
-        self.pressed_brush = brush;
+        self.pressed_brush = Some(brush);
         self
     }
 
     /// Sets a desired brush for `Selected` state.
     pub fn with_selected_brush(mut self, brush: Brush) -> Self {
-        self.selected_brush = brush;
+        self.selected_brush = Some(brush);
         self
     }
 
@@ -321,11 +371,48 @@ impl DecoratorBuilder {
 
     /// Finishes decorator instance building.
     pub fn build(mut self, ui: &mut BuildContext) -> Handle<UiNode> {
-        let normal_brush = self.normal_brush;
-        let selected_brush = self.selected_brush;
+        let style = ui.style().data_ref();
+        // Brushes that weren't set explicitly are taken from the style.
+        let mut brush_styles = DecoratorStyle::default();
+        let styled = |brush: Option<Brush>,
+                      name: &'static str,
+                      default: Brush,
+                      binding: &mut Option<ImmutableString>| {
+            brush.unwrap_or_else(|| {
+                *binding = Some(ImmutableString::new(name));
+                style.get_brush(name).unwrap_or(default)
+            })
+        };
+        let normal_brush = styled(
+            self.normal_brush,
+            Style::DECORATOR_BRUSH_NORMAL,
+            BRUSH_LIGHT,
+            &mut brush_styles.normal,
+        );
+        let hover_brush = styled(
+            self.hover_brush,
+            Style::DECORATOR_BRUSH_HOVER,
+            BRUSH_LIGHTER,
+            &mut brush_styles.hover,
+        );
+        let pressed_brush = styled(
+            self.pressed_brush,
+            Style::DECORATOR_BRUSH_PRESSED,
+            BRUSH_LIGHTEST,
+            &mut brush_styles.pressed,
+        );
+        let selected_brush = styled(
+            self.selected_brush,
+            Style::DECORATOR_BRUSH_SELECTED,
+            BRUSH_BRIGHT,
+            &mut brush_styles.selected,
+        );
+        drop(style);
 
-        if self.border_builder.widget_builder.foreground.is_none() {
-            self.border_builder.widget_builder.foreground = Some(BRUSH_DARKER);
+        let widget_builder = &mut self.border_builder.widget_builder;
+        if widget_builder.foreground.is_none() && widget_builder.foreground_style.is_none() {
+            widget_builder.foreground_style =
+                Some(ImmutableString::new(Style::DECORATOR_BRUSH_BORDER));
         }
 
         let mut border = self.border_builder.build_border();
@@ -339,11 +426,12 @@ impl DecoratorBuilder {
         let node = UiNode::new(Decorator {
             border,
             normal_brush: normal_brush.into(),
-            hover_brush: self.hover_brush.into(),
-            pressed_brush: self.pressed_brush.into(),
+            hover_brush: hover_brush.into(),
+            pressed_brush: pressed_brush.into(),
             selected_brush: selected_brush.into(),
             is_selected: self.selected.into(),
             is_pressable: self.pressable.into(),
+            brush_styles,
         });
         ui.add_node(node)
     }
@@ -36,6 +36,10 @@ pub enum TileContentDescriptor {
     Empty,
     Window(String),
     SplitTiles(SplitTilesDescriptor),
+    MultiWindow {
+        index: u32,
+        names: Vec<String>,
+    },
 }
 
 impl TileContentDescriptor {
@@ -47,6 +51,13 @@ impl TileContentDescriptor {
                     .map(|w| w.name.clone())
                     .unwrap_or_default(),
             ),
+            TileContent::MultiWindow { index, windows } => Self::MultiWindow {
+                index: *index,
+                names: windows
+                    .iter()
+                    .map(|w| ui.try_get(*w).map(|w| w.name.clone()).unwrap_or_default())
+                    .collect(),
+            },
             TileContent::VerticalTiles { splitter, tiles } => {
                 Self::SplitTiles(SplitTilesDescriptor {
                     splitter: *splitter,
@@ -65,6 +76,34 @@ impl TileContentDescriptor {
     }
 }
 
+fn find_window(
+    window_name: &str,
+    ui: &mut UserInterface,
+    windows: &[Handle<UiNode>],
+) -> Handle<UiNode> {
+    let mut window_handle = ui.find_handle(ui.root(), &mut |n| n.name() == window_name);
+
+    if window_handle.is_none() {
+        for other_window_handle in windows.iter().cloned() {
+            if let Some(window_node) = ui.try_get(other_window_handle) {
+                if window_node.name == window_name {
+                    window_handle = other_window_handle;
+                }
+            }
+        }
+    }
+
+    if window_handle.is_some() {
+        ui.send_message(WindowMessage::open(
+            window_handle,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    window_handle
+}
+
 #[derive(Debug, PartialEq, Clone, Visit, Default, Serialize, Deserialize)]
 pub struct TileDescriptor {
     pub content: TileContentDescriptor,
@@ -96,31 +135,30 @@ impl TileDescriptor {
             .with_content(match &self.content {
                 TileContentDescriptor::Empty => TileContent::Empty,
                 TileContentDescriptor::Window(window_name) => {
-                    let mut window_handle =
-                        ui.find_handle(ui.root(), &mut |n| n.name() == window_name);
-
-                    if window_handle.is_none() {
-                        for other_window_handle in windows.iter().cloned() {
-                            if let Some(window_node) = ui.try_get(other_window_handle) {
-                                if &window_node.name == window_name {
-                                    window_handle = other_window_handle;
-                                }
-                            }
-                        }
-                    }
+                    let window_handle = find_window(window_name, ui, windows);
 
                     if window_handle.is_some() {
-                        ui.send_message(WindowMessage::open(
-                            window_handle,
-                            MessageDirection::ToWidget,
-                            false,
-                        ));
-
                         TileContent::Window(window_handle)
                     } else {
                         TileContent::Empty
                     }
                 }
+                TileContentDescriptor::MultiWindow { index, names } => {
+                    let found_windows = names
+                        .iter()
+                        .map(|name| find_window(name, ui, windows))
+                        .filter(|h| h.is_some())
+                        .collect::<Vec<_>>();
+
+                    match found_windows.len() {
+                        0 => TileContent::Empty,
+                        1 => TileContent::Window(found_windows[0]),
+                        len => TileContent::MultiWindow {
+                            index: (*index).min(len as u32 - 1),
+                            windows: found_windows,
+                        },
+                    }
+                }
                 TileContentDescriptor::SplitTiles(split_tiles) => match split_tiles.orientation {
                     Orientation::Vertical => TileContent::VerticalTiles {
                         splitter: split_tiles.splitter,
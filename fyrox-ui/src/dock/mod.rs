@@ -71,6 +71,16 @@ impl Control for DockingManager {
                                         windows.push(window);
                                     }
                                 }
+                                TileContent::MultiWindow {
+                                    windows: ref tile_windows,
+                                    ..
+                                } => {
+                                    for &window in tile_windows {
+                                        if ui.try_get(window).is_some() {
+                                            windows.push(window);
+                                        }
+                                    }
+                                }
                                 TileContent::VerticalTiles { tiles, .. }
                                 | TileContent::HorizontalTiles { tiles, .. } => {
                                     stack.extend_from_slice(&tiles);
@@ -1,15 +1,20 @@
 use crate::{
     border::BorderBuilder,
     brush::Brush,
+    button::{ButtonBuilder, ButtonMessage},
     core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
     core::{reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
+    decorator::DecoratorBuilder,
     define_constructor,
     dock::DockingManager,
     grid::{Column, GridBuilder, Row},
     message::{CursorIcon, MessageDirection, UiMessage},
+    stack_panel::StackPanelBuilder,
+    text::Text,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     window::{Window, WindowMessage},
-    BuildContext, Control, Thickness, UiNode, UserInterface,
+    BuildContext, Control, Orientation, Thickness, UiNode, UserInterface, BRUSH_BRIGHT_BLUE,
+    BRUSH_DARKER, BRUSH_LIGHT,
 };
 use fyrox_core::uuid_provider;
 use fyrox_graph::SceneGraph;
@@ -55,12 +60,77 @@ pub enum TileContent {
         /// will most likely not work.
         tiles: [Handle<UiNode>; 2],
     },
+    /// A set of windows stacked on top of each other, only one window (at `index`) is visible at
+    /// a time. The tile shows a tab header for each window, that could be used to switch between them.
+    MultiWindow {
+        index: u32,
+        windows: Vec<Handle<UiNode>>,
+    },
 }
 
 impl TileContent {
     pub fn is_empty(&self) -> bool {
         matches!(self, TileContent::Empty)
     }
+
+    /// Checks whether the tile content is a window (or a set of windows) or not.
+    pub fn contains_window(&self, window: Handle<UiNode>) -> bool {
+        match self {
+            TileContent::Window(tile_window) => *tile_window == window,
+            TileContent::MultiWindow { windows, .. } => windows.contains(&window),
+            _ => false,
+        }
+    }
+
+    /// Returns a new content, that contains every window of the current content and the given
+    /// window. The new window will be the active one.
+    pub fn plus_window(self, window: Handle<UiNode>) -> Self {
+        match self {
+            TileContent::Empty => TileContent::Window(window),
+            TileContent::Window(existing) => TileContent::MultiWindow {
+                index: 1,
+                windows: vec![existing, window],
+            },
+            TileContent::MultiWindow { mut windows, .. } => {
+                windows.push(window);
+                TileContent::MultiWindow {
+                    index: windows.len() as u32 - 1,
+                    windows,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Returns a new content without the given window. Multi-window content with just one window
+    /// left is converted to a single window content.
+    pub fn minus_window(self, window: Handle<UiNode>) -> Self {
+        match self {
+            TileContent::Window(existing) if existing == window => TileContent::Empty,
+            TileContent::MultiWindow { index, mut windows } => {
+                windows.retain(|w| *w != window);
+                match windows.len() {
+                    0 => TileContent::Empty,
+                    1 => TileContent::Window(windows[0]),
+                    len => TileContent::MultiWindow {
+                        index: index.min(len as u32 - 1),
+                        windows,
+                    },
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+fn window_title(window: Handle<UiNode>, ui: &UserInterface) -> String {
+    ui.try_get(window)
+        .and_then(|w| w.query_component::<Window>())
+        .and_then(|w| ui.try_get(w.title))
+        .and_then(|t| t.query_component::<Text>())
+        .map(|t| t.text())
+        .or_else(|| ui.try_get(window).map(|w| w.name.clone()))
+        .unwrap_or_default()
 }
 
 #[derive(Default, Clone, Debug, Visit, Reflect, ComponentProvider)]
@@ -75,8 +145,13 @@ pub struct Tile {
     pub splitter: Handle<UiNode>,
     pub dragging_splitter: bool,
     pub drop_anchor: Cell<Handle<UiNode>>,
+    /// A panel with tab headers, it is visible only if the tile has multiple windows.
+    #[visit(optional)]
+    pub tab_bar: Handle<UiNode>,
 }
 
+pub const DEFAULT_TAB_BAR_HEIGHT: f32 = 22.0;
+
 crate::define_widget_deref!(Tile);
 
 uuid_provider!(Tile = "8ed17fa9-890e-4dd7-b4f9-a24660882234");
@@ -113,6 +188,16 @@ impl Control for Tile {
                         available_size
                     }
                 }
+                TileContent::MultiWindow { ref windows, .. } => {
+                    if windows.contains(&child_handle) {
+                        Vector2::new(
+                            available_size.x,
+                            (available_size.y - DEFAULT_TAB_BAR_HEIGHT).max(0.0),
+                        )
+                    } else {
+                        available_size
+                    }
+                }
                 _ => available_size,
             };
 
@@ -187,6 +272,20 @@ impl Control for Tile {
                         full_bounds
                     }
                 }
+                TileContent::MultiWindow { ref windows, .. } => {
+                    if windows.contains(&child_handle) {
+                        Rect::new(
+                            0.0,
+                            DEFAULT_TAB_BAR_HEIGHT,
+                            final_size.x,
+                            (final_size.y - DEFAULT_TAB_BAR_HEIGHT).max(0.0),
+                        )
+                    } else if self.tab_bar == child_handle {
+                        Rect::new(0.0, 0.0, final_size.x, DEFAULT_TAB_BAR_HEIGHT)
+                    } else {
+                        full_bounds
+                    }
+                }
                 _ => full_bounds,
             };
 
@@ -195,7 +294,7 @@ impl Control for Tile {
             // Main difference between tile arrangement and other arrangement methods in
             // library is that tile has to explicitly set width of child windows, otherwise
             // layout will be weird - window will most likely will stay at its previous size.
-            if child_handle != self.splitter {
+            if child_handle != self.splitter && child_handle != self.tab_bar {
                 ui.send_message(WidgetMessage::width(
                     child_handle,
                     MessageDirection::ToWidget,
@@ -221,6 +320,8 @@ impl Control for Tile {
                     TileMessage::Content(content) => {
                         self.content = content.clone();
 
+                        self.sync_tab_bar(ui);
+
                         match content {
                             TileContent::Empty => {
                                 ui.send_message(WidgetMessage::visibility(
@@ -247,6 +348,34 @@ impl Control for Tile {
                                     MessageDirection::ToWidget,
                                     false,
                                 ));
+
+                                // The window could be hidden if it was a part of multi-window tile.
+                                ui.send_message(WidgetMessage::visibility(
+                                    window,
+                                    MessageDirection::ToWidget,
+                                    true,
+                                ));
+                            }
+                            TileContent::MultiWindow { windows, .. } => {
+                                for &window in windows {
+                                    ui.send_message(WidgetMessage::link(
+                                        window,
+                                        MessageDirection::ToWidget,
+                                        self.handle(),
+                                    ));
+
+                                    ui.send_message(WindowMessage::can_resize(
+                                        window,
+                                        MessageDirection::ToWidget,
+                                        false,
+                                    ));
+                                }
+
+                                ui.send_message(WidgetMessage::visibility(
+                                    self.splitter,
+                                    MessageDirection::ToWidget,
+                                    false,
+                                ));
                             }
                             TileContent::VerticalTiles { tiles, .. }
                             | TileContent::HorizontalTiles { tiles, .. } => {
@@ -299,12 +428,28 @@ impl Control for Tile {
                         direction,
                         first,
                     } => {
-                        if matches!(self.content, TileContent::Window(_)) {
+                        if matches!(
+                            self.content,
+                            TileContent::Window(_) | TileContent::MultiWindow { .. }
+                        ) {
                             self.split(ui, window, direction, first);
                         }
                     }
                 }
             }
+        } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if let Some(new_index) = ui.try_get(self.tab_bar).and_then(|tab_bar| {
+                tab_bar
+                    .children()
+                    .iter()
+                    .position(|h| *h == message.destination())
+            }) {
+                if let TileContent::MultiWindow { ref mut index, .. } = self.content {
+                    *index = new_index as u32;
+                    self.sync_tab_bar(ui);
+                    self.invalidate_layout();
+                }
+            }
         } else if let Some(msg) = message.data::<WidgetMessage>() {
             match msg {
                 &WidgetMessage::MouseDown { .. } => {
@@ -379,6 +524,21 @@ impl Control for Tile {
                                                     false,
                                                 ));
                                             }
+                                            // Same as above, but for a set of windows.
+                                            TileContent::MultiWindow { ref windows, .. } => {
+                                                for &window in windows {
+                                                    ui.send_message(WidgetMessage::unlink(
+                                                        window,
+                                                        MessageDirection::ToWidget,
+                                                    ));
+                                                }
+
+                                                ui.send_message(TileMessage::content(
+                                                    self.handle,
+                                                    MessageDirection::ToWidget,
+                                                    sub_tile.content.clone(),
+                                                ));
+                                            }
                                             // In case if we have a split tile (vertically or horizontally) left in current tile
                                             // (which is split too) we must set content of current tile to content of sub tile.
                                             TileContent::VerticalTiles {
@@ -445,10 +605,7 @@ impl Control for Tile {
             match msg {
                 WindowMessage::Move(_) => {
                     // Check if we dragging child window.
-                    let content_moved = match self.content {
-                        TileContent::Window(window) => window == message.destination(),
-                        _ => false,
-                    };
+                    let content_moved = self.content.contains_window(message.destination());
 
                     if content_moved {
                         if let Some(window) = ui.node(message.destination()).cast::<Window>() {
@@ -456,7 +613,15 @@ impl Control for Tile {
                                 ui.send_message(TileMessage::content(
                                     self.handle,
                                     MessageDirection::ToWidget,
-                                    TileContent::Empty,
+                                    self.content.clone().minus_window(message.destination()),
+                                ));
+
+                                // The window could be hidden if it was an inactive window of
+                                // multi-window tile.
+                                ui.send_message(WidgetMessage::visibility(
+                                    message.destination(),
+                                    MessageDirection::ToWidget,
+                                    true,
                                 ));
 
                                 ui.send_message(WidgetMessage::unlink(
@@ -483,6 +648,31 @@ impl Control for Tile {
                     }
                 }
                 WindowMessage::Close => match self.content {
+                    TileContent::MultiWindow { ref windows, .. }
+                        if windows.contains(&message.destination()) =>
+                    {
+                        let closed_window = message.destination();
+
+                        ui.send_message(WidgetMessage::unlink(
+                            closed_window,
+                            MessageDirection::ToWidget,
+                        ));
+
+                        ui.send_message(TileMessage::content(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                            self.content.clone().minus_window(closed_window),
+                        ));
+
+                        if let Some((_, docking_manager)) =
+                            ui.find_component_up::<DockingManager>(self.parent())
+                        {
+                            docking_manager
+                                .floating_windows
+                                .borrow_mut()
+                                .push(closed_window);
+                        }
+                    }
                     TileContent::VerticalTiles { tiles, .. }
                     | TileContent::HorizontalTiles { tiles, .. } => {
                         let closed_window = message.destination();
@@ -523,6 +713,14 @@ impl Control for Tile {
                                                 MessageDirection::ToWidget,
                                             ));
                                         }
+                                        TileContent::MultiWindow { ref windows, .. } => {
+                                            for &window in windows {
+                                                ui.send_message(WidgetMessage::unlink(
+                                                    window,
+                                                    MessageDirection::ToWidget,
+                                                ));
+                                            }
+                                        }
                                         TileContent::VerticalTiles {
                                             tiles: sub_tiles, ..
                                         }
@@ -589,7 +787,10 @@ impl Control for Tile {
                 {
                     match msg {
                         &WindowMessage::Move(_) => {
-                            if let TileContent::Empty | TileContent::Window(_) = self.content {
+                            if let TileContent::Empty
+                            | TileContent::Window(_)
+                            | TileContent::MultiWindow { .. } = self.content
+                            {
                                 // Show anchors.
                                 for &anchor in &self.anchors() {
                                     ui.send_message(WidgetMessage::visibility(
@@ -601,7 +802,10 @@ impl Control for Tile {
                             }
 
                             // Window can be docked only if current tile is not split already.
-                            if let TileContent::Empty | TileContent::Window(_) = self.content {
+                            if let TileContent::Empty
+                            | TileContent::Window(_)
+                            | TileContent::MultiWindow { .. } = self.content
+                            {
                                 // When window is being dragged, we should check which tile can accept it.
                                 let pos = ui.cursor_position;
                                 for &anchor in &self.anchors() {
@@ -680,8 +884,17 @@ impl Control for Tile {
                                             ));
                                         }
                                     }
-                                    TileContent::Window(_) => {
-                                        if self.drop_anchor.get() == self.left_anchor {
+                                    TileContent::Window(_) | TileContent::MultiWindow { .. } => {
+                                        if self.drop_anchor.get() == self.center_anchor {
+                                            // Stack the window on top of existing ones as a tab.
+                                            ui.send_message(TileMessage::content(
+                                                self.handle,
+                                                MessageDirection::ToWidget,
+                                                self.content
+                                                    .clone()
+                                                    .plus_window(message.destination()),
+                                            ));
+                                        } else if self.drop_anchor.get() == self.left_anchor {
                                             // Split horizontally, dock to left.
                                             ui.send_message(TileMessage::split(
                                                 self.handle,
@@ -749,6 +962,54 @@ impl Tile {
         ]
     }
 
+    /// Re-creates tab headers for multi-window content and updates visibility of the windows, so
+    /// only the active one is visible.
+    fn sync_tab_bar(&self, ui: &mut UserInterface) {
+        for &header in ui.node(self.tab_bar).children() {
+            ui.send_message(WidgetMessage::remove(header, MessageDirection::ToWidget));
+        }
+
+        let is_multi_window = matches!(self.content, TileContent::MultiWindow { .. });
+
+        ui.send_message(WidgetMessage::visibility(
+            self.tab_bar,
+            MessageDirection::ToWidget,
+            is_multi_window,
+        ));
+
+        if let TileContent::MultiWindow { index, ref windows } = self.content {
+            for (i, &window) in windows.iter().enumerate() {
+                let is_active = i == index as usize;
+
+                let header =
+                    ButtonBuilder::new(WidgetBuilder::new().with_margin(Thickness::right(1.0)))
+                        .with_back(
+                            DecoratorBuilder::new(BorderBuilder::new(
+                                WidgetBuilder::new().with_foreground(BRUSH_DARKER),
+                            ))
+                            .with_normal_brush(BRUSH_LIGHT)
+                            .with_selected_brush(BRUSH_BRIGHT_BLUE)
+                            .with_selected(is_active)
+                            .build(&mut ui.build_ctx()),
+                        )
+                        .with_text(&window_title(window, ui))
+                        .build(&mut ui.build_ctx());
+
+                ui.send_message(WidgetMessage::link(
+                    header,
+                    MessageDirection::ToWidget,
+                    self.tab_bar,
+                ));
+
+                ui.send_message(WidgetMessage::visibility(
+                    window,
+                    MessageDirection::ToWidget,
+                    is_active,
+                ));
+            }
+        }
+    }
+
     fn split(
         &mut self,
         ui: &mut UserInterface,
@@ -757,8 +1018,8 @@ impl Tile {
         first: bool,
     ) {
         let existing_content = match self.content {
-            TileContent::Window(existing_window) => existing_window,
-            _ => Handle::NONE,
+            TileContent::Window(_) | TileContent::MultiWindow { .. } => self.content.clone(),
+            _ => TileContent::Empty,
         };
 
         let first_tile = TileBuilder::new(WidgetBuilder::new())
@@ -781,11 +1042,11 @@ impl Tile {
             })
             .build(&mut ui.build_ctx());
 
-        if existing_content.is_some() {
+        if !existing_content.is_empty() {
             ui.send_message(TileMessage::content(
                 if first { second_tile } else { first_tile },
                 MessageDirection::ToWidget,
-                TileContent::Window(existing_content),
+                existing_content,
             ));
         }
 
@@ -898,7 +1159,12 @@ impl TileBuilder {
         .with_stroke_thickness(Thickness::uniform(0.0))
         .build(ctx);
 
-        if let TileContent::Window(window) = self.content {
+        let windows = match self.content {
+            TileContent::Window(window) => vec![window],
+            TileContent::MultiWindow { ref windows, .. } => windows.clone(),
+            _ => vec![],
+        };
+        for window in windows {
             if let Some(window) = ctx[window].cast_mut::<Window>() {
                 // Every docked window must be non-resizable (it means that it cannot be resized by user
                 // and it still can be resized by a proper message).
@@ -906,10 +1172,51 @@ impl TileBuilder {
             }
         }
 
+        let mut tab_headers = Vec::new();
+        if let TileContent::MultiWindow { index, ref windows } = self.content {
+            for (i, &window) in windows.iter().enumerate() {
+                let is_active = i == index as usize;
+
+                ctx[window].set_visibility(is_active);
+
+                let title = ctx
+                    .try_get_node(window)
+                    .and_then(|w| w.query_component::<Window>())
+                    .and_then(|w| ctx.try_get_node(w.title))
+                    .and_then(|t| t.query_component::<Text>())
+                    .map(|t| t.text())
+                    .unwrap_or_else(|| ctx[window].name.clone());
+
+                let back = DecoratorBuilder::new(BorderBuilder::new(
+                    WidgetBuilder::new().with_foreground(BRUSH_DARKER),
+                ))
+                .with_normal_brush(BRUSH_LIGHT)
+                .with_selected_brush(BRUSH_BRIGHT_BLUE)
+                .with_selected(is_active)
+                .build(ctx);
+
+                tab_headers.push(
+                    ButtonBuilder::new(WidgetBuilder::new().with_margin(Thickness::right(1.0)))
+                        .with_back(back)
+                        .with_text(&title)
+                        .build(ctx),
+                );
+            }
+        }
+
+        let tab_bar = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(matches!(self.content, TileContent::MultiWindow { .. }))
+                .with_children(tab_headers),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
         let children = match self.content {
             TileContent::Window(window) => vec![window],
             TileContent::VerticalTiles { tiles, .. } => vec![tiles[0], tiles[1]],
             TileContent::HorizontalTiles { tiles, .. } => vec![tiles[0], tiles[1]],
+            TileContent::MultiWindow { ref windows, .. } => windows.clone(),
             _ => vec![],
         };
 
@@ -919,6 +1226,7 @@ impl TileBuilder {
                 .with_preview_messages(true)
                 .with_child(grid)
                 .with_child(splitter)
+                .with_child(tab_bar)
                 .with_children(children)
                 .build(),
             left_anchor,
@@ -930,6 +1238,7 @@ impl TileBuilder {
             splitter,
             dragging_splitter: false,
             drop_anchor: Default::default(),
+            tab_bar,
         };
 
         ctx.add_node(UiNode::new(tile))
@@ -21,12 +21,17 @@ fn default_page_size() -> usize {
 pub struct FontImportOptions {
     #[serde(default = "default_page_size")]
     pub page_size: usize,
+    /// Paths of the fonts, that will be used to render characters that are missing in the font.
+    /// The fonts are checked in order.
+    #[serde(default)]
+    pub fallbacks: Vec<PathBuf>,
 }
 
 impl Default for FontImportOptions {
     fn default() -> Self {
         Self {
             page_size: default_page_size(),
+            fallbacks: Default::default(),
         }
     }
 }
@@ -57,9 +62,14 @@ impl ResourceLoader for FontLoader {
                 .await
                 .unwrap_or(default_import_options);
 
-            let font = Font::from_file(&path, import_options.page_size, io)
+            let mut font = Font::from_file(&path, import_options.page_size, io)
                 .await
                 .map_err(LoadError::new)?;
+            for fallback in import_options.fallbacks.iter() {
+                font.add_fallback_from_file(fallback, io)
+                    .await
+                    .map_err(LoadError::new)?;
+            }
             Ok(LoaderPayload::new(font))
         })
     }
@@ -62,6 +62,7 @@ impl Atlas {
     fn glyph(
         &mut self,
         font: &fontdue::Font,
+        fallbacks: &[fontdue::Font],
         unicode: char,
         height: FontHeight,
         page_size: usize,
@@ -74,9 +75,12 @@ impl Atlas {
             }
             None => {
                 // Char might be missing, because it wasn't requested earlier. Try to find
-                // it in the inner font and render/pack it.
+                // it in the inner font (or in the first fallback font that has it) and render/pack it.
 
-                if let Some(char_index) = font.chars().get(&unicode) {
+                if let Some((font, char_index)) = std::iter::once(font)
+                    .chain(fallbacks)
+                    .find_map(|font| font.chars().get(&unicode).map(|index| (font, *index)))
+                {
                     let (metrics, glyph_raster) =
                         font.rasterize_indexed(char_index.get(), height.0);
 
@@ -184,6 +188,10 @@ impl Atlas {
 pub struct Font {
     #[visit(skip)]
     pub inner: Option<fontdue::Font>,
+    /// A chain of fonts, that is used to render characters that are missing in the inner font. The
+    /// fonts are checked in order, the first font that has a character is used to render it.
+    #[visit(skip)]
+    pub fallbacks: Vec<fontdue::Font>,
     #[visit(skip)]
     pub atlases: FxHashMap<FontHeight, Atlas>,
     #[visit(skip)]
@@ -256,6 +264,7 @@ impl Font {
         let fontdue_font = fontdue::Font::from_bytes(data, fontdue::FontSettings::default())?;
         Ok(Font {
             inner: Some(fontdue_font),
+            fallbacks: Default::default(),
             atlases: Default::default(),
             page_size,
         })
@@ -273,10 +282,47 @@ impl Font {
         }
     }
 
+    /// Adds a font from the given data to the end of the fallback chain. Fallback fonts are used to
+    /// render characters that are missing in the main font (for example, CJK characters). The data
+    /// could be a font collection (`.ttc`), in this case the first font of the collection is used.
+    pub fn add_fallback_from_memory(
+        &mut self,
+        data: impl Deref<Target = [u8]>,
+    ) -> Result<(), &'static str> {
+        self.fallbacks.push(fontdue::Font::from_bytes(
+            data,
+            fontdue::FontSettings::default(),
+        )?);
+        Ok(())
+    }
+
+    /// Loads a font from the given file and adds it to the end of the fallback chain. See
+    /// [`Self::add_fallback_from_memory`] for more info.
+    pub async fn add_fallback_from_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        io: &dyn ResourceIo,
+    ) -> Result<(), &'static str> {
+        if let Ok(file_content) = io.load_file(path.as_ref()).await {
+            self.add_fallback_from_memory(file_content)
+        } else {
+            Err("Unable to read file")
+        }
+    }
+
+    /// Removes every fallback font of the font. Already rendered glyphs of the fallback fonts are
+    /// removed as well.
+    pub fn clear_fallbacks(&mut self) {
+        if !self.fallbacks.is_empty() {
+            self.fallbacks.clear();
+            self.atlases.clear();
+        }
+    }
+
     /// Tries to get a glyph at the given unicode position of the given height. If there's no rendered
     /// glyph, this method tries to render the glyph and put into a suitable atlas (see [`Atlas`] docs
     /// for more info). If the given unicode position has no representation in the font, [`None`] will
-    /// be returned. If the requested size of the glyph is too big to fit into the page size of the
+    /// be returned (fallback fonts are checked before that). If the requested size of the glyph is too big to fit into the page size of the
     /// font, [`None`] will be returned. Keep in mind, that this method is free to create as many atlases
     /// with any number of pages in them. Each atlas corresponds to a particular glyph size, each glyph
     /// in the atlas could be rendered at any page in the atlas.
@@ -293,6 +339,7 @@ impl Font {
                 self.inner
                     .as_ref()
                     .expect("Font reader must be initialized!"),
+                &self.fallbacks,
                 unicode,
                 FontHeight(height),
                 self.page_size,
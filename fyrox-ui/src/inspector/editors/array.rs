@@ -117,6 +117,9 @@ where
         step: array_property_info.step,
         precision: array_property_info.precision,
         description: array_property_info.description,
+        multiline: array_property_info.multiline,
+        options: array_property_info.options,
+        extensions: array_property_info.extensions,
         type_name: array_property_info.type_name,
         doc: array_property_info.doc,
     })
@@ -312,7 +315,8 @@ where
         let editor;
         let container = make_expander_container(
             ctx.layer_index,
-            ctx.property_info.display_name,
+            &ctx.definition_container
+                .property_display_name(ctx.property_info.display_name),
             ctx.property_info.description,
             Handle::NONE,
             {
@@ -1,6 +1,9 @@
 use crate::{
+    border::BorderBuilder,
+    brush::Brush,
     button::{ButtonBuilder, ButtonMessage},
     core::{
+        color::Color,
         combine_uuids,
         pool::Handle,
         reflect::{FieldInfo, FieldValue, Reflect},
@@ -20,8 +23,10 @@ use crate::{
         make_expander_container, make_property_margin, CollectionChanged, FieldKind,
         InspectorEnvironment, InspectorError, ObjectValue, PropertyChanged, PropertyFilter,
     },
-    message::{MessageDirection, UiMessage},
+    message::{CursorIcon, MessageDirection, UiMessage},
     stack_panel::StackPanelBuilder,
+    text::TextBuilder,
+    utils::make_simple_tooltip,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
     VerticalAlignment,
@@ -38,6 +43,9 @@ use std::{
 #[derive(Clone, Debug, PartialEq, Default, Visit, Reflect)]
 pub struct Item {
     editor_instance: PropertyEditorInstance,
+    drag_handle: Handle<UiNode>,
+    insert: Handle<UiNode>,
+    duplicate: Handle<UiNode>,
     remove: Handle<UiNode>,
 }
 
@@ -60,6 +68,10 @@ pub struct CollectionEditor<T: CollectionItem> {
     #[visit(skip)]
     #[reflect(hidden)]
     pub layer_index: usize,
+    /// A copy of the collection, that is used to duplicate items.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub values: Vec<T>,
     #[reflect(hidden)]
     #[visit(skip)]
     pub phantom: PhantomData<T>,
@@ -73,6 +85,7 @@ impl<T: CollectionItem> Clone for CollectionEditor<T> {
             items: self.items.clone(),
             panel: self.panel,
             layer_index: self.layer_index,
+            values: self.values.clone(),
             phantom: PhantomData,
         }
     }
@@ -127,6 +140,52 @@ impl<T: CollectionItem> Control for CollectionEditor<T> {
                     MessageDirection::FromWidget,
                     index,
                 ));
+            } else if let Some(index) = self
+                .items
+                .iter()
+                .position(|i| i.insert == message.destination())
+            {
+                ui.send_message(CollectionChanged::insert(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    index,
+                    ObjectValue {
+                        value: Box::<T>::default(),
+                    },
+                ));
+            } else if let Some(index) = self
+                .items
+                .iter()
+                .position(|i| i.duplicate == message.destination())
+            {
+                if let Some(value) = self.values.get(index) {
+                    ui.send_message(CollectionChanged::insert(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        index + 1,
+                        ObjectValue {
+                            value: Box::new(value.clone()),
+                        },
+                    ));
+                }
+            }
+        } else if let Some(WidgetMessage::Drop(dropped)) = message.data::<WidgetMessage>() {
+            // Items are dragged by their handles and dropped on the rows of other items.
+            if let (Some(old_index), Some(new_index)) = (
+                self.items.iter().position(|i| i.drag_handle == *dropped),
+                self.items
+                    .iter()
+                    .position(|i| ui.node(i.drag_handle).parent() == message.destination()),
+            ) {
+                if old_index != new_index {
+                    ui.send_message(CollectionChanged::move_item(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        old_index,
+                        new_index,
+                    ));
+                }
+                message.set_handled(true);
             }
         } else if let Some(msg) = message.data::<CollectionEditorMessage>() {
             if message.destination == self.handle {
@@ -202,15 +261,22 @@ fn create_item_views(items: &[Item], ctx: &mut BuildContext) -> Vec<Handle<UiNod
         .map(|item| {
             GridBuilder::new(
                 WidgetBuilder::new()
+                    .with_allow_drop(true)
                     .with_child(match item.editor_instance {
                         PropertyEditorInstance::Simple { editor } => editor,
                         PropertyEditorInstance::Custom { container, .. } => container,
                     })
+                    .with_child(item.drag_handle)
+                    .with_child(item.insert)
+                    .with_child(item.duplicate)
                     .with_child(item.remove),
             )
             .add_row(Row::stretch())
             .add_column(Column::stretch())
             .add_column(Column::auto())
+            .add_column(Column::auto())
+            .add_column(Column::auto())
+            .add_column(Column::auto())
             .build(ctx)
         })
         .collect::<Vec<_>>()
@@ -239,6 +305,9 @@ where
         step: collection_property_info.step,
         precision: collection_property_info.precision,
         description: collection_property_info.description,
+        multiline: collection_property_info.multiline,
+        options: collection_property_info.options,
+        extensions: collection_property_info.extensions,
         type_name: collection_property_info.type_name,
         doc: collection_property_info.doc,
     })
@@ -282,21 +351,54 @@ where
                 ctx[editor].set_margin(make_property_margin(layer_index + 1));
             }
 
-            let remove = ButtonBuilder::new(
+            let drag_handle = BorderBuilder::new(
                 WidgetBuilder::new()
                     .with_visibility(!immutable_collection)
                     .with_margin(Thickness::uniform(1.0))
                     .with_vertical_alignment(VerticalAlignment::Top)
-                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                    .with_width(12.0)
+                    .with_height(16.0)
+                    .with_allow_drag(true)
+                    .with_cursor(Some(CursorIcon::Grab))
+                    .with_tooltip(make_simple_tooltip(ctx, "Drag to reorder"))
+                    .with_background(Brush::Solid(Color::TRANSPARENT))
                     .on_column(1)
-                    .with_width(16.0)
-                    .with_height(16.0),
+                    .with_child(
+                        TextBuilder::new(WidgetBuilder::new())
+                            .with_text("::")
+                            .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx),
+                    ),
             )
-            .with_text("-")
+            .with_stroke_thickness(Thickness::zero())
             .build(ctx);
 
+            let mut make_button = |text: &str, tooltip: &str, column: usize| {
+                ButtonBuilder::new(
+                    WidgetBuilder::new()
+                        .with_visibility(!immutable_collection)
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_vertical_alignment(VerticalAlignment::Top)
+                        .with_horizontal_alignment(HorizontalAlignment::Right)
+                        .with_tooltip(make_simple_tooltip(ctx, tooltip))
+                        .on_column(column)
+                        .with_width(16.0)
+                        .with_height(16.0),
+                )
+                .with_text(text)
+                .build(ctx)
+            };
+
+            let insert = make_button("+", "Insert new item here", 2);
+            let duplicate = make_button("=", "Duplicate", 3);
+            let remove = make_button("-", "Remove", 4);
+
             items.push(Item {
                 editor_instance: editor,
+                drag_handle,
+                insert,
+                duplicate,
                 remove,
             });
         } else {
@@ -386,9 +488,12 @@ where
             .unwrap_or_else(|| Arc::new(PropertyEditorDefinitionContainer::new()));
 
         let environment = self.environment;
+        let mut values = Vec::new();
         let items = if let Some(collection) = self.collection {
             create_items(
-                collection,
+                collection
+                    .into_iter()
+                    .inspect(|value| values.push((*value).clone())),
                 environment,
                 definition_container,
                 property_info,
@@ -418,6 +523,7 @@ where
             items,
             panel,
             layer_index: self.layer_index,
+            values,
             phantom: PhantomData,
         };
 
@@ -483,7 +589,8 @@ where
         let editor;
         let container = make_expander_container(
             ctx.layer_index,
-            ctx.property_info.display_name,
+            &ctx.definition_container
+                .property_display_name(ctx.property_info.display_name),
             ctx.property_info.description,
             add,
             {
@@ -523,6 +630,13 @@ where
             filter,
         } = ctx;
 
+        let value = property_info.cast_value::<Vec<T>>()?;
+
+        // Keep a copy of the collection, it is used to duplicate items.
+        if let Some(instance_mut) = ui.node_mut(instance).cast_mut::<CollectionEditor<T>>() {
+            instance_mut.values.clone_from(value);
+        }
+
         let instance_ref = if let Some(instance) = ui.node(instance).cast::<CollectionEditor<T>>() {
             instance
         } else {
@@ -531,8 +645,6 @@ where
             ));
         };
 
-        let value = property_info.cast_value::<Vec<T>>()?;
-
         if value.len() != instance_ref.items.len() {
             // Re-create items.
             let items = create_items(
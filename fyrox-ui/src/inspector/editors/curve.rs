@@ -59,7 +59,7 @@ impl PropertyEditorDefinition for CurvePropertyEditorDefinition {
 
     fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
         if ctx.message.direction() == MessageDirection::FromWidget {
-            if let Some(CurveEditorMessage::Sync(value)) = ctx.message.data() {
+            if let Some(CurveEditorMessage::Changed(value)) = ctx.message.data() {
                 return Some(PropertyChanged {
                     name: ctx.name.to_string(),
                     owner_type_id: ctx.owner_type_id,
@@ -408,7 +408,8 @@ where
         let editor;
         let container = make_expander_container(
             ctx.layer_index,
-            ctx.property_info.display_name,
+            &ctx.definition_container
+                .property_display_name(ctx.property_info.display_name),
             ctx.property_info.description,
             variant_selector,
             {
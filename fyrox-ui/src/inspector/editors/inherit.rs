@@ -1,7 +1,10 @@
 //! Property editor for [`InheritableVariable`]. It acts like a proxy to inner property, but also
-//! adds special "revert" button that is used to revert value to its parent's value.
+//! adds special "revert" button that is used to revert value to its parent's value. Modified
+//! properties are also highlighted with a colored marker on the left side of the editor.
 
 use crate::{
+    border::BorderBuilder,
+    brush::Brush,
     button::{ButtonBuilder, ButtonMessage},
     core::{
         pool::Handle, reflect::prelude::*, reflect::FieldValue, type_traits::prelude::*,
@@ -21,7 +24,7 @@ use crate::{
     utils::make_simple_tooltip,
     widget::WidgetBuilder,
     BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface, VerticalAlignment,
-    Widget, WidgetMessage,
+    Widget, WidgetMessage, BRUSH_BRIGHT_BLUE,
 };
 use fyrox_graph::SceneGraph;
 use std::{
@@ -45,6 +48,8 @@ impl InheritablePropertyEditorMessage {
 pub struct InheritablePropertyEditor {
     widget: Widget,
     revert: Handle<UiNode>,
+    #[visit(optional)]
+    modified_marker: Handle<UiNode>,
     inner_editor: Handle<UiNode>,
 }
 
@@ -77,11 +82,13 @@ impl Control for InheritablePropertyEditor {
             }
         } else if let Some(InheritablePropertyEditorMessage::Modified(modified)) = message.data() {
             if message.destination() == self.handle {
-                ui.send_message(WidgetMessage::visibility(
-                    self.revert,
-                    MessageDirection::ToWidget,
-                    *modified,
-                ));
+                for widget in [self.revert, self.modified_marker] {
+                    ui.send_message(WidgetMessage::visibility(
+                        widget,
+                        MessageDirection::ToWidget,
+                        *modified,
+                    ));
+                }
             }
         }
 
@@ -127,23 +134,48 @@ impl InheritablePropertyEditorBuilder {
     }
 
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let modified_marker = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(self.modified)
+                .with_width(2.0)
+                .with_margin(Thickness::right(1.0))
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "The value is modified and overrides its parent's value",
+                ))
+                .with_background(BRUSH_BRIGHT_BLUE)
+                .with_foreground(Brush::Solid(Default::default()))
+                .on_column(0),
+        )
+        .build(ctx);
+
+        if let Some(container) = ctx.try_get_node_mut(self.container) {
+            container.set_column(1);
+        }
+
         let revert;
-        let grid = GridBuilder::new(WidgetBuilder::new().with_child(self.container).with_child({
-            revert = ButtonBuilder::new(
-                WidgetBuilder::new()
-                    .with_visibility(self.modified)
-                    .with_width(16.0)
-                    .with_height(16.0)
-                    .with_vertical_alignment(VerticalAlignment::Top)
-                    .with_tooltip(make_simple_tooltip(ctx, "Revert To Parent"))
-                    .with_margin(Thickness::uniform(1.0))
-                    .on_column(1),
-            )
-            .with_text("<")
-            .build(ctx);
-            revert
-        }))
+        let grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(modified_marker)
+                .with_child(self.container)
+                .with_child({
+                    revert = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_visibility(self.modified)
+                            .with_width(16.0)
+                            .with_height(16.0)
+                            .with_vertical_alignment(VerticalAlignment::Top)
+                            .with_tooltip(make_simple_tooltip(ctx, "Revert To Parent"))
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_column(2),
+                    )
+                    .with_text("<")
+                    .build(ctx);
+                    revert
+                }),
+        )
         .add_row(Row::auto())
+        .add_column(Column::auto())
         .add_column(Column::stretch())
         .add_column(Column::auto())
         .build(ctx);
@@ -151,6 +183,7 @@ impl InheritablePropertyEditorBuilder {
         ctx.add_node(UiNode::new(InheritablePropertyEditor {
             widget: self.widget_builder.with_child(grid).build(),
             revert,
+            modified_marker,
             inner_editor: self.inner_editor,
         }))
     }
@@ -206,6 +239,9 @@ where
         step: property_info.step,
         precision: property_info.precision,
         description: property_info.description,
+        multiline: property_info.multiline,
+        options: property_info.options,
+        extensions: property_info.extensions,
         type_name: property_info.type_name,
         doc: property_info.doc,
     })
@@ -186,15 +186,22 @@ pub trait PropertyEditorDefinition: Debug + Send + Sync {
     fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged>;
 }
 
+/// A function, that maps display names of properties to the names that will be shown in an
+/// inspector. Could be used to localize property names.
+pub type PropertyNameTranslator = fn(&str) -> String;
+
 #[derive(Default)]
 pub struct PropertyEditorDefinitionContainer {
     definitions: RwLock<FxHashMap<TypeId, Arc<dyn PropertyEditorDefinition>>>,
+    /// Optional translator of property names, names are shown as is if it is not set.
+    pub property_name_translator: Option<PropertyNameTranslator>,
 }
 
 impl Clone for PropertyEditorDefinitionContainer {
     fn clone(&self) -> Self {
         Self {
             definitions: RwLock::new(self.definitions.read().clone()),
+            property_name_translator: self.property_name_translator,
         }
     }
 }
@@ -530,4 +537,13 @@ impl PropertyEditorDefinitionContainer {
     ) -> RwLockReadGuard<FxHashMap<TypeId, Arc<dyn PropertyEditorDefinition>>> {
         self.definitions.read()
     }
+
+    /// Returns the name of a property, that should be shown in an inspector. See
+    /// [`Self::property_name_translator`] for more info.
+    pub fn property_display_name(&self, display_name: &str) -> String {
+        match self.property_name_translator {
+            Some(translator) => translator(display_name),
+            None => display_name.to_string(),
+        }
+    }
 }
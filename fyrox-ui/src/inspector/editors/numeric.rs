@@ -1,5 +1,8 @@
 use crate::{
+    border::BorderBuilder,
     core::num_traits::NumCast,
+    decorator::DecoratorBuilder,
+    dropdown_list::{DropdownListBuilder, DropdownListMessage},
     inspector::{
         editors::{
             PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
@@ -9,8 +12,9 @@ use crate::{
     },
     message::{MessageDirection, UiMessage},
     numeric::{NumericType, NumericUpDownBuilder, NumericUpDownMessage},
+    text::TextBuilder,
     widget::WidgetBuilder,
-    Thickness,
+    HorizontalAlignment, Thickness, VerticalAlignment,
 };
 use std::{any::TypeId, marker::PhantomData};
 
@@ -46,6 +50,37 @@ where
         ctx: PropertyEditorBuildContext,
     ) -> Result<PropertyEditorInstance, InspectorError> {
         let value = ctx.property_info.cast_value::<T>()?;
+
+        // Named options turns the editor into a drop-down list, where index of an option is the value.
+        if !ctx.property_info.options.is_empty() {
+            return Ok(PropertyEditorInstance::Simple {
+                editor: DropdownListBuilder::new(
+                    WidgetBuilder::new().with_margin(Thickness::top_bottom(1.0)),
+                )
+                .with_selected(value.to_usize().unwrap_or_default())
+                .with_items(
+                    ctx.property_info
+                        .options
+                        .iter()
+                        .map(|option| {
+                            DecoratorBuilder::new(BorderBuilder::new(
+                                WidgetBuilder::new().with_child(
+                                    TextBuilder::new(WidgetBuilder::new())
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                                        .with_text(*option)
+                                        .build(ctx.build_context),
+                                ),
+                            ))
+                            .build(ctx.build_context)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .with_close_on_selection(true)
+                .build(ctx.build_context),
+            });
+        }
+
         Ok(PropertyEditorInstance::Simple {
             editor: NumericUpDownBuilder::new(
                 WidgetBuilder::new().with_margin(Thickness::top_bottom(1.0)),
@@ -79,6 +114,15 @@ where
         ctx: PropertyEditorMessageContext,
     ) -> Result<Option<UiMessage>, InspectorError> {
         let value = ctx.property_info.cast_value::<T>()?;
+
+        if !ctx.property_info.options.is_empty() {
+            return Ok(Some(DropdownListMessage::selection(
+                ctx.instance,
+                MessageDirection::ToWidget,
+                value.to_usize(),
+            )));
+        }
+
         Ok(Some(NumericUpDownMessage::value(
             ctx.instance,
             MessageDirection::ToWidget,
@@ -96,6 +140,14 @@ where
                     owner_type_id: ctx.owner_type_id,
                     value: FieldKind::object(*value),
                 });
+            } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) =
+                ctx.message.data::<DropdownListMessage>()
+            {
+                return Some(PropertyChanged {
+                    name: ctx.name.to_string(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::object(<T as NumCast>::from(*index)?),
+                });
             }
         }
 
@@ -1,4 +1,5 @@
 use crate::{
+    core::num_traits::NumCast,
     inspector::{
         editors::{
             PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
@@ -37,10 +38,18 @@ impl<T: NumericType> PropertyEditorDefinition for RangePropertyEditorDefinition<
     ) -> Result<PropertyEditorInstance, InspectorError> {
         let value = ctx.property_info.cast_value::<Range<T>>()?;
 
+        let mut builder = RangeEditorBuilder::new(WidgetBuilder::new()).with_value(value.clone());
+
+        // Show the slider track only if the property has both bounds.
+        if let (Some(min), Some(max)) = (
+            ctx.property_info.min_value.and_then(NumCast::from),
+            ctx.property_info.max_value.and_then(NumCast::from),
+        ) {
+            builder = builder.with_bounds(min..max);
+        }
+
         Ok(PropertyEditorInstance::Simple {
-            editor: RangeEditorBuilder::new(WidgetBuilder::new())
-                .with_value(value.clone())
-                .build(ctx.build_context),
+            editor: builder.build(ctx.build_context),
         })
     }
 
@@ -65,6 +65,9 @@ where
         step: property_info.step,
         precision: property_info.precision,
         description: property_info.description,
+        multiline: property_info.multiline,
+        options: property_info.options,
+        extensions: property_info.extensions,
         type_name: property_info.type_name,
         doc: property_info.doc,
     })
@@ -29,15 +29,21 @@ impl PropertyEditorDefinition for StringPropertyEditorDefinition {
         ctx: PropertyEditorBuildContext,
     ) -> Result<PropertyEditorInstance, InspectorError> {
         let value = ctx.property_info.cast_value::<String>()?;
+        let multiline = ctx.property_info.multiline;
         Ok(PropertyEditorInstance::Simple {
             editor: TextBoxBuilder::new(
                 WidgetBuilder::new()
-                    .with_min_size(Vector2::new(0.0, 17.0))
+                    .with_min_size(Vector2::new(0.0, if multiline { 68.0 } else { 17.0 }))
                     .with_margin(Thickness::uniform(1.0)),
             )
             .with_wrap(WrapMode::Word)
+            .with_multiline(multiline)
             .with_text(value)
-            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .with_vertical_text_alignment(if multiline {
+                VerticalAlignment::Top
+            } else {
+                VerticalAlignment::Center
+            })
             .build(ctx.build_context),
         })
     }
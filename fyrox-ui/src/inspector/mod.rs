@@ -49,6 +49,21 @@ pub enum CollectionChanged {
     Add(ObjectValue),
     /// An item in the collection should be removed.
     Remove(usize),
+    /// An item should be inserted in the collection at the given index.
+    Insert {
+        /// Index at which the item should be inserted.
+        index: usize,
+        /// New item.
+        value: ObjectValue,
+    },
+    /// An item in the collection should be moved to a new position.
+    Move {
+        /// Current index of the item.
+        old_index: usize,
+        /// New index of the item. The index is calculated as if the item was already removed from
+        /// its old position.
+        new_index: usize,
+    },
     /// An item in the collection has changed one of its properties.
     ItemChanged {
         /// Index of an item in the collection.
@@ -60,6 +75,8 @@ pub enum CollectionChanged {
 impl CollectionChanged {
     define_constructor!(CollectionChanged:Add => fn add(ObjectValue), layout: false);
     define_constructor!(CollectionChanged:Remove => fn remove(usize), layout: false);
+    define_constructor!(CollectionChanged:Insert => fn insert(index: usize, value: ObjectValue), layout: false);
+    define_constructor!(CollectionChanged:Move => fn move_item(old_index: usize, new_index: usize), layout: false);
     define_constructor!(CollectionChanged:ItemChanged => fn item_changed(index: usize, property: FieldKind), layout: false);
 }
 
@@ -94,6 +111,20 @@ pub enum PropertyAction {
         /// Index of an item.
         index: usize,
     },
+    /// An item needs to be inserted in a collection property at the given index.
+    InsertItem {
+        /// Index at which the item should be inserted.
+        index: usize,
+        /// New collection item.
+        value: Box<dyn Reflect>,
+    },
+    /// An item of a collection property needs to be moved to a new position.
+    MoveItem {
+        /// Current index of an item.
+        old_index: usize,
+        /// New index of an item.
+        new_index: usize,
+    },
     /// Revert value to parent.
     Revert,
 }
@@ -112,6 +143,17 @@ impl PropertyAction {
                     value: value.clone().into_box_reflect(),
                 },
                 CollectionChanged::Remove(index) => Self::RemoveItem { index },
+                CollectionChanged::Insert { index, ref value } => Self::InsertItem {
+                    index,
+                    value: value.clone().into_box_reflect(),
+                },
+                CollectionChanged::Move {
+                    old_index,
+                    new_index,
+                } => Self::MoveItem {
+                    old_index,
+                    new_index,
+                },
                 CollectionChanged::ItemChanged { ref property, .. } => {
                     Self::from_field_kind(property)
                 }
@@ -187,6 +229,55 @@ impl PropertyAction {
                     result_callback(Err(Self::RemoveItem { index }))
                 }
             }),
+            PropertyAction::InsertItem { index, value } => {
+                let mut value = Some(value);
+                target.resolve_path_mut(path, &mut |result| {
+                    if let Ok(field) = result {
+                        field.as_list_mut(&mut |result| {
+                            if let Some(list) = result {
+                                if let Err(value) =
+                                    list.reflect_insert(index, value.take().unwrap())
+                                {
+                                    result_callback(Err(Self::InsertItem { index, value }))
+                                } else {
+                                    result_callback(Ok(None))
+                                }
+                            } else {
+                                result_callback(Err(Self::InsertItem {
+                                    index,
+                                    value: value.take().unwrap(),
+                                }))
+                            }
+                        })
+                    } else {
+                        result_callback(Err(Self::InsertItem {
+                            index,
+                            value: value.take().unwrap(),
+                        }))
+                    }
+                })
+            }
+            PropertyAction::MoveItem {
+                old_index,
+                new_index,
+            } => target.resolve_path_mut(path, &mut |result| {
+                let mut moved = false;
+                if let Ok(field) = result {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            moved = move_list_item(list, old_index, new_index);
+                        }
+                    })
+                }
+                if moved {
+                    result_callback(Ok(None))
+                } else {
+                    result_callback(Err(Self::MoveItem {
+                        old_index,
+                        new_index,
+                    }))
+                }
+            }),
             PropertyAction::Revert => {
                 // Unsupported due to lack of context (a reference to parent entity).
                 result_callback(Err(Self::Revert))
@@ -195,6 +286,25 @@ impl PropertyAction {
     }
 }
 
+/// Moves an item of the given list from one position to another. `new_index` is calculated as if
+/// the item was already removed from its old position. Returns `true` if the item was moved.
+pub fn move_list_item(list: &mut dyn ReflectList, old_index: usize, new_index: usize) -> bool {
+    if old_index >= list.reflect_len() || new_index >= list.reflect_len() {
+        return false;
+    }
+    if let Some(item) = list.reflect_remove(old_index) {
+        if let Err(item) = list.reflect_insert(new_index, item) {
+            // Put the item back, it should never fail.
+            let _ = list.reflect_insert(old_index, item);
+            false
+        } else {
+            true
+        }
+    } else {
+        false
+    }
+}
+
 pub trait Value: Reflect + Debug + Send {
     fn clone_box(&self) -> Box<dyn Value>;
 
@@ -319,6 +429,8 @@ impl PropertyChanged {
             FieldKind::Collection(ref collection_changed) => match **collection_changed {
                 CollectionChanged::Add(_) => false,
                 CollectionChanged::Remove(_) => false,
+                CollectionChanged::Insert { .. } => false,
+                CollectionChanged::Move { .. } => false,
                 CollectionChanged::ItemChanged { ref property, .. } => match property {
                     FieldKind::Inspectable(inspectable) => inspectable.is_inheritable(),
                     FieldKind::Inheritable(_) => true,
@@ -687,10 +799,12 @@ impl InspectorContext {
                     continue;
                 }
 
+                let display_name = definition_container.property_display_name(info.display_name);
+
                 let description = if info.description.is_empty() {
-                    info.display_name.to_string()
+                    display_name.clone()
                 } else {
-                    format!("{}\n\n{}", info.display_name, info.description)
+                    format!("{}\n\n{}", display_name, info.description)
                 };
 
                 if let Some(definition) = definition_container
@@ -711,7 +825,7 @@ impl InspectorContext {
                             let (container, editor) = match instance {
                                 PropertyEditorInstance::Simple { editor } => (
                                     make_simple_property_container(
-                                        create_header(ctx, info.display_name, layer_index),
+                                        create_header(ctx, &display_name, layer_index),
                                         editor,
                                         &description,
                                         ctx,
@@ -739,7 +853,7 @@ impl InspectorContext {
                             container
                         }
                         Err(e) => make_simple_property_container(
-                            create_header(ctx, info.display_name, layer_index),
+                            create_header(ctx, &display_name, layer_index),
                             TextBuilder::new(WidgetBuilder::new().on_row(i).on_column(1))
                                 .with_wrap(WrapMode::Word)
                                 .with_vertical_text_alignment(VerticalAlignment::Center)
@@ -757,7 +871,7 @@ impl InspectorContext {
                     editors.push(editor);
                 } else {
                     editors.push(make_simple_property_container(
-                        create_header(ctx, info.display_name, layer_index),
+                        create_header(ctx, &display_name, layer_index),
                         TextBuilder::new(WidgetBuilder::new().on_row(i).on_column(1))
                             .with_wrap(WrapMode::Word)
                             .with_vertical_text_alignment(VerticalAlignment::Center)
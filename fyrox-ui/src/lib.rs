@@ -184,6 +184,7 @@ pub use fyrox_core as core;
 use message::TouchPhase;
 
 mod alignment;
+pub mod binding;
 pub mod bit;
 pub mod border;
 pub mod brush;
@@ -194,6 +195,7 @@ pub mod check_box;
 pub mod color;
 mod control;
 pub mod curve;
+pub mod data_grid;
 pub mod decorator;
 pub mod dock;
 pub mod draw;
@@ -219,12 +221,14 @@ pub mod popup;
 pub mod progress_bar;
 pub mod range;
 pub mod rect;
+pub mod rich_text;
 pub mod screen;
 pub mod scroll_bar;
 pub mod scroll_panel;
 pub mod scroll_viewer;
 pub mod searchbar;
 pub mod stack_panel;
+pub mod style;
 pub mod tab_control;
 pub mod text;
 pub mod text_box;
@@ -239,6 +243,7 @@ pub mod window;
 pub mod wrap_panel;
 
 use crate::{
+    binding::Bindings,
     brush::Brush,
     canvas::Canvas,
     constructor::WidgetConstructorContainer,
@@ -261,6 +266,7 @@ use crate::{
         UiMessage,
     },
     popup::{Placement, PopupMessage},
+    style::{StyleResource, DEFAULT_STYLE},
     widget::{Widget, WidgetBuilder, WidgetMessage},
 };
 use copypasta::ClipboardContext;
@@ -636,8 +642,14 @@ pub struct UserInterface {
     pub default_font: FontResource,
     #[visit(skip)]
     #[reflect(hidden)]
+    style: StyleResource,
+    #[visit(skip)]
+    #[reflect(hidden)]
     double_click_entries: FxHashMap<MouseButton, DoubleClickEntry>,
     pub double_click_time_slice: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    bindings: Bindings,
 }
 
 impl Clone for UserInterface {
@@ -678,8 +690,10 @@ impl Clone for UserInterface {
             layout_events_sender,
             need_update_global_transform: self.need_update_global_transform,
             default_font: self.default_font.clone(),
+            style: self.style.clone(),
             double_click_entries: self.double_click_entries.clone(),
             double_click_time_slice: self.double_click_time_slice,
+            bindings: self.bindings.clone(),
         }
     }
 }
@@ -832,8 +846,10 @@ impl UserInterface {
             layout_events_sender,
             need_update_global_transform: Default::default(),
             default_font: BUILT_IN_FONT.clone(),
+            style: DEFAULT_STYLE.clone(),
             double_click_entries: Default::default(),
             double_click_time_slice: 0.5, // 500 ms is standard in most operating systems.
+            bindings: Default::default(),
         };
         ui.root_canvas = ui.add_node(UiNode::new(Canvas {
             widget: WidgetBuilder::new().build(),
@@ -951,6 +967,40 @@ impl UserInterface {
         self.screen_size = screen_size;
     }
 
+    /// Returns a reference to the bindings of the user interface. See [`Bindings`] docs for more info.
+    pub fn bindings(&self) -> &Bindings {
+        &self.bindings
+    }
+
+    /// Returns a reference to the bindings of the user interface. The bindings are updated automatically
+    /// on every [`Self::update`] call. See [`Bindings`] docs for more info.
+    pub fn bindings_mut(&mut self) -> &mut Bindings {
+        &mut self.bindings
+    }
+
+    /// Returns current style of the user interface.
+    pub fn style(&self) -> &StyleResource {
+        &self.style
+    }
+
+    /// Sets a new style of the user interface and sends [`WidgetMessage::Style`] message to every widget, so they can
+    /// update their look.
+    pub fn set_style(&mut self, style: StyleResource) {
+        self.style = style;
+
+        let mut stack = vec![self.root_canvas];
+        while let Some(handle) = stack.pop() {
+            if let Some(node) = self.nodes.try_borrow(handle) {
+                self.send_message(WidgetMessage::style(
+                    handle,
+                    MessageDirection::ToWidget,
+                    self.style.clone(),
+                ));
+                stack.extend_from_slice(node.children());
+            }
+        }
+    }
+
     fn handle_layout_events(&mut self) {
         fn invalidate_recursive_up(
             nodes: &Pool<UiNode, WidgetContainer>,
@@ -1000,6 +1050,9 @@ impl UserInterface {
             entry.timer -= dt;
         }
 
+        // Notify widgets, which bound sources have changed.
+        self.bindings.update(self, None);
+
         self.handle_layout_events();
 
         self.measure_node(self.root_canvas, screen_size);
@@ -1859,6 +1912,11 @@ impl UserInterface {
         self.captured_node
     }
 
+    /// Returns a handle of the widget, that currently has keyboard focus.
+    pub fn keyboard_focus_node(&self) -> Handle<UiNode> {
+        self.keyboard_focus_node
+    }
+
     // Tries to set new picked node (a node under the cursor) and returns `true` if the node was
     // changed.
     fn try_set_picked_node(&mut self, node: Handle<UiNode>) -> bool {
@@ -2375,6 +2433,7 @@ impl UserInterface {
             self.link_nodes(child, node_handle, false)
         }
         let node = self.nodes[node_handle].deref_mut();
+        node.apply_style(&self.style.data_ref());
         node.layout_events_sender = Some(self.layout_events_sender.clone());
         if node.preview_messages {
             self.preview_set.insert(node_handle);
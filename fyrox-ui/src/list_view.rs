@@ -7,22 +7,58 @@ use crate::{
     border::BorderBuilder,
     brush::Brush,
     core::{
-        color::Color, pool::Handle, reflect::prelude::*, type_traits::prelude::*,
-        visitor::prelude::*,
+        algebra::Vector2, color::Color, parking_lot::Mutex, pool::Handle, reflect::prelude::*,
+        type_traits::prelude::*, visitor::prelude::*,
     },
     decorator::{Decorator, DecoratorMessage},
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::{MessageDirection, UiMessage},
+    scroll_bar::ScrollBar,
+    scroll_panel::ScrollPanelMessage,
     scroll_viewer::{ScrollViewer, ScrollViewerBuilder, ScrollViewerMessage},
     stack_panel::StackPanelBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, Thickness, UiNode, UserInterface, BRUSH_DARK, BRUSH_LIGHT,
 };
-use fyrox_core::uuid_provider;
 use fyrox_core::variable::InheritableVariable;
+use fyrox_core::{log::Log, uuid_provider};
 use fyrox_graph::SceneGraph;
-use std::ops::{Deref, DerefMut};
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut, Range},
+    sync::{mpsc::Sender, Arc},
+};
+
+/// Item provider is a source of the items for a virtualized [`ListView`]. The list view asks the provider to build only
+/// the items that are currently visible, so the provider could hold any number of items. See [`ListView#virtualization`]
+/// for more info.
+pub trait ListViewItemProvider: Debug + Send {
+    /// Returns total amount of items.
+    fn item_count(&self) -> usize;
+
+    /// Builds a widget for an item at the given index.
+    fn build_item(&mut self, index: usize, ctx: &mut BuildContext) -> Handle<UiNode>;
+}
+
+/// A shared reference to a [`ListViewItemProvider`].
+#[derive(Debug, Clone)]
+pub struct ListViewItemProviderRef(pub Arc<Mutex<dyn ListViewItemProvider>>);
+
+impl ListViewItemProviderRef {
+    /// Wraps the given provider in a shared reference.
+    pub fn new<P: ListViewItemProvider + 'static>(provider: P) -> Self {
+        Self(Arc::new(Mutex::new(provider)))
+    }
+}
+
+impl PartialEq for ListViewItemProviderRef {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ListViewItemProviderRef {}
 
 /// A set of messages that can be used to modify/fetch the state of a [`ListView`] widget at runtime.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,6 +73,11 @@ pub enum ListViewMessage {
     RemoveItem(Handle<UiNode>),
     /// A message, that is used to bring an item into view.
     BringItemIntoView(Handle<UiNode>),
+    /// A message, that is used to set new item provider of a virtualized list view.
+    ItemProvider(ListViewItemProviderRef),
+    /// A message, that forces a virtualized list view to re-create its visible items. It should be sent every time when
+    /// the data of the item provider has changed.
+    RefreshItems,
 }
 
 impl ListViewMessage {
@@ -60,6 +101,14 @@ impl ListViewMessage {
         /// Creates [`ListViewMessage::BringItemIntoView`] message.
         ListViewMessage:BringItemIntoView => fn bring_item_into_view(Handle<UiNode>), layout: false
     );
+    define_constructor!(
+        /// Creates [`ListViewMessage::ItemProvider`] message.
+        ListViewMessage:ItemProvider => fn item_provider(ListViewItemProviderRef), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ListViewMessage::RefreshItems`] message.
+        ListViewMessage:RefreshItems => fn refresh_items(), layout: false
+    );
 }
 
 /// List view is used to display lists with arbitrary items. It supports single-selection and by default, it stacks the items
@@ -215,6 +264,47 @@ impl ListViewMessage {
 ///     ));
 /// }
 /// ```
+///
+/// ## Virtualization
+///
+/// Every item of a list view is a widget, which is fine for dozens or even hundreds of items, but with thousands of items
+/// the list view becomes slow to build and to update. In this case the list view could be virtualized - instead of a set
+/// of items it takes an item provider ([`ListViewItemProvider`]) and builds only the items that are currently visible.
+/// Virtualized list view requires every item to have the same height:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     list_view::{ListViewBuilder, ListViewItemProvider, ListViewItemProviderRef},
+/// #     text::TextBuilder,
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// #[derive(Debug)]
+/// struct Numbers;
+///
+/// impl ListViewItemProvider for Numbers {
+///     fn item_count(&self) -> usize {
+///         100_000
+///     }
+///
+///     fn build_item(&mut self, index: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+///         TextBuilder::new(WidgetBuilder::new())
+///             .with_text(format!("Item {}", index))
+///             .build(ctx)
+///     }
+/// }
+///
+/// fn create_list(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     ListViewBuilder::new(WidgetBuilder::new())
+///         .with_item_provider(ListViewItemProviderRef::new(Numbers), 20.0)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Selection of a virtualized list view is an index of an item in the provider, [`ListView::items`] contains only the
+/// items that are currently visible. When the data of the provider has changed, send [`ListViewMessage::RefreshItems`]
+/// message to the list view to re-create its visible items.
 #[derive(Default, Clone, Visit, Reflect, Debug, ComponentProvider)]
 pub struct ListView {
     /// Base widget of the list view.
@@ -232,6 +322,21 @@ pub struct ListView {
     /// Current scroll viewer instance that is used to provide scrolling functionality, when items does
     /// not fit in the view entirely.
     pub scroll_viewer: InheritableVariable<Handle<UiNode>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    virtualization: Option<Virtualization>,
+}
+
+#[derive(Clone, Debug)]
+struct Virtualization {
+    provider: ListViewItemProviderRef,
+    item_height: f32,
+    /// Range of indices of the items (in the provider) that are currently materialized.
+    range: Range<usize>,
+    /// Empty widgets above and below of the items panel, that occupies the space of the items that are not materialized.
+    top_spacer: Handle<UiNode>,
+    bottom_spacer: Handle<UiNode>,
+    last_size: Vector2<f32>,
 }
 
 crate::define_widget_deref!(ListView);
@@ -242,14 +347,31 @@ impl ListView {
         &self.items
     }
 
+    /// Returns total amount of items of the list view. For virtualized list views it is the amount of items in the
+    /// provider, not the amount of materialized items.
+    pub fn item_count(&self) -> usize {
+        match self.virtualization {
+            Some(ref virtualization) => virtualization.provider.0.lock().item_count(),
+            None => self.items.len(),
+        }
+    }
+
+    /// Returns index of the first materialized item.
+    fn first_index(&self) -> usize {
+        self.virtualization
+            .as_ref()
+            .map_or(0, |virtualization| virtualization.range.start)
+    }
+
     fn fix_selection(&self, ui: &UserInterface) {
         // Check if current selection is out-of-bounds.
         if let Some(selected_index) = self.selected_index {
-            if selected_index >= self.items.len() {
-                let new_selection = if self.items.is_empty() {
+            let item_count = self.item_count();
+            if selected_index >= item_count {
+                let new_selection = if item_count == 0 {
                     None
                 } else {
-                    Some(self.items.len() - 1)
+                    Some(item_count - 1)
                 };
 
                 ui.send_message(ListViewMessage::selection(
@@ -262,10 +384,11 @@ impl ListView {
     }
 
     fn sync_decorators(&self, ui: &UserInterface) {
+        let first_index = self.first_index();
         for (i, &container) in self.item_containers.iter().enumerate() {
             let select = match self.selected_index {
                 None => false,
-                Some(selected_index) => i == selected_index,
+                Some(selected_index) => first_index + i == selected_index,
             };
             if let Some(container) = ui.node(container).cast::<ListViewItem>() {
                 let mut stack = container.children().to_vec();
@@ -287,6 +410,82 @@ impl ListView {
             }
         }
     }
+
+    /// Materializes the items of a virtualized list view, that are currently visible, and destroys the rest.
+    fn sync_virtual_items(&mut self, ui: &mut UserInterface, force: bool) {
+        let Some(virtualization) = self.virtualization.as_mut() else {
+            return;
+        };
+
+        let (scroll, viewport_height) = ui
+            .try_get(*self.scroll_viewer)
+            .and_then(|n| n.cast::<ScrollViewer>())
+            .map_or((0.0, 0.0), |scroll_viewer| {
+                (
+                    ui.try_get(scroll_viewer.v_scroll_bar)
+                        .and_then(|n| n.cast::<ScrollBar>())
+                        .map_or(0.0, |scroll_bar| *scroll_bar.value),
+                    scroll_viewer.actual_local_size().y,
+                )
+            });
+
+        let item_height = virtualization.item_height.max(1.0);
+        let item_count = virtualization.provider.0.lock().item_count();
+        let first = ((scroll.max(0.0) / item_height) as usize).min(item_count);
+        let last = ((((scroll.max(0.0) + viewport_height) / item_height).ceil() as usize) + 1)
+            .min(item_count)
+            .max(first);
+        let range = first..last;
+
+        if !force && range == virtualization.range {
+            return;
+        }
+
+        for &container in self.item_containers.iter() {
+            ui.send_message(WidgetMessage::remove(container, MessageDirection::ToWidget));
+        }
+
+        let mut items = Vec::with_capacity(range.len());
+        let mut item_containers = Vec::with_capacity(range.len());
+        {
+            let mut provider = virtualization.provider.0.lock();
+            let ctx = &mut ui.build_ctx();
+            for index in range.clone() {
+                let item = provider.build_item(index, ctx);
+                let container = generate_item_container(ctx, item);
+                ctx[container].set_height(item_height);
+                items.push(item);
+                item_containers.push(container);
+            }
+        }
+
+        for &container in item_containers.iter() {
+            ui.send_message(WidgetMessage::link(
+                container,
+                MessageDirection::ToWidget,
+                *self.panel,
+            ));
+        }
+
+        ui.send_message(WidgetMessage::height(
+            virtualization.top_spacer,
+            MessageDirection::ToWidget,
+            range.start as f32 * item_height,
+        ));
+        ui.send_message(WidgetMessage::height(
+            virtualization.bottom_spacer,
+            MessageDirection::ToWidget,
+            (item_count - range.end) as f32 * item_height,
+        ));
+
+        virtualization.range = range;
+        self.items.set_value_and_mark_modified(items);
+        self.item_containers
+            .set_value_and_mark_modified(item_containers);
+
+        self.fix_selection(ui);
+        self.sync_decorators(ui);
+    }
 }
 
 /// A wrapper for list view items, that is used to add selection functionality to arbitrary items.
@@ -320,14 +519,16 @@ impl Control for ListViewItem {
 
         if let Some(WidgetMessage::MouseUp { .. }) = message.data::<WidgetMessage>() {
             if !message.handled() {
-                let self_index = ui
+                let list_view = ui
                     .node(parent_list_view)
                     .cast::<ListView>()
-                    .expect("Parent of ListViewItem must be ListView!")
-                    .item_containers
-                    .iter()
-                    .position(|c| *c == self.handle)
-                    .expect("ListViewItem must be used as a child of ListView");
+                    .expect("Parent of ListViewItem must be ListView!");
+                let self_index = list_view.first_index()
+                    + list_view
+                        .item_containers
+                        .iter()
+                        .position(|c| *c == self.handle)
+                        .expect("ListViewItem must be used as a child of ListView");
 
                 // Explicitly set selection on parent items control. This will send
                 // SelectionChanged message and all items will react.
@@ -345,10 +546,34 @@ impl Control for ListViewItem {
 uuid_provider!(ListView = "5832a643-5bf9-4d84-8358-b4c45bb440e8");
 
 impl Control for ListView {
+    fn update(&mut self, _dt: f32, sender: &Sender<UiMessage>, _screen_size: Vector2<f32>) {
+        // Amount of visible items depends on the size of the list view, so they must be re-created when the size has
+        // changed.
+        let size = self.actual_local_size();
+        if let Some(virtualization) = self.virtualization.as_mut() {
+            if size != virtualization.last_size {
+                virtualization.last_size = size;
+                let _ = sender.send(ListViewMessage::refresh_items(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
-        if let Some(msg) = message.data::<ListViewMessage>() {
+        if let Some(ScrollPanelMessage::VerticalScroll(_)) = message.data() {
+            if self.virtualization.is_some()
+                && ui
+                    .try_get(*self.scroll_viewer)
+                    .and_then(|n| n.cast::<ScrollViewer>())
+                    .is_some_and(|sv| sv.scroll_panel == message.destination())
+            {
+                self.sync_virtual_items(ui, false);
+            }
+        } else if let Some(msg) = message.data::<ListViewMessage>() {
             if message.destination() == self.handle()
                 && message.direction() == MessageDirection::ToWidget
             {
@@ -424,6 +649,17 @@ impl Control for ListView {
                             ));
                         }
                     }
+                    ListViewMessage::ItemProvider(provider) => {
+                        if let Some(virtualization) = self.virtualization.as_mut() {
+                            virtualization.provider = provider.clone();
+                            self.sync_virtual_items(ui, true);
+                        } else {
+                            Log::warn("Item provider can be set only for virtualized list views!");
+                        }
+                    }
+                    ListViewMessage::RefreshItems => {
+                        self.sync_virtual_items(ui, true);
+                    }
                 }
             }
         }
@@ -436,6 +672,7 @@ pub struct ListViewBuilder {
     items: Vec<Handle<UiNode>>,
     panel: Option<Handle<UiNode>>,
     scroll_viewer: Option<Handle<UiNode>>,
+    item_provider: Option<(ListViewItemProviderRef, f32)>,
 }
 
 impl ListViewBuilder {
@@ -446,6 +683,7 @@ impl ListViewBuilder {
             items: Vec::new(),
             panel: None,
             scroll_viewer: None,
+            item_provider: None,
         }
     }
 
@@ -467,16 +705,53 @@ impl ListViewBuilder {
         self
     }
 
+    /// Makes the list view virtualized - it will build only visible items using the given item provider. Every item will
+    /// have the given height. Items and items panel, that were set using [`Self::with_items`] and [`Self::with_items_panel`],
+    /// are ignored in this case. See [`ListView#virtualization`] for more info.
+    pub fn with_item_provider(
+        mut self,
+        provider: ListViewItemProviderRef,
+        item_height: f32,
+    ) -> Self {
+        self.item_provider = Some((provider, item_height));
+        self
+    }
+
     /// Finishes list view building and adds it to the user interface.
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
-        let item_containers = generate_item_containers(ctx, &self.items);
-
-        let panel = self.panel.unwrap_or_else(|| {
-            StackPanelBuilder::new(
-                WidgetBuilder::new().with_children(item_containers.iter().cloned()),
-            )
-            .build(ctx)
-        });
+        let (item_containers, items, panel, content, virtualization) =
+            if let Some((provider, item_height)) = self.item_provider {
+                let top_spacer =
+                    StackPanelBuilder::new(WidgetBuilder::new().with_height(0.0)).build(ctx);
+                let panel = StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+                let bottom_spacer =
+                    StackPanelBuilder::new(WidgetBuilder::new().with_height(0.0)).build(ctx);
+                let content = StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(top_spacer)
+                        .with_child(panel)
+                        .with_child(bottom_spacer),
+                )
+                .build(ctx);
+                let virtualization = Virtualization {
+                    provider,
+                    item_height,
+                    range: 0..0,
+                    top_spacer,
+                    bottom_spacer,
+                    last_size: Default::default(),
+                };
+                (Vec::new(), Vec::new(), panel, content, Some(virtualization))
+            } else {
+                let item_containers = generate_item_containers(ctx, &self.items);
+                let panel = self.panel.unwrap_or_else(|| {
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new().with_children(item_containers.iter().cloned()),
+                    )
+                    .build(ctx)
+                });
+                (item_containers, self.items, panel, panel, None)
+            };
 
         let back = BorderBuilder::new(
             WidgetBuilder::new()
@@ -493,9 +768,9 @@ impl ListViewBuilder {
         let scroll_viewer_ref = ctx[scroll_viewer]
             .cast_mut::<ScrollViewer>()
             .expect("ListView must have ScrollViewer");
-        scroll_viewer_ref.content = panel;
+        scroll_viewer_ref.content = content;
         let content_presenter = scroll_viewer_ref.scroll_panel;
-        ctx.link(panel, content_presenter);
+        ctx.link(content, content_presenter);
 
         ctx.link(scroll_viewer, back);
 
@@ -503,9 +778,10 @@ impl ListViewBuilder {
             widget: self.widget_builder.with_child(back).build(),
             selected_index: None,
             item_containers: item_containers.into(),
-            items: self.items.into(),
+            items: items.into(),
             panel: panel.into(),
             scroll_viewer: scroll_viewer.into(),
+            virtualization,
         };
 
         ctx.add_node(UiNode::new(list_box))
@@ -529,3 +805,49 @@ fn generate_item_containers(
         .map(|&item| generate_item_container(ctx, item))
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector2, pool::Handle},
+        list_view::{ListView, ListViewBuilder, ListViewItemProvider, ListViewItemProviderRef},
+        text::TextBuilder,
+        widget::WidgetBuilder,
+        BuildContext, UiNode, UserInterface,
+    };
+    use fyrox_graph::SceneGraph;
+
+    #[derive(Debug)]
+    struct Items(usize);
+
+    impl ListViewItemProvider for Items {
+        fn item_count(&self) -> usize {
+            self.0
+        }
+
+        fn build_item(&mut self, _index: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+            TextBuilder::new(WidgetBuilder::new()).build(ctx)
+        }
+    }
+
+    #[test]
+    fn test_virtualized_list_view() {
+        let screen_size = Vector2::new(100.0, 100.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let list_view = ListViewBuilder::new(WidgetBuilder::new())
+            .with_item_provider(ListViewItemProviderRef::new(Items(10_000)), 10.0)
+            .build(&mut ui.build_ctx());
+
+        for _ in 0..3 {
+            ui.update(screen_size, 0.0);
+            while ui.poll_message().is_some() {}
+        }
+
+        let list_view_ref = ui.node(list_view).cast::<ListView>().unwrap();
+        assert_eq!(list_view_ref.item_count(), 10_000);
+        // Only the items that fit in the view must be created.
+        assert!(!list_view_ref.items().is_empty());
+        assert!(list_view_ref.items().len() <= 12);
+    }
+}
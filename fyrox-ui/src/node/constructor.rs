@@ -10,6 +10,7 @@ use crate::{
     color::{AlphaBar, ColorField, ColorPicker, HueBar, SaturationBrightnessField},
     core::{parking_lot::Mutex, uuid::Uuid, TypeUuidProvider},
     curve::CurveEditor,
+    data_grid::DataGrid,
     decorator::Decorator,
     dock::{DockingManager, Tile},
     dropdown_list::DropdownList,
@@ -28,6 +29,7 @@ use crate::{
     progress_bar::ProgressBar,
     range::RangeEditor,
     rect::RectEditor,
+    rich_text::RichText,
     scroll_bar::ScrollBar,
     scroll_panel::ScrollPanel,
     scroll_viewer::ScrollViewer,
@@ -79,6 +81,7 @@ impl WidgetConstructorContainer {
         container.add::<Border>();
         container.add::<Button>();
         container.add::<Canvas>();
+        container.add::<DataGrid>();
         container.add::<CheckBox>();
         container.add::<Decorator>();
         container.add::<DropdownList>();
@@ -129,6 +132,7 @@ impl WidgetConstructorContainer {
 
         container.add::<PathEditor>();
         container.add::<ProgressBar>();
+        container.add::<RichText>();
         container.add::<ScrollBar>();
         container.add::<ScrollPanel>();
         container.add::<ScrollViewer>();
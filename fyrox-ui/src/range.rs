@@ -4,8 +4,12 @@
 #![warn(missing_docs)]
 
 use crate::{
+    border::BorderBuilder,
+    canvas::CanvasBuilder,
     core::{
+        algebra::Vector2,
         combine_uuids,
+        num_traits::NumCast,
         pool::Handle,
         reflect::prelude::*,
         type_traits::prelude::*,
@@ -18,10 +22,12 @@ use crate::{
     message::{MessageDirection, UiMessage},
     numeric::{NumericType, NumericUpDownBuilder, NumericUpDownMessage},
     text::TextBuilder,
-    widget::{Widget, WidgetBuilder},
-    BuildContext, Control, Thickness, UiNode, UserInterface, VerticalAlignment,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, Thickness, UiNode, UserInterface, VerticalAlignment, BRUSH_BRIGHT_BLUE,
+    BRUSH_DARKER, BRUSH_LIGHTEST,
 };
 use fyrox_core::variable::InheritableVariable;
+use fyrox_graph::SceneGraph;
 use std::ops::{Deref, DerefMut, Range};
 
 /// A set of messages, that can be used to modify/fetch the state of a [`RangeEditor`] widget instance.
@@ -103,6 +109,26 @@ impl<T: NumericType> RangeEditorMessage<T> {
 ///
 /// Be very careful about the type of the range when sending a message, you need to send a range of exact type, that match the type
 /// of your editor, otherwise the message have no effect. The same applied to fetching.
+///
+/// ## Slider
+///
+/// Range editor can optionally show a slider track with two draggable thumbs under the numeric fields, which allows
+/// you to set the range graphically. The slider is shown only if the editor has bounds, use
+/// [`RangeEditorBuilder::with_bounds`] to set them:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, range::RangeEditorBuilder, widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_range_editor_with_slider(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     RangeEditorBuilder::new(WidgetBuilder::new())
+///         .with_value(1.0f32..2.5f32)
+///         .with_bounds(0.0f32..10.0f32)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Bounds also limits the values that could be entered in the numeric fields.
 #[derive(Default, Debug, Clone, Reflect, Visit, ComponentProvider)]
 pub struct RangeEditor<T>
 where
@@ -116,6 +142,20 @@ where
     pub start: InheritableVariable<Handle<UiNode>>,
     /// A handle to numeric field that is used to show/modify end value of current range.
     pub end: InheritableVariable<Handle<UiNode>>,
+    /// Optional bounds of the range. Slider track is shown only if the bounds are set.
+    pub bounds: InheritableVariable<Option<Range<T>>>,
+    /// A handle to a canvas, that is used as a slider track. Could be unassigned if the editor has no bounds.
+    pub track: InheritableVariable<Handle<UiNode>>,
+    /// A handle to a widget, that is used to highlight the current range on the slider track.
+    pub selection: InheritableVariable<Handle<UiNode>>,
+    /// A handle to a thumb, that is used to change the start value of the range.
+    pub start_thumb: InheritableVariable<Handle<UiNode>>,
+    /// A handle to a thumb, that is used to change the end value of the range.
+    pub end_thumb: InheritableVariable<Handle<UiNode>>,
+    /// A handle of a thumb that is currently being dragged.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub dragged_thumb: Handle<UiNode>,
 }
 
 impl<T> Deref for RangeEditor<T>
@@ -139,6 +179,36 @@ where
 }
 
 const SYNC_FLAG: u64 = 1;
+const THUMB_WIDTH: f32 = 8.0;
+
+fn value_to_fraction<T>(value: T, bounds: &Range<T>) -> f32
+where
+    T: NumericType,
+{
+    let value = <f64 as NumCast>::from(value).unwrap_or_default();
+    let min = <f64 as NumCast>::from(bounds.start).unwrap_or_default();
+    let max = <f64 as NumCast>::from(bounds.end).unwrap_or_default();
+    let span = max - min;
+    if span > 0.0 {
+        ((value - min) / span).clamp(0.0, 1.0) as f32
+    } else {
+        0.0
+    }
+}
+
+fn fraction_to_value<T>(fraction: f32, bounds: &Range<T>) -> T
+where
+    T: NumericType,
+{
+    let min = <f64 as NumCast>::from(bounds.start).unwrap_or_default();
+    let max = <f64 as NumCast>::from(bounds.end).unwrap_or_default();
+    let mut value = min + fraction.clamp(0.0, 1.0) as f64 * (max - min);
+    if <T as NumCast>::from(0.5).map_or(true, |v: T| v == T::zero()) {
+        // Integer types must be rounded to the nearest value instead of truncation.
+        value = value.round();
+    }
+    <T as NumCast>::from(value).unwrap_or(bounds.start)
+}
 
 impl<T> TypeUuidProvider for RangeEditor<T>
 where
@@ -156,14 +226,120 @@ impl<T> Control for RangeEditor<T>
 where
     T: NumericType,
 {
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        let size = self.widget.arrange_override(ui, final_size);
+
+        if let Some(bounds) = self.bounds.as_ref() {
+            // Adjust thumbs and selection according to the current value.
+            let track_size = ui.node(*self.track).actual_local_size();
+            let span = (track_size.x - THUMB_WIDTH).max(0.0);
+            let start = value_to_fraction(self.value.start, bounds) * span;
+            let end = value_to_fraction(self.value.end, bounds) * span;
+
+            for (thumb, position) in [(*self.start_thumb, start), (*self.end_thumb, end)] {
+                ui.send_message(WidgetMessage::height(
+                    thumb,
+                    MessageDirection::ToWidget,
+                    track_size.y,
+                ));
+                ui.send_message(WidgetMessage::desired_position(
+                    thumb,
+                    MessageDirection::ToWidget,
+                    Vector2::new(position, 0.0),
+                ));
+            }
+
+            ui.send_message(WidgetMessage::desired_position(
+                *self.selection,
+                MessageDirection::ToWidget,
+                Vector2::new(start + THUMB_WIDTH * 0.5, track_size.y * 0.25),
+            ));
+            ui.send_message(WidgetMessage::width(
+                *self.selection,
+                MessageDirection::ToWidget,
+                end - start,
+            ));
+            ui.send_message(WidgetMessage::height(
+                *self.selection,
+                MessageDirection::ToWidget,
+                track_size.y * 0.5,
+            ));
+        }
+
+        size
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            if message.destination() == *self.start_thumb
+                || message.destination() == *self.end_thumb
+            {
+                match msg {
+                    WidgetMessage::MouseDown { .. } => {
+                        self.dragged_thumb = message.destination();
+                        ui.capture_mouse(message.destination());
+                        message.set_handled(true);
+                    }
+                    WidgetMessage::MouseUp { .. } => {
+                        self.dragged_thumb = Handle::NONE;
+                        ui.release_mouse_capture();
+                        message.set_handled(true);
+                    }
+                    WidgetMessage::MouseMove { pos, .. } => {
+                        if let (true, Some(bounds)) = (
+                            self.dragged_thumb == message.destination(),
+                            self.bounds.as_ref(),
+                        ) {
+                            let track = ui.node(*self.track);
+                            let span = track.actual_global_size().x - THUMB_WIDTH;
+                            let fraction = if span > 0.0 {
+                                (pos.x - track.screen_position().x - THUMB_WIDTH * 0.5) / span
+                            } else {
+                                0.0
+                            };
+                            let value = fraction_to_value(fraction, bounds);
+
+                            let new_range = if self.dragged_thumb == *self.start_thumb {
+                                Range {
+                                    start: if value > self.value.end {
+                                        self.value.end
+                                    } else {
+                                        value
+                                    },
+                                    end: self.value.end,
+                                }
+                            } else {
+                                Range {
+                                    start: self.value.start,
+                                    end: if value < self.value.start {
+                                        self.value.start
+                                    } else {
+                                        value
+                                    },
+                                }
+                            };
+
+                            ui.send_message(RangeEditorMessage::value(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                new_range,
+                            ));
+                            message.set_handled(true);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
         if message.direction() == MessageDirection::ToWidget && message.flags != SYNC_FLAG {
             if let Some(RangeEditorMessage::Value(range)) = message.data::<RangeEditorMessage<T>>()
             {
                 if message.destination() == self.handle && *self.value != *range {
                     self.value.set_value_and_mark_modified(range.clone());
+                    self.invalidate_arrange();
 
                     ui.send_message(NumericUpDownMessage::value(
                         *self.start,
@@ -232,6 +408,7 @@ where
 {
     widget_builder: WidgetBuilder,
     value: Range<T>,
+    bounds: Option<Range<T>>,
 }
 
 impl<T> RangeEditorBuilder<T>
@@ -243,6 +420,7 @@ where
         Self {
             widget_builder,
             value: Range::default(),
+            bounds: None,
         }
     }
 
@@ -252,14 +430,27 @@ where
         self
     }
 
+    /// Sets the desired bounds (min and max values) of the editor. When the bounds are set, the editor will
+    /// show a slider track with draggable thumbs, that could be used to change the range graphically.
+    pub fn with_bounds(mut self, bounds: Range<T>) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
     /// Finished widget building and adds the new instance to the user interface.
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let (min, max) = match self.bounds.as_ref() {
+            Some(bounds) => (bounds.start, bounds.end),
+            None => (T::min_value(), T::max_value()),
+        };
         let start = NumericUpDownBuilder::new(
             WidgetBuilder::new()
                 .with_margin(Thickness::uniform(1.0))
                 .on_column(0),
         )
         .with_value(self.value.start)
+        .with_min_value(min)
+        .with_max_value(max)
         .build(ctx);
         let end = NumericUpDownBuilder::new(
             WidgetBuilder::new()
@@ -267,38 +458,120 @@ where
                 .on_column(2),
         )
         .with_value(self.value.end)
+        .with_min_value(min)
+        .with_max_value(max)
         .build(ctx);
+
+        let mut track = Handle::NONE;
+        let mut selection = Handle::NONE;
+        let mut start_thumb = Handle::NONE;
+        let mut end_thumb = Handle::NONE;
+        if self.bounds.is_some() {
+            let make_thumb = |ctx: &mut BuildContext| {
+                BorderBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(THUMB_WIDTH)
+                        .with_background(BRUSH_LIGHTEST)
+                        .with_foreground(BRUSH_DARKER),
+                )
+                .with_stroke_thickness(Thickness::uniform(1.0))
+                .build(ctx)
+            };
+            selection = BorderBuilder::new(WidgetBuilder::new().with_background(BRUSH_BRIGHT_BLUE))
+                .build(ctx);
+            start_thumb = make_thumb(ctx);
+            end_thumb = make_thumb(ctx);
+            track = CanvasBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(selection)
+                    .with_child(start_thumb)
+                    .with_child(end_thumb),
+            )
+            .build(ctx);
+        }
+
         let editor = RangeEditor {
             widget: self
                 .widget_builder
                 .with_child(
                     GridBuilder::new(
                         WidgetBuilder::new()
-                            .with_child(start)
                             .with_child(
-                                TextBuilder::new(
+                                GridBuilder::new(
                                     WidgetBuilder::new()
-                                        .on_column(1)
-                                        .with_margin(Thickness::uniform(1.0)),
+                                        .with_child(start)
+                                        .with_child(
+                                            TextBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .on_column(1)
+                                                    .with_margin(Thickness::uniform(1.0)),
+                                            )
+                                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                                            .with_text("..")
+                                            .build(ctx),
+                                        )
+                                        .with_child(end),
                                 )
-                                .with_vertical_text_alignment(VerticalAlignment::Center)
-                                .with_text("..")
+                                .add_column(Column::stretch())
+                                .add_column(Column::strict(10.0))
+                                .add_column(Column::stretch())
+                                .add_row(Row::stretch())
                                 .build(ctx),
                             )
-                            .with_child(end),
+                            .with_child(
+                                BorderBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_row(1)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_visibility(track.is_some())
+                                        .with_background(BRUSH_DARKER)
+                                        .with_child(track),
+                                )
+                                .build(ctx),
+                            ),
                     )
                     .add_column(Column::stretch())
-                    .add_column(Column::strict(10.0))
-                    .add_column(Column::stretch())
                     .add_row(Row::stretch())
+                    .add_row(if track.is_some() {
+                        Row::strict(12.0)
+                    } else {
+                        Row::strict(0.0)
+                    })
                     .build(ctx),
                 )
                 .build(),
             value: self.value.into(),
             start: start.into(),
             end: end.into(),
+            bounds: self.bounds.into(),
+            track: track.into(),
+            selection: selection.into(),
+            start_thumb: start_thumb.into(),
+            end_thumb: end_thumb.into(),
+            dragged_thumb: Handle::NONE,
         };
 
         ctx.add_node(UiNode::new(editor))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::range::{fraction_to_value, value_to_fraction};
+
+    #[test]
+    fn test_value_to_fraction() {
+        assert_eq!(value_to_fraction(5.0f32, &(0.0..10.0)), 0.5);
+        assert_eq!(value_to_fraction(-5.0f32, &(0.0..10.0)), 0.0);
+        assert_eq!(value_to_fraction(15u32, &(0..10)), 1.0);
+        assert_eq!(value_to_fraction(3i32, &(3..3)), 0.0);
+    }
+
+    #[test]
+    fn test_fraction_to_value() {
+        assert_eq!(fraction_to_value(0.5, &(0.0f32..10.0)), 5.0);
+        assert_eq!(fraction_to_value(2.0, &(0.0f32..10.0)), 10.0);
+        assert_eq!(fraction_to_value(0.26, &(0u32..10)), 3);
+        assert_eq!(fraction_to_value(0.24, &(-10i32..10)), -5);
+    }
+}
@@ -0,0 +1,697 @@
+//! Rich text is a text with runs of different fonts, sizes, colors, inline images and clickable links. See [`RichText`]
+//! docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    brush::Brush,
+    core::{
+        algebra::{Matrix3, Vector2},
+        color::Color,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    define_constructor,
+    font::FontResource,
+    image::ImageBuilder,
+    message::{CursorIcon, MessageDirection, MouseButton, UiMessage},
+    stack_panel::StackPanelBuilder,
+    style::Style,
+    text::TextBuilder,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    wrap_panel::WrapPanelBuilder,
+    BuildContext, Control, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    BRUSH_BRIGHT_BLUE,
+};
+use fxhash::FxHashMap;
+use fyrox_core::{uuid_provider, variable::InheritableVariable};
+use fyrox_graph::SceneGraph;
+use fyrox_resource::untyped::UntypedResource;
+use std::ops::{Deref, DerefMut};
+
+/// Horizontal shear of the glyphs, that is used to imitate italic text when there's no italic font.
+const ITALIC_SHEAR: f32 = -0.2;
+
+/// Content of a single [`RichTextRun`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum RichTextContent {
+    /// A piece of text, it could contain line breaks.
+    Text(String),
+    /// An inline image of the given size.
+    Image {
+        /// Texture of the image.
+        texture: Option<UntypedResource>,
+        /// Size of the image.
+        size: Vector2<f32>,
+    },
+}
+
+impl Default for RichTextContent {
+    fn default() -> Self {
+        Self::Text(Default::default())
+    }
+}
+
+/// A run is a piece of rich text with the same style.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichTextRun {
+    /// Content of the run.
+    pub content: RichTextContent,
+    /// Font of the run. If not set, the font of the rich text is used.
+    pub font: Option<FontResource>,
+    /// Font size of the run. If not set, the font size of the rich text is used.
+    pub font_size: Option<f32>,
+    /// Brush of the run. If not set, the foreground brush of the rich text is used (or the link brush for links).
+    pub brush: Option<Brush>,
+    /// Whether the run is bold or not.
+    pub bold: bool,
+    /// Whether the run is italic or not.
+    pub italic: bool,
+    /// An arbitrary string, that will be sent in [`RichTextMessage::LinkClicked`] message when the run is clicked.
+    pub link: Option<String>,
+}
+
+impl RichTextRun {
+    /// Creates a new text run.
+    pub fn text<S: AsRef<str>>(text: S) -> Self {
+        Self {
+            content: RichTextContent::Text(text.as_ref().to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new inline image run.
+    pub fn image(texture: Option<UntypedResource>, size: Vector2<f32>) -> Self {
+        Self {
+            content: RichTextContent::Image { texture, size },
+            ..Default::default()
+        }
+    }
+
+    /// Sets the desired font of the run.
+    pub fn with_font(mut self, font: FontResource) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Sets the desired font size of the run.
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Sets the desired brush of the run.
+    pub fn with_brush(mut self, brush: Brush) -> Self {
+        self.brush = Some(brush);
+        self
+    }
+
+    /// Makes the run bold.
+    pub fn with_bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    /// Makes the run italic.
+    pub fn with_italic(mut self, italic: bool) -> Self {
+        self.italic = italic;
+        self
+    }
+
+    /// Makes the run a clickable link.
+    pub fn with_link<S: AsRef<str>>(mut self, link: S) -> Self {
+        self.link = Some(link.as_ref().to_owned());
+        self
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    let component = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match hex.len() {
+        6 => Some(Color::opaque(component(0)?, component(2)?, component(4)?)),
+        8 => Some(Color::from_rgba(
+            component(0)?,
+            component(2)?,
+            component(4)?,
+            component(6)?,
+        )),
+        _ => None,
+    }
+}
+
+/// Parses a simple markup into a set of runs. The following tags are supported:
+///
+/// - `[b]bold[/b]`
+/// - `[i]italic[/i]`
+/// - `[color=#RRGGBB]colored[/color]` (or `#RRGGBBAA`)
+/// - `[size=20]big[/size]`
+/// - `[link=target]clickable[/link]`
+///
+/// Tags could be nested. Anything that is not a valid tag is treated as a text.
+pub fn parse_markup(markup: &str) -> Vec<RichTextRun> {
+    #[derive(Default, Clone)]
+    struct State {
+        bold: usize,
+        italic: usize,
+        colors: Vec<Color>,
+        sizes: Vec<f32>,
+        links: Vec<String>,
+    }
+
+    fn make_run(text: &str, state: &State) -> RichTextRun {
+        RichTextRun {
+            content: RichTextContent::Text(text.to_owned()),
+            font: None,
+            font_size: state.sizes.last().cloned(),
+            brush: state.colors.last().map(|c| Brush::Solid(*c)),
+            bold: state.bold > 0,
+            italic: state.italic > 0,
+            link: state.links.last().cloned(),
+        }
+    }
+
+    let mut runs = Vec::new();
+    let mut state = State::default();
+    let mut text = String::new();
+    let mut rest = markup;
+
+    while let Some(start) = rest.find('[') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find(']') else {
+            break;
+        };
+        let tag = &rest[1..end];
+
+        let mut new_state = state.clone();
+        let is_valid = match tag.split_once('=') {
+            Some(("color", value)) => parse_color(value)
+                .map(|color| new_state.colors.push(color))
+                .is_some(),
+            Some(("size", value)) => value
+                .parse::<f32>()
+                .ok()
+                .map(|size| new_state.sizes.push(size))
+                .is_some(),
+            Some(("link", value)) => {
+                new_state.links.push(value.to_owned());
+                true
+            }
+            Some(_) => false,
+            None => match tag {
+                "b" => {
+                    new_state.bold += 1;
+                    true
+                }
+                "i" => {
+                    new_state.italic += 1;
+                    true
+                }
+                "/b" if state.bold > 0 => {
+                    new_state.bold -= 1;
+                    true
+                }
+                "/i" if state.italic > 0 => {
+                    new_state.italic -= 1;
+                    true
+                }
+                "/color" => new_state.colors.pop().is_some(),
+                "/size" => new_state.sizes.pop().is_some(),
+                "/link" => new_state.links.pop().is_some(),
+                _ => false,
+            },
+        };
+
+        if is_valid {
+            if !text.is_empty() {
+                runs.push(make_run(&text, &state));
+                text.clear();
+            }
+            state = new_state;
+        } else {
+            text.push_str(&rest[..=end]);
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        runs.push(make_run(&text, &state));
+    }
+
+    runs
+}
+
+/// A set of messages, that can be used to modify/fetch the state of a [`RichText`] widget at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RichTextMessage {
+    /// Sets new runs of the rich text.
+    Runs(Vec<RichTextRun>),
+    /// Emitted by a rich text, when a link was clicked. Contains the link string of the clicked run.
+    LinkClicked(String),
+}
+
+impl RichTextMessage {
+    define_constructor!(
+        /// Creates [`RichTextMessage::Runs`] message.
+        RichTextMessage:Runs => fn runs(Vec<RichTextRun>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`RichTextMessage::LinkClicked`] message.
+        RichTextMessage:LinkClicked => fn link_clicked(String), layout: false
+    );
+}
+
+/// Rich text is a text, that consists of multiple runs with different fonts, sizes, colors, inline images and clickable
+/// links. It is useful for dialogue systems, tooltips, logs and so on. The text is wrapped by words, line breaks split
+/// the text into paragraphs.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     brush::Brush,
+/// #     core::{color::Color, pool::Handle},
+/// #     rich_text::{RichTextBuilder, RichTextRun},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_rich_text(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     RichTextBuilder::new(WidgetBuilder::new())
+///         .with_runs(vec![
+///             RichTextRun::text("This is "),
+///             RichTextRun::text("bold").with_bold(true),
+///             RichTextRun::text(" and this is ").with_brush(Brush::Solid(Color::RED)),
+///             RichTextRun::text("a link").with_link("my_link"),
+///         ])
+///         .build(ctx)
+/// }
+/// ```
+///
+/// ## Markup
+///
+/// Runs could also be created from a simple markup using [`parse_markup`] or [`RichTextBuilder::with_markup`]:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, rich_text::RichTextBuilder, widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_rich_text(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     RichTextBuilder::new(WidgetBuilder::new())
+///         .with_markup("[b]Guard:[/b] Halt! Go [color=#FF0000]away[/color] or [link=fight]fight[/link].")
+///         .build(ctx)
+/// }
+/// ```
+///
+/// ## Links
+///
+/// When a run with a link is clicked, the rich text emits [`RichTextMessage::LinkClicked`] message with the link string
+/// of the run:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, message::{MessageDirection, UiMessage}, rich_text::RichTextMessage, UiNode,
+/// # };
+/// fn handle_message(rich_text: Handle<UiNode>, message: &UiMessage) {
+///     if let Some(RichTextMessage::LinkClicked(link)) = message.data() {
+///         if message.destination() == rich_text && message.direction() == MessageDirection::FromWidget {
+///             println!("{} was clicked!", link);
+///         }
+///     }
+/// }
+/// ```
+///
+/// ## Bold and italic
+///
+/// Bold and italic runs use the fonts set by [`RichTextBuilder::with_bold_font`] and [`RichTextBuilder::with_italic_font`].
+/// If there are no such fonts, the rich text imitates them.
+#[derive(Default, Clone, Visit, Reflect, Debug, ComponentProvider)]
+pub struct RichText {
+    /// Base widget of the rich text.
+    pub widget: Widget,
+    /// A panel, that holds the paragraphs of the rich text.
+    pub panel: InheritableVariable<Handle<UiNode>>,
+    /// Default font size of the runs.
+    pub font_size: InheritableVariable<f32>,
+    /// A font, that is used for bold runs.
+    pub bold_font: InheritableVariable<Option<FontResource>>,
+    /// A font, that is used for italic runs.
+    pub italic_font: InheritableVariable<Option<FontResource>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    runs: Vec<RichTextRun>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    links: FxHashMap<Handle<UiNode>, String>,
+}
+
+crate::define_widget_deref!(RichText);
+
+uuid_provider!(RichText = "9bd1c2d6-5d8b-4a4c-9d4e-8bf0a1e2b0f3");
+
+impl RichText {
+    /// Returns current runs of the rich text.
+    pub fn runs(&self) -> &[RichTextRun] {
+        &self.runs
+    }
+}
+
+struct ContentBuilder<'a> {
+    font_size: f32,
+    bold_font: Option<&'a FontResource>,
+    italic_font: Option<&'a FontResource>,
+    links: FxHashMap<Handle<UiNode>, String>,
+    paragraphs: Vec<Handle<UiNode>>,
+    words: Vec<Handle<UiNode>>,
+}
+
+impl<'a> ContentBuilder<'a> {
+    fn finish_paragraph(&mut self, ctx: &mut BuildContext) {
+        if self.words.is_empty() {
+            // Keep the height of empty lines.
+            self.words.push(
+                TextBuilder::new(WidgetBuilder::new())
+                    .with_text(" ")
+                    .with_font_size(self.font_size)
+                    .build(ctx),
+            );
+        }
+
+        self.paragraphs.push(
+            WrapPanelBuilder::new(WidgetBuilder::new().with_children(self.words.drain(..)))
+                .with_orientation(Orientation::Horizontal)
+                .build(ctx),
+        );
+    }
+
+    fn add_item(&mut self, item: Handle<UiNode>, link: Option<&String>, ctx: &mut BuildContext) {
+        let item = if let Some(link) = link {
+            // Links are underlined and have transparent background, so they can be clicked anywhere.
+            let brush = ctx
+                .style()
+                .data_ref()
+                .get_brush(Style::BRUSH_BRIGHT_BLUE)
+                .unwrap_or(BRUSH_BRIGHT_BLUE);
+            let container = BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_vertical_alignment(VerticalAlignment::Bottom)
+                    .with_background(Brush::Solid(Color::TRANSPARENT))
+                    .with_foreground(brush)
+                    .with_cursor(Some(CursorIcon::Pointer))
+                    .with_child(item),
+            )
+            .with_stroke_thickness(Thickness {
+                left: 0.0,
+                top: 0.0,
+                right: 0.0,
+                bottom: 1.0,
+            })
+            .build(ctx);
+            self.links.insert(container, link.clone());
+            container
+        } else {
+            item
+        };
+        self.words.push(item);
+    }
+
+    fn add_run(&mut self, run: &RichTextRun, ctx: &mut BuildContext) {
+        match run.content {
+            RichTextContent::Text(ref text) => {
+                let font = run.font.clone().or_else(|| {
+                    if run.bold {
+                        self.bold_font.cloned()
+                    } else if run.italic {
+                        self.italic_font.cloned()
+                    } else {
+                        None
+                    }
+                });
+                let fake_bold = run.bold && run.font.is_none() && self.bold_font.is_none();
+                let fake_italic = run.italic && run.font.is_none() && self.italic_font.is_none();
+                let brush = run.brush.clone().or_else(|| {
+                    run.link.as_ref().map(|_| {
+                        ctx.style()
+                            .data_ref()
+                            .get_brush(Style::BRUSH_BRIGHT_BLUE)
+                            .unwrap_or(BRUSH_BRIGHT_BLUE)
+                    })
+                });
+
+                for (i, line) in text.split('\n').enumerate() {
+                    if i > 0 {
+                        self.finish_paragraph(ctx);
+                    }
+
+                    // Every word is a separate widget, so the wrap panel could wrap the text by words.
+                    for word in line.split_inclusive(' ') {
+                        let mut widget_builder =
+                            WidgetBuilder::new().with_vertical_alignment(VerticalAlignment::Bottom);
+                        if let Some(ref brush) = brush {
+                            widget_builder = widget_builder.with_foreground(brush.clone());
+                        }
+                        if fake_italic {
+                            widget_builder = widget_builder.with_layout_transform(Matrix3::new(
+                                1.0,
+                                ITALIC_SHEAR,
+                                0.0,
+                                0.0,
+                                1.0,
+                                0.0,
+                                0.0,
+                                0.0,
+                                1.0,
+                            ));
+                        }
+
+                        let mut text_builder = TextBuilder::new(widget_builder)
+                            .with_text(word)
+                            .with_opt_font(font.clone())
+                            .with_font_size(run.font_size.unwrap_or(self.font_size));
+                        if fake_bold {
+                            // Thicken the glyphs using the shadow of the same color.
+                            text_builder = text_builder
+                                .with_shadow(true)
+                                .with_shadow_brush(brush.clone().unwrap_or_else(|| {
+                                    ctx.style()
+                                        .data_ref()
+                                        .get_brush(Style::BRUSH_TEXT)
+                                        .unwrap_or(Brush::Solid(Color::WHITE))
+                                }))
+                                .with_shadow_dilation(0.5)
+                                .with_shadow_offset(Vector2::new(0.5, 0.0));
+                        }
+
+                        let word = text_builder.build(ctx);
+                        self.add_item(word, run.link.as_ref(), ctx);
+                    }
+                }
+            }
+            RichTextContent::Image { ref texture, size } => {
+                let image = ImageBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(size.x)
+                        .with_height(size.y)
+                        .with_vertical_alignment(VerticalAlignment::Bottom),
+                )
+                .with_opt_texture(texture.clone())
+                .build(ctx);
+                self.add_item(image, run.link.as_ref(), ctx);
+            }
+        }
+    }
+}
+
+fn build_content(
+    runs: &[RichTextRun],
+    font_size: f32,
+    bold_font: Option<&FontResource>,
+    italic_font: Option<&FontResource>,
+    ctx: &mut BuildContext,
+) -> (Vec<Handle<UiNode>>, FxHashMap<Handle<UiNode>, String>) {
+    let mut builder = ContentBuilder {
+        font_size,
+        bold_font,
+        italic_font,
+        links: Default::default(),
+        paragraphs: Default::default(),
+        words: Default::default(),
+    };
+    for run in runs {
+        builder.add_run(run, ctx);
+    }
+    builder.finish_paragraph(ctx);
+    (builder.paragraphs, builder.links)
+}
+
+impl Control for RichText {
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(RichTextMessage::Runs(runs)) = message.data() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                for &paragraph in ui.node(*self.panel).children() {
+                    ui.send_message(WidgetMessage::remove(paragraph, MessageDirection::ToWidget));
+                }
+
+                let (paragraphs, links) = build_content(
+                    runs,
+                    *self.font_size,
+                    self.bold_font.as_ref(),
+                    self.italic_font.as_ref(),
+                    &mut ui.build_ctx(),
+                );
+                for paragraph in paragraphs {
+                    ui.send_message(WidgetMessage::link(
+                        paragraph,
+                        MessageDirection::ToWidget,
+                        *self.panel,
+                    ));
+                }
+
+                self.runs.clone_from(runs);
+                self.links = links;
+            }
+        } else if let Some(WidgetMessage::MouseDown {
+            button: MouseButton::Left,
+            ..
+        }) = message.data()
+        {
+            if !message.handled() {
+                let destination = message.destination();
+                let link = self
+                    .links
+                    .get(&destination)
+                    .or_else(|| self.links.get(&ui.node(destination).parent()));
+                if let Some(link) = link {
+                    ui.send_message(RichTextMessage::link_clicked(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        link.clone(),
+                    ));
+                    message.set_handled(true);
+                }
+            }
+        }
+    }
+}
+
+/// Rich text builder creates [`RichText`] widget instances and adds them to the user interface.
+pub struct RichTextBuilder {
+    widget_builder: WidgetBuilder,
+    runs: Vec<RichTextRun>,
+    font_size: f32,
+    bold_font: Option<FontResource>,
+    italic_font: Option<FontResource>,
+}
+
+impl RichTextBuilder {
+    /// Creates new rich text builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            runs: Default::default(),
+            font_size: 14.0,
+            bold_font: None,
+            italic_font: None,
+        }
+    }
+
+    /// Sets the desired runs of the rich text.
+    pub fn with_runs(mut self, runs: Vec<RichTextRun>) -> Self {
+        self.runs = runs;
+        self
+    }
+
+    /// Sets the desired runs of the rich text by parsing the given markup. See [`parse_markup`] for more info.
+    pub fn with_markup(mut self, markup: &str) -> Self {
+        self.runs = parse_markup(markup);
+        self
+    }
+
+    /// Sets the default font size of the runs.
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets a font, that will be used for bold runs.
+    pub fn with_bold_font(mut self, font: FontResource) -> Self {
+        self.bold_font = Some(font);
+        self
+    }
+
+    /// Sets a font, that will be used for italic runs.
+    pub fn with_italic_font(mut self, font: FontResource) -> Self {
+        self.italic_font = Some(font);
+        self
+    }
+
+    /// Finishes rich text building and adds it to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let (paragraphs, links) = build_content(
+            &self.runs,
+            self.font_size,
+            self.bold_font.as_ref(),
+            self.italic_font.as_ref(),
+            ctx,
+        );
+
+        let panel =
+            StackPanelBuilder::new(WidgetBuilder::new().with_children(paragraphs)).build(ctx);
+
+        let rich_text = RichText {
+            widget: self.widget_builder.with_child(panel).build(),
+            panel: panel.into(),
+            font_size: self.font_size.into(),
+            bold_font: self.bold_font.into(),
+            italic_font: self.italic_font.into(),
+            runs: self.runs,
+            links,
+        };
+
+        ctx.add_node(UiNode::new(rich_text))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        brush::Brush,
+        core::color::Color,
+        rich_text::{parse_markup, RichTextRun},
+    };
+
+    #[test]
+    fn test_markup_parsing() {
+        assert_eq!(
+            parse_markup("Hello [b]bold [i]both[/i][/b] [color=#FF0000]red[/color]"),
+            vec![
+                RichTextRun::text("Hello "),
+                RichTextRun::text("bold ").with_bold(true),
+                RichTextRun::text("both").with_bold(true).with_italic(true),
+                RichTextRun::text(" "),
+                RichTextRun::text("red").with_brush(Brush::Solid(Color::opaque(255, 0, 0))),
+            ]
+        );
+
+        assert_eq!(
+            parse_markup("[size=20][link=go]Go[/link][/size]"),
+            vec![RichTextRun::text("Go").with_font_size(20.0).with_link("go")]
+        );
+
+        // Invalid tags are just a text.
+        assert_eq!(
+            parse_markup("[x] [/b] [color=red]"),
+            vec![RichTextRun::text("[x] [/b] [color=red]")]
+        );
+    }
+}
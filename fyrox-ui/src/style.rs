@@ -0,0 +1,314 @@
+//! Style is a named set of properties (brushes, numbers, thicknesses, fonts), that is used by the widgets to define their
+//! look. It allows to have dark, light or any custom themes and switch between them at runtime. See [`Style`] docs for
+//! more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{
+        color::Color, reflect::prelude::*, sstorage::ImmutableString, type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    font::FontResource,
+    Thickness, BRUSH_BRIGHT, BRUSH_BRIGHT_BLUE, BRUSH_DARK, BRUSH_DARKER, BRUSH_DARKEST,
+    BRUSH_FOREGROUND, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST, BRUSH_PRIMARY, BRUSH_TEXT,
+};
+use fxhash::FxHashMap;
+use fyrox_resource::{Resource, ResourceData};
+use lazy_static::lazy_static;
+use std::{any::Any, error::Error, path::Path};
+
+/// A value of a single style property.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub enum StyleProperty {
+    /// A brush, that is used for backgrounds, foregrounds, etc.
+    Brush(Brush),
+    /// A number, that could be used for sizes, font sizes, etc.
+    Number(f32),
+    /// A thickness, that is used for margins, paddings, borders.
+    Thickness(Thickness),
+    /// A font.
+    Font(FontResource),
+}
+
+impl Default for StyleProperty {
+    fn default() -> Self {
+        Self::Number(0.0)
+    }
+}
+
+impl From<Brush> for StyleProperty {
+    fn from(value: Brush) -> Self {
+        Self::Brush(value)
+    }
+}
+
+impl From<f32> for StyleProperty {
+    fn from(value: f32) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<Thickness> for StyleProperty {
+    fn from(value: Thickness) -> Self {
+        Self::Thickness(value)
+    }
+}
+
+impl From<FontResource> for StyleProperty {
+    fn from(value: FontResource) -> Self {
+        Self::Font(value)
+    }
+}
+
+/// Style is a named set of properties, that is used by the widgets to define their look. Every widget that supports
+/// styling fetches the values of its properties by their names (see the constants of this type) from the current style
+/// of the user interface, when it is being built. The widgets remember the names of the properties, so when the style
+/// is changed (using [`crate::UserInterface::set_style`]) every widget in the user interface receives
+/// [`crate::widget::WidgetMessage::Style`] message and updates its look. Properties that were set explicitly (for
+/// example, using [`crate::widget::WidgetBuilder::with_background`]) are not affected by the style.
+///
+/// ## Example
+///
+/// The following example creates a custom style, based on the built-in light style, and applies it to the user
+/// interface:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     brush::Brush,
+/// #     core::color::Color,
+/// #     style::{Style, StyleResource},
+/// #     UserInterface,
+/// # };
+/// #
+/// fn apply_custom_style(ui: &mut UserInterface) {
+///     let style = Style::light_style()
+///         .with(Style::BRUSH_BRIGHT, Brush::Solid(Color::opaque(200, 100, 100)));
+///
+///     ui.set_style(StyleResource::new_ok(Default::default(), style));
+/// }
+/// ```
+///
+/// Widgets could use any property of a style, including custom ones, it is just a matter of choosing a unique name
+/// for the property.
+#[derive(Clone, Debug, Default, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "38a63b49-d765-4c01-8fb5-202cc43d607e")]
+pub struct Style {
+    properties: FxHashMap<ImmutableString, StyleProperty>,
+}
+
+/// A shared style, that could be used by multiple user interfaces.
+pub type StyleResource = Resource<Style>;
+
+lazy_static! {
+    /// Default style of every user interface. It is the same as [`Style::dark_style`].
+    pub static ref DEFAULT_STYLE: StyleResource =
+        StyleResource::new_ok(Default::default(), Style::dark_style());
+}
+
+impl Style {
+    /// The darkest brush of the style.
+    pub const BRUSH_DARKEST: &'static str = "Global.Brush.Darkest";
+    /// A brush that is darker than the dark one.
+    pub const BRUSH_DARKER: &'static str = "Global.Brush.Darker";
+    /// Dark brush, it is used as a background of the windows.
+    pub const BRUSH_DARK: &'static str = "Global.Brush.Dark";
+    /// Primary brush, it is used as a background of the widgets by default.
+    pub const BRUSH_PRIMARY: &'static str = "Global.Brush.Primary";
+    /// Light brush, it is used as a background of the buttons and other interactive widgets.
+    pub const BRUSH_LIGHT: &'static str = "Global.Brush.Light";
+    /// A brush that is lighter than the light one, it is used to highlight hovered widgets.
+    pub const BRUSH_LIGHTER: &'static str = "Global.Brush.Lighter";
+    /// The lightest brush of the style, it is used to highlight pressed widgets.
+    pub const BRUSH_LIGHTEST: &'static str = "Global.Brush.Lightest";
+    /// Bright brush, it is used to highlight selected widgets.
+    pub const BRUSH_BRIGHT: &'static str = "Global.Brush.Bright";
+    /// Bright accent brush.
+    pub const BRUSH_BRIGHT_BLUE: &'static str = "Global.Brush.BrightBlue";
+    /// A brush for the text.
+    pub const BRUSH_TEXT: &'static str = "Global.Brush.Text";
+    /// Foreground brush of the widgets.
+    pub const BRUSH_FOREGROUND: &'static str = "Global.Brush.Foreground";
+    /// Brush for the `Normal` state of a [`crate::decorator::Decorator`].
+    pub const DECORATOR_BRUSH_NORMAL: &'static str = "Decorator.Brush.Normal";
+    /// Brush for the `Hovered` state of a [`crate::decorator::Decorator`].
+    pub const DECORATOR_BRUSH_HOVER: &'static str = "Decorator.Brush.Hover";
+    /// Brush for the `Pressed` state of a [`crate::decorator::Decorator`].
+    pub const DECORATOR_BRUSH_PRESSED: &'static str = "Decorator.Brush.Pressed";
+    /// Brush for the `Selected` state of a [`crate::decorator::Decorator`].
+    pub const DECORATOR_BRUSH_SELECTED: &'static str = "Decorator.Brush.Selected";
+    /// Border brush of a [`crate::decorator::Decorator`].
+    pub const DECORATOR_BRUSH_BORDER: &'static str = "Decorator.Brush.Border";
+
+    /// Creates the dark style, which is the default style of the user interface.
+    pub fn dark_style() -> Self {
+        let mut style = Self::default();
+        style
+            .set(Self::BRUSH_DARKEST, BRUSH_DARKEST)
+            .set(Self::BRUSH_DARKER, BRUSH_DARKER)
+            .set(Self::BRUSH_DARK, BRUSH_DARK)
+            .set(Self::BRUSH_PRIMARY, BRUSH_PRIMARY)
+            .set(Self::BRUSH_LIGHT, BRUSH_LIGHT)
+            .set(Self::BRUSH_LIGHTER, BRUSH_LIGHTER)
+            .set(Self::BRUSH_LIGHTEST, BRUSH_LIGHTEST)
+            .set(Self::BRUSH_BRIGHT, BRUSH_BRIGHT)
+            .set(Self::BRUSH_BRIGHT_BLUE, BRUSH_BRIGHT_BLUE)
+            .set(Self::BRUSH_TEXT, BRUSH_TEXT)
+            .set(Self::BRUSH_FOREGROUND, BRUSH_FOREGROUND)
+            .set(Self::DECORATOR_BRUSH_NORMAL, BRUSH_LIGHT)
+            .set(Self::DECORATOR_BRUSH_HOVER, BRUSH_LIGHTER)
+            .set(Self::DECORATOR_BRUSH_PRESSED, BRUSH_LIGHTEST)
+            .set(Self::DECORATOR_BRUSH_SELECTED, BRUSH_BRIGHT)
+            .set(Self::DECORATOR_BRUSH_BORDER, BRUSH_DARKER);
+        style
+    }
+
+    /// Creates the light style.
+    pub fn light_style() -> Self {
+        let brush = |r, g, b| Brush::Solid(Color::opaque(r, g, b));
+        Self::dark_style()
+            .with(Self::BRUSH_DARKEST, brush(140, 140, 140))
+            .with(Self::BRUSH_DARKER, brush(160, 160, 160))
+            .with(Self::BRUSH_DARK, brush(190, 190, 190))
+            .with(Self::BRUSH_PRIMARY, brush(210, 210, 210))
+            .with(Self::BRUSH_LIGHT, brush(225, 225, 225))
+            .with(Self::BRUSH_LIGHTER, brush(235, 235, 235))
+            .with(Self::BRUSH_LIGHTEST, brush(245, 245, 245))
+            .with(Self::BRUSH_BRIGHT, brush(130, 130, 130))
+            .with(Self::BRUSH_BRIGHT_BLUE, brush(80, 118, 178))
+            .with(Self::BRUSH_TEXT, brush(20, 20, 20))
+            .with(Self::BRUSH_FOREGROUND, brush(0, 0, 0))
+            .with(Self::DECORATOR_BRUSH_NORMAL, brush(225, 225, 225))
+            .with(Self::DECORATOR_BRUSH_HOVER, brush(235, 235, 235))
+            .with(Self::DECORATOR_BRUSH_PRESSED, brush(245, 245, 245))
+            .with(Self::DECORATOR_BRUSH_SELECTED, brush(150, 180, 225))
+            .with(Self::DECORATOR_BRUSH_BORDER, brush(160, 160, 160))
+    }
+
+    /// Sets a new value of a property with the given name. The property will be added, if it does not exist.
+    pub fn set(&mut self, name: &str, value: impl Into<StyleProperty>) -> &mut Self {
+        self.properties
+            .insert(ImmutableString::new(name), value.into());
+        self
+    }
+
+    /// The same as [`Self::set`], but consumes and returns the style, which is useful for chained calls.
+    pub fn with(mut self, name: &str, value: impl Into<StyleProperty>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Returns a value of a property with the given name.
+    pub fn get(&self, name: &str) -> Option<&StyleProperty> {
+        self.properties.get(&ImmutableString::new(name))
+    }
+
+    /// Returns a brush with the given name. [`None`] is returned if there's no such property or it is not a brush.
+    pub fn get_brush(&self, name: &str) -> Option<Brush> {
+        match self.get(name)? {
+            StyleProperty::Brush(brush) => Some(brush.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns a number with the given name. [`None`] is returned if there's no such property or it is not a number.
+    pub fn get_number(&self, name: &str) -> Option<f32> {
+        match self.get(name)? {
+            StyleProperty::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Returns a thickness with the given name. [`None`] is returned if there's no such property or it is not a
+    /// thickness.
+    pub fn get_thickness(&self, name: &str) -> Option<Thickness> {
+        match self.get(name)? {
+            StyleProperty::Thickness(thickness) => Some(*thickness),
+            _ => None,
+        }
+    }
+
+    /// Returns a font with the given name. [`None`] is returned if there's no such property or it is not a font.
+    pub fn get_font(&self, name: &str) -> Option<FontResource> {
+        match self.get(name)? {
+            StyleProperty::Font(font) => Some(font.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over all properties of the style.
+    pub fn properties(&self) -> impl Iterator<Item = (&ImmutableString, &StyleProperty)> {
+        self.properties.iter()
+    }
+}
+
+impl ResourceData for Style {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        border::BorderBuilder,
+        decorator::{Decorator, DecoratorBuilder},
+        style::{Style, StyleResource},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_core::algebra::Vector2;
+    use fyrox_graph::SceneGraph;
+
+    #[test]
+    fn test_style_switching() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let styled =
+            BorderBuilder::new(WidgetBuilder::new().with_background_style(Style::BRUSH_DARK))
+                .build(&mut ui.build_ctx());
+        let decorator = DecoratorBuilder::new(BorderBuilder::new(WidgetBuilder::new()))
+            .build(&mut ui.build_ctx());
+
+        let dark = Style::dark_style();
+        assert_eq!(
+            ui.node(styled).background(),
+            dark.get_brush(Style::BRUSH_DARK).unwrap()
+        );
+
+        let light = Style::light_style();
+        ui.set_style(StyleResource::new_ok(Default::default(), light.clone()));
+        while ui.poll_message().is_some() {}
+
+        assert_eq!(
+            ui.node(styled).background(),
+            light.get_brush(Style::BRUSH_DARK).unwrap()
+        );
+        let decorator_ref = ui.node(decorator).cast::<Decorator>().unwrap();
+        assert_eq!(
+            *decorator_ref.normal_brush,
+            light.get_brush(Style::DECORATOR_BRUSH_NORMAL).unwrap()
+        );
+        assert_eq!(
+            decorator_ref.background(),
+            light.get_brush(Style::DECORATOR_BRUSH_NORMAL).unwrap()
+        );
+    }
+}
@@ -6,14 +6,15 @@
 use crate::{
     brush::Brush,
     core::{
-        algebra::Vector2, color::Color, pool::Handle, reflect::prelude::*, type_traits::prelude::*,
-        visitor::prelude::*,
+        algebra::Vector2, color::Color, pool::Handle, reflect::prelude::*,
+        sstorage::ImmutableString, type_traits::prelude::*, visitor::prelude::*,
     },
     define_constructor,
     draw::DrawingContext,
     font::FontResource,
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
     message::{MessageDirection, UiMessage},
+    style::Style,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, HorizontalAlignment, UiNode, UserInterface, VerticalAlignment,
 };
@@ -551,8 +552,10 @@ impl TextBuilder {
             ui.default_font()
         };
 
-        if self.widget_builder.foreground.is_none() {
-            self.widget_builder.foreground = Some(Brush::Solid(Color::opaque(220, 220, 220)));
+        if self.widget_builder.foreground.is_none()
+            && self.widget_builder.foreground_style.is_none()
+        {
+            self.widget_builder.foreground_style = Some(ImmutableString::new(Style::BRUSH_TEXT));
         }
 
         let text = Text {
@@ -12,6 +12,7 @@ use crate::{
         parking_lot::Mutex,
         pool::Handle,
         reflect::prelude::*,
+        sstorage::ImmutableString,
         type_traits::prelude::*,
         uuid_provider,
         visitor::prelude::*,
@@ -21,10 +22,10 @@ use crate::{
     font::FontResource,
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
     message::{CursorIcon, KeyCode, MessageDirection, MouseButton, UiMessage},
+    style::Style,
     text::TextMessage,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, UiNode, UserInterface, VerticalAlignment,
-    BRUSH_DARKER, BRUSH_TEXT,
 };
 use copypasta::ClipboardProvider;
 use fyrox_core::variable::InheritableVariable;
@@ -1745,11 +1746,15 @@ impl TextBoxBuilder {
 
     /// Creates a new [`TextBox`] instance and adds it to the user interface.
     pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
-        if self.widget_builder.foreground.is_none() {
-            self.widget_builder.foreground = Some(BRUSH_TEXT);
+        if self.widget_builder.foreground.is_none()
+            && self.widget_builder.foreground_style.is_none()
+        {
+            self.widget_builder.foreground_style = Some(ImmutableString::new(Style::BRUSH_TEXT));
         }
-        if self.widget_builder.background.is_none() {
-            self.widget_builder.background = Some(BRUSH_DARKER);
+        if self.widget_builder.background.is_none()
+            && self.widget_builder.background_style.is_none()
+        {
+            self.widget_builder.background_style = Some(ImmutableString::new(Style::BRUSH_DARKER));
         }
         if self.widget_builder.cursor.is_none() {
             self.widget_builder.cursor = Some(CursorIcon::Text);
@@ -10,11 +10,13 @@ use crate::{
         math::Rect,
         pool::Handle,
         reflect::prelude::*,
+        sstorage::ImmutableString,
         uuid::Uuid,
         visitor::prelude::*,
     },
     define_constructor,
     message::{CursorIcon, Force, KeyCode, MessageDirection, UiMessage},
+    style::{Style, StyleResource},
     HorizontalAlignment, LayoutEvent, MouseButton, MouseState, RcUiNodeHandle, Thickness, UiNode,
     UserInterface, VerticalAlignment, BRUSH_FOREGROUND, BRUSH_PRIMARY,
 };
@@ -373,6 +375,13 @@ pub enum WidgetMessage {
         /// unique identifier for touch event
         id: u64,
     },
+
+    /// A request to apply a new style to the widget. It is sent to every widget of a user interface, when the style of
+    /// the user interface has changed (see [`UserInterface::set_style`]). Widgets should take the values of their styled
+    /// properties from the new style.
+    ///
+    /// Direction: **To UI**.
+    Style(StyleResource),
 }
 
 impl WidgetMessage {
@@ -659,6 +668,11 @@ impl WidgetMessage {
         /// be used anywhere else.
         WidgetMessage:DoubleTap => fn double_tap(pos: Vector2<f32>, force: Option<Force>, id: u64), layout: false
     );
+
+    define_constructor!(
+        /// Creates [`WidgetMessage::Style`] message.
+        WidgetMessage:Style => fn style(StyleResource), layout: false
+    );
 }
 
 /// Widget is a base UI element, that is always used to build derived, more complex, widgets. In general, it is a container
@@ -693,6 +707,12 @@ pub struct Widget {
     pub background: InheritableVariable<Brush>,
     /// Foreground brush of the widget.
     pub foreground: InheritableVariable<Brush>,
+    /// Name of a style property, that is used as the background brush of the widget. See [`Style`] docs for more info.
+    #[visit(optional)]
+    pub background_style: Option<ImmutableString>,
+    /// Name of a style property, that is used as the foreground brush of the widget. See [`Style`] docs for more info.
+    #[visit(optional)]
+    pub foreground_style: Option<ImmutableString>,
     /// Index of the row to which this widget belongs to. It is valid only in when used in [`crate::grid::Grid`] widget.
     #[reflect(setter = "set_row_notify")]
     pub row: InheritableVariable<usize>,
@@ -1038,6 +1058,8 @@ impl Widget {
     #[inline]
     pub fn set_background(&mut self, brush: Brush) -> &mut Self {
         self.background.set_value_and_mark_modified(brush);
+        // Explicitly set brush must not be overwritten by a style.
+        self.background_style = None;
         self
     }
 
@@ -1051,6 +1073,7 @@ impl Widget {
     #[inline]
     pub fn set_foreground(&mut self, brush: Brush) -> &mut Self {
         self.foreground.set_value_and_mark_modified(brush);
+        self.foreground_style = None;
         self
     }
 
@@ -1060,6 +1083,25 @@ impl Widget {
         (*self.foreground).clone()
     }
 
+    /// Takes the values of styled properties of the widget from the given style. Properties without a style binding or
+    /// the properties, that are missing in the style, remain unchanged.
+    pub fn apply_style(&mut self, style: &Style) {
+        if let Some(brush) = self
+            .background_style
+            .as_ref()
+            .and_then(|name| style.get_brush(name))
+        {
+            self.background.set_value_and_mark_modified(brush);
+        }
+        if let Some(brush) = self
+            .foreground_style
+            .as_ref()
+            .and_then(|name| style.get_brush(name))
+        {
+            self.foreground.set_value_and_mark_modified(brush);
+        }
+    }
+
     /// Sets new width of the widget.
     #[inline]
     pub fn set_width(&mut self, width: f32) -> &mut Self {
@@ -1297,10 +1339,16 @@ impl Widget {
                     WidgetMessage::Background(background) => {
                         self.background
                             .set_value_and_mark_modified(background.clone());
+                        // Explicitly set brush must not be overwritten by a style.
+                        self.background_style = None;
                     }
                     WidgetMessage::Foreground(foreground) => {
                         self.foreground
                             .set_value_and_mark_modified(foreground.clone());
+                        self.foreground_style = None;
+                    }
+                    WidgetMessage::Style(style) => {
+                        self.apply_style(&style.data_ref());
                     }
                     WidgetMessage::Name(name) => self.name = name.clone(),
                     &WidgetMessage::Width(width) => {
@@ -1705,6 +1753,10 @@ pub struct WidgetBuilder {
     pub background: Option<Brush>,
     /// Foreground brush of the widget.
     pub foreground: Option<Brush>,
+    /// Name of a style property, that will be used as the background brush of the widget.
+    pub background_style: Option<ImmutableString>,
+    /// Name of a style property, that will be used as the foreground brush of the widget.
+    pub foreground_style: Option<ImmutableString>,
     /// Row index of the widget.
     pub row: usize,
     /// Column index of the widget.
@@ -1772,6 +1824,8 @@ impl WidgetBuilder {
             min_size: None,
             background: None,
             foreground: None,
+            background_style: None,
+            foreground_style: None,
             row: 0,
             column: 0,
             margin: Thickness::zero(),
@@ -1873,6 +1927,22 @@ impl WidgetBuilder {
         self
     }
 
+    /// Sets a name of a style property, that will be used as the background brush of the widget. The brush will be
+    /// taken from the style of a user interface when the widget is added to it and every time when the style changes.
+    /// See [`Style`] docs for more info.
+    pub fn with_background_style(mut self, name: &str) -> Self {
+        self.background_style = Some(ImmutableString::new(name));
+        self
+    }
+
+    /// Sets a name of a style property, that will be used as the foreground brush of the widget. The brush will be
+    /// taken from the style of a user interface when the widget is added to it and every time when the style changes.
+    /// See [`Style`] docs for more info.
+    pub fn with_foreground_style(mut self, name: &str) -> Self {
+        self.foreground_style = Some(ImmutableString::new(name));
+        self
+    }
+
     /// Sets the desired row index of the widget.
     pub fn on_row(mut self, row: usize) -> Self {
         self.row = row;
@@ -2029,6 +2099,18 @@ impl WidgetBuilder {
 
     /// Finishes building of the base widget.
     pub fn build(self) -> Widget {
+        // Brushes, that weren't set explicitly, are taken from the style.
+        let background_style = self.background_style.or_else(|| {
+            self.background
+                .is_none()
+                .then(|| ImmutableString::new(Style::BRUSH_PRIMARY))
+        });
+        let foreground_style = self.foreground_style.or_else(|| {
+            self.foreground
+                .is_none()
+                .then(|| ImmutableString::new(Style::BRUSH_FOREGROUND))
+        });
+
         Widget {
             handle: Default::default(),
             name: self.name,
@@ -2051,6 +2133,8 @@ impl WidgetBuilder {
                 .foreground
                 .unwrap_or_else(|| BRUSH_FOREGROUND.clone())
                 .into(),
+            background_style,
+            foreground_style,
             row: self.row.into(),
             column: self.column.into(),
             vertical_alignment: self.vertical_alignment.into(),
@@ -1,10 +1,13 @@
 //! Executor is a small wrapper that manages plugins and scripts for your game.
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::plugin::dynamic::DynamicPluginError;
 use crate::{
     asset::manager::ResourceManager,
     core::{
         instant::Instant,
         log::{Log, MessageKind},
+        visitor::Visitor,
     },
     engine::{
         Engine, EngineInitParams, GraphicsContext, GraphicsContextParams, SerializationContext,
@@ -16,24 +19,184 @@ use crate::{
     window::WindowAttributes,
 };
 use clap::Parser;
-use fyrox_core::task::TaskPool;
+use fyrox_core::{reflect::prelude::*, task::TaskPool};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
 use std::{
+    fs::File,
     ops::{Deref, DerefMut},
-    sync::Arc,
+    path::{Path, PathBuf},
+    sync::{mpsc::Receiver, Arc},
 };
 
+/// Fixed time step settings of a project. The executor updates the engine (scripts, plugins,
+/// physics, etc.) with a fixed time step, that is defined by the update rate. Rendering is not
+/// bound to the update rate, so when the frame rate is higher than the update rate, rendered
+/// transforms of physical objects could be interpolated between physics steps to eliminate jitter.
+///
+/// The settings could be stored per project in [`Executor::TIME_STEP_SETTINGS_FILE`] file in the
+/// working directory, the executor loads the file automatically if it exists. For example:
+///
+/// ```text
+/// (
+///     update_rate: 120.0,
+///     interpolation: true,
+/// )
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct TimeStepSettings {
+    /// Desired amount of updates per second. Default is [`Executor::DEFAULT_UPDATE_RATE`].
+    #[serde(default = "default_update_rate")]
+    #[reflect(min_value = 1.0)]
+    pub update_rate: f32,
+    /// Whether rendered transforms of dynamic rigid bodies (and their descendants) should be
+    /// interpolated between physics steps or not. Default is `true`. See
+    /// [`crate::scene::graph::Graph::interpolate_physics_transforms`] for more info.
+    #[serde(default = "default_interpolation")]
+    pub interpolation: bool,
+}
+
+fn default_update_rate() -> f32 {
+    Executor::DEFAULT_UPDATE_RATE
+}
+
+fn default_interpolation() -> bool {
+    true
+}
+
+impl Default for TimeStepSettings {
+    fn default() -> Self {
+        Self {
+            update_rate: default_update_rate(),
+            interpolation: default_interpolation(),
+        }
+    }
+}
+
+impl TimeStepSettings {
+    /// Tries to load the settings from the given file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let file = File::open(path.as_ref()).map_err(|e| e.to_string())?;
+        ron::de::from_reader(file).map_err(|e| e.to_string())
+    }
+
+    /// Tries to save the settings to the given file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let file = File::create(path.as_ref()).map_err(|e| e.to_string())?;
+        ron::ser::to_writer_pretty(file, self, PrettyConfig::default()).map_err(|e| e.to_string())
+    }
+
+    /// Returns the length of a single update step in seconds.
+    pub fn time_step(&self) -> f32 {
+        1.0 / self.update_rate.max(f32::EPSILON)
+    }
+}
+
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
     #[clap(short, long, default_value = "")]
     override_scene: String,
+    /// Writes every dispatched script message to the log.
+    #[clap(long)]
+    log_script_messages: bool,
+    /// Reads [`PlayModeCommand`]s from the standard input.
+    #[clap(long)]
+    editor_controlled: bool,
+}
+
+/// A command that controls the game started by the editor in play mode. The editor writes the commands
+/// to the standard input of the game, one command per line, the executor reads them only when it is
+/// started with `--editor-controlled` argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayModeCommand {
+    /// Stops updating the game, rendering continues.
+    Pause,
+    /// Continues updating the game.
+    Resume,
+    /// Performs exactly one update step of the paused game.
+    Step,
+    /// Saves the first scene of the game to the given path and closes the game.
+    SaveAndExit(PathBuf),
+}
+
+impl PlayModeCommand {
+    /// Creates a textual representation of the command, that could be parsed back by [`Self::from_line`].
+    pub fn to_line(&self) -> String {
+        match self {
+            PlayModeCommand::Pause => "pause".to_string(),
+            PlayModeCommand::Resume => "resume".to_string(),
+            PlayModeCommand::Step => "step".to_string(),
+            PlayModeCommand::SaveAndExit(path) => format!("save_and_exit {}", path.display()),
+        }
+    }
+
+    /// Tries to parse a command from its textual representation.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let (name, argument) = line.split_once(' ').unwrap_or((line, ""));
+        match name {
+            "pause" => Some(PlayModeCommand::Pause),
+            "resume" => Some(PlayModeCommand::Resume),
+            "step" => Some(PlayModeCommand::Step),
+            "save_and_exit" if !argument.is_empty() => {
+                Some(PlayModeCommand::SaveAndExit(PathBuf::from(argument)))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn spawn_play_mode_command_reader() -> Option<Receiver<PlayModeCommand>> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for line in std::io::stdin().lines().map_while(Result::ok) {
+                match PlayModeCommand::from_line(&line) {
+                    Some(command) => {
+                        if sender.send(command).is_err() {
+                            break;
+                        }
+                    }
+                    None => Log::warn(format!("Unknown play mode command: {line}")),
+                }
+            }
+        });
+        Some(receiver)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
+fn save_first_scene(engine: &mut Engine, path: &Path) {
+    let Some(scene) = engine.scenes.iter_mut().next() else {
+        Log::err("There is no scene to save!");
+        return;
+    };
+
+    let mut visitor = Visitor::new();
+    let result = scene
+        .save("Scene", &mut visitor)
+        .map_err(|e| e.to_string())
+        .and_then(|_| visitor.save_binary(path).map_err(|e| e.to_string()));
+    match result {
+        Ok(_) => Log::info(format!("Game state was saved to {}", path.display())),
+        Err(e) => Log::err(format!(
+            "Failed to save game state to {}. Reason: {e}",
+            path.display()
+        )),
+    }
 }
 
 /// Executor is a small wrapper that manages plugins and scripts for your game.
 pub struct Executor {
     event_loop: EventLoop<()>,
     engine: Engine,
-    desired_update_rate: f32,
+    time_step_settings: TimeStepSettings,
     headless: bool,
 }
 
@@ -61,6 +224,14 @@ impl Executor {
     /// Default update rate in frames per second.
     pub const DEFAULT_UPDATE_RATE: f32 = 60.0;
 
+    /// Name of the pack file, that will be mounted automatically if it exists in the working
+    /// directory. See [`crate::asset::vfs`] docs for more info.
+    pub const DEFAULT_PACK_FILE: &'static str = "data.pak";
+
+    /// Name of the file with time step settings of a project, that will be loaded automatically if
+    /// it exists in the working directory. See [`TimeStepSettings`] docs for more info.
+    pub const TIME_STEP_SETTINGS_FILE: &'static str = "time_step.ron";
+
     /// Creates new game executor using specified set of parameters. Much more flexible version of
     /// [`Executor::new`].
     pub fn from_params(
@@ -69,9 +240,12 @@ impl Executor {
     ) -> Self {
         let serialization_context = Arc::new(SerializationContext::new());
         let task_pool = Arc::new(TaskPool::new());
+        let resource_manager = ResourceManager::new(task_pool.clone());
+        #[cfg(not(target_arch = "wasm32"))]
+        Self::mount_default_pack(&resource_manager);
         let engine = Engine::new(EngineInitParams {
             graphics_context_params,
-            resource_manager: ResourceManager::new(task_pool.clone()),
+            resource_manager,
             serialization_context,
             task_pool,
         })
@@ -80,11 +254,53 @@ impl Executor {
         Self {
             event_loop,
             engine,
-            desired_update_rate: Self::DEFAULT_UPDATE_RATE,
+            time_step_settings: Self::load_time_step_settings(),
             headless: false,
         }
     }
 
+    fn load_time_step_settings() -> TimeStepSettings {
+        if !Path::new(Self::TIME_STEP_SETTINGS_FILE).exists() {
+            return Default::default();
+        }
+
+        match TimeStepSettings::load(Self::TIME_STEP_SETTINGS_FILE) {
+            Ok(settings) => settings,
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to load {} time step settings. Reason: {}",
+                    Self::TIME_STEP_SETTINGS_FILE,
+                    err
+                ));
+                Default::default()
+            }
+        }
+    }
+
+    /// Shipped games store their resources in a pack file. Loose files in the working directory
+    /// are still accessible and have priority over the packed ones.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn mount_default_pack(resource_manager: &ResourceManager) {
+        use crate::asset::vfs::{PackFile, VirtualFileSystem};
+
+        if !Path::new(Self::DEFAULT_PACK_FILE).exists() {
+            return;
+        }
+
+        match PackFile::open(Self::DEFAULT_PACK_FILE) {
+            Ok(pack) => {
+                let mut vfs = VirtualFileSystem::new();
+                vfs.mount_pack("", pack).mount_directory("", ".");
+                resource_manager.state().set_resource_io(Arc::new(vfs));
+            }
+            Err(err) => Log::err(format!(
+                "Unable to open {} pack file. Reason: {:?}",
+                Self::DEFAULT_PACK_FILE,
+                err
+            )),
+        }
+    }
+
     /// Creates new game executor using default window and with vsync turned on. For more flexible
     /// way to create an executor see [`Executor::from_params`].
     pub fn new() -> Self {
@@ -115,12 +331,22 @@ impl Executor {
 
     /// Sets the desired update rate in frames per second.
     pub fn set_desired_update_rate(&mut self, update_rate: f32) {
-        self.desired_update_rate = update_rate.abs();
+        self.time_step_settings.update_rate = update_rate.abs();
     }
 
     /// Returns desired update rate in frames per second.
     pub fn desired_update_rate(&self) -> f32 {
-        self.desired_update_rate
+        self.time_step_settings.update_rate
+    }
+
+    /// Sets new time step settings, overriding the ones loaded from [`Self::TIME_STEP_SETTINGS_FILE`].
+    pub fn set_time_step_settings(&mut self, settings: TimeStepSettings) {
+        self.time_step_settings = settings;
+    }
+
+    /// Returns current time step settings.
+    pub fn time_step_settings(&self) -> &TimeStepSettings {
+        &self.time_step_settings
     }
 
     /// Adds new plugin constructor to the executor, the plugin will be enabled only on [`Executor::run`].
@@ -131,6 +357,20 @@ impl Executor {
         self.engine.add_plugin_constructor(plugin)
     }
 
+    /// Adds new plugin from a dynamic library to the executor, the plugin will be enabled only on
+    /// [`Executor::run`]. See [`crate::plugin::dynamic::DynamicPlugin`] docs for more info.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_dynamic_plugin<P>(
+        &mut self,
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<(), DynamicPluginError>
+    where
+        P: AsRef<Path>,
+    {
+        self.engine.add_dynamic_plugin(path, reload_when_changed)
+    }
+
     /// Runs the executor - starts your game.
     pub fn run(self) {
         let mut engine = self.engine;
@@ -139,6 +379,18 @@ impl Executor {
 
         let args = Args::parse();
 
+        if args.log_script_messages {
+            engine.script_processor.log_message_traffic = true;
+        }
+
+        let play_mode_commands = if args.editor_controlled {
+            spawn_play_mode_command_reader()
+        } else {
+            None
+        };
+        let mut paused = false;
+        let mut pending_steps = 0;
+
         engine.enable_plugins(
             if args.override_scene.is_empty() {
                 None
@@ -150,7 +402,8 @@ impl Executor {
         );
 
         let mut previous = Instant::now();
-        let fixed_time_step = 1.0 / self.desired_update_rate;
+        let fixed_time_step = self.time_step_settings.time_step();
+        let interpolation = self.time_step_settings.interpolation;
         let mut lag = 0.0;
 
         run_executor(event_loop, move |event, window_target| {
@@ -196,15 +449,53 @@ impl Executor {
                     );
                 }
                 Event::AboutToWait => {
+                    if let Some(play_mode_commands) = play_mode_commands.as_ref() {
+                        while let Ok(command) = play_mode_commands.try_recv() {
+                            match command {
+                                PlayModeCommand::Pause => paused = true,
+                                PlayModeCommand::Resume => paused = false,
+                                PlayModeCommand::Step => pending_steps += 1,
+                                PlayModeCommand::SaveAndExit(path) => {
+                                    save_first_scene(&mut engine, &path);
+                                    window_target.exit();
+                                }
+                            }
+                        }
+                    }
+
                     let elapsed = previous.elapsed();
                     previous = Instant::now();
-                    lag += elapsed.as_secs_f32();
+
+                    if paused {
+                        // Paused game must not accumulate time, otherwise it will try to catch up
+                        // after resuming.
+                        lag = 0.0;
+                        while pending_steps > 0 {
+                            engine.update(
+                                fixed_time_step,
+                                window_target,
+                                &mut lag,
+                                Default::default(),
+                            );
+                            pending_steps -= 1;
+                        }
+                    } else {
+                        pending_steps = 0;
+                        lag += elapsed.as_secs_f32();
+                    }
 
                     while lag >= fixed_time_step {
                         engine.update(fixed_time_step, window_target, &mut lag, Default::default());
                         lag -= fixed_time_step;
                     }
 
+                    if interpolation {
+                        let alpha = lag / fixed_time_step;
+                        for scene in engine.scenes.iter_mut().filter(|s| *s.enabled) {
+                            scene.graph.interpolate_physics_transforms(alpha);
+                        }
+                    }
+
                     if let GraphicsContext::Initialized(ref ctx) = engine.graphics_context {
                         ctx.window.request_redraw();
                     }
@@ -257,3 +548,27 @@ where
         event_loop.run(callback).unwrap();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::PlayModeCommand;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_play_mode_command_line() {
+        for command in [
+            PlayModeCommand::Pause,
+            PlayModeCommand::Resume,
+            PlayModeCommand::Step,
+            PlayModeCommand::SaveAndExit(PathBuf::from("data/play mode state.rgs")),
+        ] {
+            assert_eq!(
+                PlayModeCommand::from_line(&command.to_line()),
+                Some(command)
+            );
+        }
+
+        assert_eq!(PlayModeCommand::from_line("save_and_exit"), None);
+        assert_eq!(PlayModeCommand::from_line("jump"), None);
+    }
+}
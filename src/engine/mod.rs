@@ -25,29 +25,37 @@ use crate::{
         shader::{loader::ShaderLoader, Shader, ShaderResource, ShaderResourceExtension},
         Material,
     },
-    plugin::{Plugin, PluginConstructor, PluginContext, PluginRegistrationContext},
+    plugin::{
+        Plugin, PluginConstructor, PluginConstructorContainer, PluginContext,
+        PluginRegistrationContext,
+    },
     renderer::{framework::error::FrameworkError, framework::state::GlKind, Renderer},
     resource::{
+        audio_event::{loader::AudioEventBankLoader, AudioEventBank},
+        convex_decomposition::{loader::ConvexDecompositionLoader, ConvexDecomposition},
         curve::{loader::CurveLoader, CurveResourceState},
         model::{loader::ModelLoader, Model, ModelResource},
         texture::{loader::TextureLoader, Texture, TextureKind},
+        visual_script::{loader::VisualScriptLoader, VisualScript},
     },
     scene::{
+        animation::{AnimationEventMessage, AnimationPlayer},
         base::NodeScriptMessage,
         camera::SkyBoxKind,
-        graph::{GraphUpdateSwitches, NodePool},
+        graph::{Graph, GraphUpdateSwitches, NodePool},
         node::{constructor::NodeConstructorContainer, Node},
         sound::SoundEngine,
+        streaming::SceneStreaming,
         Scene, SceneContainer, SceneLoader,
     },
     script::{
-        constructor::ScriptConstructorContainer, RoutingStrategy, Script, ScriptContext,
-        ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
-        ScriptMessageSender,
+        constructor::ScriptConstructorContainer, visual::VisualScriptRunner, RoutingStrategy,
+        Script, ScriptContext, ScriptDeinitContext, ScriptMessage, ScriptMessageContext,
+        ScriptMessageKind, ScriptMessageSender, ScriptMessageTrafficEntry, SubscriptionFilter,
     },
     window::{Window, WindowBuilder},
 };
-use fxhash::{FxHashMap, FxHashSet};
+use fxhash::FxHashMap;
 use fyrox_resource::untyped::{ResourceKind, UntypedResource};
 use fyrox_sound::{
     buffer::{loader::SoundBufferLoader, SoundBuffer},
@@ -68,6 +76,17 @@ use glutin_winit::{DisplayBuilder, GlWindow};
 #[cfg(not(target_arch = "wasm32"))]
 use raw_window_handle::HasRawWindowHandle;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    core::visitor::Visitor,
+    plugin::dynamic::{self, DynamicPlugin, DynamicPluginError},
+};
+#[cfg(feature = "wasm_scripting")]
+use crate::{
+    resource::wasm_module::{loader::WasmModuleLoader, WasmModule},
+    script::wasm::WasmScript,
+};
+
 #[cfg(not(target_arch = "wasm32"))]
 use std::{ffi::CString, num::NonZeroU32};
 
@@ -119,9 +138,14 @@ impl Default for SerializationContext {
 impl SerializationContext {
     /// Creates default serialization context.
     pub fn new() -> Self {
+        let script_constructors = ScriptConstructorContainer::new();
+        script_constructors.add::<VisualScriptRunner>("Visual Script Runner");
+        #[cfg(feature = "wasm_scripting")]
+        script_constructors.add::<WasmScript>("WASM Script");
+
         Self {
             node_constructors: NodeConstructorContainer::new(),
-            script_constructors: ScriptConstructorContainer::new(),
+            script_constructors,
         }
     }
 }
@@ -438,7 +462,7 @@ pub struct Engine {
     sound_engine: SoundEngine,
 
     // A set of plugin constructors.
-    plugin_constructors: Vec<Box<dyn PluginConstructor>>,
+    plugin_constructors: Vec<PluginConstructorContainer>,
 
     // A set of plugins used by the engine.
     plugins: Vec<Box<dyn Plugin>>,
@@ -456,9 +480,23 @@ pub struct Engine {
     pub script_processor: ScriptProcessor,
 }
 
-/// Performs dispatch of script messages.
+struct Subscription {
+    receiver: Handle<Node>,
+    filter: SubscriptionFilter,
+}
+
+/// Performs dispatch of script messages. Messages are never delivered immediately, instead they're
+/// queued and dispatched after each update pass of scripts with the following guarantees:
+///
+/// - Messages are delivered in the order they were sent.
+/// - Messages sent from [`crate::script::ScriptTrait::on_message`] are added to the end of the queue
+/// and delivered in the same dispatch pass, after every message that was sent before them.
+/// - Every global message is delivered to its receivers in the order of their subscription, targeted and
+/// hierarchical messages are delivered in the order defined by their [routing strategy](RoutingStrategy).
+/// - Receivers of a message are collected at the moment of its dispatch, any changes of the scene graph
+/// made by the receivers does not affect the delivery of the current message.
 pub struct ScriptMessageDispatcher {
-    type_groups: FxHashMap<TypeId, FxHashSet<Handle<Node>>>,
+    type_groups: FxHashMap<TypeId, Vec<Subscription>>,
     message_receiver: Receiver<ScriptMessage>,
 }
 
@@ -473,25 +511,83 @@ impl ScriptMessageDispatcher {
     /// Subscribes a node to receive any message of the given type `T`. Subscription is automatically removed
     /// if the node dies.
     pub fn subscribe_to<T: 'static>(&mut self, receiver: Handle<Node>) {
-        self.type_groups
-            .entry(TypeId::of::<T>())
-            .and_modify(|v| {
-                v.insert(receiver);
-            })
-            .or_insert_with(|| FxHashSet::from_iter([receiver]));
+        self.subscribe::<T>(receiver, SubscriptionFilter::Any)
+    }
+
+    /// Subscribes a node to receive messages of the given type `T`, that passes the given filter. Repeated
+    /// subscription to the same type replaces the filter, but keeps the delivery order of the receiver.
+    /// Subscription is automatically removed if the node dies.
+    pub fn subscribe<T: 'static>(&mut self, receiver: Handle<Node>, filter: SubscriptionFilter) {
+        let group = self.type_groups.entry(TypeId::of::<T>()).or_default();
+        if let Some(subscription) = group.iter_mut().find(|s| s.receiver == receiver) {
+            subscription.filter = filter;
+        } else {
+            group.push(Subscription { receiver, filter });
+        }
     }
 
     /// Unsubscribes a node from receiving any messages of the given type `T`.
     pub fn unsubscribe_from<T: 'static>(&mut self, receiver: Handle<Node>) {
         if let Some(group) = self.type_groups.get_mut(&TypeId::of::<T>()) {
-            group.remove(&receiver);
+            group.retain(|s| s.receiver != receiver);
         }
     }
 
     /// Unsubscribes a node from receiving any messages.
     pub fn unsubscribe(&mut self, receiver: Handle<Node>) {
         for group in self.type_groups.values_mut() {
-            group.remove(&receiver);
+            group.retain(|s| s.receiver != receiver);
+        }
+    }
+
+    /// Returns `true` if the node is subscribed to receive messages of the given type `T`.
+    pub fn is_subscribed_to<T: 'static>(&self, receiver: Handle<Node>) -> bool {
+        self.type_groups
+            .get(&TypeId::of::<T>())
+            .map_or(false, |group| group.iter().any(|s| s.receiver == receiver))
+    }
+
+    fn collect_receivers(
+        subscriptions: &[Subscription],
+        message: &ScriptMessage,
+        graph: &Graph,
+    ) -> Vec<Handle<Node>> {
+        let accepts = |node: Handle<Node>| {
+            subscriptions
+                .iter()
+                .any(|s| s.receiver == node && s.filter.accepts(message.sender, graph))
+        };
+
+        match message.kind {
+            ScriptMessageKind::Targeted(target) => {
+                if accepts(target) {
+                    vec![target]
+                } else {
+                    vec![]
+                }
+            }
+            ScriptMessageKind::Hierarchical { root, ref routing } => match routing {
+                RoutingStrategy::Up => {
+                    let mut receivers = Vec::new();
+                    let mut node = root;
+                    while let Some(node_ref) = graph.try_get(node) {
+                        if accepts(node) {
+                            receivers.push(node);
+                        }
+                        node = node_ref.parent();
+                    }
+                    receivers
+                }
+                RoutingStrategy::Down => graph
+                    .traverse_handle_iter(root)
+                    .filter(|node| accepts(*node))
+                    .collect(),
+            },
+            ScriptMessageKind::Global => subscriptions
+                .iter()
+                .filter(|s| s.filter.accepts(message.sender, graph))
+                .map(|s| s.receiver)
+                .collect(),
         }
     }
 
@@ -507,117 +603,53 @@ impl ScriptMessageDispatcher {
         user_interface: &mut UserInterface,
         graphics_context: &mut GraphicsContext,
         task_pool: &mut TaskPoolHandler,
+        log_traffic: bool,
     ) {
         while let Ok(message) = self.message_receiver.try_recv() {
-            let receivers = self.type_groups.get(&message.payload.deref().type_id());
+            let subscriptions = self.type_groups.get(&message.payload.deref().type_id());
 
-            if receivers.map_or(true, |r| r.is_empty()) {
+            if subscriptions.map_or(true, |s| s.is_empty()) {
                 Log::warn(format!(
                     "Script message {message:?} was sent, but there's no receivers. \
                     Did you forgot to subscribe your script to the message?"
                 ));
             }
 
-            if let Some(receivers) = receivers {
-                let mut payload = message.payload;
-
-                match message.kind {
-                    ScriptMessageKind::Targeted(target) => {
-                        if receivers.contains(&target) {
-                            let mut context = ScriptMessageContext {
-                                dt,
-                                elapsed_time,
-                                plugins: PluginsRefMut(plugins),
-                                handle: target,
-                                scene,
-                                scene_handle,
-                                resource_manager,
-                                message_sender,
-                                task_pool,
-                                graphics_context,
-                                user_interface,
-                            };
-
-                            process_node_message(&mut context, &mut |s, ctx| {
-                                s.on_message(&mut *payload, ctx)
-                            })
-                        }
-                    }
-                    ScriptMessageKind::Hierarchical { root, routing } => match routing {
-                        RoutingStrategy::Up => {
-                            let mut node = root;
-                            while let Some(node_ref) = scene.graph.try_get(node) {
-                                let parent = node_ref.parent();
+            let receivers = subscriptions
+                .map(|s| Self::collect_receivers(s, &message, &scene.graph))
+                .unwrap_or_default();
+
+            let traffic_entry = log_traffic.then(|| ScriptMessageTrafficEntry {
+                time: elapsed_time,
+                type_name: message.payload.payload_type_name().to_string(),
+                payload: format!("{:?}", message.payload),
+                kind: format!("{:?}", message.kind),
+                sender: message.sender,
+                receivers: receivers.clone(),
+            });
 
-                                let mut context = ScriptMessageContext {
-                                    dt,
-                                    elapsed_time,
-                                    plugins: PluginsRefMut(plugins),
-                                    handle: node,
-                                    scene,
-                                    scene_handle,
-                                    resource_manager,
-                                    message_sender,
-                                    task_pool,
-                                    graphics_context,
-                                    user_interface,
-                                };
-
-                                if receivers.contains(&node) {
-                                    process_node_message(&mut context, &mut |s, ctx| {
-                                        s.on_message(&mut *payload, ctx)
-                                    });
-                                }
+            let mut payload = message.payload;
 
-                                node = parent;
-                            }
-                        }
-                        RoutingStrategy::Down => {
-                            for node in scene.graph.traverse_handle_iter(root).collect::<Vec<_>>() {
-                                let mut context = ScriptMessageContext {
-                                    dt,
-                                    elapsed_time,
-                                    plugins: PluginsRefMut(plugins),
-                                    handle: node,
-                                    scene,
-                                    scene_handle,
-                                    resource_manager,
-                                    message_sender,
-                                    task_pool,
-                                    graphics_context,
-                                    user_interface,
-                                };
-
-                                if receivers.contains(&node) {
-                                    process_node_message(&mut context, &mut |s, ctx| {
-                                        s.on_message(&mut *payload, ctx)
-                                    });
-                                }
-                            }
-                        }
-                    },
-                    ScriptMessageKind::Global => {
-                        for &node in receivers {
-                            let mut context = ScriptMessageContext {
-                                dt,
-                                elapsed_time,
-                                plugins: PluginsRefMut(plugins),
-                                handle: node,
-                                scene,
-                                scene_handle,
-                                resource_manager,
-                                message_sender,
-                                task_pool,
-                                graphics_context,
-                                user_interface,
-                            };
-
-                            process_node_message(&mut context, &mut |s, ctx| {
-                                s.on_message(&mut *payload, ctx)
-                            });
-                        }
-                    }
-                }
+            for node in receivers {
+                let mut context = ScriptMessageContext {
+                    dt,
+                    elapsed_time,
+                    plugins: PluginsRefMut(plugins),
+                    handle: node,
+                    scene,
+                    scene_handle,
+                    resource_manager,
+                    message_sender,
+                    task_pool,
+                    graphics_context,
+                    user_interface,
+                };
+
+                process_node_message(&mut context, &mut |s, ctx| s.on_message(&mut *payload, ctx));
+            }
+
+            if let Some(traffic_entry) = traffic_entry {
+                Log::info(traffic_entry.to_log_line());
             }
         }
     }
@@ -638,6 +670,9 @@ pub struct ScriptProcessor {
     wait_list: Vec<ResourceWaitContext>,
     /// A list of scenes.
     pub scripted_scenes: Vec<ScriptedScene>,
+    /// If set, every dispatched script message will be written to the log as [`ScriptMessageTrafficEntry`].
+    /// Could be useful for debugging, but it is very slow if there are lots of messages.
+    pub log_message_traffic: bool,
 }
 
 impl ScriptProcessor {
@@ -656,7 +691,10 @@ impl ScriptProcessor {
         let (tx, rx) = channel();
         self.scripted_scenes.push(ScriptedScene {
             handle: scene,
-            message_sender: ScriptMessageSender { sender: tx },
+            message_sender: ScriptMessageSender {
+                sender: tx,
+                origin: Handle::NONE,
+            },
             message_dispatcher: ScriptMessageDispatcher::new(rx),
         });
 
@@ -716,6 +754,8 @@ impl ScriptProcessor {
                 }
             }
 
+            send_animation_events(&mut scene.graph, &scripted_scene.message_sender);
+
             // We'll gather all scripts queued for destruction and destroy them all at once at the
             // end of the frame.
             let mut destruction_queue = VecDeque::new();
@@ -818,6 +858,7 @@ impl ScriptProcessor {
                         user_interface,
                         graphics_context,
                         task_pool,
+                        self.log_message_traffic,
                     );
                 }
 
@@ -1043,6 +1084,32 @@ macro_rules! define_process_node {
 define_process_node!(process_node, ScriptContext);
 define_process_node!(process_node_message, ScriptMessageContext);
 
+/// Delivers animation events produced on the last update to scripts.
+fn send_animation_events(graph: &mut Graph, sender: &ScriptMessageSender) {
+    for (handle, node) in graph.pair_iter_mut() {
+        let Some(animation_player) = node.cast_mut::<AnimationPlayer>() else {
+            continue;
+        };
+        for (animation_handle, animation) in animation_player
+            .animations_mut()
+            .get_value_mut_silent()
+            .pair_iter_mut()
+        {
+            for event in animation.take_last_tick_events() {
+                sender.send_hierarchical(
+                    handle,
+                    RoutingStrategy::Up,
+                    AnimationEventMessage {
+                        animation_player: handle,
+                        animation: animation_handle,
+                        event,
+                    },
+                );
+            }
+        }
+    }
+}
+
 pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
     scene_handle: Handle<Scene>,
@@ -1134,6 +1201,11 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<Shader>();
     state.constructors_container.add::<Model>();
     state.constructors_container.add::<CurveResourceState>();
+    state.constructors_container.add::<ConvexDecomposition>();
+    state.constructors_container.add::<AudioEventBank>();
+    state.constructors_container.add::<VisualScript>();
+    #[cfg(feature = "wasm_scripting")]
+    state.constructors_container.add::<WasmModule>();
     state.constructors_container.add::<SoundBuffer>();
     state.constructors_container.add::<HrirSphereResourceData>();
     state.constructors_container.add::<Material>();
@@ -1144,12 +1216,21 @@ pub(crate) fn initialize_resource_manager_loaders(
     loaders.set(model_loader);
     loaders.set(TextureLoader {
         default_import_options: Default::default(),
+        #[cfg(not(target_arch = "wasm32"))]
+        derived_data_cache: Some(TextureLoader::default_derived_data_cache()),
+        #[cfg(target_arch = "wasm32")]
+        derived_data_cache: None,
     });
     loaders.set(SoundBufferLoader {
         default_import_options: Default::default(),
     });
     loaders.set(ShaderLoader);
     loaders.set(CurveLoader);
+    loaders.set(ConvexDecompositionLoader);
+    loaders.set(AudioEventBankLoader);
+    loaders.set(VisualScriptLoader);
+    #[cfg(feature = "wasm_scripting")]
+    loaders.set(WasmModuleLoader);
     loaders.set(HrirSphereLoader);
     loaders.set(MaterialLoader {
         resource_manager: resource_manager.clone(),
@@ -1745,6 +1826,9 @@ impl Engine {
         lag: &mut f32,
         switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
     ) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle_plugins_hot_reloading(Some(window_target));
+
         if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
             let inner_size = ctx.window.inner_size();
             let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
@@ -1767,11 +1851,13 @@ impl Engine {
                             }
                         });
 
-                scene.update(
-                    frame_size,
-                    dt,
-                    switches.get(&handle).cloned().unwrap_or_default(),
-                );
+                let switches = switches.get(&handle).cloned().unwrap_or_default();
+
+                if switches.streaming && !switches.paused {
+                    SceneStreaming::update(scene, &self.resource_manager);
+                }
+
+                scene.update(frame_size, dt, switches);
             }
 
             self.update_plugins(dt, window_target, lag);
@@ -2111,7 +2197,8 @@ impl Engine {
         }
     }
 
-    /// Handle hot-reloading of resources.
+    /// Handle hot-reloading of resources. Reloaded models are propagated to their instances in
+    /// every scene, reloaded shaders are propagated to every material that uses them.
     ///
     /// Normally, this is called from `Engine::update()`.
     /// You should only call this manually if you don't use that method.
@@ -2136,6 +2223,34 @@ impl Engine {
                     for scene in self.scenes.iter_mut() {
                         scene.resolve(&self.resource_manager);
                     }
+                } else if let Some(shader) = resource.try_cast::<Shader>() {
+                    Log::info(format!(
+                        "A shader resource {} was reloaded, propagating changes...",
+                        shader.kind()
+                    ));
+
+                    // Shader's GPU programs will be re-created automatically, but property sets
+                    // of the materials must be synchronized with the new shader definition.
+                    let materials = self
+                        .resource_manager
+                        .state()
+                        .iter()
+                        .filter_map(|resource| resource.try_cast::<Material>())
+                        .collect::<Vec<_>>();
+                    for material in materials {
+                        let mut material_state = material.state();
+                        if let Some(material) = material_state.data() {
+                            if material.shader() == &shader {
+                                material.sync_to_shader(&self.resource_manager);
+                            }
+                        }
+                    }
+
+                    for scene in self.scenes.iter_mut() {
+                        scene
+                            .graph
+                            .sync_materials_to_shaders(&self.resource_manager);
+                    }
                 }
             }
         }
@@ -2182,8 +2297,8 @@ impl Engine {
 
             if self.plugins_enabled {
                 // Create and initialize instances.
-                for constructor in self.plugin_constructors.iter() {
-                    self.plugins.push(constructor.create_instance(
+                for container in self.plugin_constructors.iter() {
+                    self.plugins.push(container.constructor().create_instance(
                         scene_path,
                         PluginContext {
                             scenes: &mut self.scenes,
@@ -2237,7 +2352,177 @@ impl Engine {
             resource_manager: &self.resource_manager,
         });
 
-        self.plugin_constructors.push(Box::new(constructor));
+        self.plugin_constructors
+            .push(PluginConstructorContainer::Static(Box::new(constructor)));
+    }
+
+    /// Loads a plugin from a dynamic library at the given path. If `reload_when_changed` is set,
+    /// the plugin will be reloaded automatically every time the library changes. See [`DynamicPlugin`]
+    /// docs for more info about the requirements for the library and about reloading.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_dynamic_plugin<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<(), DynamicPluginError> {
+        let mut plugin = DynamicPlugin::load(path, reload_when_changed)?;
+
+        plugin.register(&self.serialization_context, &self.resource_manager);
+
+        self.plugin_constructors
+            .push(PluginConstructorContainer::Dynamic(plugin));
+
+        Ok(())
+    }
+
+    /// Reloads every dynamic plugin, even if its library hasn't changed. Errors are written to the
+    /// log, a plugin that failed to reload keeps working with the old library.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reload_dynamic_plugins(&mut self, window_target: Option<&EventLoopWindowTarget<()>>) {
+        for index in 0..self.plugin_constructors.len() {
+            if let PluginConstructorContainer::Dynamic(_) = self.plugin_constructors[index] {
+                self.reload_dynamic_plugin_logged(index, window_target);
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_plugins_hot_reloading(&mut self, window_target: Option<&EventLoopWindowTarget<()>>) {
+        for index in 0..self.plugin_constructors.len() {
+            if let PluginConstructorContainer::Dynamic(plugin) =
+                &mut self.plugin_constructors[index]
+            {
+                if plugin.is_reload_needed() {
+                    self.reload_dynamic_plugin_logged(index, window_target);
+                }
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_dynamic_plugin_logged(
+        &mut self,
+        index: usize,
+        window_target: Option<&EventLoopWindowTarget<()>>,
+    ) {
+        match self.reload_dynamic_plugin(index, window_target) {
+            Ok(()) => Log::info("Dynamic plugin was reloaded successfully!"),
+            Err(e) => Log::err(format!("Unable to reload dynamic plugin. Reason: {e}")),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_dynamic_plugin(
+        &mut self,
+        index: usize,
+        window_target: Option<&EventLoopWindowTarget<()>>,
+    ) -> Result<(), DynamicPluginError> {
+        let PluginConstructorContainer::Dynamic(plugin) = &mut self.plugin_constructors[index]
+        else {
+            return Ok(());
+        };
+
+        let models = self
+            .resource_manager
+            .state()
+            .resources()
+            .into_iter()
+            .filter_map(|resource| resource.try_cast::<Model>())
+            .filter(|model| model.is_ok())
+            .collect::<Vec<_>>();
+
+        for scene in self.scenes.iter() {
+            dynamic::check_nodes(&scene.graph, &plugin.node_types)?;
+        }
+        for model in models.iter() {
+            dynamic::check_nodes(&model.data_ref().scene.graph, &plugin.node_types)?;
+        }
+
+        // Load the new library before destroying anything, so a broken library won't affect the
+        // running game.
+        let library = plugin.load_next()?;
+
+        // Save and destroy everything, that was created by the old library.
+        let mut scene_scripts = Vec::new();
+        for (handle, scene) in self.scenes.pair_iter_mut() {
+            let scripts = dynamic::take_scripts(&mut scene.graph, &plugin.script_types)?;
+            scene_scripts.push((handle, scripts));
+        }
+        let mut model_scripts = Vec::new();
+        for model in models {
+            let scripts =
+                dynamic::take_scripts(&mut model.data_ref().scene.graph, &plugin.script_types)?;
+            model_scripts.push((model, scripts));
+        }
+
+        let plugin_state = if self.plugins_enabled {
+            let mut old_instance = self.plugins.remove(index);
+            let mut visitor = Visitor::new();
+            old_instance.visit_hot_reload_state(&mut visitor)?;
+            Some(visitor.save_binary_to_vec()?)
+        } else {
+            None
+        };
+
+        // Replace the library and restore everything using the new one.
+        plugin.replace_library(library, &self.serialization_context);
+        plugin.register(&self.serialization_context, &self.resource_manager);
+
+        if let Some(plugin_state) = plugin_state {
+            let mut new_instance = plugin
+                .constructor()
+                .create_reloaded_instance(PluginContext {
+                    scenes: &mut self.scenes,
+                    resource_manager: &self.resource_manager,
+                    graphics_context: &mut self.graphics_context,
+                    dt: 0.0,
+                    lag: &mut 0.0,
+                    user_interface: &mut self.user_interface,
+                    serialization_context: &self.serialization_context,
+                    performance_statistics: &self.performance_statistics,
+                    elapsed_time: self.elapsed_time,
+                    script_processor: &self.script_processor,
+                    async_scene_loader: &mut self.async_scene_loader,
+                    window_target,
+                    task_pool: &mut self.task_pool,
+                });
+
+            let mut visitor = Visitor::load_from_memory(&plugin_state)?;
+            visitor
+                .blackboard
+                .register(self.serialization_context.clone());
+            visitor
+                .blackboard
+                .register(Arc::new(self.resource_manager.clone()));
+            if let Err(e) = new_instance.visit_hot_reload_state(&mut visitor) {
+                Log::err(format!(
+                    "Unable to restore the state of reloaded plugin. Reason: {e:?}"
+                ));
+            }
+
+            self.plugins.insert(index, new_instance);
+        }
+
+        for (handle, scripts) in scene_scripts {
+            if let Some(scene) = self.scenes.try_get_mut(handle) {
+                dynamic::restore_scripts(
+                    &mut scene.graph,
+                    scripts,
+                    &self.serialization_context,
+                    &self.resource_manager,
+                );
+            }
+        }
+        for (model, scripts) in model_scripts {
+            dynamic::restore_scripts(
+                &mut model.data_ref().scene.graph,
+                scripts,
+                &self.serialization_context,
+                &self.resource_manager,
+            );
+        }
+
+        Ok(())
     }
 }
 
@@ -2273,7 +2558,7 @@ mod test {
         scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
         script::{
             Script, ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
-            ScriptTrait,
+            ScriptMessageTrafficEntry, ScriptTrait, SubscriptionFilter,
         },
     };
     use std::sync::Arc;
@@ -2594,4 +2879,135 @@ mod test {
             }
         }
     }
+
+    #[derive(Debug)]
+    struct FilteredMessage(u32);
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptSendingFilteredMessages {
+        value: u32,
+    }
+
+    impl_component_provider!(ScriptSendingFilteredMessages);
+    uuid_provider!(ScriptSendingFilteredMessages = "2c617f58-cee5-4a22-a6b8-11030939fd59");
+
+    impl ScriptTrait for ScriptSendingFilteredMessages {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            if self.value != 0 {
+                ctx.message_sender
+                    .with_origin(ctx.handle)
+                    .send_global(FilteredMessage(self.value));
+                self.value = 0;
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptFilteringMessages {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        filter: SubscriptionFilter,
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(Handle<Node>, u32)>,
+    }
+
+    impl_component_provider!(ScriptFilteringMessages);
+    uuid_provider!(ScriptFilteringMessages = "82025244-3958-4642-a449-5286a501a2eb");
+
+    impl ScriptTrait for ScriptFilteringMessages {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            ctx.message_dispatcher
+                .subscribe::<FilteredMessage>(ctx.handle, self.filter);
+        }
+
+        fn on_message(
+            &mut self,
+            message: &mut dyn ScriptMessagePayload,
+            ctx: &mut ScriptMessageContext,
+        ) {
+            let FilteredMessage(value) = message.downcast_ref::<FilteredMessage>().unwrap();
+            self.sender.send((ctx.handle, *value)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_message_filters() {
+        let resource_manager = ResourceManager::new(Arc::new(Default::default()));
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(ScriptSendingFilteredMessages { value: 1 })),
+        )
+        .build(&mut scene.graph);
+        let parent =
+            PivotBuilder::new(BaseBuilder::new().with_children(&[child])).build(&mut scene.graph);
+        let other = PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(ScriptSendingFilteredMessages { value: 2 })),
+        )
+        .build(&mut scene.graph);
+
+        let mut add_listener = |filter| {
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(
+                ScriptFilteringMessages {
+                    filter,
+                    sender: tx.clone(),
+                },
+            )))
+            .build(&mut scene.graph)
+        };
+        let hierarchy_listener = add_listener(SubscriptionFilter::Hierarchy(parent));
+        let sender_listener = add_listener(SubscriptionFilter::Sender(other));
+        let any_listener = add_listener(SubscriptionFilter::Any);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        let mut task_pool = TaskPoolHandler::new(Arc::new(TaskPool::new()));
+        let mut gc = GraphicsContext::Uninitialized(Default::default());
+        let mut user_interface = UserInterface::default();
+
+        script_processor.register_scripted_scene(scene_handle, &resource_manager);
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Vec::new(),
+            &resource_manager,
+            &mut task_pool,
+            &mut gc,
+            &mut user_interface,
+            0.0,
+            0.0,
+        );
+
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![
+                (hierarchy_listener, 1),
+                (any_listener, 1),
+                (sender_listener, 2),
+                (any_listener, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_traffic_entry_log_line() {
+        let entry = ScriptMessageTrafficEntry {
+            time: 1.5,
+            type_name: "FilteredMessage".to_string(),
+            payload: "FilteredMessage(1)".to_string(),
+            kind: "Global".to_string(),
+            sender: Handle::new(1, 1),
+            receivers: vec![Handle::new(2, 1), Handle::new(3, 1)],
+        };
+        let line = format!("[INFO]: {}", entry.to_log_line());
+        assert_eq!(ScriptMessageTrafficEntry::from_log_line(&line), Some(entry));
+        assert_eq!(
+            ScriptMessageTrafficEntry::from_log_line("[INFO]: foo"),
+            None
+        );
+    }
 }
@@ -163,6 +163,19 @@
 //! | fyrox_blendShapesStorage   | `sampler3D`  | 3D texture of layered blend shape storage. Use `S_FetchBlendShapeOffsets` built-in method to fetch info.          |
 //! | fyrox_blendShapesWeights   | `float[128]` | Weights of all available blend shapes.                                                                            |
 //! | fyrox_blendShapesCount     | `int`        | Total amount of blend shapes.                                                                                     |
+//! | fyrox_useInstancing        | `bool`       | Whether multiple instances are rendering in a single draw call or not.                                            |
+//! | fyrox_instanceMatrices     | `sampler2D`  | Per-instance data. Use `S_FetchInstanceWorldMatrix` and `S_FetchInstanceColor` built-in methods with `gl_InstanceID`. |
+//! | fyrox_instanceColor        | `vec4`       | Color of the instance, when instancing is not used.                                                               |
+//! | fyrox_useClusteredLighting | `bool`       | Whether clustered lighting data is available or not. Forward pass only.                                           |
+//! | fyrox_clusterLights        | `sampler2D`  | Lights packed into a texture. Use `S_FetchClusteredLight` built-in method to fetch a light by its index.          |
+//! | fyrox_clusterGrid          | `sampler2D`  | Grid of clusters. Use `S_FetchClusterLightRange` built-in method to fetch a range of light indices of a cluster.  |
+//! | fyrox_clusterLightIndices  | `sampler2D`  | Light indices of clusters. Use `S_FetchClusterLightIndex` built-in method to fetch a light index.                 |
+//! | fyrox_clusterParams        | `vec4`       | Parameters of the cluster grid, must be passed to `S_FetchClusterLightRange`.                                     |
+//! | fyrox_clusterDepthAxis     | `vec3`       | Depth axis of the cluster grid, must be passed to `S_FetchClusterLightRange`.                                     |
+//! | fyrox_prevWorldMatrix      | `mat4`       | Local-to-world transformation in the previous frame.                                                              |
+//! | fyrox_prevBoneMatrices     | `sampler2D`  | Bone matrices in the previous frame. Use `S_SkinPosition` built-in method to skin a vertex.                       |
+//! | fyrox_prevViewProjectionMatrix | `mat4`       | World-to-clip-space transform of the camera in the previous frame (without jitter).                               |
+//! | fyrox_unjitteredViewProjectionMatrix | `mat4`       | World-to-clip-space transform of the camera without temporal anti-aliasing jitter.                                |
 //!
 //! To use any of the properties, just define a uniform with an appropriate name:
 //!
@@ -173,6 +186,49 @@
 //!
 //! This list will be extended in future releases.
 //!
+//! ## Instancing
+//!
+//! The renderer automatically draws all instances of a surface (that share the same surface data and
+//! material) using a single draw call, but only if the shader of a render pass defines both
+//! `fyrox_useInstancing` and `fyrox_instanceMatrices` uniforms. When `fyrox_useInstancing` is `true`,
+//! the shader must fetch the world matrix (and the color) of an instance using `gl_InstanceID`, the
+//! `fyrox_worldViewProjection` in this case is valid only for the first instance. The standard
+//! shader supports instancing in every render pass except `Forward`.
+//!
+//! ## Velocity
+//!
+//! Temporal anti-aliasing needs per-pixel velocities, that must be written by the `GBuffer` pass to
+//! the render target at location 5. A vertex shader must calculate clip-space positions of a vertex in
+//! the current and the previous frames using `fyrox_unjitteredViewProjectionMatrix` and
+//! `fyrox_prev*` uniforms, and a fragment shader should write them using `S_Velocity` built-in method:
+//!
+//! ```glsl
+//! outVelocity = vec4(S_Velocity(currentClipPosition, previousClipPosition), 0.0, 1.0);
+//! ```
+//!
+//! Pixels with zero alpha in the velocity render target are reprojected using depth, which is correct
+//! only for static objects.
+//!
+//! ## Clustered lighting
+//!
+//! Forward pass can use any amount of lights using clustered lighting (see
+//! [`crate::renderer::cluster::LightClusterGrid`] docs for more info). A fragment shader must find
+//! a cluster, that contains a fragment, and then iterate over its lights:
+//!
+//! ```glsl
+//! ivec2 range = S_FetchClusterLightRange(fyrox_clusterGrid, fragmentPosition,
+//!     fyrox_viewProjectionMatrix, fyrox_cameraPosition, fyrox_clusterParams, fyrox_clusterDepthAxis);
+//! for (int i = 0; i < range.y; ++i) {
+//!     int lightIndex = S_FetchClusterLightIndex(fyrox_clusterLightIndices, range.x + i);
+//!     TClusteredLight light = S_FetchClusteredLight(fyrox_clusterLights, lightIndex);
+//!     // Calculate lighting.
+//! }
+//! ```
+//!
+//! When clustered lighting is disabled in quality settings, `fyrox_useClusteredLighting` is `false`
+//! and the shader should fall back to `fyrox_lightCount` and `fyrox_lights*` uniforms, that contain
+//! first 16 visible lights. See the standard 2D shader for an example.
+//!
 //! # Drawing parameters
 //!
 //! Drawing parameters defines which GPU functions to use and at which state. For example, to render
@@ -99,6 +99,10 @@
                 uniform mat4 fyrox_worldViewProjection;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform sampler2D fyrox_boneMatrices;
+                uniform sampler2D fyrox_prevBoneMatrices;
+                uniform mat4 fyrox_prevWorldMatrix;
+                uniform mat4 fyrox_prevViewProjectionMatrix;
+                uniform mat4 fyrox_unjitteredViewProjectionMatrix;
 
                 out vec3 position;
                 out vec3 normal;
@@ -106,6 +110,8 @@
                 out vec3 tangent;
                 out vec3 binormal;
                 out vec2 secondTexCoord;
+                out vec4 currentClipPosition;
+                out vec4 previousClipPosition;
 
                 void main()
                 {
@@ -158,6 +164,12 @@
                     secondTexCoord = vertexSecondTexCoord;
 
                     gl_Position = fyrox_worldViewProjection * localPosition;
+
+                    vec4 prevLocalPosition = fyrox_useSkeletalAnimation
+                        ? S_SkinPosition(fyrox_prevBoneMatrices, vec4(vertexPosition, 1.0), boneIndices, boneWeights)
+                        : vec4(vertexPosition, 1.0);
+                    currentClipPosition = fyrox_unjitteredViewProjectionMatrix * fyrox_worldMatrix * localPosition;
+                    previousClipPosition = fyrox_prevViewProjectionMatrix * fyrox_prevWorldMatrix * prevLocalPosition;
                 }
                 "#,
             fragment_shader:
@@ -167,6 +179,10 @@
                 layout(location = 2) out vec4 outAmbient;
                 layout(location = 3) out vec4 outMaterial;
                 layout(location = 4) out uint outDecalMask;
+                layout(location = 5) out vec4 outVelocity;
+
+                in vec4 currentClipPosition;
+                in vec4 previousClipPosition;
 
                 // Properties.
                 uniform sampler2D diffuseTexture;
@@ -235,6 +251,8 @@
                     outAmbient.a = 1.0;
 
                     outDecalMask = layerIndex;
+
+                    outVelocity = vec4(S_Velocity(currentClipPosition, previousClipPosition), 0.0, 1.0);
                 }
                 "#,
         ),
@@ -97,11 +97,19 @@
                 // required data to these uniforms.
                 uniform mat4 fyrox_worldMatrix;
                 uniform mat4 fyrox_worldViewProjection;
+                uniform mat4 fyrox_viewProjectionMatrix;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform sampler2D fyrox_boneMatrices;
                 uniform sampler3D fyrox_blendShapesStorage;
                 uniform float fyrox_blendShapesWeights[128];
                 uniform int fyrox_blendShapesCount;
+                uniform bool fyrox_useInstancing;
+                uniform sampler2D fyrox_instanceMatrices;
+                uniform vec4 fyrox_instanceColor;
+                uniform sampler2D fyrox_prevBoneMatrices;
+                uniform mat4 fyrox_prevWorldMatrix;
+                uniform mat4 fyrox_prevViewProjectionMatrix;
+                uniform mat4 fyrox_unjitteredViewProjectionMatrix;
 
                 out vec3 position;
                 out vec3 normal;
@@ -109,6 +117,9 @@
                 out vec3 tangent;
                 out vec3 binormal;
                 out vec2 secondTexCoord;
+                out vec4 instanceColor;
+                out vec4 currentClipPosition;
+                out vec4 previousClipPosition;
 
                 void main()
                 {
@@ -162,15 +173,33 @@
                         localTangent = inputTangent;
                     }
 
-                    mat3 nm = mat3(fyrox_worldMatrix);
+                    mat4 worldMatrix = fyrox_worldMatrix;
+                    mat4 worldViewProjection = fyrox_worldViewProjection;
+                    instanceColor = fyrox_instanceColor;
+                    if (fyrox_useInstancing)
+                    {
+                        worldMatrix = S_FetchInstanceWorldMatrix(fyrox_instanceMatrices, gl_InstanceID);
+                        worldViewProjection = fyrox_viewProjectionMatrix * worldMatrix;
+                        instanceColor = S_FetchInstanceColor(fyrox_instanceMatrices, gl_InstanceID);
+                    }
+
+                    mat3 nm = mat3(worldMatrix);
                     normal = normalize(nm * localNormal);
                     tangent = normalize(nm * localTangent);
                     binormal = normalize(vertexTangent.w * cross(normal, tangent));
                     texCoord = vertexTexCoord;
-                    position = vec3(fyrox_worldMatrix * localPosition);
+                    position = vec3(worldMatrix * localPosition);
                     secondTexCoord = vertexSecondTexCoord;
 
-                    gl_Position = fyrox_worldViewProjection * localPosition;
+                    gl_Position = worldViewProjection * localPosition;
+
+                    // Instances use the same world matrix in both frames, only camera motion is taken into account.
+                    mat4 prevWorldMatrix = fyrox_useInstancing ? worldMatrix : fyrox_prevWorldMatrix;
+                    vec4 prevLocalPosition = fyrox_useSkeletalAnimation
+                        ? S_SkinPosition(fyrox_prevBoneMatrices, inputPosition, boneIndices, boneWeights)
+                        : inputPosition;
+                    currentClipPosition = fyrox_unjitteredViewProjectionMatrix * worldMatrix * localPosition;
+                    previousClipPosition = fyrox_prevViewProjectionMatrix * prevWorldMatrix * prevLocalPosition;
                 }
                 "#,
             fragment_shader:
@@ -180,6 +209,10 @@
                 layout(location = 2) out vec4 outAmbient;
                 layout(location = 3) out vec4 outMaterial;
                 layout(location = 4) out uint outDecalMask;
+                layout(location = 5) out vec4 outVelocity;
+
+                in vec4 currentClipPosition;
+                in vec4 previousClipPosition;
 
                 // Properties.
                 uniform sampler2D diffuseTexture;
@@ -208,6 +241,7 @@
                 in vec3 tangent;
                 in vec3 binormal;
                 in vec2 secondTexCoord;
+                in vec4 instanceColor;
 
                 void main()
                 {
@@ -228,7 +262,7 @@
                         tc = texCoord * texCoordScale;
                     }
 
-                    outColor = diffuseColor * texture(diffuseTexture, tc);
+                    outColor = instanceColor * diffuseColor * texture(diffuseTexture, tc);
 
                     // Alpha test.
                     if (outColor.a < 0.5) {
@@ -248,6 +282,8 @@
                     outAmbient.a = 1.0;
 
                     outDecalMask = layerIndex;
+
+                    outVelocity = vec4(S_Velocity(currentClipPosition, previousClipPosition), 0.0, 1.0);
                 }
                 "#,
         ),
@@ -343,13 +379,15 @@
                 uniform sampler2D diffuseTexture;
                 uniform vec4 diffuseColor;
 
+                uniform vec4 fyrox_instanceColor;
+
                 out vec4 FragColor;
 
                 in vec2 texCoord;
 
                 void main()
                 {
-                    FragColor = diffuseColor * texture(diffuseTexture, texCoord);
+                    FragColor = fyrox_instanceColor * diffuseColor * texture(diffuseTexture, texCoord);
                 }
                "#,
         ),
@@ -384,11 +422,14 @@
                 layout(location = 5) in vec4 boneIndices;
 
                 uniform mat4 fyrox_worldViewProjection;
+                uniform mat4 fyrox_viewProjectionMatrix;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform sampler2D fyrox_boneMatrices;
                 uniform sampler3D fyrox_blendShapesStorage;
                 uniform float fyrox_blendShapesWeights[128];
                 uniform int fyrox_blendShapesCount;
+                uniform bool fyrox_useInstancing;
+                uniform sampler2D fyrox_instanceMatrices;
 
                 out vec2 texCoord;
 
@@ -423,7 +464,15 @@
                         localPosition = inputPosition;
                     }
 
-                    gl_Position = fyrox_worldViewProjection * localPosition;
+                    if (fyrox_useInstancing)
+                    {
+                        mat4 worldMatrix = S_FetchInstanceWorldMatrix(fyrox_instanceMatrices, gl_InstanceID);
+                        gl_Position = fyrox_viewProjectionMatrix * worldMatrix * localPosition;
+                    }
+                    else
+                    {
+                        gl_Position = fyrox_worldViewProjection * localPosition;
+                    }
                     texCoord = vertexTexCoord;
                 }
                 "#,
@@ -471,11 +520,14 @@
                 layout(location = 5) in vec4 boneIndices;
 
                 uniform mat4 fyrox_worldViewProjection;
+                uniform mat4 fyrox_viewProjectionMatrix;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform sampler2D fyrox_boneMatrices;
                 uniform sampler3D fyrox_blendShapesStorage;
                 uniform float fyrox_blendShapesWeights[128];
                 uniform int fyrox_blendShapesCount;
+                uniform bool fyrox_useInstancing;
+                uniform sampler2D fyrox_instanceMatrices;
 
                 out vec2 texCoord;
 
@@ -510,7 +562,15 @@
                         localPosition = inputPosition;
                     }
 
-                    gl_Position = fyrox_worldViewProjection * localPosition;
+                    if (fyrox_useInstancing)
+                    {
+                        mat4 worldMatrix = S_FetchInstanceWorldMatrix(fyrox_instanceMatrices, gl_InstanceID);
+                        gl_Position = fyrox_viewProjectionMatrix * worldMatrix * localPosition;
+                    }
+                    else
+                    {
+                        gl_Position = fyrox_worldViewProjection * localPosition;
+                    }
                     texCoord = vertexTexCoord;
                 }
                 "#,
@@ -559,11 +619,14 @@
 
                 uniform mat4 fyrox_worldMatrix;
                 uniform mat4 fyrox_worldViewProjection;
+                uniform mat4 fyrox_viewProjectionMatrix;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform sampler2D fyrox_boneMatrices;
                 uniform sampler3D fyrox_blendShapesStorage;
                 uniform float fyrox_blendShapesWeights[128];
                 uniform int fyrox_blendShapesCount;
+                uniform bool fyrox_useInstancing;
+                uniform sampler2D fyrox_instanceMatrices;
 
                 out vec2 texCoord;
                 out vec3 worldPosition;
@@ -599,8 +662,16 @@
                         localPosition = inputPosition;
                     }
 
-                    gl_Position = fyrox_worldViewProjection * localPosition;
-                    worldPosition = (fyrox_worldMatrix * localPosition).xyz;
+                    mat4 worldMatrix = fyrox_worldMatrix;
+                    mat4 worldViewProjection = fyrox_worldViewProjection;
+                    if (fyrox_useInstancing)
+                    {
+                        worldMatrix = S_FetchInstanceWorldMatrix(fyrox_instanceMatrices, gl_InstanceID);
+                        worldViewProjection = fyrox_viewProjectionMatrix * worldMatrix;
+                    }
+
+                    gl_Position = worldViewProjection * localPosition;
+                    worldPosition = (worldMatrix * localPosition).xyz;
                     texCoord = vertexTexCoord;
                 }
                 "#,
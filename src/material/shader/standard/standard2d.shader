@@ -74,32 +74,65 @@
                 uniform vec2 fyrox_lightsParameters[16]; // x = hotspot angle, y - full cone angle delta
                 uniform vec4 fyrox_ambientLightColor;
 
+                uniform bool fyrox_useClusteredLighting;
+                uniform sampler2D fyrox_clusterLights;
+                uniform sampler2D fyrox_clusterGrid;
+                uniform sampler2D fyrox_clusterLightIndices;
+                uniform vec4 fyrox_clusterParams;
+                uniform vec3 fyrox_clusterDepthAxis;
+                uniform mat4 fyrox_viewProjectionMatrix;
+                uniform vec3 fyrox_cameraPosition;
+
                 out vec4 FragColor;
 
                 in vec2 texCoord;
                 in vec4 color;
                 in vec3 fragmentPosition;
 
+                vec3 CalculateLighting(vec3 lightPosition, vec3 direction, vec3 lightColor, float radius, float halfHotspotAngleCos, float halfConeAngleCos) {
+                    vec3 toFragment = fragmentPosition - lightPosition;
+                    float distance = length(toFragment);
+                    vec3 toFragmentNormalized = toFragment / distance;
+                    float distanceAttenuation = S_LightDistanceAttenuation(distance, radius);
+                    float spotAngleCos = dot(toFragmentNormalized, direction);
+                    float directionalAttenuation = smoothstep(halfConeAngleCos, halfHotspotAngleCos, spotAngleCos);
+                    return lightColor * (distanceAttenuation * directionalAttenuation);
+                }
+
                 void main()
                 {
                     vec3 lighting = fyrox_ambientLightColor.xyz;
-                    for(int i = 0; i < fyrox_lightCount; ++i) {
-                        // "Unpack" light parameters.
-                        float halfHotspotAngleCos = fyrox_lightsParameters[i].x;
-                        float halfConeAngleCos = fyrox_lightsParameters[i].y;
-                        vec3 lightColor = fyrox_lightsColorRadius[i].xyz;
-                        float radius = fyrox_lightsColorRadius[i].w;
-                        vec3 lightPosition = fyrox_lightsPosition[i];
-                        vec3 direction = fyrox_lightsDirection[i];
+                    if (fyrox_useClusteredLighting) {
+                        ivec2 range = S_FetchClusterLightRange(
+                            fyrox_clusterGrid,
+                            fragmentPosition,
+                            fyrox_viewProjectionMatrix,
+                            fyrox_cameraPosition,
+                            fyrox_clusterParams,
+                            fyrox_clusterDepthAxis);
+                        for (int i = 0; i < range.y; ++i) {
+                            int lightIndex = S_FetchClusterLightIndex(fyrox_clusterLightIndices, range.x + i);
+                            TClusteredLight light = S_FetchClusteredLight(fyrox_clusterLights, lightIndex);
+                            lighting += CalculateLighting(
+                                light.position,
+                                light.direction,
+                                light.color,
+                                light.radius,
+                                light.halfHotspotConeAngleCos,
+                                light.halfConeAngleCos);
+                        }
+                    } else {
+                        for(int i = 0; i < fyrox_lightCount; ++i) {
+                            // "Unpack" light parameters.
+                            float halfHotspotAngleCos = fyrox_lightsParameters[i].x;
+                            float halfConeAngleCos = fyrox_lightsParameters[i].y;
+                            vec3 lightColor = fyrox_lightsColorRadius[i].xyz;
+                            float radius = fyrox_lightsColorRadius[i].w;
+                            vec3 lightPosition = fyrox_lightsPosition[i];
+                            vec3 direction = fyrox_lightsDirection[i];
 
-                        // Calculate lighting.
-                        vec3 toFragment = fragmentPosition - lightPosition;
-                        float distance = length(toFragment);
-                        vec3 toFragmentNormalized = toFragment / distance;
-                        float distanceAttenuation = S_LightDistanceAttenuation(distance, radius);
-                        float spotAngleCos = dot(toFragmentNormalized, direction);
-                        float directionalAttenuation = smoothstep(halfConeAngleCos, halfHotspotAngleCos, spotAngleCos);
-                        lighting += lightColor * (distanceAttenuation * directionalAttenuation);
+                            lighting += CalculateLighting(lightPosition, direction, lightColor, radius, halfHotspotAngleCos, halfConeAngleCos);
+                        }
                     }
 
                     FragColor = vec4(lighting, 1.0) * color * S_SRGBToLinear(texture(diffuseTexture, texCoord));
@@ -43,10 +43,18 @@
             name: "heightMapTexture",
             kind: Sampler(default: None, fallback: White),
         ),
+        (
+            name: "holeMaskTexture",
+            kind: Sampler(default: None, fallback: White),
+        ),
         (
             name: "nodeUvOffsets",
             kind: Vector4((0.0, 0.0, 0.0, 0.0)),
         ),
+        (
+            name: "skirtDepth",
+            kind: Float(0.0),
+        ),
         (
             name: "texCoordScale",
             kind: Vector2((1.0, 1.0)),
@@ -117,11 +125,15 @@
                 // Properties
                 uniform sampler2D heightMapTexture;
                 uniform vec4 nodeUvOffsets;
+                uniform float skirtDepth;
 
                 // Define uniforms with reserved names. Fyrox will automatically provide
                 // required data to these uniforms.
                 uniform mat4 fyrox_worldMatrix;
                 uniform mat4 fyrox_worldViewProjection;
+                uniform mat4 fyrox_prevWorldMatrix;
+                uniform mat4 fyrox_prevViewProjectionMatrix;
+                uniform mat4 fyrox_unjitteredViewProjectionMatrix;
 
                 out vec3 position;
                 out vec3 normal;
@@ -129,13 +141,18 @@
                 out vec3 tangent;
                 out vec3 binormal;
                 out vec2 secondTexCoord;
+                out vec4 currentClipPosition;
+                out vec4 previousClipPosition;
 
                 void main()
                 {
                     // Each node has tex coords in [0; 1] range, here we must scale and offset it
                     // to match the actual position.
                     vec2 actualTexCoords = vec2(vertexTexCoord * nodeUvOffsets.zw + nodeUvOffsets.xy);
+                    // Skirt vertices have negative Y coordinate, they're moved down to hide seams between
+                    // patches with different level of detail.
                     float height = texture(heightMapTexture, actualTexCoords).r;
+                    height += vertexPosition.y * skirtDepth;
                     vec4 finalVertexPosition = vec4(vertexPosition.x, height, vertexPosition.z, 1.0);
 
                     mat3 nm = mat3(fyrox_worldMatrix);
@@ -146,6 +163,9 @@
                     position = vec3(fyrox_worldMatrix * finalVertexPosition);
                     secondTexCoord = vertexSecondTexCoord;
                     gl_Position = fyrox_worldViewProjection * finalVertexPosition;
+
+                    currentClipPosition = fyrox_unjitteredViewProjectionMatrix * fyrox_worldMatrix * finalVertexPosition;
+                    previousClipPosition = fyrox_prevViewProjectionMatrix * fyrox_prevWorldMatrix * finalVertexPosition;
                 }
                 "#,
             fragment_shader:
@@ -155,6 +175,10 @@
                 layout(location = 2) out vec4 outAmbient;
                 layout(location = 3) out vec4 outMaterial;
                 layout(location = 4) out uint outDecalMask;
+                layout(location = 5) out vec4 outVelocity;
+
+                in vec4 currentClipPosition;
+                in vec4 previousClipPosition;
 
                 // Properties.
                 uniform sampler2D diffuseTexture;
@@ -169,6 +193,7 @@
                 uniform uint layerIndex;
                 uniform vec3 emissionStrength;
                 uniform sampler2D maskTexture;
+                uniform sampler2D holeMaskTexture;
                 uniform vec4 diffuseColor;
                 uniform float parallaxCenter;
                 uniform float parallaxScale;
@@ -187,6 +212,8 @@
 
                 void main()
                 {
+                    if (texture(holeMaskTexture, texCoord).r < 0.5) discard;
+
                     mat3 tangentSpace = mat3(tangent, binormal, normal);
                     vec3 toFragment = normalize(position - fyrox_cameraPosition);
 
@@ -219,6 +246,8 @@
 
                     outDecalMask = layerIndex;
 
+                    outVelocity = vec4(S_Velocity(currentClipPosition, previousClipPosition), 0.0, 1.0);
+
                     float mask = texture(maskTexture, texCoord).r;
 
                     outColor.a = mask;
@@ -268,6 +297,7 @@
                 // Properties
                 uniform sampler2D heightMapTexture;
                 uniform vec4 nodeUvOffsets;
+                uniform float skirtDepth;
 
                 uniform mat4 fyrox_worldViewProjection;
 
@@ -277,7 +307,10 @@
                 void main()
                 {
                     vec2 actualTexCoords = vec2(vertexTexCoord * nodeUvOffsets.zw + nodeUvOffsets.xy);
+                    // Skirt vertices have negative Y coordinate, they're moved down to hide seams between
+                    // patches with different level of detail.
                     float height = texture(heightMapTexture, actualTexCoords).r;
+                    height += vertexPosition.y * skirtDepth;
                     vec4 finalVertexPosition = vec4(vertexPosition.x, height, vertexPosition.z, 1.0);
 
                     gl_Position = fyrox_worldViewProjection * finalVertexPosition;
@@ -289,6 +322,7 @@
                r#"
                 uniform sampler2D diffuseTexture;
                 uniform vec4 diffuseColor;
+                uniform sampler2D holeMaskTexture;
 
                 out vec4 FragColor;
 
@@ -296,6 +330,7 @@
 
                 void main()
                 {
+                    if (texture(holeMaskTexture, texCoord).r < 0.5) discard;
                     FragColor = diffuseColor * texture(diffuseTexture, texCoord);
                 }
                "#,
@@ -331,6 +366,7 @@
                 // Properties
                 uniform sampler2D heightMapTexture;
                 uniform vec4 nodeUvOffsets;
+                uniform float skirtDepth;
 
                 uniform mat4 fyrox_worldViewProjection;
 
@@ -339,7 +375,10 @@
                 void main()
                 {
                     vec2 actualTexCoords = vec2(vertexTexCoord * nodeUvOffsets.zw + nodeUvOffsets.xy);
+                    // Skirt vertices have negative Y coordinate, they're moved down to hide seams between
+                    // patches with different level of detail.
                     float height = texture(heightMapTexture, actualTexCoords).r;
+                    height += vertexPosition.y * skirtDepth;
                     vec4 finalVertexPosition = vec4(vertexPosition.x, height, vertexPosition.z, 1.0);
 
                     gl_Position = fyrox_worldViewProjection * finalVertexPosition;
@@ -350,11 +389,13 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform sampler2D holeMaskTexture;
 
                 in vec2 texCoord;
 
                 void main()
                 {
+                    if (texture(holeMaskTexture, texCoord).r < 0.5) discard;
                     if (texture(diffuseTexture, texCoord).a < 0.2) discard;
                 }
                 "#,
@@ -390,6 +431,7 @@
                 // Properties
                 uniform sampler2D heightMapTexture;
                 uniform vec4 nodeUvOffsets;
+                uniform float skirtDepth;
 
                 uniform mat4 fyrox_worldViewProjection;
 
@@ -398,7 +440,10 @@
                 void main()
                 {
                     vec2 actualTexCoords = vec2(vertexTexCoord * nodeUvOffsets.zw + nodeUvOffsets.xy);
+                    // Skirt vertices have negative Y coordinate, they're moved down to hide seams between
+                    // patches with different level of detail.
                     float height = texture(heightMapTexture, actualTexCoords).r;
+                    height += vertexPosition.y * skirtDepth;
                     vec4 finalVertexPosition = vec4(vertexPosition.x, height, vertexPosition.z, 1.0);
 
                     gl_Position = fyrox_worldViewProjection * finalVertexPosition;
@@ -409,11 +454,13 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform sampler2D holeMaskTexture;
 
                 in vec2 texCoord;
 
                 void main()
                 {
+                    if (texture(holeMaskTexture, texCoord).r < 0.5) discard;
                     if (texture(diffuseTexture, texCoord).a < 0.2) discard;
                 }
                 "#,
@@ -449,6 +496,7 @@
                 // Properties
                 uniform sampler2D heightMapTexture;
                 uniform vec4 nodeUvOffsets;
+                uniform float skirtDepth;
 
                 uniform mat4 fyrox_worldMatrix;
                 uniform mat4 fyrox_worldViewProjection;
@@ -459,7 +507,10 @@
                 void main()
                 {
                     vec2 actualTexCoords = vec2(vertexTexCoord * nodeUvOffsets.zw + nodeUvOffsets.xy);
+                    // Skirt vertices have negative Y coordinate, they're moved down to hide seams between
+                    // patches with different level of detail.
                     float height = texture(heightMapTexture, actualTexCoords).r;
+                    height += vertexPosition.y * skirtDepth;
                     vec4 finalVertexPosition = vec4(vertexPosition.x, height, vertexPosition.z, 1.0);
 
                     gl_Position = fyrox_worldViewProjection * finalVertexPosition;
@@ -471,6 +522,7 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform sampler2D holeMaskTexture;
 
                 uniform vec3 fyrox_lightPosition;
 
@@ -481,6 +533,7 @@
 
                 void main()
                 {
+                    if (texture(holeMaskTexture, texCoord).r < 0.5) discard;
                     if (texture(diffuseTexture, texCoord).a < 0.2) discard;
                     depth = length(fyrox_lightPosition - worldPosition);
                 }
@@ -0,0 +1,504 @@
+//! Dynamic plugins are plugins, that are loaded from dynamic libraries (DLL, SO, DyLib) and could be
+//! reloaded while the game is running. See [`DynamicPlugin`] docs for more info.
+
+use crate::{
+    asset::manager::ResourceManager,
+    core::{log::Log, pool::Handle, uuid::Uuid, visitor::prelude::*, watcher::FileSystemWatcher},
+    engine::SerializationContext,
+    plugin::{PluginConstructor, PluginRegistrationContext},
+    scene::{graph::Graph, node::Node},
+    script::Script,
+};
+use fxhash::FxHashSet;
+use std::{
+    fmt::{Display, Formatter},
+    mem::ManuallyDrop,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Name of the function, that must be exported by a dynamic library to be loaded as a plugin. The
+/// function must have `fn() -> Box<dyn PluginConstructor>` signature. See [`DynamicPlugin`] docs for
+/// an example.
+pub const PLUGIN_ENTRY_POINT: &str = "fyrox_plugin_constructor";
+
+/// The amount of time, that must pass since the last modification of a library, before it will be
+/// reloaded. Compilers write libraries in multiple steps, so reloading a library on the first change
+/// will most likely fail.
+const RELOAD_DELAY: Duration = Duration::from_millis(1000);
+
+/// An error, that may occur during loading or reloading of a dynamic plugin.
+#[derive(Debug)]
+pub enum DynamicPluginError {
+    /// An i/o error has occurred.
+    Io(std::io::Error),
+    /// Unable to load the library or to find the entry point in it.
+    Library(libloading::Error),
+    /// Unable to save or restore the state of the plugin or its scripts.
+    Visit(VisitError),
+    /// The plugin has registered custom scene nodes, that are currently used by some scene. Such
+    /// nodes can not be reloaded.
+    NodeInUse {
+        /// Name of a node, that prevents the reloading.
+        name: String,
+    },
+}
+
+impl Display for DynamicPluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DynamicPluginError::Io(v) => {
+                write!(f, "A file i/o error has occurred {v:?}")
+            }
+            DynamicPluginError::Library(v) => {
+                write!(f, "Unable to load the library. Reason: {v}")
+            }
+            DynamicPluginError::Visit(v) => {
+                write!(
+                    f,
+                    "Unable to transfer the state of the plugin. Reason: {v:?}"
+                )
+            }
+            DynamicPluginError::NodeInUse { name } => {
+                write!(
+                    f,
+                    "Node {name} has a type, that is defined in the plugin. Custom nodes \
+                    can not be hot-reloaded, remove them from the scene first."
+                )
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for DynamicPluginError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<libloading::Error> for DynamicPluginError {
+    fn from(e: libloading::Error) -> Self {
+        Self::Library(e)
+    }
+}
+
+impl From<VisitError> for DynamicPluginError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A loaded copy of a dynamic library together with the plugin constructor from it.
+pub(crate) struct LoadedLibrary {
+    constructor: Box<dyn PluginConstructor>,
+    path: PathBuf,
+    // The library is unloaded only on reloading, when every instance of its types is destroyed.
+    // On destruction of the engine some instances (in resources for example) may outlive the
+    // library, so it is never unloaded in this case.
+    library: ManuallyDrop<libloading::Library>,
+}
+
+/// Dynamic plugin is a plugin, that is loaded from a dynamic library and could be reloaded while
+/// the game is running. It is primarily used to speed up iterations: you recompile the library with
+/// your game logic and the engine replaces the old code with the new one, keeping the state of your
+/// game.
+///
+/// # Reloading
+///
+/// When the library changes (or when [`crate::engine::Engine::reload_dynamic_plugins`] is called),
+/// the engine does the following:
+///
+/// 1) Saves the state of every script, that is defined in the plugin, using [`Visit`] trait. This
+/// includes scripts of all scenes and all loaded model resources (prefabs). The scripts are then
+/// removed from their nodes without calling `on_deinit`.
+/// 2) Saves the state of the plugin instance using [`crate::plugin::Plugin::visit_hot_reload_state`]
+/// and destroys the instance without calling `on_deinit`.
+/// 3) Unregisters all script and node constructors of the plugin and unloads the old library.
+/// 4) Registers the plugin from the new library, creates a new plugin instance using
+/// [`PluginConstructor::create_reloaded_instance`] and restores its state.
+/// 5) Creates new script instances and restores their state. Initialization flags are preserved, so
+/// `on_init` and `on_start` won't be called again.
+///
+/// # Limitations
+///
+/// Rust does not have a stable ABI, so both the host executable and the library must be compiled
+/// by the same compiler with the same engine version and both must link the engine dynamically,
+/// otherwise there will be two copies of the engine with distinct global state. Anything else, that
+/// references the code of the library (custom scene nodes, custom resources, pending tasks, message
+/// subscriptions with message types, that are defined in the library) is not transferred. The
+/// engine refuses to reload a plugin, if any scene contains a node of a type from the plugin.
+///
+/// # Example
+///
+/// A library with a plugin must be compiled as `dylib` and export the entry point:
+///
+/// ```rust
+/// # use fyrox::plugin::{Plugin, PluginConstructor, PluginContext};
+/// # #[derive(Default)]
+/// # struct Game;
+/// # impl Plugin for Game {}
+/// # struct GameConstructor;
+/// # impl PluginConstructor for GameConstructor {
+/// #     fn create_instance(&self, _scene_path: Option<&str>, _context: PluginContext) -> Box<dyn Plugin> {
+/// #         Box::new(Game)
+/// #     }
+/// # }
+/// #[no_mangle]
+/// pub fn fyrox_plugin_constructor() -> Box<dyn PluginConstructor> {
+///     Box::new(GameConstructor)
+/// }
+/// ```
+///
+/// The executor then loads it using [`crate::engine::Engine::add_dynamic_plugin`].
+pub struct DynamicPlugin {
+    source_path: PathBuf,
+    library: LoadedLibrary,
+    generation: usize,
+    watcher: Option<FileSystemWatcher>,
+    last_change: Option<Instant>,
+    pub(crate) script_types: Vec<Uuid>,
+    pub(crate) node_types: Vec<Uuid>,
+}
+
+impl DynamicPlugin {
+    /// Loads a plugin from the library at the given path. If `reload_when_changed` is set, the
+    /// library file will be watched for changes and the plugin will be reloaded automatically.
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<Self, DynamicPluginError> {
+        let source_path = path.as_ref().to_path_buf();
+
+        let watcher = if reload_when_changed {
+            // Watch the parent directory instead of the file itself, because compilers usually
+            // replace the file and watchers stop to track replaced files.
+            let directory = source_path
+                .parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."));
+            match FileSystemWatcher::new(directory, RELOAD_DELAY) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    Log::err(format!(
+                        "Unable to watch {} for changes. Hot reloading is disabled. Reason: {:?}",
+                        source_path.display(),
+                        e
+                    ));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            library: load_library(&source_path, 0)?,
+            source_path,
+            generation: 0,
+            watcher,
+            last_change: None,
+            script_types: Default::default(),
+            node_types: Default::default(),
+        })
+    }
+
+    /// Returns a path to the library file, that is used as a source for the plugin.
+    pub fn source_path(&self) -> &Path {
+        &self.source_path
+    }
+
+    /// Returns a reference to the plugin constructor from the currently loaded library.
+    pub fn constructor(&self) -> &dyn PluginConstructor {
+        &*self.library.constructor
+    }
+
+    /// Checks whether the library was changed and enough time has passed for the compiler to
+    /// finish writing it.
+    pub(crate) fn is_reload_needed(&mut self) -> bool {
+        if let Some(watcher) = self.watcher.as_ref() {
+            while let Some(event) = watcher.try_get_event() {
+                if event.kind.is_modify() || event.kind.is_create() {
+                    let file_name = self.source_path.file_name();
+                    if event.paths.iter().any(|p| p.file_name() == file_name) {
+                        self.last_change = Some(Instant::now());
+                    }
+                }
+            }
+        }
+
+        if self
+            .last_change
+            .map_or(false, |last_change| last_change.elapsed() >= RELOAD_DELAY)
+        {
+            self.last_change = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Loads a new copy of the library. The old one stays loaded until [`Self::replace_library`]
+    /// is called, so a failed compilation does not break the running game.
+    pub(crate) fn load_next(&mut self) -> Result<LoadedLibrary, DynamicPluginError> {
+        self.generation += 1;
+        load_library(&self.source_path, self.generation)
+    }
+
+    /// Unregisters everything the plugin has registered and replaces the loaded library with the
+    /// new one. All instances of the types from the old library must be destroyed before calling
+    /// this method.
+    pub(crate) fn replace_library(
+        &mut self,
+        library: LoadedLibrary,
+        serialization_context: &Arc<SerializationContext>,
+    ) {
+        for type_uuid in self.script_types.drain(..) {
+            serialization_context.script_constructors.remove(type_uuid);
+        }
+        for type_uuid in self.node_types.drain(..) {
+            serialization_context.node_constructors.remove(type_uuid);
+        }
+
+        let LoadedLibrary {
+            constructor,
+            path,
+            library,
+        } = std::mem::replace(&mut self.library, library);
+
+        // The constructor must be destroyed before the library is unloaded, its code lives in it.
+        drop(constructor);
+        drop(ManuallyDrop::into_inner(library));
+        Log::verify(std::fs::remove_file(path));
+    }
+
+    /// Registers the plugin and remembers every script and node type, that it has registered.
+    pub(crate) fn register(
+        &mut self,
+        serialization_context: &Arc<SerializationContext>,
+        resource_manager: &ResourceManager,
+    ) {
+        let scripts_before = serialization_context
+            .script_constructors
+            .map()
+            .keys()
+            .cloned()
+            .collect::<FxHashSet<_>>();
+        let nodes_before = serialization_context
+            .node_constructors
+            .map()
+            .keys()
+            .cloned()
+            .collect::<FxHashSet<_>>();
+
+        self.library
+            .constructor
+            .register(PluginRegistrationContext {
+                serialization_context,
+                resource_manager,
+            });
+
+        self.script_types = serialization_context
+            .script_constructors
+            .map()
+            .keys()
+            .filter(|type_uuid| !scripts_before.contains(type_uuid))
+            .cloned()
+            .collect();
+        self.node_types = serialization_context
+            .node_constructors
+            .map()
+            .keys()
+            .filter(|type_uuid| !nodes_before.contains(type_uuid))
+            .cloned()
+            .collect();
+    }
+}
+
+fn load_library(
+    source_path: &Path,
+    generation: usize,
+) -> Result<LoadedLibrary, DynamicPluginError> {
+    // Load a copy of the library, so the compiler will be able to overwrite the original file. It
+    // also forces the OS to load a new library instead of returning the old one from its cache.
+    let mut file_name = source_path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(format!("_hot_reload_{generation}"));
+    let mut path = source_path.with_file_name(file_name);
+    if let Some(extension) = source_path.extension() {
+        path.set_extension(extension);
+    }
+    std::fs::copy(source_path, &path)?;
+
+    Log::info(format!(
+        "Loading dynamic plugin library {}",
+        source_path.display()
+    ));
+
+    // SAFETY: The library must be compiled by the same compiler with the same engine version. There
+    // is no way to check it, see the docs of `DynamicPlugin` for more info.
+    unsafe {
+        let library = libloading::Library::new(&path)?;
+        let entry_point =
+            library.get::<fn() -> Box<dyn PluginConstructor>>(PLUGIN_ENTRY_POINT.as_bytes())?;
+        let constructor = entry_point();
+        Ok(LoadedLibrary {
+            constructor,
+            path,
+            library: ManuallyDrop::new(library),
+        })
+    }
+}
+
+/// State of a script, that was taken from a node during reloading of a dynamic plugin.
+pub(crate) struct SavedScript {
+    node: Handle<Node>,
+    type_uuid: Uuid,
+    started: bool,
+    data: Vec<u8>,
+}
+
+/// Checks whether the graph contains nodes of the given types.
+pub(crate) fn check_nodes(graph: &Graph, node_types: &[Uuid]) -> Result<(), DynamicPluginError> {
+    match graph
+        .linear_iter()
+        .find(|node| node_types.contains(&node.id()))
+    {
+        Some(node) => Err(DynamicPluginError::NodeInUse {
+            name: node.name_owned(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Serializes and destroys every script of the given types in the graph.
+pub(crate) fn take_scripts(
+    graph: &mut Graph,
+    script_types: &[Uuid],
+) -> Result<Vec<SavedScript>, DynamicPluginError> {
+    let mut saved = Vec::new();
+    for (handle, node) in graph.pair_iter_mut() {
+        if !node
+            .script
+            .as_ref()
+            .map_or(false, |script| script_types.contains(&script.id()))
+        {
+            continue;
+        }
+
+        if let Some(mut script) = node.script.take() {
+            let mut visitor = Visitor::new();
+            script.visit("Script", &mut visitor)?;
+            saved.push(SavedScript {
+                node: handle,
+                type_uuid: script.id(),
+                started: script.started,
+                data: visitor.save_binary_to_vec()?,
+            });
+        }
+    }
+    Ok(saved)
+}
+
+/// Creates new script instances using the (new) script constructors and restores their state.
+pub(crate) fn restore_scripts(
+    graph: &mut Graph,
+    saved: Vec<SavedScript>,
+    serialization_context: &Arc<SerializationContext>,
+    resource_manager: &ResourceManager,
+) {
+    for saved_script in saved {
+        let Some(node) = graph.try_get_mut(saved_script.node) else {
+            continue;
+        };
+
+        match restore_script(&saved_script, serialization_context, resource_manager) {
+            Ok(script) => node.script = Some(script),
+            Err(e) => Log::err(format!(
+                "Unable to restore script {} of node {}. Reason: {}",
+                saved_script.type_uuid,
+                node.name(),
+                e
+            )),
+        }
+    }
+}
+
+fn restore_script(
+    saved_script: &SavedScript,
+    serialization_context: &Arc<SerializationContext>,
+    resource_manager: &ResourceManager,
+) -> Result<Script, DynamicPluginError> {
+    let mut script = serialization_context
+        .script_constructors
+        .try_create(&saved_script.type_uuid)
+        .ok_or_else(|| {
+            VisitError::User(format!(
+                "There is no script constructor for type {}. The script was removed from the plugin?",
+                saved_script.type_uuid
+            ))
+        })?;
+
+    let mut visitor = Visitor::load_from_memory(&saved_script.data)?;
+    visitor.blackboard.register(serialization_context.clone());
+    visitor
+        .blackboard
+        .register(Arc::new(resource_manager.clone()));
+    script.visit("Script", &mut visitor)?;
+    script.started = saved_script.started;
+
+    Ok(script)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{
+            impl_component_provider, reflect::prelude::*, uuid_provider, visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        engine::SerializationContext,
+        plugin::dynamic::{restore_scripts, take_scripts},
+        scene::{base::BaseBuilder, graph::Graph, pivot::PivotBuilder},
+        script::{Script, ScriptTrait},
+    };
+    use std::sync::Arc;
+
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct MyScript {
+        counter: u32,
+    }
+
+    impl_component_provider!(MyScript);
+    uuid_provider!(MyScript = "8d2a5c1e-7b4f-4e9a-b3d6-1f8c9e2a4b70");
+
+    impl ScriptTrait for MyScript {}
+
+    #[test]
+    fn test_scripts_transfer() {
+        let serialization_context = Arc::new(SerializationContext::new());
+        serialization_context
+            .script_constructors
+            .add::<MyScript>("MyScript");
+        let resource_manager = ResourceManager::new(Arc::new(Default::default()));
+
+        let mut graph = Graph::new();
+        let with_script = PivotBuilder::new(
+            BaseBuilder::new().with_script(Script::new(MyScript { counter: 42 })),
+        )
+        .build(&mut graph);
+        let without_script = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        graph[with_script].script.as_mut().unwrap().started = true;
+
+        let saved = take_scripts(&mut graph, &[MyScript::type_uuid()]).unwrap();
+        assert_eq!(saved.len(), 1);
+        assert!(graph[with_script].script.is_none());
+
+        restore_scripts(&mut graph, saved, &serialization_context, &resource_manager);
+
+        let script = graph[with_script].script.as_ref().unwrap();
+        assert!(script.started);
+        assert_eq!(script.cast::<MyScript>().unwrap().counter, 42);
+        assert!(graph[without_script].script.is_none());
+    }
+}
@@ -2,6 +2,9 @@
 
 #![warn(missing_docs)]
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dynamic;
+
 use crate::engine::task::TaskPoolHandler;
 use crate::{
     asset::manager::ResourceManager,
@@ -14,7 +17,7 @@ use crate::{
     gui::{message::UiMessage, UserInterface},
     scene::{Scene, SceneContainer},
 };
-use fyrox_core::visitor::VisitError;
+use fyrox_core::visitor::{VisitError, VisitResult, Visitor};
 use std::{any::Any, path::Path, sync::Arc};
 use winit::event_loop::EventLoopWindowTarget;
 
@@ -40,6 +43,35 @@ pub trait PluginConstructor {
     /// by the editor, to run your game with a scene you have current opened in the editor. Typical
     /// usage would be: `scene_path.unwrap_or("a/path/to/my/default/scene.rgs")`
     fn create_instance(&self, scene_path: Option<&str>, context: PluginContext) -> Box<dyn Plugin>;
+
+    /// The method is called when a dynamic plugin was reloaded (see [`dynamic::DynamicPlugin`] docs)
+    /// and the engine needs a new plugin instance to restore the state of the old one into. Default
+    /// implementation calls [`Self::create_instance`] without a scene path; override the method if
+    /// plugin creation does something, that must not be repeated (loads a scene for example).
+    fn create_reloaded_instance(&self, context: PluginContext) -> Box<dyn Plugin> {
+        self.create_instance(None, context)
+    }
+}
+
+/// A container for plugin constructors, that could be either linked statically or loaded from a
+/// dynamic library.
+pub enum PluginConstructorContainer {
+    /// Statically linked plugin constructor.
+    Static(Box<dyn PluginConstructor>),
+    /// Plugin constructor, that is loaded from a dynamic library and could be reloaded.
+    #[cfg(not(target_arch = "wasm32"))]
+    Dynamic(dynamic::DynamicPlugin),
+}
+
+impl PluginConstructorContainer {
+    /// Returns a reference to the plugin constructor.
+    pub fn constructor(&self) -> &dyn PluginConstructor {
+        match self {
+            PluginConstructorContainer::Static(constructor) => &**constructor,
+            #[cfg(not(target_arch = "wasm32"))]
+            PluginConstructorContainer::Dynamic(plugin) => plugin.constructor(),
+        }
+    }
 }
 
 /// Contains plugin environment for the registration stage.
@@ -158,6 +190,10 @@ impl dyn Plugin {
 /// `#[repr(C)]` attribute which is not always easy and even possible (because some structures could
 /// be re-exported from dependencies). These are the main reasons why the engine uses static plugins.
 ///
+/// However, recompiling the entire game to tweak a few lines of code is slow, so plugins could also
+/// be loaded from dynamic libraries for development purposes. Such plugins could be reloaded while
+/// the game is running, see [`dynamic::DynamicPlugin`] docs for more info and its limitations.
+///
 /// # Example
 ///
 /// ```rust
@@ -263,4 +299,16 @@ pub trait Plugin: BasePlugin {
         #[allow(unused_variables)] context: &mut PluginContext,
     ) {
     }
+
+    /// The method is used to transfer the state of a plugin from the old instance to the new one,
+    /// when a dynamic plugin is reloaded. It is called once on the old instance with a visitor in
+    /// write mode and once on the new instance with a visitor in read mode. Default implementation
+    /// does nothing, which means that the state of the plugin is lost on reloading. Handles of
+    /// scenes and nodes stay valid, so they can be visited as is.
+    fn visit_hot_reload_state(
+        &mut self,
+        #[allow(unused_variables)] visitor: &mut Visitor,
+    ) -> VisitResult {
+        Ok(())
+    }
 }
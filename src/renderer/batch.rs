@@ -3,6 +3,7 @@
 use crate::{
     core::{
         algebra::{Matrix4, Vector3},
+        color::Color,
         math::{frustum::Frustum, TriangleDefinition},
         pool::Handle,
         sstorage::ImmutableString,
@@ -88,6 +89,16 @@ impl PersistentIdentifier {
         hasher.write_usize(index);
         Self(hasher.finish())
     }
+
+    /// Creates a new persistent identifier, that is derived from the current one. It is used to
+    /// store data of the previous frame (for example - bone matrices) separately from the data of
+    /// the current frame.
+    pub fn previous_frame(self) -> Self {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.write_u8(1);
+        Self(hasher.finish())
+    }
 }
 
 /// A set of data of a surface for rendering.  
@@ -108,6 +119,10 @@ pub struct SurfaceInstanceData {
     pub persistent_identifier: PersistentIdentifier,
     /// A handle of a node that emitted this surface data. Could be none, if there's no info about scene node.
     pub node_handle: Handle<Node>,
+    /// A color of the instance. It is passed to shaders using `fyrox_instanceColor` built-in uniform (or
+    /// as a part of per-instance data, when instancing is used). Standard shader multiplies diffuse color
+    /// with this color.
+    pub color: Color,
 }
 
 /// A set of surface instances that share the same vertex/index data and a material.
@@ -130,6 +145,48 @@ pub struct RenderDataBatch {
     sort_index: u64,
 }
 
+impl RenderDataBatch {
+    /// Checks whether all the instances of the batch could be rendered using a single instanced draw
+    /// call. It is possible only if there's more than one instance and every instance uses the full
+    /// range of elements, no skinning, no blend shapes and no depth offset.
+    pub fn is_instanceable(&self) -> bool {
+        !self.is_skinned
+            && self.instances.len() > 1
+            && self.instances.iter().all(|instance| {
+                instance.element_range == ElementRange::Full
+                    && instance.depth_offset == 0.0
+                    && instance.blend_shapes_weights.is_empty()
+            })
+    }
+
+    /// Packs per-instance data of the batch into a list of matrices. Every instance takes two
+    /// matrices: the first one is the world transform, the second one contains the color of the
+    /// instance (linear RGBA) in its first column. Use `S_FetchInstanceWorldMatrix` and
+    /// `S_FetchInstanceColor` built-in shader functions to fetch the data.
+    pub fn instance_matrices(&self) -> Vec<Matrix4<f32>> {
+        let mut matrices = Vec::with_capacity(self.instances.len() * 2);
+        for instance in self.instances.iter() {
+            matrices.push(instance.world_transform);
+            let mut color = Matrix4::zeros();
+            color.set_column(0, &instance.color.srgb_to_linear_f32());
+            matrices.push(color);
+        }
+        matrices
+    }
+
+    /// Calculates a persistent identifier for the whole set of instances of the batch. It is used to
+    /// upload per-instance data to GPU only once per frame for every unique set of instances.
+    pub fn instancing_identifier(&self) -> PersistentIdentifier {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(self.data.key());
+        hasher.write_u64(self.material.key() as u64);
+        for instance in self.instances.iter() {
+            instance.persistent_identifier.hash(&mut hasher);
+        }
+        PersistentIdentifier(hasher.finish())
+    }
+}
+
 impl Debug for RenderDataBatch {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -158,6 +215,18 @@ impl RenderDataBatchStorage {
         graph: &Graph,
         observer_info: ObserverInfo,
         render_pass_name: ImmutableString,
+    ) -> Self {
+        Self::from_graph_with_filter(graph, observer_info, render_pass_name, None)
+    }
+
+    /// The same as [`Self::from_graph`], but also skips the nodes for which the given filter has
+    /// `false` at their index. It is used for occlusion culling, see
+    /// [`crate::renderer::occlusion::OcclusionBuffer::cull`].
+    pub fn from_graph_with_filter(
+        graph: &Graph,
+        observer_info: ObserverInfo,
+        render_pass_name: ImmutableString,
+        filter: Option<&[bool]>,
     ) -> Self {
         // Aim for the worst-case scenario when every node has unique render data.
         let capacity = graph.node_count() as usize;
@@ -204,7 +273,8 @@ impl RenderDataBatchStorage {
         };
 
         for (handle, node) in graph.pair_iter() {
-            if lod_filter[handle.index() as usize] {
+            let index = handle.index() as usize;
+            if lod_filter[index] && filter.map_or(true, |filter| filter[index]) {
                 node.collect_render_data(&mut ctx);
             }
         }
@@ -288,6 +358,7 @@ impl RenderDataBatchStorage {
                         element_range: Default::default(),
                         persistent_identifier,
                         node_handle,
+                        color: Color::WHITE,
                     },
                 ],
                 material: material.clone(),
@@ -362,3 +433,80 @@ impl RenderDataBatchStorage {
         self.batches.sort_unstable_by_key(|b| b.sort_index);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Matrix4, Vector3},
+            color::Color,
+            pool::Handle,
+        },
+        material::{Material, MaterialResource},
+        renderer::{
+            batch::{PersistentIdentifier, RenderDataBatchStorage, SurfaceInstanceData},
+            framework::geometry_buffer::ElementRange,
+        },
+        scene::mesh::{
+            surface::{SurfaceData, SurfaceSharedData},
+            RenderPath,
+        },
+    };
+
+    fn instance(position: Vector3<f32>, color: Color) -> SurfaceInstanceData {
+        SurfaceInstanceData {
+            world_transform: Matrix4::new_translation(&position),
+            bone_matrices: Default::default(),
+            depth_offset: 0.0,
+            blend_shapes_weights: Default::default(),
+            element_range: ElementRange::Full,
+            persistent_identifier: PersistentIdentifier(position.x as u64),
+            node_handle: Handle::NONE,
+            color,
+        }
+    }
+
+    #[test]
+    fn test_instancing() {
+        let data = SurfaceSharedData::new(SurfaceData::make_cube(Matrix4::identity()));
+        let material = MaterialResource::new_ok(Default::default(), Material::standard());
+
+        let mut storage = RenderDataBatchStorage::default();
+        for (i, color) in [Color::WHITE, Color::RED].into_iter().enumerate() {
+            storage.push(
+                &data,
+                &material,
+                RenderPath::Deferred,
+                0,
+                0,
+                instance(Vector3::new(i as f32, 0.0, 0.0), color),
+            );
+        }
+
+        // Different colors must not split the batch.
+        assert_eq!(storage.batches.len(), 1);
+        let batch = &storage.batches[0];
+        assert!(batch.is_instanceable());
+
+        let matrices = batch.instance_matrices();
+        assert_eq!(matrices.len(), 4);
+        assert_eq!(
+            matrices[2],
+            Matrix4::new_translation(&Vector3::new(1.0, 0.0, 0.0))
+        );
+        assert_eq!(matrices[3][(0, 0)], 1.0);
+        assert_eq!(matrices[3][(1, 0)], 0.0);
+
+        // Partial element ranges cannot be instanced.
+        let mut storage = RenderDataBatchStorage::default();
+        for i in 0..2 {
+            let mut instance = instance(Vector3::new(i as f32, 0.0, 0.0), Color::WHITE);
+            instance.element_range = ElementRange::Specific {
+                offset: 0,
+                count: 1,
+            };
+            storage.push(&data, &material, RenderPath::Deferred, 0, 0, instance);
+        }
+        assert!(!storage.batches[0].is_instanceable());
+    }
+}
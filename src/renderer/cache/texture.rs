@@ -30,10 +30,17 @@ fn create_gpu_texture(
     state: &PipelineState,
     texture: &Texture,
 ) -> Result<TextureRenderData, FrameworkError> {
+    let pixel_kind = match PixelKind::from(texture.pixel_kind()) {
+        PixelKind::RGB8 if texture.is_srgb() => PixelKind::SRGB8,
+        PixelKind::RGBA8 if texture.is_srgb() => PixelKind::SRGBA8,
+        PixelKind::BC7RGBA if texture.is_srgb() => PixelKind::BC7SRGBA,
+        PixelKind::ASTC4x4RGBA if texture.is_srgb() => PixelKind::ASTC4x4SRGBA,
+        pixel_kind => pixel_kind,
+    };
     GpuTexture::new(
         state,
         texture.kind().into(),
-        PixelKind::from(texture.pixel_kind()),
+        pixel_kind,
         texture.minification_filter().into(),
         texture.magnification_filter().into(),
         texture.mip_count() as usize,
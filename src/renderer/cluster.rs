@@ -0,0 +1,693 @@
+//! Clustered light culling. See [`LightClusterGrid`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector3, Vector4},
+        array_as_u8_slice,
+        math::{frustum::Frustum, Matrix4Ext, Rect},
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{BlendParameters, DrawParameters, FrameBuffer},
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter, PixelKind,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        RenderPassStatistics,
+    },
+    scene::{
+        camera::{Camera, Projection},
+        graph::Graph,
+        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        mesh::surface::SurfaceData,
+        node::Node,
+    },
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Amount of clusters along horizontal axis of the screen.
+pub const CLUSTER_GRID_WIDTH: usize = 16;
+/// Amount of clusters along vertical axis of the screen.
+pub const CLUSTER_GRID_HEIGHT: usize = 9;
+/// Amount of depth slices of the grid.
+pub const CLUSTER_GRID_DEPTH: usize = 24;
+/// Total amount of clusters in the grid.
+pub const CLUSTER_COUNT: usize = CLUSTER_GRID_WIDTH * CLUSTER_GRID_HEIGHT * CLUSTER_GRID_DEPTH;
+/// Maximum amount of lights that can be visible at once. Lights beyond this limit are ignored.
+pub const MAX_CLUSTERED_LIGHTS: usize = 4096;
+/// Maximum amount of lights that can affect a single cluster. Lights beyond this limit are ignored.
+pub const MAX_LIGHTS_PER_CLUSTER: usize = 256;
+
+// Maximum width of the storage textures.
+const STORAGE_TEXTURE_WIDTH: usize = 1024;
+
+/// A light, prepared for clustered lighting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusteredLight {
+    /// World-space position of the light.
+    pub position: Vector3<f32>,
+    /// World-space direction of the light (used by spot lights).
+    pub direction: Vector3<f32>,
+    /// Linear color of the light.
+    pub color: Vector3<f32>,
+    /// Radius of the light. Infinite radius means that the light affects every cluster (directional
+    /// lights).
+    pub radius: f32,
+    /// Cosine of the half of the hotspot cone angle.
+    pub half_hotspot_cone_angle_cos: f32,
+    /// Cosine of the half of the full cone angle.
+    pub half_cone_angle_cos: f32,
+}
+
+impl ClusteredLight {
+    /// Tries to create clustered light from the given scene node. Returns `None` if the node is not
+    /// a light.
+    pub fn from_node(node: &Node) -> Option<Self> {
+        let (radius, half_hotspot_cone_angle_cos, half_cone_angle_cos, color) =
+            if let Some(point) = node.cast::<PointLight>() {
+                (
+                    point.radius(),
+                    std::f32::consts::PI.cos(),
+                    std::f32::consts::PI.cos(),
+                    point.base_light_ref().color().as_frgb(),
+                )
+            } else if let Some(spot) = node.cast::<SpotLight>() {
+                (
+                    spot.distance(),
+                    (spot.hotspot_cone_angle() * 0.5).cos(),
+                    (spot.full_cone_angle() * 0.5).cos(),
+                    spot.base_light_ref().color().as_frgb(),
+                )
+            } else if let Some(directional) = node.cast::<DirectionalLight>() {
+                (
+                    f32::INFINITY,
+                    std::f32::consts::PI.cos(),
+                    std::f32::consts::PI.cos(),
+                    directional.base_light_ref().color().as_frgb(),
+                )
+            } else {
+                return None;
+            };
+
+        Some(Self {
+            position: node.global_position(),
+            direction: node.up_vector(),
+            color,
+            radius,
+            half_hotspot_cone_angle_cos,
+            half_cone_angle_cos,
+        })
+    }
+}
+
+/// A set of camera parameters, that defines the grid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterView {
+    /// View-projection matrix of the camera.
+    pub view_projection: Matrix4<f32>,
+    /// World-space position of the camera.
+    pub position: Vector3<f32>,
+    /// Normalized world-space side vector of the camera.
+    pub side: Vector3<f32>,
+    /// Normalized world-space up vector of the camera.
+    pub up: Vector3<f32>,
+    /// Normalized world-space look vector of the camera.
+    pub look: Vector3<f32>,
+    /// Distance to the near clipping plane.
+    pub z_near: f32,
+    /// Distance to the far clipping plane.
+    pub z_far: f32,
+    /// Whether to use exponential depth slicing (perspective cameras) or linear (orthographic
+    /// cameras).
+    pub exponential: bool,
+}
+
+impl Default for ClusterView {
+    fn default() -> Self {
+        Self {
+            view_projection: Matrix4::identity(),
+            position: Default::default(),
+            side: Vector3::x(),
+            up: Vector3::y(),
+            look: Vector3::z(),
+            z_near: 0.025,
+            z_far: 2048.0,
+            exponential: true,
+        }
+    }
+}
+
+impl ClusterView {
+    /// Creates cluster view from the given camera.
+    pub fn from_camera(camera: &Camera) -> Self {
+        let inv_view = camera.inv_view_matrix().unwrap_or_default();
+        let projection = camera.projection();
+        Self {
+            view_projection: camera.view_projection_matrix(),
+            position: camera.global_position(),
+            side: inv_view
+                .side()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default(),
+            up: inv_view
+                .up()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default(),
+            look: camera
+                .look_vector()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default(),
+            z_near: projection.z_near(),
+            z_far: projection.z_far(),
+            exponential: matches!(projection, Projection::Perspective(_))
+                && projection.z_near() > 0.0,
+        }
+    }
+
+    /// Calculates depth slice (not clamped) for the given view-space depth.
+    pub fn depth_slice(&self, depth: f32) -> f32 {
+        let slice = if self.exponential {
+            (depth.max(f32::EPSILON) / self.z_near).ln() / (self.z_far / self.z_near).ln()
+        } else {
+            (depth - self.z_near) / (self.z_far - self.z_near)
+        };
+        slice * CLUSTER_GRID_DEPTH as f32
+    }
+
+    /// Calculates view-space depth of the given world-space position.
+    pub fn depth(&self, world_position: &Vector3<f32>) -> f32 {
+        (world_position - self.position).dot(&self.look)
+    }
+}
+
+/// Clustered light culling splits the view frustum of a camera into a grid of clusters (froxels):
+/// [`CLUSTER_GRID_WIDTH`] x [`CLUSTER_GRID_HEIGHT`] screen-space tiles and [`CLUSTER_GRID_DEPTH`]
+/// depth slices. Depth slices are exponential for perspective cameras and linear for orthographic
+/// ones. Every light is assigned to every cluster its bounding sphere intersects, so a shader has to
+/// process only the lights of a cluster that contains a fragment. This allows to have hundreds of
+/// lights in the view at once.
+///
+/// The grid is rebuilt for every camera on CPU and then uploaded to GPU as a set of textures (see
+/// `fyrox_cluster*` built-in properties in [`crate::material::shader`] docs).
+#[derive(Default, Debug, Clone)]
+pub struct LightClusterGrid {
+    view: ClusterView,
+    lights: Vec<ClusteredLight>,
+    // Offset and count of light indices of every cluster.
+    clusters: Vec<(u32, u32)>,
+    light_indices: Vec<u32>,
+}
+
+impl LightClusterGrid {
+    /// Rebuilds the grid using visible lights of the given graph.
+    pub fn update(&mut self, graph: &Graph, camera: &Camera) {
+        let frustum = Frustum::from_view_projection_matrix(camera.view_projection_matrix())
+            .unwrap_or_default();
+
+        let lights = graph
+            .linear_iter()
+            .filter(|node| {
+                node.global_visibility()
+                    && node.is_globally_enabled()
+                    && frustum.is_intersects_aabb(&node.world_bounding_box())
+            })
+            .filter_map(ClusteredLight::from_node)
+            .take(MAX_CLUSTERED_LIGHTS)
+            .collect();
+
+        self.assign(ClusterView::from_camera(camera), lights);
+    }
+
+    /// Assigns the given lights to the clusters, defined by the given view.
+    pub fn assign(&mut self, view: ClusterView, lights: Vec<ClusteredLight>) {
+        let mut clusters = vec![Vec::<u32>::new(); CLUSTER_COUNT];
+
+        for (light_index, light) in lights.iter().enumerate() {
+            let Some((min, max)) = cluster_bounds(&view, light) else {
+                continue;
+            };
+
+            for z in min.z..=max.z {
+                for y in min.y..=max.y {
+                    for x in min.x..=max.x {
+                        let cluster = &mut clusters[cluster_index(x, y, z)];
+                        if cluster.len() < MAX_LIGHTS_PER_CLUSTER {
+                            cluster.push(light_index as u32);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.clusters.clear();
+        self.light_indices.clear();
+        for cluster in clusters {
+            self.clusters
+                .push((self.light_indices.len() as u32, cluster.len() as u32));
+            self.light_indices.extend(cluster);
+        }
+
+        self.view = view;
+        self.lights = lights;
+    }
+
+    /// Returns a view, that was used to build the grid.
+    pub fn view(&self) -> &ClusterView {
+        &self.view
+    }
+
+    /// Returns a list of lights that were assigned to the clusters.
+    pub fn lights(&self) -> &[ClusteredLight] {
+        &self.lights
+    }
+
+    /// Returns an index of a cluster, that contains the given world-space position.
+    pub fn cluster_at(&self, world_position: &Vector3<f32>) -> Option<usize> {
+        let clip = self.view.view_projection * world_position.push(1.0);
+        if clip.w <= f32::EPSILON {
+            return None;
+        }
+        let ndc = clip.xy().scale(1.0 / clip.w);
+        let slice = self.view.depth_slice(self.view.depth(world_position));
+        if ndc.x < -1.0 || ndc.x > 1.0 || ndc.y < -1.0 || ndc.y > 1.0 || slice < 0.0 {
+            return None;
+        }
+        Some(cluster_index(
+            ndc_to_tile(ndc.x, CLUSTER_GRID_WIDTH),
+            ndc_to_tile(ndc.y, CLUSTER_GRID_HEIGHT),
+            (slice as usize).min(CLUSTER_GRID_DEPTH - 1),
+        ))
+    }
+
+    /// Returns an iterator over the lights of the given cluster.
+    pub fn cluster_lights(&self, cluster: usize) -> impl Iterator<Item = &ClusteredLight> {
+        let (offset, count) = self.clusters.get(cluster).cloned().unwrap_or_default();
+        self.light_indices[offset as usize..(offset + count) as usize]
+            .iter()
+            .map(|index| &self.lights[*index as usize])
+    }
+
+    /// Returns the highest amount of lights that affect a single cluster.
+    pub fn max_occupancy(&self) -> usize {
+        self.clusters
+            .iter()
+            .map(|(_, count)| *count as usize)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+fn cluster_index(x: usize, y: usize, z: usize) -> usize {
+    (z * CLUSTER_GRID_HEIGHT + y) * CLUSTER_GRID_WIDTH + x
+}
+
+fn ndc_to_tile(ndc: f32, tile_count: usize) -> usize {
+    (((ndc * 0.5 + 0.5) * tile_count as f32).max(0.0) as usize).min(tile_count - 1)
+}
+
+// Calculates inclusive range of clusters, that are affected by the given light.
+fn cluster_bounds(
+    view: &ClusterView,
+    light: &ClusteredLight,
+) -> Option<(Vector3<usize>, Vector3<usize>)> {
+    let full_min = Vector3::new(0, 0, 0);
+    let full_max = Vector3::new(
+        CLUSTER_GRID_WIDTH - 1,
+        CLUSTER_GRID_HEIGHT - 1,
+        CLUSTER_GRID_DEPTH - 1,
+    );
+
+    if !light.radius.is_finite() {
+        return Some((full_min, full_max));
+    }
+
+    let depth = view.depth(&light.position);
+    if depth + light.radius < view.z_near || depth - light.radius > view.z_far {
+        return None;
+    }
+    let min_slice = view.depth_slice((depth - light.radius).max(view.z_near));
+    let max_slice = view.depth_slice((depth + light.radius).min(view.z_far));
+    let min_z = (min_slice.max(0.0) as usize).min(CLUSTER_GRID_DEPTH - 1);
+    let max_z = (max_slice.max(0.0) as usize).min(CLUSTER_GRID_DEPTH - 1);
+
+    // Project view-aligned bounding box of the light to the screen.
+    let mut ndc_min = Vector3::repeat(f32::MAX);
+    let mut ndc_max = Vector3::repeat(-f32::MAX);
+    for i in 0..8 {
+        let sign = |bit: usize| if i & bit == 0 { -1.0 } else { 1.0 };
+        let corner = light.position
+            + (view.side.scale(sign(1)) + view.up.scale(sign(2)) + view.look.scale(sign(4)))
+                .scale(light.radius);
+        let clip = view.view_projection * corner.push(1.0);
+        if clip.w <= f32::EPSILON {
+            // The box intersects the camera plane, the light may affect any tile.
+            return Some((
+                Vector3::new(0, 0, min_z),
+                Vector3::new(CLUSTER_GRID_WIDTH - 1, CLUSTER_GRID_HEIGHT - 1, max_z),
+            ));
+        }
+        let ndc = clip.xyz().scale(1.0 / clip.w);
+        ndc_min = ndc_min.inf(&ndc);
+        ndc_max = ndc_max.sup(&ndc);
+    }
+
+    if ndc_max.x < -1.0 || ndc_min.x > 1.0 || ndc_max.y < -1.0 || ndc_min.y > 1.0 {
+        return None;
+    }
+
+    Some((
+        Vector3::new(
+            ndc_to_tile(ndc_min.x, CLUSTER_GRID_WIDTH),
+            ndc_to_tile(ndc_min.y, CLUSTER_GRID_HEIGHT),
+            min_z,
+        ),
+        Vector3::new(
+            ndc_to_tile(ndc_max.x, CLUSTER_GRID_WIDTH),
+            ndc_to_tile(ndc_max.y, CLUSTER_GRID_HEIGHT),
+            max_z,
+        ),
+    ))
+}
+
+fn create_storage_texture(
+    state: &PipelineState,
+    pixel_kind: PixelKind,
+    data: &[u8],
+) -> Result<Rc<RefCell<GpuTexture>>, FrameworkError> {
+    Ok(Rc::new(RefCell::new(GpuTexture::new(
+        state,
+        GpuTextureKind::Rectangle {
+            width: 1,
+            height: 1,
+        },
+        pixel_kind,
+        MinificationFilter::Nearest,
+        MagnificationFilter::Nearest,
+        1,
+        Some(data),
+    )?)))
+}
+
+fn upload_texels<T: Copy + Default>(
+    state: &PipelineState,
+    texture: &Rc<RefCell<GpuTexture>>,
+    pixel_kind: PixelKind,
+    mut texels: Vec<T>,
+) -> Result<(), FrameworkError> {
+    if texels.is_empty() {
+        texels.push(T::default());
+    }
+    let width = texels.len().min(STORAGE_TEXTURE_WIDTH);
+    let height = (texels.len() + width - 1) / width;
+    texels.resize(width * height, T::default());
+
+    texture.borrow_mut().bind_mut(state, 0).set_data(
+        GpuTextureKind::Rectangle { width, height },
+        pixel_kind,
+        1,
+        Some(array_as_u8_slice(&texels)),
+    )?;
+
+    Ok(())
+}
+
+struct LightClusterDebugShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    depth_texture: UniformLocation,
+    cluster_grid: UniformLocation,
+    inv_view_proj: UniformLocation,
+    view_proj: UniformLocation,
+    camera_position: UniformLocation,
+    cluster_params: UniformLocation,
+    cluster_depth_axis: UniformLocation,
+    max_light_count: UniformLocation,
+}
+
+impl LightClusterDebugShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/light_cluster_debug_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+
+        let program = GpuProgram::from_source(
+            state,
+            "LightClusterDebugShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            cluster_grid: program.uniform_location(state, &ImmutableString::new("clusterGrid"))?,
+            inv_view_proj: program.uniform_location(state, &ImmutableString::new("invViewProj"))?,
+            view_proj: program.uniform_location(state, &ImmutableString::new("viewProj"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            cluster_params: program
+                .uniform_location(state, &ImmutableString::new("clusterParams"))?,
+            cluster_depth_axis: program
+                .uniform_location(state, &ImmutableString::new("clusterDepthAxis"))?,
+            max_light_count: program
+                .uniform_location(state, &ImmutableString::new("maxLightCount"))?,
+            program,
+        })
+    }
+}
+
+/// GPU side of the clustered lighting. Holds the content of [`LightClusterGrid`] packed into
+/// textures.
+pub struct LightClusterStorage {
+    pub(crate) lights: Rc<RefCell<GpuTexture>>,
+    pub(crate) grid: Rc<RefCell<GpuTexture>>,
+    pub(crate) light_indices: Rc<RefCell<GpuTexture>>,
+    // x - z near, y - z far, z - 1.0 if slicing is exponential, w - unused.
+    pub(crate) params: Vector4<f32>,
+    pub(crate) depth_axis: Vector3<f32>,
+    view_projection: Matrix4<f32>,
+    position: Vector3<f32>,
+    debug_shader: LightClusterDebugShader,
+    quad: GeometryBuffer,
+}
+
+impl LightClusterStorage {
+    pub(crate) fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let empty = [Vector4::<f32>::zeros()];
+        Ok(Self {
+            lights: create_storage_texture(state, PixelKind::RGBA32F, array_as_u8_slice(&empty))?,
+            grid: create_storage_texture(state, PixelKind::RGBA32F, array_as_u8_slice(&empty))?,
+            light_indices: create_storage_texture(
+                state,
+                PixelKind::R32F,
+                array_as_u8_slice(&[0.0f32]),
+            )?,
+            params: Default::default(),
+            depth_axis: Default::default(),
+            view_projection: Matrix4::identity(),
+            position: Default::default(),
+            debug_shader: LightClusterDebugShader::new(state)?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            )?,
+        })
+    }
+
+    pub(crate) fn upload(
+        &mut self,
+        state: &PipelineState,
+        grid: &LightClusterGrid,
+    ) -> Result<(), FrameworkError> {
+        let lights = grid
+            .lights
+            .iter()
+            .flat_map(|light| {
+                [
+                    light.position.push(light.radius),
+                    light.direction.push(light.half_hotspot_cone_angle_cos),
+                    light.color.push(light.half_cone_angle_cos),
+                ]
+            })
+            .collect::<Vec<_>>();
+        upload_texels(state, &self.lights, PixelKind::RGBA32F, lights)?;
+
+        let clusters = grid
+            .clusters
+            .iter()
+            .map(|(offset, count)| Vector4::new(*offset as f32, *count as f32, 0.0, 0.0))
+            .collect::<Vec<_>>();
+        upload_texels(state, &self.grid, PixelKind::RGBA32F, clusters)?;
+
+        let indices = grid
+            .light_indices
+            .iter()
+            .map(|index| *index as f32)
+            .collect::<Vec<_>>();
+        upload_texels(state, &self.light_indices, PixelKind::R32F, indices)?;
+
+        let view = &grid.view;
+        self.params = Vector4::new(
+            view.z_near,
+            view.z_far,
+            if view.exponential { 1.0 } else { 0.0 },
+            0.0,
+        );
+        self.depth_axis = view.look;
+        self.view_projection = view.view_projection;
+        self.position = view.position;
+
+        Ok(())
+    }
+
+    /// Draws amount of lights of the clusters at visible surfaces as a heat map over the frame.
+    pub(crate) fn render_debug(
+        &self,
+        state: &PipelineState,
+        viewport: Rect<i32>,
+        framebuffer: &mut FrameBuffer,
+        depth_texture: Rc<RefCell<GpuTexture>>,
+        max_light_count: usize,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        let frame_matrix = Matrix4::new_orthographic(
+            0.0,
+            viewport.w() as f32,
+            viewport.h() as f32,
+            0.0,
+            -1.0,
+            1.0,
+        ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
+            viewport.w() as f32,
+            viewport.h() as f32,
+            0.0,
+        ));
+
+        let shader = &self.debug_shader;
+        let inv_view_projection = self.view_projection.try_inverse().unwrap_or_default();
+
+        let mut statistics = RenderPassStatistics::default();
+        statistics += framebuffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: Some(BlendParameters {
+                    func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+                    ..Default::default()
+                }),
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&shader.depth_texture, &depth_texture)
+                    .set_texture(&shader.cluster_grid, &self.grid)
+                    .set_matrix4(&shader.inv_view_proj, &inv_view_projection)
+                    .set_matrix4(&shader.view_proj, &self.view_projection)
+                    .set_vector3(&shader.camera_position, &self.position)
+                    .set_vector4(&shader.cluster_params, &self.params)
+                    .set_vector3(&shader.cluster_depth_axis, &self.depth_axis)
+                    .set_f32(&shader.max_light_count, max_light_count.max(1) as f32);
+            },
+        )?;
+
+        Ok(statistics)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Matrix4, Point3, Vector3},
+        renderer::cluster::{
+            ClusterView, ClusteredLight, LightClusterGrid, CLUSTER_GRID_DEPTH, CLUSTER_GRID_HEIGHT,
+            CLUSTER_GRID_WIDTH,
+        },
+    };
+
+    fn point_light(position: Vector3<f32>, radius: f32) -> ClusteredLight {
+        ClusteredLight {
+            position,
+            direction: Vector3::y(),
+            color: Vector3::repeat(1.0),
+            radius,
+            half_hotspot_cone_angle_cos: -1.0,
+            half_cone_angle_cos: -1.0,
+        }
+    }
+
+    fn view() -> ClusterView {
+        let position = Vector3::default();
+        let look = Vector3::z();
+        let up = Vector3::y();
+        let view =
+            Matrix4::look_at_rh(&Point3::from(position), &Point3::from(position + look), &up);
+        let projection = Matrix4::new_perspective(16.0 / 9.0, 1.2, 0.1, 100.0);
+        ClusterView {
+            view_projection: projection * view,
+            position,
+            side: -Vector3::x(),
+            up,
+            look,
+            z_near: 0.1,
+            z_far: 100.0,
+            exponential: true,
+        }
+    }
+
+    #[test]
+    fn test_light_clusters() {
+        let mut grid = LightClusterGrid::default();
+
+        // Hundreds of small lights in front of the camera.
+        let mut lights = Vec::new();
+        for x in -10..10 {
+            for z in 1..11 {
+                lights.push(point_light(
+                    Vector3::new(x as f32 * 2.0, 0.0, z as f32 * 5.0),
+                    1.0,
+                ));
+            }
+        }
+        // Behind the camera.
+        lights.push(point_light(Vector3::new(0.0, 0.0, -10.0), 1.0));
+        // Directional light affects every cluster.
+        lights.push(point_light(Vector3::default(), f32::INFINITY));
+        let light_count = lights.len();
+
+        grid.assign(view(), lights);
+        assert_eq!(grid.lights().len(), light_count);
+        assert!(grid.max_occupancy() < light_count);
+
+        // Every light must be found in the cluster that contains the light.
+        for (index, light) in grid.lights().iter().enumerate() {
+            if let Some(cluster) = grid.cluster_at(&light.position) {
+                assert!(grid
+                    .cluster_lights(cluster)
+                    .any(|l| std::ptr::eq(l, &grid.lights()[index])));
+            }
+        }
+
+        // The light behind the camera must not be assigned to any cluster.
+        let behind = &grid.lights()[light_count - 2];
+        for cluster in 0..CLUSTER_GRID_WIDTH * CLUSTER_GRID_HEIGHT * CLUSTER_GRID_DEPTH {
+            assert!(grid
+                .cluster_lights(cluster)
+                .all(|l| !std::ptr::eq(l, behind)));
+            assert!(grid.cluster_lights(cluster).count() >= 1);
+        }
+    }
+}
@@ -6,7 +6,15 @@
 use crate::core::sstorage::ImmutableString;
 use crate::renderer::framework::geometry_buffer::ElementRange;
 use crate::{
-    core::{algebra::Vector3, math::Rect, scope_profile},
+    core::{
+        algebra::{Vector2, Vector3},
+        math::Rect,
+        scope_profile,
+    },
+    gui::{
+        brush::Brush, draw::DrawingContext, font::BUILT_IN_FONT,
+        formatted_text::FormattedTextBuilder,
+    },
     renderer::framework::{
         error::FrameworkError,
         framebuffer::{DrawParameters, FrameBuffer},
@@ -18,7 +26,10 @@ use crate::{
         state::PipelineState,
     },
     renderer::RenderPassStatistics,
-    scene::{camera::Camera, debug::SceneDrawingContext},
+    scene::{
+        camera::Camera,
+        debug::{DebugText, SceneDrawingContext},
+    },
 };
 
 #[repr(C)]
@@ -33,8 +44,11 @@ pub struct DebugRenderer {
     vertices: Vec<Vertex>,
     line_indices: Vec<[u32; 2]>,
     shader: DebugShader,
+    text_drawing_context: DrawingContext,
 }
 
+const DEBUG_TEXT_FONT_SIZE: f32 = 14.0;
+
 pub(crate) struct DebugShader {
     program: GpuProgram,
     wvp_matrix: UniformLocation,
@@ -79,9 +93,46 @@ impl DebugRenderer {
             shader: DebugShader::new(state)?,
             vertices: Default::default(),
             line_indices: Default::default(),
+            text_drawing_context: DrawingContext::new(),
         })
     }
 
+    /// Projects the given text labels on screen and prepares a drawing context for the UI renderer.
+    /// Labels behind the camera are skipped.
+    pub(crate) fn prepare_texts(
+        &mut self,
+        texts: &[DebugText],
+        camera: &Camera,
+        frame_size: Vector2<f32>,
+        viewport: Rect<i32>,
+    ) -> &DrawingContext {
+        scope_profile!();
+
+        let ctx = &mut self.text_drawing_context;
+        ctx.clear();
+
+        let clip_bounds = Rect::new(
+            viewport.x() as f32,
+            viewport.y() as f32,
+            viewport.w() as f32,
+            viewport.h() as f32,
+        );
+        for text in texts {
+            if let Some(position) = camera.project(text.position, frame_size) {
+                let mut formatted_text = FormattedTextBuilder::new(BUILT_IN_FONT.clone())
+                    .with_text(text.text.clone())
+                    .with_font_size(DEBUG_TEXT_FONT_SIZE)
+                    .with_brush(Brush::Solid(text.color))
+                    .with_shadow(true)
+                    .build();
+                let size = formatted_text.build();
+                ctx.draw_text(clip_bounds, position - size.scale(0.5), &formatted_text);
+            }
+        }
+
+        ctx
+    }
+
     pub(crate) fn render(
         &mut self,
         state: &PipelineState,
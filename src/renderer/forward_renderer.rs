@@ -7,17 +7,12 @@
 //! path).
 
 use crate::{
-    core::{
-        algebra::{Vector2, Vector4},
-        color::Color,
-        math::{frustum::Frustum, Rect},
-        scope_profile,
-        sstorage::ImmutableString,
-    },
+    core::{algebra::Vector2, color::Color, math::Rect, scope_profile, sstorage::ImmutableString},
     renderer::{
         apply_material,
         batch::RenderDataBatchStorage,
         cache::{shader::ShaderCache, texture::TextureCache},
+        cluster::{LightClusterGrid, LightClusterStorage},
         framework::{
             error::FrameworkError, framebuffer::FrameBuffer, gpu_texture::GpuTexture,
             state::PipelineState,
@@ -25,12 +20,7 @@ use crate::{
         storage::MatrixStorageCache,
         GeometryCache, LightData, MaterialContext, QualitySettings, RenderPassStatistics,
     },
-    scene::{
-        camera::Camera,
-        graph::Graph,
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
-        mesh::RenderPath,
-    },
+    scene::{camera::Camera, mesh::RenderPath},
 };
 use fyrox_core::math::Matrix4Ext;
 use std::{cell::RefCell, rc::Rc};
@@ -41,7 +31,6 @@ pub(crate) struct ForwardRenderer {
 
 pub(crate) struct ForwardRenderContext<'a, 'b> {
     pub state: &'a PipelineState,
-    pub graph: &'b Graph,
     pub camera: &'b Camera,
     pub geom_cache: &'a mut GeometryCache,
     pub texture_cache: &'a mut TextureCache,
@@ -57,6 +46,8 @@ pub(crate) struct ForwardRenderContext<'a, 'b> {
     pub scene_depth: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
     pub ambient_light: Color,
+    pub light_cluster_grid: &'a LightClusterGrid,
+    pub light_cluster_storage: Option<&'a LightClusterStorage>,
 }
 
 impl ForwardRenderer {
@@ -76,7 +67,6 @@ impl ForwardRenderer {
 
         let ForwardRenderContext {
             state,
-            graph,
             camera,
             geom_cache,
             texture_cache,
@@ -92,62 +82,33 @@ impl ForwardRenderer {
             scene_depth,
             matrix_storage,
             ambient_light,
+            light_cluster_grid,
+            light_cluster_storage,
         } = args;
 
         let initial_view_projection = camera.view_projection_matrix();
 
-        let frustum = Frustum::from_view_projection_matrix(camera.view_projection_matrix())
-            .unwrap_or_default();
-
         let inv_view = camera.inv_view_matrix().unwrap();
 
         let camera_up = inv_view.up();
         let camera_side = inv_view.side();
 
+        // Lights for shaders that do not support clustered lighting.
         let mut light_data = LightData::default();
-        for light in graph.linear_iter() {
-            if !light.global_visibility() || light_data.count == light_data.parameters.len() {
-                continue;
-            }
-
-            let (radius, half_cone_angle_cos, half_hotspot_angle_cos, color) =
-                if let Some(point) = light.cast::<PointLight>() {
-                    (
-                        point.radius(),
-                        std::f32::consts::PI.cos(),
-                        std::f32::consts::PI.cos(),
-                        point.base_light_ref().color().as_frgb(),
-                    )
-                } else if let Some(spot) = light.cast::<SpotLight>() {
-                    (
-                        spot.distance(),
-                        (spot.hotspot_cone_angle() * 0.5).cos(),
-                        (spot.full_cone_angle() * 0.5).cos(),
-                        spot.base_light_ref().color().as_frgb(),
-                    )
-                } else if let Some(directional) = light.cast::<DirectionalLight>() {
-                    (
-                        f32::INFINITY,
-                        std::f32::consts::PI.cos(),
-                        std::f32::consts::PI.cos(),
-                        directional.base_light_ref().color().as_frgb(),
-                    )
-                } else {
-                    continue;
-                };
-
-            if frustum.is_intersects_aabb(&light.world_bounding_box()) {
-                let light_num = light_data.count;
+        for light in light_cluster_grid
+            .lights()
+            .iter()
+            .take(light_data.parameters.len())
+        {
+            let light_num = light_data.count;
 
-                light_data.position[light_num] = light.global_position();
-                light_data.direction[light_num] = light.up_vector();
-                light_data.color_radius[light_num] =
-                    Vector4::new(color.x, color.y, color.z, radius);
-                light_data.parameters[light_num] =
-                    Vector2::new(half_cone_angle_cos, half_hotspot_angle_cos);
+            light_data.position[light_num] = light.position;
+            light_data.direction[light_num] = light.direction;
+            light_data.color_radius[light_num] = light.color.push(light.radius);
+            light_data.parameters[light_num] =
+                Vector2::new(light.half_hotspot_cone_angle_cos, light.half_cone_angle_cos);
 
-                light_data.count += 1;
-            }
+            light_data.count += 1;
         }
 
         for batch in batch_storage
@@ -221,8 +182,12 @@ impl ForwardRenderer {
                             matrix_storage,
                             persistent_identifier: instance.persistent_identifier,
                             light_data: Some(&light_data),
+                            light_clusters: light_cluster_storage,
+                            motion: None,
                             ambient_light,
                             scene_depth: Some(&scene_depth),
+                            instance_matrices: &[],
+                            instance_color: instance.color,
                         });
                     },
                 )?;
@@ -4,7 +4,7 @@ use crate::{
         error::FrameworkError,
         geometry_buffer::{DrawCallStatistics, ElementRange, GeometryBuffer},
         gpu_program::{GpuProgram, GpuProgramBinding},
-        gpu_texture::{CubeMapFace, GpuTexture, GpuTextureKind, PixelElementKind},
+        gpu_texture::{CubeMapFace, GpuTexture, GpuTextureKind, PixelElementKind, PixelKind},
         state::{BlendEquation, BlendFunc, ColorMask, PipelineState, StencilFunc, StencilOp},
     },
 };
@@ -195,6 +195,36 @@ impl FrameBuffer {
         self.depth_attachment.as_ref()
     }
 
+    /// Reads pixels of the first color attachment back from GPU memory. Returns `None` if there is
+    /// no color attachments, or the first color attachment is not a rectangle texture with `RGBA8`
+    /// pixel format. This method stalls the pipeline until all the previous commands are finished,
+    /// so it should be used sparingly.
+    pub fn read_pixels(&self, state: &PipelineState) -> Option<Vec<u8>> {
+        let attachment = self.color_attachments.first()?;
+        let texture = attachment.texture.borrow();
+        if texture.pixel_kind() != PixelKind::RGBA8 {
+            return None;
+        }
+        if let GpuTextureKind::Rectangle { width, height } = texture.kind() {
+            let mut pixels = vec![0u8; width * height * 4];
+            state.set_framebuffer(self.id());
+            unsafe {
+                state.gl.read_pixels(
+                    0,
+                    0,
+                    width as i32,
+                    height as i32,
+                    glow::RGBA,
+                    glow::UNSIGNED_BYTE,
+                    glow::PixelPackData::Slice(&mut pixels),
+                );
+            }
+            Some(pixels)
+        } else {
+            None
+        }
+    }
+
     pub fn set_cubemap_face(
         &mut self,
         state: &PipelineState,
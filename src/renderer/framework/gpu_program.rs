@@ -47,6 +47,19 @@ pub enum BuiltInUniform {
     LightsDirection,
     LightsParameters,
     AmbientLight,
+    UseInstancing,
+    InstanceMatrices,
+    InstanceColor,
+    UseClusteredLighting,
+    ClusterLights,
+    ClusterGrid,
+    ClusterLightIndices,
+    ClusterParams,
+    ClusterDepthAxis,
+    PreviousWorldMatrix,
+    PreviousBoneMatrices,
+    PreviousViewProjectionMatrix,
+    UnjitteredViewProjectionMatrix,
     // Must be last.
     Count,
 }
@@ -481,6 +494,35 @@ fn fetch_built_in_uniform_locations(
     locations[BuiltInUniform::LightPosition as usize] =
         fetch_uniform_location(state, program, "fyrox_lightPosition");
 
+    locations[BuiltInUniform::UseInstancing as usize] =
+        fetch_uniform_location(state, program, "fyrox_useInstancing");
+    locations[BuiltInUniform::InstanceMatrices as usize] =
+        fetch_uniform_location(state, program, "fyrox_instanceMatrices");
+    locations[BuiltInUniform::InstanceColor as usize] =
+        fetch_uniform_location(state, program, "fyrox_instanceColor");
+
+    locations[BuiltInUniform::UseClusteredLighting as usize] =
+        fetch_uniform_location(state, program, "fyrox_useClusteredLighting");
+    locations[BuiltInUniform::ClusterLights as usize] =
+        fetch_uniform_location(state, program, "fyrox_clusterLights");
+    locations[BuiltInUniform::ClusterGrid as usize] =
+        fetch_uniform_location(state, program, "fyrox_clusterGrid");
+    locations[BuiltInUniform::ClusterLightIndices as usize] =
+        fetch_uniform_location(state, program, "fyrox_clusterLightIndices");
+    locations[BuiltInUniform::ClusterParams as usize] =
+        fetch_uniform_location(state, program, "fyrox_clusterParams");
+    locations[BuiltInUniform::ClusterDepthAxis as usize] =
+        fetch_uniform_location(state, program, "fyrox_clusterDepthAxis");
+
+    locations[BuiltInUniform::PreviousWorldMatrix as usize] =
+        fetch_uniform_location(state, program, "fyrox_prevWorldMatrix");
+    locations[BuiltInUniform::PreviousBoneMatrices as usize] =
+        fetch_uniform_location(state, program, "fyrox_prevBoneMatrices");
+    locations[BuiltInUniform::PreviousViewProjectionMatrix as usize] =
+        fetch_uniform_location(state, program, "fyrox_prevViewProjectionMatrix");
+    locations[BuiltInUniform::UnjitteredViewProjectionMatrix as usize] =
+        fetch_uniform_location(state, program, "fyrox_unjitteredViewProjectionMatrix");
+
     locations
 }
 
@@ -548,6 +590,14 @@ impl GpuProgram {
         }
     }
 
+    /// Returns `true` if the program can render multiple instances in a single draw call, `false` -
+    /// otherwise. Such programs must fetch per-instance data from `fyrox_instanceMatrices` when
+    /// `fyrox_useInstancing` is set.
+    pub fn supports_instancing(&self) -> bool {
+        self.built_in_uniform_locations[BuiltInUniform::UseInstancing as usize].is_some()
+            && self.built_in_uniform_locations[BuiltInUniform::InstanceMatrices as usize].is_some()
+    }
+
     pub fn uniform_location_internal(
         &self,
         state: &PipelineState,
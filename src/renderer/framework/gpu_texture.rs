@@ -6,7 +6,11 @@ use crate::{
         TextureWrapMode,
     },
 };
-use glow::{HasContext, COMPRESSED_RED_RGTC1, COMPRESSED_RG_RGTC2};
+use glow::{
+    COMPRESSED_RGBA_ASTC_4x4_KHR, COMPRESSED_SRGB8_ALPHA8_ASTC_4x4_KHR, HasContext,
+    COMPRESSED_RED_RGTC1, COMPRESSED_RGBA_BPTC_UNORM, COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT,
+    COMPRESSED_RG_RGTC2, COMPRESSED_SRGB_ALPHA_BPTC_UNORM,
+};
 use std::marker::PhantomData;
 use std::rc::Weak;
 
@@ -104,6 +108,11 @@ pub enum PixelKind {
     RG8RGTC,
     R11G11B10F,
     RGB10A2,
+    BC6HRGBUF,
+    BC7RGBA,
+    BC7SRGBA,
+    ASTC4x4RGBA,
+    ASTC4x4SRGBA,
 }
 
 impl From<TexturePixelKind> for PixelKind {
@@ -134,6 +143,9 @@ impl From<TexturePixelKind> for PixelKind {
             TexturePixelKind::LuminanceAlpha16 => Self::LA16,
             TexturePixelKind::R32F => Self::R32F,
             TexturePixelKind::R16F => Self::R16F,
+            TexturePixelKind::BC6HRGBUF => Self::BC6HRGBUF,
+            TexturePixelKind::BC7RGBA => Self::BC7RGBA,
+            TexturePixelKind::ASTC4x4RGBA => Self::ASTC4x4RGBA,
         }
     }
 }
@@ -176,7 +188,12 @@ impl PixelKind {
             | Self::DXT3RGBA
             | Self::DXT5RGBA
             | Self::R8RGTC
-            | Self::RG8RGTC => None,
+            | Self::RG8RGTC
+            | Self::BC6HRGBUF
+            | Self::BC7RGBA
+            | Self::BC7SRGBA
+            | Self::ASTC4x4RGBA
+            | Self::ASTC4x4SRGBA => None,
         }
     }
 
@@ -187,7 +204,12 @@ impl PixelKind {
             | Self::DXT3RGBA
             | Self::DXT5RGBA
             | Self::R8RGTC
-            | Self::RG8RGTC => true,
+            | Self::RG8RGTC
+            | Self::BC6HRGBUF
+            | Self::BC7RGBA
+            | Self::BC7SRGBA
+            | Self::ASTC4x4RGBA
+            | Self::ASTC4x4SRGBA => true,
             // Explicit match for rest of formats instead of _ will help to not forget
             // to add new entry here.
             Self::RGBA16
@@ -230,7 +252,8 @@ impl PixelKind {
             | Self::RGBA16F
             | Self::RGB16F
             | Self::D32F
-            | Self::R11G11B10F => PixelElementKind::Float,
+            | Self::R11G11B10F
+            | Self::BC6HRGBUF => PixelElementKind::Float,
             Self::D16
             | Self::D24S8
             | Self::RGBA8
@@ -251,6 +274,10 @@ impl PixelKind {
             | Self::DXT5RGBA
             | Self::R8RGTC
             | Self::RG8RGTC
+            | Self::BC7RGBA
+            | Self::BC7SRGBA
+            | Self::ASTC4x4RGBA
+            | Self::ASTC4x4SRGBA
             | Self::RGB10A2
             | Self::LA8
             | Self::L8
@@ -309,7 +336,14 @@ fn image_3d_size_bytes(pixel_kind: PixelKind, width: usize, height: usize, depth
             let block_size = 8;
             ceil_div_4(width) * ceil_div_4(height) * ceil_div_4(depth) * block_size
         }
-        PixelKind::DXT3RGBA | PixelKind::DXT5RGBA | PixelKind::RG8RGTC => {
+        PixelKind::DXT3RGBA
+        | PixelKind::DXT5RGBA
+        | PixelKind::RG8RGTC
+        | PixelKind::BC6HRGBUF
+        | PixelKind::BC7RGBA
+        | PixelKind::BC7SRGBA
+        | PixelKind::ASTC4x4RGBA
+        | PixelKind::ASTC4x4SRGBA => {
             let block_size = 16;
             ceil_div_4(width) * ceil_div_4(height) * ceil_div_4(depth) * block_size
         }
@@ -345,7 +379,14 @@ fn image_2d_size_bytes(pixel_kind: PixelKind, width: usize, height: usize) -> us
             let block_size = 8;
             ceil_div_4(width) * ceil_div_4(height) * block_size
         }
-        PixelKind::DXT3RGBA | PixelKind::DXT5RGBA | PixelKind::RG8RGTC => {
+        PixelKind::DXT3RGBA
+        | PixelKind::DXT5RGBA
+        | PixelKind::RG8RGTC
+        | PixelKind::BC6HRGBUF
+        | PixelKind::BC7RGBA
+        | PixelKind::BC7SRGBA
+        | PixelKind::ASTC4x4RGBA
+        | PixelKind::ASTC4x4SRGBA => {
             let block_size = 16;
             ceil_div_4(width) * ceil_div_4(height) * block_size
         }
@@ -380,7 +421,14 @@ fn image_1d_size_bytes(pixel_kind: PixelKind, length: usize) -> usize {
             let block_size = 8;
             ceil_div_4(length) * block_size
         }
-        PixelKind::DXT3RGBA | PixelKind::DXT5RGBA | PixelKind::RG8RGTC => {
+        PixelKind::DXT3RGBA
+        | PixelKind::DXT5RGBA
+        | PixelKind::RG8RGTC
+        | PixelKind::BC6HRGBUF
+        | PixelKind::BC7RGBA
+        | PixelKind::BC7SRGBA
+        | PixelKind::ASTC4x4RGBA
+        | PixelKind::ASTC4x4SRGBA => {
             let block_size = 16;
             ceil_div_4(length) * block_size
         }
@@ -719,6 +767,11 @@ impl<'a> TextureBinding<'a> {
                 PixelKind::DXT5RGBA => (0, 0, GL_COMPRESSED_RGBA_S3TC_DXT5_EXT, None),
                 PixelKind::R8RGTC => (0, 0, COMPRESSED_RED_RGTC1, None),
                 PixelKind::RG8RGTC => (0, 0, COMPRESSED_RG_RGTC2, None),
+                PixelKind::BC6HRGBUF => (0, 0, COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT, None),
+                PixelKind::BC7RGBA => (0, 0, COMPRESSED_RGBA_BPTC_UNORM, None),
+                PixelKind::BC7SRGBA => (0, 0, COMPRESSED_SRGB_ALPHA_BPTC_UNORM, None),
+                PixelKind::ASTC4x4RGBA => (0, 0, COMPRESSED_RGBA_ASTC_4x4_KHR, None),
+                PixelKind::ASTC4x4SRGBA => (0, 0, COMPRESSED_SRGB8_ALPHA8_ASTC_4x4_KHR, None),
                 PixelKind::RGB32F => (glow::FLOAT, glow::RGB, glow::RGB32F, None),
                 PixelKind::RGBA32F => (glow::FLOAT, glow::RGBA, glow::RGBA32F, None),
                 PixelKind::RGBA16F => (glow::HALF_FLOAT, glow::RGBA, glow::RGBA16F, None),
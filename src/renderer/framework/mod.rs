@@ -5,4 +5,5 @@ pub mod framebuffer;
 pub mod geometry_buffer;
 pub mod gpu_program;
 pub mod gpu_texture;
+pub mod query;
 pub mod state;
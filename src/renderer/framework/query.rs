@@ -0,0 +1,64 @@
+use crate::renderer::framework::{error::FrameworkError, state::PipelineState};
+use glow::HasContext;
+use std::rc::Weak;
+
+/// Timer query measures time (in nanoseconds) that GPU spends on the commands issued between
+/// [`TimerQuery::begin`] and [`TimerQuery::end`]. Results are available only after GPU has
+/// finished the commands, which usually takes a few frames.
+pub struct TimerQuery {
+    state: Weak<PipelineState>,
+    id: glow::Query,
+}
+
+impl TimerQuery {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let id = unsafe { state.gl.create_query() }.map_err(FrameworkError::Custom)?;
+        Ok(Self {
+            state: state.weak(),
+            id,
+        })
+    }
+
+    /// Starts measuring. Only one timer query could be active at a time.
+    pub fn begin(&self, state: &PipelineState) {
+        unsafe {
+            state.gl.begin_query(glow::TIME_ELAPSED, self.id);
+        }
+    }
+
+    /// Stops measuring.
+    pub fn end(&self, state: &PipelineState) {
+        unsafe {
+            state.gl.end_query(glow::TIME_ELAPSED);
+        }
+    }
+
+    pub fn is_result_available(&self, state: &PipelineState) -> bool {
+        unsafe {
+            state
+                .gl
+                .get_query_parameter_u32(self.id, glow::QUERY_RESULT_AVAILABLE)
+                != 0
+        }
+    }
+
+    /// Returns measured time in nanoseconds. It blocks until the result is available, use
+    /// [`Self::is_result_available`] to check it first.
+    pub fn result_nanoseconds(&self, state: &PipelineState) -> u32 {
+        unsafe {
+            state
+                .gl
+                .get_query_parameter_u32(self.id, glow::QUERY_RESULT)
+        }
+    }
+}
+
+impl Drop for TimerQuery {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.upgrade() {
+            unsafe {
+                state.gl.delete_query(self.id);
+            }
+        }
+    }
+}
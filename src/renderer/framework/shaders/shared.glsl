@@ -351,6 +351,30 @@ mat4 S_FetchMatrix(in sampler2D storage, int index) {
     return mat4(col1, col2, col3, col4);
 }
 
+// Per-instance data is stored as two matrices per instance: world matrix and a matrix with the color
+// of the instance in its first column.
+mat4 S_FetchInstanceWorldMatrix(in sampler2D storage, int instanceIndex) {
+    return S_FetchMatrix(storage, 2 * instanceIndex);
+}
+
+vec4 S_FetchInstanceColor(in sampler2D storage, int instanceIndex) {
+    return S_FetchMatrix(storage, 2 * instanceIndex + 1)[0];
+}
+
+// Transforms a position using four bones with the given indices and weights.
+vec4 S_SkinPosition(in sampler2D boneMatrices, vec4 position, vec4 boneIndices, vec4 boneWeights) {
+    return S_FetchMatrix(boneMatrices, int(boneIndices.x)) * position * boneWeights.x +
+           S_FetchMatrix(boneMatrices, int(boneIndices.y)) * position * boneWeights.y +
+           S_FetchMatrix(boneMatrices, int(boneIndices.z)) * position * boneWeights.z +
+           S_FetchMatrix(boneMatrices, int(boneIndices.w)) * position * boneWeights.w;
+}
+
+// Calculates screen-space velocity (in texture coordinates) from clip-space positions of a point
+// in the current and the previous frames.
+vec2 S_Velocity(vec4 currentClipPosition, vec4 previousClipPosition) {
+    return 0.5 * (currentClipPosition.xy / currentClipPosition.w - previousClipPosition.xy / previousClipPosition.w);
+}
+
 struct TBlendShapeOffsets {
     vec3 position;
     vec3 normal;
@@ -364,4 +388,68 @@ TBlendShapeOffsets S_FetchBlendShapeOffsets(in sampler3D storage, int vertexInde
     vec3 normal = texelFetch(storage, ivec3(pos.x + 1, pos.y, pos.z), 0).xyz;
     vec3 tangent = texelFetch(storage, ivec3(pos.x + 2, pos.y, pos.z), 0).xyz;
     return TBlendShapeOffsets(position, normal, tangent);
-}
\ No newline at end of file
+}
+// Clustered lighting. Size of the grid must match the constants in `renderer/cluster.rs`.
+const int S_CLUSTER_GRID_WIDTH = 16;
+const int S_CLUSTER_GRID_HEIGHT = 9;
+const int S_CLUSTER_GRID_DEPTH = 24;
+
+struct TClusteredLight {
+    vec3 position;
+    float radius;
+    vec3 direction;
+    float halfHotspotConeAngleCos;
+    vec3 color;
+    float halfConeAngleCos;
+};
+
+// Returns offset and count of the light indices of a cluster, that contains the given world-space
+// position. `clusterParams`: x - z near, y - z far, z - 1.0 if depth slicing is exponential.
+ivec2 S_FetchClusterLightRange(
+    in sampler2D grid,
+    vec3 worldPosition,
+    mat4 viewProjection,
+    vec3 cameraPosition,
+    vec4 clusterParams,
+    vec3 clusterDepthAxis)
+{
+    vec4 clip = viewProjection * vec4(worldPosition, 1.0);
+    vec2 ndc = clip.xy / clip.w;
+    float depth = dot(worldPosition - cameraPosition, clusterDepthAxis);
+    float zNear = clusterParams.x;
+    float zFar = clusterParams.y;
+    float slice;
+    if (clusterParams.z > 0.5) {
+        slice = log(max(depth, 0.000001) / zNear) / log(zFar / zNear);
+    } else {
+        slice = (depth - zNear) / (zFar - zNear);
+    }
+    if (clip.w <= 0.0 || slice < 0.0) {
+        return ivec2(0, 0);
+    }
+    ivec3 cell = ivec3(
+        clamp(int((ndc.x * 0.5 + 0.5) * float(S_CLUSTER_GRID_WIDTH)), 0, S_CLUSTER_GRID_WIDTH - 1),
+        clamp(int((ndc.y * 0.5 + 0.5) * float(S_CLUSTER_GRID_HEIGHT)), 0, S_CLUSTER_GRID_HEIGHT - 1),
+        clamp(int(slice * float(S_CLUSTER_GRID_DEPTH)), 0, S_CLUSTER_GRID_DEPTH - 1));
+    int index = (cell.z * S_CLUSTER_GRID_HEIGHT + cell.y) * S_CLUSTER_GRID_WIDTH + cell.x;
+    vec4 range = texelFetch(grid, S_LinearIndexToPosition(index, textureSize(grid, 0).x), 0);
+    return ivec2(range.xy);
+}
+
+int S_FetchClusterLightIndex(in sampler2D lightIndices, int index) {
+    return int(texelFetch(lightIndices, S_LinearIndexToPosition(index, textureSize(lightIndices, 0).x), 0).r);
+}
+
+TClusteredLight S_FetchClusteredLight(in sampler2D lights, int lightIndex) {
+    ivec2 pos = S_LinearIndexToPosition(3 * lightIndex, textureSize(lights, 0).x);
+    vec4 positionRadius = texelFetch(lights, pos, 0);
+    vec4 directionHotspot = texelFetch(lights, ivec2(pos.x + 1, pos.y), 0);
+    vec4 colorCone = texelFetch(lights, ivec2(pos.x + 2, pos.y), 0);
+    return TClusteredLight(
+        positionRadius.xyz,
+        positionRadius.w,
+        directionHotspot.xyz,
+        directionHotspot.w,
+        colorCone.xyz,
+        colorCone.w);
+}
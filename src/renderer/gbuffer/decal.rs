@@ -10,12 +10,18 @@ pub struct DecalShader {
     pub scene_depth: UniformLocation,
     pub diffuse_texture: UniformLocation,
     pub normal_texture: UniformLocation,
+    pub roughness_texture: UniformLocation,
     pub inv_view_proj: UniformLocation,
     pub inv_world_decal: UniformLocation,
     pub resolution: UniformLocation,
     pub color: UniformLocation,
     pub layer_index: UniformLocation,
     pub decal_mask: UniformLocation,
+    pub projection_axis: UniformLocation,
+    pub camera_position: UniformLocation,
+    pub angle_fade: UniformLocation,
+    pub distance_fade: UniformLocation,
+    pub roughness_pass: UniformLocation,
     pub program: GpuProgram,
 }
 
@@ -34,6 +40,8 @@ impl DecalShader {
                 .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
             normal_texture: program
                 .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            roughness_texture: program
+                .uniform_location(state, &ImmutableString::new("roughnessTexture"))?,
             inv_view_proj: program.uniform_location(state, &ImmutableString::new("invViewProj"))?,
             inv_world_decal: program
                 .uniform_location(state, &ImmutableString::new("invWorldDecal"))?,
@@ -41,6 +49,15 @@ impl DecalShader {
             color: program.uniform_location(state, &ImmutableString::new("color"))?,
             layer_index: program.uniform_location(state, &ImmutableString::new("layerIndex"))?,
             decal_mask: program.uniform_location(state, &ImmutableString::new("decalMask"))?,
+            projection_axis: program
+                .uniform_location(state, &ImmutableString::new("projectionAxis"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            angle_fade: program.uniform_location(state, &ImmutableString::new("angleFade"))?,
+            distance_fade: program
+                .uniform_location(state, &ImmutableString::new("distanceFade"))?,
+            roughness_pass: program
+                .uniform_location(state, &ImmutableString::new("roughnessPass"))?,
             program,
         })
     }
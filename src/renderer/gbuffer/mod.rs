@@ -5,13 +5,14 @@
 //! RT2: RGBA16F - Ambient light + emission (both in xyz)
 //! RT3: RGBA8 - Metallic (x) + Roughness (y) + Ambient Occlusion (z)
 //! RT4: R8UI - Decal mask (x)
+//! RT5: RGBA16F - Velocity in screen space (xy) + Velocity flag (w)
 //!
 //! Every alpha channel is used for layer blending for terrains. This is inefficient, but for
 //! now I don't know better solution.
 
 use crate::{
     core::{
-        algebra::{Matrix4, Vector2},
+        algebra::{Matrix4, Vector2, Vector3},
         color::Color,
         math::Rect,
         scope_profile,
@@ -32,11 +33,12 @@ use crate::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
                 PixelKind, WrapMode,
             },
-            state::{BlendFactor, BlendFunc, PipelineState},
+            state::{BlendFactor, BlendFunc, ColorMask, PipelineState},
         },
         gbuffer::decal::DecalShader,
         storage::MatrixStorageCache,
-        GeometryCache, MaterialContext, RenderPassStatistics, TextureCache,
+        taa::InstanceHistory,
+        GeometryCache, MaterialContext, MotionData, RenderPassStatistics, TextureCache,
     },
     scene::{
         camera::Camera,
@@ -53,6 +55,7 @@ mod decal;
 pub struct GBuffer {
     framebuffer: FrameBuffer,
     decal_framebuffer: FrameBuffer,
+    decal_roughness_framebuffer: FrameBuffer,
     pub width: i32,
     pub height: i32,
     cube: GeometryBuffer,
@@ -76,6 +79,11 @@ pub(crate) struct GBufferRenderContext<'a, 'b> {
     pub use_parallax_mapping: bool,
     pub graph: &'b Graph,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    /// History of instances, it must be provided if velocities are needed (for example - for
+    /// temporal anti-aliasing).
+    pub instance_history: Option<&'a mut InstanceHistory>,
+    /// View-projection matrix (without jitter) of the camera in the previous frame.
+    pub prev_view_projection: Option<Matrix4<f32>>,
 }
 
 impl GBuffer {
@@ -156,6 +164,20 @@ impl GBuffer {
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
 
+        let mut velocity_texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::RGBA16F,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        velocity_texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
         let mut material_texture = GpuTexture::new(
             state,
             GpuTextureKind::Rectangle { width, height },
@@ -169,6 +191,7 @@ impl GBuffer {
             .bind_mut(state, 0)
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        let material_texture = Rc::new(RefCell::new(material_texture));
 
         let framebuffer = FrameBuffer::new(
             state,
@@ -191,12 +214,16 @@ impl GBuffer {
                 },
                 Attachment {
                     kind: AttachmentKind::Color,
-                    texture: Rc::new(RefCell::new(material_texture)),
+                    texture: material_texture.clone(),
                 },
                 Attachment {
                     kind: AttachmentKind::Color,
                     texture: Rc::new(RefCell::new(decal_mask_texture)),
                 },
+                Attachment {
+                    kind: AttachmentKind::Color,
+                    texture: Rc::new(RefCell::new(velocity_texture)),
+                },
             ],
         )?;
 
@@ -215,6 +242,15 @@ impl GBuffer {
             ],
         )?;
 
+        let decal_roughness_framebuffer = FrameBuffer::new(
+            state,
+            None,
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: material_texture,
+            }],
+        )?;
+
         Ok(Self {
             framebuffer,
             width: width as i32,
@@ -226,6 +262,7 @@ impl GBuffer {
                 state,
             )?,
             decal_framebuffer,
+            decal_roughness_framebuffer,
             render_pass_name: ImmutableString::new("GBuffer"),
         })
     }
@@ -258,6 +295,10 @@ impl GBuffer {
         self.framebuffer.color_attachments()[4].texture.clone()
     }
 
+    pub fn velocity_texture(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[5].texture.clone()
+    }
+
     pub(crate) fn fill(
         &mut self,
         args: GBufferRenderContext,
@@ -280,6 +321,8 @@ impl GBuffer {
             volume_dummy,
             graph,
             matrix_storage,
+            mut instance_history,
+            prev_view_projection,
             ..
         } = args;
 
@@ -293,6 +336,8 @@ impl GBuffer {
         );
 
         let initial_view_projection = camera.view_projection_matrix();
+        let unjittered_view_projection = camera.unjittered_view_projection_matrix();
+        let prev_view_projection = prev_view_projection.unwrap_or(unjittered_view_projection);
 
         let inv_view = camera.inv_view_matrix().unwrap();
 
@@ -328,7 +373,42 @@ impl GBuffer {
                 continue;
             };
 
-            for instance in batch.instances.iter() {
+            // Render every instance of the batch in a single draw call, if possible.
+            let instance_matrices =
+                if batch.is_instanceable() && render_pass.program.supports_instancing() {
+                    batch.instance_matrices()
+                } else {
+                    Vec::new()
+                };
+            let is_instanced = !instance_matrices.is_empty();
+            let instances = if is_instanced {
+                &batch.instances[..1]
+            } else {
+                &batch.instances[..]
+            };
+
+            for instance in instances {
+                let persistent_identifier = if is_instanced {
+                    batch.instancing_identifier()
+                } else {
+                    instance.persistent_identifier
+                };
+
+                // Velocities of instanced draws are calculated using camera motion only.
+                let motion = instance_history.as_deref().map(|history| {
+                    let previous = history
+                        .previous(instance.persistent_identifier)
+                        .filter(|_| !is_instanced);
+                    MotionData {
+                        prev_world_matrix: previous
+                            .map_or(&instance.world_transform, |p| &p.world_transform),
+                        prev_bone_matrices: previous
+                            .map_or(&instance.bone_matrices, |p| &p.bone_matrices),
+                        prev_view_projection_matrix: &prev_view_projection,
+                        unjittered_view_projection_matrix: &unjittered_view_projection,
+                    }
+                });
+
                 let apply_uniforms = |mut program_binding: GpuProgramBinding| {
                     let view_projection = if instance.depth_offset != 0.0 {
                         let mut projection = camera.projection_matrix();
@@ -360,23 +440,51 @@ impl GBuffer {
                         white_dummy: &white_dummy,
                         black_dummy: &black_dummy,
                         volume_dummy: &volume_dummy,
-                        persistent_identifier: instance.persistent_identifier,
+                        persistent_identifier,
                         light_data: None,
+                        light_clusters: None,
+                        motion,
                         ambient_light: Color::WHITE, // TODO
                         scene_depth: None,           // TODO. Add z-pre-pass.
                         z_far: camera.projection().z_far(),
+                        instance_matrices: &instance_matrices,
+                        instance_color: instance.color,
                     });
                 };
 
-                statistics += self.framebuffer.draw(
-                    geometry,
-                    state,
-                    viewport,
-                    &render_pass.program,
-                    &render_pass.draw_params,
-                    instance.element_range,
-                    apply_uniforms,
-                )?;
+                if is_instanced {
+                    statistics += self.framebuffer.draw_instances(
+                        batch.instances.len(),
+                        geometry,
+                        state,
+                        viewport,
+                        &render_pass.program,
+                        &render_pass.draw_params,
+                        apply_uniforms,
+                    );
+                    statistics.instanced_batches += 1;
+                    statistics.instances_rendered += batch.instances.len();
+                } else {
+                    statistics += self.framebuffer.draw(
+                        geometry,
+                        state,
+                        viewport,
+                        &render_pass.program,
+                        &render_pass.draw_params,
+                        instance.element_range,
+                        apply_uniforms,
+                    )?;
+                }
+
+                if !is_instanced {
+                    if let Some(history) = instance_history.as_deref_mut() {
+                        history.record(
+                            instance.persistent_identifier,
+                            &instance.world_transform,
+                            &instance.bone_matrices,
+                        );
+                    }
+                }
             }
         }
 
@@ -389,11 +497,62 @@ impl GBuffer {
         // for rendering. We'll render in the G-Buffer, but depth will be used from final frame, since
         // decals do not modify depth (only diffuse and normal maps).
         let unit_cube = &self.cube;
+        let camera_position = camera.global_position();
         for decal in graph.linear_iter().filter_map(|n| n.cast::<Decal>()) {
             let shader = &self.decal_shader;
             let program = &self.decal_shader.program;
 
             let world_view_proj = initial_view_projection * decal.global_transform();
+            let inv_world_decal = decal.global_transform().try_inverse().unwrap_or_default();
+            let projection_axis = decal
+                .up_vector()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y);
+            let angle_fade = Vector2::new(decal.angle_fade_start(), decal.angle_fade_end());
+            let distance_fade =
+                Vector2::new(decal.distance_fade_start(), decal.distance_fade_end());
+            let diffuse_texture = decal
+                .diffuse_texture()
+                .and_then(|t| texture_cache.get(state, t))
+                .unwrap_or(&white_dummy)
+                .clone();
+            let normal_texture = decal
+                .normal_texture()
+                .and_then(|t| texture_cache.get(state, t))
+                .unwrap_or(&normal_dummy)
+                .clone();
+            let roughness_texture = decal
+                .roughness_texture()
+                .and_then(|t| texture_cache.get(state, t))
+                .cloned();
+
+            let bind_decal = |program_binding: &mut GpuProgramBinding, roughness_pass: bool| {
+                program_binding
+                    .set_matrix4(&shader.world_view_projection, &world_view_proj)
+                    .set_matrix4(&shader.inv_view_proj, &inv_view_proj)
+                    .set_matrix4(&shader.inv_world_decal, &inv_world_decal)
+                    .set_vector2(&shader.resolution, &resolution)
+                    .set_texture(&shader.scene_depth, &depth)
+                    .set_texture(&shader.diffuse_texture, &diffuse_texture)
+                    .set_texture(&shader.normal_texture, &normal_texture)
+                    .set_texture(
+                        &shader.roughness_texture,
+                        roughness_texture.as_ref().unwrap_or(&white_dummy),
+                    )
+                    .set_texture(&shader.decal_mask, &decal_mask)
+                    .set_u32(&shader.layer_index, decal.layer() as u32)
+                    .set_linear_color(&shader.color, &decal.color())
+                    .set_vector3(&shader.projection_axis, &projection_axis)
+                    .set_vector3(&shader.camera_position, &camera_position)
+                    .set_vector2(&shader.angle_fade, &angle_fade)
+                    .set_vector2(&shader.distance_fade, &distance_fade)
+                    .set_bool(&shader.roughness_pass, roughness_pass);
+            };
+
+            let blend = Some(BlendParameters {
+                func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+                ..Default::default()
+            });
 
             statistics += self.decal_framebuffer.draw(
                 unit_cube,
@@ -406,42 +565,39 @@ impl GBuffer {
                     depth_write: false,
                     stencil_test: None,
                     depth_test: false,
-                    blend: Some(BlendParameters {
-                        func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
-                        ..Default::default()
-                    }),
+                    blend: blend.clone(),
                     stencil_op: Default::default(),
                 },
                 ElementRange::Full,
-                |mut program_binding| {
-                    program_binding
-                        .set_matrix4(&shader.world_view_projection, &world_view_proj)
-                        .set_matrix4(&shader.inv_view_proj, &inv_view_proj)
-                        .set_matrix4(
-                            &shader.inv_world_decal,
-                            &decal.global_transform().try_inverse().unwrap_or_default(),
-                        )
-                        .set_vector2(&shader.resolution, &resolution)
-                        .set_texture(&shader.scene_depth, &depth)
-                        .set_texture(
-                            &shader.diffuse_texture,
-                            decal
-                                .diffuse_texture()
-                                .and_then(|t| texture_cache.get(state, t))
-                                .unwrap_or(&white_dummy),
-                        )
-                        .set_texture(
-                            &shader.normal_texture,
-                            decal
-                                .normal_texture()
-                                .and_then(|t| texture_cache.get(state, t))
-                                .unwrap_or(&normal_dummy),
-                        )
-                        .set_texture(&shader.decal_mask, &decal_mask)
-                        .set_u32(&shader.layer_index, decal.layer() as u32)
-                        .set_linear_color(&shader.color, &decal.color());
-                },
+                |mut program_binding| bind_decal(&mut program_binding, false),
             )?;
+
+            // Roughness is stored in the green channel of the material texture, other channels must
+            // be left untouched, so it is projected in a separate pass with a color mask.
+            if roughness_texture.is_some() {
+                statistics += self.decal_roughness_framebuffer.draw(
+                    unit_cube,
+                    state,
+                    viewport,
+                    program,
+                    &DrawParameters {
+                        cull_face: None,
+                        color_write: ColorMask {
+                            red: false,
+                            green: true,
+                            blue: false,
+                            alpha: false,
+                        },
+                        depth_write: false,
+                        stencil_test: None,
+                        depth_test: false,
+                        blend,
+                        stencil_op: Default::default(),
+                    },
+                    ElementRange::Full,
+                    |mut program_binding| bind_decal(&mut program_binding, true),
+                )?;
+            }
         }
 
         Ok(statistics)
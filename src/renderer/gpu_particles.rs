@@ -0,0 +1,893 @@
+//! GPU particle systems. See [`ParticleSimulationMode::Gpu`] docs for more info.
+//!
+//! Particles are stored in floating point textures (one texel per particle) and simulated by a
+//! fragment shader, that renders into another set of textures (ping-pong). Initial state of
+//! particles, that were emitted on CPU, is uploaded to a spawn texture and then copied into a ring
+//! buffer by the simulation shader. Particles could be optionally sorted back-to-front by bitonic
+//! sort and then they are drawn by a single instanced draw call.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector3},
+        array_as_u8_slice,
+        curve::Curve,
+        math::{Matrix4Ext, Rect},
+        pool::Handle,
+        scope_profile,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        cache::texture::TextureCache,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{
+                DrawCallStatistics, ElementRange, GeometryBuffer, GeometryBufferKind,
+            },
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        RenderPassStatistics,
+    },
+    scene::{
+        camera::Camera,
+        graph::Graph,
+        mesh::surface::SurfaceData,
+        node::Node,
+        particle_system::{particle::Particle, ParticleSimulationMode, ParticleSystem},
+    },
+};
+use fxhash::FxHashMap;
+use fyrox_graph::SceneGraph;
+use std::{cell::RefCell, rc::Rc};
+
+/// Amount of particles in a row of the spawn texture, must match the value in the simulation
+/// shader.
+const SPAWN_ROW_SIZE: usize = 256;
+
+/// Width of the color over lifetime and lifetime curves textures.
+const GRADIENT_TEXTURE_WIDTH: usize = 64;
+
+struct SimulationShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    position_texture: UniformLocation,
+    velocity_texture: UniformLocation,
+    parameters_texture: UniformLocation,
+    spawn_texture: UniformLocation,
+    lifetime_curves_texture: UniformLocation,
+    depth_texture: UniformLocation,
+    normal_texture: UniformLocation,
+    texture_size: UniformLocation,
+    spawn_start: UniformLocation,
+    spawn_count: UniformLocation,
+    elapsed: UniformLocation,
+    steps: UniformLocation,
+    acceleration_offset: UniformLocation,
+    depth_collision: UniformLocation,
+    world_matrix: UniformLocation,
+    inv_world_matrix: UniformLocation,
+    view_projection_matrix: UniformLocation,
+    inv_view_projection_matrix: UniformLocation,
+    camera_position: UniformLocation,
+    restitution: UniformLocation,
+    collision_thickness: UniformLocation,
+}
+
+impl SimulationShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/gpu_particle_simulation_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "GpuParticleSimulationShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            position_texture: program
+                .uniform_location(state, &ImmutableString::new("positionTexture"))?,
+            velocity_texture: program
+                .uniform_location(state, &ImmutableString::new("velocityTexture"))?,
+            parameters_texture: program
+                .uniform_location(state, &ImmutableString::new("parametersTexture"))?,
+            spawn_texture: program
+                .uniform_location(state, &ImmutableString::new("spawnTexture"))?,
+            lifetime_curves_texture: program
+                .uniform_location(state, &ImmutableString::new("lifetimeCurvesTexture"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            texture_size: program.uniform_location(state, &ImmutableString::new("textureSize"))?,
+            spawn_start: program.uniform_location(state, &ImmutableString::new("spawnStart"))?,
+            spawn_count: program.uniform_location(state, &ImmutableString::new("spawnCount"))?,
+            elapsed: program.uniform_location(state, &ImmutableString::new("elapsed"))?,
+            steps: program.uniform_location(state, &ImmutableString::new("steps"))?,
+            acceleration_offset: program
+                .uniform_location(state, &ImmutableString::new("accelerationOffset"))?,
+            depth_collision: program
+                .uniform_location(state, &ImmutableString::new("depthCollision"))?,
+            world_matrix: program.uniform_location(state, &ImmutableString::new("worldMatrix"))?,
+            inv_world_matrix: program
+                .uniform_location(state, &ImmutableString::new("invWorldMatrix"))?,
+            view_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("viewProjectionMatrix"))?,
+            inv_view_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("invViewProjectionMatrix"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            restitution: program.uniform_location(state, &ImmutableString::new("restitution"))?,
+            collision_thickness: program
+                .uniform_location(state, &ImmutableString::new("collisionThickness"))?,
+            program,
+        })
+    }
+}
+
+struct SortShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    position_texture: UniformLocation,
+    velocity_texture: UniformLocation,
+    sort_texture: UniformLocation,
+    texture_size: UniformLocation,
+    initialize: UniformLocation,
+    world_matrix: UniformLocation,
+    camera_position: UniformLocation,
+    block_size: UniformLocation,
+    stride: UniformLocation,
+}
+
+impl SortShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/gpu_particle_sort_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "GpuParticleSortShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            position_texture: program
+                .uniform_location(state, &ImmutableString::new("positionTexture"))?,
+            velocity_texture: program
+                .uniform_location(state, &ImmutableString::new("velocityTexture"))?,
+            sort_texture: program.uniform_location(state, &ImmutableString::new("sortTexture"))?,
+            texture_size: program.uniform_location(state, &ImmutableString::new("textureSize"))?,
+            initialize: program.uniform_location(state, &ImmutableString::new("initialize"))?,
+            world_matrix: program.uniform_location(state, &ImmutableString::new("worldMatrix"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            block_size: program.uniform_location(state, &ImmutableString::new("blockSize"))?,
+            stride: program.uniform_location(state, &ImmutableString::new("stride"))?,
+            program,
+        })
+    }
+}
+
+struct RenderShader {
+    program: GpuProgram,
+    position_texture: UniformLocation,
+    velocity_texture: UniformLocation,
+    parameters_texture: UniformLocation,
+    sort_texture: UniformLocation,
+    color_over_lifetime_texture: UniformLocation,
+    lifetime_curves_texture: UniformLocation,
+    texture_size: UniformLocation,
+    sorted: UniformLocation,
+    view_projection_matrix: UniformLocation,
+    world_matrix: UniformLocation,
+    camera_up_vector: UniformLocation,
+    camera_side_vector: UniformLocation,
+    diffuse_texture: UniformLocation,
+    soft_boundary_sharpness_factor: UniformLocation,
+    scene_depth: UniformLocation,
+    z_near: UniformLocation,
+    z_far: UniformLocation,
+}
+
+impl RenderShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/gpu_particle_fs.glsl");
+        let vertex_source = include_str!("shaders/gpu_particle_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "GpuParticleShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            position_texture: program
+                .uniform_location(state, &ImmutableString::new("positionTexture"))?,
+            velocity_texture: program
+                .uniform_location(state, &ImmutableString::new("velocityTexture"))?,
+            parameters_texture: program
+                .uniform_location(state, &ImmutableString::new("parametersTexture"))?,
+            sort_texture: program.uniform_location(state, &ImmutableString::new("sortTexture"))?,
+            color_over_lifetime_texture: program
+                .uniform_location(state, &ImmutableString::new("colorOverLifetimeTexture"))?,
+            lifetime_curves_texture: program
+                .uniform_location(state, &ImmutableString::new("lifetimeCurvesTexture"))?,
+            texture_size: program.uniform_location(state, &ImmutableString::new("textureSize"))?,
+            sorted: program.uniform_location(state, &ImmutableString::new("sorted"))?,
+            view_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("viewProjectionMatrix"))?,
+            world_matrix: program.uniform_location(state, &ImmutableString::new("worldMatrix"))?,
+            camera_up_vector: program
+                .uniform_location(state, &ImmutableString::new("cameraUpVector"))?,
+            camera_side_vector: program
+                .uniform_location(state, &ImmutableString::new("cameraSideVector"))?,
+            diffuse_texture: program
+                .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
+            soft_boundary_sharpness_factor: program
+                .uniform_location(state, &ImmutableString::new("softBoundarySharpnessFactor"))?,
+            scene_depth: program.uniform_location(state, &ImmutableString::new("sceneDepth"))?,
+            z_near: program.uniform_location(state, &ImmutableString::new("zNear"))?,
+            z_far: program.uniform_location(state, &ImmutableString::new("zFar"))?,
+            program,
+        })
+    }
+}
+
+fn make_texture(
+    state: &PipelineState,
+    width: usize,
+    height: usize,
+    pixel_kind: PixelKind,
+    filter: bool,
+) -> Result<Rc<RefCell<GpuTexture>>, FrameworkError> {
+    let (min_filter, mag_filter) = if filter {
+        (MinificationFilter::Linear, MagnificationFilter::Linear)
+    } else {
+        (MinificationFilter::Nearest, MagnificationFilter::Nearest)
+    };
+    let mut texture = GpuTexture::new(
+        state,
+        GpuTextureKind::Rectangle { width, height },
+        pixel_kind,
+        min_filter,
+        mag_filter,
+        1,
+        None,
+    )?;
+    texture
+        .bind_mut(state, 0)
+        .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+        .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+    Ok(Rc::new(RefCell::new(texture)))
+}
+
+fn make_framebuffer(
+    state: &PipelineState,
+    size: usize,
+    attachment_count: usize,
+) -> Result<FrameBuffer, FrameworkError> {
+    let mut attachments = Vec::with_capacity(attachment_count);
+    for _ in 0..attachment_count {
+        attachments.push(Attachment {
+            kind: AttachmentKind::Color,
+            texture: make_texture(state, size, size, PixelKind::RGBA32F, false)?,
+        });
+    }
+    FrameBuffer::new(state, None, attachments)
+}
+
+/// GPU state of a particle system.
+pub(crate) struct GpuParticleSystemState {
+    texture_size: usize,
+    /// Ping-pong buffers of the simulation, each has position, velocity and parameters textures.
+    state_buffers: [FrameBuffer; 2],
+    current: usize,
+    /// Ping-pong buffers of the sort, each has a single texture with sort keys and indices.
+    sort_buffers: [FrameBuffer; 2],
+    spawn_texture: Rc<RefCell<GpuTexture>>,
+    color_over_lifetime_texture: Rc<RefCell<GpuTexture>>,
+    /// Size (r) and speed (g) scales of particles over their lifetime.
+    lifetime_curves_texture: Rc<RefCell<GpuTexture>>,
+    /// Position of the next particle in the ring buffer.
+    spawn_cursor: usize,
+}
+
+impl GpuParticleSystemState {
+    fn new(state: &PipelineState, texture_size: usize) -> Result<Self, FrameworkError> {
+        let mut system = Self {
+            texture_size,
+            state_buffers: [
+                make_framebuffer(state, texture_size, 3)?,
+                make_framebuffer(state, texture_size, 3)?,
+            ],
+            current: 0,
+            sort_buffers: [
+                make_framebuffer(state, texture_size, 1)?,
+                make_framebuffer(state, texture_size, 1)?,
+            ],
+            spawn_texture: make_texture(state, 3, 1, PixelKind::RGBA32F, false)?,
+            color_over_lifetime_texture: make_texture(
+                state,
+                GRADIENT_TEXTURE_WIDTH,
+                1,
+                PixelKind::RGBA8,
+                true,
+            )?,
+            lifetime_curves_texture: make_texture(
+                state,
+                GRADIENT_TEXTURE_WIDTH,
+                1,
+                PixelKind::RGBA32F,
+                // Filtering of float textures is not guaranteed to be supported.
+                false,
+            )?,
+            spawn_cursor: 0,
+        };
+        // Contents of new textures are undefined.
+        system.reset(state);
+        Ok(system)
+    }
+
+    fn viewport(&self) -> Rect<i32> {
+        Rect::new(0, 0, self.texture_size as i32, self.texture_size as i32)
+    }
+
+    fn reset(&mut self, state: &PipelineState) {
+        let viewport = self.viewport();
+        for framebuffer in self.state_buffers.iter_mut() {
+            framebuffer.clear(state, viewport, Some(Default::default()), None, None);
+        }
+        self.spawn_cursor = 0;
+    }
+
+    fn upload_color_over_lifetime(
+        &self,
+        state: &PipelineState,
+        particle_system: &ParticleSystem,
+    ) -> Result<(), FrameworkError> {
+        let gradient = particle_system.color_over_lifetime_gradient();
+        let texels = (0..GRADIENT_TEXTURE_WIDTH)
+            .map(|i| {
+                let color = gradient.get_color(i as f32 / (GRADIENT_TEXTURE_WIDTH - 1) as f32);
+                [color.r, color.g, color.b, color.a]
+            })
+            .collect::<Vec<_>>();
+        self.color_over_lifetime_texture
+            .borrow_mut()
+            .bind_mut(state, 0)
+            .set_data(
+                GpuTextureKind::Rectangle {
+                    width: GRADIENT_TEXTURE_WIDTH,
+                    height: 1,
+                },
+                PixelKind::RGBA8,
+                1,
+                Some(array_as_u8_slice(&texels)),
+            )?;
+        Ok(())
+    }
+
+    fn upload_lifetime_curves(
+        &self,
+        state: &PipelineState,
+        particle_system: &ParticleSystem,
+    ) -> Result<(), FrameworkError> {
+        let evaluate = |curve: Option<&Curve>, k: f32| curve.map_or(1.0, |c| c.value_at(k));
+        let texels = (0..GRADIENT_TEXTURE_WIDTH)
+            .map(|i| {
+                let k = i as f32 / (GRADIENT_TEXTURE_WIDTH - 1) as f32;
+                [
+                    evaluate(particle_system.size_over_lifetime(), k),
+                    evaluate(particle_system.speed_over_lifetime(), k),
+                    0.0,
+                    0.0,
+                ]
+            })
+            .collect::<Vec<_>>();
+        self.lifetime_curves_texture
+            .borrow_mut()
+            .bind_mut(state, 0)
+            .set_data(
+                GpuTextureKind::Rectangle {
+                    width: GRADIENT_TEXTURE_WIDTH,
+                    height: 1,
+                },
+                PixelKind::RGBA32F,
+                1,
+                Some(array_as_u8_slice(&texels)),
+            )?;
+        Ok(())
+    }
+
+    /// Uploads spawned particles to the spawn texture and returns amount of them.
+    fn upload_spawned(
+        &self,
+        state: &PipelineState,
+        particle_system: &ParticleSystem,
+    ) -> Result<usize, FrameworkError> {
+        let capacity = self.texture_size * self.texture_size;
+        let mut spawned = particle_system.gpu_queue().spawned.borrow_mut();
+        if spawned.len() > capacity {
+            let excess = spawned.len() - capacity;
+            spawned.drain(..excess);
+        }
+        let count = spawned.len();
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let (width, height, texels) = pack_spawned(&spawned);
+        spawned.clear();
+
+        self.spawn_texture
+            .borrow_mut()
+            .bind_mut(state, 0)
+            .set_data(
+                GpuTextureKind::Rectangle {
+                    width: width * 3,
+                    height,
+                },
+                PixelKind::RGBA32F,
+                1,
+                Some(array_as_u8_slice(&texels)),
+            )?;
+
+        Ok(count)
+    }
+}
+
+/// Packs particles into texels of the spawn texture, each particle takes three texels in a row of
+/// [`SPAWN_ROW_SIZE`] particles. Returns width (in particles) and height of the texture and the
+/// texels.
+fn pack_spawned(particles: &[Particle]) -> (usize, usize, Vec<[f32; 4]>) {
+    let count = particles.len();
+    let width = count.min(SPAWN_ROW_SIZE);
+    let height = (count + SPAWN_ROW_SIZE - 1) / SPAWN_ROW_SIZE;
+    let mut texels = vec![[0.0f32; 4]; width * 3 * height];
+    for (i, particle) in particles.iter().enumerate() {
+        let row = i / SPAWN_ROW_SIZE;
+        let base = row * width * 3 + (i % SPAWN_ROW_SIZE) * 3;
+        texels[base] = [
+            particle.position.x,
+            particle.position.y,
+            particle.position.z,
+            0.0,
+        ];
+        texels[base + 1] = [
+            particle.velocity.x,
+            particle.velocity.y,
+            particle.velocity.z,
+            particle.initial_lifetime,
+        ];
+        texels[base + 2] = [
+            particle.size,
+            particle.size_modifier,
+            particle.rotation,
+            particle.rotation_speed,
+        ];
+    }
+    (width, height, texels)
+}
+
+pub(crate) struct GpuParticleRenderContext<'a, 'b> {
+    pub state: &'a PipelineState,
+    pub graph: &'b Graph,
+    pub camera: &'b Camera,
+    pub systems: &'b mut FxHashMap<Handle<Node>, GpuParticleSystemState>,
+    pub framebuffer: &'b mut FrameBuffer,
+    pub viewport: Rect<i32>,
+    pub depth_texture: Rc<RefCell<GpuTexture>>,
+    pub normal_texture: Rc<RefCell<GpuTexture>>,
+    pub texture_cache: &'b mut TextureCache,
+    pub white_dummy: Rc<RefCell<GpuTexture>>,
+}
+
+/// Simulates and renders particle systems with [`ParticleSimulationMode::Gpu`] simulation mode.
+pub struct GpuParticleRenderer {
+    simulation_shader: SimulationShader,
+    sort_shader: SortShader,
+    render_shader: RenderShader,
+    quad: GeometryBuffer,
+}
+
+fn texture_matrix(size: usize) -> Matrix4<f32> {
+    let size = size as f32;
+    Matrix4::new_orthographic(0.0, size, size, 0.0, -1.0, 1.0)
+        * Matrix4::new_nonuniform_scaling(&Vector3::new(size, size, 0.0))
+}
+
+fn offscreen_draw_parameters() -> DrawParameters {
+    DrawParameters {
+        cull_face: None,
+        color_write: Default::default(),
+        depth_write: false,
+        stencil_test: None,
+        depth_test: false,
+        blend: None,
+        stencil_op: Default::default(),
+    }
+}
+
+impl GpuParticleRenderer {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            simulation_shader: SimulationShader::new(state)?,
+            sort_shader: SortShader::new(state)?,
+            render_shader: RenderShader::new(state)?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            )?,
+        })
+    }
+
+    /// Removes the state of particle systems, that were deleted or do not use GPU simulation
+    /// anymore.
+    pub(crate) fn retain_systems(
+        graph: &Graph,
+        systems: &mut FxHashMap<Handle<Node>, GpuParticleSystemState>,
+    ) {
+        systems.retain(|handle, system| {
+            graph
+                .try_get(*handle)
+                .and_then(|node| node.cast::<ParticleSystem>())
+                .is_some_and(|particle_system| {
+                    particle_system.simulation_mode() == ParticleSimulationMode::Gpu
+                        && particle_system.gpu_settings().texture_size() == system.texture_size
+                })
+        });
+    }
+
+    fn simulate(
+        &self,
+        ctx: &GpuParticleRenderContext,
+        particle_system: &ParticleSystem,
+        system: &mut GpuParticleSystemState,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        let mut statistics = RenderPassStatistics::default();
+
+        let queue = particle_system.gpu_queue();
+        if queue.reset.replace(false) {
+            system.reset(ctx.state);
+        }
+
+        let elapsed = queue.elapsed.replace(0.0);
+        let ticks = queue.ticks.replace(0);
+        let spawn_count = system.upload_spawned(ctx.state, particle_system)?;
+        if ticks == 0 && spawn_count == 0 {
+            return Ok(statistics);
+        }
+
+        system.upload_color_over_lifetime(ctx.state, particle_system)?;
+        system.upload_lifetime_curves(ctx.state, particle_system)?;
+
+        let capacity = system.texture_size * system.texture_size;
+        let spawn_start = system.spawn_cursor;
+        system.spawn_cursor = (system.spawn_cursor + spawn_count) % capacity;
+
+        let tick_dt = if ticks > 0 {
+            elapsed / ticks as f32
+        } else {
+            0.0
+        };
+        let acceleration_offset = particle_system.acceleration().scale(tick_dt * tick_dt);
+        let world_matrix = particle_system.global_transform();
+        let inv_world_matrix = world_matrix.try_inverse().unwrap_or_default();
+        let view_projection = ctx.camera.view_projection_matrix();
+        let inv_view_projection = view_projection.try_inverse().unwrap_or_default();
+        let settings = particle_system.gpu_settings();
+
+        let viewport = system.viewport();
+        let (source, destination) = if system.current == 0 {
+            let (a, b) = system.state_buffers.split_at_mut(1);
+            (&a[0], &mut b[0])
+        } else {
+            let (a, b) = system.state_buffers.split_at_mut(1);
+            (&b[0], &mut a[0])
+        };
+        let source_textures = source
+            .color_attachments()
+            .iter()
+            .map(|a| a.texture.clone())
+            .collect::<Vec<_>>();
+
+        let shader = &self.simulation_shader;
+        statistics += destination.draw(
+            &self.quad,
+            ctx.state,
+            viewport,
+            &shader.program,
+            &offscreen_draw_parameters(),
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &texture_matrix(system.texture_size))
+                    .set_texture(&shader.position_texture, &source_textures[0])
+                    .set_texture(&shader.velocity_texture, &source_textures[1])
+                    .set_texture(&shader.parameters_texture, &source_textures[2])
+                    .set_texture(&shader.spawn_texture, &system.spawn_texture)
+                    .set_texture(
+                        &shader.lifetime_curves_texture,
+                        &system.lifetime_curves_texture,
+                    )
+                    .set_texture(&shader.depth_texture, &ctx.depth_texture)
+                    .set_texture(&shader.normal_texture, &ctx.normal_texture)
+                    .set_i32(&shader.texture_size, system.texture_size as i32)
+                    .set_i32(&shader.spawn_start, spawn_start as i32)
+                    .set_i32(&shader.spawn_count, spawn_count as i32)
+                    .set_f32(&shader.elapsed, elapsed)
+                    .set_f32(&shader.steps, ticks as f32)
+                    .set_vector3(&shader.acceleration_offset, &acceleration_offset)
+                    .set_bool(&shader.depth_collision, settings.depth_collision)
+                    .set_matrix4(&shader.world_matrix, &world_matrix)
+                    .set_matrix4(&shader.inv_world_matrix, &inv_world_matrix)
+                    .set_matrix4(&shader.view_projection_matrix, &view_projection)
+                    .set_matrix4(&shader.inv_view_projection_matrix, &inv_view_projection)
+                    .set_vector3(&shader.camera_position, &ctx.camera.global_position())
+                    .set_f32(&shader.restitution, settings.restitution)
+                    .set_f32(&shader.collision_thickness, settings.collision_thickness);
+            },
+        )?;
+
+        system.current = 1 - system.current;
+
+        Ok(statistics)
+    }
+
+    /// Sorts particles back-to-front and returns the index of the sort buffer with the result.
+    fn sort(
+        &self,
+        ctx: &GpuParticleRenderContext,
+        particle_system: &ParticleSystem,
+        system: &mut GpuParticleSystemState,
+    ) -> Result<(usize, RenderPassStatistics), FrameworkError> {
+        let mut statistics = RenderPassStatistics::default();
+
+        let viewport = system.viewport();
+        let matrix = texture_matrix(system.texture_size);
+        let world_matrix = particle_system.global_transform();
+        let camera_position = ctx.camera.global_position();
+        let state_textures = system.state_buffers[system.current]
+            .color_attachments()
+            .iter()
+            .map(|a| a.texture.clone())
+            .collect::<Vec<_>>();
+        let shader = &self.sort_shader;
+
+        let pass = |system: &mut GpuParticleSystemState,
+                    destination: usize,
+                    initialize: bool,
+                    block_size: usize,
+                    stride: usize|
+         -> Result<DrawCallStatistics, FrameworkError> {
+            let source_texture = system.sort_buffers[1 - destination].color_attachments()[0]
+                .texture
+                .clone();
+            let texture_size = system.texture_size;
+            system.sort_buffers[destination].draw(
+                &self.quad,
+                ctx.state,
+                viewport,
+                &shader.program,
+                &offscreen_draw_parameters(),
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&shader.wvp_matrix, &matrix)
+                        .set_texture(&shader.position_texture, &state_textures[0])
+                        .set_texture(&shader.velocity_texture, &state_textures[1])
+                        .set_texture(&shader.sort_texture, &source_texture)
+                        .set_i32(&shader.texture_size, texture_size as i32)
+                        .set_bool(&shader.initialize, initialize)
+                        .set_matrix4(&shader.world_matrix, &world_matrix)
+                        .set_vector3(&shader.camera_position, &camera_position)
+                        .set_i32(&shader.block_size, block_size as i32)
+                        .set_i32(&shader.stride, stride as i32);
+                },
+            )
+        };
+
+        let mut destination = 0;
+        statistics += pass(system, destination, true, 0, 0)?;
+
+        let count = system.texture_size * system.texture_size;
+        let mut block_size = 2;
+        while block_size <= count {
+            let mut stride = block_size / 2;
+            while stride > 0 {
+                destination = 1 - destination;
+                statistics += pass(system, destination, false, block_size, stride)?;
+                stride /= 2;
+            }
+            block_size *= 2;
+        }
+
+        Ok((destination, statistics))
+    }
+
+    pub(crate) fn render(
+        &self,
+        mut ctx: GpuParticleRenderContext,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        scope_profile!();
+
+        let mut statistics = RenderPassStatistics::default();
+
+        for (handle, node) in ctx.graph.pair_iter() {
+            let Some(particle_system) = node.cast::<ParticleSystem>() else {
+                continue;
+            };
+
+            if particle_system.simulation_mode() != ParticleSimulationMode::Gpu
+                || !particle_system.is_globally_enabled()
+                || !particle_system.global_visibility()
+            {
+                continue;
+            }
+
+            let texture_size = particle_system.gpu_settings().texture_size();
+            let mut system = match ctx.systems.remove(&handle) {
+                Some(system) => system,
+                None => GpuParticleSystemState::new(ctx.state, texture_size)?,
+            };
+
+            statistics += self.simulate(&ctx, particle_system, &mut system)?;
+
+            let (sort_buffer, sorted) = if particle_system.gpu_settings().sort {
+                let (sort_buffer, sort_statistics) =
+                    self.sort(&ctx, particle_system, &mut system)?;
+                statistics += sort_statistics;
+                (sort_buffer, true)
+            } else {
+                (0, false)
+            };
+
+            statistics += self.draw(&mut ctx, particle_system, &system, sort_buffer, sorted)?;
+
+            ctx.systems.insert(handle, system);
+        }
+
+        Ok(statistics)
+    }
+
+    fn draw(
+        &self,
+        ctx: &mut GpuParticleRenderContext,
+        particle_system: &ParticleSystem,
+        system: &GpuParticleSystemState,
+        sort_buffer: usize,
+        sorted: bool,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        let mut statistics = RenderPassStatistics::default();
+
+        let (diffuse_texture, soft_boundary_sharpness_factor) = {
+            let mut material_state = particle_system.texture_ref().state();
+            match material_state.data() {
+                Some(material) => (
+                    material
+                        .property_ref(&ImmutableString::new("diffuseTexture"))
+                        .and_then(|p| p.as_sampler()),
+                    material
+                        .property_ref(&ImmutableString::new("softBoundarySharpnessFactor"))
+                        .and_then(|p| p.as_float())
+                        .unwrap_or(100.0),
+                ),
+                None => (None, 100.0),
+            }
+        };
+        let diffuse_texture = diffuse_texture
+            .and_then(|texture| ctx.texture_cache.get(ctx.state, &texture).cloned())
+            .unwrap_or_else(|| ctx.white_dummy.clone());
+
+        let state_textures = system.state_buffers[system.current]
+            .color_attachments()
+            .iter()
+            .map(|a| a.texture.clone())
+            .collect::<Vec<_>>();
+        let sort_texture = system.sort_buffers[sort_buffer].color_attachments()[0]
+            .texture
+            .clone();
+
+        let camera = ctx.camera;
+        let inv_view = camera.inv_view_matrix().unwrap_or_default();
+        let camera_up = inv_view.up();
+        let camera_side = inv_view.side();
+        let shader = &self.render_shader;
+
+        statistics += ctx.framebuffer.draw_instances(
+            system.texture_size * system.texture_size,
+            &self.quad,
+            ctx.state,
+            ctx.viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: true,
+                blend: Some(BlendParameters {
+                    func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+                    ..Default::default()
+                }),
+                stencil_op: Default::default(),
+            },
+            |mut program_binding| {
+                program_binding
+                    .set_texture(&shader.position_texture, &state_textures[0])
+                    .set_texture(&shader.velocity_texture, &state_textures[1])
+                    .set_texture(&shader.parameters_texture, &state_textures[2])
+                    .set_texture(&shader.sort_texture, &sort_texture)
+                    .set_texture(
+                        &shader.color_over_lifetime_texture,
+                        &system.color_over_lifetime_texture,
+                    )
+                    .set_texture(
+                        &shader.lifetime_curves_texture,
+                        &system.lifetime_curves_texture,
+                    )
+                    .set_i32(&shader.texture_size, system.texture_size as i32)
+                    .set_bool(&shader.sorted, sorted)
+                    .set_matrix4(
+                        &shader.view_projection_matrix,
+                        &camera.view_projection_matrix(),
+                    )
+                    .set_matrix4(&shader.world_matrix, &particle_system.global_transform())
+                    .set_vector3(&shader.camera_up_vector, &camera_up)
+                    .set_vector3(&shader.camera_side_vector, &camera_side)
+                    .set_texture(&shader.diffuse_texture, &diffuse_texture)
+                    .set_f32(
+                        &shader.soft_boundary_sharpness_factor,
+                        soft_boundary_sharpness_factor,
+                    )
+                    .set_texture(&shader.scene_depth, &ctx.depth_texture)
+                    .set_f32(&shader.z_near, camera.projection().z_near())
+                    .set_f32(&shader.z_far, camera.projection().z_far());
+            },
+        );
+
+        Ok(statistics)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        renderer::gpu_particles::{pack_spawned, SPAWN_ROW_SIZE},
+        scene::particle_system::particle::Particle,
+    };
+
+    #[test]
+    fn test_pack_spawned() {
+        let particles = (0..SPAWN_ROW_SIZE + 2)
+            .map(|i| {
+                let mut particle = Particle::default();
+                particle.position = Vector3::new(i as f32, 0.0, 0.0);
+                particle.initial_lifetime = 2.0;
+                particle.size = 0.5;
+                particle
+            })
+            .collect::<Vec<_>>();
+
+        let (width, height, texels) = pack_spawned(&particles);
+        assert_eq!(width, SPAWN_ROW_SIZE);
+        assert_eq!(height, 2);
+        assert_eq!(texels.len(), SPAWN_ROW_SIZE * 3 * 2);
+
+        // The first particle of the second row.
+        let base = SPAWN_ROW_SIZE * 3;
+        assert_eq!(texels[base], [SPAWN_ROW_SIZE as f32, 0.0, 0.0, 0.0]);
+        assert_eq!(texels[base + 1][3], 2.0);
+        assert_eq!(texels[base + 2][0], 0.5);
+
+        let (width, height, texels) = pack_spawned(&particles[..3]);
+        assert_eq!((width, height), (3, 1));
+        assert_eq!(texels[6][0], 2.0);
+    }
+}
@@ -18,6 +18,7 @@ pub struct MapShader {
     pub max_luminance: UniformLocation,
     pub auto_exposure: UniformLocation,
     pub fixed_exposure: UniformLocation,
+    pub tone_mapping: UniformLocation,
 }
 
 impl MapShader {
@@ -48,6 +49,7 @@ impl MapShader {
                 .uniform_location(state, &ImmutableString::new("autoExposure"))?,
             fixed_exposure: program
                 .uniform_location(state, &ImmutableString::new("fixedExposure"))?,
+            tone_mapping: program.uniform_location(state, &ImmutableString::new("toneMapping"))?,
             program,
         })
     }
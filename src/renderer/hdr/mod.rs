@@ -24,7 +24,7 @@ use crate::{
         },
         make_viewport_matrix, RenderPassStatistics,
     },
-    scene::camera::{ColorGradingLut, Exposure},
+    scene::camera::{ColorGradingLut, Exposure, ToneMapping},
 };
 use std::{cell::RefCell, rc::Rc};
 
@@ -217,6 +217,7 @@ impl HighDynamicRangeRenderer {
         state: &PipelineState,
         quad: &GeometryBuffer,
         dt: f32,
+        speed: f32,
     ) -> Result<DrawCallStatistics, FrameworkError> {
         let new_lum = self.downscale_chain.last().unwrap().texture();
         let ctx = self.adaptation_chain.begin();
@@ -244,8 +245,7 @@ impl HighDynamicRangeRenderer {
                     .set_matrix4(&shader.wvp_matrix, &matrix)
                     .set_texture(&shader.old_lum_sampler, &prev_lum)
                     .set_texture(&shader.new_lum_sampler, &new_lum)
-                    .set_f32(&shader.speed, 0.3 * dt) // TODO: Make configurable
-                ;
+                    .set_f32(&shader.speed, speed * dt);
             },
         )
     }
@@ -259,6 +259,7 @@ impl HighDynamicRangeRenderer {
         viewport: Rect<i32>,
         quad: &GeometryBuffer,
         exposure: Exposure,
+        tone_mapping: ToneMapping,
         color_grading_lut: Option<&ColorGradingLut>,
         use_color_grading: bool,
         texture_cache: &mut TextureCache,
@@ -296,7 +297,16 @@ impl HighDynamicRangeRenderer {
                         &shader.use_color_grading,
                         use_color_grading && color_grading_lut.is_some(),
                     )
-                    .set_texture(&shader.color_map_sampler, color_grading_lut_tex);
+                    .set_texture(&shader.color_map_sampler, color_grading_lut_tex)
+                    .set_i32(
+                        &shader.tone_mapping,
+                        match tone_mapping {
+                            ToneMapping::Exponential => 0,
+                            ToneMapping::Reinhard => 1,
+                            ToneMapping::Filmic => 2,
+                            ToneMapping::Aces => 3,
+                        },
+                    );
 
                 match exposure {
                     Exposure::Auto {
@@ -330,6 +340,8 @@ impl HighDynamicRangeRenderer {
         quad: &GeometryBuffer,
         dt: f32,
         exposure: Exposure,
+        eye_adaptation_speed: f32,
+        tone_mapping: ToneMapping,
         color_grading_lut: Option<&ColorGradingLut>,
         use_color_grading: bool,
         texture_cache: &mut TextureCache,
@@ -337,7 +349,7 @@ impl HighDynamicRangeRenderer {
         let mut stats = RenderPassStatistics::default();
         stats += self.calculate_frame_luminance(state, hdr_scene_frame.clone(), quad)?;
         stats += self.calculate_avg_frame_luminance(state, quad)?;
-        stats += self.adaptation(state, quad, dt)?;
+        stats += self.adaptation(state, quad, dt, eye_adaptation_speed)?;
         stats += self.map_hdr_to_ldr(
             state,
             hdr_scene_frame,
@@ -346,6 +358,7 @@ impl HighDynamicRangeRenderer {
             viewport,
             quad,
             exposure,
+            tone_mapping,
             color_grading_lut,
             use_color_grading,
             texture_cache,
@@ -25,6 +25,8 @@ use crate::{
             point::PointLightShader, spot::SpotLightShader,
         },
         light_volume::LightVolumeRenderer,
+        probe::ReflectionProbeRenderer,
+        profiler::{GpuPass, GpuProfiler},
         shadow::{
             csm::{CsmRenderContext, CsmRenderer},
             point::{PointShadowMapRenderContext, PointShadowMapRenderer},
@@ -32,6 +34,7 @@ use crate::{
         },
         skybox_shader::SkyboxShader,
         ssao::ScreenSpaceAmbientOcclusionRenderer,
+        ssr::ScreenSpaceReflectionsRenderer,
         storage::MatrixStorageCache,
         GeometryCache, QualitySettings, RenderPassStatistics, TextureCache,
     },
@@ -43,7 +46,7 @@ use crate::{
             surface::SurfaceData,
             vertex::SimpleVertex,
         },
-        Scene,
+        sky, Scene,
     },
 };
 use std::{
@@ -102,6 +105,8 @@ impl Display for LightingStatistics {
 
 pub struct DeferredLightRenderer {
     pub ssao_renderer: ScreenSpaceAmbientOcclusionRenderer,
+    pub ssr_renderer: ScreenSpaceReflectionsRenderer,
+    pub reflection_probe_renderer: ReflectionProbeRenderer,
     spot_light_shader: SpotLightShader,
     point_light_shader: PointLightShader,
     directional_light_shader: DirectionalLightShader,
@@ -132,7 +137,10 @@ pub(crate) struct DeferredRendererContext<'a> {
     pub white_dummy: Rc<RefCell<GpuTexture>>,
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
+    pub environment_dummy: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub debug_cascades: bool,
+    pub gpu_profiler: &'a mut GpuProfiler,
 }
 
 impl DeferredLightRenderer {
@@ -182,6 +190,16 @@ impl DeferredLightRenderer {
                 frame_size.0 as usize,
                 frame_size.1 as usize,
             )?,
+            ssr_renderer: ScreenSpaceReflectionsRenderer::new(
+                state,
+                frame_size.0 as usize,
+                frame_size.1 as usize,
+            )?,
+            reflection_probe_renderer: ReflectionProbeRenderer::new(
+                state,
+                frame_size.0 as usize,
+                frame_size.1 as usize,
+            )?,
             spot_light_shader: SpotLightShader::new(state)?,
             point_light_shader: PointLightShader::new(state)?,
             directional_light_shader: DirectionalLightShader::new(state)?,
@@ -285,6 +303,13 @@ impl DeferredLightRenderer {
             frame_size.0 as usize,
             frame_size.1 as usize,
         )?;
+        self.ssr_renderer = ScreenSpaceReflectionsRenderer::new(
+            state,
+            frame_size.0 as usize,
+            frame_size.1 as usize,
+        )?;
+        self.reflection_probe_renderer =
+            ReflectionProbeRenderer::new(state, frame_size.0 as usize, frame_size.1 as usize)?;
         Ok(())
     }
 
@@ -312,7 +337,10 @@ impl DeferredLightRenderer {
             frame_buffer,
             black_dummy,
             volume_dummy,
+            environment_dummy,
             matrix_storage,
+            debug_cascades,
+            gpu_profiler,
         } = args;
 
         let viewport = Rect::new(0, 0, gbuffer.width, gbuffer.height);
@@ -340,16 +368,21 @@ impl DeferredLightRenderer {
 
         // Fill SSAO map.
         if settings.use_ssao {
+            gpu_profiler.begin(state, GpuPass::Ssao);
             pass_stats += self.ssao_renderer.render(
                 state,
                 gbuffer,
                 projection_matrix,
                 camera.view_matrix().basis(),
             )?;
+            gpu_profiler.end(state);
         }
 
+        // Procedural sky of the scene (if any) replaces the skybox of the camera.
+        let sky_skybox = sky::active_sky(&scene.graph).and_then(|sky| sky.skybox());
+
         // Render skybox (if any).
-        if let Some(skybox) = camera.skybox_ref() {
+        if let Some(skybox) = sky_skybox.or_else(|| camera.skybox_ref()) {
             let size = camera.projection().z_far() / 2.0f32.sqrt();
             let scale = Matrix4::new_scaling(size);
             let wvp = Matrix4::new_translation(&camera.global_position()) * scale;
@@ -509,6 +542,8 @@ impl DeferredLightRenderer {
             let mut light_view_projection = Matrix4::identity();
 
             if shadows_enabled {
+                gpu_profiler.begin(state, GpuPass::Shadows);
+
                 if let Some(spot) = light.cast::<SpotLight>() {
                     let z_near = 0.01;
                     let z_far = light_radius;
@@ -589,6 +624,8 @@ impl DeferredLightRenderer {
 
                     light_stats.csm_rendered += 1;
                 };
+
+                gpu_profiler.end(state);
             }
 
             // Mark lighted areas in stencil buffer to do light calculations only on them.
@@ -825,6 +862,11 @@ impl DeferredLightRenderer {
                             self.csm_renderer.cascades()[2].view_proj_matrix,
                         ];
                         let csm_map_size = self.csm_renderer.size() as f32;
+                        let biases = [
+                            directional.csm_options.effective_cascade_bias(0),
+                            directional.csm_options.effective_cascade_bias(1),
+                            directional.csm_options.effective_cascade_bias(2),
+                        ];
 
                         program_binding
                             .set_vector3(&shader.light_direction, &emit_direction)
@@ -858,7 +900,12 @@ impl DeferredLightRenderer {
                             )
                             .set_f32_slice(&shader.cascade_distances, &distances)
                             .set_matrix4(&shader.view_matrix, &camera.view_matrix())
-                            .set_f32(&shader.shadow_bias, directional.csm_options.shadow_bias())
+                            .set_f32_slice(&shader.shadow_biases, &biases)
+                            .set_i32(
+                                &shader.cascade_count,
+                                directional.csm_options.cascade_count() as i32,
+                            )
+                            .set_bool(&shader.debug_cascades, debug_cascades)
                             .set_bool(&shader.shadows_enabled, shadows_enabled)
                             .set_bool(&shader.soft_shadows, settings.csm_settings.pcf)
                             .set_f32(&shader.shadow_map_inv_size, 1.0 / csm_map_size);
@@ -885,6 +932,51 @@ impl DeferredLightRenderer {
             }
         }
 
+        // Reflections must be rendered when the frame is fully lit.
+        let probe_count = self.reflection_probe_renderer.accumulate(
+            state,
+            gbuffer,
+            camera,
+            &frustum,
+            &scene.graph,
+            textures,
+            &mut pass_stats,
+        )?;
+
+        if settings.ssr_settings.enabled && camera.screen_space_reflections_enabled() {
+            // Use reflection probes and then the sky or environment map as a fallback for the parts
+            // of reflections that are not on screen, if there's no environment map - use skybox.
+            let environment = sky_skybox
+                .and_then(|skybox| skybox.cubemap_ref())
+                .and_then(|cube_map| textures.get(state, cube_map).cloned())
+                .or_else(|| {
+                    camera
+                        .environment_ref()
+                        .and_then(|environment| textures.get(state, environment).cloned())
+                })
+                .or_else(|| {
+                    camera
+                        .skybox_ref()
+                        .and_then(|skybox| skybox.cubemap_ref())
+                        .and_then(|cube_map| textures.get(state, cube_map).cloned())
+                })
+                .unwrap_or(environment_dummy);
+
+            pass_stats += self.ssr_renderer.render(
+                state,
+                gbuffer,
+                frame_buffer,
+                camera,
+                &environment,
+                &self.reflection_probe_renderer.reflection_map(),
+                &settings.ssr_settings,
+            )?;
+        } else if probe_count > 0 {
+            pass_stats +=
+                self.reflection_probe_renderer
+                    .resolve(state, gbuffer, frame_buffer, camera)?;
+        }
+
         Ok((pass_stats, light_stats))
     }
 }
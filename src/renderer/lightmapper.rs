@@ -0,0 +1,684 @@
+//! GPU lightmapper. See [`GpuLightmapper`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3, Vector4},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, Rect},
+        scope_profile,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        probe::cube_map_face_orientations,
+    },
+    resource::texture::TextureResource,
+    scene::mesh::{
+        buffer::{TriangleBuffer, VertexBuffer},
+        surface::SurfaceData,
+        vertex::StaticVertex,
+    },
+    utils::lightmap::{
+        estimate_size, finalize_lightmap, CancellationToken, LightDefinition, Lightmap,
+        LightmapEntry, LightmapGenerationError, PreparedLightmapData, ProgressIndicator,
+        ProgressStage,
+    },
+};
+use fxhash::FxHashMap;
+use std::{cell::RefCell, rc::Rc};
+
+struct LightShader {
+    program: GpuProgram,
+    coverage_pass: UniformLocation,
+    light_kind: UniformLocation,
+    light_color: UniformLocation,
+    light_position: UniformLocation,
+    light_direction: UniformLocation,
+    light_sqr_radius: UniformLocation,
+    spot_edges: UniformLocation,
+    shadow_map: UniformLocation,
+    light_view_projection: UniformLocation,
+    light_eye: UniformLocation,
+    light_forward: UniformLocation,
+    light_far: UniformLocation,
+    is_orthographic: UniformLocation,
+    is_point_face: UniformLocation,
+    shadow_texel_size: UniformLocation,
+}
+
+impl LightShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/lightmap_fs.glsl");
+        let vertex_source = include_str!("shaders/lightmap_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "LightmapShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            coverage_pass: program
+                .uniform_location(state, &ImmutableString::new("coveragePass"))?,
+            light_kind: program.uniform_location(state, &ImmutableString::new("lightKind"))?,
+            light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
+            light_position: program
+                .uniform_location(state, &ImmutableString::new("lightPosition"))?,
+            light_direction: program
+                .uniform_location(state, &ImmutableString::new("lightDirection"))?,
+            light_sqr_radius: program
+                .uniform_location(state, &ImmutableString::new("lightSqrRadius"))?,
+            spot_edges: program.uniform_location(state, &ImmutableString::new("spotEdges"))?,
+            shadow_map: program.uniform_location(state, &ImmutableString::new("shadowMap"))?,
+            light_view_projection: program
+                .uniform_location(state, &ImmutableString::new("lightViewProjection"))?,
+            light_eye: program.uniform_location(state, &ImmutableString::new("lightEye"))?,
+            light_forward: program
+                .uniform_location(state, &ImmutableString::new("lightForward"))?,
+            light_far: program.uniform_location(state, &ImmutableString::new("lightFar"))?,
+            is_orthographic: program
+                .uniform_location(state, &ImmutableString::new("isOrthographic"))?,
+            is_point_face: program.uniform_location(state, &ImmutableString::new("isPointFace"))?,
+            shadow_texel_size: program
+                .uniform_location(state, &ImmutableString::new("shadowTexelSize"))?,
+            program,
+        })
+    }
+}
+
+struct ShadowShader {
+    program: GpuProgram,
+    light_view_projection: UniformLocation,
+    light_eye: UniformLocation,
+    light_forward: UniformLocation,
+    light_far: UniformLocation,
+}
+
+impl ShadowShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/lightmap_shadow_fs.glsl");
+        let vertex_source = include_str!("shaders/lightmap_shadow_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "LightmapShadowShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            light_view_projection: program
+                .uniform_location(state, &ImmutableString::new("lightViewProjection"))?,
+            light_eye: program.uniform_location(state, &ImmutableString::new("lightEye"))?,
+            light_forward: program
+                .uniform_location(state, &ImmutableString::new("lightForward"))?,
+            light_far: program.uniform_location(state, &ImmutableString::new("lightFar"))?,
+            program,
+        })
+    }
+}
+
+/// A view of a light, that is used to render a shadow map. Directional and spot lights have a
+/// single view, point lights have six views - one per each face of a cube.
+#[derive(Debug)]
+struct LightView {
+    view_projection: Matrix4<f32>,
+    eye: Vector3<f32>,
+    forward: Vector3<f32>,
+    z_far: f32,
+    is_orthographic: bool,
+    is_point_face: bool,
+    /// World-space size of a shadow map texel for orthographic views, or the size of a texel per
+    /// unit of distance from the light for perspective views.
+    texel_size: f32,
+}
+
+fn make_perspective_view(
+    eye: Vector3<f32>,
+    forward: Vector3<f32>,
+    up: Vector3<f32>,
+    fov: f32,
+    z_far: f32,
+    is_point_face: bool,
+    shadow_map_size: usize,
+) -> LightView {
+    let z_far = z_far.max(0.02);
+    let view = Matrix4::look_at_rh(&Point3::from(eye), &Point3::from(eye + forward), &up);
+    let projection = Matrix4::new_perspective(1.0, fov, 0.01, z_far);
+    LightView {
+        view_projection: projection * view,
+        eye,
+        forward,
+        z_far,
+        is_orthographic: false,
+        is_point_face,
+        texel_size: 2.0 * (fov * 0.5).tan() / shadow_map_size as f32,
+    }
+}
+
+fn any_perpendicular_up(forward: Vector3<f32>) -> Vector3<f32> {
+    if forward.y.abs() > 0.99 {
+        Vector3::z()
+    } else {
+        Vector3::y()
+    }
+}
+
+fn light_views(
+    light: &LightDefinition,
+    bounds: &AxisAlignedBoundingBox,
+    shadow_map_size: usize,
+) -> Vec<LightView> {
+    match light {
+        LightDefinition::Directional(directional) => {
+            // Orthographic view that covers the entire geometry.
+            let center = bounds.center();
+            let radius = bounds.half_extents().norm().max(0.01);
+            let forward = -directional.direction;
+            let eye = center - forward.scale(2.0 * radius);
+            let view = Matrix4::look_at_rh(
+                &Point3::from(eye),
+                &Point3::from(center),
+                &any_perpendicular_up(forward),
+            );
+            let projection =
+                Matrix4::new_orthographic(-radius, radius, -radius, radius, 0.0, 4.0 * radius);
+            vec![LightView {
+                view_projection: projection * view,
+                eye,
+                forward,
+                z_far: 4.0 * radius,
+                is_orthographic: true,
+                is_point_face: false,
+                texel_size: 2.0 * radius / shadow_map_size as f32,
+            }]
+        }
+        LightDefinition::Spot(spot) => {
+            let forward = -spot.direction;
+            let fov =
+                (2.0 * spot.edge0.clamp(-1.0, 1.0).acos()).clamp(0.01, std::f32::consts::PI - 0.01);
+            vec![make_perspective_view(
+                spot.position,
+                forward,
+                any_perpendicular_up(forward),
+                fov,
+                spot.distance,
+                false,
+                shadow_map_size,
+            )]
+        }
+        LightDefinition::Point(point) => cube_map_face_orientations()
+            .into_iter()
+            .map(|(look, up)| {
+                make_perspective_view(
+                    point.position,
+                    look,
+                    up,
+                    std::f32::consts::FRAC_PI_2,
+                    point.radius,
+                    true,
+                    shadow_map_size,
+                )
+            })
+            .collect(),
+    }
+}
+
+struct GpuInstance {
+    geometry: GeometryBuffer,
+    atlas: FrameBuffer,
+    atlas_size: u32,
+}
+
+/// GPU lightmapper calculates lighting of [`PreparedLightmapData`] using graphics hardware, which is
+/// orders of magnitude faster than the CPU lightmapper ([`PreparedLightmapData::bake`]) on large
+/// scenes. It works as follows:
+///
+/// 1) Every instance is rasterized in the space of its secondary texture coordinates into its own
+///    lightmap atlas, so every fragment corresponds to a texel of the lightmap.
+/// 2) For every light, a shadow map is rendered for each view of the light (point lights have six
+///    views), then lighting of the light is added to every atlas with the help of the shadow map.
+/// 3) The atlases are read back, empty texels are filled and the result is blurred, exactly as the
+///    CPU lightmapper does.
+///
+/// Lights are baked one-by-one with [`Self::step`], so the baking could be spread across multiple
+/// frames, cancelled at any time using a [`CancellationToken`], and its intermediate result could be
+/// previewed using [`Self::lightmap`].
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use fyrox::{
+/// #     renderer::{framework::state::PipelineState, lightmapper::GpuLightmapper},
+/// #     scene::Scene,
+/// #     utils::lightmap::{Lightmap, LightmapGenerationError, LightmapInputData},
+/// # };
+/// fn bake(scene: &Scene, state: &PipelineState) -> Result<Lightmap, LightmapGenerationError> {
+///     let data = LightmapInputData::from_scene(
+///         scene,
+///         |_, _| true,
+///         Default::default(),
+///         Default::default(),
+///     )?
+///     .prepare(64, 0.005, Default::default(), Default::default())?;
+///
+///     let mut lightmapper =
+///         GpuLightmapper::new(state, data, 2048, Default::default(), Default::default())?;
+///     while !lightmapper.is_finished() {
+///         lightmapper.step(state)?;
+///     }
+///     lightmapper.lightmap(state)
+/// }
+/// ```
+pub struct GpuLightmapper {
+    data: PreparedLightmapData,
+    light_shader: LightShader,
+    shadow_shader: ShadowShader,
+    instances: Vec<GpuInstance>,
+    shadow_map: FrameBuffer,
+    shadow_map_size: usize,
+    bounds: AxisAlignedBoundingBox,
+    current_light: usize,
+    cancellation_token: CancellationToken,
+    progress_indicator: ProgressIndicator,
+}
+
+impl GpuLightmapper {
+    /// Creates a new GPU lightmapper for the given data. `shadow_map_size` defines the resolution of
+    /// shadow maps, that are used to calculate shadows of every light; the larger the value, the
+    /// sharper shadows will be.
+    pub fn new(
+        state: &PipelineState,
+        data: PreparedLightmapData,
+        shadow_map_size: usize,
+        cancellation_token: CancellationToken,
+        progress_indicator: ProgressIndicator,
+    ) -> Result<Self, LightmapGenerationError> {
+        scope_profile!();
+
+        let shadow_map_size = shadow_map_size.max(1);
+        let light_shader = LightShader::new(state)?;
+        let shadow_shader = ShadowShader::new(state)?;
+
+        let mut bounds = AxisAlignedBoundingBox::default();
+        let mut instances = Vec::with_capacity(data.instances.len());
+        for instance in data.instances.iter() {
+            let instance_data = instance.data();
+
+            let vertices = instance_data
+                .vertices
+                .iter()
+                .map(|vertex| {
+                    bounds.add_point(vertex.world_position);
+
+                    StaticVertex {
+                        position: vertex.world_position,
+                        tex_coord: vertex.second_tex_coord,
+                        normal: vertex.world_normal,
+                        tangent: Vector4::default(),
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let surface_data = SurfaceData::new(
+                VertexBuffer::new(vertices.len(), vertices).unwrap(),
+                TriangleBuffer::new(instance_data.triangles.clone()),
+                false,
+            );
+
+            let atlas_size = estimate_size(instance_data, data.texels_per_unit).max(1);
+
+            let mut atlas = FrameBuffer::new(
+                state,
+                None,
+                vec![Attachment {
+                    kind: AttachmentKind::Color,
+                    texture: Rc::new(RefCell::new(GpuTexture::new(
+                        state,
+                        GpuTextureKind::Rectangle {
+                            width: atlas_size as usize,
+                            height: atlas_size as usize,
+                        },
+                        PixelKind::RGBA8,
+                        MinificationFilter::Nearest,
+                        MagnificationFilter::Nearest,
+                        1,
+                        None,
+                    )?)),
+                }],
+            )?;
+
+            let geometry = GeometryBuffer::from_surface_data(
+                &surface_data,
+                GeometryBufferKind::StaticDraw,
+                state,
+            )?;
+
+            // Mark texels, that are covered by triangles.
+            let viewport = Rect::new(0, 0, atlas_size as i32, atlas_size as i32);
+            atlas.clear(state, viewport, Some(Color::TRANSPARENT), None, None);
+            let shader = &light_shader;
+            atlas.draw(
+                &geometry,
+                state,
+                viewport,
+                &shader.program,
+                &DrawParameters {
+                    cull_face: None,
+                    depth_write: false,
+                    depth_test: false,
+                    ..Default::default()
+                },
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding.set_bool(&shader.coverage_pass, true);
+                },
+            )?;
+
+            instances.push(GpuInstance {
+                geometry,
+                atlas,
+                atlas_size,
+            });
+        }
+
+        if !bounds.is_valid() {
+            bounds = AxisAlignedBoundingBox::unit();
+        }
+
+        let mut shadow_map_texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle {
+                width: shadow_map_size,
+                height: shadow_map_size,
+            },
+            PixelKind::R32F,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        shadow_map_texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        let shadow_map = FrameBuffer::new(
+            state,
+            Some(Attachment {
+                kind: AttachmentKind::Depth,
+                texture: Rc::new(RefCell::new(GpuTexture::new(
+                    state,
+                    GpuTextureKind::Rectangle {
+                        width: shadow_map_size,
+                        height: shadow_map_size,
+                    },
+                    PixelKind::D32F,
+                    MinificationFilter::Nearest,
+                    MagnificationFilter::Nearest,
+                    1,
+                    None,
+                )?)),
+            }),
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: Rc::new(RefCell::new(shadow_map_texture)),
+            }],
+        )?;
+
+        progress_indicator.set_stage(ProgressStage::CalculatingLight, data.lights.len() as u32);
+
+        Ok(Self {
+            data,
+            light_shader,
+            shadow_shader,
+            instances,
+            shadow_map,
+            shadow_map_size,
+            bounds,
+            current_light: 0,
+            cancellation_token,
+            progress_indicator,
+        })
+    }
+
+    /// Returns `true` if every light was baked, `false` - otherwise.
+    pub fn is_finished(&self) -> bool {
+        self.current_light >= self.data.lights.len()
+    }
+
+    /// Bakes next light. Does nothing if every light was baked already. Returns
+    /// [`LightmapGenerationError::Cancelled`] if the baking was cancelled.
+    pub fn step(&mut self, state: &PipelineState) -> Result<(), LightmapGenerationError> {
+        scope_profile!();
+
+        if self.cancellation_token.is_cancelled() {
+            return Err(LightmapGenerationError::Cancelled);
+        }
+
+        let Some(light) = self.data.lights.get(self.current_light) else {
+            return Ok(());
+        };
+
+        let (kind, color, intensity, position, direction, sqr_radius, spot_edges) = match light {
+            LightDefinition::Directional(directional) => (
+                0,
+                directional.color,
+                directional.intensity,
+                Vector3::default(),
+                directional.direction,
+                1.0,
+                Vector2::default(),
+            ),
+            LightDefinition::Spot(spot) => (
+                1,
+                spot.color,
+                spot.intensity,
+                spot.position,
+                spot.direction,
+                spot.sqr_distance,
+                Vector2::new(spot.edge0, spot.edge1),
+            ),
+            LightDefinition::Point(point) => (
+                2,
+                point.color,
+                point.intensity,
+                point.position,
+                Vector3::default(),
+                point.sqr_radius,
+                Vector2::default(),
+            ),
+        };
+        let light_color = color.scale(intensity);
+
+        let shadow_map_viewport = Rect::new(
+            0,
+            0,
+            self.shadow_map_size as i32,
+            self.shadow_map_size as i32,
+        );
+        let shadow_map_texture = self.shadow_map.color_attachments()[0].texture.clone();
+
+        for view in light_views(light, &self.bounds, self.shadow_map_size) {
+            self.shadow_map.clear(
+                state,
+                shadow_map_viewport,
+                Some(Color::WHITE),
+                Some(1.0),
+                None,
+            );
+
+            let shader = &self.shadow_shader;
+            for instance in self.instances.iter() {
+                self.shadow_map.draw(
+                    &instance.geometry,
+                    state,
+                    shadow_map_viewport,
+                    &shader.program,
+                    &DrawParameters {
+                        cull_face: None,
+                        ..Default::default()
+                    },
+                    ElementRange::Full,
+                    |mut program_binding| {
+                        program_binding
+                            .set_matrix4(&shader.light_view_projection, &view.view_projection)
+                            .set_vector3(&shader.light_eye, &view.eye)
+                            .set_vector3(&shader.light_forward, &view.forward)
+                            .set_f32(&shader.light_far, view.z_far);
+                    },
+                )?;
+            }
+
+            let shader = &self.light_shader;
+            for instance in self.instances.iter_mut() {
+                let viewport =
+                    Rect::new(0, 0, instance.atlas_size as i32, instance.atlas_size as i32);
+                instance.atlas.draw(
+                    &instance.geometry,
+                    state,
+                    viewport,
+                    &shader.program,
+                    &DrawParameters {
+                        cull_face: None,
+                        depth_write: false,
+                        depth_test: false,
+                        blend: Some(BlendParameters {
+                            func: BlendFunc::new(BlendFactor::One, BlendFactor::One),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                    ElementRange::Full,
+                    |mut program_binding| {
+                        program_binding
+                            .set_bool(&shader.coverage_pass, false)
+                            .set_i32(&shader.light_kind, kind)
+                            .set_vector3(&shader.light_color, &light_color)
+                            .set_vector3(&shader.light_position, &position)
+                            .set_vector3(&shader.light_direction, &direction)
+                            .set_f32(&shader.light_sqr_radius, sqr_radius)
+                            .set_vector2(&shader.spot_edges, &spot_edges)
+                            .set_texture(&shader.shadow_map, &shadow_map_texture)
+                            .set_matrix4(&shader.light_view_projection, &view.view_projection)
+                            .set_vector3(&shader.light_eye, &view.eye)
+                            .set_vector3(&shader.light_forward, &view.forward)
+                            .set_f32(&shader.light_far, view.z_far)
+                            .set_bool(&shader.is_orthographic, view.is_orthographic)
+                            .set_bool(&shader.is_point_face, view.is_point_face)
+                            .set_f32(&shader.shadow_texel_size, view.texel_size);
+                    },
+                )?;
+            }
+        }
+
+        self.current_light += 1;
+        self.progress_indicator.advance_progress();
+
+        Ok(())
+    }
+
+    /// Reads back the lightmap atlases and creates a lightmap from them. This method could be used
+    /// at any time to preview intermediate results, it contains the lighting of every light, that
+    /// was baked so far.
+    pub fn lightmap(&self, state: &PipelineState) -> Result<Lightmap, LightmapGenerationError> {
+        scope_profile!();
+
+        let lights = self
+            .data
+            .lights
+            .iter()
+            .map(|light| light.handle())
+            .collect::<Vec<_>>();
+
+        let mut map: FxHashMap<_, Vec<LightmapEntry>> = FxHashMap::default();
+        for (instance, gpu_instance) in self.data.instances.iter().zip(self.instances.iter()) {
+            let bytes = gpu_instance.atlas.read_pixels(state).ok_or_else(|| {
+                FrameworkError::Custom("Unable to read pixels of a lightmap!".to_string())
+            })?;
+            let pixels = bytes
+                .chunks_exact(4)
+                .map(|pixel| Vector4::new(pixel[0], pixel[1], pixel[2], pixel[3]))
+                .collect::<Vec<_>>();
+            let texture = finalize_lightmap(&pixels, gpu_instance.atlas_size);
+
+            map.entry(instance.owner).or_default().push(LightmapEntry {
+                texture: Some(TextureResource::new_ok(Default::default(), texture)),
+                lights: lights.clone(),
+            });
+        }
+
+        Ok(Lightmap {
+            map,
+            patches: self.data.patches.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Point3, Vector3},
+            math::aabb::AxisAlignedBoundingBox,
+            pool::Handle,
+        },
+        renderer::lightmapper::light_views,
+        utils::lightmap::{DirectionalLightDefinition, LightDefinition, PointLightDefinition},
+    };
+
+    #[test]
+    fn test_light_views() {
+        let bounds = AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-10.0, -1.0, -3.0),
+            Vector3::new(4.0, 5.0, 6.0),
+        );
+
+        // Orthographic view of a directional light must contain the entire geometry.
+        let views = light_views(
+            &LightDefinition::Directional(DirectionalLightDefinition {
+                handle: Handle::NONE,
+                intensity: 1.0,
+                direction: Vector3::new(1.0, 2.0, 0.5).normalize(),
+                color: Vector3::repeat(1.0),
+            }),
+            &bounds,
+            1024,
+        );
+        assert_eq!(views.len(), 1);
+        let view = &views[0];
+        assert!(view.is_orthographic);
+        for corner in bounds.corners() {
+            let projected = view.view_projection.transform_point(&Point3::from(corner));
+            assert!(projected.coords.iter().all(|c| (-1.0..=1.0).contains(c)));
+        }
+
+        // Point light has six views, one per each axis.
+        let views = light_views(
+            &LightDefinition::Point(PointLightDefinition {
+                handle: Handle::NONE,
+                intensity: 1.0,
+                position: Vector3::new(1.0, 2.0, 3.0),
+                color: Vector3::repeat(1.0),
+                radius: 5.0,
+                sqr_radius: 25.0,
+            }),
+            &bounds,
+            1024,
+        );
+        assert_eq!(views.len(), 6);
+        for view in views.iter() {
+            assert!(!view.is_orthographic && view.is_point_face);
+            // A point along the view direction must be in the center of the view.
+            let projected = view
+                .view_projection
+                .transform_point(&Point3::from(view.eye + view.forward.scale(2.0)));
+            assert!(projected.x.abs() < 0.0001 && projected.y.abs() < 0.0001);
+        }
+    }
+}
@@ -15,7 +15,12 @@ pub mod framework;
 
 pub mod batch;
 pub mod cache;
+pub mod cluster;
 pub mod debug_renderer;
+pub mod lightmapper;
+pub mod occlusion;
+pub mod profiler;
+pub mod render_graph;
 pub mod storage;
 pub mod ui_renderer;
 
@@ -24,12 +29,16 @@ mod flat_shader;
 mod forward_renderer;
 mod fxaa;
 mod gbuffer;
+mod gpu_particles;
 mod hdr;
 mod light;
 mod light_volume;
+mod probe;
 mod shadow;
 mod skybox_shader;
 mod ssao;
+mod ssr;
+mod taa;
 
 use crate::renderer::cache::texture::TextureRenderData;
 
@@ -38,7 +47,7 @@ use crate::renderer::framework::state::SharedPipelineState;
 use crate::{
     asset::{event::ResourceEvent, manager::ResourceManager},
     core::{
-        algebra::{Matrix4, Vector2, Vector3},
+        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
         color::Color,
         instant,
         log::{Log, MessageKind},
@@ -48,6 +57,7 @@ use crate::{
         scope_profile,
         sstorage::ImmutableString,
     },
+    graph::SceneGraph,
     gui::draw::DrawingContext,
     material::{
         shader::{SamplerFallback, Shader, ShaderResource, ShaderResourceExtension},
@@ -57,6 +67,7 @@ use crate::{
         batch::{ObserverInfo, PersistentIdentifier, RenderDataBatchStorage},
         bloom::BloomRenderer,
         cache::{geometry::GeometryCache, shader::ShaderCache, texture::TextureCache},
+        cluster::{LightClusterGrid, LightClusterStorage},
         debug_renderer::DebugRenderer,
         flat_shader::FlatShader,
         forward_renderer::{ForwardRenderContext, ForwardRenderer},
@@ -75,13 +86,29 @@ use crate::{
         },
         fxaa::FxaaRenderer,
         gbuffer::{GBuffer, GBufferRenderContext},
+        gpu_particles::{GpuParticleRenderContext, GpuParticleRenderer, GpuParticleSystemState},
         hdr::HighDynamicRangeRenderer,
         light::{DeferredLightRenderer, DeferredRendererContext, LightingStatistics},
+        occlusion::OcclusionBuffer,
+        profiler::{GpuPass, GpuPassTimings, GpuProfiler},
+        render_graph::{
+            RenderGraph, RenderGraphPassContext, RenderGraphStage, RenderGraphTargetStorage,
+        },
         storage::MatrixStorageCache,
+        taa::{InstanceHistory, TaaHistory, TemporalAntiAliasingRenderer},
         ui_renderer::{UiRenderContext, UiRenderer},
     },
-    resource::texture::{Texture, TextureKind, TextureResource},
-    scene::{camera::Camera, mesh::surface::SurfaceData, Scene, SceneContainer},
+    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+    scene::{
+        base::BaseBuilder,
+        camera::{
+            Camera, CameraBuilder, Exposure, PerspectiveProjection, Projection, SkyBox, SkyBoxKind,
+        },
+        debug::SceneDrawingContext,
+        mesh::surface::SurfaceData,
+        node::Node,
+        sky, Scene, SceneContainer,
+    },
 };
 use fxhash::FxHashMap;
 use fyrox_core::algebra::Vector4;
@@ -139,6 +166,11 @@ pub struct Statistics {
     pub capped_frame_time: f32,
     /// Total amount of frames been rendered in one second.
     pub frames_per_second: usize,
+    /// Amount of objects, that were skipped by occlusion culling.
+    pub occluded_objects: usize,
+    /// GPU time spent on each render pass. It is filled only if GPU profiling is enabled, see
+    /// [`Renderer::set_gpu_profiling_enabled`] for more info. The timings are a few frames old.
+    pub gpu_timings: GpuPassTimings,
     frame_counter: usize,
     frame_start_time: instant::Instant,
     last_fps_commit_time: instant::Instant,
@@ -151,12 +183,14 @@ impl Display for Statistics {
             "FPS: {}\n\
             Pure Frame Time: {:.2} ms\n\
             Capped Frame Time: {:.2} ms\n\
+            Occluded Objects: {}\n\
             {}\n\
             {}\n\
             {}\n",
             self.frames_per_second,
             self.pure_frame_time * 1000.0,
             self.capped_frame_time * 1000.0,
+            self.occluded_objects,
             self.geometry,
             self.lighting,
             self.pipeline
@@ -171,6 +205,10 @@ pub struct RenderPassStatistics {
     pub draw_calls: usize,
     /// Amount of triangles per frame.
     pub triangles_rendered: usize,
+    /// Amount of batches that were rendered using instancing (a single draw call per batch).
+    pub instanced_batches: usize,
+    /// Total amount of instances, that were rendered using instancing.
+    pub instances_rendered: usize,
 }
 
 impl Display for RenderPassStatistics {
@@ -178,8 +216,13 @@ impl Display for RenderPassStatistics {
         write!(
             f,
             "Draw Calls: {}\n\
-            Triangles Rendered: {}",
-            self.draw_calls, self.triangles_rendered
+            Triangles Rendered: {}\n\
+            Instanced Batches: {}\n\
+            Instances Rendered: {}",
+            self.draw_calls,
+            self.triangles_rendered,
+            self.instanced_batches,
+            self.instances_rendered
         )
     }
 }
@@ -188,6 +231,8 @@ impl std::ops::AddAssign for RenderPassStatistics {
     fn add_assign(&mut self, rhs: Self) {
         self.draw_calls += rhs.draw_calls;
         self.triangles_rendered += rhs.triangles_rendered;
+        self.instanced_batches += rhs.instanced_batches;
+        self.instances_rendered += rhs.instances_rendered;
     }
 }
 
@@ -260,6 +305,40 @@ impl Default for CsmSettings {
     }
 }
 
+/// Screen-space reflections settings.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct SsrSettings {
+    /// Whether screen-space reflections enabled or not. Each camera could also disable reflections
+    /// for itself, see [`Camera::set_screen_space_reflections_enabled`].
+    pub enabled: bool,
+
+    /// Maximum amount of steps of a reflected ray. The more steps, the more precise reflections are,
+    /// but the worse performance is.
+    pub max_steps: usize,
+
+    /// Maximum distance (in meters) that a reflected ray could travel.
+    pub max_distance: f32,
+
+    /// Thickness (in meters) of every object on screen. A ray is considered to hit an object only if
+    /// it is behind the object's surface, but no further than this value.
+    pub thickness: f32,
+}
+
+impl Default for SsrSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_steps: 32,
+            max_distance: 10.0,
+            thickness: 0.5,
+        }
+    }
+}
+
+fn default_use_clustered_lighting() -> bool {
+    true
+}
+
 /// Quality settings allows you to find optimal balance between performance and
 /// graphics quality.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Reflect)]
@@ -315,6 +394,21 @@ pub struct QualitySettings {
 
     /// Whether to use bloom effect.
     pub use_bloom: bool,
+
+    /// Whether to use occlusion culling or not. See [`occlusion::OcclusionBuffer`] docs for more
+    /// info.
+    #[serde(default)]
+    pub use_occlusion_culling: bool,
+
+    /// Whether to use clustered lighting in forward rendering or not. Clustered lighting allows to
+    /// have hundreds of lights affecting forward-rendered objects (such as sprites), otherwise only
+    /// first 16 visible lights are used. See [`cluster::LightClusterGrid`] docs for more info.
+    #[serde(default = "default_use_clustered_lighting")]
+    pub use_clustered_lighting: bool,
+
+    /// Screen-space reflections settings.
+    #[serde(default)]
+    pub ssr_settings: SsrSettings,
 }
 
 impl Default for QualitySettings {
@@ -351,9 +445,20 @@ impl QualitySettings {
 
             use_bloom: true,
 
+            use_occlusion_culling: true,
+
+            use_clustered_lighting: true,
+
             use_parallax_mapping: true,
 
             csm_settings: Default::default(),
+
+            ssr_settings: SsrSettings {
+                enabled: true,
+                max_steps: 64,
+                max_distance: 20.0,
+                thickness: 0.5,
+            },
         }
     }
 
@@ -384,6 +489,10 @@ impl QualitySettings {
 
             use_bloom: true,
 
+            use_occlusion_culling: true,
+
+            use_clustered_lighting: true,
+
             use_parallax_mapping: true,
 
             csm_settings: CsmSettings {
@@ -392,6 +501,8 @@ impl QualitySettings {
                 precision: ShadowMapPrecision::Full,
                 pcf: true,
             },
+
+            ssr_settings: Default::default(),
         }
     }
 
@@ -422,6 +533,10 @@ impl QualitySettings {
 
             use_bloom: true,
 
+            use_occlusion_culling: true,
+
+            use_clustered_lighting: true,
+
             use_parallax_mapping: false,
 
             csm_settings: CsmSettings {
@@ -430,6 +545,11 @@ impl QualitySettings {
                 precision: ShadowMapPrecision::Full,
                 pcf: false,
             },
+
+            ssr_settings: SsrSettings {
+                enabled: false,
+                ..Default::default()
+            },
         }
     }
 
@@ -460,6 +580,10 @@ impl QualitySettings {
 
             use_bloom: false,
 
+            use_occlusion_culling: false,
+
+            use_clustered_lighting: true,
+
             use_parallax_mapping: false,
 
             csm_settings: CsmSettings {
@@ -468,6 +592,11 @@ impl QualitySettings {
                 precision: ShadowMapPrecision::Half,
                 pcf: false,
             },
+
+            ssr_settings: SsrSettings {
+                enabled: false,
+                ..Default::default()
+            },
         }
     }
 }
@@ -478,6 +607,7 @@ impl Statistics {
         self.frame_start_time = instant::Instant::now();
         self.geometry = Default::default();
         self.lighting = Default::default();
+        self.occluded_objects = 0;
     }
 
     /// Must be called before SwapBuffers but after all rendering is done.
@@ -517,6 +647,8 @@ impl Default for Statistics {
             pure_frame_time: 0.0,
             capped_frame_time: 0.0,
             frames_per_second: 0,
+            occluded_objects: 0,
+            gpu_timings: Default::default(),
             frame_counter: 0,
             frame_start_time: instant::Instant::now(),
             last_fps_commit_time: instant::Instant::now(),
@@ -545,6 +677,15 @@ pub struct AssociatedSceneData {
     /// Bloom contains only overly bright pixels that creates light
     /// bleeding effect (glow effect).
     pub bloom_renderer: BloomRenderer,
+
+    /// Histories of temporal anti-aliasing of every camera of the scene that uses it.
+    pub(crate) taa_histories: FxHashMap<Handle<Node>, TaaHistory>,
+
+    /// Custom render targets of the render graph.
+    pub(crate) render_graph_targets: RenderGraphTargetStorage,
+
+    /// GPU state of every particle system of the scene that is simulated on GPU.
+    pub(crate) gpu_particle_systems: FxHashMap<Handle<Node>, GpuParticleSystemState>,
 }
 
 impl AssociatedSceneData {
@@ -642,6 +783,9 @@ impl AssociatedSceneData {
             hdr_scene_framebuffer,
             ldr_scene_framebuffer,
             ldr_temp_framebuffer,
+            taa_histories: Default::default(),
+            render_graph_targets: Default::default(),
+            gpu_particle_systems: Default::default(),
         })
     }
 
@@ -704,6 +848,8 @@ pub(crate) fn make_viewport_matrix(viewport: Rect<i32>) -> Matrix4<f32> {
 pub struct Renderer {
     backbuffer: FrameBuffer,
     scene_render_passes: Vec<Rc<RefCell<dyn SceneRenderPass>>>,
+    render_graph: RenderGraph,
+    gpu_profiler: GpuProfiler,
     deferred_light_renderer: DeferredLightRenderer,
     flat_shader: FlatShader,
     /// Dummy white one pixel texture which will be used as stub when rendering
@@ -725,6 +871,12 @@ pub struct Renderer {
     quad: GeometryBuffer,
     frame_size: (u32, u32),
     quality_settings: QualitySettings,
+    occlusion_buffer: OcclusionBuffer,
+    debug_occlusion_culling: bool,
+    light_cluster_grid: LightClusterGrid,
+    light_cluster_storage: LightClusterStorage,
+    debug_light_clusters: bool,
+    debug_cascades: bool,
     /// Debug renderer instance can be used for debugging purposes
     pub debug_renderer: DebugRenderer,
     /// A set of associated data for each scene that was rendered.
@@ -736,6 +888,9 @@ pub struct Renderer {
     geometry_cache: GeometryCache,
     forward_renderer: ForwardRenderer,
     fxaa_renderer: FxaaRenderer,
+    gpu_particle_renderer: GpuParticleRenderer,
+    taa_renderer: TemporalAntiAliasingRenderer,
+    instance_history: InstanceHistory,
     texture_event_receiver: Receiver<ResourceEvent>,
     shader_event_receiver: Receiver<ResourceEvent>,
     matrix_storage: MatrixStorageCache,
@@ -969,6 +1124,20 @@ impl<const N: usize> Default for LightData<N> {
     }
 }
 
+/// Data of the previous frame of a surface instance. It is used to calculate per-pixel velocities
+/// for temporal anti-aliasing.
+#[derive(Copy, Clone)]
+pub struct MotionData<'a> {
+    /// World matrix of the instance in the previous frame.
+    pub prev_world_matrix: &'a Matrix4<f32>,
+    /// Bone matrices of the instance in the previous frame.
+    pub prev_bone_matrices: &'a [Matrix4<f32>],
+    /// View-projection matrix of the camera in the previous frame (without jitter).
+    pub prev_view_projection_matrix: &'a Matrix4<f32>,
+    /// View-projection matrix of the camera in the current frame (without jitter).
+    pub unjittered_view_projection_matrix: &'a Matrix4<f32>,
+}
+
 #[allow(missing_docs)] // TODO
 pub struct MaterialContext<'a, 'b, 'c> {
     pub material: &'a Material,
@@ -988,10 +1157,19 @@ pub struct MaterialContext<'a, 'b, 'c> {
     pub blend_shapes_storage: Option<&'a TextureResource>,
     pub blend_shapes_weights: &'a [f32],
     pub light_data: Option<&'a LightData>,
+    /// Clustered lighting data, see [`cluster::LightClusterGrid`] docs for more info.
+    pub light_clusters: Option<&'a LightClusterStorage>,
+    /// Data of the previous frame, if `None` - the data of the current frame is used, which means
+    /// that velocities will be zero.
+    pub motion: Option<MotionData<'a>>,
     pub ambient_light: Color,
     // TODO: Add depth pre-pass to remove Option here. Current architecture allows only forward
     // renderer to have access to depth buffer that is available from G-Buffer.
     pub scene_depth: Option<&'a Rc<RefCell<GpuTexture>>>,
+    /// Per-instance data packed by [`batch::RenderDataBatch::instance_matrices`]. Must be empty if
+    /// the draw call is not instanced.
+    pub instance_matrices: &'a [Matrix4<f32>],
+    pub instance_color: Color,
 
     pub camera_position: &'a Vector3<f32>,
     pub camera_up_vector: &'a Vector3<f32>,
@@ -1036,6 +1214,29 @@ pub fn apply_material(ctx: MaterialContext) {
 
         ctx.program_binding.set_texture(location, storage.texture());
     }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::InstanceMatrices as usize] {
+        let active_sampler = ctx.program_binding.active_sampler();
+
+        let storage = ctx
+            .matrix_storage
+            .try_bind_and_upload(
+                ctx.program_binding.state,
+                ctx.persistent_identifier,
+                ctx.instance_matrices,
+                active_sampler,
+            )
+            .expect("Failed to upload instance matrices!");
+
+        ctx.program_binding.set_texture(location, storage.texture());
+    }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::UseInstancing as usize] {
+        ctx.program_binding
+            .set_bool(location, !ctx.instance_matrices.is_empty());
+    }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::InstanceColor as usize] {
+        ctx.program_binding
+            .set_srgb_color(location, &ctx.instance_color);
+    }
     if let Some(location) = &built_in_uniforms[BuiltInUniform::UseSkeletalAnimation as usize] {
         ctx.program_binding
             .set_bool(location, ctx.use_skeletal_animation);
@@ -1101,11 +1302,99 @@ pub fn apply_material(ctx: MaterialContext) {
         }
     }
 
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::UseClusteredLighting as usize] {
+        ctx.program_binding
+            .set_bool(location, ctx.light_clusters.is_some());
+    }
+
+    if let Some(light_clusters) = ctx.light_clusters {
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::ClusterLights as usize] {
+            ctx.program_binding
+                .set_texture(location, &light_clusters.lights);
+        }
+
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::ClusterGrid as usize] {
+            ctx.program_binding
+                .set_texture(location, &light_clusters.grid);
+        }
+
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::ClusterLightIndices as usize] {
+            ctx.program_binding
+                .set_texture(location, &light_clusters.light_indices);
+        }
+
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::ClusterParams as usize] {
+            ctx.program_binding
+                .set_vector4(location, &light_clusters.params);
+        }
+
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::ClusterDepthAxis as usize] {
+            ctx.program_binding
+                .set_vector3(location, &light_clusters.depth_axis);
+        }
+    } else {
+        // Samplers must be bound to something anyway.
+        for uniform in [
+            BuiltInUniform::ClusterLights,
+            BuiltInUniform::ClusterGrid,
+            BuiltInUniform::ClusterLightIndices,
+        ] {
+            if let Some(location) = &built_in_uniforms[uniform as usize] {
+                ctx.program_binding.set_texture(location, ctx.black_dummy);
+            }
+        }
+    }
+
     if let Some(location) = &built_in_uniforms[BuiltInUniform::AmbientLight as usize] {
         ctx.program_binding
             .set_srgb_color(location, &ctx.ambient_light);
     }
 
+    let (motion, prev_bones_identifier) = match ctx.motion {
+        Some(motion) => (motion, ctx.persistent_identifier.previous_frame()),
+        None => (
+            MotionData {
+                prev_world_matrix: ctx.world_matrix,
+                prev_bone_matrices: ctx.bone_matrices,
+                prev_view_projection_matrix: ctx.view_projection_matrix,
+                unjittered_view_projection_matrix: ctx.view_projection_matrix,
+            },
+            // Share the storage with current bone matrices.
+            ctx.persistent_identifier,
+        ),
+    };
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::PreviousWorldMatrix as usize] {
+        ctx.program_binding
+            .set_matrix4(location, motion.prev_world_matrix);
+    }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::PreviousBoneMatrices as usize] {
+        let active_sampler = ctx.program_binding.active_sampler();
+
+        let storage = ctx
+            .matrix_storage
+            .try_bind_and_upload(
+                ctx.program_binding.state,
+                prev_bones_identifier,
+                motion.prev_bone_matrices,
+                active_sampler,
+            )
+            .expect("Failed to upload previous bone matrices!");
+
+        ctx.program_binding.set_texture(location, storage.texture());
+    }
+    if let Some(location) =
+        &built_in_uniforms[BuiltInUniform::PreviousViewProjectionMatrix as usize]
+    {
+        ctx.program_binding
+            .set_matrix4(location, motion.prev_view_projection_matrix);
+    }
+    if let Some(location) =
+        &built_in_uniforms[BuiltInUniform::UnjitteredViewProjectionMatrix as usize]
+    {
+        ctx.program_binding
+            .set_matrix4(location, motion.unjittered_view_projection_matrix);
+    }
+
     if let Some(location) = &built_in_uniforms[BuiltInUniform::BlendShapesStorage as usize] {
         if let Some(texture) = ctx
             .blend_shapes_storage
@@ -1339,6 +1628,12 @@ impl Renderer {
             )?,
             ui_renderer: UiRenderer::new(&state)?,
             quality_settings: settings,
+            occlusion_buffer: Default::default(),
+            debug_occlusion_culling: false,
+            light_cluster_grid: Default::default(),
+            light_cluster_storage: LightClusterStorage::new(&state)?,
+            debug_light_clusters: false,
+            debug_cascades: false,
             debug_renderer: DebugRenderer::new(&state)?,
             scene_data_map: Default::default(),
             backbuffer_clear_color: Color::BLACK,
@@ -1347,11 +1642,16 @@ impl Renderer {
             forward_renderer: ForwardRenderer::new(),
             ui_frame_buffers: Default::default(),
             fxaa_renderer: FxaaRenderer::new(&state)?,
+            gpu_particle_renderer: GpuParticleRenderer::new(&state)?,
+            taa_renderer: TemporalAntiAliasingRenderer::new(&state)?,
+            instance_history: Default::default(),
             statistics: Statistics::default(),
             shader_event_receiver,
             texture_event_receiver,
             shader_cache,
             scene_render_passes: Default::default(),
+            render_graph: Default::default(),
+            gpu_profiler: GpuProfiler::new(&state),
             matrix_storage: MatrixStorageCache::new(&state)?,
             state,
         })
@@ -1383,11 +1683,43 @@ impl Renderer {
         self.scene_render_passes.clear()
     }
 
+    /// Returns a reference to the render graph. See [`render_graph`] module docs for more info.
+    pub fn render_graph(&self) -> &RenderGraph {
+        &self.render_graph
+    }
+
+    /// Returns a reference to the render graph. It could be used to add custom render passes with
+    /// declared inputs, outputs and execution order. See [`render_graph`] module docs for more info.
+    pub fn render_graph_mut(&mut self) -> &mut RenderGraph {
+        &mut self.render_graph
+    }
+
     /// Returns statistics for last frame.
     pub fn get_statistics(&self) -> Statistics {
         self.statistics
     }
 
+    /// Enables or disables GPU profiling. When enabled, the renderer measures GPU time of each
+    /// render pass using timer queries, the results are available in [`Statistics::gpu_timings`].
+    /// Profiling has small, but non-zero cost, so it is disabled by default. It could not be
+    /// enabled if timer queries are not supported by the graphics driver.
+    pub fn set_gpu_profiling_enabled(&mut self, enabled: bool) {
+        self.gpu_profiler.set_enabled(enabled);
+        if !enabled {
+            self.statistics.gpu_timings = Default::default();
+        }
+    }
+
+    /// Returns `true` if GPU profiling is enabled, `false` - otherwise.
+    pub fn is_gpu_profiling_enabled(&self) -> bool {
+        self.gpu_profiler.is_enabled()
+    }
+
+    /// Returns `true` if GPU profiling is supported by the graphics driver, `false` - otherwise.
+    pub fn is_gpu_profiling_supported(&self) -> bool {
+        self.gpu_profiler.is_supported()
+    }
+
     /// Unloads texture from GPU memory.
     pub fn unload_texture(&mut self, texture: TextureResource) {
         self.texture_cache.unload(texture)
@@ -1403,6 +1735,16 @@ impl Renderer {
         &mut self.state
     }
 
+    /// Reads the final (tone mapped and gamma corrected) frame of the given scene back from GPU
+    /// memory as a set of `RGBA8` pixels. Returns `None` if the scene wasn't rendered yet. Keep
+    /// in mind, that the frame is stored upside down. This method is quite slow, because it
+    /// stalls the pipeline until the GPU finishes all the previous commands.
+    pub fn read_scene_frame_pixels(&self, scene: Handle<Scene>) -> Option<Vec<u8>> {
+        self.scene_data_map
+            .get(&scene)
+            .and_then(|data| data.ldr_scene_framebuffer.read_pixels(&self.state))
+    }
+
     /// Sets new frame size. You should call the same method on [`crate::engine::Engine`]
     /// instead, which will update the size for the user interface and rendering context
     /// as well as this one.
@@ -1448,6 +1790,50 @@ impl Renderer {
         self.quality_settings
     }
 
+    /// Returns a reference to the light cluster grid, that was used to render the last camera.
+    pub fn light_cluster_grid(&self) -> &LightClusterGrid {
+        &self.light_cluster_grid
+    }
+
+    /// Enables or disables debug visualization of clustered lighting. When enabled, amount of lights
+    /// affecting visible surfaces is drawn as a heat map (blue - few lights, red - many lights).
+    pub fn set_light_clusters_debug(&mut self, enabled: bool) {
+        self.debug_light_clusters = enabled;
+    }
+
+    /// Returns `true` if debug visualization of clustered lighting is enabled.
+    pub fn is_light_clusters_debug_enabled(&self) -> bool {
+        self.debug_light_clusters
+    }
+
+    /// Enables or disables debug visualization of cascaded shadow maps. When enabled, surfaces lit by
+    /// directional lights are tinted with a color of the cascade they belong to (red - first,
+    /// green - second, blue - third).
+    pub fn set_cascades_debug(&mut self, enabled: bool) {
+        self.debug_cascades = enabled;
+    }
+
+    /// Returns `true` if debug visualization of cascaded shadow maps is enabled.
+    pub fn is_cascades_debug_enabled(&self) -> bool {
+        self.debug_cascades
+    }
+
+    /// Returns a reference to the occlusion buffer, that was used to render the last camera.
+    pub fn occlusion_buffer(&self) -> &OcclusionBuffer {
+        &self.occlusion_buffer
+    }
+
+    /// Enables or disables debug visualization of occlusion culling. When enabled, occluders are
+    /// drawn in green and occluded objects are drawn in red.
+    pub fn set_occlusion_culling_debug(&mut self, enabled: bool) {
+        self.debug_occlusion_culling = enabled;
+    }
+
+    /// Returns `true` if debug visualization of occlusion culling is enabled.
+    pub fn is_occlusion_culling_debug_enabled(&self) -> bool {
+        self.debug_occlusion_culling
+    }
+
     /// Removes all cached GPU data, forces renderer to re-upload data to GPU.
     /// Do not call this method until you absolutely need! It may cause **significant**
     /// performance lag!
@@ -1571,6 +1957,93 @@ impl Renderer {
         self.shader_cache.update(dt)
     }
 
+    /// Renders the scene into a cube map from the given position. Every face of the cube map is
+    /// rendered with a camera that has 90 degrees field of view, the given clipping planes and the
+    /// optional skybox. The result is a texture in sRGB color space with a full chain of mip levels,
+    /// which is typically used as a baked environment of a
+    /// [`crate::scene::reflection_probe::ReflectionProbe`].
+    ///
+    /// Everything that is visible in the scene will be rendered, so it is up to the caller to hide
+    /// anything that should not be in the cube map (for example editor gizmos). Global transforms of
+    /// the scene must be up-to-date.
+    pub fn render_cube_map(
+        &mut self,
+        scene: &Scene,
+        position: Vector3<f32>,
+        size: u32,
+        z_near: f32,
+        z_far: f32,
+        skybox: Option<SkyBox>,
+    ) -> Result<Texture, FrameworkError> {
+        scope_profile!();
+
+        let size = size.max(1);
+        let frame_size = Vector2::new(size as f32, size as f32);
+
+        let mut camera = CameraBuilder::new(BaseBuilder::new())
+            .with_projection(Projection::Perspective(PerspectiveProjection {
+                fov: std::f32::consts::FRAC_PI_2,
+                z_near,
+                z_far,
+            }))
+            .with_specific_skybox(skybox.map_or(SkyBoxKind::None, SkyBoxKind::Specific))
+            // Cube maps are used as a source of reflections, and they must not depend on auto
+            // exposure of an arbitrary camera.
+            .with_exposure(Exposure::Manual(std::f32::consts::E))
+            .build_camera();
+
+        let mut scene_associated_data =
+            AssociatedSceneData::new(&self.state, size as usize, size as usize)?;
+
+        let mut faces = Vec::with_capacity(6);
+        for (look, up) in probe::cube_map_face_orientations() {
+            camera.global_transform.set(
+                Matrix4::new_translation(&position)
+                    * UnitQuaternion::face_towards(&look, &up).to_homogeneous(),
+            );
+            camera.calculate_matrices(frame_size);
+
+            self.state.invalidate_resource_bindings_cache();
+            self.matrix_storage.begin_frame();
+
+            self.render_scene_camera(
+                Handle::NONE,
+                scene,
+                &mut scene_associated_data,
+                Handle::NONE,
+                &camera,
+                0.0,
+                false,
+            )?;
+
+            faces.push(
+                scene_associated_data
+                    .ldr_scene_framebuffer
+                    .read_pixels(&self.state)
+                    .ok_or_else(|| {
+                        FrameworkError::Custom(
+                            "Unable to read pixels of a cube map face!".to_string(),
+                        )
+                    })?,
+            );
+        }
+
+        let (bytes, mip_count) = probe::build_cube_map_mip_chain(&faces, size);
+        let mut texture = Texture::from_mip_chain(
+            TextureKind::Cube {
+                width: size,
+                height: size,
+            },
+            TexturePixelKind::RGBA8,
+            mip_count,
+            bytes,
+        )
+        .ok_or_else(|| FrameworkError::Custom("Invalid cube map data!".to_string()))?;
+        texture.set_srgb(true);
+
+        Ok(texture)
+    }
+
     /// Update caches - this will remove timed out resources.
     ///
     /// Normally, this is called from `Engine::update()`.
@@ -1605,6 +2078,9 @@ impl Renderer {
         self.state.invalidate_resource_bindings_cache();
         let dt = self.statistics.capped_frame_time;
         self.statistics.begin_frame();
+        self.instance_history.begin_frame();
+        self.gpu_profiler.begin_frame(&self.state);
+        self.statistics.gpu_timings = self.gpu_profiler.timings();
 
         let window_viewport = Rect::new(0, 0, self.frame_size.0 as i32, self.frame_size.1 as i32);
         self.backbuffer.clear(
@@ -1642,36 +2118,35 @@ impl Renderer {
 
             let state = &mut self.state;
 
-            let scene_associated_data = self
-                .scene_data_map
-                .entry(scene_handle)
-                .and_modify(|data| {
-                    if data.gbuffer.width != frame_size.x as i32
-                        || data.gbuffer.height != frame_size.y as i32
-                    {
-                        let width = frame_size.x as usize;
-                        let height = frame_size.y as usize;
-
-                        Log::info(format!(
-                            "Associated scene rendering data was re-created for scene {}, because render frame size was changed. Old is {}x{}, new {}x{}!",
-                            scene_handle,
-                            data.gbuffer.width,data.gbuffer.height,width,height
-                        ));
-
-                        *data = AssociatedSceneData::new(state, width, height).unwrap();
-                    }
-                })
-                .or_insert_with(|| {
-                    let width = frame_size.x as usize;
-                    let height = frame_size.y as usize;
+            // The data is taken out of the map while the scene is rendering, so it can be passed
+            // along with the renderer itself.
+            let width = frame_size.x as usize;
+            let height = frame_size.y as usize;
+            let mut scene_associated_data = match self.scene_data_map.remove(&scene_handle) {
+                Some(data)
+                    if data.gbuffer.width == width as i32
+                        && data.gbuffer.height == height as i32 =>
+                {
+                    data
+                }
+                Some(data) => {
+                    Log::info(format!(
+                        "Associated scene rendering data was re-created for scene {}, because render frame size was changed. Old is {}x{}, new {}x{}!",
+                        scene_handle,
+                        data.gbuffer.width,data.gbuffer.height,width,height
+                    ));
 
+                    AssociatedSceneData::new(state, width, height)?
+                }
+                None => {
                     Log::info(format!(
                         "A new associated scene rendering data was created for scene {}!",
                         scene_handle
                     ));
 
-                    AssociatedSceneData::new(state, width, height).unwrap()
-                });
+                    AssociatedSceneData::new(state, width, height)?
+                }
+            };
 
             // If we specified a texture to draw to, we have to register it in texture cache
             // so it can be used in later on as texture. This is useful in case if you need
@@ -1689,252 +2164,550 @@ impl Renderer {
                 );
             }
 
-            for camera in graph
-                .linear_iter()
-                .filter(|&node| node.is_globally_enabled())
-                .filter_map(|node| node.cast::<Camera>().filter(|c| c.is_enabled()))
+            // Remove histories of cameras that do not use temporal anti-aliasing anymore.
+            scene_associated_data.taa_histories.retain(|handle, _| {
+                graph
+                    .try_get(*handle)
+                    .and_then(|node| node.cast::<Camera>())
+                    .is_some_and(|camera| camera.temporal_anti_aliasing_enabled())
+            });
+
+            GpuParticleRenderer::retain_systems(
+                graph,
+                &mut scene_associated_data.gpu_particle_systems,
+            );
+
+            for (camera_handle, camera) in graph
+                .pair_iter()
+                .filter(|(_, node)| node.is_globally_enabled())
+                .filter_map(|(handle, node)| {
+                    node.cast::<Camera>()
+                        .filter(|c| c.is_enabled())
+                        .map(|c| (handle, c))
+                })
             {
-                let viewport = camera.viewport_pixels(frame_size);
-
-                let batch_storage = RenderDataBatchStorage::from_graph(
-                    graph,
-                    ObserverInfo {
-                        observer_position: camera.global_position(),
-                        z_near: camera.projection().z_near(),
-                        z_far: camera.projection().z_far(),
-                        view_matrix: camera.view_matrix(),
-                        projection_matrix: camera.projection_matrix(),
-                    },
-                    GBUFFER_PASS_NAME.clone(),
-                );
+                self.render_scene_camera(
+                    scene_handle,
+                    scene,
+                    &mut scene_associated_data,
+                    camera_handle,
+                    camera,
+                    dt,
+                    true,
+                )?;
+            }
 
-                state.set_polygon_fill_mode(
-                    PolygonFace::FrontAndBack,
-                    scene.rendering_options.polygon_rasterization_mode,
-                );
+            // Optionally render everything into back buffer.
+            if scene.rendering_options.render_target.is_none() {
+                let quad = &self.quad;
+                self.statistics.geometry += blit_pixels(
+                    &self.state,
+                    &mut self.backbuffer,
+                    scene_associated_data.ldr_scene_frame_texture(),
+                    &self.flat_shader,
+                    window_viewport,
+                    quad,
+                )?;
+            }
+
+            self.scene_data_map
+                .insert(scene_handle, scene_associated_data);
+        }
+
+        self.pipeline_state()
+            .set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
 
-                self.statistics += scene_associated_data.gbuffer.fill(GBufferRenderContext {
+        // Render UI on top of everything without gamma correction.
+        self.gpu_profiler.begin(&self.state, GpuPass::Ui);
+        self.statistics += self.ui_renderer.render(UiRenderContext {
+            state: &mut self.state,
+            viewport: window_viewport,
+            frame_buffer: &mut self.backbuffer,
+            frame_width: backbuffer_width,
+            frame_height: backbuffer_height,
+            drawing_context,
+            white_dummy: self.white_dummy.clone(),
+            texture_cache: &mut self.texture_cache,
+        })?;
+        self.gpu_profiler.end(&self.state);
+
+        self.gpu_profiler.end_frame(&self.state);
+
+        Ok(())
+    }
+
+    /// Renders the scene from the given camera into the frame buffers of the given scene data.
+    /// `auxiliary_passes` defines whether debug geometry and custom scene render passes should be
+    /// rendered or not.
+    #[allow(clippy::too_many_arguments)]
+    fn render_scene_camera(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        scene: &Scene,
+        scene_associated_data: &mut AssociatedSceneData,
+        camera_handle: Handle<Node>,
+        camera: &Camera,
+        dt: f32,
+        auxiliary_passes: bool,
+    ) -> Result<(), FrameworkError> {
+        let graph = &scene.graph;
+        let state = &mut self.state;
+        let scene_render_passes: &[Rc<RefCell<dyn SceneRenderPass>>] = if auxiliary_passes {
+            &self.scene_render_passes
+        } else {
+            &[]
+        };
+        let frame_size = Vector2::new(
+            scene_associated_data.gbuffer.width as f32,
+            scene_associated_data.gbuffer.height as f32,
+        );
+
+        let viewport = camera.viewport_pixels(frame_size);
+
+        // Procedural sky of the scene (if any) overrides the ambient lighting.
+        let ambient_color = sky::active_sky(graph)
+            .and_then(|sky| sky.ambient_color())
+            .unwrap_or(scene.rendering_options.ambient_lighting_color);
+
+        let use_taa = camera.temporal_anti_aliasing_enabled();
+        if use_taa
+            && !scene_associated_data
+                .taa_histories
+                .contains_key(&camera_handle)
+        {
+            let history = TaaHistory::new(
+                state,
+                scene_associated_data.gbuffer.width as usize,
+                scene_associated_data.gbuffer.height as usize,
+            )?;
+            scene_associated_data
+                .taa_histories
+                .insert(camera_handle, history);
+        }
+        let prev_view_projection = scene_associated_data
+            .taa_histories
+            .get(&camera_handle)
+            .and_then(|history| history.prev_view_projection());
+
+        let occlusion_filter = if self.quality_settings.use_occlusion_culling {
+            self.occlusion_buffer.clear(camera.view_projection_matrix());
+            self.occlusion_buffer.rasterize_occluders(graph);
+            let filter = self.occlusion_buffer.cull(graph);
+            self.statistics.occluded_objects += self.occlusion_buffer.occluded_nodes().len();
+            Some(filter)
+        } else {
+            None
+        };
+
+        let batch_storage = RenderDataBatchStorage::from_graph_with_filter(
+            graph,
+            ObserverInfo {
+                observer_position: camera.global_position(),
+                z_near: camera.projection().z_near(),
+                z_far: camera.projection().z_far(),
+                view_matrix: camera.view_matrix(),
+                projection_matrix: camera.projection_matrix(),
+            },
+            GBUFFER_PASS_NAME.clone(),
+            occlusion_filter.as_deref(),
+        );
+
+        state.set_polygon_fill_mode(
+            PolygonFace::FrontAndBack,
+            scene.rendering_options.polygon_rasterization_mode,
+        );
+
+        self.gpu_profiler.begin(state, GpuPass::GBuffer);
+        self.statistics += scene_associated_data.gbuffer.fill(GBufferRenderContext {
+            state,
+            camera,
+            geom_cache: &mut self.geometry_cache,
+            batch_storage: &batch_storage,
+            texture_cache: &mut self.texture_cache,
+            shader_cache: &mut self.shader_cache,
+            environment_dummy: self.environment_dummy.clone(),
+            use_parallax_mapping: self.quality_settings.use_parallax_mapping,
+            normal_dummy: self.normal_dummy.clone(),
+            white_dummy: self.white_dummy.clone(),
+            black_dummy: self.black_dummy.clone(),
+            volume_dummy: self.volume_dummy.clone(),
+            graph,
+            matrix_storage: &mut self.matrix_storage,
+            instance_history: if use_taa {
+                Some(&mut self.instance_history)
+            } else {
+                None
+            },
+            prev_view_projection,
+        })?;
+        self.gpu_profiler.end(state);
+
+        state.set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
+
+        scene_associated_data.copy_depth_stencil_to_scene_framebuffer(state);
+
+        scene_associated_data.hdr_scene_framebuffer.clear(
+            state,
+            viewport,
+            Some(
+                scene
+                    .rendering_options
+                    .clear_color
+                    .unwrap_or(self.backbuffer_clear_color),
+            ),
+            None, // Keep depth, we've just copied valid data in it.
+            Some(0),
+        );
+
+        self.gpu_profiler.begin(state, GpuPass::Lighting);
+        let (pass_stats, light_stats) =
+            self.deferred_light_renderer
+                .render(DeferredRendererContext {
                     state,
+                    scene,
                     camera,
-                    geom_cache: &mut self.geometry_cache,
-                    batch_storage: &batch_storage,
-                    texture_cache: &mut self.texture_cache,
+                    gbuffer: &mut scene_associated_data.gbuffer,
+                    white_dummy: self.white_dummy.clone(),
+                    ambient_color,
+                    settings: &self.quality_settings,
+                    textures: &mut self.texture_cache,
+                    geometry_cache: &mut self.geometry_cache,
+                    frame_buffer: &mut scene_associated_data.hdr_scene_framebuffer,
                     shader_cache: &mut self.shader_cache,
-                    environment_dummy: self.environment_dummy.clone(),
-                    use_parallax_mapping: self.quality_settings.use_parallax_mapping,
                     normal_dummy: self.normal_dummy.clone(),
-                    white_dummy: self.white_dummy.clone(),
                     black_dummy: self.black_dummy.clone(),
                     volume_dummy: self.volume_dummy.clone(),
-                    graph,
+                    environment_dummy: self.environment_dummy.clone(),
                     matrix_storage: &mut self.matrix_storage,
+                    debug_cascades: self.debug_cascades,
+                    gpu_profiler: &mut self.gpu_profiler,
                 })?;
+        self.gpu_profiler.end(state);
 
-                state.set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
-
-                scene_associated_data.copy_depth_stencil_to_scene_framebuffer(state);
-
-                scene_associated_data.hdr_scene_framebuffer.clear(
-                    state,
-                    viewport,
-                    Some(
-                        scene
-                            .rendering_options
-                            .clear_color
-                            .unwrap_or(self.backbuffer_clear_color),
-                    ),
-                    None, // Keep depth, we've just copied valid data in it.
-                    Some(0),
-                );
+        self.statistics.lighting += light_stats;
+        self.statistics.geometry += pass_stats;
 
-                let (pass_stats, light_stats) =
-                    self.deferred_light_renderer
-                        .render(DeferredRendererContext {
-                            state,
-                            scene,
-                            camera,
-                            gbuffer: &mut scene_associated_data.gbuffer,
-                            white_dummy: self.white_dummy.clone(),
-                            ambient_color: scene.rendering_options.ambient_lighting_color,
-                            settings: &self.quality_settings,
-                            textures: &mut self.texture_cache,
-                            geometry_cache: &mut self.geometry_cache,
-                            frame_buffer: &mut scene_associated_data.hdr_scene_framebuffer,
-                            shader_cache: &mut self.shader_cache,
-                            normal_dummy: self.normal_dummy.clone(),
-                            black_dummy: self.black_dummy.clone(),
-                            volume_dummy: self.volume_dummy.clone(),
-                            matrix_storage: &mut self.matrix_storage,
-                        })?;
+        let depth = scene_associated_data.gbuffer.depth();
 
-                self.statistics.lighting += light_stats;
-                self.statistics.geometry += pass_stats;
+        // The grid is also used as a source of lights for shaders without clustered
+        // lighting support, so it must be always updated.
+        self.light_cluster_grid.update(graph, camera);
+        if self.quality_settings.use_clustered_lighting || self.debug_light_clusters {
+            self.light_cluster_storage
+                .upload(state, &self.light_cluster_grid)?;
+        }
 
-                let depth = scene_associated_data.gbuffer.depth();
+        self.gpu_profiler.begin(state, GpuPass::Forward);
+        self.statistics += self.forward_renderer.render(ForwardRenderContext {
+            state,
+            camera,
+            geom_cache: &mut self.geometry_cache,
+            texture_cache: &mut self.texture_cache,
+            shader_cache: &mut self.shader_cache,
+            batch_storage: &batch_storage,
+            framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
+            viewport,
+            quality_settings: &self.quality_settings,
+            white_dummy: self.white_dummy.clone(),
+            normal_dummy: self.normal_dummy.clone(),
+            black_dummy: self.black_dummy.clone(),
+            volume_dummy: self.volume_dummy.clone(),
+            scene_depth: depth,
+            matrix_storage: &mut self.matrix_storage,
+            ambient_light: ambient_color,
+            light_cluster_grid: &self.light_cluster_grid,
+            light_cluster_storage: if self.quality_settings.use_clustered_lighting {
+                Some(&self.light_cluster_storage)
+            } else {
+                None
+            },
+        })?;
 
-                self.statistics += self.forward_renderer.render(ForwardRenderContext {
-                    state,
-                    graph,
-                    camera,
-                    geom_cache: &mut self.geometry_cache,
+        self.statistics += self
+            .gpu_particle_renderer
+            .render(GpuParticleRenderContext {
+                state,
+                graph,
+                camera,
+                systems: &mut scene_associated_data.gpu_particle_systems,
+                framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
+                viewport,
+                depth_texture: scene_associated_data.gbuffer.depth(),
+                normal_texture: scene_associated_data.gbuffer.normal_texture(),
+                texture_cache: &mut self.texture_cache,
+                white_dummy: self.white_dummy.clone(),
+            })?;
+        self.gpu_profiler.end(state);
+
+        self.gpu_profiler.begin(state, GpuPass::PostEffects);
+
+        for render_pass in scene_render_passes {
+            self.statistics += render_pass
+                .borrow_mut()
+                .on_hdr_render(SceneRenderPassContext {
+                    pipeline_state: state,
                     texture_cache: &mut self.texture_cache,
+                    geometry_cache: &mut self.geometry_cache,
                     shader_cache: &mut self.shader_cache,
+                    quality_settings: &self.quality_settings,
                     batch_storage: &batch_storage,
-                    framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
                     viewport,
-                    quality_settings: &self.quality_settings,
+                    scene,
+                    camera,
+                    scene_handle,
                     white_dummy: self.white_dummy.clone(),
                     normal_dummy: self.normal_dummy.clone(),
+                    metallic_dummy: self.metallic_dummy.clone(),
+                    environment_dummy: self.environment_dummy.clone(),
                     black_dummy: self.black_dummy.clone(),
                     volume_dummy: self.volume_dummy.clone(),
-                    scene_depth: depth,
+                    depth_texture: scene_associated_data.gbuffer.depth(),
+                    normal_texture: scene_associated_data.gbuffer.normal_texture(),
+                    ambient_texture: scene_associated_data.gbuffer.ambient_texture(),
+                    framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
+                    ui_renderer: &mut self.ui_renderer,
                     matrix_storage: &mut self.matrix_storage,
-                    ambient_light: scene.rendering_options.ambient_lighting_color,
                 })?;
+        }
 
-                for render_pass in self.scene_render_passes.iter() {
-                    self.statistics +=
-                        render_pass
-                            .borrow_mut()
-                            .on_hdr_render(SceneRenderPassContext {
-                                pipeline_state: state,
-                                texture_cache: &mut self.texture_cache,
-                                geometry_cache: &mut self.geometry_cache,
-                                shader_cache: &mut self.shader_cache,
-                                quality_settings: &self.quality_settings,
-                                batch_storage: &batch_storage,
-                                viewport,
-                                scene,
-                                camera,
-                                scene_handle,
-                                white_dummy: self.white_dummy.clone(),
-                                normal_dummy: self.normal_dummy.clone(),
-                                metallic_dummy: self.metallic_dummy.clone(),
-                                environment_dummy: self.environment_dummy.clone(),
-                                black_dummy: self.black_dummy.clone(),
-                                volume_dummy: self.volume_dummy.clone(),
-                                depth_texture: scene_associated_data.gbuffer.depth(),
-                                normal_texture: scene_associated_data.gbuffer.normal_texture(),
-                                ambient_texture: scene_associated_data.gbuffer.ambient_texture(),
-                                framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
-                                ui_renderer: &mut self.ui_renderer,
-                                matrix_storage: &mut self.matrix_storage,
-                            })?;
-                }
+        if auxiliary_passes && !self.render_graph.is_empty() {
+            let scene_depth = scene_associated_data
+                .hdr_scene_framebuffer
+                .depth_attachment()
+                .unwrap()
+                .texture
+                .clone();
+            self.render_graph.prepare_targets(
+                state,
+                &mut scene_associated_data.render_graph_targets,
+                scene_associated_data.gbuffer.width as usize,
+                scene_associated_data.gbuffer.height as usize,
+                &scene_depth,
+            )?;
+        }
 
-                let quad = &self.quad;
+        if auxiliary_passes {
+            let targets = &mut scene_associated_data.render_graph_targets;
+            let hdr_framebuffer = &mut scene_associated_data.hdr_scene_framebuffer;
+            let ldr_framebuffer = &mut scene_associated_data.ldr_scene_framebuffer;
+            let gbuffer = &scene_associated_data.gbuffer;
+            self.statistics +=
+                self.render_graph
+                    .execute(RenderGraphStage::Hdr, |descriptor, pass| {
+                        pass.execute(RenderGraphPassContext {
+                            pipeline_state: state,
+                            texture_cache: &mut self.texture_cache,
+                            geometry_cache: &mut self.geometry_cache,
+                            shader_cache: &mut self.shader_cache,
+                            batch_storage: &batch_storage,
+                            quality_settings: &self.quality_settings,
+                            matrix_storage: &mut self.matrix_storage,
+                            quad: &self.quad,
+                            white_dummy: self.white_dummy.clone(),
+                            normal_dummy: self.normal_dummy.clone(),
+                            black_dummy: self.black_dummy.clone(),
+                            scene,
+                            camera,
+                            viewport,
+                            scene_handle,
+                            targets: targets.targets(
+                                descriptor,
+                                hdr_framebuffer,
+                                ldr_framebuffer,
+                                gbuffer.depth(),
+                                gbuffer.normal_texture(),
+                                gbuffer.ambient_texture(),
+                            ),
+                        })
+                    })?;
+        }
 
-                // Prepare glow map.
-                self.statistics.geometry += scene_associated_data.bloom_renderer.render(
-                    state,
-                    quad,
-                    scene_associated_data.hdr_scene_frame_texture(),
-                )?;
+        if let Some(history) = scene_associated_data.taa_histories.get_mut(&camera_handle) {
+            self.statistics.geometry += self.taa_renderer.render(
+                state,
+                viewport,
+                &scene_associated_data.gbuffer,
+                &mut scene_associated_data.hdr_scene_framebuffer,
+                history,
+                camera,
+            )?;
+        }
 
-                // Convert high dynamic range frame to low dynamic range (sRGB) with tone mapping and gamma correction.
-                self.statistics.geometry += scene_associated_data.hdr_renderer.render(
-                    state,
-                    scene_associated_data.hdr_scene_frame_texture(),
-                    scene_associated_data.bloom_renderer.result(),
-                    &mut scene_associated_data.ldr_scene_framebuffer,
-                    viewport,
-                    quad,
-                    dt,
-                    camera.exposure(),
-                    camera.color_grading_lut_ref(),
-                    camera.color_grading_enabled(),
-                    &mut self.texture_cache,
-                )?;
+        let quad = &self.quad;
 
-                // Apply FXAA if needed.
-                if self.quality_settings.fxaa {
-                    self.statistics.geometry += self.fxaa_renderer.render(
-                        state,
-                        viewport,
-                        scene_associated_data.ldr_scene_frame_texture(),
-                        &mut scene_associated_data.ldr_temp_framebuffer,
-                    )?;
-
-                    let quad = &self.quad;
-                    let temp_frame_texture = scene_associated_data.ldr_temp_frame_texture();
-                    self.statistics.geometry += blit_pixels(
-                        state,
-                        &mut scene_associated_data.ldr_scene_framebuffer,
-                        temp_frame_texture,
-                        &self.flat_shader,
-                        viewport,
-                        quad,
-                    )?;
-                }
+        // Prepare glow map.
+        self.statistics.geometry += scene_associated_data.bloom_renderer.render(
+            state,
+            quad,
+            scene_associated_data.hdr_scene_frame_texture(),
+        )?;
+
+        // Convert high dynamic range frame to low dynamic range (sRGB) with tone mapping and gamma correction.
+        self.statistics.geometry += scene_associated_data.hdr_renderer.render(
+            state,
+            scene_associated_data.hdr_scene_frame_texture(),
+            scene_associated_data.bloom_renderer.result(),
+            &mut scene_associated_data.ldr_scene_framebuffer,
+            viewport,
+            quad,
+            dt,
+            camera.exposure(),
+            camera.eye_adaptation_speed(),
+            camera.tone_mapping(),
+            camera.color_grading_lut_ref(),
+            camera.color_grading_enabled(),
+            &mut self.texture_cache,
+        )?;
+
+        // Apply FXAA if needed. Temporal anti-aliasing replaces it.
+        if self.quality_settings.fxaa && !use_taa {
+            self.statistics.geometry += self.fxaa_renderer.render(
+                state,
+                viewport,
+                scene_associated_data.ldr_scene_frame_texture(),
+                &mut scene_associated_data.ldr_temp_framebuffer,
+            )?;
+
+            let quad = &self.quad;
+            let temp_frame_texture = scene_associated_data.ldr_temp_frame_texture();
+            self.statistics.geometry += blit_pixels(
+                state,
+                &mut scene_associated_data.ldr_scene_framebuffer,
+                temp_frame_texture,
+                &self.flat_shader,
+                viewport,
+                quad,
+            )?;
+        }
+
+        if auxiliary_passes {
+            // Render debug geometry in the LDR frame buffer.
+            self.statistics += self.debug_renderer.render(
+                state,
+                viewport,
+                &mut scene_associated_data.ldr_scene_framebuffer,
+                &scene.drawing_context,
+                camera,
+            )?;
+
+            // Render primitives of the debug drawer of the graph on top of everything.
+            let debug_drawer = scene.graph.debug_drawer_ref();
+            self.statistics += self.debug_renderer.render(
+                state,
+                viewport,
+                &mut scene_associated_data.ldr_scene_framebuffer,
+                debug_drawer.drawing_context(),
+                camera,
+            )?;
+            if !debug_drawer.texts().is_empty() {
+                let drawing_context = self.debug_renderer.prepare_texts(
+                    debug_drawer.texts(),
+                    camera,
+                    frame_size,
+                    viewport,
+                );
+                self.statistics += self.ui_renderer.render(UiRenderContext {
+                    state,
+                    viewport: Rect::new(0, 0, frame_size.x as i32, frame_size.y as i32),
+                    frame_buffer: &mut scene_associated_data.ldr_scene_framebuffer,
+                    frame_width: frame_size.x,
+                    frame_height: frame_size.y,
+                    drawing_context,
+                    white_dummy: self.white_dummy.clone(),
+                    texture_cache: &mut self.texture_cache,
+                })?;
+            }
 
-                // Render debug geometry in the LDR frame buffer.
+            if self.debug_occlusion_culling && self.quality_settings.use_occlusion_culling {
+                let mut drawing_context = SceneDrawingContext::default();
+                self.occlusion_buffer
+                    .debug_draw(graph, &mut drawing_context);
                 self.statistics += self.debug_renderer.render(
                     state,
                     viewport,
                     &mut scene_associated_data.ldr_scene_framebuffer,
-                    &scene.drawing_context,
+                    &drawing_context,
                     camera,
                 )?;
-
-                for render_pass in self.scene_render_passes.iter() {
-                    self.statistics +=
-                        render_pass
-                            .borrow_mut()
-                            .on_ldr_render(SceneRenderPassContext {
-                                pipeline_state: state,
-                                texture_cache: &mut self.texture_cache,
-                                geometry_cache: &mut self.geometry_cache,
-                                shader_cache: &mut self.shader_cache,
-                                quality_settings: &self.quality_settings,
-                                batch_storage: &batch_storage,
-                                viewport,
-                                scene,
-                                camera,
-                                scene_handle,
-                                white_dummy: self.white_dummy.clone(),
-                                normal_dummy: self.normal_dummy.clone(),
-                                metallic_dummy: self.metallic_dummy.clone(),
-                                environment_dummy: self.environment_dummy.clone(),
-                                black_dummy: self.black_dummy.clone(),
-                                volume_dummy: self.volume_dummy.clone(),
-                                depth_texture: scene_associated_data.gbuffer.depth(),
-                                normal_texture: scene_associated_data.gbuffer.normal_texture(),
-                                ambient_texture: scene_associated_data.gbuffer.ambient_texture(),
-                                framebuffer: &mut scene_associated_data.ldr_scene_framebuffer,
-                                ui_renderer: &mut self.ui_renderer,
-                                matrix_storage: &mut self.matrix_storage,
-                            })?;
-                }
             }
 
-            // Optionally render everything into back buffer.
-            if scene.rendering_options.render_target.is_none() {
-                let quad = &self.quad;
-                self.statistics.geometry += blit_pixels(
+            if self.debug_light_clusters {
+                self.statistics += self.light_cluster_storage.render_debug(
                     state,
-                    &mut self.backbuffer,
-                    scene_associated_data.ldr_scene_frame_texture(),
-                    &self.flat_shader,
-                    window_viewport,
-                    quad,
+                    viewport,
+                    &mut scene_associated_data.ldr_scene_framebuffer,
+                    scene_associated_data.gbuffer.depth(),
+                    self.light_cluster_grid.max_occupancy(),
                 )?;
             }
         }
 
-        self.pipeline_state()
-            .set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
+        for render_pass in scene_render_passes {
+            self.statistics += render_pass
+                .borrow_mut()
+                .on_ldr_render(SceneRenderPassContext {
+                    pipeline_state: state,
+                    texture_cache: &mut self.texture_cache,
+                    geometry_cache: &mut self.geometry_cache,
+                    shader_cache: &mut self.shader_cache,
+                    quality_settings: &self.quality_settings,
+                    batch_storage: &batch_storage,
+                    viewport,
+                    scene,
+                    camera,
+                    scene_handle,
+                    white_dummy: self.white_dummy.clone(),
+                    normal_dummy: self.normal_dummy.clone(),
+                    metallic_dummy: self.metallic_dummy.clone(),
+                    environment_dummy: self.environment_dummy.clone(),
+                    black_dummy: self.black_dummy.clone(),
+                    volume_dummy: self.volume_dummy.clone(),
+                    depth_texture: scene_associated_data.gbuffer.depth(),
+                    normal_texture: scene_associated_data.gbuffer.normal_texture(),
+                    ambient_texture: scene_associated_data.gbuffer.ambient_texture(),
+                    framebuffer: &mut scene_associated_data.ldr_scene_framebuffer,
+                    ui_renderer: &mut self.ui_renderer,
+                    matrix_storage: &mut self.matrix_storage,
+                })?;
+        }
 
-        // Render UI on top of everything without gamma correction.
-        self.statistics += self.ui_renderer.render(UiRenderContext {
-            state: &mut self.state,
-            viewport: window_viewport,
-            frame_buffer: &mut self.backbuffer,
-            frame_width: backbuffer_width,
-            frame_height: backbuffer_height,
-            drawing_context,
-            white_dummy: self.white_dummy.clone(),
-            texture_cache: &mut self.texture_cache,
-        })?;
+        if auxiliary_passes {
+            let targets = &mut scene_associated_data.render_graph_targets;
+            let hdr_framebuffer = &mut scene_associated_data.hdr_scene_framebuffer;
+            let ldr_framebuffer = &mut scene_associated_data.ldr_scene_framebuffer;
+            let gbuffer = &scene_associated_data.gbuffer;
+            self.statistics +=
+                self.render_graph
+                    .execute(RenderGraphStage::Ldr, |descriptor, pass| {
+                        pass.execute(RenderGraphPassContext {
+                            pipeline_state: state,
+                            texture_cache: &mut self.texture_cache,
+                            geometry_cache: &mut self.geometry_cache,
+                            shader_cache: &mut self.shader_cache,
+                            batch_storage: &batch_storage,
+                            quality_settings: &self.quality_settings,
+                            matrix_storage: &mut self.matrix_storage,
+                            quad: &self.quad,
+                            white_dummy: self.white_dummy.clone(),
+                            normal_dummy: self.normal_dummy.clone(),
+                            black_dummy: self.black_dummy.clone(),
+                            scene,
+                            camera,
+                            viewport,
+                            scene_handle,
+                            targets: targets.targets(
+                                descriptor,
+                                hdr_framebuffer,
+                                ldr_framebuffer,
+                                gbuffer.depth(),
+                                gbuffer.normal_texture(),
+                                gbuffer.ambient_texture(),
+                            ),
+                        })
+                    })?;
+        }
+
+        self.gpu_profiler.end(state);
 
         Ok(())
     }
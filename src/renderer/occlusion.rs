@@ -0,0 +1,345 @@
+//! Occlusion culling allows the renderer to skip objects, that are fully hidden behind other objects
+//! (occluders). It uses low-resolution software rasterized depth buffer - triangles of occluders
+//! are rasterized on CPU, and then screen-space bounds of every other object are tested against
+//! the depth buffer. See [`OcclusionBuffer`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3, Vector4},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+    },
+    graph::SceneGraph,
+    scene::{
+        debug::SceneDrawingContext,
+        graph::Graph,
+        mesh::{buffer::VertexAttributeUsage, buffer::VertexReadTrait, Mesh},
+        node::Node,
+    },
+};
+
+/// Default width of the occlusion buffer in pixels.
+pub const DEFAULT_OCCLUSION_BUFFER_WIDTH: usize = 256;
+/// Default height of the occlusion buffer in pixels.
+pub const DEFAULT_OCCLUSION_BUFFER_HEIGHT: usize = 128;
+
+/// Software depth buffer, that is used for occlusion culling. Occluders (nodes with
+/// [`crate::scene::base::Base::is_occluder`] flag) are rasterized into the buffer, and then every
+/// other node (with [`crate::scene::base::Base::occlusion_culling`] flag) is tested against it,
+/// using its world-space bounding box. The test is conservative - a node is considered occluded only
+/// if its screen-space bounds are fully covered by closer occluders.
+pub struct OcclusionBuffer {
+    width: usize,
+    height: usize,
+    // Normalized depth in [0; 1] range, where 1.0 is the far plane.
+    depth: Vec<f32>,
+    view_projection: Matrix4<f32>,
+    occluded: Vec<Handle<Node>>,
+    has_occluders: bool,
+}
+
+impl Default for OcclusionBuffer {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_OCCLUSION_BUFFER_WIDTH,
+            DEFAULT_OCCLUSION_BUFFER_HEIGHT,
+        )
+    }
+}
+
+impl OcclusionBuffer {
+    /// Creates new occlusion buffer of the given size. Low resolution is enough in most cases,
+    /// higher resolutions give more accurate results, but take more time to rasterize occluders.
+    pub fn new(width: usize, height: usize) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        Self {
+            width,
+            height,
+            depth: vec![1.0; width * height],
+            view_projection: Matrix4::identity(),
+            occluded: Default::default(),
+            has_occluders: false,
+        }
+    }
+
+    /// Returns width of the buffer in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns height of the buffer in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns depth values of the buffer. Values are in `[0; 1]` range, where `1.0` means that
+    /// there's no occluder at the pixel.
+    pub fn depth(&self) -> &[f32] {
+        &self.depth
+    }
+
+    /// Returns a list of nodes, that were occluded during the last call of [`Self::cull`].
+    pub fn occluded_nodes(&self) -> &[Handle<Node>] {
+        &self.occluded
+    }
+
+    /// Clears the buffer and sets new view-projection matrix of an observer.
+    pub fn clear(&mut self, view_projection: Matrix4<f32>) {
+        self.depth.fill(1.0);
+        self.view_projection = view_projection;
+        self.occluded.clear();
+        self.has_occluders = false;
+    }
+
+    /// Returns `true` if at least one triangle was rasterized into the buffer since the last
+    /// [`Self::clear`] call.
+    pub fn has_occluders(&self) -> bool {
+        self.has_occluders
+    }
+
+    fn project(&self, point: &Vector3<f32>) -> Option<Vector3<f32>> {
+        let clip = self.view_projection * Vector4::new(point.x, point.y, point.z, 1.0);
+        if clip.w <= f32::EPSILON {
+            // The point is behind the observer.
+            return None;
+        }
+        let ndc = clip.xyz() / clip.w;
+        Some(Vector3::new(
+            (ndc.x * 0.5 + 0.5) * self.width as f32,
+            (0.5 - ndc.y * 0.5) * self.height as f32,
+            (ndc.z * 0.5 + 0.5).clamp(0.0, 1.0),
+        ))
+    }
+
+    /// Rasterizes a triangle (in world coordinates) into the buffer. Triangles, that are partially
+    /// behind the observer are ignored, it only makes the culling less effective, but never wrong.
+    pub fn rasterize_triangle(&mut self, a: &Vector3<f32>, b: &Vector3<f32>, c: &Vector3<f32>) {
+        let (Some(a), Some(b), Some(c)) = (self.project(a), self.project(b), self.project(c))
+        else {
+            return;
+        };
+
+        let edge = |p: Vector2<f32>, q: Vector2<f32>, r: Vector2<f32>| {
+            (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x)
+        };
+
+        let area = edge(a.xy(), b.xy(), c.xy());
+        if area.abs() <= f32::EPSILON {
+            return;
+        }
+
+        let min_x = a.x.min(b.x).min(c.x).floor().max(0.0) as usize;
+        let min_y = a.y.min(b.y).min(c.y).floor().max(0.0) as usize;
+        let max_x = (a.x.max(b.x).max(c.x).ceil() as usize).min(self.width);
+        let max_y = (a.y.max(b.y).max(c.y).ceil() as usize).min(self.height);
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = Vector2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let w0 = edge(b.xy(), c.xy(), p) / area;
+                let w1 = edge(c.xy(), a.xy(), p) / area;
+                let w2 = edge(a.xy(), b.xy(), p) / area;
+                if w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0 {
+                    // Depth in NDC is linear in screen space.
+                    let z = w0 * a.z + w1 * b.z + w2 * c.z;
+                    let depth = &mut self.depth[y * self.width + x];
+                    if z < *depth {
+                        *depth = z;
+                        self.has_occluders = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Rasterizes every occluder of the graph into the buffer.
+    pub fn rasterize_occluders(&mut self, graph: &Graph) {
+        for node in graph.linear_iter() {
+            if !node.is_occluder() || !node.global_visibility() || !node.is_globally_enabled() {
+                continue;
+            }
+
+            let Some(mesh) = node.cast::<Mesh>() else {
+                continue;
+            };
+
+            let world = mesh.global_transform();
+            for surface in mesh.surfaces() {
+                // Skinned meshes are deformed on GPU, their vertices cannot be used as is.
+                if !surface.bones().is_empty() {
+                    continue;
+                }
+
+                let data = surface.data();
+                let data = data.lock();
+                let vertices = data
+                    .vertex_buffer
+                    .iter()
+                    .map(|v| {
+                        v.read_3_f32(VertexAttributeUsage::Position)
+                            .map(|p| world.transform_point(&p.into()).coords)
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<_>>();
+                for triangle in data.geometry_buffer.iter() {
+                    let [a, b, c] = triangle.0;
+                    if let (Some(a), Some(b), Some(c)) = (
+                        vertices.get(a as usize),
+                        vertices.get(b as usize),
+                        vertices.get(c as usize),
+                    ) {
+                        self.rasterize_triangle(a, b, c);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the given bounding box (in world coordinates) is not fully hidden by
+    /// occluders.
+    pub fn is_visible(&self, aabb: &AxisAlignedBoundingBox) -> bool {
+        let mut min = Vector3::repeat(f32::MAX);
+        let mut max = Vector3::repeat(-f32::MAX);
+        for corner in aabb.corners() {
+            let Some(projected) = self.project(&corner) else {
+                // Intersects the near plane, consider visible.
+                return true;
+            };
+            min = min.inf(&projected);
+            max = max.sup(&projected);
+        }
+
+        let min_x = min.x.floor().max(0.0) as usize;
+        let min_y = min.y.floor().max(0.0) as usize;
+        let max_x = (max.x.ceil().max(0.0) as usize).min(self.width);
+        let max_y = (max.y.ceil().max(0.0) as usize).min(self.height);
+
+        if min_x >= max_x || min_y >= max_y {
+            // Off-screen objects are handled by frustum culling.
+            return true;
+        }
+
+        let nearest_depth = min.z;
+        for y in min_y..max_y {
+            let row = &self.depth[(y * self.width + min_x)..(y * self.width + max_x)];
+            if row.iter().any(|&depth| depth >= nearest_depth) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Tests every node of the graph against the buffer and returns a filter, where `false` means
+    /// that a node at the respective index is occluded. The buffer must be filled before (see
+    /// [`Self::rasterize_occluders`]).
+    pub fn cull(&mut self, graph: &Graph) -> Vec<bool> {
+        let mut filter = vec![true; graph.capacity() as usize];
+        self.occluded.clear();
+        if !self.has_occluders {
+            return filter;
+        }
+        for (handle, node) in graph.pair_iter() {
+            if node.is_occluder() || !node.occlusion_culling() {
+                continue;
+            }
+
+            let aabb = node.world_bounding_box();
+            if aabb.is_invalid_or_degenerate() {
+                continue;
+            }
+
+            if !self.is_visible(&aabb) {
+                filter[handle.index() as usize] = false;
+                self.occluded.push(handle);
+            }
+        }
+        filter
+    }
+
+    /// Draws debug information about the last culling - occluders are drawn in green and occluded
+    /// nodes are drawn in red.
+    pub fn debug_draw(&self, graph: &Graph, ctx: &mut SceneDrawingContext) {
+        for node in graph.linear_iter() {
+            if node.is_occluder() {
+                ctx.draw_aabb(&node.world_bounding_box(), Color::GREEN);
+            }
+        }
+        for &handle in self.occluded.iter() {
+            if let Some(node) = graph.try_get(handle) {
+                ctx.draw_aabb(&node.world_bounding_box(), Color::RED);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::math::aabb::AxisAlignedBoundingBox;
+
+    fn make_buffer() -> OcclusionBuffer {
+        let mut buffer = OcclusionBuffer::new(64, 64);
+        let projection = Matrix4::new_perspective(1.0, 90.0f32.to_radians(), 0.1, 100.0);
+        let view = Matrix4::look_at_rh(
+            &Vector3::new(0.0, 0.0, 0.0).into(),
+            &Vector3::new(0.0, 0.0, -1.0).into(),
+            &Vector3::y(),
+        );
+        buffer.clear(projection * view);
+        buffer
+    }
+
+    fn rasterize_wall(buffer: &mut OcclusionBuffer, z: f32, half_size: f32) {
+        let a = Vector3::new(-half_size, -half_size, z);
+        let b = Vector3::new(half_size, -half_size, z);
+        let c = Vector3::new(half_size, half_size, z);
+        let d = Vector3::new(-half_size, half_size, z);
+        buffer.rasterize_triangle(&a, &b, &c);
+        buffer.rasterize_triangle(&a, &c, &d);
+    }
+
+    #[test]
+    fn test_occlusion() {
+        let mut buffer = make_buffer();
+        rasterize_wall(&mut buffer, -5.0, 100.0);
+
+        // Behind the wall.
+        assert!(!buffer.is_visible(&AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-1.0, -1.0, -12.0),
+            Vector3::new(1.0, 1.0, -10.0),
+        )));
+
+        // In front of the wall.
+        assert!(buffer.is_visible(&AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-1.0, -1.0, -4.0),
+            Vector3::new(1.0, 1.0, -2.0),
+        )));
+
+        // Behind the observer.
+        assert!(buffer.is_visible(&AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-1.0, -1.0, 2.0),
+            Vector3::new(1.0, 1.0, 4.0),
+        )));
+    }
+
+    #[test]
+    fn test_partial_occlusion() {
+        let mut buffer = make_buffer();
+        rasterize_wall(&mut buffer, -5.0, 1.0);
+
+        // Larger than the wall, so it must be visible.
+        assert!(buffer.is_visible(&AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-10.0, -10.0, -22.0),
+            Vector3::new(10.0, 10.0, -20.0),
+        )));
+
+        // Fully hidden by the small wall.
+        assert!(!buffer.is_visible(&AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-0.5, -0.5, -22.0),
+            Vector3::new(0.5, 0.5, -20.0),
+        )));
+    }
+}
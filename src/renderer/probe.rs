@@ -0,0 +1,470 @@
+//! Reflection probes. See [`ReflectionProbeRenderer`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector3},
+        color::Color,
+        math::{frustum::Frustum, Rect},
+        scope_profile,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        cache::texture::TextureCache,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        gbuffer::GBuffer,
+        RenderPassStatistics,
+    },
+    scene::{
+        camera::Camera,
+        graph::Graph,
+        mesh::surface::SurfaceData,
+        node::NodeTrait,
+        reflection_probe::{ReflectionProbe, ReflectionProbeShape},
+    },
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Look and up vectors of cameras, that are used to render faces of a cube map. The order matches
+/// the order of faces in cube textures.
+pub(crate) fn cube_map_face_orientations() -> [(Vector3<f32>, Vector3<f32>); 6] {
+    [
+        (Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, -1.0, 0.0)),
+        (Vector3::new(-1.0, 0.0, 0.0), Vector3::new(0.0, -1.0, 0.0)),
+        (Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0)),
+        (Vector3::new(0.0, -1.0, 0.0), Vector3::new(0.0, 0.0, -1.0)),
+        (Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.0, -1.0, 0.0)),
+        (Vector3::new(0.0, 0.0, -1.0), Vector3::new(0.0, -1.0, 0.0)),
+    ]
+}
+
+/// Builds a full chain of mip levels for the given faces of a cube map with RGBA8 pixels. Returns
+/// the data of every level (each level contains all six faces) and the amount of levels.
+pub(crate) fn build_cube_map_mip_chain(faces: &[Vec<u8>], size: u32) -> (Vec<u8>, u32) {
+    let mut levels = vec![faces.to_vec()];
+    let mut level_size = size as usize;
+    while level_size > 1 {
+        let next_size = level_size / 2;
+        let next_level = levels
+            .last()
+            .unwrap()
+            .iter()
+            .map(|face| {
+                let mut next_face = vec![0u8; next_size * next_size * 4];
+                for y in 0..next_size {
+                    for x in 0..next_size {
+                        for channel in 0..4 {
+                            let sample = |sx: usize, sy: usize| {
+                                face[(sy * level_size + sx) * 4 + channel] as u32
+                            };
+                            let sum = sample(2 * x, 2 * y)
+                                + sample(2 * x + 1, 2 * y)
+                                + sample(2 * x, 2 * y + 1)
+                                + sample(2 * x + 1, 2 * y + 1);
+                            next_face[(y * next_size + x) * 4 + channel] = (sum / 4) as u8;
+                        }
+                    }
+                }
+                next_face
+            })
+            .collect::<Vec<_>>();
+        levels.push(next_level);
+        level_size = next_size;
+    }
+
+    let mip_count = levels.len() as u32;
+    (levels.into_iter().flatten().flatten().collect(), mip_count)
+}
+
+struct AccumulateShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    depth_texture: UniformLocation,
+    normal_texture: UniformLocation,
+    material_texture: UniformLocation,
+    environment_texture: UniformLocation,
+    inv_view_projection: UniformLocation,
+    camera_position: UniformLocation,
+    probe_matrix: UniformLocation,
+    inv_probe_matrix: UniformLocation,
+    is_sphere: UniformLocation,
+    shape_size: UniformLocation,
+    blend_distance: UniformLocation,
+    intensity: UniformLocation,
+    parallax_correction: UniformLocation,
+    max_lod: UniformLocation,
+}
+
+impl AccumulateShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/reflection_probe_fs.glsl");
+        let vertex_source = include_str!("shaders/reflection_probe_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "ReflectionProbeShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_texture: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            environment_texture: program
+                .uniform_location(state, &ImmutableString::new("environmentTexture"))?,
+            inv_view_projection: program
+                .uniform_location(state, &ImmutableString::new("inverseViewProjection"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            probe_matrix: program.uniform_location(state, &ImmutableString::new("probeMatrix"))?,
+            inv_probe_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseProbeMatrix"))?,
+            is_sphere: program.uniform_location(state, &ImmutableString::new("isSphere"))?,
+            shape_size: program.uniform_location(state, &ImmutableString::new("shapeSize"))?,
+            blend_distance: program
+                .uniform_location(state, &ImmutableString::new("blendDistance"))?,
+            intensity: program.uniform_location(state, &ImmutableString::new("intensity"))?,
+            parallax_correction: program
+                .uniform_location(state, &ImmutableString::new("parallaxCorrection"))?,
+            max_lod: program.uniform_location(state, &ImmutableString::new("maxLod"))?,
+            program,
+        })
+    }
+}
+
+struct ResolveShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    reflection_texture: UniformLocation,
+    depth_texture: UniformLocation,
+    diffuse_texture: UniformLocation,
+    normal_texture: UniformLocation,
+    material_texture: UniformLocation,
+    inv_view_projection: UniformLocation,
+    camera_position: UniformLocation,
+}
+
+impl ResolveShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/reflection_probe_resolve_fs.glsl");
+        let vertex_source = include_str!("shaders/reflection_probe_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "ReflectionProbeResolveShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            reflection_texture: program
+                .uniform_location(state, &ImmutableString::new("reflectionTexture"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            diffuse_texture: program
+                .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_texture: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            inv_view_projection: program
+                .uniform_location(state, &ImmutableString::new("inverseViewProjection"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            program,
+        })
+    }
+}
+
+/// Reflection probe renderer works in two passes:
+///
+/// 1) Accumulation pass - every visible probe adds its (parallax-corrected) reflections, weighted
+///    by the influence of the probe, to the reflection map. Alpha channel of the map contains the
+///    sum of weights, so overlapping probes are blended smoothly.
+/// 2) Resolve pass - the accumulated reflections are normalized, weighted by Fresnel term and added
+///    to the frame. This pass is skipped if screen-space reflections are enabled, because they use
+///    the reflection map as a fallback for the parts of reflections that are not on screen.
+pub struct ReflectionProbeRenderer {
+    accumulate_shader: AccumulateShader,
+    resolve_shader: ResolveShader,
+    framebuffer: FrameBuffer,
+    quad: GeometryBuffer,
+}
+
+impl ReflectionProbeRenderer {
+    /// Creates new reflection probe renderer for the given frame size.
+    pub fn new(
+        state: &PipelineState,
+        frame_width: usize,
+        frame_height: usize,
+    ) -> Result<Self, FrameworkError> {
+        let mut reflection = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle {
+                width: frame_width,
+                height: frame_height,
+            },
+            PixelKind::RGBA16F,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        reflection
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        Ok(Self {
+            accumulate_shader: AccumulateShader::new(state)?,
+            resolve_shader: ResolveShader::new(state)?,
+            framebuffer: FrameBuffer::new(
+                state,
+                None,
+                vec![Attachment {
+                    kind: AttachmentKind::Color,
+                    texture: Rc::new(RefCell::new(reflection)),
+                }],
+            )?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            )?,
+        })
+    }
+
+    /// Returns a texture with accumulated reflections of probes. Color is premultiplied by the
+    /// weight of probes, alpha channel contains the sum of weights.
+    pub fn reflection_map(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[0].texture.clone()
+    }
+
+    /// Accumulates reflections of every visible probe, returns the amount of rendered probes.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn accumulate(
+        &mut self,
+        state: &PipelineState,
+        gbuffer: &GBuffer,
+        camera: &Camera,
+        frustum: &Frustum,
+        graph: &Graph,
+        textures: &mut TextureCache,
+        stats: &mut RenderPassStatistics,
+    ) -> Result<usize, FrameworkError> {
+        scope_profile!();
+
+        let viewport = Rect::new(0, 0, gbuffer.width, gbuffer.height);
+
+        // The map must be cleared even if there's no probes, because it is also used as a fallback
+        // for screen-space reflections.
+        self.framebuffer
+            .clear(state, viewport, Some(Color::TRANSPARENT), None, None);
+
+        let frame_matrix = make_frame_matrix(viewport);
+        let inv_view_projection = camera
+            .view_projection_matrix()
+            .try_inverse()
+            .unwrap_or_default();
+        let camera_position = camera.global_position();
+        let depth = gbuffer.depth();
+        let normal = gbuffer.normal_texture();
+        let material = gbuffer.material_texture();
+
+        let mut count = 0;
+        for probe in graph
+            .linear_iter()
+            .filter(|node| node.is_globally_enabled())
+            .filter_map(|node| node.cast::<ReflectionProbe>())
+        {
+            if !frustum.is_intersects_aabb(&probe.world_bounding_box()) {
+                continue;
+            }
+
+            let Some(environment) = probe
+                .environment()
+                .and_then(|environment| textures.get(state, environment))
+            else {
+                continue;
+            };
+
+            let max_lod = if let GpuTextureKind::Cube { width, .. } = environment.borrow().kind() {
+                (width.max(1) as f32).log2()
+            } else {
+                continue;
+            };
+
+            let probe_matrix = probe.global_transform();
+            let inv_probe_matrix = probe_matrix.try_inverse().unwrap_or_default();
+            let (is_sphere, shape_size) = match probe.shape() {
+                ReflectionProbeShape::Box { half_extents } => (false, *half_extents),
+                ReflectionProbeShape::Sphere { radius } => (true, Vector3::repeat(*radius)),
+            };
+
+            let shader = &self.accumulate_shader;
+            *stats += self.framebuffer.draw(
+                &self.quad,
+                state,
+                viewport,
+                &shader.program,
+                &DrawParameters {
+                    cull_face: None,
+                    color_write: Default::default(),
+                    depth_write: false,
+                    stencil_test: None,
+                    depth_test: false,
+                    blend: Some(BlendParameters {
+                        func: BlendFunc::new(BlendFactor::One, BlendFactor::One),
+                        ..Default::default()
+                    }),
+                    stencil_op: Default::default(),
+                },
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                        .set_texture(&shader.depth_texture, &depth)
+                        .set_texture(&shader.normal_texture, &normal)
+                        .set_texture(&shader.material_texture, &material)
+                        .set_texture(&shader.environment_texture, environment)
+                        .set_matrix4(&shader.inv_view_projection, &inv_view_projection)
+                        .set_vector3(&shader.camera_position, &camera_position)
+                        .set_matrix4(&shader.probe_matrix, &probe_matrix)
+                        .set_matrix4(&shader.inv_probe_matrix, &inv_probe_matrix)
+                        .set_bool(&shader.is_sphere, is_sphere)
+                        .set_vector3(&shader.shape_size, &shape_size)
+                        .set_f32(&shader.blend_distance, probe.blend_distance())
+                        .set_f32(&shader.intensity, probe.intensity())
+                        .set_bool(
+                            &shader.parallax_correction,
+                            probe.is_parallax_correction_enabled(),
+                        )
+                        .set_f32(&shader.max_lod, max_lod);
+                },
+            )?;
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Adds accumulated reflections to the frame.
+    pub(crate) fn resolve(
+        &self,
+        state: &PipelineState,
+        gbuffer: &GBuffer,
+        frame_buffer: &mut FrameBuffer,
+        camera: &Camera,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        scope_profile!();
+
+        let viewport = Rect::new(0, 0, gbuffer.width, gbuffer.height);
+        let frame_matrix = make_frame_matrix(viewport);
+        let inv_view_projection = camera
+            .view_projection_matrix()
+            .try_inverse()
+            .unwrap_or_default();
+        let camera_position = camera.global_position();
+        let reflection_map = self.reflection_map();
+
+        let mut stats = RenderPassStatistics::default();
+
+        let shader = &self.resolve_shader;
+        stats += frame_buffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: Some(BlendParameters {
+                    func: BlendFunc::new(BlendFactor::One, BlendFactor::One),
+                    ..Default::default()
+                }),
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&shader.reflection_texture, &reflection_map)
+                    .set_texture(&shader.depth_texture, &gbuffer.depth())
+                    .set_texture(&shader.diffuse_texture, &gbuffer.diffuse_texture())
+                    .set_texture(&shader.normal_texture, &gbuffer.normal_texture())
+                    .set_texture(&shader.material_texture, &gbuffer.material_texture())
+                    .set_matrix4(&shader.inv_view_projection, &inv_view_projection)
+                    .set_vector3(&shader.camera_position, &camera_position);
+            },
+        )?;
+
+        Ok(stats)
+    }
+}
+
+fn make_frame_matrix(viewport: Rect<i32>) -> Matrix4<f32> {
+    Matrix4::new_orthographic(
+        0.0,
+        viewport.w() as f32,
+        viewport.h() as f32,
+        0.0,
+        -1.0,
+        1.0,
+    ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
+        viewport.w() as f32,
+        viewport.h() as f32,
+        0.0,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::probe::build_cube_map_mip_chain;
+
+    #[test]
+    fn test_cube_map_mip_chain() {
+        let faces = (0..6u8)
+            .map(|face| {
+                // 4x4 face, where every pixel is (face, 0..15, 255, 255).
+                (0..16u8)
+                    .flat_map(|i| [face, i, 255, 255])
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let (data, mip_count) = build_cube_map_mip_chain(&faces, 4);
+        assert_eq!(mip_count, 3);
+        assert_eq!(data.len(), 6 * (16 + 4 + 1) * 4);
+
+        // The first level is the faces as is.
+        assert_eq!(&data[..6 * 16 * 4], faces.concat().as_slice());
+
+        // The last level contains one pixel per face, that is the average of the face.
+        let last_level = &data[6 * (16 + 4) * 4..];
+        for face in 0..6 {
+            assert_eq!(
+                &last_level[face * 4..(face + 1) * 4],
+                &[face as u8, 7, 255, 255]
+            );
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! GPU profiler measures time that GPU spends on each render pass of a frame. It uses timer queries,
+//! which results are available only after a few frames, so the timings are always a little bit
+//! delayed. See [`GpuPassTimings`] docs for more info.
+
+use crate::{
+    core::log::Log,
+    renderer::framework::{
+        query::TimerQuery,
+        state::{GlKind, PipelineState},
+    },
+};
+use glow::HasContext;
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter},
+};
+
+/// A render pass, which GPU time is measured by the profiler.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GpuPass {
+    /// G-Buffer filling pass.
+    GBuffer,
+    /// Rendering of shadow maps of every light source.
+    Shadows,
+    /// Screen-space ambient occlusion pass.
+    Ssao,
+    /// Deferred lighting pass, it includes ambient lighting, light volumes, reflections, etc.
+    Lighting,
+    /// Forward rendering pass (transparent objects, particles, etc.).
+    Forward,
+    /// Post effects - bloom, tone mapping, anti-aliasing, custom passes, etc.
+    PostEffects,
+    /// User interface rendering.
+    Ui,
+}
+
+/// Amount of GPU time (in **milliseconds**) spent on each render pass. Time of nested passes is
+/// excluded from their parent passes, for example shadow maps are rendered in the middle of the
+/// lighting pass, but their time is not included in [`Self::lighting`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct GpuPassTimings {
+    /// GPU time of the G-Buffer pass.
+    pub gbuffer: f32,
+    /// GPU time of shadow maps rendering.
+    pub shadows: f32,
+    /// GPU time of the screen-space ambient occlusion pass.
+    pub ssao: f32,
+    /// GPU time of the deferred lighting pass.
+    pub lighting: f32,
+    /// GPU time of the forward rendering pass.
+    pub forward: f32,
+    /// GPU time of post effects.
+    pub post_effects: f32,
+    /// GPU time of user interface rendering.
+    pub ui: f32,
+}
+
+impl GpuPassTimings {
+    /// Returns GPU time of the given pass.
+    pub fn get(&self, pass: GpuPass) -> f32 {
+        match pass {
+            GpuPass::GBuffer => self.gbuffer,
+            GpuPass::Shadows => self.shadows,
+            GpuPass::Ssao => self.ssao,
+            GpuPass::Lighting => self.lighting,
+            GpuPass::Forward => self.forward,
+            GpuPass::PostEffects => self.post_effects,
+            GpuPass::Ui => self.ui,
+        }
+    }
+
+    fn get_mut(&mut self, pass: GpuPass) -> &mut f32 {
+        match pass {
+            GpuPass::GBuffer => &mut self.gbuffer,
+            GpuPass::Shadows => &mut self.shadows,
+            GpuPass::Ssao => &mut self.ssao,
+            GpuPass::Lighting => &mut self.lighting,
+            GpuPass::Forward => &mut self.forward,
+            GpuPass::PostEffects => &mut self.post_effects,
+            GpuPass::Ui => &mut self.ui,
+        }
+    }
+
+    /// Returns total GPU time of every measured pass.
+    pub fn total(&self) -> f32 {
+        self.gbuffer
+            + self.shadows
+            + self.ssao
+            + self.lighting
+            + self.forward
+            + self.post_effects
+            + self.ui
+    }
+}
+
+impl Display for GpuPassTimings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "GPU Pass Timings\n\
+            \tG-Buffer: {:.3} ms\n\
+            \tShadows: {:.3} ms\n\
+            \tSSAO: {:.3} ms\n\
+            \tLighting: {:.3} ms\n\
+            \tForward: {:.3} ms\n\
+            \tPost Effects: {:.3} ms\n\
+            \tUI: {:.3} ms\n\
+            \tTotal: {:.3} ms\n",
+            self.gbuffer,
+            self.shadows,
+            self.ssao,
+            self.lighting,
+            self.forward,
+            self.post_effects,
+            self.ui,
+            self.total()
+        )
+    }
+}
+
+/// Max amount of frames, which queries could be in flight. If GPU lags behind more, the oldest
+/// frame will be discarded.
+const MAX_PENDING_FRAMES: usize = 4;
+
+#[derive(Default)]
+struct PendingFrame {
+    queries: Vec<(GpuPass, TimerQuery)>,
+}
+
+/// Source of timer queries. It is abstracted out of the profiler, so the bookkeeping of nested passes
+/// does not depend on a graphics context.
+trait TimerQueries {
+    /// Starts a query for the given pass. Returns `false` if the query could not be started.
+    fn start(&mut self, pass: GpuPass) -> bool;
+
+    /// Stops the active query.
+    fn stop(&mut self);
+}
+
+/// A stack of currently measured passes, only the top one could have an active query.
+#[derive(Default)]
+struct PassStack {
+    passes: Vec<GpuPass>,
+    query_active: bool,
+}
+
+impl PassStack {
+    fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Pauses the outer pass (if any) and starts measuring the given one. Returns `false` if the query
+    /// could not be started, in this case the stack is cleared.
+    fn begin(&mut self, queries: &mut impl TimerQueries, pass: GpuPass) -> bool {
+        self.stop_active(queries);
+        self.passes.push(pass);
+        self.start(queries, pass)
+    }
+
+    /// Stops measuring the top pass and resumes the outer pass (if any and if `resume` is set). The
+    /// pass is always removed from the stack. Returns `false` if the query of the outer pass could not
+    /// be started, in this case the stack is cleared.
+    fn end(&mut self, queries: &mut impl TimerQueries, resume: bool) -> bool {
+        if self.passes.pop().is_none() {
+            return true;
+        }
+        self.stop_active(queries);
+        match self.passes.last() {
+            Some(&outer) if resume => self.start(queries, outer),
+            _ => true,
+        }
+    }
+
+    fn start(&mut self, queries: &mut impl TimerQueries, pass: GpuPass) -> bool {
+        self.query_active = queries.start(pass);
+        if !self.query_active {
+            self.passes.clear();
+        }
+        self.query_active
+    }
+
+    fn stop_active(&mut self, queries: &mut impl TimerQueries) {
+        if self.query_active {
+            queries.stop();
+            self.query_active = false;
+        }
+    }
+}
+
+struct GlTimerQueries<'a> {
+    state: &'a PipelineState,
+    free_queries: &'a mut Vec<TimerQuery>,
+    frame: &'a mut PendingFrame,
+}
+
+impl TimerQueries for GlTimerQueries<'_> {
+    fn start(&mut self, pass: GpuPass) -> bool {
+        let query = match self.free_queries.pop() {
+            Some(query) => query,
+            None => match TimerQuery::new(self.state) {
+                Ok(query) => query,
+                Err(err) => {
+                    Log::err(format!(
+                        "Unable to create GPU timer query, profiling will be disabled. \
+                        Reason: {err}"
+                    ));
+                    return false;
+                }
+            },
+        };
+        query.begin(self.state);
+        self.frame.queries.push((pass, query));
+        true
+    }
+
+    fn stop(&mut self) {
+        if let Some((_, query)) = self.frame.queries.last() {
+            query.end(self.state);
+        }
+    }
+}
+
+/// See module docs.
+pub(crate) struct GpuProfiler {
+    supported: bool,
+    enabled: bool,
+    free_queries: Vec<TimerQuery>,
+    pending_frames: VecDeque<PendingFrame>,
+    current_frame: PendingFrame,
+    stack: PassStack,
+    timings: GpuPassTimings,
+}
+
+impl GpuProfiler {
+    pub(crate) fn new(state: &PipelineState) -> Self {
+        let supported = match state.gl_kind() {
+            GlKind::OpenGL => true,
+            GlKind::OpenGLES => {
+                let extensions = state.gl.supported_extensions();
+                extensions.contains("GL_EXT_disjoint_timer_query")
+                    || extensions.contains("EXT_disjoint_timer_query_webgl2")
+            }
+        };
+
+        Self {
+            supported,
+            enabled: false,
+            free_queries: Default::default(),
+            pending_frames: Default::default(),
+            current_frame: Default::default(),
+            stack: Default::default(),
+            timings: Default::default(),
+        }
+    }
+
+    pub(crate) fn is_supported(&self) -> bool {
+        self.supported
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled && self.supported;
+        if !self.enabled {
+            self.timings = Default::default();
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn timings(&self) -> GpuPassTimings {
+        self.timings
+    }
+
+    /// Starts measuring GPU time of the given pass. If there is other pass being measured, it will
+    /// be paused until [`Self::end`] is called.
+    pub(crate) fn begin(&mut self, state: &PipelineState, pass: GpuPass) {
+        if !self.enabled {
+            return;
+        }
+        let mut queries = GlTimerQueries {
+            state,
+            free_queries: &mut self.free_queries,
+            frame: &mut self.current_frame,
+        };
+        if !self.stack.begin(&mut queries, pass) {
+            self.enabled = false;
+        }
+    }
+
+    /// Stops measuring GPU time of the current pass and resumes measuring of the outer pass (if any).
+    /// The pass is finished even if the profiler was disabled in the middle of the frame.
+    pub(crate) fn end(&mut self, state: &PipelineState) {
+        let mut queries = GlTimerQueries {
+            state,
+            free_queries: &mut self.free_queries,
+            frame: &mut self.current_frame,
+        };
+        if !self.stack.end(&mut queries, self.enabled) {
+            self.enabled = false;
+        }
+    }
+
+    /// Collects results of finished frames, must be called once at the beginning of every frame.
+    pub(crate) fn begin_frame(&mut self, state: &PipelineState) {
+        // Previous frame could be interrupted by an error, finish it properly.
+        self.end_frame(state);
+
+        while let Some(frame) = self.pending_frames.front() {
+            let available = frame
+                .queries
+                .iter()
+                .all(|(_, query)| query.is_result_available(state));
+            if !available && self.pending_frames.len() < MAX_PENDING_FRAMES {
+                break;
+            }
+
+            let frame = self.pending_frames.pop_front().unwrap();
+            let mut timings = GpuPassTimings::default();
+            for (pass, query) in frame.queries {
+                if available {
+                    let nanoseconds = query.result_nanoseconds(state);
+                    *timings.get_mut(pass) += nanoseconds as f32 / 1_000_000.0;
+                }
+                self.free_queries.push(query);
+            }
+            if available {
+                self.timings = timings;
+            }
+        }
+    }
+
+    /// Finishes current frame, its results will be available in a few frames.
+    pub(crate) fn end_frame(&mut self, state: &PipelineState) {
+        while !self.stack.is_empty() {
+            self.end(state);
+        }
+        if !self.current_frame.queries.is_empty() {
+            self.pending_frames
+                .push_back(std::mem::take(&mut self.current_frame));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GpuPass, PassStack, TimerQueries};
+
+    #[derive(Default)]
+    struct MockQueries {
+        // Amount of queries that could be started, the rest will fail.
+        available: usize,
+        started: Vec<GpuPass>,
+        active: usize,
+    }
+
+    impl TimerQueries for MockQueries {
+        fn start(&mut self, pass: GpuPass) -> bool {
+            if self.available == 0 {
+                return false;
+            }
+            self.available -= 1;
+            self.started.push(pass);
+            self.active += 1;
+            true
+        }
+
+        fn stop(&mut self) {
+            assert_eq!(self.active, 1);
+            self.active -= 1;
+        }
+    }
+
+    #[test]
+    fn test_nested_passes() {
+        let mut queries = MockQueries {
+            available: 10,
+            ..Default::default()
+        };
+        let mut stack = PassStack::default();
+        assert!(stack.begin(&mut queries, GpuPass::Lighting));
+        assert!(stack.begin(&mut queries, GpuPass::Shadows));
+        assert!(stack.end(&mut queries, true));
+        assert!(stack.end(&mut queries, true));
+        assert!(stack.is_empty());
+        assert_eq!(queries.active, 0);
+        assert_eq!(
+            queries.started,
+            [GpuPass::Lighting, GpuPass::Shadows, GpuPass::Lighting]
+        );
+    }
+
+    #[test]
+    fn test_failed_query() {
+        let mut queries = MockQueries {
+            available: 1,
+            ..Default::default()
+        };
+        let mut stack = PassStack::default();
+        assert!(stack.begin(&mut queries, GpuPass::Lighting));
+        // Creation of the second query fails, the stack must be cleared and no query must be left
+        // active.
+        assert!(!stack.begin(&mut queries, GpuPass::Shadows));
+        assert!(stack.is_empty());
+        assert_eq!(queries.active, 0);
+
+        // Unbalanced `end` calls after the failure must do nothing.
+        assert!(stack.end(&mut queries, false));
+        assert!(stack.end(&mut queries, false));
+        assert_eq!(queries.active, 0);
+    }
+
+    #[test]
+    fn test_end_when_disabled() {
+        let mut queries = MockQueries {
+            available: 10,
+            ..Default::default()
+        };
+        let mut stack = PassStack::default();
+        assert!(stack.begin(&mut queries, GpuPass::Lighting));
+        assert!(stack.begin(&mut queries, GpuPass::Shadows));
+        // The profiler was disabled in the middle of a frame, the passes must be popped without
+        // resuming the outer one.
+        while !stack.is_empty() {
+            assert!(stack.end(&mut queries, false));
+        }
+        assert_eq!(queries.active, 0);
+        assert_eq!(queries.started, [GpuPass::Lighting, GpuPass::Shadows]);
+    }
+}
@@ -0,0 +1,997 @@
+//! Render graph allows user code to extend the renderer with custom render passes without forking
+//! it. Each pass declares render targets it reads from and writes to (by their names), may create
+//! its own render targets and may define explicit execution order constraints. The renderer then
+//! sorts every registered pass so that each render target is written before it is read and
+//! executes the passes at the appropriate stage of the frame.
+//!
+//! ## Render targets
+//!
+//! There are a few built-in render targets:
+//!
+//! - [`HDR_FRAME_TARGET`] - high dynamic range frame of the scene, before tone mapping.
+//! - [`LDR_FRAME_TARGET`] - final low dynamic range frame of the scene. It is available only at
+//!   [`RenderGraphStage::Ldr`].
+//! - [`DEPTH_TARGET`], [`NORMAL_TARGET`], [`AMBIENT_TARGET`] - read-only textures of G-Buffer.
+//!
+//! Custom render targets could be created by passes using [`RenderGraphPassDescriptor::with_target`].
+//! They're allocated per scene and have the size of the frame multiplied by the scale from their
+//! [`RenderTargetDescriptor`].
+//!
+//! ## Example
+//!
+//! The following example shows a pass, that renders some mask into a custom render target and
+//! another pass, that uses the mask to draw outlines on top of the final frame. The second pass
+//! was registered first, but it will be executed after the first one, because it reads the mask.
+//!
+//! ```rust
+//! use fyrox::{
+//!     core::log::Log,
+//!     renderer::{
+//!         framework::{error::FrameworkError, gpu_texture::PixelKind},
+//!         render_graph::{
+//!             RenderGraphPass, RenderGraphPassContext, RenderGraphPassDescriptor,
+//!             RenderGraphStage, RenderTargetDescriptor, LDR_FRAME_TARGET,
+//!         },
+//!         RenderPassStatistics, Renderer,
+//!     },
+//! };
+//! use std::{cell::RefCell, rc::Rc};
+//!
+//! struct MaskPass;
+//!
+//! impl RenderGraphPass for MaskPass {
+//!     fn descriptor(&self) -> RenderGraphPassDescriptor {
+//!         RenderGraphPassDescriptor::new("Mask", RenderGraphStage::Ldr).with_target(
+//!             "OutlineMask",
+//!             RenderTargetDescriptor::new(PixelKind::R8).with_scene_depth(true),
+//!         )
+//!     }
+//!
+//!     fn execute(
+//!         &mut self,
+//!         mut ctx: RenderGraphPassContext,
+//!     ) -> Result<RenderPassStatistics, FrameworkError> {
+//!         let _framebuffer = ctx.targets.framebuffer("OutlineMask").unwrap();
+//!         // Draw something in the mask here.
+//!         Ok(Default::default())
+//!     }
+//! }
+//!
+//! struct OutlinePass;
+//!
+//! impl RenderGraphPass for OutlinePass {
+//!     fn descriptor(&self) -> RenderGraphPassDescriptor {
+//!         RenderGraphPassDescriptor::new("Outline", RenderGraphStage::Ldr)
+//!             .with_read("OutlineMask")
+//!             .with_write(LDR_FRAME_TARGET)
+//!     }
+//!
+//!     fn execute(
+//!         &mut self,
+//!         mut ctx: RenderGraphPassContext,
+//!     ) -> Result<RenderPassStatistics, FrameworkError> {
+//!         let _mask = ctx.targets.texture("OutlineMask").unwrap();
+//!         let _frame = ctx.targets.framebuffer(LDR_FRAME_TARGET).unwrap();
+//!         // Draw outlines using the mask here.
+//!         Ok(Default::default())
+//!     }
+//! }
+//!
+//! fn register_passes(renderer: &mut Renderer) {
+//!     let graph = renderer.render_graph_mut();
+//!     graph.add_pass(Rc::new(RefCell::new(OutlinePass)));
+//!     graph.add_pass(Rc::new(RefCell::new(MaskPass)));
+//!     if let Err(err) = graph.execution_order() {
+//!         Log::err(format!("Invalid render graph: {err}"));
+//!     }
+//! }
+//! ```
+
+use crate::{
+    core::{log::Log, math::Rect, pool::Handle},
+    renderer::{
+        batch::RenderDataBatchStorage,
+        cache::{geometry::GeometryCache, shader::ShaderCache, texture::TextureCache},
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, FrameBuffer},
+            geometry_buffer::GeometryBuffer,
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::PipelineState,
+        },
+        storage::MatrixStorageCache,
+        QualitySettings, RenderPassStatistics,
+    },
+    scene::{camera::Camera, Scene},
+};
+use fxhash::FxHashMap;
+use std::{
+    cell::RefCell,
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::{Display, Formatter},
+    rc::Rc,
+};
+
+/// Name of the built-in render target with high dynamic range frame of the scene.
+pub const HDR_FRAME_TARGET: &str = "HdrFrame";
+/// Name of the built-in render target with final (tone mapped) frame of the scene. It is available
+/// only at [`RenderGraphStage::Ldr`].
+pub const LDR_FRAME_TARGET: &str = "LdrFrame";
+/// Name of the built-in read-only render target with depth values of the scene.
+pub const DEPTH_TARGET: &str = "Depth";
+/// Name of the built-in read-only render target with world-space normals of the scene.
+pub const NORMAL_TARGET: &str = "Normal";
+/// Name of the built-in read-only render target with ambient lighting of the scene.
+pub const AMBIENT_TARGET: &str = "Ambient";
+
+const READ_ONLY_TARGETS: [&str; 3] = [DEPTH_TARGET, NORMAL_TARGET, AMBIENT_TARGET];
+
+fn is_built_in_target(name: &str) -> bool {
+    name == HDR_FRAME_TARGET || name == LDR_FRAME_TARGET || READ_ONLY_TARGETS.contains(&name)
+}
+
+/// A stage of the frame at which a render pass will be executed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RenderGraphStage {
+    /// A pass will be executed right after the scene was rendered into the high dynamic range
+    /// frame, before tone mapping, bloom and anti-aliasing. Use this stage for effects that must
+    /// be affected by tone mapping (for example - planar reflections).
+    Hdr,
+    /// A pass will be executed after the final frame was produced. Use this stage for effects like
+    /// outlines, custom post effects, etc.
+    Ldr,
+}
+
+/// Describes a custom render target, that will be created by the renderer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderTargetDescriptor {
+    /// Pixel format of the render target.
+    pub pixel_kind: PixelKind,
+    /// Size of the render target relative to the size of the frame.
+    pub scale: f32,
+    /// If `true`, the render target will share depth and stencil buffer with the scene, thus
+    /// allowing the pass to perform depth testing against the scene geometry. Could be used only
+    /// with the scale of `1.0`.
+    pub use_scene_depth: bool,
+}
+
+impl RenderTargetDescriptor {
+    /// Creates a new descriptor of a frame-sized render target with the given pixel format.
+    pub fn new(pixel_kind: PixelKind) -> Self {
+        Self {
+            pixel_kind,
+            scale: 1.0,
+            use_scene_depth: false,
+        }
+    }
+
+    /// Sets the size of the render target relative to the size of the frame.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Defines whether the render target should share depth and stencil buffer with the scene.
+    pub fn with_scene_depth(mut self, use_scene_depth: bool) -> Self {
+        self.use_scene_depth = use_scene_depth;
+        self
+    }
+
+    fn size(&self, frame_width: usize, frame_height: usize) -> (usize, usize) {
+        (
+            ((frame_width as f32 * self.scale) as usize).max(1),
+            ((frame_height as f32 * self.scale) as usize).max(1),
+        )
+    }
+}
+
+/// Describes inputs, outputs and execution order constraints of a render pass.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderGraphPassDescriptor {
+    /// Unique name of the pass.
+    pub name: String,
+    /// A stage at which the pass will be executed.
+    pub stage: RenderGraphStage,
+    /// Names of render targets that will be read by the pass.
+    pub reads: Vec<String>,
+    /// Names of render targets that will be written by the pass.
+    pub writes: Vec<String>,
+    /// Custom render targets that will be created for the pass. The pass is allowed to write to
+    /// these targets.
+    pub targets: Vec<(String, RenderTargetDescriptor)>,
+    /// Names of passes that must be executed before the pass.
+    pub after: Vec<String>,
+    /// Names of passes that must be executed after the pass.
+    pub before: Vec<String>,
+}
+
+impl RenderGraphPassDescriptor {
+    /// Creates a new descriptor of a pass with the given name, that will be executed at the
+    /// given stage.
+    pub fn new<S: Into<String>>(name: S, stage: RenderGraphStage) -> Self {
+        Self {
+            name: name.into(),
+            stage,
+            reads: Default::default(),
+            writes: Default::default(),
+            targets: Default::default(),
+            after: Default::default(),
+            before: Default::default(),
+        }
+    }
+
+    /// Adds a render target that will be read by the pass.
+    pub fn with_read<S: Into<String>>(mut self, target: S) -> Self {
+        self.reads.push(target.into());
+        self
+    }
+
+    /// Adds a render target that will be written by the pass.
+    pub fn with_write<S: Into<String>>(mut self, target: S) -> Self {
+        self.writes.push(target.into());
+        self
+    }
+
+    /// Adds a custom render target, that will be created by the renderer and written by the pass.
+    /// Other passes may use the target by its name.
+    pub fn with_target<S: Into<String>>(
+        mut self,
+        target: S,
+        descriptor: RenderTargetDescriptor,
+    ) -> Self {
+        self.targets.push((target.into(), descriptor));
+        self
+    }
+
+    /// Forces the pass to be executed after a pass with the given name.
+    pub fn with_after<S: Into<String>>(mut self, pass: S) -> Self {
+        self.after.push(pass.into());
+        self
+    }
+
+    /// Forces the pass to be executed before a pass with the given name.
+    pub fn with_before<S: Into<String>>(mut self, pass: S) -> Self {
+        self.before.push(pass.into());
+        self
+    }
+
+    fn is_readable(&self, target: &str) -> bool {
+        self.reads.iter().any(|t| t == target)
+    }
+
+    fn is_writable(&self, target: &str) -> bool {
+        self.writes.iter().any(|t| t == target) || self.targets.iter().any(|(t, _)| t == target)
+    }
+}
+
+/// A set of possible errors that may occur when building execution order of a render graph.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderGraphError {
+    /// There are two or more passes with the same name.
+    DuplicatedPassName(String),
+    /// A pass has an order constraint that refers to an unknown pass.
+    UnknownPass {
+        /// Name of the pass.
+        pass: String,
+        /// Name of the unknown pass.
+        dependency: String,
+    },
+    /// A pass uses a render target, that is neither built-in nor created by any pass.
+    UnknownTarget {
+        /// Name of the pass.
+        pass: String,
+        /// Name of the unknown target.
+        target: String,
+    },
+    /// A pass tries to write to a read-only built-in render target.
+    ReadOnlyTarget {
+        /// Name of the pass.
+        pass: String,
+        /// Name of the target.
+        target: String,
+    },
+    /// A pass uses a render target, that is not available at the stage of the pass.
+    UnavailableTarget {
+        /// Name of the pass.
+        pass: String,
+        /// Name of the target.
+        target: String,
+    },
+    /// A render target was created by more than one pass, or it uses a built-in name, or it has
+    /// invalid descriptor.
+    InvalidTarget(String),
+    /// A pass of [`RenderGraphStage::Hdr`] stage depends on a pass of [`RenderGraphStage::Ldr`]
+    /// stage.
+    StageMismatch {
+        /// Name of the pass.
+        pass: String,
+        /// Name of the pass, that must be executed before the pass.
+        dependency: String,
+    },
+    /// Passes have cyclic dependencies, contains names of the passes in the cycle.
+    Cycle(Vec<String>),
+}
+
+impl Display for RenderGraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderGraphError::DuplicatedPassName(name) => {
+                write!(f, "There are more than one pass with {name} name.")
+            }
+            RenderGraphError::UnknownPass { pass, dependency } => {
+                write!(f, "Pass {pass} depends on unknown pass {dependency}.")
+            }
+            RenderGraphError::UnknownTarget { pass, target } => {
+                write!(f, "Pass {pass} uses unknown render target {target}.")
+            }
+            RenderGraphError::ReadOnlyTarget { pass, target } => {
+                write!(f, "Pass {pass} writes to read-only render target {target}.")
+            }
+            RenderGraphError::UnavailableTarget { pass, target } => {
+                write!(
+                    f,
+                    "Pass {pass} uses render target {target}, that is not available at its stage."
+                )
+            }
+            RenderGraphError::InvalidTarget(target) => {
+                write!(
+                    f,
+                    "Render target {target} is created more than once, uses a built-in name \
+                    or has invalid descriptor."
+                )
+            }
+            RenderGraphError::StageMismatch { pass, dependency } => {
+                write!(
+                    f,
+                    "Pass {pass} of HDR stage cannot be executed after pass {dependency} \
+                    of LDR stage."
+                )
+            }
+            RenderGraphError::Cycle(passes) => {
+                write!(f, "Passes {passes:?} have cyclic dependencies.")
+            }
+        }
+    }
+}
+
+/// A set of render targets available to a render pass. Only the targets declared in the descriptor
+/// of the pass could be accessed.
+pub struct RenderGraphTargets<'a> {
+    descriptor: &'a RenderGraphPassDescriptor,
+    hdr_framebuffer: &'a mut FrameBuffer,
+    ldr_framebuffer: &'a mut FrameBuffer,
+    depth_texture: Rc<RefCell<GpuTexture>>,
+    normal_texture: Rc<RefCell<GpuTexture>>,
+    ambient_texture: Rc<RefCell<GpuTexture>>,
+    custom: &'a mut FxHashMap<String, CustomRenderTarget>,
+}
+
+impl<'a> RenderGraphTargets<'a> {
+    /// Returns a texture of a render target with the given name. The target must be declared as
+    /// an input of the pass, otherwise the method returns `None`.
+    pub fn texture(&self, name: &str) -> Option<Rc<RefCell<GpuTexture>>> {
+        if !self.descriptor.is_readable(name) {
+            return None;
+        }
+        match name {
+            HDR_FRAME_TARGET => Some(self.hdr_framebuffer.color_attachments()[0].texture.clone()),
+            LDR_FRAME_TARGET => Some(self.ldr_framebuffer.color_attachments()[0].texture.clone()),
+            DEPTH_TARGET => Some(self.depth_texture.clone()),
+            NORMAL_TARGET => Some(self.normal_texture.clone()),
+            AMBIENT_TARGET => Some(self.ambient_texture.clone()),
+            _ => self
+                .custom
+                .get(name)
+                .map(|target| target.framebuffer.color_attachments()[0].texture.clone()),
+        }
+    }
+
+    /// Returns a frame buffer of a render target with the given name. The target must be declared
+    /// as an output of the pass, otherwise the method returns `None`.
+    pub fn framebuffer(&mut self, name: &str) -> Option<&mut FrameBuffer> {
+        if !self.descriptor.is_writable(name) {
+            return None;
+        }
+        match name {
+            HDR_FRAME_TARGET => Some(self.hdr_framebuffer),
+            LDR_FRAME_TARGET => Some(self.ldr_framebuffer),
+            _ => self
+                .custom
+                .get_mut(name)
+                .map(|target| &mut target.framebuffer),
+        }
+    }
+}
+
+/// A context for render graph passes.
+pub struct RenderGraphPassContext<'a, 'b> {
+    /// A pipeline state that is used as a wrapper to underlying graphics API.
+    pub pipeline_state: &'a PipelineState,
+
+    /// A texture cache that uploads engine's `Texture` as internal `GpuTexture` to GPU.
+    pub texture_cache: &'a mut TextureCache,
+
+    /// A geometry cache that uploads engine's `SurfaceData` as internal `GeometryBuffer` to GPU.
+    pub geometry_cache: &'a mut GeometryCache,
+
+    /// A cache that stores all native shaders associated with a shader resource.
+    pub shader_cache: &'a mut ShaderCache,
+
+    /// A storage that contains "pre-compiled" groups of render data (batches).
+    pub batch_storage: &'a RenderDataBatchStorage,
+
+    /// Current quality settings of the renderer.
+    pub quality_settings: &'a QualitySettings,
+
+    /// Matrix storage is container of procedural textures that stores matrices for bones.
+    pub matrix_storage: &'a mut MatrixStorageCache,
+
+    /// A full-screen quad, that could be used for post effects.
+    pub quad: &'a GeometryBuffer,
+
+    /// An 1x1 white pixel texture that could be used a stub when there is no texture.
+    pub white_dummy: Rc<RefCell<GpuTexture>>,
+
+    /// An 1x1 pixel texture with (0, 1, 0) vector that could be used a stub when
+    /// there is no normal map.
+    pub normal_dummy: Rc<RefCell<GpuTexture>>,
+
+    /// An 1x1 black pixel texture that could be used a stub when there is no texture.
+    pub black_dummy: Rc<RefCell<GpuTexture>>,
+
+    /// A scene being rendered.
+    pub scene: &'b Scene,
+
+    /// A camera from the scene that is used as "eyes".
+    pub camera: &'b Camera,
+
+    /// A viewport of the camera.
+    pub viewport: Rect<i32>,
+
+    /// A handle of the scene being rendered.
+    pub scene_handle: Handle<Scene>,
+
+    /// Render targets, that were declared by the pass.
+    pub targets: RenderGraphTargets<'a>,
+}
+
+/// A trait for render graph passes. See module docs for more info.
+pub trait RenderGraphPass {
+    /// Returns a descriptor of the pass. It is called only when the render graph is rebuilt, call
+    /// [`RenderGraph::invalidate`] if the descriptor of the pass was changed after it was added
+    /// to the graph.
+    fn descriptor(&self) -> RenderGraphPassDescriptor;
+
+    /// Executes the pass. It will be called for **each** scene registered in the engine, but you
+    /// are able to filter out scene by its handle.
+    fn execute(
+        &mut self,
+        ctx: RenderGraphPassContext,
+    ) -> Result<RenderPassStatistics, FrameworkError>;
+}
+
+struct RenderGraphEntry {
+    pass: Rc<RefCell<dyn RenderGraphPass>>,
+    descriptor: RenderGraphPassDescriptor,
+}
+
+struct CompiledRenderGraph {
+    order: Vec<usize>,
+    targets: FxHashMap<String, RenderTargetDescriptor>,
+}
+
+/// A set of render passes sorted in execution order. See module docs for more info.
+#[derive(Default)]
+pub struct RenderGraph {
+    entries: Vec<RenderGraphEntry>,
+    compiled: Option<Result<CompiledRenderGraph, RenderGraphError>>,
+}
+
+impl RenderGraph {
+    /// Adds a new pass to the graph.
+    pub fn add_pass(&mut self, pass: Rc<RefCell<dyn RenderGraphPass>>) {
+        let descriptor = pass.borrow().descriptor();
+        self.entries.push(RenderGraphEntry { pass, descriptor });
+        self.compiled = None;
+    }
+
+    /// Removes the specified pass from the graph.
+    pub fn remove_pass(&mut self, pass: &Rc<RefCell<dyn RenderGraphPass>>) {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| Rc::ptr_eq(&entry.pass, pass))
+        {
+            self.entries.remove(index);
+            self.compiled = None;
+        }
+    }
+
+    /// Removes every pass from the graph.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.compiled = None;
+    }
+
+    /// Returns an iterator over every pass of the graph in the order of addition.
+    pub fn passes(&self) -> impl Iterator<Item = &Rc<RefCell<dyn RenderGraphPass>>> {
+        self.entries.iter().map(|entry| &entry.pass)
+    }
+
+    /// Fetches descriptors of every pass again and forces the graph to be rebuilt.
+    pub fn invalidate(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry.descriptor = entry.pass.borrow().descriptor();
+        }
+        self.compiled = None;
+    }
+
+    /// Returns names of the passes in the order of execution or an error, if the graph is invalid.
+    pub fn execution_order(&mut self) -> Result<Vec<String>, RenderGraphError> {
+        let _ = self.compile();
+        match self.compiled.as_ref().unwrap() {
+            Ok(compiled) => Ok(compiled
+                .order
+                .iter()
+                .map(|i| self.entries[*i].descriptor.name.clone())
+                .collect()),
+            Err(err) => Err(err.clone()),
+        }
+    }
+
+    fn compile(&mut self) -> &Result<CompiledRenderGraph, RenderGraphError> {
+        if self.compiled.is_none() {
+            let result = compile(&self.entries);
+            if let Err(ref err) = result {
+                Log::err(format!("Unable to build render graph. Reason: {err}"));
+            }
+            self.compiled = Some(result);
+        }
+        self.compiled.as_ref().unwrap()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Makes sure, that every custom render target of the graph exists and has correct size.
+    pub(crate) fn prepare_targets(
+        &mut self,
+        state: &PipelineState,
+        targets: &mut RenderGraphTargetStorage,
+        width: usize,
+        height: usize,
+        scene_depth: &Rc<RefCell<GpuTexture>>,
+    ) -> Result<(), FrameworkError> {
+        let Ok(compiled) = self.compile() else {
+            return Ok(());
+        };
+
+        targets
+            .targets
+            .retain(|name, target| compiled.targets.get(name) == Some(&target.descriptor));
+
+        for (name, descriptor) in compiled.targets.iter() {
+            let size = descriptor.size(width, height);
+            if targets
+                .targets
+                .get(name)
+                .map_or(true, |target| target.size != size)
+            {
+                targets.targets.insert(
+                    name.clone(),
+                    CustomRenderTarget::new(state, descriptor.clone(), size, scene_depth)?,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes every pass of the given stage, `execute_pass` is responsible for preparing a context
+    /// for the pass and executing the pass.
+    pub(crate) fn execute<F>(
+        &mut self,
+        stage: RenderGraphStage,
+        mut execute_pass: F,
+    ) -> Result<RenderPassStatistics, FrameworkError>
+    where
+        F: FnMut(
+            &RenderGraphPassDescriptor,
+            &mut dyn RenderGraphPass,
+        ) -> Result<RenderPassStatistics, FrameworkError>,
+    {
+        let mut stats = RenderPassStatistics::default();
+        let _ = self.compile();
+        let Some(Ok(compiled)) = self.compiled.as_ref() else {
+            return Ok(stats);
+        };
+        for index in compiled.order.iter() {
+            let entry = &self.entries[*index];
+            if entry.descriptor.stage == stage {
+                stats += execute_pass(&entry.descriptor, &mut *entry.pass.borrow_mut())?;
+            }
+        }
+        Ok(stats)
+    }
+}
+
+pub(crate) struct CustomRenderTarget {
+    descriptor: RenderTargetDescriptor,
+    size: (usize, usize),
+    framebuffer: FrameBuffer,
+}
+
+impl CustomRenderTarget {
+    fn new(
+        state: &PipelineState,
+        descriptor: RenderTargetDescriptor,
+        size: (usize, usize),
+        scene_depth: &Rc<RefCell<GpuTexture>>,
+    ) -> Result<Self, FrameworkError> {
+        let mut texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle {
+                width: size.0,
+                height: size.1,
+            },
+            descriptor.pixel_kind,
+            MinificationFilter::Linear,
+            MagnificationFilter::Linear,
+            1,
+            None,
+        )?;
+        texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        let framebuffer = FrameBuffer::new(
+            state,
+            if descriptor.use_scene_depth {
+                Some(Attachment {
+                    kind: AttachmentKind::DepthStencil,
+                    texture: scene_depth.clone(),
+                })
+            } else {
+                None
+            },
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: Rc::new(RefCell::new(texture)),
+            }],
+        )?;
+
+        Ok(Self {
+            descriptor,
+            size,
+            framebuffer,
+        })
+    }
+}
+
+/// Per-scene storage of custom render targets of the render graph.
+#[derive(Default)]
+pub struct RenderGraphTargetStorage {
+    targets: FxHashMap<String, CustomRenderTarget>,
+}
+
+impl RenderGraphTargetStorage {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn targets<'a>(
+        &'a mut self,
+        descriptor: &'a RenderGraphPassDescriptor,
+        hdr_framebuffer: &'a mut FrameBuffer,
+        ldr_framebuffer: &'a mut FrameBuffer,
+        depth_texture: Rc<RefCell<GpuTexture>>,
+        normal_texture: Rc<RefCell<GpuTexture>>,
+        ambient_texture: Rc<RefCell<GpuTexture>>,
+    ) -> RenderGraphTargets<'a> {
+        RenderGraphTargets {
+            descriptor,
+            hdr_framebuffer,
+            ldr_framebuffer,
+            depth_texture,
+            normal_texture,
+            ambient_texture,
+            custom: &mut self.targets,
+        }
+    }
+}
+
+fn compile(entries: &[RenderGraphEntry]) -> Result<CompiledRenderGraph, RenderGraphError> {
+    let mut pass_indices = FxHashMap::default();
+    for (index, entry) in entries.iter().enumerate() {
+        if pass_indices
+            .insert(entry.descriptor.name.as_str(), index)
+            .is_some()
+        {
+            return Err(RenderGraphError::DuplicatedPassName(
+                entry.descriptor.name.clone(),
+            ));
+        }
+    }
+
+    let mut targets = FxHashMap::default();
+    for entry in entries {
+        for (name, descriptor) in entry.descriptor.targets.iter() {
+            if is_built_in_target(name)
+                || descriptor.scale <= 0.0
+                || (descriptor.use_scene_depth && descriptor.scale != 1.0)
+                || targets.insert(name.clone(), descriptor.clone()).is_some()
+            {
+                return Err(RenderGraphError::InvalidTarget(name.clone()));
+            }
+        }
+    }
+
+    // Validate targets and collect writers and readers of each target.
+    let mut writers = FxHashMap::<&str, Vec<usize>>::default();
+    let mut readers = FxHashMap::<&str, Vec<usize>>::default();
+    for (index, entry) in entries.iter().enumerate() {
+        let descriptor = &entry.descriptor;
+        let written = descriptor
+            .writes
+            .iter()
+            .chain(descriptor.targets.iter().map(|(name, _)| name));
+        for (target, is_write) in descriptor
+            .reads
+            .iter()
+            .map(|t| (t, false))
+            .chain(written.map(|t| (t, true)))
+        {
+            if !is_built_in_target(target) && !targets.contains_key(target) {
+                return Err(RenderGraphError::UnknownTarget {
+                    pass: descriptor.name.clone(),
+                    target: target.clone(),
+                });
+            }
+            if is_write && READ_ONLY_TARGETS.contains(&target.as_str()) {
+                return Err(RenderGraphError::ReadOnlyTarget {
+                    pass: descriptor.name.clone(),
+                    target: target.clone(),
+                });
+            }
+            if target == LDR_FRAME_TARGET && descriptor.stage == RenderGraphStage::Hdr {
+                return Err(RenderGraphError::UnavailableTarget {
+                    pass: descriptor.name.clone(),
+                    target: target.clone(),
+                });
+            }
+            if is_write {
+                writers.entry(target.as_str()).or_default().push(index);
+            } else {
+                readers.entry(target.as_str()).or_default().push(index);
+            }
+        }
+    }
+
+    // Collect edges of the graph, an edge (a, b) means that pass `a` must be executed before `b`.
+    let mut edges = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        for (dependencies, is_after) in [
+            (&entry.descriptor.after, true),
+            (&entry.descriptor.before, false),
+        ] {
+            for dependency in dependencies.iter() {
+                let Some(&other) = pass_indices.get(dependency.as_str()) else {
+                    return Err(RenderGraphError::UnknownPass {
+                        pass: entry.descriptor.name.clone(),
+                        dependency: dependency.clone(),
+                    });
+                };
+                edges.push(if is_after {
+                    (other, index)
+                } else {
+                    (index, other)
+                });
+            }
+        }
+    }
+    for (target, target_readers) in readers.iter() {
+        if let Some(target_writers) = writers.get(target) {
+            for &writer in target_writers {
+                for &reader in target_readers {
+                    if writer != reader {
+                        edges.push((writer, reader));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut successors = vec![Vec::new(); entries.len()];
+    let mut in_degree = vec![0usize; entries.len()];
+    for (from, to) in edges {
+        if entries[from].descriptor.stage > entries[to].descriptor.stage {
+            return Err(RenderGraphError::StageMismatch {
+                pass: entries[to].descriptor.name.clone(),
+                dependency: entries[from].descriptor.name.clone(),
+            });
+        }
+        successors[from].push(to);
+        in_degree[to] += 1;
+    }
+
+    // Kahn's algorithm, ties are resolved by the stage and then by the order of addition.
+    let mut queue = BinaryHeap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if in_degree[index] == 0 {
+            queue.push(Reverse((entry.descriptor.stage, index)));
+        }
+    }
+    let mut order = Vec::with_capacity(entries.len());
+    while let Some(Reverse((_, index))) = queue.pop() {
+        order.push(index);
+        for &successor in successors[index].iter() {
+            in_degree[successor] -= 1;
+            if in_degree[successor] == 0 {
+                queue.push(Reverse((entries[successor].descriptor.stage, successor)));
+            }
+        }
+    }
+
+    if order.len() != entries.len() {
+        return Err(RenderGraphError::Cycle(
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| in_degree[*index] > 0)
+                .map(|(_, entry)| entry.descriptor.name.clone())
+                .collect(),
+        ));
+    }
+
+    Ok(CompiledRenderGraph { order, targets })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::{
+        framework::{error::FrameworkError, gpu_texture::PixelKind},
+        render_graph::{
+            RenderGraph, RenderGraphError, RenderGraphPass, RenderGraphPassContext,
+            RenderGraphPassDescriptor, RenderGraphStage, RenderTargetDescriptor, DEPTH_TARGET,
+            HDR_FRAME_TARGET, LDR_FRAME_TARGET,
+        },
+        RenderPassStatistics,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    struct TestPass(RenderGraphPassDescriptor);
+
+    impl RenderGraphPass for TestPass {
+        fn descriptor(&self) -> RenderGraphPassDescriptor {
+            self.0.clone()
+        }
+
+        fn execute(
+            &mut self,
+            _ctx: RenderGraphPassContext,
+        ) -> Result<RenderPassStatistics, FrameworkError> {
+            Ok(Default::default())
+        }
+    }
+
+    fn graph(descriptors: Vec<RenderGraphPassDescriptor>) -> RenderGraph {
+        let mut graph = RenderGraph::default();
+        for descriptor in descriptors {
+            graph.add_pass(Rc::new(RefCell::new(TestPass(descriptor))));
+        }
+        graph
+    }
+
+    #[test]
+    fn test_render_graph_order() {
+        let mut graph = graph(vec![
+            RenderGraphPassDescriptor::new("Outline", RenderGraphStage::Ldr)
+                .with_read("Mask")
+                .with_write(LDR_FRAME_TARGET),
+            RenderGraphPassDescriptor::new("Mask", RenderGraphStage::Ldr)
+                .with_target("Mask", RenderTargetDescriptor::new(PixelKind::R8))
+                .with_read(DEPTH_TARGET),
+            RenderGraphPassDescriptor::new("Reflections", RenderGraphStage::Hdr)
+                .with_write(HDR_FRAME_TARGET),
+            RenderGraphPassDescriptor::new("Fog", RenderGraphStage::Hdr)
+                .with_write(HDR_FRAME_TARGET)
+                .with_before("Reflections"),
+            RenderGraphPassDescriptor::new("Vignette", RenderGraphStage::Ldr)
+                .with_write(LDR_FRAME_TARGET)
+                .with_after("Outline"),
+        ]);
+
+        assert_eq!(
+            graph.execution_order(),
+            Ok(vec![
+                "Fog".to_string(),
+                "Reflections".to_string(),
+                "Mask".to_string(),
+                "Outline".to_string(),
+                "Vignette".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_render_graph_errors() {
+        let mut cycle = graph(vec![
+            RenderGraphPassDescriptor::new("A", RenderGraphStage::Ldr).with_after("B"),
+            RenderGraphPassDescriptor::new("B", RenderGraphStage::Ldr).with_after("A"),
+            RenderGraphPassDescriptor::new("C", RenderGraphStage::Ldr),
+        ]);
+        assert_eq!(
+            cycle.execution_order(),
+            Err(RenderGraphError::Cycle(vec![
+                "A".to_string(),
+                "B".to_string()
+            ]))
+        );
+
+        let mut stage_mismatch = graph(vec![
+            RenderGraphPassDescriptor::new("A", RenderGraphStage::Ldr)
+                .with_target("T", RenderTargetDescriptor::new(PixelKind::RGBA8)),
+            RenderGraphPassDescriptor::new("B", RenderGraphStage::Hdr).with_read("T"),
+        ]);
+        assert_eq!(
+            stage_mismatch.execution_order(),
+            Err(RenderGraphError::StageMismatch {
+                pass: "B".to_string(),
+                dependency: "A".to_string()
+            })
+        );
+
+        let mut unknown_target = graph(vec![RenderGraphPassDescriptor::new(
+            "A",
+            RenderGraphStage::Ldr,
+        )
+        .with_read("T")]);
+        assert_eq!(
+            unknown_target.execution_order(),
+            Err(RenderGraphError::UnknownTarget {
+                pass: "A".to_string(),
+                target: "T".to_string()
+            })
+        );
+
+        let mut read_only = graph(vec![RenderGraphPassDescriptor::new(
+            "A",
+            RenderGraphStage::Hdr,
+        )
+        .with_write(DEPTH_TARGET)]);
+        assert_eq!(
+            read_only.execution_order(),
+            Err(RenderGraphError::ReadOnlyTarget {
+                pass: "A".to_string(),
+                target: DEPTH_TARGET.to_string()
+            })
+        );
+
+        let mut duplicate = graph(vec![
+            RenderGraphPassDescriptor::new("A", RenderGraphStage::Hdr),
+            RenderGraphPassDescriptor::new("A", RenderGraphStage::Ldr),
+        ]);
+        assert_eq!(
+            duplicate.execution_order(),
+            Err(RenderGraphError::DuplicatedPassName("A".to_string()))
+        );
+
+        let mut invalid_target = graph(vec![RenderGraphPassDescriptor::new(
+            "A",
+            RenderGraphStage::Hdr,
+        )
+        .with_target(
+            "T",
+            RenderTargetDescriptor::new(PixelKind::RGBA8)
+                .with_scale(0.5)
+                .with_scene_depth(true),
+        )]);
+        assert_eq!(
+            invalid_target.execution_order(),
+            Err(RenderGraphError::InvalidTarget("T".to_string()))
+        );
+    }
+}
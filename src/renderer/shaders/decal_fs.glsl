@@ -1,18 +1,35 @@
 uniform sampler2D sceneDepth;
 uniform sampler2D diffuseTexture;
 uniform sampler2D normalTexture;
+uniform sampler2D roughnessTexture;
 uniform usampler2D decalMask;
 uniform mat4 invViewProj;
 uniform mat4 invWorldDecal;
 uniform vec2 resolution;
 uniform vec4 color;
 uniform uint layerIndex;
+uniform vec3 projectionAxis;
+uniform vec3 cameraPosition;
+uniform vec2 angleFade;
+uniform vec2 distanceFade;
+// When set, only roughness is written to the first render target (which is material texture
+// in this case).
+uniform bool roughnessPass;
 
 layout(location = 0) out vec4 outDiffuseMap;
 layout(location = 1) out vec4 outNormalMap;
 
 in vec4 clipSpacePosition;
 
+// Returns 1.0 before `range.x` and 0.0 after `range.y`, fading is disabled if `range.y <= range.x`.
+float Fade(float value, vec2 range)
+{
+    if (range.y <= range.x) {
+        return 1.0;
+    }
+    return 1.0 - clamp((value - range.x) / (range.y - range.x), 0.0, 1.0);
+}
+
 void main()
 {
     vec2 screenPos = clipSpacePosition.xy / clipSpacePosition.w;
@@ -43,18 +60,31 @@ void main()
 
     vec2 decalTexCoord = decalSpacePosition.xz + 0.5;
 
-    outDiffuseMap = color * texture(diffuseTexture, decalTexCoord);
-
     vec3 fragmentTangent = dFdx(sceneWorldPosition);
     vec3 fragmentBinormal = dFdy(sceneWorldPosition);
-    vec3 fragmentNormal = cross(fragmentTangent, fragmentBinormal);
+    vec3 fragmentNormal = normalize(cross(fragmentTangent, fragmentBinormal));
+
+    float angle = acos(clamp(dot(fragmentNormal, projectionAxis), -1.0, 1.0));
+    float distance = length(sceneWorldPosition - cameraPosition);
+    float opacity = Fade(angle, angleFade) * Fade(distance, distanceFade);
+
+    vec4 diffuse = color * texture(diffuseTexture, decalTexCoord);
+    diffuse.a *= opacity;
+
+    if (roughnessPass) {
+        // Only green channel is written, the rest of material properties are left untouched.
+        outDiffuseMap = vec4(0.0, texture(roughnessTexture, decalTexCoord).r, 0.0, diffuse.a);
+        return;
+    }
+
+    outDiffuseMap = diffuse;
 
     mat3 tangentToWorld;
     tangentToWorld[0] = normalize(fragmentTangent); // Tangent
     tangentToWorld[1] = normalize(fragmentBinormal); // Binormal
-    tangentToWorld[2] = normalize(fragmentNormal); // Normal
+    tangentToWorld[2] = fragmentNormal; // Normal
 
     vec3 rawNormal = (texture(normalTexture, decalTexCoord) * 2.0 - 1.0).xyz;
     vec3 worldSpaceNormal = tangentToWorld * rawNormal;
     outNormalMap = vec4(worldSpaceNormal * 0.5 + 0.5, outDiffuseMap.a);
-}
\ No newline at end of file
+}
@@ -20,7 +20,9 @@ uniform sampler2D shadowCascade1;
 uniform sampler2D shadowCascade2;
 
 uniform bool shadowsEnabled;
-uniform float shadowBias;
+uniform float shadowBiases[NUM_CASCADES];
+uniform int cascadeCount;
+uniform bool debugCascades;
 uniform bool softShadows;
 uniform float shadowMapInvSize;
 
@@ -28,9 +30,9 @@ in vec2 texCoord;
 out vec4 FragColor;
 
 // Returns **inverted** shadow factor where 1 - fully bright, 0 - fully in shadow.
-float CsmGetShadow(in sampler2D sampler, in vec3 fragmentPosition, in mat4 lightViewProjMatrix)
+float CsmGetShadow(in sampler2D sampler, in vec3 fragmentPosition, in int cascadeIndex)
 {
-    return S_SpotShadowFactor(shadowsEnabled, softShadows, shadowBias, fragmentPosition, lightViewProjMatrix, shadowMapInvSize, sampler);
+    return S_SpotShadowFactor(shadowsEnabled, softShadows, shadowBiases[cascadeIndex], fragmentPosition, lightViewProjMatrices[cascadeIndex], shadowMapInvSize, sampler);
 }
 
 void main()
@@ -54,13 +56,31 @@ void main()
     float fragmentZViewSpace = abs((viewMatrix * vec4(fragmentPosition, 1.0)).z);
 
     float shadow = 1.0;
-    if (fragmentZViewSpace <= cascadeDistances[0]) {
-        shadow = CsmGetShadow(shadowCascade0, fragmentPosition, lightViewProjMatrices[0]);
-    } else if (fragmentZViewSpace <= cascadeDistances[1]) {
-        shadow = CsmGetShadow(shadowCascade1, fragmentPosition, lightViewProjMatrices[1]);
-    } else if (fragmentZViewSpace <= cascadeDistances[2]) {
-        shadow = CsmGetShadow(shadowCascade2, fragmentPosition, lightViewProjMatrices[2]);
+    int cascadeIndex = -1;
+    if (cascadeCount > 0 && fragmentZViewSpace <= cascadeDistances[0]) {
+        cascadeIndex = 0;
+        shadow = CsmGetShadow(shadowCascade0, fragmentPosition, 0);
+    } else if (cascadeCount > 1 && fragmentZViewSpace <= cascadeDistances[1]) {
+        cascadeIndex = 1;
+        shadow = CsmGetShadow(shadowCascade1, fragmentPosition, 1);
+    } else if (cascadeCount > 2 && fragmentZViewSpace <= cascadeDistances[2]) {
+        cascadeIndex = 2;
+        shadow = CsmGetShadow(shadowCascade2, fragmentPosition, 2);
     }
 
     FragColor = shadow * vec4(lightIntensity * lighting, diffuseColor.a);
+
+    if (debugCascades) {
+        // Tint each cascade with its own color: red, green, blue. Fragments outside of any
+        // cascade are left untouched.
+        vec3 tint = vec3(1.0);
+        if (cascadeIndex == 0) {
+            tint = vec3(1.0, 0.25, 0.25);
+        } else if (cascadeIndex == 1) {
+            tint = vec3(0.25, 1.0, 0.25);
+        } else if (cascadeIndex == 2) {
+            tint = vec3(0.25, 0.25, 1.0);
+        }
+        FragColor.rgb = mix(FragColor.rgb, tint * max(lightIntensity * lighting, vec3(0.1)), 0.5);
+    }
 }
@@ -0,0 +1,28 @@
+// Same as the fragment shader of the standard particle system material.
+
+uniform sampler2D diffuseTexture;
+uniform float softBoundarySharpnessFactor;
+
+uniform sampler2D sceneDepth;
+uniform float zNear;
+uniform float zFar;
+
+out vec4 FragColor;
+in vec2 texCoord;
+in vec4 color;
+
+float toProjSpace(float z)
+{
+    return (zFar * zNear) / (zFar - z * (zFar - zNear));
+}
+
+void main()
+{
+    ivec2 depthTextureSize = textureSize(sceneDepth, 0);
+    vec2 pixelSize = vec2(1.0 / float(depthTextureSize.x), 1.0 / float(depthTextureSize.y));
+    float sceneDepthValue = toProjSpace(texture(sceneDepth, gl_FragCoord.xy * pixelSize).r);
+    float fragmentDepth = toProjSpace(gl_FragCoord.z);
+    float depthOpacity = smoothstep((sceneDepthValue - fragmentDepth) * softBoundarySharpnessFactor, 0.0, 1.0);
+    FragColor = color * S_SRGBToLinear(texture(diffuseTexture, texCoord)).r;
+    FragColor.a *= depthOpacity;
+}
@@ -0,0 +1,105 @@
+// Simulates GPU particles. Every texel of the state textures contains a single particle:
+//  - Position texture: local position (xyz) and current lifetime (w).
+//  - Velocity texture: local velocity (xyz) and initial lifetime (w).
+//  - Parameters texture: size (x), size modifier (y), rotation (z), rotation speed (w).
+// Velocity is a displacement per tick, the same as in CPU particle systems.
+
+// Amount of particles in a row of the spawn texture, every particle takes 3 texels.
+#define SPAWN_ROW_SIZE 256
+
+uniform sampler2D positionTexture;
+uniform sampler2D velocityTexture;
+uniform sampler2D parametersTexture;
+uniform sampler2D spawnTexture;
+// Size (r) and speed (g) scales over lifetime.
+uniform sampler2D lifetimeCurvesTexture;
+uniform sampler2D depthTexture;
+uniform sampler2D normalTexture;
+
+uniform int textureSize;
+uniform int spawnStart;
+uniform int spawnCount;
+uniform float elapsed;
+uniform float steps;
+uniform vec3 accelerationOffset;
+
+uniform bool depthCollision;
+uniform mat4 worldMatrix;
+uniform mat4 invWorldMatrix;
+uniform mat4 viewProjectionMatrix;
+uniform mat4 invViewProjectionMatrix;
+uniform vec3 cameraPosition;
+uniform float restitution;
+uniform float collisionThickness;
+
+layout(location = 0) out vec4 outPosition;
+layout(location = 1) out vec4 outVelocity;
+layout(location = 2) out vec4 outParameters;
+
+vec4 fetchSpawn(int index, int component)
+{
+    ivec2 coord = ivec2((index % SPAWN_ROW_SIZE) * 3 + component, index / SPAWN_ROW_SIZE);
+    return texelFetch(spawnTexture, coord, 0);
+}
+
+void main()
+{
+    ivec2 coord = ivec2(gl_FragCoord.xy);
+    int index = coord.y * textureSize + coord.x;
+    int capacity = textureSize * textureSize;
+
+    int spawnIndex = (index - spawnStart + capacity) % capacity;
+    if (spawnIndex < spawnCount) {
+        outPosition = fetchSpawn(spawnIndex, 0);
+        outVelocity = fetchSpawn(spawnIndex, 1);
+        outParameters = fetchSpawn(spawnIndex, 2);
+        return;
+    }
+
+    vec4 position = texelFetch(positionTexture, coord, 0);
+    vec4 velocity = texelFetch(velocityTexture, coord, 0);
+    vec4 parameters = texelFetch(parametersTexture, coord, 0);
+
+    // Dead particles are left as is.
+    if (position.w >= velocity.w) {
+        outPosition = position;
+        outVelocity = velocity;
+        outParameters = parameters;
+        return;
+    }
+
+    position.w += elapsed;
+    float speedScale = texture(lifetimeCurvesTexture, vec2(position.w / velocity.w, 0.5)).g;
+    velocity.xyz += accelerationOffset * steps;
+    vec3 newPosition = position.xyz + velocity.xyz * steps * speedScale;
+    parameters.x = max(parameters.x + parameters.y * elapsed, 0.0);
+    parameters.z += parameters.w * elapsed;
+
+    if (depthCollision) {
+        vec3 worldPosition = (worldMatrix * vec4(newPosition, 1.0)).xyz;
+        vec4 clipPosition = viewProjectionMatrix * vec4(worldPosition, 1.0);
+        if (clipPosition.w > 0.0) {
+            vec3 screenPosition = clipPosition.xyz / clipPosition.w * 0.5 + 0.5;
+            if (all(greaterThanEqual(screenPosition, vec3(0.0))) && all(lessThanEqual(screenPosition, vec3(1.0)))) {
+                float sceneDepth = texture(depthTexture, screenPosition.xy).r;
+                if (screenPosition.z > sceneDepth) {
+                    vec3 surfacePosition = S_UnProject(vec3(screenPosition.xy, sceneDepth), invViewProjectionMatrix);
+                    float penetration = distance(worldPosition, cameraPosition) - distance(surfacePosition, cameraPosition);
+                    vec3 normal = normalize(texture(normalTexture, screenPosition.xy).xyz * 2.0 - 1.0);
+                    vec3 worldVelocity = mat3(worldMatrix) * velocity.xyz;
+                    if (penetration < collisionThickness && dot(worldVelocity, normal) < 0.0) {
+                        worldVelocity = reflect(worldVelocity, normal) * restitution;
+                        velocity.xyz = mat3(invWorldMatrix) * worldVelocity;
+                        newPosition = position.xyz;
+                    }
+                }
+            }
+        }
+    }
+
+    position.xyz = newPosition;
+
+    outPosition = position;
+    outVelocity = velocity;
+    outParameters = parameters;
+}
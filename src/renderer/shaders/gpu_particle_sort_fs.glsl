@@ -0,0 +1,54 @@
+// Sorts GPU particles back-to-front using bitonic sort. Every texel contains sorting key (x) and
+// index of a particle (y). Each pass of the sort is a single draw call.
+
+uniform sampler2D positionTexture;
+uniform sampler2D velocityTexture;
+uniform sampler2D sortTexture;
+
+uniform int textureSize;
+// True if the pass must fill the initial keys.
+uniform bool initialize;
+uniform mat4 worldMatrix;
+uniform vec3 cameraPosition;
+// Size of a bitonic sequence (k) and distance between compared elements (j).
+uniform int blockSize;
+uniform int stride;
+
+out vec4 FragColor;
+
+// Returns true if `a` must be placed before `b` in the ascending order.
+bool precedes(vec2 a, vec2 b)
+{
+    return a.x < b.x || (a.x == b.x && a.y < b.y);
+}
+
+void main()
+{
+    ivec2 coord = ivec2(gl_FragCoord.xy);
+    int index = coord.y * textureSize + coord.x;
+
+    if (initialize) {
+        vec4 position = texelFetch(positionTexture, coord, 0);
+        float initialLifetime = texelFetch(velocityTexture, coord, 0).w;
+        float key = -1.0;
+        if (position.w < initialLifetime) {
+            vec3 worldPosition = (worldMatrix * vec4(position.xyz, 1.0)).xyz;
+            vec3 delta = worldPosition - cameraPosition;
+            key = dot(delta, delta);
+        }
+        FragColor = vec4(key, float(index), 0.0, 0.0);
+        return;
+    }
+
+    int partnerIndex = index ^ stride;
+    vec2 current = texelFetch(sortTexture, coord, 0).xy;
+    vec2 partner = texelFetch(sortTexture, ivec2(partnerIndex % textureSize, partnerIndex / textureSize), 0).xy;
+
+    // Sort order is inverted to get the farthest particles first.
+    bool ascending = (index & blockSize) != 0;
+    bool lower = index < partnerIndex;
+    bool keepMin = ascending == lower;
+
+    bool currentFirst = precedes(current, partner);
+    FragColor = vec4((keepMin == currentFirst) ? current : partner, 0.0, 0.0);
+}
@@ -0,0 +1,60 @@
+// Draws GPU particles as camera-facing quads, one instance per particle. Dead particles are
+// collapsed into degenerate quads.
+
+layout(location = 0) in vec3 vertexPosition;
+layout(location = 1) in vec2 vertexTexCoord;
+
+uniform sampler2D positionTexture;
+uniform sampler2D velocityTexture;
+uniform sampler2D parametersTexture;
+uniform sampler2D sortTexture;
+uniform sampler2D colorOverLifetimeTexture;
+// Size (r) and speed (g) scales over lifetime.
+uniform sampler2D lifetimeCurvesTexture;
+
+uniform int textureSize;
+uniform bool sorted;
+uniform mat4 viewProjectionMatrix;
+uniform mat4 worldMatrix;
+uniform vec3 cameraUpVector;
+uniform vec3 cameraSideVector;
+
+out vec2 texCoord;
+out vec4 color;
+
+vec2 rotateVec2(vec2 v, float angle)
+{
+    float c = cos(angle);
+    float s = sin(angle);
+    mat2 m = mat2(c, -s, s, c);
+    return m * v;
+}
+
+void main()
+{
+    int index = gl_InstanceID;
+    if (sorted) {
+        index = int(texelFetch(sortTexture, ivec2(index % textureSize, index / textureSize), 0).y);
+    }
+    ivec2 coord = ivec2(index % textureSize, index / textureSize);
+
+    vec4 position = texelFetch(positionTexture, coord, 0);
+    float initialLifetime = texelFetch(velocityTexture, coord, 0).w;
+    vec4 parameters = texelFetch(parametersTexture, coord, 0);
+
+    if (position.w >= initialLifetime) {
+        texCoord = vec2(0.0);
+        color = vec4(0.0);
+        gl_Position = vec4(0.0);
+        return;
+    }
+
+    float k = position.w / initialLifetime;
+    color = texture(colorOverLifetimeTexture, vec2(k, 0.5));
+    float sizeScale = texture(lifetimeCurvesTexture, vec2(k, 0.5)).r;
+    texCoord = vertexTexCoord;
+    vec2 vertexOffset = rotateVec2(vertexTexCoord * 2.0 - 1.0, parameters.z);
+    vec4 worldPosition = worldMatrix * vec4(position.xyz, 1.0);
+    vec3 offset = (vertexOffset.x * cameraSideVector + vertexOffset.y * cameraUpVector) * parameters.x * sizeScale;
+    gl_Position = viewProjectionMatrix * (worldPosition + vec4(offset, 0.0));
+}
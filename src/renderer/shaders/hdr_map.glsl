@@ -8,6 +8,8 @@ uniform float minLuminance;
 uniform float maxLuminance;
 uniform bool autoExposure;
 uniform float fixedExposure;
+// 0 - Exponential, 1 - Reinhard, 2 - Filmic, 3 - ACES.
+uniform int toneMapping;
 
 in vec2 texCoord;
 
@@ -22,6 +24,44 @@ vec3 ColorGrading(vec3 color) {
     return texture(colorMapSampler, scale * color + offset).rgb;
 }
 
+vec3 FilmicCurve(vec3 x) {
+    const float A = 0.15;
+    const float B = 0.50;
+    const float C = 0.10;
+    const float D = 0.20;
+    const float E = 0.02;
+    const float F = 0.30;
+    return ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F;
+}
+
+vec3 Filmic(vec3 color) {
+    const float whitePoint = 11.2;
+    const float exposureBias = 2.0;
+    return FilmicCurve(exposureBias * color) / FilmicCurve(vec3(whitePoint));
+}
+
+vec3 Aces(vec3 color) {
+    // Fitted curve by Krzysztof Narkowicz.
+    const float a = 2.51;
+    const float b = 0.03;
+    const float c = 2.43;
+    const float d = 0.59;
+    const float e = 0.14;
+    return clamp((color * (a * color + b)) / (color * (c * color + d) + e), 0.0, 1.0);
+}
+
+vec3 ToneMap(vec3 color) {
+    if (toneMapping == 1) {
+        return color / (vec3(1.0) + color);
+    } else if (toneMapping == 2) {
+        return Filmic(color);
+    } else if (toneMapping == 3) {
+        return Aces(color);
+    } else {
+        return vec3(1.0) - exp(-color);
+    }
+}
+
 void main() {
     vec4 hdrColor = texture(hdrSampler, texCoord);
 
@@ -36,7 +76,7 @@ void main() {
         exposure = fixedExposure;
     }
 
-    vec4 ldrColor = vec4(vec3(1.0) - exp(-hdrColor.rgb * exposure), hdrColor.a);
+    vec4 ldrColor = vec4(ToneMap(hdrColor.rgb * exposure), hdrColor.a);
 
     if (useColorGrading) {
         outLdrColor = vec4(ColorGrading(S_LinearToSRGB(ldrColor).rgb), ldrColor.a);
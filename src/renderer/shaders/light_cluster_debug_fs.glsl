@@ -0,0 +1,30 @@
+uniform sampler2D depthTexture;
+uniform sampler2D clusterGrid;
+uniform mat4 invViewProj;
+uniform mat4 viewProj;
+uniform vec3 cameraPosition;
+uniform vec4 clusterParams;
+uniform vec3 clusterDepthAxis;
+uniform float maxLightCount;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+void main()
+{
+    float depth = texture(depthTexture, texCoord).r;
+    if (depth >= 1.0) {
+        discard;
+    }
+
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, depth), invViewProj);
+    ivec2 range = S_FetchClusterLightRange(clusterGrid, fragmentPosition, viewProj, cameraPosition, clusterParams, clusterDepthAxis);
+    if (range.y == 0) {
+        discard;
+    }
+
+    // Blue - few lights, green - average, red - many lights.
+    float t = clamp(float(range.y) / maxLightCount, 0.0, 1.0);
+    vec3 color = t < 0.5 ? mix(vec3(0.0, 0.0, 1.0), vec3(0.0, 1.0, 0.0), t * 2.0) : mix(vec3(0.0, 1.0, 0.0), vec3(1.0, 0.0, 0.0), t * 2.0 - 1.0);
+    FragColor = vec4(color, 0.5);
+}
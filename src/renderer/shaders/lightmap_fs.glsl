@@ -0,0 +1,97 @@
+// Adds lighting of a single light (as seen from a single shadow view of the light) to the texels
+// of a lightmap. Coverage pass marks texels, that are covered by triangles, so empty texels could be
+// filled later to prevent bleeding when the lightmap is sampled with bilinear filtration.
+
+#define DIRECTIONAL_LIGHT 0
+#define SPOT_LIGHT 1
+#define POINT_LIGHT 2
+
+uniform bool coveragePass;
+uniform int lightKind;
+uniform vec3 lightColor;
+uniform vec3 lightPosition;
+uniform vec3 lightDirection;
+uniform float lightSqrRadius;
+uniform vec2 spotEdges;
+uniform sampler2D shadowMap;
+uniform mat4 lightViewProjection;
+uniform vec3 lightEye;
+uniform vec3 lightForward;
+uniform float lightFar;
+uniform bool isOrthographic;
+uniform bool isPointFace;
+uniform float shadowTexelSize;
+
+in vec3 worldPosition;
+in vec3 worldNormal;
+
+out vec4 FragColor;
+
+float DistanceAttenuation(float distance, float sqrRadius)
+{
+    float attenuation = clamp(1.0 - distance * distance / sqrRadius, 0.0, 1.0);
+    return attenuation * attenuation;
+}
+
+void main()
+{
+    if (coveragePass) {
+        FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+
+    vec3 N = normalize(worldNormal);
+
+    vec3 L;
+    float attenuation;
+    if (lightKind == DIRECTIONAL_LIGHT) {
+        L = lightDirection;
+        attenuation = 1.0;
+    } else {
+        vec3 toLight = lightPosition - worldPosition;
+        float distance = length(toLight);
+        L = toLight / max(distance, 0.000001);
+        attenuation = DistanceAttenuation(distance, lightSqrRadius);
+        if (lightKind == SPOT_LIGHT) {
+            attenuation *= smoothstep(spotEdges.x, spotEdges.y, dot(L, lightDirection));
+        }
+    }
+    attenuation *= max(dot(N, L), 0.0);
+
+    if (attenuation <= 0.0) {
+        discard;
+    }
+
+    // Faces of a point light overlap only at their edges, pick the face by the dominant axis of the
+    // direction from the light, so every texel is lit exactly once.
+    if (isPointFace) {
+        vec3 fromLight = worldPosition - lightPosition;
+        float dominant = max(abs(fromLight.x), max(abs(fromLight.y), abs(fromLight.z)));
+        if (dot(fromLight, lightForward) < dominant) {
+            discard;
+        }
+    }
+
+    // Offset the position along the normal by the size of a shadow map texel to prevent self-shadowing.
+    float depth = dot(worldPosition - lightEye, lightForward);
+    float texelSize = isOrthographic ? shadowTexelSize : shadowTexelSize * max(depth, 0.0);
+    vec3 biasedPosition = worldPosition + N * texelSize * 1.5;
+
+    vec3 lightSpacePosition = S_Project(biasedPosition, lightViewProjection);
+    if (any(lessThan(lightSpacePosition.xy, vec2(0.0))) || any(greaterThan(lightSpacePosition.xy, vec2(1.0)))) {
+        discard;
+    }
+
+    float biasedDepth = (dot(biasedPosition - lightEye, lightForward) - texelSize) / lightFar;
+    vec2 shadowMapTexelSize = 1.0 / vec2(textureSize(shadowMap, 0));
+    float lit = 0.0;
+    for (int y = -1; y <= 1; ++y) {
+        for (int x = -1; x <= 1; ++x) {
+            vec2 fetchTexCoord = lightSpacePosition.xy + vec2(float(x), float(y)) * shadowMapTexelSize;
+            lit += biasedDepth <= texture(shadowMap, fetchTexCoord).r ? 1.0 : 0.0;
+        }
+    }
+    lit /= 9.0;
+
+    FragColor = vec4(lightColor * attenuation * lit, 0.0);
+}
@@ -0,0 +1,14 @@
+// Writes normalized linear distance from the light along its view direction.
+
+uniform vec3 lightEye;
+uniform vec3 lightForward;
+uniform float lightFar;
+
+in vec3 worldPosition;
+
+out vec4 FragColor;
+
+void main()
+{
+    FragColor = vec4(dot(worldPosition - lightEye, lightForward) / lightFar);
+}
@@ -0,0 +1,11 @@
+layout(location = 0) in vec3 vertexPosition;
+
+uniform mat4 lightViewProjection;
+
+out vec3 worldPosition;
+
+void main()
+{
+    worldPosition = vertexPosition;
+    gl_Position = lightViewProjection * vec4(vertexPosition, 1.0);
+}
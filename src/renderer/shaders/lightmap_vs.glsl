@@ -0,0 +1,16 @@
+// Rasterizes triangles of a mesh in the space of its second texture coordinates, so every fragment
+// corresponds to a texel of the lightmap. Vertices are already in world space.
+
+layout(location = 0) in vec3 vertexPosition;
+layout(location = 1) in vec2 vertexSecondTexCoord;
+layout(location = 2) in vec3 vertexNormal;
+
+out vec3 worldPosition;
+out vec3 worldNormal;
+
+void main()
+{
+    worldPosition = vertexPosition;
+    worldNormal = vertexNormal;
+    gl_Position = vec4(vertexSecondTexCoord * 2.0 - 1.0, 0.0, 1.0);
+}
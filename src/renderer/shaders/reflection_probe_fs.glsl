@@ -0,0 +1,83 @@
+// Accumulates parallax-corrected reflections of a single reflection probe. Color is premultiplied by
+// the influence of the probe, alpha channel contains the influence itself, so overlapping probes can
+// be blended by normalizing the accumulated value.
+
+uniform sampler2D depthTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform samplerCube environmentTexture;
+uniform mat4 inverseViewProjection;
+uniform vec3 cameraPosition;
+uniform mat4 probeMatrix;
+uniform mat4 inverseProbeMatrix;
+uniform bool isSphere;
+uniform vec3 shapeSize;
+uniform float blendDistance;
+uniform float intensity;
+uniform bool parallaxCorrection;
+uniform float maxLod;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+// Returns signed distance from the point (in local space of the probe) to the boundary of the
+// influence volume, positive values are inside the volume.
+float DistanceToBoundary(vec3 localPoint)
+{
+    if (isSphere) {
+        return shapeSize.x - length(localPoint);
+    }
+    vec3 d = shapeSize - abs(localPoint);
+    return min(d.x, min(d.y, d.z));
+}
+
+// Finds the intersection of the ray, that starts inside the influence volume, with its boundary.
+vec3 IntersectBoundary(vec3 origin, vec3 direction)
+{
+    if (isSphere) {
+        float b = dot(origin, direction);
+        float c = dot(origin, origin) - shapeSize.x * shapeSize.x;
+        float t = -b + sqrt(max(b * b - c, 0.0));
+        return origin + direction * t;
+    }
+    vec3 first = (shapeSize - origin) / direction;
+    vec3 second = (-shapeSize - origin) / direction;
+    vec3 furthest = max(first, second);
+    float t = min(furthest.x, min(furthest.y, furthest.z));
+    return origin + direction * t;
+}
+
+void main()
+{
+    float depth = texture(depthTexture, texCoord).r;
+    if (depth >= 1.0) {
+        discard;
+    }
+
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, depth), inverseViewProjection);
+    vec3 localPosition = (inverseProbeMatrix * vec4(fragmentPosition, 1.0)).xyz;
+
+    float distance = DistanceToBoundary(localPosition);
+    if (distance <= 0.0) {
+        discard;
+    }
+    float weight = blendDistance > 0.0 ? clamp(distance / blendDistance, 0.0, 1.0) : 1.0;
+
+    float roughness = texture(materialTexture, texCoord).g;
+    vec3 N = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+    vec3 V = normalize(cameraPosition - fragmentPosition);
+    vec3 R = reflect(-V, N);
+
+    vec3 direction = R;
+    if (parallaxCorrection) {
+        vec3 localDirection = normalize((inverseProbeMatrix * vec4(R, 0.0)).xyz);
+        vec3 hit = IntersectBoundary(localPosition, localDirection);
+        vec3 worldHit = (probeMatrix * vec4(hit, 1.0)).xyz;
+        vec3 probePosition = (probeMatrix * vec4(0.0, 0.0, 0.0, 1.0)).xyz;
+        direction = worldHit - probePosition;
+    }
+
+    vec3 radiance = textureLod(environmentTexture, direction, roughness * maxLod).rgb;
+
+    FragColor = vec4(radiance * intensity * weight, weight);
+}
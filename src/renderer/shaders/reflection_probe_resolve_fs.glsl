@@ -0,0 +1,39 @@
+// Normalizes reflections accumulated from reflection probes and adds them to the frame.
+
+uniform sampler2D reflectionTexture;
+uniform sampler2D depthTexture;
+uniform sampler2D diffuseTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform mat4 inverseViewProjection;
+uniform vec3 cameraPosition;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+void main()
+{
+    vec4 reflection = texture(reflectionTexture, texCoord);
+    float depth = texture(depthTexture, texCoord).r;
+    if (depth >= 1.0 || reflection.a <= 0.0) {
+        discard;
+    }
+
+    vec3 material = texture(materialTexture, texCoord).rgb;
+    float metallic = material.x;
+    float roughness = material.y;
+    float ambientOcclusion = material.z;
+
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, depth), inverseViewProjection);
+    vec3 N = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+    vec3 V = normalize(cameraPosition - fragmentPosition);
+
+    vec3 radiance = reflection.rgb / max(reflection.a, 1.0);
+
+    vec3 albedo = S_SRGBToLinear(texture(diffuseTexture, texCoord)).rgb;
+    vec3 F0 = mix(vec3(0.04), albedo, metallic);
+    vec3 F = S_FresnelSchlick(max(dot(N, V), 0.0), F0);
+    float smoothness = 1.0 - roughness;
+
+    FragColor = vec4(radiance * F * smoothness * smoothness * ambientOcclusion, 0.0);
+}
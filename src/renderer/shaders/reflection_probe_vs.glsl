@@ -0,0 +1,12 @@
+layout(location = 0) in vec3 vertexPosition;
+layout(location = 1) in vec2 vertexTexCoord;
+
+uniform mat4 worldViewProjection;
+
+out vec2 texCoord;
+
+void main()
+{
+    texCoord = vertexTexCoord;
+    gl_Position = worldViewProjection * vec4(vertexPosition, 1.0);
+}
\ No newline at end of file
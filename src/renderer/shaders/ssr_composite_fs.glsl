@@ -0,0 +1,64 @@
+// Blurs screen-space reflections depending on roughness of surfaces, fills the missing parts of
+// reflections using reflection probes (or environment map, if there's no probes) and adds the result
+// to the frame.
+
+#define BLUR_HALF_SIZE 2
+#define MAX_BLUR_RADIUS 8.0
+#define ENVIRONMENT_MAX_LOD 8.0
+
+uniform sampler2D reflectionTexture;
+uniform sampler2D depthTexture;
+uniform sampler2D diffuseTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform samplerCube environmentTexture;
+uniform sampler2D probeTexture;
+uniform mat4 inverseViewProjection;
+uniform vec3 cameraPosition;
+uniform vec2 reflectionTexelSize;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+void main()
+{
+    float depth = texture(depthTexture, texCoord).r;
+    if (depth >= 1.0) {
+        discard;
+    }
+
+    vec3 material = texture(materialTexture, texCoord).rgb;
+    float metallic = material.x;
+    float roughness = material.y;
+    float ambientOcclusion = material.z;
+
+    // Rough surfaces have wider reflection lobe, emulate it by a blur of wider radius.
+    vec2 blurStep = reflectionTexelSize * (roughness * MAX_BLUR_RADIUS / float(BLUR_HALF_SIZE));
+    vec4 reflection = vec4(0.0);
+    float totalWeight = 0.0;
+    for (int y = -BLUR_HALF_SIZE; y <= BLUR_HALF_SIZE; ++y) {
+        for (int x = -BLUR_HALF_SIZE; x <= BLUR_HALF_SIZE; ++x) {
+            vec2 offset = vec2(float(x), float(y));
+            float weight = exp(-0.5 * dot(offset, offset));
+            reflection += weight * texture(reflectionTexture, texCoord + offset * blurStep);
+            totalWeight += weight;
+        }
+    }
+    reflection /= totalWeight;
+
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, depth), inverseViewProjection);
+    vec3 N = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+    vec3 V = normalize(cameraPosition - fragmentPosition);
+
+    vec3 environment = textureLod(environmentTexture, reflect(-V, N), roughness * ENVIRONMENT_MAX_LOD).rgb;
+    vec4 probe = texture(probeTexture, texCoord);
+    environment = probe.rgb / max(probe.a, 1.0) + (1.0 - clamp(probe.a, 0.0, 1.0)) * environment;
+    vec3 radiance = reflection.rgb + (1.0 - clamp(reflection.a, 0.0, 1.0)) * environment;
+
+    vec3 albedo = S_SRGBToLinear(texture(diffuseTexture, texCoord)).rgb;
+    vec3 F0 = mix(vec3(0.04), albedo, metallic);
+    vec3 F = S_FresnelSchlick(max(dot(N, V), 0.0), F0);
+    float smoothness = 1.0 - roughness;
+
+    FragColor = vec4(radiance * F * smoothness * smoothness * ambientOcclusion, 0.0);
+}
@@ -0,0 +1,103 @@
+// Screen-space reflections. Marches reflected view ray through the depth buffer and fetches
+// the color of lit frame at the hit point. Output is premultiplied by a confidence of the hit,
+// so the composite pass can mix it with environment map.
+
+#define BINARY_SEARCH_STEPS 6
+
+// Rough surfaces have very blurry reflections, which are too expensive to calculate correctly
+// using screen-space information only.
+#define MAX_ROUGHNESS 0.8
+
+uniform sampler2D depthTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform sampler2D frameTexture;
+uniform mat4 projectionMatrix;
+uniform mat4 inverseProjectionMatrix;
+uniform mat3 viewMatrix;
+uniform int maxSteps;
+uniform float maxDistance;
+uniform float thickness;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+vec3 GetViewPosition(vec2 uv)
+{
+    return S_UnProject(vec3(uv, texture(depthTexture, uv).r), inverseProjectionMatrix);
+}
+
+bool IsOnScreen(vec3 screenPos)
+{
+    return all(greaterThanEqual(screenPos, vec3(0.0))) && all(lessThanEqual(screenPos, vec3(1.0)));
+}
+
+void main()
+{
+    float depth = texture(depthTexture, texCoord).r;
+    float roughness = texture(materialTexture, texCoord).y;
+
+    if (depth >= 1.0 || roughness > MAX_ROUGHNESS) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
+    vec3 position = S_UnProject(vec3(texCoord, depth), inverseProjectionMatrix);
+    vec3 normal = normalize(viewMatrix * (texture(normalTexture, texCoord).xyz * 2.0 - 1.0));
+    vec3 reflectionDir = normalize(reflect(normalize(position), normal));
+
+    float stepSize = maxDistance / float(max(maxSteps, 1));
+    vec3 previousPos = position;
+    vec3 rayPos = position;
+    bool hit = false;
+    float travelled = 0.0;
+
+    for (int i = 0; i < maxSteps; ++i) {
+        previousPos = rayPos;
+        rayPos += reflectionDir * stepSize;
+        travelled += stepSize;
+
+        // Camera looks along -Z axis, the ray went behind the camera.
+        if (rayPos.z >= 0.0) {
+            break;
+        }
+
+        vec3 screenPos = S_Project(rayPos, projectionMatrix);
+        if (!IsOnScreen(screenPos)) {
+            break;
+        }
+
+        float delta = GetViewPosition(screenPos.xy).z - rayPos.z;
+        if (delta > 0.0 && delta < thickness) {
+            hit = true;
+            break;
+        }
+    }
+
+    if (!hit) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
+    // Refine the hit point between last two positions of the ray.
+    for (int i = 0; i < BINARY_SEARCH_STEPS; ++i) {
+        vec3 middle = 0.5 * (previousPos + rayPos);
+        if (GetViewPosition(S_Project(middle, projectionMatrix).xy).z - middle.z > 0.0) {
+            rayPos = middle;
+        } else {
+            previousPos = middle;
+        }
+    }
+
+    vec2 hitCoord = S_Project(rayPos, projectionMatrix).xy;
+
+    // Smoothly fade out reflections near screen edges, at the end of the ray and on rough surfaces
+    // to hide discontinuities.
+    vec2 edgeDistance = min(hitCoord, 1.0 - hitCoord);
+    float edgeFade = clamp(min(edgeDistance.x, edgeDistance.y) * 10.0, 0.0, 1.0);
+    float distanceFade = 1.0 - clamp(travelled / maxDistance, 0.0, 1.0);
+    float roughnessFade = 1.0 - clamp(roughness / MAX_ROUGHNESS, 0.0, 1.0);
+    float confidence = edgeFade * distanceFade * roughnessFade;
+
+    FragColor = vec4(texture(frameTexture, hitCoord).rgb * confidence, confidence);
+}
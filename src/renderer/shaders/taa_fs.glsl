@@ -0,0 +1,64 @@
+// Temporal anti-aliasing resolve. Blends current frame with reprojected history, history is clamped
+// to the color range of the neighborhood of a pixel to prevent ghosting.
+
+// Weight of the current frame in the result.
+#define CURRENT_FRAME_WEIGHT 0.1
+
+uniform sampler2D currentTexture;
+uniform sampler2D historyTexture;
+uniform sampler2D velocityTexture;
+uniform sampler2D depthTexture;
+uniform mat4 inverseViewProjection;
+uniform mat4 prevViewProjection;
+uniform vec2 inverseScreenSize;
+uniform bool historyValid;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+void main()
+{
+    vec4 current = texture(currentTexture, texCoord);
+
+    if (!historyValid) {
+        FragColor = current;
+        return;
+    }
+
+    // Velocity is written only by G-Buffer pass, the rest (sky, forward-rendered objects) is
+    // reprojected using depth.
+    vec4 velocitySample = texture(velocityTexture, texCoord);
+    vec2 velocity;
+    if (velocitySample.a > 0.5) {
+        velocity = velocitySample.xy;
+    } else {
+        vec3 worldPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), inverseViewProjection);
+        velocity = texCoord - S_Project(worldPosition, prevViewProjection).xy;
+    }
+
+    vec2 historyCoord = texCoord - velocity;
+
+    // Off-screen history is invalid.
+    if (any(lessThan(historyCoord, vec2(0.0))) || any(greaterThan(historyCoord, vec2(1.0)))) {
+        FragColor = current;
+        return;
+    }
+
+    vec3 minColor = current.rgb;
+    vec3 maxColor = current.rgb;
+    for (int y = -1; y <= 1; ++y) {
+        for (int x = -1; x <= 1; ++x) {
+            vec3 neighbour = texture(currentTexture, texCoord + vec2(float(x), float(y)) * inverseScreenSize).rgb;
+            minColor = min(minColor, neighbour);
+            maxColor = max(maxColor, neighbour);
+        }
+    }
+
+    vec3 history = clamp(texture(historyTexture, historyCoord).rgb, minColor, maxColor);
+
+    // Weight samples by inverse luminance to reduce flickering of very bright pixels in HDR.
+    float currentWeight = CURRENT_FRAME_WEIGHT / (1.0 + S_Luminance(current.rgb));
+    float historyWeight = (1.0 - CURRENT_FRAME_WEIGHT) / (1.0 + S_Luminance(history));
+
+    FragColor = vec4((current.rgb * currentWeight + history * historyWeight) / (currentWeight + historyWeight), current.a);
+}
@@ -10,6 +10,7 @@ use crate::{
         framework::{
             error::FrameworkError,
             framebuffer::{Attachment, AttachmentKind, CullFace, DrawParameters, FrameBuffer},
+            gpu_program::GpuProgramBinding,
             gpu_texture::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
                 PixelKind, WrapMode,
@@ -22,7 +23,7 @@ use crate::{
     scene::{
         camera::Camera,
         graph::Graph,
-        light::directional::{DirectionalLight, FrustumSplitOptions, CSM_NUM_CASCADES},
+        light::directional::{DirectionalLight, CSM_NUM_CASCADES},
     },
 };
 use fyrox_core::color::Color;
@@ -170,25 +171,25 @@ impl CsmRenderer {
             .try_normalize(f32::EPSILON)
             .unwrap_or_else(Vector3::z);
 
-        let z_values = match light.csm_options.split_options {
-            FrustumSplitOptions::Absolute { far_planes } => [
-                camera.projection().z_near(),
-                far_planes[0],
-                far_planes[1],
-                far_planes[2],
-            ],
-            FrustumSplitOptions::Relative { fractions } => [
-                camera.projection().z_near(),
-                camera.projection().z_far() * fractions[0],
-                camera.projection().z_far() * fractions[1],
-                camera.projection().z_far() * fractions[2],
-            ],
-        };
+        let cascade_count = light.csm_options.cascade_count();
+        let texel_snapping = light.csm_options.is_texel_snapping_enabled();
+
+        let z_values = light.csm_options.split_options.split_distances(
+            camera.projection().z_near(),
+            camera.projection().z_far(),
+            cascade_count,
+        );
 
         for i in 0..CSM_NUM_CASCADES {
             let z_near = z_values[i];
             let mut z_far = z_values[i + 1];
 
+            if i >= cascade_count {
+                // Unused cascades must never be selected by the light shader.
+                self.cascades[i].z_far = 0.0;
+                continue;
+            }
+
             if z_far.eq(&z_near) {
                 z_far += 10.0 * f32::EPSILON;
             }
@@ -204,7 +205,13 @@ impl CsmRenderer {
                 Frustum::from_view_projection_matrix(projection_matrix * camera.view_matrix())
                     .unwrap_or_default();
 
-            let center = frustum.center();
+            // Texel snapping requires light space to be fixed in the world, otherwise it moves
+            // together with the camera and snapping has no effect.
+            let center = if texel_snapping {
+                Vector3::default()
+            } else {
+                frustum.center()
+            };
             let light_view_matrix = Matrix4::look_at_lh(
                 &Point3::from(center + light_direction),
                 &Point3::from(center),
@@ -231,18 +238,31 @@ impl CsmRenderer {
                 aabb.add_point(light_space_corner);
             }
 
-            // Make sure most of the objects outside of the frustum will cast shadows.
+            if texel_snapping {
+                snap_to_texels(&mut aabb, &frustum, &light_view_matrix, self.size);
+            }
+
+            // Make sure most of the objects outside of the frustum will cast shadows. Depth range
+            // is extended relative to the center of the sub-frustum, which is not at the origin
+            // of the light space when texel snapping is used.
+            let z_origin = light_view_matrix
+                .transform_point(&Point3::from(frustum.center()))
+                .z;
             let z_mult = 10.0;
-            if aabb.min.z < 0.0 {
-                aabb.min.z *= z_mult;
+            let mut min_z = aabb.min.z - z_origin;
+            let mut max_z = aabb.max.z - z_origin;
+            if min_z < 0.0 {
+                min_z *= z_mult;
             } else {
-                aabb.min.z /= z_mult;
+                min_z /= z_mult;
             }
-            if aabb.max.z < 0.0 {
-                aabb.max.z /= z_mult;
+            if max_z < 0.0 {
+                max_z /= z_mult;
             } else {
-                aabb.max.z *= z_mult;
+                max_z *= z_mult;
             }
+            aabb.min.z = min_z + z_origin;
+            aabb.max.z = max_z + z_origin;
 
             let cascade_projection_matrix = Matrix4::new_orthographic(
                 aabb.min.x, aabb.max.x, aabb.min.y, aabb.max.y, aabb.min.z, aabb.max.z,
@@ -287,53 +307,95 @@ impl CsmRenderer {
                     continue;
                 };
 
-                for instance in batch.instances.iter() {
-                    stats += framebuffer.draw(
-                        geometry,
-                        state,
-                        viewport,
-                        &render_pass.program,
-                        &DrawParameters {
-                            cull_face: Some(CullFace::Back),
-                            color_write: ColorMask::all(false),
-                            depth_write: true,
-                            stencil_test: None,
-                            depth_test: true,
-                            blend: None,
-                            stencil_op: Default::default(),
-                        },
-                        instance.element_range,
-                        |mut program_binding| {
-                            apply_material(MaterialContext {
-                                material,
-                                program_binding: &mut program_binding,
-                                texture_cache,
-                                matrix_storage,
-                                world_matrix: &instance.world_transform,
-                                view_projection_matrix: &light_view_projection,
-                                wvp_matrix: &(light_view_projection * instance.world_transform),
-                                bone_matrices: &instance.bone_matrices,
-                                use_skeletal_animation: batch.is_skinned,
-                                camera_position: &camera.global_position(),
-                                camera_up_vector: &camera_up,
-                                camera_side_vector: &camera_side,
-                                z_near,
-                                use_pom: false,
-                                light_position: &Default::default(),
-                                blend_shapes_storage: blend_shapes_storage.as_ref(),
-                                blend_shapes_weights: &instance.blend_shapes_weights,
-                                normal_dummy: &normal_dummy,
-                                white_dummy: &white_dummy,
-                                black_dummy: &black_dummy,
-                                volume_dummy: &volume_dummy,
-                                persistent_identifier: instance.persistent_identifier,
-                                light_data: None,            // TODO
-                                ambient_light: Color::WHITE, // TODO
-                                scene_depth: None,
-                                z_far,
-                            });
-                        },
-                    )?;
+                // Render every instance of the batch in a single draw call, if possible.
+                let instance_matrices =
+                    if batch.is_instanceable() && render_pass.program.supports_instancing() {
+                        batch.instance_matrices()
+                    } else {
+                        Vec::new()
+                    };
+                let is_instanced = !instance_matrices.is_empty();
+                let instances = if is_instanced {
+                    &batch.instances[..1]
+                } else {
+                    &batch.instances[..]
+                };
+
+                for instance in instances {
+                    let persistent_identifier = if is_instanced {
+                        batch.instancing_identifier()
+                    } else {
+                        instance.persistent_identifier
+                    };
+
+                    let apply_uniforms = |mut program_binding: GpuProgramBinding| {
+                        apply_material(MaterialContext {
+                            material,
+                            program_binding: &mut program_binding,
+                            texture_cache,
+                            matrix_storage,
+                            world_matrix: &instance.world_transform,
+                            view_projection_matrix: &light_view_projection,
+                            wvp_matrix: &(light_view_projection * instance.world_transform),
+                            bone_matrices: &instance.bone_matrices,
+                            use_skeletal_animation: batch.is_skinned,
+                            camera_position: &camera.global_position(),
+                            camera_up_vector: &camera_up,
+                            camera_side_vector: &camera_side,
+                            z_near,
+                            use_pom: false,
+                            light_position: &Default::default(),
+                            blend_shapes_storage: blend_shapes_storage.as_ref(),
+                            blend_shapes_weights: &instance.blend_shapes_weights,
+                            normal_dummy: &normal_dummy,
+                            white_dummy: &white_dummy,
+                            black_dummy: &black_dummy,
+                            volume_dummy: &volume_dummy,
+                            persistent_identifier,
+                            light_data: None, // TODO
+                            light_clusters: None,
+                            motion: None,
+                            ambient_light: Color::WHITE, // TODO
+                            scene_depth: None,
+                            z_far,
+                            instance_matrices: &instance_matrices,
+                            instance_color: instance.color,
+                        });
+                    };
+
+                    let draw_params = &DrawParameters {
+                        cull_face: Some(CullFace::Back),
+                        color_write: ColorMask::all(false),
+                        depth_write: true,
+                        stencil_test: None,
+                        depth_test: true,
+                        blend: None,
+                        stencil_op: Default::default(),
+                    };
+
+                    if is_instanced {
+                        stats += framebuffer.draw_instances(
+                            batch.instances.len(),
+                            geometry,
+                            state,
+                            viewport,
+                            &render_pass.program,
+                            draw_params,
+                            apply_uniforms,
+                        );
+                        stats.instanced_batches += 1;
+                        stats.instances_rendered += batch.instances.len();
+                    } else {
+                        stats += framebuffer.draw(
+                            geometry,
+                            state,
+                            viewport,
+                            &render_pass.program,
+                            draw_params,
+                            instance.element_range,
+                            apply_uniforms,
+                        )?;
+                    }
                 }
             }
         }
@@ -341,3 +403,88 @@ impl CsmRenderer {
         Ok(stats)
     }
 }
+
+/// Makes cascade bounds stable: the size of the bounds depends only on the size of the cascade's
+/// sub-frustum (bounding sphere is invariant to rotations of the camera) and its position is moved
+/// in whole shadow map texels. This way every shadow map texel always covers the same area of the
+/// world and shadow edges do not shimmer when the camera moves.
+fn snap_to_texels(
+    aabb: &mut AxisAlignedBoundingBox,
+    frustum: &Frustum,
+    light_view_matrix: &Matrix4<f32>,
+    shadow_map_size: usize,
+) {
+    let corners = frustum.corners();
+    let center = corners
+        .iter()
+        .sum::<Vector3<f32>>()
+        .scale(1.0 / corners.len() as f32);
+    let radius = corners
+        .iter()
+        .map(|corner| corner.metric_distance(&center))
+        .fold(0.0f32, f32::max);
+    // Round the radius to prevent size changes caused by floating-point errors.
+    let radius = (radius * 16.0).ceil() / 16.0;
+
+    let light_space_center = light_view_matrix
+        .transform_point(&Point3::from(center))
+        .coords;
+    let texel_size = 2.0 * radius / shadow_map_size.max(1) as f32;
+    let snap = |value: f32| (value / texel_size).floor() * texel_size;
+
+    aabb.min.x = snap(light_space_center.x - radius);
+    aabb.min.y = snap(light_space_center.y - radius);
+    aabb.max.x = aabb.min.x + 2.0 * radius;
+    aabb.max.y = aabb.min.y + 2.0 * radius;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Matrix4, Point3, Vector3},
+            math::{aabb::AxisAlignedBoundingBox, frustum::Frustum},
+        },
+        renderer::shadow::csm::snap_to_texels,
+    };
+
+    fn snapped_bounds(offset: Vector3<f32>) -> AxisAlignedBoundingBox {
+        let view = Matrix4::look_at_lh(
+            &Point3::from(offset),
+            &Point3::from(offset + Vector3::z()),
+            &Vector3::y(),
+        );
+        let projection = Matrix4::new_perspective(1.0, 1.0, 0.1, 10.0);
+        let frustum = Frustum::from_view_projection_matrix(projection * view).unwrap();
+        let light_view = Matrix4::look_at_lh(
+            &Point3::new(0.0, 1.0, 0.0),
+            &Point3::origin(),
+            &Vector3::z(),
+        );
+        let mut aabb = AxisAlignedBoundingBox::default();
+        for corner in frustum.corners() {
+            aabb.add_point(light_view.transform_point(&Point3::from(corner)).coords);
+        }
+        snap_to_texels(&mut aabb, &frustum, &light_view, 1024);
+        aabb
+    }
+
+    #[test]
+    fn test_texel_snapping() {
+        let a = snapped_bounds(Vector3::default());
+        let b = snapped_bounds(Vector3::new(0.0001, 0.0, 0.0003));
+
+        let size = a.max.x - a.min.x;
+        let texel_size = size / 1024.0;
+
+        // Size of the cascade does not depend on camera position.
+        assert!((size - (b.max.x - b.min.x)).abs() < 1e-4);
+        assert!((size - (a.max.y - a.min.y)).abs() < 1e-4);
+
+        // Bounds are always aligned to texels.
+        for value in [a.min.x, a.min.y, b.min.x, b.min.y] {
+            let texels = value / texel_size;
+            assert!((texels - texels.round()).abs() < 1e-2);
+        }
+    }
+}
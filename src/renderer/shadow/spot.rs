@@ -12,6 +12,7 @@ use crate::{
         framework::{
             error::FrameworkError,
             framebuffer::{Attachment, AttachmentKind, CullFace, DrawParameters, FrameBuffer},
+            gpu_program::GpuProgramBinding,
             gpu_texture::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
                 PixelKind, WrapMode,
@@ -187,53 +188,95 @@ impl SpotShadowMapRenderer {
                 continue;
             };
 
-            for instance in batch.instances.iter() {
-                statistics += framebuffer.draw(
-                    geometry,
-                    state,
-                    viewport,
-                    &render_pass.program,
-                    &DrawParameters {
-                        cull_face: Some(CullFace::Back),
-                        color_write: ColorMask::all(false),
-                        depth_write: true,
-                        stencil_test: None,
-                        depth_test: true,
-                        blend: None,
-                        stencil_op: Default::default(),
-                    },
-                    instance.element_range,
-                    |mut program_binding| {
-                        apply_material(MaterialContext {
-                            material,
-                            program_binding: &mut program_binding,
-                            texture_cache,
-                            matrix_storage,
-                            world_matrix: &instance.world_transform,
-                            view_projection_matrix: &light_view_projection,
-                            wvp_matrix: &(light_view_projection * instance.world_transform),
-                            bone_matrices: &instance.bone_matrices,
-                            use_skeletal_animation: batch.is_skinned,
-                            camera_position: &Default::default(),
-                            camera_up_vector: &camera_up,
-                            camera_side_vector: &camera_side,
-                            z_near,
-                            use_pom: false,
-                            light_position: &Default::default(),
-                            blend_shapes_storage: blend_shapes_storage.as_ref(),
-                            blend_shapes_weights: &instance.blend_shapes_weights,
-                            normal_dummy: &normal_dummy,
-                            white_dummy: &white_dummy,
-                            black_dummy: &black_dummy,
-                            volume_dummy: &volume_dummy,
-                            persistent_identifier: instance.persistent_identifier,
-                            light_data: None,            // TODO
-                            ambient_light: Color::WHITE, // TODO
-                            scene_depth: None,
-                            z_far,
-                        });
-                    },
-                )?;
+            // Render every instance of the batch in a single draw call, if possible.
+            let instance_matrices =
+                if batch.is_instanceable() && render_pass.program.supports_instancing() {
+                    batch.instance_matrices()
+                } else {
+                    Vec::new()
+                };
+            let is_instanced = !instance_matrices.is_empty();
+            let instances = if is_instanced {
+                &batch.instances[..1]
+            } else {
+                &batch.instances[..]
+            };
+
+            for instance in instances {
+                let persistent_identifier = if is_instanced {
+                    batch.instancing_identifier()
+                } else {
+                    instance.persistent_identifier
+                };
+
+                let apply_uniforms = |mut program_binding: GpuProgramBinding| {
+                    apply_material(MaterialContext {
+                        material,
+                        program_binding: &mut program_binding,
+                        texture_cache,
+                        matrix_storage,
+                        world_matrix: &instance.world_transform,
+                        view_projection_matrix: &light_view_projection,
+                        wvp_matrix: &(light_view_projection * instance.world_transform),
+                        bone_matrices: &instance.bone_matrices,
+                        use_skeletal_animation: batch.is_skinned,
+                        camera_position: &Default::default(),
+                        camera_up_vector: &camera_up,
+                        camera_side_vector: &camera_side,
+                        z_near,
+                        use_pom: false,
+                        light_position: &Default::default(),
+                        blend_shapes_storage: blend_shapes_storage.as_ref(),
+                        blend_shapes_weights: &instance.blend_shapes_weights,
+                        normal_dummy: &normal_dummy,
+                        white_dummy: &white_dummy,
+                        black_dummy: &black_dummy,
+                        volume_dummy: &volume_dummy,
+                        persistent_identifier,
+                        light_data: None, // TODO
+                        light_clusters: None,
+                        motion: None,
+                        ambient_light: Color::WHITE, // TODO
+                        scene_depth: None,
+                        z_far,
+                        instance_matrices: &instance_matrices,
+                        instance_color: instance.color,
+                    });
+                };
+
+                let draw_params = &DrawParameters {
+                    cull_face: Some(CullFace::Back),
+                    color_write: ColorMask::all(false),
+                    depth_write: true,
+                    stencil_test: None,
+                    depth_test: true,
+                    blend: None,
+                    stencil_op: Default::default(),
+                };
+
+                if is_instanced {
+                    statistics += framebuffer.draw_instances(
+                        batch.instances.len(),
+                        geometry,
+                        state,
+                        viewport,
+                        &render_pass.program,
+                        draw_params,
+                        apply_uniforms,
+                    );
+                    statistics.instanced_batches += 1;
+                    statistics.instances_rendered += batch.instances.len();
+                } else {
+                    statistics += framebuffer.draw(
+                        geometry,
+                        state,
+                        viewport,
+                        &render_pass.program,
+                        draw_params,
+                        instance.element_range,
+                        apply_uniforms,
+                    )?;
+                }
             }
         }
 
@@ -0,0 +1,321 @@
+//! Screen-space reflections (SSR). See [`ScreenSpaceReflectionsRenderer`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        color::Color,
+        math::{Matrix4Ext, Rect},
+        scope_profile,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        gbuffer::GBuffer,
+        RenderPassStatistics, SsrSettings,
+    },
+    scene::{camera::Camera, mesh::surface::SurfaceData},
+};
+use std::{cell::RefCell, rc::Rc};
+
+struct TraceShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    depth_texture: UniformLocation,
+    normal_texture: UniformLocation,
+    material_texture: UniformLocation,
+    frame_texture: UniformLocation,
+    projection_matrix: UniformLocation,
+    inv_projection_matrix: UniformLocation,
+    view_matrix: UniformLocation,
+    max_steps: UniformLocation,
+    max_distance: UniformLocation,
+    thickness: UniformLocation,
+}
+
+impl TraceShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/ssr_fs.glsl");
+        let vertex_source = include_str!("shaders/ssr_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "SsrTraceShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_texture: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            frame_texture: program
+                .uniform_location(state, &ImmutableString::new("frameTexture"))?,
+            projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("projectionMatrix"))?,
+            inv_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseProjectionMatrix"))?,
+            view_matrix: program.uniform_location(state, &ImmutableString::new("viewMatrix"))?,
+            max_steps: program.uniform_location(state, &ImmutableString::new("maxSteps"))?,
+            max_distance: program.uniform_location(state, &ImmutableString::new("maxDistance"))?,
+            thickness: program.uniform_location(state, &ImmutableString::new("thickness"))?,
+            program,
+        })
+    }
+}
+
+struct CompositeShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    reflection_texture: UniformLocation,
+    depth_texture: UniformLocation,
+    diffuse_texture: UniformLocation,
+    normal_texture: UniformLocation,
+    material_texture: UniformLocation,
+    environment_texture: UniformLocation,
+    probe_texture: UniformLocation,
+    inv_view_projection: UniformLocation,
+    camera_position: UniformLocation,
+    reflection_texel_size: UniformLocation,
+}
+
+impl CompositeShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/ssr_composite_fs.glsl");
+        let vertex_source = include_str!("shaders/ssr_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "SsrCompositeShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            reflection_texture: program
+                .uniform_location(state, &ImmutableString::new("reflectionTexture"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            diffuse_texture: program
+                .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_texture: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            environment_texture: program
+                .uniform_location(state, &ImmutableString::new("environmentTexture"))?,
+            probe_texture: program
+                .uniform_location(state, &ImmutableString::new("probeTexture"))?,
+            inv_view_projection: program
+                .uniform_location(state, &ImmutableString::new("inverseViewProjection"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            reflection_texel_size: program
+                .uniform_location(state, &ImmutableString::new("reflectionTexelSize"))?,
+            program,
+        })
+    }
+}
+
+/// Screen-space reflections renderer works in two passes:
+///
+/// 1) Trace pass - for each pixel of the G-Buffer a reflected view ray is marched through the
+///    depth buffer in half resolution. If the ray hits something, the color of the lit frame at the
+///    hit point is taken as a reflection.
+/// 2) Composite pass - the reflections are blurred using a kernel, which radius depends on
+///    roughness of a surface. Parts of reflections that could not be found on screen (the ray went
+///    off-screen, or behind an object) are taken from reflection probes, and then from the
+///    environment map of the camera (or its skybox, if there is no environment map). The result is weighted by Fresnel term and added to
+///    the frame.
+///
+/// Screen-space reflections can only reflect things that are visible on screen, so they're best
+/// suited for flat, smooth surfaces such as wet floors, water, polished metal, etc.
+pub struct ScreenSpaceReflectionsRenderer {
+    trace_shader: TraceShader,
+    composite_shader: CompositeShader,
+    framebuffer: FrameBuffer,
+    quad: GeometryBuffer,
+    width: i32,
+    height: i32,
+}
+
+impl ScreenSpaceReflectionsRenderer {
+    /// Creates new screen-space reflections renderer for the given frame size.
+    pub fn new(
+        state: &PipelineState,
+        frame_width: usize,
+        frame_height: usize,
+    ) -> Result<Self, FrameworkError> {
+        // Reflections are blurred anyway, so there's no need to trace rays in full resolution.
+        let width = (frame_width / 2).max(1);
+        let height = (frame_height / 2).max(1);
+
+        let mut reflection = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::RGBA16F,
+            MinificationFilter::Linear,
+            MagnificationFilter::Linear,
+            1,
+            None,
+        )?;
+        reflection
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        Ok(Self {
+            trace_shader: TraceShader::new(state)?,
+            composite_shader: CompositeShader::new(state)?,
+            framebuffer: FrameBuffer::new(
+                state,
+                None,
+                vec![Attachment {
+                    kind: AttachmentKind::Color,
+                    texture: Rc::new(RefCell::new(reflection)),
+                }],
+            )?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            )?,
+            width: width as i32,
+            height: height as i32,
+        })
+    }
+
+    /// Returns a texture with raw (not blurred) reflections. Color is premultiplied by confidence of
+    /// a reflection, which is stored in alpha channel.
+    pub fn reflection_map(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[0].texture.clone()
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        state: &PipelineState,
+        gbuffer: &GBuffer,
+        frame_buffer: &mut FrameBuffer,
+        camera: &Camera,
+        environment: &Rc<RefCell<GpuTexture>>,
+        probes: &Rc<RefCell<GpuTexture>>,
+        settings: &SsrSettings,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        scope_profile!();
+
+        let mut stats = RenderPassStatistics::default();
+
+        let projection_matrix = camera.projection_matrix();
+        let inv_projection_matrix = projection_matrix.try_inverse().unwrap_or_default();
+        let inv_view_projection = camera
+            .view_projection_matrix()
+            .try_inverse()
+            .unwrap_or_default();
+        let view_matrix = camera.view_matrix().basis();
+        let camera_position = camera.global_position();
+
+        let trace_viewport = Rect::new(0, 0, self.width, self.height);
+
+        self.framebuffer.clear(
+            state,
+            trace_viewport,
+            Some(Color::from_rgba(0, 0, 0, 0)),
+            None,
+            None,
+        );
+
+        let frame_texture = frame_buffer.color_attachments()[0].texture.clone();
+        let shader = &self.trace_shader;
+        stats += self.framebuffer.draw(
+            &self.quad,
+            state,
+            trace_viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: None,
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &make_frame_matrix(trace_viewport))
+                    .set_texture(&shader.depth_texture, &gbuffer.depth())
+                    .set_texture(&shader.normal_texture, &gbuffer.normal_texture())
+                    .set_texture(&shader.material_texture, &gbuffer.material_texture())
+                    .set_texture(&shader.frame_texture, &frame_texture)
+                    .set_matrix4(&shader.projection_matrix, &projection_matrix)
+                    .set_matrix4(&shader.inv_projection_matrix, &inv_projection_matrix)
+                    .set_matrix3(&shader.view_matrix, &view_matrix)
+                    .set_i32(&shader.max_steps, settings.max_steps as i32)
+                    .set_f32(&shader.max_distance, settings.max_distance)
+                    .set_f32(&shader.thickness, settings.thickness);
+            },
+        )?;
+
+        let frame_viewport = Rect::new(0, 0, gbuffer.width, gbuffer.height);
+        let reflection_map = self.reflection_map();
+        let reflection_texel_size = Vector2::new(1.0 / self.width as f32, 1.0 / self.height as f32);
+        let shader = &self.composite_shader;
+        stats += frame_buffer.draw(
+            &self.quad,
+            state,
+            frame_viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: Some(BlendParameters {
+                    func: BlendFunc::new(BlendFactor::One, BlendFactor::One),
+                    ..Default::default()
+                }),
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &make_frame_matrix(frame_viewport))
+                    .set_texture(&shader.reflection_texture, &reflection_map)
+                    .set_texture(&shader.depth_texture, &gbuffer.depth())
+                    .set_texture(&shader.diffuse_texture, &gbuffer.diffuse_texture())
+                    .set_texture(&shader.normal_texture, &gbuffer.normal_texture())
+                    .set_texture(&shader.material_texture, &gbuffer.material_texture())
+                    .set_texture(&shader.environment_texture, environment)
+                    .set_texture(&shader.probe_texture, probes)
+                    .set_matrix4(&shader.inv_view_projection, &inv_view_projection)
+                    .set_vector3(&shader.camera_position, &camera_position)
+                    .set_vector2(&shader.reflection_texel_size, &reflection_texel_size);
+            },
+        )?;
+
+        Ok(stats)
+    }
+}
+
+fn make_frame_matrix(viewport: Rect<i32>) -> Matrix4<f32> {
+    Matrix4::new_orthographic(
+        0.0,
+        viewport.w() as f32,
+        viewport.h() as f32,
+        0.0,
+        -1.0,
+        1.0,
+    ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
+        viewport.w() as f32,
+        viewport.h() as f32,
+        0.0,
+    ))
+}
@@ -0,0 +1,296 @@
+//! Temporal anti-aliasing (TAA). See [`TemporalAntiAliasingRenderer`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        color::Color,
+        math::Rect,
+        scope_profile,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        batch::PersistentIdentifier,
+        flat_shader::FlatShader,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::PipelineState,
+        },
+        gbuffer::GBuffer,
+        RenderPassStatistics,
+    },
+    scene::{camera::Camera, mesh::surface::SurfaceData},
+};
+use fxhash::FxHashMap;
+use std::{cell::RefCell, rc::Rc};
+
+/// State of a surface instance in a frame.
+pub(crate) struct InstanceState {
+    pub world_transform: Matrix4<f32>,
+    pub bone_matrices: Vec<Matrix4<f32>>,
+}
+
+/// Stores world and bone matrices of surface instances from the previous frame, so the velocity of
+/// every vertex could be calculated in shaders.
+#[derive(Default)]
+pub(crate) struct InstanceHistory {
+    previous: FxHashMap<PersistentIdentifier, InstanceState>,
+    current: FxHashMap<PersistentIdentifier, InstanceState>,
+}
+
+impl InstanceHistory {
+    /// Makes the data of the current frame the data of the previous one. Must be called once per
+    /// frame, before rendering anything.
+    pub fn begin_frame(&mut self) {
+        std::mem::swap(&mut self.previous, &mut self.current);
+        self.current.clear();
+    }
+
+    /// Remembers the state of an instance in the current frame.
+    pub fn record(
+        &mut self,
+        id: PersistentIdentifier,
+        world_transform: &Matrix4<f32>,
+        bone_matrices: &[Matrix4<f32>],
+    ) {
+        let state = self.current.entry(id).or_insert_with(|| InstanceState {
+            world_transform: Matrix4::identity(),
+            bone_matrices: Vec::new(),
+        });
+        state.world_transform = *world_transform;
+        state.bone_matrices.clear();
+        state.bone_matrices.extend_from_slice(bone_matrices);
+    }
+
+    /// Returns the state of an instance in the previous frame (if any).
+    pub fn previous(&self, id: PersistentIdentifier) -> Option<&InstanceState> {
+        self.previous.get(&id)
+    }
+}
+
+/// History of resolved frames of a camera.
+pub(crate) struct TaaHistory {
+    framebuffers: [FrameBuffer; 2],
+    current: usize,
+    prev_view_projection: Option<Matrix4<f32>>,
+}
+
+impl TaaHistory {
+    pub fn new(state: &PipelineState, width: usize, height: usize) -> Result<Self, FrameworkError> {
+        let make_framebuffer = || -> Result<FrameBuffer, FrameworkError> {
+            let mut texture = GpuTexture::new(
+                state,
+                GpuTextureKind::Rectangle { width, height },
+                PixelKind::RGBA16F,
+                MinificationFilter::Linear,
+                MagnificationFilter::Linear,
+                1,
+                None,
+            )?;
+            texture
+                .bind_mut(state, 0)
+                .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+                .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+            FrameBuffer::new(
+                state,
+                None,
+                vec![Attachment {
+                    kind: AttachmentKind::Color,
+                    texture: Rc::new(RefCell::new(texture)),
+                }],
+            )
+        };
+
+        Ok(Self {
+            framebuffers: [make_framebuffer()?, make_framebuffer()?],
+            current: 0,
+            prev_view_projection: None,
+        })
+    }
+
+    /// Returns view-projection matrix (without jitter) of the camera in the previous frame.
+    pub fn prev_view_projection(&self) -> Option<Matrix4<f32>> {
+        self.prev_view_projection
+    }
+}
+
+struct ResolveShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    current_texture: UniformLocation,
+    history_texture: UniformLocation,
+    velocity_texture: UniformLocation,
+    depth_texture: UniformLocation,
+    inverse_view_projection: UniformLocation,
+    prev_view_projection: UniformLocation,
+    inverse_screen_size: UniformLocation,
+    history_valid: UniformLocation,
+}
+
+impl ResolveShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/taa_fs.glsl");
+        let vertex_source = include_str!("shaders/taa_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "TaaResolveShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            current_texture: program
+                .uniform_location(state, &ImmutableString::new("currentTexture"))?,
+            history_texture: program
+                .uniform_location(state, &ImmutableString::new("historyTexture"))?,
+            velocity_texture: program
+                .uniform_location(state, &ImmutableString::new("velocityTexture"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            inverse_view_projection: program
+                .uniform_location(state, &ImmutableString::new("inverseViewProjection"))?,
+            prev_view_projection: program
+                .uniform_location(state, &ImmutableString::new("prevViewProjection"))?,
+            inverse_screen_size: program
+                .uniform_location(state, &ImmutableString::new("inverseScreenSize"))?,
+            history_valid: program
+                .uniform_location(state, &ImmutableString::new("historyValid"))?,
+            program,
+        })
+    }
+}
+
+/// Temporal anti-aliasing renderer blends current (jittered) frame with the history of previous
+/// frames. History is fetched using per-pixel velocities from the G-Buffer (or using reprojection of
+/// the depth, for pixels without velocity, such as sky or forward-rendered objects). To prevent
+/// ghosting, the history is clamped to the color range of the neighborhood of a pixel in the
+/// current frame and rejected completely when it comes from outside the screen.
+pub struct TemporalAntiAliasingRenderer {
+    shader: ResolveShader,
+    flat_shader: FlatShader,
+    quad: GeometryBuffer,
+}
+
+impl TemporalAntiAliasingRenderer {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            shader: ResolveShader::new(state)?,
+            flat_shader: FlatShader::new(state)?,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            )?,
+        })
+    }
+
+    /// Resolves the frame in the given frame buffer and writes the result back to it.
+    pub(crate) fn render(
+        &self,
+        state: &PipelineState,
+        viewport: Rect<i32>,
+        gbuffer: &GBuffer,
+        frame_buffer: &mut FrameBuffer,
+        history: &mut TaaHistory,
+        camera: &Camera,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        scope_profile!();
+
+        let mut statistics = RenderPassStatistics::default();
+
+        let frame_matrix = Matrix4::new_orthographic(
+            0.0,
+            viewport.w() as f32,
+            viewport.h() as f32,
+            0.0,
+            -1.0,
+            1.0,
+        ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
+            viewport.w() as f32,
+            viewport.h() as f32,
+            0.0,
+        ));
+
+        let view_projection = camera.unjittered_view_projection_matrix();
+        let inverse_view_projection = view_projection.try_inverse().unwrap_or_default();
+        let prev_view_projection = history.prev_view_projection.unwrap_or(view_projection);
+        let history_valid = history.prev_view_projection.is_some();
+
+        let current_texture = frame_buffer.color_attachments()[0].texture.clone();
+        let prev_index = history.current;
+        let next_index = 1 - history.current;
+        let history_texture = history.framebuffers[prev_index].color_attachments()[0]
+            .texture
+            .clone();
+        let resolved_framebuffer = &mut history.framebuffers[next_index];
+
+        resolved_framebuffer.clear(state, viewport, Some(Color::TRANSPARENT), None, None);
+
+        let shader = &self.shader;
+        statistics += resolved_framebuffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: None,
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&shader.current_texture, &current_texture)
+                    .set_texture(&shader.history_texture, &history_texture)
+                    .set_texture(&shader.velocity_texture, &gbuffer.velocity_texture())
+                    .set_texture(&shader.depth_texture, &gbuffer.depth())
+                    .set_matrix4(&shader.inverse_view_projection, &inverse_view_projection)
+                    .set_matrix4(&shader.prev_view_projection, &prev_view_projection)
+                    .set_vector2(
+                        &shader.inverse_screen_size,
+                        &Vector2::new(1.0 / viewport.w() as f32, 1.0 / viewport.h() as f32),
+                    )
+                    .set_bool(&shader.history_valid, history_valid);
+            },
+        )?;
+
+        // Copy resolved frame back. Depth must be left untouched, because it is shared with the
+        // G-Buffer.
+        let resolved_texture = resolved_framebuffer.color_attachments()[0].texture.clone();
+        let flat_shader = &self.flat_shader;
+        statistics += frame_buffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &flat_shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: None,
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&flat_shader.wvp_matrix, &frame_matrix)
+                    .set_texture(&flat_shader.diffuse_texture, &resolved_texture);
+            },
+        )?;
+
+        history.current = next_index;
+        history.prev_view_projection = Some(view_projection);
+
+        Ok(statistics)
+    }
+}
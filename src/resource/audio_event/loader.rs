@@ -0,0 +1,34 @@
+//! Audio event bank loader.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+    },
+    core::{uuid::Uuid, TypeUuidProvider},
+    resource::audio_event::AudioEventBank,
+};
+use fyrox_resource::state::LoadError;
+use std::{path::PathBuf, sync::Arc};
+
+/// Default implementation for audio event bank loading.
+pub struct AudioEventBankLoader;
+
+impl ResourceLoader for AudioEventBankLoader {
+    fn extensions(&self) -> &[&str] {
+        &["audio_bank"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        AudioEventBank::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let bank = AudioEventBank::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(bank))
+        })
+    }
+}
@@ -0,0 +1,409 @@
+//! Audio event bank is an asset that contains a set of named audio events. Each event references
+//! multiple sound clips and defines how to pick one of them as well as pitch and volume variations.
+//! It is a convenient "fire-and-forget" way of playing sounds, such as footsteps, impacts, gun
+//! shots, etc. that should sound a bit different each time.
+//!
+//! # Example
+//!
+//! ```rust
+//! use fyrox::{
+//!     core::algebra::Vector3,
+//!     resource::audio_event::{AudioEventBankResource, AudioEventBankResourceExtension},
+//!     scene::Scene,
+//! };
+//!
+//! fn on_footstep(bank: &AudioEventBankResource, scene: &mut Scene, position: Vector3<f32>) {
+//!     bank.play_event("footstep_grass", position, &mut scene.graph);
+//! }
+//! ```
+
+use crate::{
+    asset::{io::ResourceIo, Resource, ResourceData},
+    core::{
+        algebra::Vector3,
+        instant::Instant,
+        io::FileLoadError,
+        numeric_range::RangeExt,
+        pool::Handle,
+        rand::{thread_rng, Rng},
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        node::Node,
+        sound::{AudioBusGraph, SoundBufferResource, SoundBuilder, Status},
+        transform::TransformBuilder,
+    },
+};
+use fyrox_core::uuid_provider;
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::Range,
+    path::Path,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+pub mod loader;
+
+/// An error that may occur during audio event bank loading.
+#[derive(Debug)]
+pub enum AudioEventBankError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for AudioEventBankError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudioEventBankError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            AudioEventBankError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for AudioEventBankError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for AudioEventBankError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Defines how an audio event picks a clip to play.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum AudioEventSelection {
+    /// A random clip is picked each time, the same clip is never played twice in a row (unless
+    /// there is only one clip).
+    #[default]
+    Random,
+    /// Clips are played one after another in the order they're defined.
+    Sequential,
+}
+
+uuid_provider!(AudioEventSelection = "b0c2e6a5-1d8e-4b0f-a0b3-2a5b4b7c9e61");
+
+/// A named audio event. See module docs for more info.
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct AudioEvent {
+    /// Name of the event, it is used to find the event in a bank.
+    pub name: String,
+
+    /// A set of clips of the event.
+    pub clips: Vec<Option<SoundBufferResource>>,
+
+    /// Defines how a clip to play is picked.
+    pub selection: AudioEventSelection,
+
+    /// Range of gain that is randomly picked each time the event is played.
+    pub gain: Range<f32>,
+
+    /// Range of pitch that is randomly picked each time the event is played.
+    pub pitch: Range<f32>,
+
+    /// Minimal time (in seconds) between two consecutive playbacks of the event. The event is
+    /// ignored if it is played during cooldown.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub cooldown: f32,
+
+    /// Radius of the spawned sounds. See [`crate::scene::sound::Sound::set_radius`] for more info.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub radius: f32,
+
+    /// Spatial blend factor of the spawned sounds. See [`crate::scene::sound::Sound::set_spatial_blend`]
+    /// for more info.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub spatial_blend: f32,
+
+    /// Name of an audio bus to which the spawned sounds will be attached to.
+    pub audio_bus: String,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    last_clip: Option<usize>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    last_played: Option<Instant>,
+}
+
+uuid_provider!(AudioEvent = "33540fd2-4c12-4751-9d8e-9da57805eeac");
+
+impl Default for AudioEvent {
+    fn default() -> Self {
+        Self {
+            name: "Event".to_string(),
+            clips: Default::default(),
+            selection: Default::default(),
+            gain: 1.0..1.0,
+            pitch: 1.0..1.0,
+            cooldown: 0.0,
+            radius: 10.0,
+            spatial_blend: 1.0,
+            audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            last_clip: None,
+            last_played: None,
+        }
+    }
+}
+
+impl AudioEvent {
+    /// Creates a new audio event with the given name and a set of clips.
+    pub fn new<S: AsRef<str>>(name: S, clips: Vec<SoundBufferResource>) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            clips: clips.into_iter().map(Some).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn is_cooling_down(&self, now: Instant) -> bool {
+        self.last_played.map_or(false, |last_played| {
+            now.duration_since(last_played).as_secs_f32() < self.cooldown
+        })
+    }
+
+    fn pick_clip(&mut self) -> Option<SoundBufferResource> {
+        let available = self
+            .clips
+            .iter()
+            .enumerate()
+            .filter_map(|(i, clip)| clip.as_ref().map(|_| i))
+            .collect::<Vec<_>>();
+        if available.is_empty() {
+            return None;
+        }
+
+        let index = match self.selection {
+            AudioEventSelection::Random => {
+                let mut index = available[thread_rng().gen_range(0..available.len())];
+                if available.len() > 1 && Some(index) == self.last_clip {
+                    // Pick the next one to prevent repetition.
+                    let position = available.iter().position(|i| *i == index).unwrap_or(0);
+                    index = available[(position + 1) % available.len()];
+                }
+                index
+            }
+            AudioEventSelection::Sequential => self
+                .last_clip
+                .and_then(|last| available.iter().find(|i| **i > last).cloned())
+                .unwrap_or(available[0]),
+        };
+
+        self.last_clip = Some(index);
+        self.clips[index].clone()
+    }
+
+    /// Picks a clip and spawns a play-once sound at the given position. Returns a handle of the
+    /// spawned sound or [`Handle::NONE`] if the event is cooling down or it has no clips.
+    pub fn play(&mut self, position: Vector3<f32>, graph: &mut Graph) -> Handle<Node> {
+        let now = Instant::now();
+        if self.is_cooling_down(now) {
+            return Handle::NONE;
+        }
+
+        let Some(clip) = self.pick_clip() else {
+            return Handle::NONE;
+        };
+
+        self.last_played = Some(now);
+
+        let mut rng = thread_rng();
+        SoundBuilder::new(
+            BaseBuilder::new()
+                .with_name(&self.name)
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                ),
+        )
+        .with_buffer(Some(clip))
+        .with_play_once(true)
+        .with_status(Status::Playing)
+        .with_gain(self.gain.random(&mut rng))
+        .with_pitch(self.pitch.random(&mut rng) as f64)
+        .with_radius(self.radius)
+        .with_spatial_blend_factor(self.spatial_blend)
+        .with_audio_bus(self.audio_bus.clone())
+        .build(graph)
+    }
+}
+
+/// A set of named audio events. See module docs for more info.
+#[derive(Debug, Default, Clone, Visit, Reflect)]
+pub struct AudioEventBank {
+    /// Audio events of the bank.
+    pub events: Vec<AudioEvent>,
+}
+
+impl ResourceData for AudioEventBank {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("AudioEventBank", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+impl TypeUuidProvider for AudioEventBank {
+    fn type_uuid() -> Uuid {
+        uuid!("6d0a9f2e-3b7c-4e5a-9c1d-8f4e2a7b5c93")
+    }
+}
+
+impl AudioEventBank {
+    /// Tries to find an event with the given name.
+    pub fn find_event(&self, name: &str) -> Option<&AudioEvent> {
+        self.events.iter().find(|event| event.name == name)
+    }
+
+    /// Tries to find an event with the given name.
+    pub fn find_event_mut(&mut self, name: &str) -> Option<&mut AudioEvent> {
+        self.events.iter_mut().find(|event| event.name == name)
+    }
+
+    /// Plays an event with the given name at the given position. See [`AudioEvent::play`] for more
+    /// info.
+    pub fn play_event(
+        &mut self,
+        name: &str,
+        position: Vector3<f32>,
+        graph: &mut Graph,
+    ) -> Handle<Node> {
+        match self.find_event_mut(name) {
+            Some(event) => event.play(position, graph),
+            None => Handle::NONE,
+        }
+    }
+
+    /// Load an audio event bank from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, AudioEventBankError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut bank = AudioEventBank::default();
+        bank.visit("AudioEventBank", &mut visitor)?;
+        Ok(bank)
+    }
+}
+
+/// Type alias for audio event bank resources.
+pub type AudioEventBankResource = Resource<AudioEventBank>;
+
+/// Extension trait for audio event bank resources.
+pub trait AudioEventBankResourceExtension {
+    /// Plays an event with the given name at the given position. Does nothing and returns
+    /// [`Handle::NONE`] if the bank is not loaded yet.
+    fn play_event(&self, name: &str, position: Vector3<f32>, graph: &mut Graph) -> Handle<Node>;
+}
+
+impl AudioEventBankResourceExtension for AudioEventBankResource {
+    fn play_event(&self, name: &str, position: Vector3<f32>, graph: &mut Graph) -> Handle<Node> {
+        match self.state().data() {
+            Some(bank) => bank.play_event(name, position, graph),
+            None => Handle::NONE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        resource::audio_event::{AudioEvent, AudioEventBank, AudioEventSelection},
+        scene::{
+            graph::Graph,
+            sound::{DataSource, SoundBufferResource},
+        },
+    };
+    use fyrox_sound::buffer::SoundBufferResourceExtension;
+
+    fn make_clip() -> SoundBufferResource {
+        SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples: vec![0.0; 16],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_audio_event_sequential_selection() {
+        let clips = vec![make_clip(), make_clip(), make_clip()];
+        let mut event = AudioEvent::new("Event", clips.clone());
+        event.selection = AudioEventSelection::Sequential;
+
+        for i in 0..6 {
+            let clip = event.pick_clip().unwrap();
+            assert_eq!(clip, clips[i % clips.len()]);
+        }
+    }
+
+    #[test]
+    fn test_audio_event_cooldown() {
+        let mut graph = Graph::new();
+        let mut bank = AudioEventBank {
+            events: vec![AudioEvent {
+                cooldown: 10.0,
+                ..AudioEvent::new("footstep", vec![make_clip(), make_clip()])
+            }],
+        };
+
+        assert!(bank
+            .play_event("footstep", Vector3::default(), &mut graph)
+            .is_some());
+        assert!(bank
+            .play_event("footstep", Vector3::default(), &mut graph)
+            .is_none());
+        assert!(bank
+            .play_event("unknown", Vector3::default(), &mut graph)
+            .is_none());
+    }
+}
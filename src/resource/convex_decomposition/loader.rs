@@ -0,0 +1,34 @@
+//! Convex decomposition loader.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+    },
+    core::{uuid::Uuid, TypeUuidProvider},
+    resource::convex_decomposition::ConvexDecomposition,
+};
+use fyrox_resource::state::LoadError;
+use std::{path::PathBuf, sync::Arc};
+
+/// Default implementation for convex decomposition loading.
+pub struct ConvexDecompositionLoader;
+
+impl ResourceLoader for ConvexDecompositionLoader {
+    fn extensions(&self) -> &[&str] {
+        &["convex"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        ConvexDecomposition::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let decomposition = ConvexDecomposition::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(decomposition))
+        })
+    }
+}
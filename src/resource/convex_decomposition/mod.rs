@@ -0,0 +1,265 @@
+//! Convex decomposition resource holds a set of convex hulls that approximates an arbitrary (possibly
+//! concave) mesh. It is used by colliders with [`crate::scene::collider::ColliderShape::ConvexDecomposition`]
+//! shape. Unlike triangle meshes, compound convex shapes could be used by dynamic rigid bodies. Convex
+//! decomposition is a very slow process, that's why it is performed once (for example - in the editor)
+//! and its result is stored as an asset.
+
+use crate::{
+    asset::{io::ResourceIo, Resource, ResourceData},
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        io::FileLoadError,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::mesh::{
+        buffer::{VertexAttributeUsage, VertexReadTrait},
+        Mesh,
+    },
+};
+use rapier3d::parry::transformation::vhacd::{VHACDParameters, VHACD};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+pub mod loader;
+
+/// An error that may occur during convex decomposition resource loading.
+#[derive(Debug)]
+pub enum ConvexDecompositionError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for ConvexDecompositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConvexDecompositionError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            ConvexDecompositionError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for ConvexDecompositionError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for ConvexDecompositionError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A set of parameters for convex decomposition.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct ConvexDecompositionParameters {
+    /// Maximum allowed concavity of each convex part. Smaller values produce more precise results,
+    /// but increase the amount of convex parts.
+    #[reflect(min_value = 0.0, step = 0.001)]
+    pub concavity: f32,
+    /// Resolution of the voxel grid used for decomposition. Higher values produce more precise
+    /// results, but significantly increase the time needed for decomposition.
+    #[reflect(min_value = 16.0)]
+    pub resolution: u32,
+    /// Maximum amount of convex parts.
+    #[reflect(min_value = 1.0)]
+    pub max_convex_hulls: u32,
+}
+
+impl Default for ConvexDecompositionParameters {
+    fn default() -> Self {
+        Self {
+            concavity: 0.01,
+            resolution: 64,
+            max_convex_hulls: 1024,
+        }
+    }
+}
+
+/// A single convex part of a convex decomposition.
+#[derive(Debug, Default, Clone, PartialEq, Visit, Reflect)]
+pub struct ConvexHull {
+    /// Vertices of the convex hull.
+    pub vertices: Vec<Vector3<f32>>,
+}
+
+/// Result of convex decomposition of a mesh. See module docs for more info.
+#[derive(Debug, Default, Clone, PartialEq, Visit, Reflect)]
+pub struct ConvexDecomposition {
+    /// A set of convex hulls that approximates the source mesh.
+    pub hulls: Vec<ConvexHull>,
+}
+
+impl ResourceData for ConvexDecomposition {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("ConvexDecomposition", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+impl TypeUuidProvider for ConvexDecomposition {
+    fn type_uuid() -> Uuid {
+        uuid!("3c1c0f3d-7a0e-4a8e-9e37-9b1f3d5b6c27")
+    }
+}
+
+impl ConvexDecomposition {
+    /// Performs approximate convex decomposition (V-HACD) of the given triangle mesh.
+    pub fn from_triangles(
+        vertices: &[Vector3<f32>],
+        triangles: &[[u32; 3]],
+        parameters: &ConvexDecompositionParameters,
+    ) -> Self {
+        if vertices.is_empty() || triangles.is_empty() {
+            return Default::default();
+        }
+
+        let points = vertices
+            .iter()
+            .map(|v| Point3::from(*v))
+            .collect::<Vec<_>>();
+
+        let vhacd_parameters = VHACDParameters {
+            concavity: parameters.concavity,
+            resolution: parameters.resolution,
+            max_convex_hulls: parameters.max_convex_hulls,
+            ..Default::default()
+        };
+
+        // Hulls are built from the source triangles, not from the voxels, so they do not stick out
+        // of the mesh by the size of a voxel.
+        let decomposition = VHACD::decompose(&vhacd_parameters, &points, triangles, true);
+
+        Self {
+            hulls: decomposition
+                .compute_exact_convex_hulls(&points, triangles)
+                .into_iter()
+                .map(|(points, _)| ConvexHull {
+                    vertices: points.into_iter().map(|p| p.coords).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Performs approximate convex decomposition (V-HACD) of every surface of the given mesh. Vertices
+    /// of the mesh are transformed using the given transform, it could be used to bake scaling into
+    /// the resulting convex hulls.
+    pub fn from_mesh(
+        mesh: &Mesh,
+        transform: &Matrix4<f32>,
+        parameters: &ConvexDecompositionParameters,
+    ) -> Self {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+
+        for surface in mesh.surfaces() {
+            let data = surface.data();
+            let data = data.lock();
+
+            let offset = vertices.len() as u32;
+            for vertex in data.vertex_buffer.iter() {
+                if let Ok(position) = vertex.read_3_f32(VertexAttributeUsage::Position) {
+                    vertices.push(transform.transform_point(&Point3::from(position)).coords);
+                }
+            }
+
+            triangles.extend(
+                data.geometry_buffer
+                    .iter()
+                    .map(|t| [t[0] + offset, t[1] + offset, t[2] + offset]),
+            );
+        }
+
+        Self::from_triangles(&vertices, &triangles, parameters)
+    }
+
+    /// Load a convex decomposition resource from the specific file path.
+    pub async fn from_file(
+        path: &Path,
+        io: &dyn ResourceIo,
+    ) -> Result<Self, ConvexDecompositionError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut decomposition = ConvexDecomposition::default();
+        decomposition.visit("ConvexDecomposition", &mut visitor)?;
+        Ok(decomposition)
+    }
+}
+
+/// Type alias for convex decomposition resources.
+pub type ConvexDecompositionResource = Resource<ConvexDecomposition>;
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Matrix4,
+        resource::convex_decomposition::{ConvexDecomposition, ConvexDecompositionParameters},
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+        },
+    };
+
+    #[test]
+    fn test_convex_decomposition() {
+        let mut graph = Graph::new();
+        let mesh = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .build()])
+            .build(&mut graph);
+
+        let decomposition = ConvexDecomposition::from_mesh(
+            graph[mesh].as_mesh(),
+            &Matrix4::new_scaling(2.0),
+            &ConvexDecompositionParameters::default(),
+        );
+
+        assert!(!decomposition.hulls.is_empty());
+        for hull in decomposition.hulls.iter() {
+            for vertex in hull.vertices.iter() {
+                assert!(vertex.iter().all(|c| c.abs() <= 1.01));
+            }
+        }
+    }
+}
@@ -553,7 +553,7 @@ async fn convert_mesh(
                                 );
                             }
                             if let Some(tangents) = blend_shape_geometry.tangents.as_ref() {
-                                blend_shape.normals.insert(
+                                blend_shape.tangents.insert(
                                     final_index as u32,
                                     utils::vec3_f16_from_f32(tangents[*relative_index as usize]),
                                 );
@@ -761,6 +761,51 @@ async fn convert_model(
         animation.add_track(scale_track);
     }
 
+    // Convert animations of blend shape weights. Mesh blend shapes are taken from the last geometry,
+    // so do the same here to keep the indices in sync.
+    if let Some(&geom_handle) = model.geoms.last() {
+        let geom = fbx_scene.get(geom_handle).as_mesh_geometry()?;
+        for (index, channel) in geom
+            .collect_blend_shapes_refs(fbx_scene)?
+            .into_iter()
+            .enumerate()
+        {
+            if channel.animation_curve_node.is_none() {
+                continue;
+            }
+            let FbxComponent::AnimationCurveNode(curve_node) =
+                fbx_scene.get(channel.animation_curve_node)
+            else {
+                continue;
+            };
+            if curve_node.actual_type != FbxAnimationCurveNodeType::DeformPercent {
+                continue;
+            }
+
+            let mut track = Mesh::make_blend_shape_weight_track(index);
+            track.set_target(node_handle);
+            let curve = &mut track.data_container_mut().curves_mut()[0];
+            if let Some(FbxComponent::AnimationCurve(fbx_curve)) = curve_node
+                .curves
+                .values()
+                .next()
+                .map(|curve_handle| fbx_scene.get(*curve_handle))
+            {
+                for pair in fbx_curve.keys.iter() {
+                    curve.add_key(CurveKey::new(pair.time, pair.value, CurveKeyKind::Linear));
+                }
+            }
+            if curve.keys().is_empty() {
+                curve.add_key(CurveKey::new(
+                    0.0,
+                    channel.deform_percent,
+                    CurveKeyKind::Constant,
+                ));
+            }
+            animation.add_track(track);
+        }
+    }
+
     animation.fit_length_to_content();
 
     Ok(node_handle)
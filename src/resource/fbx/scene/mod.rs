@@ -202,11 +202,11 @@ fn link_child_with_parent_component(
                 model.inv_bind_transform = sub_deformer.transform;
             }
         }
-        FbxComponent::BlendShapeChannel(channel) => {
-            if let FbxComponent::ShapeGeometry(_) = child {
-                channel.geometry = child_handle;
-            }
-        }
+        FbxComponent::BlendShapeChannel(channel) => match child {
+            FbxComponent::ShapeGeometry(_) => channel.geometry = child_handle,
+            FbxComponent::AnimationCurveNode(_) => channel.animation_curve_node = child_handle,
+            _ => (),
+        },
         // Ignore rest
         _ => (),
     }
@@ -252,6 +252,9 @@ const FBX_TIME_UNIT: f64 = 1.0 / 46_186_158_000.0;
 
 pub struct FbxBlendShapeChannel {
     pub geometry: Handle<FbxComponent>,
+    /// Animation curve node of `DeformPercent` property, could be `none` if the weight of the
+    /// channel is not animated.
+    pub animation_curve_node: Handle<FbxComponent>,
     pub deform_percent: f32,
     pub name: String,
 }
@@ -271,6 +274,7 @@ impl FbxBlendShapeChannel {
 
         Ok(Self {
             geometry: Default::default(),
+            animation_curve_node: Default::default(),
             deform_percent,
             name,
         })
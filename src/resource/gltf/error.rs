@@ -0,0 +1,92 @@
+//! Contains all possible errors that can occur during glTF parsing and conversion.
+
+use crate::core::io::FileLoadError;
+use std::fmt::{Display, Formatter};
+
+/// See module docs.
+#[derive(Debug)]
+pub enum GltfError {
+    /// The document is malformed or uses unsupported features.
+    Gltf(gltf::Error),
+
+    /// An error occurred during file loading.
+    FileLoadError(FileLoadError),
+
+    /// A buffer references binary chunk of a `.glb` file, but there is no such chunk.
+    MissingBlob,
+
+    /// A buffer or an image is stored in a data URI that cannot be decoded.
+    InvalidDataUri(String),
+
+    /// A buffer is shorter than the document claims it is.
+    BufferTooShort {
+        /// Index of the buffer.
+        index: usize,
+        /// Length of the buffer, declared in the document.
+        expected: usize,
+        /// Actual length of the loaded data.
+        actual: usize,
+    },
+
+    /// A mesh primitive does not have vertex positions.
+    MissingPositions,
+
+    /// A mesh primitive references a vertex that does not exist.
+    IndexOutOfBounds,
+
+    /// A vertex references a joint with an index that the engine cannot store.
+    TooManyJoints(u16),
+}
+
+impl Display for GltfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GltfError::Gltf(v) => {
+                write!(f, "glTF: Invalid document: {v}")
+            }
+            GltfError::FileLoadError(v) => {
+                write!(f, "glTF: File load error {v:?}.")
+            }
+            GltfError::MissingBlob => {
+                write!(f, "glTF: Binary chunk is missing.")
+            }
+            GltfError::InvalidDataUri(v) => {
+                write!(f, "glTF: Unable to decode data URI: {v}")
+            }
+            GltfError::BufferTooShort {
+                index,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "glTF: Buffer {index} is too short. Expected {expected} bytes, got {actual}."
+                )
+            }
+            GltfError::MissingPositions => {
+                write!(f, "glTF: Mesh primitive does not have vertex positions.")
+            }
+            GltfError::IndexOutOfBounds => {
+                write!(f, "glTF: Mesh primitive references non existing vertex.")
+            }
+            GltfError::TooManyJoints(v) => {
+                write!(
+                    f,
+                    "glTF: Vertex references joint {v}, but only 256 joints per skin are supported."
+                )
+            }
+        }
+    }
+}
+
+impl From<gltf::Error> for GltfError {
+    fn from(err: gltf::Error) -> Self {
+        GltfError::Gltf(err)
+    }
+}
+
+impl From<FileLoadError> for GltfError {
+    fn from(err: FileLoadError) -> Self {
+        GltfError::FileLoadError(err)
+    }
+}
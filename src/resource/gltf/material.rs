@@ -0,0 +1,463 @@
+//! Conversion of glTF PBR metallic-roughness materials to engine materials.
+
+use crate::{
+    asset::{manager::ResourceManager, untyped::ResourceKind},
+    core::{
+        algebra::{Vector3, Vector4},
+        color::Color,
+        log::Log,
+        sstorage::ImmutableString,
+    },
+    material::{shader::SamplerFallback, Material, MaterialResource, PropertyValue},
+    resource::{
+        gltf::{decode_data_uri, error::GltfError, percent_decode},
+        model::{MaterialSearchOptions, ModelImportOptions},
+        texture::{
+            Texture, TextureKind, TextureMinificationFilter, TexturePixelKind, TextureResource,
+            TextureResourceExtension,
+        },
+    },
+};
+use fxhash::FxHashMap;
+use fyrox_resource::io::ResourceIo;
+use gltf::{image::Source, material::AlphaMode, Document};
+use std::path::{Path, PathBuf};
+
+/// Engine material that corresponds to a glTF material.
+pub(super) struct ConvertedMaterial {
+    pub resource: MaterialResource,
+    /// Whether the material needs blending and thus must be rendered using forward renderer.
+    pub transparent: bool,
+}
+
+impl ConvertedMaterial {
+    /// Material that is used for primitives that do not reference any material. glTF spec
+    /// defines it as plain white, fully rough dielectric.
+    pub fn default_material() -> Self {
+        Self {
+            resource: MaterialResource::new_ok(ResourceKind::Embedded, Material::standard()),
+            transparent: false,
+        }
+    }
+}
+
+pub(super) struct MaterialConverter<'a> {
+    document: &'a Document,
+    buffers: &'a [Vec<u8>],
+    resource_manager: &'a ResourceManager,
+    io: &'a dyn ResourceIo,
+    model_path: &'a Path,
+    model_import_options: &'a ModelImportOptions,
+    textures: FxHashMap<usize, TextureResource>,
+}
+
+fn set_property(material: &mut Material, name: &str, value: PropertyValue) {
+    if let Err(e) = material.set_property(&ImmutableString::new(name), value) {
+        Log::err(format!(
+            "Failed to set {name} property for glTF material. Reason: {e:?}"
+        ))
+    }
+}
+
+fn set_texture(
+    material: &mut Material,
+    name: &str,
+    texture: TextureResource,
+    fallback: SamplerFallback,
+) {
+    set_property(
+        material,
+        name,
+        PropertyValue::Sampler {
+            value: Some(texture),
+            fallback,
+        },
+    )
+}
+
+fn make_texture(
+    width: u32,
+    height: u32,
+    pixel_kind: TexturePixelKind,
+    bytes: Vec<u8>,
+) -> Option<TextureResource> {
+    let texture = TextureResource::from_bytes(
+        TextureKind::Rectangle { width, height },
+        pixel_kind,
+        bytes,
+        ResourceKind::Embedded,
+    )?;
+    // Generated textures do not have mip levels.
+    texture
+        .data_ref()
+        .set_minification_filter(TextureMinificationFilter::Linear);
+    Some(texture)
+}
+
+fn make_constant_texture(value: f32) -> Option<TextureResource> {
+    make_texture(
+        1,
+        1,
+        TexturePixelKind::R8,
+        vec![(value.clamp(0.0, 1.0) * 255.0) as u8],
+    )
+}
+
+impl<'a> MaterialConverter<'a> {
+    pub fn new(
+        document: &'a Document,
+        buffers: &'a [Vec<u8>],
+        resource_manager: &'a ResourceManager,
+        io: &'a dyn ResourceIo,
+        model_path: &'a Path,
+        model_import_options: &'a ModelImportOptions,
+    ) -> Self {
+        Self {
+            document,
+            buffers,
+            resource_manager,
+            io,
+            model_path,
+            model_import_options,
+            textures: Default::default(),
+        }
+    }
+
+    /// Tries to find a file referenced by the given relative URI, using material search options
+    /// of the model.
+    async fn resolve_path(&self, uri: &str) -> Option<PathBuf> {
+        let relative_path = PathBuf::from(percent_decode(uri));
+        let model_dir = self.model_path.parent().unwrap_or_else(|| Path::new("."));
+        let file_name = relative_path.file_name()?;
+
+        match self.model_import_options.material_search_options {
+            MaterialSearchOptions::MaterialsDirectory(ref directory) => {
+                Some(directory.join(file_name))
+            }
+            MaterialSearchOptions::RecursiveUp => {
+                // glTF URIs are relative to the model, so check the exact location first.
+                let candidate = model_dir.join(&relative_path);
+                if self.io.exists(&candidate).await {
+                    return Some(candidate);
+                }
+                let mut path = self.model_path.to_owned();
+                while let Some(parent) = path.parent() {
+                    let candidate = parent.join(file_name);
+                    if self.io.exists(&candidate).await {
+                        return Some(candidate);
+                    }
+                    path.pop();
+                }
+                None
+            }
+            MaterialSearchOptions::WorkingDirectory => {
+                if let Ok(iter) = self.io.walk_directory(Path::new(".")).await {
+                    for dir in iter {
+                        if self.io.is_dir(&dir).await {
+                            let candidate = dir.join(file_name);
+                            if self.io.exists(&candidate).await {
+                                return Some(candidate);
+                            }
+                        }
+                    }
+                }
+                None
+            }
+            MaterialSearchOptions::UsePathDirectly => Some(model_dir.join(relative_path)),
+        }
+    }
+
+    /// Returns raw (encoded) image data, if the image is embedded in the model. External images
+    /// are loaded by the resource manager, so `None` is returned for them.
+    fn embedded_image_data(&self, image: &gltf::Image) -> Result<Option<Vec<u8>>, GltfError> {
+        match image.source() {
+            Source::View { view, .. } => {
+                let buffer = &self.buffers[view.buffer().index()];
+                Ok(buffer
+                    .get(view.offset()..view.offset() + view.length())
+                    .map(|data| data.to_vec()))
+            }
+            Source::Uri { uri, .. } => match uri.strip_prefix("data:") {
+                Some(data_uri) => decode_data_uri(data_uri).map(Some),
+                None => Ok(None),
+            },
+        }
+    }
+
+    /// Returns raw (encoded) image data from any source.
+    async fn image_data(&self, image: &gltf::Image<'_>) -> Result<Option<Vec<u8>>, GltfError> {
+        if let Some(data) = self.embedded_image_data(image)? {
+            return Ok(Some(data));
+        }
+        if let Source::Uri { uri, .. } = image.source() {
+            if let Some(path) = self.resolve_path(uri).await {
+                return Ok(Some(self.io.load_file(&path).await?));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn texture(&mut self, texture: gltf::Texture<'_>) -> Option<TextureResource> {
+        let image = texture.source();
+        if let Some(texture) = self.textures.get(&image.index()) {
+            return Some(texture.clone());
+        }
+
+        let resource = match self.embedded_image_data(&image) {
+            Ok(Some(data)) => {
+                match TextureResource::load_from_memory(
+                    ResourceKind::Embedded,
+                    &data,
+                    Default::default(),
+                ) {
+                    Ok(texture) => Some(texture),
+                    Err(e) => {
+                        Log::err(format!(
+                            "Unable to load embedded texture {} of glTF model {:?}. Reason: {e:?}",
+                            image.index(),
+                            self.model_path
+                        ));
+                        None
+                    }
+                }
+            }
+            Ok(None) => {
+                if let Source::Uri { uri, .. } = image.source() {
+                    match self.resolve_path(uri).await {
+                        Some(path) => Some(self.resource_manager.request::<Texture>(path)),
+                        None => {
+                            Log::err(format!(
+                                "Unable to find a texture {uri:?} for glTF model {:?} using {:?} option!",
+                                self.model_path, self.model_import_options.material_search_options
+                            ));
+                            None
+                        }
+                    }
+                } else {
+                    None
+                }
+            }
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to decode texture {} of glTF model {:?}. Reason: {e}",
+                    image.index(),
+                    self.model_path
+                ));
+                None
+            }
+        };
+
+        if let Some(resource) = resource.as_ref() {
+            self.textures.insert(image.index(), resource.clone());
+        }
+
+        resource
+    }
+
+    /// glTF packs metalness into blue channel and roughness into green channel of a single
+    /// texture, while the engine uses separate single-channel textures. Scalar factors of the
+    /// material are baked into the resulting textures.
+    async fn split_metallic_roughness(
+        &self,
+        texture: gltf::Texture<'_>,
+        metallic_factor: f32,
+        roughness_factor: f32,
+    ) -> Option<(TextureResource, TextureResource)> {
+        let image = texture.source();
+        let data = match self.image_data(&image).await {
+            Ok(Some(data)) => data,
+            Ok(None) => {
+                Log::err(format!(
+                    "Unable to find metallic-roughness texture {} for glTF model {:?}!",
+                    image.index(),
+                    self.model_path
+                ));
+                return None;
+            }
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to load metallic-roughness texture {} for glTF model {:?}. Reason: {e}",
+                    image.index(),
+                    self.model_path
+                ));
+                return None;
+            }
+        };
+
+        let decoded = match image::load_from_memory(&data) {
+            Ok(decoded) => decoded.into_rgba8(),
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to decode metallic-roughness texture {} for glTF model {:?}. Reason: {e}",
+                    image.index(),
+                    self.model_path
+                ));
+                return None;
+            }
+        };
+
+        let scale = |value: u8, factor: f32| (value as f32 * factor).clamp(0.0, 255.0) as u8;
+        let (width, height) = decoded.dimensions();
+        let mut metallic = Vec::with_capacity((width * height) as usize);
+        let mut roughness = Vec::with_capacity((width * height) as usize);
+        for pixel in decoded.pixels() {
+            roughness.push(scale(pixel[1], roughness_factor));
+            metallic.push(scale(pixel[2], metallic_factor));
+        }
+
+        Some((
+            make_texture(width, height, TexturePixelKind::R8, metallic)?,
+            make_texture(width, height, TexturePixelKind::R8, roughness)?,
+        ))
+    }
+
+    async fn convert_material(&mut self, material: gltf::Material<'_>) -> ConvertedMaterial {
+        let mut result = if material.double_sided() {
+            Material::standard_two_sides()
+        } else {
+            Material::standard()
+        };
+
+        let pbr = material.pbr_metallic_roughness();
+
+        let mut base_color = Vector4::from(pbr.base_color_factor());
+        let transmission = material
+            .transmission()
+            .map(|t| t.transmission_factor())
+            .unwrap_or_default();
+        // The engine has no dedicated transmission model, so approximate it using transparency.
+        base_color.w *= 1.0 - transmission;
+        set_property(
+            &mut result,
+            "diffuseColor",
+            PropertyValue::Color(Color::from(base_color).linear_to_srgb()),
+        );
+
+        for (info, name, fallback) in [
+            (
+                pbr.base_color_texture(),
+                "diffuseTexture",
+                SamplerFallback::White,
+            ),
+            (
+                material.emissive_texture(),
+                "emissionTexture",
+                SamplerFallback::Black,
+            ),
+        ] {
+            if let Some(info) = info {
+                if info.tex_coord() != 0 {
+                    Log::warn(format!(
+                        "glTF model {:?} uses texture coordinate set {} for {name}, only the first set is supported.",
+                        self.model_path,
+                        info.tex_coord()
+                    ));
+                }
+                if let Some(texture) = self.texture(info.texture()).await {
+                    set_texture(&mut result, name, texture, fallback);
+                }
+            }
+        }
+
+        if let Some(normal) = material.normal_texture() {
+            if let Some(texture) = self.texture(normal.texture()).await {
+                set_texture(
+                    &mut result,
+                    "normalTexture",
+                    texture,
+                    SamplerFallback::Normal,
+                );
+            }
+        }
+
+        // Occlusion is stored in red channel, which is exactly what the engine samples.
+        if let Some(occlusion) = material.occlusion_texture() {
+            if let Some(texture) = self.texture(occlusion.texture()).await {
+                set_texture(&mut result, "aoTexture", texture, SamplerFallback::White);
+            }
+        }
+
+        let metallic_factor = pbr.metallic_factor();
+        let roughness_factor = pbr.roughness_factor();
+        let split = match pbr.metallic_roughness_texture() {
+            Some(info) => {
+                self.split_metallic_roughness(info.texture(), metallic_factor, roughness_factor)
+                    .await
+            }
+            None => None,
+        };
+        match split {
+            Some((metallic, roughness)) => {
+                set_texture(
+                    &mut result,
+                    "metallicTexture",
+                    metallic,
+                    SamplerFallback::Black,
+                );
+                set_texture(
+                    &mut result,
+                    "roughnessTexture",
+                    roughness,
+                    SamplerFallback::White,
+                );
+            }
+            None => {
+                // Factors that match sampler fallbacks need no texture at all.
+                if metallic_factor != 0.0 {
+                    if let Some(texture) = make_constant_texture(metallic_factor) {
+                        set_texture(
+                            &mut result,
+                            "metallicTexture",
+                            texture,
+                            SamplerFallback::Black,
+                        );
+                    }
+                }
+                if roughness_factor != 1.0 {
+                    if let Some(texture) = make_constant_texture(roughness_factor) {
+                        set_texture(
+                            &mut result,
+                            "roughnessTexture",
+                            texture,
+                            SamplerFallback::White,
+                        );
+                    }
+                }
+            }
+        }
+
+        let emissive_strength = material.emissive_strength().unwrap_or(1.0);
+        let emissive = Vector3::from(material.emissive_factor()) * emissive_strength;
+        if emissive != Vector3::default() {
+            set_property(
+                &mut result,
+                "emissionStrength",
+                PropertyValue::Vector3(emissive),
+            );
+            if material.emissive_texture().is_none() {
+                if let Some(texture) = make_texture(1, 1, TexturePixelKind::RGBA8, vec![255; 4]) {
+                    set_texture(
+                        &mut result,
+                        "emissionTexture",
+                        texture,
+                        SamplerFallback::Black,
+                    );
+                }
+            }
+        }
+
+        ConvertedMaterial {
+            resource: MaterialResource::new_ok(ResourceKind::Embedded, result),
+            transparent: material.alpha_mode() == AlphaMode::Blend || transmission > 0.0,
+        }
+    }
+
+    /// Converts every material of the document, the result is indexed by material index.
+    pub async fn convert_all(mut self) -> Vec<ConvertedMaterial> {
+        let mut materials = Vec::new();
+        for material in self.document.materials() {
+            materials.push(self.convert_material(material).await);
+        }
+        materials
+    }
+}
@@ -0,0 +1,871 @@
+//! Contains all methods to load and convert glTF 2.0 model format.
+//!
+//! glTF is an open, well-specified format for transmission of 3D scenes. Both text (`.gltf`) and
+//! binary (`.glb`) flavours are supported, as well as external, embedded (data URI) and binary
+//! chunk buffers and images. The importer converts meshes (including skinned meshes and morph
+//! targets), skins, node and morph target weight animations, cameras and punctual lights
+//! (`KHR_lights_punctual`). PBR metallic-roughness materials are mapped to the standard shader,
+//! `KHR_materials_emissive_strength` and `KHR_materials_transmission` extensions are taken into
+//! account as well.
+//!
+//! # Limitations
+//!
+//! - Tangents of cubic spline animations are dropped and such animations are interpolated linearly.
+//! - Only the first texture coordinate set is used.
+//! - Light intensities are not converted from physical units, only the color is imported.
+//! - Transmission is approximated using transparency, because the engine does not have a
+//!   dedicated transmission model.
+//!
+//! Normally you should never use methods from this module directly, use resource manager to load
+//! models and create their instances.
+
+pub mod error;
+mod material;
+
+use crate::{
+    asset::manager::ResourceManager,
+    core::{
+        algebra::{Matrix4, Quaternion, UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        curve::{CurveKey, CurveKeyKind},
+        instant::Instant,
+        log::Log,
+        math::TriangleDefinition,
+        pool::Handle,
+    },
+    graph::SceneGraph,
+    resource::{
+        gltf::{
+            error::GltfError,
+            material::{ConvertedMaterial, MaterialConverter},
+        },
+        model::{rotations_to_euler, ModelImportOptions},
+    },
+    scene::{
+        animation::{Animation, AnimationContainer, AnimationPlayerBuilder, Track},
+        base::BaseBuilder,
+        camera::{CameraBuilder, OrthographicProjection, PerspectiveProjection, Projection},
+        graph::Graph,
+        light::{
+            directional::DirectionalLightBuilder, point::PointLightBuilder, spot::SpotLightBuilder,
+            BaseLightBuilder,
+        },
+        mesh::{
+            buffer::{TriangleBuffer, VertexBuffer},
+            surface::{
+                BlendShape, BlendShapesContainer, InputBlendShapeData, SurfaceBuilder, SurfaceData,
+                SurfaceSharedData,
+            },
+            vertex::{AnimatedVertex, StaticVertex},
+            Mesh, MeshBuilder, RenderPath,
+        },
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+        Scene,
+    },
+    utils,
+};
+use base64::Engine;
+use fxhash::FxHashMap;
+use fyrox_resource::io::ResourceIo;
+use gltf::{
+    animation::{util::ReadOutputs, Interpolation},
+    buffer::Source,
+    camera,
+    khr_lights_punctual::Kind,
+    mesh::Mode,
+    Document, Gltf,
+};
+use half::f16;
+use serde::Deserialize;
+use std::{f32::consts::PI, path::Path};
+
+/// Decodes `%XX` escape sequences of a relative URI.
+pub(crate) fn percent_decode(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(byte) = uri
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Decodes contents of a data URI (without `data:` prefix), only base64 encoding is supported.
+pub(crate) fn decode_data_uri(data_uri: &str) -> Result<Vec<u8>, GltfError> {
+    let (header, data) = data_uri
+        .split_once(',')
+        .ok_or_else(|| GltfError::InvalidDataUri("Missing data separator.".to_string()))?;
+    if !header.ends_with(";base64") {
+        return Err(GltfError::InvalidDataUri(format!(
+            "Unsupported encoding {header}."
+        )));
+    }
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| GltfError::InvalidDataUri(e.to_string()))
+}
+
+async fn load_buffers(
+    gltf: &Gltf,
+    io: &dyn ResourceIo,
+    model_path: &Path,
+) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut buffers = Vec::new();
+    for buffer in gltf.buffers() {
+        let data = match buffer.source() {
+            Source::Bin => gltf.blob.clone().ok_or(GltfError::MissingBlob)?,
+            Source::Uri(uri) => match uri.strip_prefix("data:") {
+                Some(data_uri) => decode_data_uri(data_uri)?,
+                None => {
+                    let model_dir = model_path.parent().unwrap_or_else(|| Path::new("."));
+                    io.load_file(&model_dir.join(percent_decode(uri))).await?
+                }
+            },
+        };
+        if data.len() < buffer.length() {
+            return Err(GltfError::BufferTooShort {
+                index: buffer.index(),
+                expected: buffer.length(),
+                actual: data.len(),
+            });
+        }
+        buffers.push(data);
+    }
+    Ok(buffers)
+}
+
+#[derive(Deserialize, Default)]
+struct MeshExtras {
+    #[serde(rename = "targetNames", default)]
+    target_names: Vec<String>,
+}
+
+/// Collects blend shapes of a mesh. glTF does not have a standard way of naming morph targets, but
+/// most exporters write the names to `targetNames` array of mesh extras.
+fn convert_blend_shapes(mesh: &gltf::Mesh) -> Vec<BlendShape> {
+    let target_count = mesh
+        .primitives()
+        .map(|primitive| primitive.morph_targets().len())
+        .max()
+        .unwrap_or_default();
+    let extras = mesh
+        .extras()
+        .as_ref()
+        .and_then(|extras| gltf::json::deserialize::from_str::<MeshExtras>(extras.get()).ok())
+        .unwrap_or_default();
+    let weights = mesh.weights().unwrap_or_default();
+    (0..target_count)
+        .map(|i| BlendShape {
+            // glTF weights are in [0; 1] range, while the engine uses [0; 100] range.
+            weight: weights.get(i).copied().unwrap_or_default() * 100.0,
+            name: extras
+                .target_names
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("Target{i}")),
+        })
+        .collect()
+}
+
+fn convert_primitive(
+    primitive: &gltf::Primitive,
+    buffers: &[Vec<u8>],
+    blend_shapes: &[BlendShape],
+) -> Result<Option<SurfaceData>, GltfError> {
+    let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|b| b.as_slice()));
+
+    let positions = reader
+        .read_positions()
+        .ok_or(GltfError::MissingPositions)?
+        .map(Vector3::from)
+        .collect::<Vec<_>>();
+    let vertex_count = positions.len();
+    let normals = reader
+        .read_normals()
+        .map(|normals| normals.map(Vector3::from).collect::<Vec<_>>());
+    let tangents = reader
+        .read_tangents()
+        .map(|tangents| tangents.map(Vector4::from).collect::<Vec<_>>());
+    let tex_coords = reader
+        .read_tex_coords(0)
+        .map(|tex_coords| tex_coords.into_f32().map(Vector2::from).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let joints = reader
+        .read_joints(0)
+        .map(|joints| joints.into_u16().collect::<Vec<_>>());
+    let weights = reader
+        .read_weights(0)
+        .map(|weights| weights.into_f32().collect::<Vec<_>>());
+
+    let indices = reader
+        .read_indices()
+        .map(|indices| indices.into_u32().collect::<Vec<_>>())
+        .unwrap_or_else(|| (0..vertex_count as u32).collect());
+    if indices.iter().any(|&i| i as usize >= vertex_count) {
+        return Err(GltfError::IndexOutOfBounds);
+    }
+
+    let triangles = match primitive.mode() {
+        Mode::Triangles => indices
+            .chunks_exact(3)
+            .map(|t| TriangleDefinition([t[0], t[1], t[2]]))
+            .collect::<Vec<_>>(),
+        Mode::TriangleStrip => indices
+            .windows(3)
+            .enumerate()
+            .map(|(i, t)| {
+                // Every odd triangle of a strip has reversed winding.
+                if i % 2 == 0 {
+                    TriangleDefinition([t[0], t[1], t[2]])
+                } else {
+                    TriangleDefinition([t[1], t[0], t[2]])
+                }
+            })
+            .collect(),
+        Mode::TriangleFan => indices
+            .get(1..)
+            .unwrap_or_default()
+            .windows(2)
+            .map(|t| TriangleDefinition([indices[0], t[0], t[1]]))
+            .collect(),
+        mode => {
+            Log::warn(format!(
+                "glTF: Primitive mode {mode:?} is not supported, the primitive is skipped."
+            ));
+            return Ok(None);
+        }
+    };
+
+    let tex_coord = |i: usize| tex_coords.get(i).copied().unwrap_or_default();
+    let normal = |i: usize| {
+        normals
+            .as_ref()
+            .and_then(|n| n.get(i).copied())
+            .unwrap_or_default()
+    };
+    let tangent = |i: usize| {
+        tangents
+            .as_ref()
+            .and_then(|t| t.get(i).copied())
+            .unwrap_or_default()
+    };
+
+    let vertex_buffer = if let (Some(joints), Some(weights)) = (joints, weights) {
+        let mut vertices = Vec::with_capacity(vertex_count);
+        for (i, &position) in positions.iter().enumerate() {
+            let mut bone_indices = [0u8; 4];
+            for (index, &joint) in bone_indices
+                .iter_mut()
+                .zip(joints.get(i).unwrap_or(&[0; 4]))
+            {
+                *index = u8::try_from(joint).map_err(|_| GltfError::TooManyJoints(joint))?;
+            }
+            vertices.push(AnimatedVertex {
+                position,
+                tex_coord: tex_coord(i),
+                normal: normal(i),
+                tangent: tangent(i),
+                bone_weights: weights.get(i).copied().unwrap_or_default(),
+                bone_indices,
+            });
+        }
+        VertexBuffer::new(vertex_count, vertices)
+    } else {
+        VertexBuffer::new(
+            vertex_count,
+            positions
+                .iter()
+                .enumerate()
+                .map(|(i, &position)| StaticVertex {
+                    position,
+                    tex_coord: tex_coord(i),
+                    normal: normal(i),
+                    tangent: tangent(i),
+                })
+                .collect(),
+        )
+    }
+    .unwrap();
+
+    let mut data = SurfaceData::new(vertex_buffer, TriangleBuffer::new(triangles), false);
+    if normals.is_none() {
+        data.calculate_normals().unwrap();
+    }
+    if tangents.is_none() {
+        data.calculate_tangents().unwrap();
+    }
+
+    // Morph targets store offsets only, zero offsets are skipped to keep the maps sparse.
+    fn collect_offsets(
+        offsets: Option<impl Iterator<Item = [f32; 3]>>,
+    ) -> FxHashMap<u32, Vector3<f16>> {
+        offsets
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter(|(_, offset)| offset.iter().any(|c| *c != 0.0))
+            .map(|(i, offset)| (i as u32, utils::vec3_f16_from_f32(Vector3::from(offset))))
+            .collect()
+    }
+
+    let input_blend_shapes = reader
+        .read_morph_targets()
+        .enumerate()
+        .map(|(i, (positions, normals, tangents))| {
+            let blend_shape = blend_shapes.get(i).cloned().unwrap_or_default();
+            InputBlendShapeData {
+                default_weight: blend_shape.weight,
+                name: blend_shape.name,
+                positions: collect_offsets(positions),
+                normals: collect_offsets(normals),
+                tangents: collect_offsets(tangents),
+            }
+        })
+        .collect::<Vec<_>>();
+    if !input_blend_shapes.is_empty() {
+        data.blend_shapes_container = Some(BlendShapesContainer::from_lists(
+            &data.vertex_buffer,
+            &input_blend_shapes,
+        ));
+    }
+
+    Ok(Some(data))
+}
+
+struct SceneConverter<'a> {
+    document: &'a Document,
+    buffers: &'a [Vec<u8>],
+    materials: Vec<ConvertedMaterial>,
+    default_material: ConvertedMaterial,
+    surfaces: FxHashMap<(usize, usize), Option<SurfaceSharedData>>,
+    node_map: FxHashMap<usize, Handle<Node>>,
+}
+
+impl<'a> SceneConverter<'a> {
+    fn convert_mesh(
+        &mut self,
+        base: BaseBuilder,
+        mesh: gltf::Mesh,
+        graph: &mut Graph,
+    ) -> Result<Handle<Node>, GltfError> {
+        let mut surfaces = Vec::new();
+        let mut render_path = RenderPath::Deferred;
+        let blend_shapes = convert_blend_shapes(&mesh);
+        for (i, primitive) in mesh.primitives().enumerate() {
+            let data = match self.surfaces.get(&(mesh.index(), i)) {
+                Some(data) => data.clone(),
+                None => {
+                    let data = convert_primitive(&primitive, self.buffers, &blend_shapes)?
+                        .map(SurfaceSharedData::new);
+                    self.surfaces.insert((mesh.index(), i), data.clone());
+                    data
+                }
+            };
+
+            if let Some(data) = data {
+                let material = primitive
+                    .material()
+                    .index()
+                    .and_then(|index| self.materials.get(index))
+                    .unwrap_or(&self.default_material);
+                if material.transparent {
+                    render_path = RenderPath::Forward;
+                }
+                surfaces.push(
+                    SurfaceBuilder::new(data)
+                        .with_material(material.resource.clone())
+                        .build(),
+                );
+            }
+        }
+
+        Ok(MeshBuilder::new(base)
+            .with_surfaces(surfaces)
+            .with_blend_shapes(blend_shapes)
+            .with_render_path(render_path)
+            .build(graph))
+    }
+
+    fn convert_node(
+        &mut self,
+        node: &gltf::Node,
+        graph: &mut Graph,
+    ) -> Result<Handle<Node>, GltfError> {
+        let (translation, rotation, scale) = node.transform().decomposed();
+        let name = node
+            .name()
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| format!("Node{}", node.index()));
+        let mut transform = TransformBuilder::new()
+            .with_local_position(Vector3::from(translation))
+            .with_local_rotation(UnitQuaternion::from_quaternion(Quaternion::from(rotation)))
+            .with_local_scale(Vector3::from(scale));
+
+        if let Some(mesh) = node.mesh() {
+            let base = BaseBuilder::new()
+                .with_name(name)
+                .with_local_transform(transform.build());
+            return self.convert_mesh(base, mesh, graph);
+        }
+
+        if let Some(camera) = node.camera() {
+            // glTF cameras look along -Z, while the engine cameras look along +Z.
+            transform = transform
+                .with_post_rotation(UnitQuaternion::from_axis_angle(&Vector3::y_axis(), PI));
+            let base = BaseBuilder::new()
+                .with_name(name)
+                .with_local_transform(transform.build());
+            let projection = match camera.projection() {
+                camera::Projection::Perspective(perspective) => {
+                    Projection::Perspective(PerspectiveProjection {
+                        fov: perspective.yfov(),
+                        z_near: perspective.znear(),
+                        z_far: perspective.zfar().unwrap_or(2048.0),
+                    })
+                }
+                camera::Projection::Orthographic(orthographic) => {
+                    Projection::Orthographic(OrthographicProjection {
+                        z_near: orthographic.znear(),
+                        z_far: orthographic.zfar(),
+                        vertical_size: orthographic.ymag(),
+                    })
+                }
+            };
+            return Ok(CameraBuilder::new(base)
+                .with_projection(projection)
+                .build(graph));
+        }
+
+        if let Some(light) = node.light() {
+            // glTF lights shine along -Z, while the engine lights shine along -Y.
+            transform = transform.with_post_rotation(UnitQuaternion::from_axis_angle(
+                &Vector3::x_axis(),
+                -PI / 2.0,
+            ));
+            let base = BaseBuilder::new()
+                .with_name(name)
+                .with_local_transform(transform.build());
+            let base_light = BaseLightBuilder::new(base)
+                .with_color(Color::from(Vector3::from(light.color())).linear_to_srgb());
+            let range = light.range().unwrap_or(10.0);
+            return Ok(match light.kind() {
+                Kind::Directional => DirectionalLightBuilder::new(base_light).build(graph),
+                Kind::Point => PointLightBuilder::new(base_light)
+                    .with_radius(range)
+                    .build(graph),
+                Kind::Spot {
+                    inner_cone_angle,
+                    outer_cone_angle,
+                } => SpotLightBuilder::new(base_light)
+                    .with_distance(range)
+                    // glTF angles are measured from the center of the cone.
+                    .with_hotspot_cone_angle(2.0 * inner_cone_angle)
+                    .with_falloff_angle_delta(2.0 * (outer_cone_angle - inner_cone_angle))
+                    .build(graph),
+            });
+        }
+
+        let base = BaseBuilder::new()
+            .with_name(name)
+            .with_local_transform(transform.build());
+        Ok(PivotBuilder::new(base).build(graph))
+    }
+
+    fn convert_skins(&self, graph: &mut Graph) {
+        for node in self.document.nodes() {
+            let (Some(skin), Some(&handle)) = (node.skin(), self.node_map.get(&node.index()))
+            else {
+                continue;
+            };
+
+            let Some(joints) = skin
+                .joints()
+                .map(|joint| self.node_map.get(&joint.index()).copied())
+                .collect::<Option<Vec<_>>>()
+            else {
+                Log::warn(format!(
+                    "glTF: Skin {} references nodes outside of the scene, it is ignored.",
+                    skin.index()
+                ));
+                continue;
+            };
+
+            let reader =
+                skin.reader(|buffer| self.buffers.get(buffer.index()).map(|b| b.as_slice()));
+            if let Some(inverse_bind_matrices) = reader.read_inverse_bind_matrices() {
+                for (&joint, matrix) in joints.iter().zip(inverse_bind_matrices) {
+                    graph[joint].inv_bind_pose_transform = Matrix4::from(matrix);
+                }
+            }
+
+            if let Some(mesh) = graph[handle].cast_mut::<Mesh>() {
+                for surface in mesh.surfaces_mut() {
+                    surface.bones.set_value_silent(joints.clone());
+                }
+            }
+        }
+    }
+
+    fn convert_animation(&self, animation: gltf::Animation) -> Animation {
+        let mut result = Animation::default();
+        result.set_name(
+            animation
+                .name()
+                .map(ToOwned::to_owned)
+                .unwrap_or_else(|| format!("Animation{}", animation.index())),
+        );
+
+        for channel in animation.channels() {
+            let Some(&target) = self.node_map.get(&channel.target().node().index()) else {
+                continue;
+            };
+
+            let reader =
+                channel.reader(|buffer| self.buffers.get(buffer.index()).map(|b| b.as_slice()));
+            let (Some(inputs), Some(outputs)) = (reader.read_inputs(), reader.read_outputs())
+            else {
+                continue;
+            };
+
+            let interpolation = channel.sampler().interpolation();
+            // Cubic spline samplers store (in-tangent, value, out-tangent) triplets, tangents are
+            // dropped and the values are interpolated linearly.
+            let pick_values = |values: Vec<Vector3<f32>>| -> Vec<Vector3<f32>> {
+                if interpolation == Interpolation::CubicSpline {
+                    values.chunks_exact(3).map(|triplet| triplet[1]).collect()
+                } else {
+                    values
+                }
+            };
+
+            let (mut track, values) = match outputs {
+                ReadOutputs::Translations(translations) => (
+                    Track::new_position(),
+                    pick_values(translations.map(Vector3::from).collect()),
+                ),
+                ReadOutputs::Scales(scales) => (
+                    Track::new_scale(),
+                    pick_values(scales.map(Vector3::from).collect()),
+                ),
+                ReadOutputs::Rotations(rotations) => {
+                    let mut rotations = rotations
+                        .into_f32()
+                        .map(|r| UnitQuaternion::from_quaternion(Quaternion::from(r)))
+                        .collect::<Vec<_>>();
+                    if interpolation == Interpolation::CubicSpline {
+                        rotations = rotations.chunks_exact(3).map(|t| t[1]).collect();
+                    }
+                    (Track::new_rotation(), rotations_to_euler(&rotations))
+                }
+                ReadOutputs::MorphTargetWeights(weights) => {
+                    let target_count = channel
+                        .target()
+                        .node()
+                        .mesh()
+                        .and_then(|mesh| mesh.primitives().next())
+                        .map(|primitive| primitive.morph_targets().len())
+                        .unwrap_or_default();
+                    let times = inputs.collect::<Vec<_>>();
+                    let mut weights = weights.into_f32().collect::<Vec<_>>();
+                    if interpolation == Interpolation::CubicSpline {
+                        // Every key is stored as (in-tangents, values, out-tangents), where each
+                        // part has a weight per morph target.
+                        weights = weights
+                            .chunks_exact(3 * target_count.max(1))
+                            .flat_map(|key| key[target_count..2 * target_count].to_vec())
+                            .collect();
+                    }
+                    let kind = match interpolation {
+                        Interpolation::Step => CurveKeyKind::Constant,
+                        Interpolation::Linear | Interpolation::CubicSpline => CurveKeyKind::Linear,
+                    };
+                    for target_index in 0..target_count {
+                        let mut track = Mesh::make_blend_shape_weight_track(target_index);
+                        track.set_target(target);
+                        let curve = &mut track.data_container_mut().curves_mut()[0];
+                        for (time, key_weights) in
+                            times.iter().zip(weights.chunks_exact(target_count))
+                        {
+                            curve.add_key(CurveKey::new(
+                                *time,
+                                key_weights[target_index] * 100.0,
+                                kind.clone(),
+                            ));
+                        }
+                        result.add_track(track);
+                    }
+                    continue;
+                }
+            };
+
+            let kind = match interpolation {
+                Interpolation::Step => CurveKeyKind::Constant,
+                Interpolation::Linear | Interpolation::CubicSpline => CurveKeyKind::Linear,
+            };
+            track.set_target(target);
+            let curves = track.data_container_mut().curves_mut();
+            for (time, value) in inputs.zip(values) {
+                for (curve, component) in curves.iter_mut().zip(value.iter()) {
+                    curve.add_key(CurveKey::new(time, *component, kind.clone()));
+                }
+            }
+            result.add_track(track);
+        }
+
+        result.fit_length_to_content();
+        result
+    }
+}
+
+fn convert(
+    document: &Document,
+    buffers: &[Vec<u8>],
+    materials: Vec<ConvertedMaterial>,
+    scene: &mut Scene,
+) -> Result<(), GltfError> {
+    let Some(gltf_scene) = document
+        .default_scene()
+        .or_else(|| document.scenes().next())
+    else {
+        return Ok(());
+    };
+
+    let mut converter = SceneConverter {
+        document,
+        buffers,
+        materials,
+        default_material: ConvertedMaterial::default_material(),
+        surfaces: Default::default(),
+        node_map: Default::default(),
+    };
+
+    let root = scene.graph.get_root();
+    let mut stack = gltf_scene
+        .nodes()
+        .map(|node| (node, root))
+        .collect::<Vec<_>>();
+    // Reverse the stack to keep the order of nodes as in the document.
+    stack.reverse();
+    while let Some((node, parent)) = stack.pop() {
+        if converter.node_map.contains_key(&node.index()) {
+            continue;
+        }
+        let handle = converter.convert_node(&node, &mut scene.graph)?;
+        scene.graph.link_nodes(handle, parent);
+        converter.node_map.insert(node.index(), handle);
+        let children = node.children().collect::<Vec<_>>();
+        stack.extend(children.into_iter().rev().map(|child| (child, handle)));
+    }
+
+    converter.convert_skins(&mut scene.graph);
+
+    let mut animations_container = AnimationContainer::new();
+    for animation in document.animations() {
+        let animation = converter.convert_animation(animation);
+        if !animation.tracks().is_empty() {
+            animations_container.add(animation);
+        }
+    }
+    // Do not create animation player if there's no animation content.
+    if animations_container.iter().next().is_some() {
+        AnimationPlayerBuilder::new(BaseBuilder::new().with_name("AnimationPlayer"))
+            .with_animations(animations_container)
+            .build(&mut scene.graph);
+    }
+
+    scene.graph.update_hierarchical_data();
+
+    Ok(())
+}
+
+/// Tries to load and convert glTF from given path.
+///
+/// Normally you should never use this method, use resource manager to load models.
+pub async fn load_to_scene<P: AsRef<Path>>(
+    scene: &mut Scene,
+    resource_manager: ResourceManager,
+    io: &dyn ResourceIo,
+    path: P,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let path = path.as_ref();
+    let start_time = Instant::now();
+
+    Log::info(format!("Trying to load {path:?}"));
+
+    let data = io.load_file(path).await?;
+    let gltf = Gltf::from_slice(&data)?;
+    let buffers = load_buffers(&gltf, io, path).await?;
+
+    let materials = MaterialConverter::new(
+        &gltf.document,
+        &buffers,
+        &resource_manager,
+        io,
+        path,
+        model_import_options,
+    )
+    .convert_all()
+    .await;
+
+    convert(&gltf.document, &buffers, materials, scene)?;
+
+    Log::info(format!(
+        "glTF {path:?} loaded in {} ms",
+        start_time.elapsed().as_millis()
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        graph::SceneGraph,
+        resource::gltf::{convert, decode_data_uri, percent_decode},
+        scene::{mesh::Mesh, Scene},
+    };
+    use base64::Engine;
+    use gltf::Gltf;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("foo%20bar.png"), "foo bar.png");
+        assert_eq!(percent_decode("100%.png"), "100%.png");
+        assert_eq!(percent_decode("plain.bin"), "plain.bin");
+    }
+
+    #[test]
+    fn test_decode_data_uri() {
+        assert_eq!(
+            decode_data_uri("application/octet-stream;base64,AQID").unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(decode_data_uri("text/plain,123").is_err());
+        assert!(decode_data_uri("application/octet-stream;base64").is_err());
+    }
+
+    #[test]
+    fn test_convert_triangle() {
+        let mut data = Vec::new();
+        for v in [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]] {
+            for c in v {
+                data.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        for i in [0u16, 1, 2, 0] {
+            data.extend_from_slice(&i.to_le_bytes());
+        }
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+
+        let json = format!(
+            r#"{{
+                "asset": {{ "version": "2.0" }},
+                "scene": 0,
+                "scenes": [{{ "nodes": [0] }}],
+                "nodes": [{{ "name": "Triangle", "mesh": 0, "translation": [1.0, 2.0, 3.0] }}],
+                "meshes": [{{ "primitives": [{{ "attributes": {{ "POSITION": 0 }}, "indices": 1 }}] }}],
+                "buffers": [{{ "byteLength": 44, "uri": "data:application/octet-stream;base64,{encoded}" }}],
+                "bufferViews": [
+                    {{ "buffer": 0, "byteOffset": 0, "byteLength": 36 }},
+                    {{ "buffer": 0, "byteOffset": 36, "byteLength": 6 }}
+                ],
+                "accessors": [
+                    {{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                       "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0] }},
+                    {{ "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }}
+                ]
+            }}"#
+        );
+
+        let gltf = Gltf::from_slice(json.as_bytes()).unwrap();
+        let buffers = vec![data];
+        let mut scene = Scene::new();
+        convert(&gltf.document, &buffers, Vec::new(), &mut scene).unwrap();
+
+        let (handle, node) = scene.graph.find_by_name_from_root("Triangle").unwrap();
+        assert_eq!(
+            **node.local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        let mesh = scene.graph[handle].cast::<Mesh>().unwrap();
+        assert_eq!(mesh.surfaces().len(), 1);
+        let data = mesh.surfaces()[0].data();
+        let data = data.lock();
+        assert_eq!(data.vertex_buffer.vertex_count(), 3);
+        assert_eq!(data.geometry_buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_convert_morph_targets() {
+        let mut data = Vec::new();
+        for v in [
+            // Base shape.
+            [0.0f32, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            // Offsets of the morph target.
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, 0.0],
+        ] {
+            for c in v {
+                data.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+
+        let json = format!(
+            r#"{{
+                "asset": {{ "version": "2.0" }},
+                "scene": 0,
+                "scenes": [{{ "nodes": [0] }}],
+                "nodes": [{{ "name": "Face", "mesh": 0 }}],
+                "meshes": [{{
+                    "primitives": [{{ "attributes": {{ "POSITION": 0 }}, "targets": [{{ "POSITION": 1 }}] }}],
+                    "weights": [0.5],
+                    "extras": {{ "targetNames": ["Smile"] }}
+                }}],
+                "buffers": [{{ "byteLength": 72, "uri": "data:application/octet-stream;base64,{encoded}" }}],
+                "bufferViews": [
+                    {{ "buffer": 0, "byteOffset": 0, "byteLength": 36 }},
+                    {{ "buffer": 0, "byteOffset": 36, "byteLength": 36 }}
+                ],
+                "accessors": [
+                    {{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                       "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0] }},
+                    {{ "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3",
+                       "min": [0.0, 0.0, 0.0], "max": [0.0, 0.0, 1.0] }}
+                ]
+            }}"#
+        );
+
+        let gltf = Gltf::from_slice(json.as_bytes()).unwrap();
+        let buffers = vec![data];
+        let mut scene = Scene::new();
+        convert(&gltf.document, &buffers, Vec::new(), &mut scene).unwrap();
+
+        let (handle, _) = scene.graph.find_by_name_from_root("Face").unwrap();
+        let mesh = scene.graph[handle].cast::<Mesh>().unwrap();
+        assert_eq!(mesh.blend_shapes().len(), 1);
+        assert_eq!(mesh.blend_shapes()[0].name, "Smile");
+        assert_eq!(mesh.blend_shapes()[0].weight, 50.0);
+        let data = mesh.surfaces()[0].data();
+        let data = data.lock();
+        assert!(data.blend_shapes_container.is_some());
+    }
+}
@@ -2,7 +2,13 @@
 
 #![warn(missing_docs)]
 
+pub mod audio_event;
+pub mod convex_decomposition;
 pub mod curve;
 pub mod fbx;
+pub mod gltf;
 pub mod model;
 pub mod texture;
+pub mod visual_script;
+#[cfg(feature = "wasm_scripting")]
+pub mod wasm_module;
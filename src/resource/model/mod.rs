@@ -25,7 +25,9 @@ use crate::{
     },
     core::{
         algebra::{UnitQuaternion, Vector3},
+        curve::{CurveKey, CurveKeyKind},
         log::{Log, MessageKind},
+        math::{quat_from_euler, RotationOrder},
         pool::Handle,
         reflect::prelude::*,
         uuid::Uuid,
@@ -34,16 +36,26 @@ use crate::{
         TypeUuidProvider,
     },
     engine::SerializationContext,
+    generic_animation::{value::ValueBinding, AnimationCompressionSettings},
     graph::{NodeHandleMap, PrefabData, SceneGraph},
-    resource::fbx::{self, error::FbxError},
+    resource::{
+        fbx::{self, error::FbxError},
+        gltf::{self, error::GltfError},
+    },
     scene::{
         animation::{Animation, AnimationPlayer},
         base::SceneNodeId,
         graph::Graph,
+        mesh::{
+            lod::{MeshLod, MeshLodMode},
+            surface::{SurfaceBuilder, SurfaceSharedData},
+            Mesh,
+        },
         node::Node,
         transform::Transform,
         Scene, SceneLoader,
     },
+    utils::simplify::simplify,
 };
 use fxhash::FxHashMap;
 use fyrox_graph::NodeMapping;
@@ -523,20 +535,421 @@ impl MaterialSearchOptions {
 ///
 /// ```text
 /// (
-///     material_search_options: RecursiveUp
+///     material_search_options: RecursiveUp,
+///     scale: 0.01,
+///     up_axis: Z,
+///     animation_compression: Low,
+///     animation_clips: [
+///         (name: "Idle", start: 0.0, end: 1.5),
+///         (name: "Walk", start: 1.5, end: 2.5, compression: High),
+///     ],
+///     lod_mode: Distance,
+///     lods: [
+///         (threshold: 10.0, triangle_ratio: 0.5),
+///         (threshold: 30.0, triangle_ratio: 0.2),
+///     ],
 /// )
 /// ```
 ///
 /// Check documentation of the field of the structure for more info about each parameter.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Reflect, Eq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Reflect)]
 pub struct ModelImportOptions {
     /// See [`MaterialSearchOptions`] docs for more info.
     #[serde(default)]
     pub material_search_options: MaterialSearchOptions,
+    /// Uniform scale that will be applied to the imported model. It is useful to convert models
+    /// that use different units of length, for example models made in centimeters should use
+    /// `0.01` scale.
+    #[serde(default = "default_scale")]
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub scale: f32,
+    /// See [`ModelUpAxis`] docs for more info.
+    #[serde(default)]
+    pub up_axis: ModelUpAxis,
+    /// Compression of every animation of the model. Clips (see below) are cut from the compressed
+    /// animations. See [`AnimationCompression`] docs for more info.
+    #[serde(default)]
+    pub animation_compression: AnimationCompression,
+    /// A list of clips that will be cut out of the animation of the model. It is useful for models,
+    /// that store all animations one after another in a single long animation. Source animation
+    /// is kept as is, clips are added as separate animations. See [`AnimationClipImportOptions`]
+    /// docs for more info.
+    #[serde(default)]
+    pub animation_clips: Vec<AnimationClipImportOptions>,
+    /// Defines how thresholds of generated levels of detail are interpreted. See [`MeshLodMode`]
+    /// docs for more info.
+    #[serde(default)]
+    pub lod_mode: MeshLodMode,
+    /// A list of levels of detail, that will be generated for every mesh of the model. Levels must
+    /// be sorted in the order of decreasing detail. See [`LodImportOptions`] docs for more info.
+    #[serde(default)]
+    pub lods: Vec<LodImportOptions>,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl Default for ModelImportOptions {
+    fn default() -> Self {
+        Self {
+            material_search_options: Default::default(),
+            scale: default_scale(),
+            up_axis: Default::default(),
+            animation_compression: Default::default(),
+            animation_clips: Default::default(),
+            lod_mode: Default::default(),
+            lods: Default::default(),
+        }
+    }
 }
 
 impl ImportOptions for ModelImportOptions {}
 
+/// Defines which axis of a source model points up. The engine uses Y axis as up axis, models with
+/// other up axis will be rotated on import.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Deserialize,
+    Serialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum ModelUpAxis {
+    /// Y axis points up, no conversion is needed. This is **default** option.
+    #[default]
+    Y,
+    /// Z axis points up, it is common for models made in CAD software.
+    Z,
+}
+
+uuid_provider!(ModelUpAxis = "cee73fff-2059-4136-a58b-e21dc19ae2f8");
+
+impl ModelUpAxis {
+    /// Returns a rotation that converts the axis to the engine's up axis.
+    pub fn rotation(self) -> UnitQuaternion<f32> {
+        match self {
+            ModelUpAxis::Y => UnitQuaternion::identity(),
+            ModelUpAxis::Z => {
+                UnitQuaternion::from_axis_angle(&Vector3::x_axis(), -std::f32::consts::FRAC_PI_2)
+            }
+        }
+    }
+}
+
+/// A part of a source animation, that will be imported as a separate animation. Every animation
+/// player of the model gets a new animation for every clip; the clip is cut from the first animation
+/// of the player.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Reflect)]
+pub struct AnimationClipImportOptions {
+    /// Name of the new animation.
+    pub name: String,
+    /// Start time of the clip (in seconds).
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub start: f32,
+    /// End time of the clip (in seconds).
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub end: f32,
+    /// Additional compression of the clip. See [`AnimationCompression`] docs for more info.
+    #[serde(default)]
+    pub compression: AnimationCompression,
+}
+
+uuid_provider!(AnimationClipImportOptions = "62d188f8-4571-4d15-aff7-d7f6c16596b9");
+
+/// Quality of lossy compression of imported animations. Compression removes redundant keys (keys that
+/// can be restored by interpolation between their neighbours with a small error) and quantizes
+/// rotations. It greatly reduces memory usage of long baked animations, that usually have a key on
+/// every frame. See [`crate::generic_animation::Animation::compress`] for more info.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Deserialize,
+    Serialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum AnimationCompression {
+    /// Animations are kept as is. This is **default** option.
+    #[default]
+    None,
+    /// Only keys that are (almost) exactly restorable are removed. Visually lossless.
+    Low,
+    /// Good balance between quality and size. Rotations are quantized with 16-bit precision.
+    Medium,
+    /// The smallest size, with noticeable errors on fine motions. Rotations are quantized with
+    /// 12-bit precision.
+    High,
+}
+
+uuid_provider!(AnimationCompression = "8d3c6f7e-5b2a-4c19-9e0d-1f6a7b8c9d0e");
+
+impl AnimationCompression {
+    /// Returns compression settings of the quality level, or [`None`] if the compression is disabled.
+    pub fn settings(self) -> Option<AnimationCompressionSettings> {
+        let full_turn = std::f32::consts::TAU;
+        match self {
+            AnimationCompression::None => None,
+            AnimationCompression::Low => Some(AnimationCompressionSettings {
+                tolerance: 0.0001,
+                rotation_tolerance: 0.0005,
+                rotation_quantization_step: 0.0,
+            }),
+            AnimationCompression::Medium => Some(AnimationCompressionSettings {
+                tolerance: 0.001,
+                rotation_tolerance: 0.002,
+                rotation_quantization_step: full_turn / 65536.0,
+            }),
+            AnimationCompression::High => Some(AnimationCompressionSettings {
+                tolerance: 0.005,
+                rotation_tolerance: 0.01,
+                rotation_quantization_step: full_turn / 4096.0,
+            }),
+        }
+    }
+}
+
+/// A level of detail, that will be generated for every mesh of a model by simplifying its surfaces.
+/// See [`crate::utils::simplify`] docs for more info about simplification.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Reflect)]
+pub struct LodImportOptions {
+    /// Threshold of the level of detail. Its meaning depends on [`MeshLodMode`].
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub threshold: f32,
+    /// Amount of triangles of the level of detail relative to the source surfaces, in `[0; 1]` range.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub triangle_ratio: f32,
+}
+
+uuid_provider!(LodImportOptions = "1f5e6a2b-7c3d-4e8f-9a0b-2c4d6e8f0a1b");
+
+/// Converts rotations to Euler angles (XYZ order), which are used by rotation tracks of animations.
+/// Angles are unwrapped to keep consecutive values as close as possible, otherwise interpolation
+/// between them might take the "long way" around the circle.
+pub(crate) fn rotations_to_euler(rotations: &[UnitQuaternion<f32>]) -> Vec<Vector3<f32>> {
+    let mut angles = rotations
+        .iter()
+        .map(|rotation| {
+            let (x, y, z) = rotation.euler_angles();
+            Vector3::new(x, y, z)
+        })
+        .collect::<Vec<_>>();
+    for i in 1..angles.len() {
+        let previous = angles[i - 1];
+        for (angle, previous) in angles[i].iter_mut().zip(previous.iter()) {
+            while *angle - previous > std::f32::consts::PI {
+                *angle -= 2.0 * std::f32::consts::PI;
+            }
+            while *angle - previous < -std::f32::consts::PI {
+                *angle += 2.0 * std::f32::consts::PI;
+            }
+        }
+    }
+    angles
+}
+
+/// Applies scale and up axis conversion to the direct children of the scene root (and to their
+/// animations), so the whole hierarchy of an imported model is transformed.
+fn apply_import_transform(scene: &mut Scene, options: &ModelImportOptions) {
+    let rotation = options.up_axis.rotation();
+    let scale = options.scale;
+    if scale == 1.0 && options.up_axis == ModelUpAxis::Y {
+        return;
+    }
+
+    let root = scene.graph.get_root();
+    let children = scene.graph[root].children().to_vec();
+    for &child in children.iter() {
+        let transform = scene.graph[child].local_transform_mut();
+        let new_position = rotation.transform_vector(&(**transform.position() * scale));
+        let new_rotation = rotation * **transform.rotation();
+        let new_scale = **transform.scale() * scale;
+        transform
+            .set_position(new_position)
+            .set_rotation(new_rotation)
+            .set_scale(new_scale);
+    }
+
+    for node in scene.graph.linear_iter_mut() {
+        let Some(animation_player) = node.cast_mut::<AnimationPlayer>() else {
+            continue;
+        };
+        for animation in animation_player
+            .animations_mut()
+            .get_value_mut_silent()
+            .iter_mut()
+        {
+            for track in animation.tracks_mut() {
+                if !children.contains(&track.target()) {
+                    continue;
+                }
+                let binding = track.binding().clone();
+                let curves = track.data_container_mut().curves_mut();
+                if curves.len() != 3 {
+                    continue;
+                }
+
+                // Keys of the curves may be located at different times, so sample the curves at
+                // every key location.
+                let mut locations = Vec::<(f32, CurveKeyKind)>::new();
+                for curve in curves.iter() {
+                    for key in curve.keys() {
+                        if !locations.iter().any(|(l, _)| *l == key.location()) {
+                            locations.push((key.location(), key.kind.clone()));
+                        }
+                    }
+                }
+                locations.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let values = locations
+                    .iter()
+                    .map(|(location, _)| {
+                        Vector3::new(
+                            curves[0].value_at(*location),
+                            curves[1].value_at(*location),
+                            curves[2].value_at(*location),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                let values = match binding {
+                    ValueBinding::Position => values
+                        .iter()
+                        .map(|v| rotation.transform_vector(&(v * scale)))
+                        .collect(),
+                    ValueBinding::Scale => values.iter().map(|v| v * scale).collect(),
+                    ValueBinding::Rotation => rotations_to_euler(
+                        &values
+                            .iter()
+                            .map(|v| rotation * quat_from_euler(*v, RotationOrder::XYZ))
+                            .collect::<Vec<_>>(),
+                    ),
+                    ValueBinding::Property { .. } => continue,
+                };
+
+                for (i, curve) in curves.iter_mut().enumerate() {
+                    curve.clear();
+                    for ((location, kind), value) in locations.iter().zip(values.iter()) {
+                        curve.add_key(CurveKey::new(*location, value[i], kind.clone()));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Compresses every animation of every animation player of the scene. See [`AnimationCompression`]
+/// for more info.
+fn compress_animations(scene: &mut Scene, compression: AnimationCompression) {
+    let Some(settings) = compression.settings() else {
+        return;
+    };
+
+    for node in scene.graph.linear_iter_mut() {
+        if let Some(animation_player) = node.cast_mut::<AnimationPlayer>() {
+            for animation in animation_player
+                .animations_mut()
+                .get_value_mut_silent()
+                .iter_mut()
+            {
+                animation.compress(&settings);
+            }
+        }
+    }
+}
+
+/// Adds animation clips to every animation player of the scene. See [`AnimationClipImportOptions`]
+/// for more info.
+fn add_animation_clips(scene: &mut Scene, clips: &[AnimationClipImportOptions]) {
+    if clips.is_empty() {
+        return;
+    }
+
+    for node in scene.graph.linear_iter_mut() {
+        let Some(animation_player) = node.cast_mut::<AnimationPlayer>() else {
+            continue;
+        };
+        let animations = animation_player.animations_mut().get_value_mut_silent();
+        let Some(source) = animations.iter().next().cloned() else {
+            continue;
+        };
+        for clip in clips {
+            if clip.end <= clip.start {
+                Log::warn(format!(
+                    "Animation clip {} has invalid time range {}..{}, it is ignored.",
+                    clip.name, clip.start, clip.end
+                ));
+                continue;
+            }
+            let mut animation = source.clone();
+            animation.set_name(&clip.name);
+            animation.set_time_slice(clip.start..clip.end);
+            if let Some(settings) = clip.compression.settings() {
+                animation.compress(&settings);
+            }
+            animations.add(animation);
+        }
+    }
+}
+
+/// Generates levels of detail for every mesh of the scene. See [`LodImportOptions`] for more info.
+fn generate_lods(scene: &mut Scene, mode: MeshLodMode, lods: &[LodImportOptions]) {
+    if lods.is_empty() {
+        return;
+    }
+
+    // Surface data could be shared across multiple meshes, simplify it only once.
+    let mut cache = FxHashMap::<(u64, usize), SurfaceSharedData>::default();
+
+    for node in scene.graph.linear_iter_mut() {
+        let Some(mesh) = node.cast_mut::<Mesh>() else {
+            continue;
+        };
+        let mesh_lods = lods
+            .iter()
+            .enumerate()
+            .map(|(level, options)| MeshLod {
+                threshold: options.threshold,
+                surfaces: mesh
+                    .surfaces()
+                    .iter()
+                    .map(|surface| {
+                        let source = surface.data();
+                        let data = cache
+                            .entry((source.key(), level))
+                            .or_insert_with(|| {
+                                SurfaceSharedData::new(simplify(
+                                    &source.lock(),
+                                    options.triangle_ratio,
+                                ))
+                            })
+                            .clone();
+                        SurfaceBuilder::new(data)
+                            .with_material(surface.material().clone())
+                            .with_bones(surface.bones().to_vec())
+                            .with_unique_material(surface.is_unique_material())
+                            .build()
+                    })
+                    .collect(),
+            })
+            .collect();
+        mesh.set_lod_mode(mode);
+        mesh.set_lods(mesh_lods);
+    }
+}
+
 /// All possible errors that may occur while trying to load model from some
 /// data source.
 #[derive(Debug)]
@@ -547,6 +960,8 @@ pub enum ModelLoadError {
     NotSupported(String),
     /// An error occurred while loading FBX file.
     Fbx(FbxError),
+    /// An error occurred while loading glTF file.
+    Gltf(GltfError),
 }
 
 impl Display for ModelLoadError {
@@ -559,6 +974,7 @@ impl Display for ModelLoadError {
                 write!(f, "Model format is not supported: {v}")
             }
             ModelLoadError::Fbx(v) => v.fmt(f),
+            ModelLoadError::Gltf(v) => v.fmt(f),
         }
     }
 }
@@ -569,6 +985,12 @@ impl From<FbxError> for ModelLoadError {
     }
 }
 
+impl From<GltfError> for ModelLoadError {
+    fn from(gltf: GltfError) -> Self {
+        ModelLoadError::Gltf(gltf)
+    }
+}
+
 impl From<VisitError> for ModelLoadError {
     fn from(e: VisitError) -> Self {
         ModelLoadError::Visit(e)
@@ -590,7 +1012,7 @@ impl Model {
             .to_string_lossy()
             .as_ref()
             .to_lowercase();
-        let (scene, mapping) = match extension.as_ref() {
+        let (mut scene, mapping) = match extension.as_ref() {
             "fbx" => {
                 let mut scene = Scene::new();
                 if let Some(filename) = path.as_ref().file_name() {
@@ -609,6 +1031,24 @@ impl Model {
                 // any persistent unique ids, and we have to use names.
                 (scene, NodeMapping::UseNames)
             }
+            "gltf" | "glb" => {
+                let mut scene = Scene::new();
+                if let Some(filename) = path.as_ref().file_name() {
+                    let root = scene.graph.get_root();
+                    scene.graph[root].set_name(filename.to_string_lossy());
+                }
+                gltf::load_to_scene(
+                    &mut scene,
+                    resource_manager,
+                    io,
+                    path.as_ref(),
+                    &model_import_options,
+                )
+                .await?;
+                // glTF nodes are identified by their indices, which are not persistent across
+                // re-exports, so names are used for mapping.
+                (scene, NodeMapping::UseNames)
+            }
             // Scene can be used directly as model resource. Such scenes can be created in
             // Fyroxed.
             "rgs" => (
@@ -633,6 +1073,18 @@ impl Model {
             }
         };
 
+        // Native scenes are made in the editor, they do not need any conversion.
+        if extension != "rgs" {
+            apply_import_transform(&mut scene, &model_import_options);
+            compress_animations(&mut scene, model_import_options.animation_compression);
+            add_animation_clips(&mut scene, &model_import_options.animation_clips);
+            generate_lods(
+                &mut scene,
+                model_import_options.lod_mode,
+                &model_import_options.lods,
+            );
+        }
+
         Ok(Self { scene, mapping })
     }
 
@@ -653,3 +1105,129 @@ impl Model {
         &mut self.scene
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Matrix4, Vector3},
+        resource::model::{
+            add_animation_clips, apply_import_transform, generate_lods, AnimationClipImportOptions,
+            LodImportOptions, ModelImportOptions, ModelUpAxis,
+        },
+        scene::{
+            animation::{Animation, AnimationContainer, AnimationPlayer, AnimationPlayerBuilder},
+            base::BaseBuilder,
+            mesh::{
+                lod::MeshLodMode,
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                Mesh, MeshBuilder,
+            },
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+            Scene,
+        },
+    };
+
+    #[test]
+    fn test_apply_import_transform() {
+        let mut scene = Scene::new();
+        let pivot = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, 100.0))
+                    .build(),
+            ),
+        )
+        .build(&mut scene.graph);
+
+        apply_import_transform(
+            &mut scene,
+            &ModelImportOptions {
+                scale: 0.01,
+                up_axis: ModelUpAxis::Z,
+                ..Default::default()
+            },
+        );
+
+        let transform = scene.graph[pivot].local_transform();
+        assert!((**transform.position() - Vector3::new(0.0, 1.0, 0.0)).norm() < 1.0e-5);
+        assert!((**transform.scale() - Vector3::repeat(0.01)).norm() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_add_animation_clips() {
+        let mut scene = Scene::new();
+        let mut animations = AnimationContainer::new();
+        animations.add(Animation::default());
+        let player = AnimationPlayerBuilder::new(BaseBuilder::new())
+            .with_animations(animations)
+            .build(&mut scene.graph);
+
+        add_animation_clips(
+            &mut scene,
+            &[
+                AnimationClipImportOptions {
+                    name: "Idle".to_string(),
+                    start: 0.0,
+                    end: 1.0,
+                    ..Default::default()
+                },
+                AnimationClipImportOptions {
+                    name: "Invalid".to_string(),
+                    start: 2.0,
+                    end: 1.0,
+                    ..Default::default()
+                },
+            ],
+        );
+
+        let player = scene.graph[player].cast::<AnimationPlayer>().unwrap();
+        let animations = player.animations();
+        assert_eq!(animations.iter().count(), 2);
+        let (_, clip) = animations.find_by_name_ref("Idle").unwrap();
+        assert_eq!(clip.time_slice(), 0.0..1.0);
+    }
+
+    #[test]
+    fn test_generate_lods() {
+        let mut scene = Scene::new();
+        let data = SurfaceData::make_sphere(16, 16, 1.0, &Matrix4::identity());
+        let source_triangles = data.geometry_buffer.len();
+        let mesh = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![
+                SurfaceBuilder::new(SurfaceSharedData::new(data)).build()
+            ])
+            .build(&mut scene.graph);
+
+        generate_lods(
+            &mut scene,
+            MeshLodMode::Distance,
+            &[
+                LodImportOptions {
+                    threshold: 10.0,
+                    triangle_ratio: 0.5,
+                },
+                LodImportOptions {
+                    threshold: 20.0,
+                    triangle_ratio: 0.1,
+                },
+            ],
+        );
+
+        let mesh = scene.graph[mesh].cast::<Mesh>().unwrap();
+        assert_eq!(mesh.lod_mode(), MeshLodMode::Distance);
+        assert_eq!(mesh.lods().len(), 2);
+        let first = mesh.lods()[0].surfaces[0]
+            .data_ref()
+            .lock()
+            .geometry_buffer
+            .len();
+        let second = mesh.lods()[1].surfaces[0]
+            .data_ref()
+            .lock()
+            .geometry_buffer
+            .len();
+        assert!(first < source_triangles);
+        assert!(second < first);
+    }
+}
@@ -0,0 +1,434 @@
+//! Block compression encoders for formats that are not covered by `tbc` crate - BC7 and ASTC 4x4.
+//!
+//! Both encoders are intentionally simple: every block is encoded using a single set of endpoints
+//! (BC7 mode 6 and single-partition ASTC), that are found using principal component analysis and
+//! then refined using least squares fitting. This is much faster than exhaustive search used in
+//! offline tools and gives quality that is noticeably better than BC1/BC3.
+
+/// Size of a single compressed block in bytes. It is the same for BC7 and ASTC.
+const BLOCK_SIZE: usize = 16;
+
+type Block = [[f32; 4]; 16];
+
+struct BitWriter {
+    data: [u8; BLOCK_SIZE],
+    position: usize,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            data: Default::default(),
+            position: 0,
+        }
+    }
+
+    fn write(&mut self, value: u32, bits: usize) {
+        for i in 0..bits {
+            self.set_bit(self.position + i, (value >> i) & 1 != 0);
+        }
+        self.position += bits;
+    }
+
+    fn set_bit(&mut self, index: usize, value: bool) {
+        if value {
+            self.data[index / 8] |= 1 << (index % 8);
+        }
+    }
+}
+
+/// Splits the image in 4x4 blocks and encodes each block using the given function. Pixels of blocks
+/// that are partially outside of the image (when its size is not a multiple of 4) are clamped to edge.
+fn encode_blocks<F>(
+    bytes: &[u8],
+    channels: usize,
+    width: usize,
+    height: usize,
+    encode: F,
+) -> Vec<u8>
+where
+    F: Fn(&Block) -> [u8; BLOCK_SIZE],
+{
+    let blocks_x = (width + 3) / 4;
+    let blocks_y = (height + 3) / 4;
+    let mut output = Vec::with_capacity(blocks_x * blocks_y * BLOCK_SIZE);
+    for by in 0..blocks_y {
+        for bx in 0..blocks_x {
+            let mut block = [[0.0, 0.0, 0.0, 255.0]; 16];
+            for (i, pixel) in block.iter_mut().enumerate() {
+                let x = (bx * 4 + i % 4).min(width - 1);
+                let y = (by * 4 + i / 4).min(height - 1);
+                let offset = (y * width + x) * channels;
+                for (c, channel) in pixel.iter_mut().enumerate().take(channels) {
+                    *channel = bytes[offset + c] as f32;
+                }
+            }
+            output.extend_from_slice(&encode(&block));
+        }
+    }
+    output
+}
+
+fn mean(block: &Block) -> [f32; 4] {
+    let mut mean = [0.0; 4];
+    for pixel in block {
+        for c in 0..4 {
+            mean[c] += pixel[c] / 16.0;
+        }
+    }
+    mean
+}
+
+/// Finds the direction of the largest variance of pixel colors using power iteration.
+fn principal_axis(block: &Block, mean: [f32; 4]) -> [f32; 4] {
+    let mut covariance = [[0.0f32; 4]; 4];
+    for pixel in block {
+        for i in 0..4 {
+            for j in 0..4 {
+                covariance[i][j] += (pixel[i] - mean[i]) * (pixel[j] - mean[j]);
+            }
+        }
+    }
+    let mut axis = [1.0f32; 4];
+    for _ in 0..8 {
+        let mut next = [0.0; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                next[i] += covariance[i][j] * axis[j];
+            }
+        }
+        let length = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if length <= f32::EPSILON {
+            return [0.0; 4];
+        }
+        axis = next.map(|v| v / length);
+    }
+    axis
+}
+
+/// Returns initial endpoints of a block, that are the extreme points of the projections of the colors
+/// on the principal axis.
+fn bounding_endpoints(block: &Block) -> ([f32; 4], [f32; 4]) {
+    let mean = mean(block);
+    let axis = principal_axis(block, mean);
+    let (mut min, mut max) = (0.0f32, 0.0f32);
+    for pixel in block {
+        let t = (0..4).map(|c| (pixel[c] - mean[c]) * axis[c]).sum::<f32>();
+        min = min.min(t);
+        max = max.max(t);
+    }
+    let point = |t: f32| {
+        let mut point = [0.0; 4];
+        for c in 0..4 {
+            point[c] = (mean[c] + axis[c] * t).clamp(0.0, 255.0);
+        }
+        point
+    };
+    (point(min), point(max))
+}
+
+/// Finds the endpoints that minimize squared error for the given interpolation weights (in `[0; 1]`
+/// range). Returns `None` if the system is degenerate (all weights are the same).
+fn least_squares_endpoints(block: &Block, weights: &[f32; 16]) -> Option<([f32; 4], [f32; 4])> {
+    let (mut a, mut b, mut c) = (0.0, 0.0, 0.0);
+    let mut x = [0.0; 4];
+    let mut y = [0.0; 4];
+    for (pixel, &w) in block.iter().zip(weights) {
+        let iw = 1.0 - w;
+        a += iw * iw;
+        b += iw * w;
+        c += w * w;
+        for i in 0..4 {
+            x[i] += iw * pixel[i];
+            y[i] += w * pixel[i];
+        }
+    }
+    let det = a * c - b * b;
+    if det.abs() <= f32::EPSILON {
+        return None;
+    }
+    let mut e0 = [0.0; 4];
+    let mut e1 = [0.0; 4];
+    for i in 0..4 {
+        e0[i] = ((c * x[i] - b * y[i]) / det).clamp(0.0, 255.0);
+        e1[i] = ((a * y[i] - b * x[i]) / det).clamp(0.0, 255.0);
+    }
+    Some((e0, e1))
+}
+
+fn distance_squared(a: &[f32; 4], b: &[u32; 4], channels: usize) -> f32 {
+    (0..channels).map(|c| (a[c] - b[c] as f32).powi(2)).sum()
+}
+
+/// Result of fitting a block to a palette.
+struct Fit {
+    error: f32,
+    e0: [u32; 4],
+    e1: [u32; 4],
+    indices: [u8; 16],
+}
+
+/// Assigns the closest palette entry for every pixel of the block.
+fn fit_palette(block: &Block, palette: &[[u32; 4]], channels: usize) -> (f32, [u8; 16]) {
+    let mut error = 0.0;
+    let mut indices = [0; 16];
+    for (pixel, index) in block.iter().zip(indices.iter_mut()) {
+        let (best, best_error) = palette
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (i, distance_squared(pixel, entry, channels)))
+            .fold((0, f32::MAX), |a, b| if b.1 < a.1 { b } else { a });
+        *index = best as u8;
+        error += best_error;
+    }
+    (error, indices)
+}
+
+/// Runs the fitting function on the bounding endpoints, then refines the endpoints once using
+/// least squares and returns the best result.
+fn fit_block<F>(block: &Block, weights: &[u32], fit: F) -> Fit
+where
+    F: Fn([f32; 4], [f32; 4]) -> Fit,
+{
+    let (e0, e1) = bounding_endpoints(block);
+    let first = fit(e0, e1);
+    let mut interpolation = [0.0; 16];
+    for (w, &index) in interpolation.iter_mut().zip(first.indices.iter()) {
+        *w = weights[index as usize] as f32 / 64.0;
+    }
+    match least_squares_endpoints(block, &interpolation) {
+        Some((e0, e1)) => {
+            let refined = fit(e0, e1);
+            if refined.error < first.error {
+                refined
+            } else {
+                first
+            }
+        }
+        None => first,
+    }
+}
+
+const BC7_WEIGHTS: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+fn bc7_fit(block: &Block, e0: [f32; 4], e1: [f32; 4]) -> Fit {
+    // Endpoints of mode 6 are stored using 7 bits per channel plus a shared (per-endpoint) p-bit
+    // which is the least significant bit of each channel. Try every combination of p-bits.
+    let quantize = |e: [f32; 4], p: u32| {
+        e.map(|v| (((v - p as f32) / 2.0).round().clamp(0.0, 127.0) as u32) << 1 | p)
+    };
+    let mut best: Option<Fit> = None;
+    for p0 in 0..2 {
+        for p1 in 0..2 {
+            let q0 = quantize(e0, p0);
+            let q1 = quantize(e1, p1);
+            let palette = BC7_WEIGHTS.map(|w| {
+                let mut color = [0; 4];
+                for c in 0..4 {
+                    color[c] = ((64 - w) * q0[c] + w * q1[c] + 32) >> 6;
+                }
+                color
+            });
+            let (error, indices) = fit_palette(block, &palette, 4);
+            if best.as_ref().map_or(true, |best| error < best.error) {
+                best = Some(Fit {
+                    error,
+                    e0: q0,
+                    e1: q1,
+                    indices,
+                });
+            }
+        }
+    }
+    best.unwrap()
+}
+
+fn encode_bc7_block(block: &Block) -> [u8; BLOCK_SIZE] {
+    let mut fit = fit_block(block, &BC7_WEIGHTS, |e0, e1| bc7_fit(block, e0, e1));
+
+    // The most significant bit of the first index is implicitly zero (anchor index), swap the
+    // endpoints if needed.
+    if fit.indices[0] >= 8 {
+        std::mem::swap(&mut fit.e0, &mut fit.e1);
+        for index in fit.indices.iter_mut() {
+            *index = 15 - *index;
+        }
+    }
+
+    let mut writer = BitWriter::new();
+    // Mode 6.
+    writer.write(1 << 6, 7);
+    for c in 0..4 {
+        writer.write(fit.e0[c] >> 1, 7);
+        writer.write(fit.e1[c] >> 1, 7);
+    }
+    writer.write(fit.e0[0] & 1, 1);
+    writer.write(fit.e1[0] & 1, 1);
+    for (i, &index) in fit.indices.iter().enumerate() {
+        writer.write(index as u32, if i == 0 { 3 } else { 4 });
+    }
+    writer.data
+}
+
+/// Encodes 8-bit RGB (`channels == 3`) or RGBA (`channels == 4`) image using BC7 block compression.
+/// Every 4x4 block takes 16 bytes, which gives 1:4 compression ratio for RGBA images.
+pub(crate) fn encode_bc7(bytes: &[u8], channels: usize, width: usize, height: usize) -> Vec<u8> {
+    encode_blocks(bytes, channels, width, height, encode_bc7_block)
+}
+
+// Unquantized weights for 2-bit and 3-bit weight ranges.
+const ASTC_WEIGHTS_2BIT: [u32; 4] = [0, 21, 43, 64];
+const ASTC_WEIGHTS_3BIT: [u32; 8] = [0, 9, 18, 27, 37, 46, 55, 64];
+
+// LDR RGB and LDR RGBA direct color endpoint modes.
+const ASTC_CEM_RGB: u32 = 8;
+const ASTC_CEM_RGBA: u32 = 12;
+
+fn astc_fit(block: &Block, e0: [f32; 4], e1: [f32; 4], weights: &[u32], channels: usize) -> Fit {
+    let mut q0 = e0.map(|v| v.round() as u32);
+    let mut q1 = e1.map(|v| v.round() as u32);
+    if channels == 3 {
+        q0[3] = 255;
+        q1[3] = 255;
+    }
+    // The decoder swaps endpoints and applies blue contraction if the sum of RGB components of the
+    // second endpoint is less than the sum of the first one, so make sure it does not happen.
+    if q1[..3].iter().sum::<u32>() < q0[..3].iter().sum::<u32>() {
+        std::mem::swap(&mut q0, &mut q1);
+    }
+    let palette = weights
+        .iter()
+        .map(|&w| {
+            let mut color = [0; 4];
+            for c in 0..4 {
+                // Endpoints are expanded to 16 bits before interpolation.
+                color[c] = ((((64 - w) * q0[c] * 257 + w * q1[c] * 257) + 32) >> 6) >> 8;
+            }
+            color
+        })
+        .collect::<Vec<_>>();
+    let (error, indices) = fit_palette(block, &palette, channels);
+    Fit {
+        error,
+        e0: q0,
+        e1: q1,
+        indices,
+    }
+}
+
+fn encode_astc_block(block: &Block) -> [u8; BLOCK_SIZE] {
+    let has_alpha = block.iter().any(|pixel| pixel[3] < 255.0);
+
+    // 4x4 weight grid with a single plane. Opaque blocks use 3-bit weights, blocks with alpha
+    // need two more endpoint values, so there's only enough space for 2-bit weights. In both
+    // cases endpoints are stored using 8 bits.
+    let (block_mode, cem, channels, weights, weight_bits): (u32, u32, usize, &[u32], usize) =
+        if has_alpha {
+            (0x42, ASTC_CEM_RGBA, 4, &ASTC_WEIGHTS_2BIT, 2)
+        } else {
+            (0x53, ASTC_CEM_RGB, 3, &ASTC_WEIGHTS_3BIT, 3)
+        };
+
+    let fit = fit_block(block, weights, |e0, e1| {
+        astc_fit(block, e0, e1, weights, channels)
+    });
+
+    let mut writer = BitWriter::new();
+    writer.write(block_mode, 11);
+    // Single partition.
+    writer.write(0, 2);
+    writer.write(cem, 4);
+    for c in 0..channels {
+        writer.write(fit.e0[c], 8);
+        writer.write(fit.e1[c], 8);
+    }
+    // Weights are stored in reverse bit order starting from the end of the block.
+    for (i, &index) in fit.indices.iter().enumerate() {
+        for bit in 0..weight_bits {
+            writer.set_bit(127 - (i * weight_bits + bit), (index >> bit) & 1 != 0);
+        }
+    }
+    writer.data
+}
+
+/// Encodes 8-bit RGB (`channels == 3`) or RGBA (`channels == 4`) image using ASTC LDR block
+/// compression with 4x4 block footprint. Every block takes 16 bytes, which gives 1:4 compression
+/// ratio for RGBA images.
+pub(crate) fn encode_astc_4x4(
+    bytes: &[u8],
+    channels: usize,
+    width: usize,
+    height: usize,
+) -> Vec<u8> {
+    encode_blocks(bytes, channels, width, height, encode_astc_block)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Every channel is a linear function of the horizontal position, so every block can be
+    // represented using a single pair of endpoints.
+    fn test_image(width: usize, height: usize, alpha: bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for _ in 0..height {
+            for x in 0..width {
+                bytes.push((x * 16) as u8);
+                bytes.push(255 - (x * 16) as u8);
+                bytes.push((40 + x * 8) as u8);
+                if alpha {
+                    bytes.push(255 - (x * 10) as u8);
+                }
+            }
+        }
+        bytes
+    }
+
+    fn max_error(source: &[u8], channels: usize, decoded: &[u32]) -> u8 {
+        let mut max_error = 0;
+        for (pixel, &decoded) in source.chunks_exact(channels).zip(decoded) {
+            let [b, g, r, a] = decoded.to_le_bytes();
+            for (c, value) in [r, g, b, a].into_iter().enumerate().take(channels) {
+                max_error = max_error.max(pixel[c].abs_diff(value));
+            }
+        }
+        max_error
+    }
+
+    #[test]
+    fn test_bc7_round_trip() {
+        for (channels, alpha) in [(3, false), (4, true)] {
+            let (width, height) = (16, 12);
+            let source = test_image(width, height, alpha);
+            let encoded = encode_bc7(&source, channels, width, height);
+            assert_eq!(encoded.len(), 4 * 3 * BLOCK_SIZE);
+            let mut decoded = vec![0; width * height];
+            texture2ddecoder::decode_bc7(&encoded, width, height, &mut decoded).unwrap();
+            assert!(max_error(&source, channels, &decoded) <= 2);
+        }
+    }
+
+    #[test]
+    fn test_astc_round_trip() {
+        for (channels, alpha) in [(3, false), (4, true)] {
+            let (width, height) = (16, 12);
+            let source = test_image(width, height, alpha);
+            let encoded = encode_astc_4x4(&source, channels, width, height);
+            assert_eq!(encoded.len(), 4 * 3 * BLOCK_SIZE);
+            let mut decoded = vec![0; width * height];
+            texture2ddecoder::decode_astc_4_4(&encoded, width, height, &mut decoded).unwrap();
+            assert!(max_error(&source, channels, &decoded) <= 4);
+        }
+    }
+
+    #[test]
+    fn test_partial_blocks() {
+        let source = [10, 20, 30, 40, 50, 60];
+        assert_eq!(encode_bc7(&source, 3, 2, 1).len(), BLOCK_SIZE);
+        assert_eq!(encode_astc_4x4(&source, 3, 1, 2).len(), BLOCK_SIZE);
+
+        let mut decoded = vec![0; 2];
+        texture2ddecoder::decode_bc7(&encode_bc7(&source, 3, 2, 1), 2, 1, &mut decoded).unwrap();
+        assert!(max_error(&source, 3, &decoded) <= 2);
+    }
+}
@@ -9,15 +9,84 @@ use crate::{
         options::{try_get_import_settings, try_get_import_settings_opaque, BaseImportOptions},
         state::LoadError,
     },
-    core::{uuid::Uuid, TypeUuidProvider},
-    resource::texture::{Texture, TextureImportOptions},
+    core::{
+        log::Log,
+        uuid::Uuid,
+        visitor::{Visit, Visitor},
+        TypeUuidProvider,
+    },
+    resource::texture::{
+        data_hash, CompressionOptions, Texture, TextureError, TextureImportOptions,
+    },
+};
+use fxhash::FxHasher;
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
-use std::{path::PathBuf, sync::Arc};
+
+/// Version of the derived data format. Must be increased every time when the output of texture
+/// import changes (new encoders, fixes, etc.), so the stale data will be ignored.
+const DERIVED_DATA_VERSION: u32 = 1;
 
 /// Default implementation for texture loading.
 pub struct TextureLoader {
     /// Default import options for textures.
     pub default_import_options: TextureImportOptions,
+    /// A directory to store compressed textures in. Block compression is slow, so its results are
+    /// stored in this directory and reused if neither source data, nor import options are changed.
+    /// `None` disables the cache. Textures without compression are never cached.
+    pub derived_data_cache: Option<PathBuf>,
+}
+
+impl TextureLoader {
+    /// Returns default path of the derived data cache. Cached textures are named after the content
+    /// of their source files and import options, so the directory can be shared across projects.
+    pub fn default_derived_data_cache() -> PathBuf {
+        std::env::temp_dir()
+            .join("fyrox_derived_data")
+            .join("textures")
+    }
+}
+
+fn derived_data_path(cache: &Path, data: &[u8], import_options: &TextureImportOptions) -> PathBuf {
+    let mut hasher = FxHasher::default();
+    DERIVED_DATA_VERSION.hash(&mut hasher);
+    data.hash(&mut hasher);
+    ron::to_string(import_options)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    // Compression depends on the target platform.
+    (import_options.target_compression() as u32).hash(&mut hasher);
+    cache.join(format!("{:016x}.texture", hasher.finish()))
+}
+
+fn load_derived_data(path: &Path) -> Option<Texture> {
+    let bytes = std::fs::read(path).ok()?;
+    let mut visitor = Visitor::load_from_memory(&bytes).ok()?;
+    let mut texture = Texture::default();
+    texture.visit("Texture", &mut visitor).ok()?;
+    texture.data_hash = data_hash(&texture.bytes);
+    Some(texture)
+}
+
+fn save_derived_data(path: &Path, texture: &Texture) {
+    if let Some(dir) = path.parent() {
+        Log::verify(std::fs::create_dir_all(dir));
+    }
+    let mut visitor = Visitor::new();
+    let mut texture = texture.clone();
+    if let Err(err) = texture
+        .visit("Texture", &mut visitor)
+        .and_then(|_| visitor.save_binary(path))
+    {
+        Log::err(format!(
+            "Unable to save derived data of a texture to {}. Reason: {:?}",
+            path.display(),
+            err
+        ));
+    }
 }
 
 impl ResourceLoader for TextureLoader {
@@ -33,6 +102,7 @@ impl ResourceLoader for TextureLoader {
 
     fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
         let default_import_options = self.default_import_options.clone();
+        let derived_data_cache = self.derived_data_cache.clone();
         Box::pin(async move {
             let io = io.as_ref();
 
@@ -40,9 +110,29 @@ impl ResourceLoader for TextureLoader {
                 .await
                 .unwrap_or(default_import_options);
 
-            let raw_texture = Texture::load_from_file(&path, io, import_options)
+            let Some(cache) = derived_data_cache.filter(|_| {
+                import_options.target_compression() != CompressionOptions::NoCompression
+            }) else {
+                let raw_texture = Texture::load_from_file(&path, io, import_options)
+                    .await
+                    .map_err(LoadError::new)?;
+                return Ok(LoaderPayload::new(raw_texture));
+            };
+
+            let data = io
+                .load_file(&path)
                 .await
-                .map_err(LoadError::new)?;
+                .map_err(|e| LoadError::new(TextureError::from(e)))?;
+            let derived_data_path = derived_data_path(&cache, &data, &import_options);
+            let raw_texture = match load_derived_data(&derived_data_path) {
+                Some(texture) => texture,
+                None => {
+                    let texture =
+                        Texture::load_from_memory(&data, import_options).map_err(LoadError::new)?;
+                    save_derived_data(&derived_data_path, &texture);
+                    texture
+                }
+            };
 
             Ok(LoaderPayload::new(raw_texture))
         })
@@ -62,3 +152,33 @@ impl ResourceLoader for TextureLoader {
         Some(Box::<TextureImportOptions>::default())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::resource::texture::{test::create_test_png, TexturePixelKind};
+
+    #[test]
+    fn test_derived_data_round_trip() {
+        let cache = std::env::temp_dir().join("fyrox_derived_data_test");
+        let png = create_test_png(8, 8);
+        let import_options =
+            TextureImportOptions::default().with_compression(CompressionOptions::Bc7);
+
+        let path = derived_data_path(&cache, &png, &import_options);
+        assert_ne!(
+            path,
+            derived_data_path(&cache, &png, &import_options.clone().with_srgb(true))
+        );
+
+        let texture = Texture::load_from_memory(&png, import_options).unwrap();
+        save_derived_data(&path, &texture);
+        let cached = load_derived_data(&path).unwrap();
+        assert_eq!(cached.pixel_kind(), TexturePixelKind::BC7RGBA);
+        assert_eq!(cached.mip_count(), texture.mip_count());
+        assert_eq!(cached.data(), texture.data());
+        assert_eq!(cached.data_hash(), texture.data_hash());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -32,7 +32,7 @@ use crate::{
         TypeUuidProvider,
     },
 };
-use ddsfile::{Caps2, D3DFormat};
+use ddsfile::{AlphaMode, Caps2, D3D10ResourceDimension, D3DFormat, DxgiFormat};
 use fast_image_resize as fr;
 use fxhash::FxHasher;
 use fyrox_core::num_traits::Bounded;
@@ -55,6 +55,7 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+mod encoder;
 pub mod loader;
 
 /// Texture kind.
@@ -258,6 +259,7 @@ pub struct Texture {
     anisotropy: f32,
     data_hash: u64,
     is_render_target: bool,
+    is_srgb: bool,
     #[doc(hidden)]
     #[reflect(hidden)]
     pub cache_index: Arc<AtomicIndex>,
@@ -304,28 +306,63 @@ impl ResourceData for Texture {
             | TexturePixelKind::DXT5RGBA
             | TexturePixelKind::R8RGTC
             | TexturePixelKind::RG8RGTC
+            | TexturePixelKind::BC6HRGBUF
+            | TexturePixelKind::BC7RGBA
+            | TexturePixelKind::ASTC4x4RGBA
             | TexturePixelKind::BGR8
             | TexturePixelKind::BGRA8
             | TexturePixelKind::RGB16F
             | TexturePixelKind::R32F
             | TexturePixelKind::R16F => return Err(Box::new(TextureError::UnsupportedFormat)),
         };
-        if let TextureKind::Rectangle { width, height } = self.kind {
-            Ok(image::save_buffer(
+        match self.kind {
+            TextureKind::Rectangle { width, height } => Ok(image::save_buffer(
                 path,
                 self.bytes.as_ref(),
                 width,
                 height,
                 color_type,
-            )?)
-        } else {
-            Err(Box::new(TextureError::UnsupportedFormat))
+            )?),
+            // Common image formats cannot store cube maps, so DDS is used.
+            TextureKind::Cube { width, height } if self.pixel_kind == TexturePixelKind::RGBA8 => {
+                let mut dds = ddsfile::Dds::new_dxgi(ddsfile::NewDxgiParams {
+                    height,
+                    width,
+                    depth: None,
+                    format: if self.is_srgb {
+                        DxgiFormat::R8G8B8A8_UNorm_sRGB
+                    } else {
+                        DxgiFormat::R8G8B8A8_UNorm
+                    },
+                    mipmap_levels: Some(self.mip_count),
+                    array_layers: Some(6),
+                    caps2: Some(Caps2::CUBEMAP | Caps2::CUBEMAP_ALLFACES),
+                    is_cubemap: true,
+                    resource_dimension: D3D10ResourceDimension::Texture2D,
+                    alpha_mode: AlphaMode::Unknown,
+                })?;
+                dds.data = reorder_cube_map_data(
+                    &self.bytes,
+                    width,
+                    height,
+                    self.pixel_kind,
+                    self.mip_count,
+                    CubeMapDataOrder::FaceMajor,
+                );
+                dds.write(&mut std::fs::File::create(path)?)?;
+                Ok(())
+            }
+            _ => Err(Box::new(TextureError::UnsupportedFormat)),
         }
     }
 
     fn can_be_saved(&self) -> bool {
         true
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.bytes.len()
+    }
 }
 
 impl Visit for Texture {
@@ -347,6 +384,8 @@ impl Visit for Texture {
         self.t_wrap_mode.visit("TWrapMode", &mut region)?;
         self.mip_count.visit("MipCount", &mut region)?;
         self.kind.visit("Kind", &mut region)?;
+        // Optional for backward compatibility.
+        let _ = self.is_srgb.visit("IsSrgb", &mut region);
         let mut bytes_view = PodVecView::from_pod_vec(&mut self.bytes);
         let _ = bytes_view.visit("Data", &mut region);
 
@@ -374,6 +413,7 @@ impl Default for Texture {
             anisotropy: 16.0,
             data_hash: 0,
             is_render_target: false,
+            is_srgb: false,
             cache_index: Default::default(),
         }
     }
@@ -438,7 +478,9 @@ impl MipFilter {
 ///     s_wrap_mode: Repeat,
 ///     t_wrap_mode: ClampToEdge,
 ///     anisotropy: 8.0,
-///     compression: NoCompression,
+///     compression: Bc7,
+///     mobile_compression: Astc,
+///     srgb: true,
 /// )
 /// ```
 #[derive(Clone, Deserialize, Serialize, Debug, Reflect)]
@@ -459,6 +501,10 @@ pub struct TextureImportOptions {
     pub(crate) mip_filter: MipFilter,
     #[serde(default)]
     pub(crate) flip_green_channel: bool,
+    #[serde(default)]
+    pub(crate) srgb: bool,
+    #[serde(default)]
+    pub(crate) mobile_compression: CompressionOptions,
 }
 
 impl Default for TextureImportOptions {
@@ -472,6 +518,8 @@ impl Default for TextureImportOptions {
             compression: CompressionOptions::default(),
             mip_filter: Default::default(),
             flip_green_channel: false,
+            srgb: false,
+            mobile_compression: CompressionOptions::default(),
         }
     }
 }
@@ -560,6 +608,44 @@ impl TextureImportOptions {
     pub fn set_compression(&mut self, compression: CompressionOptions) {
         self.compression = compression;
     }
+
+    /// Sets desired texture compression for mobile platforms (Android and iOS). Mobile GPUs usually
+    /// does not support BCn formats, so [`CompressionOptions::Astc`] is the best option here.
+    pub fn with_mobile_compression(mut self, compression: CompressionOptions) -> Self {
+        self.mobile_compression = compression;
+        self
+    }
+
+    /// Sets desired texture compression for mobile platforms (Android and iOS). See
+    /// [`Self::with_mobile_compression`] for more info.
+    pub fn set_mobile_compression(&mut self, compression: CompressionOptions) {
+        self.mobile_compression = compression;
+    }
+
+    /// Returns compression options for the current target platform.
+    pub fn target_compression(&self) -> CompressionOptions {
+        if cfg!(any(target_os = "android", target_os = "ios")) {
+            self.mobile_compression
+        } else {
+            self.compression
+        }
+    }
+
+    /// Defines whether the texture data is in sRGB color space or not. Color textures (diffuse,
+    /// emission, etc.) are usually stored in sRGB color space, while data textures (normal maps,
+    /// roughness maps, etc.) are linear. sRGB textures are converted to linear color space by GPU
+    /// when sampled. Only 8-bit RGB and RGBA textures (and their BC7 or ASTC compressed versions)
+    /// are affected.
+    pub fn with_srgb(mut self, srgb: bool) -> Self {
+        self.srgb = srgb;
+        self
+    }
+
+    /// Defines whether the texture data is in sRGB color space or not. See [`Self::with_srgb`]
+    /// for more info.
+    pub fn set_srgb(&mut self, srgb: bool) {
+        self.srgb = srgb;
+    }
 }
 
 lazy_static! {
@@ -636,6 +722,7 @@ impl TextureResourceExtension for TextureResource {
                 anisotropy: 1.0,
                 data_hash: 0,
                 is_render_target: true,
+                is_srgb: false,
                 cache_index: Default::default(),
             },
         )
@@ -928,6 +1015,15 @@ pub enum TexturePixelKind {
 
     /// Red component as 2-byte, half-precision float.
     R16F = 24,
+
+    /// Compressed unsigned half-float RGB texture (BC6H). Can only be loaded from DDS files.
+    BC6HRGBUF = 25,
+
+    /// Compressed RGBA texture (BC7).
+    BC7RGBA = 26,
+
+    /// Compressed RGBA texture (ASTC LDR with 4x4 blocks). Mostly supported by mobile GPUs.
+    ASTC4x4RGBA = 27,
 }
 
 impl TexturePixelKind {
@@ -958,6 +1054,9 @@ impl TexturePixelKind {
             22 => Ok(Self::RGB16F),
             23 => Ok(Self::R32F),
             24 => Ok(Self::R16F),
+            25 => Ok(Self::BC6HRGBUF),
+            26 => Ok(Self::BC7RGBA),
+            27 => Ok(Self::ASTC4x4RGBA),
             _ => Err(format!("Invalid texture kind {}!", id)),
         }
     }
@@ -987,7 +1086,10 @@ impl TexturePixelKind {
             | Self::DXT3RGBA
             | Self::DXT5RGBA
             | Self::R8RGTC
-            | Self::RG8RGTC => None,
+            | Self::RG8RGTC
+            | Self::BC6HRGBUF
+            | Self::BC7RGBA
+            | Self::ASTC4x4RGBA => None,
         }
     }
 }
@@ -1086,6 +1188,22 @@ pub enum CompressionOptions {
     /// This option is faster than `NoCompression` speed by lower requirements of memory
     /// bandwidth.
     Quality = 2,
+
+    /// An image will be encoded via BC7 compression if it is not already compressed. Only
+    /// RGB and RGBA images are affected, other formats will be compressed using `Quality`
+    /// option.
+    /// Compression ratio is 1:4 (including alpha), quality is noticeably better than
+    /// `Quality` option, especially for images with smooth gradients. Requires
+    /// `ARB_texture_compression_bptc` support (all desktop GPUs since 2010).
+    Bc7 = 3,
+
+    /// An image will be encoded via ASTC compression with 4x4 blocks if it is not already
+    /// compressed. Only RGB and RGBA images are affected, other formats will be stored
+    /// without compression.
+    /// Compression ratio is 1:4 (including alpha). This is preferred option for mobile
+    /// devices, since BCn formats are usually not supported there. Requires
+    /// `KHR_texture_compression_astc_ldr` support.
+    Astc = 4,
 }
 
 uuid_provider!(CompressionOptions = "fbdcc081-d0b8-4b62-9925-2de6c013fbf5");
@@ -1138,7 +1256,7 @@ fn compress_rg8_bc4<T: tbc::color::ColorRedGreen8>(
     tbc::encode_image_bc4_rg8_conv_u8::<T>(transmute_slice::<T>(bytes), width, height)
 }
 
-fn data_hash(data: &[u8]) -> u64 {
+pub(crate) fn data_hash(data: &[u8]) -> u64 {
     let mut hasher = FxHasher::default();
     data.hash(&mut hasher);
     hasher.finish()
@@ -1168,17 +1286,37 @@ fn try_compress(
             compress_bc3::<tbc::color::Rgba8>(bytes, w, h),
             TexturePixelKind::DXT5RGBA,
         )),
+        (TexturePixelKind::RGB8, CompressionOptions::Bc7) => Some((
+            encoder::encode_bc7(bytes, 3, w, h),
+            TexturePixelKind::BC7RGBA,
+        )),
+        (TexturePixelKind::RGBA8, CompressionOptions::Bc7) => Some((
+            encoder::encode_bc7(bytes, 4, w, h),
+            TexturePixelKind::BC7RGBA,
+        )),
+        (TexturePixelKind::RGB8, CompressionOptions::Astc) => Some((
+            encoder::encode_astc_4x4(bytes, 3, w, h),
+            TexturePixelKind::ASTC4x4RGBA,
+        )),
+        (TexturePixelKind::RGBA8, CompressionOptions::Astc) => Some((
+            encoder::encode_astc_4x4(bytes, 4, w, h),
+            TexturePixelKind::ASTC4x4RGBA,
+        )),
         (TexturePixelKind::R8, CompressionOptions::Speed)
         | (TexturePixelKind::R8, CompressionOptions::Quality)
+        | (TexturePixelKind::R8, CompressionOptions::Bc7)
         | (TexturePixelKind::Luminance8, CompressionOptions::Speed)
-        | (TexturePixelKind::Luminance8, CompressionOptions::Quality) => Some((
+        | (TexturePixelKind::Luminance8, CompressionOptions::Quality)
+        | (TexturePixelKind::Luminance8, CompressionOptions::Bc7) => Some((
             compress_r8_bc4::<tbc::color::Red8>(bytes, w, h),
             TexturePixelKind::R8RGTC,
         )),
         (TexturePixelKind::RG8, CompressionOptions::Speed)
         | (TexturePixelKind::RG8, CompressionOptions::Quality)
+        | (TexturePixelKind::RG8, CompressionOptions::Bc7)
         | (TexturePixelKind::LuminanceAlpha8, CompressionOptions::Speed)
-        | (TexturePixelKind::LuminanceAlpha8, CompressionOptions::Quality) => Some((
+        | (TexturePixelKind::LuminanceAlpha8, CompressionOptions::Quality)
+        | (TexturePixelKind::LuminanceAlpha8, CompressionOptions::Bc7) => Some((
             compress_rg8_bc4::<tbc::color::RedGreen8>(bytes, w, h),
             TexturePixelKind::RG8RGTC,
         )),
@@ -1186,6 +1324,54 @@ fn try_compress(
     }
 }
 
+/// Layout of cube map data with multiple mip levels.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum CubeMapDataOrder {
+    /// Every mip level contains six faces. This is how textures store cube maps.
+    MipMajor,
+    /// Every face contains all its mip levels. This is how DDS files store cube maps.
+    FaceMajor,
+}
+
+/// Converts cube map data to the given layout. Data is returned as is, if its size does not match
+/// the given parameters.
+fn reorder_cube_map_data(
+    bytes: &[u8],
+    width: u32,
+    height: u32,
+    pixel_kind: TexturePixelKind,
+    mip_count: u32,
+    order: CubeMapDataOrder,
+) -> Vec<u8> {
+    let face_sizes = (0..mip_count.max(1) as usize)
+        .map(|mip| {
+            bytes_in_mip_level(TextureKind::Rectangle { width, height }, pixel_kind, mip) as usize
+        })
+        .collect::<Vec<_>>();
+    let face_chain_size = face_sizes.iter().sum::<usize>();
+
+    if mip_count <= 1 || 6 * face_chain_size != bytes.len() {
+        return bytes.to_vec();
+    }
+
+    let mut result = vec![0; bytes.len()];
+    let mut mip_major_offset = 0;
+    let mut mip_offset = 0;
+    for face_size in face_sizes {
+        for face in 0..6 {
+            let face_major_offset = face * face_chain_size + mip_offset;
+            let (src, dest) = match order {
+                CubeMapDataOrder::MipMajor => (face_major_offset, mip_major_offset),
+                CubeMapDataOrder::FaceMajor => (mip_major_offset, face_major_offset),
+            };
+            result[dest..(dest + face_size)].copy_from_slice(&bytes[src..(src + face_size)]);
+            mip_major_offset += face_size;
+        }
+        mip_offset += face_size;
+    }
+    result
+}
+
 fn bytes_in_mip_level(kind: TextureKind, pixel_kind: TexturePixelKind, mip: usize) -> u32 {
     let pixel_count = match kind {
         TextureKind::Line { length } => length.shr(mip),
@@ -1222,29 +1408,40 @@ fn bytes_in_mip_level(kind: TextureKind, pixel_kind: TexturePixelKind, mip: usiz
         | TexturePixelKind::DXT3RGBA
         | TexturePixelKind::DXT5RGBA
         | TexturePixelKind::R8RGTC
-        | TexturePixelKind::RG8RGTC => {
+        | TexturePixelKind::RG8RGTC
+        | TexturePixelKind::BC6HRGBUF
+        | TexturePixelKind::BC7RGBA
+        | TexturePixelKind::ASTC4x4RGBA => {
             let block_size = match pixel_kind {
                 TexturePixelKind::DXT1RGB
                 | TexturePixelKind::DXT1RGBA
                 | TexturePixelKind::R8RGTC => 8,
                 TexturePixelKind::DXT3RGBA
                 | TexturePixelKind::DXT5RGBA
-                | TexturePixelKind::RG8RGTC => 16,
+                | TexturePixelKind::RG8RGTC
+                | TexturePixelKind::BC6HRGBUF
+                | TexturePixelKind::BC7RGBA
+                | TexturePixelKind::ASTC4x4RGBA => 16,
                 _ => unreachable!(),
             };
             match kind {
-                TextureKind::Line { length } => ceil_div_4(length) * block_size,
+                TextureKind::Line { length } => ceil_div_4(length.shr(mip)) * block_size,
                 TextureKind::Rectangle { width, height } => {
-                    ceil_div_4(width) * ceil_div_4(height) * block_size
+                    ceil_div_4(width.shr(mip)) * ceil_div_4(height.shr(mip)) * block_size
                 }
                 TextureKind::Cube { width, height } => {
-                    6 * ceil_div_4(width) * ceil_div_4(height) * block_size
+                    6 * ceil_div_4(width.shr(mip)) * ceil_div_4(height.shr(mip)) * block_size
                 }
                 TextureKind::Volume {
                     width,
                     height,
                     depth,
-                } => ceil_div_4(width) * ceil_div_4(height) * ceil_div_4(depth) * block_size,
+                } => {
+                    ceil_div_4(width.shr(mip))
+                        * ceil_div_4(height.shr(mip))
+                        * ceil_div_4(depth.shr(mip))
+                        * block_size
+                }
             }
         }
     }
@@ -1317,60 +1514,112 @@ impl Texture {
     ) -> Result<Self, TextureError> {
         // DDS is special. It can contain various kinds of textures as well as textures with
         // various pixel formats.
-        //
-        // TODO: Add support for DXGI formats.
         if let Ok(dds) = ddsfile::Dds::read(&mut Cursor::new(data)) {
-            let d3dformat = dds
-                .get_d3d_format()
-                .ok_or(TextureError::UnsupportedFormat)?;
             let mip_count = dds.get_num_mipmap_levels();
+            let dxgi_format = dds.header10.as_ref().map(|header10| header10.dxgi_format);
+            let d3d_format = dds.get_d3d_format();
+            let mut is_srgb = import_options.srgb;
             let mut bytes = dds.data;
 
             // Try to use as much formats as possible.
-            let pixel_kind = match d3dformat {
-                D3DFormat::DXT1 => TexturePixelKind::DXT1RGBA,
-                D3DFormat::DXT3 => TexturePixelKind::DXT3RGBA,
-                D3DFormat::DXT5 => TexturePixelKind::DXT5RGBA,
-                D3DFormat::L8 | D3DFormat::A8 => TexturePixelKind::R8,
-                D3DFormat::L16 => TexturePixelKind::R16,
-                D3DFormat::R8G8B8 => TexturePixelKind::RGB8,
-                D3DFormat::A8L8 => TexturePixelKind::RG8,
-                D3DFormat::A8R8G8B8 => {
-                    // // ARGB8 -> RGBA8
-                    // assert_eq!(bytes.len() % 4, 0);
-                    // for chunk in bytes.chunks_exact_mut(4) {
-                    //     let a = chunk[0];
-                    //     let r = chunk[1];
-                    //     let g = chunk[2];
-                    //     let b = chunk[3];
-                    //     chunk[0] = r;
-                    //     chunk[1] = g;
-                    //     chunk[2] = b;
-                    //     chunk[3] = a;
-                    // }
-                    TexturePixelKind::RGBA8
+            let pixel_kind = if let Some(dxgi_format) = dxgi_format {
+                // Files with DX10 header (BC6H, BC7, etc.) have DXGI formats only.
+                match dxgi_format {
+                    DxgiFormat::BC1_UNorm => TexturePixelKind::DXT1RGBA,
+                    DxgiFormat::BC2_UNorm => TexturePixelKind::DXT3RGBA,
+                    DxgiFormat::BC3_UNorm => TexturePixelKind::DXT5RGBA,
+                    DxgiFormat::BC4_UNorm => TexturePixelKind::R8RGTC,
+                    DxgiFormat::BC5_UNorm => TexturePixelKind::RG8RGTC,
+                    DxgiFormat::BC6H_UF16 => TexturePixelKind::BC6HRGBUF,
+                    DxgiFormat::BC7_UNorm => TexturePixelKind::BC7RGBA,
+                    DxgiFormat::BC7_UNorm_sRGB => {
+                        is_srgb = true;
+                        TexturePixelKind::BC7RGBA
+                    }
+                    DxgiFormat::R8G8B8A8_UNorm => TexturePixelKind::RGBA8,
+                    DxgiFormat::R8G8B8A8_UNorm_sRGB => {
+                        is_srgb = true;
+                        TexturePixelKind::RGBA8
+                    }
+                    DxgiFormat::R8G8_UNorm => TexturePixelKind::RG8,
+                    DxgiFormat::R8_UNorm => TexturePixelKind::R8,
+                    _ => return Err(TextureError::UnsupportedFormat),
                 }
-                D3DFormat::G16R16 => {
-                    // GR16 -> RG16
-                    assert_eq!(bytes.len() % 4, 0);
-                    for chunk in bytes.chunks_exact_mut(4) {
-                        // Red Hi + Lo bytes
-                        let gh = chunk[0];
-                        let gl = chunk[1];
-                        // Green Hi + Lo bytes
-                        let rh = chunk[2];
-                        let rl = chunk[3];
-                        // Swap
-                        chunk[0] = rh;
-                        chunk[1] = rl;
-                        chunk[2] = gh;
-                        chunk[3] = gl;
+            } else {
+                match d3d_format.ok_or(TextureError::UnsupportedFormat)? {
+                    D3DFormat::DXT1 => TexturePixelKind::DXT1RGBA,
+                    D3DFormat::DXT3 => TexturePixelKind::DXT3RGBA,
+                    D3DFormat::DXT5 => TexturePixelKind::DXT5RGBA,
+                    D3DFormat::L8 | D3DFormat::A8 => TexturePixelKind::R8,
+                    D3DFormat::L16 => TexturePixelKind::R16,
+                    D3DFormat::R8G8B8 => TexturePixelKind::RGB8,
+                    D3DFormat::A8L8 => TexturePixelKind::RG8,
+                    D3DFormat::A8R8G8B8 => {
+                        // // ARGB8 -> RGBA8
+                        // assert_eq!(bytes.len() % 4, 0);
+                        // for chunk in bytes.chunks_exact_mut(4) {
+                        //     let a = chunk[0];
+                        //     let r = chunk[1];
+                        //     let g = chunk[2];
+                        //     let b = chunk[3];
+                        //     chunk[0] = r;
+                        //     chunk[1] = g;
+                        //     chunk[2] = b;
+                        //     chunk[3] = a;
+                        // }
+                        TexturePixelKind::RGBA8
+                    }
+                    D3DFormat::G16R16 => {
+                        // GR16 -> RG16
+                        assert_eq!(bytes.len() % 4, 0);
+                        for chunk in bytes.chunks_exact_mut(4) {
+                            // Red Hi + Lo bytes
+                            let gh = chunk[0];
+                            let gl = chunk[1];
+                            // Green Hi + Lo bytes
+                            let rh = chunk[2];
+                            let rl = chunk[3];
+                            // Swap
+                            chunk[0] = rh;
+                            chunk[1] = rl;
+                            chunk[2] = gh;
+                            chunk[3] = gl;
+                        }
+                        TexturePixelKind::RG16
                     }
-                    TexturePixelKind::RG16
+                    _ => return Err(TextureError::UnsupportedFormat),
                 }
-                _ => return Err(TextureError::UnsupportedFormat),
             };
 
+            let kind = if dds.header.caps2 & Caps2::CUBEMAP == Caps2::CUBEMAP {
+                TextureKind::Cube {
+                    width: dds.header.width,
+                    height: dds.header.height,
+                }
+            } else if dds.header.caps2 & Caps2::VOLUME == Caps2::VOLUME {
+                TextureKind::Volume {
+                    width: dds.header.width,
+                    height: dds.header.height,
+                    depth: dds.header.depth.unwrap(),
+                }
+            } else {
+                TextureKind::Rectangle {
+                    width: dds.header.width,
+                    height: dds.header.height,
+                }
+            };
+
+            if let TextureKind::Cube { width, height } = kind {
+                bytes = reorder_cube_map_data(
+                    &bytes,
+                    width,
+                    height,
+                    pixel_kind,
+                    mip_count,
+                    CubeMapDataOrder::MipMajor,
+                );
+            }
+
             Ok(Self {
                 pixel_kind,
                 data_hash: data_hash(&bytes),
@@ -1381,24 +1630,9 @@ impl Texture {
                 anisotropy: import_options.anisotropy,
                 mip_count,
                 bytes: bytes.into(),
-                kind: if dds.header.caps2 & Caps2::CUBEMAP == Caps2::CUBEMAP {
-                    TextureKind::Cube {
-                        width: dds.header.width,
-                        height: dds.header.height,
-                    }
-                } else if dds.header.caps2 & Caps2::VOLUME == Caps2::VOLUME {
-                    TextureKind::Volume {
-                        width: dds.header.width,
-                        height: dds.header.height,
-                        depth: dds.header.depth.unwrap(),
-                    }
-                } else {
-                    TextureKind::Rectangle {
-                        width: dds.header.width,
-                        height: dds.header.height,
-                    }
-                },
+                kind,
                 is_render_target: false,
+                is_srgb,
                 cache_index: Default::default(),
             })
         } else {
@@ -1438,6 +1672,7 @@ impl Texture {
                 _ => return Err(TextureError::UnsupportedFormat),
             };
             let mut final_pixel_kind = src_pixel_kind;
+            let compression = import_options.target_compression();
 
             let mut mip_count = 0;
             let mut bytes = Vec::with_capacity(
@@ -1477,14 +1712,14 @@ impl Texture {
 
                     mip_count += 1;
 
-                    if import_options.compression == CompressionOptions::NoCompression {
+                    if compression == CompressionOptions::NoCompression {
                         bytes.extend_from_slice(current_level.buffer())
                     } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
                         src_pixel_kind,
                         current_level.buffer(),
                         level_width as usize,
                         level_height as usize,
-                        import_options.compression,
+                        compression,
                     ) {
                         final_pixel_kind = new_pixel_kind;
                         bytes.extend_from_slice(&compressed_data);
@@ -1498,14 +1733,14 @@ impl Texture {
             } else {
                 mip_count = 1;
 
-                if import_options.compression == CompressionOptions::NoCompression {
+                if compression == CompressionOptions::NoCompression {
                     bytes.extend_from_slice(dyn_img.as_bytes());
                 } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
                     src_pixel_kind,
                     dyn_img.as_bytes(),
                     width as usize,
                     height as usize,
-                    import_options.compression,
+                    compression,
                 ) {
                     final_pixel_kind = new_pixel_kind;
                     bytes.extend_from_slice(&compressed_data);
@@ -1526,6 +1761,7 @@ impl Texture {
                 t_wrap_mode: import_options.t_wrap_mode,
                 anisotropy: import_options.anisotropy,
                 is_render_target: false,
+                is_srgb: import_options.srgb,
                 cache_index: Default::default(),
             })
         }
@@ -1569,6 +1805,38 @@ impl Texture {
         }
     }
 
+    /// Creates new texture instance from given parameters. Unlike [`Self::from_bytes`], the data
+    /// could contain multiple mip levels, that must be stored one after another starting from the
+    /// largest one. Every mip level of a cube texture must contain all six faces.
+    pub fn from_mip_chain(
+        kind: TextureKind,
+        pixel_kind: TexturePixelKind,
+        mip_count: u32,
+        bytes: Vec<u8>,
+    ) -> Option<Self> {
+        let expected_size = (0..mip_count.max(1) as usize)
+            .map(|mip| bytes_in_mip_level(kind, pixel_kind, mip))
+            .sum::<u32>();
+        if expected_size != bytes.len() as u32 {
+            None
+        } else {
+            Some(Self {
+                kind,
+                data_hash: data_hash(&bytes),
+                bytes: bytes.into(),
+                pixel_kind,
+                mip_count: mip_count.max(1),
+                ..Default::default()
+            })
+        }
+    }
+
+    /// Defines whether the texture data is in sRGB color space or not. It must be set before the
+    /// texture is used for rendering for the first time, otherwise it won't have any effect.
+    pub fn set_srgb(&mut self, srgb: bool) {
+        self.is_srgb = srgb;
+    }
+
     /// Sets new minification filter. It is used when texture becomes smaller.
     pub fn set_minification_filter(&mut self, filter: TextureMinificationFilter) {
         self.minification_filter = filter;
@@ -1690,6 +1958,13 @@ impl Texture {
         self.is_render_target
     }
 
+    /// Returns `true` if the texture data is in sRGB color space, `false` - otherwise. See
+    /// [`TextureImportOptions::with_srgb`] for more info.
+    #[inline]
+    pub fn is_srgb(&self) -> bool {
+        self.is_srgb
+    }
+
     /// Max samples for anisotropic filtering. Default value is 16.0 (max).
     /// However real value passed to GPU will be clamped to maximum supported
     /// by current GPU. To disable anisotropic filtering set this to 1.0.
@@ -1760,9 +2035,13 @@ impl<'a> TextureDataRefMut<'a> {
 
 #[cfg(test)]
 pub mod test {
+    use crate::asset::ResourceData;
     use crate::resource::texture::{
-        TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension,
+        CompressionOptions, Texture, TextureImportOptions, TextureKind, TexturePixelKind,
+        TextureResource, TextureResourceExtension,
     };
+    use image::{ImageOutputFormat, RgbaImage};
+    use std::io::Cursor;
 
     pub fn create_test_texture() -> TextureResource {
         TextureResource::from_bytes(
@@ -1776,4 +2055,78 @@ pub mod test {
         )
         .unwrap()
     }
+
+    /// Creates 8-bit RGBA PNG image with a gradient.
+    pub fn create_test_png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_fn(width, height, |x, y| {
+            image::Rgba([(x * 16) as u8, (y * 16) as u8, 128, 255])
+        });
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+            .unwrap();
+        png
+    }
+
+    #[test]
+    fn test_cube_map_dds_round_trip() {
+        // 2x2 faces with two mip levels, every pixel stores its face and mip indices.
+        let mut bytes = Vec::new();
+        for (mip, size) in [(0u8, 2), (1u8, 1)] {
+            for face in 0..6u8 {
+                for _ in 0..size * size {
+                    bytes.extend_from_slice(&[face, mip, 0, 255]);
+                }
+            }
+        }
+
+        let mut texture = Texture::from_mip_chain(
+            TextureKind::Cube {
+                width: 2,
+                height: 2,
+            },
+            TexturePixelKind::RGBA8,
+            2,
+            bytes.clone(),
+        )
+        .unwrap();
+        texture.set_srgb(true);
+
+        let path = std::env::temp_dir().join("fyrox_test_cube_map_dds_round_trip.dds");
+        texture.save(&path).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let loaded = Texture::load_from_memory(&data, Default::default()).unwrap();
+        assert!(matches!(
+            loaded.kind(),
+            TextureKind::Cube {
+                width: 2,
+                height: 2
+            }
+        ));
+        assert_eq!(loaded.pixel_kind(), TexturePixelKind::RGBA8);
+        assert_eq!(loaded.mip_count(), 2);
+        assert!(loaded.is_srgb());
+        assert_eq!(loaded.data(), bytes.as_slice());
+    }
+
+    #[test]
+    fn test_load_with_compression() {
+        let png = create_test_png(8, 8);
+        for (compression, pixel_kind) in [
+            (CompressionOptions::Bc7, TexturePixelKind::BC7RGBA),
+            (CompressionOptions::Astc, TexturePixelKind::ASTC4x4RGBA),
+        ] {
+            let texture = Texture::load_from_memory(
+                &png,
+                TextureImportOptions::default().with_compression(compression),
+            )
+            .unwrap();
+            assert_eq!(texture.pixel_kind(), pixel_kind);
+            assert_eq!(texture.mip_count(), 4);
+            // 8x8, 4x4, 2x2 and 1x1 mip levels, each level takes at least one 16-byte block.
+            assert_eq!(texture.data().len(), (4 + 1 + 1 + 1) * 16);
+        }
+    }
 }
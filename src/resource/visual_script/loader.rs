@@ -0,0 +1,34 @@
+//! Visual script loader.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+    },
+    core::{uuid::Uuid, TypeUuidProvider},
+    resource::visual_script::VisualScript,
+};
+use fyrox_resource::state::LoadError;
+use std::{path::PathBuf, sync::Arc};
+
+/// Default implementation for visual script loading.
+pub struct VisualScriptLoader;
+
+impl ResourceLoader for VisualScriptLoader {
+    fn extensions(&self) -> &[&str] {
+        &["visual_script"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        VisualScript::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let script = VisualScript::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(script))
+        })
+    }
+}
@@ -0,0 +1,761 @@
+//! Visual script is a graph-based asset, that allows to define the logic of scene nodes without
+//! writing any code. A visual script consists of nodes of three kinds:
+//!
+//! - **Event nodes** - entry points of the script, that start execution when something happens
+//! (script started, every frame, a message received).
+//! - **Flow nodes** - nodes that do something (set a property, send a message, etc.) and pass the
+//! execution further using flow connections.
+//! - **Expression nodes** - nodes that compute values (constants, properties of scene nodes,
+//! arithmetic, etc.). Their values are pulled by flow nodes using data connections.
+//!
+//! Visual scripts are executed by [`crate::script::visual::VisualScriptRunner`] script, which could
+//! be assigned to any scene node. Visual scripts could be edited in the editor, there is a special
+//! graph editor for them, that opens when a visual script asset is double-clicked in the asset
+//! browser.
+//!
+//! # Example
+//!
+//! The following example creates a script, that moves its node along X axis every frame.
+//!
+//! ```rust
+//! use fyrox::{
+//!     core::algebra::Vector3,
+//!     resource::visual_script::{
+//!         BinaryOperation, Value, VisualConnectionKind, VisualNode, VisualNodeKind, VisualScript,
+//!     },
+//! };
+//!
+//! fn make_script() -> VisualScript {
+//!     let mut script = VisualScript::default();
+//!     let on_update = script.add_node(VisualNode::new(VisualNodeKind::OnUpdate));
+//!     let set = script.add_node(VisualNode::new(VisualNodeKind::SetProperty {
+//!         path: "base.local_transform.local_position".to_string(),
+//!     }));
+//!     let get = script.add_node(VisualNode::new(VisualNodeKind::GetProperty {
+//!         path: "base.local_transform.local_position".to_string(),
+//!     }));
+//!     let offset = script.add_node(VisualNode::new(VisualNodeKind::Constant {
+//!         value: Value::Vector3(Vector3::new(0.01, 0.0, 0.0)),
+//!     }));
+//!     let add = script.add_node(VisualNode::new(VisualNodeKind::Binary {
+//!         operation: BinaryOperation::Add,
+//!     }));
+//!
+//!     script.connect(VisualConnectionKind::Flow, on_update, 0, set, 0);
+//!     script.connect(VisualConnectionKind::Data, get, 0, add, 0);
+//!     script.connect(VisualConnectionKind::Data, offset, 0, add, 1);
+//!     script.connect(VisualConnectionKind::Data, add, 0, set, 1);
+//!     script
+//! }
+//! ```
+
+use crate::{
+    asset::{io::ResourceIo, Resource, ResourceData},
+    core::{
+        algebra::{Vector2, Vector3},
+        io::FileLoadError,
+        pool::{Handle, Pool},
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::node::Node,
+};
+use fyrox_core::uuid_provider;
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::Path,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+pub mod loader;
+
+/// An error that may occur during visual script loading.
+#[derive(Debug)]
+pub enum VisualScriptError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for VisualScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VisualScriptError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            VisualScriptError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for VisualScriptError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for VisualScriptError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A value, that is produced by expression nodes and consumed by flow nodes.
+#[derive(
+    Clone, Debug, Default, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum Value {
+    /// No value. It is produced when a value cannot be computed (for example, when a property does
+    /// not exist).
+    #[default]
+    None,
+    /// A boolean value.
+    Bool(bool),
+    /// A number. All numeric properties are converted to (and from) this type.
+    Number(f32),
+    /// A 3D vector.
+    Vector3(Vector3<f32>),
+    /// A string.
+    String(String),
+    /// A handle of a scene node.
+    Node(Handle<Node>),
+}
+
+uuid_provider!(Value = "4f1c7e2a-9b3d-4a6e-8c5f-1d7b3e9a2c64");
+
+impl Value {
+    /// Tries to convert a reflected value into a visual script value. Supports booleans, numbers of
+    /// any type, 3D vectors, strings and node handles.
+    pub fn from_reflect(value: &dyn Reflect) -> Self {
+        let mut result = Value::None;
+        value.as_any(&mut |any| {
+            result = if let Some(v) = any.downcast_ref::<bool>() {
+                Value::Bool(*v)
+            } else if let Some(v) = any.downcast_ref::<f32>() {
+                Value::Number(*v)
+            } else if let Some(v) = any.downcast_ref::<f64>() {
+                Value::Number(*v as f32)
+            } else if let Some(v) = any.downcast_ref::<i32>() {
+                Value::Number(*v as f32)
+            } else if let Some(v) = any.downcast_ref::<u32>() {
+                Value::Number(*v as f32)
+            } else if let Some(v) = any.downcast_ref::<usize>() {
+                Value::Number(*v as f32)
+            } else if let Some(v) = any.downcast_ref::<Vector3<f32>>() {
+                Value::Vector3(*v)
+            } else if let Some(v) = any.downcast_ref::<String>() {
+                Value::String(v.clone())
+            } else if let Some(v) = any.downcast_ref::<Handle<Node>>() {
+                Value::Node(*v)
+            } else {
+                Value::None
+            };
+        });
+        result
+    }
+
+    /// Converts the value into a reflected value of the same type as the given `target` value.
+    /// Numbers are converted to the numeric type of the target. Returns `None` if the conversion is
+    /// not possible.
+    pub fn to_reflect(&self, target: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let mut result: Option<Box<dyn Reflect>> = None;
+        target.as_any(&mut |any| {
+            result = match self {
+                Value::None => None,
+                Value::Bool(v) => any.is::<bool>().then(|| Box::new(*v) as Box<dyn Reflect>),
+                Value::Number(v) => {
+                    if any.is::<f32>() {
+                        Some(Box::new(*v) as Box<dyn Reflect>)
+                    } else if any.is::<f64>() {
+                        Some(Box::new(*v as f64) as Box<dyn Reflect>)
+                    } else if any.is::<i32>() {
+                        Some(Box::new(*v as i32) as Box<dyn Reflect>)
+                    } else if any.is::<u32>() {
+                        Some(Box::new(*v as u32) as Box<dyn Reflect>)
+                    } else if any.is::<usize>() {
+                        Some(Box::new(*v as usize) as Box<dyn Reflect>)
+                    } else {
+                        None
+                    }
+                }
+                Value::Vector3(v) => any
+                    .is::<Vector3<f32>>()
+                    .then(|| Box::new(*v) as Box<dyn Reflect>),
+                Value::String(v) => any
+                    .is::<String>()
+                    .then(|| Box::new(v.clone()) as Box<dyn Reflect>),
+                Value::Node(v) => any
+                    .is::<Handle<Node>>()
+                    .then(|| Box::new(*v) as Box<dyn Reflect>),
+            };
+        });
+        result
+    }
+
+    /// Returns `true` if the value is "truthy": `true`, non-zero number, non-empty string or
+    /// some node handle.
+    pub fn as_bool(&self) -> bool {
+        match self {
+            Value::None => false,
+            Value::Bool(v) => *v,
+            Value::Number(v) => *v != 0.0,
+            Value::Vector3(v) => *v != Vector3::default(),
+            Value::String(v) => !v.is_empty(),
+            Value::Node(v) => v.is_some(),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::None => write!(f, "None"),
+            Value::Bool(v) => write!(f, "{v}"),
+            Value::Number(v) => write!(f, "{v}"),
+            Value::Vector3(v) => write!(f, "({}; {}; {})", v.x, v.y, v.z),
+            Value::String(v) => write!(f, "{v}"),
+            Value::Node(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// An operation of [`VisualNodeKind::Binary`] node.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum BinaryOperation {
+    /// Sum of numbers or vectors, or concatenation of strings.
+    #[default]
+    Add,
+    /// Difference of numbers or vectors.
+    Sub,
+    /// Product of numbers, or a vector scaled by a number.
+    Mul,
+    /// Quotient of numbers, or a vector divided by a number.
+    Div,
+    /// `true` if the left number is less than the right one.
+    Less,
+    /// `true` if the left number is greater than the right one.
+    Greater,
+    /// `true` if the values are equal.
+    Equal,
+    /// Logical "and".
+    And,
+    /// Logical "or".
+    Or,
+}
+
+uuid_provider!(BinaryOperation = "c8e3a5d1-7f2b-4e9c-a1d6-3b5f8e2c7a40");
+
+impl BinaryOperation {
+    /// Applies the operation to the given values. Returns [`Value::None`] if the operation is not
+    /// defined for the given types.
+    pub fn apply(self, left: &Value, right: &Value) -> Value {
+        match (self, left, right) {
+            (BinaryOperation::And, _, _) => Value::Bool(left.as_bool() && right.as_bool()),
+            (BinaryOperation::Or, _, _) => Value::Bool(left.as_bool() || right.as_bool()),
+            (BinaryOperation::Equal, _, _) => Value::Bool(left == right),
+            (BinaryOperation::Add, Value::Number(a), Value::Number(b)) => Value::Number(a + b),
+            (BinaryOperation::Sub, Value::Number(a), Value::Number(b)) => Value::Number(a - b),
+            (BinaryOperation::Mul, Value::Number(a), Value::Number(b)) => Value::Number(a * b),
+            (BinaryOperation::Div, Value::Number(a), Value::Number(b)) => Value::Number(a / b),
+            (BinaryOperation::Less, Value::Number(a), Value::Number(b)) => Value::Bool(a < b),
+            (BinaryOperation::Greater, Value::Number(a), Value::Number(b)) => Value::Bool(a > b),
+            (BinaryOperation::Add, Value::Vector3(a), Value::Vector3(b)) => Value::Vector3(a + b),
+            (BinaryOperation::Sub, Value::Vector3(a), Value::Vector3(b)) => Value::Vector3(a - b),
+            (BinaryOperation::Mul, Value::Vector3(a), Value::Number(b))
+            | (BinaryOperation::Mul, Value::Number(b), Value::Vector3(a)) => {
+                Value::Vector3(a.scale(*b))
+            }
+            (BinaryOperation::Div, Value::Vector3(a), Value::Number(b)) => {
+                Value::Vector3(a.unscale(*b))
+            }
+            (BinaryOperation::Add, Value::String(a), b) => Value::String(format!("{a}{b}")),
+            _ => Value::None,
+        }
+    }
+}
+
+/// Kind of a visual script node, it defines what the node does and which pins it has. See module
+/// docs for more info.
+#[derive(
+    Clone, Debug, Default, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum VisualNodeKind {
+    /// Event, that is triggered once, when the script starts.
+    #[default]
+    OnStart,
+    /// Event, that is triggered every frame.
+    OnUpdate,
+    /// Event, that is triggered when a [`crate::script::visual::VisualScriptMessage`] with the
+    /// given name is received.
+    OnMessage {
+        /// Name of the message.
+        name: String,
+    },
+    /// Passes the execution to `True` or `False` output depending on the `Condition` input.
+    Branch,
+    /// Passes the execution to every connected output one after another.
+    Sequence,
+    /// Sets a property of a scene node (self, if `Node` input is not connected) using the given
+    /// reflection path, for example `base.local_transform.local_position`.
+    SetProperty {
+        /// Reflection path of the property.
+        path: String,
+    },
+    /// Sets a value of a variable of the script instance.
+    SetVariable {
+        /// Name of the variable.
+        name: String,
+    },
+    /// Sends a [`crate::script::visual::VisualScriptMessage`] with the given name. The message is
+    /// sent to the `Target` node, or globally if the input is not connected.
+    SendMessage {
+        /// Name of the message.
+        name: String,
+    },
+    /// Writes the input value to the log.
+    Log,
+    /// A constant value.
+    Constant {
+        /// The value.
+        value: Value,
+    },
+    /// Reads a property of a scene node (self, if `Node` input is not connected) using the given
+    /// reflection path.
+    GetProperty {
+        /// Reflection path of the property.
+        path: String,
+    },
+    /// Reads a value of a variable of the script instance.
+    GetVariable {
+        /// Name of the variable.
+        name: String,
+    },
+    /// Handle of the node, that runs the script.
+    SelfNode,
+    /// Time (in seconds) passed since the last frame.
+    DeltaTime,
+    /// Value of the message, that triggered the current execution. It is [`Value::None`] if the
+    /// execution was not triggered by a message.
+    MessageValue,
+    /// Finds a node with the given name in the scene.
+    FindNode {
+        /// Name of the node.
+        name: String,
+    },
+    /// Applies a binary operation to two input values.
+    Binary {
+        /// The operation.
+        operation: BinaryOperation,
+    },
+    /// Logical negation of the input value.
+    Not,
+}
+
+uuid_provider!(VisualNodeKind = "7a3d9e1f-5c2b-4f8a-b6e4-2c9d1a7f3e58");
+
+impl VisualNodeKind {
+    /// Returns `true` if the node is an event node.
+    pub fn is_event(&self) -> bool {
+        matches!(
+            self,
+            VisualNodeKind::OnStart | VisualNodeKind::OnUpdate | VisualNodeKind::OnMessage { .. }
+        )
+    }
+
+    /// Returns `true` if the node could be executed, which means that it has a flow input pin.
+    pub fn has_flow_input(&self) -> bool {
+        matches!(
+            self,
+            VisualNodeKind::Branch
+                | VisualNodeKind::Sequence
+                | VisualNodeKind::SetProperty { .. }
+                | VisualNodeKind::SetVariable { .. }
+                | VisualNodeKind::SendMessage { .. }
+                | VisualNodeKind::Log
+        )
+    }
+
+    /// Returns names of flow output pins of the node.
+    pub fn flow_outputs(&self) -> &'static [&'static str] {
+        match self {
+            VisualNodeKind::OnStart
+            | VisualNodeKind::OnUpdate
+            | VisualNodeKind::OnMessage { .. }
+            | VisualNodeKind::SetProperty { .. }
+            | VisualNodeKind::SetVariable { .. }
+            | VisualNodeKind::SendMessage { .. }
+            | VisualNodeKind::Log => &["Next"],
+            VisualNodeKind::Branch => &["True", "False"],
+            VisualNodeKind::Sequence => &["0", "1", "2", "3"],
+            _ => &[],
+        }
+    }
+
+    /// Returns names of data input pins of the node.
+    pub fn data_inputs(&self) -> &'static [&'static str] {
+        match self {
+            VisualNodeKind::Branch => &["Condition"],
+            VisualNodeKind::SetProperty { .. } => &["Node", "Value"],
+            VisualNodeKind::SetVariable { .. } | VisualNodeKind::Log => &["Value"],
+            VisualNodeKind::SendMessage { .. } => &["Target", "Value"],
+            VisualNodeKind::GetProperty { .. } => &["Node"],
+            VisualNodeKind::Binary { .. } => &["A", "B"],
+            VisualNodeKind::Not => &["Value"],
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if the node produces a value, which means that it has a data output pin.
+    pub fn has_data_output(&self) -> bool {
+        matches!(
+            self,
+            VisualNodeKind::Constant { .. }
+                | VisualNodeKind::GetProperty { .. }
+                | VisualNodeKind::GetVariable { .. }
+                | VisualNodeKind::SelfNode
+                | VisualNodeKind::DeltaTime
+                | VisualNodeKind::MessageValue
+                | VisualNodeKind::FindNode { .. }
+                | VisualNodeKind::Binary { .. }
+                | VisualNodeKind::Not
+        )
+    }
+}
+
+/// A node of a visual script.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct VisualNode {
+    /// Position of the node in the graph editor.
+    #[reflect(hidden)]
+    pub position: Vector2<f32>,
+    /// Kind of the node.
+    pub kind: VisualNodeKind,
+}
+
+uuid_provider!(VisualNode = "e1b5d9a3-2f7c-4c6e-9d8a-5a3f1c7e9b26");
+
+impl VisualNode {
+    /// Creates a new node of the given kind.
+    pub fn new(kind: VisualNodeKind) -> Self {
+        Self {
+            position: Default::default(),
+            kind,
+        }
+    }
+
+    /// Sets the position of the node in the graph editor.
+    pub fn with_position(mut self, position: Vector2<f32>) -> Self {
+        self.position = position;
+        self
+    }
+}
+
+/// Kind of a connection between two nodes.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum VisualConnectionKind {
+    /// Connects a flow output of a node with a flow input of another node, it defines the order
+    /// of execution.
+    #[default]
+    Flow,
+    /// Connects a data output of a node with a data input of another node, it defines where the
+    /// values come from.
+    Data,
+}
+
+uuid_provider!(VisualConnectionKind = "9d4f2b6e-1a8c-4e3d-b7f5-6c2e9a4d1b83");
+
+/// A connection between two nodes. Flow connections go from a flow output (`source_pin` is the
+/// index of the output) to a flow input (`target_pin` is always zero). Data connections go from a
+/// data output (`source_pin` is always zero) to a data input (`target_pin` is the index of the
+/// input).
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct VisualConnection {
+    /// Kind of the connection.
+    pub kind: VisualConnectionKind,
+    /// A node from which the connection starts.
+    pub source: Handle<VisualNode>,
+    /// Index of the output pin of the source node.
+    pub source_pin: u32,
+    /// A node at which the connection ends.
+    pub target: Handle<VisualNode>,
+    /// Index of the input pin of the target node.
+    pub target_pin: u32,
+}
+
+/// A graph of visual script nodes. See module docs for more info.
+#[derive(Debug, Default, Clone, Visit, Reflect)]
+pub struct VisualScript {
+    #[reflect(hidden)]
+    nodes: Pool<VisualNode>,
+    #[reflect(hidden)]
+    connections: Vec<VisualConnection>,
+}
+
+impl ResourceData for VisualScript {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("VisualScript", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+impl TypeUuidProvider for VisualScript {
+    fn type_uuid() -> Uuid {
+        uuid!("3c8a1f5d-9e2b-4d7a-a4c6-8f1e5b3d7a92")
+    }
+}
+
+impl VisualScript {
+    /// Adds a new node to the script and returns its handle.
+    pub fn add_node(&mut self, node: VisualNode) -> Handle<VisualNode> {
+        self.nodes.spawn(node)
+    }
+
+    /// Removes a node and every connection of the node.
+    pub fn remove_node(&mut self, handle: Handle<VisualNode>) -> Option<VisualNode> {
+        self.connections
+            .retain(|c| c.source != handle && c.target != handle);
+        self.nodes.try_free(handle)
+    }
+
+    /// Returns a reference to the pool of nodes.
+    pub fn nodes(&self) -> &Pool<VisualNode> {
+        &self.nodes
+    }
+
+    /// Returns a reference to a node.
+    pub fn node(&self, handle: Handle<VisualNode>) -> Option<&VisualNode> {
+        self.nodes.try_borrow(handle)
+    }
+
+    /// Returns a reference to a node.
+    pub fn node_mut(&mut self, handle: Handle<VisualNode>) -> Option<&mut VisualNode> {
+        self.nodes.try_borrow_mut(handle)
+    }
+
+    /// Returns a slice of connections.
+    pub fn connections(&self) -> &[VisualConnection] {
+        &self.connections
+    }
+
+    fn is_valid_connection(&self, connection: &VisualConnection) -> bool {
+        let (Some(source), Some(target)) = (
+            self.nodes.try_borrow(connection.source),
+            self.nodes.try_borrow(connection.target),
+        ) else {
+            return false;
+        };
+
+        connection.source != connection.target
+            && match connection.kind {
+                VisualConnectionKind::Flow => {
+                    (connection.source_pin as usize) < source.kind.flow_outputs().len()
+                        && target.kind.has_flow_input()
+                        && connection.target_pin == 0
+                }
+                VisualConnectionKind::Data => {
+                    source.kind.has_data_output()
+                        && connection.source_pin == 0
+                        && (connection.target_pin as usize) < target.kind.data_inputs().len()
+                }
+            }
+    }
+
+    /// Connects two nodes. A data input could have only one source and a flow output could have
+    /// only one target, so an existing connection of the pin (if any) is replaced. Returns `false`
+    /// if any of the nodes does not exist or any of the pins does not exist.
+    pub fn connect(
+        &mut self,
+        kind: VisualConnectionKind,
+        source: Handle<VisualNode>,
+        source_pin: u32,
+        target: Handle<VisualNode>,
+        target_pin: u32,
+    ) -> bool {
+        let connection = VisualConnection {
+            kind,
+            source,
+            source_pin,
+            target,
+            target_pin,
+        };
+        if !self.is_valid_connection(&connection) {
+            return false;
+        }
+
+        self.connections.retain(|c| match kind {
+            VisualConnectionKind::Flow => {
+                !(c.kind == kind && c.source == source && c.source_pin == source_pin)
+            }
+            VisualConnectionKind::Data => {
+                !(c.kind == kind && c.target == target && c.target_pin == target_pin)
+            }
+        });
+        self.connections.push(connection);
+
+        true
+    }
+
+    /// Removes every connection, that refers to a nonexistent node or pin. Pins of a node depend
+    /// on its kind, so this method should be called after changing the kind of a node.
+    pub fn remove_invalid_connections(&mut self) {
+        let connections = std::mem::take(&mut self.connections);
+        self.connections = connections
+            .into_iter()
+            .filter(|c| self.is_valid_connection(c))
+            .collect();
+    }
+
+    /// Removes a connection at the given index.
+    pub fn disconnect(&mut self, index: usize) -> Option<VisualConnection> {
+        (index < self.connections.len()).then(|| self.connections.remove(index))
+    }
+
+    /// Returns a node, connected to the given flow output of the given node.
+    pub fn flow_target(&self, source: Handle<VisualNode>, pin: u32) -> Handle<VisualNode> {
+        self.connections
+            .iter()
+            .find(|c| {
+                c.kind == VisualConnectionKind::Flow && c.source == source && c.source_pin == pin
+            })
+            .map(|c| c.target)
+            .unwrap_or_default()
+    }
+
+    /// Returns a node, connected to the given data input of the given node.
+    pub fn data_source(&self, target: Handle<VisualNode>, pin: u32) -> Handle<VisualNode> {
+        self.connections
+            .iter()
+            .find(|c| {
+                c.kind == VisualConnectionKind::Data && c.target == target && c.target_pin == pin
+            })
+            .map(|c| c.source)
+            .unwrap_or_default()
+    }
+
+    /// Load a visual script from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, VisualScriptError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut script = VisualScript::default();
+        script.visit("VisualScript", &mut visitor)?;
+        Ok(script)
+    }
+}
+
+/// Type alias for visual script resources.
+pub type VisualScriptResource = Resource<VisualScript>;
+
+#[cfg(test)]
+mod test {
+    use crate::resource::visual_script::{
+        BinaryOperation, Value, VisualConnectionKind, VisualNode, VisualNodeKind, VisualScript,
+    };
+
+    #[test]
+    fn test_visual_script_connections() {
+        let mut script = VisualScript::default();
+        let start = script.add_node(VisualNode::new(VisualNodeKind::OnStart));
+        let log = script.add_node(VisualNode::new(VisualNodeKind::Log));
+        let a = script.add_node(VisualNode::new(VisualNodeKind::Constant {
+            value: Value::Number(1.0),
+        }));
+        let b = script.add_node(VisualNode::new(VisualNodeKind::Constant {
+            value: Value::Number(2.0),
+        }));
+
+        assert!(script.connect(VisualConnectionKind::Flow, start, 0, log, 0));
+        assert!(script.connect(VisualConnectionKind::Data, a, 0, log, 0));
+        // Data input could have only one source.
+        assert!(script.connect(VisualConnectionKind::Data, b, 0, log, 0));
+        assert_eq!(script.data_source(log, 0), b);
+        // Pins must exist.
+        assert!(!script.connect(VisualConnectionKind::Flow, log, 0, start, 0));
+        assert!(!script.connect(VisualConnectionKind::Data, a, 0, log, 1));
+
+        // Changing the kind of a node removes connections of the pins, that no longer exist.
+        script.node_mut(a).unwrap().kind = VisualNodeKind::OnUpdate;
+        script.remove_invalid_connections();
+        assert_eq!(script.connections().len(), 2);
+        script.node_mut(b).unwrap().kind = VisualNodeKind::OnUpdate;
+        script.remove_invalid_connections();
+        assert_eq!(script.connections().len(), 1);
+
+        assert_eq!(script.flow_target(start, 0), log);
+        script.remove_node(log);
+        assert!(script.flow_target(start, 0).is_none());
+        assert!(script.connections().is_empty());
+    }
+
+    #[test]
+    fn test_binary_operations() {
+        assert_eq!(
+            BinaryOperation::Add.apply(&Value::Number(1.0), &Value::Number(2.0)),
+            Value::Number(3.0)
+        );
+        assert_eq!(
+            BinaryOperation::Less.apply(&Value::Number(1.0), &Value::Number(2.0)),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            BinaryOperation::Add.apply(&Value::String("a".to_string()), &Value::Number(2.0)),
+            Value::String("a2".to_string())
+        );
+        assert_eq!(
+            BinaryOperation::Sub.apply(&Value::Bool(true), &Value::Number(2.0)),
+            Value::None
+        );
+    }
+}
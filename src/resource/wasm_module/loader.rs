@@ -0,0 +1,34 @@
+//! WASM module loader.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+    },
+    core::{uuid::Uuid, TypeUuidProvider},
+    resource::wasm_module::WasmModule,
+};
+use fyrox_resource::state::LoadError;
+use std::{path::PathBuf, sync::Arc};
+
+/// Default implementation for WASM module loading.
+pub struct WasmModuleLoader;
+
+impl ResourceLoader for WasmModuleLoader {
+    fn extensions(&self) -> &[&str] {
+        &["wasm"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        WasmModule::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let module = WasmModule::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(module))
+        })
+    }
+}
@@ -0,0 +1,105 @@
+//! WASM module is a compiled WebAssembly binary, that is used by [`crate::script::wasm::WasmScript`]
+//! to define the logic of scene nodes. WASM modules could be written in any language, that compiles
+//! to WebAssembly, and they could be shipped separately from the game (as mods for example), because
+//! they're executed in a sandbox and can access the game only through the host API. See
+//! [`crate::script::wasm`] module docs for the host API and the list of exported functions, that the
+//! engine calls.
+
+use crate::{
+    asset::{io::ResourceIo, Resource, ResourceData},
+    core::{
+        io::FileLoadError,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+pub mod loader;
+
+/// An error that may occur during WASM module loading.
+#[derive(Debug)]
+pub enum WasmModuleError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+}
+
+impl Display for WasmModuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmModuleError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for WasmModuleError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Default, Visit, Reflect)]
+pub struct WasmModule {
+    #[reflect(hidden)]
+    bytes: Vec<u8>,
+}
+
+impl ResourceData for WasmModule {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        std::fs::write(path, &self.bytes)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        false
+    }
+}
+
+impl TypeUuidProvider for WasmModule {
+    fn type_uuid() -> Uuid {
+        uuid!("b7e3a9c2-5d1f-4a8e-9c6b-2f4d8a1e7c53")
+    }
+}
+
+impl WasmModule {
+    /// Creates a new module from the given WebAssembly binary. The binary is validated only when a
+    /// script instantiates the module.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the WebAssembly binary of the module.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Loads a module from the given file.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, WasmModuleError> {
+        Ok(Self::from_bytes(io.load_file(path).await?))
+    }
+}
+
+/// Type alias for WASM module resources.
+pub type WasmModuleResource = Resource<WasmModule>;
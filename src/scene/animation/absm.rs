@@ -90,6 +90,7 @@ pub mod prelude {
         RootMotionSettings, State, StateAction, StateActionWrapper, Transition, XorNode,
     };
     pub use crate::generic_animation::machine::{
+        layer::LayerBlendMode,
         node::AnimationEventCollectionStrategy,
         parameter::{Parameter, ParameterContainer, ParameterDefinition, PoseWeight},
     };
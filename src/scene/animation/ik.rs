@@ -0,0 +1,747 @@
+//! Inverse kinematics (IK) allows you to procedurally adjust a chain of bones, so the end of the
+//! chain reaches some target. See [`IkChain`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        uuid_provider,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        node::{Node, NodeTrait},
+    },
+};
+use fyrox_graph::SceneGraph;
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Maximum amount of bones in a chain. It is used to prevent infinite loops when the root of a
+/// chain is not an ancestor of its effector.
+const MAX_CHAIN_LENGTH: usize = 64;
+
+/// Analytic solver for chains of exactly two bones (three joints), such as arms and legs. The
+/// bending plane of the chain is defined by an optional pole target.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct TwoBoneSolver {
+    /// A handle of a node which position defines the direction in which the middle joint (elbow,
+    /// knee, etc.) will bend. If the handle is invalid, the current bending direction is preserved.
+    #[reflect(
+        description = "A node which position defines the direction in which the middle joint will bend."
+    )]
+    pub pole_target: Handle<Node>,
+}
+
+/// Iterative solver (Forward And Backward Reaching Inverse Kinematics) for chains of arbitrary
+/// length, such as spines, tails, tentacles, etc.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct FabrikSolver {
+    /// Maximum amount of iterations of the solver. Default is 10.
+    #[reflect(description = "Maximum amount of iterations of the solver.")]
+    pub iterations: u32,
+
+    /// Maximum allowed distance between the effector and the target. The solver stops when the
+    /// distance is less than this value. Default is 0.001.
+    #[reflect(
+        min_value = 0.0,
+        description = "Maximum allowed distance between the effector and the target."
+    )]
+    pub tolerance: f32,
+}
+
+impl Default for FabrikSolver {
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            tolerance: 0.001,
+        }
+    }
+}
+
+/// Rotates the effector, so its forward axis points towards the target. It is useful for heads,
+/// eyes, turrets, etc. The root of the chain is ignored by this solver.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct LookAtSolver {
+    /// An axis, in local coordinates of the effector, that should point towards the target.
+    /// Default is +Z.
+    #[reflect(
+        description = "An axis, in local coordinates of the effector, that should point towards the target."
+    )]
+    pub forward_axis: Vector3<f32>,
+}
+
+impl Default for LookAtSolver {
+    fn default() -> Self {
+        Self {
+            forward_axis: Vector3::z(),
+        }
+    }
+}
+
+/// The exact kind of the inverse kinematics solver.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum IkSolver {
+    /// See [`TwoBoneSolver`] for more info.
+    TwoBone(TwoBoneSolver),
+    /// See [`FabrikSolver`] for more info.
+    Fabrik(FabrikSolver),
+    /// See [`LookAtSolver`] for more info.
+    LookAt(LookAtSolver),
+}
+
+uuid_provider!(IkSolver = "5a0ac2a0-84e1-4c3b-8a3e-0ad36b1f8a5e");
+
+impl Default for IkSolver {
+    fn default() -> Self {
+        Self::TwoBone(Default::default())
+    }
+}
+
+/// IK chain is a node that modifies rotations of a chain of bones (from the root down to the
+/// effector), so the effector reaches the target. The chain is solved once per frame, after every
+/// other node was updated, which means that it is applied on top of the pose produced by animation
+/// players and animation blending state machines.
+///
+/// The chain itself is defined by two handles: `root` and `effector`, where the root must be an
+/// ancestor of the effector. The IK chain node itself could be placed anywhere in the graph, it
+/// only uses its handles to find the bones.
+///
+/// # Solvers
+///
+/// - [`TwoBoneSolver`] - analytic solver for limbs (arms, legs) with optional pole target.
+/// - [`FabrikSolver`] - iterative solver for chains of arbitrary length.
+/// - [`LookAtSolver`] - rotates the effector towards the target (heads, eyes).
+///
+/// # Weight
+///
+/// The result of the solver is blended with the current pose using the weight of the chain. It
+/// could be used to smoothly enable or disable IK, for example when a foot is lifted from the ground.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::pool::Handle,
+/// #     scene::{
+/// #         animation::ik::{IkChainBuilder, IkSolver, TwoBoneSolver},
+/// #         base::BaseBuilder,
+/// #         graph::Graph,
+/// #         node::Node,
+/// #     },
+/// # };
+/// fn create_leg_ik(
+///     graph: &mut Graph,
+///     hip: Handle<Node>,
+///     foot: Handle<Node>,
+///     target: Handle<Node>,
+///     knee_pole: Handle<Node>,
+/// ) -> Handle<Node> {
+///     IkChainBuilder::new(BaseBuilder::new().with_name("LegIK"))
+///         .with_root(hip)
+///         .with_effector(foot)
+///         .with_target(target)
+///         .with_solver(IkSolver::TwoBone(TwoBoneSolver {
+///             pole_target: knee_pole,
+///         }))
+///         .build(graph)
+/// }
+/// ```
+#[derive(Clone, Reflect, Visit, Debug)]
+pub struct IkChain {
+    base: Base,
+
+    #[reflect(
+        setter = "set_root",
+        description = "The first bone of the chain. It must be an ancestor of the effector."
+    )]
+    root: InheritableVariable<Handle<Node>>,
+
+    #[reflect(
+        setter = "set_effector",
+        description = "The last bone of the chain, that should reach the target."
+    )]
+    effector: InheritableVariable<Handle<Node>>,
+
+    #[reflect(
+        setter = "set_target",
+        description = "A node which position should be reached by the effector."
+    )]
+    target: InheritableVariable<Handle<Node>>,
+
+    #[reflect(
+        setter = "set_weight",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05,
+        description = "Blending weight between the current pose (0.0) and the solved pose (1.0)."
+    )]
+    weight: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_solver")]
+    solver: InheritableVariable<IkSolver>,
+}
+
+impl Default for IkChain {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            root: Default::default(),
+            effector: Default::default(),
+            target: Default::default(),
+            weight: InheritableVariable::new_modified(1.0),
+            solver: Default::default(),
+        }
+    }
+}
+
+impl Deref for IkChain {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for IkChain {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for IkChain {
+    fn type_uuid() -> Uuid {
+        uuid!("c1b2b5a6-3c9e-4f57-9d35-71d7a2f6e0c4")
+    }
+}
+
+impl NodeTrait for IkChain {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+impl IkChain {
+    /// Sets the first bone of the chain. It must be an ancestor of the effector, otherwise the
+    /// chain will have no effect.
+    pub fn set_root(&mut self, root: Handle<Node>) -> Handle<Node> {
+        self.root.set_value_and_mark_modified(root)
+    }
+
+    /// Returns the first bone of the chain.
+    pub fn root(&self) -> Handle<Node> {
+        *self.root
+    }
+
+    /// Sets the last bone of the chain, that should reach the target.
+    pub fn set_effector(&mut self, effector: Handle<Node>) -> Handle<Node> {
+        self.effector.set_value_and_mark_modified(effector)
+    }
+
+    /// Returns the last bone of the chain.
+    pub fn effector(&self) -> Handle<Node> {
+        *self.effector
+    }
+
+    /// Sets a node which position should be reached by the effector.
+    pub fn set_target(&mut self, target: Handle<Node>) -> Handle<Node> {
+        self.target.set_value_and_mark_modified(target)
+    }
+
+    /// Returns a handle of the target node.
+    pub fn target(&self) -> Handle<Node> {
+        *self.target
+    }
+
+    /// Sets blending weight between the current pose (0.0) and the solved pose (1.0). The value is
+    /// clamped to `[0.0; 1.0]` range.
+    pub fn set_weight(&mut self, weight: f32) -> f32 {
+        self.weight
+            .set_value_and_mark_modified(weight.clamp(0.0, 1.0))
+    }
+
+    /// Returns current blending weight.
+    pub fn weight(&self) -> f32 {
+        *self.weight
+    }
+
+    /// Sets new solver of the chain.
+    pub fn set_solver(&mut self, solver: IkSolver) -> IkSolver {
+        self.solver.set_value_and_mark_modified(solver)
+    }
+
+    /// Returns a reference to the current solver of the chain.
+    pub fn solver(&self) -> &IkSolver {
+        &self.solver
+    }
+
+    /// Returns a list of bones of the chain starting from the root and ending with the effector.
+    /// The list will be empty if the chain is invalid (the root is not an ancestor of the effector).
+    pub fn collect_bones(&self, graph: &Graph) -> Vec<Handle<Node>> {
+        let effector = *self.effector;
+        if graph.try_get(effector).is_none() {
+            return Vec::new();
+        }
+
+        if let IkSolver::LookAt(_) = *self.solver {
+            return vec![effector];
+        }
+
+        let mut bones = vec![effector];
+        let mut current = effector;
+        while current != *self.root {
+            current = graph[current].parent();
+            if current.is_none() || bones.len() >= MAX_CHAIN_LENGTH {
+                return Vec::new();
+            }
+            bones.push(current);
+        }
+        bones.reverse();
+        bones
+    }
+
+    /// Calculates new local rotations of the bones of the chain. This method does not modify the
+    /// graph, it is up to the caller to apply the rotations.
+    pub fn solve(&self, graph: &Graph) -> Vec<(Handle<Node>, UnitQuaternion<f32>)> {
+        let weight = *self.weight;
+        if weight <= 0.0 {
+            return Vec::new();
+        }
+
+        let Some(target) = graph.try_get(*self.target).map(|t| t.global_position()) else {
+            return Vec::new();
+        };
+
+        let bones = self.collect_bones(graph);
+        if bones.is_empty() {
+            return Vec::new();
+        }
+
+        let rotations = match *self.solver {
+            IkSolver::TwoBone(ref solver) => {
+                if bones.len() != 3 {
+                    return Vec::new();
+                }
+                let positions = joint_positions(graph, &bones);
+                let pole = graph
+                    .try_get(solver.pole_target)
+                    .map(|pole| pole.global_position());
+                let desired = solve_two_bone(&positions, target, pole);
+                aim_bones(graph, &bones, &positions, &desired)
+            }
+            IkSolver::Fabrik(ref solver) => {
+                if bones.len() < 2 {
+                    return Vec::new();
+                }
+                let positions = joint_positions(graph, &bones);
+                let desired = solve_fabrik(&positions, target, solver.iterations, solver.tolerance);
+                aim_bones(graph, &bones, &positions, &desired)
+            }
+            IkSolver::LookAt(ref solver) => look_at(graph, bones[0], target, solver.forward_axis)
+                .into_iter()
+                .collect(),
+        };
+
+        rotations
+            .into_iter()
+            .map(|(bone, rotation)| {
+                let current = **graph[bone].local_transform().rotation();
+                let blended = current
+                    .try_slerp(&rotation, weight, f32::EPSILON)
+                    .unwrap_or(rotation);
+                (bone, blended)
+            })
+            .collect()
+    }
+}
+
+fn joint_positions(graph: &Graph, bones: &[Handle<Node>]) -> Vec<Vector3<f32>> {
+    bones
+        .iter()
+        .map(|bone| graph[*bone].global_position())
+        .collect()
+}
+
+fn parent_global_rotation(graph: &Graph, bone: Handle<Node>) -> UnitQuaternion<f32> {
+    let parent = graph[bone].parent();
+    if parent.is_some() {
+        graph.global_rotation(parent)
+    } else {
+        UnitQuaternion::identity()
+    }
+}
+
+/// Converts world-space rotation delta of a bone to its new local rotation. Global rotation of a
+/// bone is `parent * pre_rotation * rotation * post_rotation`, so the delta must be moved into the
+/// space of the rotation.
+fn apply_world_delta(
+    graph: &Graph,
+    bone: Handle<Node>,
+    parent_rotation: UnitQuaternion<f32>,
+    delta: UnitQuaternion<f32>,
+) -> UnitQuaternion<f32> {
+    let transform = graph[bone].local_transform();
+    let frame = parent_rotation * **transform.pre_rotation();
+    frame.inverse() * delta * frame * **transform.rotation()
+}
+
+/// Rotates every bone of the chain (except the effector), so the next joint lies on the line
+/// between the bone and its desired position.
+fn aim_bones(
+    graph: &Graph,
+    bones: &[Handle<Node>],
+    positions: &[Vector3<f32>],
+    desired: &[Vector3<f32>],
+) -> Vec<(Handle<Node>, UnitQuaternion<f32>)> {
+    let mut positions = positions.to_vec();
+    let mut parent_rotation = parent_global_rotation(graph, bones[0]);
+    let mut rotations = Vec::with_capacity(bones.len() - 1);
+
+    for i in 0..bones.len() - 1 {
+        let pivot = positions[i];
+        let delta = UnitQuaternion::rotation_between(
+            &(positions[i + 1] - pivot),
+            &(desired[i + 1] - pivot),
+        )
+        .unwrap_or_else(UnitQuaternion::identity);
+
+        // Descendant joints are rotated together with the bone.
+        for position in positions[i + 1..].iter_mut() {
+            *position = pivot + delta * (*position - pivot);
+        }
+
+        let rotation = apply_world_delta(graph, bones[i], parent_rotation, delta);
+
+        let transform = graph[bones[i]].local_transform();
+        parent_rotation =
+            parent_rotation * **transform.pre_rotation() * rotation * **transform.post_rotation();
+
+        rotations.push((bones[i], rotation));
+    }
+
+    rotations
+}
+
+fn look_at(
+    graph: &Graph,
+    bone: Handle<Node>,
+    target: Vector3<f32>,
+    forward_axis: Vector3<f32>,
+) -> Option<(Handle<Node>, UnitQuaternion<f32>)> {
+    let forward = graph.global_rotation(bone) * forward_axis;
+    let delta =
+        UnitQuaternion::rotation_between(&forward, &(target - graph[bone].global_position()))?;
+    let parent_rotation = parent_global_rotation(graph, bone);
+    Some((bone, apply_world_delta(graph, bone, parent_rotation, delta)))
+}
+
+fn any_perpendicular(v: Vector3<f32>) -> Vector3<f32> {
+    let axis = if v.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    v.cross(&axis).try_normalize(f32::EPSILON).unwrap_or(axis)
+}
+
+/// Calculates desired positions of three joints of a two-bone chain, so the last joint reaches the
+/// target (or gets as close as possible to it).
+fn solve_two_bone(
+    positions: &[Vector3<f32>],
+    target: Vector3<f32>,
+    pole: Option<Vector3<f32>>,
+) -> [Vector3<f32>; 3] {
+    let a = positions[0];
+    let b = positions[1];
+    let c = positions[2];
+
+    let upper_len = (b - a).norm();
+    let lower_len = (c - b).norm();
+
+    let to_target = target - a;
+    let Some(direction) = to_target.try_normalize(f32::EPSILON) else {
+        return [a, b, c];
+    };
+
+    let min_len = (upper_len - lower_len).abs();
+    let max_len = upper_len + lower_len;
+    let distance = to_target.norm().clamp(
+        min_len + f32::EPSILON,
+        (max_len - f32::EPSILON).max(min_len),
+    );
+
+    // The middle joint bends towards the pole target, or keeps its current bending direction.
+    let hint = pole.unwrap_or(b) - a;
+    let bend = (hint - direction.scale(hint.dot(&direction)))
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(|| any_perpendicular(direction));
+
+    // Law of cosines.
+    let x =
+        (upper_len * upper_len - lower_len * lower_len + distance * distance) / (2.0 * distance);
+    let y = (upper_len * upper_len - x * x).max(0.0).sqrt();
+
+    [
+        a,
+        a + direction.scale(x) + bend.scale(y),
+        a + direction.scale(distance),
+    ]
+}
+
+/// Calculates desired positions of the joints of a chain using FABRIK algorithm.
+fn solve_fabrik(
+    positions: &[Vector3<f32>],
+    target: Vector3<f32>,
+    iterations: u32,
+    tolerance: f32,
+) -> Vec<Vector3<f32>> {
+    let mut joints = positions.to_vec();
+    let lengths = positions
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).norm())
+        .collect::<Vec<_>>();
+    let root = joints[0];
+    let last = joints.len() - 1;
+
+    if (target - root).norm() >= lengths.iter().sum::<f32>() {
+        // Target is unreachable, stretch the chain towards it.
+        let direction = (target - root)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::z);
+        for i in 0..last {
+            joints[i + 1] = joints[i] + direction.scale(lengths[i]);
+        }
+        return joints;
+    }
+
+    for _ in 0..iterations {
+        if (joints[last] - target).norm() <= tolerance {
+            break;
+        }
+
+        // Backward pass - from the effector to the root.
+        joints[last] = target;
+        for i in (0..last).rev() {
+            let direction = (joints[i] - joints[i + 1])
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y);
+            joints[i] = joints[i + 1] + direction.scale(lengths[i]);
+        }
+
+        // Forward pass - from the root to the effector.
+        joints[0] = root;
+        for i in 0..last {
+            let direction = (joints[i + 1] - joints[i])
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y);
+            joints[i + 1] = joints[i] + direction.scale(lengths[i]);
+        }
+    }
+
+    joints
+}
+
+/// Allows you to create IK chain node in declarative manner.
+pub struct IkChainBuilder {
+    base_builder: BaseBuilder,
+    root: Handle<Node>,
+    effector: Handle<Node>,
+    target: Handle<Node>,
+    weight: f32,
+    solver: IkSolver,
+}
+
+impl IkChainBuilder {
+    /// Creates new IK chain builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            root: Default::default(),
+            effector: Default::default(),
+            target: Default::default(),
+            weight: 1.0,
+            solver: Default::default(),
+        }
+    }
+
+    /// Sets the first bone of the chain.
+    pub fn with_root(mut self, root: Handle<Node>) -> Self {
+        self.root = root;
+        self
+    }
+
+    /// Sets the last bone of the chain.
+    pub fn with_effector(mut self, effector: Handle<Node>) -> Self {
+        self.effector = effector;
+        self
+    }
+
+    /// Sets a node which position should be reached by the effector.
+    pub fn with_target(mut self, target: Handle<Node>) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets blending weight of the chain.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the solver of the chain.
+    pub fn with_solver(mut self, solver: IkSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    /// Creates new IK chain node.
+    pub fn build_node(self) -> Node {
+        Node::new(IkChain {
+            base: self.base_builder.build_base(),
+            root: self.root.into(),
+            effector: self.effector.into(),
+            target: self.target.into(),
+            weight: self.weight.into(),
+            solver: self.solver.into(),
+        })
+    }
+
+    /// Creates new IK chain node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        core::pool::Handle,
+        scene::{
+            animation::ik::{FabrikSolver, IkChainBuilder, IkSolver, LookAtSolver, TwoBoneSolver},
+            base::BaseBuilder,
+            graph::Graph,
+            node::Node,
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn make_bone(
+        graph: &mut Graph,
+        offset: Vector3<f32>,
+        children: &[Handle<Node>],
+    ) -> Handle<Node> {
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(TransformBuilder::new().with_local_position(offset).build())
+                .with_children(children),
+        )
+        .build(graph)
+    }
+
+    fn make_chain(graph: &mut Graph, count: usize) -> Vec<Handle<Node>> {
+        let mut bones = Vec::new();
+        let mut child = Handle::NONE;
+        for i in 0..count {
+            let children = if child.is_some() { vec![child] } else { vec![] };
+            let offset = if i + 1 == count {
+                Vector3::default()
+            } else {
+                Vector3::new(0.0, 1.0, 0.0)
+            };
+            child = make_bone(graph, offset, &children);
+            bones.push(child);
+        }
+        bones.reverse();
+        bones
+    }
+
+    fn assert_reached(graph: &Graph, effector: Handle<Node>, target: Vector3<f32>) {
+        let position = graph[effector].global_position();
+        assert!(
+            (position - target).norm() < 0.01,
+            "{:?} != {:?}",
+            position,
+            target
+        );
+    }
+
+    #[test]
+    fn test_two_bone_ik() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 3);
+        let target_position = Vector3::new(1.0, 1.0, 0.0);
+        let target = make_bone(&mut graph, target_position, &[]);
+        let pole = make_bone(&mut graph, Vector3::new(0.0, 1.0, 1.0), &[]);
+
+        IkChainBuilder::new(BaseBuilder::new())
+            .with_root(bones[0])
+            .with_effector(bones[2])
+            .with_target(target)
+            .with_solver(IkSolver::TwoBone(TwoBoneSolver { pole_target: pole }))
+            .build(&mut graph);
+
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        assert_reached(&graph, bones[2], target_position);
+        // The middle joint must bend towards the pole.
+        assert!(graph[bones[1]].global_position().z > 0.0);
+    }
+
+    #[test]
+    fn test_fabrik_ik() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 5);
+        let target_position = Vector3::new(2.0, 1.0, 1.0);
+        let target = make_bone(&mut graph, target_position, &[]);
+
+        IkChainBuilder::new(BaseBuilder::new())
+            .with_root(bones[0])
+            .with_effector(bones[4])
+            .with_target(target)
+            .with_solver(IkSolver::Fabrik(FabrikSolver {
+                iterations: 32,
+                tolerance: 0.0001,
+            }))
+            .build(&mut graph);
+
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        assert_reached(&graph, bones[4], target_position);
+    }
+
+    #[test]
+    fn test_look_at_ik() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 2);
+        let target = make_bone(&mut graph, Vector3::new(5.0, 1.0, 0.0), &[]);
+
+        IkChainBuilder::new(BaseBuilder::new())
+            .with_effector(bones[1])
+            .with_target(target)
+            .with_solver(IkSolver::LookAt(LookAtSolver::default()))
+            .build(&mut graph);
+
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        let forward = graph.global_rotation(bones[1]) * Vector3::z();
+        assert!((forward - Vector3::x()).norm() < 0.01);
+    }
+}
@@ -12,7 +12,10 @@ use crate::{
         visitor::prelude::*,
         TypeUuidProvider,
     },
-    generic_animation::value::{BoundValueCollection, TrackValue, ValueBinding},
+    generic_animation::{
+        value::{BoundValueCollection, TrackValue, ValueBinding},
+        AnimationEvent,
+    },
     scene::{
         base::{Base, BaseBuilder},
         graph::{Graph, NodePool},
@@ -22,6 +25,7 @@ use crate::{
 use std::ops::{Deref, DerefMut};
 
 pub mod absm;
+pub mod ik;
 pub mod spritesheet;
 
 /// Scene specific animation.
@@ -38,9 +42,9 @@ pub type NodePose = crate::generic_animation::NodePose<Handle<Node>>;
 /// Standard prelude for animations, that contains all most commonly used types and traits.
 pub mod prelude {
     pub use super::{
-        Animation, AnimationContainer, AnimationContainerExt, AnimationPlayer,
-        AnimationPlayerBuilder, AnimationPose, AnimationPoseExt, BoundValueCollectionExt, NodePose,
-        Track,
+        Animation, AnimationContainer, AnimationContainerExt, AnimationEventMessage,
+        AnimationPlayer, AnimationPlayerBuilder, AnimationPose, AnimationPoseExt,
+        BoundValueCollectionExt, NodePose, Track,
     };
     pub use crate::generic_animation::{
         container::{TrackDataContainer, TrackValueKind},
@@ -50,6 +54,60 @@ pub mod prelude {
     };
 }
 
+/// A script message, that is sent by the engine when an animation of an [`AnimationPlayer`] passes
+/// one of its signals. The message is sent hierarchically (up in the hierarchy) starting from the
+/// animation player, so any script on the animation player or on any of its ancestors can receive
+/// it. To do so, the script must subscribe to the messages of this type:
+///
+/// ```rust
+/// use fyrox::{
+///     core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*, type_traits::prelude::*},
+///     core::TypeUuidProvider,
+///     scene::animation::AnimationEventMessage,
+///     script::{ScriptContext, ScriptMessageContext, ScriptMessagePayload, ScriptTrait},
+/// };
+///
+/// #[derive(Reflect, Visit, Debug, Clone, ComponentProvider)]
+/// struct Character {}
+///
+/// # impl TypeUuidProvider for Character {
+/// #     fn type_uuid() -> Uuid {
+/// #         todo!();
+/// #     }
+/// # }
+///
+/// impl ScriptTrait for Character {
+///     fn on_start(&mut self, ctx: &mut ScriptContext) {
+///         ctx.message_dispatcher
+///             .subscribe_to::<AnimationEventMessage>(ctx.handle)
+///     }
+///
+///     fn on_message(
+///         &mut self,
+///         message: &mut dyn ScriptMessagePayload,
+///         ctx: &mut ScriptMessageContext,
+///     ) {
+///         if let Some(message) = message.downcast_ref::<AnimationEventMessage>() {
+///             if message.event.name == "Footstep" {
+///                 // Play footstep sound here.
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// Events are still put in the events queue of the animation, so they could be fetched manually
+/// using [`Animation::pop_event`] as well.
+#[derive(Debug, Clone)]
+pub struct AnimationEventMessage {
+    /// A handle of the animation player, that contains the animation.
+    pub animation_player: Handle<Node>,
+    /// A handle of the animation, that produced the event.
+    pub animation: Handle<Animation>,
+    /// The event itself.
+    pub event: AnimationEvent,
+}
+
 /// Extension trait for [`AnimationContainer`].
 pub trait AnimationContainerExt {
     /// Updates all animations in the container and applies their poses to respective nodes. This method is intended to
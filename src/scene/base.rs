@@ -360,6 +360,12 @@ pub struct Base {
     #[reflect(setter = "set_frustum_culling")]
     frustum_culling: InheritableVariable<bool>,
 
+    #[reflect(setter = "set_occluder")]
+    occluder: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_occlusion_culling")]
+    occlusion_culling: InheritableVariable<bool>,
+
     #[reflect(hidden)]
     pub(crate) transform_modified: Cell<bool>,
 
@@ -710,6 +716,33 @@ impl Base {
             .set_value_and_mark_modified(frustum_culling)
     }
 
+    /// Returns `true` if the node hides other nodes behind it during occlusion culling.
+    #[inline]
+    pub fn is_occluder(&self) -> bool {
+        *self.occluder
+    }
+
+    /// Sets whether the node hides other nodes behind it during occlusion culling or not. Only
+    /// non-skinned meshes could be occluders, it is better to use large and simple meshes (walls,
+    /// floors, etc.) as occluders, because their triangles are rasterized on CPU.
+    #[inline]
+    pub fn set_occluder(&mut self, occluder: bool) -> bool {
+        self.occluder.set_value_and_mark_modified(occluder)
+    }
+
+    /// Returns `true` if the node could be hidden by occluders.
+    #[inline]
+    pub fn occlusion_culling(&self) -> bool {
+        *self.occlusion_culling
+    }
+
+    /// Sets whether the node could be hidden by occluders or not.
+    #[inline]
+    pub fn set_occlusion_culling(&mut self, occlusion_culling: bool) -> bool {
+        self.occlusion_culling
+            .set_value_and_mark_modified(occlusion_culling)
+    }
+
     /// Returns true if the node should cast shadows, false - otherwise.
     #[inline]
     pub fn cast_shadows(&self) -> bool {
@@ -962,6 +995,10 @@ impl Visit for Base {
         let _ = self.properties.visit("Properties", &mut region);
         let _ = self.frustum_culling.visit("FrustumCulling", &mut region);
         let _ = self.cast_shadows.visit("CastShadows", &mut region);
+        let _ = self.occluder.visit("Occluder", &mut region);
+        let _ = self
+            .occlusion_culling
+            .visit("OcclusionCulling", &mut region);
         let _ = self.instance_id.visit("InstanceId", &mut region);
         let _ = self.enabled.visit("Enabled", &mut region);
 
@@ -999,6 +1036,8 @@ pub struct BaseBuilder {
     tag: String,
     frustum_culling: bool,
     cast_shadows: bool,
+    occluder: bool,
+    occlusion_culling: bool,
     script: Option<Script>,
     instance_id: SceneNodeId,
     enabled: bool,
@@ -1027,6 +1066,8 @@ impl BaseBuilder {
             tag: Default::default(),
             frustum_culling: true,
             cast_shadows: true,
+            occluder: false,
+            occlusion_culling: true,
             script: None,
             instance_id: SceneNodeId(Uuid::new_v4()),
             enabled: true,
@@ -1123,6 +1164,20 @@ impl BaseBuilder {
         self
     }
 
+    /// Sets whether the node hides other nodes behind it during occlusion culling or not.
+    #[inline]
+    pub fn with_occluder(mut self, occluder: bool) -> Self {
+        self.occluder = occluder;
+        self
+    }
+
+    /// Sets whether the node could be hidden by occluders or not.
+    #[inline]
+    pub fn with_occlusion_culling(mut self, occlusion_culling: bool) -> Self {
+        self.occlusion_culling = occlusion_culling;
+        self
+    }
+
     /// Sets whether mesh should cast shadows or not.
     #[inline]
     pub fn with_cast_shadows(mut self, cast_shadows: bool) -> Self {
@@ -1169,6 +1224,8 @@ impl BaseBuilder {
             transform_modified: Cell::new(false),
             frustum_culling: self.frustum_culling.into(),
             cast_shadows: self.cast_shadows.into(),
+            occluder: self.occluder.into(),
+            occlusion_culling: self.occlusion_culling.into(),
             script: self.script,
             instance_id: SceneNodeId(Uuid::new_v4()),
             enabled: self.enabled.into(),
@@ -285,6 +285,39 @@ impl Default for Exposure {
     }
 }
 
+/// Tone mapping operator, that is used to convert high dynamic range frame into low dynamic range
+/// one. Different operators give different contrast and color response, especially on very bright
+/// areas of the frame.
+#[derive(
+    Visit,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Default,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum ToneMapping {
+    /// Simple exponential operator: `1 - exp(-color * exposure)`. This is default option.
+    #[default]
+    Exponential,
+    /// Classic Reinhard operator: `color / (1 + color)`. It preserves details in dark areas, but
+    /// makes bright areas of the frame look washed out.
+    Reinhard,
+    /// Filmic operator (also known as Uncharted 2 or Hable operator). It imitates response curve
+    /// of a film and gives good contrast with soft highlights.
+    Filmic,
+    /// An approximation of the Academy Color Encoding System (ACES) reference rendering transform.
+    /// It gives high contrast and slightly saturated image, commonly used in modern games.
+    Aces,
+}
+
+uuid_provider!(ToneMapping = "b3c6e74e-3a0c-4b0e-9d4f-0f6fa8a0d5c1");
+
 /// Camera allows you to see world from specific point in world. You must have at least one camera in
 /// your scene to see anything.
 ///
@@ -330,12 +363,57 @@ pub struct Camera {
     #[reflect(setter = "set_exposure")]
     exposure: InheritableVariable<Exposure>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_eye_adaptation_speed",
+        min_value = 0.0,
+        step = 0.05,
+        description = "Speed of automatic eye adaptation to changes of frame luminance. Has effect \
+        only with automatic exposure."
+    )]
+    eye_adaptation_speed: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_tone_mapping",
+        description = "Tone mapping operator, that is used to convert HDR frame into LDR frame."
+    )]
+    tone_mapping: InheritableVariable<ToneMapping>,
+
     #[reflect(setter = "set_color_grading_lut")]
     color_grading_lut: InheritableVariable<Option<ColorGradingLut>>,
 
     #[reflect(setter = "set_color_grading_enabled")]
     color_grading_enabled: InheritableVariable<bool>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_screen_space_reflections_enabled",
+        description = "Whether screen-space reflections should be rendered for this camera or not. \
+        Has no effect if screen-space reflections are disabled in quality settings."
+    )]
+    screen_space_reflections_enabled: InheritableVariable<bool>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_temporal_anti_aliasing_enabled",
+        description = "Whether temporal anti-aliasing should be used for this camera or not. When \
+        enabled, it replaces FXAA for this camera."
+    )]
+    temporal_anti_aliasing_enabled: InheritableVariable<bool>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    jitter_index: usize,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    jitter: Vector2<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    unjittered_projection_matrix: Matrix4<f32>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     view_matrix: Matrix4<f32>,
@@ -393,6 +471,21 @@ pub enum FitParameters {
     },
 }
 
+/// Amount of unique sub-pixel offsets used by temporal anti-aliasing.
+const TAA_JITTER_SEQUENCE_LENGTH: usize = 8;
+
+/// Calculates `index`-th element of the Halton low-discrepancy sequence with the given base.
+fn halton(mut index: usize, base: usize) -> f32 {
+    let mut fraction = 1.0;
+    let mut result = 0.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
 impl Camera {
     /// Explicitly calculates view and projection matrices. Normally, you should not call
     /// this method, it will be called automatically when new frame starts.
@@ -403,7 +496,27 @@ impl Camera {
         let up = self.base.up_vector();
 
         self.view_matrix = Matrix4::look_at_rh(&Point3::from(pos), &Point3::from(pos + look), &up);
-        self.projection_matrix = self.projection.matrix(frame_size);
+        self.unjittered_projection_matrix = self.projection.matrix(frame_size);
+
+        if *self.temporal_anti_aliasing_enabled {
+            // Shift the projection by a sub-pixel offset every frame, so temporal anti-aliasing
+            // could accumulate multiple samples per pixel over time.
+            self.jitter_index = (self.jitter_index + 1) % TAA_JITTER_SEQUENCE_LENGTH;
+            let offset = Vector2::new(
+                halton(self.jitter_index + 1, 2) - 0.5,
+                halton(self.jitter_index + 1, 3) - 0.5,
+            );
+            self.jitter = Vector2::new(
+                2.0 * offset.x / frame_size.x.max(1.0),
+                2.0 * offset.y / frame_size.y.max(1.0),
+            );
+        } else {
+            self.jitter = Vector2::default();
+        }
+
+        self.projection_matrix =
+            Matrix4::new_translation(&Vector3::new(self.jitter.x, self.jitter.y, 0.0))
+                * self.unjittered_projection_matrix;
     }
 
     /// Sets new viewport in resolution-independent format. In other words
@@ -457,6 +570,26 @@ impl Camera {
         self.projection_matrix
     }
 
+    /// Returns current projection matrix without sub-pixel jitter of temporal anti-aliasing. It is
+    /// the same as [`Self::projection_matrix`], if temporal anti-aliasing is disabled.
+    #[inline]
+    pub fn unjittered_projection_matrix(&self) -> Matrix4<f32> {
+        self.unjittered_projection_matrix
+    }
+
+    /// Returns current view-projection matrix without sub-pixel jitter of temporal anti-aliasing.
+    #[inline]
+    pub fn unjittered_view_projection_matrix(&self) -> Matrix4<f32> {
+        self.unjittered_projection_matrix * self.view_matrix
+    }
+
+    /// Returns current sub-pixel offset (in normalized device coordinates) of the projection matrix.
+    /// It is always zero, if temporal anti-aliasing is disabled.
+    #[inline]
+    pub fn jitter(&self) -> Vector2<f32> {
+        self.jitter
+    }
+
     /// Returns current view matrix.
     #[inline]
     pub fn view_matrix(&self) -> Matrix4<f32> {
@@ -679,6 +812,32 @@ impl Camera {
         *self.color_grading_enabled
     }
 
+    /// Enables or disables screen-space reflections for the camera. Screen-space reflections must
+    /// also be enabled in renderer's quality settings, otherwise this flag has no effect.
+    pub fn set_screen_space_reflections_enabled(&mut self, enable: bool) -> bool {
+        self.screen_space_reflections_enabled
+            .set_value_and_mark_modified(enable)
+    }
+
+    /// Whether screen-space reflections enabled for the camera or not.
+    pub fn screen_space_reflections_enabled(&self) -> bool {
+        *self.screen_space_reflections_enabled
+    }
+
+    /// Enables or disables temporal anti-aliasing (TAA) for the camera. TAA jitters the projection
+    /// matrix of the camera by a sub-pixel offset every frame and blends current frame with the
+    /// history of previous frames. It produces much sharper image than FXAA, which is not used for
+    /// the camera when TAA is enabled.
+    pub fn set_temporal_anti_aliasing_enabled(&mut self, enable: bool) -> bool {
+        self.temporal_anti_aliasing_enabled
+            .set_value_and_mark_modified(enable)
+    }
+
+    /// Whether temporal anti-aliasing enabled for the camera or not.
+    pub fn temporal_anti_aliasing_enabled(&self) -> bool {
+        *self.temporal_anti_aliasing_enabled
+    }
+
     /// Sets new exposure. See `Exposure` struct docs for more info.
     pub fn set_exposure(&mut self, exposure: Exposure) -> Exposure {
         self.exposure.set_value_and_mark_modified(exposure)
@@ -688,6 +847,28 @@ impl Camera {
     pub fn exposure(&self) -> Exposure {
         *self.exposure
     }
+
+    /// Sets new speed of automatic eye adaptation. The higher the value, the faster the camera
+    /// adapts to changes of frame luminance. Has effect only with [`Exposure::Auto`].
+    pub fn set_eye_adaptation_speed(&mut self, speed: f32) -> f32 {
+        self.eye_adaptation_speed
+            .set_value_and_mark_modified(speed.max(0.0))
+    }
+
+    /// Returns current speed of automatic eye adaptation.
+    pub fn eye_adaptation_speed(&self) -> f32 {
+        *self.eye_adaptation_speed
+    }
+
+    /// Sets new tone mapping operator. See [`ToneMapping`] docs for more info.
+    pub fn set_tone_mapping(&mut self, tone_mapping: ToneMapping) -> ToneMapping {
+        self.tone_mapping.set_value_and_mark_modified(tone_mapping)
+    }
+
+    /// Returns current tone mapping operator.
+    pub fn tone_mapping(&self) -> ToneMapping {
+        *self.tone_mapping
+    }
 }
 
 impl NodeTrait for Camera {
@@ -1012,8 +1193,12 @@ pub struct CameraBuilder {
     skybox: SkyBoxKind,
     environment: Option<TextureResource>,
     exposure: Exposure,
+    eye_adaptation_speed: f32,
+    tone_mapping: ToneMapping,
     color_grading_lut: Option<ColorGradingLut>,
     color_grading_enabled: bool,
+    screen_space_reflections_enabled: bool,
+    temporal_anti_aliasing_enabled: bool,
     projection: Projection,
 }
 
@@ -1030,8 +1215,12 @@ impl CameraBuilder {
             skybox: SkyBoxKind::Builtin,
             environment: None,
             exposure: Exposure::Manual(std::f32::consts::E),
+            eye_adaptation_speed: 0.3,
+            tone_mapping: ToneMapping::default(),
             color_grading_lut: None,
             color_grading_enabled: false,
+            screen_space_reflections_enabled: true,
+            temporal_anti_aliasing_enabled: false,
             projection: Projection::default(),
         }
     }
@@ -1096,12 +1285,36 @@ impl CameraBuilder {
         self
     }
 
+    /// Sets whether screen-space reflections should be enabled or not.
+    pub fn with_screen_space_reflections_enabled(mut self, enabled: bool) -> Self {
+        self.screen_space_reflections_enabled = enabled;
+        self
+    }
+
+    /// Sets whether temporal anti-aliasing should be enabled or not.
+    pub fn with_temporal_anti_aliasing_enabled(mut self, enabled: bool) -> Self {
+        self.temporal_anti_aliasing_enabled = enabled;
+        self
+    }
+
     /// Sets desired exposure options.
     pub fn with_exposure(mut self, exposure: Exposure) -> Self {
         self.exposure = exposure;
         self
     }
 
+    /// Sets desired speed of automatic eye adaptation.
+    pub fn with_eye_adaptation_speed(mut self, speed: f32) -> Self {
+        self.eye_adaptation_speed = speed;
+        self
+    }
+
+    /// Sets desired tone mapping operator.
+    pub fn with_tone_mapping(mut self, tone_mapping: ToneMapping) -> Self {
+        self.tone_mapping = tone_mapping;
+        self
+    }
+
     /// Sets desired projection mode.
     pub fn with_projection(mut self, projection: Projection) -> Self {
         self.projection = projection;
@@ -1119,6 +1332,9 @@ impl CameraBuilder {
             // recalculated before rendering.
             view_matrix: Matrix4::identity(),
             projection_matrix: Matrix4::identity(),
+            unjittered_projection_matrix: Matrix4::identity(),
+            jitter: Vector2::default(),
+            jitter_index: 0,
             sky_box: InheritableVariable::new_modified(match self.skybox {
                 SkyBoxKind::Builtin => Some(SkyBoxKind::built_in_skybox().clone()),
                 SkyBoxKind::None => None,
@@ -1126,8 +1342,12 @@ impl CameraBuilder {
             }),
             environment: self.environment.into(),
             exposure: self.exposure.into(),
+            eye_adaptation_speed: self.eye_adaptation_speed.max(0.0).into(),
+            tone_mapping: self.tone_mapping.into(),
             color_grading_lut: self.color_grading_lut.into(),
             color_grading_enabled: self.color_grading_enabled.into(),
+            screen_space_reflections_enabled: self.screen_space_reflections_enabled.into(),
+            temporal_anti_aliasing_enabled: self.temporal_anti_aliasing_enabled.into(),
         }
     }
 
@@ -1529,3 +1749,40 @@ impl SkyBox {
         self.back.clone()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        scene::{base::BaseBuilder, camera::CameraBuilder},
+    };
+
+    #[test]
+    fn test_temporal_anti_aliasing_jitter() {
+        let frame_size = Vector2::new(800.0, 600.0);
+
+        let mut camera = CameraBuilder::new(BaseBuilder::new()).build_camera();
+        camera.calculate_matrices(frame_size);
+        assert_eq!(camera.jitter(), Vector2::default());
+        assert_eq!(
+            camera.projection_matrix(),
+            camera.unjittered_projection_matrix()
+        );
+
+        camera.set_temporal_anti_aliasing_enabled(true);
+        let mut jitters = Vec::new();
+        for _ in 0..8 {
+            camera.calculate_matrices(frame_size);
+            let jitter = camera.jitter();
+            // Offset must not exceed half of a pixel.
+            assert!(jitter.x.abs() <= 1.0 / frame_size.x);
+            assert!(jitter.y.abs() <= 1.0 / frame_size.y);
+            assert_ne!(
+                camera.projection_matrix(),
+                camera.unjittered_projection_matrix()
+            );
+            assert!(!jitters.contains(&jitter));
+            jitters.push(jitter);
+        }
+    }
+}
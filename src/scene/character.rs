@@ -0,0 +1,546 @@
+//! Kinematic character controller moves a rigid body using shape casts, instead of forces. See
+//! [`CharacterController`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::{
+            physics::{CharacterMotion, Intersection, RayCastOptions},
+            Graph,
+        },
+        node::{Node, NodeTrait, UpdateContext},
+        rigidbody::RigidBody,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Character controller is a node, that moves a kinematic rigid body (usually with a capsule
+/// collider) using a series of shape casts. It implements the classic "move-and-slide" approach:
+/// the capsule slides along walls, climbs steps and slopes that are not too steep and stays
+/// snapped to the ground when walking down stairs or slopes. It also falls under the gravity of
+/// the physics world and follows moving platforms (any rigid bodies) it stands on.
+///
+/// # Setup
+///
+/// 1) Create a rigid body with [`crate::scene::rigidbody::RigidBodyType::KinematicPositionBased`]
+/// type and a capsule collider, that matches the size of the controller.
+/// 2) Create a character controller node (usually as a child of the body) and assign the body to
+/// it using [`CharacterController::set_body`].
+///
+/// The controller uses its own capsule (defined by its radius and height) for the shape casts, the
+/// colliders of the body are ignored.
+///
+/// # Movement
+///
+/// Call [`CharacterController::set_desired_velocity`] every frame (usually from a script) to move
+/// the character and [`CharacterController::jump`] to make it jump. Gravity is applied
+/// automatically.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         character::CharacterControllerBuilder,
+/// #         collider::{ColliderBuilder, ColliderShape},
+/// #         graph::Graph,
+/// #         node::Node,
+/// #         rigidbody::{RigidBodyBuilder, RigidBodyType},
+/// #     },
+/// # };
+/// fn create_player(graph: &mut Graph) -> Handle<Node> {
+///     let collider = ColliderBuilder::new(BaseBuilder::new())
+///         .with_shape(ColliderShape::capsule_y(0.55, 0.35))
+///         .build(graph);
+///
+///     let body = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+///         .with_body_type(RigidBodyType::KinematicPositionBased)
+///         .build(graph);
+///
+///     let controller = CharacterControllerBuilder::new(BaseBuilder::new())
+///         .with_body(body)
+///         .with_radius(0.35)
+///         .with_height(1.8)
+///         .build(graph);
+///
+///     graph.link_nodes(controller, body);
+///
+///     body
+/// }
+/// ```
+#[derive(Clone, Reflect, Visit, Debug)]
+pub struct CharacterController {
+    base: Base,
+
+    #[reflect(
+        setter = "set_body",
+        description = "A kinematic rigid body that will be moved by the controller."
+    )]
+    body: InheritableVariable<Handle<Node>>,
+
+    #[reflect(
+        setter = "set_radius",
+        min_value = 0.0,
+        step = 0.01,
+        description = "Radius of the capsule of the character."
+    )]
+    radius: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_height",
+        min_value = 0.0,
+        step = 0.01,
+        description = "Total height of the capsule of the character."
+    )]
+    height: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_skin_width",
+        min_value = 0.0,
+        step = 0.005,
+        description = "A small gap that is preserved between the capsule and the obstacles."
+    )]
+    skin_width: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_step_height",
+        min_value = 0.0,
+        step = 0.01,
+        description = "Maximum height of the steps that the character could climb."
+    )]
+    step_height: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_max_slope_angle",
+        min_value = 0.0,
+        max_value = 1.57,
+        step = 0.01,
+        description = "Maximum angle (in radians) of slopes that the character could climb."
+    )]
+    max_slope_angle: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_snap_distance",
+        min_value = 0.0,
+        step = 0.01,
+        description = "Maximum distance at which the character will be snapped to the ground."
+    )]
+    snap_distance: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_gravity_scale",
+        description = "A multiplier for the gravity of the physics world."
+    )]
+    gravity_scale: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    desired_velocity: Vector3<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    vertical_velocity: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    is_grounded: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    platform: Handle<Node>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    platform_transform: Matrix4<f32>,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        CharacterControllerBuilder::new(BaseBuilder::new()).build_character_controller()
+    }
+}
+
+impl Deref for CharacterController {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for CharacterController {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for CharacterController {
+    fn type_uuid() -> Uuid {
+        uuid!("5e4b6c2d-8f1a-4a3b-9c7d-2e6f0a1b3c5d")
+    }
+}
+
+impl NodeTrait for CharacterController {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, ctx: &mut UpdateContext) {
+        if ctx.dt <= 0.0 {
+            return;
+        }
+
+        let Some(body) = ctx
+            .nodes
+            .try_borrow(*self.body)
+            .and_then(|n| n.query_component_ref::<RigidBody>())
+        else {
+            return;
+        };
+        let native = body.native.get();
+        let position = body.global_position();
+        let parent = body.parent();
+
+        if self.is_grounded && self.vertical_velocity < 0.0 {
+            self.vertical_velocity = 0.0;
+        }
+        self.vertical_velocity += ctx.physics.gravity.y * *self.gravity_scale * ctx.dt;
+
+        // Follow the movement of the platform the character stands on.
+        let mut platform_translation = Vector3::default();
+        if let Some(platform) = ctx.nodes.try_borrow(self.platform) {
+            if let Some(inv_prev_transform) = self.platform_transform.try_inverse() {
+                let new_position = (platform.global_transform() * inv_prev_transform)
+                    .transform_point(&Point3::from(position));
+                platform_translation = new_position.coords - position;
+            }
+        }
+
+        let half_height = (*self.height * 0.5 - *self.radius).max(0.0);
+        let result = ctx.physics.move_character(
+            ctx.dt,
+            CharacterMotion {
+                position,
+                translation: (self.desired_velocity
+                    + Vector3::new(0.0, self.vertical_velocity, 0.0))
+                    * ctx.dt
+                    + platform_translation,
+                radius: *self.radius,
+                half_height,
+                skin_width: *self.skin_width,
+                step_height: *self.step_height,
+                max_slope_angle: *self.max_slope_angle,
+                // Do not snap the character to the ground while it is jumping.
+                snap_distance: if self.vertical_velocity > 0.0 {
+                    0.0
+                } else {
+                    *self.snap_distance
+                },
+                exclude_body: native,
+            },
+        );
+
+        // Stop moving up when the character hits a ceiling.
+        if self.vertical_velocity > 0.0 && result.translation.y <= platform_translation.y {
+            self.vertical_velocity = 0.0;
+        }
+
+        self.is_grounded = result.grounded;
+
+        let new_position = position + result.translation;
+        let local_position = ctx
+            .nodes
+            .try_borrow(parent)
+            .and_then(|p| p.global_transform().try_inverse())
+            .map_or(new_position, |inv| {
+                inv.transform_point(&Point3::from(new_position)).coords
+            });
+        ctx.nodes[*self.body]
+            .local_transform_mut()
+            .set_position(local_position);
+
+        // Find a platform under the character.
+        self.platform = Handle::NONE;
+        if self.is_grounded {
+            let mut intersections = Vec::<Intersection>::new();
+            ctx.physics.cast_ray(
+                RayCastOptions {
+                    ray_origin: Point3::from(new_position),
+                    ray_direction: -Vector3::y(),
+                    max_len: *self.height * 0.5 + *self.skin_width + *self.snap_distance,
+                    groups: Default::default(),
+                    sort_results: true,
+                },
+                &mut intersections,
+            );
+
+            for intersection in intersections {
+                let Some(collider) = ctx.nodes.try_borrow(intersection.collider) else {
+                    continue;
+                };
+                if collider.parent() == *self.body {
+                    continue;
+                }
+                let platform = collider.parent();
+                if let Some(platform_ref) = ctx.nodes.try_borrow(platform) {
+                    self.platform = platform;
+                    self.platform_transform = platform_ref.global_transform();
+                }
+                break;
+            }
+        }
+    }
+}
+
+impl CharacterController {
+    /// Sets a kinematic rigid body that will be moved by the controller.
+    pub fn set_body(&mut self, body: Handle<Node>) -> Handle<Node> {
+        self.body.set_value_and_mark_modified(body)
+    }
+
+    /// Returns a handle of the rigid body that is moved by the controller.
+    pub fn body(&self) -> Handle<Node> {
+        *self.body
+    }
+
+    /// Sets radius of the capsule of the character.
+    pub fn set_radius(&mut self, radius: f32) -> f32 {
+        self.radius.set_value_and_mark_modified(radius.max(0.0))
+    }
+
+    /// Returns radius of the capsule of the character.
+    pub fn radius(&self) -> f32 {
+        *self.radius
+    }
+
+    /// Sets total height of the capsule of the character (including its caps).
+    pub fn set_height(&mut self, height: f32) -> f32 {
+        self.height.set_value_and_mark_modified(height.max(0.0))
+    }
+
+    /// Returns total height of the capsule of the character.
+    pub fn height(&self) -> f32 {
+        *self.height
+    }
+
+    /// Sets a small gap that is preserved between the capsule and the obstacles. It prevents the
+    /// capsule from getting stuck in the obstacles due to numerical errors.
+    pub fn set_skin_width(&mut self, skin_width: f32) -> f32 {
+        self.skin_width
+            .set_value_and_mark_modified(skin_width.max(0.0))
+    }
+
+    /// Returns the gap that is preserved between the capsule and the obstacles.
+    pub fn skin_width(&self) -> f32 {
+        *self.skin_width
+    }
+
+    /// Sets maximum height of the steps that the character could climb. Zero disables stepping.
+    pub fn set_step_height(&mut self, step_height: f32) -> f32 {
+        self.step_height
+            .set_value_and_mark_modified(step_height.max(0.0))
+    }
+
+    /// Returns maximum height of the steps that the character could climb.
+    pub fn step_height(&self) -> f32 {
+        *self.step_height
+    }
+
+    /// Sets maximum angle (in radians) of slopes that the character could climb. The character
+    /// slides down from steeper slopes.
+    pub fn set_max_slope_angle(&mut self, angle: f32) -> f32 {
+        self.max_slope_angle.set_value_and_mark_modified(angle)
+    }
+
+    /// Returns maximum angle (in radians) of slopes that the character could climb.
+    pub fn max_slope_angle(&self) -> f32 {
+        *self.max_slope_angle
+    }
+
+    /// Sets maximum distance at which the character will be snapped to the ground. It keeps the
+    /// character on the ground when it walks down stairs or slopes. Zero disables snapping.
+    pub fn set_snap_distance(&mut self, distance: f32) -> f32 {
+        self.snap_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns maximum distance at which the character will be snapped to the ground.
+    pub fn snap_distance(&self) -> f32 {
+        *self.snap_distance
+    }
+
+    /// Sets a multiplier for the gravity of the physics world.
+    pub fn set_gravity_scale(&mut self, scale: f32) -> f32 {
+        self.gravity_scale.set_value_and_mark_modified(scale)
+    }
+
+    /// Returns the multiplier for the gravity of the physics world.
+    pub fn gravity_scale(&self) -> f32 {
+        *self.gravity_scale
+    }
+
+    /// Sets desired velocity of the character. Usually it is a horizontal velocity defined by
+    /// player input, the vertical velocity is controlled by gravity and [`Self::jump`].
+    pub fn set_desired_velocity(&mut self, velocity: Vector3<f32>) {
+        self.desired_velocity = velocity;
+    }
+
+    /// Returns desired velocity of the character.
+    pub fn desired_velocity(&self) -> Vector3<f32> {
+        self.desired_velocity
+    }
+
+    /// Makes the character jump with the given vertical speed. Does nothing if the character is
+    /// not on the ground.
+    pub fn jump(&mut self, speed: f32) {
+        if self.is_grounded {
+            self.vertical_velocity = speed;
+            self.is_grounded = false;
+        }
+    }
+
+    /// Returns current vertical velocity of the character.
+    pub fn vertical_velocity(&self) -> f32 {
+        self.vertical_velocity
+    }
+
+    /// Returns `true` if the character stands on the ground, `false` - otherwise.
+    pub fn is_grounded(&self) -> bool {
+        self.is_grounded
+    }
+
+    /// Returns a handle of the node (usually a rigid body) on which the character stands.
+    pub fn platform(&self) -> Handle<Node> {
+        self.platform
+    }
+}
+
+/// Allows you to create character controllers in declarative manner.
+pub struct CharacterControllerBuilder {
+    base_builder: BaseBuilder,
+    body: Handle<Node>,
+    radius: f32,
+    height: f32,
+    skin_width: f32,
+    step_height: f32,
+    max_slope_angle: f32,
+    snap_distance: f32,
+    gravity_scale: f32,
+}
+
+impl CharacterControllerBuilder {
+    /// Creates new character controller builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            body: Default::default(),
+            radius: 0.35,
+            height: 1.8,
+            skin_width: 0.02,
+            step_height: 0.3,
+            max_slope_angle: 45.0f32.to_radians(),
+            snap_distance: 0.2,
+            gravity_scale: 1.0,
+        }
+    }
+
+    /// Sets desired body of the character.
+    pub fn with_body(mut self, body: Handle<Node>) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Sets desired radius of the capsule.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets desired total height of the capsule.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets desired skin width.
+    pub fn with_skin_width(mut self, skin_width: f32) -> Self {
+        self.skin_width = skin_width;
+        self
+    }
+
+    /// Sets desired maximum step height.
+    pub fn with_step_height(mut self, step_height: f32) -> Self {
+        self.step_height = step_height;
+        self
+    }
+
+    /// Sets desired maximum slope angle (in radians).
+    pub fn with_max_slope_angle(mut self, angle: f32) -> Self {
+        self.max_slope_angle = angle;
+        self
+    }
+
+    /// Sets desired ground snapping distance.
+    pub fn with_snap_distance(mut self, distance: f32) -> Self {
+        self.snap_distance = distance;
+        self
+    }
+
+    /// Sets desired gravity scale.
+    pub fn with_gravity_scale(mut self, scale: f32) -> Self {
+        self.gravity_scale = scale;
+        self
+    }
+
+    /// Creates new character controller instance.
+    pub fn build_character_controller(self) -> CharacterController {
+        CharacterController {
+            base: self.base_builder.build_base(),
+            body: self.body.into(),
+            radius: self.radius.into(),
+            height: self.height.into(),
+            skin_width: self.skin_width.into(),
+            step_height: self.step_height.into(),
+            max_slope_angle: self.max_slope_angle.into(),
+            snap_distance: self.snap_distance.into(),
+            gravity_scale: self.gravity_scale.into(),
+            desired_velocity: Default::default(),
+            vertical_velocity: 0.0,
+            is_grounded: false,
+            platform: Default::default(),
+            platform_transform: Matrix4::identity(),
+        }
+    }
+
+    /// Creates new character controller node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_character_controller())
+    }
+
+    /// Creates new character controller node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
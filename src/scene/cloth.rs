@@ -0,0 +1,1029 @@
+//! Cloth is a soft body, that simulates a rectangular piece of fabric. It could be used for capes,
+//! flags, banners, curtains, etc. See [`Cloth`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3, Vector4},
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        uuid_provider,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    material::{Material, MaterialResource},
+    renderer::{self, batch::RenderContext},
+    scene::{
+        base::{Base, BaseBuilder},
+        collider::InteractionGroups,
+        graph::{
+            physics::{PhysicsWorld, PointProjection},
+            Graph, NodePool,
+        },
+        mesh::{vertex::StaticVertex, RenderPath},
+        node::{Node, NodeTrait, UpdateContext},
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// A pinned vertex of a [`Cloth`]. Pinned vertices are not simulated, instead they follow their
+/// attachment.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct ClothPin {
+    /// Index of the pinned vertex. Vertices are stored row-by-row, starting from the top-left
+    /// corner of the cloth, every row has `resolution + 1` vertices.
+    pub vertex: u32,
+
+    /// A node to which the vertex is attached. It could be any node, for example a bone of a
+    /// skinned mesh. The vertex keeps its initial offset relative to the node. If the handle is
+    /// none, the vertex is attached to the cloth node itself.
+    pub attachment: Handle<Node>,
+}
+
+uuid_provider!(ClothPin = "cc614ab2-022c-4107-9afc-4d35cb359d26");
+
+#[derive(Clone, Debug, Default)]
+struct Particle {
+    position: Vector3<f32>,
+    prev_position: Vector3<f32>,
+    velocity: Vector3<f32>,
+    inv_mass: f32,
+}
+
+#[derive(Clone, Debug)]
+struct DistanceConstraint {
+    a: usize,
+    b: usize,
+    rest_length: f32,
+    bending: bool,
+}
+
+#[derive(Clone, Debug)]
+struct PinState {
+    particle: usize,
+    attachment: Handle<Node>,
+    // Position of the particle in the local space of the attachment.
+    local_position: Vector3<f32>,
+}
+
+/// Cloth is a soft body, that simulates a rectangular piece of fabric using extended position based
+/// dynamics (XPBD). The cloth is a grid of particles connected with distance constraints, that
+/// resist stretching, shearing and bending.
+///
+/// At rest, the cloth lies in the local XY plane of the node: its top edge is centered at the origin
+/// of the node and the cloth hangs down along -Y axis. Some vertices could be pinned (see
+/// [`ClothPin`]), pinned vertices follow the cloth node or any other node, for example a bone of a
+/// skinned mesh - this is how capes are attached to characters. By default, two top corners of the
+/// cloth are pinned.
+///
+/// The simulation is performed in world space, the cloth collides with every (non-sensor) collider
+/// of the scene, that passes [`Cloth::collision_groups`] filter. Only particles collide with
+/// colliders, so thin colliders could pass through large cloth cells.
+///
+/// The cloth is rendered using a dynamically updated mesh with the given material. By default, a
+/// two-sided standard material is used.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector2, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         cloth::{ClothBuilder, ClothPin},
+/// #         graph::Graph,
+/// #         node::Node,
+/// #     },
+/// # };
+/// fn create_banner(graph: &mut Graph) -> Handle<Node> {
+///     let resolution = 16;
+///     ClothBuilder::new(BaseBuilder::new())
+///         .with_size(Vector2::new(1.0, 2.0))
+///         .with_resolution(resolution)
+///         // Pin the entire top row of the banner.
+///         .with_pins(
+///             (0..=resolution)
+///                 .map(|vertex| ClothPin {
+///                     vertex,
+///                     attachment: Handle::NONE,
+///                 })
+///                 .collect(),
+///         )
+///         .build(graph)
+/// }
+/// ```
+#[derive(Clone, Reflect, Visit, Debug)]
+pub struct Cloth {
+    base: Base,
+
+    #[reflect(
+        setter = "set_size",
+        description = "Width and height of the cloth in meters."
+    )]
+    size: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(
+        setter = "set_resolution",
+        min_value = 1.0,
+        description = "Amount of cells along each side of the cloth. Higher values produce more \
+        detailed cloth, but significantly increase the time needed for simulation."
+    )]
+    resolution: InheritableVariable<u32>,
+
+    #[reflect(
+        setter = "set_pins",
+        description = "A set of pinned vertices. Pinned vertices are not simulated, they follow \
+        their attachment (or the cloth node itself)."
+    )]
+    pins: InheritableVariable<Vec<ClothPin>>,
+
+    #[reflect(setter = "set_material")]
+    material: InheritableVariable<MaterialResource>,
+
+    #[reflect(
+        setter = "set_stretch_compliance",
+        min_value = 0.0,
+        step = 0.0001,
+        description = "Inverse stiffness of the cloth against stretching and shearing. Zero means \
+        inextensible cloth."
+    )]
+    stretch_compliance: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_bend_compliance",
+        min_value = 0.0,
+        step = 0.001,
+        description = "Inverse stiffness of the cloth against bending. Larger values produce \
+        softer cloth."
+    )]
+    bend_compliance: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_damping",
+        min_value = 0.0,
+        step = 0.01,
+        description = "Fraction of the velocity of the particles lost per second."
+    )]
+    damping: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_gravity_scale",
+        step = 0.1,
+        description = "A multiplier for the gravity of the physics world."
+    )]
+    gravity_scale: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_wind",
+        description = "Velocity of the wind in world space (in meters per second)."
+    )]
+    wind: InheritableVariable<Vector3<f32>>,
+
+    #[reflect(
+        setter = "set_solver_iterations",
+        min_value = 1.0,
+        description = "Amount of sub-steps per update. Higher values make the cloth stiffer and \
+        collisions more robust, but increase the time needed for simulation."
+    )]
+    solver_iterations: InheritableVariable<u32>,
+
+    #[reflect(
+        setter = "set_collisions_enabled",
+        description = "Whether the cloth collides with scene colliders or not."
+    )]
+    collisions_enabled: InheritableVariable<bool>,
+
+    #[reflect(
+        setter = "set_thickness",
+        min_value = 0.0,
+        step = 0.005,
+        description = "Minimal distance between the cloth and colliders in meters."
+    )]
+    thickness: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_friction",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05,
+        description = "Friction between the cloth and colliders. Zero - no friction, one - \
+        particles in contact do not slide at all."
+    )]
+    friction: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_collision_groups",
+        description = "Only colliders, that passes this filter, will collide with the cloth."
+    )]
+    collision_groups: InheritableVariable<InteractionGroups>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    particles: Vec<Particle>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    constraints: Vec<DistanceConstraint>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pin_states: Vec<PinState>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    needs_reset: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    world_bounds: AxisAlignedBoundingBox,
+}
+
+impl Default for Cloth {
+    fn default() -> Self {
+        ClothBuilder::new(BaseBuilder::new()).build_cloth()
+    }
+}
+
+impl Deref for Cloth {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Cloth {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Cloth {
+    fn type_uuid() -> Uuid {
+        uuid!("b8e0f1c2-5d3a-4f6e-9a7b-2c4d6e8f0a13")
+    }
+}
+
+impl Cloth {
+    /// Sets width and height of the cloth in meters. Resets the simulation.
+    pub fn set_size(&mut self, size: Vector2<f32>) -> Vector2<f32> {
+        self.needs_reset = true;
+        self.size
+            .set_value_and_mark_modified(size.sup(&Vector2::repeat(0.0)))
+    }
+
+    /// Returns width and height of the cloth in meters.
+    pub fn size(&self) -> Vector2<f32> {
+        *self.size
+    }
+
+    /// Sets amount of cells along each side of the cloth. Resets the simulation. Keep in mind,
+    /// that indices of pinned vertices depend on the resolution.
+    pub fn set_resolution(&mut self, resolution: u32) -> u32 {
+        self.needs_reset = true;
+        self.resolution
+            .set_value_and_mark_modified(resolution.max(1))
+    }
+
+    /// Returns amount of cells along each side of the cloth.
+    pub fn resolution(&self) -> u32 {
+        *self.resolution
+    }
+
+    /// Sets a new set of pinned vertices. Resets the simulation.
+    pub fn set_pins(&mut self, pins: Vec<ClothPin>) -> Vec<ClothPin> {
+        self.needs_reset = true;
+        self.pins.set_value_and_mark_modified(pins)
+    }
+
+    /// Returns a set of pinned vertices.
+    pub fn pins(&self) -> &[ClothPin] {
+        &self.pins
+    }
+
+    /// Sets a new material of the cloth.
+    pub fn set_material(&mut self, material: MaterialResource) -> MaterialResource {
+        self.material.set_value_and_mark_modified(material)
+    }
+
+    /// Returns a reference to the current material of the cloth.
+    pub fn material(&self) -> &MaterialResource {
+        &self.material
+    }
+
+    /// Sets inverse stiffness of the cloth against stretching and shearing. Zero means
+    /// inextensible cloth.
+    pub fn set_stretch_compliance(&mut self, compliance: f32) -> f32 {
+        self.stretch_compliance
+            .set_value_and_mark_modified(compliance.max(0.0))
+    }
+
+    /// Returns inverse stiffness of the cloth against stretching and shearing.
+    pub fn stretch_compliance(&self) -> f32 {
+        *self.stretch_compliance
+    }
+
+    /// Sets inverse stiffness of the cloth against bending.
+    pub fn set_bend_compliance(&mut self, compliance: f32) -> f32 {
+        self.bend_compliance
+            .set_value_and_mark_modified(compliance.max(0.0))
+    }
+
+    /// Returns inverse stiffness of the cloth against bending.
+    pub fn bend_compliance(&self) -> f32 {
+        *self.bend_compliance
+    }
+
+    /// Sets fraction of the velocity of the particles lost per second.
+    pub fn set_damping(&mut self, damping: f32) -> f32 {
+        self.damping.set_value_and_mark_modified(damping.max(0.0))
+    }
+
+    /// Returns fraction of the velocity of the particles lost per second.
+    pub fn damping(&self) -> f32 {
+        *self.damping
+    }
+
+    /// Sets a multiplier for the gravity of the physics world.
+    pub fn set_gravity_scale(&mut self, scale: f32) -> f32 {
+        self.gravity_scale.set_value_and_mark_modified(scale)
+    }
+
+    /// Returns the multiplier for the gravity of the physics world.
+    pub fn gravity_scale(&self) -> f32 {
+        *self.gravity_scale
+    }
+
+    /// Sets velocity of the wind in world space. The wind pushes the cloth along normals of its
+    /// surface, so it is possible to make fluttering flags.
+    pub fn set_wind(&mut self, wind: Vector3<f32>) -> Vector3<f32> {
+        self.wind.set_value_and_mark_modified(wind)
+    }
+
+    /// Returns velocity of the wind in world space.
+    pub fn wind(&self) -> Vector3<f32> {
+        *self.wind
+    }
+
+    /// Sets amount of sub-steps per update.
+    pub fn set_solver_iterations(&mut self, iterations: u32) -> u32 {
+        self.solver_iterations
+            .set_value_and_mark_modified(iterations.max(1))
+    }
+
+    /// Returns amount of sub-steps per update.
+    pub fn solver_iterations(&self) -> u32 {
+        *self.solver_iterations
+    }
+
+    /// Enables or disables collisions with scene colliders.
+    pub fn set_collisions_enabled(&mut self, enabled: bool) -> bool {
+        self.collisions_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the cloth collides with scene colliders, `false` - otherwise.
+    pub fn is_collisions_enabled(&self) -> bool {
+        *self.collisions_enabled
+    }
+
+    /// Sets minimal distance between the cloth and colliders in meters.
+    pub fn set_thickness(&mut self, thickness: f32) -> f32 {
+        self.thickness
+            .set_value_and_mark_modified(thickness.max(0.0))
+    }
+
+    /// Returns minimal distance between the cloth and colliders in meters.
+    pub fn thickness(&self) -> f32 {
+        *self.thickness
+    }
+
+    /// Sets friction between the cloth and colliders in `[0; 1]` range.
+    pub fn set_friction(&mut self, friction: f32) -> f32 {
+        self.friction
+            .set_value_and_mark_modified(friction.clamp(0.0, 1.0))
+    }
+
+    /// Returns friction between the cloth and colliders.
+    pub fn friction(&self) -> f32 {
+        *self.friction
+    }
+
+    /// Sets collision groups filter. Only colliders, that passes this filter, will collide with
+    /// the cloth.
+    pub fn set_collision_groups(&mut self, groups: InteractionGroups) -> InteractionGroups {
+        self.collision_groups.set_value_and_mark_modified(groups)
+    }
+
+    /// Returns collision groups filter.
+    pub fn collision_groups(&self) -> InteractionGroups {
+        *self.collision_groups
+    }
+
+    /// Returns an iterator over world-space positions of the vertices of the cloth. The iterator is
+    /// empty until the first update of the cloth.
+    pub fn vertices(&self) -> impl Iterator<Item = Vector3<f32>> + '_ {
+        self.particles.iter().map(|p| p.position)
+    }
+
+    /// Forces the cloth to return to its rest shape on the next update.
+    pub fn reset(&mut self) {
+        self.needs_reset = true;
+    }
+
+    fn row_size(&self) -> usize {
+        *self.resolution as usize + 1
+    }
+
+    fn rest_position(&self, column: usize, row: usize) -> Vector3<f32> {
+        let resolution = *self.resolution as f32;
+        Vector3::new(
+            (column as f32 / resolution - 0.5) * self.size.x,
+            -(row as f32 / resolution) * self.size.y,
+            0.0,
+        )
+    }
+
+    fn initialize(&mut self, nodes: &NodePool) {
+        let row_size = self.row_size();
+        let global_transform = self.global_transform();
+
+        self.particles.clear();
+        for row in 0..row_size {
+            for column in 0..row_size {
+                let position = global_transform
+                    .transform_point(&Point3::from(self.rest_position(column, row)))
+                    .coords;
+                self.particles.push(Particle {
+                    position,
+                    prev_position: position,
+                    velocity: Vector3::default(),
+                    inv_mass: 1.0,
+                });
+            }
+        }
+
+        let index = |column: usize, row: usize| row * row_size + column;
+        let mut links = Vec::new();
+        for row in 0..row_size {
+            for column in 0..row_size {
+                // Structural links.
+                if column + 1 < row_size {
+                    links.push((index(column, row), index(column + 1, row), false));
+                }
+                if row + 1 < row_size {
+                    links.push((index(column, row), index(column, row + 1), false));
+                }
+                // Shear links.
+                if column + 1 < row_size && row + 1 < row_size {
+                    links.push((index(column, row), index(column + 1, row + 1), false));
+                    links.push((index(column + 1, row), index(column, row + 1), false));
+                }
+                // Bending links.
+                if column + 2 < row_size {
+                    links.push((index(column, row), index(column + 2, row), true));
+                }
+                if row + 2 < row_size {
+                    links.push((index(column, row), index(column, row + 2), true));
+                }
+            }
+        }
+
+        self.constraints = links
+            .into_iter()
+            .map(|(a, b, bending)| DistanceConstraint {
+                a,
+                b,
+                rest_length: (self.particles[a].position - self.particles[b].position).norm(),
+                bending,
+            })
+            .collect();
+
+        self.pin_states.clear();
+        for pin in self.pins.iter() {
+            let Some(particle) = self.particles.get_mut(pin.vertex as usize) else {
+                continue;
+            };
+
+            particle.inv_mass = 0.0;
+
+            let attachment_transform = nodes
+                .try_borrow(pin.attachment)
+                .map_or(global_transform, |node| node.global_transform());
+            let local_position = attachment_transform
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity)
+                .transform_point(&Point3::from(particle.position))
+                .coords;
+
+            self.pin_states.push(PinState {
+                particle: pin.vertex as usize,
+                attachment: pin.attachment,
+                local_position,
+            });
+        }
+
+        self.needs_reset = false;
+    }
+
+    fn calculate_normal(&self, column: usize, row: usize) -> Vector3<f32> {
+        let row_size = self.row_size();
+        let position = |column: usize, row: usize| self.particles[row * row_size + column].position;
+
+        let horizontal =
+            position((column + 1).min(row_size - 1), row) - position(column.saturating_sub(1), row);
+        let vertical =
+            position(column, (row + 1).min(row_size - 1)) - position(column, row.saturating_sub(1));
+
+        vertical
+            .cross(&horizontal)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::z)
+    }
+
+    fn solve_constraints(&mut self, dt: f32) {
+        let stretch_alpha = *self.stretch_compliance / (dt * dt);
+        let bend_alpha = *self.bend_compliance / (dt * dt);
+
+        for constraint in self.constraints.iter() {
+            let a = &self.particles[constraint.a];
+            let b = &self.particles[constraint.b];
+
+            let w = a.inv_mass + b.inv_mass;
+            if w == 0.0 {
+                continue;
+            }
+
+            let delta = a.position - b.position;
+            let length = delta.norm();
+            if length <= f32::EPSILON {
+                continue;
+            }
+            let direction = delta.scale(1.0 / length);
+
+            let alpha = if constraint.bending {
+                bend_alpha
+            } else {
+                stretch_alpha
+            };
+            let lambda = -(length - constraint.rest_length) / (w + alpha);
+            let correction = direction.scale(lambda);
+
+            let (wa, wb) = (a.inv_mass, b.inv_mass);
+            self.particles[constraint.a].position += correction.scale(wa);
+            self.particles[constraint.b].position -= correction.scale(wb);
+        }
+    }
+
+    fn solve_collisions(&mut self, projections: &[Option<PointProjection>]) {
+        let thickness = *self.thickness;
+        let friction = *self.friction;
+
+        for (particle, projection) in self.particles.iter_mut().zip(projections) {
+            let Some(projection) = projection else {
+                continue;
+            };
+
+            if particle.inv_mass == 0.0 {
+                continue;
+            }
+
+            let delta = particle.position - projection.point;
+            let distance = delta.norm();
+
+            let normal = if projection.is_inside {
+                // The particle is inside the collider, push it out in the opposite direction.
+                match (-delta).try_normalize(f32::EPSILON) {
+                    Some(normal) => normal,
+                    None => match (particle.prev_position - particle.position)
+                        .try_normalize(f32::EPSILON)
+                    {
+                        Some(normal) => normal,
+                        None => continue,
+                    },
+                }
+            } else if distance < thickness {
+                match delta.try_normalize(f32::EPSILON) {
+                    Some(normal) => normal,
+                    None => continue,
+                }
+            } else {
+                continue;
+            };
+
+            particle.position = projection.point + normal.scale(thickness);
+
+            // Reduce tangential motion of the particle in contact.
+            let motion = particle.position - particle.prev_position;
+            let tangential_motion = motion - normal.scale(motion.dot(&normal));
+            particle.position -= tangential_motion.scale(friction);
+        }
+    }
+
+    fn simulate(&mut self, dt: f32, nodes: &NodePool, physics: &PhysicsWorld) {
+        let global_transform = self.global_transform();
+        let pin_targets = self
+            .pin_states
+            .iter()
+            .map(|pin| {
+                let transform = nodes
+                    .try_borrow(pin.attachment)
+                    .map_or(global_transform, |node| node.global_transform());
+                transform
+                    .transform_point(&Point3::from(pin.local_position))
+                    .coords
+            })
+            .collect::<Vec<_>>();
+        let pin_sources = self
+            .pin_states
+            .iter()
+            .map(|pin| self.particles[pin.particle].position)
+            .collect::<Vec<_>>();
+
+        let row_size = self.row_size();
+        let normals = (0..row_size)
+            .flat_map(|row| (0..row_size).map(move |column| (column, row)))
+            .map(|(column, row)| self.calculate_normal(column, row))
+            .collect::<Vec<_>>();
+
+        let gravity = physics.gravity.scale(*self.gravity_scale);
+        let wind = *self.wind;
+
+        let substeps = (*self.solver_iterations).max(1);
+        let h = dt / substeps as f32;
+        let damping = (1.0 - *self.damping * h).max(0.0);
+
+        let mut positions = Vec::new();
+        let mut projections = Vec::new();
+
+        for step in 0..substeps {
+            for (particle, normal) in self.particles.iter_mut().zip(normals.iter()) {
+                if particle.inv_mass == 0.0 {
+                    continue;
+                }
+
+                // Simple aerodynamic model - wind pushes the cloth along its normals.
+                let relative_wind = wind - particle.velocity;
+                let acceleration = gravity + normal.scale(normal.dot(&relative_wind));
+
+                particle.velocity += acceleration.scale(h);
+                particle.prev_position = particle.position;
+                particle.position += particle.velocity.scale(h);
+            }
+
+            // Move pinned particles smoothly towards their targets.
+            let t = (step + 1) as f32 / substeps as f32;
+            for ((pin, source), target) in self
+                .pin_states
+                .iter()
+                .zip(pin_sources.iter())
+                .zip(pin_targets.iter())
+            {
+                let particle = &mut self.particles[pin.particle];
+                particle.prev_position = particle.position;
+                particle.position = source.lerp(target, t);
+            }
+
+            self.solve_constraints(h);
+
+            if *self.collisions_enabled {
+                positions.clear();
+                positions.extend(self.particles.iter().map(|p| p.position));
+                physics.project_points(&positions, *self.collision_groups, &mut projections);
+                self.solve_collisions(&projections);
+            }
+
+            for particle in self.particles.iter_mut() {
+                if particle.inv_mass != 0.0 {
+                    particle.velocity =
+                        (particle.position - particle.prev_position).scale(damping / h);
+                }
+            }
+        }
+    }
+}
+
+impl NodeTrait for Cloth {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-self.size.x * 0.5, -self.size.y, 0.0),
+            Vector3::new(self.size.x * 0.5, 0.0, 0.0),
+        )
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        if self.particles.is_empty() {
+            self.base.world_bounding_box()
+        } else {
+            self.world_bounds
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        if !self.is_globally_enabled() {
+            return;
+        }
+
+        if self.needs_reset || self.particles.len() != self.row_size() * self.row_size() {
+            self.initialize(context.nodes);
+        }
+
+        // Clamp time step to keep the simulation stable in case of hiccups.
+        let dt = context.dt.min(1.0 / 20.0);
+        if dt > 0.0 {
+            self.simulate(dt, context.nodes, context.physics);
+        }
+
+        self.world_bounds = AxisAlignedBoundingBox::from_points(
+            &self
+                .particles
+                .iter()
+                .map(|p| p.position)
+                .collect::<Vec<_>>(),
+        );
+        self.world_bounds
+            .inflate(Vector3::repeat(2.0 * *self.thickness));
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) {
+        if !self.global_visibility()
+            || !self.is_globally_enabled()
+            || self.particles.is_empty()
+            || !ctx.frustum.is_intersects_aabb(&self.world_bounding_box())
+        {
+            return;
+        }
+
+        if renderer::is_shadow_pass(ctx.render_pass_name) && !self.cast_shadows() {
+            return;
+        }
+
+        let row_size = self.row_size();
+        let resolution = *self.resolution as f32;
+
+        let vertices = (0..row_size).flat_map(|row| {
+            (0..row_size).map(move |column| {
+                let position =
+                    |column: usize, row: usize| self.particles[row * row_size + column].position;
+
+                let tangent = (position((column + 1).min(row_size - 1), row)
+                    - position(column.saturating_sub(1), row))
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::x);
+
+                StaticVertex {
+                    position: position(column, row),
+                    tex_coord: Vector2::new(column as f32 / resolution, row as f32 / resolution),
+                    normal: self.calculate_normal(column, row),
+                    tangent: Vector4::new(tangent.x, tangent.y, tangent.z, 1.0),
+                }
+            })
+        });
+
+        let triangles = (0..row_size - 1).flat_map(|row| {
+            (0..row_size - 1).flat_map(move |column| {
+                let top_left = (row * row_size + column) as u32;
+                let top_right = top_left + 1;
+                let bottom_left = top_left + row_size as u32;
+                let bottom_right = bottom_left + 1;
+                [
+                    TriangleDefinition([top_left, bottom_left, top_right]),
+                    TriangleDefinition([top_right, bottom_left, bottom_right]),
+                ]
+            })
+        });
+
+        ctx.storage.push_triangles(
+            vertices,
+            triangles,
+            &self.material,
+            RenderPath::Deferred,
+            0,
+            0,
+            false,
+            self.self_handle,
+        )
+    }
+}
+
+/// Cloth builder allows you to construct cloth in declarative manner.
+pub struct ClothBuilder {
+    base_builder: BaseBuilder,
+    size: Vector2<f32>,
+    resolution: u32,
+    pins: Option<Vec<ClothPin>>,
+    material: MaterialResource,
+    stretch_compliance: f32,
+    bend_compliance: f32,
+    damping: f32,
+    gravity_scale: f32,
+    wind: Vector3<f32>,
+    solver_iterations: u32,
+    collisions_enabled: bool,
+    thickness: f32,
+    friction: f32,
+    collision_groups: InteractionGroups,
+}
+
+impl ClothBuilder {
+    /// Creates new cloth builder. By default, the cloth is 1x1 meters square with 16x16 cells and
+    /// its top corners are pinned to the cloth node.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            size: Vector2::new(1.0, 1.0),
+            resolution: 16,
+            pins: None,
+            material: MaterialResource::new_ok(Default::default(), Material::standard_two_sides()),
+            stretch_compliance: 0.0,
+            bend_compliance: 0.01,
+            damping: 0.5,
+            gravity_scale: 1.0,
+            wind: Vector3::default(),
+            solver_iterations: 8,
+            collisions_enabled: true,
+            thickness: 0.02,
+            friction: 0.3,
+            collision_groups: Default::default(),
+        }
+    }
+
+    /// Sets desired width and height of the cloth in meters.
+    pub fn with_size(mut self, size: Vector2<f32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets desired amount of cells along each side of the cloth.
+    pub fn with_resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution.max(1);
+        self
+    }
+
+    /// Sets desired set of pinned vertices. If not set, the top corners of the cloth are pinned.
+    pub fn with_pins(mut self, pins: Vec<ClothPin>) -> Self {
+        self.pins = Some(pins);
+        self
+    }
+
+    /// Sets desired material of the cloth.
+    pub fn with_material(mut self, material: MaterialResource) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Sets desired inverse stiffness of the cloth against stretching and shearing.
+    pub fn with_stretch_compliance(mut self, compliance: f32) -> Self {
+        self.stretch_compliance = compliance;
+        self
+    }
+
+    /// Sets desired inverse stiffness of the cloth against bending.
+    pub fn with_bend_compliance(mut self, compliance: f32) -> Self {
+        self.bend_compliance = compliance;
+        self
+    }
+
+    /// Sets desired fraction of the velocity of the particles lost per second.
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets desired multiplier for the gravity of the physics world.
+    pub fn with_gravity_scale(mut self, gravity_scale: f32) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    /// Sets desired velocity of the wind in world space.
+    pub fn with_wind(mut self, wind: Vector3<f32>) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Sets desired amount of sub-steps per update.
+    pub fn with_solver_iterations(mut self, iterations: u32) -> Self {
+        self.solver_iterations = iterations.max(1);
+        self
+    }
+
+    /// Enables or disables collisions with scene colliders.
+    pub fn with_collisions_enabled(mut self, enabled: bool) -> Self {
+        self.collisions_enabled = enabled;
+        self
+    }
+
+    /// Sets desired minimal distance between the cloth and colliders in meters.
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets desired friction between the cloth and colliders.
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets desired collision groups filter.
+    pub fn with_collision_groups(mut self, groups: InteractionGroups) -> Self {
+        self.collision_groups = groups;
+        self
+    }
+
+    fn build_cloth(self) -> Cloth {
+        Cloth {
+            base: self.base_builder.build_base(),
+            size: self.size.into(),
+            resolution: self.resolution.into(),
+            pins: self
+                .pins
+                .unwrap_or_else(|| {
+                    vec![
+                        ClothPin {
+                            vertex: 0,
+                            attachment: Handle::NONE,
+                        },
+                        ClothPin {
+                            vertex: self.resolution,
+                            attachment: Handle::NONE,
+                        },
+                    ]
+                })
+                .into(),
+            material: self.material.into(),
+            stretch_compliance: self.stretch_compliance.into(),
+            bend_compliance: self.bend_compliance.into(),
+            damping: self.damping.into(),
+            gravity_scale: self.gravity_scale.into(),
+            wind: self.wind.into(),
+            solver_iterations: self.solver_iterations.into(),
+            collisions_enabled: self.collisions_enabled.into(),
+            thickness: self.thickness.into(),
+            friction: self.friction.into(),
+            collision_groups: self.collision_groups.into(),
+            particles: Default::default(),
+            constraints: Default::default(),
+            pin_states: Default::default(),
+            needs_reset: true,
+            world_bounds: Default::default(),
+        }
+    }
+
+    /// Creates new cloth node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_cloth())
+    }
+
+    /// Creates new cloth node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            cloth::{Cloth, ClothBuilder},
+            graph::Graph,
+        },
+    };
+
+    #[test]
+    fn test_cloth_pins() {
+        let mut graph = Graph::new();
+        let cloth = ClothBuilder::new(BaseBuilder::new())
+            .with_resolution(4)
+            .with_wind(Vector3::new(0.0, 0.0, 5.0))
+            .build(&mut graph);
+
+        for _ in 0..30 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let vertices = graph[cloth]
+            .cast::<Cloth>()
+            .unwrap()
+            .vertices()
+            .collect::<Vec<_>>();
+        assert_eq!(vertices.len(), 25);
+
+        // Pinned corners must stay in place.
+        assert!((vertices[0] - Vector3::new(-0.5, 0.0, 0.0)).norm() < 1.0e-4);
+        assert!((vertices[4] - Vector3::new(0.5, 0.0, 0.0)).norm() < 1.0e-4);
+
+        // While the rest of the cloth is blown away by the wind.
+        assert!(vertices[22].z > 0.01);
+    }
+}
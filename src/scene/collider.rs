@@ -14,13 +14,16 @@ use crate::{
         visitor::prelude::*,
         TypeUuidProvider,
     },
+    resource::convex_decomposition::ConvexDecompositionResource,
     scene::{
         base::{Base, BaseBuilder},
         graph::{
-            physics::{CoefficientCombineRule, ContactPair, IntersectionPair, PhysicsWorld},
+            physics::{
+                CoefficientCombineRule, CollisionEvent, ContactPair, IntersectionPair, PhysicsWorld,
+            },
             Graph,
         },
-        node::{Node, NodeTrait, SyncContext},
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
         rigidbody::RigidBody,
         Scene,
     },
@@ -28,6 +31,7 @@ use crate::{
 use fyrox_core::uuid_provider;
 use fyrox_graph::SceneGraph;
 use rapier3d::geometry::{self, ColliderHandle};
+use serde::{Deserialize, Serialize};
 use std::{
     cell::Cell,
     ops::{Add, BitAnd, BitOr, Deref, DerefMut, Mul, Not, Shl},
@@ -198,8 +202,18 @@ pub struct ConvexPolyhedronShape {
     pub geometry_source: GeometrySource,
 }
 
+/// Compound shape made of a set of convex hulls, stored in a convex decomposition resource. Convex
+/// decomposition could be generated in the editor (see "Generate Convex Decomposition..." in the
+/// context menu of mesh nodes) or using [`crate::resource::convex_decomposition::ConvexDecomposition::from_mesh`].
+/// Unlike [`TrimeshShape`], this shape could be used by dynamic rigid bodies.
+#[derive(Default, Clone, Debug, Visit, Reflect, PartialEq, Eq)]
+pub struct ConvexDecompositionShape {
+    /// A convex decomposition resource.
+    pub decomposition: Option<ConvexDecompositionResource>,
+}
+
 /// A set of bits used for pairwise collision filtering.
-#[derive(Clone, Copy, Default, PartialEq, Debug, Reflect, Eq)]
+#[derive(Clone, Copy, Default, PartialEq, Debug, Reflect, Eq, Serialize, Deserialize)]
 pub struct BitMask(pub u32);
 
 uuid_provider!(BitMask = "f2db0c2a-921b-4728-9ce4-2506d95c60fa");
@@ -341,6 +355,138 @@ impl From<geometry::InteractionGroups> for InteractionGroups {
     }
 }
 
+/// Maximum amount of collision layers. It is defined by the amount of bits in [`BitMask`].
+pub const MAX_COLLISION_LAYERS: usize = 32;
+
+/// A named collision layer. See [`CollisionLayers`] docs for more info.
+#[derive(Clone, Debug, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct CollisionLayer {
+    /// Name of the layer.
+    pub name: String,
+    /// A set of layers with which this layer collides. `N`-th bit corresponds to `N`-th layer.
+    pub collides_with: BitMask,
+}
+
+uuid_provider!(CollisionLayer = "9b7d3a1e-52c4-4f0e-8a6b-3d1f2e4c5a7b");
+
+/// A table of named collision layers, that allows you to work with collision groups using human-readable
+/// names instead of raw bit masks. Every collider belongs to exactly one layer, and layers define with
+/// which other layers they collide (the table is symmetric - if layer `A` collides with layer `B`, then
+/// layer `B` collides with layer `A`).
+///
+/// The table itself is not used by the physics, it is used only to calculate [`InteractionGroups`] for
+/// colliders (see [`CollisionLayers::interaction_groups`]), which means that changes in the table do not
+/// affect existing colliders.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox::scene::collider::CollisionLayers;
+/// let mut layers = CollisionLayers::default();
+/// layers.set_name(1, "Player");
+/// layers.set_name(2, "Debris");
+/// // Debris should not collide with the player.
+/// layers.set_collision_enabled(1, 2, false);
+/// let player_groups = layers.interaction_groups(1).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct CollisionLayers {
+    #[reflect(immutable_collection)]
+    layers: Vec<CollisionLayer>,
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self {
+            layers: (0..MAX_COLLISION_LAYERS)
+                .map(|i| CollisionLayer {
+                    name: if i == 0 {
+                        "Default".to_string()
+                    } else {
+                        format!("Layer {}", i)
+                    },
+                    collides_with: BitMask(u32::MAX),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CollisionLayers {
+    /// Returns a reference to the layer with the given index.
+    pub fn layer(&self, index: usize) -> Option<&CollisionLayer> {
+        self.layers.get(index)
+    }
+
+    /// Returns a slice with every layer.
+    pub fn layers(&self) -> &[CollisionLayer] {
+        &self.layers
+    }
+
+    /// Sets a new name of the layer with the given index.
+    pub fn set_name<S: AsRef<str>>(&mut self, index: usize, name: S) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.name = name.as_ref().to_owned();
+        }
+    }
+
+    /// Tries to find an index of a layer with the given name.
+    pub fn find_by_name<S: AsRef<str>>(&self, name: S) -> Option<usize> {
+        self.layers.iter().position(|l| l.name == name.as_ref())
+    }
+
+    /// Enables or disables collisions between two layers. The table stays symmetric.
+    pub fn set_collision_enabled(&mut self, a: usize, b: usize, enabled: bool) {
+        if a >= self.layers.len() || b >= self.layers.len() {
+            return;
+        }
+
+        for (this, other) in [(a, b), (b, a)] {
+            let mask = &mut self.layers[this].collides_with;
+            if enabled {
+                mask.0 |= 1 << other;
+            } else {
+                mask.0 &= !(1 << other);
+            }
+        }
+    }
+
+    /// Returns `true` if the two layers collide, `false` - otherwise.
+    pub fn is_collision_enabled(&self, a: usize, b: usize) -> bool {
+        self.layers
+            .get(a)
+            .map_or(false, |layer| layer.collides_with.0 & (1 << b) != 0)
+    }
+
+    /// Makes the table symmetric by mirroring every cell that was changed compared to the `prev` table.
+    /// It is useful when the table is edited cell-by-cell (for example, using reflection).
+    pub fn mirror_changes(&mut self, prev: &CollisionLayers) {
+        for a in 0..self.layers.len() {
+            for b in 0..self.layers.len() {
+                let enabled = self.is_collision_enabled(a, b);
+                if enabled != prev.is_collision_enabled(a, b)
+                    && enabled != self.is_collision_enabled(b, a)
+                {
+                    self.set_collision_enabled(a, b, enabled);
+                }
+            }
+        }
+    }
+
+    /// Calculates interaction groups for a collider that belongs to the layer with the given index.
+    pub fn interaction_groups(&self, index: usize) -> Option<InteractionGroups> {
+        self.layers
+            .get(index)
+            .map(|layer| InteractionGroups::new(BitMask(1 << index), layer.collides_with))
+    }
+
+    /// Tries to find an index of the layer, that produces the given interaction groups.
+    pub fn find_layer(&self, groups: &InteractionGroups) -> Option<usize> {
+        (0..self.layers.len())
+            .find(|&index| self.interaction_groups(index).as_ref() == Some(groups))
+    }
+}
+
 /// Possible collider shapes.
 #[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
 pub enum ColliderShape {
@@ -364,6 +510,8 @@ pub enum ColliderShape {
     Heightfield(HeightfieldShape),
     /// See [`ConvexPolyhedronShape`] docs.
     Polyhedron(ConvexPolyhedronShape),
+    /// See [`ConvexDecompositionShape`] docs.
+    ConvexDecomposition(ConvexDecompositionShape),
 }
 
 uuid_provider!(ColliderShape = "2e627337-71ea-4b33-a5f1-be697f705a86");
@@ -450,6 +598,13 @@ impl ColliderShape {
     pub fn heightfield(geometry_source: GeometrySource) -> Self {
         Self::Heightfield(HeightfieldShape { geometry_source })
     }
+
+    /// Initializes a compound shape defined by a convex decomposition resource.
+    pub fn convex_decomposition(decomposition: ConvexDecompositionResource) -> Self {
+        Self::ConvexDecomposition(ConvexDecompositionShape {
+            decomposition: Some(decomposition),
+        })
+    }
 }
 
 /// Collider is a geometric entity that can be attached to a rigid body to allow participate it
@@ -485,9 +640,40 @@ pub struct Collider {
     #[reflect(setter = "set_restitution_combine_rule")]
     pub(crate) restitution_combine_rule: InheritableVariable<CoefficientCombineRule>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_report_events",
+        description = "Whether the collider should generate collision events (contact and \
+        intersection begin/end) or not."
+    )]
+    pub(crate) report_events: InheritableVariable<bool>,
+
+    #[visit(optional)]
+    #[reflect(
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05,
+        setter = "set_sound_absorption",
+        description = "Defines how much sound is absorbed when it passes through the collider. It is \
+        used by sound occlusion, 0.0 - sound passes freely, 1.0 - sound is fully blocked."
+    )]
+    pub(crate) sound_absorption: InheritableVariable<f32>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) native: Cell<ColliderHandle>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) height_map_stamp: Cell<u64>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    touching: Vec<(Handle<Node>, bool)>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
 }
 
 impl Default for Collider {
@@ -503,7 +689,12 @@ impl Default for Collider {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            report_events: Default::default(),
+            sound_absorption: InheritableVariable::new_modified(1.0),
             native: Cell::new(ColliderHandle::invalid()),
+            height_map_stamp: Default::default(),
+            touching: Default::default(),
+            collision_events: Default::default(),
         }
     }
 }
@@ -535,8 +726,13 @@ impl Clone for Collider {
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
+            report_events: self.report_events.clone(),
+            sound_absorption: self.sound_absorption.clone(),
             // Do not copy. The copy will have its own native representation (for example - Rapier's collider)
             native: Cell::new(ColliderHandle::invalid()),
+            height_map_stamp: Default::default(),
+            touching: Default::default(),
+            collision_events: Default::default(),
         }
     }
 }
@@ -745,6 +941,72 @@ impl Collider {
         physics.intersections_with(self.native.get())
     }
 
+    /// Enables or disables collision events for the collider. When enabled, the collider tracks its
+    /// contacts and intersections and generates [`CollisionEvent`]s every frame when they begin or
+    /// end. The events could be fetched using [`Self::collision_events`].
+    pub fn set_report_events(&mut self, report_events: bool) -> bool {
+        if !report_events {
+            self.touching.clear();
+            self.collision_events.clear();
+        }
+        self.report_events
+            .set_value_and_mark_modified(report_events)
+    }
+
+    /// Returns `true` if the collider generates collision events, `false` - otherwise.
+    pub fn is_reporting_events(&self) -> bool {
+        *self.report_events
+    }
+
+    /// Sets how much sound is absorbed when it passes through the collider. The value must be in
+    /// `[0.0; 1.0]` range, where 0.0 means that the sound passes freely and 1.0 means that the sound
+    /// is fully blocked. Absorption factors of every collider between a sound source and the listener
+    /// are summed to calculate occlusion of the sound source (see [`crate::scene::sound::SoundOcclusion`]).
+    pub fn set_sound_absorption(&mut self, absorption: f32) -> f32 {
+        self.sound_absorption
+            .set_value_and_mark_modified(absorption.clamp(0.0, 1.0))
+    }
+
+    /// Returns current sound absorption factor of the collider.
+    pub fn sound_absorption(&self) -> f32 {
+        *self.sound_absorption
+    }
+
+    /// Returns a list of collision events that were generated by the collider in the current frame. The
+    /// list is filled only if event reporting is enabled (see [`Self::set_report_events`]), and it is
+    /// refreshed every frame after the physics step, so it could be read in `on_update` of scripts.
+    ///
+    /// ```rust
+    /// # use fyrox::scene::{
+    /// #     collider::Collider,
+    /// #     graph::{physics::CollisionEventKind, Graph},
+    /// #     node::Node,
+    /// # };
+    /// # use fyrox::core::pool::Handle;
+    /// fn check_collisions(graph: &Graph, collider: Handle<Node>) {
+    ///     if let Some(collider) = graph.try_get_of_type::<Collider>(collider) {
+    ///         for event in collider.collision_events() {
+    ///             match event.kind {
+    ///                 CollisionEventKind::ContactBegin => {
+    ///                     if let Some(contact) = event.contact.as_ref() {
+    ///                         for manifold in contact.manifolds.iter() {
+    ///                             println!("Contact normal: {:?}", manifold.normal);
+    ///                         }
+    ///                     }
+    ///                 }
+    ///                 CollisionEventKind::IntersectionBegin => {
+    ///                     println!("Entered trigger {}", event.other);
+    ///                 }
+    ///                 _ => (),
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
     pub(crate) fn needs_sync_model(&self) -> bool {
         self.shape.need_sync()
             || self.friction.need_sync()
@@ -796,6 +1058,18 @@ impl NodeTrait for Collider {
             .sync_to_collider_node(context.nodes, self_handle, self);
     }
 
+    fn update(&mut self, context: &mut UpdateContext) {
+        self.collision_events.clear();
+
+        if *self.report_events {
+            context.physics.collect_collision_events(
+                self.native.get(),
+                &mut self.touching,
+                &mut self.collision_events,
+            );
+        }
+    }
+
     fn validate(&self, scene: &Scene) -> Result<(), String> {
         if scene
             .graph
@@ -826,6 +1100,8 @@ pub struct ColliderBuilder {
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
+    report_events: bool,
+    sound_absorption: f32,
 }
 
 impl ColliderBuilder {
@@ -842,6 +1118,8 @@ impl ColliderBuilder {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            report_events: false,
+            sound_absorption: 1.0,
         }
     }
 
@@ -899,6 +1177,18 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets whether the collider should generate collision events or not.
+    pub fn with_report_events(mut self, report_events: bool) -> Self {
+        self.report_events = report_events;
+        self
+    }
+
+    /// Sets desired sound absorption factor. See [`Collider::set_sound_absorption`] for more info.
+    pub fn with_sound_absorption(mut self, sound_absorption: f32) -> Self {
+        self.sound_absorption = sound_absorption;
+        self
+    }
+
     /// Creates collider node, but does not add it to a graph.
     pub fn build_collider(self) -> Collider {
         Collider {
@@ -912,7 +1202,12 @@ impl ColliderBuilder {
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
+            report_events: self.report_events.into(),
+            sound_absorption: self.sound_absorption.into(),
             native: Cell::new(ColliderHandle::invalid()),
+            height_map_stamp: Default::default(),
+            touching: Default::default(),
+            collision_events: Default::default(),
         }
     }
 
@@ -932,8 +1227,8 @@ mod test {
     use crate::core::algebra::Vector2;
     use crate::scene::{
         base::BaseBuilder,
-        collider::{ColliderBuilder, ColliderShape},
-        graph::Graph,
+        collider::{BitMask, ColliderBuilder, ColliderShape, CollisionLayers, InteractionGroups},
+        graph::{physics::CollisionEventKind, Graph},
         rigidbody::{RigidBodyBuilder, RigidBodyType},
     };
 
@@ -998,4 +1293,82 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_collision_events() {
+        let mut graph = Graph::new();
+
+        let mut create_rigid_body = |is_sensor| {
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+                .with_sensor(is_sensor)
+                .with_report_events(true)
+                .build(&mut graph);
+
+            // Rapier does not check intersections between two fixed bodies, so the body of the
+            // regular collider is dynamic, but it is not affected by gravity.
+            RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+                .with_body_type(if is_sensor {
+                    RigidBodyType::Static
+                } else {
+                    RigidBodyType::Dynamic
+                })
+                .with_gravity_scale(0.0)
+                .build(&mut graph);
+
+            collider
+        };
+
+        let collider_sensor = create_rigid_body(true);
+        let collider_non_sensor = create_rigid_body(false);
+
+        let mut events = Vec::new();
+        for _ in 0..2 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+            events.extend(
+                graph[collider_non_sensor]
+                    .as_collider()
+                    .collision_events()
+                    .iter()
+                    .cloned(),
+            );
+        }
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, CollisionEventKind::IntersectionBegin);
+        assert_eq!(events[0].other, collider_sensor);
+
+        // Events must not be repeated while the colliders keep intersecting.
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        assert!(graph[collider_non_sensor]
+            .as_collider()
+            .collision_events()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_collision_layers() {
+        let mut layers = CollisionLayers::default();
+        layers.set_name(1, "Player");
+        assert_eq!(layers.find_by_name("Player"), Some(1));
+
+        layers.set_collision_enabled(1, 2, false);
+        assert!(!layers.is_collision_enabled(1, 2));
+        assert!(!layers.is_collision_enabled(2, 1));
+        assert!(layers.is_collision_enabled(1, 3));
+
+        let groups = layers.interaction_groups(1).unwrap();
+        assert_eq!(
+            groups,
+            InteractionGroups::new(BitMask(0b10), BitMask(!0b100))
+        );
+        assert_eq!(layers.find_layer(&groups), Some(1));
+        assert_eq!(layers.find_layer(&InteractionGroups::default()), None);
+
+        // Simulate editing of a single cell.
+        let prev = layers.clone();
+        layers.layers[3].collides_with.0 &= !(1 << 4);
+        layers.mirror_changes(&prev);
+        assert!(!layers.is_collision_enabled(4, 3));
+    }
 }
@@ -1096,3 +1096,180 @@ impl SceneDrawingContext {
         self.lines.clear()
     }
 }
+
+/// A text label, that is drawn at some position in world space. See [`DebugDrawer::text_3d`] for
+/// more info.
+#[derive(Clone, Debug)]
+pub struct DebugText {
+    /// World-space position of the center of the text.
+    pub position: Vector3<f32>,
+    /// Text of the label.
+    pub text: String,
+    /// Color of the text.
+    pub color: Color,
+    time_left: f32,
+}
+
+/// Immediate-mode debug drawing facility of a scene graph. Unlike [`SceneDrawingContext`], it manages
+/// lifetime of drawn primitives by itself: each primitive is drawn for the given duration (in seconds)
+/// and then removed automatically. Zero duration means that a primitive will be drawn only in the
+/// current frame, which is the most common usage from scripts and plugins - just call the drawing
+/// methods every frame.
+///
+/// Every primitive is rendered on top of the scene in a single batch, lines are drawn with depth test
+/// and text labels are always drawn on top of everything.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, color::Color, math::aabb::AxisAlignedBoundingBox},
+/// #     scene::graph::Graph,
+/// # };
+/// fn draw_debug_info(graph: &mut Graph) {
+///     let drawer = graph.debug_drawer();
+///
+///     // Will be drawn in the current frame only.
+///     drawer.line(Vector3::default(), Vector3::new(0.0, 1.0, 0.0), Color::GREEN, 0.0);
+///     drawer.text_3d(Vector3::new(0.0, 1.0, 0.0), "Spawn point", Color::WHITE, 0.0);
+///
+///     // Will be drawn for 2 seconds.
+///     drawer.sphere(Vector3::new(1.0, 0.0, 0.0), 0.5, Color::RED, 2.0);
+///     drawer.aabb(
+///         &AxisAlignedBoundingBox::unit(),
+///         Color::opaque(255, 255, 0),
+///         2.0,
+///     );
+/// }
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct DebugDrawer {
+    context: SceneDrawingContext,
+    lines_time_left: Vec<f32>,
+    texts: Vec<DebugText>,
+}
+
+impl DebugDrawer {
+    /// Draws arbitrary geometry using the given closure for the given duration (in seconds). It
+    /// allows you to use every drawing method of [`SceneDrawingContext`].
+    pub fn draw_with<F>(&mut self, duration: f32, func: F)
+    where
+        F: FnOnce(&mut SceneDrawingContext),
+    {
+        func(&mut self.context);
+        self.lines_time_left
+            .resize(self.context.lines.len(), duration.max(0.0));
+    }
+
+    /// Draws a line between two points for the given duration (in seconds).
+    pub fn line(&mut self, begin: Vector3<f32>, end: Vector3<f32>, color: Color, duration: f32) {
+        self.draw_with(duration, |ctx| ctx.add_line(Line { begin, end, color }));
+    }
+
+    /// Draws a wire sphere for the given duration (in seconds).
+    pub fn sphere(&mut self, center: Vector3<f32>, radius: f32, color: Color, duration: f32) {
+        self.draw_with(duration, |ctx| {
+            ctx.draw_sphere(center, 10, 10, radius, color)
+        });
+    }
+
+    /// Draws an axis-aligned bounding box for the given duration (in seconds).
+    pub fn aabb(&mut self, aabb: &AxisAlignedBoundingBox, color: Color, duration: f32) {
+        self.draw_with(duration, |ctx| ctx.draw_aabb(aabb, color));
+    }
+
+    /// Draws a text label at the given world-space position for the given duration (in seconds).
+    pub fn text_3d<S: Into<String>>(
+        &mut self,
+        position: Vector3<f32>,
+        text: S,
+        color: Color,
+        duration: f32,
+    ) {
+        self.texts.push(DebugText {
+            position,
+            text: text.into(),
+            color,
+            time_left: duration.max(0.0),
+        });
+    }
+
+    /// Returns a drawing context with every line, that should be drawn in the current frame.
+    pub fn drawing_context(&self) -> &SceneDrawingContext {
+        &self.context
+    }
+
+    /// Returns a slice with every text label, that should be drawn in the current frame.
+    pub fn texts(&self) -> &[DebugText] {
+        &self.texts
+    }
+
+    /// Removes every primitive regardless of its remaining duration.
+    pub fn clear(&mut self) {
+        self.context.clear_lines();
+        self.lines_time_left.clear();
+        self.texts.clear();
+    }
+
+    /// Removes expired primitives and advances time of the rest. It is called automatically by
+    /// the graph at the beginning of every frame, so primitives drawn with zero duration are
+    /// removed at the next frame.
+    pub fn update(&mut self, dt: f32) {
+        let mut time_left = self.lines_time_left.iter();
+        self.context
+            .lines
+            .retain(|_| time_left.next().map_or(false, |t| *t > 0.0));
+        self.lines_time_left.retain(|t| *t > 0.0);
+        for t in self.lines_time_left.iter_mut() {
+            *t -= dt;
+        }
+
+        self.texts.retain(|text| text.time_left > 0.0);
+        for text in self.texts.iter_mut() {
+            text.time_left -= dt;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, color::Color},
+        scene::debug::DebugDrawer,
+    };
+
+    #[test]
+    fn test_debug_drawer_durations() {
+        let mut drawer = DebugDrawer::default();
+        drawer.line(
+            Vector3::default(),
+            Vector3::new(1.0, 0.0, 0.0),
+            Color::RED,
+            0.0,
+        );
+        drawer.line(
+            Vector3::default(),
+            Vector3::new(0.0, 1.0, 0.0),
+            Color::RED,
+            1.5,
+        );
+        drawer.text_3d(Vector3::default(), "Text", Color::WHITE, 0.0);
+        assert_eq!(drawer.drawing_context().lines.len(), 2);
+        assert_eq!(drawer.texts().len(), 1);
+
+        // Primitives with zero duration are drawn only once.
+        drawer.update(1.0);
+        assert_eq!(drawer.drawing_context().lines.len(), 1);
+        assert_eq!(
+            drawer.drawing_context().lines[0].end,
+            Vector3::new(0.0, 1.0, 0.0)
+        );
+        assert!(drawer.texts().is_empty());
+
+        drawer.update(1.0);
+        assert_eq!(drawer.drawing_context().lines.len(), 1);
+
+        drawer.update(1.0);
+        assert!(drawer.drawing_context().lines.is_empty());
+    }
+}
@@ -41,8 +41,18 @@ use std::ops::{Deref, DerefMut};
 ///
 /// # Supported maps
 ///
-/// Currently, only diffuse and normal maps are supported. Diffuse and normal maps will be automatically projected
-/// on the data stored in G-Buffer.
+/// Diffuse, normal and roughness maps are supported. They will be automatically projected on the data stored in
+/// G-Buffer. Roughness is taken from the red channel of the roughness map and it replaces only roughness of a
+/// surface, metallic and ambient occlusion are left untouched.
+///
+/// # Fading
+///
+/// A decal can be faded out by the angle between its projection axis (local Y axis) and the normal of a surface.
+/// This is useful to hide the stretching of a texture on surfaces that are almost parallel to the projection axis.
+/// It can also be faded out by the distance to a camera, which is useful to hide small details (such as bullet holes)
+/// far away from the camera. Each fading has start and end values, a decal has full opacity before the start value and
+/// fully transparent after the end value. Fading is disabled if its end value is less or equal to its start value
+/// (this is the default).
 ///
 /// # Limitations
 ///
@@ -93,12 +103,52 @@ pub struct Decal {
     #[reflect(setter = "set_normal_texture")]
     normal_texture: InheritableVariable<Option<TextureResource>>,
 
+    #[visit(optional)]
+    #[reflect(setter = "set_roughness_texture")]
+    roughness_texture: InheritableVariable<Option<TextureResource>>,
+
     #[reflect(setter = "set_color")]
     color: InheritableVariable<Color>,
 
     #[reflect(min_value = 0.0)]
     #[reflect(setter = "set_layer")]
     layer: InheritableVariable<u8>,
+
+    #[visit(optional)]
+    #[reflect(
+        min_value = 0.0,
+        max_value = 3.14,
+        step = 0.1,
+        setter = "set_angle_fade_start",
+        description = "An angle (in radians) between projection axis and surface normal at which the decal starts to fade out."
+    )]
+    angle_fade_start: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        min_value = 0.0,
+        max_value = 3.14,
+        step = 0.1,
+        setter = "set_angle_fade_end",
+        description = "An angle (in radians) between projection axis and surface normal at which the decal becomes fully transparent."
+    )]
+    angle_fade_end: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        min_value = 0.0,
+        setter = "set_distance_fade_start",
+        description = "Distance to a camera at which the decal starts to fade out."
+    )]
+    distance_fade_start: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        min_value = 0.0,
+        setter = "set_distance_fade_end",
+        description = "Distance to a camera at which the decal becomes fully transparent."
+    )]
+    distance_fade_end: InheritableVariable<f32>,
 }
 
 impl Deref for Decal {
@@ -164,6 +214,27 @@ impl Decal {
         (*self.normal_texture).clone()
     }
 
+    /// Sets new roughness texture. Roughness is taken from the red channel of the texture.
+    pub fn set_roughness_texture(
+        &mut self,
+        roughness_texture: Option<TextureResource>,
+    ) -> Option<TextureResource> {
+        std::mem::replace(
+            self.roughness_texture.get_value_mut_and_mark_modified(),
+            roughness_texture,
+        )
+    }
+
+    /// Returns current roughness texture.
+    pub fn roughness_texture(&self) -> Option<&TextureResource> {
+        self.roughness_texture.as_ref()
+    }
+
+    /// Returns current roughness texture.
+    pub fn roughness_texture_value(&self) -> Option<TextureResource> {
+        (*self.roughness_texture).clone()
+    }
+
     /// Sets new color for the decal.
     pub fn set_color(&mut self, color: Color) -> Color {
         self.color.set_value_and_mark_modified(color)
@@ -187,6 +258,49 @@ impl Decal {
     pub fn layer(&self) -> u8 {
         *self.layer
     }
+
+    /// Sets an angle (in radians) between projection axis and surface normal at which the decal
+    /// starts to fade out.
+    pub fn set_angle_fade_start(&mut self, angle: f32) -> f32 {
+        self.angle_fade_start.set_value_and_mark_modified(angle)
+    }
+
+    /// Returns an angle (in radians) at which the decal starts to fade out.
+    pub fn angle_fade_start(&self) -> f32 {
+        *self.angle_fade_start
+    }
+
+    /// Sets an angle (in radians) between projection axis and surface normal at which the decal
+    /// becomes fully transparent.
+    pub fn set_angle_fade_end(&mut self, angle: f32) -> f32 {
+        self.angle_fade_end.set_value_and_mark_modified(angle)
+    }
+
+    /// Returns an angle (in radians) at which the decal becomes fully transparent.
+    pub fn angle_fade_end(&self) -> f32 {
+        *self.angle_fade_end
+    }
+
+    /// Sets a distance to a camera at which the decal starts to fade out.
+    pub fn set_distance_fade_start(&mut self, distance: f32) -> f32 {
+        self.distance_fade_start
+            .set_value_and_mark_modified(distance)
+    }
+
+    /// Returns a distance to a camera at which the decal starts to fade out.
+    pub fn distance_fade_start(&self) -> f32 {
+        *self.distance_fade_start
+    }
+
+    /// Sets a distance to a camera at which the decal becomes fully transparent.
+    pub fn set_distance_fade_end(&mut self, distance: f32) -> f32 {
+        self.distance_fade_end.set_value_and_mark_modified(distance)
+    }
+
+    /// Returns a distance to a camera at which the decal becomes fully transparent.
+    pub fn distance_fade_end(&self) -> f32 {
+        *self.distance_fade_end
+    }
 }
 
 impl NodeTrait for Decal {
@@ -214,8 +328,13 @@ pub struct DecalBuilder {
     base_builder: BaseBuilder,
     diffuse_texture: Option<TextureResource>,
     normal_texture: Option<TextureResource>,
+    roughness_texture: Option<TextureResource>,
     color: Color,
     layer: u8,
+    angle_fade_start: f32,
+    angle_fade_end: f32,
+    distance_fade_start: f32,
+    distance_fade_end: f32,
 }
 
 impl DecalBuilder {
@@ -225,8 +344,13 @@ impl DecalBuilder {
             base_builder,
             diffuse_texture: None,
             normal_texture: None,
+            roughness_texture: None,
             color: Color::opaque(255, 255, 255),
             layer: 0,
+            angle_fade_start: 0.0,
+            angle_fade_end: 0.0,
+            distance_fade_start: 0.0,
+            distance_fade_end: 0.0,
         }
     }
 
@@ -242,6 +366,12 @@ impl DecalBuilder {
         self
     }
 
+    /// Sets desired roughness texture.
+    pub fn with_roughness_texture(mut self, roughness_texture: TextureResource) -> Self {
+        self.roughness_texture = Some(roughness_texture);
+        self
+    }
+
     /// Sets desired decal color.
     pub fn with_color(mut self, color: Color) -> Self {
         self.color = color;
@@ -254,14 +384,33 @@ impl DecalBuilder {
         self
     }
 
+    /// Sets desired angle fading range (in radians).
+    pub fn with_angle_fade(mut self, start: f32, end: f32) -> Self {
+        self.angle_fade_start = start;
+        self.angle_fade_end = end;
+        self
+    }
+
+    /// Sets desired distance fading range.
+    pub fn with_distance_fade(mut self, start: f32, end: f32) -> Self {
+        self.distance_fade_start = start;
+        self.distance_fade_end = end;
+        self
+    }
+
     /// Creates new Decal node.
     pub fn build_decal(self) -> Decal {
         Decal {
             base: self.base_builder.build_base(),
             diffuse_texture: self.diffuse_texture.into(),
             normal_texture: self.normal_texture.into(),
+            roughness_texture: self.roughness_texture.into(),
             color: self.color.into(),
             layer: self.layer.into(),
+            angle_fade_start: self.angle_fade_start.into(),
+            angle_fade_end: self.angle_fade_end.into(),
+            distance_fade_start: self.distance_fade_start.into(),
+            distance_fade_end: self.distance_fade_end.into(),
         }
     }
 
@@ -16,6 +16,7 @@ use crate::{
         base::{Base, BaseBuilder},
         dim2::rigidbody::RigidBody,
         graph::Graph,
+        joint::JointMotor,
         node::{Node, NodeTrait, SyncContext},
         Scene,
     },
@@ -43,6 +44,11 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angles range for the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits_angles: Range<f32>,
+
+    /// Motor that drives rotation of the joint.
+    #[reflect(description = "Motor that drives rotation of the joint.")]
+    #[visit(optional)]
+    pub motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -50,6 +56,7 @@ impl Default for BallJoint {
         Self {
             limits_enabled: false,
             limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -74,6 +81,11 @@ pub struct PrismaticJoint {
     #[reflect(description = "Allowed linear distance range along local X axis of the joint.")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that drives translation along local X axis of the joint.
+    #[reflect(description = "Motor that drives translation along local X axis of the joint.")]
+    #[visit(optional)]
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -81,6 +93,7 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
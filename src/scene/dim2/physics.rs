@@ -21,12 +21,16 @@ use crate::{
     scene::{
         self,
         collider::{self},
-        debug::SceneDrawingContext,
+        debug::{Line, SceneDrawingContext},
         dim2::{self, collider::ColliderShape, joint::JointParams, rigidbody::ApplyAction},
         graph::{
-            physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
+            physics::{
+                FeatureId, IntegrationParameters, PhysicsDebugDrawOptions,
+                PhysicsPerformanceStatistics,
+            },
             NodePool,
         },
+        joint::JointMotor,
         node::{Node, NodeTrait},
     },
 };
@@ -42,7 +46,10 @@ use rapier2d::{
         BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
+    pipeline::{
+        DebugRenderBackend, DebugRenderObject, DebugRenderPipeline, EventHandler, PhysicsPipeline,
+        QueryFilter, QueryPipeline,
+    },
 };
 use std::num::NonZeroUsize;
 use std::{
@@ -283,18 +290,34 @@ fn convert_joint_params(
                     [v.limits_angles.start, v.limits_angles.end],
                 );
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
         }
         scene::dim2::joint::JointParams::FixedJoint(_) => {}
         scene::dim2::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::X, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::X, &v.motor);
         }
     }
 
     joint
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force);
+    }
+}
+
 // Converts descriptor in a shared shape.
 fn collider_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape> {
     match shape {
@@ -354,6 +377,11 @@ pub struct PhysicsWorld {
     #[reflect(hidden)]
     pub performance_statistics: PhysicsPerformanceStatistics,
 
+    /// A set of options for the debug overlay. See [`PhysicsDebugDrawOptions`] docs for more info.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub debug_draw_options: PhysicsDebugDrawOptions,
+
     // Current physics pipeline.
     #[visit(skip)]
     #[reflect(hidden)]
@@ -403,6 +431,39 @@ pub struct PhysicsWorld {
     debug_render_pipeline: Mutex<DebugRenderPipeline>,
 }
 
+// Recolors collider wireframes, produced by the debug render pipeline of rapier, according to
+// the type and sleep state of their rigid bodies.
+struct ColliderDebugRenderBackend<'a> {
+    context: &'a mut SceneDrawingContext,
+    bodies: &'a RigidBodySet,
+}
+
+impl<'a> DebugRenderBackend for ColliderDebugRenderBackend<'a> {
+    fn draw_line(
+        &mut self,
+        object: DebugRenderObject,
+        a: Point2<f32>,
+        b: Point2<f32>,
+        _color: [f32; 4],
+    ) {
+        let color = match object {
+            DebugRenderObject::Collider(_, collider) => collider
+                .parent()
+                .and_then(|parent| self.bodies.get(parent))
+                .map_or(PhysicsDebugDrawOptions::FREE_COLLIDER_COLOR, |body| {
+                    PhysicsDebugDrawOptions::body_color(body.body_type().into(), body.is_sleeping())
+                }),
+            _ => return,
+        };
+
+        self.context.add_line(Line {
+            begin: Vector3::new(a.x, a.y, 0.0),
+            end: Vector3::new(b.x, b.y, 0.0),
+            color,
+        })
+    }
+}
+
 fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry2<f32> {
     Isometry2 {
         translation: Translation2::new(transform[12], transform[13]),
@@ -456,6 +517,7 @@ impl PhysicsWorld {
             event_handler: Box::new(()),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
+            debug_draw_options: Default::default(),
             debug_render_pipeline: Default::default(),
         }
     }
@@ -561,17 +623,96 @@ impl PhysicsWorld {
         }
     }
 
-    /// Draws physics world. Very useful for debugging, it allows you to see where are
-    /// rigid bodies, which colliders they have and so on.
+    /// Draws physics world using [`Self::debug_draw_options`]. Very useful for debugging, it allows
+    /// you to see where are rigid bodies, which colliders they have and so on.
     pub fn draw(&self, context: &mut SceneDrawingContext) {
-        self.debug_render_pipeline.lock().render(
-            context,
-            &self.bodies,
-            &self.colliders,
-            &self.joints.set,
-            &self.multibody_joints.set,
-            &self.narrow_phase,
-        );
+        self.draw_with_options(context, &self.debug_draw_options)
+    }
+
+    /// Draws physics world using the given options. See [`PhysicsDebugDrawOptions`] docs for more
+    /// info.
+    pub fn draw_with_options(
+        &self,
+        context: &mut SceneDrawingContext,
+        options: &PhysicsDebugDrawOptions,
+    ) {
+        if options.colliders {
+            self.debug_render_pipeline.lock().render_colliders(
+                &mut ColliderDebugRenderBackend {
+                    context: &mut *context,
+                    bodies: &self.bodies,
+                },
+                &self.bodies,
+                &self.colliders,
+            );
+        }
+
+        if options.contacts {
+            for pair in self.narrow_phase.contact_pairs() {
+                if !pair.has_any_active_contact {
+                    continue;
+                }
+
+                for manifold in pair.manifolds.iter() {
+                    for contact in manifold.data.solver_contacts.iter() {
+                        let point = Vector3::new(contact.point.x, contact.point.y, 0.0);
+                        let normal =
+                            Vector3::new(manifold.data.normal.x, manifold.data.normal.y, 0.0);
+                        PhysicsDebugDrawOptions::draw_marker(
+                            context,
+                            point,
+                            0.03,
+                            PhysicsDebugDrawOptions::CONTACT_COLOR,
+                        );
+                        context.add_line(Line {
+                            begin: point,
+                            end: point + normal.scale(0.2),
+                            color: PhysicsDebugDrawOptions::CONTACT_NORMAL_COLOR,
+                        });
+                    }
+                }
+            }
+        }
+
+        if options.joints {
+            for (_, joint) in self.joints.set.iter() {
+                for (body, local_frame) in [
+                    (joint.body1, &joint.data.local_frame1),
+                    (joint.body2, &joint.data.local_frame2),
+                ] {
+                    if let Some(body) = self.bodies.get(body) {
+                        let anchor = (body.position() * local_frame).translation.vector;
+                        let center_of_mass = body.center_of_mass();
+                        context.add_line(Line {
+                            begin: Vector3::new(center_of_mass.x, center_of_mass.y, 0.0),
+                            end: Vector3::new(anchor.x, anchor.y, 0.0),
+                            color: PhysicsDebugDrawOptions::JOINT_ANCHOR_COLOR,
+                        });
+                        context.draw_circle(
+                            Vector3::new(anchor.x, anchor.y, 0.0),
+                            0.05,
+                            8,
+                            Matrix4::identity(),
+                            PhysicsDebugDrawOptions::JOINT_ANCHOR_COLOR,
+                        );
+                    }
+                }
+            }
+        }
+
+        if options.centers_of_mass {
+            for (_, body) in self.bodies.iter() {
+                if body.is_dynamic() {
+                    let center_of_mass = body.center_of_mass();
+                    PhysicsDebugDrawOptions::draw_marker(
+                        context,
+                        Vector3::new(center_of_mass.x, center_of_mass.y, 0.0),
+                        0.1,
+                        PhysicsDebugDrawOptions::CENTER_OF_MASS_COLOR,
+                    );
+                }
+            }
+        }
     }
 
     /// Casts a ray with given options.
@@ -118,6 +118,9 @@ pub struct RigidBody {
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) actions: Mutex<VecDeque<ApplyAction>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) interpolation_target: Cell<Option<Matrix4<f32>>>,
 }
 
 impl Debug for RigidBody {
@@ -146,6 +149,7 @@ impl Default for RigidBody {
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: Default::default(),
+            interpolation_target: Default::default(),
         }
     }
 }
@@ -185,6 +189,7 @@ impl Clone for RigidBody {
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: self.reset_forces.clone(),
+            interpolation_target: Default::default(),
         }
     }
 }
@@ -439,14 +444,22 @@ impl NodeTrait for RigidBody {
     }
 
     fn update(&mut self, context: &mut UpdateContext) {
-        context.physics2d.sync_rigid_body_node(
-            self,
-            // Rigid body 2D can be root node of a scene, in this case it does not have a parent.
-            context
-                .nodes
-                .try_borrow(self.parent)
-                .map(|p| p.global_transform())
-                .unwrap_or_else(Matrix4::identity),
+        // Rigid body 2D can be root node of a scene, in this case it does not have a parent.
+        let parent_transform = context
+            .nodes
+            .try_borrow(self.parent)
+            .map(|p| p.global_transform())
+            .unwrap_or_else(Matrix4::identity);
+
+        context
+            .physics2d
+            .sync_rigid_body_node(self, parent_transform);
+
+        // Remember the transform the body will have after the next hierarchical data update, so its
+        // rendered transform could be interpolated between physics steps.
+        self.interpolation_target.set(
+            (*self.body_type == RigidBodyType::Dynamic)
+                .then(|| parent_transform * self.local_transform().matrix()),
         );
     }
 
@@ -600,6 +613,7 @@ impl RigidBodyBuilder {
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: Default::default(),
+            interpolation_target: Default::default(),
         }
     }
 
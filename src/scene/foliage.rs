@@ -0,0 +1,790 @@
+//! Foliage is a scatter system, that allows you to paint a lot of small objects (grass, rocks, trees,
+//! etc.) onto terrains or meshes. See [`Foliage`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, UnitQuaternion, Vector3},
+        arrayvec::ArrayVec,
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, ray::Ray, Matrix4Ext},
+        pool::Handle,
+        rand::{thread_rng, Rng},
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        uuid_provider,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    graph::SceneGraph,
+    renderer::{
+        self,
+        batch::{PersistentIdentifier, RenderContext, SurfaceInstanceData},
+        framework::geometry_buffer::ElementRange,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            surface::Surface,
+            Mesh, RenderPath,
+        },
+        node::{Node, NodeTrait},
+        terrain::{Terrain, TerrainRayCastResult},
+    },
+};
+use fxhash::FxHashSet;
+use std::ops::{Deref, DerefMut, Range};
+
+/// A single instance of a foliage layer. Every instance is defined in local coordinates of the
+/// foliage node.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct FoliageInstance {
+    /// Local position of the instance.
+    pub position: Vector3<f32>,
+    /// Local rotation of the instance.
+    pub rotation: UnitQuaternion<f32>,
+    /// Uniform scale of the instance.
+    pub scale: f32,
+    /// A random value in `[0; 1]` range, that defines the order in which instances are hidden by
+    /// distance-based density falloff. Instances with smaller values are hidden first.
+    pub fade_key: f32,
+}
+
+impl Default for FoliageInstance {
+    fn default() -> Self {
+        Self {
+            position: Default::default(),
+            rotation: UnitQuaternion::identity(),
+            scale: 1.0,
+            fade_key: 1.0,
+        }
+    }
+}
+
+impl FoliageInstance {
+    /// Returns local transformation matrix of the instance.
+    pub fn local_transform(&self) -> Matrix4<f32> {
+        Matrix4::new_translation(&self.position)
+            * self.rotation.to_homogeneous()
+            * Matrix4::new_scaling(self.scale)
+    }
+}
+
+/// A set of rules, that is used to place new instances when painting foliage.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct ScatterRules {
+    /// Desired amount of instances per square meter.
+    #[reflect(
+        description = "Desired amount of instances per square meter.",
+        min_value = 0.0
+    )]
+    pub density: f32,
+
+    /// A range of uniform scale of new instances.
+    #[reflect(description = "A range of uniform scale of new instances.")]
+    pub scale_range: Range<f32>,
+
+    /// If `true`, every new instance gets random rotation around its vertical axis.
+    #[reflect(
+        description = "If true, every new instance gets random rotation around its vertical axis."
+    )]
+    pub random_rotation: bool,
+
+    /// Maximum random tilt of new instances (in degrees).
+    #[reflect(
+        description = "Maximum random tilt of new instances (in degrees).",
+        min_value = 0.0,
+        max_value = 90.0
+    )]
+    pub max_tilt: f32,
+
+    /// If `true`, new instances are aligned with the normal of the surface they're placed on.
+    /// Otherwise, instances are placed upright.
+    #[reflect(
+        description = "If true, new instances are aligned with the normal of the surface they're \
+        placed on. Otherwise, instances are placed upright."
+    )]
+    pub align_to_normal: bool,
+
+    /// A range of slopes (in degrees) of the surface on which new instances could be placed.
+    #[reflect(
+        description = "A range of slopes (in degrees) of the surface on which new instances could be placed."
+    )]
+    pub slope_range: Range<f32>,
+
+    /// A range of world-space heights on which new instances could be placed.
+    #[reflect(
+        description = "A range of world-space heights on which new instances could be placed."
+    )]
+    pub height_range: Range<f32>,
+}
+
+impl Default for ScatterRules {
+    fn default() -> Self {
+        Self {
+            density: 1.0,
+            scale_range: 0.8..1.2,
+            random_rotation: true,
+            max_tilt: 0.0,
+            align_to_normal: false,
+            slope_range: 0.0..90.0,
+            height_range: -f32::MAX..f32::MAX,
+        }
+    }
+}
+
+impl ScatterRules {
+    /// Checks whether an instance could be placed at the given world-space point with the given
+    /// world-space surface normal.
+    pub fn is_allowed(&self, point: &ScatterPoint) -> bool {
+        let slope = point
+            .normal
+            .try_normalize(f32::EPSILON)
+            .map_or(0.0, |n| n.y.clamp(-1.0, 1.0).acos().to_degrees());
+
+        slope >= self.slope_range.start
+            && slope <= self.slope_range.end
+            && point.position.y >= self.height_range.start
+            && point.position.y <= self.height_range.end
+    }
+}
+
+/// Foliage layer is a set of instances of the same object (for example - grass), that is rendered
+/// using GPU instancing.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct FoliageLayer {
+    /// Name of the layer.
+    pub name: String,
+
+    /// Surfaces of the object, that is used to render every instance of the layer.
+    pub surfaces: Vec<Surface>,
+
+    /// A set of rules, that is used to place new instances.
+    pub rules: ScatterRules,
+
+    /// A distance from the observer at which the instances start to fade out (their density
+    /// decreases gradually).
+    #[reflect(
+        description = "A distance from the observer at which the instances start to fade out.",
+        min_value = 0.0
+    )]
+    pub fade_start_distance: f32,
+
+    /// A distance from the observer at which all the instances are hidden.
+    #[reflect(
+        description = "A distance from the observer at which all the instances are hidden.",
+        min_value = 0.0
+    )]
+    pub max_distance: f32,
+
+    #[reflect(hidden)]
+    instances: Vec<FoliageInstance>,
+}
+
+uuid_provider!(FoliageLayer = "093c2618-1c1d-4a97-9070-00238626041f");
+
+impl Default for FoliageLayer {
+    fn default() -> Self {
+        Self {
+            name: "Layer".to_string(),
+            surfaces: Default::default(),
+            rules: Default::default(),
+            fade_start_distance: 50.0,
+            max_distance: 100.0,
+            instances: Default::default(),
+        }
+    }
+}
+
+impl FoliageLayer {
+    /// Creates a new layer without instances, that uses the given surfaces to render its instances.
+    pub fn new(name: &str, surfaces: Vec<Surface>) -> Self {
+        Self {
+            name: name.to_string(),
+            surfaces,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a reference to the instances of the layer.
+    pub fn instances(&self) -> &[FoliageInstance] {
+        &self.instances
+    }
+
+    /// Sets new instances of the layer and returns the old ones.
+    pub fn set_instances(&mut self, instances: Vec<FoliageInstance>) -> Vec<FoliageInstance> {
+        std::mem::replace(&mut self.instances, instances)
+    }
+
+    /// Checks whether an instance with the given fade key should be visible at the given distance
+    /// from the observer.
+    pub fn is_visible_at(&self, distance: f32, fade_key: f32) -> bool {
+        if distance >= self.max_distance {
+            false
+        } else if distance <= self.fade_start_distance {
+            true
+        } else {
+            let fade = (distance - self.fade_start_distance)
+                / (self.max_distance - self.fade_start_distance);
+            fade_key >= fade
+        }
+    }
+
+    /// Returns the radius of a sphere around the origin, that contains every surface of the layer.
+    fn bounding_radius(&self) -> f32 {
+        let mut radius = 0.0f32;
+        for surface in self.surfaces.iter() {
+            let data = surface.data();
+            let data = data.lock();
+            for view in data.vertex_buffer.iter() {
+                if let Ok(position) = view.read_3_f32(VertexAttributeUsage::Position) {
+                    radius = radius.max(position.norm());
+                }
+            }
+        }
+        radius
+    }
+}
+
+/// A point on a surface, that is used to place foliage instances.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ScatterPoint {
+    /// World-space position of the point.
+    pub position: Vector3<f32>,
+    /// World-space normal of the surface at the point.
+    pub normal: Vector3<f32>,
+}
+
+/// Foliage brush is used to add or remove instances of a foliage layer.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct FoliageBrush {
+    /// Index of the layer to paint.
+    pub layer: usize,
+
+    /// Radius of the brush (in meters).
+    #[reflect(min_value = 0.0)]
+    pub radius: f32,
+
+    /// Defines how much of the desired density is reached by a single stroke of the brush. 1.0
+    /// means that the desired density is reached immediately.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub strength: f32,
+
+    /// If `true`, the brush removes instances instead of adding them.
+    pub erase: bool,
+}
+
+impl Default for FoliageBrush {
+    fn default() -> Self {
+        Self {
+            layer: 0,
+            radius: 2.0,
+            strength: 0.25,
+            erase: false,
+        }
+    }
+}
+
+/// Foliage is a scatter system, that is used to place a lot of small objects (grass, rocks, trees,
+/// etc.) onto terrains or meshes. Foliage consists of layers (see [`FoliageLayer`]), each layer
+/// stores an array of instances of a single object, and all the instances are rendered using GPU
+/// instancing.
+///
+/// ## Painting
+///
+/// Instances are usually painted in the editor, but it could be done from code as well using
+/// [`Foliage::paint`] or [`paint_foliage`]. New instances are placed randomly inside the brush
+/// until the desired density of the layer is reached, every new instance gets random scale and
+/// rotation, and it is placed only if the surface under it satisfies the slope and height rules
+/// of the layer (see [`ScatterRules`]).
+///
+/// ## Performance
+///
+/// The density of each layer decreases gradually with the distance to the observer, starting from
+/// [`FoliageLayer::fade_start_distance`]. All the instances further than
+/// [`FoliageLayer::max_distance`] are not rendered at all.
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct Foliage {
+    base: Base,
+
+    #[reflect(setter = "set_layers")]
+    layers: InheritableVariable<Vec<FoliageLayer>>,
+
+    #[reflect(setter = "set_decal_layer_index")]
+    #[visit(optional)]
+    decal_layer_index: InheritableVariable<u8>,
+}
+
+impl Deref for Foliage {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Foliage {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for Foliage {
+    fn default() -> Self {
+        FoliageBuilder::new(BaseBuilder::new()).build_foliage()
+    }
+}
+
+impl TypeUuidProvider for Foliage {
+    fn type_uuid() -> Uuid {
+        uuid!("2d8f5c1a-9b4e-4f7a-8c3d-6e1b0a9f4d27")
+    }
+}
+
+impl Foliage {
+    /// Sets new layers of the foliage and returns the old ones.
+    pub fn set_layers(&mut self, layers: Vec<FoliageLayer>) -> Vec<FoliageLayer> {
+        self.layers.set_value_and_mark_modified(layers)
+    }
+
+    /// Returns a reference to the layers of the foliage.
+    pub fn layers(&self) -> &[FoliageLayer] {
+        &self.layers
+    }
+
+    /// Returns a mutable reference to the layers of the foliage.
+    pub fn layers_mut(&mut self) -> &mut Vec<FoliageLayer> {
+        self.layers.get_value_mut_and_mark_modified()
+    }
+
+    /// Sets new decal layer index. It defines which decals will be applied to the instances of the
+    /// foliage, see [`crate::scene::mesh::Mesh::set_decal_layer_index`] for more info.
+    pub fn set_decal_layer_index(&mut self, index: u8) -> u8 {
+        self.decal_layer_index.set_value_and_mark_modified(index)
+    }
+
+    /// Returns current decal layer index.
+    pub fn decal_layer_index(&self) -> u8 {
+        *self.decal_layer_index
+    }
+
+    /// Adds or removes (see [`FoliageBrush::erase`]) instances of a layer around the given
+    /// world-space center of the brush. `probe` is used to find a point on a surface below the
+    /// given world-space point, see [`probe_graph`] for the default implementation. Returns the
+    /// amount of added or removed instances.
+    pub fn paint<P>(&mut self, brush: &FoliageBrush, center: Vector3<f32>, mut probe: P) -> usize
+    where
+        P: FnMut(Vector3<f32>) -> Option<ScatterPoint>,
+    {
+        let global_transform = self.global_transform();
+        let Some(inv_global_transform) = global_transform.try_inverse() else {
+            return 0;
+        };
+        let Some(layer) = self
+            .layers
+            .get_value_mut_and_mark_modified()
+            .get_mut(brush.layer)
+        else {
+            return 0;
+        };
+
+        let is_inside = |instance: &FoliageInstance| {
+            let position = global_transform
+                .transform_point(&Point3::from(instance.position))
+                .coords;
+            (position.x - center.x).powi(2) + (position.z - center.z).powi(2)
+                <= brush.radius * brush.radius
+        };
+
+        if brush.erase {
+            let count = layer.instances.len();
+            layer.instances.retain(|instance| !is_inside(instance));
+            return count - layer.instances.len();
+        }
+
+        let existing = layer
+            .instances
+            .iter()
+            .filter(|&instance| is_inside(instance))
+            .count();
+        let desired = layer.rules.density * std::f32::consts::PI * brush.radius * brush.radius;
+        let count = ((desired - existing as f32) * brush.strength.clamp(0.0, 1.0))
+            .ceil()
+            .max(0.0) as usize;
+
+        let mut rng = thread_rng();
+        let mut added = 0;
+        // Some points could be rejected by the scatter rules, so give it a few more attempts.
+        for _ in 0..count * 4 {
+            if added >= count {
+                break;
+            }
+
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let distance = brush.radius * rng.gen_range(0.0f32..1.0).sqrt();
+            let sample = center + Vector3::new(angle.cos(), 0.0, angle.sin()).scale(distance);
+
+            let Some(point) = probe(sample) else {
+                continue;
+            };
+            if !layer.rules.is_allowed(&point) {
+                continue;
+            }
+
+            let up = Vector3::y();
+            let local_normal = inv_global_transform
+                .transform_vector(&point.normal)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or(up);
+
+            let mut rotation = if layer.rules.align_to_normal {
+                UnitQuaternion::rotation_between(&up, &local_normal)
+                    .unwrap_or_else(UnitQuaternion::identity)
+            } else {
+                UnitQuaternion::identity()
+            };
+            if layer.rules.max_tilt > 0.0 {
+                let tilt = rng.gen_range(0.0..=layer.rules.max_tilt.to_radians());
+                let axis_angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                let axis = Vector3::new(axis_angle.cos(), 0.0, axis_angle.sin());
+                rotation *= UnitQuaternion::from_scaled_axis(axis.scale(tilt));
+            }
+            if layer.rules.random_rotation {
+                rotation *= UnitQuaternion::from_axis_angle(
+                    &Vector3::y_axis(),
+                    rng.gen_range(0.0..std::f32::consts::TAU),
+                );
+            }
+
+            let scale_range = &layer.rules.scale_range;
+            let scale = if scale_range.start < scale_range.end {
+                rng.gen_range(scale_range.clone())
+            } else {
+                scale_range.start
+            };
+
+            layer.instances.push(FoliageInstance {
+                position: inv_global_transform
+                    .transform_point(&Point3::from(point.position))
+                    .coords,
+                rotation,
+                scale,
+                fade_key: rng.gen_range(0.0..=1.0),
+            });
+            added += 1;
+        }
+
+        added
+    }
+}
+
+impl NodeTrait for Foliage {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let mut bounding_box = AxisAlignedBoundingBox::default();
+        for layer in self.layers.iter() {
+            let radius = layer.bounding_radius();
+            for instance in layer.instances.iter() {
+                let offset = Vector3::repeat(radius * instance.scale);
+                bounding_box.add_point(instance.position - offset);
+                bounding_box.add_point(instance.position + offset);
+            }
+        }
+        bounding_box
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) {
+        if !self.global_visibility() || !self.is_globally_enabled() {
+            return;
+        }
+
+        if renderer::is_shadow_pass(ctx.render_pass_name) && !self.cast_shadows() {
+            return;
+        }
+
+        let global_transform = self.global_transform();
+
+        for layer in self.layers.iter() {
+            if layer.surfaces.is_empty() {
+                continue;
+            }
+
+            let radius = layer.bounding_radius();
+
+            for (instance_index, instance) in layer.instances.iter().enumerate() {
+                let world_transform = global_transform * instance.local_transform();
+                let position = world_transform.position();
+
+                let distance = ctx.observer_position.metric_distance(&position);
+                if !layer.is_visible_at(distance, instance.fade_key)
+                    || !ctx
+                        .frustum
+                        .is_intersects_sphere(position, radius * instance.scale)
+                {
+                    continue;
+                }
+
+                for (surface_index, surface) in layer.surfaces.iter().enumerate() {
+                    ctx.storage.push(
+                        surface.data_ref(),
+                        surface.material(),
+                        RenderPath::Deferred,
+                        self.decal_layer_index(),
+                        surface.material().key() as u64,
+                        SurfaceInstanceData {
+                            world_transform,
+                            bone_matrices: Default::default(),
+                            depth_offset: 0.0,
+                            blend_shapes_weights: Default::default(),
+                            element_range: ElementRange::Full,
+                            persistent_identifier: PersistentIdentifier::new_combined(
+                                surface.data_ref(),
+                                self.self_handle,
+                                instance_index * layer.surfaces.len() + surface_index,
+                            ),
+                            node_handle: self.self_handle,
+                            color: Color::WHITE,
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Allows you to create foliage in declarative manner.
+pub struct FoliageBuilder {
+    base_builder: BaseBuilder,
+    layers: Vec<FoliageLayer>,
+    decal_layer_index: u8,
+}
+
+impl FoliageBuilder {
+    /// Creates new foliage builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            layers: Default::default(),
+            decal_layer_index: 0,
+        }
+    }
+
+    /// Sets the desired layers of the foliage.
+    pub fn with_layers(mut self, layers: Vec<FoliageLayer>) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Sets the desired decal layer index.
+    pub fn with_decal_layer_index(mut self, decal_layer_index: u8) -> Self {
+        self.decal_layer_index = decal_layer_index;
+        self
+    }
+
+    fn build_foliage(self) -> Foliage {
+        Foliage {
+            base: self.base_builder.build_base(),
+            layers: self.layers.into(),
+            decal_layer_index: self.decal_layer_index.into(),
+        }
+    }
+
+    /// Creates new foliage node, but does not add it to a graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_foliage())
+    }
+
+    /// Creates new foliage node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+/// Casts the given ray against every enabled terrain and mesh of the graph (except the given node
+/// and its descendants) and returns the closest intersection point with the surface normal at it.
+pub fn probe_graph(graph: &Graph, ray: &Ray, exclude: Handle<Node>) -> Option<ScatterPoint> {
+    let excluded = if graph.is_valid_handle(exclude) {
+        graph.traverse_handle_iter(exclude).collect()
+    } else {
+        FxHashSet::default()
+    };
+
+    let mut closest_toi = f32::MAX;
+    let mut closest = None;
+
+    for (handle, node) in graph.pair_iter() {
+        if excluded.contains(&handle) || !node.is_globally_enabled() {
+            continue;
+        }
+
+        if let Some(terrain) = node.cast::<Terrain>() {
+            let mut results = ArrayVec::<TerrainRayCastResult, 16>::new();
+            terrain.raycast(*ray, &mut results, true);
+            if let Some(result) = results.first() {
+                if result.toi < closest_toi {
+                    closest_toi = result.toi;
+                    closest = Some(ScatterPoint {
+                        position: result.position,
+                        normal: result.normal,
+                    });
+                }
+            }
+        } else if let Some(mesh) = node.cast::<Mesh>() {
+            if ray.aabb_intersection(&mesh.world_bounding_box()).is_none() {
+                continue;
+            }
+
+            let global_transform = mesh.global_transform();
+            for surface in mesh.surfaces() {
+                let data = surface.data();
+                let data = data.lock();
+
+                let vertex = |i: u32| {
+                    data.vertex_buffer
+                        .get(i as usize)
+                        .and_then(|v| v.read_3_f32(VertexAttributeUsage::Position).ok())
+                        .map(|p| global_transform.transform_point(&Point3::from(p)).coords)
+                };
+
+                for triangle in data.geometry_buffer.iter() {
+                    let (Some(a), Some(b), Some(c)) = (
+                        vertex(triangle[0]),
+                        vertex(triangle[1]),
+                        vertex(triangle[2]),
+                    ) else {
+                        continue;
+                    };
+
+                    if let Some((toi, position)) = ray.triangle_intersection(&[a, b, c]) {
+                        if toi < closest_toi {
+                            let mut normal = (b - a).cross(&(c - a));
+                            // Make sure the normal faces the ray.
+                            if normal.dot(&ray.dir) > 0.0 {
+                                normal = -normal;
+                            }
+                            closest_toi = toi;
+                            closest = Some(ScatterPoint {
+                                position,
+                                normal: normal.try_normalize(f32::EPSILON).unwrap_or_default(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    closest
+}
+
+/// Paints instances of a foliage node using the given brush. It is a shortcut for
+/// [`Foliage::paint`], that uses [`probe_graph`] to place instances on the terrains and meshes of
+/// the graph below the brush. `probe_height` defines the height above the center of the brush from
+/// which the surfaces are probed. Returns the amount of added or removed instances.
+pub fn paint_foliage(
+    graph: &mut Graph,
+    foliage: Handle<Node>,
+    brush: &FoliageBrush,
+    center: Vector3<f32>,
+    probe_height: f32,
+) -> usize {
+    if graph.try_get_of_type::<Foliage>(foliage).is_none() {
+        return 0;
+    }
+
+    let (ticket, mut node) = graph.take_reserve(foliage);
+    let count = node
+        .cast_mut::<Foliage>()
+        .map(|foliage_ref| {
+            foliage_ref.paint(brush, center, |point| {
+                let ray = Ray::from_two_points(
+                    point + Vector3::new(0.0, probe_height, 0.0),
+                    point - Vector3::new(0.0, probe_height, 0.0),
+                );
+                probe_graph(graph, &ray, Handle::NONE)
+            })
+        })
+        .unwrap_or_default();
+    graph.put_back(ticket, node);
+    count
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            foliage::{FoliageBrush, FoliageBuilder, FoliageLayer, ScatterPoint},
+        },
+    };
+
+    #[test]
+    fn test_paint_and_erase() {
+        let mut node = FoliageBuilder::new(BaseBuilder::new())
+            .with_layers(vec![FoliageLayer::default()])
+            .build_node();
+        let foliage = node.cast_mut::<super::Foliage>().unwrap();
+
+        let mut brush = FoliageBrush {
+            strength: 1.0,
+            ..Default::default()
+        };
+
+        // Flat ground.
+        let flat = |p: Vector3<f32>| {
+            Some(ScatterPoint {
+                position: Vector3::new(p.x, 0.0, p.z),
+                normal: Vector3::y(),
+            })
+        };
+
+        let added = foliage.paint(&brush, Vector3::default(), flat);
+        assert!(added > 0);
+        // Density is already reached, so nothing is added.
+        assert_eq!(foliage.paint(&brush, Vector3::default(), flat), 0);
+
+        // Walls are not allowed by the slope rules.
+        foliage.layers_mut()[0].rules.slope_range = 0.0..45.0;
+        let wall = |p: Vector3<f32>| {
+            Some(ScatterPoint {
+                position: p,
+                normal: Vector3::x(),
+            })
+        };
+        assert_eq!(
+            foliage.paint(&brush, Vector3::new(100.0, 0.0, 0.0), wall),
+            0
+        );
+
+        brush.erase = true;
+        assert_eq!(foliage.paint(&brush, Vector3::default(), flat), added);
+        assert!(foliage.layers()[0].instances().is_empty());
+    }
+
+    #[test]
+    fn test_distance_fade() {
+        let layer = FoliageLayer {
+            fade_start_distance: 10.0,
+            max_distance: 20.0,
+            ..Default::default()
+        };
+
+        assert!(layer.is_visible_at(5.0, 0.0));
+        assert!(layer.is_visible_at(15.0, 0.75));
+        assert!(!layer.is_visible_at(15.0, 0.25));
+        assert!(!layer.is_visible_at(25.0, 1.0));
+    }
+}
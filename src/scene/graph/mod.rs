@@ -38,8 +38,10 @@ use crate::{
     material::{shader::SamplerFallback, MaterialResource, PropertyValue},
     resource::model::{Model, ModelResource, ModelResourceExtension},
     scene::{
+        animation::ik::IkChain,
         base::{NodeScriptMessage, SceneNodeId},
         camera::Camera,
+        debug::DebugDrawer,
         dim2::{self},
         graph::{
             event::{GraphEvent, GraphEventBroadcaster},
@@ -53,6 +55,8 @@ use crate::{
         navmesh,
         node::{container::NodeContainer, Node, NodeTrait, SyncContext, UpdateContext},
         pivot::Pivot,
+        ragdoll::Ragdoll,
+        rigidbody::RigidBody,
         sound::context::SoundContext,
         transform::TransformBuilder,
     },
@@ -104,6 +108,31 @@ impl GraphPerformanceStatistics {
     }
 }
 
+fn decompose_transform(
+    transform: &Matrix4<f32>,
+) -> (Vector3<f32>, UnitQuaternion<f32>, Vector3<f32>) {
+    let basis = transform.basis();
+    let scale = Vector3::new(
+        basis.column(0).norm(),
+        basis.column(1).norm(),
+        basis.column(2).norm(),
+    );
+    let rotation =
+        UnitQuaternion::from_matrix_eps(&basis, f32::EPSILON, 16, UnitQuaternion::identity());
+    (transform.position(), rotation, scale)
+}
+
+fn interpolate_transform(a: &Matrix4<f32>, b: &Matrix4<f32>, t: f32) -> Matrix4<f32> {
+    let (a_position, a_rotation, a_scale) = decompose_transform(a);
+    let (b_position, b_rotation, b_scale) = decompose_transform(b);
+    Matrix4::new_translation(&a_position.lerp(&b_position, t))
+        * a_rotation
+            .try_slerp(&b_rotation, t, f32::EPSILON)
+            .unwrap_or(b_rotation)
+            .to_homogeneous()
+        * Matrix4::new_nonuniform_scaling(&a_scale.lerp(&b_scale, t))
+}
+
 /// A helper type alias for node pool.
 pub type NodePool = Pool<Node, NodeContainer>;
 
@@ -139,12 +168,20 @@ pub struct Graph {
     /// Current lightmap.
     lightmap: Option<Lightmap>,
 
+    #[reflect(hidden)]
+    debug_drawer: DebugDrawer,
+
     #[reflect(hidden)]
     pub(crate) script_message_sender: Sender<NodeScriptMessage>,
     #[reflect(hidden)]
     pub(crate) script_message_receiver: Receiver<NodeScriptMessage>,
 
     instance_id_map: FxHashMap<SceneNodeId, Handle<Node>>,
+
+    // Original global transforms of the nodes, that were modified by the last
+    // `interpolate_physics_transforms` call.
+    #[reflect(hidden)]
+    interpolated_transforms: Vec<(Handle<Node>, Matrix4<f32>)>,
 }
 
 impl Default for Graph {
@@ -163,7 +200,9 @@ impl Default for Graph {
             script_message_receiver: rx,
             script_message_sender: tx,
             lightmap: None,
+            debug_drawer: Default::default(),
             instance_id_map: Default::default(),
+            interpolated_transforms: Default::default(),
         }
     }
 }
@@ -241,6 +280,9 @@ pub struct GraphUpdateSwitches {
     /// Whether the graph update is paused or not. Paused graphs won't be updated and their sound content will be also paused
     /// so it won't emit any sounds.
     pub paused: bool,
+    /// Enables or disables scene streaming (loading and unloading of scene chunks). See
+    /// [`crate::scene::streaming::SceneStreaming`] for more info.
+    pub streaming: bool,
 }
 
 impl Default for GraphUpdateSwitches {
@@ -251,6 +293,7 @@ impl Default for GraphUpdateSwitches {
             node_overrides: Default::default(),
             delete_dead_nodes: true,
             paused: false,
+            streaming: true,
         }
     }
 }
@@ -286,7 +329,9 @@ impl Graph {
             script_message_receiver: rx,
             script_message_sender: tx,
             lightmap: None,
+            debug_drawer: Default::default(),
             instance_id_map,
+            interpolated_transforms: Default::default(),
         }
     }
 
@@ -493,6 +538,18 @@ impl Graph {
         self.link_nodes(child, parent);
     }
 
+    /// Moves the given node to the given position in the list of children of its parent. The position
+    /// is clamped to the amount of children. Returns the previous position of the node, or `None` if
+    /// the node does not exist or it has no parent.
+    pub fn set_child_position(&mut self, child: Handle<Node>, position: usize) -> Option<usize> {
+        let parent = self.pool.try_borrow(child)?.parent();
+        let children = &mut self.pool.try_borrow_mut(parent)?.children;
+        let prev_position = children.iter().position(|c| *c == child)?;
+        children.remove(prev_position);
+        children.insert(position.min(children.len()), child);
+        Some(prev_position)
+    }
+
     /// Unlinks specified node from its parent and attaches it to root graph node.
     #[inline]
     pub fn unlink_node(&mut self, node_handle: Handle<Node>) {
@@ -515,6 +572,38 @@ impl Graph {
         })
     }
 
+    /// Searches for a **first** node with the given tag in the hierarchy starting from the given
+    /// `root_node`.
+    #[inline]
+    pub fn find_first_by_tag(
+        &self,
+        root_node: Handle<Node>,
+        tag: &str,
+    ) -> Option<(Handle<Node>, &Node)> {
+        self.find(root_node, &mut |n| n.tag() == tag)
+    }
+
+    /// Returns an iterator over every node in the graph, that has the given tag. The order of
+    /// the nodes is undefined.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use fyrox::scene::graph::Graph;
+    /// fn damage_enemies(graph: &Graph) {
+    ///     for (handle, enemy) in graph.find_by_tag("enemy") {
+    ///         println!("{} ({}) is an enemy!", enemy.name(), handle);
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn find_by_tag<'a>(
+        &'a self,
+        tag: &'a str,
+    ) -> impl Iterator<Item = (Handle<Node>, &'a Node)> + 'a {
+        self.pool.pair_iter().filter(move |(_, n)| n.tag() == tag)
+    }
+
     /// Creates deep copy of node with all children. This is relatively heavy operation!
     /// In case if any error happened it returns `Handle::NONE`. This method can be used
     /// to create exact copy of given node hierarchy. For example you can prepare rocket
@@ -718,7 +807,17 @@ impl Graph {
             }
         }
 
-        // Sync materials with shaders.
+        self.sync_materials_to_shaders(resource_manager);
+
+        self.apply_lightmap();
+
+        Log::writeln(MessageKind::Information, "Graph resolved successfully!");
+    }
+
+    /// Synchronizes property sets of every material used in the graph with their shaders. This
+    /// method is used by shader hot reloading, see
+    /// [`crate::material::Material::sync_to_shader`] for more info.
+    pub fn sync_materials_to_shaders(&mut self, resource_manager: &ResourceManager) {
         let mut materials = FxHashSet::default();
         for node in self.linear_iter_mut() {
             (node as &mut dyn Reflect).enumerate_fields_recursively(
@@ -739,10 +838,6 @@ impl Graph {
                 material.sync_to_shader(resource_manager);
             }
         }
-
-        self.apply_lightmap();
-
-        Log::writeln(MessageKind::Information, "Graph resolved successfully!");
     }
 
     /// Tries to set new lightmap to scene.
@@ -782,11 +877,50 @@ impl Graph {
         Ok(std::mem::replace(&mut self.lightmap, Some(lightmap)))
     }
 
+    /// Removes current lightmap (if any) and resets lightmap textures of every surface, that
+    /// used it. Returns the removed lightmap.
+    pub fn clear_lightmap(&mut self) -> Option<Lightmap> {
+        let lightmap = self.lightmap.take()?;
+        for handle in lightmap.map.keys() {
+            if let Some(mesh) = self
+                .pool
+                .try_borrow_mut(*handle)
+                .and_then(|n| n.cast_mut::<Mesh>())
+            {
+                for surface in mesh.surfaces_mut() {
+                    let mut material_state = surface.material().state();
+                    if let Some(material) = material_state.data() {
+                        Log::verify(material.set_property(
+                            &ImmutableString::new("lightmapTexture"),
+                            PropertyValue::Sampler {
+                                value: None,
+                                fallback: SamplerFallback::Black,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+        Some(lightmap)
+    }
+
     /// Returns current lightmap.
     pub fn lightmap(&self) -> Option<&Lightmap> {
         self.lightmap.as_ref()
     }
 
+    /// Returns a reference to the debug drawer of the graph. It could be used to draw debug
+    /// primitives (lines, spheres, text, etc.) from scripts and plugins. See [`DebugDrawer`] docs
+    /// for more info.
+    pub fn debug_drawer(&mut self) -> &mut DebugDrawer {
+        &mut self.debug_drawer
+    }
+
+    /// Returns a shared reference to the debug drawer of the graph.
+    pub fn debug_drawer_ref(&self) -> &DebugDrawer {
+        &self.debug_drawer
+    }
+
     fn apply_lightmap(&mut self) {
         // Re-apply lightmap if any. This has to be done after resolve because we must patch surface
         // data at this stage, but if we'd do this before we wouldn't be able to do this because
@@ -1069,7 +1203,13 @@ impl Graph {
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
         self.sound_context.state().pause(switches.paused);
 
+        self.restore_interpolated_transforms();
+
+        self.debug_drawer
+            .update(if switches.paused { 0.0 } else { dt });
+
         if switches.paused {
+            self.draw_physics_debug_overlay();
             return;
         }
 
@@ -1094,6 +1234,8 @@ impl Graph {
             self.performance_statistics.physics2d = self.physics2d.performance_statistics.clone();
         }
 
+        self.draw_physics_debug_overlay();
+
         self.performance_statistics.sound_update_time =
             self.sound_context.state().full_render_duration();
 
@@ -1111,6 +1253,163 @@ impl Graph {
                 );
             }
         }
+
+        self.sound_context.apply_reverb_zones();
+
+        self.blend_ragdolls(&switches);
+        self.solve_inverse_kinematics(&switches);
+    }
+
+    /// Interpolates rendered transforms of every dynamic rigid body (and its descendants) between the
+    /// last two physics steps. `alpha` defines the position between the steps, it is usually a ratio of
+    /// the time left in the accumulator of a fixed time step loop to the length of the step. It removes
+    /// visual jitter of physical objects when the frame rate is not a multiple of the update rate.
+    ///
+    /// Only global transforms are affected, the changes are reverted at the beginning of the next
+    /// [`Self::update`] call (or the next call of this method), so the simulation itself stays intact.
+    /// [`crate::engine::executor::Executor`] calls this method automatically, if interpolation is
+    /// enabled in its [`crate::engine::executor::TimeStepSettings`].
+    pub fn interpolate_physics_transforms(&mut self, alpha: f32) {
+        self.restore_interpolated_transforms();
+
+        let alpha = alpha.clamp(0.0, 1.0);
+
+        let targets = self
+            .pool
+            .pair_iter()
+            .filter_map(|(handle, node)| {
+                let target = if let Some(rigid_body) = node.cast::<RigidBody>() {
+                    rigid_body.interpolation_target.get()
+                } else if let Some(rigid_body) = node.cast::<dim2::rigidbody::RigidBody>() {
+                    rigid_body.interpolation_target.get()
+                } else {
+                    None
+                }?;
+                Some((handle, target))
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        for (&body, target) in targets.iter() {
+            let current = self.pool[body].global_transform();
+            let Some(inv_current) = current.try_inverse() else {
+                continue;
+            };
+            let delta = interpolate_transform(&current, target, alpha) * inv_current;
+
+            self.stack.clear();
+            self.stack.push(body);
+            while let Some(handle) = self.stack.pop() {
+                let node = &self.pool[handle];
+                let global_transform = node.global_transform();
+                self.interpolated_transforms
+                    .push((handle, global_transform));
+                node.global_transform.set(delta * global_transform);
+                // Nested rigid bodies are interpolated on their own.
+                self.stack.extend(
+                    node.children()
+                        .iter()
+                        .filter(|child| !targets.contains_key(*child)),
+                );
+            }
+        }
+    }
+
+    fn restore_interpolated_transforms(&mut self) {
+        for (handle, global_transform) in self.interpolated_transforms.drain(..) {
+            if let Some(node) = self.pool.try_borrow(handle) {
+                node.global_transform.set(global_transform);
+            }
+        }
+    }
+
+    /// Draws physics worlds using the debug drawer, if it is enabled in their debug draw options.
+    /// See [`physics::PhysicsDebugDrawOptions`] docs for more info.
+    fn draw_physics_debug_overlay(&mut self) {
+        let physics = &self.physics;
+        if physics.debug_draw_options.enabled {
+            self.debug_drawer.draw_with(0.0, |ctx| physics.draw(ctx));
+        }
+
+        let physics2d = &self.physics2d;
+        if physics2d.debug_draw_options.enabled {
+            self.debug_drawer.draw_with(0.0, |ctx| physics2d.draw(ctx));
+        }
+    }
+
+    /// Applies physical poses of every active ragdoll in the graph to their bones. It must be done after
+    /// every other node was updated, so the physical pose could be blended with the animated pose.
+    fn blend_ragdolls(&mut self, switches: &GraphUpdateSwitches) {
+        let ragdolls = self
+            .pool
+            .pair_iter()
+            .filter_map(|(handle, node)| {
+                let ragdoll = node.cast::<Ragdoll>()?;
+                if ragdoll.is_active()
+                    && node.is_globally_enabled()
+                    && switches
+                        .node_overrides
+                        .as_ref()
+                        .map_or(true, |overrides| overrides.contains(&handle))
+                {
+                    Some((ragdoll.root_limb().clone(), ragdoll.blend_weight()))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if ragdolls.is_empty() {
+            return;
+        }
+
+        self.update_hierarchical_data();
+
+        for (root_limb, blend_weight) in ragdolls {
+            Ragdoll::apply_physical_pose(&root_limb, blend_weight, self);
+        }
+    }
+
+    /// Solves every enabled IK chain in the graph. It must be done after every other node was updated,
+    /// so the chains are applied on top of the animated pose.
+    fn solve_inverse_kinematics(&mut self, switches: &GraphUpdateSwitches) {
+        let chains = self
+            .pool
+            .pair_iter()
+            .filter(|(handle, node)| {
+                node.cast::<IkChain>().is_some()
+                    && node.is_globally_enabled()
+                    && switches
+                        .node_overrides
+                        .as_ref()
+                        .map_or(true, |overrides| overrides.contains(handle))
+            })
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+
+        if chains.is_empty() {
+            return;
+        }
+
+        // Animations could change local transforms of the bones, so global transforms must be
+        // refreshed first.
+        self.update_hierarchical_data();
+
+        for chain_handle in chains {
+            let Some(chain) = self.pool[chain_handle].cast::<IkChain>() else {
+                continue;
+            };
+
+            let rotations = chain.solve(self);
+            let Some(&(first_bone, _)) = rotations.first() else {
+                continue;
+            };
+
+            for (bone, rotation) in rotations {
+                self.pool[bone].local_transform_mut().set_rotation(rotation);
+            }
+
+            self.update_hierarchical_data_for_descendants(first_bone);
+        }
     }
 
     /// Returns capacity of internal pool. Can be used to iterate over all **potentially**
@@ -1651,10 +1950,15 @@ mod test {
             algebra::{Matrix4, Vector3},
             futures::executor::block_on,
             pool::Handle,
+            sstorage::ImmutableString,
             visitor::Visitor,
         },
         engine::{self, SerializationContext},
-        resource::model::{Model, ModelResourceExtension},
+        material::PropertyValue,
+        resource::{
+            model::{Model, ModelResourceExtension},
+            texture::test::create_test_texture,
+        },
         scene::{
             base::BaseBuilder,
             graph::Graph,
@@ -1664,10 +1968,13 @@ mod test {
             },
             node::Node,
             pivot::{Pivot, PivotBuilder},
+            rigidbody::{RigidBody, RigidBodyBuilder},
             transform::TransformBuilder,
             Scene, SceneLoader,
         },
+        utils::lightmap::{Lightmap, LightmapEntry},
     };
+    use fxhash::FxHashMap;
     use std::{fs, path::Path, sync::Arc};
 
     #[test]
@@ -1737,6 +2044,37 @@ mod test {
         assert_eq!(result.1, "A");
     }
 
+    #[test]
+    fn test_graph_search_by_tag() {
+        let mut graph = Graph::new();
+
+        let a =
+            PivotBuilder::new(BaseBuilder::new().with_tag("enemy".to_string())).build(&mut graph);
+        let b = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_tag("enemy".to_string())
+                .with_children(&[PivotBuilder::new(
+                    BaseBuilder::new().with_tag("ally".to_string()),
+                )
+                .build(&mut graph)]),
+        )
+        .build(&mut graph);
+
+        let mut enemies = graph
+            .find_by_tag("enemy")
+            .map(|(h, _)| h)
+            .collect::<Vec<_>>();
+        enemies.sort();
+        let mut expected = vec![a, b];
+        expected.sort();
+        assert_eq!(enemies, expected);
+        assert_eq!(graph.find_by_tag("ally").count(), 1);
+        assert_eq!(graph.find_by_tag("neutral").count(), 0);
+
+        assert_eq!(graph.find_first_by_tag(b, "enemy").unwrap().0, b);
+        assert!(graph.find_first_by_tag(a, "ally").is_none());
+    }
+
     fn create_scene() -> Scene {
         let mut scene = Scene::new();
 
@@ -1871,4 +2209,90 @@ mod test {
                 .unwrap();
         }
     }
+
+    #[test]
+    fn test_clear_lightmap() {
+        let mut graph = Graph::new();
+        let mesh = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .build()])
+            .build(&mut graph);
+
+        let lightmap_texture = |graph: &Graph| {
+            let surface = &graph[mesh].as_mesh().surfaces()[0];
+            let mut material_state = surface.material().state();
+            let material = material_state.data().unwrap();
+            match material.property_ref(&ImmutableString::new("lightmapTexture")) {
+                Some(PropertyValue::Sampler { value, .. }) => value.clone(),
+                _ => None,
+            }
+        };
+
+        let mut map = FxHashMap::default();
+        map.insert(
+            mesh,
+            vec![LightmapEntry {
+                texture: Some(create_test_texture()),
+                lights: Default::default(),
+            }],
+        );
+        graph
+            .set_lightmap(Lightmap {
+                map,
+                patches: Default::default(),
+            })
+            .unwrap();
+        assert!(lightmap_texture(&graph).is_some());
+
+        assert!(graph.clear_lightmap().is_some());
+        assert!(graph.lightmap().is_none());
+        assert!(lightmap_texture(&graph).is_none());
+    }
+
+    #[test]
+    fn test_physics_transforms_interpolation() {
+        let mut graph = Graph::new();
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let body =
+            RigidBodyBuilder::new(BaseBuilder::new().with_children(&[child])).build(&mut graph);
+        graph.update_hierarchical_data();
+
+        graph[body]
+            .cast::<RigidBody>()
+            .unwrap()
+            .interpolation_target
+            .set(Some(Matrix4::new_translation(&Vector3::new(2.0, 0.0, 0.0))));
+
+        graph.interpolate_physics_transforms(0.5);
+        assert_eq!(graph[body].global_position(), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(graph[child].global_position(), Vector3::new(1.0, 1.0, 0.0));
+
+        graph.restore_interpolated_transforms();
+        assert_eq!(graph[body].global_position(), Vector3::default());
+        assert_eq!(graph[child].global_position(), Vector3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_set_child_position() {
+        let mut graph = Graph::new();
+        let a = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let b = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let c = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let root = graph.root;
+
+        assert_eq!(graph.set_child_position(c, 0), Some(2));
+        assert_eq!(graph[root].children(), &[c, a, b]);
+        assert_eq!(graph.set_child_position(c, 10), Some(0));
+        assert_eq!(graph[root].children(), &[a, b, c]);
+        assert_eq!(graph.set_child_position(root, 0), None);
+    }
 }
@@ -7,6 +7,7 @@ use crate::{
             Vector2, Vector3,
         },
         arrayvec::ArrayVec,
+        color::Color,
         instant,
         log::{Log, MessageKind},
         math::Matrix4Ext,
@@ -17,12 +18,13 @@ use crate::{
         visitor::prelude::*,
         BiDirHashMap,
     },
+    resource::convex_decomposition::ConvexDecompositionResource,
     scene::{
         self,
         collider::{self, ColliderShape, GeometrySource},
-        debug::SceneDrawingContext,
+        debug::{Line, SceneDrawingContext},
         graph::{isometric_global_transform, NodePool},
-        joint::{JointLocalFrames, JointParams},
+        joint::{JointLocalFrames, JointMotor, JointParams},
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
             Mesh,
@@ -36,16 +38,21 @@ use crate::{
 use fyrox_core::algebra::Translation;
 use fyrox_core::uuid_provider;
 use rapier3d::{
+    control::{CharacterAutostep, CharacterLength, KinematicCharacterController},
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
         IslandManager, JointAxesMask, MultibodyJointHandle, MultibodyJointSet, RigidBody,
         RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
     },
     geometry::{
-        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
+        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid, HeightField,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
+    parry::shape::HeightFieldCellStatus,
+    pipeline::{
+        DebugRenderBackend, DebugRenderObject, DebugRenderPipeline, EventHandler, PhysicsPipeline,
+        QueryFilter, QueryPipeline,
+    },
     prelude::JointAxis,
 };
 use std::num::NonZeroUsize;
@@ -216,6 +223,49 @@ pub struct Intersection {
     pub toi: f32,
 }
 
+/// A result of a point projection on a collider. See [`PhysicsWorld::project_points`] for more info.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+
+    /// A projected point on the surface of the collider in world coordinates.
+    pub point: Vector3<f32>,
+
+    /// A flag, that tells whether the source point was inside the collider or not.
+    pub is_inside: bool,
+}
+
+/// A set of parameters for a kinematic movement of a character capsule.
+pub(crate) struct CharacterMotion {
+    /// Current position of the center of the capsule.
+    pub position: Vector3<f32>,
+    /// Desired translation of the capsule.
+    pub translation: Vector3<f32>,
+    /// Radius of the capsule.
+    pub radius: f32,
+    /// Half height of the cylindrical part of the capsule.
+    pub half_height: f32,
+    /// A small gap that is preserved between the capsule and the obstacles.
+    pub skin_width: f32,
+    /// Maximum height of the steps that the capsule could climb.
+    pub step_height: f32,
+    /// Maximum angle (in radians) of slopes that the capsule could climb.
+    pub max_slope_angle: f32,
+    /// Maximum distance at which the capsule will be snapped to the ground.
+    pub snap_distance: f32,
+    /// A body that will be ignored by the shape casts.
+    pub exclude_body: RigidBodyHandle,
+}
+
+/// Result of [`CharacterMotion`].
+pub(crate) struct CharacterMotionResult {
+    /// Actual translation of the capsule.
+    pub translation: Vector3<f32>,
+    /// Whether the capsule stands on the ground after the movement or not.
+    pub grounded: bool,
+}
+
 /// A set of options for the ray cast.
 pub struct RayCastOptions {
     /// A ray origin.
@@ -376,6 +426,34 @@ impl ContactPair {
     }
 }
 
+/// A kind of [`CollisionEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CollisionEventKind {
+    /// Two regular colliders started touching each other.
+    ContactBegin,
+    /// Two regular colliders stopped touching each other.
+    ContactEnd,
+    /// A collider started intersecting with a sensor (trigger) collider, or vice versa.
+    IntersectionBegin,
+    /// A collider stopped intersecting with a sensor (trigger) collider, or vice versa.
+    IntersectionEnd,
+}
+
+/// A collision event of a collider with enabled event reporting (see
+/// [`crate::scene::collider::Collider::set_report_events`]). Events are generated once per frame, after
+/// the physics step, and could be read in `on_update` of scripts using
+/// [`crate::scene::collider::Collider::collision_events`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionEvent {
+    /// A kind of the event.
+    pub kind: CollisionEventKind,
+    /// A handle of the other collider involved in the collision.
+    pub other: Handle<Node>,
+    /// Contact information (contact points, normals and impulses). It is available only for
+    /// [`CollisionEventKind::ContactBegin`] events.
+    pub contact: Option<ContactPair>,
+}
+
 /// Intersection info for pair of colliders.
 #[derive(Debug, Clone, PartialEq)]
 pub struct IntersectionPair {
@@ -432,23 +510,42 @@ fn convert_joint_params(
                     [v.z_limits_angles.start, v.z_limits_angles.end],
                 );
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.x_motor);
+            set_joint_motor(&mut joint, JointAxis::AngY, &v.y_motor);
+            set_joint_motor(&mut joint, JointAxis::AngZ, &v.z_motor);
         }
         scene::joint::JointParams::FixedJoint(_) => {}
         scene::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::X, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::X, &v.motor);
         }
         scene::joint::JointParams::RevoluteJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::AngX, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
         }
     }
 
     joint
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force);
+    }
+}
+
 /// Creates new trimesh collider shape from given mesh node. It also bakes scale into
 /// vertices of trimesh because rapier does not support collider scaling yet.
 fn make_trimesh(
@@ -624,53 +721,162 @@ fn make_polyhedron_shape(owner_inv_transform: Matrix4<f32>, mesh: &Mesh) -> Shar
     SharedShape::convex_decomposition(&vertices, &indices)
 }
 
-/// Creates height field shape from given terrain.
-fn make_heightfield(terrain: &Terrain) -> SharedShape {
-    assert!(!terrain.chunks_ref().is_empty());
+/// Creates a compound shape from the given convex decomposition. Returns `None` if the resource
+/// is not loaded yet (or failed to load) or there are no valid convex hulls in it.
+fn make_compound_shape(resource: &ConvexDecompositionResource) -> Option<SharedShape> {
+    let mut state = resource.state();
+    let decomposition = state.data()?;
+
+    let parts = decomposition
+        .hulls
+        .iter()
+        .filter_map(|hull| {
+            let points = hull
+                .vertices
+                .iter()
+                .map(|v| Point3::from(*v))
+                .collect::<Vec<_>>();
+            SharedShape::convex_hull(&points).map(|shape| (Isometry3::identity(), shape))
+        })
+        .collect::<Vec<_>>();
 
+    if parts.is_empty() {
+        None
+    } else {
+        Some(SharedShape::compound(parts))
+    }
+}
+
+/// Returns the amount of rows and columns of a height field for the given terrain.
+fn heightfield_dimensions(terrain: &Terrain) -> (usize, usize) {
+    let height_map_size = terrain.height_map_size();
+    let nrows = height_map_size.y as usize * terrain.length_chunks().len();
+    let ncols = height_map_size.x as usize * terrain.width_chunks().len();
+    (nrows, ncols)
+}
+
+/// Returns the scale of a height field for the given terrain.
+fn heightfield_scale(terrain: &Terrain) -> Vector3<f32> {
     // HACK: Temporary solution for https://github.com/FyroxEngine/Fyrox/issues/365
     let scale = terrain.local_transform().scale();
 
-    // Count rows and columns.
+    Vector3::new(
+        terrain.chunk_size().x * scale.x * terrain.width_chunks().len() as f32,
+        1.0,
+        terrain.chunk_size().y * scale.z * terrain.length_chunks().len() as f32,
+    )
+}
+
+/// Copies height maps of the terrain chunks that were modified after the given stamp into the
+/// combined height map.
+fn copy_terrain_heights(terrain: &Terrain, since_stamp: u64, heights: &mut DMatrix<f32>) {
+    let scale = terrain.local_transform().scale();
     let height_map_size = terrain.height_map_size();
-    let nrows = height_map_size.y * terrain.length_chunks().len() as u32;
-    let ncols = height_map_size.x * terrain.width_chunks().len() as u32;
+    let width_chunks = terrain.width_chunks().len();
 
-    // Combine height map of each chunk into bigger one.
-    let mut ox = 0;
-    let mut oz = 0;
-    let mut data = vec![0.0; (nrows * ncols) as usize];
-    for cz in 0..terrain.length_chunks().len() {
-        for cx in 0..terrain.width_chunks().len() {
-            let chunk = &terrain.chunks_ref()[cz * terrain.width_chunks().len() + cx];
-            let texture = chunk.heightmap().data_ref();
-            let height_map = texture.data_of_type::<f32>().unwrap();
-            for iy in 0..height_map_size.y {
-                for ix in 0..height_map_size.x {
-                    let value = height_map[(iy * height_map_size.x + ix) as usize] * scale.y;
-                    data[((ox + ix) * nrows + oz + iy) as usize] = value;
-                }
+    for (i, chunk) in terrain.chunks_ref().iter().enumerate() {
+        // Streamed out chunks keep their heights in the height field.
+        if chunk.height_map_stamp() <= since_stamp || !chunk.is_loaded() {
+            continue;
+        }
+
+        let ox = (i % width_chunks) as u32 * height_map_size.x;
+        let oz = (i / width_chunks) as u32 * height_map_size.y;
+
+        let texture = chunk.heightmap().data_ref();
+        let height_map = texture.data_of_type::<f32>().unwrap();
+        for iy in 0..height_map_size.y {
+            for ix in 0..height_map_size.x {
+                let value = height_map[(iy * height_map_size.x + ix) as usize] * scale.y;
+                heights[((oz + iy) as usize, (ox + ix) as usize)] = value;
             }
+        }
+    }
+}
+
+/// Creates height field shape from the given heights and removes the cells that are inside the
+/// holes of the terrain chunks.
+fn make_heightfield_with_holes(
+    terrain: &Terrain,
+    heights: DMatrix<f32>,
+    scale: Vector3<f32>,
+) -> SharedShape {
+    let mut heightfield = HeightField::new(heights, scale);
+
+    let height_map_size = terrain.height_map_size();
+    let width_chunks = terrain.width_chunks().len();
+    let cells_x = height_map_size.x.saturating_sub(1);
+    let cells_z = height_map_size.y.saturating_sub(1);
+
+    for (i, chunk) in terrain.chunks_ref().iter().enumerate() {
+        if chunk.hole_mask().is_none() {
+            continue;
+        }
+
+        let ox = (i % width_chunks) as u32 * height_map_size.x;
+        let oz = (i / width_chunks) as u32 * height_map_size.y;
 
-            ox += height_map_size.x;
+        for iz in 0..cells_z {
+            for ix in 0..cells_x {
+                let uv = Vector2::new(
+                    (ix as f32 + 0.5) / cells_x as f32,
+                    (iz as f32 + 0.5) / cells_z as f32,
+                );
+                if chunk.is_hole(uv) {
+                    heightfield.set_cell_status(
+                        (oz + iz) as usize,
+                        (ox + ix) as usize,
+                        HeightFieldCellStatus::CELL_REMOVED,
+                    );
+                }
+            }
         }
+    }
+
+    SharedShape::new(heightfield)
+}
+
+/// Creates height field shape from given terrain.
+fn make_heightfield(terrain: &Terrain) -> SharedShape {
+    assert!(!terrain.chunks_ref().is_empty());
+
+    // Combine height map of each chunk into bigger one.
+    let (nrows, ncols) = heightfield_dimensions(terrain);
+    let mut heights = DMatrix::from_data(VecStorage::new(
+        Dyn(nrows),
+        Dyn(ncols),
+        vec![0.0; nrows * ncols],
+    ));
+    copy_terrain_heights(terrain, 0, &mut heights);
+
+    make_heightfield_with_holes(terrain, heights, heightfield_scale(terrain))
+}
 
-        ox = 0;
-        oz += height_map_size.y;
+/// Updates height field shape using height maps of the terrain chunks that were modified after
+/// the given stamp. The rest of the heights are taken from the existing height field. If the layout
+/// of the terrain has changed, the height field is rebuilt from scratch.
+fn update_heightfield(terrain: &Terrain, existing: &HeightField, since_stamp: u64) -> SharedShape {
+    let scale = heightfield_scale(terrain);
+    let (nrows, ncols) = heightfield_dimensions(terrain);
+    let heights = existing.heights();
+    if heights.nrows() != nrows || heights.ncols() != ncols || *existing.scale() != scale {
+        return make_heightfield(terrain);
     }
 
-    SharedShape::heightfield(
-        DMatrix::from_data(VecStorage::new(
-            Dyn(nrows as usize),
-            Dyn(ncols as usize),
-            data,
-        )),
-        Vector3::new(
-            terrain.chunk_size().x * scale.x * terrain.width_chunks().len() as f32,
-            1.0,
-            terrain.chunk_size().y * scale.z * terrain.length_chunks().len() as f32,
-        ),
-    )
+    let mut heights = heights.clone();
+    copy_terrain_heights(terrain, since_stamp, &mut heights);
+    make_heightfield_with_holes(terrain, heights, scale)
+}
+
+/// Returns a terrain that is used as a geometry source for a height field shape.
+fn heightfield_terrain<'a>(shape: &ColliderShape, nodes: &'a NodePool) -> Option<&'a Terrain> {
+    if let ColliderShape::Heightfield(heightfield) = shape {
+        nodes
+            .try_borrow(heightfield.geometry_source.0)
+            .and_then(|n| n.cast::<Terrain>())
+    } else {
+        None
+    }
 }
 
 // Converts descriptor in a shared shape.
@@ -724,6 +930,10 @@ fn collider_shape_into_native_shape(
             .try_borrow(polyhedron.geometry_source.0)
             .and_then(|n| n.cast::<Mesh>())
             .map(|mesh| make_polyhedron_shape(owner_inv_global_transform, mesh)),
+        ColliderShape::ConvexDecomposition(decomposition) => decomposition
+            .decomposition
+            .as_ref()
+            .and_then(make_compound_shape),
     }
 }
 
@@ -876,6 +1086,131 @@ impl Default for IntegrationParameters {
     }
 }
 
+/// A set of options that defines which parts of a physics world will be drawn by the debug overlay.
+/// Colliders are color-coded by the type of their rigid body (see [`PhysicsDebugDrawOptions::body_color`])
+/// and drawn with dimmed colors when their rigid body is sleeping.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::scene::graph::Graph;
+/// fn toggle_physics_overlay(graph: &mut Graph) {
+///     let options = &mut graph.physics.debug_draw_options;
+///     options.enabled = !options.enabled;
+///     // Contacts could be very noisy in large scenes.
+///     options.contacts = false;
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct PhysicsDebugDrawOptions {
+    /// Whether the physics world should be drawn automatically every frame using the debug drawer of
+    /// the graph or not. Default is `false`. It is a runtime toggle, that could be used from scripts
+    /// and plugins. Other flags define what exactly will be drawn.
+    pub enabled: bool,
+    /// Whether wireframes of colliders should be drawn or not. Default is `true`.
+    pub colliders: bool,
+    /// Whether contact points (and contact normals) should be drawn or not. Default is `true`.
+    pub contacts: bool,
+    /// Whether anchors of joints should be drawn or not. Default is `true`.
+    pub joints: bool,
+    /// Whether centers of mass of dynamic rigid bodies should be drawn or not. Default is `true`.
+    pub centers_of_mass: bool,
+}
+
+impl Default for PhysicsDebugDrawOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            colliders: true,
+            contacts: true,
+            joints: true,
+            centers_of_mass: true,
+        }
+    }
+}
+
+impl PhysicsDebugDrawOptions {
+    /// Color of colliders, that are not attached to any rigid body.
+    pub const FREE_COLLIDER_COLOR: Color = Color::opaque(255, 0, 255);
+    /// Color of contact points.
+    pub const CONTACT_COLOR: Color = Color::opaque(255, 40, 40);
+    /// Color of contact normals.
+    pub const CONTACT_NORMAL_COLOR: Color = Color::opaque(255, 200, 0);
+    /// Color of joint anchors.
+    pub const JOINT_ANCHOR_COLOR: Color = Color::opaque(0, 200, 255);
+    /// Color of centers of mass.
+    pub const CENTER_OF_MASS_COLOR: Color = Color::opaque(255, 255, 255);
+
+    /// Returns a color, that is used to draw colliders of a rigid body with the given type. Dynamic
+    /// bodies are green, kinematic - yellow, static - gray. Colliders of sleeping bodies are drawn
+    /// with half brightness.
+    pub fn body_color(body_type: scene::rigidbody::RigidBodyType, sleeping: bool) -> Color {
+        use scene::rigidbody::RigidBodyType;
+
+        let color = match body_type {
+            RigidBodyType::Dynamic => Color::opaque(60, 220, 60),
+            RigidBodyType::Static => Color::opaque(150, 150, 150),
+            RigidBodyType::KinematicPositionBased | RigidBodyType::KinematicVelocityBased => {
+                Color::opaque(230, 220, 40)
+            }
+        };
+
+        if sleeping {
+            Color::opaque(color.r / 2, color.g / 2, color.b / 2)
+        } else {
+            color
+        }
+    }
+
+    pub(crate) fn draw_marker(
+        context: &mut SceneDrawingContext,
+        position: Vector3<f32>,
+        size: f32,
+        color: Color,
+    ) {
+        for axis in [Vector3::x(), Vector3::y(), Vector3::z()] {
+            context.add_line(Line {
+                begin: position - axis.scale(size),
+                end: position + axis.scale(size),
+                color,
+            });
+        }
+    }
+}
+
+// Recolors collider wireframes, produced by the debug render pipeline of rapier, according to
+// the type and sleep state of their rigid bodies.
+struct ColliderDebugRenderBackend<'a> {
+    context: &'a mut SceneDrawingContext,
+    bodies: &'a RigidBodySet,
+}
+
+impl<'a> DebugRenderBackend for ColliderDebugRenderBackend<'a> {
+    fn draw_line(
+        &mut self,
+        object: DebugRenderObject,
+        a: Point3<f32>,
+        b: Point3<f32>,
+        _color: [f32; 4],
+    ) {
+        let color = match object {
+            DebugRenderObject::Collider(_, collider) => collider
+                .parent()
+                .and_then(|parent| self.bodies.get(parent))
+                .map_or(PhysicsDebugDrawOptions::FREE_COLLIDER_COLOR, |body| {
+                    PhysicsDebugDrawOptions::body_color(body.body_type().into(), body.is_sleeping())
+                }),
+            _ => return,
+        };
+
+        self.context.add_line(Line {
+            begin: a.coords,
+            end: b.coords,
+            color,
+        })
+    }
+}
+
 /// Physics world is responsible for physics simulation in the engine. There is a very few public
 /// methods, mostly for ray casting. You should add physical entities using scene graph nodes, such
 /// as RigidBody, Collider, Joint.
@@ -895,6 +1230,11 @@ pub struct PhysicsWorld {
     #[reflect(hidden)]
     pub performance_statistics: PhysicsPerformanceStatistics,
 
+    /// A set of options for the debug overlay. See [`PhysicsDebugDrawOptions`] docs for more info.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub debug_draw_options: PhysicsDebugDrawOptions,
+
     // Current physics pipeline.
     #[visit(skip)]
     #[reflect(hidden)]
@@ -998,6 +1338,7 @@ impl PhysicsWorld {
             event_handler: Box::new(()),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
+            debug_draw_options: Default::default(),
             debug_render_pipeline: Default::default(),
         }
     }
@@ -1103,17 +1444,91 @@ impl PhysicsWorld {
         }
     }
 
-    /// Draws physics world. Very useful for debugging, it allows you to see where are
-    /// rigid bodies, which colliders they have and so on.
+    /// Draws physics world using [`Self::debug_draw_options`]. Very useful for debugging, it allows
+    /// you to see where are rigid bodies, which colliders they have and so on.
     pub fn draw(&self, context: &mut SceneDrawingContext) {
-        self.debug_render_pipeline.lock().render(
-            context,
-            &self.bodies,
-            &self.colliders,
-            &self.joints.set,
-            &self.multibody_joints.set,
-            &self.narrow_phase,
-        );
+        self.draw_with_options(context, &self.debug_draw_options)
+    }
+
+    /// Draws physics world using the given options. See [`PhysicsDebugDrawOptions`] docs for more
+    /// info.
+    pub fn draw_with_options(
+        &self,
+        context: &mut SceneDrawingContext,
+        options: &PhysicsDebugDrawOptions,
+    ) {
+        if options.colliders {
+            self.debug_render_pipeline.lock().render_colliders(
+                &mut ColliderDebugRenderBackend {
+                    context: &mut *context,
+                    bodies: &self.bodies,
+                },
+                &self.bodies,
+                &self.colliders,
+            );
+        }
+
+        if options.contacts {
+            for pair in self.narrow_phase.contact_pairs() {
+                if !pair.has_any_active_contact {
+                    continue;
+                }
+
+                for manifold in pair.manifolds.iter() {
+                    for contact in manifold.data.solver_contacts.iter() {
+                        let point = contact.point.coords;
+                        PhysicsDebugDrawOptions::draw_marker(
+                            context,
+                            point,
+                            0.03,
+                            PhysicsDebugDrawOptions::CONTACT_COLOR,
+                        );
+                        context.add_line(Line {
+                            begin: point,
+                            end: point + manifold.data.normal.scale(0.2),
+                            color: PhysicsDebugDrawOptions::CONTACT_NORMAL_COLOR,
+                        });
+                    }
+                }
+            }
+        }
+
+        if options.joints {
+            for (_, joint) in self.joints.set.iter() {
+                for (body, local_frame) in [
+                    (joint.body1, &joint.data.local_frame1),
+                    (joint.body2, &joint.data.local_frame2),
+                ] {
+                    if let Some(body) = self.bodies.get(body) {
+                        let anchor = (body.position() * local_frame).translation.vector;
+                        context.add_line(Line {
+                            begin: body.center_of_mass().coords,
+                            end: anchor,
+                            color: PhysicsDebugDrawOptions::JOINT_ANCHOR_COLOR,
+                        });
+                        context.draw_wire_sphere(
+                            anchor,
+                            0.05,
+                            8,
+                            PhysicsDebugDrawOptions::JOINT_ANCHOR_COLOR,
+                        );
+                    }
+                }
+            }
+        }
+
+        if options.centers_of_mass {
+            for (_, body) in self.bodies.iter() {
+                if body.is_dynamic() {
+                    PhysicsDebugDrawOptions::draw_marker(
+                        context,
+                        body.center_of_mass().coords,
+                        0.1,
+                        PhysicsDebugDrawOptions::CENTER_OF_MASS_COLOR,
+                    );
+                }
+            }
+        }
     }
 
     /// Casts a ray with given options.
@@ -1176,6 +1591,102 @@ impl PhysicsWorld {
         );
     }
 
+    /// Projects every given point (in world coordinates) on the surface of the closest collider, that
+    /// passes the given interaction groups filter. Sensors are ignored. `results` will contain a
+    /// projection for every point (or `None` if there's no suitable collider), in the same order as
+    /// the points. Use this method to project multiple points at once, it is much faster than
+    /// projecting the points one-by-one.
+    pub fn project_points(
+        &self,
+        points: &[Vector3<f32>],
+        groups: collider::InteractionGroups,
+        results: &mut Vec<Option<PointProjection>>,
+    ) {
+        let mut query = self.query.borrow_mut();
+
+        // See comments in `cast_ray` method.
+        query.update(&self.bodies, &self.colliders);
+
+        let filter = QueryFilter::new()
+            .exclude_sensors()
+            .groups(InteractionGroups::new(
+                u32_to_group(groups.memberships.0),
+                u32_to_group(groups.filter.0),
+            ));
+
+        results.clear();
+        results.extend(points.iter().map(|point| {
+            query
+                .project_point(
+                    &self.bodies,
+                    &self.colliders,
+                    &Point3::from(*point),
+                    false,
+                    filter,
+                )
+                .map(|(handle, projection)| PointProjection {
+                    collider: Handle::decode_from_u128(
+                        self.colliders.get(handle).unwrap().user_data,
+                    ),
+                    point: projection.point.coords,
+                    is_inside: projection.is_inside,
+                })
+        }));
+    }
+
+    /// Moves a capsule using a series of shape casts, sliding it along obstacles, climbing steps
+    /// and slopes and snapping it to the ground. The capsule itself is not added to the world,
+    /// only the actual translation is calculated.
+    pub(crate) fn move_character(&self, dt: f32, motion: CharacterMotion) -> CharacterMotionResult {
+        let mut query = self.query.borrow_mut();
+
+        query.update(&self.bodies, &self.colliders);
+
+        let controller = KinematicCharacterController {
+            up: Vector3::y_axis(),
+            offset: CharacterLength::Absolute(motion.skin_width),
+            slide: true,
+            autostep: if motion.step_height > 0.0 {
+                Some(CharacterAutostep {
+                    max_height: CharacterLength::Absolute(motion.step_height),
+                    min_width: CharacterLength::Absolute(motion.radius * 0.5),
+                    include_dynamic_bodies: false,
+                })
+            } else {
+                None
+            },
+            max_slope_climb_angle: motion.max_slope_angle,
+            min_slope_slide_angle: motion.max_slope_angle,
+            snap_to_ground: if motion.snap_distance > 0.0 {
+                Some(CharacterLength::Absolute(motion.snap_distance))
+            } else {
+                None
+            },
+            ..Default::default()
+        };
+
+        let shape = SharedShape::capsule_y(motion.half_height, motion.radius);
+
+        let movement = controller.move_shape(
+            dt,
+            &self.bodies,
+            &self.colliders,
+            &query,
+            &*shape,
+            &Isometry3::translation(motion.position.x, motion.position.y, motion.position.z),
+            motion.translation,
+            QueryFilter::new()
+                .exclude_sensors()
+                .exclude_rigid_body(motion.exclude_body),
+            |_| {},
+        );
+
+        CharacterMotionResult {
+            translation: movement.translation,
+            grounded: movement.grounded,
+        }
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::rigidbody::RigidBody,
@@ -1435,6 +1946,11 @@ impl PhysicsWorld {
                             nodes,
                         ) {
                             native.set_shape(shape);
+                            collider_node.height_map_stamp.set(
+                                heightfield_terrain(&v, nodes)
+                                    .map(|t| t.height_map_stamp())
+                                    .unwrap_or_default(),
+                            );
                         }
                     });
                     collider_node
@@ -1466,6 +1982,25 @@ impl PhysicsWorld {
                         .try_sync_model(|v| native.set_restitution_combine_rule(v.into()));
                 }
             }
+
+            // Height fields are updated incrementally: only the chunks of the terrain that were
+            // modified since the last sync are copied into the existing height field.
+            if let Some(terrain) = heightfield_terrain(collider_node.shape(), nodes) {
+                let stamp = terrain.height_map_stamp();
+                let prev_stamp = collider_node.height_map_stamp.get();
+                if stamp > prev_stamp {
+                    if let Some(native) = self.colliders.get_mut(collider_node.native.get()) {
+                        let shape = native
+                            .shape()
+                            .as_heightfield()
+                            .map(|existing| update_heightfield(terrain, existing, prev_stamp));
+                        if let Some(shape) = shape {
+                            native.set_shape(shape);
+                        }
+                    }
+                    collider_node.height_map_stamp.set(stamp);
+                }
+            }
         } else if let Some(parent_body) = nodes
             .try_borrow(collider_node.parent())
             .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
@@ -1510,6 +2045,11 @@ impl PhysicsWorld {
                         self.add_collider(handle, rigid_body_native, builder.build());
 
                     collider_node.native.set(native_handle);
+                    collider_node.height_map_stamp.set(
+                        heightfield_terrain(collider_node.shape(), nodes)
+                            .map(|t| t.height_map_stamp())
+                            .unwrap_or_default(),
+                    );
 
                     Log::writeln(
                         MessageKind::Information,
@@ -1665,6 +2205,87 @@ impl PhysicsWorld {
             .filter_map(|c| ContactPair::from_native(c, self))
     }
 
+    /// Compares current contacts and intersections of the given collider with the previous ones
+    /// and generates begin/end events for every difference. `touching` holds handles of the
+    /// colliders that touched the collider on the previous frame (with a flag that tells whether
+    /// it was an intersection or a contact), it will be updated to hold the current state.
+    pub(crate) fn collect_collision_events(
+        &self,
+        collider: ColliderHandle,
+        touching: &mut Vec<(Handle<Node>, bool)>,
+        events: &mut Vec<CollisionEvent>,
+    ) {
+        let user_data_of = |other: ColliderHandle| {
+            self.colliders
+                .get(other)
+                .map(|c| Handle::<Node>::decode_from_u128(c.user_data))
+        };
+
+        let mut current = Vec::new();
+        for pair in self.narrow_phase.contact_pairs_with(collider) {
+            if pair.has_any_active_contact {
+                let other = if pair.collider1 == collider {
+                    pair.collider2
+                } else {
+                    pair.collider1
+                };
+                if let Some(other) = user_data_of(other) {
+                    current.push((other, false));
+                }
+            }
+        }
+        for (collider1, collider2, intersecting) in
+            self.narrow_phase.intersection_pairs_with(collider)
+        {
+            if intersecting {
+                let other = if collider1 == collider {
+                    collider2
+                } else {
+                    collider1
+                };
+                if let Some(other) = user_data_of(other) {
+                    current.push((other, true));
+                }
+            }
+        }
+
+        for &(other, is_intersection) in touching.iter() {
+            if !current.contains(&(other, is_intersection)) {
+                events.push(CollisionEvent {
+                    kind: if is_intersection {
+                        CollisionEventKind::IntersectionEnd
+                    } else {
+                        CollisionEventKind::ContactEnd
+                    },
+                    other,
+                    contact: None,
+                });
+            }
+        }
+
+        for &(other, is_intersection) in current.iter() {
+            if !touching.contains(&(other, is_intersection)) {
+                events.push(if is_intersection {
+                    CollisionEvent {
+                        kind: CollisionEventKind::IntersectionBegin,
+                        other,
+                        contact: None,
+                    }
+                } else {
+                    CollisionEvent {
+                        kind: CollisionEventKind::ContactBegin,
+                        other,
+                        contact: self
+                            .contacts_with(collider)
+                            .find(|c| c.collider1 == other || c.collider2 == other),
+                    }
+                });
+            }
+        }
+
+        *touching = current;
+    }
+
     /// Returns an iterator over all contact pairs generated in this frame.
     pub fn contacts(&self) -> impl Iterator<Item = ContactPair> + '_ {
         self.narrow_phase
@@ -32,6 +32,61 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+/// Motor of a joint drives relative motion of the attached bodies along (or around) one of the joint
+/// axes. The motor works like a spring with the given stiffness and damping that tries to reach the
+/// target position and target velocity. A motor with zero stiffness is a velocity motor, a motor with
+/// zero damping is a position motor.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct JointMotor {
+    /// Whether the motor is enabled or not. Default is `false`.
+    #[reflect(description = "Whether the motor is enabled or not.")]
+    pub enabled: bool,
+
+    /// Target relative velocity of the attached bodies along the axis (linear velocity for linear
+    /// axes, angular velocity for angular axes).
+    #[reflect(description = "Target relative velocity of the attached bodies along the axis.")]
+    pub target_velocity: f32,
+
+    /// Target relative position of the attached bodies along the axis (in meters for linear axes,
+    /// in radians for angular axes).
+    #[reflect(description = "Target relative position of the attached bodies along the axis.")]
+    pub target_position: f32,
+
+    /// Stiffness of the motor. It defines how strongly the motor tries to reach the target position.
+    #[reflect(
+        min_value = 0.0,
+        description = "Stiffness of the motor. It defines how strongly the motor tries to reach the target position."
+    )]
+    pub stiffness: f32,
+
+    /// Damping of the motor. It defines how strongly the motor tries to reach the target velocity.
+    #[reflect(
+        min_value = 0.0,
+        description = "Damping of the motor. It defines how strongly the motor tries to reach the target velocity."
+    )]
+    pub damping: f32,
+
+    /// Maximum force (or torque for angular axes) the motor can apply.
+    #[reflect(
+        min_value = 0.0,
+        description = "Maximum force (or torque for angular axes) the motor can apply."
+    )]
+    pub max_force: f32,
+}
+
+impl Default for JointMotor {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_velocity: 0.0,
+            target_position: 0.0,
+            stiffness: 0.0,
+            damping: 1.0,
+            max_force: f32::MAX,
+        }
+    }
+}
+
 /// Ball joint locks any translational moves between two objects on the axis between objects, but
 /// allows rigid bodies to perform relative rotations. The real world example is a human shoulder,
 /// pendulum, etc.
@@ -66,6 +121,21 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angle range around local Z axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub z_limits_angles: Range<f32>,
+
+    /// Motor that drives rotation around local X axis of the joint.
+    #[reflect(description = "Motor that drives rotation around local X axis of the joint.")]
+    #[visit(optional)]
+    pub x_motor: JointMotor,
+
+    /// Motor that drives rotation around local Y axis of the joint.
+    #[reflect(description = "Motor that drives rotation around local Y axis of the joint.")]
+    #[visit(optional)]
+    pub y_motor: JointMotor,
+
+    /// Motor that drives rotation around local Z axis of the joint.
+    #[reflect(description = "Motor that drives rotation around local Z axis of the joint.")]
+    #[visit(optional)]
+    pub z_motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -77,6 +147,9 @@ impl Default for BallJoint {
             y_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
             z_limits_enabled: false,
             z_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            x_motor: Default::default(),
+            y_motor: Default::default(),
+            z_motor: Default::default(),
         }
     }
 }
@@ -101,6 +174,11 @@ pub struct PrismaticJoint {
     )]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that drives translation along local X axis of the joint.
+    #[reflect(description = "Motor that drives translation along local X axis of the joint.")]
+    #[visit(optional)]
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -108,6 +186,7 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -128,6 +207,11 @@ pub struct RevoluteJoint {
     #[reflect(description = "Allowed angle range around local X axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that drives rotation around local X axis of the joint.
+    #[reflect(description = "Motor that drives rotation around local X axis of the joint.")]
+    #[visit(optional)]
+    pub motor: JointMotor,
 }
 
 impl Default for RevoluteJoint {
@@ -135,6 +219,7 @@ impl Default for RevoluteJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
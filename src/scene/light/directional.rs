@@ -58,6 +58,21 @@ pub enum FrustumSplitOptions {
         /// sub-frustum will be relative to camera's frustum.
         fractions: [f32; CSM_NUM_CASCADES],
     },
+    /// Camera frustum will be split using "practical split scheme" - a blend between logarithmic and
+    /// uniform distribution of split distances. Logarithmic distribution gives the same amount of
+    /// shadow map texels per screen pixel at any distance, while uniform distribution gives more
+    /// texels to distant cascades.
+    ///
+    /// This option gives good results out of the box and adapts to the amount of cascades in use.
+    Logarithmic {
+        /// Blend factor in `[0; 1]` range between uniform (0.0) and logarithmic (1.0) distributions.
+        #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+        lambda: f32,
+        /// Maximum distance at which shadows will be rendered. It will be clamped to the far plane
+        /// of the camera.
+        #[reflect(min_value = 0.0, step = 1.0)]
+        far_plane: f32,
+    },
 }
 
 uuid_provider!(FrustumSplitOptions = "b2ed128a-b7da-4d34-b027-a0af19c2f563");
@@ -70,6 +85,43 @@ impl Default for FrustumSplitOptions {
     }
 }
 
+impl FrustumSplitOptions {
+    /// Calculates distances (along camera's view axis) of the planes that separate cascades for a
+    /// camera with the given near and far planes. The first value is always `z_near`, value at
+    /// `i + 1` index is the far plane of `i`-th cascade. Only the first `cascade_count + 1` values
+    /// are meaningful, the rest are equal to the far plane of the last used cascade.
+    pub fn split_distances(
+        &self,
+        z_near: f32,
+        z_far: f32,
+        cascade_count: usize,
+    ) -> [f32; CSM_NUM_CASCADES + 1] {
+        let cascade_count = cascade_count.clamp(1, CSM_NUM_CASCADES);
+
+        let mut distances = [z_near; CSM_NUM_CASCADES + 1];
+        for i in 0..CSM_NUM_CASCADES {
+            distances[i + 1] = if i < cascade_count {
+                match self {
+                    FrustumSplitOptions::Absolute { far_planes } => far_planes[i],
+                    FrustumSplitOptions::Relative { fractions } => z_far * fractions[i],
+                    FrustumSplitOptions::Logarithmic { lambda, far_plane } => {
+                        let near = z_near.max(f32::EPSILON);
+                        let far = far_plane.min(z_far).max(near);
+                        let t = (i + 1) as f32 / cascade_count as f32;
+                        let logarithmic = near * (far / near).powf(t);
+                        let uniform = near + (far - near) * t;
+                        let lambda = lambda.clamp(0.0, 1.0);
+                        lambda * logarithmic + (1.0 - lambda) * uniform
+                    }
+                }
+            } else {
+                distances[i]
+            };
+        }
+        distances
+    }
+}
+
 /// Cascade Shadow Mapping (CSM) options.
 #[derive(Reflect, Clone, Visit, PartialEq, Debug)]
 pub struct CsmOptions {
@@ -78,6 +130,29 @@ pub struct CsmOptions {
 
     #[reflect(min_value = 0.0, step = 0.000025)]
     shadow_bias: f32,
+
+    #[reflect(
+        min_value = 1.0,
+        max_value = 3.0,
+        step = 1.0,
+        description = "Amount of cascades used to cover the camera frustum."
+    )]
+    #[visit(optional)]
+    cascade_count: usize,
+
+    #[reflect(
+        description = "Snaps cascades to shadow map texels to eliminate shimmering of \
+        shadow edges when the camera moves."
+    )]
+    #[visit(optional)]
+    texel_snapping: bool,
+
+    #[reflect(
+        description = "Additional bias for each cascade, added on top of the shadow bias. \
+        Distant cascades cover more space per texel and usually need larger bias."
+    )]
+    #[visit(optional)]
+    cascade_biases: [f32; CSM_NUM_CASCADES],
 }
 
 impl Default for CsmOptions {
@@ -85,6 +160,9 @@ impl Default for CsmOptions {
         Self {
             split_options: Default::default(),
             shadow_bias: 0.00025,
+            cascade_count: CSM_NUM_CASCADES,
+            texel_snapping: true,
+            cascade_biases: [0.0; CSM_NUM_CASCADES],
         }
     }
 }
@@ -101,6 +179,53 @@ impl CsmOptions {
     pub fn shadow_bias(&self) -> f32 {
         self.shadow_bias
     }
+
+    /// Sets amount of cascades, that will be used to cover camera frustum. The value will be
+    /// clamped to `[1; CSM_NUM_CASCADES]` range. Lesser amount of cascades improves performance,
+    /// but reduces the quality of shadows.
+    pub fn set_cascade_count(&mut self, count: usize) {
+        self.cascade_count = count.clamp(1, CSM_NUM_CASCADES);
+    }
+
+    /// Returns amount of cascades in use.
+    pub fn cascade_count(&self) -> usize {
+        self.cascade_count.clamp(1, CSM_NUM_CASCADES)
+    }
+
+    /// Enables or disables texel snapping. When enabled, each cascade is moved in whole shadow map
+    /// texels and keeps its size regardless of camera orientation, which eliminates shimmering
+    /// of shadow edges when camera moves or rotates. The price is slightly lower effective
+    /// resolution of shadow maps.
+    pub fn set_texel_snapping(&mut self, enabled: bool) {
+        self.texel_snapping = enabled;
+    }
+
+    /// Returns `true` if texel snapping is enabled, `false` - otherwise.
+    pub fn is_texel_snapping_enabled(&self) -> bool {
+        self.texel_snapping
+    }
+
+    /// Sets additional shadow bias for a cascade with the given index. The value is added to the
+    /// [`Self::shadow_bias`]. Out-of-bounds indices are ignored.
+    pub fn set_cascade_bias(&mut self, cascade: usize, bias: f32) {
+        if let Some(cascade_bias) = self.cascade_biases.get_mut(cascade) {
+            *cascade_bias = bias.max(0.0);
+        }
+    }
+
+    /// Returns additional shadow bias of a cascade with the given index.
+    pub fn cascade_bias(&self, cascade: usize) -> f32 {
+        self.cascade_biases
+            .get(cascade)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns final shadow bias of a cascade with the given index, it is a sum of the shadow bias
+    /// and the additional bias of the cascade.
+    pub fn effective_cascade_bias(&self, cascade: usize) -> f32 {
+        self.shadow_bias + self.cascade_bias(cascade)
+    }
 }
 
 /// See module docs.
@@ -220,3 +345,69 @@ impl DirectionalLightBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::scene::light::directional::{CsmOptions, FrustumSplitOptions, CSM_NUM_CASCADES};
+
+    #[test]
+    fn test_split_distances() {
+        let absolute = FrustumSplitOptions::Absolute {
+            far_planes: [5.0, 25.0, 64.0],
+        };
+        assert_eq!(
+            absolute.split_distances(0.1, 100.0, CSM_NUM_CASCADES),
+            [0.1, 5.0, 25.0, 64.0]
+        );
+        // Unused cascades collapse into the far plane of the last used one.
+        assert_eq!(
+            absolute.split_distances(0.1, 100.0, 2),
+            [0.1, 5.0, 25.0, 25.0]
+        );
+
+        let relative = FrustumSplitOptions::Relative {
+            fractions: [0.25, 0.5, 1.0],
+        };
+        assert_eq!(
+            relative.split_distances(0.1, 100.0, CSM_NUM_CASCADES),
+            [0.1, 25.0, 50.0, 100.0]
+        );
+
+        let uniform = FrustumSplitOptions::Logarithmic {
+            lambda: 0.0,
+            far_plane: 1000.0,
+        };
+        let distances = uniform.split_distances(1.0, 100.0, 3);
+        assert!((distances[1] - 34.0).abs() < 1e-3);
+        assert!((distances[2] - 67.0).abs() < 1e-3);
+        assert!((distances[3] - 100.0).abs() < 1e-3);
+
+        let logarithmic = FrustumSplitOptions::Logarithmic {
+            lambda: 1.0,
+            far_plane: 100.0,
+        };
+        let distances = logarithmic.split_distances(1.0, 1000.0, 2);
+        assert!((distances[1] - 10.0).abs() < 1e-3);
+        assert!((distances[2] - 100.0).abs() < 1e-3);
+        assert_eq!(distances[2], distances[3]);
+    }
+
+    #[test]
+    fn test_csm_options() {
+        let mut options = CsmOptions::default();
+        assert_eq!(options.cascade_count(), CSM_NUM_CASCADES);
+        assert!(options.is_texel_snapping_enabled());
+
+        options.set_cascade_count(0);
+        assert_eq!(options.cascade_count(), 1);
+        options.set_cascade_count(10);
+        assert_eq!(options.cascade_count(), CSM_NUM_CASCADES);
+
+        options.set_shadow_bias(0.001);
+        options.set_cascade_bias(2, 0.002);
+        options.set_cascade_bias(10, 1.0);
+        assert_eq!(options.effective_cascade_bias(0), 0.001);
+        assert_eq!(options.effective_cascade_bias(2), 0.003);
+        assert_eq!(options.cascade_bias(10), 0.0);
+    }
+}
@@ -523,6 +523,36 @@ impl<'a> VertexBufferRefMut<'a> {
         self.vertex_buffer.vertex_count -= 1;
     }
 
+    /// Rebuilds the buffer so it contains only the vertices with the given indices, in the given
+    /// order. Indices that are out of bounds are ignored.
+    pub fn retain_vertices(&mut self, indices: &[u32]) {
+        let vertex_size = self.vertex_buffer.vertex_size as usize;
+        let mut bytes = Vec::with_capacity(indices.len() * vertex_size);
+        let mut count = 0;
+        for &index in indices {
+            let offset = index as usize * vertex_size;
+            if let Some(vertex) = self.vertex_buffer.data.get(offset..(offset + vertex_size)) {
+                bytes.extend_from_slice(vertex);
+                count += 1;
+            }
+        }
+        self.vertex_buffer.data.clear();
+        self.vertex_buffer.data.extend_from_slice(&bytes);
+        self.vertex_buffer.vertex_count = count;
+    }
+
+    /// Appends all the vertices of the given buffer to the buffer. Both buffers must have the same
+    /// layout.
+    pub fn append(&mut self, other: &VertexBuffer) -> Result<(), ValidationError> {
+        if self.vertex_buffer.layout_hash() == other.layout_hash() {
+            self.vertex_buffer.data.extend_from_slice(&other.data);
+            self.vertex_buffer.vertex_count += other.vertex_count;
+            Ok(())
+        } else {
+            Err(ValidationError::LayoutMismatch)
+        }
+    }
+
     /// Copies data of last vertex from the buffer to an instance of variable of a type.
     ///
     /// # Safety and validation
@@ -713,6 +743,9 @@ pub enum ValidationError {
 
     /// Duplicate shader locations were found.
     ConflictingShaderLocations(usize),
+
+    /// Layouts of vertex buffers do not match.
+    LayoutMismatch,
 }
 
 impl Display for ValidationError {
@@ -733,6 +766,9 @@ impl Display for ValidationError {
             ValidationError::ConflictingShaderLocations(v) => {
                 write!(f, "Duplicate shader locations were found {v}.")
             }
+            ValidationError::LayoutMismatch => {
+                write!(f, "Layouts of vertex buffers do not match.")
+            }
         }
     }
 }
@@ -0,0 +1,140 @@
+//! Levels of detail of a mesh. See [`MeshLod`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        reflect::prelude::*,
+        uuid_provider,
+        visitor::prelude::*,
+    },
+    scene::mesh::surface::Surface,
+};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines how thresholds of mesh levels of detail are interpreted.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum MeshLodMode {
+    /// Thresholds are distances (in meters) from an observer to the center of the bounds of a mesh.
+    /// A level of detail is used when the distance is greater or equal to its threshold. This is
+    /// **default** mode.
+    #[default]
+    Distance,
+    /// Thresholds are fractions of screen height occupied by the bounds of a mesh. A level of
+    /// detail is used when the screen size is less or equal to its threshold. This mode is
+    /// independent of field of view of a camera.
+    ScreenSize,
+}
+
+uuid_provider!(MeshLodMode = "8b7e2c5d-43b7-4d79-8f2a-5b4c1f0b7e9a");
+
+impl MeshLodMode {
+    /// Calculates a metric that is compared with thresholds of levels of detail, for a mesh with
+    /// the given world-space bounds.
+    pub fn metric(
+        self,
+        bounds: &AxisAlignedBoundingBox,
+        observer_position: &Vector3<f32>,
+        projection_matrix: &Matrix4<f32>,
+    ) -> f32 {
+        let distance = observer_position.metric_distance(&bounds.center());
+        match self {
+            MeshLodMode::Distance => distance,
+            MeshLodMode::ScreenSize => {
+                let radius = bounds.half_extents().norm();
+                let scale = projection_matrix[(1, 1)].abs();
+                // Orthographic projection does not depend on distance.
+                if projection_matrix[(3, 3)] == 1.0 {
+                    radius * scale
+                } else {
+                    radius * scale / distance.max(f32::EPSILON)
+                }
+            }
+        }
+    }
+}
+
+/// A level of detail of a mesh. It contains a set of surfaces, that will be rendered instead of
+/// the surfaces of the mesh, when the threshold is satisfied. See [`MeshLodMode`] docs for more
+/// info about thresholds.
+///
+/// Levels of detail are usually generated automatically on model import, see
+/// [`crate::resource::model::ModelImportOptions`] docs for more info.
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct MeshLod {
+    /// Threshold of the level of detail. Its meaning depends on [`MeshLodMode`].
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub threshold: f32,
+    /// Surfaces of the level of detail.
+    pub surfaces: Vec<Surface>,
+}
+
+uuid_provider!(MeshLod = "0c3dbd7e-bb8f-4f3d-8a3a-0b2b8d1c6a55");
+
+/// Selects a level of detail using the given metric (see [`MeshLodMode::metric`]). Returns `0` if
+/// the main surfaces of a mesh should be used, or `n + 1` for n-th level of detail. Levels of detail
+/// must be sorted in the order of decreasing detail, the last suitable level is selected.
+pub fn select_lod(mode: MeshLodMode, lods: &[MeshLod], metric: f32) -> usize {
+    let mut selected = 0;
+    for (index, lod) in lods.iter().enumerate() {
+        let suitable = match mode {
+            MeshLodMode::Distance => metric >= lod.threshold,
+            MeshLodMode::ScreenSize => metric <= lod.threshold,
+        };
+        if suitable {
+            selected = index + 1;
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scene::mesh::lod::{select_lod, MeshLod, MeshLodMode};
+
+    #[test]
+    fn test_select_lod() {
+        let lods = [
+            MeshLod {
+                threshold: 10.0,
+                surfaces: vec![],
+            },
+            MeshLod {
+                threshold: 20.0,
+                surfaces: vec![],
+            },
+        ];
+        assert_eq!(select_lod(MeshLodMode::Distance, &lods, 5.0), 0);
+        assert_eq!(select_lod(MeshLodMode::Distance, &lods, 15.0), 1);
+        assert_eq!(select_lod(MeshLodMode::Distance, &lods, 25.0), 2);
+
+        let lods = [
+            MeshLod {
+                threshold: 0.5,
+                surfaces: vec![],
+            },
+            MeshLod {
+                threshold: 0.1,
+                surfaces: vec![],
+            },
+        ];
+        assert_eq!(select_lod(MeshLodMode::ScreenSize, &lods, 0.8), 0);
+        assert_eq!(select_lod(MeshLodMode::ScreenSize, &lods, 0.3), 1);
+        assert_eq!(select_lod(MeshLodMode::ScreenSize, &lods, 0.05), 2);
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     core::{
-        algebra::{Matrix4, Point3, Vector3},
+        algebra::{Matrix4, Point3, Vector3, Vector4},
         color::Color,
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
@@ -13,18 +13,25 @@ use crate::{
         visitor::{Visit, VisitResult, Visitor},
         TypeUuidProvider,
     },
+    generic_animation::{
+        container::{TrackDataContainer, TrackValueKind},
+        value::{ValueBinding, ValueType},
+    },
     renderer::{
         self,
         batch::{PersistentIdentifier, RenderContext, SurfaceInstanceData},
         framework::geometry_buffer::ElementRange,
     },
     scene::{
+        animation::Track,
         base::{Base, BaseBuilder},
         debug::{Line, SceneDrawingContext},
         graph::Graph,
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
+            lod::{MeshLod, MeshLodMode},
             surface::{BlendShape, Surface},
+            vertex::StaticVertex,
         },
         node::{Node, NodeTrait, UpdateContext},
     },
@@ -38,6 +45,7 @@ use std::{
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 pub mod buffer;
+pub mod lod;
 pub mod surface;
 pub mod vertex;
 
@@ -124,6 +132,13 @@ impl RenderPath {
 ///
 /// This example creates a unit cube surface with default material and then creates a mesh with this surface. If you need to create
 /// custom surface, see [`crate::scene::mesh::surface::SurfaceData`] docs for more info.
+///
+/// ## Levels of detail
+///
+/// A mesh may have a set of levels of detail (LODs), each level of detail has its own set of surfaces that will be rendered
+/// instead of the main surfaces of the mesh when a threshold of the level is satisfied. Thresholds could be either distances
+/// to an observer or fractions of the screen occupied by the mesh, see [`MeshLodMode`] docs for more info. Levels of detail
+/// could be generated automatically on model import, see [`crate::resource::model::ModelImportOptions`] docs for more info.
 #[derive(Debug, Reflect, Clone, Visit)]
 pub struct Mesh {
     #[visit(rename = "Common")]
@@ -141,6 +156,47 @@ pub struct Mesh {
     #[visit(optional)]
     blend_shapes: InheritableVariable<Vec<BlendShape>>,
 
+    #[visit(optional)]
+    #[reflect(setter = "set_lod_mode")]
+    lod_mode: InheritableVariable<MeshLodMode>,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_lods")]
+    lods: InheritableVariable<Vec<MeshLod>>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_color",
+        description = "A color of the mesh, that is multiplied with the diffuse color of every surface \
+        (supported by the standard shader). Unlike material properties, it does not prevent meshes from \
+        being rendered using instancing."
+    )]
+    color: InheritableVariable<Color>,
+
+    #[visit(skip)]
+    #[reflect(
+        setter = "set_forced_lod",
+        description = "Forces the mesh to use the specified level of detail (0 - main surfaces, \
+        1 - first level of detail and so on). Intended to be used for previewing, it is not saved."
+    )]
+    forced_lod: Option<usize>,
+
+    #[visit(optional)]
+    #[reflect(
+        read_only,
+        description = "Whether the mesh is merged into a static batch. Statically batched meshes are not \
+        rendered, static batches are rendered instead."
+    )]
+    statically_batched: bool,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_cpu_skinning",
+        description = "Whether skinning of the mesh should be performed on CPU instead of GPU. CPU \
+        skinning is much slower, use it only when exact vertex positions must match on CPU and GPU."
+    )]
+    cpu_skinning: InheritableVariable<bool>,
+
     #[reflect(hidden)]
     #[visit(skip)]
     local_bounding_box: Cell<AxisAlignedBoundingBox>,
@@ -165,6 +221,12 @@ impl Default for Mesh {
             render_path: InheritableVariable::new_modified(RenderPath::Deferred),
             decal_layer_index: InheritableVariable::new_modified(0),
             blend_shapes: Default::default(),
+            lod_mode: Default::default(),
+            lods: Default::default(),
+            color: InheritableVariable::new_modified(Color::WHITE),
+            forced_lod: None,
+            statically_batched: false,
+            cpu_skinning: InheritableVariable::new_modified(false),
         }
     }
 }
@@ -234,6 +296,18 @@ impl Mesh {
         self.blend_shapes.get_value_mut_and_mark_modified()
     }
 
+    /// Creates a track, that animates weight of a blend shape with the given index. Weights are
+    /// stored in `[0; 100]` range, so the values of the track should be in the same range.
+    pub fn make_blend_shape_weight_track(blend_shape_index: usize) -> Track {
+        Track::new(
+            TrackDataContainer::new(TrackValueKind::Real),
+            ValueBinding::Property {
+                name: format!("blend_shapes[{blend_shape_index}].weight"),
+                value_type: ValueType::F32,
+            },
+        )
+    }
+
     /// Sets new render path for the mesh.
     pub fn set_render_path(&mut self, render_path: RenderPath) -> RenderPath {
         self.render_path.set_value_and_mark_modified(render_path)
@@ -313,6 +387,204 @@ impl Mesh {
     pub fn decal_layer_index(&self) -> u8 {
         *self.decal_layer_index
     }
+
+    /// Sets new color of the mesh. The color is multiplied with the diffuse color of every surface
+    /// (if the shader of the surface supports it, the standard shader does). Unlike material
+    /// properties, the color is passed as per-instance data, so meshes with different colors could
+    /// still be rendered using a single instanced draw call.
+    pub fn set_color(&mut self, color: Color) -> Color {
+        self.color.set_value_and_mark_modified(color)
+    }
+
+    /// Returns current color of the mesh.
+    pub fn color(&self) -> Color {
+        *self.color
+    }
+
+    /// Returns `true` if the mesh is merged into a static batch. Such meshes are not rendered, see
+    /// [`crate::utils::static_batching`] docs for more info.
+    pub fn is_statically_batched(&self) -> bool {
+        self.statically_batched
+    }
+
+    pub(crate) fn set_statically_batched(&mut self, batched: bool) {
+        self.statically_batched = batched;
+    }
+
+    /// Sets new mode of levels of detail. See [`MeshLodMode`] docs for more info.
+    pub fn set_lod_mode(&mut self, mode: MeshLodMode) -> MeshLodMode {
+        self.lod_mode.set_value_and_mark_modified(mode)
+    }
+
+    /// Returns current mode of levels of detail.
+    pub fn lod_mode(&self) -> MeshLodMode {
+        *self.lod_mode
+    }
+
+    /// Sets new levels of detail of the mesh. Levels must be sorted in the order of decreasing detail.
+    pub fn set_lods(&mut self, lods: Vec<MeshLod>) -> Vec<MeshLod> {
+        self.lods.set_value_and_mark_modified(lods)
+    }
+
+    /// Returns a list of levels of detail of the mesh.
+    pub fn lods(&self) -> &[MeshLod] {
+        &self.lods
+    }
+
+    /// Returns a list of levels of detail of the mesh.
+    pub fn lods_mut(&mut self) -> &mut Vec<MeshLod> {
+        self.lods.get_value_mut_and_mark_modified()
+    }
+
+    /// Forces the mesh to use the specified level of detail (`0` - main surfaces, `n + 1` - n-th level
+    /// of detail), `None` - restores automatic selection. It is useful to preview levels of detail.
+    pub fn set_forced_lod(&mut self, lod: Option<usize>) -> Option<usize> {
+        std::mem::replace(&mut self.forced_lod, lod)
+    }
+
+    /// Returns forced level of detail, if any.
+    pub fn forced_lod(&self) -> Option<usize> {
+        self.forced_lod
+    }
+
+    /// Returns an index of the level of detail that should be used for the given observer: `0` -
+    /// main surfaces, `n + 1` - n-th level of detail.
+    pub fn lod_index(
+        &self,
+        observer_position: &Vector3<f32>,
+        projection_matrix: &Matrix4<f32>,
+    ) -> usize {
+        if let Some(forced_lod) = self.forced_lod {
+            return forced_lod.min(self.lods.len());
+        }
+
+        if self.lods.is_empty() {
+            return 0;
+        }
+
+        let metric = self.lod_mode.metric(
+            &self.world_bounding_box(),
+            observer_position,
+            projection_matrix,
+        );
+        lod::select_lod(*self.lod_mode, &self.lods, metric)
+    }
+
+    /// Enables or disables skinning on CPU. By default, skinned meshes are animated in the vertex
+    /// shader using bone matrices, which is very fast, but vertices transformed on GPU are not
+    /// accessible from the game code. When CPU skinning is enabled, vertices are transformed on CPU
+    /// (see [`Self::skinned_vertices`]) every frame and then sent to GPU as is. It is much slower
+    /// and should be used only when exact CPU-side vertex positions are required.
+    pub fn set_cpu_skinning(&mut self, enabled: bool) -> bool {
+        self.cpu_skinning.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if skinning is performed on CPU, `false` - otherwise.
+    pub fn cpu_skinning(&self) -> bool {
+        *self.cpu_skinning
+    }
+
+    /// Calculates world-space vertices of a surface with the given index, taking skinning into
+    /// account. It produces exactly the same positions as the skinning in the vertex shader, but
+    /// blend shapes are ignored. Returns `None` if there is no surface with the given index.
+    pub fn skinned_vertices(
+        &self,
+        surface_index: usize,
+        graph: &Graph,
+    ) -> Option<Vec<StaticVertex>> {
+        self.surfaces
+            .get(surface_index)
+            .map(|surface| self.skin_surface(surface, graph))
+    }
+
+    fn bone_matrices(&self, surface: &Surface, graph: &Graph) -> Vec<Matrix4<f32>> {
+        surface
+            .bones
+            .iter()
+            .map(|bone_handle| {
+                if let Some(bone_node) = graph.try_get(*bone_handle) {
+                    bone_node.global_transform() * bone_node.inv_bind_pose_transform()
+                } else {
+                    Matrix4::identity()
+                }
+            })
+            .collect()
+    }
+
+    fn skin_surface(&self, surface: &Surface, graph: &Graph) -> Vec<StaticVertex> {
+        let bone_matrices = self.bone_matrices(surface, graph);
+        let global_transform = self.global_transform();
+
+        let data = surface.data_ref().lock();
+        data.vertex_buffer
+            .iter()
+            .map(|vertex| {
+                let position = vertex
+                    .read_3_f32(VertexAttributeUsage::Position)
+                    .unwrap_or_default();
+                let normal = vertex
+                    .read_3_f32(VertexAttributeUsage::Normal)
+                    .unwrap_or_default();
+                let tangent = vertex
+                    .read_4_f32(VertexAttributeUsage::Tangent)
+                    .unwrap_or_default();
+
+                let transform = if bone_matrices.is_empty() {
+                    global_transform
+                } else {
+                    let weights = vertex
+                        .read_4_f32(VertexAttributeUsage::BoneWeight)
+                        .unwrap_or_default();
+                    let indices = vertex
+                        .read_4_u8(VertexAttributeUsage::BoneIndices)
+                        .unwrap_or_default();
+                    let mut transform = Matrix4::zeros();
+                    for (weight, index) in weights.iter().zip(indices.iter()) {
+                        if let Some(bone_matrix) = bone_matrices.get(*index as usize) {
+                            transform += bone_matrix.scale(*weight);
+                        }
+                    }
+                    transform
+                };
+
+                // Normals must stay perpendicular to the surface under non-uniform scaling, so
+                // directions are transformed using inverse-transpose of the transform.
+                let basis = transform.fixed_view::<3, 3>(0, 0).into_owned();
+                let normal_matrix = basis
+                    .try_inverse()
+                    .map(|inv| inv.transpose())
+                    .unwrap_or(basis);
+
+                let tangent_xyz = (normal_matrix * tangent.xyz())
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default();
+
+                StaticVertex {
+                    position: transform.transform_point(&Point3::from(position)).coords,
+                    tex_coord: vertex
+                        .read_2_f32(VertexAttributeUsage::TexCoord0)
+                        .unwrap_or_default(),
+                    normal: (normal_matrix * normal)
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_default(),
+                    tangent: Vector4::new(tangent_xyz.x, tangent_xyz.y, tangent_xyz.z, tangent.w),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a set of surfaces that should be rendered for the given observer, taking levels of
+    /// detail into account.
+    pub fn surfaces_for_observer(
+        &self,
+        observer_position: &Vector3<f32>,
+        projection_matrix: &Matrix4<f32>,
+    ) -> &[Surface] {
+        match self.lod_index(observer_position, projection_matrix) {
+            0 => self.surfaces(),
+            index => &self.lods[index - 1].surfaces,
+        }
+    }
 }
 
 impl NodeTrait for Mesh {
@@ -372,7 +644,8 @@ impl NodeTrait for Mesh {
     }
 
     fn collect_render_data(&self, ctx: &mut RenderContext) {
-        if !self.global_visibility()
+        if self.statically_batched
+            || !self.global_visibility()
             || !self.is_globally_enabled()
             || !ctx.frustum.is_intersects_aabb(&self.world_bounding_box())
         {
@@ -383,9 +656,32 @@ impl NodeTrait for Mesh {
             return;
         }
 
-        for (index, surface) in self.surfaces().iter().enumerate() {
+        let surfaces = self.surfaces_for_observer(ctx.observer_position, ctx.projection_matrix);
+        for (index, surface) in surfaces.iter().enumerate() {
             let is_skinned = !surface.bones.is_empty();
 
+            if is_skinned && *self.cpu_skinning {
+                let vertices = self.skin_surface(surface, ctx.graph);
+                let triangles = surface
+                    .data_ref()
+                    .lock()
+                    .geometry_buffer
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                ctx.storage.push_triangles(
+                    vertices.into_iter(),
+                    triangles.into_iter(),
+                    surface.material(),
+                    self.render_path(),
+                    self.decal_layer_index(),
+                    surface.material().key() as u64,
+                    false,
+                    self.self_handle,
+                );
+                continue;
+            }
+
             let world = if is_skinned {
                 Matrix4::identity()
             } else {
@@ -400,17 +696,7 @@ impl NodeTrait for Mesh {
                 surface.material().key() as u64,
                 SurfaceInstanceData {
                     world_transform: world,
-                    bone_matrices: surface
-                        .bones
-                        .iter()
-                        .map(|bone_handle| {
-                            if let Some(bone_node) = ctx.graph.try_get(*bone_handle) {
-                                bone_node.global_transform() * bone_node.inv_bind_pose_transform()
-                            } else {
-                                Matrix4::identity()
-                            }
-                        })
-                        .collect::<Vec<_>>(),
+                    bone_matrices: self.bone_matrices(surface, ctx.graph),
                     depth_offset: self.depth_offset_factor(),
                     blend_shapes_weights: self
                         .blend_shapes()
@@ -424,6 +710,7 @@ impl NodeTrait for Mesh {
                         index,
                     ),
                     node_handle: self.self_handle,
+                    color: self.color(),
                 },
             );
         }
@@ -490,6 +777,10 @@ pub struct MeshBuilder {
     render_path: RenderPath,
     decal_layer_index: u8,
     blend_shapes: Vec<BlendShape>,
+    lod_mode: MeshLodMode,
+    lods: Vec<MeshLod>,
+    color: Color,
+    cpu_skinning: bool,
 }
 
 impl MeshBuilder {
@@ -501,6 +792,10 @@ impl MeshBuilder {
             render_path: RenderPath::Deferred,
             decal_layer_index: 0,
             blend_shapes: Default::default(),
+            lod_mode: Default::default(),
+            lods: Default::default(),
+            color: Color::WHITE,
+            cpu_skinning: false,
         }
     }
 
@@ -530,6 +825,30 @@ impl MeshBuilder {
         self
     }
 
+    /// Sets desired mode of levels of detail. See [`MeshLodMode`] docs for more info.
+    pub fn with_lod_mode(mut self, lod_mode: MeshLodMode) -> Self {
+        self.lod_mode = lod_mode;
+        self
+    }
+
+    /// Sets desired levels of detail. Levels must be sorted in the order of decreasing detail.
+    pub fn with_lods(mut self, lods: Vec<MeshLod>) -> Self {
+        self.lods = lods;
+        self
+    }
+
+    /// Sets desired color of the mesh. See [`Mesh::set_color`] docs for more info.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Enables or disables skinning on CPU. See [`Mesh::set_cpu_skinning`] docs for more info.
+    pub fn with_cpu_skinning(mut self, enabled: bool) -> Self {
+        self.cpu_skinning = enabled;
+        self
+    }
+
     /// Creates new mesh.
     pub fn build_node(self) -> Node {
         Node::new(Mesh {
@@ -541,6 +860,12 @@ impl MeshBuilder {
             render_path: self.render_path.into(),
             decal_layer_index: self.decal_layer_index.into(),
             world_bounding_box: Default::default(),
+            lod_mode: self.lod_mode.into(),
+            lods: self.lods.into(),
+            color: self.color.into(),
+            forced_lod: None,
+            statically_batched: false,
+            cpu_skinning: self.cpu_skinning.into(),
         })
     }
 
@@ -549,3 +874,73 @@ impl MeshBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3, Vector4},
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            mesh::{
+                buffer::{TriangleBuffer, VertexBuffer},
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                vertex::AnimatedVertex,
+                Mesh, MeshBuilder,
+            },
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_skinned_vertices() {
+        let mut graph = Graph::new();
+
+        let bone = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_scale(Vector3::new(2.0, 1.0, 1.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+
+        let vertex = AnimatedVertex {
+            position: Vector3::new(1.0, 0.0, 0.0),
+            tex_coord: Vector2::default(),
+            normal: Vector3::new(1.0, 1.0, 0.0).normalize(),
+            tangent: Vector4::new(1.0, -1.0, 0.0, 1.0),
+            bone_weights: [1.0, 0.0, 0.0, 0.0],
+            bone_indices: [0; 4],
+        };
+        let data = SurfaceData::new(
+            VertexBuffer::new(1, vec![vertex]).unwrap(),
+            TriangleBuffer::default(),
+            true,
+        );
+
+        let mesh = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(data))
+                .with_bones(vec![bone])
+                .build()])
+            .build(&mut graph);
+
+        graph.update_hierarchical_data();
+
+        let vertices = graph[mesh]
+            .cast::<Mesh>()
+            .unwrap()
+            .skinned_vertices(0, &graph)
+            .unwrap();
+        assert_eq!(vertices.len(), 1);
+
+        let skinned = &vertices[0];
+        assert_eq!(skinned.position, Vector3::new(2.0, 0.0, 0.0));
+        // Stretching along X tilts the normal towards Y, the normal must stay perpendicular to the
+        // stretched surface.
+        let expected_normal = Vector3::new(0.5, 1.0, 0.0).normalize();
+        assert!((skinned.normal - expected_normal).norm() < 1.0e-5);
+        assert_eq!(skinned.tangent.w, 1.0);
+    }
+}
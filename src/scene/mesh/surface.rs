@@ -1012,6 +1012,11 @@ impl SurfaceData {
     pub fn set_embedded(&mut self, is_embedded: bool) {
         self.is_embedded = is_embedded;
     }
+
+    /// Returns `true` if the content of the surface is procedural (created from code), `false` - otherwise.
+    pub fn is_embedded(&self) -> bool {
+        self.is_embedded
+    }
 }
 
 impl Visit for SurfaceData {
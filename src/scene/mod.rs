@@ -8,10 +8,13 @@ pub mod accel;
 pub mod animation;
 pub mod base;
 pub mod camera;
+pub mod character;
+pub mod cloth;
 pub mod collider;
 pub mod debug;
 pub mod decal;
 pub mod dim2;
+pub mod foliage;
 pub mod graph;
 pub mod joint;
 pub mod light;
@@ -21,11 +24,17 @@ pub mod node;
 pub mod particle_system;
 pub mod pivot;
 pub mod ragdoll;
+pub mod reflection_probe;
 pub mod rigidbody;
+pub mod sky;
 pub mod sound;
+pub mod spline;
 pub mod sprite;
+pub mod streaming;
 pub mod terrain;
+pub mod trail;
 pub mod transform;
+pub mod vehicle;
 
 use crate::{
     asset::{self, manager::ResourceManager, untyped::UntypedResource},
@@ -50,6 +59,7 @@ use crate::{
         navmesh::NavigationalMeshBuilder,
         node::Node,
         sound::SoundEngine,
+        streaming::SceneStreaming,
     },
     utils::navmesh::Navmesh,
 };
@@ -212,6 +222,11 @@ pub struct Scene {
     /// to false for menu's scene and when you need to open a menu - set it to true and
     /// set `enabled` flag to false for level's scene.
     pub enabled: InheritableVariable<bool>,
+
+    /// A set of chunks, that are loaded and unloaded on demand. See [`SceneStreaming`] docs for
+    /// more info.
+    #[reflect(hidden)]
+    pub streaming: SceneStreaming,
 }
 
 impl Default for Scene {
@@ -222,6 +237,7 @@ impl Default for Scene {
             drawing_context: Default::default(),
             performance_statistics: Default::default(),
             enabled: true.into(),
+            streaming: Default::default(),
         }
     }
 }
@@ -383,6 +399,7 @@ impl Scene {
             drawing_context: Default::default(),
             performance_statistics: Default::default(),
             enabled: true.into(),
+            streaming: Default::default(),
         }
     }
 
@@ -429,6 +446,9 @@ impl Scene {
             self.graph
                 .clone(root, filter, pre_process_callback, post_process_callback);
 
+        let mut streaming = self.streaming.clone();
+        streaming.remap_handles(&old_new_map);
+
         (
             Self {
                 graph,
@@ -436,6 +456,7 @@ impl Scene {
                 drawing_context: self.drawing_context.clone(),
                 performance_statistics: Default::default(),
                 enabled: self.enabled.clone(),
+                streaming,
             },
             old_new_map,
         )
@@ -450,6 +471,7 @@ impl Scene {
         let _ = self
             .rendering_options
             .visit("RenderingOptions", &mut region);
+        let _ = self.streaming.visit("Streaming", &mut region);
 
         // Backward compatibility.
         let mut navmeshes = NavMeshContainer::default();
@@ -0,0 +1,703 @@
+//! Navigation agent is a node that moves itself along paths on a navigational mesh, avoiding other
+//! agents. See [`NavigationAgent`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector2, Vector3},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::{Line, SceneDrawingContext},
+        graph::{Graph, NodePool},
+        navmesh::NavigationalMesh,
+        node::{Node, NodeTrait, UpdateContext},
+    },
+    utils::navmesh::{
+        avoidance::{AvoidanceAgent, AvoidanceNeighbour},
+        NavmeshAgent, PathSegment, PathSegmentKind,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// An event, that is produced by a [`NavigationAgent`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NavigationAgentEvent {
+    /// The agent has reached its target.
+    Arrived,
+    /// There's no path to the target, the target was reset.
+    PathNotFound,
+    /// The agent has started to traverse an off-mesh link (jump, ladder, etc.). It could be used to
+    /// start an appropriate animation.
+    OffMeshLinkStarted {
+        /// User-defined flags of the link. See [`crate::utils::navmesh::OffMeshLink::flags`].
+        flags: u32,
+    },
+    /// The agent has finished traversing an off-mesh link.
+    OffMeshLinkFinished {
+        /// User-defined flags of the link. See [`crate::utils::navmesh::OffMeshLink::flags`].
+        flags: u32,
+    },
+}
+
+/// Navigation agent is a node that moves itself along paths on a [`NavigationalMesh`] and avoids
+/// collisions with other navigation agents using Optimal Reciprocal Collision Avoidance (see
+/// [`crate::utils::navmesh::avoidance`] for more info). It is a high-level replacement of
+/// [`NavmeshAgent`], that does not require any manual updates and could be used to move NPCs in
+/// crowds.
+///
+/// Once a target is set via [`NavigationAgent::set_target`], the agent builds a path to it and
+/// starts moving, respecting its maximum speed and acceleration. The agent moves itself by changing
+/// the position of its local transform, so any child nodes (for example, a character model) will
+/// move with it. When the target is reached, the agent stops and emits
+/// [`NavigationAgentEvent::Arrived`] event, events could be fetched using [`NavigationAgent::events`].
+/// Keep in mind, that agents are updated only when the scene is running.
+///
+/// Off-mesh links (see [`crate::utils::navmesh::OffMeshLink`]) are traversed in a straight line
+/// with the maximum speed of the agent, the avoidance is disabled during the traversal. The agent
+/// emits [`NavigationAgentEvent::OffMeshLinkStarted`] and [`NavigationAgentEvent::OffMeshLinkFinished`]
+/// events, so gameplay code could play jump or climb animations.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         graph::Graph,
+/// #         navmesh::agent::{NavigationAgent, NavigationAgentBuilder, NavigationAgentEvent},
+/// #         node::Node,
+/// #     },
+/// # };
+/// fn create_agent(graph: &mut Graph, navmesh: Handle<Node>) -> Handle<Node> {
+///     let agent = NavigationAgentBuilder::new(BaseBuilder::new())
+///         .with_navmesh(navmesh)
+///         .with_max_speed(2.0)
+///         .build(graph);
+///     graph[agent]
+///         .cast_mut::<NavigationAgent>()
+///         .unwrap()
+///         .set_target(Some(Vector3::new(10.0, 0.0, 5.0)));
+///     agent
+/// }
+///
+/// fn has_arrived(graph: &Graph, agent: Handle<Node>) -> bool {
+///     graph[agent]
+///         .cast::<NavigationAgent>()
+///         .map_or(false, |a| a.events().contains(&NavigationAgentEvent::Arrived))
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct NavigationAgent {
+    base: Base,
+
+    #[reflect(
+        description = "A handle of a navigational mesh node, that will be used for path finding.",
+        setter = "set_navmesh"
+    )]
+    navmesh: InheritableVariable<Handle<Node>>,
+
+    #[reflect(
+        description = "Maximum speed of the agent (in meters per second).",
+        min_value = 0.0,
+        setter = "set_max_speed"
+    )]
+    max_speed: InheritableVariable<f32>,
+
+    #[reflect(
+        description = "Maximum acceleration of the agent (in meters per second squared).",
+        min_value = 0.0,
+        setter = "set_max_acceleration"
+    )]
+    max_acceleration: InheritableVariable<f32>,
+
+    #[reflect(
+        description = "Radius of the agent. It is used to avoid other agents.",
+        min_value = 0.0,
+        setter = "set_radius"
+    )]
+    radius: InheritableVariable<f32>,
+
+    #[reflect(
+        description = "Defines whether the agent should avoid other agents or not.",
+        setter = "set_avoidance_enabled"
+    )]
+    avoidance_enabled: InheritableVariable<bool>,
+
+    #[reflect(
+        description = "Maximum distance (in meters) to other agents, that will be taken into account \
+        by the avoidance.",
+        min_value = 0.0,
+        setter = "set_neighbour_distance"
+    )]
+    neighbour_distance: InheritableVariable<f32>,
+
+    #[reflect(
+        description = "Minimal amount of time (in seconds) for which the velocity of the agent should \
+        be safe with respect to other agents.",
+        min_value = 0.0,
+        setter = "set_time_horizon"
+    )]
+    time_horizon: InheritableVariable<f32>,
+
+    #[reflect(
+        description = "Distance (in meters) to the target at which the agent is considered arrived.",
+        min_value = 0.0,
+        setter = "set_arrival_distance"
+    )]
+    arrival_distance: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    target: Option<Vector3<f32>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    path_dirty: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    path: Vec<Vector3<f32>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    segment_kinds: Vec<PathSegmentKind>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    path_index: usize,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    traversing_link: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    velocity: Vector3<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    events: Vec<NavigationAgentEvent>,
+}
+
+impl Deref for NavigationAgent {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for NavigationAgent {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for NavigationAgent {
+    fn default() -> Self {
+        NavigationAgentBuilder::new(BaseBuilder::new()).build_navigation_agent()
+    }
+}
+
+impl TypeUuidProvider for NavigationAgent {
+    fn type_uuid() -> Uuid {
+        uuid!("9a0e4d2b-7c61-4f3e-b8a5-1e6d3c9f2b47")
+    }
+}
+
+fn to_plane(v: Vector3<f32>) -> Vector2<f32> {
+    Vector2::new(v.x, v.z)
+}
+
+impl NavigationAgent {
+    /// Sets a handle of a [`NavigationalMesh`] node, that will be used for path finding.
+    pub fn set_navmesh(&mut self, navmesh: Handle<Node>) -> Handle<Node> {
+        self.path_dirty = true;
+        self.navmesh.set_value_and_mark_modified(navmesh)
+    }
+
+    /// Returns a handle of a [`NavigationalMesh`] node, that is used for path finding.
+    pub fn navmesh(&self) -> Handle<Node> {
+        *self.navmesh
+    }
+
+    /// Sets maximum speed of the agent (in meters per second).
+    pub fn set_max_speed(&mut self, max_speed: f32) -> f32 {
+        self.max_speed
+            .set_value_and_mark_modified(max_speed.max(0.0))
+    }
+
+    /// Returns maximum speed of the agent (in meters per second).
+    pub fn max_speed(&self) -> f32 {
+        *self.max_speed
+    }
+
+    /// Sets maximum acceleration of the agent (in meters per second squared).
+    pub fn set_max_acceleration(&mut self, max_acceleration: f32) -> f32 {
+        self.max_acceleration
+            .set_value_and_mark_modified(max_acceleration.max(0.0))
+    }
+
+    /// Returns maximum acceleration of the agent (in meters per second squared).
+    pub fn max_acceleration(&self) -> f32 {
+        *self.max_acceleration
+    }
+
+    /// Sets radius of the agent, that is used to avoid other agents.
+    pub fn set_radius(&mut self, radius: f32) -> f32 {
+        self.radius.set_value_and_mark_modified(radius.max(0.0))
+    }
+
+    /// Returns radius of the agent.
+    pub fn radius(&self) -> f32 {
+        *self.radius
+    }
+
+    /// Enables or disables avoidance of other agents.
+    pub fn set_avoidance_enabled(&mut self, enabled: bool) -> bool {
+        self.avoidance_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the agent avoids other agents, `false` - otherwise.
+    pub fn is_avoidance_enabled(&self) -> bool {
+        *self.avoidance_enabled
+    }
+
+    /// Sets maximum distance (in meters) to other agents, that will be taken into account by the
+    /// avoidance.
+    pub fn set_neighbour_distance(&mut self, distance: f32) -> f32 {
+        self.neighbour_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns maximum distance (in meters) to other agents, that will be taken into account by the
+    /// avoidance.
+    pub fn neighbour_distance(&self) -> f32 {
+        *self.neighbour_distance
+    }
+
+    /// Sets minimal amount of time (in seconds) for which the velocity of the agent should be safe
+    /// with respect to other agents. The larger the value, the sooner the agent will respond to
+    /// other agents, but the less freedom it has in choosing its velocity.
+    pub fn set_time_horizon(&mut self, time_horizon: f32) -> f32 {
+        self.time_horizon
+            .set_value_and_mark_modified(time_horizon.max(0.0))
+    }
+
+    /// Returns the avoidance time horizon (in seconds).
+    pub fn time_horizon(&self) -> f32 {
+        *self.time_horizon
+    }
+
+    /// Sets the distance (in meters) to the target at which the agent is considered arrived.
+    pub fn set_arrival_distance(&mut self, distance: f32) -> f32 {
+        self.arrival_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns the distance (in meters) to the target at which the agent is considered arrived.
+    pub fn arrival_distance(&self) -> f32 {
+        *self.arrival_distance
+    }
+
+    /// Sets a new target point (in world coordinates) for the agent. `None` stops the agent.
+    pub fn set_target(&mut self, target: Option<Vector3<f32>>) {
+        self.target = target;
+        self.path_dirty = true;
+    }
+
+    /// Returns current target point (in world coordinates) of the agent.
+    pub fn target(&self) -> Option<Vector3<f32>> {
+        self.target
+    }
+
+    /// Returns current path of the agent.
+    pub fn path(&self) -> &[Vector3<f32>] {
+        &self.path
+    }
+
+    /// Returns the segment of the path, that the agent is currently moving along. It could be used
+    /// to check whether the agent is traversing an off-mesh link or not.
+    pub fn current_segment(&self) -> Option<PathSegment> {
+        let index = self.path_index.checked_sub(1)?;
+        Some(PathSegment {
+            begin: *self.path.get(index)?,
+            end: *self.path.get(index + 1)?,
+            kind: self.segment_kinds.get(index).copied().unwrap_or_default(),
+        })
+    }
+
+    /// Returns current velocity of the agent.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
+
+    /// Returns a list of events, that were produced by the agent during the last update.
+    pub fn events(&self) -> &[NavigationAgentEvent] {
+        &self.events
+    }
+
+    fn stop(&mut self, event: NavigationAgentEvent) {
+        self.target = None;
+        self.path.clear();
+        self.segment_kinds.clear();
+        self.path_index = 0;
+        self.traversing_link = false;
+        self.velocity = Vector3::zeros();
+        self.events.push(event);
+    }
+
+    fn current_link_flags(&self) -> Option<u32> {
+        match self.current_segment()?.kind {
+            PathSegmentKind::Walk => None,
+            PathSegmentKind::OffMeshLink { flags, .. } => Some(flags),
+        }
+    }
+
+    fn has_links_ahead(&self) -> bool {
+        self.segment_kinds
+            .iter()
+            .skip(self.path_index.saturating_sub(1))
+            .any(|kind| *kind != PathSegmentKind::Walk)
+    }
+
+    fn set_world_position(&mut self, nodes: &NodePool, position: Vector3<f32>) {
+        let local_position = nodes
+            .try_borrow(self.parent())
+            .and_then(|parent| parent.global_transform().try_inverse())
+            .map_or(position, |inv_parent_transform| {
+                inv_parent_transform
+                    .transform_point(&Point3::from(position))
+                    .coords
+            });
+        self.local_transform_mut().set_position(local_position);
+    }
+
+    fn traverse_link(&mut self, nodes: &NodePool, position: Vector3<f32>, flags: u32, dt: f32) {
+        if !self.traversing_link {
+            self.traversing_link = true;
+            self.events
+                .push(NavigationAgentEvent::OffMeshLinkStarted { flags });
+        }
+
+        let end = self.path[self.path_index];
+        let to_end = end - position;
+        let distance = to_end.norm();
+        let step = *self.max_speed * dt;
+        let new_position = if distance <= step {
+            self.path_index += 1;
+            self.traversing_link = false;
+            self.events
+                .push(NavigationAgentEvent::OffMeshLinkFinished { flags });
+            end
+        } else {
+            position + to_end * (step / distance)
+        };
+
+        self.velocity = if dt > 0.0 {
+            (new_position - position) / dt
+        } else {
+            Vector3::zeros()
+        };
+        self.set_world_position(nodes, new_position);
+    }
+
+    fn preferred_velocity(&mut self, position: Vector3<f32>) -> Vector2<f32> {
+        // Skip the points that were already reached. Off-mesh links are never skipped, since their
+        // endpoints could be at the same point on XZ plane (ladders, for example).
+        while self.current_link_flags().is_none()
+            && self.path_index + 1 < self.path.len()
+            && to_plane(self.path[self.path_index] - position).norm()
+                <= (*self.arrival_distance).max(*self.radius)
+        {
+            self.path_index += 1;
+        }
+
+        let Some(waypoint) = self.path.get(self.path_index) else {
+            return Vector2::zeros();
+        };
+        let to_waypoint = to_plane(waypoint - position);
+        let distance = to_waypoint.norm();
+        if distance <= f32::EPSILON {
+            return Vector2::zeros();
+        }
+
+        // Slow down smoothly when approaching the last point of the path.
+        let mut speed = *self.max_speed;
+        if self.path_index + 1 == self.path.len() && *self.max_acceleration > 0.0 {
+            let slowing_distance = speed * speed / (2.0 * *self.max_acceleration);
+            if distance < slowing_distance {
+                speed *= distance / slowing_distance;
+            }
+        }
+
+        to_waypoint * (speed / distance)
+    }
+}
+
+impl NodeTrait for NavigationAgent {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        self.events.clear();
+
+        let Some(target) = self.target else {
+            self.velocity = Vector3::zeros();
+            return;
+        };
+
+        let Some(navmesh) = context
+            .nodes
+            .try_borrow(*self.navmesh)
+            .and_then(|n| n.cast::<NavigationalMesh>())
+        else {
+            return;
+        };
+        let navmesh = navmesh.navmesh_ref();
+
+        let position = self.global_position();
+
+        if self.path_dirty {
+            self.path_dirty = false;
+            self.path_index = 0;
+            self.traversing_link = false;
+            let mut path_builder = NavmeshAgent::new();
+            path_builder.set_radius(*self.radius);
+            if path_builder
+                .calculate_path(&navmesh, position, target)
+                .is_err()
+            {
+                self.stop(NavigationAgentEvent::PathNotFound);
+                return;
+            }
+            self.path = path_builder.path().to_vec();
+            self.segment_kinds = path_builder.path_segments().map(|s| s.kind).collect();
+        }
+
+        if !self.has_links_ahead() {
+            if let Some(last) = self.path.last() {
+                if to_plane(last - position).norm() <= *self.arrival_distance {
+                    self.stop(NavigationAgentEvent::Arrived);
+                    return;
+                }
+            }
+        }
+
+        let dt = context.dt;
+
+        if let Some(flags) = self.current_link_flags() {
+            drop(navmesh);
+            self.traverse_link(context.nodes, position, flags, dt);
+            return;
+        }
+
+        let preferred_velocity = self.preferred_velocity(position);
+
+        let desired_velocity = if *self.avoidance_enabled {
+            // The agent itself is taken out of the pool during the update, so it won't be among
+            // its neighbours.
+            let neighbours = context
+                .nodes
+                .iter()
+                .filter_map(|node| {
+                    let agent = node.cast::<NavigationAgent>()?;
+                    let agent_position = agent.global_position();
+                    (agent.is_globally_enabled()
+                        && agent_position.metric_distance(&position) <= *self.neighbour_distance)
+                        .then(|| AvoidanceNeighbour {
+                            position: to_plane(agent_position),
+                            velocity: to_plane(agent.velocity),
+                            radius: *agent.radius,
+                        })
+                })
+                .collect::<Vec<_>>();
+
+            AvoidanceAgent {
+                position: to_plane(position),
+                velocity: to_plane(self.velocity),
+                radius: *self.radius,
+                max_speed: *self.max_speed,
+                time_horizon: *self.time_horizon,
+            }
+            .compute_velocity(preferred_velocity, &neighbours, dt)
+        } else {
+            preferred_velocity
+        };
+
+        // Apply the acceleration limit.
+        let current_velocity = to_plane(self.velocity);
+        let mut delta = desired_velocity - current_velocity;
+        let max_delta = *self.max_acceleration * dt;
+        if delta.norm() > max_delta {
+            delta = delta.normalize() * max_delta;
+        }
+        let velocity = current_velocity + delta;
+
+        // Keep the agent on the surface of the navmesh.
+        let mut new_position = position + Vector3::new(velocity.x, 0.0, velocity.y) * dt;
+        if let Some((projection, _)) = navmesh.query_closest(new_position) {
+            new_position = projection;
+        }
+        self.velocity = if dt > 0.0 {
+            (new_position - position) / dt
+        } else {
+            Vector3::zeros()
+        };
+
+        drop(navmesh);
+
+        self.set_world_position(context.nodes, new_position);
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        let color = Color::opaque(255, 200, 0);
+        ctx.draw_wire_sphere(self.global_position(), *self.radius, 16, color);
+        for (index, pair) in self.path.windows(2).enumerate() {
+            let is_link = matches!(
+                self.segment_kinds.get(index),
+                Some(PathSegmentKind::OffMeshLink { .. })
+            );
+            ctx.add_line(Line {
+                begin: pair[0],
+                end: pair[1],
+                color: if is_link {
+                    Color::opaque(0, 200, 255)
+                } else {
+                    color
+                },
+            });
+        }
+    }
+}
+
+/// Allows you to create navigation agents in declarative manner.
+pub struct NavigationAgentBuilder {
+    base_builder: BaseBuilder,
+    navmesh: Handle<Node>,
+    max_speed: f32,
+    max_acceleration: f32,
+    radius: f32,
+    avoidance_enabled: bool,
+    neighbour_distance: f32,
+    time_horizon: f32,
+    arrival_distance: f32,
+}
+
+impl NavigationAgentBuilder {
+    /// Creates new navigation agent builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            navmesh: Default::default(),
+            max_speed: 3.5,
+            max_acceleration: 8.0,
+            radius: 0.4,
+            avoidance_enabled: true,
+            neighbour_distance: 5.0,
+            time_horizon: 1.5,
+            arrival_distance: 0.2,
+        }
+    }
+
+    /// Sets the desired navigational mesh node, that will be used for path finding.
+    pub fn with_navmesh(mut self, navmesh: Handle<Node>) -> Self {
+        self.navmesh = navmesh;
+        self
+    }
+
+    /// Sets the desired maximum speed of the agent (in meters per second).
+    pub fn with_max_speed(mut self, max_speed: f32) -> Self {
+        self.max_speed = max_speed;
+        self
+    }
+
+    /// Sets the desired maximum acceleration of the agent (in meters per second squared).
+    pub fn with_max_acceleration(mut self, max_acceleration: f32) -> Self {
+        self.max_acceleration = max_acceleration;
+        self
+    }
+
+    /// Sets the desired radius of the agent.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Enables or disables avoidance of other agents.
+    pub fn with_avoidance_enabled(mut self, enabled: bool) -> Self {
+        self.avoidance_enabled = enabled;
+        self
+    }
+
+    /// Sets the desired maximum distance to other agents, that will be taken into account by the
+    /// avoidance.
+    pub fn with_neighbour_distance(mut self, distance: f32) -> Self {
+        self.neighbour_distance = distance;
+        self
+    }
+
+    /// Sets the desired avoidance time horizon (in seconds).
+    pub fn with_time_horizon(mut self, time_horizon: f32) -> Self {
+        self.time_horizon = time_horizon;
+        self
+    }
+
+    /// Sets the desired distance to the target at which the agent is considered arrived.
+    pub fn with_arrival_distance(mut self, distance: f32) -> Self {
+        self.arrival_distance = distance;
+        self
+    }
+
+    fn build_navigation_agent(self) -> NavigationAgent {
+        NavigationAgent {
+            base: self.base_builder.build_base(),
+            navmesh: self.navmesh.into(),
+            max_speed: self.max_speed.into(),
+            max_acceleration: self.max_acceleration.into(),
+            radius: self.radius.into(),
+            avoidance_enabled: self.avoidance_enabled.into(),
+            neighbour_distance: self.neighbour_distance.into(),
+            time_horizon: self.time_horizon.into(),
+            arrival_distance: self.arrival_distance.into(),
+            target: None,
+            path_dirty: false,
+            path: Default::default(),
+            segment_kinds: Default::default(),
+            path_index: 0,
+            traversing_link: false,
+            velocity: Default::default(),
+            events: Default::default(),
+        }
+    }
+
+    /// Creates new navigation agent node, but does not add it to a graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_navigation_agent())
+    }
+
+    /// Creates new navigation agent node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
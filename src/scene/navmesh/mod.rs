@@ -0,0 +1,610 @@
+//! Navigational mesh (navmesh for short) is a surface which can be used for path finding. See [`NavigationalMesh`] docs
+//! for more info and usage examples.
+
+use crate::{
+    core::{
+        algebra::Vector3,
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        parking_lot::RwLock,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        navmesh::obstacle::NavmeshObstacle,
+        node::{Node, NodeTrait, UpdateContext},
+    },
+    utils::navmesh::{bake::NavmeshBakeSettings, Navmesh, OffMeshLink},
+};
+use fxhash::FxHashMap;
+use fyrox_core::parking_lot::{RwLockReadGuard, RwLockWriteGuard};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+pub mod agent;
+pub mod obstacle;
+
+/// Size (in meters) of a tile of the obstacle carving grid. When an obstacle changes, only the
+/// triangles of the tiles affected by the obstacle are re-checked.
+const CARVING_TILE_SIZE: f32 = 8.0;
+
+#[derive(Clone, Debug, Default)]
+struct CarvingTile {
+    bounds: AxisAlignedBoundingBox,
+    triangles: Vec<usize>,
+}
+
+/// Runtime state of obstacle carving.
+#[derive(Clone, Debug, Default)]
+struct ObstacleCarving {
+    /// Navmesh without any carving. It is `None` if there are no active obstacles.
+    source: Option<Navmesh>,
+    tiles: FxHashMap<(i32, i32), CarvingTile>,
+    blocked: Vec<bool>,
+    obstacles: FxHashMap<Handle<Node>, AxisAlignedBoundingBox>,
+}
+
+fn triangle_bounds(navmesh: &Navmesh, triangle: usize) -> AxisAlignedBoundingBox {
+    let vertices = navmesh.vertices();
+    let triangle = navmesh.triangles()[triangle];
+    AxisAlignedBoundingBox::from_points(&[
+        vertices[triangle[0] as usize],
+        vertices[triangle[1] as usize],
+        vertices[triangle[2] as usize],
+    ])
+}
+
+fn is_blocked(
+    triangle_bounds: &AxisAlignedBoundingBox,
+    obstacle_bounds: &AxisAlignedBoundingBox,
+    agent_height: f32,
+) -> bool {
+    // Triangles that are only touching the obstacle are not blocked.
+    const EPSILON: f32 = 0.001;
+    triangle_bounds.min.x < obstacle_bounds.max.x - EPSILON
+        && triangle_bounds.max.x > obstacle_bounds.min.x + EPSILON
+        && triangle_bounds.min.z < obstacle_bounds.max.z - EPSILON
+        && triangle_bounds.max.z > obstacle_bounds.min.z + EPSILON
+        // An agent standing on the triangle must intersect the obstacle.
+        && triangle_bounds.min.y <= obstacle_bounds.max.y
+        && triangle_bounds.max.y + agent_height >= obstacle_bounds.min.y
+}
+
+impl ObstacleCarving {
+    fn reset(&mut self, navmesh: &mut Navmesh) {
+        if let Some(source) = self.source.take() {
+            *navmesh = source;
+        }
+        self.tiles.clear();
+        self.blocked.clear();
+        self.obstacles.clear();
+    }
+
+    fn split_into_tiles(&mut self, source: &Navmesh) {
+        self.tiles.clear();
+        self.blocked = vec![false; source.triangles().len()];
+        for index in 0..source.triangles().len() {
+            let bounds = triangle_bounds(source, index);
+            let center = bounds.center();
+            let tile = self
+                .tiles
+                .entry((
+                    (center.x / CARVING_TILE_SIZE).floor() as i32,
+                    (center.z / CARVING_TILE_SIZE).floor() as i32,
+                ))
+                .or_insert_with(|| CarvingTile {
+                    bounds,
+                    triangles: Default::default(),
+                });
+            tile.bounds.add_box(bounds);
+            tile.triangles.push(index);
+        }
+    }
+
+    fn update(
+        &mut self,
+        navmesh: &mut Navmesh,
+        obstacles: FxHashMap<Handle<Node>, AxisAlignedBoundingBox>,
+        agent_height: f32,
+    ) {
+        // Collect the areas that were affected by spawned, moved or removed obstacles.
+        let mut dirty_areas = Vec::new();
+        for (handle, bounds) in obstacles.iter() {
+            match self.obstacles.get(handle) {
+                Some(old_bounds)
+                    if old_bounds.min == bounds.min && old_bounds.max == bounds.max => {}
+                Some(old_bounds) => {
+                    dirty_areas.push(*old_bounds);
+                    dirty_areas.push(*bounds);
+                }
+                None => dirty_areas.push(*bounds),
+            }
+        }
+        for (handle, old_bounds) in self.obstacles.iter() {
+            if !obstacles.contains_key(handle) {
+                dirty_areas.push(*old_bounds);
+            }
+        }
+
+        if dirty_areas.is_empty() {
+            return;
+        }
+
+        if obstacles.is_empty() {
+            self.reset(navmesh);
+            return;
+        }
+
+        let source = match self.source.take() {
+            Some(source) => source,
+            None => {
+                let source = std::mem::take(navmesh);
+                self.split_into_tiles(&source);
+                source
+            }
+        };
+
+        for tile in self.tiles.values() {
+            if !dirty_areas
+                .iter()
+                .any(|area| is_blocked(&tile.bounds, area, agent_height))
+            {
+                continue;
+            }
+
+            for &triangle in tile.triangles.iter() {
+                let bounds = triangle_bounds(&source, triangle);
+                self.blocked[triangle] = obstacles
+                    .values()
+                    .any(|obstacle| is_blocked(&bounds, obstacle, agent_height));
+            }
+        }
+
+        *navmesh = Navmesh::new(
+            source
+                .triangles()
+                .iter()
+                .zip(self.blocked.iter())
+                .filter_map(|(triangle, blocked)| (!blocked).then_some(*triangle))
+                .collect(),
+            source.vertices().to_vec(),
+        );
+
+        self.source = Some(source);
+        self.obstacles = obstacles;
+    }
+}
+
+#[derive(Clone, Default, Reflect, Debug)]
+pub(crate) struct Container(Arc<RwLock<Navmesh>>);
+
+impl PartialEq for Container {
+    fn eq(&self, other: &Self) -> bool {
+        *self.0.read() == *other.0.read()
+    }
+}
+
+impl Visit for Container {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        self.0.write().visit(name, visitor)
+    }
+}
+
+/// Navigational mesh (navmesh for short) is a surface which can be used for path finding. Unlike [A* Pathfinder](crate::utils::astar),
+/// it can build arbitrary paths on a surface of large polygons, making a path from point A to point B linear (standard pathfinder builds
+/// path only from vertex to vertex). Navmeshes should be used when you have an arbitrary "walkable" surface, for example, a game level
+/// with rooms, hallways, multiple floors and so on. A* pathfinder should be used for strategies or any other types of games with uniform
+/// pathfinding grid.
+///
+/// ## How to create
+///
+/// You should prefer using the navmesh editor to create navigational meshes, however if it is not possible, you can create it manually.
+/// The editor is also able to bake navigational meshes from static meshes and terrains of a scene using [`NavmeshBakeSettings`]
+/// of the node, see [`crate::utils::navmesh::bake`] module docs for more info.
+/// Use [`NavigationalMeshBuilder`] to create new instance and add it to your scene graph. Keep in mind, that this node is just a
+/// convenient wrapper around [`Navmesh`], so you should also read its docs to get better understanding how it works.
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, math::TriangleDefinition, pool::Handle},
+/// #     scene::{base::BaseBuilder, graph::Graph, navmesh::NavigationalMeshBuilder, node::Node},
+/// #     utils::navmesh::Navmesh,
+/// # };
+/// fn create_navmesh(graph: &mut Graph) -> Handle<Node> {
+///     // A simple navmesh with four vertices and two triangles.
+///     let navmesh = Navmesh::new(
+///         vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])],
+///         vec![
+///             Vector3::new(-1.0, 0.0, 1.0),
+///             Vector3::new(1.0, 0.0, 1.0),
+///             Vector3::new(1.0, 0.0, -1.0),
+///             Vector3::new(-1.0, 0.0, -1.0),
+///         ],
+///     );
+///     NavigationalMeshBuilder::new(BaseBuilder::new())
+///         .with_navmesh(navmesh)
+///         .build(graph)
+/// }
+/// ```
+///
+/// ## Agents
+///
+/// Navigational mesh agent helps you to build paths along the surface of a navigational mesh and follow it. Agents can be
+/// used to drive the motion of your game characters. Every agent knows about its target and automatically rebuilds the path
+/// if the target has moved. Navmesh agents are able to move along the path, providing you with their current position, so you
+/// can use it to perform an actual motion of your game characters. Agents work together with navigational meshes, you need
+/// to update their state every frame, so they can recalculate path if needed. A simple example could something like this:
+///
+/// ```rust
+/// # use fyrox::utils::navmesh::NavmeshAgent;
+/// # struct Foo {
+/// // Add this to your script
+/// agent: NavmeshAgent
+/// # }
+/// ```
+///
+/// After that, you need to update the agent every frame to make sure it will follow the target:
+///
+/// ```rust
+/// # use fyrox::{
+/// #    core::algebra::Vector3, scene::navmesh::NavigationalMesh, utils::navmesh::NavmeshAgent,
+/// # };
+/// fn update_agent(
+///     agent: &mut NavmeshAgent,
+///     target: Vector3<f32>,
+///     dt: f32,
+///     navmesh: &NavigationalMesh,
+/// ) {
+///     // Set the target to follow and the speed.
+///     agent.set_target(target);
+///     agent.set_speed(1.0);
+///
+///     // Update the agent.
+///     agent.update(dt, &navmesh.navmesh_ref()).unwrap();
+///
+///     // Print its position - you can use this position as target point of your game character.
+///     println!("{}", agent.position());
+/// }
+/// ```
+///
+/// This method should be called in `on_update` of your script. It accepts four parameters: a reference to the agent, a
+/// target which it will follow, a time step (`context.dt`), and a reference to navigational mesh node. You can fetch
+/// navigational mesh from the scene graph by its name:
+///
+/// ```rust
+/// # use fyrox::scene::{navmesh::NavigationalMesh, Scene};
+/// # use fyrox_graph::SceneGraph;
+/// fn find_navmesh<'a>(scene: &'a mut Scene, name: &str) -> &'a mut NavigationalMesh {
+///     let handle = scene.graph.find_by_name_from_root(name).unwrap().0;
+///     scene.graph[handle].as_navigational_mesh_mut()
+/// }
+/// ```
+#[derive(Debug, Clone, Visit, Reflect, Default)]
+pub struct NavigationalMesh {
+    base: Base,
+    #[reflect(read_only)]
+    navmesh: InheritableVariable<Container>,
+
+    #[visit(optional)]
+    #[reflect(
+        description = "Settings that are used to bake the navmesh from the scene geometry.",
+        setter = "set_bake_settings"
+    )]
+    bake_settings: InheritableVariable<NavmeshBakeSettings>,
+
+    #[visit(optional)]
+    #[reflect(
+        description = "A set of connections between the points of the navmesh, that cannot be \
+        traversed by walking (jumps, ladders, etc.).",
+        setter = "set_off_mesh_links"
+    )]
+    off_mesh_links: InheritableVariable<Vec<OffMeshLink>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    carving: ObstacleCarving,
+}
+
+impl TypeUuidProvider for NavigationalMesh {
+    fn type_uuid() -> Uuid {
+        uuid!("d0ce963c-b50a-4707-bd21-af6dc0d1c668")
+    }
+}
+
+impl Deref for NavigationalMesh {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for NavigationalMesh {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl NodeTrait for NavigationalMesh {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        let margin = Vector3::new(
+            self.bake_settings.agent_radius,
+            0.0,
+            self.bake_settings.agent_radius,
+        );
+        let obstacles = context
+            .nodes
+            .pair_iter()
+            .filter_map(|(handle, node)| {
+                let obstacle = node.cast::<NavmeshObstacle>()?;
+                obstacle.is_globally_enabled().then(|| {
+                    let bounds = obstacle.world_bounding_box();
+                    (
+                        handle,
+                        AxisAlignedBoundingBox::from_min_max(
+                            bounds.min - margin,
+                            bounds.max + margin,
+                        ),
+                    )
+                })
+            })
+            .collect();
+
+        let mut navmesh = self.navmesh.0.write();
+
+        self.carving
+            .update(&mut navmesh, obstacles, self.bake_settings.agent_height);
+
+        // Links could be changed via reflection, so they're synced here.
+        if navmesh.off_mesh_links() != self.off_mesh_links.as_slice() {
+            navmesh.set_off_mesh_links((*self.off_mesh_links).clone());
+        }
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        let navmesh = self.navmesh.0.read();
+
+        for vertex in navmesh.vertices().iter() {
+            ctx.draw_sphere(*vertex, 6, 6, 0.1, Color::GREEN);
+        }
+
+        for triangle in navmesh.triangles().iter() {
+            for edge in &triangle.edges() {
+                ctx.add_line(Line {
+                    begin: navmesh.vertices()[edge.a as usize],
+                    end: navmesh.vertices()[edge.b as usize],
+                    color: Color::GREEN,
+                });
+            }
+        }
+
+        for link in self.off_mesh_links.iter() {
+            let color = Color::opaque(0, 200, 255);
+            ctx.draw_sphere(link.start, 6, 6, 0.1, color);
+            ctx.draw_sphere(link.end, 6, 6, 0.1, color);
+
+            // Draw the link as an arc, so it could be distinguished from the edges of the navmesh.
+            const SEGMENTS: usize = 8;
+            let height = 0.25 * link.start.metric_distance(&link.end);
+            let point = |t: f32| {
+                link.start.lerp(&link.end, t) + Vector3::new(0.0, 4.0 * height * t * (1.0 - t), 0.0)
+            };
+            for i in 0..SEGMENTS {
+                ctx.add_line(Line {
+                    begin: point(i as f32 / SEGMENTS as f32),
+                    end: point((i + 1) as f32 / SEGMENTS as f32),
+                    color,
+                });
+            }
+        }
+    }
+}
+
+impl NavigationalMesh {
+    /// Returns a reference to the inner navigational mesh.
+    pub fn navmesh_ref(&self) -> RwLockReadGuard<Navmesh> {
+        self.navmesh.0.read()
+    }
+
+    /// Returns a reference to the inner navigational mesh.
+    pub fn navmesh_mut(&mut self) -> RwLockWriteGuard<Navmesh> {
+        self.navmesh.0.write()
+    }
+
+    /// Returns a shared reference to the inner navigational mesh. It could be used to perform
+    /// off-thread path calculations.
+    pub fn navmesh(&self) -> Arc<RwLock<Navmesh>> {
+        self.navmesh.0.clone()
+    }
+
+    /// Replaces the inner navigational mesh with the new one and returns the old one. It could be
+    /// used to store the result of [`crate::utils::navmesh::bake::bake`] in the node.
+    pub fn set_navmesh(&mut self, navmesh: Navmesh) -> Navmesh {
+        self.navmesh.mark_modified();
+        let mut current = self.navmesh.0.write();
+        // Obstacles will carve the new navmesh on the next update.
+        self.carving.reset(&mut current);
+        let old = std::mem::replace(&mut *current, navmesh);
+        current.set_off_mesh_links((*self.off_mesh_links).clone());
+        old
+    }
+
+    /// Returns `true` if the navmesh is currently carved by at least one [`NavmeshObstacle`].
+    pub fn is_carved(&self) -> bool {
+        self.carving.source.is_some()
+    }
+
+    /// Sets new settings that will be used to bake the navmesh from the scene geometry. See
+    /// [`NavmeshBakeSettings`] docs for more info.
+    pub fn set_bake_settings(&mut self, settings: NavmeshBakeSettings) -> NavmeshBakeSettings {
+        self.bake_settings.set_value_and_mark_modified(settings)
+    }
+
+    /// Returns current settings that will be used to bake the navmesh from the scene geometry.
+    pub fn bake_settings(&self) -> &NavmeshBakeSettings {
+        &self.bake_settings
+    }
+
+    /// Sets a new set of off-mesh links (jumps, ladders, etc.) of the navmesh. The links of the
+    /// node overwrite the links of the inner navmesh. See [`OffMeshLink`] docs for more info.
+    pub fn set_off_mesh_links(&mut self, links: Vec<OffMeshLink>) -> Vec<OffMeshLink> {
+        self.navmesh.0.write().set_off_mesh_links(links.clone());
+        self.off_mesh_links.set_value_and_mark_modified(links)
+    }
+
+    /// Returns a reference to the array of off-mesh links of the navmesh.
+    pub fn off_mesh_links(&self) -> &[OffMeshLink] {
+        &self.off_mesh_links
+    }
+}
+
+/// Creates navigational meshes and adds them to a scene graph.
+pub struct NavigationalMeshBuilder {
+    base_builder: BaseBuilder,
+    navmesh: Navmesh,
+    bake_settings: NavmeshBakeSettings,
+    off_mesh_links: Vec<OffMeshLink>,
+}
+
+impl NavigationalMeshBuilder {
+    /// Creates new navigational mesh builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            navmesh: Default::default(),
+            bake_settings: Default::default(),
+            off_mesh_links: Default::default(),
+        }
+    }
+
+    /// Sets the actual navigational mesh.
+    pub fn with_navmesh(mut self, navmesh: Navmesh) -> Self {
+        self.navmesh = navmesh;
+        self
+    }
+
+    /// Sets the desired settings that will be used to bake the navmesh from the scene geometry.
+    pub fn with_bake_settings(mut self, settings: NavmeshBakeSettings) -> Self {
+        self.bake_settings = settings;
+        self
+    }
+
+    /// Sets the desired off-mesh links (jumps, ladders, etc.) of the navmesh.
+    pub fn with_off_mesh_links(mut self, links: Vec<OffMeshLink>) -> Self {
+        self.off_mesh_links = links;
+        self
+    }
+
+    fn build_navigational_mesh(mut self) -> NavigationalMesh {
+        self.navmesh.set_off_mesh_links(self.off_mesh_links.clone());
+        NavigationalMesh {
+            base: self.base_builder.build_base(),
+            navmesh: InheritableVariable::new_modified(Container(Arc::new(RwLock::new(
+                self.navmesh,
+            )))),
+            bake_settings: self.bake_settings.into(),
+            off_mesh_links: self.off_mesh_links.into(),
+            carving: Default::default(),
+        }
+    }
+
+    /// Creates new navigational mesh instance.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_navigational_mesh())
+    }
+
+    /// Creates new navigational mesh instance and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::Vector3, math::aabb::AxisAlignedBoundingBox, math::TriangleDefinition,
+            pool::Handle,
+        },
+        scene::navmesh::ObstacleCarving,
+        utils::navmesh::Navmesh,
+    };
+    use fxhash::FxHashMap;
+
+    // A strip of 1x1 quads along X axis.
+    fn make_strip(length: u32) -> Navmesh {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+        for x in 0..=length {
+            vertices.push(Vector3::new(x as f32, 0.0, 0.0));
+            vertices.push(Vector3::new(x as f32, 0.0, 1.0));
+        }
+        for x in 0..length {
+            let i = x * 2;
+            triangles.push(TriangleDefinition([i, i + 1, i + 3]));
+            triangles.push(TriangleDefinition([i, i + 3, i + 2]));
+        }
+        Navmesh::new(triangles, vertices)
+    }
+
+    #[test]
+    fn test_obstacle_carving() {
+        let mut navmesh = make_strip(20);
+        let mut carving = ObstacleCarving::default();
+
+        let mut obstacles = FxHashMap::default();
+        obstacles.insert(
+            Handle::new(1, 1),
+            AxisAlignedBoundingBox::from_min_max(
+                Vector3::new(10.2, -1.0, -1.0),
+                Vector3::new(10.8, 1.0, 2.0),
+            ),
+        );
+        carving.update(&mut navmesh, obstacles.clone(), 2.0);
+        assert_eq!(navmesh.triangles().len(), 38);
+
+        // Move the obstacle to another tile.
+        obstacles.insert(
+            Handle::new(1, 1),
+            AxisAlignedBoundingBox::from_min_max(
+                Vector3::new(2.2, -1.0, -1.0),
+                Vector3::new(3.8, 1.0, 2.0),
+            ),
+        );
+        carving.update(&mut navmesh, obstacles, 2.0);
+        assert_eq!(navmesh.triangles().len(), 36);
+        assert!(navmesh
+            .triangles()
+            .iter()
+            .all(|t| navmesh.vertices()[t[0] as usize].x >= 4.0
+                || navmesh.vertices()[t[0] as usize].x <= 2.0));
+
+        // Removing the obstacle restores the original navmesh.
+        carving.update(&mut navmesh, Default::default(), 2.0);
+        assert_eq!(navmesh.triangles().len(), 40);
+        assert!(carving.source.is_none());
+    }
+}
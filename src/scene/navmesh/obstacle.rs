@@ -0,0 +1,171 @@
+//! Navmesh obstacle is a volume that carves navigational meshes at runtime. See [`NavmeshObstacle`]
+//! docs for more info.
+
+use crate::{
+    core::{
+        algebra::Vector3,
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::SceneDrawingContext,
+        graph::Graph,
+        node::{Node, NodeTrait},
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Navmesh obstacle is a box-shaped volume that carves every [`super::NavigationalMesh`] it
+/// intersects. It could be used to make doors, barricades, destructible walls, etc. affect path
+/// finding without re-baking the navmesh. Obstacles could be spawned, moved, enabled or disabled
+/// at any time, navigational meshes will track the changes and rebuild only the tiles affected by
+/// the obstacle. Disabled obstacles do not carve navmeshes.
+///
+/// Carving is done at the triangle level, every triangle of the navmesh, which bounds intersect
+/// the bounds of the obstacle (expanded by [`crate::utils::navmesh::bake::NavmeshBakeSettings::agent_radius`]
+/// of the navmesh) is removed. It gives precise results for navmeshes baked from the scene geometry,
+/// since they consist of small triangles, but could remove too much of hand-made navmeshes with
+/// large triangles.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder, graph::Graph, navmesh::obstacle::NavmeshObstacleBuilder,
+/// #         node::Node, transform::TransformBuilder,
+/// #     },
+/// # };
+/// fn create_door_obstacle(graph: &mut Graph) -> Handle<Node> {
+///     NavmeshObstacleBuilder::new(
+///         BaseBuilder::new().with_local_transform(
+///             TransformBuilder::new()
+///                 .with_local_position(Vector3::new(2.0, 1.0, 0.0))
+///                 .build(),
+///         ),
+///     )
+///     .with_half_extents(Vector3::new(1.0, 1.0, 0.1))
+///     .build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct NavmeshObstacle {
+    base: Base,
+
+    #[reflect(
+        description = "Half extents of the obstacle box in local coordinates.",
+        setter = "set_half_extents"
+    )]
+    half_extents: InheritableVariable<Vector3<f32>>,
+}
+
+impl Deref for NavmeshObstacle {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for NavmeshObstacle {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for NavmeshObstacle {
+    fn default() -> Self {
+        NavmeshObstacleBuilder::new(BaseBuilder::new()).build_navmesh_obstacle()
+    }
+}
+
+impl TypeUuidProvider for NavmeshObstacle {
+    fn type_uuid() -> Uuid {
+        uuid!("5c3a8e71-2f9d-4b06-a4e8-7d1b6c9f0e23")
+    }
+}
+
+impl NavmeshObstacle {
+    /// Sets new half extents of the obstacle box in local coordinates.
+    pub fn set_half_extents(&mut self, half_extents: Vector3<f32>) -> Vector3<f32> {
+        self.half_extents
+            .set_value_and_mark_modified(half_extents.sup(&Vector3::zeros()))
+    }
+
+    /// Returns half extents of the obstacle box in local coordinates.
+    pub fn half_extents(&self) -> Vector3<f32> {
+        *self.half_extents
+    }
+}
+
+impl NodeTrait for NavmeshObstacle {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_min_max(-*self.half_extents, *self.half_extents)
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        ctx.draw_oob(
+            &self.local_bounding_box(),
+            self.global_transform(),
+            Color::opaque(255, 140, 0),
+        );
+    }
+}
+
+/// Allows you to create navmesh obstacles in declarative manner.
+pub struct NavmeshObstacleBuilder {
+    base_builder: BaseBuilder,
+    half_extents: Vector3<f32>,
+}
+
+impl NavmeshObstacleBuilder {
+    /// Creates new navmesh obstacle builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            half_extents: Vector3::new(0.5, 1.0, 0.5),
+        }
+    }
+
+    /// Sets the desired half extents of the obstacle box in local coordinates.
+    pub fn with_half_extents(mut self, half_extents: Vector3<f32>) -> Self {
+        self.half_extents = half_extents;
+        self
+    }
+
+    fn build_navmesh_obstacle(self) -> NavmeshObstacle {
+        NavmeshObstacle {
+            base: self.base_builder.build_base(),
+            half_extents: self.half_extents.into(),
+        }
+    }
+
+    /// Creates new navmesh obstacle node, but does not add it to a graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_navmesh_obstacle())
+    }
+
+    /// Creates new navmesh obstacle node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
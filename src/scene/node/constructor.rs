@@ -1,23 +1,35 @@
 //! A special container that is able to create nodes by their type UUID.
 
 use crate::{
-    core::{parking_lot::Mutex, uuid::Uuid, TypeUuidProvider},
+    core::{
+        parking_lot::{Mutex, MutexGuard},
+        uuid::Uuid,
+        TypeUuidProvider,
+    },
     scene::{
         self,
-        animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+        animation::{absm::AnimationBlendingStateMachine, ik::IkChain, AnimationPlayer},
         camera::Camera,
+        character::CharacterController,
+        cloth::Cloth,
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
+        foliage::Foliage,
         light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
         mesh::Mesh,
-        navmesh::NavigationalMesh,
+        navmesh::{agent::NavigationAgent, obstacle::NavmeshObstacle, NavigationalMesh},
         node::{Node, NodeTrait},
         particle_system::ParticleSystem,
         pivot::Pivot,
         ragdoll::Ragdoll,
-        sound::{listener::Listener, Sound},
+        reflection_probe::ReflectionProbe,
+        sky::Sky,
+        sound::{listener::Listener, reverb_zone::ReverbZone, Sound},
+        spline::Spline,
         sprite::Sprite,
         terrain::Terrain,
+        trail::Trail,
+        vehicle::{Vehicle, Wheel},
     },
 };
 use fxhash::FxHashMap;
@@ -47,6 +59,7 @@ impl NodeConstructorContainer {
         container.add::<ParticleSystem>();
         container.add::<Sound>();
         container.add::<Listener>();
+        container.add::<ReverbZone>();
         container.add::<Camera>();
         container.add::<scene::collider::Collider>();
         container.add::<Decal>();
@@ -57,8 +70,20 @@ impl NodeConstructorContainer {
         container.add::<Terrain>();
         container.add::<AnimationPlayer>();
         container.add::<AnimationBlendingStateMachine>();
+        container.add::<IkChain>();
         container.add::<NavigationalMesh>();
+        container.add::<NavmeshObstacle>();
+        container.add::<NavigationAgent>();
         container.add::<Ragdoll>();
+        container.add::<ReflectionProbe>();
+        container.add::<Vehicle>();
+        container.add::<Wheel>();
+        container.add::<CharacterController>();
+        container.add::<Cloth>();
+        container.add::<Foliage>();
+        container.add::<Spline>();
+        container.add::<Sky>();
+        container.add::<Trail>();
 
         container
     }
@@ -102,4 +127,9 @@ impl NodeConstructorContainer {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns inner map of node constructors.
+    pub fn map(&self) -> MutexGuard<FxHashMap<Uuid, NodeConstructor>> {
+        self.map.lock()
+    }
 }
@@ -35,6 +35,7 @@ use crate::{
         particle_system::ParticleSystem,
         pivot::Pivot,
         ragdoll::Ragdoll,
+        reflection_probe::ReflectionProbe,
         sound::{context::SoundContext, listener::Listener, Sound},
         sprite::Sprite,
         terrain::Terrain,
@@ -563,6 +564,7 @@ impl Node {
     define_is_as!(AnimationBlendingStateMachine => fn is_absm, fn as_absm, fn as_absm_mut);
     define_is_as!(AnimationPlayer => fn is_animation_player, fn as_animation_player, fn as_animation_player_mut);
     define_is_as!(Ragdoll => fn is_ragdoll, fn as_ragdoll, fn as_ragdoll_mut);
+    define_is_as!(ReflectionProbe => fn is_reflection_probe, fn as_reflection_probe, fn as_reflection_probe_mut);
 }
 
 impl Visit for Node {
@@ -0,0 +1,456 @@
+//! Mesh emitter emits particles uniformly from the surface or the volume of a mesh. It supports
+//! skinned meshes, so it could be used to create effects like a dissolving character.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        numeric_range::RangeExt,
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{
+        graph::NodePool,
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            Mesh,
+        },
+        node::Node,
+        particle_system::{
+            emitter::{
+                base::{BaseEmitter, BaseEmitterBuilder},
+                Emit, Emitter,
+            },
+            particle::Particle,
+            ParticleSystemRng,
+        },
+    },
+};
+use fyrox_core::uuid_provider;
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines where particles are emitted by [`MeshEmitter`].
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum MeshEmissionMode {
+    /// Particles are emitted on the surface of the mesh. Every point of the surface has the same
+    /// probability to emit a particle, so large triangles emit more particles than small ones.
+    #[default]
+    Surface,
+    /// Particles are emitted inside the volume of the mesh. The volume is approximated by a set of
+    /// tetrahedra formed by triangles of the mesh and its center, so it is exact only for meshes
+    /// whose every point is visible from the center (spheres, cubes, etc.).
+    Volume,
+}
+
+uuid_provider!(MeshEmissionMode = "a2c6e8f1-3b5d-4f7a-9c1e-6d8b2f4a7e35");
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct EmissionTriangle {
+    vertices: [Vector3<f32>; 3],
+    normal: Vector3<f32>,
+}
+
+/// Triangles of a mesh, that are used to sample positions of particles.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct MeshEmissionCache {
+    mesh: Handle<Node>,
+    mode: MeshEmissionMode,
+    skinned: bool,
+    triangles: Vec<EmissionTriangle>,
+    /// Cumulative areas (surface mode) or volumes (volume mode) of the triangles.
+    cumulative_weights: Vec<f32>,
+    center: Vector3<f32>,
+    /// Transforms triangles into local space of the particle system.
+    transform: Matrix4<f32>,
+}
+
+impl MeshEmissionCache {
+    fn rebuild(&mut self, mesh_handle: Handle<Node>, mode: MeshEmissionMode, nodes: &NodePool) {
+        self.mesh = mesh_handle;
+        self.mode = mode;
+        self.skinned = false;
+        self.triangles.clear();
+        self.cumulative_weights.clear();
+        self.center = Vector3::default();
+
+        let Some(mesh) = nodes.try_borrow(mesh_handle).and_then(|n| n.cast::<Mesh>()) else {
+            return;
+        };
+
+        // Skinned meshes are sampled in world space, static ones - in local space of the mesh.
+        self.skinned = mesh.surfaces().iter().any(|s| !s.bones().is_empty());
+
+        let mut vertex_count = 0;
+        for surface in mesh.surfaces() {
+            let transforms = if self.skinned {
+                let bone_matrices = surface
+                    .bones()
+                    .iter()
+                    .map(|&bone| {
+                        nodes
+                            .try_borrow(bone)
+                            .map(|b| b.global_transform() * b.inv_bind_pose_transform())
+                            .unwrap_or_else(Matrix4::identity)
+                    })
+                    .collect::<Vec<_>>();
+
+                Some((mesh.global_transform(), bone_matrices))
+            } else {
+                None
+            };
+
+            let data = surface.data();
+            let data = data.lock();
+
+            let positions = data
+                .vertex_buffer
+                .iter()
+                .map(|vertex| {
+                    let position = vertex
+                        .read_3_f32(VertexAttributeUsage::Position)
+                        .unwrap_or_default();
+
+                    let Some((global_transform, bone_matrices)) = transforms.as_ref() else {
+                        return position;
+                    };
+
+                    let transform = if bone_matrices.is_empty() {
+                        *global_transform
+                    } else {
+                        let weights = vertex
+                            .read_4_f32(VertexAttributeUsage::BoneWeight)
+                            .unwrap_or_default();
+                        let indices = vertex
+                            .read_4_u8(VertexAttributeUsage::BoneIndices)
+                            .unwrap_or_default();
+                        let mut transform = Matrix4::zeros();
+                        for (weight, index) in weights.iter().zip(indices.iter()) {
+                            if let Some(bone_matrix) = bone_matrices.get(*index as usize) {
+                                transform += bone_matrix.scale(*weight);
+                            }
+                        }
+                        transform
+                    };
+
+                    transform.transform_point(&Point3::from(position)).coords
+                })
+                .collect::<Vec<_>>();
+
+            vertex_count += positions.len();
+            self.center += positions.iter().sum::<Vector3<f32>>();
+
+            for triangle in data.geometry_buffer.iter() {
+                let (Some(a), Some(b), Some(c)) = (
+                    positions.get(triangle[0] as usize),
+                    positions.get(triangle[1] as usize),
+                    positions.get(triangle[2] as usize),
+                ) else {
+                    continue;
+                };
+
+                self.triangles.push(EmissionTriangle {
+                    vertices: [*a, *b, *c],
+                    normal: (b - a)
+                        .cross(&(c - a))
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        if vertex_count > 0 {
+            self.center = self.center.scale(1.0 / vertex_count as f32);
+        }
+
+        let mut total = 0.0;
+        for triangle in self.triangles.iter() {
+            let [a, b, c] = triangle.vertices;
+            total += match mode {
+                MeshEmissionMode::Surface => (b - a).cross(&(c - a)).norm() * 0.5,
+                MeshEmissionMode::Volume => {
+                    (a - self.center)
+                        .dot(&(b - self.center).cross(&(c - self.center)))
+                        .abs()
+                        / 6.0
+                }
+            };
+            self.cumulative_weights.push(total);
+        }
+    }
+
+    /// Samples a random position and normal in local space of the particle system.
+    fn sample(&self, rng: &mut ParticleSystemRng) -> Option<(Vector3<f32>, Vector3<f32>)> {
+        let total = *self.cumulative_weights.last()?;
+        if total <= 0.0 {
+            return None;
+        }
+
+        let weight = (0.0..total).random(rng);
+        let index = self
+            .cumulative_weights
+            .partition_point(|w| *w <= weight)
+            .min(self.triangles.len() - 1);
+        let triangle = &self.triangles[index];
+
+        let [a, b, c] = triangle.vertices;
+        let mut u = (0.0..1.0).random(rng);
+        let mut v = (0.0..1.0).random(rng);
+        if u + v > 1.0 {
+            u = 1.0 - u;
+            v = 1.0 - v;
+        }
+        let mut position = a + (b - a).scale(u) + (c - a).scale(v);
+        let mut normal = triangle.normal;
+
+        if self.mode == MeshEmissionMode::Volume {
+            // Uniform distribution inside a tetrahedron with the apex at the center.
+            let t = (0.0f32..1.0).random(rng).cbrt();
+            position = self.center + (position - self.center).scale(t);
+            normal = (position - self.center)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or(normal);
+        }
+
+        Some((
+            self.transform
+                .transform_point(&Point3::from(position))
+                .coords,
+            self.transform
+                .transform_vector(&normal)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+/// See module docs.
+///
+/// # Caching
+///
+/// Triangles of a static mesh are cached on first use, changes of the geometry of the mesh are not
+/// tracked. Use [`MeshEmitter::set_mesh`] to force the emitter to read the geometry again. Skinned
+/// meshes are read every frame.
+#[derive(Debug, Clone, Reflect, PartialEq, Visit)]
+pub struct MeshEmitter {
+    emitter: BaseEmitter,
+    mesh: Handle<Node>,
+    mode: MeshEmissionMode,
+    #[reflect(step = 0.01)]
+    normal_velocity: f32,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    cache: MeshEmissionCache,
+}
+
+impl Deref for MeshEmitter {
+    type Target = BaseEmitter;
+
+    fn deref(&self) -> &Self::Target {
+        &self.emitter
+    }
+}
+
+impl DerefMut for MeshEmitter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.emitter
+    }
+}
+
+impl Default for MeshEmitter {
+    fn default() -> Self {
+        Self {
+            emitter: BaseEmitter::default(),
+            mesh: Handle::NONE,
+            mode: Default::default(),
+            normal_velocity: 0.0,
+            cache: Default::default(),
+        }
+    }
+}
+
+impl MeshEmitter {
+    /// Creates new mesh emitter, that emits particles from the given mesh.
+    pub fn new(emitter: BaseEmitter, mesh: Handle<Node>) -> Self {
+        Self {
+            emitter,
+            mesh,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a handle of the mesh, that emits particles.
+    pub fn mesh(&self) -> Handle<Node> {
+        self.mesh
+    }
+
+    /// Sets new mesh, that will emit particles.
+    pub fn set_mesh(&mut self, mesh: Handle<Node>) {
+        self.mesh = mesh;
+        self.cache = Default::default();
+    }
+
+    /// Returns current emission mode.
+    pub fn mode(&self) -> MeshEmissionMode {
+        self.mode
+    }
+
+    /// Sets new emission mode.
+    pub fn set_mode(&mut self, mode: MeshEmissionMode) {
+        self.mode = mode;
+    }
+
+    /// Returns current speed along the normal of the surface.
+    pub fn normal_velocity(&self) -> f32 {
+        self.normal_velocity
+    }
+
+    /// Sets the speed along the normal of the surface at the emission point, that will be added to
+    /// the velocity of emitted particles. In [`MeshEmissionMode::Volume`] mode the normal is the
+    /// direction from the center of the mesh to the emission point. Zero means that velocity of
+    /// particles is defined only by the base emitter.
+    pub fn set_normal_velocity(&mut self, velocity: f32) {
+        self.normal_velocity = velocity;
+    }
+
+    pub(crate) fn update_cache(
+        &mut self,
+        nodes: &NodePool,
+        particle_system_transform: &Matrix4<f32>,
+    ) {
+        let cache = &mut self.cache;
+        if cache.skinned || cache.mesh != self.mesh || cache.mode != self.mode {
+            cache.rebuild(self.mesh, self.mode, nodes);
+        }
+
+        let inv_transform = particle_system_transform
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity);
+        cache.transform = if cache.skinned {
+            inv_transform
+        } else {
+            nodes
+                .try_borrow(self.mesh)
+                .map(|mesh| inv_transform * mesh.global_transform())
+                .unwrap_or(inv_transform)
+        };
+    }
+}
+
+impl Emit for MeshEmitter {
+    fn emit(&self, particle: &mut Particle, rng: &mut ParticleSystemRng) {
+        self.emitter.emit(particle, rng);
+        particle.position = self.position();
+        if let Some((position, normal)) = self.cache.sample(rng) {
+            particle.position += position;
+            particle.velocity += normal.scale(self.normal_velocity);
+        }
+    }
+}
+
+/// Mesh emitter builder allows you to construct mesh emitter in declarative manner.
+/// This is typical implementation of Builder pattern.
+pub struct MeshEmitterBuilder {
+    base: BaseEmitterBuilder,
+    mesh: Handle<Node>,
+    mode: MeshEmissionMode,
+    normal_velocity: f32,
+}
+
+impl MeshEmitterBuilder {
+    /// Creates new mesh emitter builder, that emits particles from the surface of the given mesh.
+    pub fn new(base: BaseEmitterBuilder, mesh: Handle<Node>) -> Self {
+        Self {
+            base,
+            mesh,
+            mode: Default::default(),
+            normal_velocity: 0.0,
+        }
+    }
+
+    /// Sets desired emission mode.
+    pub fn with_mode(mut self, mode: MeshEmissionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets desired speed along the normal of the surface at the emission point.
+    pub fn with_normal_velocity(mut self, velocity: f32) -> Self {
+        self.normal_velocity = velocity;
+        self
+    }
+
+    /// Creates new mesh emitter.
+    pub fn build(self) -> Emitter {
+        Emitter::Mesh(MeshEmitter {
+            emitter: self.base.build(),
+            mesh: self.mesh,
+            mode: self.mode,
+            normal_velocity: self.normal_velocity,
+            cache: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Matrix4,
+        scene::{
+            base::BaseBuilder,
+            graph::NodePool,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            particle_system::{
+                emitter::{base::BaseEmitterBuilder, Emit},
+                particle::Particle,
+                ParticleSystemRng,
+            },
+        },
+    };
+
+    use super::{MeshEmissionMode, MeshEmitter};
+
+    #[test]
+    fn test_emit_from_mesh_volume() {
+        let mut nodes = NodePool::new();
+        let mesh = nodes.spawn(
+            MeshBuilder::new(BaseBuilder::new())
+                .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                    SurfaceData::make_cube(Matrix4::identity()),
+                ))
+                .build()])
+                .build_node(),
+        );
+
+        let mut emitter = MeshEmitter::new(BaseEmitterBuilder::new().build(), mesh);
+        emitter.set_mode(MeshEmissionMode::Volume);
+        emitter.update_cache(&nodes, &Matrix4::identity());
+
+        // Tetrahedra formed by the faces of the unit cube and its center fill the whole cube.
+        assert_eq!(emitter.cache.triangles.len(), 12);
+        let volume = emitter.cache.cumulative_weights.last().cloned().unwrap();
+        assert!((volume - 1.0).abs() < 1.0e-5);
+
+        let mut rng = ParticleSystemRng::default();
+        for _ in 0..100 {
+            let mut particle = Particle::default();
+            emitter.emit(&mut particle, &mut rng);
+            assert!(particle.position.iter().all(|c| (-0.5..=0.5).contains(c)));
+        }
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     core::{reflect::prelude::*, visitor::prelude::*},
     scene::particle_system::{
         emitter::{
-            base::BaseEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter,
+            base::BaseEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter, mesh::MeshEmitter,
             sphere::SphereEmitter,
         },
         Particle, ParticleSystemRng,
@@ -18,6 +18,7 @@ use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 pub mod base;
 pub mod cuboid;
 pub mod cylinder;
+pub mod mesh;
 pub mod sphere;
 
 /// Emit trait must be implemented for any particle system emitter.
@@ -35,6 +36,8 @@ pub enum Emitter {
     Sphere(SphereEmitter),
     /// Cylinder emitter.
     Cylinder(CylinderEmitter),
+    /// See MeshEmitter docs.
+    Mesh(MeshEmitter),
 }
 
 uuid_provider!(Emitter = "4cad87ed-6b2c-411d-8c05-86dc26e463b2");
@@ -46,6 +49,7 @@ impl Emitter {
             1 => Ok(Self::Cuboid(Default::default())),
             2 => Ok(Self::Sphere(Default::default())),
             3 => Ok(Self::Cylinder(Default::default())),
+            4 => Ok(Self::Mesh(Default::default())),
             _ => Err(format!("Invalid emitter id {}!", id)),
         }
     }
@@ -56,6 +60,7 @@ impl Emitter {
             Self::Cuboid(_) => 1,
             Self::Sphere(_) => 2,
             Self::Cylinder(_) => 3,
+            Self::Mesh(_) => 4,
         }
     }
 }
@@ -66,6 +71,7 @@ macro_rules! static_dispatch {
             Emitter::Cuboid(v) => v.$func($($args),*),
             Emitter::Sphere(v) => v.$func($($args),*),
             Emitter::Cylinder(v) => v.$func($($args),*),
+            Emitter::Mesh(v) => v.$func($($args),*),
         }
     };
 }
@@ -94,6 +100,7 @@ impl Clone for Emitter {
             Self::Cuboid(box_emitter) => Self::Cuboid(box_emitter.clone()),
             Self::Sphere(sphere_emitter) => Self::Sphere(sphere_emitter.clone()),
             Self::Cylinder(cylinder) => Self::Cylinder(cylinder.clone()),
+            Self::Mesh(mesh) => Self::Mesh(mesh.clone()),
         }
     }
 }
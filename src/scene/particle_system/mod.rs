@@ -5,8 +5,10 @@ use crate::{
     core::{
         algebra::{Point3, Vector2, Vector3},
         color_gradient::ColorGradient,
+        curve::Curve,
         log::Log,
         math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        numeric_range::RangeExt,
         pool::Handle,
         reflect::prelude::*,
         sstorage::ImmutableString,
@@ -20,25 +22,35 @@ use crate::{
     renderer::{self, batch::RenderContext},
     scene::{
         base::{Base, BaseBuilder},
-        graph::Graph,
+        collider::InteractionGroups,
+        graph::{
+            physics::{Intersection, PhysicsWorld, RayCastOptions},
+            Graph, NodePool,
+        },
         mesh::RenderPath,
         node::{Node, NodeTrait, UpdateContext},
         particle_system::{
             draw::Vertex,
             emitter::{Emit, Emitter},
             particle::Particle,
+            sub_emitter::{ParticleEvent, SubEmitter, SubEmitterEvent},
         },
     },
 };
+use fyrox_core::uuid_provider;
 use std::{
+    cell::{Cell, RefCell},
     cmp::Ordering,
+    collections::BinaryHeap,
     fmt::Debug,
     ops::{Deref, DerefMut},
 };
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 pub(crate) mod draw;
 pub mod emitter;
 pub mod particle;
+pub mod sub_emitter;
 
 /// Pseudo-random numbers generator for particle systems.
 #[derive(Debug, Clone, Reflect)]
@@ -109,6 +121,196 @@ impl Visit for ParticleSystemRng {
     }
 }
 
+/// Defines where particles of a particle system are simulated.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum ParticleSimulationMode {
+    /// Particles are simulated on CPU. Every particle is accessible from code, but CPU simulation
+    /// is suitable only for a few thousands of particles.
+    #[default]
+    Cpu,
+    /// Particles are simulated and rendered entirely on GPU, which allows to have hundreds of
+    /// thousands of particles. Particles are not accessible from code, see
+    /// [`GpuParticleSettings`] for more info.
+    Gpu,
+}
+
+uuid_provider!(ParticleSimulationMode = "0f6a2c4e-8b1d-4a7f-9e3c-5d2b7a1f6e48");
+
+/// Settings of particle systems with [`ParticleSimulationMode::Gpu`] simulation mode.
+///
+/// # Simulation
+///
+/// Particles are emitted by emitters on CPU as usual, then their initial state is uploaded to GPU
+/// and the rest of the simulation happens on GPU only. GPU particles are stored in a fixed-size
+/// ring buffer, when it is full, new particles replace the oldest ones.
+///
+/// # Collisions
+///
+/// Particles can collide with anything that was rendered to the depth buffer of a camera. Such
+/// collisions are approximate: particles do not collide with objects that are hidden or outside
+/// the view.
+///
+/// # Limitations
+///
+/// GPU particles are rendered using the `diffuseTexture` and `softBoundarySharpnessFactor`
+/// properties of the material of the particle system, custom shaders are not supported. GPU
+/// particles are used only by the Deferred render path and they require support for floating
+/// point render targets.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct GpuParticleSettings {
+    /// Maximum amount of particles at the same time. It is rounded up to the closest squared
+    /// power of two.
+    #[reflect(min_value = 1.0)]
+    pub capacity: u32,
+    /// Whether particles should be sorted back-to-front before rendering or not. Sorting is
+    /// required for correct alpha blending, but it is quite expensive.
+    pub sort: bool,
+    /// Whether particles should collide with the depth buffer of a camera or not.
+    pub depth_collision: bool,
+    /// Fraction of velocity, that is preserved after a collision.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub restitution: f32,
+    /// Maximum distance behind a surface in the depth buffer, at which particles still collide
+    /// with it. It is used to prevent collisions with the objects, that are in front of
+    /// particles.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub collision_thickness: f32,
+}
+
+uuid_provider!(GpuParticleSettings = "c6d1a8e3-4f2b-4d9a-b7e5-1a3c9f0d2e76");
+
+impl Default for GpuParticleSettings {
+    fn default() -> Self {
+        Self {
+            capacity: 16384,
+            sort: false,
+            depth_collision: false,
+            restitution: 0.5,
+            collision_thickness: 0.5,
+        }
+    }
+}
+
+impl GpuParticleSettings {
+    /// Returns the size of a side of square textures, that are used to store particles on GPU.
+    pub fn texture_size(&self) -> usize {
+        let capacity = self.capacity.max(1) as usize;
+        ((capacity as f64).sqrt().ceil() as usize).next_power_of_two()
+    }
+}
+
+/// Settings of collisions of particles with physical objects.
+///
+/// Collisions are detected by casting a ray along the path of every particle on each update,
+/// which is quite expensive, so collisions should be used only for particle systems with a small
+/// amount of particles. Collisions are supported only by particle systems, that are simulated on
+/// CPU (see [`ParticleSimulationMode`]), see [`GpuParticleSettings`] for collisions of GPU
+/// particles.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct ParticleCollision {
+    /// Whether particles should collide with physical objects or not.
+    pub enabled: bool,
+    /// Fraction of velocity, that is preserved after a collision.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub restitution: f32,
+    /// Fraction of initial lifetime of a particle, that is lost on each collision. `1.0` means
+    /// that particles die on first collision.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub lifetime_loss: f32,
+}
+
+uuid_provider!(ParticleCollision = "8e4b1d6f-3a9c-4c2e-b5f7-0a6d2e9c1b53");
+
+impl Default for ParticleCollision {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            restitution: 0.5,
+            lifetime_loss: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct GpuParticleDeath {
+    time: f64,
+    emitter_index: u32,
+}
+
+impl Eq for GpuParticleDeath {}
+
+impl PartialOrd for GpuParticleDeath {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GpuParticleDeath {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse ordering to make the heap return the earliest death first.
+        other.time.total_cmp(&self.time)
+    }
+}
+
+/// Particles, that were emitted on CPU, but not yet uploaded to GPU.
+#[derive(Debug)]
+pub(crate) struct GpuParticleQueue {
+    pub(crate) spawned: RefCell<Vec<Particle>>,
+    /// Amount of time, that was not yet simulated on GPU.
+    pub(crate) elapsed: Cell<f32>,
+    /// Amount of ticks, that were not yet simulated on GPU.
+    pub(crate) ticks: Cell<u32>,
+    /// Whether the state on GPU must be cleared before simulation or not.
+    pub(crate) reset: Cell<bool>,
+    /// Times of deaths of emitted particles, they're used to track amount of alive particles of
+    /// each emitter.
+    deaths: BinaryHeap<GpuParticleDeath>,
+    time: f64,
+}
+
+impl Default for GpuParticleQueue {
+    fn default() -> Self {
+        Self {
+            spawned: Default::default(),
+            elapsed: Default::default(),
+            ticks: Default::default(),
+            reset: Cell::new(true),
+            deaths: Default::default(),
+            time: 0.0,
+        }
+    }
+}
+
+impl Clone for GpuParticleQueue {
+    fn clone(&self) -> Self {
+        // The state on GPU is never shared between copies.
+        Self::default()
+    }
+}
+
+impl GpuParticleQueue {
+    fn clear(&mut self) {
+        self.spawned.get_mut().clear();
+        self.elapsed.set(0.0);
+        self.ticks.set(0);
+        self.reset.set(true);
+        self.deaths.clear();
+        self.time = 0.0;
+    }
+}
+
 /// Particle system used to create visual effects that consists of many small parts,
 /// this can be smoke, fire, dust, sparks, etc. Particle system optimized to operate
 /// on many small parts, so it is much efficient to use particle system instead of
@@ -121,6 +323,14 @@ impl Visit for ParticleSystemRng {
 /// Particle system can contain multiple particle emitters, each emitter has its own
 /// set of properties and it defines law of change of particle parameters over time.
 ///
+/// # Parameters over lifetime
+///
+/// Emitters define initial parameters of particles, which then could be changed over lifetime
+/// of particles: color is defined by a color gradient, size and speed could be scaled by optional
+/// curves (see [`ParticleSystem::set_size_over_lifetime`] and
+/// [`ParticleSystem::set_speed_over_lifetime`]). The curves and the gradient are evaluated
+/// per-particle in `[0; 1]` range, where `0` is the birth of a particle and `1` is its death.
+///
 /// # Performance
 ///
 /// In general particle system can be considered as heavy visual effect, but total impact
@@ -129,6 +339,9 @@ impl Visit for ParticleSystemRng {
 /// enough, alternatively amount of particles can be defined by some coefficient based on
 /// graphics quality settings.
 ///
+/// Particle systems with huge amounts of particles could be simulated on GPU, see
+/// [`ParticleSimulationMode`] and [`GpuParticleSettings`] for more info.
+///
 /// # Example
 ///
 /// Simple smoke effect can be create like so:
@@ -221,15 +434,42 @@ pub struct ParticleSystem {
     #[reflect(setter = "set_color_over_lifetime_gradient")]
     color_over_lifetime: InheritableVariable<ColorGradient>,
 
+    #[reflect(setter = "set_size_over_lifetime")]
+    size_over_lifetime: InheritableVariable<Option<Curve>>,
+
+    #[reflect(setter = "set_speed_over_lifetime")]
+    speed_over_lifetime: InheritableVariable<Option<Curve>>,
+
     #[reflect(setter = "play")]
     is_playing: InheritableVariable<bool>,
 
+    #[reflect(setter = "set_simulation_mode")]
+    simulation_mode: InheritableVariable<ParticleSimulationMode>,
+
+    #[reflect(setter = "set_gpu_settings")]
+    gpu_settings: InheritableVariable<GpuParticleSettings>,
+
+    #[reflect(setter = "set_collision")]
+    collision: InheritableVariable<ParticleCollision>,
+
+    /// List of sub-emitters of the particle system. See [`SubEmitter`] docs for more info.
+    pub sub_emitters: InheritableVariable<Vec<SubEmitter>>,
+
     #[reflect(hidden)]
     particles: Vec<Particle>,
 
     #[reflect(hidden)]
     free_particles: Vec<u32>,
 
+    #[reflect(hidden)]
+    gpu_queue: GpuParticleQueue,
+
+    #[reflect(hidden)]
+    events: Vec<ParticleEvent>,
+
+    #[reflect(hidden)]
+    ray_cast_results: Vec<Intersection>,
+
     rng: ParticleSystemRng,
 }
 
@@ -246,6 +486,16 @@ impl Visit for ParticleSystem {
         self.particles.visit("Particles", &mut region)?;
         self.free_particles.visit("FreeParticles", &mut region)?;
         let _ = self.rng.visit("Rng", &mut region);
+        let _ = self.simulation_mode.visit("SimulationMode", &mut region);
+        let _ = self.gpu_settings.visit("GpuSettings", &mut region);
+        let _ = self.collision.visit("Collision", &mut region);
+        let _ = self.sub_emitters.visit("SubEmitters", &mut region);
+        let _ = self
+            .size_over_lifetime
+            .visit("SizeOverLifetime", &mut region);
+        let _ = self
+            .speed_over_lifetime
+            .visit("SpeedOverLifetime", &mut region);
 
         // Backward compatibility.
         if region.is_reading() {
@@ -314,6 +564,30 @@ impl ParticleSystem {
             .set_value_and_mark_modified(gradient)
     }
 
+    /// Sets new curve, that will be used to scale size of particles over their lifetime. The curve
+    /// is evaluated in `[0; 1]` range, where `0` is the birth of a particle and `1` is its death.
+    /// `None` means that size of particles is not scaled.
+    pub fn set_size_over_lifetime(&mut self, curve: Option<Curve>) -> Option<Curve> {
+        self.size_over_lifetime.set_value_and_mark_modified(curve)
+    }
+
+    /// Returns current curve, that is used to scale size of particles over their lifetime.
+    pub fn size_over_lifetime(&self) -> Option<&Curve> {
+        self.size_over_lifetime.as_ref()
+    }
+
+    /// Sets new curve, that will be used to scale speed of particles over their lifetime. The curve
+    /// is evaluated in `[0; 1]` range, where `0` is the birth of a particle and `1` is its death.
+    /// `None` means that speed of particles is not scaled.
+    pub fn set_speed_over_lifetime(&mut self, curve: Option<Curve>) -> Option<Curve> {
+        self.speed_over_lifetime.set_value_and_mark_modified(curve)
+    }
+
+    /// Returns current curve, that is used to scale speed of particles over their lifetime.
+    pub fn speed_over_lifetime(&self) -> Option<&Curve> {
+        self.speed_over_lifetime.as_ref()
+    }
+
     /// Plays or pauses the particle system. Paused particle system remains in "frozen" state
     /// until played again again. You can manually reset state of the system by calling [`Self::clear_particles`].
     pub fn play(&mut self, is_playing: bool) -> bool {
@@ -337,10 +611,207 @@ impl ParticleSystem {
         &self.particles
     }
 
+    /// Sets new simulation mode of the particle system. Every particle will be removed.
+    pub fn set_simulation_mode(&mut self, mode: ParticleSimulationMode) -> ParticleSimulationMode {
+        let prev = self.simulation_mode.set_value_and_mark_modified(mode);
+        self.clear_particles();
+        prev
+    }
+
+    /// Returns current simulation mode of the particle system.
+    pub fn simulation_mode(&self) -> ParticleSimulationMode {
+        *self.simulation_mode
+    }
+
+    /// Sets new settings of GPU simulation. Every particle will be removed if the particle system
+    /// is simulated on GPU.
+    pub fn set_gpu_settings(&mut self, settings: GpuParticleSettings) -> GpuParticleSettings {
+        let prev = self.gpu_settings.set_value_and_mark_modified(settings);
+        if *self.simulation_mode == ParticleSimulationMode::Gpu {
+            self.clear_particles();
+        }
+        prev
+    }
+
+    /// Returns current settings of GPU simulation.
+    pub fn gpu_settings(&self) -> &GpuParticleSettings {
+        &self.gpu_settings
+    }
+
+    /// Sets new settings of collisions of particles with physical objects.
+    pub fn set_collision(&mut self, collision: ParticleCollision) -> ParticleCollision {
+        self.collision.set_value_and_mark_modified(collision)
+    }
+
+    /// Returns current settings of collisions of particles with physical objects.
+    pub fn collision(&self) -> &ParticleCollision {
+        &self.collision
+    }
+
+    /// Emits `count` particles at the given position in world space, using emitters of the
+    /// particle system. `velocity` (in world space, per update) is added to the velocity of every
+    /// emitted particle. This method is used by sub-emitters, but it also could be used to spawn
+    /// particles on game events (for example, impacts of bullets).
+    pub fn emit_at(&mut self, position: Vector3<f32>, velocity: Vector3<f32>, count: u32) {
+        let emitter_count = self.emitters.len();
+        if emitter_count == 0 {
+            return;
+        }
+
+        let inv_transform = self.global_transform().try_inverse().unwrap_or_default();
+        let local_position = inv_transform
+            .transform_point(&Point3::from(position))
+            .coords;
+        let local_velocity = inv_transform.transform_vector(&velocity);
+
+        for i in 0..count as usize {
+            let emitter_index = i % emitter_count;
+            let mut particle = Particle {
+                emitter_index: emitter_index as u32,
+                ..Particle::default()
+            };
+            let emitter = &mut self.emitters.get_value_mut_silent()[emitter_index];
+            emitter.alive_particles += 1;
+            emitter.emit(&mut particle, &mut self.rng);
+            particle.position += local_position - emitter.position();
+            particle.velocity += local_velocity;
+            self.add_particle(particle);
+        }
+    }
+
+    fn add_particle(&mut self, particle: Particle) {
+        if *self.simulation_mode == ParticleSimulationMode::Gpu {
+            let queue = &mut self.gpu_queue;
+            queue.deaths.push(GpuParticleDeath {
+                time: queue.time + particle.initial_lifetime as f64,
+                emitter_index: particle.emitter_index,
+            });
+            queue.spawned.get_mut().push(particle);
+        } else if let Some(free_index) = self.free_particles.pop() {
+            self.particles[free_index as usize] = particle;
+        } else {
+            self.particles.push(particle);
+        }
+    }
+
+    fn has_sub_emitters(&self, event: SubEmitterEvent) -> bool {
+        self.sub_emitters.iter().any(|s| s.event == event)
+    }
+
+    fn collide(&mut self, physics: &PhysicsWorld) {
+        if !self.collision.enabled || *self.simulation_mode == ParticleSimulationMode::Gpu {
+            return;
+        }
+
+        let transform = self.global_transform();
+        let Some(inv_transform) = transform.try_inverse() else {
+            return;
+        };
+        let record_events = self.has_sub_emitters(SubEmitterEvent::Collision);
+        let collision = &*self.collision;
+
+        for particle in self.particles.iter_mut().filter(|p| p.alive) {
+            // Position is already advanced by the velocity in this update.
+            let begin =
+                transform.transform_point(&Point3::from(particle.position - particle.velocity));
+            let end = transform.transform_point(&Point3::from(particle.position));
+            let path = end - begin;
+            let Some(direction) = path.try_normalize(f32::EPSILON) else {
+                continue;
+            };
+
+            self.ray_cast_results.clear();
+            physics.cast_ray(
+                RayCastOptions {
+                    ray_origin: begin,
+                    ray_direction: direction,
+                    max_len: path.norm(),
+                    groups: InteractionGroups::default(),
+                    sort_results: true,
+                },
+                &mut self.ray_cast_results,
+            );
+
+            let Some(intersection) = self.ray_cast_results.first() else {
+                continue;
+            };
+
+            let normal = intersection.normal;
+            let world_velocity = transform.transform_vector(&particle.velocity);
+            let reflected = (world_velocity - normal.scale(2.0 * world_velocity.dot(&normal)))
+                .scale(collision.restitution);
+            particle.velocity = inv_transform.transform_vector(&reflected);
+            // Keep the particle slightly above the surface to prevent tunneling.
+            particle.position = inv_transform
+                .transform_point(&(intersection.position + normal.scale(0.001)))
+                .coords;
+            particle.lifetime += particle.initial_lifetime * collision.lifetime_loss;
+
+            if record_events {
+                self.events.push(ParticleEvent {
+                    event: SubEmitterEvent::Collision,
+                    position: intersection.position.coords,
+                    velocity: reflected,
+                });
+            }
+        }
+    }
+
+    fn update_mesh_emitters(&mut self, nodes: &NodePool) {
+        let transform = self.global_transform();
+        for emitter in self.emitters.get_value_mut_silent().iter_mut() {
+            if let Emitter::Mesh(mesh_emitter) = emitter {
+                mesh_emitter.update_cache(nodes, &transform);
+            }
+        }
+    }
+
+    fn trigger_sub_emitters(&mut self, nodes: &mut NodePool) {
+        if self.events.is_empty() {
+            return;
+        }
+
+        for sub_emitter in self.sub_emitters.iter() {
+            let Some(target) = nodes
+                .try_borrow_mut(sub_emitter.particle_system)
+                .and_then(|node| node.cast_mut::<ParticleSystem>())
+            else {
+                continue;
+            };
+
+            for event in self.events.iter().filter(|e| e.event == sub_emitter.event) {
+                if sub_emitter.probability < 1.0
+                    && (0.0f32..1.0).random(&mut self.rng) >= sub_emitter.probability
+                {
+                    continue;
+                }
+
+                target.emit_at(
+                    event.position,
+                    event.velocity.scale(sub_emitter.inherit_velocity),
+                    sub_emitter.count,
+                );
+            }
+        }
+
+        self.events.clear();
+    }
+
+    pub(crate) fn gpu_queue(&self) -> &GpuParticleQueue {
+        &self.gpu_queue
+    }
+
+    /// Returns current color gradient, that is used to calculate color of particles over their
+    /// lifetime.
+    pub fn color_over_lifetime_gradient(&self) -> &ColorGradient {
+        &self.color_over_lifetime
+    }
+
     /// Removes all generated particles.
     pub fn clear_particles(&mut self) {
         self.particles.clear();
         self.free_particles.clear();
+        self.gpu_queue.clear();
         for emitter in self.emitters.get_value_mut_silent().iter_mut() {
             emitter.alive_particles = 0;
             emitter.spawned_particles = 0;
@@ -362,11 +833,78 @@ impl ParticleSystem {
         &self.material
     }
 
+    fn tick_gpu(&mut self, dt: f32) {
+        let transform = self.global_transform();
+        let record_births = self.has_sub_emitters(SubEmitterEvent::Birth);
+        let queue = &mut self.gpu_queue;
+        queue.time += dt as f64;
+
+        while let Some(death) = queue.deaths.peek() {
+            if death.time > queue.time {
+                break;
+            }
+            if let Some(emitter) = self
+                .emitters
+                .get_value_mut_silent()
+                .get_mut(death.emitter_index as usize)
+            {
+                emitter.alive_particles = emitter.alive_particles.saturating_sub(1);
+            }
+            queue.deaths.pop();
+        }
+
+        let spawned = queue.spawned.get_mut();
+        for (i, emitter) in self.emitters.get_value_mut_silent().iter_mut().enumerate() {
+            emitter.tick(dt);
+
+            for _ in 0..emitter.particles_to_spawn {
+                let mut particle = Particle {
+                    emitter_index: i as u32,
+                    ..Particle::default()
+                };
+                emitter.alive_particles += 1;
+                emitter.emit(&mut particle, &mut self.rng);
+                queue.deaths.push(GpuParticleDeath {
+                    time: queue.time + particle.initial_lifetime as f64,
+                    emitter_index: i as u32,
+                });
+                if record_births {
+                    self.events.push(ParticleEvent::new(
+                        SubEmitterEvent::Birth,
+                        &particle,
+                        &transform,
+                    ));
+                }
+                spawned.push(particle);
+            }
+        }
+
+        // Particles that do not fit into the ring buffer on GPU will be overwritten anyway.
+        let capacity = self.gpu_settings.texture_size().pow(2);
+        if spawned.len() > capacity {
+            spawned.drain(..spawned.len() - capacity);
+        }
+
+        queue.elapsed.set(queue.elapsed.get() + dt);
+        queue.ticks.set(queue.ticks.get() + 1);
+    }
+
     fn tick(&mut self, dt: f32) {
+        self.events.clear();
+
+        if *self.simulation_mode == ParticleSimulationMode::Gpu {
+            self.tick_gpu(dt);
+            return;
+        }
+
         for emitter in self.emitters.get_value_mut_silent().iter_mut() {
             emitter.tick(dt);
         }
 
+        let transform = self.global_transform();
+        let record_births = self.has_sub_emitters(SubEmitterEvent::Birth);
+        let record_deaths = self.has_sub_emitters(SubEmitterEvent::Death);
+
         for (i, emitter) in self.emitters.get_value_mut_silent().iter_mut().enumerate() {
             for _ in 0..emitter.particles_to_spawn {
                 let mut particle = Particle {
@@ -375,6 +913,13 @@ impl ParticleSystem {
                 };
                 emitter.alive_particles += 1;
                 emitter.emit(&mut particle, &mut self.rng);
+                if record_births {
+                    self.events.push(ParticleEvent::new(
+                        SubEmitterEvent::Birth,
+                        &particle,
+                        &transform,
+                    ));
+                }
                 if let Some(free_index) = self.free_particles.pop() {
                     self.particles[free_index as usize] = particle;
                 } else {
@@ -399,16 +944,26 @@ impl ParticleSystem {
                     }
                     particle.alive = false;
                     particle.lifetime = particle.initial_lifetime;
+                    if record_deaths {
+                        self.events.push(ParticleEvent::new(
+                            SubEmitterEvent::Death,
+                            particle,
+                            &transform,
+                        ));
+                    }
                 } else {
+                    let k = particle.lifetime / particle.initial_lifetime;
+
                     particle.velocity += acceleration_offset;
-                    particle.position += particle.velocity;
+                    particle.position += particle
+                        .velocity
+                        .scale(evaluate_over_lifetime(&self.speed_over_lifetime, k));
                     particle.size += particle.size_modifier * dt;
                     if particle.size < 0.0 {
                         particle.size = 0.0;
                     }
+                    particle.size_scale = evaluate_over_lifetime(&self.size_over_lifetime, k);
                     particle.rotation += particle.rotation_speed * dt;
-
-                    let k = particle.lifetime / particle.initial_lifetime;
                     particle.color = self.color_over_lifetime.get_color(k);
                 }
             }
@@ -430,6 +985,11 @@ impl ParticleSystem {
     }
 }
 
+/// Evaluates an optional curve over lifetime of a particle, absent curve does not scale anything.
+fn evaluate_over_lifetime(curve: &Option<Curve>, k: f32) -> f32 {
+    curve.as_ref().map_or(1.0, |curve| curve.value_at(k))
+}
+
 impl Default for ParticleSystem {
     fn default() -> Self {
         ParticleSystemBuilder::new(BaseBuilder::new()).build_particle_system()
@@ -456,7 +1016,10 @@ impl NodeTrait for ParticleSystem {
         let dt = context.dt;
 
         if *self.is_playing {
+            self.update_mesh_emitters(context.nodes);
             self.tick(dt);
+            self.collide(context.physics);
+            self.trigger_sub_emitters(context.nodes);
         }
     }
 
@@ -472,6 +1035,11 @@ impl NodeTrait for ParticleSystem {
             return;
         }
 
+        // GPU particles are rendered by the renderer directly.
+        if *self.simulation_mode == ParticleSimulationMode::Gpu {
+            return;
+        }
+
         let mut sorted_particles = Vec::new();
         for (i, particle) in self.particles.iter().enumerate() {
             if particle.alive {
@@ -514,28 +1082,28 @@ impl NodeTrait for ParticleSystem {
                 Vertex {
                     position,
                     tex_coord: Vector2::default(),
-                    size: particle.size,
+                    size: particle.size * particle.size_scale,
                     rotation: particle.rotation,
                     color: linear_color,
                 },
                 Vertex {
                     position,
                     tex_coord: Vector2::new(1.0, 0.0),
-                    size: particle.size,
+                    size: particle.size * particle.size_scale,
                     rotation: particle.rotation,
                     color: linear_color,
                 },
                 Vertex {
                     position,
                     tex_coord: Vector2::new(1.0, 1.0),
-                    size: particle.size,
+                    size: particle.size * particle.size_scale,
                     rotation: particle.rotation,
                     color: linear_color,
                 },
                 Vertex {
                     position,
                     tex_coord: Vector2::new(0.0, 1.0),
-                    size: particle.size,
+                    size: particle.size * particle.size_scale,
                     rotation: particle.rotation,
                     color: linear_color,
                 },
@@ -573,8 +1141,14 @@ pub struct ParticleSystemBuilder {
     acceleration: Vector3<f32>,
     particles: Vec<Particle>,
     color_over_lifetime: ColorGradient,
+    size_over_lifetime: Option<Curve>,
+    speed_over_lifetime: Option<Curve>,
     is_playing: bool,
     rng: ParticleSystemRng,
+    simulation_mode: ParticleSimulationMode,
+    gpu_settings: GpuParticleSettings,
+    collision: ParticleCollision,
+    sub_emitters: Vec<SubEmitter>,
 }
 
 impl ParticleSystemBuilder {
@@ -590,8 +1164,14 @@ impl ParticleSystemBuilder {
             particles: Default::default(),
             acceleration: Vector3::new(0.0, -9.81, 0.0),
             color_over_lifetime: Default::default(),
+            size_over_lifetime: None,
+            speed_over_lifetime: None,
             is_playing: true,
             rng: ParticleSystemRng::default(),
+            simulation_mode: Default::default(),
+            gpu_settings: Default::default(),
+            collision: Default::default(),
+            sub_emitters: Default::default(),
         }
     }
 
@@ -619,6 +1199,18 @@ impl ParticleSystemBuilder {
         self
     }
 
+    /// Sets desired curve, that will be used to scale size of particles over their lifetime.
+    pub fn with_size_over_lifetime(mut self, curve: Curve) -> Self {
+        self.size_over_lifetime = Some(curve);
+        self
+    }
+
+    /// Sets desired curve, that will be used to scale speed of particles over their lifetime.
+    pub fn with_speed_over_lifetime(mut self, curve: Curve) -> Self {
+        self.speed_over_lifetime = Some(curve);
+        self
+    }
+
     /// Sets an initial set of particles that not belongs to any emitter. This method
     /// could be useful if you need a custom position/velocity/etc. of each particle.
     pub fn with_particles(mut self, particles: Vec<Particle>) -> Self {
@@ -638,6 +1230,30 @@ impl ParticleSystemBuilder {
         self
     }
 
+    /// Sets desired simulation mode.
+    pub fn with_simulation_mode(mut self, mode: ParticleSimulationMode) -> Self {
+        self.simulation_mode = mode;
+        self
+    }
+
+    /// Sets desired settings of GPU simulation.
+    pub fn with_gpu_settings(mut self, settings: GpuParticleSettings) -> Self {
+        self.gpu_settings = settings;
+        self
+    }
+
+    /// Sets desired settings of collisions of particles with physical objects.
+    pub fn with_collision(mut self, collision: ParticleCollision) -> Self {
+        self.collision = collision;
+        self
+    }
+
+    /// Sets desired sub-emitters.
+    pub fn with_sub_emitters(mut self, sub_emitters: Vec<SubEmitter>) -> Self {
+        self.sub_emitters = sub_emitters;
+        self
+    }
+
     fn build_particle_system(self) -> ParticleSystem {
         ParticleSystem {
             base: self.base_builder.build_base(),
@@ -647,8 +1263,17 @@ impl ParticleSystemBuilder {
             material: self.material.into(),
             acceleration: self.acceleration.into(),
             color_over_lifetime: self.color_over_lifetime.into(),
+            size_over_lifetime: self.size_over_lifetime.into(),
+            speed_over_lifetime: self.speed_over_lifetime.into(),
             is_playing: self.is_playing.into(),
             rng: self.rng,
+            simulation_mode: self.simulation_mode.into(),
+            gpu_settings: self.gpu_settings.into(),
+            collision: self.collision.into(),
+            sub_emitters: self.sub_emitters.into(),
+            gpu_queue: Default::default(),
+            events: Default::default(),
+            ray_cast_results: Default::default(),
         }
     }
 
@@ -662,3 +1287,180 @@ impl ParticleSystemBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            curve::{Curve, CurveKey, CurveKeyKind},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            node::Node,
+            particle_system::{
+                emitter::{
+                    base::BaseEmitterBuilder, mesh::MeshEmitterBuilder,
+                    sphere::SphereEmitterBuilder,
+                },
+                particle::Particle,
+                sub_emitter::{SubEmitter, SubEmitterEvent},
+                ParticleSimulationMode, ParticleSystem, ParticleSystemBuilder,
+            },
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_gpu_particle_queue() {
+        let mut particle_system = ParticleSystemBuilder::new(BaseBuilder::new())
+            .with_simulation_mode(ParticleSimulationMode::Gpu)
+            .with_emitters(vec![SphereEmitterBuilder::new(
+                BaseEmitterBuilder::new()
+                    .with_spawn_rate(8)
+                    .with_lifetime_range(0.5..0.6),
+            )
+            .build()])
+            .build_particle_system();
+
+        particle_system.tick(1.0);
+        assert_eq!(particle_system.gpu_queue().spawned.borrow().len(), 8);
+        assert_eq!(particle_system.emitters[0].alive_particles, 8);
+        assert!(particle_system.particles().is_empty());
+
+        // Particles of the first tick are dead at this moment.
+        particle_system.tick(1.0);
+        assert_eq!(particle_system.gpu_queue().spawned.borrow().len(), 16);
+        assert_eq!(particle_system.emitters[0].alive_particles, 8);
+        assert_eq!(particle_system.gpu_queue().ticks.get(), 2);
+
+        particle_system.clear_particles();
+        assert!(particle_system.gpu_queue().spawned.borrow().is_empty());
+        assert!(particle_system.gpu_queue().reset.get());
+    }
+
+    #[test]
+    fn test_sub_emitters() {
+        let mut graph = Graph::new();
+
+        let target = ParticleSystemBuilder::new(BaseBuilder::new())
+            .with_emitters(vec![SphereEmitterBuilder::new(
+                BaseEmitterBuilder::new()
+                    .with_spawn_rate(0)
+                    .with_lifetime_range(5.0..6.0),
+            )
+            .build()])
+            .build(&mut graph);
+
+        let source = ParticleSystemBuilder::new(BaseBuilder::new())
+            .with_emitters(vec![SphereEmitterBuilder::new(
+                BaseEmitterBuilder::new()
+                    .with_spawn_rate(8)
+                    .with_lifetime_range(0.5..0.6),
+            )
+            .build()])
+            .with_sub_emitters(vec![SubEmitter {
+                event: SubEmitterEvent::Death,
+                particle_system: target,
+                count: 2,
+                ..Default::default()
+            }])
+            .build(&mut graph);
+
+        // Every particle of the source dies in the same update, because its lifetime is less
+        // than the time step.
+        graph.update(Vector2::new(100.0, 100.0), 1.0, Default::default());
+
+        let alive_count = |handle: Handle<Node>| {
+            graph[handle]
+                .cast::<ParticleSystem>()
+                .unwrap()
+                .particles()
+                .iter()
+                .filter(|p| p.alive)
+                .count()
+        };
+        assert_eq!(alive_count(source), 0);
+        assert_eq!(alive_count(target), 16);
+    }
+
+    #[test]
+    fn test_curves_over_lifetime() {
+        let mut particle_system = ParticleSystemBuilder::new(BaseBuilder::new())
+            .with_acceleration(Vector3::default())
+            .with_particles(vec![Particle::default()
+                .with_velocity(Vector3::new(1.0, 0.0, 0.0))
+                .with_size(2.0)])
+            .with_size_over_lifetime(Curve::from(vec![
+                CurveKey::new(0.0, 1.0, CurveKeyKind::Linear),
+                CurveKey::new(1.0, 3.0, CurveKeyKind::Linear),
+            ]))
+            .with_speed_over_lifetime(Curve::from(vec![CurveKey::new(
+                0.0,
+                0.0,
+                CurveKeyKind::Constant,
+            )]))
+            .build_particle_system();
+
+        // Default lifetime of a particle is 2 seconds, so the curves are evaluated at 0.25.
+        particle_system.tick(0.5);
+
+        let particle = &particle_system.particles()[0];
+        assert_eq!(particle.position, Vector3::default());
+        assert_eq!(particle.size_scale, 1.5);
+    }
+
+    #[test]
+    fn test_mesh_emitter() {
+        let mut graph = Graph::new();
+
+        let mesh = MeshBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, 5.0))
+                    .build(),
+            ),
+        )
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+            SurfaceData::make_unit_xy_quad(),
+        ))
+        .build()])
+        .build(&mut graph);
+
+        let particle_system = ParticleSystemBuilder::new(BaseBuilder::new())
+            .with_acceleration(Vector3::default())
+            .with_emitters(vec![MeshEmitterBuilder::new(
+                BaseEmitterBuilder::new()
+                    .with_spawn_rate(8)
+                    .with_lifetime_range(5.0..5.0)
+                    .with_x_velocity_range(0.0..0.0)
+                    .with_y_velocity_range(0.0..0.0)
+                    .with_z_velocity_range(0.0..0.0),
+                mesh,
+            )
+            .with_normal_velocity(0.5)
+            .build()])
+            .build(&mut graph);
+
+        graph.update(Vector2::new(100.0, 100.0), 1.0, Default::default());
+
+        let particles = graph[particle_system]
+            .cast::<ParticleSystem>()
+            .unwrap()
+            .particles();
+        assert_eq!(particles.len(), 8);
+        for particle in particles {
+            // Particles are emitted on the quad and then moved along its normal.
+            assert!((0.0..=1.0).contains(&particle.position.x));
+            assert!((0.0..=1.0).contains(&particle.position.y));
+            assert!((particle.position.z - 5.5).abs() < 1.0e-5);
+            assert_eq!(particle.velocity, Vector3::new(0.0, 0.0, 0.5));
+        }
+    }
+}
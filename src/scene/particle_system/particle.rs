@@ -33,6 +33,9 @@ pub struct Particle {
     pub(super) lifetime: f32,
     #[visit(skip)]
     pub(super) sqr_distance_to_camera: Cell<f32>,
+    /// Scale of size, evaluated from size over lifetime curve of particle system.
+    #[visit(skip)]
+    pub(super) size_scale: f32,
 }
 
 impl Default for Particle {
@@ -50,6 +53,7 @@ impl Default for Particle {
             emitter_index: 0,
             color: Color::WHITE,
             sqr_distance_to_camera: Cell::new(0.0),
+            size_scale: 1.0,
         }
     }
 }
@@ -0,0 +1,106 @@
+//! Sub-emitters allow to spawn particles of other particle systems when some event happens with
+//! particles of a particle system. See [`SubEmitter`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{node::Node, particle_system::particle::Particle},
+};
+use fyrox_core::uuid_provider;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// An event of a particle, that triggers a sub-emitter.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum SubEmitterEvent {
+    /// A particle was just emitted.
+    #[default]
+    Birth,
+    /// A particle has collided with a physical object. Collisions must be enabled in the
+    /// particle system, see [`super::ParticleCollision`] docs.
+    Collision,
+    /// Lifetime of a particle has ended.
+    Death,
+}
+
+uuid_provider!(SubEmitterEvent = "5b9e2f1a-7c3d-4e8b-a6f0-2d4c8e1b3a97");
+
+/// Sub-emitter emits particles of other particle system (target) when some event happens with a
+/// particle of the particle system, that owns the sub-emitter. Typical examples are sparks on
+/// impact, smoke trails of embers, fireworks, etc.
+///
+/// Particles are emitted by the emitters of the target particle system, their positions are
+/// offset by the position of the particle, that triggered the event. It is a good idea to
+/// disable the emitters of target particle system (set their spawn rate to zero), so target
+/// particle system will emit particles only when sub-emitters are triggered.
+///
+/// # Limitations
+///
+/// Particle systems, that are simulated on GPU, are able to trigger only [`SubEmitterEvent::Birth`]
+/// events.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct SubEmitter {
+    /// An event, that triggers the sub-emitter.
+    pub event: SubEmitterEvent,
+    /// A handle of a particle system, that will emit particles.
+    pub particle_system: Handle<Node>,
+    /// Amount of particles, that will be emitted per event.
+    pub count: u32,
+    /// Probability of the sub-emitter being triggered by an event. Must be in `[0; 1]` range.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub probability: f32,
+    /// Fraction of the velocity of the particle, that will be added to the velocity of emitted
+    /// particles.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub inherit_velocity: f32,
+}
+
+uuid_provider!(SubEmitter = "d3a7c1e9-2b4f-4f6a-8e5d-9c1b7a3f0e24");
+
+impl Default for SubEmitter {
+    fn default() -> Self {
+        Self {
+            event: Default::default(),
+            particle_system: Default::default(),
+            count: 1,
+            probability: 1.0,
+            inherit_velocity: 0.0,
+        }
+    }
+}
+
+/// An event, that happened with a particle. Position and velocity are in world space.
+#[derive(Clone, Debug)]
+pub(crate) struct ParticleEvent {
+    pub event: SubEmitterEvent,
+    pub position: Vector3<f32>,
+    pub velocity: Vector3<f32>,
+}
+
+impl ParticleEvent {
+    pub fn new(event: SubEmitterEvent, particle: &Particle, transform: &Matrix4<f32>) -> Self {
+        Self {
+            event,
+            position: transform
+                .transform_point(&Point3::from(particle.position))
+                .coords,
+            velocity: transform.transform_vector(&particle.velocity),
+        }
+    }
+}
@@ -20,6 +20,7 @@ use crate::{
     },
 };
 use fyrox_core::uuid_provider;
+use fyrox_graph::SceneGraph;
 use std::{
     any::{type_name, Any, TypeId},
     ops::{Deref, DerefMut},
@@ -60,6 +61,9 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                multiline: false,
+                options: &[],
+                extensions: &[],
                 doc: "",
             },
             FieldInfo {
@@ -76,6 +80,9 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                multiline: false,
+                options: &[],
+                extensions: &[],
                 doc: "",
             },
             FieldInfo {
@@ -92,6 +99,9 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                multiline: false,
+                options: &[],
+                extensions: &[],
                 doc: "",
             },
         ])
@@ -174,16 +184,38 @@ impl Limb {
     }
 }
 
-#[derive(Clone, Reflect, Visit, Debug, Default)]
+#[derive(Clone, Reflect, Visit, Debug)]
 pub struct Ragdoll {
     base: Base,
     character_rigid_body: InheritableVariable<Handle<Node>>,
     is_active: InheritableVariable<bool>,
     root_limb: InheritableVariable<Limb>,
+    #[visit(optional)]
+    #[reflect(
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05,
+        description = "Defines how much the physical pose of the ragdoll affects the bones. 0.0 - \
+        the bones are fully animated, 1.0 - the bones are fully driven by physics."
+    )]
+    blend_weight: InheritableVariable<f32>,
     #[reflect(hidden)]
     prev_enabled: bool,
 }
 
+impl Default for Ragdoll {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            character_rigid_body: Default::default(),
+            is_active: Default::default(),
+            root_limb: Default::default(),
+            blend_weight: 1.0.into(),
+            prev_enabled: false,
+        }
+    }
+}
+
 impl Deref for Ragdoll {
     type Target = Base;
 
@@ -253,37 +285,6 @@ impl NodeTrait for Ragdoll {
                     if limb_body.body_type() != RigidBodyType::Dynamic {
                         limb_body.set_body_type(RigidBodyType::Dynamic);
                     }
-                    let body_transform = limb_body.global_transform();
-
-                    // Sync transform of the bone with respective body.
-                    let bone_parent = ctx.nodes[limb.bone].parent();
-                    let transform: Matrix4<f32> = ctx.nodes[bone_parent]
-                        .global_transform()
-                        .try_inverse()
-                        .unwrap_or_else(Matrix4::identity)
-                        * body_transform;
-
-                    ctx.nodes[limb.bone]
-                        .local_transform_mut()
-                        .set_position(Vector3::new(transform[12], transform[13], transform[14]))
-                        .set_pre_rotation(UnitQuaternion::identity())
-                        .set_post_rotation(UnitQuaternion::identity())
-                        .set_rotation(UnitQuaternion::from_matrix_eps(
-                            &transform.basis(),
-                            f32::EPSILON,
-                            16,
-                            Default::default(),
-                        ));
-
-                    // Calculate transform of the descendants explicitly, so the next bones in hierarchy will have new transform
-                    // that can be used to calculate relative transform.
-                    Graph::update_hierarchical_data_recursively(
-                        ctx.nodes,
-                        ctx.sound_context,
-                        ctx.physics,
-                        ctx.physics2d,
-                        limb.bone,
-                    );
                 } else {
                     limb_body.set_body_type(RigidBodyType::KinematicPositionBased);
                     limb_body.set_lin_vel(Default::default());
@@ -354,6 +355,67 @@ impl Ragdoll {
     pub fn set_root_limb(&mut self, root_limb: Limb) {
         self.root_limb.set_value_and_mark_modified(root_limb);
     }
+
+    /// Sets how much the physical pose of the ragdoll affects the bones when the ragdoll is active. 0.0 means
+    /// that the bones are fully animated, 1.0 - the bones are fully driven by physics. Values in between could
+    /// be used to smoothly transition between animation and ragdoll (and back). The value is clamped to
+    /// `[0.0; 1.0]` range.
+    pub fn set_blend_weight(&mut self, weight: f32) {
+        self.blend_weight
+            .set_value_and_mark_modified(weight.clamp(0.0, 1.0));
+    }
+
+    /// Returns current blend weight between animation and physics.
+    pub fn blend_weight(&self) -> f32 {
+        *self.blend_weight
+    }
+
+    /// Writes transforms of the physical bones to the respective bones of the given limb (and its descendants),
+    /// blending them with the current (animated) transforms of the bones using the given weight.
+    pub(crate) fn apply_physical_pose(limb: &Limb, weight: f32, graph: &mut Graph) {
+        if let (Some(bone), Some(limb_body)) =
+            (graph.try_get(limb.bone), graph.try_get(limb.physical_bone))
+        {
+            // Calculate transform of the body relative to the parent of the bone.
+            let transform: Matrix4<f32> = graph
+                .try_get(bone.parent())
+                .and_then(|parent| parent.global_transform().try_inverse())
+                .unwrap_or_else(Matrix4::identity)
+                * limb_body.global_transform();
+
+            let physical_position = Vector3::new(transform[12], transform[13], transform[14]);
+            let physical_rotation = UnitQuaternion::from_matrix_eps(
+                &transform.basis(),
+                f32::EPSILON,
+                16,
+                Default::default(),
+            );
+
+            let local_transform = bone.local_transform();
+            let pre_rotation = **local_transform.pre_rotation();
+            let post_rotation = **local_transform.post_rotation();
+            let animated_position = **local_transform.position();
+            let animated_rotation = **local_transform.rotation();
+
+            // Local rotation of a node is composed as `pre * rotation * post`, compensate pre- and post-
+            // rotations so the bone will match the body exactly.
+            let physical_rotation =
+                pre_rotation.inverse() * physical_rotation * post_rotation.inverse();
+
+            graph[limb.bone]
+                .local_transform_mut()
+                .set_position(animated_position.lerp(&physical_position, weight))
+                .set_rotation(animated_rotation.nlerp(&physical_rotation, weight));
+
+            // Calculate transform of the descendants explicitly, so the next bones in hierarchy will have new
+            // transform that can be used to calculate relative transform.
+            graph.update_hierarchical_data_for_descendants(limb.bone);
+        }
+
+        for child in limb.children.iter() {
+            Self::apply_physical_pose(child, weight, graph);
+        }
+    }
 }
 
 pub struct RagdollBuilder {
@@ -361,6 +423,7 @@ pub struct RagdollBuilder {
     character_rigid_body: Handle<Node>,
     is_active: bool,
     root_limb: Limb,
+    blend_weight: f32,
 }
 
 impl RagdollBuilder {
@@ -370,6 +433,7 @@ impl RagdollBuilder {
             character_rigid_body: Default::default(),
             is_active: true,
             root_limb: Default::default(),
+            blend_weight: 1.0,
         }
     }
 
@@ -388,12 +452,18 @@ impl RagdollBuilder {
         self
     }
 
+    pub fn with_blend_weight(mut self, weight: f32) -> Self {
+        self.blend_weight = weight;
+        self
+    }
+
     pub fn build_ragdoll(self) -> Ragdoll {
         Ragdoll {
             base: self.base_builder.build_base(),
             character_rigid_body: self.character_rigid_body.into(),
             is_active: self.is_active.into(),
             root_limb: self.root_limb.into(),
+            blend_weight: self.blend_weight.clamp(0.0, 1.0).into(),
             prev_enabled: self.is_active,
         }
     }
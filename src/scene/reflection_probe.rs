@@ -0,0 +1,490 @@
+//! Reflection probe is a source of environment reflections for a region of a scene.
+//!
+//! For more info see [`ReflectionProbe`]
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    resource::texture::TextureResource,
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        node::{Node, NodeTrait},
+    },
+};
+use fyrox_core::uuid_provider;
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Shape of the influence volume of a reflection probe. The volume is defined in local coordinates
+/// of the probe.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum ReflectionProbeShape {
+    /// Box volume with the given half extents.
+    Box {
+        /// Half extents of the box along each axis.
+        #[reflect(min_value = 0.0, step = 0.1)]
+        half_extents: Vector3<f32>,
+    },
+    /// Sphere volume with the given radius.
+    Sphere {
+        /// Radius of the sphere.
+        #[reflect(min_value = 0.0, step = 0.1)]
+        radius: f32,
+    },
+}
+
+uuid_provider!(ReflectionProbeShape = "6b7d2f5c-7f38-4a5e-9b3a-0c2f8b3e8d41");
+
+impl Default for ReflectionProbeShape {
+    fn default() -> Self {
+        Self::Box {
+            half_extents: Vector3::new(5.0, 5.0, 5.0),
+        }
+    }
+}
+
+impl ReflectionProbeShape {
+    /// Returns signed distance from the given point (in local coordinates of a probe) to the
+    /// boundary of the shape. The distance is positive for points inside the shape.
+    pub fn distance_to_boundary(&self, point: Vector3<f32>) -> f32 {
+        match self {
+            Self::Box { half_extents } => (half_extents.x - point.x.abs())
+                .min(half_extents.y - point.y.abs())
+                .min(half_extents.z - point.z.abs()),
+            Self::Sphere { radius } => radius - point.norm(),
+        }
+    }
+
+    /// Returns local-space bounding box of the shape.
+    pub fn bounding_box(&self) -> AxisAlignedBoundingBox {
+        let half_extents = match self {
+            Self::Box { half_extents } => *half_extents,
+            Self::Sphere { radius } => Vector3::repeat(*radius),
+        };
+        AxisAlignedBoundingBox::from_min_max(-half_extents, half_extents)
+    }
+}
+
+/// Reflection probe is a source of environment reflections for a region of a scene. It stores a
+/// cube map with the surroundings of the probe, rendered (baked) from the position of the probe.
+/// Every opaque surface inside the influence volume of the probe reflects the cube map.
+///
+/// # Baking
+///
+/// The cube map is not rendered at runtime, it must be baked using
+/// [`crate::renderer::Renderer::render_cube_map`] and then assigned to the probe using
+/// [`ReflectionProbe::set_environment`]. The editor has a special button to bake every probe of a
+/// scene and save the cube maps to disk.
+///
+/// # Parallax correction
+///
+/// A cube map represents the surroundings of a single point, so reflections on surfaces that are
+/// far from the probe will be noticeably misplaced. Parallax correction fixes this by intersecting
+/// a reflection ray with the influence volume of the probe, the cube map is then sampled in the
+/// direction from the probe to the intersection point. It works best when the shape of the volume
+/// matches the surroundings (for example, a box that matches the walls of a room).
+///
+/// # Blending
+///
+/// Reflections of overlapping probes are blended using weights, that fade from `1.0` to `0.0` over
+/// [`ReflectionProbe::blend_distance`] near the boundary of each volume. This prevents seams when
+/// moving from one probe to another.
+///
+/// # Limitations
+///
+/// Reflection probes are used only by the Deferred render path.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector3, pool::Handle},
+///     scene::{
+///         base::BaseBuilder,
+///         graph::Graph,
+///         node::Node,
+///         reflection_probe::{ReflectionProbeBuilder, ReflectionProbeShape},
+///     },
+/// };
+///
+/// fn create_room_probe(graph: &mut Graph) -> Handle<Node> {
+///     ReflectionProbeBuilder::new(BaseBuilder::new())
+///         .with_shape(ReflectionProbeShape::Box {
+///             half_extents: Vector3::new(4.0, 2.0, 3.0),
+///         })
+///         .with_blend_distance(0.5)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Debug, Visit, Clone, Reflect)]
+pub struct ReflectionProbe {
+    base: Base,
+
+    #[reflect(
+        setter = "set_shape",
+        description = "Shape of the influence volume of the probe in local coordinates."
+    )]
+    shape: InheritableVariable<ReflectionProbeShape>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_blend_distance",
+        description = "Distance from the boundary of the volume over which reflections fade out."
+    )]
+    blend_distance: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_intensity",
+        description = "Multiplier of the brightness of reflections."
+    )]
+    intensity: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_parallax_correction",
+        description = "Whether to correct reflections using the shape of the volume or not."
+    )]
+    parallax_correction: InheritableVariable<bool>,
+
+    #[reflect(
+        min_value = 1.0,
+        max_value = 2048.0,
+        setter = "set_resolution",
+        description = "Size (in pixels) of a face of the cube map, that is used when baking the probe."
+    )]
+    resolution: InheritableVariable<u32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_z_near",
+        description = "Near clipping plane distance, that is used when baking the probe."
+    )]
+    z_near: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_z_far",
+        description = "Far clipping plane distance, that is used when baking the probe."
+    )]
+    z_far: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_environment",
+        description = "Baked cube map with the surroundings of the probe."
+    )]
+    environment: InheritableVariable<Option<TextureResource>>,
+}
+
+impl Default for ReflectionProbe {
+    fn default() -> Self {
+        ReflectionProbeBuilder::new(BaseBuilder::new()).build_reflection_probe()
+    }
+}
+
+impl Deref for ReflectionProbe {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for ReflectionProbe {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for ReflectionProbe {
+    fn type_uuid() -> Uuid {
+        uuid!("1d5b5b6e-8d1c-4ad4-a1ab-5f4a8a3f9c27")
+    }
+}
+
+impl ReflectionProbe {
+    /// Sets new shape of the influence volume.
+    pub fn set_shape(&mut self, shape: ReflectionProbeShape) -> ReflectionProbeShape {
+        self.shape.set_value_and_mark_modified(shape)
+    }
+
+    /// Returns current shape of the influence volume.
+    pub fn shape(&self) -> &ReflectionProbeShape {
+        &self.shape
+    }
+
+    /// Sets new distance from the boundary of the volume over which reflections fade out.
+    pub fn set_blend_distance(&mut self, distance: f32) -> f32 {
+        self.blend_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns current blend distance.
+    pub fn blend_distance(&self) -> f32 {
+        *self.blend_distance
+    }
+
+    /// Sets new multiplier of the brightness of reflections.
+    pub fn set_intensity(&mut self, intensity: f32) -> f32 {
+        self.intensity.set_value_and_mark_modified(intensity)
+    }
+
+    /// Returns current multiplier of the brightness of reflections.
+    pub fn intensity(&self) -> f32 {
+        *self.intensity
+    }
+
+    /// Enables or disables parallax correction of reflections.
+    pub fn set_parallax_correction(&mut self, enabled: bool) -> bool {
+        self.parallax_correction
+            .set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if parallax correction is enabled, `false` - otherwise.
+    pub fn is_parallax_correction_enabled(&self) -> bool {
+        *self.parallax_correction
+    }
+
+    /// Sets new size (in pixels) of a face of the cube map, that is used when baking the probe.
+    pub fn set_resolution(&mut self, resolution: u32) -> u32 {
+        self.resolution
+            .set_value_and_mark_modified(resolution.max(1))
+    }
+
+    /// Returns current size (in pixels) of a face of the cube map.
+    pub fn resolution(&self) -> u32 {
+        *self.resolution
+    }
+
+    /// Sets new near clipping plane distance, that is used when baking the probe.
+    pub fn set_z_near(&mut self, z_near: f32) -> f32 {
+        self.z_near.set_value_and_mark_modified(z_near)
+    }
+
+    /// Returns current near clipping plane distance.
+    pub fn z_near(&self) -> f32 {
+        *self.z_near
+    }
+
+    /// Sets new far clipping plane distance, that is used when baking the probe.
+    pub fn set_z_far(&mut self, z_far: f32) -> f32 {
+        self.z_far.set_value_and_mark_modified(z_far)
+    }
+
+    /// Returns current far clipping plane distance.
+    pub fn z_far(&self) -> f32 {
+        *self.z_far
+    }
+
+    /// Sets new baked cube map.
+    pub fn set_environment(
+        &mut self,
+        environment: Option<TextureResource>,
+    ) -> Option<TextureResource> {
+        std::mem::replace(
+            self.environment.get_value_mut_and_mark_modified(),
+            environment,
+        )
+    }
+
+    /// Returns current baked cube map.
+    pub fn environment(&self) -> Option<&TextureResource> {
+        self.environment.as_ref()
+    }
+
+    /// Returns current baked cube map.
+    pub fn environment_value(&self) -> Option<TextureResource> {
+        (*self.environment).clone()
+    }
+
+    /// Returns a weight of reflections of the probe at the given point in world coordinates. The
+    /// weight is `1.0` deep inside the volume, and it fades to `0.0` at the boundary of the volume.
+    pub fn influence(&self, world_point: Vector3<f32>) -> f32 {
+        let local_point = self
+            .global_transform()
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity)
+            .transform_point(&world_point.into())
+            .coords;
+        let distance = self.shape.distance_to_boundary(local_point);
+        if distance < 0.0 {
+            0.0
+        } else if *self.blend_distance <= 0.0 {
+            1.0
+        } else {
+            (distance / *self.blend_distance).min(1.0)
+        }
+    }
+}
+
+impl NodeTrait for ReflectionProbe {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.shape.bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// Allows you to create a reflection probe in a declarative manner.
+pub struct ReflectionProbeBuilder {
+    base_builder: BaseBuilder,
+    shape: ReflectionProbeShape,
+    blend_distance: f32,
+    intensity: f32,
+    parallax_correction: bool,
+    resolution: u32,
+    z_near: f32,
+    z_far: f32,
+    environment: Option<TextureResource>,
+}
+
+impl ReflectionProbeBuilder {
+    /// Creates a new instance of the builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            shape: Default::default(),
+            blend_distance: 1.0,
+            intensity: 1.0,
+            parallax_correction: true,
+            resolution: 128,
+            z_near: 0.025,
+            z_far: 128.0,
+            environment: None,
+        }
+    }
+
+    /// Sets desired shape of the influence volume.
+    pub fn with_shape(mut self, shape: ReflectionProbeShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Sets desired blend distance.
+    pub fn with_blend_distance(mut self, blend_distance: f32) -> Self {
+        self.blend_distance = blend_distance;
+        self
+    }
+
+    /// Sets desired intensity of reflections.
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Sets whether parallax correction should be used or not.
+    pub fn with_parallax_correction(mut self, enabled: bool) -> Self {
+        self.parallax_correction = enabled;
+        self
+    }
+
+    /// Sets desired size (in pixels) of a face of the baked cube map.
+    pub fn with_resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Sets desired clipping planes, that are used when baking the probe.
+    pub fn with_clip_planes(mut self, z_near: f32, z_far: f32) -> Self {
+        self.z_near = z_near;
+        self.z_far = z_far;
+        self
+    }
+
+    /// Sets desired baked cube map.
+    pub fn with_environment(mut self, environment: TextureResource) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Creates new reflection probe.
+    pub fn build_reflection_probe(self) -> ReflectionProbe {
+        ReflectionProbe {
+            base: self.base_builder.build_base(),
+            shape: self.shape.into(),
+            blend_distance: self.blend_distance.into(),
+            intensity: self.intensity.into(),
+            parallax_correction: self.parallax_correction.into(),
+            resolution: self.resolution.max(1).into(),
+            z_near: self.z_near.into(),
+            z_far: self.z_far.into(),
+            environment: self.environment.into(),
+        }
+    }
+
+    /// Creates new reflection probe node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_reflection_probe())
+    }
+
+    /// Creates new reflection probe node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            reflection_probe::{ReflectionProbeBuilder, ReflectionProbeShape},
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_reflection_probe_influence() {
+        let probe = ReflectionProbeBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(10.0, 0.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_shape(ReflectionProbeShape::Box {
+            half_extents: Vector3::new(2.0, 1.0, 1.0),
+        })
+        .with_blend_distance(0.5)
+        .build_reflection_probe();
+        probe.global_transform.set(probe.local_transform().matrix());
+
+        assert_eq!(probe.influence(Vector3::new(10.0, 0.0, 0.0)), 1.0);
+        assert_eq!(probe.influence(Vector3::new(11.75, 0.0, 0.0)), 0.5);
+        assert_eq!(probe.influence(Vector3::new(12.5, 0.0, 0.0)), 0.0);
+        // Outside of the volume along Y, even though X is inside.
+        assert_eq!(probe.influence(Vector3::new(10.0, 1.5, 0.0)), 0.0);
+
+        let sphere = ReflectionProbeShape::Sphere { radius: 2.0 };
+        assert_eq!(
+            sphere.distance_to_boundary(Vector3::new(0.0, 1.0, 0.0)),
+            1.0
+        );
+        assert_eq!(
+            sphere.distance_to_boundary(Vector3::new(0.0, 3.0, 0.0)),
+            -1.0
+        );
+    }
+}
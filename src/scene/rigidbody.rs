@@ -76,6 +76,21 @@ impl From<dynamics::RigidBodyType> for RigidBodyType {
     }
 }
 
+impl From<rapier2d::dynamics::RigidBodyType> for RigidBodyType {
+    fn from(s: rapier2d::dynamics::RigidBodyType) -> Self {
+        match s {
+            rapier2d::dynamics::RigidBodyType::Dynamic => Self::Dynamic,
+            rapier2d::dynamics::RigidBodyType::Fixed => Self::Static,
+            rapier2d::dynamics::RigidBodyType::KinematicPositionBased => {
+                Self::KinematicPositionBased
+            }
+            rapier2d::dynamics::RigidBodyType::KinematicVelocityBased => {
+                Self::KinematicVelocityBased
+            }
+        }
+    }
+}
+
 impl From<RigidBodyType> for rapier3d::dynamics::RigidBodyType {
     fn from(v: RigidBodyType) -> Self {
         match v {
@@ -189,6 +204,9 @@ pub struct RigidBody {
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) actions: Mutex<VecDeque<ApplyAction>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) interpolation_target: Cell<Option<Matrix4<f32>>>,
 }
 
 impl Debug for RigidBody {
@@ -219,6 +237,7 @@ impl Default for RigidBody {
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: Default::default(),
+            interpolation_target: Default::default(),
         }
     }
 }
@@ -260,6 +279,7 @@ impl Clone for RigidBody {
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: self.reset_forces.clone(),
+            interpolation_target: Default::default(),
         }
     }
 }
@@ -543,14 +563,20 @@ impl NodeTrait for RigidBody {
     }
 
     fn update(&mut self, context: &mut UpdateContext) {
-        context.physics.sync_rigid_body_node(
-            self,
-            // Rigid body can be root node of a scene, in this case it does not have a parent.
-            context
-                .nodes
-                .try_borrow(self.parent)
-                .map(|p| p.global_transform())
-                .unwrap_or_else(Matrix4::identity),
+        // Rigid body can be root node of a scene, in this case it does not have a parent.
+        let parent_transform = context
+            .nodes
+            .try_borrow(self.parent)
+            .map(|p| p.global_transform())
+            .unwrap_or_else(Matrix4::identity);
+
+        context.physics.sync_rigid_body_node(self, parent_transform);
+
+        // Remember the transform the body will have after the next hierarchical data update, so its
+        // rendered transform could be interpolated between physics steps.
+        self.interpolation_target.set(
+            (*self.body_type == RigidBodyType::Dynamic)
+                .then(|| parent_transform * self.local_transform().matrix()),
         );
     }
 
@@ -730,6 +756,7 @@ impl RigidBodyBuilder {
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
             reset_forces: Default::default(),
+            interpolation_target: Default::default(),
         }
     }
 
@@ -0,0 +1,1255 @@
+//! Procedural sky with physically based atmosphere scattering and day/night cycle.
+//!
+//! For more info see [`Sky`]
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    resource::texture::{TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension},
+    scene::{
+        base::{Base, BaseBuilder},
+        camera::SkyBox,
+        graph::Graph,
+        light::directional::DirectionalLight,
+        node::{Node, NodeTrait, UpdateContext},
+    },
+};
+use fyrox_core::uuid_provider;
+use fyrox_resource::untyped::ResourceKind;
+use std::{
+    f32::consts::{PI, TAU},
+    ops::{Deref, DerefMut},
+};
+
+const EARTH_RADIUS: f32 = 6360e3;
+const ATMOSPHERE_RADIUS: f32 = 6420e3;
+const RAYLEIGH_SCALE_HEIGHT: f32 = 7994.0;
+const MIE_SCALE_HEIGHT: f32 = 1200.0;
+const RAYLEIGH_COEFFICIENTS: [f32; 3] = [5.5e-6, 13.0e-6, 22.4e-6];
+const MIE_COEFFICIENT: f32 = 21e-6;
+const VIEW_SAMPLES: usize = 12;
+const LIGHT_SAMPLES: usize = 6;
+/// Cosine of the angular radius of the sun disk.
+const SUN_DISK_COS: f32 = 0.9998;
+/// Maximum distance (in meters) at which clouds are still visible.
+const MAX_CLOUD_DISTANCE: f32 = 30000.0;
+
+/// Settings of the optional cloud layer of the [`Sky`].
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct SkyClouds {
+    /// Whether the cloud layer is rendered or not.
+    pub enabled: bool,
+    /// Amount of the sky covered by clouds in `[0; 1]` range.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub coverage: f32,
+    /// Optical density of clouds. Higher values make clouds darker and less transparent.
+    #[reflect(min_value = 0.0, step = 0.001)]
+    pub density: f32,
+    /// Height (in meters) of the bottom of the cloud layer.
+    #[reflect(min_value = 0.0, step = 10.0)]
+    pub altitude: f32,
+    /// Thickness (in meters) of the cloud layer.
+    #[reflect(min_value = 0.0, step = 10.0)]
+    pub thickness: f32,
+    /// Approximate size (in meters) of a single cloud.
+    #[reflect(min_value = 1.0, step = 10.0)]
+    pub scale: f32,
+    /// Velocity (in meters per second) of clouds in XZ plane.
+    pub wind: Vector2<f32>,
+    /// Amount of samples along a view ray through the cloud layer. Higher values improve quality
+    /// at the cost of performance.
+    #[reflect(min_value = 1.0, max_value = 64.0)]
+    pub steps: u32,
+}
+
+uuid_provider!(SkyClouds = "3a1c6f0e-2b7d-4f5a-9c8e-7d4b1e0a5f62");
+
+impl Default for SkyClouds {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            coverage: 0.45,
+            density: 0.02,
+            altitude: 1500.0,
+            thickness: 600.0,
+            scale: 2000.0,
+            wind: Vector2::new(10.0, 0.0),
+            steps: 12,
+        }
+    }
+}
+
+/// Cached results of the last sky update. It is never shared between copies of a sky, because
+/// the cube map is modified in-place.
+#[derive(Default, Debug)]
+struct SkyCache {
+    skybox: Option<SkyBox>,
+    ambient_color: Color,
+    cloud_offset: Vector2<f32>,
+    time_since_refresh: f32,
+    fingerprint: Vec<f32>,
+}
+
+impl Clone for SkyCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Sky is a procedural atmosphere, that simulates Rayleigh and Mie scattering of sun light. It
+/// drives a sun (a directional light) by its time of day and provides the environment for the
+/// scene.
+///
+/// # Time of day
+///
+/// [`Sky::time_of_day`] defines the position of the sun on the sky: `6.0` is sunrise, `12.0` is
+/// noon, `18.0` is sunset. When [`Sky::day_duration`] is non-zero, the time of day advances
+/// automatically and a full day takes the given amount of seconds. The path of the sun is
+/// defined by [`Sky::latitude`] and [`Sky::north_angle`].
+///
+/// # Sun
+///
+/// The sky rotates a [`DirectionalLight`] set by [`Sky::set_sun`] to match the direction of the
+/// sun, its color and intensity are changed according to the amount of light that passes through
+/// the atmosphere. The local rotation of the light is overwritten, so it should not have rotated
+/// ancestors.
+///
+/// # Environment
+///
+/// The sky is rendered into a small cube map (see [`Sky::resolution`]), that replaces the skybox
+/// of every camera, and it is also used as a source of reflections. The ambient lighting of the
+/// scene is calculated from the sky as well. Both can be disabled, see
+/// [`Sky::set_update_environment`] and [`Sky::set_update_ambient`]. Only the first enabled sky
+/// in a scene is used.
+///
+/// The cube map is calculated on CPU and updated only when the sky changes, at most every
+/// [`Sky::update_interval`] seconds.
+///
+/// # Clouds
+///
+/// Optional cloud layer (see [`SkyClouds`]) is ray marched through a 3D noise, clouds are moved
+/// by the wind.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::pool::Handle,
+///     scene::{
+///         base::BaseBuilder, graph::Graph, light::{directional::DirectionalLightBuilder,
+///         BaseLightBuilder}, node::Node, sky::SkyBuilder,
+///     },
+/// };
+///
+/// fn create_sky(graph: &mut Graph) -> Handle<Node> {
+///     let sun = DirectionalLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new()))
+///         .build(graph);
+///
+///     SkyBuilder::new(BaseBuilder::new())
+///         .with_sun(sun)
+///         .with_time_of_day(9.0)
+///         // A full day takes 10 minutes.
+///         .with_day_duration(600.0)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Debug, Visit, Clone, Reflect)]
+pub struct Sky {
+    base: Base,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 24.0,
+        step = 0.1,
+        setter = "set_time_of_day",
+        description = "Current time of day in hours. 6 is sunrise, 12 is noon, 18 is sunset."
+    )]
+    time_of_day: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 1.0,
+        setter = "set_day_duration",
+        description = "Duration of a full day in seconds. Zero stops the time."
+    )]
+    day_duration: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = -90.0,
+        max_value = 90.0,
+        setter = "set_latitude",
+        description = "Latitude (in degrees) of the observer, it defines the height of the sun at noon."
+    )]
+    latitude: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_north_angle",
+        description = "Rotation (in degrees) of the path of the sun around Y axis."
+    )]
+    north_angle: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_sun",
+        description = "A directional light, that will be rotated and colored to match the sun."
+    )]
+    sun: InheritableVariable<Handle<Node>>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_sun_intensity",
+        description = "Intensity of the sun light at noon."
+    )]
+    sun_intensity: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_sun_radiance",
+        description = "Brightness of the sun light, that is scattered in the atmosphere."
+    )]
+    sun_radiance: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_rayleigh_scattering",
+        description = "Multiplier of Rayleigh scattering. It defines the blue color of the sky and red color of sunsets."
+    )]
+    rayleigh_scattering: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_mie_scattering",
+        description = "Multiplier of Mie scattering. It defines the haze and the glow around the sun."
+    )]
+    mie_scattering: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 0.999,
+        step = 0.01,
+        setter = "set_mie_directionality",
+        description = "Directionality of Mie scattering, higher values make the glow around the sun smaller."
+    )]
+    mie_directionality: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_exposure",
+        description = "Exposure, that is used to map the brightness of the sky to displayable range."
+    )]
+    exposure: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_night_color",
+        description = "Minimal color of the sky, it is used when the sun is below the horizon."
+    )]
+    night_color: InheritableVariable<Color>,
+
+    #[reflect(setter = "set_clouds", description = "Settings of the cloud layer.")]
+    clouds: InheritableVariable<SkyClouds>,
+
+    #[reflect(
+        min_value = 1.0,
+        max_value = 256.0,
+        setter = "set_resolution",
+        description = "Size (in pixels) of a face of the sky cube map."
+    )]
+    resolution: InheritableVariable<u32>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.01,
+        setter = "set_update_interval",
+        description = "Minimal time (in seconds) between updates of the sky cube map."
+    )]
+    update_interval: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_update_ambient",
+        description = "Whether the ambient lighting of the scene is calculated from the sky or not."
+    )]
+    update_ambient: InheritableVariable<bool>,
+
+    #[reflect(
+        min_value = 0.0,
+        step = 0.1,
+        setter = "set_ambient_intensity",
+        description = "Multiplier of the ambient lighting calculated from the sky."
+    )]
+    ambient_intensity: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_update_environment",
+        description = "Whether the sky replaces skyboxes and environment maps of cameras or not."
+    )]
+    update_environment: InheritableVariable<bool>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    cache: SkyCache,
+}
+
+impl Default for Sky {
+    fn default() -> Self {
+        SkyBuilder::new(BaseBuilder::new()).build_sky()
+    }
+}
+
+impl Deref for Sky {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Sky {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Sky {
+    fn type_uuid() -> Uuid {
+        uuid!("e7b2d4a9-5c3f-4b1e-8a6d-9f0c2e4b7a15")
+    }
+}
+
+impl Sky {
+    /// Sets new time of day in hours, it is wrapped to `[0; 24)` range.
+    pub fn set_time_of_day(&mut self, time: f32) -> f32 {
+        self.time_of_day
+            .set_value_and_mark_modified(time.rem_euclid(24.0))
+    }
+
+    /// Returns current time of day in hours.
+    pub fn time_of_day(&self) -> f32 {
+        *self.time_of_day
+    }
+
+    /// Sets new duration of a full day in seconds. Zero stops the time.
+    pub fn set_day_duration(&mut self, duration: f32) -> f32 {
+        self.day_duration
+            .set_value_and_mark_modified(duration.max(0.0))
+    }
+
+    /// Returns current duration of a full day in seconds.
+    pub fn day_duration(&self) -> f32 {
+        *self.day_duration
+    }
+
+    /// Sets new latitude (in degrees) of the observer.
+    pub fn set_latitude(&mut self, latitude: f32) -> f32 {
+        self.latitude
+            .set_value_and_mark_modified(latitude.clamp(-90.0, 90.0))
+    }
+
+    /// Returns current latitude (in degrees) of the observer.
+    pub fn latitude(&self) -> f32 {
+        *self.latitude
+    }
+
+    /// Sets new rotation (in degrees) of the path of the sun around Y axis.
+    pub fn set_north_angle(&mut self, angle: f32) -> f32 {
+        self.north_angle.set_value_and_mark_modified(angle)
+    }
+
+    /// Returns current rotation (in degrees) of the path of the sun around Y axis.
+    pub fn north_angle(&self) -> f32 {
+        *self.north_angle
+    }
+
+    /// Sets new directional light, that will be driven by the sky.
+    pub fn set_sun(&mut self, sun: Handle<Node>) -> Handle<Node> {
+        self.sun.set_value_and_mark_modified(sun)
+    }
+
+    /// Returns a handle of the directional light, that is driven by the sky.
+    pub fn sun(&self) -> Handle<Node> {
+        *self.sun
+    }
+
+    /// Sets new intensity of the sun light at noon.
+    pub fn set_sun_intensity(&mut self, intensity: f32) -> f32 {
+        self.sun_intensity.set_value_and_mark_modified(intensity)
+    }
+
+    /// Returns current intensity of the sun light at noon.
+    pub fn sun_intensity(&self) -> f32 {
+        *self.sun_intensity
+    }
+
+    /// Sets new brightness of the sun light, that is scattered in the atmosphere.
+    pub fn set_sun_radiance(&mut self, radiance: f32) -> f32 {
+        self.sun_radiance.set_value_and_mark_modified(radiance)
+    }
+
+    /// Returns current brightness of the sun light, that is scattered in the atmosphere.
+    pub fn sun_radiance(&self) -> f32 {
+        *self.sun_radiance
+    }
+
+    /// Sets new multiplier of Rayleigh scattering.
+    pub fn set_rayleigh_scattering(&mut self, scattering: f32) -> f32 {
+        self.rayleigh_scattering
+            .set_value_and_mark_modified(scattering)
+    }
+
+    /// Returns current multiplier of Rayleigh scattering.
+    pub fn rayleigh_scattering(&self) -> f32 {
+        *self.rayleigh_scattering
+    }
+
+    /// Sets new multiplier of Mie scattering.
+    pub fn set_mie_scattering(&mut self, scattering: f32) -> f32 {
+        self.mie_scattering.set_value_and_mark_modified(scattering)
+    }
+
+    /// Returns current multiplier of Mie scattering.
+    pub fn mie_scattering(&self) -> f32 {
+        *self.mie_scattering
+    }
+
+    /// Sets new directionality of Mie scattering in `[0; 1)` range.
+    pub fn set_mie_directionality(&mut self, directionality: f32) -> f32 {
+        self.mie_directionality
+            .set_value_and_mark_modified(directionality.clamp(0.0, 0.999))
+    }
+
+    /// Returns current directionality of Mie scattering.
+    pub fn mie_directionality(&self) -> f32 {
+        *self.mie_directionality
+    }
+
+    /// Sets new exposure of the sky.
+    pub fn set_exposure(&mut self, exposure: f32) -> f32 {
+        self.exposure.set_value_and_mark_modified(exposure)
+    }
+
+    /// Returns current exposure of the sky.
+    pub fn exposure(&self) -> f32 {
+        *self.exposure
+    }
+
+    /// Sets new minimal color of the sky.
+    pub fn set_night_color(&mut self, color: Color) -> Color {
+        self.night_color.set_value_and_mark_modified(color)
+    }
+
+    /// Returns current minimal color of the sky.
+    pub fn night_color(&self) -> Color {
+        *self.night_color
+    }
+
+    /// Sets new settings of the cloud layer.
+    pub fn set_clouds(&mut self, clouds: SkyClouds) -> SkyClouds {
+        self.clouds.set_value_and_mark_modified(clouds)
+    }
+
+    /// Returns current settings of the cloud layer.
+    pub fn clouds(&self) -> &SkyClouds {
+        &self.clouds
+    }
+
+    /// Sets new size (in pixels) of a face of the sky cube map.
+    pub fn set_resolution(&mut self, resolution: u32) -> u32 {
+        self.resolution
+            .set_value_and_mark_modified(resolution.max(1))
+    }
+
+    /// Returns current size (in pixels) of a face of the sky cube map.
+    pub fn resolution(&self) -> u32 {
+        *self.resolution
+    }
+
+    /// Sets new minimal time (in seconds) between updates of the sky cube map.
+    pub fn set_update_interval(&mut self, interval: f32) -> f32 {
+        self.update_interval
+            .set_value_and_mark_modified(interval.max(0.0))
+    }
+
+    /// Returns current minimal time (in seconds) between updates of the sky cube map.
+    pub fn update_interval(&self) -> f32 {
+        *self.update_interval
+    }
+
+    /// Enables or disables calculation of the ambient lighting of the scene from the sky.
+    pub fn set_update_ambient(&mut self, enabled: bool) -> bool {
+        self.update_ambient.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the ambient lighting of the scene is calculated from the sky.
+    pub fn is_update_ambient(&self) -> bool {
+        *self.update_ambient
+    }
+
+    /// Sets new multiplier of the ambient lighting calculated from the sky.
+    pub fn set_ambient_intensity(&mut self, intensity: f32) -> f32 {
+        self.ambient_intensity
+            .set_value_and_mark_modified(intensity)
+    }
+
+    /// Returns current multiplier of the ambient lighting calculated from the sky.
+    pub fn ambient_intensity(&self) -> f32 {
+        *self.ambient_intensity
+    }
+
+    /// Enables or disables replacement of skyboxes and environment maps of cameras by the sky.
+    pub fn set_update_environment(&mut self, enabled: bool) -> bool {
+        self.update_environment.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the sky replaces skyboxes and environment maps of cameras.
+    pub fn is_update_environment(&self) -> bool {
+        *self.update_environment
+    }
+
+    /// Returns normalized direction to the sun in world coordinates for the current time of day.
+    pub fn sun_direction(&self) -> Vector3<f32> {
+        // Angle of the sun on its circular path, the sun is at the horizon at 6 and 18 hours.
+        let angle = (*self.time_of_day / 24.0) * TAU - PI * 0.5;
+        let direction = Vector3::new(-angle.cos(), angle.sin(), 0.0);
+        let tilt = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), self.latitude.to_radians());
+        let rotation =
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.north_angle.to_radians());
+        (rotation * tilt * direction).normalize()
+    }
+
+    /// Returns the cube map of the sky, it is available only after the first update of the sky
+    /// and only if [`Sky::is_update_environment`] is `true`.
+    pub fn skybox(&self) -> Option<&SkyBox> {
+        if *self.update_environment {
+            self.cache.skybox.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns ambient lighting color calculated from the sky, it is available only after the
+    /// first update of the sky and only if [`Sky::is_update_ambient`] is `true`.
+    pub fn ambient_color(&self) -> Option<Color> {
+        if *self.update_ambient && self.cache.skybox.is_some() {
+            Some(self.cache.ambient_color)
+        } else {
+            None
+        }
+    }
+
+    fn atmosphere(&self) -> Atmosphere {
+        Atmosphere {
+            sun_direction: self.sun_direction(),
+            sun_radiance: *self.sun_radiance,
+            rayleigh: Vector3::from(RAYLEIGH_COEFFICIENTS).scale(*self.rayleigh_scattering),
+            mie: MIE_COEFFICIENT * *self.mie_scattering,
+            g: *self.mie_directionality,
+        }
+    }
+
+    /// Returns a set of values, that affect the sky cube map. It is used to skip the update of the
+    /// cube map when nothing has changed.
+    fn fingerprint(&self) -> Vec<f32> {
+        let sun_direction = self.sun_direction();
+        let clouds = &*self.clouds;
+        let night_color = self.night_color.as_frgb();
+        let mut fingerprint = vec![
+            sun_direction.x,
+            sun_direction.y,
+            sun_direction.z,
+            *self.sun_radiance,
+            *self.rayleigh_scattering,
+            *self.mie_scattering,
+            *self.mie_directionality,
+            *self.exposure,
+            *self.ambient_intensity,
+            *self.resolution as f32,
+            night_color.x,
+            night_color.y,
+            night_color.z,
+        ];
+        if clouds.enabled {
+            fingerprint.extend([
+                clouds.coverage,
+                clouds.density,
+                clouds.altitude,
+                clouds.thickness,
+                clouds.scale,
+                clouds.steps as f32,
+                self.cache.cloud_offset.x,
+                self.cache.cloud_offset.y,
+            ]);
+        }
+        fingerprint
+    }
+
+    /// Calculates linear radiance of the sky in the given direction, including sun disk and
+    /// clouds.
+    pub fn radiance(&self, direction: Vector3<f32>) -> Vector3<f32> {
+        let atmosphere = self.atmosphere();
+        let night_color = self.night_color.srgb_to_linear_f32().xyz();
+        let sky = atmosphere.radiance(direction, true);
+        let sky = if self.clouds.enabled {
+            apply_clouds(
+                sky,
+                direction,
+                &atmosphere,
+                &self.clouds,
+                self.cache.cloud_offset,
+            )
+        } else {
+            sky
+        };
+        sky.sup(&night_color)
+    }
+
+    fn refresh(&mut self) {
+        let resolution = *self.resolution as usize;
+        let exposure = *self.exposure;
+
+        let mut bytes = Vec::with_capacity(resolution * resolution * 6 * 4);
+        let mut ambient = Vector3::default();
+        let mut ambient_weight = 0.0;
+        for face in 0..6 {
+            for y in 0..resolution {
+                for x in 0..resolution {
+                    let u = 2.0 * (x as f32 + 0.5) / resolution as f32 - 1.0;
+                    let v = 2.0 * (y as f32 + 0.5) / resolution as f32 - 1.0;
+                    let direction = cube_face_direction(face, u, v);
+
+                    let radiance = self.radiance(direction);
+
+                    if direction.y > 0.0 {
+                        ambient += radiance.scale(direction.y);
+                        ambient_weight += direction.y;
+                    }
+
+                    let color = tone_map(radiance, exposure);
+                    bytes.extend([color.r, color.g, color.b, 255]);
+                }
+            }
+        }
+
+        let ambient = if ambient_weight > 0.0 {
+            ambient.scale(*self.ambient_intensity / ambient_weight)
+        } else {
+            Vector3::default()
+        };
+        self.cache.ambient_color = tone_map(ambient, exposure);
+
+        let size = resolution as u32;
+        let cubemap = self
+            .cache
+            .skybox
+            .as_ref()
+            .and_then(|skybox| skybox.cubemap_ref())
+            .filter(|cubemap| {
+                matches!(
+                    cubemap.data_ref().kind(),
+                    TextureKind::Cube { width, height } if width == size && height == size
+                )
+            })
+            .cloned();
+
+        if let Some(cubemap) = cubemap {
+            let mut texture = cubemap.data_ref();
+            let mut modify = texture.modify();
+            let data = modify.data_mut();
+            if data.len() == bytes.len() {
+                data.copy_from_slice(&bytes);
+            }
+        } else {
+            self.cache.skybox = TextureResource::from_bytes(
+                TextureKind::Cube {
+                    width: size,
+                    height: size,
+                },
+                TexturePixelKind::RGBA8,
+                bytes,
+                ResourceKind::Embedded,
+            )
+            .map(|cubemap| SkyBox {
+                cubemap: Some(cubemap),
+                ..Default::default()
+            });
+        }
+    }
+
+    fn update_sun(&self, context: &mut UpdateContext) {
+        let Some(sun) = context
+            .nodes
+            .try_borrow_mut(*self.sun)
+            .and_then(|n| n.cast_mut::<DirectionalLight>())
+        else {
+            return;
+        };
+
+        let direction = self.sun_direction();
+
+        // The light shines in the opposite direction of its up vector.
+        let rotation = UnitQuaternion::rotation_between(&Vector3::y(), &direction)
+            .unwrap_or_else(|| UnitQuaternion::from_axis_angle(&Vector3::x_axis(), PI));
+        sun.local_transform_mut().set_rotation(rotation);
+
+        let transmittance = self.atmosphere().transmittance(direction);
+        let max = transmittance.max();
+        let color = if max > 0.0 {
+            transmittance.scale(1.0 / max)
+        } else {
+            Vector3::repeat(1.0)
+        };
+        let color = Color::from(color.map(|c| c.powf(1.0 / 2.2)));
+        // Fade the sun out smoothly when it goes below the horizon.
+        let intensity = *self.sun_intensity * (direction.y * 10.0).clamp(0.0, 1.0);
+
+        let base_light = sun.base_light_mut();
+        if base_light.color() != color {
+            base_light.set_color(color);
+        }
+        if base_light.intensity() != intensity {
+            base_light.set_intensity(intensity);
+        }
+    }
+}
+
+impl NodeTrait for Sky {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::unit()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        if *self.day_duration > 0.0 {
+            let time = *self.time_of_day + context.dt * 24.0 / *self.day_duration;
+            self.time_of_day.set_value_silent(time.rem_euclid(24.0));
+        }
+
+        if self.clouds.enabled {
+            self.cache.cloud_offset += self.clouds.wind.scale(context.dt);
+        }
+
+        self.update_sun(context);
+
+        self.cache.time_since_refresh += context.dt;
+        if self.cache.skybox.is_none() || self.cache.time_since_refresh >= *self.update_interval {
+            let fingerprint = self.fingerprint();
+            if self.cache.skybox.is_none() || fingerprint != self.cache.fingerprint {
+                self.refresh();
+                self.cache.fingerprint = fingerprint;
+                self.cache.time_since_refresh = 0.0;
+            }
+        }
+    }
+}
+
+/// Returns the first enabled sky of the graph, if any. The renderer uses the sky as a source of
+/// the skybox and ambient lighting.
+pub fn active_sky(graph: &Graph) -> Option<&Sky> {
+    graph
+        .linear_iter()
+        .filter(|node| node.is_globally_enabled())
+        .find_map(|node| node.cast::<Sky>())
+}
+
+struct Atmosphere {
+    sun_direction: Vector3<f32>,
+    sun_radiance: f32,
+    rayleigh: Vector3<f32>,
+    mie: f32,
+    g: f32,
+}
+
+impl Atmosphere {
+    fn observer() -> Vector3<f32> {
+        Vector3::new(0.0, EARTH_RADIUS + 1.0, 0.0)
+    }
+
+    fn extinction(&self, rayleigh_depth: f32, mie_depth: f32) -> Vector3<f32> {
+        (self.rayleigh.scale(rayleigh_depth) + Vector3::repeat(self.mie * 1.1 * mie_depth))
+            .map(|t| (-t).exp())
+    }
+
+    /// Returns optical depths (Rayleigh, Mie) from the given point to the edge of the atmosphere,
+    /// or `None` if the ray hits the ground.
+    fn optical_depth(
+        &self,
+        origin: Vector3<f32>,
+        direction: Vector3<f32>,
+        samples: usize,
+    ) -> Option<(f32, f32)> {
+        if hits_ground(origin, direction) {
+            return None;
+        }
+
+        let length = ray_sphere_far(origin, direction, ATMOSPHERE_RADIUS)?;
+        let step = length / samples as f32;
+        let mut rayleigh = 0.0;
+        let mut mie = 0.0;
+        for i in 0..samples {
+            let height = (origin + direction.scale((i as f32 + 0.5) * step)).norm() - EARTH_RADIUS;
+            rayleigh += (-height / RAYLEIGH_SCALE_HEIGHT).exp() * step;
+            mie += (-height / MIE_SCALE_HEIGHT).exp() * step;
+        }
+        Some((rayleigh, mie))
+    }
+
+    /// Returns amount of sun light, that reaches the ground.
+    fn transmittance(&self, direction: Vector3<f32>) -> Vector3<f32> {
+        let direction = Vector3::new(direction.x, direction.y.max(0.0), direction.z)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::y);
+        self.optical_depth(Self::observer(), direction, LIGHT_SAMPLES)
+            .map_or(Vector3::default(), |(rayleigh, mie)| {
+                self.extinction(rayleigh, mie)
+            })
+    }
+
+    fn radiance(&self, direction: Vector3<f32>, sun_disk: bool) -> Vector3<f32> {
+        // There's no terrain below the horizon, so reflect the sky above the horizon and darken it
+        // a bit.
+        let (direction, ground_factor) = if direction.y < 0.0 {
+            (
+                Vector3::new(direction.x, 0.0, direction.z)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::x),
+                (1.0 + direction.y * 2.0).max(0.3),
+            )
+        } else {
+            (direction, 1.0)
+        };
+
+        let origin = Self::observer();
+        let Some(length) = ray_sphere_far(origin, direction, ATMOSPHERE_RADIUS) else {
+            return Vector3::default();
+        };
+        let step = length / VIEW_SAMPLES as f32;
+
+        let mut rayleigh_depth = 0.0;
+        let mut mie_depth = 0.0;
+        let mut rayleigh_sum = Vector3::default();
+        let mut mie_sum = Vector3::default();
+        for i in 0..VIEW_SAMPLES {
+            let position = origin + direction.scale((i as f32 + 0.5) * step);
+            let height = position.norm() - EARTH_RADIUS;
+            let rayleigh = (-height / RAYLEIGH_SCALE_HEIGHT).exp() * step;
+            let mie = (-height / MIE_SCALE_HEIGHT).exp() * step;
+            rayleigh_depth += rayleigh;
+            mie_depth += mie;
+
+            if let Some((light_rayleigh, light_mie)) =
+                self.optical_depth(position, self.sun_direction, LIGHT_SAMPLES)
+            {
+                let attenuation =
+                    self.extinction(rayleigh_depth + light_rayleigh, mie_depth + light_mie);
+                rayleigh_sum += attenuation.scale(rayleigh);
+                mie_sum += attenuation.scale(mie);
+            }
+        }
+
+        let mu = direction.dot(&self.sun_direction);
+        let rayleigh_phase = 3.0 / (16.0 * PI) * (1.0 + mu * mu);
+        let g = self.g;
+        let mie_phase = 3.0 / (8.0 * PI) * ((1.0 - g * g) * (1.0 + mu * mu))
+            / ((2.0 + g * g) * (1.0 + g * g - 2.0 * g * mu).powf(1.5));
+
+        let mut radiance = (rayleigh_sum
+            .component_mul(&self.rayleigh)
+            .scale(rayleigh_phase)
+            + mie_sum.scale(self.mie * mie_phase))
+        .scale(self.sun_radiance);
+
+        if sun_disk && ground_factor == 1.0 && mu > SUN_DISK_COS {
+            radiance += self
+                .extinction(rayleigh_depth, mie_depth)
+                .scale(self.sun_radiance * 10.0);
+        }
+
+        radiance.scale(ground_factor)
+    }
+}
+
+/// Returns the distance to the far intersection point of a ray with a sphere at the origin.
+fn ray_sphere_far(origin: Vector3<f32>, direction: Vector3<f32>, radius: f32) -> Option<f32> {
+    let b = origin.dot(&direction);
+    let c = origin.norm_squared() - radius * radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        None
+    } else {
+        Some(-b + discriminant.sqrt())
+    }
+}
+
+/// Checks whether a ray, that starts above the ground, hits the ground or not.
+fn hits_ground(origin: Vector3<f32>, direction: Vector3<f32>) -> bool {
+    let b = origin.dot(&direction);
+    let c = origin.norm_squared() - EARTH_RADIUS * EARTH_RADIUS;
+    b < 0.0 && b * b - c >= 0.0
+}
+
+fn apply_clouds(
+    sky: Vector3<f32>,
+    direction: Vector3<f32>,
+    atmosphere: &Atmosphere,
+    clouds: &SkyClouds,
+    offset: Vector2<f32>,
+) -> Vector3<f32> {
+    if direction.y <= 0.01 || clouds.thickness <= 0.0 {
+        return sky;
+    }
+
+    let start = clouds.altitude / direction.y;
+    let end = (clouds.altitude + clouds.thickness) / direction.y;
+    if start > MAX_CLOUD_DISTANCE {
+        return sky;
+    }
+
+    let steps = clouds.steps.max(1);
+    let step = (end - start) / steps as f32;
+    let sun_color = atmosphere
+        .transmittance(atmosphere.sun_direction)
+        .scale(atmosphere.sun_radiance * 0.1);
+    // Clouds are lit by the sky as well.
+    let ambient = atmosphere.radiance(Vector3::y(), false);
+    let mu = direction.dot(&atmosphere.sun_direction);
+    let g = 0.6f32;
+    let phase = (1.0 - g * g) / (4.0 * PI * (1.0 + g * g - 2.0 * g * mu).powf(1.5));
+
+    let mut transmittance = 1.0;
+    let mut light = Vector3::default();
+    for i in 0..steps {
+        let t = start + (i as f32 + 0.5) * step;
+        let position = direction.scale(t);
+        let height = (position.y - clouds.altitude) / clouds.thickness;
+        // Round the bottom and the top of the clouds.
+        let height_falloff = (4.0 * height * (1.0 - height)).clamp(0.0, 1.0);
+        let sample = Vector3::new(
+            (position.x + offset.x) / clouds.scale,
+            position.y / clouds.scale,
+            (position.z + offset.y) / clouds.scale,
+        );
+        let density = ((fbm(sample) - (1.0 - clouds.coverage)) / clouds.coverage.max(0.01))
+            .clamp(0.0, 1.0)
+            * height_falloff
+            * clouds.density;
+        if density <= 0.0 {
+            continue;
+        }
+
+        let step_transmittance = (-density * step).exp();
+        // Upper parts of the clouds are lit better.
+        let lighting = sun_color.scale(phase * 4.0 + height * 0.5) + ambient;
+        light += lighting.scale(transmittance * (1.0 - step_transmittance));
+        transmittance *= step_transmittance;
+        if transmittance < 0.01 {
+            break;
+        }
+    }
+
+    // Fade the clouds out near the horizon.
+    let fade = (1.0 - start / MAX_CLOUD_DISTANCE).clamp(0.0, 1.0);
+    let cloudy = sky.scale(transmittance) + light;
+    sky.lerp(&cloudy, fade)
+}
+
+fn hash(x: i32, y: i32, z: i32) -> f32 {
+    let mut n = x
+        .wrapping_mul(374761393)
+        .wrapping_add(y.wrapping_mul(668265263))
+        .wrapping_add(z.wrapping_mul(1274126177));
+    n = (n ^ (n >> 13)).wrapping_mul(1274126177);
+    ((n ^ (n >> 16)) & 0xFFFF) as f32 / 65535.0
+}
+
+fn value_noise(p: Vector3<f32>) -> f32 {
+    let i = p.map(|c| c.floor());
+    let f = p - i;
+    let f = f.map(|c| c * c * (3.0 - 2.0 * c));
+    let (x, y, z) = (i.x as i32, i.y as i32, i.z as i32);
+
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    let x00 = lerp(hash(x, y, z), hash(x + 1, y, z), f.x);
+    let x10 = lerp(hash(x, y + 1, z), hash(x + 1, y + 1, z), f.x);
+    let x01 = lerp(hash(x, y, z + 1), hash(x + 1, y, z + 1), f.x);
+    let x11 = lerp(hash(x, y + 1, z + 1), hash(x + 1, y + 1, z + 1), f.x);
+    lerp(lerp(x00, x10, f.y), lerp(x01, x11, f.y), f.z)
+}
+
+fn fbm(mut p: Vector3<f32>) -> f32 {
+    let mut value = 0.0;
+    let mut amplitude = 0.5;
+    for _ in 0..4 {
+        value += value_noise(p) * amplitude;
+        p = p.scale(2.03);
+        amplitude *= 0.5;
+    }
+    value / 0.9375
+}
+
+/// Returns a direction for the given texel of a cube map face, `u` and `v` are in `[-1; 1]` range.
+/// Faces are in the following order: +X, -X, +Y, -Y, +Z, -Z.
+fn cube_face_direction(face: usize, u: f32, v: f32) -> Vector3<f32> {
+    match face {
+        0 => Vector3::new(1.0, -v, -u),
+        1 => Vector3::new(-1.0, -v, u),
+        2 => Vector3::new(u, 1.0, v),
+        3 => Vector3::new(u, -1.0, -v),
+        4 => Vector3::new(u, -v, 1.0),
+        _ => Vector3::new(-u, -v, -1.0),
+    }
+    .normalize()
+}
+
+/// Maps linear HDR radiance to displayable sRGB color.
+fn tone_map(radiance: Vector3<f32>, exposure: f32) -> Color {
+    Color::from(radiance.map(|c| (1.0 - (-c * exposure).exp()).powf(1.0 / 2.2)))
+}
+
+/// Allows you to create a sky in a declarative manner.
+pub struct SkyBuilder {
+    base_builder: BaseBuilder,
+    time_of_day: f32,
+    day_duration: f32,
+    latitude: f32,
+    north_angle: f32,
+    sun: Handle<Node>,
+    sun_intensity: f32,
+    sun_radiance: f32,
+    rayleigh_scattering: f32,
+    mie_scattering: f32,
+    mie_directionality: f32,
+    exposure: f32,
+    night_color: Color,
+    clouds: SkyClouds,
+    resolution: u32,
+    update_interval: f32,
+    update_ambient: bool,
+    ambient_intensity: f32,
+    update_environment: bool,
+}
+
+impl SkyBuilder {
+    /// Creates a new instance of the builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            time_of_day: 12.0,
+            day_duration: 0.0,
+            latitude: 30.0,
+            north_angle: 0.0,
+            sun: Handle::NONE,
+            sun_intensity: 1.0,
+            sun_radiance: 20.0,
+            rayleigh_scattering: 1.0,
+            mie_scattering: 1.0,
+            mie_directionality: 0.76,
+            exposure: 1.0,
+            night_color: Color::opaque(4, 6, 14),
+            clouds: Default::default(),
+            resolution: 32,
+            update_interval: 0.1,
+            update_ambient: true,
+            ambient_intensity: 1.0,
+            update_environment: true,
+        }
+    }
+
+    /// Sets desired time of day in hours.
+    pub fn with_time_of_day(mut self, time: f32) -> Self {
+        self.time_of_day = time;
+        self
+    }
+
+    /// Sets desired duration of a full day in seconds.
+    pub fn with_day_duration(mut self, duration: f32) -> Self {
+        self.day_duration = duration;
+        self
+    }
+
+    /// Sets desired latitude (in degrees) of the observer.
+    pub fn with_latitude(mut self, latitude: f32) -> Self {
+        self.latitude = latitude;
+        self
+    }
+
+    /// Sets desired rotation (in degrees) of the path of the sun around Y axis.
+    pub fn with_north_angle(mut self, angle: f32) -> Self {
+        self.north_angle = angle;
+        self
+    }
+
+    /// Sets desired directional light, that will be driven by the sky.
+    pub fn with_sun(mut self, sun: Handle<Node>) -> Self {
+        self.sun = sun;
+        self
+    }
+
+    /// Sets desired intensity of the sun light at noon.
+    pub fn with_sun_intensity(mut self, intensity: f32) -> Self {
+        self.sun_intensity = intensity;
+        self
+    }
+
+    /// Sets desired brightness of the sun light, that is scattered in the atmosphere.
+    pub fn with_sun_radiance(mut self, radiance: f32) -> Self {
+        self.sun_radiance = radiance;
+        self
+    }
+
+    /// Sets desired multipliers of Rayleigh and Mie scattering.
+    pub fn with_scattering(mut self, rayleigh: f32, mie: f32) -> Self {
+        self.rayleigh_scattering = rayleigh;
+        self.mie_scattering = mie;
+        self
+    }
+
+    /// Sets desired directionality of Mie scattering.
+    pub fn with_mie_directionality(mut self, directionality: f32) -> Self {
+        self.mie_directionality = directionality;
+        self
+    }
+
+    /// Sets desired exposure of the sky.
+    pub fn with_exposure(mut self, exposure: f32) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    /// Sets desired minimal color of the sky.
+    pub fn with_night_color(mut self, color: Color) -> Self {
+        self.night_color = color;
+        self
+    }
+
+    /// Sets desired settings of the cloud layer.
+    pub fn with_clouds(mut self, clouds: SkyClouds) -> Self {
+        self.clouds = clouds;
+        self
+    }
+
+    /// Sets desired size (in pixels) of a face of the sky cube map.
+    pub fn with_resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Sets desired minimal time (in seconds) between updates of the sky cube map.
+    pub fn with_update_interval(mut self, interval: f32) -> Self {
+        self.update_interval = interval;
+        self
+    }
+
+    /// Sets whether the ambient lighting of the scene should be calculated from the sky or not.
+    pub fn with_update_ambient(mut self, enabled: bool) -> Self {
+        self.update_ambient = enabled;
+        self
+    }
+
+    /// Sets desired multiplier of the ambient lighting calculated from the sky.
+    pub fn with_ambient_intensity(mut self, intensity: f32) -> Self {
+        self.ambient_intensity = intensity;
+        self
+    }
+
+    /// Sets whether the sky should replace skyboxes and environment maps of cameras or not.
+    pub fn with_update_environment(mut self, enabled: bool) -> Self {
+        self.update_environment = enabled;
+        self
+    }
+
+    /// Creates new sky.
+    pub fn build_sky(self) -> Sky {
+        Sky {
+            base: self.base_builder.build_base(),
+            time_of_day: self.time_of_day.rem_euclid(24.0).into(),
+            day_duration: self.day_duration.max(0.0).into(),
+            latitude: self.latitude.clamp(-90.0, 90.0).into(),
+            north_angle: self.north_angle.into(),
+            sun: self.sun.into(),
+            sun_intensity: self.sun_intensity.into(),
+            sun_radiance: self.sun_radiance.into(),
+            rayleigh_scattering: self.rayleigh_scattering.into(),
+            mie_scattering: self.mie_scattering.into(),
+            mie_directionality: self.mie_directionality.clamp(0.0, 0.999).into(),
+            exposure: self.exposure.into(),
+            night_color: self.night_color.into(),
+            clouds: self.clouds.into(),
+            resolution: self.resolution.max(1).into(),
+            update_interval: self.update_interval.max(0.0).into(),
+            update_ambient: self.update_ambient.into(),
+            ambient_intensity: self.ambient_intensity.into(),
+            update_environment: self.update_environment.into(),
+            cache: Default::default(),
+        }
+    }
+
+    /// Creates new sky node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_sky())
+    }
+
+    /// Creates new sky node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{base::BaseBuilder, sky::SkyBuilder},
+    };
+
+    #[test]
+    fn test_sky_sun_and_radiance() {
+        let mut sky = SkyBuilder::new(BaseBuilder::new())
+            .with_latitude(0.0)
+            .with_time_of_day(12.0)
+            .build_sky();
+
+        // The sun is at zenith at noon on the equator.
+        assert!((sky.sun_direction() - Vector3::y()).norm() < 1.0e-5);
+
+        // The sky at noon is blue.
+        let zenith = sky.radiance(Vector3::new(0.0, 1.0, 0.1).normalize());
+        assert!(zenith.z > zenith.x);
+
+        // Sunrise is at the horizon, midnight is below it.
+        sky.set_time_of_day(6.0);
+        assert!(sky.sun_direction().y.abs() < 1.0e-5);
+        sky.set_time_of_day(24.0);
+        assert_eq!(sky.time_of_day(), 0.0);
+        assert!(sky.sun_direction().y < -0.99);
+
+        // Nothing but the night color is left at midnight.
+        let night = sky.radiance(Vector3::y());
+        let night_color = sky.night_color().srgb_to_linear_f32().xyz();
+        assert!((night - night_color).norm() < 1.0e-3);
+
+        sky.refresh();
+        assert!(sky.skybox().is_some());
+        assert!(sky.ambient_color().is_some());
+    }
+}
@@ -2,11 +2,15 @@
 
 use crate::{
     core::{
+        algebra::Vector3,
         log::{Log, MessageKind},
         pool::Handle,
         visitor::prelude::*,
     },
-    scene::{node::Node, sound::Sound},
+    scene::{
+        node::Node,
+        sound::{reverb_zone, reverb_zone::ReverbZoneContribution, Sound},
+    },
 };
 use fxhash::FxHashSet;
 use fyrox_sound::{
@@ -22,6 +26,9 @@ use std::{sync::MutexGuard, time::Duration};
 pub struct SoundContext {
     #[visit(optional)]
     pub(crate) native: fyrox_sound::context::SoundContext,
+
+    #[visit(skip)]
+    pub(crate) reverb_zones: Vec<ReverbZoneContribution>,
 }
 
 /// Proxy for guarded access to the sound context.
@@ -60,6 +67,27 @@ impl<'a> SoundContextGuard<'a> {
         self.guard.distance_model()
     }
 
+    /// Sets new doppler factor. See [`fyrox_sound::context::State::set_doppler_factor`] for more info.
+    pub fn set_doppler_factor(&mut self, doppler_factor: f32) {
+        self.guard.set_doppler_factor(doppler_factor);
+    }
+
+    /// Returns current doppler factor.
+    pub fn doppler_factor(&self) -> f32 {
+        self.guard.doppler_factor()
+    }
+
+    /// Sets new speed of sound (in units per second). See [`fyrox_sound::context::State::set_speed_of_sound`]
+    /// for more info.
+    pub fn set_speed_of_sound(&mut self, speed_of_sound: f32) {
+        self.guard.set_speed_of_sound(speed_of_sound);
+    }
+
+    /// Returns current speed of sound.
+    pub fn speed_of_sound(&self) -> f32 {
+        self.guard.speed_of_sound()
+    }
+
     /// Normalizes given frequency using context's sampling rate. Normalized frequency then can be used
     /// to create filters.
     pub fn normalize_frequency(&self, f: f32) -> f32 {
@@ -104,7 +132,10 @@ impl Default for SoundContext {
         // There's no need to serialize native sources, because they'll be re-created automatically.
         state.serialization_options.skip_sources = true;
         drop(state);
-        Self { native }
+        Self {
+            native,
+            reverb_zones: Default::default(),
+        }
     }
 }
 
@@ -117,6 +148,7 @@ impl SoundContext {
     pub fn deep_clone(&self) -> Self {
         Self {
             native: self.native.deep_clone(),
+            reverb_zones: Default::default(),
         }
     }
 
@@ -127,6 +159,19 @@ impl SoundContext {
         }
     }
 
+    /// Blends reverberation parameters of every reverb zone, that was updated during the frame, and
+    /// applies them to the respective audio buses.
+    pub(crate) fn apply_reverb_zones(&mut self) {
+        if self.reverb_zones.is_empty() {
+            return;
+        }
+
+        let mut state = SoundContextGuard {
+            guard: self.native.state(),
+        };
+        reverb_zone::apply_reverb_zones(&mut self.reverb_zones, &mut state);
+    }
+
     pub(crate) fn remove_sound(&mut self, sound: Handle<SoundSource>, name: &str) {
         let mut state = self.native.state();
         if state.is_valid_handle(sound) {
@@ -145,6 +190,18 @@ impl SoundContext {
         }
     }
 
+    pub(crate) fn set_sound_velocity(&self, sound: &Sound, velocity: Vector3<f32>) {
+        if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
+            source.set_velocity(velocity);
+        }
+    }
+
+    pub(crate) fn set_sound_occlusion(&self, sound: &Sound, gain: f32, cutoff_frequency_hz: f32) {
+        if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
+            source.set_occlusion(gain, cutoff_frequency_hz);
+        }
+    }
+
     pub(crate) fn sync_with_sound(&self, sound: &mut Sound) {
         if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
             // Sync back.
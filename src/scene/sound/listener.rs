@@ -5,6 +5,7 @@
 
 use crate::{
     core::{
+        algebra::Vector3,
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
         reflect::prelude::*,
@@ -15,7 +16,7 @@ use crate::{
     scene::{
         base::{Base, BaseBuilder},
         graph::Graph,
-        node::{Node, NodeTrait, SyncContext},
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
     },
 };
 use std::ops::{Deref, DerefMut};
@@ -37,6 +38,10 @@ use std::ops::{Deref, DerefMut};
 #[derive(Visit, Reflect, Default, Clone, Debug)]
 pub struct Listener {
     base: Base,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    prev_position: Option<Vector3<f32>>,
 }
 
 impl Deref for Listener {
@@ -89,6 +94,28 @@ impl NodeTrait for Listener {
         native.set_position(self.global_position());
         native.set_orientation_lh(self.look_vector(), self.up_vector());
     }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        if !self.is_globally_enabled() {
+            return;
+        }
+
+        let position = self.global_position();
+        let velocity = match self.prev_position {
+            Some(prev_position) if context.dt > 0.0 => {
+                (position - prev_position).scale(1.0 / context.dt)
+            }
+            _ => Vector3::default(),
+        };
+        self.prev_position = Some(position);
+
+        context
+            .sound_context
+            .native
+            .state()
+            .listener_mut()
+            .set_velocity(velocity);
+    }
 }
 
 /// Allows you to create listener in declarative manner.
@@ -106,6 +133,7 @@ impl ListenerBuilder {
     pub fn build_listener(self) -> Listener {
         Listener {
             base: self.base_builder.build_base(),
+            prev_position: None,
         }
     }
 
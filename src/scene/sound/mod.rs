@@ -2,8 +2,9 @@
 
 use crate::{
     core::{
-        algebra::Matrix4,
-        math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
+        algebra::{Matrix4, Point3, Vector3},
+        arrayvec::ArrayVec,
+        math::{aabb::AxisAlignedBoundingBox, lerpf, m4x4_approx_eq},
         pool::Handle,
         reflect::prelude::*,
         uuid::{uuid, Uuid},
@@ -14,7 +15,11 @@ use crate::{
     define_with,
     scene::{
         base::{Base, BaseBuilder},
-        graph::Graph,
+        collider::{Collider, InteractionGroups},
+        graph::{
+            physics::{Intersection, PhysicsWorld, RayCastOptions},
+            Graph, NodePool,
+        },
         node::{Node, NodeTrait, SyncContext, UpdateContext},
     },
 };
@@ -47,6 +52,64 @@ use std::{
 
 pub mod context;
 pub mod listener;
+pub mod reverb_zone;
+
+/// Occlusion settings of a sound. When enabled, the engine casts a ray from the listener to the
+/// sound every frame and sums [`Collider::sound_absorption`] of every collider hit by the ray. The
+/// sum is then used to attenuate and muffle the sound, so it sounds like it is coming from behind
+/// an obstacle.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct SoundOcclusion {
+    /// Whether the occlusion is enabled or not. It is disabled by default, because raycasting
+    /// is not free.
+    pub enabled: bool,
+
+    /// Gain of the sound when it is fully occluded.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub occluded_gain: f32,
+
+    /// Cutoff frequency (in Hertz) of the low pass filter, that is applied to the sound when it
+    /// is fully occluded.
+    #[reflect(min_value = 0.0, step = 10.0)]
+    pub occluded_cutoff_frequency_hz: f32,
+
+    /// How fast (per second) the occlusion follows the changes in the environment. It prevents
+    /// abrupt changes of the volume when an obstacle appears between the sound and the listener.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub transition_speed: f32,
+
+    /// Collision groups that will be used to cast the occlusion ray.
+    pub collision_groups: InteractionGroups,
+}
+
+impl Default for SoundOcclusion {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            occluded_gain: 0.3,
+            occluded_cutoff_frequency_hz: 1000.0,
+            transition_speed: 4.0,
+            collision_groups: Default::default(),
+        }
+    }
+}
+
+impl SoundOcclusion {
+    // Cutoff frequency of the non-occluded sound, everything above is barely audible anyway.
+    const MAX_CUTOFF_FREQUENCY_HZ: f32 = 20000.0;
+
+    fn gain(&self, factor: f32) -> f32 {
+        lerpf(1.0, self.occluded_gain, factor)
+    }
+
+    fn cutoff_frequency_hz(&self, factor: f32) -> f32 {
+        // Interpolate in log space, because pitch perception is logarithmic.
+        let occluded = self
+            .occluded_cutoff_frequency_hz
+            .clamp(1.0, Self::MAX_CUTOFF_FREQUENCY_HZ);
+        Self::MAX_CUTOFF_FREQUENCY_HZ * (occluded / Self::MAX_CUTOFF_FREQUENCY_HZ).powf(factor)
+    }
+}
 
 /// Sound source.
 #[derive(Visit, Reflect, Debug)]
@@ -102,9 +165,24 @@ pub struct Sound {
     )]
     audio_bus: InheritableVariable<String>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion",
+        description = "Occlusion settings. Occluded sound is quieter and muffled."
+    )]
+    occlusion: InheritableVariable<SoundOcclusion>,
+
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) native: Cell<Handle<SoundSource>>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_factor: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    prev_position: Option<Vector3<f32>>,
 }
 
 impl Deref for Sound {
@@ -138,7 +216,10 @@ impl Default for Sound {
             playback_time: Default::default(),
             spatial_blend: InheritableVariable::new_modified(1.0),
             audio_bus: InheritableVariable::new_modified(AudioBusGraph::PRIMARY_BUS.to_string()),
+            occlusion: Default::default(),
             native: Default::default(),
+            occlusion_factor: 0.0,
+            prev_position: None,
         }
     }
 }
@@ -160,8 +241,11 @@ impl Clone for Sound {
             playback_time: self.playback_time.clone(),
             spatial_blend: self.spatial_blend.clone(),
             audio_bus: self.audio_bus.clone(),
+            occlusion: self.occlusion.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Default::default(),
+            occlusion_factor: self.occlusion_factor,
+            prev_position: self.prev_position,
         }
     }
 }
@@ -362,6 +446,118 @@ impl Sound {
     pub fn audio_bus(&self) -> &str {
         &self.audio_bus
     }
+
+    /// Sets new occlusion settings of the sound. See [`SoundOcclusion`] docs for more info.
+    pub fn set_occlusion(&mut self, occlusion: SoundOcclusion) -> SoundOcclusion {
+        self.occlusion.set_value_and_mark_modified(occlusion)
+    }
+
+    /// Returns current occlusion settings of the sound.
+    pub fn occlusion(&self) -> &SoundOcclusion {
+        &self.occlusion
+    }
+
+    /// Returns current occlusion factor of the sound, where `0.0` means that there are no obstacles
+    /// between the sound and the listener and `1.0` - the sound is fully occluded.
+    pub fn occlusion_factor(&self) -> f32 {
+        self.occlusion_factor
+    }
+
+    fn is_attached_to(&self, node: Handle<Node>, nodes: &NodePool) -> bool {
+        let mut ancestor = self.parent();
+        while let Some(ancestor_ref) = nodes.try_borrow(ancestor) {
+            if ancestor == node {
+                return true;
+            }
+            ancestor = ancestor_ref.parent();
+        }
+        false
+    }
+
+    fn calculate_occlusion(
+        &self,
+        listener_position: Vector3<f32>,
+        nodes: &NodePool,
+        physics: &PhysicsWorld,
+    ) -> f32 {
+        let direction = self.global_position() - listener_position;
+        let distance = direction.norm();
+        if distance <= f32::EPSILON {
+            return 0.0;
+        }
+
+        let mut intersections = ArrayVec::<Intersection, 32>::new();
+        physics.cast_ray(
+            RayCastOptions {
+                ray_origin: Point3::from(listener_position),
+                ray_direction: direction,
+                max_len: distance,
+                groups: self.occlusion.collision_groups,
+                sort_results: false,
+            },
+            &mut intersections,
+        );
+
+        let mut absorption = 0.0;
+        for intersection in intersections {
+            // Ray starts inside the collider, it is most likely the collider of the listener's
+            // owner (a player for example).
+            if intersection.toi <= f32::EPSILON {
+                continue;
+            }
+
+            if let Some(collider) = nodes
+                .try_borrow(intersection.collider)
+                .and_then(|n| n.cast::<Collider>())
+            {
+                if !collider.is_sensor() && !self.is_attached_to(collider.parent(), nodes) {
+                    absorption += collider.sound_absorption();
+                }
+            }
+        }
+        absorption.min(1.0)
+    }
+
+    fn update_velocity(&mut self, context: &mut UpdateContext) {
+        let position = self.global_position();
+        let velocity = match self.prev_position {
+            Some(prev_position) if context.dt > 0.0 => {
+                (position - prev_position).scale(1.0 / context.dt)
+            }
+            _ => Vector3::default(),
+        };
+        self.prev_position = Some(position);
+        context.sound_context.set_sound_velocity(self, velocity);
+    }
+
+    fn update_occlusion(&mut self, context: &mut UpdateContext) {
+        if !self.occlusion.enabled {
+            if self.occlusion_factor != 0.0 {
+                self.occlusion_factor = 0.0;
+                context
+                    .sound_context
+                    .set_sound_occlusion(self, 1.0, f32::MAX);
+            }
+            return;
+        }
+
+        let listener_position = context.sound_context.native.state().listener().position();
+        let target = self.calculate_occlusion(listener_position, context.nodes, context.physics);
+        let t = (self.occlusion.transition_speed * context.dt).min(1.0);
+        self.occlusion_factor += (target - self.occlusion_factor) * t;
+
+        if self.occlusion_factor <= 0.001 {
+            context
+                .sound_context
+                .set_sound_occlusion(self, 1.0, f32::MAX);
+        } else {
+            context.sound_context.set_sound_occlusion(
+                self,
+                self.occlusion.gain(self.occlusion_factor),
+                self.occlusion.cutoff_frequency_hz(self.occlusion_factor),
+            );
+        }
+    }
 }
 
 impl NodeTrait for Sound {
@@ -411,6 +607,8 @@ impl NodeTrait for Sound {
 
     fn update(&mut self, context: &mut UpdateContext) {
         context.sound_context.sync_with_sound(self);
+        self.update_velocity(context);
+        self.update_occlusion(context);
     }
 
     fn validate(&self, _scene: &Scene) -> Result<(), String> {
@@ -451,6 +649,7 @@ pub struct SoundBuilder {
     playback_time: Duration,
     spatial_blend: f32,
     audio_bus: String,
+    occlusion: SoundOcclusion,
 }
 
 impl SoundBuilder {
@@ -471,6 +670,7 @@ impl SoundBuilder {
             spatial_blend: 1.0,
             playback_time: Default::default(),
             audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            occlusion: Default::default(),
         }
     }
 
@@ -539,6 +739,11 @@ impl SoundBuilder {
         fn with_audio_bus(audio_bus: String)
     );
 
+    define_with!(
+        /// Sets desired occlusion settings. See [`Sound::set_occlusion`] for more info.
+        fn with_occlusion(occlusion: SoundOcclusion)
+    );
+
     /// Creates a new [`Sound`] node.
     #[must_use]
     pub fn build_sound(self) -> Sound {
@@ -557,7 +762,10 @@ impl SoundBuilder {
             playback_time: self.playback_time.as_secs_f32().into(),
             spatial_blend: self.spatial_blend.into(),
             audio_bus: self.audio_bus.into(),
+            occlusion: self.occlusion.into(),
             native: Default::default(),
+            occlusion_factor: 0.0,
+            prev_position: None,
         }
     }
 
@@ -0,0 +1,587 @@
+//! Reverb zone is a volume that defines reverberation parameters for the listener inside it.
+//!
+//! See [`ReverbZone`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector3},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    define_with,
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::SceneDrawingContext,
+        graph::Graph,
+        node::{Node, NodeTrait, UpdateContext},
+        sound::{context::SoundContextGuard, reverb::Reverb, AudioBusGraph, Effect, SAMPLE_RATE},
+    },
+};
+use fyrox_core::uuid_provider;
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Shape of a reverb zone. Its size is defined in local coordinates of the zone, which means that
+/// it will be affected by the scale of the node.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum ReverbZoneShape {
+    /// Box shape with the given half extents.
+    Box {
+        /// Half extents of the box along each axis.
+        half_extents: Vector3<f32>,
+    },
+    /// Sphere shape with the given radius.
+    Sphere {
+        /// Radius of the sphere.
+        radius: f32,
+    },
+}
+
+impl Default for ReverbZoneShape {
+    fn default() -> Self {
+        Self::Box {
+            half_extents: Vector3::repeat(5.0),
+        }
+    }
+}
+
+uuid_provider!(ReverbZoneShape = "0b5c9f4e-6a3d-4e1b-8c27-d9e4a1f3b6c8");
+
+impl ReverbZoneShape {
+    /// Returns a signed distance from the given local point to the surface of the shape, positive
+    /// values mean that the point is inside the shape.
+    fn inner_distance(&self, point: Vector3<f32>) -> f32 {
+        match self {
+            ReverbZoneShape::Box { half_extents } => (half_extents.x - point.x.abs())
+                .min(half_extents.y - point.y.abs())
+                .min(half_extents.z - point.z.abs()),
+            ReverbZoneShape::Sphere { radius } => radius - point.norm(),
+        }
+    }
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let half_extents = match self {
+            ReverbZoneShape::Box { half_extents } => *half_extents,
+            ReverbZoneShape::Sphere { radius } => Vector3::repeat(*radius),
+        };
+        AxisAlignedBoundingBox::from_min_max(-half_extents, half_extents)
+    }
+}
+
+/// A set of reverberation parameters, that could be blended with each other.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ReverbParameters {
+    decay_time: f32,
+    cutoff_frequency_hz: f32,
+    gain: f32,
+    dry: f32,
+}
+
+impl ReverbParameters {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            decay_time: lerp(self.decay_time, other.decay_time),
+            cutoff_frequency_hz: lerp(self.cutoff_frequency_hz, other.cutoff_frequency_hz),
+            gain: lerp(self.gain, other.gain),
+            dry: lerp(self.dry, other.dry),
+        }
+    }
+
+    fn apply(&self, reverb: &mut Reverb) {
+        if reverb.decay_time() != self.decay_time {
+            reverb.set_decay_time(self.decay_time);
+        }
+        let fc = self.cutoff_frequency_hz / SAMPLE_RATE as f32;
+        if reverb.fc() != fc {
+            reverb.set_fc(fc);
+        }
+        reverb.set_gain(self.gain);
+        reverb.set_dry(self.dry);
+    }
+}
+
+/// Influence of a single reverb zone on the listener, it is collected by every zone on each frame
+/// and then all of them are blended together.
+#[derive(Clone, Debug)]
+pub(crate) struct ReverbZoneContribution {
+    audio_bus: String,
+    priority: i32,
+    weight: f32,
+    parameters: ReverbParameters,
+}
+
+/// Blends every contribution collected during the frame and applies the result to the reverb
+/// effects of the respective audio buses.
+pub(crate) fn apply_reverb_zones(
+    contributions: &mut Vec<ReverbZoneContribution>,
+    state: &mut SoundContextGuard,
+) {
+    // Zones with higher priority are blended last, so they override the other zones.
+    contributions.sort_by_key(|c| c.priority);
+
+    let mut blended = Vec::<(String, ReverbParameters)>::new();
+    for contribution in contributions.drain(..) {
+        let index = match blended
+            .iter()
+            .position(|(bus, _)| *bus == contribution.audio_bus)
+        {
+            Some(index) => index,
+            None => {
+                // Outside of any zone there's no reverberation at all.
+                blended.push((
+                    contribution.audio_bus.clone(),
+                    ReverbParameters {
+                        gain: 0.0,
+                        dry: 1.0,
+                        ..contribution.parameters.clone()
+                    },
+                ));
+                blended.len() - 1
+            }
+        };
+
+        let parameters = &mut blended[index].1;
+        *parameters = parameters.lerp(&contribution.parameters, contribution.weight);
+    }
+
+    let graph = state.bus_graph_mut();
+    for (bus_name, parameters) in blended {
+        let Some(bus) = graph
+            .find_bus_by_name(&bus_name)
+            .and_then(|bus| graph.try_get_bus_mut(bus))
+        else {
+            continue;
+        };
+
+        if !bus.effects().any(|e| matches!(e, Effect::Reverb(_))) {
+            bus.add_effect(Effect::Reverb(Reverb::new()));
+        }
+
+        if let Some(Effect::Reverb(reverb)) =
+            bus.effects_mut().find(|e| matches!(e, Effect::Reverb(_)))
+        {
+            parameters.apply(reverb);
+        }
+    }
+}
+
+/// Reverb zone is a volume that defines reverberation parameters for the listener inside it. It
+/// allows you to make caves, halls, small rooms, etc. sound differently without any scripting.
+///
+/// # How it works
+///
+/// Each frame, every enabled reverb zone calculates its influence (weight) on the listener. The
+/// weight is `1.0` when the listener is deep inside the zone, it smoothly goes to `0.0` within
+/// [`ReverbZone::blend_distance`] from the zone's boundary and it is `0.0` outside of the zone.
+/// Parameters of every zone are then blended using the weights and applied to the first reverb
+/// effect of the audio bus with the name [`ReverbZone::audio_bus`]. If the bus does not have a
+/// reverb effect, it will be added automatically. Reverb effect of the bus is fully controlled by
+/// the zones, outside of any zone the reverberation is muted.
+///
+/// Zones could overlap each other, zones with higher [`ReverbZone::priority`] override the zones
+/// with lower priority. It could be used to create a small room inside a large hall, for example.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         graph::Graph,
+/// #         node::Node,
+/// #         sound::reverb_zone::{ReverbZoneBuilder, ReverbZoneShape},
+/// #     },
+/// # };
+/// fn create_cave(graph: &mut Graph) -> Handle<Node> {
+///     ReverbZoneBuilder::new(BaseBuilder::new().with_name("Cave"))
+///         .with_shape(ReverbZoneShape::Box {
+///             half_extents: Vector3::new(20.0, 5.0, 20.0),
+///         })
+///         .with_decay_time(6.0)
+///         .with_cutoff_frequency_hz(3000.0)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct ReverbZone {
+    base: Base,
+
+    #[reflect(setter = "set_shape")]
+    shape: InheritableVariable<ReverbZoneShape>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_blend_distance")]
+    blend_distance: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_priority")]
+    priority: InheritableVariable<i32>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_decay_time")]
+    decay_time: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 100.0)]
+    #[reflect(setter = "set_cutoff_frequency_hz")]
+    cutoff_frequency_hz: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(setter = "set_gain")]
+    gain: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    #[reflect(setter = "set_dry")]
+    dry: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_audio_bus")]
+    audio_bus: InheritableVariable<String>,
+}
+
+impl Deref for ReverbZone {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for ReverbZone {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for ReverbZone {
+    fn default() -> Self {
+        ReverbZoneBuilder::new(BaseBuilder::new()).build_reverb_zone()
+    }
+}
+
+impl TypeUuidProvider for ReverbZone {
+    fn type_uuid() -> Uuid {
+        uuid!("e3f6c2a9-1b4d-4d7e-a05c-8f2b9d6e4a71")
+    }
+}
+
+impl ReverbZone {
+    /// Sets new shape of the zone.
+    pub fn set_shape(&mut self, shape: ReverbZoneShape) -> ReverbZoneShape {
+        self.shape.set_value_and_mark_modified(shape)
+    }
+
+    /// Returns current shape of the zone.
+    pub fn shape(&self) -> &ReverbZoneShape {
+        &self.shape
+    }
+
+    /// Sets the distance from the boundary of the zone at which its influence reaches the maximum.
+    /// Larger values give smoother transitions between zones.
+    pub fn set_blend_distance(&mut self, distance: f32) -> f32 {
+        self.blend_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns the blend distance of the zone.
+    pub fn blend_distance(&self) -> f32 {
+        *self.blend_distance
+    }
+
+    /// Sets priority of the zone. Zones with higher priority override zones with lower priority.
+    pub fn set_priority(&mut self, priority: i32) -> i32 {
+        self.priority.set_value_and_mark_modified(priority)
+    }
+
+    /// Returns priority of the zone.
+    pub fn priority(&self) -> i32 {
+        *self.priority
+    }
+
+    /// Sets reverberation decay time (in seconds). The larger the environment, the longer the decay
+    /// time should be.
+    pub fn set_decay_time(&mut self, decay_time: f32) -> f32 {
+        self.decay_time
+            .set_value_and_mark_modified(decay_time.max(0.0))
+    }
+
+    /// Returns reverberation decay time (in seconds).
+    pub fn decay_time(&self) -> f32 {
+        *self.decay_time
+    }
+
+    /// Sets cutoff frequency (in Hertz) of reflections. Lower values make reflections more muffled,
+    /// which could be used to simulate environments with high absorption of high frequencies.
+    pub fn set_cutoff_frequency_hz(&mut self, frequency: f32) -> f32 {
+        self.cutoff_frequency_hz
+            .set_value_and_mark_modified(frequency.max(0.0))
+    }
+
+    /// Returns cutoff frequency (in Hertz) of reflections.
+    pub fn cutoff_frequency_hz(&self) -> f32 {
+        *self.cutoff_frequency_hz
+    }
+
+    /// Sets the gain of the reverberated part of the signal.
+    pub fn set_gain(&mut self, gain: f32) -> f32 {
+        self.gain.set_value_and_mark_modified(gain.max(0.0))
+    }
+
+    /// Returns the gain of the reverberated part of the signal.
+    pub fn gain(&self) -> f32 {
+        *self.gain
+    }
+
+    /// Sets how much of the input signal should be passed to the output without any processing.
+    pub fn set_dry(&mut self, dry: f32) -> f32 {
+        self.dry.set_value_and_mark_modified(dry.clamp(0.0, 1.0))
+    }
+
+    /// Returns how much of the input signal is passed to the output without any processing.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets the name of the audio bus, whose reverb effect is controlled by the zone.
+    pub fn set_audio_bus(&mut self, audio_bus: String) -> String {
+        self.audio_bus.set_value_and_mark_modified(audio_bus)
+    }
+
+    /// Returns the name of the audio bus, whose reverb effect is controlled by the zone.
+    pub fn audio_bus(&self) -> &str {
+        &self.audio_bus
+    }
+
+    /// Calculates influence of the zone at the given point in world coordinates. Returns a value in
+    /// `[0.0; 1.0]` range.
+    pub fn weight_at(&self, point: Vector3<f32>) -> f32 {
+        let Some(inv_transform) = self.global_transform().try_inverse() else {
+            return 0.0;
+        };
+        let local_point = inv_transform.transform_point(&Point3::from(point)).coords;
+        let distance = self.shape.inner_distance(local_point);
+        if distance < 0.0 {
+            0.0
+        } else if *self.blend_distance <= 0.0 {
+            1.0
+        } else {
+            (distance / *self.blend_distance).min(1.0)
+        }
+    }
+}
+
+impl NodeTrait for ReverbZone {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.shape.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        let listener_position = context.sound_context.native.state().listener().position();
+
+        context
+            .sound_context
+            .reverb_zones
+            .push(ReverbZoneContribution {
+                audio_bus: (*self.audio_bus).clone(),
+                priority: *self.priority,
+                weight: self.weight_at(listener_position),
+                parameters: ReverbParameters {
+                    decay_time: *self.decay_time,
+                    cutoff_frequency_hz: *self.cutoff_frequency_hz,
+                    gain: *self.gain,
+                    dry: *self.dry,
+                },
+            });
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        let color = Color::opaque(120, 170, 255);
+        match *self.shape {
+            ReverbZoneShape::Box { half_extents } => ctx.draw_oob(
+                &AxisAlignedBoundingBox::from_min_max(-half_extents, half_extents),
+                self.global_transform(),
+                color,
+            ),
+            ReverbZoneShape::Sphere { radius } => {
+                ctx.draw_wire_sphere(self.global_position(), radius, 24, color)
+            }
+        }
+    }
+}
+
+/// Allows you to create a [`ReverbZone`] in declarative manner.
+pub struct ReverbZoneBuilder {
+    base_builder: BaseBuilder,
+    shape: ReverbZoneShape,
+    blend_distance: f32,
+    priority: i32,
+    decay_time: f32,
+    cutoff_frequency_hz: f32,
+    gain: f32,
+    dry: f32,
+    audio_bus: String,
+}
+
+impl ReverbZoneBuilder {
+    /// Creates new reverb zone builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            shape: Default::default(),
+            blend_distance: 2.0,
+            priority: 0,
+            decay_time: 2.0,
+            cutoff_frequency_hz: 11296.0,
+            gain: 1.0,
+            dry: 1.0,
+            audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+        }
+    }
+
+    define_with!(
+        /// Sets desired shape. See [`ReverbZone::set_shape`] for more info.
+        fn with_shape(shape: ReverbZoneShape)
+    );
+
+    define_with!(
+        /// Sets desired blend distance. See [`ReverbZone::set_blend_distance`] for more info.
+        fn with_blend_distance(blend_distance: f32)
+    );
+
+    define_with!(
+        /// Sets desired priority. See [`ReverbZone::set_priority`] for more info.
+        fn with_priority(priority: i32)
+    );
+
+    define_with!(
+        /// Sets desired decay time. See [`ReverbZone::set_decay_time`] for more info.
+        fn with_decay_time(decay_time: f32)
+    );
+
+    define_with!(
+        /// Sets desired cutoff frequency. See [`ReverbZone::set_cutoff_frequency_hz`] for more info.
+        fn with_cutoff_frequency_hz(cutoff_frequency_hz: f32)
+    );
+
+    define_with!(
+        /// Sets desired gain. See [`ReverbZone::set_gain`] for more info.
+        fn with_gain(gain: f32)
+    );
+
+    define_with!(
+        /// Sets desired dry part. See [`ReverbZone::set_dry`] for more info.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired audio bus. See [`ReverbZone::set_audio_bus`] for more info.
+        fn with_audio_bus(audio_bus: String)
+    );
+
+    /// Creates a new [`ReverbZone`] instance.
+    #[must_use]
+    pub fn build_reverb_zone(self) -> ReverbZone {
+        ReverbZone {
+            base: self.base_builder.build_base(),
+            shape: self.shape.into(),
+            blend_distance: self.blend_distance.into(),
+            priority: self.priority.into(),
+            decay_time: self.decay_time.into(),
+            cutoff_frequency_hz: self.cutoff_frequency_hz.into(),
+            gain: self.gain.into(),
+            dry: self.dry.into(),
+            audio_bus: self.audio_bus.into(),
+        }
+    }
+
+    /// Creates a new [`ReverbZone`] node.
+    #[must_use]
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_reverb_zone())
+    }
+
+    /// Creates a new [`ReverbZone`] node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            sound::{
+                listener::ListenerBuilder,
+                reverb_zone::{ReverbZone, ReverbZoneBuilder, ReverbZoneShape},
+                Effect,
+            },
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_reverb_zone_weight() {
+        let mut graph = Graph::new();
+        let zone = ReverbZoneBuilder::new(BaseBuilder::new())
+            .with_shape(ReverbZoneShape::Sphere { radius: 4.0 })
+            .with_blend_distance(2.0)
+            .build(&mut graph);
+        graph.update_hierarchical_data();
+
+        let zone = graph[zone].query_component_ref::<ReverbZone>().unwrap();
+        assert_eq!(zone.weight_at(Vector3::new(0.0, 0.0, 0.0)), 1.0);
+        assert_eq!(zone.weight_at(Vector3::new(3.0, 0.0, 0.0)), 0.5);
+        assert_eq!(zone.weight_at(Vector3::new(5.0, 0.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reverb_zone_blending() {
+        let mut graph = Graph::new();
+        ReverbZoneBuilder::new(BaseBuilder::new())
+            .with_decay_time(5.0)
+            .with_blend_distance(0.0)
+            .build(&mut graph);
+        ListenerBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 0.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+
+        graph.update(Default::default(), 1.0 / 60.0, Default::default());
+
+        let state = graph.sound_context.state();
+        let reverb = state
+            .bus_graph_ref()
+            .primary_bus_ref()
+            .effects()
+            .find_map(|e| match e {
+                Effect::Reverb(reverb) => Some(reverb),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(reverb.decay_time(), 5.0);
+        assert_eq!(reverb.gain(), 1.0);
+    }
+}
@@ -0,0 +1,701 @@
+//! Spline is a smooth curve defined by a set of control points. It could be used as a path for
+//! roads, rivers, fences, camera rails, etc. See [`Spline`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        uuid_provider,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    graph::SceneGraph,
+    material::MaterialResource,
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        mesh::{
+            buffer::{TriangleBuffer, VertexBuffer},
+            surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+            vertex::StaticVertex,
+            MeshBuilder,
+        },
+        node::{Node, NodeTrait},
+        terrain::Terrain,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// A control point of a spline.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct SplinePoint {
+    /// Local position of the point.
+    pub position: Vector3<f32>,
+    /// Local tangent of the curve at the point. It defines the direction of the curve and how far
+    /// the curve "goes" in this direction before turning to the next point.
+    pub tangent: Vector3<f32>,
+}
+
+uuid_provider!(SplinePoint = "5e0c2c1f-7a0b-4b8e-9f33-0d1f6a2b4c57");
+
+impl SplinePoint {
+    /// Creates new control point with the given position and zero tangent. Use
+    /// [`Spline::calculate_tangents`] to calculate smooth tangents for every point.
+    pub fn new(position: Vector3<f32>) -> Self {
+        Self {
+            position,
+            tangent: Default::default(),
+        }
+    }
+}
+
+/// A point on a spline.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SplineSample {
+    /// Local position of the point.
+    pub position: Vector3<f32>,
+    /// Normalized local direction of the curve at the point.
+    pub direction: Vector3<f32>,
+    /// Distance along the curve from its beginning to the point.
+    pub distance: f32,
+}
+
+fn hermite(
+    p0: &Vector3<f32>,
+    m0: &Vector3<f32>,
+    p1: &Vector3<f32>,
+    m1: &Vector3<f32>,
+    t: f32,
+) -> Vector3<f32> {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    p0.scale(2.0 * t3 - 3.0 * t2 + 1.0)
+        + m0.scale(t3 - 2.0 * t2 + t)
+        + p1.scale(-2.0 * t3 + 3.0 * t2)
+        + m1.scale(t3 - t2)
+}
+
+fn hermite_derivative(
+    p0: &Vector3<f32>,
+    m0: &Vector3<f32>,
+    p1: &Vector3<f32>,
+    m1: &Vector3<f32>,
+    t: f32,
+) -> Vector3<f32> {
+    let t2 = t * t;
+    p0.scale(6.0 * t2 - 6.0 * t)
+        + m0.scale(3.0 * t2 - 4.0 * t + 1.0)
+        + p1.scale(6.0 * t - 6.0 * t2)
+        + m1.scale(3.0 * t2 - 2.0 * t)
+}
+
+/// Spline is a smooth curve (cubic Hermite spline), that goes through a set of control points.
+/// Shape of the curve between two points is defined by the tangents at these points. Tangents
+/// could be set manually, or calculated automatically using [`Spline::calculate_tangents`].
+///
+/// The curve is defined in local coordinates of the node, so it could be moved, rotated and scaled
+/// as any other node.
+///
+/// ## Roads
+///
+/// Spline could be used to create roads - see [`build_road`] function. It flattens terrains along
+/// the spline and creates a mesh with a strip of the desired width.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         graph::Graph,
+/// #         node::Node,
+/// #         spline::{SplineBuilder, SplinePoint},
+/// #     },
+/// # };
+/// fn create_spline(graph: &mut Graph) -> Handle<Node> {
+///     SplineBuilder::new(BaseBuilder::new())
+///         .with_points(vec![
+///             SplinePoint::new(Vector3::new(0.0, 0.0, 0.0)),
+///             SplinePoint::new(Vector3::new(10.0, 0.0, 5.0)),
+///             SplinePoint::new(Vector3::new(20.0, 0.0, 0.0)),
+///         ])
+///         .with_auto_tangents()
+///         .build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct Spline {
+    base: Base,
+
+    #[reflect(setter = "set_points")]
+    points: InheritableVariable<Vec<SplinePoint>>,
+
+    #[reflect(
+        setter = "set_closed",
+        description = "Whether the last point of the spline is connected with the first one or not."
+    )]
+    closed: InheritableVariable<bool>,
+}
+
+impl Deref for Spline {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Spline {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for Spline {
+    fn default() -> Self {
+        SplineBuilder::new(BaseBuilder::new()).build_spline()
+    }
+}
+
+impl TypeUuidProvider for Spline {
+    fn type_uuid() -> Uuid {
+        uuid!("8c6f1d3e-2b7a-4e59-a1c4-5d9e0f3b7a62")
+    }
+}
+
+impl Spline {
+    /// Sets new control points of the spline and returns the old ones.
+    pub fn set_points(&mut self, points: Vec<SplinePoint>) -> Vec<SplinePoint> {
+        self.points.set_value_and_mark_modified(points)
+    }
+
+    /// Returns a reference to the control points of the spline.
+    pub fn points(&self) -> &[SplinePoint] {
+        &self.points
+    }
+
+    /// Returns a mutable reference to the control points of the spline.
+    pub fn points_mut(&mut self) -> &mut Vec<SplinePoint> {
+        self.points.get_value_mut_and_mark_modified()
+    }
+
+    /// Defines whether the last point of the spline is connected with the first one or not.
+    pub fn set_closed(&mut self, closed: bool) -> bool {
+        self.closed.set_value_and_mark_modified(closed)
+    }
+
+    /// Returns `true` if the last point of the spline is connected with the first one.
+    pub fn is_closed(&self) -> bool {
+        *self.closed
+    }
+
+    /// Returns the amount of segments (curves between two adjacent points) of the spline.
+    pub fn segment_count(&self) -> usize {
+        match self.points.len() {
+            0 | 1 => 0,
+            n if *self.closed => n,
+            n => n - 1,
+        }
+    }
+
+    fn segment(&self, index: usize) -> (&SplinePoint, &SplinePoint) {
+        (
+            &self.points[index],
+            &self.points[(index + 1) % self.points.len()],
+        )
+    }
+
+    /// Calculates smooth (Catmull-Rom) tangents for every control point of the spline.
+    pub fn calculate_tangents(&mut self) {
+        let count = self.points.len();
+        if count < 2 {
+            return;
+        }
+
+        let closed = *self.closed;
+        let positions = self.points.iter().map(|p| p.position).collect::<Vec<_>>();
+        for (i, point) in self
+            .points
+            .get_value_mut_and_mark_modified()
+            .iter_mut()
+            .enumerate()
+        {
+            let (prev, next) = if closed {
+                ((i + count - 1) % count, (i + 1) % count)
+            } else {
+                (i.saturating_sub(1), (i + 1).min(count - 1))
+            };
+            let scale = if prev + 2 == next || closed { 0.5 } else { 1.0 };
+            point.tangent = (positions[next] - positions[prev]).scale(scale);
+        }
+    }
+
+    /// Calculates a local position on the spline. `t` is a parameter in `[0; segment_count]`
+    /// range, where the integer part defines a segment and the fractional part - a position
+    /// on the segment.
+    pub fn position(&self, t: f32) -> Option<Vector3<f32>> {
+        let (segment, t) = self.locate(t)?;
+        let (a, b) = self.segment(segment);
+        Some(hermite(&a.position, &a.tangent, &b.position, &b.tangent, t))
+    }
+
+    /// Calculates a local (non-normalized) derivative of the spline. See [`Self::position`] for
+    /// more info about `t` parameter.
+    pub fn derivative(&self, t: f32) -> Option<Vector3<f32>> {
+        let (segment, t) = self.locate(t)?;
+        let (a, b) = self.segment(segment);
+        Some(hermite_derivative(
+            &a.position,
+            &a.tangent,
+            &b.position,
+            &b.tangent,
+            t,
+        ))
+    }
+
+    fn locate(&self, t: f32) -> Option<(usize, f32)> {
+        let segment_count = self.segment_count();
+        if segment_count == 0 {
+            return None;
+        }
+        let t = t.clamp(0.0, segment_count as f32);
+        let segment = (t.floor() as usize).min(segment_count - 1);
+        Some((segment, t - segment as f32))
+    }
+
+    /// Samples the spline in local coordinates. Every segment of the spline is split into
+    /// `resolution` parts, so the returned array has `segment_count * resolution + 1` samples.
+    pub fn samples(&self, resolution: usize) -> Vec<SplineSample> {
+        let resolution = resolution.max(1);
+        let segment_count = self.segment_count();
+        if segment_count == 0 {
+            return Default::default();
+        }
+
+        let mut samples = Vec::with_capacity(segment_count * resolution + 1);
+        let mut distance = 0.0;
+        for i in 0..=segment_count * resolution {
+            let t = i as f32 / resolution as f32;
+            let position = self.position(t).unwrap_or_default();
+            if let Some(last) = samples.last() {
+                let last: &SplineSample = last;
+                distance += last.position.metric_distance(&position);
+            }
+            let direction = self
+                .derivative(t)
+                .and_then(|d| d.try_normalize(f32::EPSILON))
+                .unwrap_or_else(Vector3::z);
+            samples.push(SplineSample {
+                position,
+                direction,
+                distance,
+            });
+        }
+        samples
+    }
+
+    /// Calculates approximate length of the spline in local coordinates.
+    pub fn length(&self) -> f32 {
+        self.samples(16)
+            .last()
+            .map(|s| s.distance)
+            .unwrap_or_default()
+    }
+
+    /// Calculates local transforms along the spline with the given spacing between them. Every
+    /// transform is oriented along the spline (its local Z axis looks along the spline). It could
+    /// be used to place objects (fence posts, street lights, etc.) along the spline.
+    pub fn transforms_along(&self, spacing: f32) -> Vec<Matrix4<f32>> {
+        let samples = self.samples(32);
+        let spacing = spacing.max(f32::EPSILON);
+
+        let mut transforms = Vec::new();
+        let mut next_distance = 0.0;
+        for pair in samples.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            while next_distance <= b.distance {
+                let length = b.distance - a.distance;
+                let k = if length > 0.0 {
+                    (next_distance - a.distance) / length
+                } else {
+                    0.0
+                };
+                let position = a.position.lerp(&b.position, k);
+                let direction = a.direction.lerp(&b.direction, k);
+                let rotation = UnitQuaternion::face_towards(&direction, &Vector3::y());
+                transforms.push(Matrix4::new_translation(&position) * rotation.to_homogeneous());
+                next_distance += spacing;
+            }
+        }
+        transforms
+    }
+}
+
+impl NodeTrait for Spline {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_points(
+            &self
+                .samples(8)
+                .iter()
+                .map(|s| s.position)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        draw_spline(self, ctx, Color::ORANGE);
+    }
+}
+
+/// Draws the curve of the given spline and its control points.
+pub fn draw_spline(spline: &Spline, ctx: &mut SceneDrawingContext, color: Color) {
+    let transform = spline.global_transform();
+    let world = |p: &Vector3<f32>| transform.transform_point(&Point3::from(*p)).coords;
+
+    for pair in spline.samples(16).windows(2) {
+        ctx.add_line(Line {
+            begin: world(&pair[0].position),
+            end: world(&pair[1].position),
+            color,
+        });
+    }
+
+    for point in spline.points.iter() {
+        ctx.draw_wire_sphere(world(&point.position), 0.1, 8, color);
+    }
+}
+
+/// Allows you to create splines in declarative manner.
+pub struct SplineBuilder {
+    base_builder: BaseBuilder,
+    points: Vec<SplinePoint>,
+    closed: bool,
+    auto_tangents: bool,
+}
+
+impl SplineBuilder {
+    /// Creates new spline builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            points: Default::default(),
+            closed: false,
+            auto_tangents: false,
+        }
+    }
+
+    /// Sets the desired control points of the spline.
+    pub fn with_points(mut self, points: Vec<SplinePoint>) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// Sets whether the last point of the spline is connected with the first one or not.
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Calculates smooth tangents for every control point, see [`Spline::calculate_tangents`].
+    pub fn with_auto_tangents(mut self) -> Self {
+        self.auto_tangents = true;
+        self
+    }
+
+    fn build_spline(self) -> Spline {
+        let mut spline = Spline {
+            base: self.base_builder.build_base(),
+            points: self.points.into(),
+            closed: self.closed.into(),
+        };
+        if self.auto_tangents {
+            spline.calculate_tangents();
+        }
+        spline
+    }
+
+    /// Creates new spline node, but does not add it to a graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_spline())
+    }
+
+    /// Creates new spline node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+/// Parameters of a road. See [`build_road`] for more info.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct RoadSettings {
+    /// Width of the road in meters.
+    #[reflect(min_value = 0.0)]
+    pub width: f32,
+    /// Width of the smooth transition between the flattened terrain under the road and the rest of
+    /// the terrain, in meters.
+    #[reflect(min_value = 0.0)]
+    pub falloff: f32,
+    /// Offset of the road surface above the flattened terrain, it is used to prevent z-fighting.
+    pub height_offset: f32,
+    /// Length of the road (in meters) covered by a single repetition of the road texture.
+    #[reflect(min_value = 0.001)]
+    pub uv_tiling: f32,
+    /// Amount of parts every segment of the spline is split into.
+    #[reflect(min_value = 1.0, step = 1.0)]
+    pub resolution: usize,
+}
+
+uuid_provider!(RoadSettings = "b3e5a7c9-1d2f-4a6b-8c0e-9f7d5b3a1c24");
+
+impl Default for RoadSettings {
+    fn default() -> Self {
+        Self {
+            width: 4.0,
+            falloff: 2.0,
+            height_offset: 0.05,
+            uv_tiling: 4.0,
+            resolution: 16,
+        }
+    }
+}
+
+fn side_vector(direction: &Vector3<f32>) -> Vector3<f32> {
+    direction
+        .cross(&Vector3::y())
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::x)
+}
+
+/// Flattens the height map of the terrain along the spline. The heights under the road are set
+/// to the heights of the spline, and smoothly blended with the original heights in the falloff
+/// zone. Returns `false` if the terrain was not modified.
+pub fn flatten_terrain(spline: &Spline, terrain: &mut Terrain, settings: &RoadSettings) -> bool {
+    let Some(inv_terrain_transform) = terrain.global_transform().try_inverse() else {
+        return false;
+    };
+    // Samples in local coordinates of the terrain.
+    let spline_to_terrain = inv_terrain_transform * spline.global_transform();
+    let points = spline
+        .samples(settings.resolution)
+        .iter()
+        .map(|s| {
+            spline_to_terrain
+                .transform_point(&Point3::from(s.position))
+                .coords
+        })
+        .collect::<Vec<_>>();
+    if points.len() < 2 {
+        return false;
+    }
+
+    let half_width = settings.width * 0.5;
+    let max_distance = half_width + settings.falloff;
+
+    let mut modified = false;
+    terrain.for_each_height_map_pixel(|pixel, position| {
+        let mut closest_distance = f32::MAX;
+        let mut closest_height = 0.0;
+        for pair in points.windows(2) {
+            let a = Vector2::new(pair[0].x, pair[0].z);
+            let b = Vector2::new(pair[1].x, pair[1].z);
+            let ab = b - a;
+            let length_squared = ab.norm_squared();
+            let k = if length_squared > 0.0 {
+                ((position - a).dot(&ab) / length_squared).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let distance = position.metric_distance(&(a + ab.scale(k)));
+            if distance < closest_distance {
+                closest_distance = distance;
+                closest_height = pair[0].y + (pair[1].y - pair[0].y) * k;
+            }
+        }
+
+        if closest_distance > max_distance {
+            return;
+        }
+
+        let weight = if closest_distance <= half_width || settings.falloff <= 0.0 {
+            1.0
+        } else {
+            let k = 1.0 - (closest_distance - half_width) / settings.falloff;
+            // Smoothstep for softer transition.
+            k * k * (3.0 - 2.0 * k)
+        };
+
+        *pixel += (closest_height - *pixel) * weight;
+        modified = true;
+    });
+
+    modified
+}
+
+/// Creates a surface of a road along the spline. The surface is a strip of triangles in local
+/// coordinates of the spline. Texture coordinates go from 0 to 1 across the road and repeat every
+/// [`RoadSettings::uv_tiling`] meters along the road.
+pub fn make_road_surface(spline: &Spline, settings: &RoadSettings) -> SurfaceData {
+    let samples = spline.samples(settings.resolution);
+    let half_width = settings.width * 0.5;
+    let up = Vector3::new(0.0, settings.height_offset, 0.0);
+
+    let mut vertices = Vec::with_capacity(samples.len() * 2);
+    for sample in samples.iter() {
+        let side = side_vector(&sample.direction).scale(half_width);
+        let v = sample.distance / settings.uv_tiling.max(0.001);
+        for (offset, u) in [(-side, 0.0), (side, 1.0)] {
+            vertices.push(StaticVertex {
+                position: sample.position + offset + up,
+                tex_coord: Vector2::new(u, v),
+                // Normals and tangents will be calculated later.
+                normal: Default::default(),
+                tangent: Default::default(),
+            });
+        }
+    }
+
+    let mut triangles = Vec::new();
+    for i in 0..samples.len().saturating_sub(1) as u32 {
+        let (l0, r0, l1, r1) = (i * 2, i * 2 + 1, i * 2 + 2, i * 2 + 3);
+        triangles.push(TriangleDefinition([l0, l1, r1]));
+        triangles.push(TriangleDefinition([r1, r0, l0]));
+    }
+
+    let mut data = SurfaceData::new(
+        VertexBuffer::new(vertices.len(), vertices).unwrap(),
+        TriangleBuffer::new(triangles),
+        false,
+    );
+    data.calculate_normals().unwrap();
+    data.calculate_tangents().unwrap();
+    data
+}
+
+/// Creates a road along the spline. It flattens every enabled terrain of the graph along the
+/// spline (see [`flatten_terrain`]) and creates a mesh node with the road surface (see
+/// [`make_road_surface`]) as a child of the spline. Returns a handle of the road mesh.
+pub fn build_road(
+    graph: &mut Graph,
+    spline: Handle<Node>,
+    settings: &RoadSettings,
+    material: MaterialResource,
+) -> Handle<Node> {
+    let Some(spline_ref) = graph.try_get_of_type::<Spline>(spline) else {
+        return Handle::NONE;
+    };
+    let spline_ref = spline_ref.clone();
+
+    let terrains = graph
+        .pair_iter()
+        .filter(|(_, n)| n.is_globally_enabled() && n.cast::<Terrain>().is_some())
+        .map(|(h, _)| h)
+        .collect::<Vec<_>>();
+    for terrain in terrains {
+        if let Some(terrain) = graph.try_get_mut_of_type::<Terrain>(terrain) {
+            flatten_terrain(&spline_ref, terrain, settings);
+        }
+    }
+
+    let road = make_road_mesh(&spline_ref, settings, material).build(graph);
+    graph.link_nodes(road, spline);
+    road
+}
+
+/// Creates a builder of a road mesh along the spline, see [`make_road_surface`] for more info.
+/// The mesh is meant to be a child of the spline.
+pub fn make_road_mesh(
+    spline: &Spline,
+    settings: &RoadSettings,
+    material: MaterialResource,
+) -> MeshBuilder {
+    let data = make_road_surface(spline, settings);
+    MeshBuilder::new(BaseBuilder::new().with_name("Road")).with_surfaces(vec![SurfaceBuilder::new(
+        SurfaceSharedData::new(data),
+    )
+    .with_material(material)
+    .build()])
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            spline::{flatten_terrain, RoadSettings, SplineBuilder, SplinePoint},
+            terrain::TerrainBuilder,
+        },
+    };
+
+    #[test]
+    fn test_spline_sampling() {
+        let spline = SplineBuilder::new(BaseBuilder::new())
+            .with_points(vec![
+                SplinePoint::new(Vector3::new(0.0, 0.0, 0.0)),
+                SplinePoint::new(Vector3::new(10.0, 0.0, 0.0)),
+                SplinePoint::new(Vector3::new(20.0, 0.0, 0.0)),
+            ])
+            .with_auto_tangents()
+            .build_spline();
+
+        assert_eq!(spline.segment_count(), 2);
+        assert_eq!(spline.position(0.0), Some(Vector3::new(0.0, 0.0, 0.0)));
+        assert_eq!(spline.position(2.0), Some(Vector3::new(20.0, 0.0, 0.0)));
+
+        let samples = spline.samples(4);
+        assert_eq!(samples.len(), 9);
+        assert!((spline.length() - 20.0).abs() < 0.01);
+        assert_eq!(spline.transforms_along(6.0).len(), 4);
+    }
+
+    #[test]
+    fn test_flatten_terrain() {
+        let mut node = TerrainBuilder::new(BaseBuilder::new())
+            .with_chunk_size(Vector2::new(16.0, 16.0))
+            .with_width_chunks(0..1)
+            .with_length_chunks(0..1)
+            .with_height_map_size(Vector2::new(17, 17))
+            .build_node();
+        let terrain = node.as_terrain_mut();
+        terrain.for_each_height_map_pixel(|pixel, _| *pixel = 5.0);
+
+        let spline = SplineBuilder::new(BaseBuilder::new())
+            .with_points(vec![
+                SplinePoint::new(Vector3::new(0.0, 1.0, 8.0)),
+                SplinePoint::new(Vector3::new(16.0, 1.0, 8.0)),
+            ])
+            .with_auto_tangents()
+            .build_spline();
+
+        let settings = RoadSettings {
+            width: 2.0,
+            falloff: 2.0,
+            ..Default::default()
+        };
+        assert!(flatten_terrain(&spline, terrain, &settings));
+
+        let heights = terrain.chunks_ref()[0].heightmap_owned();
+        // Under the road.
+        assert!((heights[8 * 17 + 8] - 1.0).abs() < 0.001);
+        // Far from the road.
+        assert_eq!(heights[0], 5.0);
+    }
+}
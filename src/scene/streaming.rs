@@ -0,0 +1,540 @@
+//! Scene streaming allows you to split large (open-world) scenes into chunks, that are loaded and
+//! unloaded automatically depending on the distance to an observer (usually - the active camera), or
+//! on explicit request. See [`SceneStreaming`] docs for more info.
+
+use crate::{
+    asset::manager::ResourceManager,
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        log::Log,
+        pool::{Handle, Pool},
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::{NodeHandleMap, SceneGraph},
+    resource::model::{Model, ModelResource, ModelResourceExtension},
+    scene::{camera::Camera, node::Node, terrain::Terrain, Scene},
+};
+use std::path::PathBuf;
+
+/// Defines when a chunk is loaded and unloaded.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub enum StreamingTrigger {
+    /// The chunk is loaded when the observer is closer than `load_distance` to the chunk position,
+    /// and unloaded when the observer is further than `unload_distance`. `unload_distance` should be
+    /// larger than `load_distance` to prevent the chunk from "flickering" on the border.
+    Proximity {
+        /// Distance at which the chunk starts loading.
+        load_distance: f32,
+        /// Distance at which the chunk is unloaded.
+        unload_distance: f32,
+    },
+    /// The chunk is loaded and unloaded only on explicit request. See
+    /// [`SceneStreaming::request_load`] and [`SceneStreaming::request_unload`].
+    Manual {
+        /// Whether the chunk should be loaded or not.
+        load: bool,
+    },
+}
+
+impl Default for StreamingTrigger {
+    fn default() -> Self {
+        Self::Proximity {
+            load_distance: 100.0,
+            unload_distance: 120.0,
+        }
+    }
+}
+
+/// Current state of a chunk.
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub enum ChunkState {
+    /// The chunk is not loaded.
+    #[default]
+    Unloaded,
+    /// The chunk resource is loading.
+    Loading(ModelResource),
+    /// The chunk resource is loaded and waits its turn to be instantiated.
+    Instantiating(ModelResource),
+    /// The chunk is instantiated in the scene.
+    Loaded {
+        /// A handle of the root node of the chunk instance.
+        root: Handle<Node>,
+    },
+    /// The chunk resource failed to load. It won't be loaded again until it is unloaded.
+    Failed,
+}
+
+/// A part of a scene, that can be loaded and unloaded independently. The content of a chunk is
+/// stored in a separate scene (prefab), which is instantiated at the chunk position when the chunk
+/// is loaded.
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct StreamingChunk {
+    /// Name of the chunk, used only for debugging purposes.
+    pub name: String,
+    /// Path to a scene (prefab) with the content of the chunk.
+    pub path: PathBuf,
+    /// Position of the chunk in world coordinates. The content of the chunk is instantiated at this
+    /// position and the distance to the observer is measured from it.
+    pub position: Vector3<f32>,
+    /// Defines when the chunk is loaded and unloaded.
+    pub trigger: StreamingTrigger,
+    #[reflect(hidden)]
+    state: ChunkState,
+}
+
+impl StreamingChunk {
+    /// Creates new chunk with the given path and position.
+    pub fn new<P: Into<PathBuf>>(path: P, position: Vector3<f32>) -> Self {
+        Self {
+            path: path.into(),
+            position,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the name of the chunk.
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the trigger of the chunk.
+    pub fn with_trigger(mut self, trigger: StreamingTrigger) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    /// Returns current state of the chunk.
+    pub fn state(&self) -> &ChunkState {
+        &self.state
+    }
+
+    /// Returns `true` if the chunk is instantiated in the scene.
+    pub fn is_loaded(&self) -> bool {
+        matches!(self.state, ChunkState::Loaded { .. })
+    }
+
+    fn wants_load(&self, observer: Option<Vector3<f32>>) -> Option<bool> {
+        match self.trigger {
+            StreamingTrigger::Proximity {
+                load_distance,
+                unload_distance,
+            } => {
+                let distance = observer?.metric_distance(&self.position);
+                if distance <= load_distance {
+                    Some(true)
+                } else if distance > unload_distance {
+                    Some(false)
+                } else {
+                    // Keep current state in the "dead zone".
+                    None
+                }
+            }
+            StreamingTrigger::Manual { load } => Some(load),
+        }
+    }
+}
+
+/// Scene streaming is a set of chunks, that are loaded and unloaded on demand. Chunk resources are
+/// loaded asynchronously by the resource manager, and the instantiation (as well as removal) of
+/// chunks is spread over multiple frames, so large worlds could be streamed without noticeable
+/// hitches. Amount of work per frame is limited by [`Self::max_nodes_per_frame`] - a chunk is
+/// instantiated (or removed) only if it fits into the budget, but at least one chunk per frame is
+/// always processed.
+///
+/// The observer position is taken from [`Self::observer`] node, or from the first enabled camera in
+/// the scene if the observer is not set. The same observer is used to stream chunks of terrains, see
+/// [`crate::scene::terrain::TerrainStreaming`] for more info.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use fyrox::{
+/// #     core::algebra::Vector3,
+/// #     scene::{
+/// #         streaming::{StreamingChunk, StreamingTrigger},
+/// #         Scene,
+/// #     },
+/// # };
+/// fn add_chunks(scene: &mut Scene) {
+///     for i in 0..4 {
+///         scene.streaming.add_chunk(
+///             StreamingChunk::new(
+///                 format!("data/chunks/chunk{i}.rgs"),
+///                 Vector3::new(i as f32 * 200.0, 0.0, 0.0),
+///             )
+///             .with_trigger(StreamingTrigger::Proximity {
+///                 load_distance: 250.0,
+///                 unload_distance: 300.0,
+///             }),
+///         );
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Visit)]
+pub struct SceneStreaming {
+    chunks: Pool<StreamingChunk>,
+    /// A node whose position is used to calculate distances to chunks. If not set, the first
+    /// enabled camera is used.
+    pub observer: Handle<Node>,
+    /// Maximum amount of nodes, that can be instantiated or removed per frame.
+    pub max_nodes_per_frame: usize,
+}
+
+impl Default for SceneStreaming {
+    fn default() -> Self {
+        Self {
+            chunks: Default::default(),
+            observer: Default::default(),
+            max_nodes_per_frame: 2048,
+        }
+    }
+}
+
+impl SceneStreaming {
+    /// Adds a new chunk and returns its handle.
+    pub fn add_chunk(&mut self, chunk: StreamingChunk) -> Handle<StreamingChunk> {
+        self.chunks.spawn(chunk)
+    }
+
+    /// Removes the chunk. Keep in mind, that the content of the chunk (if it is loaded) stays in
+    /// the scene, use [`Self::request_unload`] before removing the chunk if you need to remove the
+    /// content too.
+    pub fn remove_chunk(&mut self, handle: Handle<StreamingChunk>) -> StreamingChunk {
+        self.chunks.free(handle)
+    }
+
+    /// Returns a reference to the chunk.
+    pub fn try_get_chunk(&self, handle: Handle<StreamingChunk>) -> Option<&StreamingChunk> {
+        self.chunks.try_borrow(handle)
+    }
+
+    /// Returns an iterator over every chunk and its handle.
+    pub fn chunks(&self) -> impl Iterator<Item = (Handle<StreamingChunk>, &StreamingChunk)> {
+        self.chunks.pair_iter()
+    }
+
+    /// Requests the chunk to be loaded. It switches the chunk to [`StreamingTrigger::Manual`]
+    /// trigger.
+    pub fn request_load(&mut self, handle: Handle<StreamingChunk>) {
+        if let Some(chunk) = self.chunks.try_borrow_mut(handle) {
+            chunk.trigger = StreamingTrigger::Manual { load: true };
+        }
+    }
+
+    /// Requests the chunk to be unloaded. It switches the chunk to [`StreamingTrigger::Manual`]
+    /// trigger.
+    pub fn request_unload(&mut self, handle: Handle<StreamingChunk>) {
+        if let Some(chunk) = self.chunks.try_borrow_mut(handle) {
+            chunk.trigger = StreamingTrigger::Manual { load: false };
+        }
+    }
+
+    /// Returns `true` if there's no chunks in transition (loading, waiting for instantiation).
+    pub fn is_idle(&self) -> bool {
+        self.chunks.iter().all(|chunk| {
+            !matches!(
+                chunk.state,
+                ChunkState::Loading(_) | ChunkState::Instantiating(_)
+            )
+        })
+    }
+
+    pub(crate) fn remap_handles(&mut self, old_new_map: &NodeHandleMap<Node>) {
+        old_new_map.try_map(&mut self.observer);
+        for chunk in self.chunks.iter_mut() {
+            if let ChunkState::Loaded { ref mut root } = chunk.state {
+                old_new_map.try_map(root);
+            }
+        }
+    }
+
+    fn observer_position(&self, scene: &Scene) -> Option<Vector3<f32>> {
+        if let Some(observer) = scene.graph.try_get(self.observer) {
+            return Some(observer.global_position());
+        }
+
+        scene
+            .graph
+            .linear_iter()
+            .filter(|node| node.is_globally_enabled())
+            .find(|node| node.cast::<Camera>().map_or(false, |c| c.is_enabled()))
+            .map(|camera| camera.global_position())
+    }
+
+    /// Loads and unloads chunks of the scene. Normally, this method is called by the engine
+    /// automatically, you should call it manually only if you're updating scenes by yourself.
+    pub fn update(scene: &mut Scene, resource_manager: &ResourceManager) {
+        let mut streaming = std::mem::take(&mut scene.streaming);
+        streaming.update_chunks(scene, resource_manager);
+        streaming.update_terrains(scene);
+        scene.streaming = streaming;
+    }
+
+    fn update_terrains(&self, scene: &mut Scene) {
+        let Some(observer) = self.observer_position(scene) else {
+            return;
+        };
+
+        for node in scene.graph.linear_iter_mut() {
+            if let Some(terrain) = node.cast_mut::<Terrain>() {
+                terrain.update_streaming(observer);
+            }
+        }
+    }
+
+    fn update_chunks(&mut self, scene: &mut Scene, resource_manager: &ResourceManager) {
+        let observer = self.observer_position(scene);
+
+        // Closest chunks should be loaded first.
+        let mut order = self
+            .chunks
+            .pair_iter()
+            .map(|(handle, chunk)| {
+                let distance = observer.map_or(0.0, |o| o.metric_distance(&chunk.position));
+                (handle, distance)
+            })
+            .collect::<Vec<_>>();
+        order.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut budget = self.max_nodes_per_frame;
+        let mut processed_any = false;
+
+        for (handle, _) in order {
+            let chunk = &mut self.chunks[handle];
+            let Some(wants_load) = chunk.wants_load(observer) else {
+                continue;
+            };
+
+            if wants_load {
+                match chunk.state {
+                    ChunkState::Unloaded => {
+                        chunk.state =
+                            ChunkState::Loading(resource_manager.request::<Model>(&chunk.path));
+                    }
+                    ChunkState::Loading(ref model) => {
+                        if model.is_ok() {
+                            chunk.state = ChunkState::Instantiating(model.clone());
+                        } else if model.is_failed_to_load() {
+                            Log::err(format!(
+                                "Unable to load streaming chunk {} ({})!",
+                                chunk.name,
+                                chunk.path.display()
+                            ));
+                            chunk.state = ChunkState::Failed;
+                        }
+                    }
+                    ChunkState::Loaded { .. } | ChunkState::Failed => (),
+                    ChunkState::Instantiating(_) => (),
+                }
+
+                if let ChunkState::Instantiating(ref model) = chunk.state {
+                    let node_count = model.data_ref().get_scene().graph.node_count() as usize;
+                    if node_count <= budget || !processed_any {
+                        budget = budget.saturating_sub(node_count);
+                        processed_any = true;
+                        let root =
+                            model.instantiate_at(scene, chunk.position, UnitQuaternion::identity());
+                        chunk.state = ChunkState::Loaded { root };
+                    }
+                }
+            } else {
+                match chunk.state {
+                    ChunkState::Loaded { root } => {
+                        let node_count = scene.graph.traverse_iter(root).count();
+                        if node_count <= budget || !processed_any {
+                            budget = budget.saturating_sub(node_count);
+                            processed_any = true;
+                            if scene.graph.is_valid_handle(root) {
+                                scene.graph.remove_node(root);
+                            }
+                            chunk.state = ChunkState::Unloaded;
+                        }
+                    }
+                    // Drop the resource, so the resource manager could unload it.
+                    _ => chunk.state = ChunkState::Unloaded,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{algebra::Vector3, futures::executor::block_on, pool::Handle, visitor::Visitor},
+        engine::{self, SerializationContext},
+        graph::SceneGraph,
+        resource::model::Model,
+        scene::{
+            base::BaseBuilder,
+            node::Node,
+            pivot::PivotBuilder,
+            streaming::{ChunkState, SceneStreaming, StreamingChunk, StreamingTrigger},
+            transform::TransformBuilder,
+            Scene,
+        },
+    };
+    use std::{fs, path::Path, sync::Arc};
+
+    fn save_chunk(path: &Path) {
+        let mut scene = Scene::new();
+        PivotBuilder::new(BaseBuilder::new().with_name("ChunkContent")).build(&mut scene.graph);
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        visitor.save_binary(path).unwrap();
+    }
+
+    fn make_resource_manager() -> ResourceManager {
+        let resource_manager = ResourceManager::new(Arc::new(Default::default()));
+        engine::initialize_resource_manager_loaders(
+            &resource_manager,
+            Arc::new(SerializationContext::new()),
+        );
+        resource_manager
+    }
+
+    fn set_position(scene: &mut Scene, node: Handle<Node>, position: Vector3<f32>) {
+        scene.graph[node]
+            .local_transform_mut()
+            .set_position(position);
+        scene.graph.update_hierarchical_data();
+    }
+
+    fn update_until_idle(scene: &mut Scene, resource_manager: &ResourceManager) {
+        for _ in 0..10 {
+            SceneStreaming::update(scene, resource_manager);
+            let loading = scene
+                .streaming
+                .chunks()
+                .filter_map(|(_, c)| match c.state() {
+                    ChunkState::Loading(model) => Some(model.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            for model in loading {
+                let _ = block_on(model);
+            }
+        }
+    }
+
+    #[test]
+    fn test_proximity_streaming() {
+        fs::create_dir_all("test_output").unwrap();
+        let chunk_path = Path::new("test_output/streaming_chunk.rgs");
+        save_chunk(chunk_path);
+
+        let resource_manager = make_resource_manager();
+        let mut scene = Scene::new();
+        let observer = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(TransformBuilder::new().build()),
+        )
+        .build(&mut scene.graph);
+        scene.streaming.observer = observer;
+
+        let chunk = scene.streaming.add_chunk(
+            StreamingChunk::new(chunk_path, Vector3::new(10.0, 0.0, 0.0)).with_trigger(
+                StreamingTrigger::Proximity {
+                    load_distance: 50.0,
+                    unload_distance: 60.0,
+                },
+            ),
+        );
+
+        update_until_idle(&mut scene, &resource_manager);
+        let ChunkState::Loaded { root } = *scene.streaming.try_get_chunk(chunk).unwrap().state()
+        else {
+            panic!("Chunk must be loaded!")
+        };
+        assert!(scene.graph.find_by_name(root, "ChunkContent").is_some());
+        assert_eq!(
+            **scene.graph[root].local_transform().position(),
+            Vector3::new(10.0, 0.0, 0.0)
+        );
+
+        // Dead zone, nothing should change.
+        set_position(&mut scene, observer, Vector3::new(65.0, 0.0, 0.0));
+        update_until_idle(&mut scene, &resource_manager);
+        assert!(scene.streaming.try_get_chunk(chunk).unwrap().is_loaded());
+
+        set_position(&mut scene, observer, Vector3::new(1000.0, 0.0, 0.0));
+        update_until_idle(&mut scene, &resource_manager);
+        assert!(!scene.streaming.try_get_chunk(chunk).unwrap().is_loaded());
+        assert!(!scene.graph.is_valid_handle(root));
+    }
+
+    #[test]
+    fn test_manual_streaming_budget() {
+        fs::create_dir_all("test_output").unwrap();
+        let chunk_path = Path::new("test_output/streaming_chunk_manual.rgs");
+        save_chunk(chunk_path);
+
+        let resource_manager = make_resource_manager();
+        let mut scene = Scene::new();
+        scene.streaming.max_nodes_per_frame = 1;
+
+        let chunks = (0..2)
+            .map(|i| {
+                scene.streaming.add_chunk(
+                    StreamingChunk::new(chunk_path, Vector3::new(i as f32, 0.0, 0.0))
+                        .with_trigger(StreamingTrigger::Manual { load: false }),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        update_until_idle(&mut scene, &resource_manager);
+        assert_eq!(
+            scene
+                .streaming
+                .chunks()
+                .filter(|(_, c)| c.is_loaded())
+                .count(),
+            0
+        );
+
+        for chunk in chunks.iter() {
+            scene.streaming.request_load(*chunk);
+        }
+
+        // Request the resource first.
+        SceneStreaming::update(&mut scene, &resource_manager);
+        let model = block_on(resource_manager.request::<Model>(chunk_path)).unwrap();
+        drop(model);
+
+        // Each chunk has two nodes, so only one chunk fits into the budget per frame.
+        SceneStreaming::update(&mut scene, &resource_manager);
+        assert_eq!(
+            scene
+                .streaming
+                .chunks()
+                .filter(|(_, c)| c.is_loaded())
+                .count(),
+            1
+        );
+        SceneStreaming::update(&mut scene, &resource_manager);
+        assert_eq!(
+            scene
+                .streaming
+                .chunks()
+                .filter(|(_, c)| c.is_loaded())
+                .count(),
+            2
+        );
+        assert!(scene.streaming.is_idle());
+
+        scene.streaming.request_unload(chunks[0]);
+        SceneStreaming::update(&mut scene, &resource_manager);
+        assert!(!scene
+            .streaming
+            .try_get_chunk(chunks[0])
+            .unwrap()
+            .is_loaded());
+        assert!(scene
+            .streaming
+            .try_get_chunk(chunks[1])
+            .unwrap()
+            .is_loaded());
+    }
+}
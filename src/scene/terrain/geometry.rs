@@ -11,6 +11,10 @@ use crate::{
     },
 };
 
+/// Geometry of a terrain patch. It is a grid of `mesh_size` vertices with a "skirt" around each
+/// quadrant. Skirt is a vertical strip of triangles, that goes down from the edges of a quadrant.
+/// Skirt vertices are the copies of the edge vertices with `-1.0` Y coordinate, the shader moves
+/// them down by the skirt depth. Skirts hide cracks between patches with different level of detail.
 #[derive(Default, Debug, Clone)]
 pub struct TerrainGeometry {
     pub data: SurfaceSharedData,
@@ -18,6 +22,20 @@ pub struct TerrainGeometry {
     pub quadrants: [ElementRange; 4],
 }
 
+fn push_skirt(
+    triangles: &mut Vec<TriangleDefinition>,
+    edge: impl Iterator<Item = u32>,
+    skirt_offset: u32,
+) {
+    let edge = edge.collect::<Vec<_>>();
+    for pair in edge.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (a_skirt, b_skirt) = (a + skirt_offset, b + skirt_offset);
+        triangles.push(TriangleDefinition([a, a_skirt, b_skirt]));
+        triangles.push(TriangleDefinition([b_skirt, b, a]));
+    }
+}
+
 impl TerrainGeometry {
     pub fn new(mesh_size: Vector2<u32>) -> Self {
         let mut surface_data = SurfaceData::new(
@@ -46,12 +64,9 @@ impl TerrainGeometry {
         }
         drop(vertex_buffer_mut);
 
-        let mut geometry_buffer_mut = surface_data.geometry_buffer.modify();
-
         let half_size = mesh_size / 2;
 
-        let mut quadrants = [ElementRange::Full; 4];
-        for ((x_range, y_range), quadrant) in [
+        let quadrant_ranges = [
             (0..(half_size.x + 1), 0..(half_size.y + 1)),
             ((half_size.x - 1)..mesh_size.x, 0..(half_size.y + 1)),
             (
@@ -59,26 +74,93 @@ impl TerrainGeometry {
                 (half_size.y - 1)..mesh_size.y,
             ),
             (0..(half_size.x + 1), (half_size.y - 1)..mesh_size.y),
-        ]
-        .into_iter()
-        .zip(&mut quadrants)
-        {
-            let offset = geometry_buffer_mut.len();
+        ];
 
-            for iy in y_range.start..y_range.end - 1 {
-                let iy_next = iy + 1;
-                for x in x_range.start..x_range.end - 1 {
-                    let x_next = x + 1;
+        let quadrant_triangles = quadrant_ranges
+            .iter()
+            .map(|(x_range, y_range)| {
+                let mut triangles = Vec::new();
+                for iy in y_range.start..y_range.end - 1 {
+                    let iy_next = iy + 1;
+                    for x in x_range.start..x_range.end - 1 {
+                        let x_next = x + 1;
 
-                    let i0 = iy * mesh_size.x + x;
-                    let i1 = iy_next * mesh_size.x + x;
-                    let i2 = iy_next * mesh_size.x + x_next;
-                    let i3 = iy * mesh_size.x + x_next;
+                        let i0 = iy * mesh_size.x + x;
+                        let i1 = iy_next * mesh_size.x + x;
+                        let i2 = iy_next * mesh_size.x + x_next;
+                        let i3 = iy * mesh_size.x + x_next;
 
-                    geometry_buffer_mut.push(TriangleDefinition([i0, i1, i2]));
-                    geometry_buffer_mut.push(TriangleDefinition([i2, i3, i0]));
+                        triangles.push(TriangleDefinition([i0, i1, i2]));
+                        triangles.push(TriangleDefinition([i2, i3, i0]));
+                    }
                 }
-            }
+                triangles
+            })
+            .collect::<Vec<_>>();
+
+        // Calculate normals and tangents using the grid only, skirts must not affect them.
+        surface_data
+            .geometry_buffer
+            .set_triangles(quadrant_triangles.concat());
+        surface_data.calculate_normals().unwrap();
+        surface_data.calculate_tangents().unwrap();
+
+        // Skirt vertices are the copies of the grid vertices, shifted down.
+        let skirt_offset = mesh_size.x * mesh_size.y;
+        let skirt_vertices = surface_data
+            .vertex_buffer
+            .cast_data_ref::<StaticVertex>()
+            .unwrap()
+            .iter()
+            .map(|v| StaticVertex {
+                position: Vector3::new(v.position.x, -1.0, v.position.z),
+                ..*v
+            })
+            .collect::<Vec<_>>();
+        surface_data
+            .vertex_buffer
+            .modify()
+            .push_vertices(&skirt_vertices)
+            .unwrap();
+
+        let mut geometry_buffer_mut = surface_data.geometry_buffer.modify();
+        geometry_buffer_mut.clear();
+
+        let mut quadrants = [ElementRange::Full; 4];
+        for (((x_range, y_range), triangles), quadrant) in quadrant_ranges
+            .into_iter()
+            .zip(quadrant_triangles)
+            .zip(&mut quadrants)
+        {
+            let offset = geometry_buffer_mut.len();
+
+            geometry_buffer_mut.push_triangles(&triangles);
+
+            let (x_first, x_last) = (x_range.start, x_range.end - 1);
+            let (y_first, y_last) = (y_range.start, y_range.end - 1);
+            let mut skirts = Vec::new();
+            // Edges are traversed in the order that makes the skirt triangles face outwards.
+            push_skirt(
+                &mut skirts,
+                (x_first..=x_last).rev().map(|x| y_first * mesh_size.x + x),
+                skirt_offset,
+            );
+            push_skirt(
+                &mut skirts,
+                (x_first..=x_last).map(|x| y_last * mesh_size.x + x),
+                skirt_offset,
+            );
+            push_skirt(
+                &mut skirts,
+                (y_first..=y_last).map(|y| y * mesh_size.x + x_first),
+                skirt_offset,
+            );
+            push_skirt(
+                &mut skirts,
+                (y_first..=y_last).rev().map(|y| y * mesh_size.x + x_last),
+                skirt_offset,
+            );
+            geometry_buffer_mut.push_triangles(&skirts);
 
             *quadrant = ElementRange::Specific {
                 offset,
@@ -87,9 +169,6 @@ impl TerrainGeometry {
         }
         drop(geometry_buffer_mut);
 
-        surface_data.calculate_normals().unwrap();
-        surface_data.calculate_tangents().unwrap();
-
         Self {
             data: SurfaceSharedData::new(surface_data),
             quadrants,
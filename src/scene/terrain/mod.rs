@@ -7,6 +7,7 @@ use crate::{
     core::{
         algebra::{Matrix4, Point3, Vector2, Vector3, Vector4},
         arrayvec::ArrayVec,
+        color::Color,
         log::Log,
         math::{aabb::AxisAlignedBoundingBox, ray::Ray, ray_rect_intersection, Rect},
         pool::Handle,
@@ -45,6 +46,8 @@ use std::{
     cmp::Ordering,
     collections::HashMap,
     ops::{Deref, DerefMut, Range},
+    path::PathBuf,
+    sync::atomic::{self, AtomicU64},
 };
 
 mod geometry;
@@ -53,6 +56,14 @@ mod quadtree;
 /// Current implementation version marker.
 pub const VERSION: u8 = 1;
 
+static HEIGHT_MAP_STAMP: AtomicU64 = AtomicU64::new(1);
+
+// Every modification of a height map gets a unique, monotonically increasing stamp. It is used
+// by height field colliders to find out which chunks must be re-read from the terrain.
+fn next_height_map_stamp() -> u64 {
+    HEIGHT_MAP_STAMP.fetch_add(1, atomic::Ordering::Relaxed)
+}
+
 /// Layers is a material Terrain can have as many layers as you want, but each layer slightly decreases
 /// performance, so keep amount of layers on reasonable level (1 - 5 should be enough for most
 /// cases).
@@ -71,6 +82,14 @@ pub struct Layer {
     /// Name of the node uv offsets property in the material.
     #[visit(optional)]
     pub node_uv_offsets_property_name: String,
+
+    /// Name of the hole mask sampler property in the material.
+    #[visit(optional)]
+    pub hole_mask_property_name: String,
+
+    /// Name of the skirt depth property in the material.
+    #[visit(optional)]
+    pub skirt_depth_property_name: String,
 }
 
 uuid_provider!(Layer = "7439d5fd-43a9-45f0-bd7c-76cf4d2ec22e");
@@ -82,6 +101,8 @@ impl Default for Layer {
             mask_property_name: "maskTexture".to_string(),
             height_map_property_name: "heightMapTexture".to_string(),
             node_uv_offsets_property_name: "nodeUvOffsets".to_string(),
+            hole_mask_property_name: "holeMaskTexture".to_string(),
+            skirt_depth_property_name: "skirtDepth".to_string(),
         }
     }
 }
@@ -91,7 +112,11 @@ fn make_quad_tree(
     height_map_size: Vector2<u32>,
     block_size: Vector2<u32>,
 ) -> QuadTree {
-    let texture = texture.as_ref().unwrap().data_ref();
+    let Some(texture) = texture.as_ref() else {
+        // Chunk is streamed out.
+        return Default::default();
+    };
+    let texture = texture.data_ref();
     let height_map = texture.data_of_type::<f32>().unwrap();
     QuadTree::new(height_map, height_map_size, block_size)
 }
@@ -149,6 +174,15 @@ pub struct Chunk {
     /// Layer blending masks of the chunk.
     #[reflect(hidden)]
     pub layer_masks: Vec<TextureResource>,
+    /// Hole mask of the chunk. It is an R8 texture, where `0` marks a hole and `255` marks a solid
+    /// surface. `None` means that the chunk has no holes.
+    #[reflect(hidden)]
+    hole_mask: Option<TextureResource>,
+    #[reflect(hidden)]
+    height_map_stamp: u64,
+    /// A path to the file with the data of the chunk, if the chunk is streamed out.
+    #[reflect(hidden)]
+    swap_file: Option<PathBuf>,
 }
 
 uuid_provider!(Chunk = "ae996754-69c1-49ba-9c17-a7bd4be072a9");
@@ -158,7 +192,7 @@ impl Clone for Chunk {
     fn clone(&self) -> Self {
         Self {
             version: self.version,
-            heightmap: Some(self.heightmap.as_ref().unwrap().deep_clone()),
+            heightmap: self.heightmap.as_ref().map(|h| h.deep_clone()),
             position: self.position,
             physical_size: self.physical_size,
             height_map_size: self.height_map_size,
@@ -169,14 +203,58 @@ impl Clone for Chunk {
                 .iter()
                 .map(|m| m.deep_clone())
                 .collect::<Vec<_>>(),
+            hole_mask: self.hole_mask.as_ref().map(|m| m.deep_clone()),
             quad_tree: make_quad_tree(&self.heightmap, self.height_map_size, self.block_size),
+            height_map_stamp: self.height_map_stamp,
+            swap_file: self.swap_file.clone(),
+        }
+    }
+}
+
+/// Data of a streamed out chunk. Textures are stored as raw pixels, so they could be read back
+/// without a resource manager.
+#[derive(Default)]
+struct ChunkSwapData {
+    height_map: Vec<f32>,
+    mask_size: Vector2<u32>,
+    layer_masks: Vec<Vec<u8>>,
+    hole_mask: Option<Vec<u8>>,
+}
+
+impl Visit for ChunkSwapData {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        PodVecView::from_pod_vec(&mut self.height_map).visit("HeightMap", &mut region)?;
+        self.mask_size.visit("MaskSize", &mut region)?;
+
+        let mut mask_count = self.layer_masks.len() as u32;
+        mask_count.visit("MaskCount", &mut region)?;
+        self.layer_masks
+            .resize(mask_count as usize, Default::default());
+        for (i, mask) in self.layer_masks.iter_mut().enumerate() {
+            PodVecView::from_pod_vec(mask).visit(&format!("Mask{i}"), &mut region)?;
+        }
+
+        let mut has_holes = self.hole_mask.is_some();
+        has_holes.visit("HasHoles", &mut region)?;
+        if has_holes {
+            PodVecView::from_pod_vec(self.hole_mask.get_or_insert_with(Default::default))
+                .visit("HoleMask", &mut region)?;
         }
+
+        Ok(())
     }
 }
 
 // Manual implementation of the trait because we need to serialize heightmap differently.
 impl Visit for Chunk {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        if !visitor.is_reading() {
+            // Streamed out chunk must be loaded back to be saved.
+            self.load()?;
+        }
+
         let mut region = visitor.enter_region(name)?;
 
         let mut version = if region.is_reading() {
@@ -224,6 +302,7 @@ impl Visit for Chunk {
                 self.layer_masks.visit("LayerMasks", &mut region)?;
                 self.grid_position.visit("GridPosition", &mut region)?;
                 let _ = self.block_size.visit("BlockSize", &mut region);
+                let _ = self.hole_mask.visit("HoleMask", &mut region);
             }
             _ => (),
         }
@@ -246,6 +325,9 @@ impl Default for Chunk {
             block_size: Vector2::new(32, 32),
             grid_position: Default::default(),
             layer_masks: Default::default(),
+            hole_mask: Default::default(),
+            height_map_stamp: next_height_map_stamp(),
+            swap_file: None,
         }
     }
 }
@@ -257,11 +339,103 @@ impl Chunk {
         map_to_local(self.position)
     }
 
-    /// Returns a reference to height map.
+    /// Returns `true` if the data of the chunk is in memory, `false` - if the chunk is streamed out.
+    /// See [`TerrainStreaming`] for more info.
+    pub fn is_loaded(&self) -> bool {
+        self.swap_file.is_none()
+    }
+
+    /// Writes the data (height map and masks) of the chunk to the given file and releases it. The
+    /// chunk won't be rendered until it is loaded back by [`Self::load`].
+    pub fn unload(&mut self, path: PathBuf) -> VisitResult {
+        if !self.is_loaded() {
+            return Ok(());
+        }
+
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+
+        let texture_size = |texture: &TextureResource| match texture.data_ref().kind() {
+            TextureKind::Rectangle { width, height } => Vector2::new(width, height),
+            _ => Vector2::default(),
+        };
+        let pixels = |texture: &TextureResource| texture.data_ref().data().to_vec();
+
+        let mut swap_data = ChunkSwapData {
+            height_map: self.heightmap_owned(),
+            mask_size: self
+                .layer_masks
+                .first()
+                .or(self.hole_mask.as_ref())
+                .map(texture_size)
+                .unwrap_or_default(),
+            layer_masks: self.layer_masks.iter().map(pixels).collect(),
+            hole_mask: self.hole_mask.as_ref().map(pixels),
+        };
+
+        let mut visitor = Visitor::new();
+        swap_data.visit("Chunk", &mut visitor)?;
+        visitor.save_binary(&path)?;
+
+        self.heightmap = None;
+        self.layer_masks.clear();
+        self.hole_mask = None;
+        self.quad_tree = Default::default();
+        self.swap_file = Some(path);
+
+        Ok(())
+    }
+
+    /// Loads the data of the chunk back, if it was streamed out by [`Self::unload`]. Does nothing
+    /// if the chunk is already loaded.
+    pub fn load(&mut self) -> VisitResult {
+        let Some(path) = self.swap_file.as_ref() else {
+            return Ok(());
+        };
+
+        let data = std::fs::read(path)?;
+        let mut visitor = Visitor::load_from_memory(&data)?;
+        let mut swap_data = ChunkSwapData::default();
+        swap_data.visit("Chunk", &mut visitor)?;
+
+        let ChunkSwapData {
+            height_map,
+            mask_size,
+            layer_masks,
+            hole_mask,
+        } = swap_data;
+        self.heightmap = Some(make_height_map_texture(height_map, self.height_map_size));
+        self.layer_masks = layer_masks
+            .into_iter()
+            .map(|pixels| make_mask_texture(mask_size, pixels))
+            .collect();
+        self.hole_mask = hole_mask.map(|pixels| make_mask_texture(mask_size, pixels));
+        self.quad_tree = make_quad_tree(&self.heightmap, self.height_map_size, self.block_size);
+        self.height_map_stamp = next_height_map_stamp();
+        self.swap_file = None;
+
+        Ok(())
+    }
+
+    /// Returns a reference to height map. Panics if the chunk is streamed out, see [`Self::is_loaded`].
     pub fn heightmap(&self) -> &TextureResource {
         self.heightmap.as_ref().unwrap()
     }
 
+    /// Returns a stamp of the last modification of the height map of the chunk. Stamps are unique
+    /// and grow monotonically, so a chunk with a greater stamp was modified later.
+    pub fn height_map_stamp(&self) -> u64 {
+        self.height_map_stamp
+    }
+
+    /// Marks the height map of the chunk as modified. Must be called if the height map texture was
+    /// modified directly, otherwise dependent entities (such as height field colliders) won't be
+    /// updated.
+    pub fn mark_height_map_modified(&mut self) {
+        self.height_map_stamp = next_height_map_stamp();
+    }
+
     /// Sets new height map to the chunk.
     pub fn set_height_map(
         &mut self,
@@ -424,6 +598,7 @@ impl Chunk {
                             if let Some(texture) =
                                 make_height_map_texture_internal(pixels, self.height_map_size)
                             {
+                                self.height_map_stamp = next_height_map_stamp();
                                 return std::mem::replace(&mut self.heightmap, Some(texture));
                             }
                         }
@@ -460,6 +635,7 @@ impl Chunk {
             {
                 drop(data);
                 self.heightmap = Some(heightmap);
+                self.height_map_stamp = next_height_map_stamp();
                 return Ok(());
             }
         }
@@ -467,6 +643,37 @@ impl Chunk {
         Err(heightmap)
     }
 
+    /// Returns a reference to the hole mask of the chunk, if any. See [`Self::set_hole_mask`] for
+    /// more info.
+    pub fn hole_mask(&self) -> Option<&TextureResource> {
+        self.hole_mask.as_ref()
+    }
+
+    /// Sets new hole mask of the chunk and returns the previous one. The mask must be an R8 texture
+    /// of the same size as layer masks, where `0` marks a hole and `255` marks a solid surface. Holes are not rendered and removed
+    /// from height field colliders. `None` removes all holes from the chunk.
+    pub fn set_hole_mask(&mut self, hole_mask: Option<TextureResource>) -> Option<TextureResource> {
+        self.height_map_stamp = next_height_map_stamp();
+        std::mem::replace(&mut self.hole_mask, hole_mask)
+    }
+
+    /// Checks whether the given point (in normalized `[0; 1]` coordinates of the chunk) is inside
+    /// a hole or not.
+    pub fn is_hole(&self, uv: Vector2<f32>) -> bool {
+        let Some(hole_mask) = self.hole_mask.as_ref() else {
+            return false;
+        };
+        let data = hole_mask.data_ref();
+        let TextureKind::Rectangle { width, height } = data.kind() else {
+            return false;
+        };
+        let x = ((uv.x.clamp(0.0, 1.0) * width as f32) as u32).min(width.saturating_sub(1));
+        let y = ((uv.y.clamp(0.0, 1.0) * height as f32) as u32).min(height.saturating_sub(1));
+        data.data()
+            .get((y * width + x) as usize)
+            .is_some_and(|v| *v < 128)
+    }
+
     /// Returns the size of the chunk in meters.
     pub fn physical_size(&self) -> Vector2<f32> {
         self.physical_size
@@ -512,6 +719,43 @@ pub struct TerrainRayCastResult {
     pub toi: f32,
 }
 
+/// Chunk streaming settings of a terrain. When enabled, the data of the chunks (height maps and masks),
+/// that are further than `unload_distance` from the observer is written to the `directory` and released
+/// from memory. Such chunks are loaded back as soon as the observer comes closer than `load_distance`.
+/// Distance is measured from the center of a chunk. The observer is the same as for the scene streaming,
+/// see [`crate::scene::streaming::SceneStreaming`] for more info.
+///
+/// Streamed out chunks are not rendered and ignored by ray casting and painting, however height field
+/// colliders keep the heights of such chunks.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct TerrainStreaming {
+    /// Whether the streaming is enabled or not.
+    pub enabled: bool,
+    /// A directory, where the data of streamed out chunks will be stored. Every terrain must have its
+    /// own directory.
+    pub directory: PathBuf,
+    /// Distance at which a chunk is loaded.
+    #[reflect(min_value = 0.0)]
+    pub load_distance: f32,
+    /// Distance at which a chunk is unloaded. Should be larger than `load_distance`, to prevent chunks
+    /// from "flickering" on the border.
+    #[reflect(min_value = 0.0)]
+    pub unload_distance: f32,
+}
+
+uuid_provider!(TerrainStreaming = "d0f7a3b2-5c4e-4e8a-9b1d-3f6c2a8e7b15");
+
+impl Default for TerrainStreaming {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: "data/terrain".into(),
+            load_distance: 200.0,
+            unload_distance: 250.0,
+        }
+    }
+}
+
 /// Terrain is a height field where each point has fixed coordinates in XZ plane, but variable Y coordinate.
 /// It can be used to create landscapes. It supports multiple layers, where each layer has its own material
 /// and mask.
@@ -547,8 +791,18 @@ pub struct TerrainRayCastResult {
 /// of the patch that will be used for rendering. It is used to divide the size of the height map into a fixed
 /// set of blocks using quad-tree algorithm.
 ///
-/// Current implementation uses modified version of CDLOD algorithm without patch morphing. Apparently it is not
-/// needed, since bilinear filtration in vertex shader prevents seams to occur.
+/// Level of detail ranges could be tweaked by `lod_distance` (`Terrain::set_lod_distance`) and `lod_falloff`
+/// (`Terrain::set_lod_falloff`) parameters. The former defines the distance at which the terrain is rendered
+/// with the lowest detail, the latter - how fast the detail decreases with distance.
+///
+/// Current implementation uses modified version of CDLOD algorithm without patch morphing. Seams between the
+/// patches with different level of detail are hidden by "skirts" - vertical strips of triangles that go down
+/// from the edges of each patch. Their depth is defined by `skirt_depth` (`Terrain::set_skirt_depth`).
+///
+/// ## Streaming
+///
+/// Large terrains could take a lot of memory, to reduce memory usage the data of distant chunks could be
+/// streamed out to disk. See [`TerrainStreaming`] docs for more info.
 ///
 /// ## Painting
 ///
@@ -568,7 +822,9 @@ pub struct TerrainRayCastResult {
 ///
 /// As usual, to have collisions working you need to create a rigid body and add an appropriate collider to it.
 /// In case of terrains you need to create a collider with `Heightfield` shape and specify your terrain as a
-/// geometry source.
+/// geometry source. The collider tracks modifications of the height maps of the terrain and updates
+/// only the modified chunks, so the terrain could be edited (for example - with [`Terrain::draw`])
+/// at runtime without re-creating the collider.
 #[derive(Debug, Reflect, Clone)]
 pub struct Terrain {
     base: Base,
@@ -611,6 +867,33 @@ pub struct Terrain {
     #[reflect(min_value = 8.0, step = 1.0, setter = "set_block_size")]
     block_size: InheritableVariable<Vector2<u32>>,
 
+    #[reflect(
+        min_value = 0.0,
+        description = "Distance (in meters) at which the terrain is rendered with the lowest level of detail. \
+        Zero means the far clipping plane of the camera.",
+        setter = "set_lod_distance"
+    )]
+    lod_distance: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 1.0,
+        description = "Defines how fast the level of detail decreases with distance. Larger values keep \
+        high detail closer to the camera.",
+        setter = "set_lod_falloff"
+    )]
+    lod_falloff: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        description = "Depth (in meters) of the skirts that hide seams between patches with different \
+        level of detail. Zero disables the skirts.",
+        setter = "set_skirt_depth"
+    )]
+    skirt_depth: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_streaming")]
+    streaming: InheritableVariable<TerrainStreaming>,
+
     #[reflect(
         min_value = 1.0,
         step = 1.0,
@@ -646,6 +929,10 @@ impl Default for Terrain {
             length_chunks: Default::default(),
             height_map_size: Default::default(),
             block_size: Vector2::new(32, 32).into(),
+            lod_distance: Default::default(),
+            lod_falloff: 3.0.into(),
+            skirt_depth: 1.0.into(),
+            streaming: Default::default(),
             mask_size: Default::default(),
             chunks: Default::default(),
             bounding_box_dirty: Cell::new(true),
@@ -771,6 +1058,10 @@ impl Visit for Terrain {
                 let _ = self.block_size.visit("BlockSize", &mut region);
                 self.mask_size.visit("MaskSize", &mut region)?;
                 self.chunks.visit("Chunks", &mut region)?;
+                let _ = self.lod_distance.visit("LodDistance", &mut region);
+                let _ = self.lod_falloff.visit("LodFalloff", &mut region);
+                let _ = self.skirt_depth.visit("SkirtDepth", &mut region);
+                let _ = self.streaming.visit("Streaming", &mut region);
             }
             _ => (),
         }
@@ -882,6 +1173,99 @@ impl Terrain {
         *self.block_size
     }
 
+    /// Sets the distance (in meters) at which the terrain is rendered with the lowest level of detail.
+    /// Zero means the far clipping plane of the camera.
+    pub fn set_lod_distance(&mut self, distance: f32) -> f32 {
+        self.lod_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns the distance at which the terrain is rendered with the lowest level of detail.
+    pub fn lod_distance(&self) -> f32 {
+        *self.lod_distance
+    }
+
+    /// Sets how fast the level of detail decreases with distance. Larger values keep high detail
+    /// closer to the camera.
+    pub fn set_lod_falloff(&mut self, falloff: f32) -> f32 {
+        self.lod_falloff
+            .set_value_and_mark_modified(falloff.max(1.0))
+    }
+
+    /// Returns current level of detail falloff.
+    pub fn lod_falloff(&self) -> f32 {
+        *self.lod_falloff
+    }
+
+    /// Sets the depth (in meters) of the skirts, that hide seams between patches with different
+    /// level of detail. Zero disables the skirts.
+    pub fn set_skirt_depth(&mut self, depth: f32) -> f32 {
+        self.skirt_depth.set_value_and_mark_modified(depth.max(0.0))
+    }
+
+    /// Returns current depth of the skirts.
+    pub fn skirt_depth(&self) -> f32 {
+        *self.skirt_depth
+    }
+
+    /// Sets new chunk streaming settings. See [`TerrainStreaming`] docs for more info.
+    pub fn set_streaming(&mut self, streaming: TerrainStreaming) -> TerrainStreaming {
+        self.streaming.set_value_and_mark_modified(streaming)
+    }
+
+    /// Returns current chunk streaming settings.
+    pub fn streaming(&self) -> &TerrainStreaming {
+        &self.streaming
+    }
+
+    /// Loads and unloads the chunks of the terrain depending on their distance to the given observer
+    /// position (in world coordinates). Does nothing if the streaming is disabled. Normally, this method
+    /// is called by the engine automatically as a part of the scene streaming.
+    pub fn update_streaming(&mut self, observer_position: Vector3<f32>) {
+        if !self.streaming.enabled {
+            return;
+        }
+
+        let transform = self.global_transform();
+        let streaming = &*self.streaming;
+
+        let mut changed = false;
+        for chunk in self.chunks.iter_mut() {
+            let center = chunk.position
+                + Vector3::new(chunk.physical_size.x, 0.0, chunk.physical_size.y).scale(0.5);
+            let distance = transform
+                .transform_point(&Point3::from(center))
+                .coords
+                .metric_distance(&observer_position);
+
+            if chunk.is_loaded() {
+                if distance > streaming.unload_distance {
+                    let path = streaming.directory.join(format!(
+                        "chunk_{}_{}.bin",
+                        chunk.grid_position.x, chunk.grid_position.y
+                    ));
+                    Log::verify(chunk.unload(path));
+                    changed = true;
+                }
+            } else if distance <= streaming.load_distance {
+                Log::verify(chunk.load());
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.bounding_box_dirty.set(true);
+        }
+    }
+
+    /// Loads every streamed out chunk of the terrain. See [`TerrainStreaming`] docs for more info.
+    pub fn load_chunks(&mut self) {
+        for chunk in self.chunks.iter_mut() {
+            Log::verify(chunk.load());
+        }
+        self.bounding_box_dirty.set(true);
+    }
+
     /// Returns the total amount of pixels along each axis of the layer blending mask.
     pub fn mask_size(&self) -> Vector2<u32> {
         *self.mask_size
@@ -942,6 +1326,7 @@ impl Terrain {
                     let heightmap =
                         vec![0.0; (self.height_map_size.x * self.height_map_size.y) as usize];
                     let new_chunk = Chunk {
+                        height_map_stamp: next_height_map_stamp(),
                         quad_tree: QuadTree::new(&heightmap, *self.block_size, *self.block_size),
                         heightmap: Some(make_height_map_texture(heightmap, self.height_map_size())),
                         position: Vector3::new(
@@ -965,6 +1350,8 @@ impl Terrain {
                                 )
                             })
                             .collect::<Vec<_>>(),
+                        hole_mask: None,
+                        swap_file: None,
                         version: VERSION,
                     };
 
@@ -983,6 +1370,16 @@ impl Terrain {
         &self.chunks
     }
 
+    /// Returns a stamp of the most recent height map modification among all chunks of the terrain.
+    /// See [`Chunk::height_map_stamp`] for more info.
+    pub fn height_map_stamp(&self) -> u64 {
+        self.chunks
+            .iter()
+            .map(|c| c.height_map_stamp)
+            .max()
+            .unwrap_or_default()
+    }
+
     /// Returns a mutable reference to chunks of the terrain.
     pub fn chunks_mut(&mut self) -> &mut [Chunk] {
         self.bounding_box_dirty.set(true);
@@ -1012,11 +1409,12 @@ impl Terrain {
     where
         F: FnMut(&mut f32, Vector2<f32>),
     {
-        for chunk in self.chunks.iter_mut() {
+        for chunk in self.chunks.iter_mut().filter(|c| c.is_loaded()) {
             let mut texture_data = chunk.heightmap.as_ref().unwrap().data_ref();
             let mut texture_modifier = texture_data.modify();
             let height_map = texture_modifier.data_mut_of_type::<f32>().unwrap();
 
+            let mut modified = false;
             for iy in 0..chunk.height_map_size.y {
                 let kz = iy as f32 / (chunk.height_map_size.y - 1) as f32;
                 for ix in 0..chunk.height_map_size.y {
@@ -1027,15 +1425,21 @@ impl Terrain {
 
                     let index = (iy * chunk.height_map_size.x + ix) as usize;
 
-                    func(&mut height_map[index], pixel_position)
+                    let pixel = &mut height_map[index];
+                    let old_value = *pixel;
+                    func(pixel, pixel_position);
+                    modified |= *pixel != old_value;
                 }
             }
 
             drop(texture_modifier);
             drop(texture_data);
 
-            chunk.quad_tree =
-                make_quad_tree(&chunk.heightmap, chunk.height_map_size, chunk.block_size);
+            if modified {
+                chunk.height_map_stamp = next_height_map_stamp();
+                chunk.quad_tree =
+                    make_quad_tree(&chunk.heightmap, chunk.height_map_size, chunk.block_size);
+            }
         }
 
         self.bounding_box_dirty.set(true);
@@ -1049,16 +1453,7 @@ impl Terrain {
         match brush.mode {
             BrushMode::ModifyHeightMap { amount } => {
                 self.for_each_height_map_pixel(|pixel, pixel_position| {
-                    let k = match brush.shape {
-                        BrushShape::Circle { radius } => {
-                            1.0 - ((center - pixel_position).norm() / radius).powf(2.0)
-                        }
-                        BrushShape::Rectangle { .. } => 1.0,
-                    };
-
-                    if brush.shape.contains(center, pixel_position) {
-                        *pixel += k * amount;
-                    }
+                    *pixel += brush.weight(center, pixel_position) * amount;
                 });
             }
             BrushMode::DrawOnMask { layer, alpha } => {
@@ -1068,41 +1463,57 @@ impl Terrain {
 
                 let alpha = alpha.clamp(-1.0, 1.0);
 
-                for chunk in self.chunks.iter_mut() {
+                for chunk in self.chunks.iter_mut().filter(|c| c.is_loaded()) {
                     let chunk_position = chunk.local_position();
-                    let mut texture_data = chunk.layer_masks[layer].data_ref();
-                    let mut texture_data_mut = texture_data.modify();
+                    let physical_size = chunk.physical_size;
+
+                    // Layers are blended one over another, so the layers above the painted one
+                    // must be erased to make the painted layer visible.
+                    let erased_layers = if alpha > 0.0 {
+                        layer + 1..chunk.layer_masks.len()
+                    } else {
+                        0..0
+                    };
 
-                    let (texture_width, texture_height) =
-                        if let TextureKind::Rectangle { width, height } = texture_data_mut.kind() {
-                            (width as usize, height as usize)
+                    for (mask_index, mask) in chunk.layer_masks.iter_mut().enumerate() {
+                        let sign = if mask_index == layer {
+                            1.0
+                        } else if erased_layers.contains(&mask_index) {
+                            -1.0
                         } else {
-                            unreachable!("Mask must be a 2D greyscale image!")
+                            continue;
                         };
 
-                    for z in 0..texture_height {
-                        let kz = z as f32 / (texture_height - 1) as f32;
-                        for x in 0..texture_width {
-                            let kx = x as f32 / (texture_width - 1) as f32;
-
-                            let pixel_position = chunk_position
-                                + Vector2::new(
-                                    kx * chunk.physical_size.x,
-                                    kz * chunk.physical_size.y,
-                                );
+                        let mut texture_data = mask.data_ref();
+                        let mut texture_data_mut = texture_data.modify();
 
-                            let k = match brush.shape {
-                                BrushShape::Circle { radius } => {
-                                    1.0 - ((center - pixel_position).norm() / radius).powf(4.0)
-                                }
-                                BrushShape::Rectangle { .. } => 1.0,
+                        let (texture_width, texture_height) =
+                            if let TextureKind::Rectangle { width, height } =
+                                texture_data_mut.kind()
+                            {
+                                (width as usize, height as usize)
+                            } else {
+                                unreachable!("Mask must be a 2D greyscale image!")
                             };
 
-                            if brush.shape.contains(center, pixel_position) {
-                                // We can draw on mask directly, without any problems because it has R8 pixel format.
-                                let data = texture_data_mut.data_mut();
-                                let pixel = &mut data[z * texture_width + x];
-                                *pixel = (*pixel as f32 + k * alpha * 255.0).min(255.0) as u8;
+                        // We can draw on mask directly, without any problems because it has R8 pixel format.
+                        let data = texture_data_mut.data_mut();
+
+                        for z in 0..texture_height {
+                            let kz = z as f32 / (texture_height - 1) as f32;
+                            for x in 0..texture_width {
+                                let kx = x as f32 / (texture_width - 1) as f32;
+
+                                let pixel_position = chunk_position
+                                    + Vector2::new(kx * physical_size.x, kz * physical_size.y);
+
+                                let k = brush.weight(center, pixel_position);
+                                if k > 0.0 {
+                                    let pixel = &mut data[z * texture_width + x];
+                                    *pixel = (*pixel as f32 + sign * k * alpha * 255.0)
+                                        .clamp(0.0, 255.0)
+                                        as u8;
+                                }
                             }
                         }
                     }
@@ -1110,11 +1521,60 @@ impl Terrain {
             }
             BrushMode::FlattenHeightMap { height } => {
                 self.for_each_height_map_pixel(|pixel, pixel_position| {
-                    if brush.shape.contains(center, pixel_position) {
-                        *pixel = height;
+                    let k = brush.weight(center, pixel_position);
+                    if k > 0.0 {
+                        *pixel += (height - *pixel) * k;
                     }
                 });
             }
+            BrushMode::DrawHoles { erase } => {
+                let mask_size = *self.mask_size;
+                let value = if erase { 255 } else { 0 };
+
+                for chunk in self.chunks.iter_mut().filter(|c| c.is_loaded()) {
+                    let chunk_position = chunk.local_position();
+                    let physical_size = chunk.physical_size;
+
+                    // Collect affected pixels first, to not create hole masks for chunks that
+                    // are not touched by the brush.
+                    let mut pixels = Vec::new();
+                    for z in 0..mask_size.y {
+                        let kz = z as f32 / (mask_size.y - 1).max(1) as f32;
+                        for x in 0..mask_size.x {
+                            let kx = x as f32 / (mask_size.x - 1).max(1) as f32;
+
+                            let pixel_position = chunk_position
+                                + Vector2::new(kx * physical_size.x, kz * physical_size.y);
+
+                            if brush.weight(center, pixel_position) > 0.0 {
+                                pixels.push((z * mask_size.x + x) as usize);
+                            }
+                        }
+                    }
+
+                    if pixels.is_empty() || (erase && chunk.hole_mask.is_none()) {
+                        continue;
+                    }
+
+                    let hole_mask = chunk
+                        .hole_mask
+                        .get_or_insert_with(|| create_layer_mask(mask_size.x, mask_size.y, 255));
+
+                    let mut texture_data = hole_mask.data_ref();
+                    let mut texture_data_mut = texture_data.modify();
+                    let data = texture_data_mut.data_mut();
+                    for index in pixels {
+                        if let Some(pixel) = data.get_mut(index) {
+                            *pixel = value;
+                        }
+                    }
+                    drop(texture_data_mut);
+                    drop(texture_data);
+
+                    // Holes are removed from height field colliders, so they must be updated too.
+                    chunk.height_map_stamp = next_height_map_stamp();
+                }
+            }
         }
     }
 
@@ -1144,6 +1604,10 @@ impl Terrain {
 
             // Check each cell of each chunk for intersection in 2D.
             'chunk_loop: for (chunk_index, chunk) in self.chunks.iter().enumerate() {
+                if !chunk.is_loaded() {
+                    continue;
+                }
+
                 let texture = chunk.heightmap.as_ref().unwrap().data_ref();
                 let height_map = texture.data_of_type::<f32>().unwrap();
 
@@ -1255,6 +1719,7 @@ impl Terrain {
 
     /// Removes a layer at the given index together with its respective blending masks from each chunk.
     pub fn remove_layer(&mut self, layer_index: usize) -> (Layer, Vec<TextureResource>) {
+        self.load_chunks();
         let layer = self
             .layers
             .get_value_mut_and_mark_modified()
@@ -1277,6 +1742,7 @@ impl Terrain {
 
     /// Inserts the layer at the given index together with its blending masks for each chunk.
     pub fn insert_layer(&mut self, layer: Layer, mut masks: Vec<TextureResource>, index: usize) {
+        self.load_chunks();
         self.layers
             .get_value_mut_and_mark_modified()
             .insert(index, layer);
@@ -1300,8 +1766,14 @@ impl Terrain {
     fn resize_masks(&mut self, mut new_size: Vector2<u32>) {
         new_size = new_size.sup(&Vector2::repeat(1));
 
+        self.load_chunks();
+
         for chunk in self.chunks.iter_mut() {
-            for mask in chunk.layer_masks.iter_mut() {
+            for mask in chunk
+                .layer_masks
+                .iter_mut()
+                .chain(chunk.hole_mask.iter_mut())
+            {
                 let data = mask.data_ref();
 
                 let mask_image = ImageBuffer::<Luma<u8>, Vec<u8>>::from_vec(
@@ -1341,6 +1813,8 @@ impl Terrain {
     fn resize_height_maps(&mut self, mut new_size: Vector2<u32>) {
         new_size = new_size.sup(&Vector2::repeat(2));
 
+        self.load_chunks();
+
         for chunk in self.chunks.iter_mut() {
             let texture = chunk.heightmap.as_ref().unwrap().data_ref();
             let mut heightmap = texture.data_of_type::<f32>().unwrap().to_vec();
@@ -1382,6 +1856,7 @@ impl Terrain {
 
             chunk.height_map_size = new_size;
             chunk.heightmap = Some(make_height_map_texture(resampled_heightmap, new_size));
+            chunk.height_map_stamp = next_height_map_stamp();
         }
 
         self.height_map_size.set_value_and_mark_modified(new_size);
@@ -1403,7 +1878,7 @@ impl NodeTrait for Terrain {
         if self.bounding_box_dirty.get() {
             let mut max_height = -f32::MAX;
             let mut min_height = f32::MAX;
-            for chunk in self.chunks.iter() {
+            for chunk in self.chunks.iter().filter(|c| c.is_loaded()) {
                 let texture = chunk.heightmap.as_ref().unwrap().data_ref();
                 let height_map = texture.data_of_type::<f32>().unwrap();
                 for &height in height_map {
@@ -1459,14 +1934,25 @@ impl NodeTrait for Terrain {
             return;
         }
 
+        let lod_distance = if *self.lod_distance > 0.0 {
+            self.lod_distance.min(ctx.z_far)
+        } else {
+            ctx.z_far
+        };
+
         for (layer_index, layer) in self.layers().iter().enumerate() {
-            for chunk in self.chunks_ref().iter() {
+            for chunk in self.chunks_ref().iter().filter(|c| c.is_loaded()) {
                 let levels = (0..chunk.quad_tree.max_level)
                     .map(|n| {
-                        ctx.z_far
-                            * ((chunk.quad_tree.max_level - n) as f32
-                                / chunk.quad_tree.max_level as f32)
-                                .powf(3.0)
+                        // The coarsest level is visible up to the far clipping plane.
+                        if n == 0 {
+                            ctx.z_far
+                        } else {
+                            lod_distance
+                                * ((chunk.quad_tree.max_level - n) as f32
+                                    / chunk.quad_tree.max_level as f32)
+                                    .powf(*self.lod_falloff)
+                        }
                     })
                     .collect::<Vec<_>>();
 
@@ -1508,6 +1994,27 @@ impl NodeTrait for Terrain {
                     "Unable to set height map texture for terrain material.",
                 );
 
+                Log::verify_message(
+                    material.set_property(
+                        &ImmutableString::new(&layer.skirt_depth_property_name),
+                        PropertyValue::Float(*self.skirt_depth),
+                    ),
+                    "Unable to set skirt depth for terrain material.",
+                );
+
+                if let Some(hole_mask) = chunk.hole_mask.as_ref() {
+                    Log::verify_message(
+                        material.set_property(
+                            &ImmutableString::new(&layer.hole_mask_property_name),
+                            PropertyValue::Sampler {
+                                value: Some(hole_mask.clone()),
+                                fallback: Default::default(),
+                            },
+                        ),
+                        "Unable to set hole mask texture for terrain material.",
+                    );
+                }
+
                 for node in selection {
                     let kx = node.position.x as f32 / self.height_map_size.x as f32;
                     let kz = node.position.y as f32 / self.height_map_size.y as f32;
@@ -1556,6 +2063,7 @@ impl NodeTrait for Terrain {
                                     node.persistent_index,
                                 ),
                                 node_handle: self.self_handle,
+                                color: Color::WHITE,
                             },
                         );
                     } else {
@@ -1579,6 +2087,7 @@ impl NodeTrait for Terrain {
                                             node.persistent_index,
                                         ),
                                         node_handle: self.self_handle,
+                                        color: Color::WHITE,
                                     },
                                 );
                             }
@@ -1616,16 +2125,15 @@ pub enum BrushShape {
 uuid_provider!(BrushShape = "a4dbfba0-077c-4658-9972-38384a8432f9");
 
 impl BrushShape {
-    fn contains(&self, brush_center: Vector2<f32>, pixel_position: Vector2<f32>) -> bool {
+    /// Returns normalized distance from the center of the brush to the given point, where 0.0 is
+    /// the center of the brush and 1.0 is its edge.
+    fn normalized_distance(&self, brush_center: Vector2<f32>, pixel_position: Vector2<f32>) -> f32 {
+        let offset = pixel_position - brush_center;
         match *self {
-            BrushShape::Circle { radius } => (brush_center - pixel_position).norm() < radius,
-            BrushShape::Rectangle { width, length } => Rect::new(
-                brush_center.x - width * 0.5,
-                brush_center.y - length * 0.5,
-                width,
-                length,
-            )
-            .contains(pixel_position),
+            BrushShape::Circle { radius } => offset.norm() / radius.max(f32::EPSILON),
+            BrushShape::Rectangle { width, length } => (offset.x.abs()
+                / (width * 0.5).max(f32::EPSILON))
+            .max(offset.y.abs() / (length * 0.5).max(f32::EPSILON)),
         }
     }
 }
@@ -1643,7 +2151,9 @@ pub enum BrushMode {
         /// Fixed height value for flattening.
         height: f32,
     },
-    /// Draws on a given layer.
+    /// Draws on a given layer. Since layers are blended one over another, painting (positive alpha)
+    /// also erases the same amount from the masks of the layers above the given one, so the painted
+    /// layer becomes visible.
     DrawOnMask {
         /// A layer to draw on.
         layer: usize,
@@ -1651,6 +2161,12 @@ pub enum BrushMode {
         /// values from mask, and positive - paints.
         alpha: f32,
     },
+    /// Cuts holes in the terrain, for example for cave or building entrances. Holes are not rendered
+    /// and removed from height field colliders.
+    DrawHoles {
+        /// If `true`, the brush restores the surface of the terrain instead of cutting holes.
+        erase: bool,
+    },
 }
 
 uuid_provider!(BrushMode = "48ad4cac-05f3-485a-b2a3-66812713841f");
@@ -1665,6 +2181,37 @@ pub struct Brush {
     pub shape: BrushShape,
     /// Paint mode of the brush.
     pub mode: BrushMode,
+    /// Defines how smoothly the effect of the brush fades out towards its edges. 0.0 means that
+    /// the brush has hard edges, 1.0 means that the effect fades out from the center of the brush
+    /// to its edges.
+    #[reflect(
+        description = "Defines how smoothly the effect of the brush fades out towards its edges.",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    pub falloff: f32,
+}
+
+impl Brush {
+    /// Returns the strength of the brush at the given point in local 2D coordinates of a terrain.
+    /// The strength is in `[0.0; 1.0]` range, it is zero outside of the brush.
+    pub fn weight(&self, brush_center: Vector2<f32>, pixel_position: Vector2<f32>) -> f32 {
+        let distance = self.shape.normalized_distance(brush_center, pixel_position);
+        if distance >= 1.0 {
+            return 0.0;
+        }
+
+        let falloff = self.falloff.clamp(0.0, 1.0);
+        let hard_part = 1.0 - falloff;
+        if distance <= hard_part {
+            1.0
+        } else {
+            // Smooth step from the hard part to the edge.
+            let t = (distance - hard_part) / falloff;
+            1.0 - t * t * (3.0 - 2.0 * t)
+        }
+    }
 }
 
 /// Terrain builder allows you to quickly build a terrain with required features.
@@ -1678,13 +2225,27 @@ pub struct TerrainBuilder {
     block_size: Vector2<u32>,
     layers: Vec<Layer>,
     decal_layer_index: u8,
+    lod_distance: f32,
+    lod_falloff: f32,
+    skirt_depth: f32,
+    streaming: TerrainStreaming,
 }
 
 fn create_layer_mask(width: u32, height: u32, value: u8) -> TextureResource {
+    make_mask_texture(
+        Vector2::new(width, height),
+        vec![value; (width * height) as usize],
+    )
+}
+
+fn make_mask_texture(size: Vector2<u32>, pixels: Vec<u8>) -> TextureResource {
     let mask = TextureResource::from_bytes(
-        TextureKind::Rectangle { width, height },
+        TextureKind::Rectangle {
+            width: size.x,
+            height: size.y,
+        },
         TexturePixelKind::R8,
-        vec![value; (width * height) as usize],
+        pixels,
         ResourceKind::Embedded,
     )
     .unwrap();
@@ -1710,6 +2271,10 @@ impl TerrainBuilder {
             block_size: Vector2::new(32, 32),
             layers: Default::default(),
             decal_layer_index: 0,
+            lod_distance: 0.0,
+            lod_falloff: 3.0,
+            skirt_depth: 1.0,
+            streaming: Default::default(),
         }
     }
 
@@ -1762,6 +2327,31 @@ impl TerrainBuilder {
         self
     }
 
+    /// Sets desired distance at which the terrain will be rendered with the lowest level of detail. Zero
+    /// means the far clipping plane of the camera.
+    pub fn with_lod_distance(mut self, lod_distance: f32) -> Self {
+        self.lod_distance = lod_distance;
+        self
+    }
+
+    /// Sets desired level of detail falloff. See [`Terrain::set_lod_falloff`] for more info.
+    pub fn with_lod_falloff(mut self, lod_falloff: f32) -> Self {
+        self.lod_falloff = lod_falloff;
+        self
+    }
+
+    /// Sets desired depth of the skirts. See [`Terrain::set_skirt_depth`] for more info.
+    pub fn with_skirt_depth(mut self, skirt_depth: f32) -> Self {
+        self.skirt_depth = skirt_depth;
+        self
+    }
+
+    /// Sets desired chunk streaming settings. See [`TerrainStreaming`] docs for more info.
+    pub fn with_streaming(mut self, streaming: TerrainStreaming) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
     /// Build terrain node.
     pub fn build_node(self) -> Node {
         let mut chunks = Vec::new();
@@ -1770,6 +2360,7 @@ impl TerrainBuilder {
                 let heightmap =
                     vec![0.0; (self.height_map_size.x * self.height_map_size.y) as usize];
                 let chunk = Chunk {
+                    height_map_stamp: next_height_map_stamp(),
                     quad_tree: QuadTree::new(&heightmap, self.height_map_size, self.block_size),
                     height_map_size: self.height_map_size,
                     heightmap: Some(make_height_map_texture(heightmap, self.height_map_size)),
@@ -1793,6 +2384,8 @@ impl TerrainBuilder {
                             )
                         })
                         .collect::<Vec<_>>(),
+                    hole_mask: None,
+                    swap_file: None,
                     version: VERSION,
                     block_size: self.block_size,
                 };
@@ -1816,6 +2409,10 @@ impl TerrainBuilder {
             version: VERSION,
             geometry: TerrainGeometry::new(self.block_size),
             block_size: self.block_size.into(),
+            lod_distance: self.lod_distance.into(),
+            lod_falloff: self.lod_falloff.into(),
+            skirt_depth: self.skirt_depth.into(),
+            streaming: self.streaming.into(),
         };
         Node::new(terrain)
     }
@@ -1825,3 +2422,100 @@ impl TerrainBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            terrain::{Brush, BrushMode, BrushShape, TerrainBuilder, TerrainStreaming},
+        },
+    };
+
+    #[test]
+    fn test_brush_falloff() {
+        let mut brush = Brush {
+            center: Vector3::default(),
+            shape: BrushShape::Circle { radius: 2.0 },
+            mode: BrushMode::ModifyHeightMap { amount: 1.0 },
+            falloff: 0.0,
+        };
+        let center = Vector2::default();
+
+        // Hard edges.
+        assert_eq!(brush.weight(center, Vector2::new(1.9, 0.0)), 1.0);
+        assert_eq!(brush.weight(center, Vector2::new(2.1, 0.0)), 0.0);
+
+        // Smooth edges.
+        brush.falloff = 0.5;
+        assert_eq!(brush.weight(center, Vector2::new(0.5, 0.0)), 1.0);
+        let middle = brush.weight(center, Vector2::new(1.5, 0.0));
+        assert!((middle - 0.5).abs() < 0.001);
+        assert_eq!(brush.weight(center, Vector2::new(0.0, 2.5)), 0.0);
+    }
+
+    #[test]
+    fn test_draw_holes() {
+        let mut node = TerrainBuilder::new(BaseBuilder::new())
+            .with_chunk_size(Vector2::new(16.0, 16.0))
+            .with_width_chunks(0..1)
+            .with_length_chunks(0..1)
+            .with_mask_size(Vector2::new(16, 16))
+            .build_node();
+        let terrain = node.as_terrain_mut();
+        assert!(terrain.chunks_ref()[0].hole_mask().is_none());
+
+        let mut brush = Brush {
+            center: Vector3::new(8.0, 0.0, 8.0),
+            shape: BrushShape::Circle { radius: 2.0 },
+            mode: BrushMode::DrawHoles { erase: false },
+            falloff: 0.0,
+        };
+        terrain.draw(&brush);
+
+        let chunk = &terrain.chunks_ref()[0];
+        assert!(chunk.is_hole(Vector2::new(0.5, 0.5)));
+        assert!(!chunk.is_hole(Vector2::new(0.0, 0.0)));
+
+        brush.mode = BrushMode::DrawHoles { erase: true };
+        terrain.draw(&brush);
+        assert!(!terrain.chunks_ref()[0].is_hole(Vector2::new(0.5, 0.5)));
+    }
+    #[test]
+    fn test_chunk_streaming() {
+        let directory = std::env::temp_dir().join("fyrox_terrain_streaming_test");
+        let mut node = TerrainBuilder::new(BaseBuilder::new())
+            .with_chunk_size(Vector2::new(16.0, 16.0))
+            .with_width_chunks(0..2)
+            .with_length_chunks(0..1)
+            .with_height_map_size(Vector2::new(16, 16))
+            .with_mask_size(Vector2::new(16, 16))
+            .with_streaming(TerrainStreaming {
+                enabled: true,
+                directory: directory.clone(),
+                load_distance: 10.0,
+                unload_distance: 20.0,
+            })
+            .build_node();
+        let terrain = node.as_terrain_mut();
+        terrain.for_each_height_map_pixel(|pixel, position| *pixel = position.x);
+        let heights = terrain.chunks_ref()[1].heightmap_owned();
+
+        // Observer is near the first chunk, the second one must be streamed out.
+        terrain.update_streaming(Vector3::new(0.0, 0.0, 8.0));
+        assert!(terrain.chunks_ref()[0].is_loaded());
+        assert!(!terrain.chunks_ref()[1].is_loaded());
+
+        // Observer is near the second chunk now.
+        terrain.update_streaming(Vector3::new(30.0, 0.0, 8.0));
+        assert!(!terrain.chunks_ref()[0].is_loaded());
+        assert!(terrain.chunks_ref()[1].is_loaded());
+        assert_eq!(terrain.chunks_ref()[1].heightmap_owned(), heights);
+
+        terrain.load_chunks();
+        assert!(terrain.chunks_ref().iter().all(|c| c.is_loaded()));
+
+        let _ = std::fs::remove_dir_all(directory);
+    }
+}
@@ -0,0 +1,639 @@
+//! Trail is a camera-facing ribbon, that is emitted behind a moving node. See [`Trail`] docs for
+//! more info.
+
+use crate::{
+    asset::untyped::ResourceKind,
+    core::{
+        algebra::{Vector2, Vector3},
+        color::Color,
+        color_gradient::{ColorGradient, ColorGradientBuilder, GradientPoint},
+        curve::{Curve, CurveKey, CurveKeyKind},
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    material::{Material, MaterialResource},
+    renderer::{self, batch::RenderContext},
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        mesh::RenderPath,
+        node::{Node, NodeTrait, UpdateContext},
+        sprite::SpriteVertex,
+    },
+};
+use fyrox_core::uuid_provider;
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines how a texture is mapped along a trail.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum TrailTextureMode {
+    /// Texture is stretched along the whole trail.
+    #[default]
+    Stretch,
+    /// Texture is repeated along the trail, each tile has [`Trail::tile_length`] length.
+    Tile,
+}
+
+uuid_provider!(TrailTextureMode = "2c8f4a1e-6d3b-4e9a-b7c5-0f1d3e5a7b92");
+
+/// Defines how a trail is oriented in space.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum TrailAlignment {
+    /// Trail always faces the camera. It is good for projectiles, sword slashes, etc.
+    #[default]
+    View,
+    /// Trail is spread along the local X axis of the node at the moment of emission of each
+    /// point. It is good for tire marks, which must lie flat on the ground.
+    Local,
+}
+
+uuid_provider!(TrailAlignment = "9a5d2e7c-1b4f-4a8e-9c3d-6e2f0b8a4d17");
+
+#[derive(Clone, Debug, PartialEq)]
+struct TrailPoint {
+    position: Vector3<f32>,
+    side: Vector3<f32>,
+    birth_time: f32,
+}
+
+/// Trail is a ribbon, that is emitted behind a moving node. Typical use cases are sword slashes,
+/// projectiles, tire marks, etc. Trail records global positions of itself while moving, so the
+/// most common usage is to attach a trail to a moving node.
+///
+/// # Shape
+///
+/// A new point is added to the trail when the node moves further than
+/// [`Self::min_vertex_distance`] from the previous point, each point lives for
+/// [`Self::lifetime`] seconds. Width and color of a point are defined by the respective curve and
+/// gradient, that are evaluated on normalized age of a point (`0.0` - newest, `1.0` - oldest).
+///
+/// # Rendering
+///
+/// Trails are rendered using the standard sprite material by default, custom materials must use
+/// the same vertex format as sprites (see [`SpriteVertex`]).
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::pool::Handle,
+/// #     scene::{base::BaseBuilder, graph::Graph, node::Node, trail::TrailBuilder},
+/// # };
+/// fn create_trail(graph: &mut Graph, projectile: Handle<Node>) -> Handle<Node> {
+///     let trail = TrailBuilder::new(BaseBuilder::new())
+///         .with_lifetime(0.5)
+///         .with_width(0.1)
+///         .build(graph);
+///     graph.link_nodes(trail, projectile);
+///     trail
+/// }
+/// ```
+#[derive(Debug, Visit, Reflect, Clone)]
+pub struct Trail {
+    base: Base,
+
+    #[reflect(setter = "set_emitting")]
+    emitting: InheritableVariable<bool>,
+
+    #[reflect(min_value = 0.0, step = 0.05, setter = "set_lifetime")]
+    lifetime: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.01, setter = "set_min_vertex_distance")]
+    min_vertex_distance: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.01, setter = "set_width")]
+    width: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_width_over_lifetime")]
+    width_over_lifetime: InheritableVariable<Curve>,
+
+    #[reflect(setter = "set_color_over_lifetime")]
+    color_over_lifetime: InheritableVariable<ColorGradient>,
+
+    #[reflect(setter = "set_texture_mode")]
+    texture_mode: InheritableVariable<TrailTextureMode>,
+
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_tile_length")]
+    tile_length: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_alignment")]
+    alignment: InheritableVariable<TrailAlignment>,
+
+    material: InheritableVariable<MaterialResource>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    points: Vec<TrailPoint>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    time: f32,
+}
+
+impl Deref for Trail {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Trail {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for Trail {
+    fn default() -> Self {
+        TrailBuilder::new(BaseBuilder::new()).build_trail()
+    }
+}
+
+impl TypeUuidProvider for Trail {
+    fn type_uuid() -> Uuid {
+        uuid!("4e1b7c3a-9d2f-4b6e-8a5c-1f3d7e9b2a64")
+    }
+}
+
+impl Trail {
+    /// Enables or disables emission of new points. Existing points will fade out as usual.
+    pub fn set_emitting(&mut self, emitting: bool) -> bool {
+        self.emitting.set_value_and_mark_modified(emitting)
+    }
+
+    /// Returns `true` if the trail emits new points, `false` - otherwise.
+    pub fn is_emitting(&self) -> bool {
+        *self.emitting
+    }
+
+    /// Sets new lifetime (in seconds) of points of the trail.
+    pub fn set_lifetime(&mut self, lifetime: f32) -> f32 {
+        self.lifetime.set_value_and_mark_modified(lifetime.max(0.0))
+    }
+
+    /// Returns current lifetime (in seconds) of points of the trail.
+    pub fn lifetime(&self) -> f32 {
+        *self.lifetime
+    }
+
+    /// Sets new minimal distance between adjacent points of the trail. Lower values make the
+    /// trail smoother, but increase amount of vertices.
+    pub fn set_min_vertex_distance(&mut self, distance: f32) -> f32 {
+        self.min_vertex_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns current minimal distance between adjacent points of the trail.
+    pub fn min_vertex_distance(&self) -> f32 {
+        *self.min_vertex_distance
+    }
+
+    /// Sets new width of the trail. Actual width of each point is multiplied by the value of
+    /// [`Self::width_over_lifetime`] curve.
+    pub fn set_width(&mut self, width: f32) -> f32 {
+        self.width.set_value_and_mark_modified(width.max(0.0))
+    }
+
+    /// Returns current width of the trail.
+    pub fn width(&self) -> f32 {
+        *self.width
+    }
+
+    /// Sets new curve, that defines the width of points over their lifetime. The curve is
+    /// evaluated in `[0; 1]` range.
+    pub fn set_width_over_lifetime(&mut self, curve: Curve) -> Curve {
+        self.width_over_lifetime.set_value_and_mark_modified(curve)
+    }
+
+    /// Returns current curve, that defines the width of points over their lifetime.
+    pub fn width_over_lifetime(&self) -> &Curve {
+        &self.width_over_lifetime
+    }
+
+    /// Sets new gradient, that defines the color of points over their lifetime.
+    pub fn set_color_over_lifetime(&mut self, gradient: ColorGradient) -> ColorGradient {
+        self.color_over_lifetime
+            .set_value_and_mark_modified(gradient)
+    }
+
+    /// Returns current gradient, that defines the color of points over their lifetime.
+    pub fn color_over_lifetime(&self) -> &ColorGradient {
+        &self.color_over_lifetime
+    }
+
+    /// Sets new texture mapping mode.
+    pub fn set_texture_mode(&mut self, mode: TrailTextureMode) -> TrailTextureMode {
+        self.texture_mode.set_value_and_mark_modified(mode)
+    }
+
+    /// Returns current texture mapping mode.
+    pub fn texture_mode(&self) -> TrailTextureMode {
+        *self.texture_mode
+    }
+
+    /// Sets new length of a texture tile. It is used only in [`TrailTextureMode::Tile`] mode.
+    /// Keep in mind, that the texture must have repeat wrapping mode.
+    pub fn set_tile_length(&mut self, length: f32) -> f32 {
+        self.tile_length.set_value_and_mark_modified(length)
+    }
+
+    /// Returns current length of a texture tile.
+    pub fn tile_length(&self) -> f32 {
+        *self.tile_length
+    }
+
+    /// Sets new alignment of the trail.
+    pub fn set_alignment(&mut self, alignment: TrailAlignment) -> TrailAlignment {
+        self.alignment.set_value_and_mark_modified(alignment)
+    }
+
+    /// Returns current alignment of the trail.
+    pub fn alignment(&self) -> TrailAlignment {
+        *self.alignment
+    }
+
+    /// Returns a reference to the current material used by the trail.
+    pub fn material(&self) -> &InheritableVariable<MaterialResource> {
+        &self.material
+    }
+
+    /// Returns a reference to the current material used by the trail.
+    pub fn material_mut(&mut self) -> &mut InheritableVariable<MaterialResource> {
+        &mut self.material
+    }
+
+    /// Returns amount of points of the trail.
+    pub fn point_count(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Removes every point of the trail. It could be useful to prevent long stretched segments
+    /// when the node is teleported.
+    pub fn clear_points(&mut self) {
+        self.points.clear();
+    }
+
+    fn add_point(&mut self) {
+        let position = self.global_position();
+        let side = self
+            .global_transform()
+            .transform_vector(&Vector3::x())
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::x);
+        let point = TrailPoint {
+            position,
+            side,
+            birth_time: self.time,
+        };
+
+        let count = self.points.len();
+        match count {
+            0 => self.points.push(point),
+            1 => {
+                if self.points[0].position.metric_distance(&position) > f32::EPSILON {
+                    self.points.push(point);
+                }
+            }
+            _ => {
+                // The last point follows the node until it is far enough from the previous one.
+                let previous = &self.points[count - 2];
+                let head = &self.points[count - 1];
+                if previous.position.metric_distance(&head.position) >= *self.min_vertex_distance {
+                    self.points.push(point);
+                } else {
+                    self.points[count - 1] = point;
+                }
+            }
+        }
+    }
+
+    fn normalized_age(&self, point: &TrailPoint) -> f32 {
+        if *self.lifetime > 0.0 {
+            ((self.time - point.birth_time) / *self.lifetime).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+impl NodeTrait for Trail {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        if self.points.is_empty() {
+            return self.base.world_bounding_box();
+        }
+
+        let mut bounding_box = AxisAlignedBoundingBox::from_point(self.points[0].position);
+        for point in self.points.iter().skip(1) {
+            bounding_box.add_point(point.position);
+        }
+        bounding_box.inflate(Vector3::repeat(*self.width));
+        bounding_box
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        self.time += context.dt;
+
+        let time = self.time;
+        let lifetime = *self.lifetime;
+        let dead_count = self
+            .points
+            .iter()
+            .take_while(|p| time - p.birth_time >= lifetime)
+            .count();
+        self.points.drain(..dead_count);
+
+        if *self.emitting && self.is_globally_enabled() {
+            self.add_point();
+        }
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) {
+        if self.points.len() < 2
+            || !self.global_visibility()
+            || !self.is_globally_enabled()
+            || !ctx.frustum.is_intersects_aabb(&self.world_bounding_box())
+        {
+            return;
+        }
+
+        if renderer::is_shadow_pass(ctx.render_pass_name) || !self.cast_shadows() {
+            return;
+        }
+
+        let count = self.points.len();
+
+        // Texture coordinates are measured from the newest point, so the texture does not slide
+        // along the trail when old points are removed.
+        let mut distances = vec![0.0; count];
+        for i in (0..count - 1).rev() {
+            distances[i] = distances[i + 1]
+                + self.points[i]
+                    .position
+                    .metric_distance(&self.points[i + 1].position);
+        }
+        let total_length = distances[0].max(f32::EPSILON);
+
+        let mut vertices = Vec::with_capacity(count * 2);
+        for (i, point) in self.points.iter().enumerate() {
+            let age = self.normalized_age(point);
+            let half_width = *self.width * self.width_over_lifetime.value_at(age) * 0.5;
+            let color = self.color_over_lifetime.get_color(age);
+
+            let side = match *self.alignment {
+                TrailAlignment::View => {
+                    let previous = &self.points[i.saturating_sub(1)];
+                    let next = &self.points[(i + 1).min(count - 1)];
+                    let tangent = next.position - previous.position;
+                    let to_observer = ctx.observer_position - point.position;
+                    tangent
+                        .cross(&to_observer)
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or(point.side)
+                }
+                TrailAlignment::Local => point.side,
+            };
+
+            let u = match *self.texture_mode {
+                TrailTextureMode::Stretch => 1.0 - distances[i] / total_length,
+                TrailTextureMode::Tile => distances[i] / self.tile_length.max(f32::EPSILON),
+            };
+
+            for (offset, v) in [(half_width, 0.0), (-half_width, 1.0)] {
+                vertices.push(SpriteVertex {
+                    position: point.position + side.scale(offset),
+                    tex_coord: Vector2::new(u, v),
+                    params: Vector2::default(),
+                    color,
+                });
+            }
+        }
+
+        let triangles = (0..count as u32 - 1).flat_map(|i| {
+            let a = i * 2;
+            [
+                TriangleDefinition([a, a + 1, a + 2]),
+                TriangleDefinition([a + 2, a + 1, a + 3]),
+            ]
+        });
+
+        ctx.storage.push_triangles(
+            vertices.into_iter(),
+            triangles,
+            &self.material,
+            RenderPath::Forward,
+            0,
+            0,
+            false,
+            self.self_handle,
+        )
+    }
+}
+
+/// Trail builder allows you to construct trails in declarative manner.
+pub struct TrailBuilder {
+    base_builder: BaseBuilder,
+    emitting: bool,
+    lifetime: f32,
+    min_vertex_distance: f32,
+    width: f32,
+    width_over_lifetime: Curve,
+    color_over_lifetime: ColorGradient,
+    texture_mode: TrailTextureMode,
+    tile_length: f32,
+    alignment: TrailAlignment,
+    material: MaterialResource,
+}
+
+impl TrailBuilder {
+    /// Creates new trail builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            emitting: true,
+            lifetime: 1.0,
+            min_vertex_distance: 0.1,
+            width: 0.5,
+            width_over_lifetime: Curve::from(vec![
+                CurveKey::new(0.0, 1.0, CurveKeyKind::Linear),
+                CurveKey::new(1.0, 0.0, CurveKeyKind::Linear),
+            ]),
+            color_over_lifetime: ColorGradientBuilder::new()
+                .with_point(GradientPoint::new(0.0, Color::WHITE))
+                .with_point(GradientPoint::new(1.0, Color::from_rgba(255, 255, 255, 0)))
+                .build(),
+            texture_mode: Default::default(),
+            tile_length: 1.0,
+            alignment: Default::default(),
+            material: MaterialResource::new_ok(ResourceKind::Embedded, Material::standard_sprite()),
+        }
+    }
+
+    /// Sets whether the trail should emit new points or not.
+    pub fn with_emitting(mut self, emitting: bool) -> Self {
+        self.emitting = emitting;
+        self
+    }
+
+    /// Sets desired lifetime of points.
+    pub fn with_lifetime(mut self, lifetime: f32) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Sets desired minimal distance between adjacent points.
+    pub fn with_min_vertex_distance(mut self, distance: f32) -> Self {
+        self.min_vertex_distance = distance;
+        self
+    }
+
+    /// Sets desired width.
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets desired curve of width over lifetime.
+    pub fn with_width_over_lifetime(mut self, curve: Curve) -> Self {
+        self.width_over_lifetime = curve;
+        self
+    }
+
+    /// Sets desired gradient of color over lifetime.
+    pub fn with_color_over_lifetime(mut self, gradient: ColorGradient) -> Self {
+        self.color_over_lifetime = gradient;
+        self
+    }
+
+    /// Sets desired texture mapping mode.
+    pub fn with_texture_mode(mut self, mode: TrailTextureMode) -> Self {
+        self.texture_mode = mode;
+        self
+    }
+
+    /// Sets desired length of a texture tile.
+    pub fn with_tile_length(mut self, length: f32) -> Self {
+        self.tile_length = length;
+        self
+    }
+
+    /// Sets desired alignment.
+    pub fn with_alignment(mut self, alignment: TrailAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Sets desired material.
+    pub fn with_material(mut self, material: MaterialResource) -> Self {
+        self.material = material;
+        self
+    }
+
+    fn build_trail(self) -> Trail {
+        Trail {
+            base: self.base_builder.build_base(),
+            emitting: self.emitting.into(),
+            lifetime: self.lifetime.into(),
+            min_vertex_distance: self.min_vertex_distance.into(),
+            width: self.width.into(),
+            width_over_lifetime: self.width_over_lifetime.into(),
+            color_over_lifetime: self.color_over_lifetime.into(),
+            texture_mode: self.texture_mode.into(),
+            tile_length: self.tile_length.into(),
+            alignment: self.alignment.into(),
+            material: self.material.into(),
+            points: Default::default(),
+            time: 0.0,
+        }
+    }
+
+    /// Creates new trail node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_trail())
+    }
+
+    /// Creates new trail node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            trail::{Trail, TrailBuilder},
+        },
+    };
+
+    #[test]
+    fn test_trail_points() {
+        let mut graph = Graph::new();
+        let trail = TrailBuilder::new(BaseBuilder::new())
+            .with_lifetime(1.0)
+            .with_min_vertex_distance(1.0)
+            .build(&mut graph);
+
+        let mut move_to = |x: f32, dt: f32| {
+            graph[trail]
+                .local_transform_mut()
+                .set_position(Vector3::new(x, 0.0, 0.0));
+            graph.update(Vector2::new(100.0, 100.0), dt, Default::default());
+            graph[trail].cast::<Trail>().unwrap().point_count()
+        };
+
+        assert_eq!(move_to(0.0, 0.1), 1);
+
+        // The last point follows the node until it is far enough from the previous one.
+        assert_eq!(move_to(0.5, 0.1), 2);
+        assert_eq!(move_to(0.8, 0.1), 2);
+        assert_eq!(move_to(1.2, 0.1), 2);
+        assert_eq!(move_to(1.5, 0.1), 3);
+
+        // Every point dies eventually.
+        assert_eq!(move_to(1.5, 2.0), 1);
+    }
+}
@@ -0,0 +1,864 @@
+//! Raycast vehicle is a simple, but robust, way of simulating wheeled vehicles. See [`Vehicle`] and
+//! [`Wheel`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Point3, UnitQuaternion, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        collider::Collider,
+        graph::{
+            physics::{Intersection, RayCastOptions},
+            Graph, NodePool,
+        },
+        node::{Node, NodeTrait, UpdateContext},
+        rigidbody::RigidBody,
+    },
+};
+use fxhash::FxHashSet;
+use std::ops::{Deref, DerefMut};
+
+/// Wheel is a part of a [`Vehicle`]. It defines a suspension ray that starts at the global
+/// position of the wheel node and goes down along its up vector (-Y). The look vector (+Z) of the
+/// wheel node defines the direction in which the wheel rolls. Wheels must be direct children of a
+/// vehicle node, otherwise they won't be simulated.
+///
+/// The wheel node itself does not move, it only defines the mounting point of the suspension.
+/// Use [`Wheel::set_model`] to assign a node (usually a mesh, that is a child of the wheel node)
+/// that will be moved along the suspension, steered and spun by the vehicle.
+#[derive(Clone, Reflect, Visit, Debug)]
+pub struct Wheel {
+    base: Base,
+
+    #[reflect(
+        setter = "set_radius",
+        min_value = 0.0,
+        step = 0.01,
+        description = "Radius of the wheel in meters."
+    )]
+    radius: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_suspension_rest_length",
+        min_value = 0.0,
+        step = 0.01,
+        description = "Length of the suspension (in meters) when it is fully extended."
+    )]
+    suspension_rest_length: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_suspension_stiffness",
+        min_value = 0.0,
+        description = "Stiffness of the suspension spring per kilogram of the mass supported \
+        by the wheel. Higher values make the suspension harder."
+    )]
+    suspension_stiffness: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_suspension_damping",
+        min_value = 0.0,
+        description = "Damping of the suspension per kilogram of the mass supported by the wheel. \
+        Higher values reduce oscillations of the suspension."
+    )]
+    suspension_damping: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_friction",
+        min_value = 0.0,
+        step = 0.05,
+        description = "Friction coefficient between the wheel and the ground. It limits the \
+        maximum force that the wheel could apply to the ground."
+    )]
+    friction: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_steerable",
+        description = "Whether the wheel is turned when the vehicle is steering or not."
+    )]
+    steerable: InheritableVariable<bool>,
+
+    #[reflect(
+        setter = "set_driven",
+        description = "Whether the engine applies torque to the wheel or not."
+    )]
+    driven: InheritableVariable<bool>,
+
+    #[reflect(
+        setter = "set_model",
+        description = "A node (usually a mesh), that will be moved along the suspension, steered \
+        and spun. It must be a child of the wheel node."
+    )]
+    model: InheritableVariable<Handle<Node>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    compression: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    in_contact: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    steering_angle: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    spin_angle: f32,
+}
+
+impl Default for Wheel {
+    fn default() -> Self {
+        WheelBuilder::new(BaseBuilder::new()).build_wheel()
+    }
+}
+
+impl Deref for Wheel {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Wheel {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Wheel {
+    fn type_uuid() -> Uuid {
+        uuid!("0c8f7a8e-4b1d-4d8e-9d0a-53f6e7b2a1c9")
+    }
+}
+
+impl NodeTrait for Wheel {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+impl Wheel {
+    /// Sets radius of the wheel in meters.
+    pub fn set_radius(&mut self, radius: f32) -> f32 {
+        self.radius.set_value_and_mark_modified(radius.max(0.0))
+    }
+
+    /// Returns radius of the wheel in meters.
+    pub fn radius(&self) -> f32 {
+        *self.radius
+    }
+
+    /// Sets length of the suspension (in meters) when it is fully extended.
+    pub fn set_suspension_rest_length(&mut self, length: f32) -> f32 {
+        self.suspension_rest_length
+            .set_value_and_mark_modified(length.max(0.0))
+    }
+
+    /// Returns length of the suspension (in meters) when it is fully extended.
+    pub fn suspension_rest_length(&self) -> f32 {
+        *self.suspension_rest_length
+    }
+
+    /// Sets stiffness of the suspension spring. The value is defined per kilogram of the mass
+    /// supported by the wheel, so the same suspension could be used for vehicles of any mass.
+    /// For example, a vehicle with the stiffness of 60.0 compresses its suspension by
+    /// `9.81 / 60.0 ≈ 0.16` meters when standing still.
+    pub fn set_suspension_stiffness(&mut self, stiffness: f32) -> f32 {
+        self.suspension_stiffness
+            .set_value_and_mark_modified(stiffness.max(0.0))
+    }
+
+    /// Returns stiffness of the suspension spring.
+    pub fn suspension_stiffness(&self) -> f32 {
+        *self.suspension_stiffness
+    }
+
+    /// Sets damping of the suspension. The value is defined per kilogram of the mass supported by
+    /// the wheel.
+    pub fn set_suspension_damping(&mut self, damping: f32) -> f32 {
+        self.suspension_damping
+            .set_value_and_mark_modified(damping.max(0.0))
+    }
+
+    /// Returns damping of the suspension.
+    pub fn suspension_damping(&self) -> f32 {
+        *self.suspension_damping
+    }
+
+    /// Sets friction coefficient between the wheel and the ground. The maximum force that the
+    /// wheel could apply to the ground (to accelerate, brake or turn) is the suspension force
+    /// multiplied by this coefficient.
+    pub fn set_friction(&mut self, friction: f32) -> f32 {
+        self.friction.set_value_and_mark_modified(friction.max(0.0))
+    }
+
+    /// Returns friction coefficient between the wheel and the ground.
+    pub fn friction(&self) -> f32 {
+        *self.friction
+    }
+
+    /// Defines whether the wheel is turned when the vehicle is steering or not.
+    pub fn set_steerable(&mut self, steerable: bool) -> bool {
+        self.steerable.set_value_and_mark_modified(steerable)
+    }
+
+    /// Returns `true` if the wheel is turned when the vehicle is steering, `false` - otherwise.
+    pub fn is_steerable(&self) -> bool {
+        *self.steerable
+    }
+
+    /// Defines whether the engine applies torque to the wheel or not.
+    pub fn set_driven(&mut self, driven: bool) -> bool {
+        self.driven.set_value_and_mark_modified(driven)
+    }
+
+    /// Returns `true` if the engine applies torque to the wheel, `false` - otherwise.
+    pub fn is_driven(&self) -> bool {
+        *self.driven
+    }
+
+    /// Sets a node that will be moved along the suspension, steered and spun. The node must be
+    /// a child of the wheel node, its local transform is overwritten by the vehicle every frame.
+    pub fn set_model(&mut self, model: Handle<Node>) -> Handle<Node> {
+        self.model.set_value_and_mark_modified(model)
+    }
+
+    /// Returns a handle of the visual model of the wheel.
+    pub fn model(&self) -> Handle<Node> {
+        *self.model
+    }
+
+    /// Returns current compression of the suspension in meters.
+    pub fn compression(&self) -> f32 {
+        self.compression
+    }
+
+    /// Returns current length of the suspension in meters.
+    pub fn suspension_length(&self) -> f32 {
+        *self.suspension_rest_length - self.compression
+    }
+
+    /// Returns `true` if the wheel touches the ground, `false` - otherwise.
+    pub fn is_in_contact(&self) -> bool {
+        self.in_contact
+    }
+
+    /// Returns current steering angle of the wheel in radians.
+    pub fn steering_angle(&self) -> f32 {
+        self.steering_angle
+    }
+
+    /// Returns current rotation angle of the wheel around its axle in radians.
+    pub fn spin_angle(&self) -> f32 {
+        self.spin_angle
+    }
+}
+
+/// Allows you to create wheels in declarative manner. Default values of the builder are tuned
+/// for a typical passenger car.
+pub struct WheelBuilder {
+    base_builder: BaseBuilder,
+    radius: f32,
+    suspension_rest_length: f32,
+    suspension_stiffness: f32,
+    suspension_damping: f32,
+    friction: f32,
+    steerable: bool,
+    driven: bool,
+    model: Handle<Node>,
+}
+
+impl WheelBuilder {
+    /// Creates new wheel builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            radius: 0.35,
+            suspension_rest_length: 0.3,
+            suspension_stiffness: 60.0,
+            suspension_damping: 6.0,
+            friction: 1.2,
+            steerable: false,
+            driven: true,
+            model: Default::default(),
+        }
+    }
+
+    /// Sets desired radius of the wheel.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets desired rest length of the suspension.
+    pub fn with_suspension_rest_length(mut self, length: f32) -> Self {
+        self.suspension_rest_length = length;
+        self
+    }
+
+    /// Sets desired stiffness of the suspension.
+    pub fn with_suspension_stiffness(mut self, stiffness: f32) -> Self {
+        self.suspension_stiffness = stiffness;
+        self
+    }
+
+    /// Sets desired damping of the suspension.
+    pub fn with_suspension_damping(mut self, damping: f32) -> Self {
+        self.suspension_damping = damping;
+        self
+    }
+
+    /// Sets desired friction coefficient.
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets whether the wheel is steerable or not.
+    pub fn with_steerable(mut self, steerable: bool) -> Self {
+        self.steerable = steerable;
+        self
+    }
+
+    /// Sets whether the wheel is driven by the engine or not.
+    pub fn with_driven(mut self, driven: bool) -> Self {
+        self.driven = driven;
+        self
+    }
+
+    /// Sets desired visual model of the wheel.
+    pub fn with_model(mut self, model: Handle<Node>) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Creates new wheel instance.
+    pub fn build_wheel(self) -> Wheel {
+        Wheel {
+            base: self.base_builder.build_base(),
+            radius: self.radius.into(),
+            suspension_rest_length: self.suspension_rest_length.into(),
+            suspension_stiffness: self.suspension_stiffness.into(),
+            suspension_damping: self.suspension_damping.into(),
+            friction: self.friction.into(),
+            steerable: self.steerable.into(),
+            driven: self.driven.into(),
+            model: self.model.into(),
+            compression: 0.0,
+            in_contact: false,
+            steering_angle: 0.0,
+            spin_angle: 0.0,
+        }
+    }
+
+    /// Creates new wheel node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_wheel())
+    }
+
+    /// Creates new wheel node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+/// Vehicle is a node that simulates a wheeled vehicle using raycast suspension. Instead of
+/// simulating each wheel as a separate rigid body, the vehicle casts a ray from every wheel down to
+/// the ground and applies suspension, traction, braking and lateral friction forces to a single
+/// rigid body (chassis). This approach is much more stable than joint-based vehicles and it is
+/// used in most of the games.
+///
+/// # Setup
+///
+/// 1) Create a dynamic rigid body with a collider - it will be the chassis of the vehicle.
+/// 2) Create a vehicle node as a child of the chassis and assign the chassis to it using
+/// [`Vehicle::set_chassis`].
+/// 3) Create [`Wheel`] nodes as direct children of the vehicle node and place them where the
+/// suspension is mounted. The look vector (+Z) of the vehicle defines its forward direction.
+///
+/// Colliders of the chassis (and its descendants) are ignored by the suspension rays.
+///
+/// # Controls
+///
+/// Use [`Vehicle::set_throttle`], [`Vehicle::set_brake`] and [`Vehicle::set_steering`] to
+/// control the vehicle. These values are not serialized and they are usually set by a script
+/// every frame.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         collider::{ColliderBuilder, ColliderShape},
+/// #         graph::Graph,
+/// #         node::Node,
+/// #         rigidbody::RigidBodyBuilder,
+/// #         transform::TransformBuilder,
+/// #         vehicle::{Vehicle, VehicleBuilder, WheelBuilder},
+/// #     },
+/// # };
+/// fn create_car(graph: &mut Graph) -> Handle<Node> {
+///     let wheels = [(0.8, 1.3, true), (-0.8, 1.3, true), (0.8, -1.3, false), (-0.8, -1.3, false)]
+///         .into_iter()
+///         .map(|(x, z, front)| {
+///             WheelBuilder::new(BaseBuilder::new().with_local_transform(
+///                 TransformBuilder::new()
+///                     .with_local_position(Vector3::new(x, 0.0, z))
+///                     .build(),
+///             ))
+///             .with_steerable(front)
+///             .with_driven(!front)
+///             .build(graph)
+///         })
+///         .collect::<Vec<_>>();
+///
+///     let vehicle = VehicleBuilder::new(BaseBuilder::new().with_children(&wheels)).build(graph);
+///
+///     let collider = ColliderBuilder::new(BaseBuilder::new())
+///         .with_shape(ColliderShape::cuboid(0.9, 0.4, 2.0))
+///         .build(graph);
+///
+///     let chassis = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[vehicle, collider]))
+///         .with_mass(1200.0)
+///         .build(graph);
+///
+///     graph[vehicle]
+///         .query_component_mut::<Vehicle>()
+///         .unwrap()
+///         .set_chassis(chassis);
+///
+///     chassis
+/// }
+/// ```
+#[derive(Clone, Reflect, Visit, Debug)]
+pub struct Vehicle {
+    base: Base,
+
+    #[reflect(
+        setter = "set_chassis",
+        description = "A rigid body, to which the forces of the wheels will be applied."
+    )]
+    chassis: InheritableVariable<Handle<Node>>,
+
+    #[reflect(
+        setter = "set_max_acceleration",
+        min_value = 0.0,
+        description = "Maximum acceleration (m/s²) that the engine could give to the vehicle."
+    )]
+    max_acceleration: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_max_deceleration",
+        min_value = 0.0,
+        description = "Maximum deceleration (m/s²) that the brakes could give to the vehicle."
+    )]
+    max_deceleration: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_max_steering_angle",
+        min_value = 0.0,
+        max_value = 1.57,
+        step = 0.01,
+        description = "Maximum steering angle of the steerable wheels in radians."
+    )]
+    max_steering_angle: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    throttle: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    brake: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    steering: f32,
+}
+
+impl Default for Vehicle {
+    fn default() -> Self {
+        VehicleBuilder::new(BaseBuilder::new()).build_vehicle()
+    }
+}
+
+impl Deref for Vehicle {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Vehicle {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Vehicle {
+    fn type_uuid() -> Uuid {
+        uuid!("a3b0e5d4-7c21-4f8a-b6e9-1d2c3f4a5b6e")
+    }
+}
+
+fn collect_colliders(nodes: &NodePool, root: Handle<Node>) -> FxHashSet<Handle<Node>> {
+    let mut colliders = FxHashSet::default();
+    let mut stack = vec![root];
+    while let Some(handle) = stack.pop() {
+        if let Some(node) = nodes.try_borrow(handle) {
+            if node.query_component_ref::<Collider>().is_some() {
+                colliders.insert(handle);
+            }
+            stack.extend_from_slice(node.children());
+        }
+    }
+    colliders
+}
+
+impl NodeTrait for Vehicle {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, ctx: &mut UpdateContext) {
+        if ctx.dt <= 0.0 {
+            return;
+        }
+
+        let Some(chassis) = ctx
+            .nodes
+            .try_borrow(*self.chassis)
+            .and_then(|n| n.query_component_ref::<RigidBody>())
+        else {
+            return;
+        };
+        let mass = chassis.mass();
+        let lin_vel = chassis.lin_vel();
+        let ang_vel = chassis.ang_vel();
+        let center = chassis.global_position();
+
+        let wheels = self
+            .children()
+            .iter()
+            .filter(|h| {
+                ctx.nodes
+                    .try_borrow(**h)
+                    .map_or(false, |n| n.query_component_ref::<Wheel>().is_some())
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if wheels.is_empty() {
+            return;
+        }
+
+        let own_colliders = collect_colliders(ctx.nodes, *self.chassis);
+        let sprung_mass = mass / wheels.len() as f32;
+        let driven_count = wheels
+            .iter()
+            .filter(|h| {
+                ctx.nodes[**h]
+                    .query_component_ref::<Wheel>()
+                    .unwrap()
+                    .is_driven()
+            })
+            .count();
+
+        let mut forces = Vec::new();
+        let mut models = Vec::new();
+        let mut intersections = Vec::<Intersection>::new();
+        for handle in wheels {
+            let wheel_node = &ctx.nodes[handle];
+            let wheel = wheel_node.query_component_ref::<Wheel>().unwrap();
+
+            let mount = wheel_node.global_position();
+            let up = wheel_node
+                .up_vector()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y);
+            let steering_angle = if wheel.is_steerable() {
+                self.steering * *self.max_steering_angle
+            } else {
+                0.0
+            };
+            let forward = UnitQuaternion::from_scaled_axis(up * steering_angle)
+                * wheel_node
+                    .look_vector()
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::z);
+            let side = up.cross(&forward);
+
+            let max_len = wheel.suspension_rest_length() + wheel.radius();
+            ctx.physics.cast_ray(
+                RayCastOptions {
+                    ray_origin: Point3::from(mount),
+                    ray_direction: -up,
+                    max_len,
+                    groups: Default::default(),
+                    sort_results: true,
+                },
+                &mut intersections,
+            );
+            let contact = intersections
+                .iter()
+                .find(|i| !own_colliders.contains(&i.collider));
+
+            let mut spin_speed = 0.0;
+            let compression = if let Some(contact) = contact {
+                let compression =
+                    (max_len - contact.toi).clamp(0.0, wheel.suspension_rest_length());
+                let compression_velocity = (compression - wheel.compression) / ctx.dt;
+                let suspension_force = (sprung_mass
+                    * (wheel.suspension_stiffness() * compression
+                        + wheel.suspension_damping() * compression_velocity))
+                    .max(0.0);
+
+                let point = contact.position.coords;
+                let point_velocity = lin_vel + ang_vel.cross(&(point - center));
+                let forward_speed = point_velocity.dot(&forward);
+                let lateral_speed = point_velocity.dot(&side);
+
+                let mut traction = Vector3::default();
+                if wheel.is_driven() {
+                    traction += forward * self.throttle * *self.max_acceleration * mass
+                        / driven_count as f32;
+                }
+                // Brakes must not accelerate the vehicle in the opposite direction.
+                let brake_force = (self.brake * *self.max_deceleration * sprung_mass)
+                    .min(forward_speed.abs() * sprung_mass / ctx.dt);
+                traction -= forward * brake_force * forward_speed.signum();
+                // Lateral friction tries to stop sliding of the wheel completely in one step.
+                traction -= side * lateral_speed * sprung_mass / ctx.dt;
+
+                let max_traction = wheel.friction() * suspension_force;
+                if traction.norm() > max_traction {
+                    traction = traction.normalize() * max_traction;
+                }
+
+                forces.push((up * suspension_force + traction, point));
+
+                spin_speed = forward_speed / wheel.radius().max(f32::EPSILON);
+
+                Some(compression)
+            } else {
+                None
+            };
+
+            let wheel = ctx.nodes[handle].query_component_mut::<Wheel>().unwrap();
+            wheel.in_contact = compression.is_some();
+            wheel.compression = compression.unwrap_or_default();
+            wheel.steering_angle = steering_angle;
+            if wheel.in_contact {
+                wheel.spin_angle =
+                    (wheel.spin_angle + spin_speed * ctx.dt).rem_euclid(2.0 * std::f32::consts::PI);
+            }
+
+            models.push((
+                wheel.model(),
+                Vector3::new(0.0, -wheel.suspension_length(), 0.0),
+                UnitQuaternion::from_axis_angle(&Vector3::y_axis(), wheel.steering_angle)
+                    * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), wheel.spin_angle),
+            ));
+        }
+
+        for (model, position, rotation) in models {
+            if let Some(model) = ctx.nodes.try_borrow_mut(model) {
+                model
+                    .local_transform_mut()
+                    .set_position(position)
+                    .set_rotation(rotation);
+            }
+        }
+
+        if let Some(chassis) = ctx
+            .nodes
+            .try_borrow_mut(*self.chassis)
+            .and_then(|n| n.query_component_mut::<RigidBody>())
+        {
+            for (force, point) in forces {
+                chassis.apply_force_at_point(force, point);
+            }
+        }
+    }
+}
+
+impl Vehicle {
+    /// Sets a rigid body, to which the forces of the wheels will be applied.
+    pub fn set_chassis(&mut self, chassis: Handle<Node>) -> Handle<Node> {
+        self.chassis.set_value_and_mark_modified(chassis)
+    }
+
+    /// Returns a handle of the chassis rigid body.
+    pub fn chassis(&self) -> Handle<Node> {
+        *self.chassis
+    }
+
+    /// Sets maximum acceleration (in m/s²) that the engine could give to the vehicle. The value
+    /// does not depend on the mass of the chassis.
+    pub fn set_max_acceleration(&mut self, acceleration: f32) -> f32 {
+        self.max_acceleration
+            .set_value_and_mark_modified(acceleration.max(0.0))
+    }
+
+    /// Returns maximum acceleration of the vehicle.
+    pub fn max_acceleration(&self) -> f32 {
+        *self.max_acceleration
+    }
+
+    /// Sets maximum deceleration (in m/s²) that the brakes could give to the vehicle. The value
+    /// does not depend on the mass of the chassis.
+    pub fn set_max_deceleration(&mut self, deceleration: f32) -> f32 {
+        self.max_deceleration
+            .set_value_and_mark_modified(deceleration.max(0.0))
+    }
+
+    /// Returns maximum deceleration of the vehicle.
+    pub fn max_deceleration(&self) -> f32 {
+        *self.max_deceleration
+    }
+
+    /// Sets maximum steering angle of the steerable wheels in radians.
+    pub fn set_max_steering_angle(&mut self, angle: f32) -> f32 {
+        self.max_steering_angle.set_value_and_mark_modified(angle)
+    }
+
+    /// Returns maximum steering angle of the steerable wheels in radians.
+    pub fn max_steering_angle(&self) -> f32 {
+        *self.max_steering_angle
+    }
+
+    /// Sets throttle of the engine in `[-1.0; 1.0]` range. Negative values move the vehicle
+    /// backwards.
+    pub fn set_throttle(&mut self, throttle: f32) {
+        self.throttle = throttle.clamp(-1.0, 1.0);
+    }
+
+    /// Returns current throttle of the engine.
+    pub fn throttle(&self) -> f32 {
+        self.throttle
+    }
+
+    /// Sets brake strength in `[0.0; 1.0]` range.
+    pub fn set_brake(&mut self, brake: f32) {
+        self.brake = brake.clamp(0.0, 1.0);
+    }
+
+    /// Returns current brake strength.
+    pub fn brake(&self) -> f32 {
+        self.brake
+    }
+
+    /// Sets steering in `[-1.0; 1.0]` range. Positive values turn the steerable wheels towards
+    /// the side vector (+X) of the wheels.
+    pub fn set_steering(&mut self, steering: f32) {
+        self.steering = steering.clamp(-1.0, 1.0);
+    }
+
+    /// Returns current steering.
+    pub fn steering(&self) -> f32 {
+        self.steering
+    }
+}
+
+/// Allows you to create vehicles in declarative manner.
+pub struct VehicleBuilder {
+    base_builder: BaseBuilder,
+    chassis: Handle<Node>,
+    max_acceleration: f32,
+    max_deceleration: f32,
+    max_steering_angle: f32,
+}
+
+impl VehicleBuilder {
+    /// Creates new vehicle builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            chassis: Default::default(),
+            max_acceleration: 8.0,
+            max_deceleration: 15.0,
+            max_steering_angle: 35.0f32.to_radians(),
+        }
+    }
+
+    /// Sets desired chassis of the vehicle.
+    pub fn with_chassis(mut self, chassis: Handle<Node>) -> Self {
+        self.chassis = chassis;
+        self
+    }
+
+    /// Sets desired maximum acceleration of the vehicle.
+    pub fn with_max_acceleration(mut self, acceleration: f32) -> Self {
+        self.max_acceleration = acceleration;
+        self
+    }
+
+    /// Sets desired maximum deceleration of the vehicle.
+    pub fn with_max_deceleration(mut self, deceleration: f32) -> Self {
+        self.max_deceleration = deceleration;
+        self
+    }
+
+    /// Sets desired maximum steering angle of the vehicle.
+    pub fn with_max_steering_angle(mut self, angle: f32) -> Self {
+        self.max_steering_angle = angle;
+        self
+    }
+
+    /// Creates new vehicle instance.
+    pub fn build_vehicle(self) -> Vehicle {
+        Vehicle {
+            base: self.base_builder.build_base(),
+            chassis: self.chassis.into(),
+            max_acceleration: self.max_acceleration.into(),
+            max_deceleration: self.max_deceleration.into(),
+            max_steering_angle: self.max_steering_angle.into(),
+            throttle: 0.0,
+            brake: 0.0,
+            steering: 0.0,
+        }
+    }
+
+    /// Creates new vehicle node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_vehicle())
+    }
+
+    /// Creates new vehicle node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
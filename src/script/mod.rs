@@ -15,10 +15,12 @@ use crate::{
     },
     engine::{task::TaskPoolHandler, GraphicsContext, ScriptMessageDispatcher},
     event::Event,
+    graph::SceneGraph,
     plugin::Plugin,
-    scene::{node::Node, Scene},
+    scene::{graph::Graph, node::Node, Scene},
 };
 use fyrox_ui::UserInterface;
+use serde::{Deserialize, Serialize};
 use std::{
     any::{Any, TypeId},
     fmt::{Debug, Formatter},
@@ -27,6 +29,9 @@ use std::{
 };
 
 pub mod constructor;
+pub mod visual;
+#[cfg(feature = "wasm_scripting")]
+pub mod wasm;
 
 /// A script message's payload.
 pub trait ScriptMessagePayload: Any + Send + Debug {
@@ -35,6 +40,9 @@ pub trait ScriptMessagePayload: Any + Send + Debug {
 
     /// Returns `self` as `&dyn Any`
     fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Returns the name of the actual type of the payload.
+    fn payload_type_name(&self) -> &'static str;
 }
 
 impl dyn ScriptMessagePayload {
@@ -60,6 +68,10 @@ where
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn payload_type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 /// Defines how a script message will be delivered for each node in a hierarchy.
@@ -78,6 +90,10 @@ pub struct ScriptMessage {
     pub payload: Box<dyn ScriptMessagePayload>,
     /// Actual script message kind.
     pub kind: ScriptMessageKind,
+    /// A node that sent the message. It could be [`Handle::NONE`], if the message was sent not on behalf
+    /// of a node (for example - from a plugin). See [`ScriptMessageSender::with_origin`] and
+    /// [`SubscriptionFilter`] for more info.
+    pub sender: Handle<Node>,
 }
 
 /// An message for a node with a script.
@@ -102,10 +118,87 @@ pub enum ScriptMessageKind {
     Global,
 }
 
+/// Defines which messages of a particular type a subscriber wants to receive. Filters are applied to
+/// the sender of a message (see [`ScriptMessage::sender`]), messages without a sender pass only
+/// [`SubscriptionFilter::Any`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubscriptionFilter {
+    /// Every message of the type will be received, regardless of its sender.
+    #[default]
+    Any,
+    /// Only messages sent by the given node or any of its descendants will be received.
+    Hierarchy(Handle<Node>),
+    /// Only messages sent by the given node will be received.
+    Sender(Handle<Node>),
+}
+
+impl SubscriptionFilter {
+    /// Checks whether a message from the given sender passes the filter. Hierarchy filter checks
+    /// the state of the graph at the moment of the call, which means that the sender must be alive.
+    pub fn accepts(&self, sender: Handle<Node>, graph: &Graph) -> bool {
+        match *self {
+            SubscriptionFilter::Any => true,
+            SubscriptionFilter::Sender(expected) => sender.is_some() && sender == expected,
+            SubscriptionFilter::Hierarchy(root) => {
+                let mut node = sender;
+                while let Some(node_ref) = graph.try_get(node) {
+                    if node == root {
+                        return true;
+                    }
+                    node = node_ref.parent();
+                }
+                false
+            }
+        }
+    }
+}
+
+/// A record about a single dispatched script message. The engine writes such records to the log when
+/// [`crate::engine::ScriptProcessor::log_message_traffic`] is set, the editor uses them to show message
+/// traffic of a running game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptMessageTrafficEntry {
+    /// Amount of time (in seconds) that passed from creation of the engine at the moment of dispatch.
+    pub time: f32,
+    /// Name of the payload type.
+    pub type_name: String,
+    /// Debug representation of the payload at the moment of dispatch.
+    pub payload: String,
+    /// Textual description of the message kind.
+    pub kind: String,
+    /// A node that sent the message.
+    pub sender: Handle<Node>,
+    /// Every node that received the message in the order of delivery.
+    pub receivers: Vec<Handle<Node>>,
+}
+
+impl ScriptMessageTrafficEntry {
+    /// A marker that is used to distinguish traffic records from other log messages.
+    pub const LOG_MARKER: &'static str = "[ScriptMessage] ";
+
+    /// Creates a single-line textual representation of the entry, that could be parsed back by
+    /// [`Self::from_log_line`].
+    pub fn to_log_line(&self) -> String {
+        format!(
+            "{}{}",
+            Self::LOG_MARKER,
+            ron::to_string(self).unwrap_or_default()
+        )
+    }
+
+    /// Tries to parse an entry from a log line. Any prefix before the marker (for example - severity
+    /// of a log message) is ignored.
+    pub fn from_log_line(line: &str) -> Option<Self> {
+        let position = line.find(Self::LOG_MARKER)?;
+        ron::from_str(&line[(position + Self::LOG_MARKER.len())..]).ok()
+    }
+}
+
 /// A script message sender.
 #[derive(Clone)]
 pub struct ScriptMessageSender {
     pub(crate) sender: Sender<ScriptMessage>,
+    pub(crate) origin: Handle<Node>,
 }
 
 impl Debug for ScriptMessageSender {
@@ -115,6 +208,22 @@ impl Debug for ScriptMessageSender {
 }
 
 impl ScriptMessageSender {
+    /// Returns a copy of the sender, that marks every message sent by its `send_*` methods as sent by
+    /// the given node. Use it when receivers could filter messages by their sender:
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// # fn foo(ctx: &mut ScriptContext) {
+    /// ctx.message_sender.with_origin(ctx.handle).send_global(123u32);
+    /// # }
+    /// ```
+    pub fn with_origin(&self, origin: Handle<Node>) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            origin,
+        }
+    }
+
     /// Send a generic script message.
     pub fn send(&self, message: ScriptMessage) {
         if self.sender.send(message).is_err() {
@@ -130,6 +239,7 @@ impl ScriptMessageSender {
         self.send(ScriptMessage {
             payload: Box::new(payload),
             kind: ScriptMessageKind::Targeted(target),
+            sender: self.origin,
         })
     }
 
@@ -141,6 +251,7 @@ impl ScriptMessageSender {
         self.send(ScriptMessage {
             payload: Box::new(payload),
             kind: ScriptMessageKind::Global,
+            sender: self.origin,
         })
     }
 
@@ -152,6 +263,7 @@ impl ScriptMessageSender {
         self.send(ScriptMessage {
             payload: Box::new(payload),
             kind: ScriptMessageKind::Hierarchical { root, routing },
+            sender: self.origin,
         })
     }
 }
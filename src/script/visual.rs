@@ -0,0 +1,455 @@
+//! A script, that runs [visual scripts](crate::resource::visual_script). See [`VisualScriptRunner`]
+//! docs for more info.
+
+use crate::{
+    core::{
+        log::Log,
+        pool::Handle,
+        reflect::{prelude::*, ResolvePath, SetFieldByPathError},
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::SceneGraph,
+    resource::visual_script::{
+        Value, VisualNode, VisualNodeKind, VisualScript, VisualScriptResource,
+    },
+    scene::{graph::Graph, node::Node},
+    script::{
+        ScriptContext, ScriptMessageContext, ScriptMessagePayload, ScriptMessageSender, ScriptTrait,
+    },
+};
+use fxhash::FxHashMap;
+use fyrox_core::uuid_provider;
+
+/// Maximum amount of flow nodes, that could be executed by a single event. It prevents infinite
+/// loops in scripts.
+pub const MAX_EXECUTION_STEPS: usize = 1024;
+
+/// Maximum depth of data connections, it prevents infinite recursion when data connections form
+/// a cycle.
+pub const MAX_EVALUATION_DEPTH: usize = 64;
+
+/// A message, that could be sent and received by visual scripts. It could also be sent from (and
+/// received by) usual scripts, which allows visual scripts to interact with the code.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VisualScriptMessage {
+    /// Name of the message, it is used to find matching [`VisualNodeKind::OnMessage`] events.
+    pub name: String,
+    /// A value, that is passed along with the message.
+    pub value: Value,
+}
+
+/// A script, that executes a [visual script](crate::resource::visual_script) on a scene node it is
+/// assigned to. Event nodes of the visual script are triggered as follows:
+///
+/// - [`VisualNodeKind::OnStart`] - once, when the script starts.
+/// - [`VisualNodeKind::OnUpdate`] - every frame.
+/// - [`VisualNodeKind::OnMessage`] - when a [`VisualScriptMessage`] with the matching name is sent
+/// globally or to the node with the script.
+///
+/// Every instance of the script has its own set of variables, they're created on the first write
+/// and they're not serialized.
+#[derive(Clone, Debug, Default, Reflect, Visit, ComponentProvider)]
+pub struct VisualScriptRunner {
+    /// A visual script to execute.
+    pub script: Option<VisualScriptResource>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    variables: FxHashMap<String, Value>,
+}
+
+uuid_provider!(VisualScriptRunner = "5e7b2d9c-4a1f-4c8e-b3d6-9f2a7c1e5b48");
+
+impl VisualScriptRunner {
+    /// Creates a new runner, that executes the given visual script.
+    pub fn new(script: VisualScriptResource) -> Self {
+        Self {
+            script: Some(script),
+            variables: Default::default(),
+        }
+    }
+
+    /// Returns a value of a variable with the given name.
+    pub fn variable(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+
+    /// Sets a value of a variable with the given name.
+    pub fn set_variable(&mut self, name: &str, value: Value) {
+        self.variables.insert(name.to_owned(), value);
+    }
+
+    fn run<F>(
+        &mut self,
+        filter: F,
+        message_value: Value,
+        graph: &mut Graph,
+        handle: Handle<Node>,
+        dt: f32,
+        message_sender: &ScriptMessageSender,
+    ) where
+        F: Fn(&VisualNodeKind) -> bool,
+    {
+        let Some(resource) = self.script.as_ref() else {
+            return;
+        };
+        let mut state = resource.state();
+        let Some(script) = state.data() else {
+            return;
+        };
+
+        let mut executor = Executor {
+            script,
+            graph,
+            handle,
+            dt,
+            message_value,
+            variables: &mut self.variables,
+            messages: Default::default(),
+        };
+        executor.run_events(filter);
+
+        let message_sender = message_sender.with_origin(handle);
+        for (target, message) in executor.messages {
+            if target.is_some() {
+                message_sender.send_to_target(target, message);
+            } else {
+                message_sender.send_global(message);
+            }
+        }
+    }
+}
+
+impl ScriptTrait for VisualScriptRunner {
+    fn on_start(&mut self, ctx: &mut ScriptContext) {
+        ctx.message_dispatcher
+            .subscribe_to::<VisualScriptMessage>(ctx.handle);
+
+        self.run(
+            |kind| matches!(kind, VisualNodeKind::OnStart),
+            Value::None,
+            &mut ctx.scene.graph,
+            ctx.handle,
+            ctx.dt,
+            ctx.message_sender,
+        );
+    }
+
+    fn on_update(&mut self, ctx: &mut ScriptContext) {
+        self.run(
+            |kind| matches!(kind, VisualNodeKind::OnUpdate),
+            Value::None,
+            &mut ctx.scene.graph,
+            ctx.handle,
+            ctx.dt,
+            ctx.message_sender,
+        );
+    }
+
+    fn on_message(
+        &mut self,
+        message: &mut dyn ScriptMessagePayload,
+        ctx: &mut ScriptMessageContext,
+    ) {
+        let Some(message) = message.downcast_ref::<VisualScriptMessage>() else {
+            return;
+        };
+
+        self.run(
+            |kind| matches!(kind, VisualNodeKind::OnMessage { name } if *name == message.name),
+            message.value.clone(),
+            &mut ctx.scene.graph,
+            ctx.handle,
+            ctx.dt,
+            ctx.message_sender,
+        );
+    }
+}
+
+/// Interpreter of visual scripts. Flow nodes are executed one after another using an explicit
+/// stack, values of expression nodes are computed on demand.
+struct Executor<'a> {
+    script: &'a VisualScript,
+    graph: &'a mut Graph,
+    handle: Handle<Node>,
+    dt: f32,
+    message_value: Value,
+    variables: &'a mut FxHashMap<String, Value>,
+    /// Messages, that were sent during the execution, paired with their targets.
+    messages: Vec<(Handle<Node>, VisualScriptMessage)>,
+}
+
+impl<'a> Executor<'a> {
+    fn run_events<F>(&mut self, filter: F)
+    where
+        F: Fn(&VisualNodeKind) -> bool,
+    {
+        let script = self.script;
+        let events = script
+            .nodes()
+            .pair_iter()
+            .filter(|(_, node)| filter(&node.kind))
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+
+        for event in events {
+            self.execute(script.flow_target(event, 0));
+        }
+    }
+
+    fn execute(&mut self, start: Handle<VisualNode>) {
+        let script = self.script;
+        let mut stack = vec![start];
+        let mut steps = 0;
+        while let Some(handle) = stack.pop() {
+            let Some(node) = script.node(handle) else {
+                continue;
+            };
+
+            steps += 1;
+            if steps > MAX_EXECUTION_STEPS {
+                Log::err(format!(
+                    "Visual script execution was aborted, because it exceeded the limit of \
+                    {MAX_EXECUTION_STEPS} steps. Make sure there are no infinite loops."
+                ));
+                return;
+            }
+
+            let mut next = 0;
+            match node.kind {
+                VisualNodeKind::Branch => {
+                    if !self.input(handle, 0, 0).as_bool() {
+                        next = 1;
+                    }
+                }
+                VisualNodeKind::Sequence => {
+                    // Push in reverse order, so the first output will be executed first.
+                    for pin in (0..node.kind.flow_outputs().len() as u32).rev() {
+                        stack.push(script.flow_target(handle, pin));
+                    }
+                    continue;
+                }
+                VisualNodeKind::SetProperty { ref path } => {
+                    let target = self.node_input(handle, 0);
+                    let value = self.input(handle, 1, 0);
+                    set_property(self.graph, target, path, &value);
+                }
+                VisualNodeKind::SetVariable { ref name } => {
+                    let value = self.input(handle, 0, 0);
+                    self.variables.insert(name.clone(), value);
+                }
+                VisualNodeKind::SendMessage { ref name } => {
+                    let target = match self.input(handle, 0, 0) {
+                        Value::Node(target) => target,
+                        _ => Handle::NONE,
+                    };
+                    let value = self.input(handle, 1, 0);
+                    self.messages.push((
+                        target,
+                        VisualScriptMessage {
+                            name: name.clone(),
+                            value,
+                        },
+                    ));
+                }
+                VisualNodeKind::Log => {
+                    Log::info(self.input(handle, 0, 0).to_string());
+                }
+                _ => {}
+            }
+
+            stack.push(script.flow_target(handle, next));
+        }
+    }
+
+    /// Computes a value of the given data input of the given node.
+    fn input(&mut self, handle: Handle<VisualNode>, pin: u32, depth: usize) -> Value {
+        let source = self.script.data_source(handle, pin);
+        if source.is_some() {
+            self.evaluate(source, depth + 1)
+        } else {
+            Value::None
+        }
+    }
+
+    /// Computes a node handle of the given data input, it is the node with the script if the input
+    /// is not connected.
+    fn node_input(&mut self, handle: Handle<VisualNode>, pin: u32) -> Handle<Node> {
+        match self.input(handle, pin, 0) {
+            Value::Node(node) => node,
+            _ => self.handle,
+        }
+    }
+
+    fn evaluate(&mut self, handle: Handle<VisualNode>, depth: usize) -> Value {
+        if depth > MAX_EVALUATION_DEPTH {
+            Log::err(format!(
+                "Visual script evaluation was aborted, because it exceeded the depth of \
+                {MAX_EVALUATION_DEPTH}. Make sure there are no cycles in data connections."
+            ));
+            return Value::None;
+        }
+
+        let script = self.script;
+        let Some(node) = script.node(handle) else {
+            return Value::None;
+        };
+
+        match node.kind {
+            VisualNodeKind::Constant { ref value } => value.clone(),
+            VisualNodeKind::GetProperty { ref path } => {
+                let target = match self.input(handle, 0, depth) {
+                    Value::Node(node) => node,
+                    _ => self.handle,
+                };
+                get_property(self.graph, target, path)
+            }
+            VisualNodeKind::GetVariable { ref name } => {
+                self.variables.get(name).cloned().unwrap_or_default()
+            }
+            VisualNodeKind::SelfNode => Value::Node(self.handle),
+            VisualNodeKind::DeltaTime => Value::Number(self.dt),
+            VisualNodeKind::MessageValue => self.message_value.clone(),
+            VisualNodeKind::FindNode { ref name } => Value::Node(
+                self.graph
+                    .find_by_name_from_root(name)
+                    .map(|(handle, _)| handle)
+                    .unwrap_or_default(),
+            ),
+            VisualNodeKind::Binary { operation } => {
+                let a = self.input(handle, 0, depth);
+                let b = self.input(handle, 1, depth);
+                operation.apply(&a, &b)
+            }
+            VisualNodeKind::Not => Value::Bool(!self.input(handle, 0, depth).as_bool()),
+            _ => Value::None,
+        }
+    }
+}
+
+/// Reads a property of a scene node using its reflection path. Returns [`Value::None`] on failure.
+pub(crate) fn get_property(graph: &Graph, node: Handle<Node>, path: &str) -> Value {
+    let Some(node) = graph.try_get(node) else {
+        return Value::None;
+    };
+
+    let mut value = Value::None;
+    node.as_reflect(&mut |node| {
+        node.resolve_path(path, &mut |result| match result {
+            Ok(property) => value = Value::from_reflect(property),
+            Err(err) => Log::err(format!(
+                "Script failed to get property {path}! Invalid path: {err}"
+            )),
+        })
+    });
+    value
+}
+
+/// Writes a property of a scene node using its reflection path. The value is converted to the type
+/// of the property first.
+pub(crate) fn set_property(graph: &mut Graph, node: Handle<Node>, path: &str, value: &Value) {
+    let Some(node) = graph.try_get_mut(node) else {
+        return;
+    };
+
+    node.as_reflect_mut(&mut |node| {
+        // Convert the value to the type of the property first.
+        let mut converted = None;
+        node.resolve_path(path, &mut |result| {
+            if let Ok(property) = result {
+                converted = value.to_reflect(property);
+            }
+        });
+
+        let Some(converted) = converted else {
+            Log::err(format!(
+                "Script failed to set property {path}! Types mismatch or invalid path!"
+            ));
+            return;
+        };
+
+        node.set_field_by_path(path, converted, &mut |result| match result {
+            Err(SetFieldByPathError::InvalidPath { reason, .. }) => Log::err(format!(
+                "Script failed to set property {path}! Invalid path: {reason}"
+            )),
+            Err(SetFieldByPathError::InvalidValue(_)) => Log::err(format!(
+                "Script failed to set property {path}! Types mismatch!"
+            )),
+            Ok(_) => {}
+        })
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, pool::Handle},
+        resource::visual_script::{
+            BinaryOperation, Value, VisualConnectionKind, VisualNode, VisualNodeKind, VisualScript,
+        },
+        scene::{base::BaseBuilder, graph::Graph, pivot::PivotBuilder},
+        script::visual::Executor,
+    };
+    use fxhash::FxHashMap;
+
+    #[test]
+    fn test_visual_script_execution() {
+        let mut graph = Graph::new();
+        let pivot = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let mut script = VisualScript::default();
+        let on_update = script.add_node(VisualNode::new(VisualNodeKind::OnUpdate));
+        let sequence = script.add_node(VisualNode::new(VisualNodeKind::Sequence));
+        let set_position = script.add_node(VisualNode::new(VisualNodeKind::SetProperty {
+            path: "base.local_transform.local_position".to_string(),
+        }));
+        let get_position = script.add_node(VisualNode::new(VisualNodeKind::GetProperty {
+            path: "base.local_transform.local_position".to_string(),
+        }));
+        let offset = script.add_node(VisualNode::new(VisualNodeKind::Constant {
+            value: Value::Vector3(Vector3::new(1.0, 0.0, 0.0)),
+        }));
+        let add = script.add_node(VisualNode::new(VisualNodeKind::Binary {
+            operation: BinaryOperation::Add,
+        }));
+        let set_variable = script.add_node(VisualNode::new(VisualNodeKind::SetVariable {
+            name: "dt".to_string(),
+        }));
+        let dt = script.add_node(VisualNode::new(VisualNodeKind::DeltaTime));
+        let send = script.add_node(VisualNode::new(VisualNodeKind::SendMessage {
+            name: "moved".to_string(),
+        }));
+
+        script.connect(VisualConnectionKind::Flow, on_update, 0, sequence, 0);
+        script.connect(VisualConnectionKind::Flow, sequence, 0, set_position, 0);
+        script.connect(VisualConnectionKind::Flow, sequence, 1, set_variable, 0);
+        script.connect(VisualConnectionKind::Flow, set_variable, 0, send, 0);
+        script.connect(VisualConnectionKind::Data, get_position, 0, add, 0);
+        script.connect(VisualConnectionKind::Data, offset, 0, add, 1);
+        script.connect(VisualConnectionKind::Data, add, 0, set_position, 1);
+        script.connect(VisualConnectionKind::Data, dt, 0, set_variable, 0);
+
+        let mut variables = FxHashMap::default();
+        for _ in 0..2 {
+            let mut executor = Executor {
+                script: &script,
+                graph: &mut graph,
+                handle: pivot,
+                dt: 0.5,
+                message_value: Value::None,
+                variables: &mut variables,
+                messages: Default::default(),
+            };
+            executor.run_events(|kind| matches!(kind, VisualNodeKind::OnUpdate));
+            assert_eq!(executor.messages.len(), 1);
+            assert_eq!(executor.messages[0].0, Handle::NONE);
+            assert_eq!(executor.messages[0].1.name, "moved");
+        }
+
+        assert_eq!(
+            **graph[pivot].local_transform().position(),
+            Vector3::new(2.0, 0.0, 0.0)
+        );
+        assert_eq!(variables.get("dt"), Some(&Value::Number(0.5)));
+    }
+}
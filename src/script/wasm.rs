@@ -0,0 +1,691 @@
+//! A script, that runs [WebAssembly modules](crate::resource::wasm_module). It allows to write the
+//! logic of scene nodes in any language, that compiles to WebAssembly, and to load it at runtime,
+//! which makes it suitable for modding. Modules are executed in a sandbox by an interpreter: they
+//! can't access memory of the game, file system or network, every call has a limited amount of
+//! instructions to execute ([`FUEL_PER_CALL`]) and the memory of a module is limited by
+//! [`MAX_MEMORY_SIZE`], so a broken mod can't freeze the game or exhaust its memory.
+//!
+//! # Exports
+//!
+//! A module may export the following functions, all of them are optional:
+//!
+//! - `on_start()` - called once, when the script starts.
+//! - `on_update(dt: f32)` - called every frame.
+//! - `on_message(value: f32)` - called when a [`VisualScriptMessage`] is sent globally or to the
+//!   node with the script. Use `message_name` host function to get the name of the message.
+//! - `on_deinit()` - called when the script is destroyed.
+//!
+//! The module must also export its memory as `memory`, if it uses host functions with strings.
+//!
+//! # Host API
+//!
+//! Host functions are imported from `fyrox` module ([`HOST_MODULE`]). Strings are passed as a
+//! pointer and a length of UTF-8 bytes in the memory of the module. Scene nodes are passed as `i64`
+//! values, zero means "no node". Properties of scene nodes are accessed by their reflection paths,
+//! for example `base.local_transform.local_position`.
+//!
+//! | Function | Description |
+//! |----------|-------------|
+//! | `log(ptr: i32, len: i32)` | Writes a string to the log. |
+//! | `self_node() -> i64` | Returns the node with the script. |
+//! | `parent(node: i64) -> i64` | Returns the parent of the node. |
+//! | `find_node(ptr: i32, len: i32) -> i64` | Finds a node by its name. |
+//! | `get_number(node: i64, ptr: i32, len: i32) -> f32` | Returns a numeric (or boolean) property. |
+//! | `set_number(node: i64, ptr: i32, len: i32, value: f32)` | Sets a numeric (or boolean) property. |
+//! | `get_vector3(node: i64, ptr: i32, len: i32, out: i32) -> i32` | Writes three `f32` of a vector property at `out`, returns 1 on success. |
+//! | `set_vector3(node: i64, ptr: i32, len: i32, x: f32, y: f32, z: f32)` | Sets a vector property. |
+//! | `send_message(target: i64, ptr: i32, len: i32, value: f32)` | Sends a message to the target node (or globally, if the target is zero). |
+//! | `message_name(out: i32, capacity: i32) -> i32` | Writes the name of the message, that is being handled, returns its length or -1. |
+//!
+//! Messages are [`VisualScriptMessage`]s, so WASM scripts, visual scripts and usual scripts could
+//! communicate with each other.
+
+use crate::{
+    core::{
+        log::Log, pool::Handle, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*,
+    },
+    graph::SceneGraph,
+    resource::{visual_script::Value, wasm_module::WasmModuleResource},
+    scene::{graph::Graph, node::Node},
+    script::{
+        visual::{get_property, set_property, VisualScriptMessage},
+        ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
+        ScriptMessageSender, ScriptTrait,
+    },
+};
+use fyrox_core::{algebra::Vector3, uuid_provider};
+use lazy_static::lazy_static;
+use std::fmt::{Debug, Formatter};
+use wasmi::{
+    core::F32, Caller, Config, Engine, Extern, Instance, Linker, Module, Store, StoreLimits,
+    StoreLimitsBuilder, WasmParams,
+};
+
+/// Name of the module, from which a WASM module imports host functions.
+pub const HOST_MODULE: &str = "fyrox";
+
+/// Maximum amount of fuel (roughly - instructions), that a single call of an exported function
+/// could consume. The call is aborted when the fuel is exhausted.
+pub const FUEL_PER_CALL: u64 = 10_000_000;
+
+/// Maximum size of the linear memory of a module in bytes. Modules that require more memory are
+/// not instantiated, attempts to grow the memory beyond the limit fail.
+pub const MAX_MEMORY_SIZE: usize = 64 * 1024 * 1024;
+
+/// Maximum length of strings, that could be passed from a module to the host.
+const MAX_STRING_LENGTH: usize = 65536;
+
+lazy_static! {
+    static ref ENGINE: Engine = {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        Engine::new(&config)
+    };
+}
+
+/// Converts node handle to a value, that could be passed to a module.
+fn handle_to_wasm(handle: Handle<Node>) -> i64 {
+    (((handle.generation() as u64) << 32) | handle.index() as u64) as i64
+}
+
+/// Converts a value from a module to node handle.
+fn handle_from_wasm(value: i64) -> Handle<Node> {
+    let value = value as u64;
+    Handle::new(value as u32, (value >> 32) as u32)
+}
+
+/// A state, that is accessible from host functions. The graph of the scene is moved here for the
+/// duration of a call, the rest of the time it holds an empty graph.
+struct HostState {
+    graph: Graph,
+    handle: Handle<Node>,
+    message_name: Option<String>,
+    messages: Vec<(Handle<Node>, VisualScriptMessage)>,
+    limits: StoreLimits,
+}
+
+struct WasmInstance {
+    store: Store<HostState>,
+    instance: Instance,
+}
+
+impl Debug for WasmInstance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WasmInstance")
+    }
+}
+
+fn read_string(caller: &Caller<'_, HostState>, ptr: i32, len: i32) -> Option<String> {
+    let len = usize::try_from(len).ok()?;
+    if len > MAX_STRING_LENGTH {
+        Log::err(format!(
+            "WASM script tried to pass a string with {len} bytes, the limit is {MAX_STRING_LENGTH} bytes!"
+        ));
+        return None;
+    }
+    let memory = caller.get_export("memory").and_then(Extern::into_memory)?;
+    let mut buffer = vec![0; len];
+    memory
+        .read(caller, usize::try_from(ptr).ok()?, &mut buffer)
+        .ok()?;
+    String::from_utf8(buffer).ok()
+}
+
+fn write_bytes(caller: &mut Caller<'_, HostState>, ptr: i32, bytes: &[u8]) -> bool {
+    let (Some(memory), Ok(ptr)) = (
+        caller.get_export("memory").and_then(Extern::into_memory),
+        usize::try_from(ptr),
+    ) else {
+        return false;
+    };
+    memory.write(caller, ptr, bytes).is_ok()
+}
+
+fn make_linker() -> Result<Linker<HostState>, wasmi::Error> {
+    let mut linker = Linker::<HostState>::new(&ENGINE);
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "log",
+        |caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+            if let Some(string) = read_string(&caller, ptr, len) {
+                Log::info(string);
+            }
+        },
+    )?;
+    linker.func_wrap(HOST_MODULE, "self_node", |caller: Caller<'_, HostState>| {
+        handle_to_wasm(caller.data().handle)
+    })?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "parent",
+        |caller: Caller<'_, HostState>, node: i64| {
+            caller
+                .data()
+                .graph
+                .try_get(handle_from_wasm(node))
+                .map_or(0, |node| handle_to_wasm(node.parent()))
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "find_node",
+        |caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+            read_string(&caller, ptr, len)
+                .and_then(|name| caller.data().graph.find_by_name_from_root(&name))
+                .map_or(0, |(handle, _)| handle_to_wasm(handle))
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "get_number",
+        |caller: Caller<'_, HostState>, node: i64, ptr: i32, len: i32| {
+            let Some(path) = read_string(&caller, ptr, len) else {
+                return F32::from(0.0);
+            };
+            F32::from(
+                match get_property(&caller.data().graph, handle_from_wasm(node), &path) {
+                    Value::Number(v) => v,
+                    Value::Bool(v) => v as u8 as f32,
+                    _ => 0.0,
+                },
+            )
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "set_number",
+        |mut caller: Caller<'_, HostState>, node: i64, ptr: i32, len: i32, value: F32| {
+            let Some(path) = read_string(&caller, ptr, len) else {
+                return;
+            };
+            let value = value.to_float();
+            let graph = &mut caller.data_mut().graph;
+            let node = handle_from_wasm(node);
+            // Booleans are set from numbers too, non-zero means `true`.
+            let value = match get_property(graph, node, &path) {
+                Value::Bool(_) => Value::Bool(value != 0.0),
+                _ => Value::Number(value),
+            };
+            set_property(graph, node, &path, &value);
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "get_vector3",
+        |mut caller: Caller<'_, HostState>, node: i64, ptr: i32, len: i32, out: i32| {
+            let Some(path) = read_string(&caller, ptr, len) else {
+                return 0;
+            };
+            let Value::Vector3(v) =
+                get_property(&caller.data().graph, handle_from_wasm(node), &path)
+            else {
+                return 0;
+            };
+            let mut bytes = [0; 12];
+            for (chunk, component) in bytes.chunks_exact_mut(4).zip(v.iter()) {
+                chunk.copy_from_slice(&component.to_le_bytes());
+            }
+            write_bytes(&mut caller, out, &bytes) as i32
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "set_vector3",
+        |mut caller: Caller<'_, HostState>,
+         node: i64,
+         ptr: i32,
+         len: i32,
+         x: F32,
+         y: F32,
+         z: F32| {
+            if let Some(path) = read_string(&caller, ptr, len) {
+                set_property(
+                    &mut caller.data_mut().graph,
+                    handle_from_wasm(node),
+                    &path,
+                    &Value::Vector3(Vector3::new(x.to_float(), y.to_float(), z.to_float())),
+                );
+            }
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "send_message",
+        |mut caller: Caller<'_, HostState>, target: i64, ptr: i32, len: i32, value: F32| {
+            if let Some(name) = read_string(&caller, ptr, len) {
+                caller.data_mut().messages.push((
+                    handle_from_wasm(target),
+                    VisualScriptMessage {
+                        name,
+                        value: Value::Number(value.to_float()),
+                    },
+                ));
+            }
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "message_name",
+        |mut caller: Caller<'_, HostState>, out: i32, capacity: i32| {
+            let Some(name) = caller.data().message_name.clone() else {
+                return -1;
+            };
+            let count = name
+                .len()
+                .min(usize::try_from(capacity).unwrap_or_default());
+            write_bytes(&mut caller, out, &name.as_bytes()[..count]);
+            name.len() as i32
+        },
+    )?;
+
+    Ok(linker)
+}
+
+/// A script, that executes a [WASM module](crate::resource::wasm_module) on a scene node it is
+/// assigned to. See [module docs](self) for more info.
+#[derive(Debug, Default, Reflect, Visit, ComponentProvider)]
+pub struct WasmScript {
+    /// A WASM module to execute.
+    pub module: Option<WasmModuleResource>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    instance: Option<WasmInstance>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    instantiation_failed: bool,
+}
+
+uuid_provider!(WasmScript = "c4e8a2f6-1b9d-4e3a-8f7c-5a2d6b9e1c48");
+
+impl Clone for WasmScript {
+    fn clone(&self) -> Self {
+        // Instances are not cloned, every copy of the script creates its own instance.
+        Self {
+            module: self.module.clone(),
+            instance: None,
+            instantiation_failed: false,
+        }
+    }
+}
+
+impl WasmScript {
+    /// Creates a new script, that executes the given module.
+    pub fn new(module: WasmModuleResource) -> Self {
+        Self {
+            module: Some(module),
+            ..Default::default()
+        }
+    }
+
+    /// Instantiates the module, if it is loaded and it wasn't instantiated yet. Returns `true` if
+    /// there is an instance.
+    fn try_instantiate(&mut self, handle: Handle<Node>) -> bool {
+        if self.instance.is_some() {
+            return true;
+        }
+        if self.instantiation_failed {
+            return false;
+        }
+        let Some(resource) = self.module.as_ref() else {
+            return false;
+        };
+        let module = {
+            let mut state = resource.state();
+            let Some(module) = state.data() else {
+                return false;
+            };
+            Module::new(&ENGINE, module.bytes())
+        };
+
+        let result = module.and_then(|module| {
+            let mut store = Store::new(
+                &ENGINE,
+                HostState {
+                    graph: Graph::new(),
+                    handle,
+                    message_name: None,
+                    messages: Default::default(),
+                    limits: StoreLimitsBuilder::new()
+                        .memory_size(MAX_MEMORY_SIZE)
+                        .build(),
+                },
+            );
+            store.limiter(|state| &mut state.limits);
+            let _ = store.add_fuel(FUEL_PER_CALL);
+            let instance = make_linker()?
+                .instantiate(&mut store, &module)?
+                .start(&mut store)?;
+            Ok(WasmInstance { store, instance })
+        });
+
+        match result {
+            Ok(instance) => {
+                self.instance = Some(instance);
+                true
+            }
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to instantiate WASM module {}. Reason: {err}",
+                    resource.kind()
+                ));
+                self.instantiation_failed = true;
+                false
+            }
+        }
+    }
+
+    /// Calls an exported function of the module, if it exists, and writes an error to the log if
+    /// the call has failed.
+    fn call<P>(
+        &mut self,
+        export: &str,
+        params: P,
+        graph: &mut Graph,
+        handle: Handle<Node>,
+        message_name: Option<String>,
+        message_sender: &ScriptMessageSender,
+    ) where
+        P: WasmParams,
+    {
+        if let Err(err) = self.try_call(export, params, graph, handle, message_name, message_sender)
+        {
+            Log::err(format!(
+                "WASM script failed to execute {export}. Reason: {err}"
+            ));
+        }
+    }
+
+    /// Calls an exported function of the module, if it exists. Does nothing if the module cannot
+    /// be instantiated or if it does not export the function.
+    fn try_call<P>(
+        &mut self,
+        export: &str,
+        params: P,
+        graph: &mut Graph,
+        handle: Handle<Node>,
+        message_name: Option<String>,
+        message_sender: &ScriptMessageSender,
+    ) -> Result<(), wasmi::Error>
+    where
+        P: WasmParams,
+    {
+        if !self.try_instantiate(handle) {
+            return Ok(());
+        }
+        let Some(WasmInstance { store, instance }) = self.instance.as_mut() else {
+            return Ok(());
+        };
+        let Some(func) = instance.get_func(&*store, export) else {
+            return Ok(());
+        };
+        let func = func.typed::<P, ()>(&*store)?;
+
+        // Refill the fuel: consume what left from the previous call and add a new portion.
+        if let Ok(remaining) = store.consume_fuel(0) {
+            let _ = store.consume_fuel(remaining);
+        }
+        let _ = store.add_fuel(FUEL_PER_CALL);
+
+        let state = store.data_mut();
+        std::mem::swap(&mut state.graph, graph);
+        state.handle = handle;
+        state.message_name = message_name;
+
+        let result = func.call(&mut *store, params);
+
+        let state = store.data_mut();
+        std::mem::swap(&mut state.graph, graph);
+        state.message_name = None;
+
+        let message_sender = message_sender.with_origin(handle);
+        for (target, message) in state.messages.drain(..) {
+            if target.is_some() {
+                message_sender.send_to_target(target, message);
+            } else {
+                message_sender.send_global(message);
+            }
+        }
+
+        result.map_err(Into::into)
+    }
+}
+
+impl ScriptTrait for WasmScript {
+    fn on_start(&mut self, ctx: &mut ScriptContext) {
+        ctx.message_dispatcher
+            .subscribe_to::<VisualScriptMessage>(ctx.handle);
+
+        self.call(
+            "on_start",
+            (),
+            &mut ctx.scene.graph,
+            ctx.handle,
+            None,
+            ctx.message_sender,
+        );
+    }
+
+    fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) {
+        // Do not instantiate a module just to deinitialize it.
+        if self.instance.is_some() {
+            self.call(
+                "on_deinit",
+                (),
+                &mut ctx.scene.graph,
+                ctx.node_handle,
+                None,
+                ctx.message_sender,
+            );
+        }
+    }
+
+    fn on_update(&mut self, ctx: &mut ScriptContext) {
+        self.call(
+            "on_update",
+            F32::from(ctx.dt),
+            &mut ctx.scene.graph,
+            ctx.handle,
+            None,
+            ctx.message_sender,
+        );
+    }
+
+    fn on_message(
+        &mut self,
+        message: &mut dyn ScriptMessagePayload,
+        ctx: &mut ScriptMessageContext,
+    ) {
+        let Some(message) = message.downcast_ref::<VisualScriptMessage>() else {
+            return;
+        };
+
+        let value = match message.value {
+            Value::Number(v) => v,
+            Value::Bool(v) => v as u8 as f32,
+            _ => 0.0,
+        };
+
+        self.call(
+            "on_message",
+            F32::from(value),
+            &mut ctx.scene.graph,
+            ctx.handle,
+            Some(message.name.clone()),
+            ctx.message_sender,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::untyped::ResourceKind,
+        core::{algebra::Vector3, pool::Handle},
+        resource::wasm_module::{WasmModule, WasmModuleResource},
+        scene::{base::BaseBuilder, graph::Graph, pivot::PivotBuilder},
+        script::{wasm::WasmScript, ScriptMessageSender},
+    };
+    use std::sync::mpsc::channel;
+    use wasmi::core::{TrapCode, F32};
+
+    const I32: u8 = 0x7F;
+    const I64: u8 = 0x7E;
+    const F32_TYPE: u8 = 0x7D;
+
+    fn name(name: &str) -> Vec<u8> {
+        let mut bytes = vec![name.len() as u8];
+        bytes.extend_from_slice(name.as_bytes());
+        bytes
+    }
+
+    /// Assembles a binary module from the given sections. Every section must be shorter than 128
+    /// bytes, which is enough for tiny test modules.
+    fn module(sections: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = b"\0asm\x01\0\0\0".to_vec();
+        for (id, content) in sections {
+            bytes.push(*id);
+            bytes.push(content.len() as u8);
+            bytes.extend_from_slice(content);
+        }
+        bytes
+    }
+
+    fn make_script(bytes: Vec<u8>) -> WasmScript {
+        WasmScript::new(WasmModuleResource::new_ok(
+            ResourceKind::Embedded,
+            WasmModule::from_bytes(bytes),
+        ))
+    }
+
+    fn message_sender() -> ScriptMessageSender {
+        ScriptMessageSender {
+            sender: channel().0,
+            origin: Handle::NONE,
+        }
+    }
+
+    #[test]
+    fn test_host_call() {
+        let path = "base.local_transform.local_position";
+
+        // on_update(dt) { set_vector3(self_node(), path, dt, dt, dt) }
+        let mut body = vec![0x00, 0x10, 0x00, 0x41, 0x00, 0x41, path.len() as u8];
+        body.extend_from_slice(&[0x20, 0x00, 0x20, 0x00, 0x20, 0x00, 0x10, 0x01, 0x0B]);
+        let mut code = vec![0x01, body.len() as u8];
+        code.extend(body);
+
+        let mut imports = vec![0x02];
+        imports.extend(name("fyrox"));
+        imports.extend(name("self_node"));
+        imports.extend([0x00, 0x00]);
+        imports.extend(name("fyrox"));
+        imports.extend(name("set_vector3"));
+        imports.extend([0x00, 0x01]);
+
+        let mut exports = vec![0x02];
+        exports.extend(name("memory"));
+        exports.extend([0x02, 0x00]);
+        exports.extend(name("on_update"));
+        exports.extend([0x00, 0x02]);
+
+        let mut data = vec![0x01, 0x00, 0x41, 0x00, 0x0B];
+        data.extend(name(path));
+
+        let bytes = module(&[
+            (
+                0x01,
+                vec![
+                    0x03, // Three types.
+                    0x60, 0x00, 0x01, I64, // () -> i64
+                    0x60, 0x06, I64, I32, I32, F32_TYPE, F32_TYPE, F32_TYPE,
+                    0x00, // set_vector3
+                    0x60, 0x01, F32_TYPE, 0x00, // (f32) -> ()
+                ],
+            ),
+            (0x02, imports),
+            (0x03, vec![0x01, 0x02]),
+            (0x05, vec![0x01, 0x00, 0x01]),
+            (0x07, exports),
+            (0x0A, code),
+            (0x0B, data),
+        ]);
+
+        let mut graph = Graph::new();
+        let pivot = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let mut script = make_script(bytes);
+        script
+            .try_call(
+                "on_update",
+                F32::from(0.5),
+                &mut graph,
+                pivot,
+                None,
+                &message_sender(),
+            )
+            .unwrap();
+
+        assert!(script.instance.is_some());
+        assert_eq!(
+            **graph[pivot].local_transform().position(),
+            Vector3::new(0.5, 0.5, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_fuel_limit() {
+        // on_start() { loop { br 0 } }
+        let bytes = module(&[
+            (0x01, vec![0x01, 0x60, 0x00, 0x00]),
+            (0x03, vec![0x01, 0x00]),
+            (
+                0x07,
+                [vec![0x01], name("on_start"), vec![0x00, 0x00]].concat(),
+            ),
+            (
+                0x0A,
+                vec![0x01, 0x07, 0x00, 0x03, 0x40, 0x0C, 0x00, 0x0B, 0x0B],
+            ),
+        ]);
+
+        let mut graph = Graph::new();
+        let mut script = make_script(bytes);
+        let err = script
+            .try_call(
+                "on_start",
+                (),
+                &mut graph,
+                Handle::NONE,
+                None,
+                &message_sender(),
+            )
+            .unwrap_err();
+        let trap_code = match err {
+            wasmi::Error::Trap(trap) => trap.trap_code(),
+            _ => None,
+        };
+        assert!(matches!(trap_code, Some(TrapCode::OutOfFuel)));
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        // A memory with 2048 pages of 64 KiB (128 MiB).
+        let bytes = module(&[(0x05, vec![0x01, 0x00, 0x80, 0x10])]);
+
+        let mut graph = Graph::new();
+        let mut script = make_script(bytes);
+        script
+            .try_call(
+                "on_start",
+                (),
+                &mut graph,
+                Handle::NONE,
+                None,
+                &message_sender(),
+            )
+            .unwrap();
+        assert!(script.instance.is_none());
+        assert!(script.instantiation_failed);
+    }
+}
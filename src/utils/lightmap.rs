@@ -2,7 +2,9 @@
 //!
 //! # Performance
 //!
-//! This is CPU lightmapper, its performance is linear with core count of your CPU.
+//! [`Lightmap::new`] is CPU lightmapper, its performance is linear with core count of your CPU.
+//! Large scenes should be baked using [`crate::renderer::lightmapper::GpuLightmapper`], which
+//! calculates lighting on GPU, but still uses CPU to generate secondary texture coordinates.
 //!
 //! WARNING: There is still work-in-progress, so it is not advised to use lightmapper
 //! now!
@@ -23,6 +25,7 @@ use crate::{
     },
     graph::SceneGraph,
     material::PropertyValue,
+    renderer::framework::error::FrameworkError,
     resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
     scene::{
         light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
@@ -75,21 +78,21 @@ pub struct Lightmap {
     pub patches: FxHashMap<u64, SurfaceDataPatch>,
 }
 
-struct WorldVertex {
-    world_normal: Vector3<f32>,
-    world_position: Vector3<f32>,
-    second_tex_coord: Vector2<f32>,
+pub(crate) struct WorldVertex {
+    pub(crate) world_normal: Vector3<f32>,
+    pub(crate) world_position: Vector3<f32>,
+    pub(crate) second_tex_coord: Vector2<f32>,
 }
 
-struct InstanceData {
+pub(crate) struct InstanceData {
     /// World-space vertices.
-    vertices: Vec<WorldVertex>,
-    triangles: Vec<TriangleDefinition>,
+    pub(crate) vertices: Vec<WorldVertex>,
+    pub(crate) triangles: Vec<TriangleDefinition>,
     octree: Octree,
 }
 
-struct Instance {
-    owner: Handle<Node>,
+pub(crate) struct Instance {
+    pub(crate) owner: Handle<Node>,
     source_data: SurfaceSharedData,
     data: Option<InstanceData>,
     transform: Matrix4<f32>,
@@ -187,7 +190,7 @@ impl ProgressData {
     }
 
     /// Sets new stage with max iterations per stage.
-    fn set_stage(&self, stage: ProgressStage, max_iterations: u32) {
+    pub(crate) fn set_stage(&self, stage: ProgressStage, max_iterations: u32) {
         self.max_iterations
             .store(max_iterations, atomic::Ordering::SeqCst);
         self.progress.store(0, atomic::Ordering::SeqCst);
@@ -195,7 +198,7 @@ impl ProgressData {
     }
 
     /// Advances progress.
-    fn advance_progress(&self) {
+    pub(crate) fn advance_progress(&self) {
         self.progress.fetch_add(1, atomic::Ordering::SeqCst);
     }
 }
@@ -226,6 +229,8 @@ pub enum LightmapGenerationError {
     Cancelled,
     /// Vertex buffer of a mesh lacks required data.
     InvalidData(VertexFetchError),
+    /// Rendering of a lightmap on GPU has failed.
+    Rendering(FrameworkError),
 }
 
 impl Display for LightmapGenerationError {
@@ -237,6 +242,9 @@ impl Display for LightmapGenerationError {
             LightmapGenerationError::InvalidData(v) => {
                 write!(f, "Vertex buffer of a mesh lacks required data {v}.")
             }
+            LightmapGenerationError::Rendering(v) => {
+                write!(f, "Unable to render a lightmap on GPU. Reason: {v}")
+            }
         }
     }
 }
@@ -247,6 +255,12 @@ impl From<VertexFetchError> for LightmapGenerationError {
     }
 }
 
+impl From<FrameworkError> for LightmapGenerationError {
+    fn from(e: FrameworkError) -> Self {
+        Self::Rendering(e)
+    }
+}
+
 /// Data set required to generate a lightmap. It could be produced from a scene using [`LightmapInputData::from_scene`] method.
 /// It is used to split preparation step from the actual lightmap generation; to be able to put heavy generation in a separate
 /// thread.
@@ -393,50 +407,27 @@ impl LightmapInputData {
             lights,
         })
     }
-}
 
-impl Lightmap {
-    /// Loads a light map from the given path.
-    pub async fn load<P: AsRef<Path>>(
-        path: P,
-        resource_manager: ResourceManager,
-    ) -> Result<Lightmap, VisitError> {
-        let mut visitor = Visitor::load_binary(path).await?;
-        visitor.blackboard.register(Arc::new(resource_manager));
-        let mut lightmap = Lightmap::default();
-        lightmap.visit("Lightmap", &mut visitor)?;
-        Ok(lightmap)
-    }
-
-    /// Saves a light map to the given file. Keep in mind, that the textures should be saved separately first, via
-    /// [`Self::save_textures`] method.
-    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> VisitResult {
-        let mut visitor = Visitor::new();
-        self.visit("Lightmap", &mut visitor)?;
-        visitor.save_binary(path)?;
-        Ok(())
-    }
-
-    /// Generates lightmap for given scene. This method **automatically** generates secondary
-    /// texture coordinates! This method is blocking, however internally it uses massive parallelism
-    /// to use all available CPU power efficiently.
+    /// Generates secondary texture coordinates for every surface of the input data and caches
+    /// world-space geometry of every instance. This method is blocking, however internally it uses
+    /// massive parallelism to use all available CPU power efficiently. Prepared data could be baked
+    /// either on CPU using [`PreparedLightmapData::bake`], or on GPU using
+    /// [`crate::renderer::lightmapper::GpuLightmapper`].
     ///
     /// `texels_per_unit` defines resolution of lightmap, the higher value is, the more quality
     /// lightmap will be generated, but also it will be slow to generate.
-    /// `progress_indicator` allows you to get info about current progress.
-    /// `cancellation_token` allows you to stop generation in any time.
-    pub fn new(
-        data: LightmapInputData,
+    pub fn prepare(
+        self,
         texels_per_unit: u32,
         uv_spacing: f32,
         cancellation_token: CancellationToken,
         progress_indicator: ProgressIndicator,
-    ) -> Result<Self, LightmapGenerationError> {
+    ) -> Result<PreparedLightmapData, LightmapGenerationError> {
         let LightmapInputData {
             data_set,
             mut instances,
             lights,
-        } = data;
+        } = self;
 
         progress_indicator.set_stage(ProgressStage::UvGeneration, data_set.len() as u32);
 
@@ -520,6 +511,40 @@ impl Lightmap {
             })
             .collect::<Result<(), LightmapGenerationError>>()?;
 
+        Ok(PreparedLightmapData {
+            instances,
+            lights,
+            patches,
+            texels_per_unit,
+        })
+    }
+}
+
+/// Lightmap input data with generated secondary texture coordinates and cached world-space geometry,
+/// that is ready to be baked. See [`LightmapInputData::prepare`] for more info.
+pub struct PreparedLightmapData {
+    pub(crate) instances: Vec<Instance>,
+    pub(crate) lights: Vec<LightDefinition>,
+    pub(crate) patches: FxHashMap<u64, SurfaceDataPatch>,
+    pub(crate) texels_per_unit: u32,
+}
+
+impl PreparedLightmapData {
+    /// Calculates lighting of the prepared data on CPU and returns the lightmap. This method is
+    /// blocking, however internally it uses massive parallelism to use all available CPU power
+    /// efficiently.
+    pub fn bake(
+        self,
+        cancellation_token: CancellationToken,
+        progress_indicator: ProgressIndicator,
+    ) -> Result<Lightmap, LightmapGenerationError> {
+        let PreparedLightmapData {
+            instances,
+            lights,
+            patches,
+            texels_per_unit,
+        } = self;
+
         progress_indicator.set_stage(ProgressStage::CalculatingLight, instances.len() as u32);
 
         let mut map: FxHashMap<Handle<Node>, Vec<LightmapEntry>> = FxHashMap::default();
@@ -537,7 +562,54 @@ impl Lightmap {
             progress_indicator.advance_progress();
         }
 
-        Ok(Self { map, patches })
+        Ok(Lightmap { map, patches })
+    }
+}
+
+impl Lightmap {
+    /// Loads a light map from the given path.
+    pub async fn load<P: AsRef<Path>>(
+        path: P,
+        resource_manager: ResourceManager,
+    ) -> Result<Lightmap, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        visitor.blackboard.register(Arc::new(resource_manager));
+        let mut lightmap = Lightmap::default();
+        lightmap.visit("Lightmap", &mut visitor)?;
+        Ok(lightmap)
+    }
+
+    /// Saves a light map to the given file. Keep in mind, that the textures should be saved separately first, via
+    /// [`Self::save_textures`] method.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("Lightmap", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    /// Generates lightmap for given scene. This method **automatically** generates secondary
+    /// texture coordinates! This method is blocking, however internally it uses massive parallelism
+    /// to use all available CPU power efficiently.
+    ///
+    /// `texels_per_unit` defines resolution of lightmap, the higher value is, the more quality
+    /// lightmap will be generated, but also it will be slow to generate.
+    /// `progress_indicator` allows you to get info about current progress.
+    /// `cancellation_token` allows you to stop generation in any time.
+    pub fn new(
+        data: LightmapInputData,
+        texels_per_unit: u32,
+        uv_spacing: f32,
+        cancellation_token: CancellationToken,
+        progress_indicator: ProgressIndicator,
+    ) -> Result<Self, LightmapGenerationError> {
+        data.prepare(
+            texels_per_unit,
+            uv_spacing,
+            cancellation_token.clone(),
+            progress_indicator.clone(),
+        )?
+        .bake(cancellation_token, progress_indicator)
     }
 
     /// Saves lightmap textures into specified folder.
@@ -628,7 +700,7 @@ pub enum LightDefinition {
 }
 
 impl LightDefinition {
-    fn handle(&self) -> Handle<Node> {
+    pub(crate) fn handle(&self) -> Handle<Node> {
         match self {
             LightDefinition::Directional(v) => v.handle,
             LightDefinition::Spot(v) => v.handle,
@@ -639,7 +711,7 @@ impl LightDefinition {
 
 /// Computes total area of triangles in surface data and returns size of square
 /// in which triangles can fit.
-fn estimate_size(data: &InstanceData, texels_per_unit: u32) -> u32 {
+pub(crate) fn estimate_size(data: &InstanceData, texels_per_unit: u32) -> u32 {
     let mut area = 0.0;
     for triangle in data.triangles.iter() {
         let a = data.vertices[triangle[0] as usize].world_position;
@@ -888,6 +960,12 @@ fn generate_lightmap(
             }
         });
 
+    finalize_lightmap(&pixels, atlas_size)
+}
+
+/// Fills the pixels of the lightmap, that are not covered by any triangle (their alpha is zero),
+/// blurs the result and creates a texture from it.
+pub(crate) fn finalize_lightmap(pixels: &[Vector4<u8>], atlas_size: u32) -> Texture {
     // Prepare light map for bilinear filtration. This step is mandatory to prevent bleeding.
     let mut rgb_pixels: Vec<Vector3<u8>> = Vec::with_capacity((atlas_size * atlas_size) as usize);
     for y in 0..(atlas_size as i32) {
@@ -7,6 +7,8 @@ pub mod behavior;
 pub mod lightmap;
 pub mod navmesh;
 pub mod raw_mesh;
+pub mod simplify;
+pub mod static_batching;
 pub mod uvgen;
 
 use crate::{
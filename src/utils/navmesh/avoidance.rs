@@ -0,0 +1,304 @@
+//! Local collision avoidance for navigation agents based on Optimal Reciprocal Collision Avoidance
+//! (ORCA). The algorithm is a port of the agent-agent part of the
+//! [RVO2 library](https://gamma.cs.unc.edu/RVO2/). All calculations are done on XZ plane, so
+//! [`Vector2::x`] corresponds to X axis and [`Vector2::y`] corresponds to Z axis in world
+//! coordinates.
+
+use crate::core::algebra::Vector2;
+
+const EPSILON: f32 = 0.00001;
+
+/// Another agent, that should be avoided.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AvoidanceNeighbour {
+    /// Position of the neighbour on XZ plane.
+    pub position: Vector2<f32>,
+    /// Current velocity of the neighbour on XZ plane.
+    pub velocity: Vector2<f32>,
+    /// Radius of the neighbour.
+    pub radius: f32,
+}
+
+/// An agent, which velocity should be adjusted to avoid collisions with its neighbours.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AvoidanceAgent {
+    /// Position of the agent on XZ plane.
+    pub position: Vector2<f32>,
+    /// Current velocity of the agent on XZ plane.
+    pub velocity: Vector2<f32>,
+    /// Radius of the agent.
+    pub radius: f32,
+    /// Maximum speed of the agent.
+    pub max_speed: f32,
+    /// Minimal amount of time (in seconds) for which the velocity of the agent should be safe with
+    /// respect to other agents. The larger the value, the sooner the agent will respond to other
+    /// agents, but the less freedom it has in choosing its velocity.
+    pub time_horizon: f32,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct Line {
+    point: Vector2<f32>,
+    direction: Vector2<f32>,
+}
+
+fn det(a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn linear_program1(
+    lines: &[Line],
+    line_no: usize,
+    radius: f32,
+    opt_velocity: Vector2<f32>,
+    direction_opt: bool,
+    result: &mut Vector2<f32>,
+) -> bool {
+    let line = lines[line_no];
+    let dot_product = line.point.dot(&line.direction);
+    let discriminant = dot_product * dot_product + radius * radius - line.point.norm_squared();
+    if discriminant < 0.0 {
+        // Max speed circle fully invalidates the line.
+        return false;
+    }
+
+    let sqrt_discriminant = discriminant.sqrt();
+    let mut t_left = -dot_product - sqrt_discriminant;
+    let mut t_right = -dot_product + sqrt_discriminant;
+
+    for other in lines[..line_no].iter() {
+        let denominator = det(line.direction, other.direction);
+        let numerator = det(other.direction, line.point - other.point);
+
+        if denominator.abs() <= EPSILON {
+            // Lines are (almost) parallel.
+            if numerator < 0.0 {
+                return false;
+            }
+            continue;
+        }
+
+        let t = numerator / denominator;
+        if denominator >= 0.0 {
+            t_right = t_right.min(t);
+        } else {
+            t_left = t_left.max(t);
+        }
+
+        if t_left > t_right {
+            return false;
+        }
+    }
+
+    if direction_opt {
+        *result = if opt_velocity.dot(&line.direction) > 0.0 {
+            line.point + line.direction * t_right
+        } else {
+            line.point + line.direction * t_left
+        };
+    } else {
+        let t = line.direction.dot(&(opt_velocity - line.point));
+        *result = line.point + line.direction * t.clamp(t_left, t_right);
+    }
+
+    true
+}
+
+fn linear_program2(
+    lines: &[Line],
+    radius: f32,
+    opt_velocity: Vector2<f32>,
+    direction_opt: bool,
+    result: &mut Vector2<f32>,
+) -> usize {
+    *result = if direction_opt {
+        opt_velocity * radius
+    } else if opt_velocity.norm_squared() > radius * radius {
+        opt_velocity.normalize() * radius
+    } else {
+        opt_velocity
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        if det(line.direction, line.point - *result) > 0.0 {
+            // Result does not satisfy constraint i, compute a new optimal result.
+            let temp_result = *result;
+            if !linear_program1(lines, i, radius, opt_velocity, direction_opt, result) {
+                *result = temp_result;
+                return i;
+            }
+        }
+    }
+
+    lines.len()
+}
+
+fn linear_program3(lines: &[Line], begin_line: usize, radius: f32, result: &mut Vector2<f32>) {
+    let mut distance = 0.0;
+
+    for i in begin_line..lines.len() {
+        if det(lines[i].direction, lines[i].point - *result) > distance {
+            // Result does not satisfy constraint of line i.
+            let mut projected_lines = Vec::with_capacity(i);
+            for j in 0..i {
+                let determinant = det(lines[i].direction, lines[j].direction);
+                let point = if determinant.abs() <= EPSILON {
+                    if lines[i].direction.dot(&lines[j].direction) > 0.0 {
+                        // Lines are in the same direction.
+                        continue;
+                    }
+                    (lines[i].point + lines[j].point) * 0.5
+                } else {
+                    lines[i].point
+                        + lines[i].direction
+                            * (det(lines[j].direction, lines[i].point - lines[j].point)
+                                / determinant)
+                };
+                let Some(direction) =
+                    (lines[j].direction - lines[i].direction).try_normalize(f32::EPSILON)
+                else {
+                    continue;
+                };
+                projected_lines.push(Line { point, direction });
+            }
+
+            let temp_result = *result;
+            if linear_program2(
+                &projected_lines,
+                radius,
+                Vector2::new(-lines[i].direction.y, lines[i].direction.x),
+                true,
+                result,
+            ) < projected_lines.len()
+            {
+                // This should in principle not happen. The result is by definition already in the
+                // feasible region of this linear program. If it fails, it is due to small floating
+                // point error, and the current result is kept.
+                *result = temp_result;
+            }
+
+            distance = det(lines[i].direction, lines[i].point - *result);
+        }
+    }
+}
+
+impl AvoidanceAgent {
+    /// Computes a new velocity of the agent, that is as close as possible to the preferred velocity
+    /// and will not lead to collisions with the given neighbours within the time horizon. `dt` is
+    /// the time step of the simulation, it is used to resolve already existing collisions.
+    pub fn compute_velocity(
+        &self,
+        preferred_velocity: Vector2<f32>,
+        neighbours: &[AvoidanceNeighbour],
+        dt: f32,
+    ) -> Vector2<f32> {
+        let inv_time_horizon = 1.0 / self.time_horizon.max(EPSILON);
+        let inv_time_step = 1.0 / dt.max(EPSILON);
+
+        let mut lines = Vec::with_capacity(neighbours.len());
+        for neighbour in neighbours {
+            let relative_position = neighbour.position - self.position;
+            let relative_velocity = self.velocity - neighbour.velocity;
+            let dist_sq = relative_position.norm_squared();
+            let combined_radius = self.radius + neighbour.radius;
+            let combined_radius_sq = combined_radius * combined_radius;
+
+            let (direction, u) = if dist_sq > combined_radius_sq {
+                // No collision.
+                let w = relative_velocity - relative_position * inv_time_horizon;
+                let w_length_sq = w.norm_squared();
+                let dot_product = w.dot(&relative_position);
+
+                if dot_product < 0.0 && dot_product * dot_product > combined_radius_sq * w_length_sq
+                {
+                    // Project on cut-off circle.
+                    let w_length = w_length_sq.sqrt();
+                    let unit_w = w / w_length;
+                    (
+                        Vector2::new(unit_w.y, -unit_w.x),
+                        unit_w * (combined_radius * inv_time_horizon - w_length),
+                    )
+                } else {
+                    // Project on legs.
+                    let leg = (dist_sq - combined_radius_sq).sqrt();
+                    let direction = if det(relative_position, w) > 0.0 {
+                        // Project on left leg.
+                        Vector2::new(
+                            relative_position.x * leg - relative_position.y * combined_radius,
+                            relative_position.x * combined_radius + relative_position.y * leg,
+                        ) / dist_sq
+                    } else {
+                        // Project on right leg.
+                        -Vector2::new(
+                            relative_position.x * leg + relative_position.y * combined_radius,
+                            -relative_position.x * combined_radius + relative_position.y * leg,
+                        ) / dist_sq
+                    };
+                    let dot_product = relative_velocity.dot(&direction);
+                    (direction, direction * dot_product - relative_velocity)
+                }
+            } else {
+                // Collision. Project on cut-off circle of time step.
+                let w = relative_velocity - relative_position * inv_time_step;
+                let Some(unit_w) = w.try_normalize(f32::EPSILON) else {
+                    continue;
+                };
+                (
+                    Vector2::new(unit_w.y, -unit_w.x),
+                    unit_w * (combined_radius * inv_time_step - w.norm()),
+                )
+            };
+
+            lines.push(Line {
+                point: self.velocity + u * 0.5,
+                direction,
+            });
+        }
+
+        let mut result = Vector2::default();
+        let line_fail = linear_program2(
+            &lines,
+            self.max_speed,
+            preferred_velocity,
+            false,
+            &mut result,
+        );
+        if line_fail < lines.len() {
+            linear_program3(&lines, line_fail, self.max_speed, &mut result);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        utils::navmesh::avoidance::{AvoidanceAgent, AvoidanceNeighbour},
+    };
+
+    #[test]
+    fn test_head_on_avoidance() {
+        let agent = AvoidanceAgent {
+            position: Vector2::new(0.0, 0.0),
+            velocity: Vector2::new(1.0, 0.0),
+            radius: 0.5,
+            max_speed: 1.0,
+            time_horizon: 2.0,
+        };
+        let neighbour = AvoidanceNeighbour {
+            position: Vector2::new(3.0, 0.0),
+            velocity: Vector2::new(-1.0, 0.0),
+            radius: 0.5,
+        };
+
+        // Nobody around - preferred velocity is kept.
+        let velocity = agent.compute_velocity(Vector2::new(1.0, 0.0), &[], 1.0 / 60.0);
+        assert_eq!(velocity, Vector2::new(1.0, 0.0));
+
+        // Head-on collision - the agent must turn aside and must not exceed its max speed.
+        let velocity = agent.compute_velocity(Vector2::new(1.0, 0.0), &[neighbour], 1.0 / 60.0);
+        assert!(velocity.y.abs() > 0.01);
+        assert!(velocity.norm() <= 1.0 + 0.0001);
+    }
+}
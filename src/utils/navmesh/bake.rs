@@ -0,0 +1,661 @@
+//! Navmesh baking. Converts arbitrary scene geometry (static meshes and terrains) into a navigational
+//! mesh, taking the size of agents into account. See [`NavmeshBakeSettings`] and [`bake`] docs for
+//! more info.
+//!
+//! ## How it works
+//!
+//! Baking is done in a few steps:
+//!
+//! 1. Source geometry is rasterized into a heightfield - a grid of columns, where each column contains
+//! a sorted set of solid spans. Every span is marked as walkable if it was produced by a triangle, which
+//! slope is less than the maximum slope.
+//! 2. Walkable spans without enough free space above them (less than agent's height) are filtered out.
+//! 3. Remaining walkable spans are linked with their neighbours, the link is created only if the height
+//! difference between the spans is less than the maximum step height.
+//! 4. Walkable area is eroded by agent's radius, so agents won't go through walls.
+//! 5. Connected spans are grouped into regions, regions that are smaller than the minimum region area
+//! are discarded (this removes "islands" on tables, window sills, etc.).
+//! 6. Finally, each remaining span is triangulated, corners of linked spans are welded together, so the
+//! resulting navmesh is fully connected.
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector3},
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::SceneGraph,
+    scene::{
+        base::Mobility,
+        graph::Graph,
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            Mesh,
+        },
+        node::Node,
+        terrain::Terrain,
+    },
+    utils::navmesh::Navmesh,
+};
+use fxhash::FxHashMap;
+use std::collections::VecDeque;
+
+/// A set of parameters that defines how a navigational mesh will be baked.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct NavmeshBakeSettings {
+    /// Size of a cell of the heightfield on XZ plane (in meters). Smaller values gives more precise
+    /// results, but increases baking time and amount of triangles in the navmesh.
+    #[reflect(
+        description = "Size of a cell of the heightfield on XZ plane (in meters). Smaller values gives \
+        more precise results, but increases baking time and amount of triangles in the navmesh.",
+        min_value = 0.01
+    )]
+    pub cell_size: f32,
+
+    /// Size of a cell of the heightfield along Y axis (in meters).
+    #[reflect(
+        description = "Size of a cell of the heightfield along Y axis (in meters).",
+        min_value = 0.01
+    )]
+    pub cell_height: f32,
+
+    /// Radius of agents (in meters). Walkable area will be shrunk by this value.
+    #[reflect(
+        description = "Radius of agents (in meters). Walkable area will be shrunk by this value.",
+        min_value = 0.0
+    )]
+    pub agent_radius: f32,
+
+    /// Height of agents (in meters). Areas with less free space above them will be excluded.
+    #[reflect(
+        description = "Height of agents (in meters). Areas with less free space above them will be excluded.",
+        min_value = 0.0
+    )]
+    pub agent_height: f32,
+
+    /// Maximum slope (in degrees) of a surface that is still considered walkable.
+    #[reflect(
+        description = "Maximum slope (in degrees) of a surface that is still considered walkable.",
+        min_value = 0.0,
+        max_value = 90.0
+    )]
+    pub max_slope: f32,
+
+    /// Maximum height (in meters) of a step which agents can climb.
+    #[reflect(
+        description = "Maximum height (in meters) of a step which agents can climb.",
+        min_value = 0.0
+    )]
+    pub max_step_height: f32,
+
+    /// Minimum amount of cells in a region. Smaller regions will be discarded.
+    #[reflect(
+        description = "Minimum amount of cells in a region. Smaller regions will be discarded."
+    )]
+    pub min_region_area: u32,
+}
+
+impl Default for NavmeshBakeSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 0.3,
+            cell_height: 0.2,
+            agent_radius: 0.4,
+            agent_height: 2.0,
+            max_slope: 45.0,
+            max_step_height: 0.4,
+            min_region_area: 8,
+        }
+    }
+}
+
+/// A triangle soup in world coordinates, that is used as a source for navmesh baking.
+#[derive(Clone, Debug, Default)]
+pub struct NavmeshBakeGeometry {
+    vertices: Vec<Vector3<f32>>,
+    triangles: Vec<TriangleDefinition>,
+}
+
+impl NavmeshBakeGeometry {
+    /// Adds a single triangle to the geometry.
+    pub fn add_triangle(&mut self, a: Vector3<f32>, b: Vector3<f32>, c: Vector3<f32>) {
+        let first = self.vertices.len() as u32;
+        self.vertices.extend_from_slice(&[a, b, c]);
+        self.triangles
+            .push(TriangleDefinition([first, first + 1, first + 2]));
+    }
+
+    /// Adds every surface of the given mesh to the geometry.
+    pub fn add_mesh(&mut self, mesh: &Mesh) {
+        let global_transform = mesh.global_transform();
+        for surface in mesh.surfaces() {
+            let data = surface.data();
+            let data = data.lock();
+
+            let first = self.vertices.len() as u32;
+            for vertex in data.vertex_buffer.iter() {
+                let position = vertex
+                    .read_3_f32(VertexAttributeUsage::Position)
+                    .unwrap_or_default();
+                self.vertices.push(
+                    global_transform
+                        .transform_point(&Point3::from(position))
+                        .coords,
+                );
+            }
+            for triangle in data.geometry_buffer.iter() {
+                self.triangles.push(TriangleDefinition([
+                    first + triangle[0],
+                    first + triangle[1],
+                    first + triangle[2],
+                ]));
+            }
+        }
+    }
+
+    /// Adds height maps of every chunk of the given terrain to the geometry.
+    pub fn add_terrain(&mut self, terrain: &Terrain) {
+        let global_transform = terrain.global_transform();
+        for chunk in terrain.chunks_ref() {
+            let size = chunk.height_map_size();
+            if size.x < 2 || size.y < 2 || !chunk.is_loaded() {
+                continue;
+            }
+            let heightmap = chunk.heightmap_owned();
+            let origin = chunk.local_position();
+            let physical_size = chunk.physical_size();
+
+            let first = self.vertices.len() as u32;
+            for iz in 0..size.y {
+                for ix in 0..size.x {
+                    let local = Vector3::new(
+                        origin.x + ix as f32 / (size.x - 1) as f32 * physical_size.x,
+                        heightmap[(iz * size.x + ix) as usize],
+                        origin.y + iz as f32 / (size.y - 1) as f32 * physical_size.y,
+                    );
+                    self.vertices.push(
+                        global_transform
+                            .transform_point(&Point3::from(local))
+                            .coords,
+                    );
+                }
+            }
+            for iz in 0..size.y - 1 {
+                for ix in 0..size.x - 1 {
+                    let i0 = first + iz * size.x + ix;
+                    let i1 = i0 + 1;
+                    let i2 = i0 + size.x + 1;
+                    let i3 = i0 + size.x;
+                    self.triangles.push(TriangleDefinition([i0, i2, i1]));
+                    self.triangles.push(TriangleDefinition([i0, i3, i2]));
+                }
+            }
+        }
+    }
+
+    /// Collects all enabled static (non-[dynamic](Mobility::Dynamic)) meshes and terrains of the
+    /// sub-graph starting from the given root.
+    pub fn from_graph(graph: &Graph, root: Handle<Node>) -> Self {
+        let mut geometry = Self::default();
+        for node in graph.traverse_iter(root) {
+            if node.mobility() == Mobility::Dynamic || !node.is_globally_enabled() {
+                continue;
+            }
+            if let Some(mesh) = node.cast::<Mesh>() {
+                geometry.add_mesh(mesh);
+            } else if let Some(terrain) = node.cast::<Terrain>() {
+                geometry.add_terrain(terrain);
+            }
+        }
+        geometry
+    }
+
+    /// Returns a reference to the vertices of the geometry.
+    pub fn vertices(&self) -> &[Vector3<f32>] {
+        &self.vertices
+    }
+
+    /// Returns a reference to the triangles of the geometry.
+    pub fn triangles(&self) -> &[TriangleDefinition] {
+        &self.triangles
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Span {
+    min: i32,
+    max: i32,
+    walkable: bool,
+}
+
+struct Heightfield {
+    width: usize,
+    depth: usize,
+    columns: Vec<Vec<Span>>,
+}
+
+impl Heightfield {
+    fn add_span(&mut self, x: usize, z: usize, mut span: Span, merge_threshold: i32) {
+        let spans = &mut self.columns[z * self.width + x];
+        let mut i = 0;
+        while i < spans.len() {
+            let other = spans[i];
+            if other.min > span.max {
+                break;
+            }
+            if other.max < span.min {
+                i += 1;
+                continue;
+            }
+            // Spans are overlapping, merge them. Walkable flag is taken from the top-most span.
+            if (other.max - span.max).abs() <= merge_threshold {
+                span.walkable |= other.walkable;
+            } else if other.max > span.max {
+                span.walkable = other.walkable;
+            }
+            span.min = span.min.min(other.min);
+            span.max = span.max.max(other.max);
+            spans.remove(i);
+        }
+        spans.insert(i, span);
+    }
+}
+
+struct OpenSpan {
+    x: usize,
+    z: usize,
+    floor: i32,
+    ceiling: i32,
+    neighbours: [Option<usize>; 4],
+    region: Option<usize>,
+}
+
+// -X, +Z, +X, -Z
+const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+fn clip_polygon(polygon: &[Vector3<f32>], axis: usize, value: f32, sign: f32) -> Vec<Vector3<f32>> {
+    let mut result = Vec::with_capacity(polygon.len() + 2);
+    for (i, current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let current_distance = sign * (current[axis] - value);
+        let next_distance = sign * (next[axis] - value);
+        if current_distance >= 0.0 {
+            result.push(*current);
+        }
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+            result.push(current.lerp(&next, t));
+        }
+    }
+    result
+}
+
+fn rasterize(
+    geometry: &NavmeshBakeGeometry,
+    settings: &NavmeshBakeSettings,
+    bounds: &AxisAlignedBoundingBox,
+) -> Heightfield {
+    let cell_size = settings.cell_size;
+    let cell_height = settings.cell_height;
+    let width = ((bounds.max.x - bounds.min.x) / cell_size).ceil().max(1.0) as usize;
+    let depth = ((bounds.max.z - bounds.min.z) / cell_size).ceil().max(1.0) as usize;
+    let mut heightfield = Heightfield {
+        width,
+        depth,
+        columns: (0..width * depth).map(|_| Vec::new()).collect(),
+    };
+
+    let walkable_threshold = settings.max_slope.to_radians().cos();
+    let merge_threshold = (settings.max_step_height / cell_height).floor() as i32;
+
+    for triangle in geometry.triangles.iter() {
+        let a = geometry.vertices[triangle[0] as usize];
+        let b = geometry.vertices[triangle[1] as usize];
+        let c = geometry.vertices[triangle[2] as usize];
+
+        let Some(normal) = (b - a).cross(&(c - a)).try_normalize(f32::EPSILON) else {
+            continue;
+        };
+        let walkable = normal.y.abs() >= walkable_threshold;
+
+        let min = a.inf(&b).inf(&c);
+        let max = a.sup(&b).sup(&c);
+        let x0 = (((min.x - bounds.min.x) / cell_size).floor().max(0.0) as usize).min(width - 1);
+        let x1 = (((max.x - bounds.min.x) / cell_size).floor().max(0.0) as usize).min(width - 1);
+        let z0 = (((min.z - bounds.min.z) / cell_size).floor().max(0.0) as usize).min(depth - 1);
+        let z1 = (((max.z - bounds.min.z) / cell_size).floor().max(0.0) as usize).min(depth - 1);
+
+        for z in z0..=z1 {
+            let cell_min_z = bounds.min.z + z as f32 * cell_size;
+            let row = clip_polygon(&[a, b, c], 2, cell_min_z, 1.0);
+            let row = clip_polygon(&row, 2, cell_min_z + cell_size, -1.0);
+            if row.len() < 3 {
+                continue;
+            }
+            for x in x0..=x1 {
+                let cell_min_x = bounds.min.x + x as f32 * cell_size;
+                let cell = clip_polygon(&row, 0, cell_min_x, 1.0);
+                let cell = clip_polygon(&cell, 0, cell_min_x + cell_size, -1.0);
+                if cell.len() < 3 {
+                    continue;
+                }
+                let (y_min, y_max) = cell.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+                    (lo.min(p.y), hi.max(p.y))
+                });
+                let span_min = ((y_min - bounds.min.y) / cell_height).floor() as i32;
+                let span_max =
+                    (((y_max - bounds.min.y) / cell_height).ceil() as i32).max(span_min + 1);
+                heightfield.add_span(
+                    x,
+                    z,
+                    Span {
+                        min: span_min,
+                        max: span_max,
+                        walkable,
+                    },
+                    merge_threshold,
+                );
+            }
+        }
+    }
+
+    heightfield
+}
+
+fn build_open_spans(heightfield: &Heightfield, settings: &NavmeshBakeSettings) -> Vec<OpenSpan> {
+    let height_cells = (settings.agent_height / settings.cell_height).ceil() as i32;
+    let step_cells = (settings.max_step_height / settings.cell_height).floor() as i32;
+
+    let mut spans = Vec::new();
+    let mut column_spans = vec![Vec::new(); heightfield.columns.len()];
+    for z in 0..heightfield.depth {
+        for x in 0..heightfield.width {
+            let index = z * heightfield.width + x;
+            let column = &heightfield.columns[index];
+            for (i, span) in column.iter().enumerate() {
+                let ceiling = column.get(i + 1).map_or(i32::MAX, |next| next.min);
+                if span.walkable && ceiling.saturating_sub(span.max) >= height_cells {
+                    column_spans[index].push(spans.len());
+                    spans.push(OpenSpan {
+                        x,
+                        z,
+                        floor: span.max,
+                        ceiling,
+                        neighbours: [None; 4],
+                        region: None,
+                    });
+                }
+            }
+        }
+    }
+
+    for span_index in 0..spans.len() {
+        for (direction, (dx, dz)) in DIRECTIONS.iter().enumerate() {
+            let nx = spans[span_index].x as i32 + dx;
+            let nz = spans[span_index].z as i32 + dz;
+            if nx < 0 || nz < 0 || nx >= heightfield.width as i32 || nz >= heightfield.depth as i32
+            {
+                continue;
+            }
+            let span = &spans[span_index];
+            let neighbour = column_spans[nz as usize * heightfield.width + nx as usize]
+                .iter()
+                .cloned()
+                .find(|&other| {
+                    let other = &spans[other];
+                    let gap = span.ceiling.min(other.ceiling) - span.floor.max(other.floor);
+                    (other.floor - span.floor).abs() <= step_cells && gap >= height_cells
+                });
+            spans[span_index].neighbours[direction] = neighbour;
+        }
+    }
+
+    spans
+}
+
+fn erode(spans: &[OpenSpan], settings: &NavmeshBakeSettings) -> Vec<bool> {
+    let radius_cells = (settings.agent_radius / settings.cell_size).ceil() as u32;
+    let mut distances = vec![u32::MAX; spans.len()];
+    let mut queue = VecDeque::new();
+    for (index, span) in spans.iter().enumerate() {
+        if span.neighbours.iter().any(|n| n.is_none()) {
+            distances[index] = 0;
+            queue.push_back(index);
+        }
+    }
+    while let Some(index) = queue.pop_front() {
+        let distance = distances[index] + 1;
+        for neighbour in spans[index].neighbours.iter().flatten() {
+            if distance < distances[*neighbour] {
+                distances[*neighbour] = distance;
+                queue.push_back(*neighbour);
+            }
+        }
+    }
+    distances.into_iter().map(|d| d >= radius_cells).collect()
+}
+
+fn build_regions(spans: &mut [OpenSpan], alive: &mut [bool], min_region_area: usize) {
+    let mut region_count = 0;
+    let mut stack = Vec::new();
+    let mut members = Vec::new();
+    for start in 0..spans.len() {
+        if !alive[start] || spans[start].region.is_some() {
+            continue;
+        }
+
+        members.clear();
+        spans[start].region = Some(region_count);
+        stack.push(start);
+        while let Some(index) = stack.pop() {
+            members.push(index);
+            for neighbour in spans[index].neighbours.into_iter().flatten() {
+                if alive[neighbour] && spans[neighbour].region.is_none() {
+                    spans[neighbour].region = Some(region_count);
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        if members.len() < min_region_area {
+            for &member in members.iter() {
+                alive[member] = false;
+            }
+        }
+
+        region_count += 1;
+    }
+}
+
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let a = find(parents, a);
+    let b = find(parents, b);
+    if a != b {
+        parents[a] = b;
+    }
+}
+
+fn triangulate(
+    spans: &[OpenSpan],
+    alive: &[bool],
+    settings: &NavmeshBakeSettings,
+    bounds: &AxisAlignedBoundingBox,
+) -> Navmesh {
+    // Every span has four corners: (x, z), (x + 1, z), (x + 1, z + 1), (x, z + 1). Corners of linked
+    // spans are welded together, so the resulting triangles will share their edges.
+    const CORNER_OFFSETS: [(usize, usize); 4] = [(0, 0), (1, 0), (1, 1), (0, 1)];
+    // Pairs of corners that are shared with a neighbour in the respective direction.
+    const SHARED_CORNERS: [[(usize, usize); 2]; 4] = [
+        [(0, 1), (3, 2)],
+        [(3, 0), (2, 1)],
+        [(1, 0), (2, 3)],
+        [(0, 3), (1, 2)],
+    ];
+
+    let mut parents = (0..spans.len() * 4).collect::<Vec<_>>();
+    for (index, span) in spans.iter().enumerate() {
+        if !alive[index] {
+            continue;
+        }
+        for (direction, neighbour) in span.neighbours.iter().enumerate() {
+            if let Some(neighbour) = *neighbour {
+                if alive[neighbour] {
+                    for (corner, neighbour_corner) in SHARED_CORNERS[direction] {
+                        union(
+                            &mut parents,
+                            index * 4 + corner,
+                            neighbour * 4 + neighbour_corner,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Height of a welded vertex is an average height of every corner that was welded into it.
+    let mut heights = FxHashMap::<usize, (f32, f32)>::default();
+    for (index, span) in spans.iter().enumerate() {
+        if alive[index] {
+            for corner in 0..4 {
+                let root = find(&mut parents, index * 4 + corner);
+                let entry = heights.entry(root).or_insert((0.0, 0.0));
+                entry.0 += span.floor as f32;
+                entry.1 += 1.0;
+            }
+        }
+    }
+
+    let mut vertex_map = FxHashMap::<usize, u32>::default();
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    for (index, span) in spans.iter().enumerate() {
+        if !alive[index] {
+            continue;
+        }
+        let mut corners = [0u32; 4];
+        for (corner, (ox, oz)) in CORNER_OFFSETS.iter().enumerate() {
+            let root = find(&mut parents, index * 4 + corner);
+            corners[corner] = *vertex_map.entry(root).or_insert_with(|| {
+                let (sum, count) = heights[&root];
+                vertices.push(Vector3::new(
+                    bounds.min.x + (span.x + ox) as f32 * settings.cell_size,
+                    bounds.min.y + sum / count * settings.cell_height,
+                    bounds.min.z + (span.z + oz) as f32 * settings.cell_size,
+                ));
+                vertices.len() as u32 - 1
+            });
+        }
+        triangles.push(TriangleDefinition([corners[0], corners[2], corners[1]]));
+        triangles.push(TriangleDefinition([corners[0], corners[3], corners[2]]));
+    }
+
+    Navmesh::new(triangles, vertices)
+}
+
+/// Bakes a navigational mesh from the given geometry using the given settings. This method could be
+/// slow on large scenes with small cell size, so it is better to call it from a separate thread if
+/// you need to bake navmeshes at runtime.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::pool::Handle,
+/// #     scene::{graph::Graph, node::Node},
+/// #     utils::navmesh::{
+/// #         bake::{self, NavmeshBakeGeometry, NavmeshBakeSettings},
+/// #         Navmesh,
+/// #     },
+/// # };
+/// fn bake_navmesh(graph: &Graph, level_root: Handle<Node>) -> Navmesh {
+///     let geometry = NavmeshBakeGeometry::from_graph(graph, level_root);
+///     bake::bake(&geometry, &NavmeshBakeSettings::default())
+/// }
+/// ```
+pub fn bake(geometry: &NavmeshBakeGeometry, settings: &NavmeshBakeSettings) -> Navmesh {
+    if geometry.triangles.is_empty() || settings.cell_size <= 0.0 || settings.cell_height <= 0.0 {
+        return Navmesh::default();
+    }
+
+    let bounds = AxisAlignedBoundingBox::from_points(&geometry.vertices);
+    let heightfield = rasterize(geometry, settings, &bounds);
+    let mut spans = build_open_spans(&heightfield, settings);
+    let mut alive = erode(&spans, settings);
+    build_regions(&mut spans, &mut alive, settings.min_region_area as usize);
+    triangulate(&spans, &alive, settings, &bounds)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        utils::{
+            astar::PathKind,
+            navmesh::{
+                bake::{bake, NavmeshBakeGeometry, NavmeshBakeSettings},
+                NavmeshAgent,
+            },
+        },
+    };
+
+    fn add_quad(geometry: &mut NavmeshBakeGeometry, min: Vector3<f32>, max: Vector3<f32>) {
+        let a = Vector3::new(min.x, min.y, min.z);
+        let b = Vector3::new(max.x, min.y, min.z);
+        let c = Vector3::new(max.x, min.y, max.z);
+        let d = Vector3::new(min.x, min.y, max.z);
+        geometry.add_triangle(a, c, b);
+        geometry.add_triangle(a, d, c);
+    }
+
+    #[test]
+    fn test_bake_flat_floor() {
+        let mut geometry = NavmeshBakeGeometry::default();
+        add_quad(
+            &mut geometry,
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(10.0, 0.0, 10.0),
+        );
+        // Add a small platform high above the floor, it should be discarded because of its area.
+        add_quad(
+            &mut geometry,
+            Vector3::new(5.0, 5.0, 5.0),
+            Vector3::new(5.5, 5.0, 5.5),
+        );
+
+        let settings = NavmeshBakeSettings {
+            cell_size: 0.5,
+            agent_radius: 0.5,
+            ..Default::default()
+        };
+        let navmesh = bake(&geometry, &settings);
+
+        assert!(!navmesh.triangles().is_empty());
+        for vertex in navmesh.vertices() {
+            // The floor must be shrunk by the radius of agents.
+            assert!(vertex.x >= 0.5 && vertex.x <= 9.5);
+            assert!(vertex.z >= 0.5 && vertex.z <= 9.5);
+            assert!(vertex.y < 1.0);
+        }
+
+        // The baked navmesh must be fully connected.
+        let mut agent = NavmeshAgent::new();
+        let path = agent
+            .calculate_path(
+                &navmesh,
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(9.0, 0.0, 9.0),
+            )
+            .unwrap();
+        assert_eq!(path, PathKind::Full);
+    }
+}
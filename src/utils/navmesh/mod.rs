@@ -13,6 +13,7 @@ use crate::{
         octree::{Octree, OctreeNode},
         pool::Handle,
         reflect::prelude::*,
+        uuid_provider,
         visitor::{Visit, VisitResult, Visitor},
     },
     scene::mesh::{
@@ -27,9 +28,15 @@ use crate::{
 use fxhash::{FxBuildHasher, FxHashMap};
 use std::ops::{Deref, DerefMut};
 
+pub mod avoidance;
+pub mod bake;
+
 #[derive(Clone, Debug, Default, Visit)]
 struct Vertex {
     triangle_index: usize,
+    /// Index of an off-mesh link, if the vertex is an endpoint of the link.
+    #[visit(skip)]
+    link: Option<usize>,
     data: VertexData,
 }
 
@@ -55,6 +62,85 @@ impl PositionProvider for Vertex {
 
 impl VertexDataProvider for Vertex {}
 
+/// Off-mesh link is a connection between two points of a navmesh, that cannot be traversed by
+/// walking along the surface of the navmesh. It could be used to describe jumps, ladders, teleports,
+/// etc. Path finder treats off-mesh links as additional edges of the navigation graph, paths that
+/// use them will have distinct segments (see [`PathSegmentKind::OffMeshLink`]), so gameplay code can
+/// trigger appropriate animations (jump, climb, etc.) when an agent traverses the link.
+#[derive(Copy, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct OffMeshLink {
+    /// Start point of the link in world coordinates. It is attached to the closest triangle of the
+    /// navmesh.
+    #[reflect(description = "Start point of the link in world coordinates.")]
+    pub start: Vector3<f32>,
+
+    /// End point of the link in world coordinates. It is attached to the closest triangle of the
+    /// navmesh.
+    #[reflect(description = "End point of the link in world coordinates.")]
+    pub end: Vector3<f32>,
+
+    /// Defines whether the link could be traversed from the end to the start or not.
+    #[reflect(
+        description = "Defines whether the link could be traversed from the end to the start or not."
+    )]
+    pub bidirectional: bool,
+
+    /// Traversal cost multiplier of the link. The higher the cost, the less likely path finder will
+    /// use the link instead of walking. The default value is 1.0, which means that the link costs
+    /// the same as walking the same distance.
+    #[reflect(
+        description = "Traversal cost multiplier of the link. The higher the cost, the less likely \
+        path finder will use the link.",
+        min_value = 0.0
+    )]
+    pub cost: f32,
+
+    /// Arbitrary user-defined flags of the link. They are not used by the engine and could be used
+    /// to tell what kind of the link it is (jump, ladder, etc.).
+    #[reflect(description = "Arbitrary user-defined flags of the link (jump, ladder, etc.).")]
+    pub flags: u32,
+}
+
+impl Default for OffMeshLink {
+    fn default() -> Self {
+        Self {
+            start: Default::default(),
+            end: Default::default(),
+            bidirectional: true,
+            cost: 1.0,
+            flags: 0,
+        }
+    }
+}
+
+uuid_provider!(OffMeshLink = "0af7c2fa-1834-4f6a-8b88-d936da9028f6");
+
+/// Kind of a path segment.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PathSegmentKind {
+    /// The segment lies on the surface of the navmesh and could be walked.
+    #[default]
+    Walk,
+    /// The segment is an off-mesh link.
+    OffMeshLink {
+        /// Index of the link in the array of the off-mesh links of the navmesh.
+        link: usize,
+        /// User-defined flags of the link. See [`OffMeshLink::flags`].
+        flags: u32,
+    },
+}
+
+/// A segment of a path between two consecutive points.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PathSegment {
+    /// Begin point of the segment.
+    pub begin: Vector3<f32>,
+    /// End point of the segment.
+    pub end: Vector3<f32>,
+    /// Kind of the segment.
+    pub kind: PathSegmentKind,
+}
+
 /// See module docs.
 #[derive(Clone, Debug, Default, Reflect)]
 #[reflect(hide_all)]
@@ -62,12 +148,15 @@ pub struct Navmesh {
     octree: Octree,
     triangles: Vec<TriangleDefinition>,
     vertices: Vec<Vector3<f32>>,
+    links: Vec<OffMeshLink>,
     graph: Graph<Vertex>,
 }
 
 impl PartialEq for Navmesh {
     fn eq(&self, other: &Self) -> bool {
-        self.triangles == other.triangles && self.vertices == other.vertices
+        self.triangles == other.triangles
+            && self.vertices == other.vertices
+            && self.links == other.links
     }
 }
 
@@ -92,6 +181,8 @@ impl Visit for Navmesh {
         }
 
         self.triangles.visit("Triangles", &mut region)?;
+        // Backward compatibility.
+        let _ = self.links.visit("OffMeshLinks", &mut region);
 
         drop(region);
 
@@ -112,8 +203,7 @@ impl Visit for Navmesh {
             self.octree = Octree::new(&raw_triangles, 32);
         }
 
-        let graph = make_graph(&self.triangles, &self.vertices);
-        self.graph = graph;
+        self.rebuild_graph();
 
         Ok(())
     }
@@ -159,6 +249,7 @@ fn make_graph(triangles: &[TriangleDefinition], vertices: &[Vector3<f32>]) -> Gr
         let center = (a + b + c).scale(1.0 / 3.0);
         graph.add_vertex(Vertex {
             triangle_index,
+            link: None,
             data: VertexData::new(center),
         });
     }
@@ -212,8 +303,7 @@ pub struct NavmeshModificationContext<'a> {
 
 impl<'a> Drop for NavmeshModificationContext<'a> {
     fn drop(&mut self) {
-        let graph = make_graph(&self.navmesh.triangles, &self.navmesh.vertices);
-        self.navmesh.graph = graph;
+        self.navmesh.rebuild_graph();
     }
 }
 
@@ -346,10 +436,63 @@ impl Navmesh {
             graph: make_graph(&triangles, &vertices),
             triangles,
             vertices,
+            links: Default::default(),
             octree: Octree::new(&raw_triangles, 32),
         }
     }
 
+    fn rebuild_graph(&mut self) {
+        let mut graph = make_graph(&self.triangles, &self.vertices);
+
+        // Every link adds two vertices to the graph, that are connected with the triangles, the
+        // endpoints of the link are attached to.
+        for (link_index, link) in self.links.iter().enumerate() {
+            let (Some((_, start_triangle)), Some((_, end_triangle))) =
+                (self.query_closest(link.start), self.query_closest(link.end))
+            else {
+                continue;
+            };
+
+            let mut add_endpoint = |position: Vector3<f32>, triangle_index: usize| {
+                let mut data = VertexData::new(position);
+                data.g_penalty = link.cost.max(0.0);
+                let index = graph.add_vertex(Vertex {
+                    triangle_index,
+                    link: Some(link_index),
+                    data,
+                }) as usize;
+                graph.link_bidirect(triangle_index, index);
+                index
+            };
+
+            let start = add_endpoint(link.start, start_triangle);
+            let end = add_endpoint(link.end, end_triangle);
+            if link.bidirectional {
+                graph.link_bidirect(start, end);
+            } else {
+                graph.link_unidirect(start, end);
+            }
+        }
+
+        self.graph = graph;
+    }
+
+    /// Sets a new set of off-mesh links of the navmesh. See [`OffMeshLink`] docs for more info.
+    ///
+    /// # Notes
+    ///
+    /// [`crate::scene::navmesh::NavigationalMesh`] node overwrites the links of its navmesh with
+    /// its own links, use [`crate::scene::navmesh::NavigationalMesh::set_off_mesh_links`] instead.
+    pub fn set_off_mesh_links(&mut self, links: Vec<OffMeshLink>) {
+        self.links = links;
+        self.rebuild_graph();
+    }
+
+    /// Returns a reference to the array of off-mesh links of the navmesh.
+    pub fn off_mesh_links(&self) -> &[OffMeshLink] {
+        &self.links
+    }
+
     /// Creates new navigation mesh (navmesh) from given mesh. It is most simple way to create complex
     /// navigation mesh, it should be used in pair with model loading functionality - you can
     /// load model from file and turn it into navigation mesh, or even build navigation mesh
@@ -636,6 +779,8 @@ impl Navmesh {
 #[visit(optional)]
 pub struct NavmeshAgent {
     path: Vec<Vector3<f32>>,
+    #[visit(skip)]
+    segment_kinds: Vec<PathSegmentKind>,
     current: u32,
     position: Vector3<f32>,
     last_warp_position: Vector3<f32>,
@@ -659,6 +804,7 @@ impl NavmeshAgent {
     pub fn new() -> Self {
         Self {
             path: vec![],
+            segment_kinds: vec![],
             current: 0,
             position: Default::default(),
             last_warp_position: Default::default(),
@@ -682,6 +828,24 @@ impl NavmeshAgent {
         &self.path
     }
 
+    /// Returns an iterator over the segments of agent's path. Unlike [`Self::path`], it allows you
+    /// to distinguish the segments that should be walked from the segments that are off-mesh links.
+    pub fn path_segments(&self) -> impl Iterator<Item = PathSegment> + '_ {
+        self.path
+            .windows(2)
+            .enumerate()
+            .map(|(index, pair)| PathSegment {
+                begin: pair[0],
+                end: pair[1],
+                kind: self.segment_kinds.get(index).copied().unwrap_or_default(),
+            })
+    }
+
+    /// Returns the segment of the path, that the agent is currently moving along.
+    pub fn current_segment(&self) -> Option<PathSegment> {
+        self.path_segments().nth(self.current as usize)
+    }
+
     /// Sets new speed of agent's movement.
     pub fn set_speed(&mut self, speed: f32) {
         self.speed = speed;
@@ -729,6 +893,7 @@ impl NavmeshAgent {
         dest_point: Vector3<f32>,
     ) -> Result<PathKind, PathError> {
         self.path.clear();
+        self.segment_kinds.clear();
 
         self.current = 0;
         self.interpolator = 0.0;
@@ -739,25 +904,69 @@ impl NavmeshAgent {
                 if src_triangle == dest_triangle {
                     self.path.push(src_point_on_navmesh);
                     self.path.push(dest_point_on_navmesh);
+                    self.segment_kinds
+                        .resize(self.path.len(), PathSegmentKind::Walk);
 
                     return Ok(PathKind::Full);
                 }
 
-                let mut path_triangle_indices = Vec::new();
+                let mut path_vertex_indices = Vec::new();
                 let path_kind = navmesh.graph.build_indexed_path(
                     src_triangle,
                     dest_triangle,
-                    &mut path_triangle_indices,
+                    &mut path_vertex_indices,
                 )?;
 
-                path_triangle_indices.reverse();
+                path_vertex_indices.reverse();
+
+                // Split the path into parts, that are connected by off-mesh links. Every part is
+                // straightened separately.
+                let mut begin = src_point_on_navmesh;
+                let mut path_triangle_indices = Vec::new();
+                let mut i = 0;
+                while i < path_vertex_indices.len() {
+                    let vertex = &navmesh.graph.vertices[path_vertex_indices[i]];
+                    match vertex.link {
+                        None => path_triangle_indices.push(vertex.triangle_index),
+                        Some(link) => {
+                            // Link vertices always come in pairs - an entry and an exit.
+                            if let Some(exit) = path_vertex_indices
+                                .get(i + 1)
+                                .map(|index| &navmesh.graph.vertices[*index])
+                                .filter(|exit| exit.link == Some(link))
+                            {
+                                self.straighten_path(
+                                    navmesh,
+                                    begin,
+                                    vertex.position,
+                                    &path_triangle_indices,
+                                );
+                                self.segment_kinds
+                                    .resize(self.path.len(), PathSegmentKind::Walk);
+                                if let Some(kind) = self.segment_kinds.last_mut() {
+                                    *kind = PathSegmentKind::OffMeshLink {
+                                        link,
+                                        flags: navmesh.links[link].flags,
+                                    };
+                                }
+
+                                begin = exit.position;
+                                path_triangle_indices.clear();
+                                i += 1;
+                            }
+                        }
+                    }
+                    i += 1;
+                }
 
                 self.straighten_path(
                     navmesh,
-                    src_point_on_navmesh,
+                    begin,
                     dest_point_on_navmesh,
                     &path_triangle_indices,
                 );
+                self.segment_kinds
+                    .resize(self.path.len(), PathSegmentKind::Walk);
 
                 return Ok(path_kind);
             }
@@ -973,7 +1182,10 @@ impl NavmeshAgentBuilder {
 mod test {
     use crate::{
         core::{algebra::Vector3, math::TriangleDefinition},
-        utils::navmesh::{Navmesh, NavmeshAgent},
+        utils::{
+            astar::PathKind,
+            navmesh::{Navmesh, NavmeshAgent, OffMeshLink, PathSegmentKind},
+        },
     };
 
     #[test]
@@ -1032,4 +1244,79 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_off_mesh_link() {
+        // Two separate quads, the second one is higher than the first one.
+        let mut navmesh = Navmesh::new(
+            vec![
+                TriangleDefinition([0, 1, 2]),
+                TriangleDefinition([0, 2, 3]),
+                TriangleDefinition([4, 5, 6]),
+                TriangleDefinition([4, 6, 7]),
+            ],
+            vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(0.0, 0.0, 1.0),
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(2.0, 2.0, 0.0),
+                Vector3::new(2.0, 2.0, 1.0),
+                Vector3::new(3.0, 2.0, 1.0),
+                Vector3::new(3.0, 2.0, 0.0),
+            ],
+        );
+
+        let mut agent = NavmeshAgent::new();
+        agent.set_radius(0.0);
+
+        // No way to the second quad without a link.
+        let path_kind = agent
+            .calculate_path(
+                &navmesh,
+                Vector3::new(0.2, 0.0, 0.5),
+                Vector3::new(2.8, 2.0, 0.5),
+            )
+            .unwrap();
+        assert_eq!(path_kind, PathKind::Partial);
+
+        navmesh.set_off_mesh_links(vec![OffMeshLink {
+            start: Vector3::new(1.0, 0.0, 0.5),
+            end: Vector3::new(2.0, 2.0, 0.5),
+            bidirectional: false,
+            cost: 1.0,
+            flags: 42,
+        }]);
+
+        let path_kind = agent
+            .calculate_path(
+                &navmesh,
+                Vector3::new(0.2, 0.0, 0.5),
+                Vector3::new(2.8, 2.0, 0.5),
+            )
+            .unwrap();
+        assert_eq!(path_kind, PathKind::Full);
+
+        let link_segments = agent
+            .path_segments()
+            .filter(|segment| segment.kind != PathSegmentKind::Walk)
+            .collect::<Vec<_>>();
+        assert_eq!(link_segments.len(), 1);
+        assert_eq!(
+            link_segments[0].kind,
+            PathSegmentKind::OffMeshLink { link: 0, flags: 42 }
+        );
+        assert_eq!(link_segments[0].begin, Vector3::new(1.0, 0.0, 0.5));
+        assert_eq!(link_segments[0].end, Vector3::new(2.0, 2.0, 0.5));
+
+        // The link is unidirectional, so there's no way back.
+        let path_kind = agent
+            .calculate_path(
+                &navmesh,
+                Vector3::new(2.8, 2.0, 0.5),
+                Vector3::new(0.2, 0.0, 0.5),
+            )
+            .unwrap();
+        assert_eq!(path_kind, PathKind::Partial);
+    }
 }
@@ -0,0 +1,182 @@
+//! Mesh simplification. Used to generate lower levels of detail for meshes.
+//!
+//! Current implementation uses vertex clustering: the bounds of a surface are split into a uniform
+//! grid, and every vertex that falls into a cell is collapsed into a single representative vertex
+//! of the cell. Triangles that become degenerate after the collapse are removed. The resolution of
+//! the grid is picked automatically to match desired amount of triangles as close as possible.
+//!
+//! Representative vertices are taken from the source vertex buffer as is, so the simplified surface
+//! keeps the vertex layout (and all the attributes, including bone weights) of the source surface.
+
+use crate::{
+    core::{
+        algebra::Vector3,
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+    },
+    scene::mesh::{
+        buffer::{TriangleBuffer, VertexAttributeUsage, VertexReadTrait},
+        surface::SurfaceData,
+    },
+};
+use fxhash::{FxHashMap, FxHashSet};
+
+/// Maximum resolution of the clustering grid along each axis.
+const MAX_GRID_RESOLUTION: u32 = 1024;
+
+fn cluster(
+    positions: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    bounds: &AxisAlignedBoundingBox,
+    resolution: u32,
+) -> Vec<TriangleDefinition> {
+    let size = bounds.max - bounds.min;
+    let cell_of = |position: &Vector3<f32>| -> [u32; 3] {
+        let mut cell = [0; 3];
+        for (axis, cell) in cell.iter_mut().enumerate() {
+            let extent = size[axis].max(f32::EPSILON);
+            let normalized = (position[axis] - bounds.min[axis]) / extent;
+            *cell = ((normalized * resolution as f32) as u32).min(resolution - 1);
+        }
+        cell
+    };
+
+    // Find the center of mass of every cell first.
+    let mut cells = FxHashMap::<[u32; 3], (Vector3<f32>, u32)>::default();
+    for position in positions {
+        let (sum, count) = cells.entry(cell_of(position)).or_default();
+        *sum += *position;
+        *count += 1;
+    }
+
+    // Then pick a vertex that is closest to the center of mass as the representative of the cell.
+    let mut representatives = FxHashMap::<[u32; 3], (u32, f32)>::default();
+    for (index, position) in positions.iter().enumerate() {
+        let cell = cell_of(position);
+        let (sum, count) = cells[&cell];
+        let distance = position.metric_distance(&sum.scale(1.0 / count as f32));
+        let representative = representatives
+            .entry(cell)
+            .or_insert((index as u32, distance));
+        if distance < representative.1 {
+            *representative = (index as u32, distance);
+        }
+    }
+
+    let remap = |index: u32| -> u32 {
+        positions
+            .get(index as usize)
+            .map_or(index, |position| representatives[&cell_of(position)].0)
+    };
+
+    let mut unique = FxHashSet::default();
+    let mut result = Vec::new();
+    for triangle in triangles {
+        let [a, b, c] = triangle.0.map(remap);
+        if a == b || b == c || a == c {
+            continue;
+        }
+        let mut key = [a, b, c];
+        key.sort_unstable();
+        if unique.insert(key) {
+            result.push(TriangleDefinition([a, b, c]));
+        }
+    }
+    result
+}
+
+/// Creates a simplified copy of the given surface data. `triangle_ratio` defines desired amount of
+/// triangles in the result relative to the amount of triangles of the source surface, it must be
+/// in `[0; 1]` range. The actual amount of triangles may slightly differ from the desired one, but
+/// it never exceeds the amount of triangles of the source surface.
+///
+/// Blend shapes are not preserved, because simplification changes the set of vertices.
+pub fn simplify(data: &SurfaceData, triangle_ratio: f32) -> SurfaceData {
+    let source_triangles = data.geometry_buffer.triangles_ref();
+    let target = (source_triangles.len() as f32 * triangle_ratio.clamp(0.0, 1.0)).ceil() as usize;
+
+    let positions = data
+        .vertex_buffer
+        .iter()
+        .map(|view| {
+            view.read_3_f32(VertexAttributeUsage::Position)
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let triangles = if target >= source_triangles.len() || positions.is_empty() {
+        source_triangles.to_vec()
+    } else {
+        let bounds = AxisAlignedBoundingBox::from_points(&positions);
+
+        // Amount of triangles grows along with the resolution of the grid (not strictly, but it
+        // is fine for our needs), so binary search can be used to find the best resolution.
+        let mut best = Vec::new();
+        let mut low = 1;
+        let mut high = MAX_GRID_RESOLUTION;
+        while low <= high {
+            let resolution = (low + high) / 2;
+            let candidate = cluster(&positions, source_triangles, &bounds, resolution);
+            if candidate.len() <= target {
+                best = candidate;
+                low = resolution + 1;
+            } else {
+                high = resolution - 1;
+            }
+        }
+        best
+    };
+
+    // Remove every vertex that is not used by any triangle.
+    let mut used = Vec::new();
+    let mut remap = FxHashMap::default();
+    let triangles = triangles
+        .into_iter()
+        .map(|triangle| {
+            TriangleDefinition(triangle.0.map(|index| {
+                *remap.entry(index).or_insert_with(|| {
+                    used.push(index);
+                    used.len() as u32 - 1
+                })
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    let mut vertex_buffer = data.vertex_buffer.clone();
+    vertex_buffer.modify().retain_vertices(&used);
+
+    SurfaceData::new(
+        vertex_buffer,
+        TriangleBuffer::new(triangles),
+        data.is_embedded(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Matrix4, scene::mesh::surface::SurfaceData, utils::simplify::simplify,
+    };
+
+    #[test]
+    fn test_simplify() {
+        let sphere = SurfaceData::make_sphere(32, 32, 1.0, &Matrix4::identity());
+        let source_triangles = sphere.geometry_buffer.len();
+
+        let half = simplify(&sphere, 0.5);
+        assert!(half.geometry_buffer.len() <= source_triangles / 2 + 1);
+        assert!(!half.geometry_buffer.is_empty());
+        assert!(half.vertex_buffer.vertex_count() < sphere.vertex_buffer.vertex_count());
+        assert_eq!(
+            half.vertex_buffer.layout_hash(),
+            sphere.vertex_buffer.layout_hash()
+        );
+        for triangle in half.geometry_buffer.iter() {
+            for &index in triangle.indices() {
+                assert!(index < half.vertex_buffer.vertex_count());
+            }
+        }
+
+        let same = simplify(&sphere, 1.0);
+        assert_eq!(same.geometry_buffer.len(), source_triangles);
+    }
+}
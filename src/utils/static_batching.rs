@@ -0,0 +1,373 @@
+//! Static batching merges static meshes that share a material into a few large meshes, which
+//! drastically reduces the amount of draw calls for level geometry.
+//!
+//! Only meshes with [`Mobility::Static`] are batched. Surfaces are grouped by their material, vertex
+//! layout, render path, decal layer, shadow casting flag, color and spatial cell. Spatial cells are
+//! cubes of [`StaticBatchingOptions::cell_size`] size, the cell of a mesh is defined by the center of
+//! its world-space bounding box. Cells keep batches reasonably small, so frustum culling still works
+//! for them. Surfaces of every group are transformed into world space and merged into a single
+//! surface of a new mesh (a static batch). All the vertex attributes are copied as is, which means
+//! that lightmap texture coordinates are kept intact. Since lightmap textures are assigned to
+//! materials, surfaces with different lightmap textures are never merged together.
+//!
+//! Only the meshes from a hierarchy of a given root node are batched (usually it is the root of the
+//! graph). Static batches are added as children of a pivot with [`STATIC_BATCHES_ROOT_NAME`] name,
+//! that is linked to the root node. Source meshes are kept in the graph (so physics, picking, etc.
+//! keeps working), but they are flagged as statically batched (see [`Mesh::is_statically_batched`])
+//! and are not rendered anymore. Changes of the source meshes are **not** reflected in batches, the
+//! batches must be rebuilt after the changes.
+//!
+//! A mesh is batched only if every surface of it could be merged with at least one other surface.
+//! Skinned meshes, meshes with blend shapes or levels of detail, as well as disabled or invisible
+//! meshes are never batched.
+
+use crate::{
+    core::{algebra::Vector3, math::TriangleDefinition, pool::Handle},
+    graph::SceneGraph,
+    scene::{
+        base::{BaseBuilder, Mobility},
+        graph::Graph,
+        mesh::{
+            buffer::{TriangleBuffer, VertexAttributeUsage},
+            surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+            Mesh, MeshBuilder, RenderPath,
+        },
+        node::{Node, NodeTrait},
+        pivot::PivotBuilder,
+    },
+};
+use fxhash::{FxHashMap, FxHashSet};
+
+/// Name of a pivot that holds every static batch of a graph.
+pub const STATIC_BATCHES_ROOT_NAME: &str = "__StaticBatches";
+
+/// A set of options for static batching.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StaticBatchingOptions {
+    /// Size of a spatial cell (in meters). Meshes from different cells are never merged together.
+    /// Smaller cells give better culling, larger cells give fewer draw calls. Default is 32 meters.
+    pub cell_size: f32,
+}
+
+impl Default for StaticBatchingOptions {
+    fn default() -> Self {
+        Self { cell_size: 32.0 }
+    }
+}
+
+/// Result of static batching.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StaticBatchingReport {
+    /// Amount of created static batches.
+    pub batch_count: usize,
+    /// Total amount of surfaces that were merged into the static batches.
+    pub merged_surface_count: usize,
+    /// Total amount of meshes that were merged into the static batches.
+    pub merged_mesh_count: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct BatchKey {
+    material: usize,
+    layout: u64,
+    render_path: RenderPath,
+    decal_layer_index: u8,
+    cast_shadows: bool,
+    color: [u8; 4],
+    cell: Vector3<i32>,
+}
+
+fn is_batchable(mesh: &Mesh) -> bool {
+    mesh.mobility() == Mobility::Static
+        && !mesh.is_statically_batched()
+        && mesh.global_visibility()
+        && mesh.is_globally_enabled()
+        && mesh.blend_shapes().is_empty()
+        && mesh.lods().is_empty()
+        && !mesh.surfaces().is_empty()
+        && mesh.surfaces().iter().all(|surface| {
+            let data = surface.data_ref().lock();
+            surface.bones().is_empty()
+                && data.blend_shapes_container.is_none()
+                && [
+                    VertexAttributeUsage::Position,
+                    VertexAttributeUsage::Normal,
+                    VertexAttributeUsage::Tangent,
+                ]
+                .into_iter()
+                .all(|usage| data.vertex_buffer.has_attribute(usage))
+        })
+}
+
+fn batch_key(mesh: &Mesh, surface_index: usize, cell_size: f32) -> BatchKey {
+    let surface = &mesh.surfaces()[surface_index];
+    let center = NodeTrait::local_bounding_box(mesh)
+        .transform(&mesh.global_transform())
+        .center();
+    let color = mesh.color();
+    BatchKey {
+        material: surface.material().key(),
+        layout: surface.data_ref().lock().vertex_buffer.layout_hash(),
+        render_path: mesh.render_path(),
+        decal_layer_index: mesh.decal_layer_index(),
+        cast_shadows: mesh.cast_shadows(),
+        color: [color.r, color.g, color.b, color.a],
+        cell: center.map(|c| (c / cell_size.max(f32::EPSILON)).floor() as i32),
+    }
+}
+
+fn merge(graph: &Graph, entries: &[(Handle<Node>, usize)]) -> SurfaceData {
+    let mut result: Option<SurfaceData> = None;
+    for &(handle, surface_index) in entries {
+        let mesh = graph[handle].as_mesh();
+        let data = mesh.surfaces()[surface_index].data_ref().lock();
+        let mut transformed = SurfaceData::new(
+            data.vertex_buffer.clone(),
+            data.geometry_buffer.clone(),
+            true,
+        );
+        // Can't fail, the attributes are checked in `is_batchable`.
+        transformed
+            .transform_geometry(&mesh.global_transform())
+            .unwrap();
+
+        if let Some(result) = result.as_mut() {
+            let offset = result.vertex_buffer.vertex_count();
+            // Can't fail, surfaces with different layouts are never in the same group.
+            result
+                .vertex_buffer
+                .modify()
+                .append(&transformed.vertex_buffer)
+                .unwrap();
+            result.geometry_buffer.modify().push_triangles_iter(
+                transformed
+                    .geometry_buffer
+                    .iter()
+                    .map(|triangle| TriangleDefinition(triangle.0.map(|i| i + offset))),
+            );
+        } else {
+            result = Some(transformed);
+        }
+    }
+    result.unwrap_or_else(|| SurfaceData::new(Default::default(), TriangleBuffer::default(), true))
+}
+
+/// Removes every static batch from the hierarchy of the given root node and makes the source meshes
+/// renderable again.
+pub fn clear_static_batches(graph: &mut Graph, root: Handle<Node>) {
+    while let Some((handle, _)) = graph.find(root, &mut |node| {
+        node.parent() == root && node.name() == STATIC_BATCHES_ROOT_NAME
+    }) {
+        graph.remove_node(handle);
+    }
+
+    for handle in graph.traverse_handle_iter(root).collect::<Vec<_>>() {
+        if let Some(mesh) = graph[handle].cast_mut::<Mesh>() {
+            mesh.set_statically_batched(false);
+        }
+    }
+}
+
+/// Builds static batches for the hierarchy of the given root node, previously built static batches
+/// of the hierarchy are removed. See [module docs](self) for more info.
+pub fn build_static_batches(
+    graph: &mut Graph,
+    root: Handle<Node>,
+    options: &StaticBatchingOptions,
+) -> StaticBatchingReport {
+    clear_static_batches(graph, root);
+
+    // Make sure that global transforms are up-to-date.
+    graph.update_hierarchical_data();
+
+    let mut candidates = graph
+        .traverse_handle_iter(root)
+        .filter_map(|handle| {
+            graph[handle]
+                .cast::<Mesh>()
+                .filter(|mesh| is_batchable(mesh))
+                .map(|mesh| {
+                    let keys = (0..mesh.surfaces().len())
+                        .map(|i| batch_key(mesh, i, options.cell_size))
+                        .collect::<Vec<_>>();
+                    (handle, keys)
+                })
+        })
+        .collect::<FxHashMap<_, _>>();
+
+    // Exclude meshes, that have at least one surface that can't be merged with anything else,
+    // until there's no such meshes left.
+    let groups = loop {
+        let mut groups = FxHashMap::<BatchKey, Vec<(Handle<Node>, usize)>>::default();
+        let mut handles = candidates.keys().cloned().collect::<Vec<_>>();
+        // Keep the order of merged surfaces stable.
+        handles.sort_by_key(|handle| handle.index());
+        for handle in handles {
+            for (surface_index, key) in candidates[&handle].iter().enumerate() {
+                groups
+                    .entry(key.clone())
+                    .or_default()
+                    .push((handle, surface_index));
+            }
+        }
+
+        let excluded = groups
+            .values()
+            .filter(|entries| entries.len() < 2)
+            .flat_map(|entries| entries.iter().map(|(handle, _)| *handle))
+            .collect::<FxHashSet<_>>();
+        if excluded.is_empty() {
+            break groups;
+        }
+        candidates.retain(|handle, _| !excluded.contains(handle));
+    };
+
+    let mut report = StaticBatchingReport {
+        merged_mesh_count: candidates.len(),
+        ..Default::default()
+    };
+
+    if groups.is_empty() {
+        return report;
+    }
+
+    let batches_root =
+        PivotBuilder::new(BaseBuilder::new().with_name(STATIC_BATCHES_ROOT_NAME)).build(graph);
+    graph.link_nodes(batches_root, root);
+
+    let mut groups = groups.into_iter().collect::<Vec<_>>();
+    groups.sort_by_key(|(_, entries)| entries[0].0.index());
+    for (key, entries) in groups {
+        let data = merge(graph, &entries);
+        let material = graph[entries[0].0].as_mesh().surfaces()[entries[0].1]
+            .material()
+            .clone();
+
+        let batch = MeshBuilder::new(
+            BaseBuilder::new()
+                .with_name(format!("StaticBatch{}", report.batch_count))
+                .with_mobility(Mobility::Static)
+                .with_cast_shadows(key.cast_shadows),
+        )
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(data))
+            .with_material(material)
+            .build()])
+        .with_render_path(key.render_path)
+        .with_decal_layer_index(key.decal_layer_index)
+        .with_color(graph[entries[0].0].as_mesh().color())
+        .build(graph);
+        graph.link_nodes(batch, batches_root);
+
+        report.batch_count += 1;
+        report.merged_surface_count += entries.len();
+    }
+
+    for handle in candidates.keys() {
+        graph[*handle].as_mesh_mut().set_statically_batched(true);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::untyped::ResourceKind,
+        core::algebra::{Matrix4, Vector3},
+        graph::SceneGraph,
+        material::{Material, MaterialResource},
+        scene::{
+            base::{BaseBuilder, Mobility},
+            graph::Graph,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            transform::TransformBuilder,
+        },
+        utils::static_batching::{
+            build_static_batches, clear_static_batches, StaticBatchingOptions,
+            STATIC_BATCHES_ROOT_NAME,
+        },
+    };
+
+    #[test]
+    fn test_static_batching() {
+        let mut graph = Graph::new();
+        let graph_root = graph.get_root();
+
+        let data = SurfaceSharedData::new(SurfaceData::make_cube(Matrix4::identity()));
+        let material = MaterialResource::new_ok(ResourceKind::Embedded, Material::standard());
+        let mut meshes = Vec::new();
+        for (x, mobility) in [
+            (0.0, Mobility::Static),
+            (2.0, Mobility::Static),
+            (4.0, Mobility::Static),
+            (6.0, Mobility::Dynamic),
+            (100.0, Mobility::Static),
+        ] {
+            meshes.push(
+                MeshBuilder::new(
+                    BaseBuilder::new()
+                        .with_mobility(mobility)
+                        .with_local_transform(
+                            TransformBuilder::new()
+                                .with_local_position(Vector3::new(x, 0.0, 0.0))
+                                .build(),
+                        ),
+                )
+                .with_surfaces(vec![SurfaceBuilder::new(data.clone())
+                    .with_material(material.clone())
+                    .build()])
+                .build(&mut graph),
+            );
+        }
+
+        let report =
+            build_static_batches(&mut graph, graph_root, &StaticBatchingOptions::default());
+        assert_eq!(report.batch_count, 1);
+        assert_eq!(report.merged_surface_count, 3);
+        assert_eq!(report.merged_mesh_count, 3);
+
+        for (i, &mesh) in meshes.iter().enumerate() {
+            assert_eq!(graph[mesh].as_mesh().is_statically_batched(), i < 3);
+        }
+
+        let (root, _) = graph
+            .find_by_name_from_root(STATIC_BATCHES_ROOT_NAME)
+            .unwrap();
+        let batch = graph[root].children()[0];
+        let batch_surfaces = graph[batch].as_mesh().surfaces();
+        assert_eq!(batch_surfaces.len(), 1);
+        let batch_data = batch_surfaces[0].data_ref().lock();
+        let source_data = data.lock();
+        assert_eq!(
+            batch_data.vertex_buffer.vertex_count(),
+            source_data.vertex_buffer.vertex_count() * 3
+        );
+        assert_eq!(
+            batch_data.geometry_buffer.len(),
+            source_data.geometry_buffer.len() * 3
+        );
+        drop(batch_data);
+        drop(source_data);
+
+        // Rebuilding must not duplicate the batches.
+        let report =
+            build_static_batches(&mut graph, graph_root, &StaticBatchingOptions::default());
+        assert_eq!(report.batch_count, 1);
+        assert!(!graph.is_valid_handle(root));
+        let (root, _) = graph
+            .find_by_name_from_root(STATIC_BATCHES_ROOT_NAME)
+            .unwrap();
+        assert_eq!(graph[root].children().len(), 1);
+
+        clear_static_batches(&mut graph, graph_root);
+        assert!(graph
+            .find_by_name_from_root(STATIC_BATCHES_ROOT_NAME)
+            .is_none());
+        for &mesh in meshes.iter() {
+            assert!(!graph[mesh].as_mesh().is_statically_batched());
+        }
+    }
+}
@@ -253,7 +253,11 @@ edition = "2021"
 
 [dependencies]
 fyrox = {{workspace = true}}
-{name} = {{ path = "../game" }}"#,
+{name} = {{ path = "../game" }}
+
+[features]
+# Loads the game from `game-dylib` library and reloads it every time it changes.
+dylib = []"#,
         ),
     );
 
@@ -263,11 +267,26 @@ fyrox = {{workspace = true}}
         format!(
             r#"//! Executor with your game connected to it as a plugin.
 use fyrox::engine::executor::Executor;
-use {}::GameConstructor;
 
 fn main() {{
     let mut executor = Executor::new();
-    executor.add_plugin_constructor(GameConstructor);
+
+    #[cfg(feature = "dylib")]
+    {{
+        let profile = if cfg!(debug_assertions) {{ "debug" }} else {{ "release" }};
+        let path = format!(
+            "target/{{profile}}/{{}}game_dylib.{{}}",
+            std::env::consts::DLL_PREFIX,
+            std::env::consts::DLL_EXTENSION
+        );
+        executor
+            .add_dynamic_plugin(path, true)
+            .expect("Unable to load the game library!");
+    }}
+
+    #[cfg(not(feature = "dylib"))]
+    executor.add_plugin_constructor({}::GameConstructor);
+
     executor.run()
 }}"#,
             name
@@ -275,6 +294,48 @@ fn main() {{
     );
 }
 
+fn init_game_dylib(base_path: &Path, name: &str) {
+    Command::new("cargo")
+        .args(["init", "--lib", "--vcs", "none"])
+        .arg(base_path.join("game-dylib"))
+        .output()
+        .unwrap();
+
+    // Write Cargo.toml
+    write_file(
+        base_path.join("game-dylib/Cargo.toml"),
+        format!(
+            r#"
+[package]
+name = "game_dylib"
+version = "0.1.0"
+edition = "2021"
+
+[lib]
+crate-type = ["dylib"]
+
+[dependencies]
+fyrox = {{workspace = true}}
+{name} = {{ path = "../game" }}"#,
+        ),
+    );
+
+    // Write lib.rs
+    write_file(
+        base_path.join("game-dylib/src/lib.rs"),
+        format!(
+            r#"//! Wrapper for hot-reloadable plugin.
+use fyrox::plugin::PluginConstructor;
+
+#[no_mangle]
+pub fn fyrox_plugin_constructor() -> Box<dyn PluginConstructor> {{
+    Box::new({}::GameConstructor)
+}}"#,
+            name
+        ),
+    );
+}
+
 fn init_wasm_executor(base_path: &Path, name: &str) {
     Command::new("cargo")
         .args(["init", "--lib", "--vcs", "none"])
@@ -510,7 +571,7 @@ fn init_workspace(base_path: &Path, vcs: &str) {
         format!(
             r#"
 [workspace]
-members = ["editor", "executor", "executor-wasm", "executor-android", "game"]
+members = ["editor", "executor", "executor-wasm", "executor-android", "game", "game-dylib"]
 resolver = "2"
 
 [workspace.dependencies.fyrox]
@@ -660,6 +721,7 @@ fn main() {
             init_game(base_path, name);
             init_editor(base_path, name);
             init_executor(base_path, name);
+            init_game_dylib(base_path, name);
             init_wasm_executor(base_path, name);
             init_android_executor(base_path, name);
 
@@ -670,6 +732,9 @@ fn main() {
             );
             println!("\tRun the Editor: cargo run --package editor --release");
             println!("\tRun the Executor: cargo run --package executor --release");
+            println!(
+                "\tRun the Executor with hot reloading: cargo build --package game_dylib && cargo run --package executor --features dylib"
+            );
             println!(
                 "\tFor WebAssembly builds - see instructions at README.md in executor-wasm folder"
             );